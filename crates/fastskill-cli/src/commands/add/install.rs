@@ -1,8 +1,12 @@
 //! Unified skill installation function replacing install_local_skill + install_copied_skill.
 
 use crate::error::{CliError, CliResult};
-use crate::utils::install_utils;
+use crate::utils::{install_utils, manifest_utils, messages, validate_skill_structure};
+use fastskill_core::core::origin::Origin;
+use fastskill_core::output::table::{Align, Table};
+use fastskill_core::validation::{SkillValidator, ValidationRules};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use tracing::info;
 use walkdir::WalkDir;
 
@@ -20,7 +24,8 @@ pub async fn install_skill(src: &Path, dst: &Path, editable: bool) -> CliResult<
             tokio::fs::remove_dir_all(dst).await.map_err(CliError::Io)?;
         }
     }
-    crate::utils::install_utils::setup_skill_in_storage(src, dst, editable).await
+    crate::utils::install_utils::setup_skill_in_storage(src, dst, editable, Default::default())
+        .await
 }
 
 fn component_is_hidden(c: std::path::Component<'_>) -> bool {
@@ -29,7 +34,7 @@ fn component_is_hidden(c: std::path::Component<'_>) -> bool {
 
 /// Discover all directories containing SKILL.md under the given base directory.
 /// Skips hidden directories (those starting with '.').
-pub(super) fn get_skill_dirs_recursive(base: &Path) -> CliResult<Vec<PathBuf>> {
+pub(crate) fn get_skill_dirs_recursive(base: &Path) -> CliResult<Vec<PathBuf>> {
     if !base.exists() {
         return Err(CliError::InvalidSource(format!(
             "Directory does not exist: {}",
@@ -81,11 +86,45 @@ pub(super) fn get_skill_dirs_recursive(base: &Path) -> CliResult<Vec<PathBuf>> {
 /// Shared post-copy logic: update skill_def fields, register, persist metadata and manifest.
 pub(super) async fn finish_skill_install(
     ctx: &super::AddContext<'_>,
-    mut skill_def: fastskill_core::SkillDefinition,
+    skill_def: fastskill_core::SkillDefinition,
     storage_dir: &Path,
     meta: super::SourceMeta,
     version_display: &str,
 ) -> CliResult<()> {
+    let skill_def = register_installed_skill(ctx, skill_def, storage_dir, meta).await?;
+
+    if ctx.global {
+        super::update_global_files(&skill_def)?;
+        println!(
+            "Successfully added skill: {} (v{})",
+            skill_def.name, version_display
+        );
+        crate::utils::messages::status!(
+            "{}",
+            crate::utils::messages::ok("Updated global-skills.lock")
+        );
+    } else {
+        super::update_project_files(&skill_def, ctx.groups.clone())?;
+        println!(
+            "Successfully added skill: {} (v{})",
+            skill_def.name, version_display
+        );
+        crate::utils::messages::status!(
+            "{}",
+            crate::utils::messages::ok("Updated skill-project.toml and skills.lock")
+        );
+    }
+    Ok(())
+}
+
+/// Register a skill already placed in `storage_dir` and record its origin.
+/// Project and lock files are left to the caller.
+async fn register_installed_skill(
+    ctx: &super::AddContext<'_>,
+    mut skill_def: fastskill_core::SkillDefinition,
+    storage_dir: &Path,
+    meta: super::SourceMeta,
+) -> CliResult<fastskill_core::SkillDefinition> {
     use chrono::Utc;
     skill_def.skill_file = storage_dir.join("SKILL.md");
     skill_def.origin = meta.origin;
@@ -109,29 +148,7 @@ pub(super) async fn finish_skill_install(
                 ctx.global,
             )
         })?;
-
-    if ctx.global {
-        super::update_global_files(&skill_def)?;
-        println!(
-            "Successfully added skill: {} (v{})",
-            skill_def.name, version_display
-        );
-        println!(
-            "{}",
-            crate::utils::messages::ok("Updated global-skills.lock")
-        );
-    } else {
-        super::update_project_files(&skill_def, ctx.groups.clone())?;
-        println!(
-            "Successfully added skill: {} (v{})",
-            skill_def.name, version_display
-        );
-        println!(
-            "{}",
-            crate::utils::messages::ok("Updated skill-project.toml and skills.lock")
-        );
-    }
-    Ok(())
+    Ok(skill_def)
 }
 
 /// Copy skill to storage, then delegate to `finish_skill_install`.
@@ -170,26 +187,7 @@ pub(super) async fn install_via_local_path(
     skill_def: fastskill_core::SkillDefinition,
     target: super::InstallTarget,
 ) -> CliResult<()> {
-    // Check existence (including broken symlinks) before proceeding
-    let path_exists = target.storage_dir.exists() || target.storage_dir.is_symlink();
-    if path_exists {
-        if !ctx.force {
-            return Err(CliError::Config(format!(
-                "Skill directory '{}' already exists. Use --force to overwrite.",
-                target.storage_dir.display()
-            )));
-        }
-        if target.storage_dir.is_symlink() || target.storage_dir.is_file() {
-            tokio::fs::remove_file(&target.storage_dir)
-                .await
-                .map_err(CliError::Io)?;
-        } else {
-            tokio::fs::remove_dir_all(&target.storage_dir)
-                .await
-                .map_err(CliError::Io)?;
-        }
-    }
-    install_utils::setup_skill_in_storage(skill_path, &target.storage_dir, ctx.editable).await?;
+    place_local_skill(ctx, skill_path, &target.storage_dir).await?;
     finish_skill_install(
         ctx,
         skill_def,
@@ -238,7 +236,122 @@ pub async fn copy_dir_recursive(src: &Path, dst: &Path) -> CliResult<()> {
     Ok(())
 }
 
+/// Symlink (editable) or copy a local skill to `storage_dir`, replacing an
+/// existing one only under `--force`.
+async fn place_local_skill(
+    ctx: &super::AddContext<'_>,
+    skill_path: &Path,
+    storage_dir: &Path,
+) -> CliResult<()> {
+    // Check existence (including broken symlinks) before proceeding
+    let path_exists = storage_dir.exists() || storage_dir.is_symlink();
+    if path_exists {
+        if !ctx.force {
+            return Err(CliError::Config(format!(
+                "Skill directory '{}' already exists. Use --force to overwrite.",
+                storage_dir.display()
+            )));
+        }
+        if storage_dir.is_symlink() || storage_dir.is_file() {
+            tokio::fs::remove_file(storage_dir)
+                .await
+                .map_err(CliError::Io)?;
+        } else {
+            tokio::fs::remove_dir_all(storage_dir)
+                .await
+                .map_err(CliError::Io)?;
+        }
+    }
+    install_utils::setup_skill_in_storage(skill_path, storage_dir, ctx.editable, ctx.link_mode)
+        .await
+}
+
+/// One row of the `add --recursive` summary
+struct ImportRow {
+    label: String,
+    version: Option<String>,
+    elapsed: Duration,
+    error: Option<String>,
+}
+
+/// Validate one skill directory, place it in storage and register it.
+/// Project and lock files are written afterwards for the whole batch.
+async fn import_skill(
+    ctx: &super::AddContext<'_>,
+    validator: &SkillValidator,
+    skill_path: &Path,
+) -> CliResult<fastskill_core::SkillDefinition> {
+    validate_skill_structure(skill_path)?;
+    let report = validator.validate_skill_directory(skill_path).await?;
+    if !report.is_valid {
+        return Err(CliError::SkillValidationFailed(
+            report
+                .errors
+                .iter()
+                .map(|e| e.message.clone())
+                .collect::<Vec<_>>()
+                .join("; "),
+        ));
+    }
+
+    let canonical_path = skill_path.canonicalize().map_err(|e| {
+        CliError::InvalidSource(format!(
+            "Failed to resolve absolute path for '{}': {}",
+            skill_path.display(),
+            e
+        ))
+    })?;
+    let origin = Origin::Local {
+        path: canonical_path.clone(),
+        editable: ctx.editable,
+    };
+    let mut skill_def = super::skill_def::create_skill_from_path(
+        skill_path,
+        origin.clone(),
+        "local",
+        ctx.editable,
+    )?;
+    super::apply_naming(ctx, &mut skill_def, skill_path).await?;
+    let storage_dir = ctx
+        .service
+        .config()
+        .skill_storage_path
+        .join(skill_def.id.as_str());
+    place_local_skill(ctx, &canonical_path, &storage_dir).await?;
+    register_installed_skill(ctx, skill_def, &storage_dir, super::SourceMeta { origin }).await
+}
+
+fn print_import_summary(rows: &[ImportRow], total_elapsed: Duration) {
+    let mut table = Table::new(["SKILL", "VERSION", "STATUS", "TIME"])
+        .max_width(2, 60)
+        .align(3, Align::Right);
+    for row in rows {
+        table.push_row([
+            row.label.clone(),
+            row.version.clone().unwrap_or_else(|| "-".to_string()),
+            match &row.error {
+                Some(e) => format!("failed: {}", e),
+                None => "added".to_string(),
+            },
+            format!("{} ms", row.elapsed.as_millis()),
+        ]);
+    }
+    print!("{}", table.render());
+    let added = rows.iter().filter(|r| r.error.is_none()).count();
+    println!(
+        "Added {} of {} skills in {:.2}s",
+        added,
+        rows.len(),
+        total_elapsed.as_secs_f64()
+    );
+}
+
 /// Add all skills under a directory (--recursive flag)
+///
+/// Every skill is validated (structure plus `SkillValidator`) and registered
+/// on its own, with one progress line each; skill-project.toml and
+/// skills.lock are then updated once for all skills that were added.
+/// Ends with a summary table of per-skill results and timings.
 pub(super) async fn handle_recursive_add(
     ctx: &super::AddContext<'_>,
     path: &Path,
@@ -260,28 +373,80 @@ pub(super) async fn handle_recursive_add(
         path.display()
     );
 
-    let mut failed: Vec<(PathBuf, CliError)> = Vec::new();
-    let mut success_count = 0;
-    for skill_path in skill_dirs {
-        match super::sources::add_from_folder(ctx, &skill_path).await {
-            Ok(()) => success_count += 1,
-            Err(e) => {
-                eprintln!("Skill at {}: {}", skill_path.display(), e);
-                failed.push((skill_path, e));
+    let started = Instant::now();
+    let rules_path = ValidationRules::default_path(&ctx.service.config().skill_storage_path);
+    let validator = SkillValidator::new().with_rules(ValidationRules::load(&rules_path)?);
+    let total = skill_dirs.len();
+    let mut rows = Vec::with_capacity(total);
+    let mut added = Vec::new();
+    for (i, skill_path) in skill_dirs.iter().enumerate() {
+        let label = skill_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| skill_path.display().to_string());
+        messages::status!("[{}/{}] {}", i + 1, total, label);
+
+        let skill_started = Instant::now();
+        let result = import_skill(ctx, &validator, skill_path).await;
+        let elapsed = skill_started.elapsed();
+        match result {
+            Ok(skill_def) => {
+                rows.push(ImportRow {
+                    label: skill_def.id.to_string(),
+                    version: Some(skill_def.version.clone()),
+                    elapsed,
+                    error: None,
+                });
+                added.push(skill_def);
+            }
+            Err(e) => rows.push(ImportRow {
+                label,
+                version: None,
+                elapsed,
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+
+    if !added.is_empty() {
+        if ctx.global {
+            for skill_def in &added {
+                super::update_global_files(skill_def)?;
             }
+        } else {
+            manifest_utils::add_skills_to_project(&added, ctx.groups.clone(), Vec::new()).map_err(
+                |e| {
+                    CliError::Config(format!(
+                        "Failed to update skill-project.toml and skills.lock: {}",
+                        e
+                    ))
+                },
+            )?;
         }
     }
-    if failed.is_empty() {
+
+    print_import_summary(&rows, started.elapsed());
+    if !added.is_empty() {
+        messages::status!(
+            "{}",
+            messages::ok(if ctx.global {
+                "Updated global-skills.lock"
+            } else {
+                "Updated skill-project.toml and skills.lock"
+            })
+        );
+    }
+
+    let failed = rows.iter().filter(|r| r.error.is_some()).count();
+    if failed == 0 {
         return Ok(());
     }
-    let total = success_count + failed.len();
     Err(CliError::Validation(format!(
         "{} of {} skills failed:\n{}",
-        failed.len(),
+        failed,
         total,
-        failed
-            .iter()
-            .map(|(path, err)| format!("  - {}: {}", path.display(), err))
+        rows.iter()
+            .filter_map(|r| r.error.as_ref().map(|e| format!("  - {}: {}", r.label, e)))
             .collect::<Vec<_>>()
             .join("\n")
     )))
@@ -478,6 +643,11 @@ skills_directory = ".claude/skills"
             recursive: false,
             reindex: false,
             no_reindex: false,
+            id_strategy: None,
+            on_collision: None,
+            link_mode: None,
+            dry_run: false,
+            json: false,
         };
 
         let result = super::super::execute_add(&service, args, false).await;
@@ -543,6 +713,11 @@ skills_directory = ".claude/skills"
             recursive: false,
             reindex: false,
             no_reindex: false,
+            id_strategy: None,
+            on_collision: None,
+            link_mode: None,
+            dry_run: false,
+            json: false,
         };
 
         let result = super::super::execute_add(&service, args, false).await;
@@ -563,4 +738,91 @@ skills_directory = ".claude/skills"
             "Storage path should be a real directory"
         );
     }
+
+    #[tokio::test]
+    #[allow(clippy::await_holding_lock)]
+    async fn test_recursive_add_writes_project_once_and_reports_failures() {
+        let _lock = fastskill_core::test_utils::DIR_MUTEX
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+
+        let temp_dir = TempDir::new().unwrap();
+        let original_dir = std::env::current_dir().ok();
+
+        struct DirGuard(Option<std::path::PathBuf>);
+        impl Drop for DirGuard {
+            fn drop(&mut self) {
+                if let Some(dir) = &self.0 {
+                    let _ = std::env::set_current_dir(dir);
+                }
+            }
+        }
+        let _guard = DirGuard(original_dir);
+        std::env::set_current_dir(temp_dir.path()).unwrap();
+
+        let skills_dir = temp_dir.path().join(".claude/skills");
+        fs::create_dir_all(&skills_dir).unwrap();
+        // Already installed and no --force: this one fails
+        fs::create_dir_all(skills_dir.join("beta-skill")).unwrap();
+
+        let source_root = temp_dir.path().join("bundle");
+        for id in ["alpha-skill", "beta-skill"] {
+            let dir = source_root.join(id);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(
+                dir.join("SKILL.md"),
+                format!("---\nname: {id}\nversion: 1.0.0\ndescription: Test skill {id}\n---\n\n# {id}\n"),
+            )
+            .unwrap();
+            fs::write(
+                dir.join("skill-project.toml"),
+                format!("[metadata]\nid = \"{id}\"\nversion = \"1.0.0\"\n"),
+            )
+            .unwrap();
+        }
+
+        fs::write(
+            temp_dir.path().join("skill-project.toml"),
+            "[tool.fastskill]\nskills_directory = \".claude/skills\"\n\n[dependencies]\n",
+        )
+        .unwrap();
+
+        let config = ServiceConfig {
+            skill_storage_path: skills_dir.clone(),
+            ..Default::default()
+        };
+        let mut service = FastSkillService::new(config).await.unwrap();
+        service.initialize().await.unwrap();
+
+        let args = super::super::AddArgs {
+            source: source_root.display().to_string(),
+            source_type: Some("local".to_string()),
+            branch: None,
+            tag: None,
+            force: false,
+            editable: false,
+            group: None,
+            recursive: true,
+            reindex: false,
+            no_reindex: false,
+            id_strategy: None,
+            on_collision: None,
+            link_mode: None,
+            dry_run: false,
+            json: false,
+        };
+
+        let err = super::super::execute_add(&service, args, false)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CliError::Validation(_)), "{:?}", err);
+        let msg = err.to_string();
+        assert!(msg.contains("1 of 2 skills failed"), "{}", msg);
+        assert!(msg.contains("beta-skill"), "{}", msg);
+
+        assert!(skills_dir.join("alpha-skill").join("SKILL.md").is_file());
+        let manifest = fs::read_to_string(temp_dir.path().join("skill-project.toml")).unwrap();
+        assert!(manifest.contains("alpha-skill"), "{}", manifest);
+        assert!(!manifest.contains("beta-skill"), "{}", manifest);
+    }
 }
@@ -6,6 +6,8 @@ pub mod sources;
 
 // Re-export public API consumed by install_utils.rs
 use crate::error::{manifest_required_message, CliError, CliResult};
+use crate::utils::audit_utils;
+use crate::utils::change_plan::{self, ChangePlan, PlannedChange};
 use crate::utils::{detect_skill_source, validate_skill_structure, SkillSource};
 use chrono::Utc;
 use clap::Args;
@@ -13,9 +15,17 @@ use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
 use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
 use cli_framework::spec::command_tree::CommandSpec;
 use cli_framework::spec::value::ArgValue;
+use fastskill_core::core::audit::AuditDetails;
+use fastskill_core::core::install::{derive_skill_id_and_version, read_skill_frontmatter};
+use fastskill_core::core::link::LinkMode;
+use fastskill_core::core::lock::{
+    global_lock_path, project_lock_path, GlobalSkillsLock, ProjectSkillsLock,
+};
+use fastskill_core::core::manifest::{parse_skill_extras, SkillProjectToml};
 use fastskill_core::core::origin::{GitRef, Origin};
 use fastskill_core::core::project::resolve_project_file;
 use fastskill_core::core::repository::RepositoryManager;
+use fastskill_core::core::skill_naming::{IdCollision, IdStrategy, SkillNaming};
 use fastskill_core::core::version::VersionConstraint;
 use fastskill_core::core::AddMode;
 use fastskill_core::{FastSkillService, SkillDefinition};
@@ -32,6 +42,8 @@ struct AddContext<'a> {
     editable: bool,
     groups: Vec<String>,
     global: bool,
+    naming: SkillNaming,
+    link_mode: LinkMode,
 }
 
 /// Source metadata to record after installing a skill.
@@ -51,19 +63,12 @@ struct InstallTarget {
 
 fn update_project_files(skill_def: &SkillDefinition, groups: Vec<String>) -> CliResult<()> {
     use crate::utils::manifest_utils;
-    manifest_utils::add_skill_to_project_toml(skill_def, groups.clone())
-        .map_err(|e| CliError::Config(format!("Failed to update skill-project.toml: {}", e)))?;
-
-    let current_dir = env::current_dir()
-        .map_err(|e| CliError::Config(format!("Failed to get current directory: {}", e)))?;
-    let project_file_result = resolve_project_file(&current_dir);
-    let lock_path = if let Some(parent) = project_file_result.path.parent() {
-        parent.join("skills.lock")
-    } else {
-        PathBuf::from("skills.lock")
-    };
-    manifest_utils::update_lock_file(&lock_path, skill_def, groups)
-        .map_err(|e| CliError::Config(format!("Failed to update lock file: {}", e)))?;
+    manifest_utils::add_skill_to_project(skill_def, groups, Vec::new()).map_err(|e| {
+        CliError::Config(format!(
+            "Failed to update skill-project.toml and skills.lock: {}",
+            e
+        ))
+    })?;
 
     Ok(())
 }
@@ -112,7 +117,8 @@ async fn register_skill_once(ctx: &AddContext<'_>, skill_def: &SkillDefinition)
 /// Mode 2: Local-only (when no skill-project.toml or --global flag)
 #[derive(Debug, Args)]
 pub struct AddArgs {
-    /// Source: path to zip file, folder, git URL, or skill ID (e.g., pptx@1.2.3)
+    /// Source: path to zip file, folder, git URL, or skill ID (e.g., pptx@1.2.3,
+    /// pptx[docs] to also select the skill's `docs` extra)
     pub source: String,
 
     /// Override source type (registry, github/git, local)
@@ -150,6 +156,29 @@ pub struct AddArgs {
     /// Skip reindex after adding
     #[arg(long)]
     pub no_reindex: bool,
+
+    /// How to generate the skill id: declared, folder, name-slug or
+    /// scoped-hash (overrides `[tool.fastskill.naming] strategy`)
+    #[arg(long)]
+    pub id_strategy: Option<String>,
+
+    /// What to do when the id is taken: error or suffix (overrides
+    /// `[tool.fastskill.naming] on_collision`)
+    #[arg(long)]
+    pub on_collision: Option<String>,
+
+    /// How an editable skill is linked: auto, symlink, junction, copy or hardlink
+    /// (overrides `[tool.fastskill] link_mode`)
+    #[arg(long)]
+    pub link_mode: Option<String>,
+
+    /// Resolve the source and print the change plan without adding anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Print the --dry-run plan as JSON
+    #[arg(long)]
+    pub json: bool,
 }
 
 impl IntoCommandSpec for AddArgs {
@@ -165,7 +194,8 @@ impl IntoCommandSpec for AddArgs {
                     value_type: ArgValueType::String,
                     cardinality: Cardinality::Required,
                     help:
-                        "Source: path to zip file, folder, git URL, or skill ID (e.g., pptx@1.2.3)",
+                        "Source: path to zip file, folder, git URL, or skill ID (e.g., pptx@1.2.3, \
+                           pptx[docs] to select extras)",
                     ..Default::default()
                 },
                 ArgSpec {
@@ -252,6 +282,51 @@ impl IntoCommandSpec for AddArgs {
                     help: "Skip reindex after adding",
                     ..Default::default()
                 },
+                ArgSpec {
+                    name: "id-strategy",
+                    kind: ArgKind::Option,
+                    long: Some("id-strategy"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    help: "How to generate the skill id: declared, folder, name-slug or scoped-hash",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "on-collision",
+                    kind: ArgKind::Option,
+                    long: Some("on-collision"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    help: "When the id is taken: error (default) or suffix (add as <id>-2, <id>-3, ...)",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "link-mode",
+                    kind: ArgKind::Option,
+                    long: Some("link-mode"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    help: "With -e: link the skill by auto (default: symlink, else junction on Windows, else copy), symlink, junction, copy or hardlink (aliases the source files)",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "dry-run",
+                    kind: ArgKind::Flag,
+                    long: Some("dry-run"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Show the skill, version, download size and lock file change an add would make, without adding it",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "json",
+                    kind: ArgKind::Flag,
+                    long: Some("json"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Print the --dry-run plan as JSON",
+                    ..Default::default()
+                },
             ],
             ..Default::default()
         }
@@ -304,6 +379,20 @@ impl FromArgValueMap for AddArgs {
             recursive: matches!(map.get("recursive"), Some(ArgValue::Bool(true))),
             reindex: matches!(map.get("reindex"), Some(ArgValue::Bool(true))),
             no_reindex: matches!(map.get("no-reindex"), Some(ArgValue::Bool(true))),
+            id_strategy: match map.get("id-strategy") {
+                Some(ArgValue::Str(s)) => Some(s.clone()),
+                _ => None,
+            },
+            on_collision: match map.get("on-collision") {
+                Some(ArgValue::Str(s)) => Some(s.clone()),
+                _ => None,
+            },
+            link_mode: match map.get("link-mode") {
+                Some(ArgValue::Str(s)) => Some(s.clone()),
+                _ => None,
+            },
+            dry_run: matches!(map.get("dry-run"), Some(ArgValue::Bool(true))),
+            json: matches!(map.get("json"), Some(ArgValue::Bool(true))),
         }
     }
 }
@@ -327,6 +416,88 @@ fn resolve_source(args: &AddArgs) -> SkillSource {
     }
 }
 
+/// `[tool.fastskill.naming]`, overridden by `--id-strategy` and `--on-collision`.
+fn resolve_naming(service: &FastSkillService, args: &AddArgs) -> CliResult<SkillNaming> {
+    let mut naming = service.config().naming;
+    if let Some(strategy) = &args.id_strategy {
+        naming.strategy = IdStrategy::parse(strategy)
+            .map_err(|e| CliError::Validation(format!("--id-strategy: {}", e)))?;
+    }
+    if let Some(on_collision) = &args.on_collision {
+        naming.on_collision = IdCollision::parse(on_collision)
+            .map_err(|e| CliError::Validation(format!("--on-collision: {}", e)))?;
+    }
+    Ok(naming)
+}
+
+/// `[tool.fastskill] link_mode`, overridden by `--link-mode` (editable adds only).
+fn resolve_link_mode(service: &FastSkillService, args: &AddArgs) -> CliResult<LinkMode> {
+    let Some(mode) = &args.link_mode else {
+        return Ok(service.config().link_mode);
+    };
+    if !args.editable {
+        return Err(CliError::Validation(
+            "--link-mode only applies to editable installs (-e)".to_string(),
+        ));
+    }
+    LinkMode::parse(mode).map_err(|e| CliError::Validation(format!("--link-mode: {}", e)))
+}
+
+fn report_renamed(base: &str, id: &str) {
+    eprintln!(
+        "{}",
+        crate::utils::messages::warning(&format!(
+            "Skill id '{}' is already taken; adding it as '{}'",
+            base, id
+        ))
+    );
+}
+
+/// Warn about required environment variables `id` declares that are unset
+fn report_missing_env(id: &str, missing: &[String]) {
+    if missing.is_empty() {
+        return;
+    }
+    eprintln!(
+        "{}",
+        crate::utils::messages::warning(&format!(
+            "Skill '{}' needs {} set before its scripts can run",
+            id,
+            missing.join(", ")
+        ))
+    );
+}
+
+/// Give `skill_def` (read from `skill_path`) the id `ctx.naming` calls for.
+/// Under `on_collision = "suffix"` a taken id becomes the first free
+/// `<id>-N`; `--force` keeps the id and overwrites, as before.
+async fn apply_naming(
+    ctx: &AddContext<'_>,
+    skill_def: &mut SkillDefinition,
+    skill_path: &Path,
+) -> CliResult<()> {
+    if ctx.naming.strategy != IdStrategy::Declared {
+        let content = std::fs::read_to_string(skill_path.join("SKILL.md"))?;
+        let frontmatter = fastskill_core::core::metadata::parse_yaml_frontmatter(&content)?;
+        skill_def.id = ctx
+            .naming
+            .strategy
+            .skill_id(skill_path, &frontmatter, &skill_def.origin)?;
+    }
+    if ctx.force || ctx.naming.on_collision == IdCollision::Error {
+        return Ok(());
+    }
+    let id = ctx
+        .service
+        .claim_skill_id(&skill_def.id, &skill_def.origin, IdCollision::Suffix)
+        .await?;
+    if id != skill_def.id {
+        report_renamed(skill_def.id.as_str(), id.as_str());
+        skill_def.id = id;
+    }
+    Ok(())
+}
+
 fn ensure_manifest() -> CliResult<()> {
     let current_dir = env::current_dir()
         .map_err(|e| CliError::Config(format!("Failed to get current directory: {}", e)))?;
@@ -406,7 +577,7 @@ async fn build_origin(
                 }
             }
         }
-        Origin::ZipUrl { .. } | Origin::Repository { .. } => {}
+        Origin::ZipUrl { .. } | Origin::Pack { .. } | Origin::Repository { .. } => {}
     }
 
     Ok(origin)
@@ -489,6 +660,121 @@ fn build_origin_explicit_source_type(source: &SkillSource, args: &AddArgs) -> Cl
     }
 }
 
+/// Strip a `[extra,...]` selector from a skill id or local path
+/// (`web[docs]`, `acme/web[docs,examples]@1.2.0`). URLs and paths that exist
+/// as written are left alone.
+fn take_source_extras(source: &mut String) -> CliResult<Vec<String>> {
+    if !source.contains('[') || source.contains("://") || Path::new(source.as_str()).exists() {
+        return Ok(Vec::new());
+    }
+    let (stripped, extras) = parse_skill_extras(source).map_err(CliError::Validation)?;
+    *source = stripped;
+    Ok(extras)
+}
+
+/// Record `extras` for a freshly added skill, warning about any the skill does
+/// not declare. Their optional dependencies are fetched by `fastskill install`.
+fn record_extras(service: &FastSkillService, skill_id: &str, extras: Vec<String>) -> CliResult<()> {
+    use crate::utils::{manifest_utils, messages};
+
+    let skill_manifest = service
+        .config()
+        .skill_storage_path
+        .join(skill_id)
+        .join("skill-project.toml");
+    let declared = SkillProjectToml::load_from_file(&skill_manifest).ok();
+    for extra in &extras {
+        let known = declared.as_ref().is_some_and(|p| {
+            p.optional_skill_entries(std::slice::from_ref(extra))
+                .is_ok()
+        });
+        if !known {
+            eprintln!(
+                "{}",
+                messages::warning(&format!("{} does not declare extra '{}'", skill_id, extra))
+            );
+        }
+    }
+    manifest_utils::record_skill_extras(skill_id, extras)
+        .map_err(|e| CliError::Config(format!("Failed to record extras: {}", e)))?;
+    messages::status!(
+        "{}",
+        messages::info(
+            "Run 'fastskill install' to fetch the optional dependencies of the selected extras"
+        )
+    );
+    Ok(())
+}
+
+/// `--dry-run`: resolve `source` to the skill it would add and print the
+/// change plan. Git and zip-url skills are only identified once fetched, so
+/// they are listed under the source as given.
+async fn plan_add(
+    service: &FastSkillService,
+    source: &SkillSource,
+    args: &AddArgs,
+    global: bool,
+) -> CliResult<()> {
+    let origin = build_origin(service, source, args).await?;
+    let (id, version) = match &origin {
+        Origin::Repository { skill, .. } => (skill.clone(), service.target_version(&origin).await?),
+        Origin::Local { path, .. } if path.is_dir() => {
+            let frontmatter = read_skill_frontmatter(path).await?;
+            let (id, version) = derive_skill_id_and_version(path, &frontmatter)?;
+            (id.to_string(), Some(version))
+        }
+        _ => (args.source.clone(), None),
+    };
+    let size = service
+        .download_size(&origin, version.as_deref())
+        .await
+        .unwrap_or_else(|e| {
+            tracing::debug!("No download size for {}: {}", id, e);
+            None
+        });
+
+    let (lockfile, locked) = if global {
+        let lock_path = global_lock_path()
+            .map_err(|e| CliError::Config(format!("Failed to resolve global lock path: {}", e)))?;
+        let locked = if lock_path.exists() {
+            GlobalSkillsLock::load_from_file(&lock_path)
+                .map_err(|e| CliError::Config(format!("Failed to load global lock file: {}", e)))?
+                .skills
+                .into_iter()
+                .find(|entry| entry.id == id)
+                .map(|entry| entry.resolved.version)
+        } else {
+            None
+        };
+        ("global-skills.lock", locked)
+    } else {
+        let current_dir = env::current_dir()
+            .map_err(|e| CliError::Config(format!("Failed to get current directory: {}", e)))?;
+        let lock_path = project_lock_path(&resolve_project_file(&current_dir).path);
+        let locked = if lock_path.exists() {
+            ProjectSkillsLock::load_from_file(&lock_path)
+                .map_err(|e| CliError::Config(format!("Failed to load lock file: {}", e)))?
+                .skills
+                .into_iter()
+                .find(|entry| entry.id == id)
+                .map(|entry| entry.resolved.version)
+        } else {
+            None
+        };
+        ("skills.lock", locked)
+    };
+    if locked.is_some() && !args.force {
+        change_plan::warn(&format!(
+            "{} is already installed; the add would fail without --force",
+            id
+        ));
+    }
+
+    let change = PlannedChange::install(id, locked, version).with_download_size(size);
+    ChangePlan::new("add", lockfile, vec![change]).print(args.json);
+    Ok(())
+}
+
 pub async fn execute_add(service: &FastSkillService, args: AddArgs, global: bool) -> CliResult<()> {
     if args.reindex && args.no_reindex {
         return Err(CliError::Validation(
@@ -498,7 +784,18 @@ pub async fn execute_add(service: &FastSkillService, args: AddArgs, global: bool
     let reindex = args.reindex;
     let no_reindex = args.no_reindex;
 
+    let mut args = args;
+    let extras = take_source_extras(&mut args.source)?;
+    if !extras.is_empty() && (global || args.recursive) {
+        return Err(CliError::Validation(
+            "Extras ([...]) can only be selected when adding a single skill to a project"
+                .to_string(),
+        ));
+    }
+
     let source = resolve_source(&args);
+    let naming = resolve_naming(service, &args)?;
+    let link_mode = resolve_link_mode(service, &args)?;
 
     if args.editable {
         match &source {
@@ -520,6 +817,68 @@ pub async fn execute_add(service: &FastSkillService, args: AddArgs, global: bool
         ensure_manifest()?;
     }
 
+    if args.json && !args.dry_run {
+        return Err(CliError::Validation(
+            "--json requires --dry-run".to_string(),
+        ));
+    }
+    if args.dry_run {
+        let is_pack = match &source {
+            SkillSource::ZipFile(path) => fastskill_core::core::pack::is_pack(path)?,
+            _ => false,
+        };
+        if args.recursive || is_pack {
+            return Err(CliError::Validation(
+                "--dry-run covers a single skill; it cannot be combined with --recursive or a \
+                 skill pack"
+                    .to_string(),
+            ));
+        }
+        return plan_add(service, &source, &args, global).await;
+    }
+
+    // A skill pack expands into one managed skill per entry, each added
+    // through the core seam with an `Origin::Pack` that names the pack.
+    if let SkillSource::ZipFile(path) = &source {
+        if fastskill_core::core::pack::is_pack(path)? {
+            if global || args.recursive {
+                return Err(CliError::Validation(
+                    "Skill packs can only be added to a project; drop --global and --recursive"
+                        .to_string(),
+                ));
+            }
+            let mode = if args.force {
+                AddMode::Update
+            } else {
+                AddMode::Fresh
+            };
+            let groups = args.group.clone().map(|g| vec![g]).unwrap_or_default();
+            let added = sources::add_from_pack(service, path, mode, groups, naming).await;
+            audit_utils::record(
+                &service.config().skill_storage_path,
+                "skill.add",
+                "add",
+                AuditDetails {
+                    source: Some(path.display().to_string()),
+                    ..Default::default()
+                },
+                &added,
+            );
+            added?;
+
+            let auto_reindex = crate::config_file::load_auto_reindex_config();
+            return crate::utils::reindex_utils::maybe_auto_reindex(
+                service,
+                "add",
+                reindex,
+                no_reindex,
+                auto_reindex,
+                false,
+            )
+            .await;
+        }
+    }
+
     // `--global` and `--recursive` are not (yet) expressible through the core
     // `add_from_origin` seam — it is single-skill + project-level only (no
     // global-lock concept, no directory-of-skills fan-out) — so both keep the
@@ -532,9 +891,11 @@ pub async fn execute_add(service: &FastSkillService, args: AddArgs, global: bool
             editable: args.editable,
             groups,
             global,
+            naming,
+            link_mode,
         };
 
-        if args.recursive {
+        let added = if args.recursive {
             let path = match &source {
                 SkillSource::Folder(p) => p,
                 _ => {
@@ -543,24 +904,33 @@ pub async fn execute_add(service: &FastSkillService, args: AddArgs, global: bool
                     ));
                 }
             };
-            install::handle_recursive_add(&ctx, path).await?;
+            install::handle_recursive_add(&ctx, path).await
         } else {
-            match source {
-                SkillSource::ZipFile(path) => sources::add_from_zip(&ctx, &path).await?,
+            match &source {
+                SkillSource::ZipFile(path) => sources::add_from_zip(&ctx, path).await,
                 SkillSource::Folder(path) => {
-                    validate_folder_has_skill(&path)?;
-                    sources::add_from_folder(&ctx, &path).await?
+                    validate_folder_has_skill(path)?;
+                    sources::add_from_folder(&ctx, path).await
                 }
                 SkillSource::GitUrl(url) => {
-                    sources::add_from_git(&ctx, &url, args.branch.as_deref(), args.tag.as_deref())
-                        .await?
-                }
-                SkillSource::RemoteZipUrl(url) => sources::add_from_zip_url(&ctx, &url).await?,
-                SkillSource::SkillId(skill_id) => {
-                    sources::add_from_registry(&ctx, &skill_id).await?
+                    sources::add_from_git(&ctx, url, args.branch.as_deref(), args.tag.as_deref())
+                        .await
                 }
+                SkillSource::RemoteZipUrl(url) => sources::add_from_zip_url(&ctx, url).await,
+                SkillSource::SkillId(skill_id) => sources::add_from_registry(&ctx, skill_id).await,
             }
-        }
+        };
+        audit_utils::record(
+            &service.config().skill_storage_path,
+            "skill.add",
+            "add",
+            AuditDetails {
+                source: Some(args.source.clone()),
+                ..Default::default()
+            },
+            &added,
+        );
+        added?;
 
         let auto_reindex = crate::config_file::load_auto_reindex_config();
         return crate::utils::reindex_utils::maybe_auto_reindex(
@@ -591,9 +961,31 @@ pub async fn execute_add(service: &FastSkillService, args: AddArgs, global: bool
     };
     let groups = args.group.clone().map(|g| vec![g]).unwrap_or_default();
     let outcome = service
-        .add_from_origin(origin, mode, groups)
+        .add_from_origin_with_naming(origin, mode, groups, naming, link_mode)
         .await
-        .map_err(CliError::Service)?;
+        .map_err(CliError::Service);
+    let details = match &outcome {
+        Ok(outcome) => audit_utils::added(outcome),
+        Err(_) => AuditDetails {
+            source: Some(args.source.clone()),
+            ..Default::default()
+        },
+    };
+    audit_utils::record(
+        &service.config().skill_storage_path,
+        "skill.add",
+        "add",
+        details,
+        &outcome,
+    );
+    let outcome = outcome?;
+    if let Some(base) = &outcome.renamed_from {
+        report_renamed(base, &outcome.id);
+    }
+    if let Some(used) = outcome.linked {
+        crate::utils::install_utils::report_link_fallback(link_mode, used);
+    }
+    report_missing_env(&outcome.id, &outcome.missing_env);
 
     // `AddOutcome` only carries the skill `id`, not its display `name`; look the
     // freshly-registered skill back up for a nicer message, falling back to the
@@ -610,11 +1002,15 @@ pub async fn execute_add(service: &FastSkillService, args: AddArgs, global: bool
         Err(_) => outcome.id.clone(),
     };
 
+    if !extras.is_empty() {
+        record_extras(service, &outcome.id, extras)?;
+    }
+
     println!(
         "Successfully added skill: {} (v{})",
         display_name, outcome.resolved.version
     );
-    println!(
+    crate::utils::messages::status!(
         "{}",
         crate::utils::messages::ok("Updated skill-project.toml and skills.lock")
     );
@@ -662,11 +1058,36 @@ mod tests {
             recursive: false,
             reindex: false,
             no_reindex: false,
+            id_strategy: None,
+            on_collision: None,
+            link_mode: None,
+            dry_run: false,
+            json: false,
         };
         let result = execute_add(&service, args, false).await;
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_take_source_extras() {
+        let mut source = "acme/web[docs,examples]@1.2.0".to_string();
+        assert_eq!(
+            take_source_extras(&mut source).unwrap(),
+            vec!["docs".to_string(), "examples".to_string()]
+        );
+        assert_eq!(source, "acme/web@1.2.0");
+
+        let mut url = "https://example.com/repo[x].git".to_string();
+        assert!(take_source_extras(&mut url).unwrap().is_empty());
+        assert_eq!(url, "https://example.com/repo[x].git");
+
+        let mut bad = "web[docs".to_string();
+        assert!(matches!(
+            take_source_extras(&mut bad),
+            Err(CliError::Validation(_))
+        ));
+    }
+
     #[tokio::test]
     async fn test_execute_add_nonexistent_source() {
         run_add_expect_err("/nonexistent/path", Some("local"), false).await;
@@ -719,6 +1140,11 @@ mod tests {
             recursive: false,
             reindex: false,
             no_reindex: false,
+            id_strategy: None,
+            on_collision: None,
+            link_mode: None,
+            dry_run: false,
+            json: false,
         };
 
         let result = execute_add(&service, args, false).await;
@@ -803,6 +1229,11 @@ mod tests {
             recursive: false,
             reindex: false,
             no_reindex: false,
+            id_strategy: None,
+            on_collision: None,
+            link_mode: None,
+            dry_run: false,
+            json: false,
         };
 
         let result = execute_add(&service, args, false).await;
@@ -842,6 +1273,97 @@ mod tests {
         assert_eq!(entry.groups, vec!["dev".to_string()]);
     }
 
+    #[tokio::test]
+    #[allow(clippy::await_holding_lock)]
+    async fn test_execute_add_dry_run_writes_nothing() {
+        let _lock = fastskill_core::test_utils::DIR_MUTEX
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let (tmp, _guard, skills_dir) = setup_common_path_project();
+
+        let src = tmp.path().join("src-skill");
+        write_valid_skill_md(&src, "dry-run-skill");
+        let manifest = std::fs::read_to_string(tmp.path().join("skill-project.toml")).unwrap();
+
+        let config = ServiceConfig {
+            skill_storage_path: skills_dir.clone(),
+            ..Default::default()
+        };
+        let mut service = FastSkillService::new(config).await.unwrap();
+        service.initialize().await.unwrap();
+
+        let args = AddArgs {
+            source: src.display().to_string(),
+            source_type: None,
+            branch: None,
+            tag: None,
+            force: false,
+            editable: false,
+            group: None,
+            recursive: false,
+            reindex: false,
+            no_reindex: false,
+            id_strategy: None,
+            on_collision: None,
+            link_mode: None,
+            dry_run: true,
+            json: true,
+        };
+
+        execute_add(&service, args, false).await.unwrap();
+        assert!(!skills_dir.join("dry-run-skill").exists());
+        assert!(!tmp.path().join("skills.lock").exists());
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join("skill-project.toml")).unwrap(),
+            manifest
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_link_mode() {
+        let tmp = TempDir::new().unwrap();
+        let config = ServiceConfig {
+            skill_storage_path: tmp.path().to_path_buf(),
+            link_mode: LinkMode::Junction,
+            ..Default::default()
+        };
+        let service = FastSkillService::new(config).await.unwrap();
+        let args = |editable: bool, link_mode: Option<&str>| AddArgs {
+            source: "./skill".to_string(),
+            source_type: None,
+            branch: None,
+            tag: None,
+            force: false,
+            editable,
+            group: None,
+            recursive: false,
+            reindex: false,
+            no_reindex: false,
+            id_strategy: None,
+            on_collision: None,
+            link_mode: link_mode.map(str::to_string),
+            dry_run: false,
+            json: false,
+        };
+
+        assert_eq!(
+            resolve_link_mode(&service, &args(true, None)).unwrap(),
+            LinkMode::Junction
+        );
+        assert_eq!(
+            resolve_link_mode(&service, &args(true, Some("copy"))).unwrap(),
+            LinkMode::Copy
+        );
+        assert!(matches!(
+            resolve_link_mode(&service, &args(false, Some("copy"))),
+            Err(CliError::Validation(_))
+        ));
+        assert!(matches!(
+            resolve_link_mode(&service, &args(true, Some("hardlink"))),
+            Err(CliError::Validation(_))
+        ));
+    }
+
     #[tokio::test]
     #[allow(clippy::await_holding_lock)]
     async fn test_execute_add_force_overwrites_via_common_path() {
@@ -871,6 +1393,11 @@ mod tests {
             recursive: false,
             reindex: false,
             no_reindex: false,
+            id_strategy: None,
+            on_collision: None,
+            link_mode: None,
+            dry_run: false,
+            json: false,
         };
 
         execute_add(&service, make_args(false), false)
@@ -946,6 +1473,11 @@ mod tests {
             recursive: false,
             reindex: false,
             no_reindex: false,
+            id_strategy: None,
+            on_collision: None,
+            link_mode: None,
+            dry_run: false,
+            json: false,
         };
 
         // Before the fix, `detect_skill_source` classified this as `GitUrl` and
@@ -134,6 +134,9 @@ pub fn create_skill_from_path(
         "1.0.0".to_string()
     };
 
+    let execution_environment =
+        fastskill_core::execution::runtime::declared_environment(&frontmatter);
+    let env = fastskill_core::core::skill_env::declared_env(skill_path, &frontmatter)?;
     let mut skill = SkillDefinition::new(
         skill_id,
         frontmatter.name,
@@ -143,6 +146,8 @@ pub fn create_skill_from_path(
     );
 
     skill.skill_file = skill_file.clone();
+    skill.execution_environment = execution_environment;
+    skill.env = env;
     skill.author = frontmatter.author;
 
     Ok(skill)
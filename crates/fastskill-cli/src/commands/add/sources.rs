@@ -4,6 +4,7 @@ use crate::error::{CliError, CliResult};
 use crate::utils::install_utils::{extract_zip, safe_subdir_join};
 use crate::utils::{parse_git_url, validate_skill_structure};
 use fastskill_core::core::origin::{GitRef, Origin};
+use fastskill_core::SkillId;
 use std::fs;
 use std::path::{Path, PathBuf};
 use tempfile::TempDir;
@@ -60,8 +61,9 @@ pub(super) async fn add_from_zip(ctx: &super::AddContext<'_>, zip_path: &Path) -
         path: canonical_zip_path.clone(),
         editable: false,
     };
-    let skill_def =
+    let mut skill_def =
         super::skill_def::create_skill_from_path(&skill_path, origin.clone(), "zip", false)?;
+    super::apply_naming(ctx, &mut skill_def, &skill_path).await?;
     let version = skill_def.version.clone();
     let target = super::InstallTarget {
         storage_dir: ctx
@@ -103,8 +105,9 @@ pub(super) async fn add_from_zip_url(ctx: &super::AddContext<'_>, url: &str) ->
     let origin = Origin::ZipUrl {
         url: url.to_string(),
     };
-    let skill_def =
+    let mut skill_def =
         super::skill_def::create_skill_from_path(&skill_path, origin.clone(), "zip-url", false)?;
+    super::apply_naming(ctx, &mut skill_def, &skill_path).await?;
     let version = skill_def.version.clone();
     let target = super::InstallTarget {
         storage_dir: ctx
@@ -135,12 +138,13 @@ pub(super) async fn add_from_folder(
         path: canonical_path.clone(),
         editable: ctx.editable,
     };
-    let skill_def = super::skill_def::create_skill_from_path(
+    let mut skill_def = super::skill_def::create_skill_from_path(
         folder_path,
         origin.clone(),
         "local",
         ctx.editable,
     )?;
+    super::apply_naming(ctx, &mut skill_def, folder_path).await?;
     let target = super::InstallTarget {
         storage_dir: ctx
             .service
@@ -153,6 +157,85 @@ pub(super) async fn add_from_folder(
     super::install::install_via_local_path(ctx, &canonical_path, skill_def, target).await
 }
 
+/// Add every skill in a skill pack. Each skill goes through the core install
+/// seam on its own, so one bad entry does not stop the rest.
+pub(super) async fn add_from_pack(
+    service: &fastskill_core::FastSkillService,
+    pack_path: &Path,
+    mode: fastskill_core::core::AddMode,
+    groups: Vec<String>,
+    naming: fastskill_core::core::skill_naming::SkillNaming,
+) -> CliResult<()> {
+    use crate::utils::messages;
+    use fastskill_core::core::pack::read_pack_manifest;
+
+    info!("Adding skill pack: {}", pack_path.display());
+    let canonical_path = pack_path.canonicalize().map_err(|e| {
+        CliError::InvalidSource(format!(
+            "Failed to resolve absolute path for '{}': {}",
+            pack_path.display(),
+            e
+        ))
+    })?;
+    let manifest = read_pack_manifest(&canonical_path)?;
+    println!(
+        "Adding skill pack {} v{} ({} skills)",
+        manifest.pack.name,
+        manifest.pack.version,
+        manifest.skills.len()
+    );
+
+    let mut failed = Vec::new();
+    for entry in &manifest.skills {
+        let origin = Origin::Pack {
+            path: canonical_path.clone(),
+            skill: entry.id.clone(),
+        };
+        match service
+            .add_from_origin_with_naming(
+                origin,
+                mode,
+                groups.clone(),
+                naming,
+                service.config().link_mode,
+            )
+            .await
+        {
+            Ok(outcome) => {
+                if let Some(base) = &outcome.renamed_from {
+                    super::report_renamed(base, &outcome.id);
+                }
+                super::report_missing_env(&outcome.id, &outcome.missing_env);
+                println!(
+                    "Successfully added skill: {} (v{})",
+                    outcome.id, outcome.resolved.version
+                )
+            }
+            Err(e) => {
+                eprintln!("{}", messages::error(&format!("{}: {}", entry.id, e)));
+                failed.push(format!("  - {}: {}", entry.id, e));
+            }
+        }
+    }
+
+    let total = manifest.skills.len();
+    if failed.len() < total {
+        messages::status!(
+            "{}",
+            messages::ok("Updated skill-project.toml and skills.lock")
+        );
+    }
+    if failed.is_empty() {
+        return Ok(());
+    }
+    Err(CliError::Validation(format!(
+        "{} of {} skills failed:\n{}",
+        failed.len(),
+        total,
+        failed.join("\n")
+    )))
+}
+
 async fn clone_and_validate_skill(
     git_url: &str,
     branch: Option<&str>,
@@ -202,9 +285,10 @@ pub(super) async fn add_from_git(
     tag: Option<&str>,
 ) -> CliResult<()> {
     info!("Adding skill from git URL: {}", git_url);
-    let (_temp_dir, skill_path, skill_def, origin) =
+    let (_temp_dir, skill_path, mut skill_def, origin) =
         clone_and_validate_skill(git_url, branch, tag).await?;
     validate_skill_structure(&skill_path)?;
+    super::apply_naming(ctx, &mut skill_def, &skill_path).await?;
     let target = super::InstallTarget {
         storage_dir: ctx
             .service
@@ -301,9 +385,7 @@ pub(super) async fn add_from_registry(
     info!("Adding skill from registry: {}", skill_id_input);
     let (skill_id_full, scope, expected_id, version_opt) = parse_registry_scope_id(skill_id_input)?;
 
-    let repositories = crate::config::load_repositories_from_project()?;
-    let repo_manager =
-        fastskill_core::core::repository::RepositoryManager::from_definitions(repositories);
+    let repo_manager = crate::config::load_repository_manager()?;
     let default_repo = repo_manager.get_default_repository().ok_or_else(|| {
         CliError::Config(
             "No default repository configured. Use 'fastskill repos add' to add a repository."
@@ -357,7 +439,7 @@ pub(super) async fn add_from_registry(
         "Downloading {}@{} from repository...",
         skill_id_full, version
     );
-    let (_temp_dir, skill_path, skill_def) =
+    let (_temp_dir, skill_path, mut skill_def) =
         download_registry_package(repo_client.as_ref(), &skill_id_full, &version, origin).await?;
 
     if skill_def.id.as_str() != expected_id {
@@ -367,13 +449,12 @@ pub(super) async fn add_from_registry(
         )));
     }
 
+    // Installed under its scoped id, in the scope's directory
+    skill_def.id = SkillId::scoped(&scope, skill_def.id.as_str())?;
+    let skills_dir = &ctx.service.config().skill_storage_path;
+    fastskill_core::core::scope::ensure_scope_dir(skills_dir, &skill_def.id)?;
     let target = super::InstallTarget {
-        storage_dir: ctx
-            .service
-            .config()
-            .skill_storage_path
-            .join(&scope)
-            .join(skill_def.id.as_str()),
+        storage_dir: skills_dir.join(skill_def.id.as_str()),
         meta: super::SourceMeta {
             origin: skill_def.origin.clone(),
         },
@@ -693,6 +774,8 @@ mod tests {
             editable: false,
             groups: Vec::new(),
             global: false,
+            naming: Default::default(),
+            link_mode: Default::default(),
         };
         let result = add_from_zip(&ctx, &zip_path).await;
         assert!(result.is_ok(), "add_from_zip should succeed: {:?}", result);
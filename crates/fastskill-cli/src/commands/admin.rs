@@ -0,0 +1,490 @@
+//! Admin commands - inspect server-side administrative state
+//!
+//! `admin audit` lists the audit log (`<skills dir>/.fastskill/audit.jsonl`),
+//! like `fastskill audit` without `--skill` and `--verify`. `admin keys`
+//! manages the scoped API keys the server checks
+//! (`<skills dir>/.fastskill/api-keys.toml`).
+
+use crate::commands::audit::{execute_audit, AuditArgs};
+use crate::error::{CliError, CliResult};
+use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
+use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
+use cli_framework::spec::command_tree::CommandSpec;
+use cli_framework::spec::value::ArgValue;
+use fastskill_core::core::api_keys::{ApiKey, ApiKeyScope, ApiKeyStore};
+use fastskill_core::output::table::Table;
+use fastskill_core::FastSkillService;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Arguments for `admin audit`
+#[derive(Debug, Clone)]
+pub struct AdminAuditArgs {
+    /// Only entries from the last N days
+    pub since_days: Option<i64>,
+    /// Exact action or `prefix.` to filter on
+    pub action: Option<String>,
+    /// Only entries by this actor
+    pub actor: Option<String>,
+    /// Show at most the N most recent entries
+    pub limit: Option<usize>,
+    /// Emit JSON Lines instead of a table
+    pub jsonl: bool,
+    /// Write JSON Lines to this file instead of stdout
+    pub output: Option<PathBuf>,
+}
+
+impl IntoCommandSpec for AdminAuditArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Show the audit log of skill changes (see also `fastskill audit`)",
+            syntax: Some("admin audit [OPTIONS]"),
+            category: Some("server"),
+            args: vec![
+                ArgSpec {
+                    name: "since-days",
+                    kind: ArgKind::Option,
+                    long: Some("since-days"),
+                    value_type: ArgValueType::Int,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    help: "Only show entries from the last N days",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "action",
+                    kind: ArgKind::Option,
+                    long: Some("action"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    help: "Filter by action (e.g. skill.delete), or a prefix ending in '.' (e.g. skill.)",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "actor",
+                    kind: ArgKind::Option,
+                    long: Some("actor"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    help: "Filter by actor (client profile name, key:<name> for an API key, or 'anonymous')",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "limit",
+                    kind: ArgKind::Option,
+                    long: Some("limit"),
+                    value_type: ArgValueType::Int,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    help: "Show at most the N most recent entries",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "jsonl",
+                    kind: ArgKind::Flag,
+                    long: Some("jsonl"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Output JSON Lines (one entry per line) instead of a table",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "output",
+                    kind: ArgKind::Option,
+                    long: Some("output"),
+                    short: Some('o'),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    help: "Export matching entries as JSON Lines to this file",
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for AdminAuditArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        let string = |key: &str| match map.get(key) {
+            Some(ArgValue::Str(s)) => Some(s.clone()),
+            _ => None,
+        };
+        Self {
+            since_days: match map.get("since-days") {
+                Some(ArgValue::Int(n)) => Some(*n),
+                _ => None,
+            },
+            action: string("action"),
+            actor: string("actor"),
+            limit: match map.get("limit") {
+                Some(ArgValue::Int(n)) if *n >= 0 => Some(*n as usize),
+                _ => None,
+            },
+            jsonl: matches!(map.get("jsonl"), Some(ArgValue::Bool(true))),
+            output: string("output").map(PathBuf::from),
+        }
+    }
+}
+
+pub async fn execute_admin_audit(
+    service: &FastSkillService,
+    args: AdminAuditArgs,
+) -> CliResult<()> {
+    let args = AuditArgs {
+        since_days: args.since_days,
+        action: args.action,
+        actor: args.actor,
+        limit: args.limit,
+        jsonl: args.jsonl,
+        output: args.output,
+        ..Default::default()
+    };
+    execute_audit(service, args).await
+}
+
+/// Arguments for `admin keys create`
+#[derive(Debug, Clone)]
+pub struct AdminKeysCreateArgs {
+    /// Name the key is listed and revoked under
+    pub name: String,
+    /// Scopes granted to the key
+    pub scopes: Vec<String>,
+}
+
+/// Arguments for `admin keys list`
+#[derive(Debug, Clone)]
+pub struct AdminKeysListArgs {
+    /// Include revoked keys
+    pub all: bool,
+    /// Output JSON instead of a table
+    pub json: bool,
+}
+
+/// Arguments for `admin keys revoke`
+#[derive(Debug, Clone)]
+pub struct AdminKeysRevokeArgs {
+    /// Id or name of the key
+    pub key: String,
+}
+
+impl IntoCommandSpec for AdminKeysCreateArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Create a scoped API key for machine clients of the server",
+            syntax: Some("admin keys create <NAME> --scope SCOPE [--scope SCOPE...]"),
+            category: Some("server"),
+            args: vec![
+                ArgSpec {
+                    name: "name",
+                    kind: ArgKind::Positional,
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Required,
+                    help: "Name of the key (e.g. the client using it)",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "scope",
+                    kind: ArgKind::Option,
+                    long: Some("scope"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Repeated,
+                    help: "Scope to grant: read:skills, write:skills, execute:tools or admin (repeatable)",
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for AdminKeysCreateArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        Self {
+            name: match map.get("name") {
+                Some(ArgValue::Str(s)) => s.clone(),
+                _ => String::new(),
+            },
+            scopes: match map.get("scope") {
+                Some(ArgValue::List(items)) => items
+                    .iter()
+                    .filter_map(|item| match item {
+                        ArgValue::Str(s) => Some(s.clone()),
+                        _ => None,
+                    })
+                    .collect(),
+                Some(ArgValue::Str(s)) => vec![s.clone()],
+                _ => Vec::new(),
+            },
+        }
+    }
+}
+
+impl IntoCommandSpec for AdminKeysListArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "List the server's API keys",
+            syntax: Some("admin keys list [--all] [--json]"),
+            category: Some("server"),
+            args: vec![
+                ArgSpec {
+                    name: "all",
+                    kind: ArgKind::Flag,
+                    long: Some("all"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Include revoked keys",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "json",
+                    kind: ArgKind::Flag,
+                    long: Some("json"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Output JSON (key hashes are never included)",
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for AdminKeysListArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        Self {
+            all: matches!(map.get("all"), Some(ArgValue::Bool(true))),
+            json: matches!(map.get("json"), Some(ArgValue::Bool(true))),
+        }
+    }
+}
+
+impl IntoCommandSpec for AdminKeysRevokeArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Revoke an API key",
+            syntax: Some("admin keys revoke <ID|NAME>"),
+            category: Some("server"),
+            args: vec![ArgSpec {
+                name: "key",
+                kind: ArgKind::Positional,
+                value_type: ArgValueType::String,
+                cardinality: Cardinality::Required,
+                help: "Id or name of the key to revoke",
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for AdminKeysRevokeArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        Self {
+            key: match map.get("key") {
+                Some(ArgValue::Str(s)) => s.clone(),
+                _ => String::new(),
+            },
+        }
+    }
+}
+
+fn key_store(service: &FastSkillService) -> CliResult<ApiKeyStore> {
+    Ok(ApiKeyStore::load(&ApiKeyStore::default_path(
+        &service.config().skill_storage_path,
+    ))?)
+}
+
+fn scopes_label(key: &ApiKey) -> String {
+    key.scopes
+        .iter()
+        .map(|s| s.as_str())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+pub async fn execute_admin_keys_create(
+    service: &FastSkillService,
+    args: AdminKeysCreateArgs,
+) -> CliResult<()> {
+    if args.scopes.is_empty() {
+        return Err(CliError::Validation(
+            "admin keys create needs at least one --scope".to_string(),
+        ));
+    }
+    let scopes = args
+        .scopes
+        .iter()
+        .map(|s| ApiKeyScope::parse(s))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut store = key_store(service)?;
+    let first = !store.is_enabled();
+    let (key, secret) = store.create(&args.name, &scopes)?;
+    store.save()?;
+
+    println!(
+        "Created API key '{}' (id {}) with scopes {}",
+        key.name,
+        key.id,
+        scopes_label(&key)
+    );
+    println!();
+    println!("  {}", secret);
+    println!();
+    println!("Store it now: only its hash is kept, so it cannot be shown again.");
+    if first {
+        println!(
+            "This is the first key: the server now requires an API key on every /api/v1 request."
+        );
+    }
+    Ok(())
+}
+
+pub async fn execute_admin_keys_list(
+    service: &FastSkillService,
+    args: AdminKeysListArgs,
+) -> CliResult<()> {
+    let store = key_store(service)?;
+    let keys: Vec<&ApiKey> = store
+        .keys()
+        .iter()
+        .filter(|k| args.all || k.is_active())
+        .collect();
+
+    if args.json {
+        let json: Vec<serde_json::Value> = keys
+            .iter()
+            .map(|k| {
+                serde_json::json!({
+                    "id": k.id,
+                    "name": k.name,
+                    "scopes": k.scopes,
+                    "created_at": k.created_at,
+                    "revoked_at": k.revoked_at,
+                })
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json)
+                .map_err(|e| CliError::Config(format!("Failed to encode keys: {}", e)))?
+        );
+        return Ok(());
+    }
+    if keys.is_empty() {
+        println!(
+            "No API keys in {}; the server accepts requests without one",
+            store.path().display()
+        );
+        return Ok(());
+    }
+    let mut table = Table::new(["ID", "NAME", "SCOPES", "CREATED (UTC)", "REVOKED (UTC)"]);
+    for key in keys {
+        table.push_row([
+            key.id.clone(),
+            key.name.clone(),
+            scopes_label(key),
+            key.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            key.revoked_at
+                .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_else(|| "-".to_string()),
+        ]);
+    }
+    print!("{}", table.render());
+    Ok(())
+}
+
+pub async fn execute_admin_keys_revoke(
+    service: &FastSkillService,
+    args: AdminKeysRevokeArgs,
+) -> CliResult<()> {
+    let mut store = key_store(service)?;
+    let key = store.revoke(&args.key)?;
+    store.save()?;
+    println!("Revoked API key '{}' (id {})", key.name, key.id);
+    if !store.keys().iter().any(|k| k.is_active()) {
+        println!(
+            "No active keys remain: the server refuses requests that need a key. \
+             Create a new key, or delete {} to turn authentication off.",
+            store.path().display()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_admin_audit_args_parse() {
+        let mut map = HashMap::new();
+        map.insert("since-days".to_string(), ArgValue::Int(3));
+        map.insert("action".to_string(), ArgValue::Str("skill.".to_string()));
+        map.insert("limit".to_string(), ArgValue::Int(10));
+        map.insert("jsonl".to_string(), ArgValue::Bool(true));
+        let args = AdminAuditArgs::from_arg_value_map(&map);
+        assert_eq!(args.since_days, Some(3));
+        assert_eq!(args.action.as_deref(), Some("skill."));
+        assert_eq!(args.limit, Some(10));
+        assert!(args.jsonl);
+        assert!(args.actor.is_none());
+        assert!(args.output.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_admin_keys_create_and_revoke() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = fastskill_core::ServiceConfig {
+            skill_storage_path: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        let service = FastSkillService::new(config).await.unwrap();
+
+        let err = execute_admin_keys_create(
+            &service,
+            AdminKeysCreateArgs {
+                name: "ci".to_string(),
+                scopes: vec!["write".to_string()],
+            },
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("Unknown scope"), "{}", err);
+
+        execute_admin_keys_create(
+            &service,
+            AdminKeysCreateArgs {
+                name: "ci".to_string(),
+                scopes: vec!["read:skills".to_string(), "execute:tools".to_string()],
+            },
+        )
+        .await
+        .unwrap();
+        let store = key_store(&service).unwrap();
+        assert!(store.is_enabled());
+        assert_eq!(
+            store.keys()[0].scopes,
+            [ApiKeyScope::ReadSkills, ApiKeyScope::ExecuteTools]
+        );
+
+        execute_admin_keys_revoke(
+            &service,
+            AdminKeysRevokeArgs {
+                key: "ci".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        // Authentication stays on after the last key is revoked
+        let store = key_store(&service).unwrap();
+        assert!(store.is_enabled());
+        assert!(!store.keys()[0].is_active());
+    }
+}
@@ -0,0 +1,158 @@
+//! Conflicts command — report installed skills that disagree with skills.lock
+//! or with the peer dependency ranges other installed skills declare.
+//!
+//! Unlike the other analyze subcommands this is purely structural and needs no
+//! embedding provider.
+
+use crate::error::{CliError, CliResult};
+use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
+use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
+use cli_framework::spec::command_tree::CommandSpec;
+use cli_framework::spec::value::ArgValue;
+use fastskill_core::core::lock::{project_lock_path, ProjectSkillsLock};
+use fastskill_core::core::peers::check_peer_dependencies;
+use fastskill_core::core::project::resolve_project_file;
+use fastskill_core::output::table::Table;
+use fastskill_core::FastSkillService;
+use serde::Serialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub struct ConflictsArgs {
+    pub json: bool,
+}
+
+impl IntoCommandSpec for ConflictsArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Report lock drift and peer dependency violations among installed skills",
+            syntax: Some("analyze conflicts [OPTIONS]"),
+            category: Some("analysis"),
+            args: vec![ArgSpec {
+                name: "json",
+                kind: ArgKind::Flag,
+                long: Some("json"),
+                value_type: ArgValueType::Bool,
+                cardinality: Cardinality::Optional,
+                help: "Output JSON instead of a table",
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for ConflictsArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        ConflictsArgs {
+            json: matches!(map.get("json"), Some(ArgValue::Bool(true))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum ConflictKind {
+    /// Installed state differs from skills.lock
+    Lock,
+    /// An installed peer is outside a declared peer dependency range
+    Peer,
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct Conflict {
+    pub kind: ConflictKind,
+    pub skill_id: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ConflictsJsonOutput {
+    total: usize,
+    conflicts: Vec<Conflict>,
+}
+
+/// Execute the conflicts command
+pub async fn execute_conflicts(service: &FastSkillService, args: ConflictsArgs) -> CliResult<()> {
+    let installed = service
+        .skill_manager()
+        .list_skills()
+        .await
+        .map_err(CliError::Service)?;
+
+    let mut conflicts = Vec::new();
+
+    let current_dir = std::env::current_dir()
+        .map_err(|e| CliError::Config(format!("Failed to get current directory: {}", e)))?;
+    let project_file = resolve_project_file(&current_dir);
+    let lock_path = project_lock_path(&project_file.path);
+    if project_file.found && lock_path.exists() {
+        let lock = ProjectSkillsLock::load_from_file(&lock_path)
+            .map_err(|e| CliError::Config(format!("Failed to load skills.lock: {}", e)))?;
+        conflicts.extend(
+            lock.verify_matches_installed(&installed)
+                .into_iter()
+                .map(|m| Conflict {
+                    kind: ConflictKind::Lock,
+                    skill_id: m.skill_id,
+                    reason: m.reason,
+                }),
+        );
+    }
+
+    let versions: HashMap<String, String> = installed
+        .iter()
+        .map(|s| (s.id.to_string(), s.version.clone()))
+        .collect();
+    conflicts.extend(
+        check_peer_dependencies(&service.config().skill_storage_path, &versions)
+            .into_iter()
+            .map(|v| Conflict {
+                kind: ConflictKind::Peer,
+                skill_id: v.skill_id,
+                reason: v.reason,
+            }),
+    );
+
+    if args.json {
+        let output = ConflictsJsonOutput {
+            total: conflicts.len(),
+            conflicts,
+        };
+        let json = serde_json::to_string_pretty(&output)
+            .map_err(|e| CliError::Validation(format!("Failed to serialize output: {}", e)))?;
+        println!("{}", json);
+        return Ok(());
+    }
+
+    if conflicts.is_empty() {
+        println!("No conflicts among {} installed skills.", installed.len());
+        return Ok(());
+    }
+    let mut table = Table::new(["KIND", "SKILL", "REASON"]);
+    for conflict in &conflicts {
+        let kind = match conflict.kind {
+            ConflictKind::Lock => "lock",
+            ConflictKind::Peer => "peer",
+        };
+        table.push_row([kind, conflict.skill_id.as_str(), conflict.reason.as_str()]);
+    }
+    table.sort_by_column(1);
+    print!("{}", table.render());
+    println!();
+    println!("{} conflict(s)", conflicts.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conflicts_args_parse() {
+        let mut map = HashMap::new();
+        assert!(!ConflictsArgs::from_arg_value_map(&map).json);
+        map.insert("json".to_string(), ArgValue::Bool(true));
+        assert!(ConflictsArgs::from_arg_value_map(&map).json);
+    }
+}
@@ -1,7 +1,7 @@
 //! Duplicates command — find semantically duplicate or very similar skills.
 
 use super::helpers::{compute_suggestion, get_file_mtime, get_skill_name};
-use super::AnalysisContext;
+use super::open_analysis_index;
 use crate::commands::common::validate_format_args;
 use crate::error::{CliError, CliResult};
 use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
@@ -9,7 +9,10 @@ use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality}
 use cli_framework::spec::command_tree::CommandSpec;
 use cli_framework::spec::value::ArgValue;
 use fastskill_core::core::analysis::skill_similarity;
-use fastskill_core::OutputFormat;
+use fastskill_core::core::vector_index::{
+    self, IndexedSkill, VectorIndexService, DEFAULT_BATCH_SIZE, LARGE_COLLECTION_THRESHOLD,
+};
+use fastskill_core::{FastSkillService, OutputFormat, ServiceError};
 use serde::Serialize;
 use std::collections::HashMap;
 
@@ -192,12 +195,16 @@ pub(super) struct DuplicatesJsonOutput {
 }
 
 /// Execute the duplicates command
-pub async fn execute_duplicates(ctx: AnalysisContext, args: DuplicatesArgs) -> CliResult<()> {
+///
+/// Collections above [`LARGE_COLLECTION_THRESHOLD`] are compared batch
+/// against batch (see [`stream_pairs`]) instead of being loaded whole.
+pub async fn execute_duplicates(svc: &FastSkillService, args: DuplicatesArgs) -> CliResult<()> {
     let format = validate_format_args(&args.format, args.json)?;
     let use_json = format == OutputFormat::Json;
-    let all_skills = ctx.skills;
+    let Some((index, n)) = open_analysis_index(svc).await? else {
+        return Ok(());
+    };
 
-    let n = all_skills.len();
     let s_floor = severity_floor_value(&args.severity, args.threshold);
     let effective_floor = args.threshold.max(s_floor);
 
@@ -208,53 +215,24 @@ pub async fn execute_duplicates(ctx: AnalysisContext, args: DuplicatesArgs) -> C
         );
     }
 
-    let mut pairs: Vec<DuplicatePair> = Vec::new();
-    for i in 0..n {
-        for j in (i + 1)..n {
-            let sim = skill_similarity(&all_skills[i].embedding, &all_skills[j].embedding);
-            if sim < effective_floor {
-                continue;
-            }
-
-            let (skill_a, skill_b) = if all_skills[i].id <= all_skills[j].id {
-                (&all_skills[i], &all_skills[j])
-            } else {
-                (&all_skills[j], &all_skills[i])
-            };
-
-            let Some(severity) = classify_severity(sim) else {
-                continue;
-            };
-            let suggestion = compute_suggestion(skill_a, skill_b);
-            let modified_at_a = get_file_mtime(&skill_a.skill_path);
-            let modified_at_b = get_file_mtime(&skill_b.skill_path);
-
-            pairs.push(DuplicatePair {
-                severity,
-                similarity: sim,
-                skill_a: DuplicateSkillInfo {
-                    id: skill_a.id.clone(),
-                    name: get_skill_name(&skill_a.frontmatter_json),
-                    modified_at: modified_at_a,
-                },
-                skill_b: DuplicateSkillInfo {
-                    id: skill_b.id.clone(),
-                    name: get_skill_name(&skill_b.frontmatter_json),
-                    modified_at: modified_at_b,
-                },
-                suggestion,
-            });
-        }
-    }
-
-    pairs.sort_by(|a, b| {
-        b.similarity
-            .partial_cmp(&a.similarity)
-            .unwrap_or(std::cmp::Ordering::Equal)
-            .then_with(|| a.skill_a.id.cmp(&b.skill_a.id))
-    });
-
-    pairs.truncate(args.limit);
+    let mut pairs = if n > LARGE_COLLECTION_THRESHOLD {
+        stream_pairs(
+            index.as_ref(),
+            effective_floor,
+            args.limit,
+            DEFAULT_BATCH_SIZE,
+        )
+        .await?
+    } else {
+        let all_skills = index
+            .get_all_skills()
+            .await
+            .map_err(|e| CliError::Validation(format!("Failed to get indexed skills: {}", e)))?;
+        let mut pairs = Vec::new();
+        collect_pairs(&all_skills, &all_skills, true, effective_floor, &mut pairs);
+        pairs
+    };
+    rank_pairs(&mut pairs, args.limit);
 
     if use_json {
         let output = DuplicatesJsonOutput {
@@ -319,6 +297,86 @@ pub async fn execute_duplicates(ctx: AnalysisContext, args: DuplicatesArgs) -> C
     Ok(())
 }
 
+/// Push every pair of `left` × `right` at or above `floor`. With
+/// `same_batch`, `left` and `right` are the same slice and each unordered
+/// pair is visited once.
+fn collect_pairs(
+    left: &[IndexedSkill],
+    right: &[IndexedSkill],
+    same_batch: bool,
+    floor: f32,
+    pairs: &mut Vec<DuplicatePair>,
+) {
+    for (i, a) in left.iter().enumerate() {
+        let candidates = if same_batch {
+            right.get(i + 1..).unwrap_or_default()
+        } else {
+            right
+        };
+        for b in candidates {
+            let sim = skill_similarity(&a.embedding, &b.embedding);
+            if sim < floor {
+                continue;
+            }
+            let Some(severity) = classify_severity(sim) else {
+                continue;
+            };
+            let (skill_a, skill_b) = if a.id <= b.id { (a, b) } else { (b, a) };
+            pairs.push(DuplicatePair {
+                severity,
+                similarity: sim,
+                skill_a: DuplicateSkillInfo {
+                    id: skill_a.id.clone(),
+                    name: get_skill_name(&skill_a.frontmatter_json),
+                    modified_at: get_file_mtime(&skill_a.skill_path),
+                },
+                skill_b: DuplicateSkillInfo {
+                    id: skill_b.id.clone(),
+                    name: get_skill_name(&skill_b.frontmatter_json),
+                    modified_at: get_file_mtime(&skill_b.skill_path),
+                },
+                suggestion: compute_suggestion(skill_a, skill_b),
+            });
+        }
+    }
+}
+
+/// Most similar first, ties broken by id so output is stable; keep `limit`.
+fn rank_pairs(pairs: &mut Vec<DuplicatePair>, limit: usize) {
+    pairs.sort_by(|a, b| {
+        b.similarity
+            .partial_cmp(&a.similarity)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.skill_a.id.cmp(&b.skill_a.id))
+            .then_with(|| a.skill_b.id.cmp(&b.skill_b.id))
+    });
+    pairs.truncate(limit);
+}
+
+/// Compare the index block by block: each batch against itself and every
+/// later batch, holding two batches and the best `limit` pairs at a time.
+async fn stream_pairs(
+    index: &dyn VectorIndexService,
+    floor: f32,
+    limit: usize,
+    batch_size: usize,
+) -> CliResult<Vec<DuplicatePair>> {
+    let read_error =
+        |e: ServiceError| CliError::Validation(format!("Failed to get indexed skills: {}", e));
+    let mut pairs = Vec::new();
+    let mut outer = vector_index::batches(index, None, batch_size);
+    while let Some(left) = outer.next_batch().await.map_err(read_error)? {
+        collect_pairs(&left, &left, true, floor, &mut pairs);
+        let last_id = left.last().map(|skill| skill.id.as_str());
+        let mut inner = vector_index::batches(index, last_id, batch_size);
+        while let Some(right) = inner.next_batch().await.map_err(read_error)? {
+            collect_pairs(&left, &right, false, floor, &mut pairs);
+        }
+        rank_pairs(&mut pairs, limit);
+    }
+    Ok(pairs)
+}
+
 /// Returns the severity floor value for a given filter
 pub(super) fn severity_floor_value(filter: &SeverityFilter, threshold: f32) -> f32 {
     match filter {
@@ -345,6 +403,45 @@ pub(super) fn classify_severity(similarity: f32) -> Option<SeverityLevel> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fastskill_core::VectorIndexServiceImpl;
+
+    #[tokio::test]
+    #[allow(clippy::unwrap_used)]
+    async fn test_stream_pairs_matches_full_scan() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let index = VectorIndexServiceImpl::new(dir.path().join("index.db"));
+        // Two near-identical groups spread over several batches.
+        let skills: Vec<IndexedSkill> = (0..7)
+            .map(|i| IndexedSkill {
+                id: format!("skill-{}", i),
+                skill_path: dir.path().join(format!("skill-{}", i)),
+                frontmatter_json: serde_json::json!({ "name": format!("Skill {}", i) }),
+                embedding: if i % 2 == 0 {
+                    vec![1.0, 0.01 * i as f32, 0.0]
+                } else {
+                    vec![0.0, 0.01 * i as f32, 1.0]
+                },
+                chunk_embeddings: Vec::new(),
+                file_hash: "hash".to_string(),
+                updated_at: chrono::Utc::now(),
+            })
+            .collect();
+        index.add_skills(skills.clone()).await.unwrap();
+
+        let mut full = Vec::new();
+        collect_pairs(&skills, &skills, true, 0.88, &mut full);
+        rank_pairs(&mut full, 5);
+        let streamed = stream_pairs(&index, 0.88, 5, 2).await.unwrap();
+
+        let ids = |pairs: &[DuplicatePair]| {
+            pairs
+                .iter()
+                .map(|p| (p.skill_a.id.clone(), p.skill_b.id.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(full.len(), 5);
+        assert_eq!(ids(&streamed), ids(&full));
+    }
 
     #[test]
     fn test_classify_severity_critical() {
@@ -1,15 +1,17 @@
 //! Diagnostic and analysis commands for skill relationships and quality
 
 pub mod cluster;
+pub mod conflicts;
 pub mod duplicates;
 pub mod helpers;
 pub mod matrix;
 pub use cluster::ClusterArgs;
+pub use conflicts::ConflictsArgs;
 pub use duplicates::DuplicatesArgs;
 pub use matrix::MatrixArgs;
 
 use crate::error::{CliError, CliResult};
-use fastskill_core::core::vector_index::IndexedSkill;
+use fastskill_core::core::vector_index::{IndexedSkill, VectorIndexService};
 use fastskill_core::FastSkillService;
 use std::sync::Arc;
 
@@ -25,27 +27,43 @@ pub enum AnalyzeSubcommand {
     Matrix(MatrixArgs),
     Cluster(ClusterArgs),
     Duplicates(DuplicatesArgs),
+    Conflicts(ConflictsArgs),
 }
 
 pub struct AnalysisContext {
     pub skills: Vec<IndexedSkill>,
     #[allow(dead_code)]
-    pub vector_svc: Arc<dyn fastskill_core::core::vector_index::VectorIndexService>,
+    pub vector_svc: Arc<dyn VectorIndexService>,
 }
 
-pub async fn load_analysis_context(svc: &FastSkillService) -> CliResult<Option<AnalysisContext>> {
+/// Vector index and its size, or `None` (after telling the user why) when
+/// there is nothing to analyze.
+pub async fn open_analysis_index(
+    svc: &FastSkillService,
+) -> CliResult<Option<(Arc<dyn VectorIndexService>, usize)>> {
     let Some(vector_svc) = svc.vector_index_service() else {
         println!("Note: semantic analysis requires an embedding provider. Results may be limited to structural analysis.");
         return Ok(None);
     };
-    let skills = vector_svc
-        .get_all_skills()
+    let total = vector_svc
+        .count_skills()
         .await
         .map_err(|e| CliError::Validation(format!("Failed to get indexed skills: {}", e)))?;
-    if skills.is_empty() {
+    if total == 0 {
         println!("No skills indexed. Run 'fastskill reindex' first.");
         return Ok(None);
     }
+    Ok(Some((vector_svc, total)))
+}
+
+pub async fn load_analysis_context(svc: &FastSkillService) -> CliResult<Option<AnalysisContext>> {
+    let Some((vector_svc, _)) = open_analysis_index(svc).await? else {
+        return Ok(None);
+    };
+    let skills = vector_svc
+        .get_all_skills()
+        .await
+        .map_err(|e| CliError::Validation(format!("Failed to get indexed skills: {}", e)))?;
     Ok(Some(AnalysisContext { skills, vector_svc }))
 }
 
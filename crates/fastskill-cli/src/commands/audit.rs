@@ -0,0 +1,353 @@
+//! Audit command - query and verify the audit log of skill changes
+//!
+//! Reads `<skills dir>/.fastskill/audit.jsonl`, which `fastskill serve` appends
+//! to for every write-route request and the CLI for `add`, `remove`, `update`,
+//! `yank` and `retire`. `--verify` checks the log's hash chain instead of
+//! listing entries.
+
+use crate::error::{CliError, CliResult};
+use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
+use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
+use cli_framework::spec::command_tree::CommandSpec;
+use cli_framework::spec::value::ArgValue;
+use fastskill_core::core::audit::{AuditEntry, AuditFilter, AuditLog};
+use fastskill_core::output::table::{Align, Table};
+use fastskill_core::FastSkillService;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Arguments for `audit`
+#[derive(Debug, Clone, Default)]
+pub struct AuditArgs {
+    /// Only entries from the last N days
+    pub since_days: Option<i64>,
+    /// Exact action or `prefix.` to filter on
+    pub action: Option<String>,
+    /// Only entries by this actor
+    pub actor: Option<String>,
+    /// Only entries for this skill id
+    pub skill: Option<String>,
+    /// Show at most the N most recent entries
+    pub limit: Option<usize>,
+    /// Emit JSON Lines instead of a table
+    pub jsonl: bool,
+    /// Write JSON Lines to this file instead of stdout
+    pub output: Option<PathBuf>,
+    /// Check the hash chain instead of listing entries
+    pub verify: bool,
+    /// Print the verification report as JSON
+    pub json: bool,
+}
+
+impl IntoCommandSpec for AuditArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Query or verify the audit log of skill changes (CLI and server)",
+            syntax: Some("audit [OPTIONS] | audit --verify [--json]"),
+            category: Some("server"),
+            args: vec![
+                ArgSpec {
+                    name: "since-days",
+                    kind: ArgKind::Option,
+                    long: Some("since-days"),
+                    value_type: ArgValueType::Int,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    help: "Only show entries from the last N days",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "action",
+                    kind: ArgKind::Option,
+                    long: Some("action"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    help: "Filter by action (e.g. skill.add), or a prefix ending in '.' (e.g. skill.)",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "actor",
+                    kind: ArgKind::Option,
+                    long: Some("actor"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    help: "Filter by actor (user:<login> for the CLI, key:<name> for an API key, a client profile name, or 'anonymous')",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "skill",
+                    kind: ArgKind::Option,
+                    long: Some("skill"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    help: "Only show entries for this skill id",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "limit",
+                    kind: ArgKind::Option,
+                    long: Some("limit"),
+                    value_type: ArgValueType::Int,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    help: "Show at most the N most recent entries",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "jsonl",
+                    kind: ArgKind::Flag,
+                    long: Some("jsonl"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Output JSON Lines (one entry per line) instead of a table",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "output",
+                    kind: ArgKind::Option,
+                    long: Some("output"),
+                    short: Some('o'),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    help: "Export matching entries as JSON Lines to this file",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "verify",
+                    kind: ArgKind::Flag,
+                    long: Some("verify"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Check the hash chain; exits non-zero if entries were altered, removed or reordered",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "json",
+                    kind: ArgKind::Flag,
+                    long: Some("json"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "With --verify, print the report as JSON",
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for AuditArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        let string = |key: &str| match map.get(key) {
+            Some(ArgValue::Str(s)) => Some(s.clone()),
+            _ => None,
+        };
+        let flag = |key: &str| matches!(map.get(key), Some(ArgValue::Bool(true)));
+        Self {
+            since_days: match map.get("since-days") {
+                Some(ArgValue::Int(n)) => Some(*n),
+                _ => None,
+            },
+            action: string("action"),
+            actor: string("actor"),
+            skill: string("skill"),
+            limit: match map.get("limit") {
+                Some(ArgValue::Int(n)) if *n >= 0 => Some(*n as usize),
+                _ => None,
+            },
+            jsonl: flag("jsonl"),
+            output: string("output").map(PathBuf::from),
+            verify: flag("verify"),
+            json: flag("json"),
+        }
+    }
+}
+
+fn to_jsonl(entries: &[AuditEntry]) -> CliResult<String> {
+    let mut out = String::new();
+    for entry in entries {
+        let line = serde_json::to_string(entry)
+            .map_err(|e| CliError::Config(format!("Failed to encode audit entry: {}", e)))?;
+        out.push_str(&line);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+fn print_table(entries: &[AuditEntry]) {
+    let mut table = Table::new([
+        "TIME (UTC)",
+        "ACTOR",
+        "ACTION",
+        "TARGET",
+        "VERSION",
+        "STATUS",
+        "OUTCOME",
+    ])
+    .max_width(3, 40)
+    .align(5, Align::Right);
+    for entry in entries {
+        let status = if entry.method == AuditEntry::CLI_METHOD {
+            "cli".to_string()
+        } else {
+            entry.status.to_string()
+        };
+        table.push_row([
+            entry.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+            entry.actor.clone(),
+            entry.action.clone(),
+            entry.target.clone().unwrap_or_else(|| "-".to_string()),
+            entry.version.clone().unwrap_or_else(|| "-".to_string()),
+            status,
+            entry.outcome().to_string(),
+        ]);
+    }
+    print!("{}", table.render());
+}
+
+fn verify(log: &AuditLog, json: bool) -> CliResult<()> {
+    let report = log.verify()?;
+    if json {
+        let out = serde_json::to_string_pretty(&report)
+            .map_err(|e| CliError::Config(format!("Failed to encode report: {}", e)))?;
+        println!("{}", out);
+    } else {
+        for problem in &report.problems {
+            println!(
+                "{}:{}: {}",
+                log.path().display(),
+                problem.line,
+                problem.message
+            );
+        }
+        if report.is_intact() {
+            println!(
+                "Audit log intact: {} entries ({} written before hash chaining)",
+                report.entries, report.unchained
+            );
+        }
+        if let Some(head) = &report.head {
+            println!("Head: {}", head);
+        }
+    }
+    if !report.is_intact() {
+        return Err(CliError::Validation(format!(
+            "Audit log failed verification: {} problem(s) in {}",
+            report.problems.len(),
+            log.path().display()
+        )));
+    }
+    Ok(())
+}
+
+pub async fn execute_audit(service: &FastSkillService, args: AuditArgs) -> CliResult<()> {
+    let log = AuditLog::new(AuditLog::default_path(&service.config().skill_storage_path));
+    if args.verify {
+        return verify(&log, args.json);
+    }
+    if args.json {
+        return Err(CliError::Validation(
+            "--json goes with --verify; use --jsonl to list entries as JSON".to_string(),
+        ));
+    }
+    if let Some(days) = args.since_days {
+        if days < 0 {
+            return Err(CliError::Validation(format!(
+                "--since-days must be zero or greater, got {}",
+                days
+            )));
+        }
+    }
+
+    let filter = AuditFilter {
+        since: args
+            .since_days
+            .map(|days| chrono::Utc::now() - chrono::Duration::days(days)),
+        action: args.action,
+        actor: args.actor,
+        target: args.skill,
+    };
+    let mut entries = log.read(&filter)?;
+    if let Some(limit) = args.limit {
+        let skip = entries.len().saturating_sub(limit);
+        entries.drain(..skip);
+    }
+
+    if let Some(path) = &args.output {
+        std::fs::write(path, to_jsonl(&entries)?)?;
+        println!(
+            "Exported {} audit entries to {}",
+            entries.len(),
+            path.display()
+        );
+        return Ok(());
+    }
+    if args.jsonl {
+        print!("{}", to_jsonl(&entries)?);
+        return Ok(());
+    }
+    if entries.is_empty() {
+        println!("No audit entries in {}", log.path().display());
+        return Ok(());
+    }
+    print_table(&entries);
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use fastskill_core::core::audit::AuditDetails;
+
+    #[test]
+    fn test_audit_args_parse() {
+        let mut map = HashMap::new();
+        map.insert("skill".to_string(), ArgValue::Str("pdf".to_string()));
+        map.insert("verify".to_string(), ArgValue::Bool(true));
+        map.insert("limit".to_string(), ArgValue::Int(-1));
+        let args = AuditArgs::from_arg_value_map(&map);
+        assert_eq!(args.skill.as_deref(), Some("pdf"));
+        assert!(args.verify);
+        assert!(args.limit.is_none());
+        assert!(!args.json);
+    }
+
+    #[tokio::test]
+    async fn test_verify_fails_on_tampered_log() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = fastskill_core::ServiceConfig {
+            skill_storage_path: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        let service = FastSkillService::new(config).await.unwrap();
+        let verify_args = AuditArgs {
+            verify: true,
+            ..Default::default()
+        };
+
+        let path = AuditLog::default_path(temp_dir.path());
+        let log = AuditLog::new(path.clone());
+        for version in ["1.0.0", "1.1.0"] {
+            let entry =
+                AuditEntry::cli("user:ana", "skill.add", "add", true).with_details(AuditDetails {
+                    target: Some("pdf".to_string()),
+                    version: Some(version.to_string()),
+                    source: None,
+                });
+            log.append(&entry).unwrap();
+        }
+        execute_audit(&service, verify_args.clone()).await.unwrap();
+
+        let tampered = std::fs::read_to_string(&path)
+            .unwrap()
+            .replace("1.0.0", "0.9.0");
+        std::fs::write(&path, tampered).unwrap();
+        let err = execute_audit(&service, verify_args).await.unwrap_err();
+        assert!(matches!(err, CliError::Validation(_)), "{}", err);
+    }
+}
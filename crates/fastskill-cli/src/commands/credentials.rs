@@ -0,0 +1,157 @@
+//! Credentials command - store registry tokens and API keys outside env/config
+//!
+//! Secrets are stored under the env var name the repository or registry auth
+//! config already uses (e.g. `GITHUB_TOKEN`); when that variable is unset,
+//! fastskill reads the stored value instead.
+
+use crate::error::{CliError, CliResult};
+use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
+use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
+use cli_framework::spec::command_tree::CommandSpec;
+use cli_framework::spec::value::ArgValue;
+use fastskill_core::core::credentials::default_store;
+use std::collections::HashMap;
+use std::io::{BufRead, IsTerminal};
+
+/// Arguments for `credentials set`
+#[derive(Debug, Clone)]
+pub struct CredentialsSetArgs {
+    /// Env var name the secret is stored under
+    pub name: String,
+}
+
+/// Arguments for `credentials remove`
+#[derive(Debug, Clone)]
+pub struct CredentialsRemoveArgs {
+    /// Env var name to remove
+    pub name: String,
+}
+
+fn name_arg(help: &'static str) -> ArgSpec {
+    ArgSpec {
+        name: "name",
+        kind: ArgKind::Positional,
+        value_type: ArgValueType::String,
+        cardinality: Cardinality::Required,
+        help,
+        ..Default::default()
+    }
+}
+
+fn name_from_map(map: &HashMap<String, ArgValue>) -> String {
+    match map.get("name") {
+        Some(ArgValue::Str(s)) => s.clone(),
+        _ => String::new(),
+    }
+}
+
+impl IntoCommandSpec for CredentialsSetArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Store a secret in the OS keyring (or encrypted file)",
+            syntax: Some("credentials set <NAME>"),
+            category: Some("repositories"),
+            args: vec![name_arg(
+                "Env var name the secret replaces, e.g. GITHUB_TOKEN (value is read from a prompt or stdin)",
+            )],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for CredentialsSetArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        Self {
+            name: name_from_map(map),
+        }
+    }
+}
+
+impl IntoCommandSpec for CredentialsRemoveArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Remove a stored secret",
+            syntax: Some("credentials remove <NAME>"),
+            category: Some("repositories"),
+            args: vec![name_arg("Env var name of the secret to remove")],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for CredentialsRemoveArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        Self {
+            name: name_from_map(map),
+        }
+    }
+}
+
+fn validate_name(name: &str) -> CliResult<()> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(CliError::Validation(format!(
+            "Credential name must be an env var name (letters, digits, '_'), got '{}'",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Read the secret without echoing it; piped input is read from stdin so the
+/// value never appears in shell history or the process list.
+fn read_secret(name: &str) -> CliResult<String> {
+    let secret = if std::io::stdin().is_terminal() {
+        inquire::Password::new(&format!("Value for {}:", name))
+            .without_confirmation()
+            .prompt()
+            .map_err(|e| CliError::Validation(format!("Failed to read secret: {}", e)))?
+    } else {
+        let mut line = String::new();
+        std::io::stdin().lock().read_line(&mut line)?;
+        line
+    };
+    let secret = secret.trim().to_string();
+    if secret.is_empty() {
+        return Err(CliError::Validation("Secret must not be empty".to_string()));
+    }
+    Ok(secret)
+}
+
+pub async fn execute_credentials_set(args: CredentialsSetArgs) -> CliResult<()> {
+    validate_name(&args.name)?;
+    let store = default_store()?;
+    let secret = read_secret(&args.name)?;
+    store.set(&args.name, &secret)?;
+    println!("Stored {} in {}", args.name, store.name());
+    if std::env::var(&args.name).is_ok() {
+        println!(
+            "Note: {} is also set in the environment, which takes precedence",
+            args.name
+        );
+    }
+    Ok(())
+}
+
+pub async fn execute_credentials_remove(args: CredentialsRemoveArgs) -> CliResult<()> {
+    validate_name(&args.name)?;
+    let store = default_store()?;
+    if store.delete(&args.name)? {
+        println!("Removed {} from {}", args.name, store.name());
+    } else {
+        println!("No stored credential named {}", args.name);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("GITHUB_TOKEN").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("bad name").is_err());
+        assert!(validate_name("../etc").is_err());
+    }
+}
@@ -0,0 +1,75 @@
+//! Diagnostics command - write a bundle to attach to bug reports
+//!
+//! See `crate::diagnostics` for what a bundle contains.
+
+use crate::diagnostics;
+use crate::error::CliResult;
+use crate::utils::messages;
+use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
+use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
+use cli_framework::spec::command_tree::CommandSpec;
+use cli_framework::spec::value::ArgValue;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Arguments for `diagnostics collect`
+#[derive(Debug, Clone)]
+pub struct DiagnosticsCollectArgs {
+    /// Bundle path; `-` prints to stdout
+    pub output: Option<PathBuf>,
+}
+
+impl IntoCommandSpec for DiagnosticsCollectArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Write a diagnostics bundle (version, platform, redacted config, logs) for bug reports",
+            syntax: Some("diagnostics collect [--output <PATH>]"),
+            category: Some("setup"),
+            args: vec![ArgSpec {
+                name: "output",
+                kind: ArgKind::Option,
+                short: Some('o'),
+                long: Some("output"),
+                value_type: ArgValueType::String,
+                cardinality: Cardinality::Optional,
+                help: "File to write (default: ./fastskill-diagnostics-<timestamp>.txt; - for stdout)",
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for DiagnosticsCollectArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        Self {
+            output: match map.get("output") {
+                Some(ArgValue::Str(s)) => Some(PathBuf::from(s)),
+                _ => None,
+            },
+        }
+    }
+}
+
+pub async fn execute_diagnostics_collect(args: DiagnosticsCollectArgs) -> CliResult<()> {
+    let path = args
+        .output
+        .unwrap_or_else(|| PathBuf::from(diagnostics::default_file_name()));
+    if path.as_os_str() == "-" {
+        print!("{}", diagnostics::render_bundle(None, None));
+        return Ok(());
+    }
+    diagnostics::write_bundle(&path, None, None)?;
+    messages::status!(
+        "{}",
+        messages::ok(&format!("Diagnostics written to {}", path.display()))
+    );
+    messages::status!(
+        "{}",
+        messages::info(&format!(
+            "Review it, then attach it to a bug report at {}",
+            diagnostics::ISSUES_URL
+        ))
+    );
+    Ok(())
+}
@@ -0,0 +1,427 @@
+//! Fixtures command - generate synthetic skill sets for benchmarking
+//!
+//! Hidden, developer-facing: `fastskill fixtures generate --count 1000` writes
+//! realistic skill directories (varied frontmatter, body sizes, optional
+//! scripts and references, some non-ASCII text) so scaling issues can be
+//! reproduced. Output depends only on `--count` and `--seed`; the generator is
+//! a fixed SplitMix64 so the same seed yields byte-identical skills on every
+//! platform and release.
+
+use crate::error::{CliError, CliResult};
+use crate::utils::messages;
+use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
+use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
+use cli_framework::spec::command_tree::CommandSpec;
+use cli_framework::spec::value::ArgValue;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_COUNT: usize = 100;
+const DEFAULT_SEED: u64 = 42;
+
+/// Arguments for `fixtures generate`
+#[derive(Debug, Clone)]
+pub struct FixturesGenerateArgs {
+    pub count: usize,
+    pub output: PathBuf,
+    pub seed: u64,
+    /// Allow writing into a non-empty directory
+    pub force: bool,
+}
+
+impl IntoCommandSpec for FixturesGenerateArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Generate a deterministic synthetic skill set for benchmarks",
+            syntax: Some("fixtures generate [--count <N>] [--output <DIR>] [--seed <N>] [--force]"),
+            category: Some("quality"),
+            args: vec![
+                ArgSpec {
+                    name: "count",
+                    kind: ArgKind::Option,
+                    long: Some("count"),
+                    short: Some('n'),
+                    value_type: ArgValueType::Int,
+                    cardinality: Cardinality::Optional,
+                    default: Some(ArgValue::Int(DEFAULT_COUNT as i64)),
+                    help: "Number of skills to generate",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "output",
+                    kind: ArgKind::Option,
+                    long: Some("output"),
+                    short: Some('o'),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    help: "Directory to write skills into (default: ./fixtures/skills)",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "seed",
+                    kind: ArgKind::Option,
+                    long: Some("seed"),
+                    value_type: ArgValueType::Int,
+                    cardinality: Cardinality::Optional,
+                    default: Some(ArgValue::Int(DEFAULT_SEED as i64)),
+                    help: "Seed; the same seed and count always produce the same files",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "force",
+                    kind: ArgKind::Flag,
+                    long: Some("force"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Write even if the output directory is not empty",
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for FixturesGenerateArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        let int = |name: &str| match map.get(name) {
+            Some(ArgValue::Int(n)) => Some(*n),
+            _ => None,
+        };
+        Self {
+            count: int("count").map_or(DEFAULT_COUNT, |n| n.max(0) as usize),
+            output: match map.get("output") {
+                Some(ArgValue::Str(s)) => PathBuf::from(s),
+                _ => PathBuf::from("fixtures/skills"),
+            },
+            seed: int("seed").map_or(DEFAULT_SEED, |n| n as u64),
+            force: matches!(map.get("force"), Some(ArgValue::Bool(true))),
+        }
+    }
+}
+
+/// SplitMix64: tiny, fast and stable, unlike `rand`'s default generators
+/// whose streams may change between releases.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0..n` (`n > 0`).
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// True with probability `percent`/100.
+    fn chance(&mut self, percent: usize) -> bool {
+        self.below(100) < percent
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len())]
+    }
+}
+
+const ACTIONS: &[&str] = &[
+    "extract",
+    "convert",
+    "summarize",
+    "validate",
+    "generate",
+    "analyze",
+    "deploy",
+    "monitor",
+    "search",
+    "clean",
+    "translate",
+    "review",
+];
+
+const DOMAINS: &[&str] = &[
+    "pdf",
+    "spreadsheet",
+    "web",
+    "image",
+    "audio",
+    "database",
+    "git",
+    "kubernetes",
+    "email",
+    "calendar",
+    "markdown",
+    "csv",
+    "json",
+    "finance",
+    "security",
+    "testing",
+    "docs",
+    "slack",
+    "api",
+    "logs",
+];
+
+const OBJECTS: &[&str] = &[
+    "tables",
+    "reports",
+    "invoices",
+    "pull requests",
+    "release notes",
+    "metrics",
+    "schemas",
+    "screenshots",
+    "transcripts",
+    "configuration files",
+    "customer records",
+    "dashboards",
+];
+
+const PURPOSES: &[&str] = &[
+    "for audits",
+    "before publishing",
+    "in CI pipelines",
+    "for weekly summaries",
+    "when onboarding new teammates",
+    "to catch regressions early",
+    "for compliance reviews",
+    "during incident response",
+];
+
+/// Occasional non-ASCII phrases, to exercise width-aware output and UTF-8
+/// handling in search and indexing.
+const UNICODE_PHRASES: &[&str] = &[
+    "données structurées",
+    "数据处理",
+    "Übersicht der Änderungen",
+    "análisis rápido",
+    "отчёт о продажах",
+];
+
+const TOOLS: &[&str] = &["Read", "Write", "Bash", "Grep", "WebFetch"];
+
+const LICENSES: &[&str] = &["MIT", "Apache-2.0", "BSD-3-Clause", "Proprietary"];
+
+const AUTHORS: &[&str] = &[
+    "Data Platform Team",
+    "DevEx Guild",
+    "Security Engineering",
+    "Docs Working Group",
+];
+
+/// One generated skill: relative path → file content.
+struct Fixture {
+    id: String,
+    files: Vec<(PathBuf, String)>,
+}
+
+fn sentence(rng: &mut Rng, domain: &str) -> String {
+    format!(
+        "{} {} {} {}.",
+        capitalize(rng.pick(ACTIONS)),
+        domain,
+        rng.pick(OBJECTS),
+        rng.pick(PURPOSES)
+    )
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Body length in sections: mostly small, some medium, a few very large.
+fn section_count(rng: &mut Rng) -> usize {
+    match rng.below(100) {
+        0..=59 => 2 + rng.below(3),
+        60..=89 => 10 + rng.below(10),
+        _ => 80 + rng.below(40),
+    }
+}
+
+fn generate_one(rng: &mut Rng, index: usize) -> Fixture {
+    let action = rng.pick(ACTIONS);
+    let domain = rng.pick(DOMAINS);
+    let id = format!("{}-{}-{:05}", action, domain, index);
+
+    let mut description = format!(
+        "{} {} {} {}.",
+        capitalize(action),
+        domain,
+        rng.pick(OBJECTS),
+        rng.pick(PURPOSES)
+    );
+    if rng.chance(5) {
+        description.push_str(&format!(" Supports {}.", rng.pick(UNICODE_PHRASES)));
+    }
+
+    let mut frontmatter = format!(
+        "---\nname: {}\ndescription: \"{}\"\nversion: {}.{}.{}\n",
+        id,
+        description,
+        1 + rng.below(3),
+        rng.below(10),
+        rng.below(20)
+    );
+    if rng.chance(50) {
+        frontmatter.push_str(&format!("author: {}\n", rng.pick(AUTHORS)));
+    }
+    if rng.chance(30) {
+        frontmatter.push_str(&format!("license: {}\n", rng.pick(LICENSES)));
+    }
+    if rng.chance(10) {
+        frontmatter.push_str("compatibility: Requires Python 3.10+\n");
+    }
+    if rng.chance(20) {
+        let first = rng.pick(TOOLS);
+        let second = rng.pick(TOOLS);
+        frontmatter.push_str(&format!("allowed-tools: {} {}\n", first, second));
+    }
+    if rng.chance(40) {
+        frontmatter.push_str(&format!(
+            "metadata:\n  category: {}\n  tier: \"{}\"\n",
+            domain,
+            1 + rng.below(3)
+        ));
+    }
+    frontmatter.push_str("---\n");
+
+    let mut body = format!("\n# {}\n\n{}\n", id, description);
+    for section in 0..section_count(rng) {
+        body.push_str(&format!("\n## Step {}\n\n", section + 1));
+        for _ in 0..3 + rng.below(5) {
+            body.push_str(&sentence(rng, domain));
+            body.push(' ');
+        }
+        body.push('\n');
+    }
+
+    let mut files = vec![(PathBuf::from("SKILL.md"), frontmatter + &body)];
+    if rng.chance(25) {
+        files.push((
+            PathBuf::from("scripts/run.sh"),
+            format!(
+                "#!/usr/bin/env bash\nset -euo pipefail\necho \"{} $*\"\n",
+                id
+            ),
+        ));
+    }
+    if rng.chance(20) {
+        files.push((
+            PathBuf::from("references/notes.md"),
+            format!("# Notes for {}\n\n{}\n", id, sentence(rng, domain)),
+        ));
+    }
+    Fixture { id, files }
+}
+
+/// Generate `count` skills from `seed`, in index order.
+fn generate(count: usize, seed: u64) -> Vec<Fixture> {
+    let mut rng = Rng(seed);
+    (0..count).map(|i| generate_one(&mut rng, i)).collect()
+}
+
+fn write_fixtures(root: &Path, fixtures: &[Fixture]) -> CliResult<()> {
+    for fixture in fixtures {
+        let dir = root.join(&fixture.id);
+        for (relative, content) in &fixture.files {
+            let path = dir.join(relative);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, content)?;
+        }
+    }
+    Ok(())
+}
+
+pub async fn execute_fixtures_generate(args: FixturesGenerateArgs) -> CliResult<()> {
+    if args.count == 0 {
+        return Err(CliError::Validation(
+            "--count must be at least 1".to_string(),
+        ));
+    }
+    let not_empty = std::fs::read_dir(&args.output)
+        .map(|mut entries| entries.next().is_some())
+        .unwrap_or(false);
+    if not_empty && !args.force {
+        return Err(CliError::Validation(format!(
+            "{} is not empty; choose another --output or pass --force",
+            args.output.display()
+        )));
+    }
+
+    let fixtures = generate(args.count, args.seed);
+    write_fixtures(&args.output, &fixtures)?;
+    messages::status!(
+        "{}",
+        messages::ok(&format!(
+            "Generated {} skills in {} (seed {})",
+            fixtures.len(),
+            args.output.display(),
+            args.seed
+        ))
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use fastskill_core::parse_yaml_frontmatter;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_generation_is_deterministic() {
+        let render = |fixtures: Vec<Fixture>| {
+            fixtures
+                .into_iter()
+                .map(|f| (f.id, f.files))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(render(generate(50, 7)), render(generate(50, 7)));
+        assert_ne!(render(generate(50, 7)), render(generate(50, 8)));
+    }
+
+    #[tokio::test]
+    async fn test_generated_skills_are_valid() {
+        let dir = TempDir::new().unwrap();
+        let output = dir.path().join("skills");
+        execute_fixtures_generate(FixturesGenerateArgs {
+            count: 40,
+            output: output.clone(),
+            seed: DEFAULT_SEED,
+            force: false,
+        })
+        .await
+        .unwrap();
+
+        let mut ids = std::collections::HashSet::new();
+        for entry in std::fs::read_dir(&output).unwrap() {
+            let entry = entry.unwrap();
+            let content = std::fs::read_to_string(entry.path().join("SKILL.md")).unwrap();
+            let frontmatter = parse_yaml_frontmatter(&content).unwrap();
+            assert_eq!(frontmatter.name, entry.file_name().to_string_lossy());
+            assert!(!frontmatter.description.is_empty());
+            ids.insert(frontmatter.name);
+        }
+        assert_eq!(ids.len(), 40);
+
+        // A second run into the same directory needs --force.
+        let again = FixturesGenerateArgs {
+            count: 1,
+            output,
+            seed: DEFAULT_SEED,
+            force: false,
+        };
+        assert!(execute_fixtures_generate(again).await.is_err());
+    }
+}
@@ -0,0 +1,256 @@
+//! Gc command - find and delete storage no locked skill uses
+//!
+//! Lists skill directories in the skills dir that `skills.lock` (or
+//! `global-skills.lock` with `--global`) does not pin, vector index rows for
+//! skills that are gone, stale partial downloads and, with `--build-cache`,
+//! build cache entries for unlocked skills. Nothing is deleted without
+//! `--prune`.
+
+use crate::error::{manifest_required_message, CliError, CliResult};
+use crate::utils::change_plan::format_size;
+use crate::utils::messages;
+use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
+use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
+use cli_framework::spec::command_tree::CommandSpec;
+use cli_framework::spec::value::ArgValue;
+use fastskill_core::core::gc::{Garbage, GarbageCollector};
+use fastskill_core::core::lock::{
+    global_lock_path, project_lock_path, GlobalSkillsLock, ProjectSkillsLock,
+};
+use fastskill_core::core::project::resolve_project_file;
+use fastskill_core::output::table::{Align, Table};
+use fastskill_core::FastSkillService;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+/// Arguments for `gc`
+#[derive(Debug, Clone, Default)]
+pub struct GcArgs {
+    /// Delete what was found instead of only reporting it
+    pub prune: bool,
+    /// Also collect entries of this build cache file
+    pub build_cache: Option<PathBuf>,
+    /// Print the findings as JSON
+    pub json: bool,
+}
+
+impl IntoCommandSpec for GcArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Report (and with --prune delete) skill storage no locked skill uses",
+            syntax: Some("gc [--prune] [--build-cache FILE] [--json]"),
+            category: Some("packages"),
+            args: vec![
+                ArgSpec {
+                    name: "prune",
+                    kind: ArgKind::Flag,
+                    long: Some("prune"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Delete the unreferenced items instead of only listing them",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "build-cache",
+                    kind: ArgKind::Option,
+                    long: Some("build-cache"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    help: "Also collect entries (and artifacts) of this build cache file for skills not in the lock file",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "json",
+                    kind: ArgKind::Flag,
+                    long: Some("json"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Print the findings as JSON",
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for GcArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        Self {
+            prune: matches!(map.get("prune"), Some(ArgValue::Bool(true))),
+            build_cache: match map.get("build-cache") {
+                Some(ArgValue::Str(s)) => Some(PathBuf::from(s)),
+                _ => None,
+            },
+            json: matches!(map.get("json"), Some(ArgValue::Bool(true))),
+        }
+    }
+}
+
+/// Ids pinned by the lock file for this scope. A missing lock file is an
+/// error: without it every installed skill would look unreferenced.
+fn locked_ids(global: bool) -> CliResult<HashSet<String>> {
+    if global {
+        let path = global_lock_path()
+            .map_err(|e| CliError::Config(format!("Failed to resolve global lock path: {}", e)))?;
+        if !path.exists() {
+            return Err(CliError::Config(format!(
+                "{} not found; nothing to compare the global skills dir against",
+                path.display()
+            )));
+        }
+        let lock = GlobalSkillsLock::load_from_file(&path)
+            .map_err(|e| CliError::Config(format!("Failed to load global lock file: {}", e)))?;
+        return Ok(lock.skills.into_iter().map(|entry| entry.id).collect());
+    }
+
+    let current_dir = std::env::current_dir()
+        .map_err(|e| CliError::Config(format!("Failed to get current directory: {}", e)))?;
+    let project_file = resolve_project_file(&current_dir);
+    if !project_file.found {
+        return Err(CliError::Config(manifest_required_message().to_string()));
+    }
+    let lock_path = project_lock_path(&project_file.path);
+    if !lock_path.exists() {
+        return Err(CliError::Config(
+            "skills.lock not found. Run 'fastskill install' first to create it.".to_string(),
+        ));
+    }
+    let lock = ProjectSkillsLock::load_from_file(&lock_path)
+        .map_err(|e| CliError::Config(format!("Failed to load lock file: {}", e)))?;
+    Ok(lock.skills.into_iter().map(|entry| entry.id).collect())
+}
+
+fn print_table(garbage: &[Garbage]) {
+    let mut table = Table::new(["KIND", "NAME", "SIZE"]).align(2, Align::Right);
+    for item in garbage {
+        let size = if item.path.is_some() {
+            format_size(item.bytes)
+        } else {
+            "-".to_string()
+        };
+        table.push_row([item.kind.to_string(), item.name.clone(), size]);
+    }
+    print!("{}", table.render());
+}
+
+/// Scan (and with `--prune` delete) against `referenced`; returns what was
+/// found.
+async fn collect(
+    service: &FastSkillService,
+    referenced: HashSet<String>,
+    args: &GcArgs,
+) -> CliResult<Vec<Garbage>> {
+    let skills_dir = service.config().skill_storage_path.clone();
+    let mut collector = GarbageCollector::new(skills_dir, referenced);
+    if let Some(index) = service.vector_index_service() {
+        collector = collector.with_index(index);
+    }
+    if let Some(path) = &args.build_cache {
+        collector = collector.with_build_cache(path.clone());
+    }
+    let garbage = collector.scan().await?;
+    let reclaimable: u64 = garbage.iter().map(|item| item.bytes).sum();
+
+    if args.json {
+        let out = serde_json::json!({
+            "items": garbage,
+            "reclaimable_bytes": reclaimable,
+            "pruned": args.prune,
+        });
+        let out = serde_json::to_string_pretty(&out)
+            .map_err(|e| CliError::Config(format!("Failed to encode gc report: {}", e)))?;
+        println!("{}", out);
+    } else if garbage.is_empty() {
+        println!("{}", messages::ok("Nothing to collect"));
+        return Ok(garbage);
+    } else {
+        print_table(&garbage);
+    }
+
+    if args.prune {
+        let freed = collector.prune(&garbage).await?;
+        if !args.json {
+            println!(
+                "{}",
+                messages::ok(&format!(
+                    "Removed {} items, freed {}",
+                    garbage.len(),
+                    format_size(freed)
+                ))
+            );
+        }
+    } else if !args.json {
+        messages::status!(
+            "{} items, {} reclaimable. Run 'fastskill gc --prune' to delete them.",
+            garbage.len(),
+            format_size(reclaimable)
+        );
+    }
+    Ok(garbage)
+}
+
+pub async fn execute_gc(service: &FastSkillService, args: GcArgs, global: bool) -> CliResult<()> {
+    let referenced = locked_ids(global)?;
+    collect(service, referenced, &args).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_gc_args_parse() {
+        let mut map = HashMap::new();
+        map.insert("prune".to_string(), ArgValue::Bool(true));
+        map.insert(
+            "build-cache".to_string(),
+            ArgValue::Str(".fastskill/build-cache.json".to_string()),
+        );
+        let args = GcArgs::from_arg_value_map(&map);
+        assert!(args.prune);
+        assert!(!args.json);
+        assert_eq!(
+            args.build_cache,
+            Some(PathBuf::from(".fastskill/build-cache.json"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_collect_reports_then_prunes() {
+        let temp_dir = TempDir::new().unwrap();
+        for id in ["kept", "stale"] {
+            let dir = temp_dir.path().join(id);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("SKILL.md"), format!("---\nname: {id}\n---\n")).unwrap();
+        }
+        let config = fastskill_core::ServiceConfig {
+            skill_storage_path: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        let service = FastSkillService::new(config).await.unwrap();
+        let referenced: HashSet<String> = ["kept".to_string()].into();
+
+        let garbage = collect(&service, referenced.clone(), &GcArgs::default())
+            .await
+            .unwrap();
+        assert_eq!(garbage.len(), 1);
+        assert!(temp_dir.path().join("stale").exists());
+
+        let args = GcArgs {
+            prune: true,
+            ..Default::default()
+        };
+        collect(&service, referenced.clone(), &args).await.unwrap();
+        assert!(!temp_dir.path().join("stale").exists());
+        assert!(temp_dir.path().join("kept").join("SKILL.md").is_file());
+        assert!(collect(&service, referenced, &GcArgs::default())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
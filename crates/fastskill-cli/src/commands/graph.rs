@@ -0,0 +1,125 @@
+//! Graph command - print the project's skill dependency graph
+//!
+//! Renders the same graph as `GET /api/v1/graph` (from `skills.lock` plus the
+//! installed skills) as an indented tree, Graphviz DOT or Mermaid, so it can
+//! be embedded in generated docs.
+
+use crate::error::{manifest_required_message, CliError, CliResult};
+use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
+use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
+use cli_framework::spec::command_tree::CommandSpec;
+use cli_framework::spec::value::ArgValue;
+use fastskill_core::core::lock::{project_lock_path, ProjectSkillsLock};
+use fastskill_core::core::project::resolve_project_file;
+use fastskill_core::http::handlers::graph::build_graph;
+use fastskill_core::output::graph::{render_graph, GraphFormat};
+use fastskill_core::FastSkillService;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Arguments for `graph`
+#[derive(Debug, Clone)]
+pub struct GraphArgs {
+    /// `text`, `dot` or `mermaid`
+    pub format: Option<String>,
+    /// Write the graph to this file instead of stdout
+    pub output: Option<PathBuf>,
+}
+
+impl IntoCommandSpec for GraphArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Show the skill dependency graph as a tree, Graphviz DOT or Mermaid",
+            syntax: Some("graph [--format text|dot|mermaid] [-o FILE]"),
+            category: Some("packages"),
+            args: vec![
+                ArgSpec {
+                    name: "format",
+                    kind: ArgKind::Option,
+                    long: Some("format"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    help: "Output format: text, dot, mermaid (default: text)",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "output",
+                    kind: ArgKind::Option,
+                    long: Some("output"),
+                    short: Some('o'),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    help: "Write the graph to this file instead of stdout",
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for GraphArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        let string = |key: &str| match map.get(key) {
+            Some(ArgValue::Str(s)) => Some(s.clone()),
+            _ => None,
+        };
+        Self {
+            format: string("format"),
+            output: string("output").map(PathBuf::from),
+        }
+    }
+}
+
+pub async fn execute_graph(service: &FastSkillService, args: GraphArgs) -> CliResult<()> {
+    let format = match &args.format {
+        Some(format) => format
+            .parse::<GraphFormat>()
+            .map_err(CliError::Validation)?,
+        None => GraphFormat::default(),
+    };
+
+    let current_dir = std::env::current_dir()
+        .map_err(|e| CliError::Config(format!("Failed to get current directory: {}", e)))?;
+    let project_file = resolve_project_file(&current_dir);
+    if !project_file.found {
+        return Err(CliError::Config(manifest_required_message().to_string()));
+    }
+    let lock_path = project_lock_path(&project_file.path);
+    let lock = if lock_path.exists() {
+        Some(
+            ProjectSkillsLock::load_from_file(&lock_path)
+                .map_err(|e| CliError::Config(format!("Failed to load skills.lock: {}", e)))?,
+        )
+    } else {
+        None
+    };
+    let installed = service.skill_manager().list_skills().await?;
+
+    let rendered = render_graph(&build_graph(lock.as_ref(), &installed), format);
+    match &args.output {
+        Some(path) => {
+            std::fs::write(path, rendered)?;
+            println!("Wrote {} dependency graph to {}", format, path.display());
+        }
+        None => print!("{}", rendered),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graph_args_parse() {
+        let mut map = HashMap::new();
+        map.insert("format".to_string(), ArgValue::Str("mermaid".to_string()));
+        map.insert("output".to_string(), ArgValue::Str("deps.mmd".to_string()));
+        let args = GraphArgs::from_arg_value_map(&map);
+        assert_eq!(args.format.as_deref(), Some("mermaid"));
+        assert_eq!(args.output, Some(PathBuf::from("deps.mmd")));
+    }
+}
@@ -4,6 +4,11 @@
 //! - **Skill context**: In skill directory - creates [metadata] section for skill authoring
 //! - **Project context**: At project root - creates [dependencies] section for managing skills
 //! - **SKILL.md only**: When skill follows standard without extra config - skill-project.toml is optional
+//!
+//! With `--template <name>` the skill directory itself (SKILL.md, scripts/,
+//! references/, assets/, tests/) is scaffolded first; see [`templates`].
+
+pub mod templates;
 
 use crate::error::{CliError, CliResult};
 use crate::utils::messages;
@@ -46,6 +51,9 @@ pub struct InitArgs {
 
     /// Skills directory path (required for project-level, optional for skill-level)
     skills_dir: Option<String>,
+
+    /// Scaffold the skill directory from this template first
+    template: Option<String>,
 }
 
 impl IntoCommandSpec for InitArgs {
@@ -109,6 +117,15 @@ impl IntoCommandSpec for InitArgs {
                     help: "Set download URL",
                     ..Default::default()
                 },
+                ArgSpec {
+                    name: "template",
+                    kind: ArgKind::Option,
+                    long: Some("template"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    help: "Scaffold a skill directory from a template (python-tool, bash-tool, doc-only, or one under ~/.fastskill/templates)",
+                    ..Default::default()
+                },
             ],
             ..Default::default()
         }
@@ -143,6 +160,7 @@ impl FromArgValueMap for InitArgs {
             download_url: map.get("download-url").and_then(opt_str),
             // skills_dir is omitted from the spec; use the global --skills-dir flag instead
             skills_dir: None,
+            template: map.get("template").and_then(opt_str),
         }
     }
 }
@@ -154,6 +172,10 @@ pub async fn execute_init(args: InitArgs) -> CliResult<()> {
     let skill_project_path = Path::new("skill-project.toml");
     ensure_can_init(skill_project_path, args.force)?;
 
+    if let Some(ref template) = args.template {
+        scaffold_template(template, &args)?;
+    }
+
     let is_skill_level = Path::new("SKILL.md").exists();
     let (skill_md_content, frontmatter) = load_skill_md_and_frontmatter(is_skill_level)?;
 
@@ -202,6 +224,48 @@ fn ensure_can_init(path: &Path, force: bool) -> CliResult<()> {
     Ok(())
 }
 
+/// Write the skill directory from `template`, so the rest of init picks its
+/// metadata up from the generated SKILL.md.
+fn scaffold_template(template: &str, args: &InitArgs) -> CliResult<()> {
+    let name = resolve_skill_id()?;
+    let version = match args.version {
+        Some(ref v) => {
+            validate_semver(v).map_err(|e| CliError::InvalidSemver(format!("{}: {}", v, e)))?;
+            v.clone()
+        }
+        None => "1.0.0".to_string(),
+    };
+    let description = match args.description {
+        Some(ref d) => Some(d.clone()),
+        None if !args.yes => prompt_for_field("Description", None)?,
+        None => None,
+    }
+    // The description sits on one line of the frontmatter
+    .map(|d| d.split_whitespace().collect::<Vec<_>>().join(" "))
+    .filter(|d| !d.is_empty())
+    .unwrap_or_else(|| format!("Describe what {} does and when to use it.", name));
+
+    let values = templates::TemplateValues {
+        name,
+        description,
+        version,
+    };
+    let written = templates::scaffold(template, Path::new("."), &values, args.force)?;
+    messages::status!(
+        "{}",
+        messages::ok(&format!(
+            "Scaffolded skill from template '{}' ({} files)",
+            template,
+            written.len()
+        ))
+    );
+    for path in &written {
+        println!("   {}", path.display());
+    }
+    println!();
+    Ok(())
+}
+
 fn load_skill_md_and_frontmatter(
     skill_md_exists: bool,
 ) -> CliResult<(
@@ -341,6 +405,7 @@ fn build_skill_project(meta: InitMetadata<'_>) -> CliResult<SkillProjectToml> {
         author: meta.author,
         download_url: meta.download_url,
         name: None,
+        exclude: None,
     });
     let dependencies = Some(DependenciesSection {
         dependencies: HashMap::new(),
@@ -353,8 +418,12 @@ fn build_skill_project(meta: InitMetadata<'_>) -> CliResult<SkillProjectToml> {
             server: None,
             install_depth: 5,
             skip_transitive: false,
+            resolution: Default::default(),
             eval: None,
             auto_reindex: true,
+            offline: false,
+            naming: Default::default(),
+            link_mode: Default::default(),
         }),
     });
     validate_project_structure(true, dependencies.is_some())
@@ -363,6 +432,10 @@ fn build_skill_project(meta: InitMetadata<'_>) -> CliResult<SkillProjectToml> {
         metadata,
         dependencies,
         tool,
+        optional_dependencies: None,
+        peer_dependencies: None,
+        requires: None,
+        env: None,
     })
 }
 
@@ -409,7 +482,7 @@ fn append_tool_comment(path: &Path, is_skill_level: bool) -> CliResult<()> {
 }
 
 fn print_success(is_skill_level: bool, version: &str, skills_directory: Option<&str>) {
-    println!(
+    messages::status!(
         "{}",
         messages::ok(&format!(
             "Created skill-project.toml with version: {}",
@@ -418,7 +491,7 @@ fn print_success(is_skill_level: bool, version: &str, skills_directory: Option<&
     );
     if is_skill_level {
         println!();
-        println!(
+        messages::status!(
             "{}",
             messages::info("This file contains author-provided metadata for your skill.")
         );
@@ -427,10 +500,10 @@ fn print_success(is_skill_level: bool, version: &str, skills_directory: Option<&
     }
     if let Some(dir) = skills_directory {
         println!();
-        println!("{}", messages::info(&format!("Skills directory: {}", dir)));
+        messages::status!("{}", messages::info(&format!("Skills directory: {}", dir)));
     }
     println!();
-    println!(
+    messages::status!(
         "{}",
         messages::info("This file configures your project's skill dependencies.")
     );
@@ -600,6 +673,7 @@ mod tests {
             author: Some("Test Author".to_string()),
             download_url: Some("https://example.com".to_string()),
             skills_dir: Some(".claude/skills".to_string()),
+            template: None,
         };
 
         let result = execute_init(args).await;
@@ -638,6 +712,7 @@ mod tests {
             author: None,
             download_url: None,
             skills_dir: Some(".claude/skills".to_string()),
+            template: None,
         };
 
         let result = execute_init(args).await;
@@ -678,6 +753,7 @@ mod tests {
             author: None,
             download_url: None,
             skills_dir: Some(".claude/skills".to_string()),
+            template: None,
         };
 
         // This test now just verifies the function doesn't panic
@@ -719,6 +795,7 @@ mod tests {
             author: None,
             download_url: None,
             skills_dir: Some(".claude/skills".to_string()),
+            template: None,
         };
 
         let result = execute_init(args).await;
@@ -732,4 +809,46 @@ mod tests {
             fs::remove_file("skill-project.toml").ok();
         }
     }
+
+    #[tokio::test]
+    async fn test_execute_init_with_template() {
+        let _lock = fastskill_core::test_utils::DIR_MUTEX
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let temp_dir = TempDir::new().unwrap();
+        let skill_dir = temp_dir.path().join("csv-cleaner");
+        fs::create_dir(&skill_dir).unwrap();
+        let original_dir = std::env::current_dir().ok();
+        struct DirGuard(Option<std::path::PathBuf>);
+        impl Drop for DirGuard {
+            fn drop(&mut self) {
+                if let Some(dir) = &self.0 {
+                    let _ = std::env::set_current_dir(dir);
+                }
+            }
+        }
+        let _guard = DirGuard(original_dir);
+        std::env::set_current_dir(&skill_dir).unwrap();
+
+        let args = InitArgs {
+            yes: true,
+            force: false,
+            version: Some("0.2.0".to_string()),
+            description: Some("Clean up CSV files".to_string()),
+            author: None,
+            download_url: None,
+            skills_dir: None,
+            template: Some("python-tool".to_string()),
+        };
+        execute_init(args).await.unwrap();
+
+        assert!(Path::new("scripts/main.py").exists());
+        assert!(Path::new("tests/test_main.py").exists());
+        let skill_md = fs::read_to_string("SKILL.md").unwrap();
+        assert!(skill_md.contains("name: csv-cleaner"));
+        let project = fs::read_to_string("skill-project.toml").unwrap();
+        assert!(project.contains("id = \"csv-cleaner\""));
+        assert!(project.contains("version = \"0.2.0\""));
+        assert!(project.contains("Clean up CSV files"));
+    }
 }
@@ -0,0 +1,541 @@
+//! Skill templates for `init --template`
+//!
+//! A template is a skill directory skeleton: `SKILL.md` plus whatever
+//! `scripts/`, `references/`, `assets/` and `tests/` files it ships. Three are
+//! built in (`python-tool`, `bash-tool`, `doc-only`); users add their own as
+//! directories under `~/.fastskill/templates/<name>/`, which take precedence
+//! over a built-in of the same name.
+//!
+//! Text files may use `{{name}}`, `{{description}}` and `{{version}}`
+//! placeholders; they are replaced when the template is rendered.
+
+use crate::error::{CliError, CliResult};
+use fastskill_core::core::metadata::parse_yaml_frontmatter;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Names of the templates compiled into the CLI
+pub const BUILTIN_TEMPLATES: &[&str] = &["python-tool", "bash-tool", "doc-only"];
+
+/// Values substituted into template placeholders
+#[derive(Debug, Clone)]
+pub struct TemplateValues {
+    pub name: String,
+    pub description: String,
+    pub version: String,
+}
+
+impl TemplateValues {
+    fn render(&self, text: &str) -> String {
+        text.replace("{{name}}", &self.name)
+            .replace("{{description}}", &self.description)
+            .replace("{{version}}", &self.version)
+    }
+}
+
+/// One file a template writes, relative to the skill directory
+#[derive(Debug)]
+struct TemplateFile {
+    path: PathBuf,
+    content: Vec<u8>,
+    executable: bool,
+}
+
+/// `~/.fastskill/templates`, where user-defined templates live
+pub fn user_templates_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".fastskill").join("templates"))
+}
+
+/// Write template `name` into `dest`, returning the files written. Existing
+/// files are only overwritten with `force`; nothing is written if any would be
+/// clobbered without it.
+pub fn scaffold(
+    name: &str,
+    dest: &Path,
+    values: &TemplateValues,
+    force: bool,
+) -> CliResult<Vec<PathBuf>> {
+    scaffold_from(name, user_templates_dir().as_deref(), dest, values, force)
+}
+
+fn scaffold_from(
+    name: &str,
+    user_dir: Option<&Path>,
+    dest: &Path,
+    values: &TemplateValues,
+    force: bool,
+) -> CliResult<Vec<PathBuf>> {
+    let files = load_template(name, user_dir, values)?;
+
+    let skill_md = files
+        .iter()
+        .find(|f| f.path == Path::new("SKILL.md"))
+        .ok_or_else(|| {
+            CliError::Validation(format!("Template '{}' has no SKILL.md at its root", name))
+        })?;
+    let rendered = String::from_utf8_lossy(&skill_md.content);
+    parse_yaml_frontmatter(&rendered).map_err(|e| {
+        CliError::Validation(format!(
+            "Template '{}' renders a SKILL.md with invalid frontmatter: {}",
+            name, e
+        ))
+    })?;
+
+    if !force {
+        let existing: Vec<String> = files
+            .iter()
+            .filter(|f| dest.join(&f.path).exists())
+            .map(|f| f.path.display().to_string())
+            .collect();
+        if !existing.is_empty() {
+            return Err(CliError::Config(format!(
+                "Template would overwrite existing files ({}). Use --force to overwrite.",
+                existing.join(", ")
+            )));
+        }
+    }
+
+    let mut written = Vec::with_capacity(files.len());
+    for file in files {
+        let target = dest.join(&file.path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&target, &file.content)?;
+        if file.executable {
+            make_executable(&target)?;
+        }
+        written.push(file.path);
+    }
+    Ok(written)
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> CliResult<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut permissions = fs::metadata(path)?.permissions();
+    permissions.set_mode(permissions.mode() | 0o755);
+    fs::set_permissions(path, permissions)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> CliResult<()> {
+    Ok(())
+}
+
+fn load_template(
+    name: &str,
+    user_dir: Option<&Path>,
+    values: &TemplateValues,
+) -> CliResult<Vec<TemplateFile>> {
+    // A template name is a single directory name, never a path
+    let mut components = Path::new(name).components();
+    if !matches!(
+        (components.next(), components.next()),
+        (Some(Component::Normal(_)), None)
+    ) {
+        return Err(CliError::Validation(format!(
+            "Invalid template name '{}'",
+            name
+        )));
+    }
+
+    if let Some(dir) = user_dir.map(|d| d.join(name)).filter(|d| d.is_dir()) {
+        return load_user_template(&dir, values);
+    }
+    builtin_template(name, values).ok_or_else(|| {
+        let mut available: Vec<String> = BUILTIN_TEMPLATES.iter().map(|s| s.to_string()).collect();
+        available.extend(list_user_templates(user_dir));
+        CliError::Validation(format!(
+            "Unknown template '{}'. Available templates: {}",
+            name,
+            available.join(", ")
+        ))
+    })
+}
+
+fn list_user_templates(user_dir: Option<&Path>) -> Vec<String> {
+    let Some(entries) = user_dir.and_then(|d| fs::read_dir(d).ok()) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| e.file_name().to_str().map(str::to_string))
+        .filter(|n| !BUILTIN_TEMPLATES.contains(&n.as_str()))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Read every regular file under `dir`. Symlinks are skipped so a template
+/// cannot pull in files from outside its own directory.
+fn load_user_template(dir: &Path, values: &TemplateValues) -> CliResult<Vec<TemplateFile>> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(dir).follow_links(false).sort_by_file_name() {
+        let entry = entry.map_err(|e| {
+            CliError::Config(format!("Failed to read template {}: {}", dir.display(), e))
+        })?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let path = entry
+            .path()
+            .strip_prefix(dir)
+            .map_err(|e| CliError::Config(format!("Failed to read template: {}", e)))?
+            .to_path_buf();
+        let bytes = fs::read(entry.path())?;
+        let content = match String::from_utf8(bytes) {
+            Ok(text) => values.render(&text).into_bytes(),
+            // Binary assets are copied as they are
+            Err(e) => e.into_bytes(),
+        };
+        files.push(TemplateFile {
+            executable: is_executable(&entry.path().metadata()?),
+            path,
+            content,
+        });
+    }
+    Ok(files)
+}
+
+#[cfg(unix)]
+fn is_executable(metadata: &fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_metadata: &fs::Metadata) -> bool {
+    false
+}
+
+fn builtin_template(name: &str, values: &TemplateValues) -> Option<Vec<TemplateFile>> {
+    let files: &[(&str, &str, bool)] = match name {
+        "python-tool" => &[
+            ("SKILL.md", PYTHON_SKILL_MD, false),
+            ("scripts/main.py", PYTHON_MAIN, true),
+            ("references/README.md", REFERENCES_README, false),
+            ("assets/.gitkeep", "", false),
+            ("tests/test_main.py", PYTHON_TEST, false),
+        ],
+        "bash-tool" => &[
+            ("SKILL.md", BASH_SKILL_MD, false),
+            ("scripts/run.sh", BASH_RUN, true),
+            ("references/README.md", REFERENCES_README, false),
+            ("assets/.gitkeep", "", false),
+            ("tests/test_run.sh", BASH_TEST, true),
+        ],
+        "doc-only" => &[
+            ("SKILL.md", DOC_SKILL_MD, false),
+            ("references/README.md", REFERENCES_README, false),
+            ("assets/.gitkeep", "", false),
+            ("tests/prompts.md", DOC_TEST_PROMPTS, false),
+        ],
+        _ => return None,
+    };
+    Some(
+        files
+            .iter()
+            .map(|(path, content, executable)| TemplateFile {
+                path: PathBuf::from(path),
+                content: values.render(content).into_bytes(),
+                executable: *executable,
+            })
+            .collect(),
+    )
+}
+
+const PYTHON_SKILL_MD: &str = r#"---
+name: {{name}}
+description: >-
+  {{description}}
+version: {{version}}
+---
+
+# {{name}}
+
+{{description}}
+
+## When to use
+
+Describe the requests this skill should handle.
+
+## How to use
+
+Run the bundled script with the input file:
+
+```bash
+python3 scripts/main.py <input>
+```
+
+See `references/` for background material and `assets/` for files the
+script reads.
+"#;
+
+const PYTHON_MAIN: &str = r#"#!/usr/bin/env python3
+"""Entry point for the {{name}} skill."""
+
+import argparse
+import sys
+
+
+def run(text: str) -> str:
+    """Transform the input; replace with the skill's real work."""
+    return text.strip()
+
+
+def main(argv=None) -> int:
+    parser = argparse.ArgumentParser(description="{{name}}")
+    parser.add_argument("input", help="Path to the input file")
+    args = parser.parse_args(argv)
+
+    with open(args.input, encoding="utf-8") as f:
+        print(run(f.read()))
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
+"#;
+
+const PYTHON_TEST: &str = r#"import os
+import sys
+import unittest
+
+sys.path.insert(0, os.path.join(os.path.dirname(__file__), "..", "scripts"))
+
+import main  # noqa: E402
+
+
+class RunTest(unittest.TestCase):
+    def test_run_strips_whitespace(self):
+        self.assertEqual(main.run("  hello \n"), "hello")
+
+
+if __name__ == "__main__":
+    unittest.main()
+"#;
+
+const BASH_SKILL_MD: &str = r#"---
+name: {{name}}
+description: >-
+  {{description}}
+version: {{version}}
+---
+
+# {{name}}
+
+{{description}}
+
+## When to use
+
+Describe the requests this skill should handle.
+
+## How to use
+
+Run the bundled script with the input file:
+
+```bash
+bash scripts/run.sh <input>
+```
+
+See `references/` for background material and `assets/` for files the
+script reads.
+"#;
+
+const BASH_RUN: &str = r#"#!/usr/bin/env bash
+# Entry point for the {{name}} skill.
+set -euo pipefail
+
+if [ "$#" -ne 1 ]; then
+  echo "usage: $0 <input>" >&2
+  exit 2
+fi
+
+# Replace with the skill's real work
+tr -s '[:space:]' < "$1"
+"#;
+
+const BASH_TEST: &str = r#"#!/usr/bin/env bash
+# Smoke test for scripts/run.sh
+set -euo pipefail
+
+here="$(cd "$(dirname "$0")" && pwd)"
+input="$(mktemp)"
+trap 'rm -f "$input"' EXIT
+
+printf 'hello   world\n' > "$input"
+output="$(bash "$here/../scripts/run.sh" "$input")"
+
+if [ "$output" != "hello world" ]; then
+  echo "unexpected output: $output" >&2
+  exit 1
+fi
+echo "ok"
+"#;
+
+const DOC_SKILL_MD: &str = r#"---
+name: {{name}}
+description: >-
+  {{description}}
+version: {{version}}
+---
+
+# {{name}}
+
+{{description}}
+
+## When to use
+
+Describe the requests this skill should handle.
+
+## Instructions
+
+Write the steps or guidelines to follow. Keep this file focused and move
+longer material into `references/`.
+"#;
+
+const REFERENCES_README: &str = r#"# References for {{name}}
+
+Put background documents here (API notes, style guides, schemas). SKILL.md
+should say when each one is worth reading.
+"#;
+
+const DOC_TEST_PROMPTS: &str = r#"# Test prompts for {{name}}
+
+Requests this skill should handle, and what a good answer does with them.
+
+1. Prompt: ...
+   Expected: ...
+"#;
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn values() -> TemplateValues {
+        TemplateValues {
+            name: "csv-tool".to_string(),
+            description: "Clean CSV files: trim cells and drop empty rows".to_string(),
+            version: "0.1.0".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_builtin_templates_render_valid_skills() {
+        for name in BUILTIN_TEMPLATES {
+            let dest = TempDir::new().unwrap();
+            let written = scaffold_from(name, None, dest.path(), &values(), false).unwrap();
+            assert!(written.contains(&PathBuf::from("SKILL.md")), "{}", name);
+            assert!(dest.path().join("references").is_dir(), "{}", name);
+            assert!(dest.path().join("assets").is_dir(), "{}", name);
+            assert!(dest.path().join("tests").is_dir(), "{}", name);
+
+            let skill_md = fs::read_to_string(dest.path().join("SKILL.md")).unwrap();
+            let frontmatter = parse_yaml_frontmatter(&skill_md).unwrap();
+            assert_eq!(frontmatter.name, "csv-tool");
+            assert_eq!(
+                frontmatter.description,
+                "Clean CSV files: trim cells and drop empty rows"
+            );
+            assert_eq!(frontmatter.version.as_deref(), Some("0.1.0"));
+            assert!(!skill_md.contains("{{"), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_scaffold_refuses_to_overwrite_without_force() {
+        let dest = TempDir::new().unwrap();
+        fs::write(dest.path().join("SKILL.md"), "mine").unwrap();
+
+        let err = scaffold_from("doc-only", None, dest.path(), &values(), false).unwrap_err();
+        assert!(err.to_string().contains("SKILL.md"), "{}", err);
+        assert_eq!(
+            fs::read_to_string(dest.path().join("SKILL.md")).unwrap(),
+            "mine"
+        );
+        assert!(!dest.path().join("references").exists());
+
+        scaffold_from("doc-only", None, dest.path(), &values(), true).unwrap();
+        assert!(fs::read_to_string(dest.path().join("SKILL.md"))
+            .unwrap()
+            .contains("name: csv-tool"));
+    }
+
+    #[test]
+    fn test_user_template_overrides_builtin_and_renders() {
+        let user_dir = TempDir::new().unwrap();
+        let template = user_dir.path().join("doc-only");
+        fs::create_dir_all(template.join("assets")).unwrap();
+        fs::write(
+            template.join("SKILL.md"),
+            "---\nname: {{name}}\ndescription: house style\n---\n# {{name}} v{{version}}\n",
+        )
+        .unwrap();
+        fs::write(template.join("assets/logo.bin"), [0xff, 0xfe, 0x00]).unwrap();
+
+        let dest = TempDir::new().unwrap();
+        scaffold_from(
+            "doc-only",
+            Some(user_dir.path()),
+            dest.path(),
+            &values(),
+            false,
+        )
+        .unwrap();
+        let skill_md = fs::read_to_string(dest.path().join("SKILL.md")).unwrap();
+        assert!(skill_md.contains("# csv-tool v0.1.0"));
+        assert_eq!(
+            fs::read(dest.path().join("assets/logo.bin")).unwrap(),
+            vec![0xff, 0xfe, 0x00]
+        );
+        assert!(!dest.path().join("tests").exists());
+    }
+
+    #[test]
+    fn test_unknown_and_path_like_template_names_are_rejected() {
+        let user_dir = TempDir::new().unwrap();
+        fs::create_dir_all(user_dir.path().join("house")).unwrap();
+        let dest = TempDir::new().unwrap();
+
+        let err = scaffold_from("nope", Some(user_dir.path()), dest.path(), &values(), false)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("python-tool"), "{}", err);
+        assert!(err.contains("house"), "{}", err);
+
+        for name in ["../house", "a/b", "/etc", ".", ""] {
+            let err = scaffold_from(name, Some(user_dir.path()), dest.path(), &values(), false)
+                .unwrap_err();
+            assert!(
+                err.to_string().contains("Invalid template name"),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_user_template_without_skill_md_is_rejected() {
+        let user_dir = TempDir::new().unwrap();
+        fs::create_dir_all(user_dir.path().join("empty/scripts")).unwrap();
+        fs::write(user_dir.path().join("empty/scripts/x.sh"), "echo").unwrap();
+        let dest = TempDir::new().unwrap();
+
+        let err = scaffold_from(
+            "empty",
+            Some(user_dir.path()),
+            dest.path(),
+            &values(),
+            false,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("no SKILL.md"), "{}", err);
+        assert!(!dest.path().join("scripts").exists());
+    }
+}
@@ -2,22 +2,33 @@
 
 use crate::config::create_service_config;
 use crate::error::{manifest_required_message, CliError, CliResult};
+use crate::utils::change_plan::{ChangePlan, PlannedChange};
+use crate::utils::install_progress::{InstallEvent, InstallProgress, ProgressMode};
 use crate::utils::{install_utils, manifest_utils, messages};
 use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
 use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
 use cli_framework::spec::command_tree::CommandSpec;
 use cli_framework::spec::value::ArgValue;
 use fastskill_core::core::{
-    dependency_resolver::{DependencyResolver, SkillInstallItem},
+    dependency_resolver::{DependencyConflict, DependencyResolver, SkillInstallItem},
+    link::LinkMode,
     lock::{project_lock_path, ProjectSkillsLock},
-    manifest::{SkillEntry, SkillProjectToml},
-    project::resolve_project_file,
+    manifest::{DependenciesSection, SkillEntry, SkillProjectToml},
+    origin::{GitRef, Origin},
+    project::{resolve_project_file, Workspace, WORKSPACE_FILE},
     repository::RepositoryManager,
+    vendor::{vendor_dir_for, VendorDir},
+    version::{ResolutionStrategy, VersionConstraint},
 };
 use fastskill_core::FastSkillService;
-use std::collections::HashMap;
+use futures::stream::{self, Stream, StreamExt};
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::fs;
+use std::future::Future;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 /// Apply manifest: install skills from skill-project.toml [dependencies]
 ///
@@ -47,8 +58,42 @@ pub struct InstallArgs {
 
     /// Skip reindex after install
     no_reindex: bool,
+
+    /// Maximum number of skills installed concurrently
+    jobs: usize,
+
+    /// Stop starting new installs after the first failure
+    fail_fast: bool,
+
+    /// Print the resolution plan (strategy and per-skill version decisions) without installing
+    plan: bool,
+
+    /// Resolve everything and print the change plan without touching the filesystem
+    dry_run: bool,
+
+    /// Print the --dry-run plan as JSON
+    json: bool,
+
+    /// Report install progress as JSON lines on stderr instead of progress bars
+    json_progress: bool,
+
+    /// Version selection strategy (overrides `[tool.fastskill] resolution`)
+    resolution: Option<String>,
+
+    /// Fail when an installed skill's peer dependency range is not satisfied
+    strict_peers: bool,
+
+    /// Install from the vendor/ directory only (overrides `[tool.fastskill] offline`)
+    offline: bool,
+
+    /// How editable skills are linked: auto, symlink, junction, copy or hardlink
+    /// (overrides `[tool.fastskill] link_mode`)
+    link_mode: Option<String>,
 }
 
+/// Default for `--jobs`.
+const DEFAULT_INSTALL_JOBS: i64 = 4;
+
 impl IntoCommandSpec for InstallArgs {
     fn command_spec() -> CommandSpec {
         CommandSpec {
@@ -110,6 +155,98 @@ impl IntoCommandSpec for InstallArgs {
                     help: "Skip reindex after install",
                     ..Default::default()
                 },
+                ArgSpec {
+                    name: "jobs",
+                    kind: ArgKind::Option,
+                    long: Some("jobs"),
+                    short: Some('j'),
+                    value_type: ArgValueType::Int,
+                    cardinality: Cardinality::Optional,
+                    default: Some(ArgValue::Int(DEFAULT_INSTALL_JOBS)),
+                    help: "Maximum number of skills to install concurrently",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "plan",
+                    kind: ArgKind::Flag,
+                    long: Some("plan"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Show the resolution strategy and the version chosen for each skill, without installing",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "dry-run",
+                    kind: ArgKind::Flag,
+                    long: Some("dry-run"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Show the skills that would be added, upgraded, downgraded or removed, their download sizes and the skills.lock diff, without changing anything",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "json",
+                    kind: ArgKind::Flag,
+                    long: Some("json"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Print the --dry-run plan as JSON",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "json-progress",
+                    kind: ArgKind::Flag,
+                    long: Some("json-progress"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Report progress as one JSON event per line on stderr instead of progress bars (for CI)",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "resolution",
+                    kind: ArgKind::Option,
+                    long: Some("resolution"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    help: "Version selection: maximum, minimum or locked (overrides [tool.fastskill] resolution)",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "fail-fast",
+                    kind: ArgKind::Flag,
+                    long: Some("fail-fast"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Stop starting new installs after the first failure",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "strict-peers",
+                    kind: ArgKind::Flag,
+                    long: Some("strict-peers"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Fail instead of warning when installed skills violate a declared peer dependency range",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "offline",
+                    kind: ArgKind::Flag,
+                    long: Some("offline"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Install only from skills vendored with 'fastskill vendor'; never use the network",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "link-mode",
+                    kind: ArgKind::Option,
+                    long: Some("link-mode"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    help: "How editable skills are linked: auto (default), symlink, junction, copy or hardlink (overrides [tool.fastskill] link_mode)",
+                    ..Default::default()
+                },
             ],
             ..Default::default()
         }
@@ -138,6 +275,13 @@ fn repeated_str_list(v: &ArgValue) -> Option<Vec<String>> {
     }
 }
 
+impl InstallArgs {
+    /// Whether this run changes installed skills (not `--dry-run`)
+    pub fn writes_storage(&self) -> bool {
+        !self.dry_run
+    }
+}
+
 #[allow(clippy::panic)]
 impl FromArgValueMap for InstallArgs {
     fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
@@ -154,6 +298,25 @@ impl FromArgValueMap for InstallArgs {
             }),
             reindex: matches!(map.get("reindex"), Some(ArgValue::Bool(true))),
             no_reindex: matches!(map.get("no-reindex"), Some(ArgValue::Bool(true))),
+            jobs: match map.get("jobs") {
+                Some(ArgValue::Int(n)) => (*n).max(0) as usize,
+                _ => DEFAULT_INSTALL_JOBS as usize,
+            },
+            fail_fast: matches!(map.get("fail-fast"), Some(ArgValue::Bool(true))),
+            plan: matches!(map.get("plan"), Some(ArgValue::Bool(true))),
+            dry_run: matches!(map.get("dry-run"), Some(ArgValue::Bool(true))),
+            json: matches!(map.get("json"), Some(ArgValue::Bool(true))),
+            json_progress: matches!(map.get("json-progress"), Some(ArgValue::Bool(true))),
+            resolution: match map.get("resolution") {
+                Some(ArgValue::Str(s)) => Some(s.clone()),
+                _ => None,
+            },
+            strict_peers: matches!(map.get("strict-peers"), Some(ArgValue::Bool(true))),
+            offline: matches!(map.get("offline"), Some(ArgValue::Bool(true))),
+            link_mode: match map.get("link-mode") {
+                Some(ArgValue::Str(s)) => Some(s.clone()),
+                _ => None,
+            },
         }
     }
 }
@@ -207,6 +370,313 @@ fn group_passes_filter(
     true
 }
 
+/// How a skill's version was (or could not be) chosen, as shown by `--plan`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct VersionDecision {
+    id: String,
+    parent_skill: Option<String>,
+    /// Version the strategy picked; only repository origins are version-resolved.
+    selected: Option<String>,
+    reason: String,
+    resolved: bool,
+}
+
+fn describe_unversioned(origin: &Origin) -> String {
+    match origin {
+        Origin::Git { url, r#ref, .. } => {
+            let at = match r#ref {
+                GitRef::Default => String::new(),
+                GitRef::Branch(b) => format!(" @ branch {}", b),
+                GitRef::Tag(t) => format!(" @ tag {}", t),
+                GitRef::Commit(c) => format!(" @ commit {}", c),
+            };
+            format!("git {}{} (not version-resolved)", url, at)
+        }
+        Origin::Local { path, .. } => format!("local {} (not version-resolved)", path.display()),
+        Origin::ZipUrl { url } => format!("zip {} (not version-resolved)", url),
+        Origin::Pack { path, skill } => {
+            format!("pack {} ({}) (not version-resolved)", path.display(), skill)
+        }
+        Origin::Repository { .. } => String::new(),
+    }
+}
+
+/// Apply `strategy` to every repository-origin item. `available(repo, skill)`
+/// lists the versions a repository offers, or `None` when the repository
+/// can't be queried; `locked` maps skill id to the version in skills.lock.
+fn decide_versions(
+    items: &[SkillInstallItem],
+    strategy: ResolutionStrategy,
+    locked: &HashMap<String, String>,
+    available: impl Fn(&str, &str) -> Option<Vec<String>>,
+) -> Vec<VersionDecision> {
+    items
+        .iter()
+        .map(|item| {
+            let id = item.entry.id.clone();
+            let decision =
+                |selected: Option<String>, reason: String, resolved: bool| VersionDecision {
+                    id: id.clone(),
+                    parent_skill: item.parent_skill.clone(),
+                    selected,
+                    reason,
+                    resolved,
+                };
+            let Origin::Repository {
+                repo,
+                skill,
+                version,
+            } = &item.entry.origin
+            else {
+                return decision(None, describe_unversioned(&item.entry.origin), true);
+            };
+            let constraint = version
+                .as_ref()
+                .map(|c| c.to_string())
+                .unwrap_or_else(|| "*".to_string());
+            let Some(offered) = available(repo, skill) else {
+                return decision(
+                    None,
+                    format!("repository '{}' is not available for install", repo),
+                    false,
+                );
+            };
+            let candidates: Vec<String> = offered
+                .iter()
+                .filter(|v| {
+                    version
+                        .as_ref()
+                        .is_none_or(|c| c.satisfies(v).unwrap_or(false))
+                })
+                .cloned()
+                .collect();
+            if candidates.is_empty() {
+                let reason = if offered.is_empty() {
+                    format!("'{}' not found in repository '{}'", skill, repo)
+                } else if item.requirements.len() > 1 {
+                    // The constraint is an intersection; show where each part
+                    // came from so the user knows which dependency to change.
+                    let mut reason = format!(
+                        "no version satisfies all requirements in '{}' (available: {})",
+                        repo,
+                        offered.join(", ")
+                    );
+                    for requirement in &item.requirements {
+                        reason.push_str(&format!("\n      requires {}", requirement));
+                    }
+                    reason
+                } else {
+                    format!(
+                        "no version satisfies {} in '{}' (available: {})",
+                        constraint,
+                        repo,
+                        offered.join(", ")
+                    )
+                };
+                return decision(None, reason, false);
+            }
+            let locked_version = locked.get(&id).map(String::as_str);
+            match strategy.select(&candidates, locked_version) {
+                Some(selected) => {
+                    let reason = match strategy {
+                        ResolutionStrategy::Maximum => format!(
+                            "newest of {} version(s) satisfying {} in '{}'",
+                            candidates.len(),
+                            constraint,
+                            repo
+                        ),
+                        ResolutionStrategy::Minimum => format!(
+                            "oldest of {} version(s) satisfying {} in '{}'",
+                            candidates.len(),
+                            constraint,
+                            repo
+                        ),
+                        ResolutionStrategy::Locked => "pinned by skills.lock".to_string(),
+                    };
+                    decision(Some(selected), reason, true)
+                }
+                None => {
+                    let reason = match locked_version {
+                        None => "not in skills.lock (resolution = locked)".to_string(),
+                        Some(v) => format!(
+                            "locked version {} does not satisfy {} or is no longer in '{}'",
+                            v, constraint, repo
+                        ),
+                    };
+                    decision(None, reason, false)
+                }
+            }
+        })
+        .collect()
+}
+
+fn print_plan(strategy: ResolutionStrategy, source: &str, decisions: &[VersionDecision]) {
+    println!("Resolution strategy: {} ({})", strategy, source);
+    println!("Plan ({} skills):", decisions.len());
+    for d in decisions {
+        let version = d
+            .selected
+            .as_ref()
+            .map(|v| format!(" {}", v))
+            .unwrap_or_default();
+        let line = format!("{}{} - {}", d.id, version, d.reason);
+        if d.resolved {
+            messages::status!("  {}", messages::ok(&line));
+        } else {
+            println!("  {}", messages::error(&line));
+        }
+        if let Some(parent) = &d.parent_skill {
+            println!("      required by {}", parent);
+        }
+    }
+}
+
+/// Resolve manifest entries to install items: transitive dependencies (read
+/// from the manifests of skills already in `skills_dir`) unless disabled, then
+/// the group filters.
+async fn resolve_install_items(
+    entries: Vec<SkillEntry>,
+    recursive_config: &RecursiveInstallConfig,
+    skills_dir: &std::path::Path,
+) -> CliResult<Vec<SkillInstallItem>> {
+    if recursive_config.skip_transitive {
+        return Ok(entries
+            .into_iter()
+            .map(|entry| SkillInstallItem {
+                entry,
+                depth: 0,
+                parent_skill: None,
+                requirements: Vec::new(),
+            })
+            .collect());
+    }
+
+    let mut resolver = DependencyResolver::new(recursive_config.max_depth);
+    let resolved_items = resolver
+        .resolve_dependencies(entries, skills_dir)
+        .await
+        .map_err(|e| CliError::Config(format!("Dependency resolution failed: {}", e)))?;
+
+    let ex = recursive_config.exclude_groups.as_deref();
+    let on = recursive_config.only_groups.as_deref();
+    Ok(resolved_items
+        .into_iter()
+        .filter(|item| group_passes_filter(&item.entry.groups, ex, on))
+        .collect())
+}
+
+/// Requirements discovered after a skill was installed that its installed
+/// version does not meet, one report per skill.
+fn unmet_by_installed(
+    items: &[SkillInstallItem],
+    installed: &HashMap<String, String>,
+) -> Vec<String> {
+    items
+        .iter()
+        .filter_map(|item| {
+            let Origin::Repository {
+                version: Some(constraint),
+                ..
+            } = &item.entry.origin
+            else {
+                return None;
+            };
+            let version = installed.get(&item.entry.id)?;
+            if constraint.satisfies(version).unwrap_or(false) {
+                return None;
+            }
+            Some(
+                DependencyConflict {
+                    skill_id: item.entry.id.clone(),
+                    problem: format!(
+                        "installed version {} does not satisfy {}",
+                        version, constraint
+                    ),
+                    requirements: item.requirements.clone(),
+                }
+                .to_string(),
+            )
+        })
+        .collect()
+}
+
+/// Run `install` for each of `items`, up to `jobs` at a time. `buffered`
+/// yields results in `items` order, so per-skill output stays deterministic.
+/// A failure never cancels in-flight installs; with `fail_fast`, installs
+/// that have not started yet are skipped instead (`None`).
+fn install_concurrently<'a, T, R, E, F, Fut>(
+    items: &'a [T],
+    jobs: usize,
+    fail_fast: bool,
+    install: F,
+) -> impl Stream<Item = (&'a T, Option<Result<R, E>>)> + 'a
+where
+    F: Fn(&'a T) -> Fut + 'a,
+    Fut: Future<Output = Result<R, E>> + 'a,
+{
+    let stop = Arc::new(AtomicBool::new(false));
+    stream::iter(items)
+        .map(move |item| {
+            let stop = Arc::clone(&stop);
+            let run = install(item);
+            async move {
+                if stop.load(Ordering::SeqCst) {
+                    return (item, None);
+                }
+                let result = run.await;
+                if result.is_err() && fail_fast {
+                    stop.store(true, Ordering::SeqCst);
+                }
+                (item, Some(result))
+            }
+        })
+        .buffered(jobs)
+}
+
+/// The error reported when some installs failed, naming the installs that
+/// `--fail-fast` skipped after the first failure.
+fn failure_summary(failed: &[String], skipped: &[String]) -> String {
+    let skipped = if skipped.is_empty() {
+        String::new()
+    } else {
+        format!("; skipped {} after the failure", skipped.len())
+    };
+    format!(
+        "Failed to install {} skill(s): {}{}",
+        failed.len(),
+        failed.join(", "),
+        skipped
+    )
+}
+
+/// The workspace to install for when run from a workspace root. Members share
+/// the root's skills directory and skills.lock, so installing from inside a
+/// member is refused.
+fn install_workspace(current_dir: &Path) -> CliResult<Option<Workspace>> {
+    let Some(workspace) = Workspace::discover(current_dir)
+        .map_err(|e| CliError::Config(format!("Failed to load {}: {}", WORKSPACE_FILE, e)))?
+    else {
+        return Ok(None);
+    };
+    let dir = current_dir.canonicalize()?;
+    if let Some(member) = workspace.member_containing(&dir) {
+        return Err(CliError::Validation(format!(
+            "{} is a member of the workspace at {} and shares its skills.lock. \
+             Run 'fastskill install' from the workspace root.",
+            workspace.label(member),
+            workspace.root.display()
+        )));
+    }
+    if workspace.root_project.is_none() {
+        return Err(CliError::Config(format!(
+            "Workspace root {} has no skill-project.toml. Add one with [dependencies] and \
+             [tool.fastskill] skills_directory; all members install into that directory.",
+            workspace.root.display()
+        )));
+    }
+    Ok(Some(workspace))
+}
+
 pub async fn execute_install(args: InstallArgs) -> CliResult<()> {
     if args.reindex && args.no_reindex {
         return Err(CliError::Validation(
@@ -214,8 +684,51 @@ pub async fn execute_install(args: InstallArgs) -> CliResult<()> {
         ));
     }
 
-    println!("Installing skills...");
-    println!();
+    if args.jobs == 0 {
+        return Err(CliError::Validation(
+            "--jobs must be at least 1".to_string(),
+        ));
+    }
+
+    if args.json && !args.dry_run {
+        return Err(CliError::Validation(
+            "--json requires --dry-run".to_string(),
+        ));
+    }
+    if args.plan && args.dry_run {
+        return Err(CliError::Validation(
+            "--plan and --dry-run cannot be used together".to_string(),
+        ));
+    }
+    if args.json_progress && (args.plan || args.dry_run) {
+        return Err(CliError::Validation(
+            "--json-progress cannot be used with --plan or --dry-run".to_string(),
+        ));
+    }
+
+    let resolution_override = args
+        .resolution
+        .as_deref()
+        .map(ResolutionStrategy::parse)
+        .transpose()
+        .map_err(|e| CliError::Validation(format!("--resolution: {}", e)))?;
+    let link_mode_override = args
+        .link_mode
+        .as_deref()
+        .map(LinkMode::parse)
+        .transpose()
+        .map_err(|e| CliError::Validation(format!("--link-mode: {}", e)))?;
+
+    // --json output must be the plan alone
+    let narrate = !args.json;
+    if narrate {
+        if args.plan || args.dry_run {
+            println!("Planning install...");
+        } else {
+            println!("Installing skills...");
+        }
+        println!();
+    }
 
     // Validate depth argument (must be > 0 if provided)
     if let Some(depth) = args.depth {
@@ -230,6 +743,7 @@ pub async fn execute_install(args: InstallArgs) -> CliResult<()> {
     // T027: Resolve skill-project.toml from project root
     let current_dir = env::current_dir()
         .map_err(|e| CliError::Config(format!("Failed to get current directory: {}", e)))?;
+    let workspace = install_workspace(&current_dir)?;
     let project_file_result = resolve_project_file(&current_dir);
     let project_file_path = project_file_result.path;
 
@@ -253,42 +767,90 @@ pub async fn execute_install(args: InstallArgs) -> CliResult<()> {
 
     // Initialize service
     // Note: install command doesn't have access to CLI sources_path, so uses env var or walk-up
-    let config = create_service_config(false, None)?;
+    let mut config = create_service_config(false, None)?;
+    if let Some(link_mode) = link_mode_override {
+        config.link_mode = link_mode;
+    }
     let mut service = FastSkillService::new(config)
         .await
         .map_err(CliError::Service)?;
     service.initialize().await.map_err(CliError::Service)?;
+    if args.dry_run {
+        // Repository access for package sizes
+        service =
+            service.with_repository_manager(Arc::new(crate::config::load_repository_manager()?));
+    }
+
+    // Determine effective depth limit, skip_transitive flag, resolution
+    // strategy and offline mode from config, then CLI override
+    let (config_depth, config_skip_transitive, config_resolution, config_offline) =
+        if project_file_result.found {
+            SkillProjectToml::load_from_file(&project_file_path)
+                .ok()
+                .and_then(|p| p.tool)
+                .and_then(|t| t.fastskill)
+                .map(|cfg| {
+                    (
+                        cfg.install_depth,
+                        cfg.skip_transitive,
+                        cfg.resolution,
+                        cfg.offline,
+                    )
+                })
+                .unwrap_or((5, false, ResolutionStrategy::default(), false))
+        } else {
+            (5, false, ResolutionStrategy::default(), false)
+        };
 
     let repositories = crate::config::load_repositories_from_project()?;
-    let repo_manager = RepositoryManager::from_definitions(repositories);
-
-    // Create SourcesManager from marketplace-based repositories for PackageResolver
-    let sources_manager = install_utils::create_sources_manager_from_repositories(&repo_manager)
-        .map_err(|e| CliError::Config(format!("Failed to create sources manager: {}", e)))?;
-
-    // Determine effective depth limit and skip_transitive flag from config then CLI override
-    let (config_depth, config_skip_transitive) = if project_file_result.found {
-        SkillProjectToml::load_from_file(&project_file_path)
-            .ok()
-            .and_then(|p| p.tool)
-            .and_then(|t| t.fastskill)
-            .map(|cfg| (cfg.install_depth, cfg.skip_transitive))
-            .unwrap_or((5, false))
+    let mut repo_manager = RepositoryManager::from_definitions(repositories);
+    if args.offline || config_offline {
+        let vendor = VendorDir::open(&vendor_dir_for(&project_file_path))
+            .map_err(|e| CliError::Config(e.to_string()))?;
+        if narrate {
+            println!(
+                "Offline: installing from {} ({} vendored skills)",
+                vendor.root().display(),
+                vendor.skills().len()
+            );
+        }
+        repo_manager = repo_manager.offline(vendor);
+    }
+
+    // Create SourcesManager from marketplace-based repositories for PackageResolver.
+    // Offline installs never reach a repository, so they need neither.
+    let sources_manager = if repo_manager.vendor().is_some() {
+        None
     } else {
-        (5, false)
+        install_utils::create_sources_manager_from_repositories(&repo_manager)
+            .map_err(|e| CliError::Config(format!("Failed to create sources manager: {}", e)))?
+    };
+    let (strategy, strategy_source) = if args.lock {
+        (ResolutionStrategy::Locked, "--lock")
+    } else if let Some(strategy) = resolution_override {
+        (strategy, "--resolution")
+    } else if config_resolution.is_default() {
+        (config_resolution, "default")
+    } else {
+        (config_resolution, "[tool.fastskill] resolution")
     };
 
     // Build recursive install config
     let recursive_config =
         RecursiveInstallConfig::from_args_and_config(&args, config_depth, config_skip_transitive);
 
-    // T027: Load from skill-project.toml or lock file
+    // T027: Load from skill-project.toml or lock file. Manifest entries are
+    // kept so transitive dependencies can be re-resolved once newly installed
+    // skills' own manifests are on disk.
+    let mut root_entries: Option<Vec<SkillEntry>> = None;
     let skills_to_install: Vec<SkillInstallItem> = if args.lock {
         // Lock file already checked above, so it exists
         let lock = ProjectSkillsLock::load_from_file(&lock_path)
             .map_err(|e| CliError::Config(format!("Failed to load lock file: {}", e)))?;
 
-        println!("Using lock file ({} skills)", lock.skills.len());
+        if narrate {
+            println!("Using lock file ({} skills)", lock.skills.len());
+        }
 
         // Convert lock entries to installable items
         lock.skills
@@ -298,16 +860,30 @@ pub async fn execute_install(args: InstallArgs) -> CliResult<()> {
                     id: locked.id,
                     origin: locked.origin,
                     groups: locked.groups,
+                    extras: locked.extras,
                 },
                 depth: locked.depth,
                 parent_skill: locked.parent_skill,
+                requirements: Vec::new(),
             })
             .collect()
     } else {
         // Load from skill-project.toml (manifest already required above when !args.lock)
-        let project = SkillProjectToml::load_from_file(&project_file_path)
+        let mut project = SkillProjectToml::load_from_file(&project_file_path)
             .map_err(|e| CliError::Config(format!("Failed to load skill-project.toml: {}", e)))?;
 
+        // A workspace root installs the dependencies of all its members
+        if let Some(workspace) = &workspace {
+            let dependencies = workspace.dependencies().map_err(CliError::Config)?;
+            if narrate {
+                println!(
+                    "Workspace: {} members, shared skills.lock",
+                    workspace.members.len()
+                );
+            }
+            project.dependencies = Some(DependenciesSection { dependencies });
+        }
+
         // Validate context
         let context = project_file_result.context;
         project.validate_for_context(context).map_err(|e| {
@@ -327,100 +903,269 @@ pub async fn execute_install(args: InstallArgs) -> CliResult<()> {
         // Sort entries by ID for deterministic output
         entries.sort_by(|a, b| a.id.as_str().cmp(b.id.as_str()));
 
-        // Recursively resolve transitive dependencies unless disabled
-        let resolved_items = if recursive_config.skip_transitive {
-            entries
-                .into_iter()
-                .map(|entry| SkillInstallItem {
-                    entry,
-                    depth: 0,
-                    parent_skill: None,
-                })
-                .collect()
-        } else {
-            let mut resolver = DependencyResolver::new(recursive_config.max_depth);
-            resolver
-                .resolve_dependencies(entries, &skills_dir)
-                .await
-                .map_err(|e| CliError::Config(format!("Dependency resolution failed: {}", e)))?
-        };
-
-        // Apply group filters to resolved dependencies
-        let filtered_items: Vec<SkillInstallItem> = if recursive_config.skip_transitive {
-            resolved_items
-        } else {
-            let ex = recursive_config.exclude_groups.as_deref();
-            let on = recursive_config.only_groups.as_deref();
-            resolved_items
-                .into_iter()
-                .filter(|item| group_passes_filter(&item.entry.groups, ex, on))
-                .collect()
-        };
-
-        filtered_items
+        root_entries = Some(entries.clone());
+        resolve_install_items(entries, &recursive_config, &skills_dir).await?
     };
 
-    println!("Found {} skills to install", skills_to_install.len());
+    if narrate {
+        println!("Found {} skills to install", skills_to_install.len());
+    }
 
     if skills_to_install.is_empty() {
-        println!(
+        if args.dry_run {
+            ChangePlan::new("install", "skills.lock", Vec::new()).print(args.json);
+            return Ok(());
+        }
+        messages::status!(
             "{}",
             messages::info("No skills to install (filtered by groups)")
         );
         return Ok(());
     }
 
+    // Settle each repository skill's version with the resolution strategy, then
+    // pin it so the install fetches exactly what the plan shows.
+    let locked_versions: HashMap<String, String> = if lock_path.exists() {
+        ProjectSkillsLock::load_from_file(&lock_path)
+            .map_err(|e| CliError::Config(format!("Failed to load lock file: {}", e)))?
+            .skills
+            .into_iter()
+            .map(|entry| (entry.id, entry.resolved.version))
+            .collect()
+    } else {
+        HashMap::new()
+    };
+    let mut package_resolver = None;
+
     // Ensure skills directory exists
-    fs::create_dir_all(&skills_dir)
-        .map_err(|e| CliError::Config(format!("Failed to create skills directory: {}", e)))?;
+    if !args.dry_run {
+        fs::create_dir_all(&skills_dir)
+            .map_err(|e| CliError::Config(format!("Failed to create skills directory: {}", e)))?;
+    }
 
-    // Install each skill
+    // Install in rounds: a skill's own dependencies are only known once its
+    // skill-project.toml is on disk, so after each round the manifest is
+    // re-resolved and any newly discovered dependencies are installed next.
+    // The number of rounds is bounded by the depth limit.
+    let mut pending = skills_to_install;
+    let mut attempted: HashSet<String> = HashSet::new();
+    let mut installed_versions: HashMap<String, String> = HashMap::new();
     let mut installed_skills = Vec::new();
     let mut failed_skills = Vec::new();
-    for item in &skills_to_install {
-        println!("  Installing {} (depth {})...", item.entry.id, item.depth);
-        match install_utils::install_skill_from_entry(
-            &service,
-            item.entry.clone(),
-            sources_manager.as_ref(),
-        )
-        .await
-        {
-            Ok(skill_def) => {
-                installed_skills.push((
-                    skill_def,
-                    item.entry.groups.clone(),
-                    item.depth,
-                    item.parent_skill.clone(),
-                ));
-                println!(
-                    "  {}",
-                    messages::ok(&format!("Installed {}", item.entry.id))
-                );
+    let mut skipped_skills = Vec::new();
+    let progress = InstallProgress::new(ProgressMode::detect(args.json_progress));
+    let mut round = 0;
+    loop {
+        round += 1;
+
+        let needs_versions = pending
+            .iter()
+            .any(|item| matches!(item.entry.origin, Origin::Repository { .. }));
+        if package_resolver.is_none() && needs_versions && sources_manager.is_some() {
+            package_resolver = Some(install_utils::create_package_resolver().await?);
+        }
+        let decisions = decide_versions(&pending, strategy, &locked_versions, |repo, skill| {
+            if let Some(vendor) = repo_manager.vendor() {
+                return Some(vendor.versions(skill));
             }
-            Err(e) => {
-                let context = match &item.parent_skill {
-                    Some(parent) => format!(" (required by {})", parent),
-                    None => String::new(),
+            package_resolver.as_ref().map(|resolver| {
+                resolver
+                    .get_available_versions(skill)
+                    .into_iter()
+                    .filter(|c| c.source_name == repo)
+                    .map(|c| c.version.clone())
+                    .collect()
+            })
+        });
+        let unresolved: Vec<&VersionDecision> = decisions.iter().filter(|d| !d.resolved).collect();
+
+        // The plan covers what is resolvable without fetching: dependencies of
+        // skills that are not installed yet only appear after the install.
+        if args.plan {
+            print_plan(strategy, strategy_source, &decisions);
+            if !unresolved.is_empty() {
+                return Err(CliError::Config(format!(
+                    "{} skill(s) could not be resolved with resolution = {}",
+                    unresolved.len(),
+                    strategy
+                )));
+            }
+            return Ok(());
+        }
+        if !unresolved.is_empty() {
+            for d in &unresolved {
+                eprintln!("  {}", messages::error(&format!("{}: {}", d.id, d.reason)));
+            }
+            return Err(CliError::Config(format!(
+                "{} skill(s) could not be resolved with resolution = {}",
+                unresolved.len(),
+                strategy
+            )));
+        }
+        // Like --plan, the dry run covers what resolves without fetching.
+        if args.dry_run {
+            let mut changes = Vec::with_capacity(decisions.len());
+            for (item, decision) in pending.iter().zip(&decisions) {
+                let selected = decision.selected.as_deref();
+                let size = match (repo_manager.vendor(), &item.entry.origin) {
+                    (Some(vendor), Origin::Repository { skill, .. }) => vendor
+                        .find(skill, selected)
+                        .and_then(|s| vendor.archive_size(s).ok()),
+                    _ => service
+                        .download_size(&item.entry.origin, selected)
+                        .await
+                        .unwrap_or_else(|e| {
+                            tracing::debug!("No download size for {}: {}", item.entry.id, e);
+                            None
+                        }),
                 };
-                eprintln!(
-                    "  {}",
-                    messages::error(&format!(
-                        "Failed to install {}{}: {}",
-                        item.entry.id, context, e
-                    ))
+                changes.push(
+                    PlannedChange::install(
+                        item.entry.id.clone(),
+                        locked_versions.get(&item.entry.id).cloned(),
+                        decision.selected.clone(),
+                    )
+                    .with_download_size(size),
                 );
-                failed_skills.push(item.entry.id.to_string());
+            }
+            ChangePlan::new("install", "skills.lock", changes).print(args.json);
+            return Ok(());
+        }
+        let round_items: Vec<SkillInstallItem> = pending
+            .into_iter()
+            .zip(&decisions)
+            .map(|(mut item, decision)| {
+                if let (Origin::Repository { version, .. }, Some(selected)) =
+                    (&mut item.entry.origin, &decision.selected)
+                {
+                    if let Ok(pin) = VersionConstraint::parse(&format!("={}", selected)) {
+                        *version = Some(pin);
+                    }
+                }
+                item
+            })
+            .collect();
+        attempted.extend(round_items.iter().map(|item| item.entry.id.clone()));
+        progress.emit(InstallEvent::Resolved {
+            skills: round_items.len(),
+        });
+
+        let mut results =
+            install_concurrently(round_items.as_slice(), args.jobs, args.fail_fast, |item| {
+                let service = &service;
+                let sources_manager = sources_manager.as_ref();
+                let vendor = repo_manager.vendor();
+                let locked_version = locked_versions.get(&item.entry.id);
+                let on_stage = progress.observer(&item.entry.id);
+                async move {
+                    match vendor {
+                        Some(vendor) => {
+                            install_utils::install_from_vendor_with_progress(
+                                service,
+                                vendor,
+                                &item.entry,
+                                locked_version.map(String::as_str),
+                                &on_stage,
+                            )
+                            .await
+                        }
+                        None => {
+                            install_utils::install_skill_from_entry_with_progress(
+                                service,
+                                item.entry.clone(),
+                                sources_manager,
+                                &on_stage,
+                            )
+                            .await
+                        }
+                    }
+                }
+            });
+
+        while let Some((item, result)) = results.next().await {
+            match result {
+                Some(Ok(skill_def)) => {
+                    installed_versions.insert(item.entry.id.clone(), skill_def.version.clone());
+                    progress.emit(InstallEvent::Installed {
+                        skill: item.entry.id.clone(),
+                        version: skill_def.version.clone(),
+                        depth: item.depth,
+                    });
+                    installed_skills.push((
+                        skill_def,
+                        item.entry.groups.clone(),
+                        item.entry.extras.clone(),
+                        item.depth,
+                        item.parent_skill.clone(),
+                    ));
+                }
+                Some(Err(e)) => {
+                    progress.emit(InstallEvent::Failed {
+                        skill: item.entry.id.clone(),
+                        required_by: item.parent_skill.clone(),
+                        error: e.to_string(),
+                    });
+                    failed_skills.push(item.entry.id.to_string());
+                }
+                None => {
+                    progress.emit(InstallEvent::Skipped {
+                        skill: item.entry.id.clone(),
+                    });
+                    skipped_skills.push(item.entry.id.to_string());
+                }
             }
         }
+        drop(results);
+
+        let Some(entries) = root_entries.clone() else {
+            break;
+        };
+        if recursive_config.skip_transitive
+            || round >= recursive_config.max_depth
+            || (args.fail_fast && !failed_skills.is_empty())
+        {
+            break;
+        }
+        let items = resolve_install_items(entries, &recursive_config, &skills_dir).await?;
+        let unmet = unmet_by_installed(&items, &installed_versions);
+        if !unmet.is_empty() {
+            progress.suspend(|| {
+                for report in &unmet {
+                    eprintln!("  {}", messages::error(report));
+                }
+            });
+            return Err(CliError::Config(format!(
+                "{} installed skill(s) conflict with newly discovered dependencies",
+                unmet.len()
+            )));
+        }
+        pending = items
+            .into_iter()
+            .filter(|item| !attempted.contains(&item.entry.id))
+            .collect();
+        if pending.is_empty() {
+            break;
+        }
+        progress.suspend(|| {
+            println!(
+                "Found {} more transitive dependencies to install",
+                pending.len()
+            )
+        });
     }
+    progress.emit(InstallEvent::Finished {
+        installed: installed_skills.len(),
+        failed: failed_skills.len(),
+        skipped: skipped_skills.len(),
+    });
 
     // Update lock file with all installed skills including depth and parent info
-    for (skill_def, groups, depth, parent_skill) in installed_skills {
+    for (skill_def, groups, extras, depth, parent_skill) in installed_skills {
         manifest_utils::update_lock_file_with_depth(
             &lock_path,
             &skill_def,
             groups,
+            extras,
             depth,
             parent_skill,
         )
@@ -428,19 +1173,28 @@ pub async fn execute_install(args: InstallArgs) -> CliResult<()> {
     }
 
     println!();
-    println!("{}", messages::ok("Installation complete"));
+    messages::status!("{}", messages::ok("Installation complete"));
     println!("   Updated skills.lock");
 
     // Return error if any skills failed to install
     if !failed_skills.is_empty() {
-        return Err(CliError::Config(format!(
-            "Failed to install {} skill(s): {}",
-            failed_skills.len(),
-            failed_skills.join(", ")
+        return Err(CliError::Config(failure_summary(
+            &failed_skills,
+            &skipped_skills,
         )));
     }
 
+    install_utils::check_installed_peers(&service, args.strict_peers).await?;
+
     let auto_reindex = crate::config_file::load_auto_reindex_config();
+    if crate::utils::reindex_utils::auto_reindex_enabled(
+        &service,
+        args.reindex,
+        args.no_reindex,
+        auto_reindex,
+    ) {
+        progress.emit(InstallEvent::Indexing);
+    }
     crate::utils::reindex_utils::maybe_auto_reindex(
         &service,
         "install",
@@ -462,8 +1216,11 @@ pub async fn execute_install(args: InstallArgs) -> CliResult<()> {
 )]
 mod tests {
     use super::*;
+    use fastskill_core::core::dependency_resolver::VersionRequirement;
     use fastskill_core::test_utils::DirGuard;
     use std::fs;
+    use std::sync::atomic::AtomicUsize;
+    use std::time::Duration;
     use tempfile::TempDir;
 
     #[tokio::test]
@@ -487,6 +1244,16 @@ mod tests {
             depth: None,
             reindex: false,
             no_reindex: false,
+            jobs: DEFAULT_INSTALL_JOBS as usize,
+            fail_fast: false,
+            plan: false,
+            dry_run: false,
+            json: false,
+            json_progress: false,
+            resolution: None,
+            strict_peers: false,
+            offline: false,
+            link_mode: None,
         };
 
         let result = execute_install(args).await;
@@ -504,6 +1271,90 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_execute_install_json_requires_dry_run() {
+        let args = InstallArgs {
+            without: None,
+            only: None,
+            lock: false,
+            depth: None,
+            reindex: false,
+            no_reindex: false,
+            jobs: DEFAULT_INSTALL_JOBS as usize,
+            fail_fast: false,
+            plan: false,
+            dry_run: false,
+            json: true,
+            json_progress: false,
+            resolution: None,
+            strict_peers: false,
+            offline: false,
+            link_mode: None,
+        };
+
+        let result = execute_install(args).await;
+        assert!(
+            matches!(&result, Err(CliError::Validation(msg)) if msg.contains("--dry-run")),
+            "{:?}",
+            result
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_install_json_progress_rejects_dry_run() {
+        let mut map = HashMap::new();
+        map.insert("json-progress".to_string(), ArgValue::Bool(true));
+        map.insert("dry-run".to_string(), ArgValue::Bool(true));
+        let args = InstallArgs::from_arg_value_map(&map);
+        assert!(args.json_progress);
+
+        let result = execute_install(args).await;
+        assert!(
+            matches!(&result, Err(CliError::Validation(msg)) if msg.contains("--json-progress")),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_install_workspace_root_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let member = root.join("app");
+        std::fs::create_dir_all(&member).unwrap();
+        std::fs::write(
+            root.join(WORKSPACE_FILE),
+            "[workspace]\nmembers = [\"app\"]\n",
+        )
+        .unwrap();
+        std::fs::write(member.join("skill-project.toml"), "[dependencies]\n").unwrap();
+
+        let err = install_workspace(root).unwrap_err();
+        assert!(
+            matches!(&err, CliError::Config(msg) if msg.contains("has no skill-project.toml")),
+            "{:?}",
+            err
+        );
+
+        std::fs::write(
+            root.join("skill-project.toml"),
+            "[dependencies]\n\n[tool.fastskill]\nskills_directory = \".skills\"\n",
+        )
+        .unwrap();
+        let workspace = install_workspace(root).unwrap().unwrap();
+        assert_eq!(workspace.members.len(), 1);
+
+        let err = install_workspace(&member).unwrap_err();
+        assert!(
+            matches!(&err, CliError::Validation(msg) if msg.contains("Run 'fastskill install' from the workspace root")),
+            "{:?}",
+            err
+        );
+
+        let outside = TempDir::new().unwrap();
+        assert!(install_workspace(outside.path()).unwrap().is_none());
+    }
+
     #[tokio::test]
     async fn test_execute_install_with_lock_file_not_found() {
         // Use a shared mutex to serialize directory changes across parallel tests
@@ -532,6 +1383,16 @@ mod tests {
             depth: None,
             reindex: false,
             no_reindex: false,
+            jobs: DEFAULT_INSTALL_JOBS as usize,
+            fail_fast: false,
+            plan: false,
+            dry_run: false,
+            json: false,
+            json_progress: false,
+            resolution: None,
+            strict_peers: false,
+            offline: false,
+            link_mode: None,
         };
 
         let result = execute_install(args).await;
@@ -548,6 +1409,52 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_execute_install_offline_requires_vendor_dir() {
+        let _lock = fastskill_core::test_utils::DIR_MUTEX
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+
+        let temp_dir = TempDir::new().unwrap();
+        let original_dir = std::env::current_dir().ok();
+
+        let _guard = DirGuard(original_dir);
+
+        std::env::set_current_dir(temp_dir.path()).unwrap();
+
+        fs::write(
+            temp_dir.path().join("skill-project.toml"),
+            "[dependencies]\n\n[tool.fastskill]\nskills_directory = \".claude/skills\"\noffline = true\n",
+        )
+        .unwrap();
+
+        let args = InstallArgs {
+            without: None,
+            only: None,
+            lock: false,
+            depth: None,
+            reindex: false,
+            no_reindex: false,
+            jobs: DEFAULT_INSTALL_JOBS as usize,
+            fail_fast: false,
+            plan: false,
+            dry_run: false,
+            json: false,
+            json_progress: false,
+            resolution: None,
+            strict_peers: false,
+            offline: false,
+            link_mode: None,
+        };
+
+        let result = execute_install(args).await;
+        assert!(
+            matches!(&result, Err(CliError::Config(msg)) if msg.contains("fastskill vendor")),
+            "Expected missing vendor error, got: {:?}",
+            result
+        );
+    }
+
     #[tokio::test]
     async fn test_execute_install_with_empty_manifest() {
         // Use a shared mutex to serialize directory changes across parallel tests
@@ -573,6 +1480,16 @@ mod tests {
             depth: None,
             reindex: false,
             no_reindex: false,
+            jobs: DEFAULT_INSTALL_JOBS as usize,
+            fail_fast: false,
+            plan: false,
+            dry_run: false,
+            json: false,
+            json_progress: false,
+            resolution: None,
+            strict_peers: false,
+            offline: false,
+            link_mode: None,
         };
 
         // Should succeed with empty manifest (no skills to install) or fail on service/repos; shouldn't panic
@@ -621,10 +1538,245 @@ test-skill = { origin = { type = "local", path = "source-skill" } }
             depth: None,
             reindex: false,
             no_reindex: false,
+            jobs: DEFAULT_INSTALL_JOBS as usize,
+            fail_fast: false,
+            plan: false,
+            dry_run: false,
+            json: false,
+            json_progress: false,
+            resolution: None,
+            strict_peers: false,
+            offline: false,
+            link_mode: None,
         };
 
         let result = execute_install(args).await;
         // May succeed or fail depending on lock file, but shouldn't panic
         assert!(result.is_ok() || result.is_err());
     }
+
+    fn repo_item(id: &str, constraint: Option<&str>) -> SkillInstallItem {
+        SkillInstallItem {
+            entry: SkillEntry {
+                id: id.to_string(),
+                origin: Origin::Repository {
+                    repo: "team".to_string(),
+                    skill: id.to_string(),
+                    version: constraint.map(|c| VersionConstraint::parse(c).unwrap()),
+                },
+                groups: Vec::new(),
+                extras: Vec::new(),
+            },
+            depth: 0,
+            parent_skill: None,
+            requirements: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_decide_versions_per_strategy() {
+        let items = vec![
+            repo_item("web", Some("^1.2")),
+            repo_item("ghost", None),
+            SkillInstallItem {
+                entry: SkillEntry {
+                    id: "local-skill".to_string(),
+                    origin: Origin::Local {
+                        path: "skills/local".into(),
+                        editable: false,
+                    },
+                    groups: Vec::new(),
+                    extras: Vec::new(),
+                },
+                depth: 1,
+                parent_skill: Some("web".to_string()),
+                requirements: Vec::new(),
+            },
+        ];
+        let available = |_: &str, skill: &str| {
+            Some(match skill {
+                "web" => vec![
+                    "1.1.0".to_string(),
+                    "1.2.0".to_string(),
+                    "1.4.0".to_string(),
+                ],
+                _ => Vec::new(),
+            })
+        };
+        let locked = HashMap::from([("web".to_string(), "1.2.0".to_string())]);
+
+        let max = decide_versions(&items, ResolutionStrategy::Maximum, &locked, available);
+        assert_eq!(max[0].selected.as_deref(), Some("1.4.0"));
+        assert!(!max[1].resolved);
+        assert!(max[2].resolved && max[2].selected.is_none());
+        assert_eq!(max[2].parent_skill.as_deref(), Some("web"));
+
+        let min = decide_versions(&items, ResolutionStrategy::Minimum, &locked, available);
+        assert_eq!(min[0].selected.as_deref(), Some("1.2.0"));
+        assert!(min[0].reason.contains("oldest of 2"));
+
+        let pinned = decide_versions(
+            &items[..1],
+            ResolutionStrategy::Locked,
+            &HashMap::new(),
+            available,
+        );
+        assert!(!pinned[0].resolved);
+        assert!(pinned[0].reason.contains("not in skills.lock"));
+        let pinned = decide_versions(&items[..1], ResolutionStrategy::Locked, &locked, available);
+        assert_eq!(pinned[0].selected.as_deref(), Some("1.2.0"));
+    }
+
+    #[test]
+    fn test_decide_versions_reports_conflicting_chains() {
+        let requirement = |constraint: &str, chain: &[&str]| VersionRequirement {
+            origin: Origin::Repository {
+                repo: "team".to_string(),
+                skill: "web".to_string(),
+                version: Some(VersionConstraint::parse(constraint).unwrap()),
+            },
+            chain: chain.iter().map(|s| s.to_string()).collect(),
+        };
+        let mut item = repo_item("web", Some("^1"));
+        item.entry.origin = Origin::Repository {
+            repo: "team".to_string(),
+            skill: "web".to_string(),
+            version: Some(
+                VersionConstraint::parse("^1")
+                    .unwrap()
+                    .intersect(&VersionConstraint::parse("^2").unwrap()),
+            ),
+        };
+        item.requirements = vec![
+            requirement("^1", &["app", "web"]),
+            requirement("^2", &["app", "tools", "web"]),
+        ];
+        let available = |_: &str, _: &str| Some(vec!["1.4.0".to_string(), "2.1.0".to_string()]);
+
+        let decisions = decide_versions(
+            &[item],
+            ResolutionStrategy::Maximum,
+            &HashMap::new(),
+            available,
+        );
+        assert!(!decisions[0].resolved);
+        let reason = &decisions[0].reason;
+        assert!(
+            reason.contains("no version satisfies all requirements"),
+            "{}",
+            reason
+        );
+        assert!(
+            reason.contains("requires ^1 from 'team/web', via skill-project.toml -> app -> web"),
+            "{}",
+            reason
+        );
+        assert!(
+            reason.contains(
+                "requires ^2 from 'team/web', via skill-project.toml -> app -> tools -> web"
+            ),
+            "{}",
+            reason
+        );
+    }
+
+    #[test]
+    fn test_unmet_by_installed_flags_narrowed_constraint() {
+        let items = vec![repo_item("web", Some("^2")), repo_item("docs", Some("^1"))];
+        let installed = HashMap::from([
+            ("web".to_string(), "1.4.0".to_string()),
+            ("docs".to_string(), "1.0.0".to_string()),
+        ]);
+        let unmet = unmet_by_installed(&items, &installed);
+        assert_eq!(unmet.len(), 1);
+        assert!(unmet[0].starts_with("web: installed version 1.4.0 does not satisfy ^2"));
+    }
+
+    #[tokio::test]
+    async fn test_install_concurrently_keeps_manifest_order() {
+        let items: Vec<u64> = (0..6).collect();
+        let running = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let results: Vec<_> = install_concurrently(&items, 3, false, |&i| {
+            let (running, peak) = (&running, &peak);
+            async move {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                // Later skills finish first
+                tokio::time::sleep(Duration::from_millis(10 * (6 - i))).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                Ok::<_, String>(i)
+            }
+        })
+        .collect()
+        .await;
+
+        let order: Vec<u64> = results.iter().map(|(item, _)| **item).collect();
+        assert_eq!(order, items);
+        for (item, result) in &results {
+            assert_eq!(result.as_ref().unwrap().as_ref().unwrap(), *item);
+        }
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_install_concurrently_failure_does_not_cancel_others() {
+        let items = ["a", "broken", "c", "d"];
+        let results: Vec<_> = install_concurrently(&items, 2, false, |&name| async move {
+            if name == "broken" {
+                return Err(format!("{} failed", name));
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            Ok(name)
+        })
+        .collect()
+        .await;
+
+        assert_eq!(results.len(), items.len());
+        for (name, result) in results {
+            match result {
+                Some(Err(e)) => assert_eq!(*name, "broken", "{}", e),
+                Some(Ok(installed)) => assert_eq!(installed, *name),
+                None => panic!("{} was skipped without --fail-fast", name),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_install_concurrently_fail_fast_skips_unstarted() {
+        let items = ["broken", "slow", "a", "b", "c"];
+        let started = std::sync::Mutex::new(Vec::new());
+        let results: Vec<_> = install_concurrently(&items, 2, true, |&name| {
+            let started = &started;
+            async move {
+                started.lock().unwrap().push(name);
+                if name == "broken" {
+                    // Fails once both installs of the first batch are running
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                    return Err(format!("{} failed", name));
+                }
+                tokio::time::sleep(Duration::from_millis(30)).await;
+                Ok(name)
+            }
+        })
+        .collect()
+        .await;
+
+        // The install already running when the failure came finishes
+        assert!(matches!(results[0].1, Some(Err(_))));
+        assert!(matches!(results[1].1, Some(Ok("slow"))));
+        let skipped: Vec<String> = results
+            .iter()
+            .filter(|(_, result)| result.is_none())
+            .map(|(name, _)| name.to_string())
+            .collect();
+        assert_eq!(skipped, ["a", "b", "c"]);
+        let mut started = started.into_inner().unwrap();
+        started.sort_unstable();
+        assert_eq!(started, ["broken", "slow"]);
+        assert_eq!(
+            failure_summary(&["broken".to_string()], &skipped),
+            "Failed to install 1 skill(s): broken; skipped 3 after the failure"
+        );
+    }
 }
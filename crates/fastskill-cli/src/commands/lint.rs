@@ -0,0 +1,355 @@
+//! Lint command - style checks for SKILL.md, with safe autofixes
+//!
+//! Thin wrapper over `fastskill_core::validation::lint`. Prints findings as
+//! text, JSON or GitHub Actions annotations and exits non-zero when errors
+//! remain (or warnings, with `--strict`), so it can gate CI.
+
+use crate::error::{CliError, CliResult};
+use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
+use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
+use cli_framework::spec::command_tree::CommandSpec;
+use cli_framework::spec::value::ArgValue;
+use fastskill_core::validation::lint::{
+    lint_skill, LintOptions, SkillLint, DEFAULT_MAX_LINE_LENGTH,
+};
+use fastskill_core::validation::RuleSeverity;
+use fastskill_core::{FastSkillService, SkillId};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// How findings are printed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LintFormat {
+    /// `path:line: severity [rule] message`
+    #[default]
+    Text,
+    Json,
+    /// `::error file=...,line=...` workflow commands for GitHub Actions
+    Github,
+}
+
+impl LintFormat {
+    fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Some(LintFormat::Text),
+            "json" => Some(LintFormat::Json),
+            "github" => Some(LintFormat::Github),
+            _ => None,
+        }
+    }
+}
+
+/// Arguments for `lint`
+#[derive(Debug, Clone)]
+pub struct LintArgs {
+    /// Installed skill ids or skill directories; all installed skills when empty
+    pub skills: Vec<String>,
+    /// Apply the safe fixes to SKILL.md
+    pub fix: bool,
+    /// Output format
+    pub format: LintFormat,
+    /// Longest allowed prose line
+    pub max_line_length: usize,
+    /// Fail on warnings too
+    pub strict: bool,
+}
+
+impl IntoCommandSpec for LintArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Check SKILL.md style and optionally fix what is safe to fix",
+            syntax: Some("lint [SKILL|PATH]... [--fix] [--format text|json|github] [--strict]"),
+            category: Some("quality"),
+            args: vec![
+                ArgSpec {
+                    name: "skills",
+                    kind: ArgKind::Positional,
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Repeated,
+                    default: None,
+                    help: "Installed skill ids or skill directories (default: all installed)",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "fix",
+                    kind: ArgKind::Flag,
+                    long: Some("fix"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Rewrite SKILL.md with the safe fixes applied",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "format",
+                    kind: ArgKind::Option,
+                    long: Some("format"),
+                    value_type: ArgValueType::Enum(vec!["text", "json", "github"]),
+                    cardinality: Cardinality::Optional,
+                    default: Some(ArgValue::Enum("text".to_string())),
+                    help: "Output format: text, json, or github (Actions annotations)",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "max-line-length",
+                    kind: ArgKind::Option,
+                    long: Some("max-line-length"),
+                    value_type: ArgValueType::Int,
+                    cardinality: Cardinality::Optional,
+                    default: Some(ArgValue::Int(DEFAULT_MAX_LINE_LENGTH as i64)),
+                    help: "Longest allowed prose line in characters",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "strict",
+                    kind: ArgKind::Flag,
+                    long: Some("strict"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Exit with an error when warnings remain, not only errors",
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for LintArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        Self {
+            skills: match map.get("skills") {
+                Some(ArgValue::List(items)) => items
+                    .iter()
+                    .filter_map(|i| match i {
+                        ArgValue::Str(s) => Some(s.clone()),
+                        _ => None,
+                    })
+                    .collect(),
+                Some(ArgValue::Str(s)) => vec![s.clone()],
+                _ => vec![],
+            },
+            fix: matches!(map.get("fix"), Some(ArgValue::Bool(true))),
+            format: match map.get("format") {
+                Some(ArgValue::Enum(s)) | Some(ArgValue::Str(s)) => {
+                    LintFormat::parse(s).unwrap_or_default()
+                }
+                _ => LintFormat::Text,
+            },
+            max_line_length: match map.get("max-line-length") {
+                Some(ArgValue::Int(n)) if *n > 0 => *n as usize,
+                _ => DEFAULT_MAX_LINE_LENGTH,
+            },
+            strict: matches!(map.get("strict"), Some(ArgValue::Bool(true))),
+        }
+    }
+}
+
+/// JSON output of `lint --format json`
+#[derive(Debug, Serialize)]
+struct LintSummary<'a> {
+    skills: &'a [SkillLint],
+    errors: usize,
+    warnings: usize,
+    fixed: usize,
+}
+
+/// A skill directory given on the command line, or an installed skill's
+async fn resolve_skill_dir(service: &FastSkillService, arg: &str) -> CliResult<PathBuf> {
+    let path = Path::new(arg);
+    if path.join("SKILL.md").is_file() {
+        return Ok(path.to_path_buf());
+    }
+    let skill_id = SkillId::new(arg.to_string())
+        .map_err(|_| CliError::Validation(format!("Invalid skill ID format: {}", arg)))?;
+    let skill = service
+        .skill_manager()
+        .get_skill(&skill_id)
+        .await?
+        .ok_or_else(|| {
+            CliError::Validation(format!(
+                "Skill '{}' is not installed and is not a skill directory",
+                arg
+            ))
+        })?;
+    Ok(skill_dir(&skill.skill_file))
+}
+
+fn skill_dir(skill_file: &Path) -> PathBuf {
+    skill_file
+        .parent()
+        .map_or_else(|| PathBuf::from("."), Path::to_path_buf)
+}
+
+fn severity_label(severity: RuleSeverity) -> &'static str {
+    match severity {
+        RuleSeverity::Error => "error",
+        RuleSeverity::Warning => "warning",
+    }
+}
+
+/// Escape a GitHub Actions workflow command message
+fn escape_annotation(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a GitHub Actions workflow command property such as `file`
+fn escape_property(value: &str) -> String {
+    escape_annotation(value)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+fn print_text(lints: &[SkillLint]) {
+    for lint in lints {
+        for f in &lint.findings {
+            let location = match f.line {
+                Some(line) => format!("{}:{}", lint.skill_file.display(), line),
+                None => lint.skill_file.display().to_string(),
+            };
+            println!(
+                "{}: {} [{}] {}{}",
+                location,
+                severity_label(f.severity),
+                f.rule,
+                f.message,
+                if f.fixable { " (fixable)" } else { "" }
+            );
+        }
+    }
+}
+
+fn print_github(lints: &[SkillLint]) {
+    for lint in lints {
+        for f in &lint.findings {
+            let line = f.line.map(|l| format!(",line={}", l)).unwrap_or_default();
+            println!(
+                "::{} file={}{},title={}::{}",
+                severity_label(f.severity),
+                escape_property(&lint.skill_file.display().to_string()),
+                line,
+                f.rule,
+                escape_annotation(&f.message)
+            );
+        }
+    }
+}
+
+pub async fn execute_lint(service: &FastSkillService, args: LintArgs) -> CliResult<()> {
+    let dirs = if args.skills.is_empty() {
+        service
+            .skill_manager()
+            .list_skills()
+            .await?
+            .iter()
+            .map(|skill| skill_dir(&skill.skill_file))
+            .collect()
+    } else {
+        let mut dirs = Vec::with_capacity(args.skills.len());
+        for arg in &args.skills {
+            dirs.push(resolve_skill_dir(service, arg).await?);
+        }
+        dirs
+    };
+
+    let options = LintOptions {
+        max_line_length: args.max_line_length,
+        fix: args.fix,
+    };
+    let lints = dirs
+        .iter()
+        .map(|dir| lint_skill(dir, &options))
+        .collect::<Result<Vec<_>, _>>()?;
+    let errors: usize = lints.iter().map(SkillLint::errors).sum();
+    let warnings: usize = lints.iter().map(SkillLint::warnings).sum();
+    let fixed: usize = lints.iter().map(|l| l.fixed).sum();
+    let fixable = lints
+        .iter()
+        .flat_map(|l| &l.findings)
+        .filter(|f| f.fixable)
+        .count();
+
+    match args.format {
+        LintFormat::Json => {
+            let summary = LintSummary {
+                skills: &lints,
+                errors,
+                warnings,
+                fixed,
+            };
+            let json = serde_json::to_string_pretty(&summary)
+                .map_err(|e| CliError::Config(format!("Failed to encode JSON: {}", e)))?;
+            println!("{}", json);
+        }
+        LintFormat::Github => print_github(&lints),
+        LintFormat::Text => {
+            print_text(&lints);
+            println!(
+                "{} skill(s) checked: {} error(s), {} warning(s){}",
+                lints.len(),
+                errors,
+                warnings,
+                if args.fix {
+                    format!(", {} fixed", fixed)
+                } else {
+                    String::new()
+                }
+            );
+            if fixable > 0 && !args.fix {
+                println!(
+                    "{} of them can be fixed with `fastskill lint --fix`",
+                    fixable
+                );
+            }
+        }
+    }
+
+    if errors > 0 || (args.strict && warnings > 0) {
+        return Err(CliError::Validation(format!(
+            "lint found {} error(s) and {} warning(s)",
+            errors, warnings
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint_args_defaults() {
+        let args = LintArgs::from_arg_value_map(&HashMap::new());
+        assert!(args.skills.is_empty());
+        assert!(!args.fix);
+        assert_eq!(args.format, LintFormat::Text);
+        assert_eq!(args.max_line_length, DEFAULT_MAX_LINE_LENGTH);
+        assert!(!args.strict);
+    }
+
+    #[test]
+    fn test_lint_args_parse() {
+        let mut map = HashMap::new();
+        map.insert(
+            "skills".to_string(),
+            ArgValue::List(vec![ArgValue::Str("skills/pdf".to_string())]),
+        );
+        map.insert("fix".to_string(), ArgValue::Bool(true));
+        map.insert("format".to_string(), ArgValue::Enum("github".to_string()));
+        map.insert("max-line-length".to_string(), ArgValue::Int(100));
+        let args = LintArgs::from_arg_value_map(&map);
+        assert_eq!(args.skills, vec!["skills/pdf".to_string()]);
+        assert!(args.fix);
+        assert_eq!(args.format, LintFormat::Github);
+        assert_eq!(args.max_line_length, 100);
+    }
+
+    #[test]
+    fn test_escape_annotation() {
+        assert_eq!(escape_annotation("50%\nmore"), "50%25%0Amore");
+        assert_eq!(escape_property("a,b:c"), "a%2Cb%3Ac");
+    }
+}
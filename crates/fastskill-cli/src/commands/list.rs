@@ -5,7 +5,11 @@
 //!
 //! Requires skill-project.toml in the hierarchy. Uses three sources: installed skills (target
 //! folder), skill-project.toml [dependencies], and skills.lock. Outputs one table with flags
-//! for missing from folder, missing from lock, missing from manifest.
+//! for missing from folder, missing from lock, missing from manifest, and installed versions
+//! that differ from the lock or violate the manifest constraint.
+//!
+//! At a workspace root (skill-workspace.toml) the manifest side is the merged
+//! dependencies of every member, checked against the shared skills.lock.
 
 use crate::commands::common::validate_format_args;
 use crate::error::{manifest_required_message, CliError, CliResult};
@@ -14,9 +18,10 @@ use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality}
 use cli_framework::spec::command_tree::CommandSpec;
 use cli_framework::spec::value::ArgValue;
 use fastskill_core::core::lock::ProjectSkillsLock;
-use fastskill_core::core::manifest::SkillProjectToml;
+use fastskill_core::core::manifest::{DependencySpec, SkillProjectToml};
 use fastskill_core::core::origin::Origin;
-use fastskill_core::core::project::resolve_project_file;
+use fastskill_core::core::project::{resolve_project_file, Workspace, WORKSPACE_FILE};
+use fastskill_core::core::reconciliation::build_reconciliation_report;
 use fastskill_core::core::service::FastSkillService;
 use fastskill_core::output::ListRow;
 use fastskill_core::OutputFormat;
@@ -33,6 +38,9 @@ pub struct ListArgs {
     /// Shorthand for --format json
     pub json: bool,
 
+    /// Shorthand for --format grid
+    pub grid: bool,
+
     /// Show detailed information (version, manifest/lock/installed status, source path, type)
     pub details: bool,
 
@@ -76,6 +84,15 @@ impl IntoCommandSpec for ListArgs {
                     help: "Shorthand for --format json",
                     ..Default::default()
                 },
+                ArgSpec {
+                    name: "grid",
+                    kind: ArgKind::Flag,
+                    long: Some("grid"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Shorthand for --format grid",
+                    ..Default::default()
+                },
                 ArgSpec {
                     name: "details",
                     kind: ArgKind::Flag,
@@ -105,6 +122,7 @@ impl FromArgValueMap for ListArgs {
                 })
                 .and_then(parse_output_format),
             json: matches!(map.get("json"), Some(ArgValue::Bool(true))),
+            grid: matches!(map.get("grid"), Some(ArgValue::Bool(true))),
             details: matches!(map.get("details"), Some(ArgValue::Bool(true))),
             // skills_dir is omitted from the spec; rely on the global --skills-dir flag
             skills_dir: None,
@@ -119,6 +137,7 @@ fn origin_type_label(origin: &Origin) -> &'static str {
         Origin::Git { .. } => "git",
         Origin::Local { .. } => "local",
         Origin::ZipUrl { .. } => "zip-url",
+        Origin::Pack { .. } => "pack",
         Origin::Repository { .. } => "repository",
     }
 }
@@ -130,6 +149,7 @@ fn origin_location_label(origin: &Origin) -> String {
         Origin::Git { url, .. } => url.clone(),
         Origin::Local { path, .. } => path.display().to_string(),
         Origin::ZipUrl { url } => url.clone(),
+        Origin::Pack { path, skill } => format!("{} ({skill})", path.display()),
         Origin::Repository { repo, skill, .. } => format!("{repo}/{skill}"),
     }
 }
@@ -149,7 +169,17 @@ pub async fn execute_list(
     _global: bool,
 ) -> CliResult<()> {
     // Validate format arguments
-    let format = validate_format_args(&args.format, args.json)?;
+    if args.grid && (args.json || args.format.is_some()) {
+        return Err(CliError::Config(
+            "Error: --grid cannot be combined with --json or --format. Use one output selector."
+                .to_string(),
+        ));
+    }
+    let format = if args.grid {
+        OutputFormat::Grid
+    } else {
+        validate_format_args(&args.format, args.json)?
+    };
 
     // Require manifest: resolve from current directory
     let current_dir = env::current_dir()
@@ -168,11 +198,28 @@ pub async fn execute_list(
     // Load skill-project.toml and skills.lock
     let project = SkillProjectToml::load_from_file(&project_file_path)
         .map_err(|e| CliError::Config(format!("Failed to load skill-project.toml: {}", e)))?;
-    let manifest_ids: HashMap<String, ()> = project
-        .dependencies
-        .as_ref()
-        .map(|d| d.dependencies.keys().cloned().map(|k| (k, ())).collect())
-        .unwrap_or_default();
+    // At a workspace root, the manifest is every member's dependencies
+    let workspace = Workspace::discover(&current_dir)
+        .map_err(|e| CliError::Config(format!("Failed to load {}: {}", WORKSPACE_FILE, e)))?
+        .filter(|w| current_dir.canonicalize().is_ok_and(|dir| dir == w.root));
+    let dependencies = match &workspace {
+        Some(workspace) => workspace.dependencies().map_err(CliError::Config)?,
+        None => project
+            .dependencies
+            .map(|d| d.dependencies)
+            .unwrap_or_default(),
+    };
+    // Skill id -> declared version constraint (inline origins carry none)
+    let manifest_ids: HashMap<String, Option<String>> = dependencies
+        .into_iter()
+        .map(|(id, spec)| {
+            let constraint = match spec {
+                DependencySpec::Version(v) => Some(v),
+                DependencySpec::Inline { .. } => None,
+            };
+            (id, constraint)
+        })
+        .collect();
 
     let lock = if lock_path.exists() {
         ProjectSkillsLock::load_from_file(&lock_path)
@@ -202,6 +249,23 @@ pub async fn execute_list(
         )))
     })?;
 
+    // Installed versions that differ from the lock or violate the manifest
+    // constraint, with the version that was expected
+    let lock_versions: HashMap<String, String> = lock_map
+        .iter()
+        .map(|(id, (version, _, _))| (id.clone(), version.clone()))
+        .collect();
+    let mismatches: HashMap<String, String> = build_reconciliation_report(
+        &installed_skills,
+        &manifest_ids,
+        &lock_versions,
+        &service.config().skill_storage_path,
+    )?
+    .version_mismatches
+    .into_iter()
+    .map(|m| (m.id, m.locked_version))
+    .collect();
+
     // Build installed map with full skill definitions
     let installed_map: HashMap<String, fastskill_core::core::skill_manager::SkillDefinition> =
         installed_skills
@@ -263,6 +327,7 @@ pub async fn execute_list(
             let missing_from_folder = (in_manifest || in_lock) && !installed;
             let missing_from_lock = (in_manifest || installed) && !in_lock;
             let missing_from_manifest = (in_lock || installed) && !in_manifest;
+            let expected_version = mismatches.get(&id).cloned();
 
             ListRow {
                 id: id.clone(),
@@ -277,6 +342,8 @@ pub async fn execute_list(
                 missing_from_folder,
                 missing_from_lock,
                 missing_from_manifest,
+                version_mismatch: expected_version.is_some(),
+                expected_version,
             }
         })
         .collect();
@@ -316,6 +383,7 @@ mod tests {
         let args = ListArgs {
             format: Some(OutputFormat::Table),
             json: true,
+            grid: false,
             details: false,
             skills_dir: None,
         };
@@ -329,6 +397,29 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_execute_list_grid_conflict() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = ServiceConfig {
+            skill_storage_path: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        let service = FastSkillService::new(config).await.unwrap();
+
+        let args = ListArgs {
+            format: None,
+            json: true,
+            grid: true,
+            details: false,
+            skills_dir: None,
+        };
+
+        let result = execute_list(&service, args, false).await;
+        assert!(
+            matches!(result, Err(CliError::Config(msg)) if msg.contains("--grid cannot be combined"))
+        );
+    }
+
     #[tokio::test]
     async fn test_execute_list_no_manifest() {
         let _lock = fastskill_core::test_utils::DIR_MUTEX
@@ -370,6 +461,7 @@ mod tests {
         let args = ListArgs {
             format: None,
             json: false,
+            grid: false,
             details: false,
             skills_dir: None,
         };
@@ -425,6 +517,7 @@ skills_directory = ".claude/skills"
         let args = ListArgs {
             format: None,
             json: false,
+            grid: false,
             details: false,
             skills_dir: None,
         };
@@ -499,6 +592,7 @@ source = { path = ".claude/skills/test-skill" }
         let args = ListArgs {
             format: None,
             json: false,
+            grid: false,
             details: false,
             skills_dir: None,
         };
@@ -547,6 +641,7 @@ skills_directory = ".claude/skills"
         let args = ListArgs {
             format: None,
             json: false,
+            grid: false,
             details: false,
             skills_dir: None,
         };
@@ -595,6 +690,7 @@ skills_directory = ".claude/skills"
         let args = ListArgs {
             format: None,
             json: false,
+            grid: false,
             details: false,
             skills_dir: None,
         };
@@ -0,0 +1,289 @@
+//! Lock command: verify and refresh skills.lock
+//!
+//! Mirrors `cargo`/`poetry` lock semantics. Without flags the lock is brought
+//! back in line with skill-project.toml, keeping existing pins. `--update`
+//! also re-pins every entry to what is installed, `--check` only reports
+//! drift (nonzero exit for CI) and `--frozen` fails unless the lock is already
+//! exactly what a relock would write. The lock command never fetches; skills
+//! that are declared but not installed stay unlocked until `fastskill install`.
+
+use crate::error::{manifest_required_message, CliError, CliResult};
+use crate::utils::{manifest_utils, messages};
+use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
+use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
+use cli_framework::spec::command_tree::CommandSpec;
+use cli_framework::spec::value::ArgValue;
+use fastskill_core::core::lock::{
+    project_lock_path, relock, verify_lock, LockDrift, ProjectSkillsLock,
+};
+use fastskill_core::core::manifest::SkillProjectToml;
+use fastskill_core::core::project::resolve_project_file;
+use fastskill_core::core::service::FastSkillService;
+use std::collections::HashMap;
+use std::env;
+
+/// Verify or refresh skills.lock
+#[derive(Debug, Clone, Default)]
+pub struct LockArgs {
+    /// Report drift and exit nonzero without writing
+    pub check: bool,
+
+    /// Re-pin every entry to the installed version
+    pub update: bool,
+
+    /// Fail unless skills.lock is already up to date; never writes
+    pub frozen: bool,
+}
+
+impl IntoCommandSpec for LockArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Verify or refresh skills.lock against skill-project.toml",
+            syntax: Some("lock [--check | --update | --frozen]"),
+            category: Some("packages"),
+            args: vec![
+                ArgSpec {
+                    name: "check",
+                    kind: ArgKind::Flag,
+                    long: Some("check"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help:
+                        "Report drift between lock, manifest and installed skills; exit 1 on drift",
+                    conflicts_with: vec!["update", "frozen"],
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "update",
+                    kind: ArgKind::Flag,
+                    long: Some("update"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Re-pin every lock entry to the installed version and commit",
+                    conflicts_with: vec!["check", "frozen"],
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "frozen",
+                    kind: ArgKind::Flag,
+                    long: Some("frozen"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Fail if skills.lock would change; never writes",
+                    conflicts_with: vec!["check", "update"],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for LockArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        Self {
+            check: matches!(map.get("check"), Some(ArgValue::Bool(true))),
+            update: matches!(map.get("update"), Some(ArgValue::Bool(true))),
+            frozen: matches!(map.get("frozen"), Some(ArgValue::Bool(true))),
+        }
+    }
+}
+
+/// Execute the lock command
+pub async fn execute_lock(
+    service: &FastSkillService,
+    args: LockArgs,
+    global: bool,
+) -> CliResult<()> {
+    // Runtime validation guard (defense-in-depth alongside conflicts_with)
+    if [args.check, args.update, args.frozen]
+        .iter()
+        .filter(|f| **f)
+        .count()
+        > 1
+    {
+        return Err(CliError::Validation(
+            "--check, --update and --frozen cannot be used together".to_string(),
+        ));
+    }
+    if global {
+        return Err(CliError::Validation(
+            "fastskill lock works on the project skills.lock; global installs are tracked in global-skills.lock".to_string(),
+        ));
+    }
+
+    let current_dir = env::current_dir()
+        .map_err(|e| CliError::Config(format!("Failed to get current directory: {}", e)))?;
+    let project_file_result = resolve_project_file(&current_dir);
+    if !project_file_result.found {
+        return Err(CliError::Config(manifest_required_message().to_string()));
+    }
+    let project_file_path = project_file_result.path;
+    let lock_path = project_lock_path(&project_file_path);
+
+    let project = SkillProjectToml::load_from_file(&project_file_path)
+        .map_err(|e| CliError::Config(format!("Failed to load skill-project.toml: {}", e)))?;
+    let declared = project
+        .to_skill_entries()
+        .map_err(|e| CliError::Config(format!("Invalid skill-project.toml: {}", e)))?;
+
+    let lock_exists = lock_path.exists();
+    let lock = if lock_exists {
+        ProjectSkillsLock::load_from_file(&lock_path)
+            .map_err(|e| CliError::Config(format!("Failed to load skills.lock: {}", e)))?
+    } else {
+        ProjectSkillsLock::new_empty()
+    };
+
+    let installed = service.skill_manager().list_skills().await.map_err(|e| {
+        CliError::Service(fastskill_core::ServiceError::Custom(format!(
+            "Failed to list installed skills: {}",
+            e
+        )))
+    })?;
+
+    if args.check || args.frozen {
+        let drift = verify_lock(&lock, &declared, &installed);
+        let mut problems: Vec<String> = drift.iter().map(LockDrift::to_string).collect();
+        if !lock_exists {
+            problems.insert(0, format!("{} does not exist", lock_path.display()));
+        } else if args.frozen && drift.is_empty() {
+            // A drift-free lock can still differ from what relock would write
+            // (entry order, stale groups/extras); --frozen rejects that too.
+            let on_disk = std::fs::read_to_string(&lock_path)
+                .map_err(|e| CliError::Config(format!("Failed to read skills.lock: {}", e)))?;
+            let expected = relock(&lock, &declared, &installed, false)
+                .to_canonical_string()
+                .map_err(|e| CliError::Config(format!("Failed to serialize skills.lock: {}", e)))?;
+            if on_disk != expected {
+                problems
+                    .push("skills.lock is not in canonical form; run `fastskill lock`".to_string());
+            }
+        }
+
+        if problems.is_empty() {
+            messages::status!("{}", messages::ok("skills.lock is up to date"));
+            return Ok(());
+        }
+        for problem in &problems {
+            eprintln!("  {}", messages::error(problem));
+        }
+        let mode = if args.frozen { "--frozen" } else { "--check" };
+        return Err(CliError::Validation(format!(
+            "skills.lock is out of date ({} issue(s)); run `fastskill lock` or `fastskill install` ({})",
+            problems.len(),
+            mode
+        )));
+    }
+
+    let next = relock(&lock, &declared, &installed, args.update);
+    let before = lock
+        .to_canonical_string()
+        .map_err(|e| CliError::Config(format!("Failed to serialize skills.lock: {}", e)))?;
+    let after = next
+        .to_canonical_string()
+        .map_err(|e| CliError::Config(format!("Failed to serialize skills.lock: {}", e)))?;
+
+    if lock_exists && before == after {
+        messages::status!("{}", messages::ok("skills.lock is up to date"));
+    } else {
+        manifest_utils::write_project_lock(&lock_path, &next)
+            .map_err(|e| CliError::Config(e.to_string()))?;
+        for line in summarize_changes(&lock, &next) {
+            messages::status!("  {}", line);
+        }
+        messages::status!(
+            "{}",
+            messages::ok(&format!("Wrote {}", lock_path.display()))
+        );
+    }
+
+    // Whatever relock could not fix needs an install (or removal) to resolve.
+    for remaining in verify_lock(&next, &declared, &installed) {
+        eprintln!("  {}", messages::warning(&remaining.to_string()));
+    }
+
+    Ok(())
+}
+
+/// One line per added, removed or re-pinned entry, sorted by skill id.
+fn summarize_changes(before: &ProjectSkillsLock, after: &ProjectSkillsLock) -> Vec<String> {
+    let mut lines = Vec::new();
+    for entry in &after.skills {
+        match before.skills.iter().find(|e| e.id == entry.id) {
+            None => lines.push(format!("+ {} {}", entry.id, entry.resolved.version)),
+            Some(old) if old.resolved != entry.resolved => lines.push(format!(
+                "~ {} {} -> {}",
+                entry.id, old.resolved.version, entry.resolved.version
+            )),
+            Some(_) => {}
+        }
+    }
+    for entry in &before.skills {
+        if !after.skills.iter().any(|e| e.id == entry.id) {
+            lines.push(format!("- {} {}", entry.id, entry.resolved.version));
+        }
+    }
+    lines.sort_by(|a, b| a[2..].cmp(&b[2..]));
+    lines
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use fastskill_core::core::lock::ProjectLockedSkillEntry;
+    use fastskill_core::core::origin::{Origin, Resolved};
+    use fastskill_core::ServiceConfig;
+    use tempfile::TempDir;
+
+    fn entry(id: &str, version: &str) -> ProjectLockedSkillEntry {
+        ProjectLockedSkillEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            origin: Origin::ZipUrl {
+                url: format!("https://example.com/{id}.zip"),
+            },
+            resolved: Resolved {
+                version: version.to_string(),
+                commit_hash: None,
+                checksum: None,
+            },
+            dependencies: Vec::new(),
+            groups: Vec::new(),
+            extras: Vec::new(),
+            depth: 0,
+            parent_skill: None,
+        }
+    }
+
+    #[test]
+    fn test_summarize_changes_lists_added_removed_and_repinned() {
+        let mut before = ProjectSkillsLock::new_empty();
+        before.skills = vec![entry("alpha", "1.0.0"), entry("gone", "0.1.0")];
+        let mut after = ProjectSkillsLock::new_empty();
+        after.skills = vec![entry("alpha", "1.1.0"), entry("beta", "2.0.0")];
+
+        assert_eq!(
+            summarize_changes(&before, &after),
+            vec!["~ alpha 1.0.0 -> 1.1.0", "+ beta 2.0.0", "- gone 0.1.0"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_lock_rejects_combined_modes() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = ServiceConfig {
+            skill_storage_path: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        let service = FastSkillService::new(config).await.unwrap();
+        let args = LockArgs {
+            check: true,
+            frozen: true,
+            ..Default::default()
+        };
+        let err = execute_lock(&service, args, false).await.unwrap_err();
+        assert!(matches!(err, CliError::Validation(_)));
+    }
+}
@@ -0,0 +1,301 @@
+//! Migrate commands - move an existing installation to another backend or
+//! layout
+//!
+//! `migrate to-postgres` copies what a filesystem installation keeps on disk
+//! into the database used by `backend = "postgres"`: every skill's
+//! `metadata.json`, the project's skills.lock and the SQLite vector index.
+//! Skill files are not copied; they stay in the skills directory. Every
+//! write is an upsert, so the command can be re-run.
+//!
+//! `migrate scoped-ids` moves skills installed from a `scope/name` registry
+//! reference before ids were scoped to their `scope/name` id; see
+//! `fastskill_core::core::scope`.
+
+use crate::error::{CliError, CliResult};
+use crate::utils::messages;
+use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
+use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
+use cli_framework::spec::command_tree::CommandSpec;
+use cli_framework::spec::value::ArgValue;
+use fastskill_core::core::project::resolve_project_file;
+use fastskill_core::FastSkillService;
+use std::collections::HashMap;
+
+/// Arguments for `migrate to-postgres`
+#[derive(Debug, Clone)]
+pub struct MigrateToPostgresArgs {
+    /// Environment variable holding the connection URL
+    pub database_url_env: String,
+    /// Report what would be copied without connecting
+    pub dry_run: bool,
+}
+
+impl IntoCommandSpec for MigrateToPostgresArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Copy skill metadata, skills.lock and the vector index into Postgres",
+            syntax: Some("migrate to-postgres [--database-url-env VAR] [--dry-run]"),
+            category: Some("server"),
+            args: vec![
+                ArgSpec {
+                    name: "database-url-env",
+                    kind: ArgKind::Option,
+                    long: Some("database-url-env"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    help: "Environment variable holding the connection URL (default: DATABASE_URL)",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "dry-run",
+                    kind: ArgKind::Flag,
+                    long: Some("dry-run"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Show what would be copied without connecting to the database",
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for MigrateToPostgresArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        Self {
+            database_url_env: match map.get("database-url-env") {
+                Some(ArgValue::Str(s)) => s.clone(),
+                _ => "DATABASE_URL".to_string(),
+            },
+            dry_run: matches!(map.get("dry-run"), Some(ArgValue::Bool(true))),
+        }
+    }
+}
+
+pub async fn execute_migrate_to_postgres(
+    service: &FastSkillService,
+    args: MigrateToPostgresArgs,
+) -> CliResult<()> {
+    if args.database_url_env.trim().is_empty() {
+        return Err(CliError::Validation(
+            "--database-url-env must name an environment variable".to_string(),
+        ));
+    }
+    to_postgres(service, args).await
+}
+
+#[cfg(feature = "postgres")]
+async fn to_postgres(service: &FastSkillService, args: MigrateToPostgresArgs) -> CliResult<()> {
+    use fastskill_core::core::lock::{project_lock_path, ProjectSkillsLock};
+    use fastskill_core::core::scope::skill_dirs;
+    use fastskill_core::core::vector_index::{batches, VectorIndexService, DEFAULT_BATCH_SIZE};
+    use fastskill_core::storage::postgres::{project_key, read_metadata_file};
+    use fastskill_core::storage::PostgresStore;
+
+    let mut metadata = Vec::new();
+    for (skill_id, dir) in skill_dirs(&service.config().skill_storage_path)? {
+        if let Some(stored) = read_metadata_file(&dir)? {
+            metadata.push((skill_id, stored));
+        }
+    }
+
+    let current_dir = std::env::current_dir()
+        .map_err(|e| CliError::Config(format!("Failed to get current directory: {}", e)))?;
+    let project = resolve_project_file(&current_dir);
+    let lock_path = project
+        .found
+        .then(|| project_lock_path(&project.path))
+        .filter(|path| path.exists());
+    let lock = lock_path
+        .as_ref()
+        .map(|path| {
+            ProjectSkillsLock::load_from_file(path)
+                .map_err(|e| CliError::Config(format!("Failed to load skills.lock: {}", e)))
+        })
+        .transpose()?;
+
+    let index = service.vector_index_service();
+    let embeddings = match &index {
+        Some(index) => index.count_skills().await?,
+        None => 0,
+    };
+
+    println!("Skill metadata:  {}", metadata.len());
+    println!(
+        "skills.lock:     {}",
+        lock_path
+            .as_ref()
+            .map_or("none found".to_string(), |p| p.display().to_string())
+    );
+    println!(
+        "Embeddings:      {}",
+        if index.is_some() {
+            embeddings.to_string()
+        } else {
+            "skipped (no [tool.fastskill.embedding] configured)".to_string()
+        }
+    );
+    if args.dry_run {
+        messages::status!("{}", messages::info("Dry run; nothing was written"));
+        return Ok(());
+    }
+
+    let url = std::env::var(&args.database_url_env).map_err(|_| {
+        CliError::Config(format!(
+            "{} is not set; export the Postgres connection URL first",
+            args.database_url_env
+        ))
+    })?;
+    let store = PostgresStore::connect(&url).await?;
+
+    for (skill_id, stored) in &metadata {
+        store.put_metadata(skill_id, stored).await?;
+    }
+    if let (Some(path), Some(lock)) = (&lock_path, &lock) {
+        store.put_lock(&project_key(path), lock).await?;
+    }
+    if let Some(index) = &index {
+        let mut pages = batches(index.as_ref(), None, DEFAULT_BATCH_SIZE);
+        while let Some(batch) = pages.next_batch().await? {
+            for skill in batch {
+                store
+                    .add_or_update_skill(
+                        &skill.id,
+                        skill.skill_path,
+                        skill.frontmatter_json,
+                        skill.embedding,
+                        &skill.file_hash,
+                    )
+                    .await?;
+            }
+        }
+    }
+
+    messages::status!(
+        "{}",
+        messages::ok(&format!(
+            "Copied {} metadata entries, {} lock and {} embeddings to Postgres ({})",
+            metadata.len(),
+            if lock.is_some() { "the" } else { "no" },
+            embeddings,
+            if store.uses_pgvector() {
+                "pgvector"
+            } else {
+                "real[] embeddings; install pgvector for in-database search"
+            }
+        ))
+    );
+    Ok(())
+}
+
+#[cfg(not(feature = "postgres"))]
+async fn to_postgres(_service: &FastSkillService, _args: MigrateToPostgresArgs) -> CliResult<()> {
+    Err(CliError::Config(
+        "this build of fastskill does not include Postgres support; reinstall with \
+         `cargo install fastskill-cli --features postgres`"
+            .to_string(),
+    ))
+}
+
+/// Arguments for `migrate scoped-ids`
+#[derive(Debug, Clone, Default)]
+pub struct MigrateScopedIdsArgs {
+    /// List the moves without making them
+    pub dry_run: bool,
+}
+
+impl IntoCommandSpec for MigrateScopedIdsArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Move registry skills installed under plain ids to their scope/name ids",
+            syntax: Some("migrate scoped-ids [--dry-run]"),
+            category: Some("server"),
+            args: vec![ArgSpec {
+                name: "dry-run",
+                kind: ArgKind::Flag,
+                long: Some("dry-run"),
+                value_type: ArgValueType::Bool,
+                cardinality: Cardinality::Optional,
+                help: "List the skills that would move without moving them",
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for MigrateScopedIdsArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        Self {
+            dry_run: matches!(map.get("dry-run"), Some(ArgValue::Bool(true))),
+        }
+    }
+}
+
+pub async fn execute_migrate_scoped_ids(
+    service: &FastSkillService,
+    args: MigrateScopedIdsArgs,
+) -> CliResult<()> {
+    let current_dir = std::env::current_dir()
+        .map_err(|e| CliError::Config(format!("Failed to get current directory: {}", e)))?;
+    let project = resolve_project_file(&current_dir);
+    if !project.found {
+        return Err(CliError::Config(
+            "skill-project.toml not found in this directory or any parent".to_string(),
+        ));
+    }
+
+    let moves = service
+        .migrate_to_scoped_ids(&project.path, args.dry_run)
+        .await?;
+    if moves.is_empty() {
+        messages::status!(
+            "{}",
+            messages::ok("All registry skills already use scoped ids")
+        );
+        return Ok(());
+    }
+    for m in &moves {
+        println!("{} -> {}", m.from, m.to);
+    }
+    if args.dry_run {
+        messages::status!("{}", messages::info("Dry run; nothing was moved"));
+    } else {
+        messages::status!(
+            "{}",
+            messages::ok(&format!(
+                "Moved {} skill(s); run 'fastskill reindex' to refresh the search index",
+                moves.len()
+            ))
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use fastskill_core::ServiceConfig;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_migrate_rejects_empty_url_env() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = ServiceConfig {
+            skill_storage_path: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        let service = FastSkillService::new(config).await.unwrap();
+        let args = MigrateToPostgresArgs {
+            database_url_env: " ".to_string(),
+            dry_run: true,
+        };
+        let err = execute_migrate_to_postgres(&service, args)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CliError::Validation(_)));
+    }
+}
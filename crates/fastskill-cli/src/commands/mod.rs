@@ -1,21 +1,40 @@
 //! Command modules for CLI
 
 pub mod add;
+pub mod admin;
 pub mod analyze;
+pub mod audit;
 pub mod common;
+pub mod credentials;
+pub mod diagnostics;
 pub mod doctor;
 pub mod eval;
+pub mod fixtures;
+pub mod gc;
+pub mod graph;
 pub mod init;
 pub mod install;
+pub mod lint;
 pub mod list;
+pub mod lock;
 pub mod marketplace;
+pub mod migrate;
+pub mod pack;
+pub mod profile;
 pub mod read;
 pub mod registry;
 pub mod reindex;
 pub mod remove;
+pub mod report;
 pub mod repos;
+pub mod retire;
 pub mod search;
+pub mod self_update;
 pub mod serve;
 pub mod skillopt;
 pub mod sources;
+pub mod telemetry;
+pub mod test;
 pub mod update;
+pub mod vendor;
+pub mod yank;
@@ -0,0 +1,345 @@
+//! Pack commands - bundle several skills into one distributable zip
+//!
+//! `pack create` zips every skill under a directory together with a
+//! `skill-pack.toml` manifest. `fastskill add <pack>.zip` installs each skill
+//! in the pack as its own managed dependency. With `--workspace` the pack
+//! holds the skills authored by the members of a skill-workspace.toml. Each
+//! skill's `.fsignore` and `exclude` globs decide which of its files are
+//! packed; `--list-files` prints them without writing the pack.
+
+use crate::error::{CliError, CliResult};
+use crate::utils::messages;
+use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
+use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
+use cli_framework::spec::command_tree::CommandSpec;
+use cli_framework::spec::value::ArgValue;
+use fastskill_core::core::pack::{build_pack, PackInfo};
+use fastskill_core::core::packaging::package_files;
+use fastskill_core::core::project::{Workspace, WORKSPACE_FILE};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Arguments for `pack create`
+#[derive(Debug, Clone)]
+pub struct PackCreateArgs {
+    /// Directory to search for skills
+    pub path: PathBuf,
+    /// Pack name
+    pub name: String,
+    /// Pack version
+    pub version: String,
+    /// Optional pack description
+    pub description: Option<String>,
+    /// Output file (default: <name>-<version>.zip)
+    pub output: Option<PathBuf>,
+    /// Pack the skill-level members of the workspace containing `path`
+    pub workspace: bool,
+    /// Print the files each skill would contribute instead of writing the pack
+    pub list_files: bool,
+}
+
+impl IntoCommandSpec for PackCreateArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Bundle the skills under a directory into one skill pack zip",
+            syntax: Some(
+                "pack create [PATH] --name NAME --version VERSION [-o FILE] [--workspace] | pack create [PATH] --list-files",
+            ),
+            category: Some("publishing"),
+            args: vec![
+                ArgSpec {
+                    name: "path",
+                    kind: ArgKind::Positional,
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    help: "Directory containing the skills to pack (default: .)",
+                    default: Some(ArgValue::Str(".".to_string())),
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "name",
+                    kind: ArgKind::Option,
+                    long: Some("name"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    help: "Pack name, recorded in skill-pack.toml (required)",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "version",
+                    kind: ArgKind::Option,
+                    long: Some("version"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    help: "Pack version, recorded in skill-pack.toml (required)",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "description",
+                    kind: ArgKind::Option,
+                    long: Some("description"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    help: "Pack description (optional)",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "output",
+                    kind: ArgKind::Option,
+                    short: Some('o'),
+                    long: Some("output"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    help: "Output file (default: <name>-<version>.zip)",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "workspace",
+                    kind: ArgKind::Flag,
+                    long: Some("workspace"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help:
+                        "Pack the skills authored by the members of the workspace containing PATH",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "list-files",
+                    kind: ArgKind::Flag,
+                    long: Some("list-files"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "List the files that would go into the pack (after .fsignore and exclude rules) without writing it",
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for PackCreateArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        let string = |key: &str| match map.get(key) {
+            Some(ArgValue::Str(s)) => Some(s.clone()),
+            _ => None,
+        };
+        Self {
+            path: PathBuf::from(string("path").unwrap_or_else(|| ".".to_string())),
+            name: string("name").unwrap_or_default(),
+            version: string("version").unwrap_or_default(),
+            description: string("description"),
+            output: string("output").map(PathBuf::from),
+            workspace: matches!(map.get("workspace"), Some(ArgValue::Bool(true))),
+            list_files: matches!(map.get("list-files"), Some(ArgValue::Bool(true))),
+        }
+    }
+}
+
+/// Directories of the skill-level members of the workspace containing `path`
+fn workspace_skill_dirs(path: &std::path::Path) -> CliResult<Vec<PathBuf>> {
+    let workspace = Workspace::discover(path)
+        .map_err(|e| CliError::Config(format!("Failed to load {}: {}", WORKSPACE_FILE, e)))?
+        .ok_or_else(|| {
+            CliError::Config(format!(
+                "{} is not in a workspace (no {} found)",
+                path.display(),
+                WORKSPACE_FILE
+            ))
+        })?;
+    Ok(workspace
+        .members
+        .iter()
+        .filter(|m| m.skill_id().is_some())
+        .map(|m| m.dir.clone())
+        .collect())
+}
+
+/// Print the files of each skill in `skill_dirs` that a pack would contain
+fn list_files(skill_dirs: &[PathBuf]) -> CliResult<()> {
+    let mut total = 0;
+    for dir in skill_dirs {
+        let files = package_files(dir)?;
+        println!("{}:", dir.display());
+        for file in &files {
+            println!("  {}", file.display());
+        }
+        total += files.len();
+    }
+    messages::status!(
+        "{} files from {} skills (nothing written)",
+        total,
+        skill_dirs.len()
+    );
+    Ok(())
+}
+
+pub async fn execute_pack_create(args: PackCreateArgs) -> CliResult<()> {
+    if !args.list_files && (args.name.trim().is_empty() || args.version.trim().is_empty()) {
+        return Err(CliError::Validation(
+            "pack create requires --name and --version".to_string(),
+        ));
+    }
+    let skill_dirs = if args.workspace {
+        workspace_skill_dirs(&args.path)?
+    } else if args.path.join("SKILL.md").is_file() {
+        vec![args.path.clone()]
+    } else {
+        super::add::install::get_skill_dirs_recursive(&args.path)?
+    };
+    if skill_dirs.is_empty() {
+        return Err(CliError::Validation(format!(
+            "No skill directories found under {}",
+            args.path.display()
+        )));
+    }
+    if args.list_files {
+        return list_files(&skill_dirs);
+    }
+
+    let output = args
+        .output
+        .unwrap_or_else(|| PathBuf::from(format!("{}-{}.zip", args.name, args.version)));
+    let info = PackInfo {
+        name: args.name,
+        version: args.version,
+        description: args.description,
+    };
+    let manifest = build_pack(info, &skill_dirs, &output).await?;
+
+    for entry in &manifest.skills {
+        messages::status!("  {} (v{})", entry.id, entry.version);
+    }
+    println!(
+        "{}",
+        messages::ok(&format!(
+            "Created skill pack {} v{} with {} skills: {}",
+            manifest.pack.name,
+            manifest.pack.version,
+            manifest.skills.len(),
+            output.display()
+        ))
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use fastskill_core::core::pack::read_pack_manifest;
+    use tempfile::TempDir;
+
+    fn write_skill(dir: &std::path::Path, id: &str) {
+        let skill_dir = dir.join(id);
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        std::fs::write(
+            skill_dir.join("SKILL.md"),
+            format!("---\nname: {id}\nversion: 1.0.0\ndescription: Test {id}\n---\n# {id}\n"),
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_execute_pack_create_bundles_all_skills() {
+        let temp_dir = TempDir::new().unwrap();
+        write_skill(&temp_dir.path().join("skills"), "pdf-tools");
+        write_skill(&temp_dir.path().join("skills").join("data"), "csv-tools");
+        let output = temp_dir.path().join("team.zip");
+
+        execute_pack_create(PackCreateArgs {
+            path: temp_dir.path().join("skills"),
+            name: "team".to_string(),
+            version: "2.0.0".to_string(),
+            description: None,
+            output: Some(output.clone()),
+            workspace: false,
+            list_files: false,
+        })
+        .await
+        .unwrap();
+
+        let manifest = read_pack_manifest(&output).unwrap();
+        let mut ids: Vec<_> = manifest.skills.iter().map(|s| s.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, ["csv-tools", "pdf-tools"]);
+        assert_eq!(manifest.pack.version, "2.0.0");
+    }
+
+    #[tokio::test]
+    async fn test_execute_pack_create_list_files_writes_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        write_skill(temp_dir.path(), "pdf-tools");
+
+        execute_pack_create(PackCreateArgs {
+            path: temp_dir.path().join("pdf-tools"),
+            name: String::new(),
+            version: String::new(),
+            description: None,
+            output: Some(temp_dir.path().join("team.zip")),
+            workspace: false,
+            list_files: true,
+        })
+        .await
+        .unwrap();
+        assert!(!temp_dir.path().join("team.zip").exists());
+    }
+
+    #[tokio::test]
+    async fn test_execute_pack_create_requires_skills() {
+        let temp_dir = TempDir::new().unwrap();
+        let err = execute_pack_create(PackCreateArgs {
+            path: temp_dir.path().to_path_buf(),
+            name: "team".to_string(),
+            version: "1.0.0".to_string(),
+            description: None,
+            output: Some(temp_dir.path().join("team.zip")),
+            workspace: false,
+            list_files: false,
+        })
+        .await
+        .unwrap_err();
+        assert!(matches!(err, CliError::Validation(_)));
+    }
+
+    #[tokio::test]
+    async fn test_execute_pack_create_workspace_members() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join(WORKSPACE_FILE),
+            "[workspace]\nmembers = [\"app\", \"skills/*\"]\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("app")).unwrap();
+        std::fs::write(root.join("app/skill-project.toml"), "[dependencies]\n").unwrap();
+        for id in ["pdf-tools", "csv-tools"] {
+            write_skill(&root.join("skills"), id);
+            std::fs::write(
+                root.join("skills").join(id).join("skill-project.toml"),
+                format!("[metadata]\nid = \"{id}\"\nversion = \"1.0.0\"\n"),
+            )
+            .unwrap();
+        }
+        // Not a member: no skill-project.toml
+        write_skill(&root.join("skills"), "draft");
+        let output = root.join("team.zip");
+
+        execute_pack_create(PackCreateArgs {
+            path: root.to_path_buf(),
+            name: "team".to_string(),
+            version: "1.0.0".to_string(),
+            description: None,
+            output: Some(output.clone()),
+            workspace: true,
+            list_files: false,
+        })
+        .await
+        .unwrap();
+
+        let manifest = read_pack_manifest(&output).unwrap();
+        let mut ids: Vec<_> = manifest.skills.iter().map(|s| s.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, ["csv-tools", "pdf-tools"]);
+    }
+}
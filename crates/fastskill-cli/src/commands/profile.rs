@@ -0,0 +1,236 @@
+//! Profile command - switch between named sets of enabled skills
+//!
+//! Profiles live in `.claude/skill-profiles.toml` next to skill-project.toml.
+//! `profile apply <name>` enables the profile's skills and disables every
+//! other installed skill in one batch; `profile save <name>` records the
+//! skills that are enabled now. Disabled skills stay installed and locked;
+//! `fastskill install` re-enables a skill it reinstalls.
+
+use crate::error::{CliError, CliResult};
+use crate::utils::messages;
+use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
+use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
+use cli_framework::spec::command_tree::CommandSpec;
+use cli_framework::spec::value::ArgValue;
+use fastskill_core::core::profiles::SkillProfiles;
+use fastskill_core::core::project::resolve_project_file;
+use fastskill_core::FastSkillService;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Arguments for `profile apply`
+#[derive(Debug, Clone)]
+pub struct ProfileApplyArgs {
+    /// Profile to apply
+    pub name: String,
+    /// Trigger reindex afterwards (overrides config)
+    pub reindex: bool,
+    /// Skip reindex afterwards
+    pub no_reindex: bool,
+    /// Print the changes as JSON
+    pub json: bool,
+}
+
+/// Arguments for `profile save`
+#[derive(Debug, Clone)]
+pub struct ProfileSaveArgs {
+    /// Profile to create or replace
+    pub name: String,
+    /// Description stored with the profile
+    pub description: Option<String>,
+    /// Replace an existing profile of that name
+    pub force: bool,
+}
+
+fn name_arg(help: &'static str) -> ArgSpec {
+    ArgSpec {
+        name: "name",
+        kind: ArgKind::Positional,
+        value_type: ArgValueType::String,
+        cardinality: Cardinality::Required,
+        help,
+        ..Default::default()
+    }
+}
+
+fn flag_arg(name: &'static str, help: &'static str) -> ArgSpec {
+    ArgSpec {
+        name,
+        kind: ArgKind::Flag,
+        long: Some(name),
+        value_type: ArgValueType::Bool,
+        cardinality: Cardinality::Optional,
+        help,
+        ..Default::default()
+    }
+}
+
+fn name_from_map(map: &HashMap<String, ArgValue>) -> String {
+    match map.get("name") {
+        Some(ArgValue::Str(s)) => s.clone(),
+        _ => String::new(),
+    }
+}
+
+impl IntoCommandSpec for ProfileApplyArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Enable a profile's skills and disable all other installed skills",
+            syntax: Some("profile apply <NAME> [--reindex|--no-reindex] [--json]"),
+            category: Some("packages"),
+            args: vec![
+                name_arg("Profile name from .claude/skill-profiles.toml"),
+                flag_arg("reindex", "Trigger reindex afterwards (overrides config)"),
+                flag_arg("no-reindex", "Skip reindex afterwards"),
+                flag_arg("json", "Print the changes as JSON"),
+            ],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for ProfileApplyArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        Self {
+            name: name_from_map(map),
+            reindex: matches!(map.get("reindex"), Some(ArgValue::Bool(true))),
+            no_reindex: matches!(map.get("no-reindex"), Some(ArgValue::Bool(true))),
+            json: matches!(map.get("json"), Some(ArgValue::Bool(true))),
+        }
+    }
+}
+
+impl IntoCommandSpec for ProfileSaveArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Save the currently enabled skills as a profile",
+            syntax: Some("profile save <NAME> [--description TEXT] [--force]"),
+            category: Some("packages"),
+            args: vec![
+                name_arg("Profile name (letters, digits, '-', '_' and '.')"),
+                ArgSpec {
+                    name: "description",
+                    kind: ArgKind::Option,
+                    long: Some("description"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    help: "Description stored with the profile",
+                    ..Default::default()
+                },
+                flag_arg("force", "Replace an existing profile of that name"),
+            ],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for ProfileSaveArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        Self {
+            name: name_from_map(map),
+            description: match map.get("description") {
+                Some(ArgValue::Str(s)) => Some(s.clone()),
+                _ => None,
+            },
+            force: matches!(map.get("force"), Some(ArgValue::Bool(true))),
+        }
+    }
+}
+
+/// `.claude/skill-profiles.toml` of the project containing the current
+/// directory, or of the current directory outside a project
+fn profiles_path() -> CliResult<PathBuf> {
+    let current_dir = std::env::current_dir()
+        .map_err(|e| CliError::Config(format!("Failed to get current directory: {}", e)))?;
+    let project_file = resolve_project_file(&current_dir);
+    let root = match project_file.path.parent() {
+        Some(parent) if project_file.found => parent.to_path_buf(),
+        _ => current_dir,
+    };
+    Ok(SkillProfiles::default_path(&root))
+}
+
+pub async fn execute_profile_apply(
+    service: &FastSkillService,
+    args: ProfileApplyArgs,
+) -> CliResult<()> {
+    let path = profiles_path()?;
+    let profiles = SkillProfiles::load(&path)?;
+    let profile = profiles.get(&args.name)?;
+    let applied = service.apply_profile(profile).await?;
+
+    if args.json {
+        let json = serde_json::to_string_pretty(&applied)
+            .map_err(|e| CliError::Config(format!("Failed to encode JSON: {}", e)))?;
+        println!("{}", json);
+    } else {
+        for id in &applied.changes.enabled {
+            println!("Enabled skill: {}", id);
+        }
+        for id in &applied.changes.disabled {
+            println!("Disabled skill: {}", id);
+        }
+        for id in &applied.not_installed {
+            eprintln!(
+                "{}",
+                messages::warning(&format!(
+                    "Profile '{}' lists '{}', which is not installed",
+                    args.name, id
+                ))
+            );
+        }
+        messages::status!(
+            "{}",
+            messages::ok(&format!(
+                "Applied profile '{}' ({} enabled, {} disabled)",
+                args.name,
+                applied.changes.enabled.len(),
+                applied.changes.disabled.len()
+            ))
+        );
+    }
+
+    if applied.changes.enabled.is_empty() && applied.changes.disabled.is_empty() {
+        return Ok(());
+    }
+    let auto_reindex = crate::config_file::load_auto_reindex_config();
+    crate::utils::reindex_utils::maybe_auto_reindex(
+        service,
+        "profile apply",
+        args.reindex,
+        args.no_reindex,
+        auto_reindex,
+        false,
+    )
+    .await
+}
+
+pub async fn execute_profile_save(
+    service: &FastSkillService,
+    args: ProfileSaveArgs,
+) -> CliResult<()> {
+    let path = profiles_path()?;
+    let mut profiles = SkillProfiles::load(&path)?;
+    if profiles.profiles.contains_key(&args.name) && !args.force {
+        return Err(CliError::Validation(format!(
+            "Profile '{}' already exists in {}; use --force to replace it",
+            args.name,
+            path.display()
+        )));
+    }
+    let profile = service.current_profile(args.description)?;
+    let count = profile.skills.len();
+    profiles.insert(&args.name, profile)?;
+    profiles.save(&path)?;
+    messages::status!(
+        "{}",
+        messages::ok(&format!(
+            "Saved profile '{}' ({} skills) to {}",
+            args.name,
+            count,
+            path.display()
+        ))
+    );
+    Ok(())
+}
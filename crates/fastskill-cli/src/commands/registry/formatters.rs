@@ -2,6 +2,7 @@ use crate::error::CliResult;
 use fastskill_core::core::repository::RepositoryConfig;
 use fastskill_core::core::repository::RepositoryDefinition;
 use fastskill_core::core::repository::RepositoryType;
+use fastskill_core::output::table::Table;
 
 pub fn format_repository_list(repos: &[&RepositoryDefinition]) -> String {
     if repos.is_empty() {
@@ -72,63 +73,32 @@ pub fn format_table_output(
     summaries: &[fastskill_core::core::registry_index::SkillSummary],
     all_versions: bool,
 ) -> CliResult<()> {
-    let headers = if all_versions {
-        vec!["Scope", "Name", "Description", "Version", "Published"]
+    let version_header = if all_versions {
+        "Version"
     } else {
-        vec![
-            "Scope",
-            "Name",
-            "Description",
-            "Latest Version",
-            "Published",
-        ]
+        "Latest Version"
     };
-
-    let mut col_widths = vec![0; headers.len()];
-    for (i, header) in headers.iter().enumerate() {
-        col_widths[i] = header.len();
-    }
-
-    for summary in summaries {
-        col_widths[0] = col_widths[0].max(summary.scope.len());
-        col_widths[1] = col_widths[1].max(summary.name.len());
-        let desc_len = summary.description.len().min(50);
-        col_widths[2] = col_widths[2].max(desc_len);
-        col_widths[3] = col_widths[3].max(summary.latest_version.len());
-        col_widths[4] = col_widths[4].max(10);
-    }
-
-    let header_row: Vec<String> = headers
-        .iter()
-        .enumerate()
-        .map(|(i, h)| format!("{:width$}", h, width = col_widths[i]))
-        .collect();
-    println!("\n{}", header_row.join("  "));
-    println!("{}", "-".repeat(header_row.join("  ").len()));
+    let mut table =
+        Table::new(["Scope", "Name", "Description", version_header, "Published"]).max_width(2, 50);
 
     for summary in summaries {
-        let description = if summary.description.len() > 50 {
-            format!("{}...", &summary.description[..47])
-        } else {
-            summary.description.clone()
-        };
-
         let published = summary
             .published_at
             .map(|dt| dt.format("%Y-%m-%d").to_string())
             .unwrap_or_else(|| "N/A".to_string());
-
-        let row = [
-            format!("{:width$}", summary.scope, width = col_widths[0]),
-            format!("{:width$}", summary.name, width = col_widths[1]),
-            format!("{:width$}", description, width = col_widths[2]),
-            format!("{:width$}", summary.latest_version, width = col_widths[3]),
-            format!("{:width$}", published, width = col_widths[4]),
-        ];
-        println!("{}", row.join("  "));
+        table.push_row([
+            summary.scope.as_str(),
+            summary.name.as_str(),
+            summary.description.as_str(),
+            summary.latest_version.as_str(),
+            published.as_str(),
+        ]);
     }
+    // Scope, then name; versions of one skill keep the registry's order.
+    table.sort_by_column(1);
+    table.sort_by_column(0);
 
-    println!();
+    println!("\n{}", table.render());
     Ok(())
 }
 
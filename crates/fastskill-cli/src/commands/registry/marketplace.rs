@@ -2,6 +2,7 @@ use crate::error::{CliError, CliResult};
 use crate::utils::messages;
 use fastskill_core::core::manifest::MetadataSection;
 use fastskill_core::core::metadata::parse_yaml_frontmatter;
+use fastskill_core::core::requirements::declared_requirements;
 use fastskill_core::core::sources::{
     ClaudeCodeMarketplaceJson, ClaudeCodeMetadata, ClaudeCodeOwner, ClaudeCodePlugin,
     MarketplaceSkill,
@@ -103,7 +104,7 @@ pub async fn execute_create(
     fs::write(&output_path, json_content)
         .map_err(|e| CliError::Validation(format!("Failed to write marketplace.json: {}", e)))?;
 
-    println!(
+    messages::status!(
         "{}",
         messages::ok(&format!(
             "Created marketplace.json: {}",
@@ -194,6 +195,9 @@ pub fn extract_skill_metadata(skill_dir: &Path, skill_file: &Path) -> CliResult<
 
     let name = frontmatter.name.clone();
 
+    let requires = declared_requirements(skill_dir, &frontmatter)
+        .map_err(|e| CliError::Validation(e.to_string()))?;
+
     let description = skill_metadata
         .as_ref()
         .and_then(|m| m.description.clone())
@@ -227,5 +231,7 @@ pub fn extract_skill_metadata(skill_dir: &Path, skill_file: &Path) -> CliResult<
         version,
         author,
         download_url,
+        yanked: false,
+        requires: (!requires.is_empty()).then_some(requires),
     })
 }
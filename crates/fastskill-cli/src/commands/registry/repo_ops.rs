@@ -129,7 +129,7 @@ pub async fn execute_update(
         .save()
         .map_err(|e| CliError::Config(format!("Failed to save repositories: {}", e)))?;
 
-    println!("{}", messages::ok(&format!("Updated repository: {}", name)));
+    messages::status!("{}", messages::ok(&format!("Updated repository: {}", name)));
     Ok(())
 }
 
@@ -140,7 +140,7 @@ pub async fn execute_test(name: String) -> CliResult<()> {
         .get_repository(&name)
         .ok_or_else(|| CliError::Config(format!("Repository '{}' not found", name)))?;
 
-    println!(
+    messages::status!(
         "{}",
         messages::info(&format!("Testing repository: {}...", name))
     );
@@ -148,7 +148,7 @@ pub async fn execute_test(name: String) -> CliResult<()> {
     match repo_manager.get_client(&name).await {
         Ok(client) => match client.list_skills().await {
             Ok(skills) => {
-                println!(
+                messages::status!(
                     "{}",
                     messages::ok(&format!(
                         "Repository '{}' is accessible ({} skills found)",
@@ -177,12 +177,12 @@ pub async fn execute_test(name: String) -> CliResult<()> {
 
 pub async fn execute_refresh(name: Option<String>) -> CliResult<()> {
     if let Some(repo_name) = name {
-        println!(
+        messages::status!(
             "{}",
             messages::ok(&format!("Refreshed cache for repository: {}", repo_name))
         );
     } else {
-        println!("{}", messages::ok("Refreshed cache for all repositories"));
+        messages::status!("{}", messages::ok("Refreshed cache for all repositories"));
     }
     Ok(())
 }
@@ -223,7 +223,7 @@ pub async fn execute_add(
         .save()
         .map_err(|e| CliError::Config(format!("Failed to save repositories: {}", e)))?;
 
-    println!("{}", messages::ok(&format!("Added repository: {}", name)));
+    messages::status!("{}", messages::ok(&format!("Added repository: {}", name)));
     Ok(())
 }
 
@@ -237,7 +237,7 @@ pub async fn execute_remove(name: String) -> CliResult<()> {
         .save()
         .map_err(|e| CliError::Config(format!("Failed to save repositories: {}", e)))?;
 
-    println!("{}", messages::ok(&format!("Removed repository: {}", name)));
+    messages::status!("{}", messages::ok(&format!("Removed repository: {}", name)));
     Ok(())
 }
 
@@ -71,7 +71,7 @@ pub async fn execute_list_skills(
     };
 
     if matches!(resolved_format, OutputFormat::Table | OutputFormat::Grid) {
-        println!(
+        messages::status!(
             "{}",
             messages::info(&format!("Listing skills from repository: {}", repo_name))
         );
@@ -129,7 +129,7 @@ pub async fn execute_show_skill(skill_id: String, repository: Option<String>) ->
 
     let repo_name = super::helpers::resolve_repository_name(&repo_manager, repository)?;
 
-    println!(
+    messages::status!(
         "{}",
         messages::info(&format!("Fetching skill: {} from {}", skill_id, repo_name))
     );
@@ -169,7 +169,7 @@ pub async fn execute_versions(skill_id: String, repository: Option<String>) -> C
 
     let repo_name = super::helpers::resolve_repository_name(&repo_manager, repository)?;
 
-    println!(
+    messages::status!(
         "{}",
         messages::info(&format!(
             "Fetching versions for: {} from {}",
@@ -1,5 +1,5 @@
 //! Reindex command implementation — a thin wrapper over the core reindex seam
-//! (`FastSkillService::reindex`, ADR-0002/0005). All indexing logic (finding
+//! (`FastSkillService::reindex_with_options`, ADR-0002/0005). All indexing logic (finding
 //! `SKILL.md` files, hashing, embedding, updating the vector index, pruning
 //! stale entries) lives in core; this module only renders progress and the
 //! final summary from the `ReindexProgress` observer callbacks and the
@@ -10,7 +10,7 @@ use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
 use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
 use cli_framework::spec::command_tree::CommandSpec;
 use cli_framework::spec::value::ArgValue;
-use fastskill_core::core::reindex::ReindexProgress;
+use fastskill_core::core::reindex::{ReindexMode, ReindexOptions, ReindexProgress};
 use fastskill_core::FastSkillService;
 use std::collections::HashMap;
 use std::io::{IsTerminal, Write};
@@ -62,12 +62,16 @@ pub struct ReindexArgs {
     /// Force re-indexing of all skills (ignore existing hashes)
     pub force: bool,
 
-    /// Maximum number of concurrent embedding requests
-    ///
-    /// NOTE (core-seam gap): the core `reindex` seam processes skills
-    /// sequentially and takes no concurrency parameter, so this is currently
-    /// accepted but has no effect. Kept for CLI/arg compatibility.
-    #[allow(dead_code)]
+    /// Only re-embed skills whose directory hash changed (the default; the
+    /// flag exists to make the mode explicit in scripts)
+    pub incremental: bool,
+
+    /// Re-embed every skill into a new index with the configured embedding
+    /// model, replacing the current index only when all succeed
+    pub migrate: bool,
+
+    /// Maximum number of embedding requests in flight at once; each request
+    /// carries a batch of skills (`batch_size` in `[tool.fastskill.embedding]`)
     pub max_concurrent: usize,
 
     /// Show progress bars and processing details
@@ -104,6 +108,33 @@ impl IntoCommandSpec for ReindexArgs {
                     value_type: ArgValueType::Bool,
                     cardinality: Cardinality::Optional,
                     default: None,
+                    conflicts_with: vec!["incremental", "migrate"],
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "incremental",
+                    long: Some("incremental"),
+                    short: None,
+                    help:
+                        "Only re-embed skills whose files changed and drop deleted ones (default)",
+                    kind: ArgKind::Flag,
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    conflicts_with: vec!["force", "migrate"],
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "migrate",
+                    long: Some("migrate"),
+                    short: None,
+                    help: "Re-embed every skill with the configured model into a new index, \
+                           keeping the old one until all succeed",
+                    kind: ArgKind::Flag,
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    conflicts_with: vec!["force", "incremental"],
                     ..Default::default()
                 },
                 ArgSpec {
@@ -156,6 +187,8 @@ impl FromArgValueMap for ReindexArgs {
                 }
             }),
             force: matches!(map.get("force"), Some(ArgValue::Bool(true))),
+            incremental: matches!(map.get("incremental"), Some(ArgValue::Bool(true))),
+            migrate: matches!(map.get("migrate"), Some(ArgValue::Bool(true))),
             max_concurrent: map
                 .get("max-concurrent")
                 .and_then(|v| {
@@ -172,23 +205,28 @@ impl FromArgValueMap for ReindexArgs {
     }
 }
 
-/// Wipe every entry currently in the vector index so the next `reindex` call
-/// re-embeds everything, regardless of unchanged file hashes.
-///
-/// This is how `--force` is honored through the core seam: `FastSkillService::reindex`
-/// has no `force` parameter (it always skips unchanged-hash skills), so instead of
-/// duplicating its indexing loop here, we use the existing public
-/// `VectorIndexService` accessor to clear the cache it consults.
-async fn clear_vector_index(service: &FastSkillService) {
-    let Some(vector_index_service) = service.vector_index_service() else {
-        return;
-    };
-    let Ok(all_indexed) = vector_index_service.get_all_skills().await else {
-        return;
-    };
-    for indexed in all_indexed {
-        let _ = vector_index_service.remove_skill(&indexed.id).await;
-    }
+/// Width of the live progress bar, in characters
+const PROGRESS_BAR_WIDTH: usize = 30;
+
+/// `Progress: [#########.....................] 12/40 (30%)`
+fn progress_bar(current: usize, total: usize) -> String {
+    let current = current.min(total);
+    let pct = current
+        .checked_mul(100)
+        .and_then(|n| n.checked_div(total))
+        .unwrap_or(0);
+    let filled = current
+        .checked_mul(PROGRESS_BAR_WIDTH)
+        .and_then(|n| n.checked_div(total))
+        .unwrap_or(0);
+    format!(
+        "Progress: [{}{}] {}/{} ({}%)",
+        "#".repeat(filled),
+        ".".repeat(PROGRESS_BAR_WIDTH - filled),
+        current,
+        total,
+        pct
+    )
 }
 
 pub async fn execute_reindex(service: &FastSkillService, args: ReindexArgs) -> CliResult<()> {
@@ -198,20 +236,31 @@ pub async fn execute_reindex(service: &FastSkillService, args: ReindexArgs) -> C
             "--progress and --no-progress cannot be used together".to_string(),
         ));
     }
+    if args.force && args.incremental {
+        return Err(CliError::Validation(
+            "--force and --incremental cannot be used together".to_string(),
+        ));
+    }
+    if args.migrate && (args.force || args.incremental) {
+        return Err(CliError::Validation(
+            "--migrate cannot be combined with --force or --incremental".to_string(),
+        ));
+    }
 
     let mode = ProgressMode::from_flags(args.progress, args.no_progress);
     let start_time = std::time::Instant::now();
 
-    // `--force`: only meaningful when reindex will actually run (an embedding
-    // provider is injected); otherwise leave the index untouched, matching the
-    // "no embedding configured" skip below.
-    if args.force && service.embedding_service().is_some() {
-        clear_vector_index(service).await;
-    }
+    let reindex_mode = if args.migrate {
+        ReindexMode::Migrate
+    } else if args.force {
+        ReindexMode::Full
+    } else {
+        ReindexMode::Incremental
+    };
 
-    // The observer fires once per skill `reindex` finds, before it decides
-    // whether that skill needs re-embedding. Its first call is also the first
-    // point at which the total skill count is known, so we print
+    // The observer fires once per skill `reindex` finds, as soon as that skill
+    // is settled (unchanged, embedded, or failed). Its first call is also the
+    // first point at which the total skill count is known, so we print
     // "Found N skills to process" there — same information as before, just
     // learned from the seam's callback instead of a CLI-side directory scan.
     let found_total = Arc::new(AtomicUsize::new(0));
@@ -232,20 +281,19 @@ pub async fn execute_reindex(service: &FastSkillService, args: ReindexArgs) -> C
             println!("Found {} skills to process", p.total);
         }
         if verbose {
-            println!("  Processing: {} ({}/{})", p.skill_id, p.current, p.total);
+            println!("  Processed: {} ({}/{})", p.skill_id, p.current, p.total);
         } else if live {
-            let pct = p
-                .current
-                .checked_mul(100)
-                .and_then(|n| n.checked_div(p.total))
-                .unwrap_or(0);
-            print!("\r\x1B[KProgress: [{}/{}] ({}%)", p.current, p.total, pct);
+            print!("\r\x1B[K{}", progress_bar(p.current, p.total));
             let _ = std::io::stdout().flush();
         }
     };
 
+    let options = ReindexOptions {
+        mode: reindex_mode,
+        concurrency: args.max_concurrent.max(1),
+    };
     let outcome = service
-        .reindex(args.skills_dir.as_deref(), Some(&observer))
+        .reindex_with_options(args.skills_dir.as_deref(), Some(&observer), options)
         .await
         .map_err(CliError::Service)?;
 
@@ -265,6 +313,9 @@ pub async fn execute_reindex(service: &FastSkillService, args: ReindexArgs) -> C
     if !seen_any.load(Ordering::SeqCst) {
         // Nothing under the skills directory at all.
         if !no_progress {
+            if outcome.removed > 0 {
+                println!("Removed {} deleted skills from the index", outcome.removed);
+            }
             let dir = args
                 .skills_dir
                 .clone()
@@ -279,7 +330,12 @@ pub async fn execute_reindex(service: &FastSkillService, args: ReindexArgs) -> C
         println!("Reindex completed");
         println!("  Total skills: {}", found_total.load(Ordering::SeqCst));
         println!("  Indexed/updated: {}", outcome.count);
+        println!("  Unchanged: {}", outcome.unchanged);
+        println!("  Removed: {}", outcome.removed);
         println!("  Total time: {:.2}s", start_time.elapsed().as_secs_f64());
+        if args.migrate {
+            println!("Index migrated to the configured embedding model");
+        }
     }
 
     Ok(())
@@ -327,6 +383,22 @@ Test skill content"#,
         assert_eq!(ProgressMode::from_flags(false, true), ProgressMode::Quiet);
     }
 
+    #[test]
+    fn test_progress_bar() {
+        assert_eq!(
+            progress_bar(12, 40),
+            format!(
+                "Progress: [{}{}] 12/40 (30%)",
+                "#".repeat(9),
+                ".".repeat(21)
+            )
+        );
+        assert_eq!(
+            progress_bar(0, 0),
+            format!("Progress: [{}] 0/0 (0%)", ".".repeat(30))
+        );
+    }
+
     #[tokio::test]
     async fn test_execute_reindex_without_embedding_config() {
         let temp_dir = TempDir::new().unwrap();
@@ -342,6 +414,8 @@ Test skill content"#,
         let args = ReindexArgs {
             skills_dir: None,
             force: false,
+            incremental: false,
+            migrate: false,
             max_concurrent: 5,
             progress: false,
             no_progress: false,
@@ -361,6 +435,10 @@ Test skill content"#,
                 openai_base_url: "https://api.openai.com/v1".to_string(),
                 embedding_model: "text-embedding-3-small".to_string(),
                 index_path: None,
+                batch_size: None,
+                chunk_size: None,
+                query_cache_size: None,
+                query_cache_ttl: None,
             }),
             ..Default::default()
         };
@@ -376,6 +454,8 @@ Test skill content"#,
         let args = ReindexArgs {
             skills_dir: Some(nonexistent_dir),
             force: false,
+            incremental: false,
+            migrate: false,
             max_concurrent: 5,
             progress: false,
             no_progress: false,
@@ -397,6 +477,10 @@ Test skill content"#,
                 openai_base_url: "https://api.openai.com/v1".to_string(),
                 embedding_model: "text-embedding-3-small".to_string(),
                 index_path: None,
+                batch_size: None,
+                chunk_size: None,
+                query_cache_size: None,
+                query_cache_ttl: None,
             }),
             ..Default::default()
         };
@@ -411,6 +495,8 @@ Test skill content"#,
         let args = ReindexArgs {
             skills_dir: Some(skills_dir),
             force: true,
+            incremental: false,
+            migrate: false,
             max_concurrent: 5,
             progress: false,
             no_progress: false,
@@ -442,6 +528,10 @@ Test skill content"#,
                 openai_base_url: "https://api.openai.com/v1".to_string(),
                 embedding_model: "text-embedding-3-small".to_string(),
                 index_path: None,
+                batch_size: None,
+                chunk_size: None,
+                query_cache_size: None,
+                query_cache_ttl: None,
             }),
             ..Default::default()
         };
@@ -456,6 +546,8 @@ Test skill content"#,
         let args = ReindexArgs {
             skills_dir: Some(skills_dir.clone()),
             force: false,
+            incremental: false,
+            migrate: false,
             max_concurrent: 2,
             progress: true,
             no_progress: false,
@@ -467,6 +559,8 @@ Test skill content"#,
         let force_args = ReindexArgs {
             skills_dir: Some(skills_dir),
             force: true,
+            incremental: false,
+            migrate: false,
             max_concurrent: 2,
             progress: false,
             no_progress: true,
@@ -492,6 +586,8 @@ Test skill content"#,
         let args = ReindexArgs {
             skills_dir: None,
             force: false,
+            incremental: false,
+            migrate: false,
             max_concurrent: 5,
             progress: true,
             no_progress: true,
@@ -500,6 +596,61 @@ Test skill content"#,
         assert!(matches!(result, Err(CliError::Validation(_))));
     }
 
+    #[tokio::test]
+    async fn test_reindex_force_incremental_conflict_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = ServiceConfig {
+            skill_storage_path: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        let mut service = FastSkillService::new(config).await.unwrap();
+        service.initialize().await.unwrap();
+
+        let args = ReindexArgs {
+            skills_dir: None,
+            force: true,
+            incremental: true,
+            migrate: false,
+            max_concurrent: 5,
+            progress: false,
+            no_progress: false,
+        };
+        let result = execute_reindex(&service, args).await;
+        assert!(matches!(result, Err(CliError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_reindex_migrate_force_conflict_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = ServiceConfig {
+            skill_storage_path: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        let mut service = FastSkillService::new(config).await.unwrap();
+        service.initialize().await.unwrap();
+
+        let args = ReindexArgs {
+            skills_dir: None,
+            force: true,
+            incremental: false,
+            migrate: true,
+            max_concurrent: 5,
+            progress: false,
+            no_progress: false,
+        };
+        let result = execute_reindex(&service, args).await;
+        assert!(matches!(result, Err(CliError::Validation(_))));
+    }
+
+    #[test]
+    fn test_reindex_args_parse_migrate() {
+        let mut map = HashMap::new();
+        map.insert("migrate".to_string(), ArgValue::Bool(true));
+        let args = ReindexArgs::from_arg_value_map(&map);
+        assert!(args.migrate);
+        assert!(!args.force && !args.incremental);
+    }
+
     /// Deterministic, network-free embedding provider for tests.
     struct MockEmbeddingService;
 
@@ -2,16 +2,17 @@
 
 use crate::config::get_skill_search_locations_for_display;
 use crate::error::{CliError, CliResult, SkillNotFoundMessage};
+use crate::utils::audit_utils;
+use crate::utils::change_plan::{ChangePlan, PlannedChange};
 use crate::utils::manifest_utils;
 use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
 use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
 use cli_framework::spec::command_tree::CommandSpec;
 use cli_framework::spec::value::ArgValue;
-use fastskill_core::core::lock::project_lock_path;
-use fastskill_core::core::project::resolve_project_file;
+use fastskill_core::core::audit::AuditDetails;
+use fastskill_core::core::scope;
 use fastskill_core::FastSkillService;
 use std::collections::HashMap;
-use std::env;
 use std::io::{self, Write};
 use std::path::PathBuf;
 use tokio::fs;
@@ -43,6 +44,12 @@ pub struct RemoveArgs {
 
     /// Skip reindex after removal
     pub no_reindex: bool,
+
+    /// Print what would be removed without removing anything
+    pub dry_run: bool,
+
+    /// Print the --dry-run plan as JSON
+    pub json: bool,
 }
 
 impl IntoCommandSpec for RemoveArgs {
@@ -107,6 +114,28 @@ impl IntoCommandSpec for RemoveArgs {
                     default: None,
                     ..Default::default()
                 },
+                ArgSpec {
+                    name: "dry-run",
+                    long: Some("dry-run"),
+                    short: None,
+                    help: "Show the skills and lock file entries that would be removed, without removing them",
+                    kind: ArgKind::Flag,
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "json",
+                    long: Some("json"),
+                    short: None,
+                    help: "Print the --dry-run plan as JSON",
+                    kind: ArgKind::Flag,
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    ..Default::default()
+                },
             ],
             ..Default::default()
         }
@@ -139,6 +168,8 @@ impl FromArgValueMap for RemoveArgs {
             }),
             reindex: matches!(map.get("reindex"), Some(ArgValue::Bool(true))),
             no_reindex: matches!(map.get("no-reindex"), Some(ArgValue::Bool(true))),
+            dry_run: matches!(map.get("dry-run"), Some(ArgValue::Bool(true))),
+            json: matches!(map.get("json"), Some(ArgValue::Bool(true))),
         }
     }
 }
@@ -275,24 +306,6 @@ async fn delete_skill_directory(service: &FastSkillService, skill_id: &str) -> C
     Ok(())
 }
 
-/// Remove skill from lock file
-fn remove_from_lock_file(skill_id: &str) -> CliResult<()> {
-    let current_dir = env::current_dir()
-        .map_err(|e| CliError::Config(format!("Failed to get current directory: {}", e)))?;
-    let project_file_result = resolve_project_file(&current_dir);
-    let lock_path = project_lock_path(&project_file_result.path);
-
-    if lock_path.exists() {
-        let mut lock = fastskill_core::core::lock::ProjectSkillsLock::load_from_file(&lock_path)
-            .map_err(|e| CliError::Config(format!("Failed to load lock file: {}", e)))?;
-        lock.remove_skill(skill_id);
-        lock.save_to_file(&lock_path)
-            .map_err(|e| CliError::Config(format!("Failed to save lock file: {}", e)))?;
-    }
-
-    Ok(())
-}
-
 /// Remove skill from vector index
 async fn remove_from_vector_index(service: &FastSkillService, skill_id: &str) {
     if let Some(vector_index_service) = service.vector_index_service() {
@@ -314,21 +327,24 @@ async fn remove_single_skill(
     global: bool,
 ) -> CliResult<()> {
     // Unregister from service
-    unregister_skill_from_service(service, skill_id).await?;
+    unregister_skill_from_service(service, skill_id.clone()).await?;
 
-    // Delete directory
+    // Delete directory, and its scope directory once that is empty
     delete_skill_directory(service, raw_id).await?;
+    scope::remove_scope_dir_if_empty(&service.config().skill_storage_path, &skill_id)?;
 
     if global {
         // Remove from global lock only; do not touch project manifest or project lock
         manifest_utils::remove_from_global_lock_file(raw_id)
             .map_err(|e| CliError::Config(format!("Failed to update global lock file: {}", e)))?;
     } else {
-        // Remove from manifest
-        manifest_utils::remove_skill_from_project_toml(raw_id)
-            .map_err(|e| CliError::Config(format!("Failed to update skill-project.toml: {}", e)))?;
-        // Remove from lock file
-        remove_from_lock_file(raw_id)?;
+        // Remove from manifest and lock file together
+        manifest_utils::remove_skill_from_project(raw_id).map_err(|e| {
+            CliError::Config(format!(
+                "Failed to update skill-project.toml and skills.lock: {}",
+                e
+            ))
+        })?;
     }
 
     // Remove from vector index (non-critical, just log warnings)
@@ -347,6 +363,11 @@ pub async fn execute_remove(
             "--reindex and --no-reindex cannot be used together".to_string(),
         ));
     }
+    if args.json && !args.dry_run {
+        return Err(CliError::Validation(
+            "--json requires --dry-run".to_string(),
+        ));
+    }
     let reindex = args.reindex;
     let no_reindex = args.no_reindex;
 
@@ -358,6 +379,27 @@ pub async fn execute_remove(
     // Validate all skills exist; get back parsed SkillIds
     let parsed_ids = validate_skills_exist(service, &args.skill_ids, global).await?;
 
+    if args.dry_run {
+        let mut changes = Vec::with_capacity(parsed_ids.len());
+        for (skill_id, raw_id) in parsed_ids.iter().zip(args.skill_ids.iter()) {
+            let version = service
+                .skill_manager()
+                .get_skill(skill_id)
+                .await
+                .ok()
+                .flatten()
+                .map(|skill| skill.version);
+            changes.push(PlannedChange::remove(raw_id.clone(), version));
+        }
+        let lockfile = if global {
+            "global-skills.lock"
+        } else {
+            "skills.lock"
+        };
+        ChangePlan::new("remove", lockfile, changes).print(args.json);
+        return Ok(());
+    }
+
     // Get user confirmation
     if !confirm_removal(&args.skill_ids, args.force)? {
         println!("Removal cancelled.");
@@ -367,7 +409,26 @@ pub async fn execute_remove(
     // Remove each skill
     let mut removed_count = 0;
     for (skill_id, raw_id) in parsed_ids.into_iter().zip(args.skill_ids.iter()) {
-        remove_single_skill(service, skill_id, raw_id, global).await?;
+        let version = service
+            .skill_manager()
+            .get_skill(&skill_id)
+            .await
+            .ok()
+            .flatten()
+            .map(|skill| skill.version);
+        let removed = remove_single_skill(service, skill_id, raw_id, global).await;
+        audit_utils::record(
+            &service.config().skill_storage_path,
+            "skill.remove",
+            "remove",
+            AuditDetails {
+                target: Some(raw_id.clone()),
+                version,
+                source: None,
+            },
+            &removed,
+        );
+        removed?;
         println!("Removed skill: {}", raw_id);
         removed_count += 1;
     }
@@ -375,12 +436,12 @@ pub async fn execute_remove(
     // Display success message
     if removed_count > 0 {
         if global {
-            println!(
+            crate::utils::messages::status!(
                 "{}",
                 crate::utils::messages::ok("Updated global-skills.lock")
             );
         } else {
-            println!(
+            crate::utils::messages::status!(
                 "{}",
                 crate::utils::messages::ok("Updated skill-project.toml and skills.lock")
             );
@@ -429,6 +490,8 @@ mod tests {
             skills_dir: None,
             reindex: false,
             no_reindex: false,
+            dry_run: false,
+            json: false,
         };
 
         let result = execute_remove(&service, args, false).await;
@@ -457,6 +520,8 @@ mod tests {
             skills_dir: None,
             reindex: false,
             no_reindex: false,
+            dry_run: false,
+            json: false,
         };
 
         let result = execute_remove(&service, args, false).await;
@@ -485,6 +550,8 @@ mod tests {
             skills_dir: None,
             reindex: false,
             no_reindex: false,
+            dry_run: false,
+            json: false,
         };
 
         // This should fail because the skill doesn't exist
@@ -552,6 +619,8 @@ source = { path = ".claude/skills/test-skill" }
             skills_dir: None,
             reindex: false,
             no_reindex: false,
+            dry_run: false,
+            json: false,
         };
 
         let result = execute_remove(&service, args, false).await;
@@ -559,6 +628,38 @@ source = { path = ".claude/skills/test-skill" }
         assert!(result.is_ok() || result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_execute_remove_dry_run_keeps_skill() {
+        let temp_dir = TempDir::new().unwrap();
+        let skill_dir = temp_dir.path().join("test-skill");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        std::fs::write(
+            skill_dir.join("SKILL.md"),
+            "---\nname: test-skill\ndescription: A test skill\n---\n",
+        )
+        .unwrap();
+
+        let config = ServiceConfig {
+            skill_storage_path: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        let mut service = FastSkillService::new(config).await.unwrap();
+        service.initialize().await.unwrap();
+
+        let args = RemoveArgs {
+            skill_ids: vec!["test-skill".to_string()],
+            force: false,
+            skills_dir: None,
+            reindex: false,
+            no_reindex: false,
+            dry_run: true,
+            json: true,
+        };
+
+        execute_remove(&service, args, false).await.unwrap();
+        assert!(skill_dir.join("SKILL.md").is_file());
+    }
+
     #[tokio::test]
     async fn test_remove_calls_vector_index_remove_skill() {
         let _lock = fastskill_core::test_utils::DIR_MUTEX
@@ -612,6 +713,10 @@ source = { path = ".claude/skills/test-skill" }
                 openai_base_url: "https://api.openai.com/v1".to_string(),
                 embedding_model: "text-embedding-3-small".to_string(),
                 index_path: None,
+                batch_size: None,
+                chunk_size: None,
+                query_cache_size: None,
+                query_cache_ttl: None,
             }),
             ..Default::default()
         };
@@ -625,6 +730,8 @@ source = { path = ".claude/skills/test-skill" }
             skills_dir: None,
             reindex: false,
             no_reindex: false,
+            dry_run: false,
+            json: false,
         };
 
         let result = execute_remove(&service, args, false).await;
@@ -0,0 +1,187 @@
+//! Report command - generate a skill health report
+//!
+//! Thin wrapper over the core report seam (`FastSkillService::health_report`).
+//! Intended to run on a schedule (cron, CI): the report is printed to stdout,
+//! written to `--output-dir`, posted to `--webhook`, or any combination.
+
+use crate::error::{CliError, CliResult};
+use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
+use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
+use cli_framework::spec::command_tree::CommandSpec;
+use cli_framework::spec::value::ArgValue;
+use fastskill_core::core::report::{post_report, write_report, ReportFormat, ReportOptions};
+use fastskill_core::FastSkillService;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Report command arguments
+#[derive(Debug, Clone)]
+pub struct ReportArgs {
+    /// Output format
+    pub format: ReportFormat,
+    /// Length of the reporting window in days
+    pub since_days: i64,
+    /// Directory to write the report into
+    pub output_dir: Option<PathBuf>,
+    /// Webhook URL to POST the report to
+    pub webhook: Option<String>,
+    /// Similarity threshold for duplicate detection
+    pub threshold: f32,
+}
+
+impl IntoCommandSpec for ReportArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Generate a skill health report",
+            syntax: Some("report [OPTIONS]"),
+            category: Some("packages"),
+            args: vec![
+                ArgSpec {
+                    name: "format",
+                    kind: ArgKind::Option,
+                    long: Some("format"),
+                    value_type: ArgValueType::Enum(vec!["markdown", "html", "json"]),
+                    cardinality: Cardinality::Optional,
+                    default: Some(ArgValue::Enum("markdown".to_string())),
+                    help: "Report format: markdown, html, or json",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "since-days",
+                    kind: ArgKind::Option,
+                    long: Some("since-days"),
+                    value_type: ArgValueType::Int,
+                    cardinality: Cardinality::Optional,
+                    default: Some(ArgValue::Int(7)),
+                    help: "Report on skills added or changed in the last N days",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "output-dir",
+                    kind: ArgKind::Option,
+                    long: Some("output-dir"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    help: "Write the report into this directory (skill-health-<date>.<ext>)",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "webhook",
+                    kind: ArgKind::Option,
+                    long: Some("webhook"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    help: "POST the report to this webhook URL",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "threshold",
+                    kind: ArgKind::Option,
+                    long: Some("threshold"),
+                    value_type: ArgValueType::Float,
+                    cardinality: Cardinality::Optional,
+                    default: Some(ArgValue::Float(0.95)),
+                    help: "Similarity threshold for reporting duplicates (0.0-1.0)",
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for ReportArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        Self {
+            format: match map.get("format") {
+                Some(ArgValue::Enum(s)) | Some(ArgValue::Str(s)) => {
+                    ReportFormat::parse(s).unwrap_or(ReportFormat::Markdown)
+                }
+                _ => ReportFormat::Markdown,
+            },
+            since_days: match map.get("since-days") {
+                Some(ArgValue::Int(n)) => *n,
+                _ => 7,
+            },
+            output_dir: match map.get("output-dir") {
+                Some(ArgValue::Str(s)) => Some(PathBuf::from(s)),
+                _ => None,
+            },
+            webhook: match map.get("webhook") {
+                Some(ArgValue::Str(s)) => Some(s.clone()),
+                _ => None,
+            },
+            threshold: match map.get("threshold") {
+                Some(ArgValue::Float(f)) => *f as f32,
+                Some(ArgValue::Int(i)) => *i as f32,
+                _ => 0.95,
+            },
+        }
+    }
+}
+
+pub async fn execute_report(service: &FastSkillService, args: ReportArgs) -> CliResult<()> {
+    if args.since_days < 0 {
+        return Err(CliError::Validation(format!(
+            "--since-days must be zero or greater, got {}",
+            args.since_days
+        )));
+    }
+    if !(0.0..=1.0).contains(&args.threshold) {
+        return Err(CliError::Validation(format!(
+            "--threshold must be between 0.0 and 1.0, got {}",
+            args.threshold
+        )));
+    }
+
+    let options = ReportOptions {
+        since: chrono::Utc::now() - chrono::Duration::days(args.since_days),
+        duplicate_threshold: args.threshold,
+    };
+    let report = service.health_report(&options).await?;
+
+    if args.output_dir.is_none() && args.webhook.is_none() {
+        println!("{}", report.render(args.format)?);
+        return Ok(());
+    }
+
+    if let Some(dir) = &args.output_dir {
+        let path = write_report(&report, args.format, dir)?;
+        println!("Report written to {}", path.display());
+    }
+    if let Some(url) = &args.webhook {
+        post_report(&report, args.format, url).await?;
+        println!("Report posted to webhook");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_args_defaults() {
+        let args = ReportArgs::from_arg_value_map(&HashMap::new());
+        assert_eq!(args.format, ReportFormat::Markdown);
+        assert_eq!(args.since_days, 7);
+        assert!(args.output_dir.is_none());
+        assert!(args.webhook.is_none());
+        assert!((args.threshold - 0.95).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_report_args_parses_format() {
+        let mut map = HashMap::new();
+        map.insert("format".to_string(), ArgValue::Enum("html".to_string()));
+        map.insert(
+            "output-dir".to_string(),
+            ArgValue::Str("reports".to_string()),
+        );
+        let args = ReportArgs::from_arg_value_map(&map);
+        assert_eq!(args.format, ReportFormat::Html);
+        assert_eq!(args.output_dir, Some(PathBuf::from("reports")));
+    }
+}
@@ -0,0 +1,347 @@
+//! Retire command - announce a skill's removal from the registry index
+//!
+//! `retire <org/skill>` records the retirement and its removal date in the
+//! index `fastskill serve` publishes from (`REGISTRY_INDEX_PATH`), then reports
+//! which published skills depend on it and which of the given projects
+//! (`--lock`) have it locked. `retire --purge` removes skills whose grace
+//! period has ended; run it on a schedule.
+
+use crate::error::{CliError, CliResult};
+use crate::utils::audit_utils;
+use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
+use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
+use cli_framework::spec::command_tree::CommandSpec;
+use cli_framework::spec::value::ArgValue;
+use fastskill_core::core::audit::AuditDetails;
+use fastskill_core::core::retirement::{
+    find_affected_projects, find_dependents, purge_expired, retire_skill, unretire_skill,
+    RetirementReport,
+};
+use fastskill_core::FastSkillService;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Grace period before a retired skill may be purged, unless `--grace-days` says otherwise
+const DEFAULT_GRACE_DAYS: i64 = 30;
+
+/// Arguments for `retire`
+#[derive(Debug, Clone)]
+pub struct RetireArgs {
+    /// `org/skill` to retire; absent with `--purge`
+    pub skill: Option<String>,
+    pub grace_days: i64,
+    pub reason: Option<String>,
+    /// Skill dependents should move to
+    pub replacement: Option<String>,
+    /// `skills.lock` files of projects to check
+    pub locks: Vec<PathBuf>,
+    /// Write the report as JSON to this file
+    pub report: Option<PathBuf>,
+    /// Withdraw the retirement instead
+    pub undo: bool,
+    /// Remove skills whose grace period has ended
+    pub purge: bool,
+}
+
+impl IntoCommandSpec for RetireArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Retire a skill: report its dependents and schedule its removal",
+            syntax: Some("retire <ORG/SKILL> [OPTIONS] | retire --purge"),
+            category: Some("server"),
+            args: vec![
+                ArgSpec {
+                    name: "skill",
+                    kind: ArgKind::Positional,
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    help: "Skill to retire (e.g. 'acme/web-scraper')",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "grace-days",
+                    kind: ArgKind::Option,
+                    long: Some("grace-days"),
+                    value_type: ArgValueType::Int,
+                    cardinality: Cardinality::Optional,
+                    default: Some(ArgValue::Int(DEFAULT_GRACE_DAYS)),
+                    help: "Days until the skill is removed from the index (default: 30)",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "reason",
+                    kind: ArgKind::Option,
+                    long: Some("reason"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    help: "Why the skill is retired, shown to its users",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "replacement",
+                    kind: ArgKind::Option,
+                    long: Some("replacement"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    help: "Skill its users should move to",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "lock",
+                    kind: ArgKind::Option,
+                    long: Some("lock"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Repeated,
+                    help: "skills.lock of a project to check; repeatable",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "report",
+                    kind: ArgKind::Option,
+                    long: Some("report"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    help: "Write the impact report as JSON to this file",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "undo",
+                    kind: ArgKind::Flag,
+                    long: Some("undo"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Withdraw the retirement",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "purge",
+                    kind: ArgKind::Flag,
+                    long: Some("purge"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Remove every retired skill whose grace period has ended",
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for RetireArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        let string = |key: &str| match map.get(key) {
+            Some(ArgValue::Str(s)) => Some(s.clone()),
+            _ => None,
+        };
+        Self {
+            skill: string("skill"),
+            grace_days: match map.get("grace-days") {
+                Some(ArgValue::Int(n)) => *n,
+                _ => DEFAULT_GRACE_DAYS,
+            },
+            reason: string("reason"),
+            replacement: string("replacement"),
+            locks: match map.get("lock") {
+                Some(ArgValue::List(items)) => items
+                    .iter()
+                    .filter_map(|item| match item {
+                        ArgValue::Str(s) => Some(PathBuf::from(s)),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            },
+            report: string("report").map(PathBuf::from),
+            undo: matches!(map.get("undo"), Some(ArgValue::Bool(true))),
+            purge: matches!(map.get("purge"), Some(ArgValue::Bool(true))),
+        }
+    }
+}
+
+fn print_report(report: &RetirementReport) {
+    let retirement = &report.retirement;
+    println!(
+        "Retired {}; it will be removed from the index after {}",
+        report.skill_id,
+        retirement.remove_after.format("%Y-%m-%d %H:%M UTC")
+    );
+    if let Some(reason) = &retirement.reason {
+        println!("  Reason: {}", reason);
+    }
+    if let Some(replacement) = &retirement.replacement {
+        println!("  Replacement: {}", replacement);
+    }
+
+    if report.dependents.is_empty() {
+        println!("\nNo published skills depend on it.");
+    } else {
+        println!("\nPublished skills that depend on it:");
+        for dependent in &report.dependents {
+            println!(
+                "  {} {} (requires {})",
+                dependent.skill_id,
+                dependent.versions.join(", "),
+                dependent.req
+            );
+        }
+    }
+
+    if report.affected_projects.is_empty() {
+        return;
+    }
+    println!("\nProjects that lock it:");
+    for project in &report.affected_projects {
+        let via = match &project.required_by {
+            Some(parent) if !project.direct => format!(", via {}", parent),
+            _ => String::new(),
+        };
+        println!(
+            "  {} ({}{})",
+            project.lock_path.display(),
+            project.locked_version,
+            via
+        );
+    }
+}
+
+fn purge(registry_index_path: &Path) -> CliResult<()> {
+    let removed = purge_expired(registry_index_path, chrono::Utc::now())?;
+    if removed.is_empty() {
+        println!("No retired skills are past their grace period");
+    }
+    for id in removed {
+        println!("Removed {} from the registry index", id);
+    }
+    Ok(())
+}
+
+pub async fn execute_retire(service: &FastSkillService, args: RetireArgs) -> CliResult<()> {
+    let registry_index_path = service
+        .config()
+        .registry_index_path
+        .as_ref()
+        .ok_or_else(|| {
+            CliError::Config(
+                "No registry index configured; set REGISTRY_INDEX_PATH to the index \
+                 directory `fastskill serve` publishes from"
+                    .to_string(),
+            )
+        })?;
+
+    let skills_dir = &service.config().skill_storage_path;
+    let skill = match (&args.skill, args.purge) {
+        (None, true) => {
+            let purged = purge(registry_index_path);
+            audit_utils::record(
+                skills_dir,
+                "registry.purge",
+                "retire",
+                AuditDetails::default(),
+                &purged,
+            );
+            return purged;
+        }
+        (Some(_), true) => {
+            return Err(CliError::Validation(
+                "--purge removes every expired retirement; do not name a skill".to_string(),
+            ))
+        }
+        (None, false) => {
+            return Err(CliError::Validation(
+                "Name the skill to retire (e.g. 'acme/web-scraper'), or pass --purge".to_string(),
+            ))
+        }
+        (Some(skill), false) => skill,
+    };
+
+    let details = AuditDetails {
+        target: Some(fastskill_core::core::registry_index::ScopedSkillName::normalize(skill)),
+        ..Default::default()
+    };
+    if args.undo {
+        let unretired = unretire_skill(registry_index_path, skill);
+        audit_utils::record(
+            skills_dir,
+            "registry.unretire",
+            "retire",
+            details,
+            &unretired,
+        );
+        if unretired? {
+            println!("{} is no longer retired", skill);
+        } else {
+            println!("{} was not retired", skill);
+        }
+        return Ok(());
+    }
+    if args.grace_days < 0 {
+        return Err(CliError::Validation(format!(
+            "--grace-days must be zero or greater, got {}",
+            args.grace_days
+        )));
+    }
+
+    let retirement = retire_skill(
+        registry_index_path,
+        skill,
+        chrono::Duration::days(args.grace_days),
+        args.reason,
+        args.replacement,
+    );
+    audit_utils::record(
+        skills_dir,
+        "registry.retire",
+        "retire",
+        details,
+        &retirement,
+    );
+    let retirement = retirement?;
+    let report = RetirementReport {
+        skill_id: fastskill_core::core::registry_index::ScopedSkillName::normalize(skill),
+        retirement,
+        dependents: find_dependents(registry_index_path, skill)?,
+        affected_projects: find_affected_projects(&args.locks, skill)?,
+    };
+    print_report(&report);
+
+    if let Some(path) = &args.report {
+        let json = serde_json::to_string_pretty(&report)
+            .map_err(|e| CliError::Config(format!("Failed to encode report: {}", e)))?;
+        std::fs::write(path, json)?;
+        println!("\nWrote report to {}", path.display());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retire_args_parse() {
+        let mut map = HashMap::new();
+        map.insert(
+            "skill".to_string(),
+            ArgValue::Str("acme/web-scraper".to_string()),
+        );
+        map.insert("grace-days".to_string(), ArgValue::Int(14));
+        map.insert(
+            "lock".to_string(),
+            ArgValue::List(vec![
+                ArgValue::Str("a/skills.lock".to_string()),
+                ArgValue::Str("b/skills.lock".to_string()),
+            ]),
+        );
+        let args = RetireArgs::from_arg_value_map(&map);
+        assert_eq!(args.skill.as_deref(), Some("acme/web-scraper"));
+        assert_eq!(args.grace_days, 14);
+        assert_eq!(args.locks.len(), 2);
+        assert!(!args.undo);
+        assert!(!args.purge);
+
+        let defaults = RetireArgs::from_arg_value_map(&HashMap::new());
+        assert_eq!(defaults.grace_days, DEFAULT_GRACE_DAYS);
+        assert!(defaults.skill.is_none());
+    }
+}
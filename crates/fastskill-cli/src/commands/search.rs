@@ -55,6 +55,9 @@ pub struct SearchArgs {
 
     /// Content to include in JSON output: none, preview, full (--local --paths only)
     pub content: Option<String>,
+
+    /// Show a score breakdown for each match (--local only)
+    pub explain: bool,
 }
 
 impl IntoCommandSpec for SearchArgs {
@@ -185,6 +188,17 @@ impl IntoCommandSpec for SearchArgs {
                     default: None,
                     ..Default::default()
                 },
+                ArgSpec {
+                    name: "explain",
+                    long: Some("explain"),
+                    short: None,
+                    help: "Show a score breakdown for each match (--local only)",
+                    kind: ArgKind::Flag,
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    ..Default::default()
+                },
             ],
             ..Default::default()
         }
@@ -253,6 +267,7 @@ impl FromArgValueMap for SearchArgs {
                     None
                 }
             }),
+            explain: matches!(map.get("explain"), Some(ArgValue::Bool(true))),
         }
     }
 }
@@ -289,6 +304,7 @@ pub async fn execute_search(service: &FastSkillService, args: SearchArgs) -> Cli
         scope,
         limit: args.limit,
         embedding: embedding_mode,
+        explain: args.explain,
     };
 
     // Execute search
@@ -317,6 +333,7 @@ async fn execute_search_with_paths(service: &FastSkillService, args: &SearchArgs
         scope: ResolveScope::Local,
         include_content: content_mode,
         resolve_paths: true,
+        max_tokens: None,
     };
 
     let response = service
@@ -375,6 +392,18 @@ fn validate_search_args(args: &SearchArgs) -> CliResult<()> {
         ));
     }
 
+    if args.explain && !args.local {
+        return Err(CliError::Config(
+            "Error: --explain requires --local; remote catalogs do not report scores. Use 'fastskill search --local --explain <query>'.".to_string(),
+        ));
+    }
+
+    if args.explain && args.paths {
+        return Err(CliError::Config(
+            "Error: --explain cannot be combined with --paths.".to_string(),
+        ));
+    }
+
     // Validate the content mode value eagerly so an invalid value is rejected
     // regardless of whether --paths is also present.
     if let Some(content) = args.content.as_deref() {
@@ -444,6 +473,7 @@ mod tests {
             skills_dir: None,
             paths: false,
             content: None,
+            explain: false,
         };
 
         let result = validate_search_args(&args);
@@ -467,6 +497,7 @@ mod tests {
             skills_dir: None,
             paths: true,
             content: None,
+            explain: false,
         };
         let result = validate_search_args(&args);
         assert!(result.is_err());
@@ -479,6 +510,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_search_args_explain_requires_local() {
+        let mut args = SearchArgs {
+            query: "test".to_string(),
+            local: false,
+            remote: false,
+            repository: None,
+            limit: 10,
+            format: None,
+            json: false,
+            embedding: None,
+            skills_dir: None,
+            paths: false,
+            content: None,
+            explain: true,
+        };
+        match validate_search_args(&args) {
+            Err(CliError::Config(msg)) => assert!(msg.contains("--explain requires --local")),
+            other => panic!("expected CliError::Config, got {:?}", other),
+        }
+
+        args.local = true;
+        assert!(validate_search_args(&args).is_ok());
+    }
+
     #[test]
     fn test_validate_search_args_content_without_local() {
         let args = SearchArgs {
@@ -493,6 +549,7 @@ mod tests {
             skills_dir: None,
             paths: false,
             content: Some("full".to_string()),
+            explain: false,
         };
         let result = validate_search_args(&args);
         assert!(matches!(result, Err(CliError::Config(_))));
@@ -512,6 +569,7 @@ mod tests {
             skills_dir: None,
             paths: false,
             content: Some("bogus".to_string()),
+            explain: false,
         };
         let result = validate_search_args(&args);
         match result {
@@ -534,6 +592,7 @@ mod tests {
             skills_dir: None,
             paths: true,
             content: Some("full".to_string()),
+            explain: false,
         };
         assert!(validate_search_args(&args).is_ok());
     }
@@ -552,6 +611,7 @@ mod tests {
             skills_dir: None,
             paths: false,
             content: None,
+            explain: false,
         };
 
         let result = validate_search_args(&args);
@@ -572,6 +632,7 @@ mod tests {
             skills_dir: None,
             paths: false,
             content: None,
+            explain: false,
         };
 
         let result = validate_search_args(&args);
@@ -592,6 +653,7 @@ mod tests {
             skills_dir: None,
             paths: false,
             content: None,
+            explain: false,
         };
 
         let scope = determine_search_scope(&args).unwrap();
@@ -612,6 +674,7 @@ mod tests {
             skills_dir: None,
             paths: false,
             content: None,
+            explain: false,
         };
 
         let scope = determine_search_scope(&args).unwrap();
@@ -632,6 +695,7 @@ mod tests {
             skills_dir: None,
             paths: false,
             content: None,
+            explain: false,
         };
 
         let scope = determine_search_scope(&args).unwrap();
@@ -652,6 +716,7 @@ mod tests {
             skills_dir: None,
             paths: false,
             content: None,
+            explain: false,
         };
 
         let format = determine_output_format(&args).unwrap();
@@ -672,6 +737,7 @@ mod tests {
             skills_dir: None,
             paths: false,
             content: None,
+            explain: false,
         };
 
         let format = determine_output_format(&args).unwrap();
@@ -702,6 +768,7 @@ mod tests {
             skills_dir: None,
             paths: false,
             content: None,
+            explain: false,
         };
 
         let result = execute_search(&service, args).await;
@@ -723,6 +790,7 @@ mod tests {
             skills_dir: None,
             paths: false,
             content: None,
+            explain: false,
         };
 
         let result = validate_search_args(&args);
@@ -743,6 +811,7 @@ mod tests {
             skills_dir: None,
             paths: false,
             content: None,
+            explain: false,
         };
 
         let mode = determine_embedding_mode(&args);
@@ -0,0 +1,493 @@
+//! Self-update command - replace the running binary with a newer release
+//!
+//! Releases are read from the GitHub releases API (or any endpoint serving the
+//! same JSON). The platform archive is checked against the release's
+//! `checksums.txt` before the binary is extracted and swapped in place with a
+//! rename, so an interrupted update never leaves a half-written executable.
+
+use crate::error::{CliError, CliResult};
+use crate::utils::messages;
+use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
+use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
+use cli_framework::spec::command_tree::CommandSpec;
+use cli_framework::spec::value::ArgValue;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::Path;
+
+const DEFAULT_RELEASES_ENDPOINT: &str =
+    "https://api.github.com/repos/gofastskill/fastskill/releases?per_page=30";
+
+/// Environment variable overriding the releases endpoint.
+const RELEASES_ENDPOINT_ENV: &str = "FASTSKILL_UPDATE_ENDPOINT";
+
+const CHECKSUMS_ASSET: &str = "checksums.txt";
+
+/// Arguments for `self-update`
+#[derive(Debug, Clone)]
+pub struct SelfUpdateArgs {
+    /// Only report whether a newer release exists
+    pub check_only: bool,
+    /// Include pre-releases (`beta`) or not (`stable`)
+    pub channel: Option<String>,
+    /// Releases endpoint (GitHub releases API format)
+    pub endpoint: Option<String>,
+}
+
+impl IntoCommandSpec for SelfUpdateArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Update the fastskill binary to the latest release",
+            syntax: Some("self-update [OPTIONS]"),
+            category: Some("setup"),
+            args: vec![
+                ArgSpec {
+                    name: "check-only",
+                    kind: ArgKind::Flag,
+                    long: Some("check-only"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Report whether a newer release exists without installing it",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "channel",
+                    kind: ArgKind::Option,
+                    long: Some("channel"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    help: "Release channel: stable or beta (default: stable)",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "endpoint",
+                    kind: ArgKind::Option,
+                    long: Some("endpoint"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    help: "Releases endpoint in GitHub releases API format (default: GitHub, or FASTSKILL_UPDATE_ENDPOINT)",
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for SelfUpdateArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        let string = |key: &str| match map.get(key) {
+            Some(ArgValue::Str(s)) => Some(s.clone()),
+            _ => None,
+        };
+        Self {
+            check_only: matches!(map.get("check-only"), Some(ArgValue::Bool(true))),
+            channel: string("channel"),
+            endpoint: string("endpoint"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Channel {
+    Stable,
+    Beta,
+}
+
+impl Channel {
+    fn as_str(self) -> &'static str {
+        match self {
+            Channel::Stable => "stable",
+            Channel::Beta => "beta",
+        }
+    }
+
+    fn parse(value: Option<&str>) -> CliResult<Self> {
+        match value.unwrap_or("stable") {
+            "stable" => Ok(Channel::Stable),
+            "beta" => Ok(Channel::Beta),
+            other => Err(CliError::Validation(format!(
+                "Invalid channel '{}'. Use stable or beta",
+                other
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Release {
+    tag_name: String,
+    #[serde(default)]
+    prerelease: bool,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ReleaseAsset {
+    name: String,
+    browser_download_url: String,
+}
+
+impl Release {
+    fn version(&self) -> Option<semver::Version> {
+        semver::Version::parse(self.tag_name.trim_start_matches('v')).ok()
+    }
+
+    fn asset(&self, name: &str) -> Option<&ReleaseAsset> {
+        self.assets.iter().find(|a| a.name == name)
+    }
+}
+
+/// Release archive name for the platform this binary was built for.
+fn platform_asset_name() -> Option<&'static str> {
+    if cfg!(all(
+        target_os = "linux",
+        target_arch = "x86_64",
+        target_env = "musl"
+    )) {
+        Some("fastskill-x86_64-unknown-linux-musl.tar.gz")
+    } else if cfg!(all(target_os = "linux", target_arch = "x86_64")) {
+        Some("fastskill-x86_64-unknown-linux-gnu.tar.gz")
+    } else if cfg!(all(target_os = "macos", target_arch = "aarch64")) {
+        Some("fastskill-aarch64-apple-darwin.tar.gz")
+    } else if cfg!(all(target_os = "macos", target_arch = "x86_64")) {
+        Some("fastskill-x86_64-apple-darwin.tar.gz")
+    } else if cfg!(all(target_os = "windows", target_arch = "x86_64")) {
+        Some("fastskill-x86_64-pc-windows-msvc.zip")
+    } else {
+        None
+    }
+}
+
+/// Newest non-draft release on `channel`, ignoring tags that are not semver.
+fn latest_release(releases: &[Release], channel: Channel) -> Option<(&Release, semver::Version)> {
+    releases
+        .iter()
+        .filter(|r| !r.draft && (channel == Channel::Beta || !r.prerelease))
+        .filter_map(|r| r.version().map(|v| (r, v)))
+        .filter(|(_, v)| channel == Channel::Beta || v.pre.is_empty())
+        .max_by(|a, b| a.1.cmp(&b.1))
+}
+
+/// Expected SHA-256 (lowercase hex) for `asset` in `sha256sum` output.
+fn expected_checksum(checksums: &str, asset: &str) -> Option<String> {
+    checksums.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        let hash = parts.next()?;
+        let name = parts.next()?.trim_start_matches('*');
+        (name == asset).then(|| hash.to_ascii_lowercase())
+    })
+}
+
+/// True when `path` names `binary` without escaping the archive root.
+fn is_binary_entry(path: &str, binary: &str) -> bool {
+    let components: Vec<&str> = path
+        .split(['/', '\\'])
+        .filter(|c| !c.is_empty() && *c != ".")
+        .collect();
+    !components.contains(&"..") && components.last() == Some(&binary)
+}
+
+/// Read the regular file named `binary` out of a tar stream. Only that one
+/// entry is returned; nothing is written to disk by path.
+fn binary_from_tar(reader: impl Read, binary: &str) -> CliResult<Vec<u8>> {
+    let corrupt =
+        |e: std::io::Error| CliError::Validation(format!("Corrupt release archive: {}", e));
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries().map_err(corrupt)? {
+        let mut entry = entry.map_err(corrupt)?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry
+            .path()
+            .map_err(corrupt)?
+            .to_string_lossy()
+            .into_owned();
+        if is_binary_entry(&path, binary) {
+            let mut out = Vec::new();
+            entry.read_to_end(&mut out).map_err(corrupt)?;
+            return Ok(out);
+        }
+    }
+    Err(CliError::Validation(format!(
+        "Release archive does not contain '{}'",
+        binary
+    )))
+}
+
+fn binary_from_archive(asset_name: &str, archive: &[u8]) -> CliResult<Vec<u8>> {
+    let binary = if cfg!(windows) {
+        "fastskill.exe"
+    } else {
+        "fastskill"
+    };
+    if asset_name.ends_with(".zip") {
+        let mut zip = zip::ZipArchive::new(std::io::Cursor::new(archive))
+            .map_err(|e| CliError::Validation(format!("Corrupt release archive: {}", e)))?;
+        for i in 0..zip.len() {
+            let mut file = zip
+                .by_index(i)
+                .map_err(|e| CliError::Validation(format!("Corrupt release archive: {}", e)))?;
+            if file.is_file() && is_binary_entry(file.name(), binary) {
+                let mut out = Vec::new();
+                file.read_to_end(&mut out)?;
+                return Ok(out);
+            }
+        }
+        return Err(CliError::Validation(format!(
+            "Release archive does not contain '{}'",
+            binary
+        )));
+    }
+    binary_from_tar(flate2::read::GzDecoder::new(archive), binary)
+}
+
+/// Write `binary` next to `exe` and rename it over `exe`. Windows cannot
+/// replace a running executable, so the old one is moved aside first.
+fn replace_executable(exe: &Path, binary: &[u8]) -> CliResult<()> {
+    let dir = exe.parent().ok_or_else(|| {
+        CliError::Config(format!("Cannot determine directory of {}", exe.display()))
+    })?;
+    let mut tmp = tempfile::Builder::new()
+        .prefix(".fastskill-update-")
+        .tempfile_in(dir)?;
+    tmp.write_all(binary)?;
+    tmp.as_file().sync_all()?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(tmp.path(), std::fs::Permissions::from_mode(0o755))?;
+    }
+    #[cfg(windows)]
+    {
+        let old = exe.with_extension("old.exe");
+        let _ = std::fs::remove_file(&old);
+        std::fs::rename(exe, &old)?;
+        if let Err(e) = tmp.persist(exe) {
+            // Put the running binary back rather than leave no fastskill at all
+            let _ = std::fs::rename(&old, exe);
+            return Err(CliError::Io(e.error));
+        }
+    }
+    #[cfg(not(windows))]
+    tmp.persist(exe).map_err(|e| CliError::Io(e.error))?;
+    Ok(())
+}
+
+async fn fetch(client: &reqwest::Client, url: &str) -> CliResult<reqwest::Response> {
+    client
+        .get(url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| CliError::Config(format!("Failed to fetch {}: {}", url, e)))
+}
+
+pub async fn execute_self_update(args: SelfUpdateArgs) -> CliResult<()> {
+    let channel = Channel::parse(args.channel.as_deref())?;
+    let endpoint = args
+        .endpoint
+        .or_else(|| std::env::var(RELEASES_ENDPOINT_ENV).ok())
+        .unwrap_or_else(|| DEFAULT_RELEASES_ENDPOINT.to_string());
+    let current = semver::Version::parse(fastskill_core::VERSION)
+        .map_err(|e| CliError::InvalidSemver(e.to_string()))?;
+
+    let client = reqwest::Client::builder()
+        .user_agent(format!("fastskill/{}", fastskill_core::VERSION))
+        .build()
+        .map_err(|e| CliError::Config(format!("Failed to create HTTP client: {}", e)))?;
+    let releases: Vec<Release> = fetch(&client, &endpoint)
+        .await?
+        .json()
+        .await
+        .map_err(|e| CliError::Config(format!("Invalid releases response: {}", e)))?;
+
+    let Some((release, latest)) = latest_release(&releases, channel) else {
+        return Err(CliError::Config(format!(
+            "No {} release found at {}",
+            channel.as_str(),
+            endpoint
+        )));
+    };
+    if latest <= current {
+        println!("fastskill {} is up to date", current);
+        return Ok(());
+    }
+    if args.check_only {
+        println!("fastskill {} is available (installed: {})", latest, current);
+        return Ok(());
+    }
+
+    let asset_name = platform_asset_name().ok_or_else(|| {
+        CliError::Config("No prebuilt release exists for this platform".to_string())
+    })?;
+    let exe = std::env::current_exe()?.canonicalize()?;
+    let exe_path = exe.to_string_lossy();
+    if exe_path.contains("/Cellar/") || exe_path.to_lowercase().contains("\\scoop\\") {
+        return Err(CliError::Validation(format!(
+            "{} is managed by a package manager; update it with `brew upgrade fastskill` or `scoop update fastskill`",
+            exe.display()
+        )));
+    }
+
+    let (asset, checksums) = match (release.asset(asset_name), release.asset(CHECKSUMS_ASSET)) {
+        (Some(asset), Some(checksums)) => (asset, checksums),
+        (None, _) => {
+            return Err(CliError::Config(format!(
+                "Release {} has no asset {}",
+                release.tag_name, asset_name
+            )))
+        }
+        (_, None) => {
+            return Err(CliError::Validation(format!(
+                "Release {} publishes no {}; refusing to install an unverified binary",
+                release.tag_name, CHECKSUMS_ASSET
+            )))
+        }
+    };
+
+    messages::status!(
+        "{}",
+        messages::info(&format!("Downloading {} {}", asset_name, latest))
+    );
+    let checksums = fetch(&client, &checksums.browser_download_url)
+        .await?
+        .text()
+        .await
+        .map_err(|e| CliError::Config(format!("Failed to read {}: {}", CHECKSUMS_ASSET, e)))?;
+    let expected = expected_checksum(&checksums, asset_name).ok_or_else(|| {
+        CliError::Validation(format!(
+            "{} has no entry for {}",
+            CHECKSUMS_ASSET, asset_name
+        ))
+    })?;
+    let archive = fetch(&client, &asset.browser_download_url)
+        .await?
+        .bytes()
+        .await
+        .map_err(|e| CliError::Config(format!("Failed to download {}: {}", asset_name, e)))?;
+    let actual = format!("{:x}", Sha256::digest(&archive));
+    if actual != expected {
+        return Err(CliError::Validation(format!(
+            "Checksum mismatch for {}: expected {}, got {}",
+            asset_name, expected, actual
+        )));
+    }
+
+    let binary = binary_from_archive(asset_name, &archive)?;
+    replace_executable(&exe, &binary)?;
+    messages::status!(
+        "{}",
+        messages::ok(&format!("Updated fastskill {} -> {}", current, latest))
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn release(tag: &str, prerelease: bool) -> Release {
+        Release {
+            tag_name: tag.to_string(),
+            prerelease,
+            draft: false,
+            assets: Vec::new(),
+        }
+    }
+
+    /// A raw tar entry; the name is written as-is, so it may try to escape.
+    fn tar_entry(name: &str, content: &[u8]) -> Vec<u8> {
+        let mut header = tar::Header::new_old();
+        header.as_old_mut().name[..name.len()].copy_from_slice(name.as_bytes());
+        header.set_size(content.len() as u64);
+        header.set_entry_type(tar::EntryType::Regular);
+        header.set_cksum();
+        let mut out = header.as_bytes().to_vec();
+        out.extend_from_slice(content);
+        out.resize(512 + content.len().div_ceil(512) * 512, 0);
+        out
+    }
+
+    #[test]
+    fn test_latest_release_respects_channel() {
+        let releases = vec![
+            release("v0.9.150", false),
+            release("v0.10.0-beta.1", true),
+            release("v0.9.152", false),
+            release("nightly", true),
+        ];
+        let (_, stable) = latest_release(&releases, Channel::Stable).unwrap();
+        assert_eq!(stable.to_string(), "0.9.152");
+        let (_, beta) = latest_release(&releases, Channel::Beta).unwrap();
+        assert_eq!(beta.to_string(), "0.10.0-beta.1");
+        assert!(Channel::parse(Some("nightly")).is_err());
+    }
+
+    #[test]
+    fn test_expected_checksum() {
+        let sums = "AB12  fastskill-x86_64-apple-darwin.tar.gz\ncd34 *fastskill-x86_64-pc-windows-msvc.zip\n";
+        assert_eq!(
+            expected_checksum(sums, "fastskill-x86_64-apple-darwin.tar.gz").as_deref(),
+            Some("ab12")
+        );
+        assert_eq!(
+            expected_checksum(sums, "fastskill-x86_64-pc-windows-msvc.zip").as_deref(),
+            Some("cd34")
+        );
+        assert!(expected_checksum(sums, "other.tar.gz").is_none());
+    }
+
+    #[test]
+    fn test_binary_from_tar_ignores_escaping_entries() {
+        let mut tar = tar_entry("../fastskill", b"evil");
+        tar.extend(tar_entry("README.md", b"docs"));
+        tar.extend(tar_entry("./fastskill", b"good"));
+        tar.extend([0u8; 1024]);
+        assert_eq!(
+            binary_from_tar(tar.as_slice(), "fastskill").unwrap(),
+            b"good"
+        );
+
+        let mut only_malicious = tar_entry("dist/../../fastskill", b"evil");
+        only_malicious.extend([0u8; 1024]);
+        assert!(binary_from_tar(only_malicious.as_slice(), "fastskill").is_err());
+    }
+
+    #[test]
+    fn test_binary_from_tar_reads_long_gnu_names() {
+        let dir = format!("{}/bin", "fastskill-x86_64-unknown-linux-gnu".repeat(4));
+        let mut builder = tar::Builder::new(Vec::new());
+        let mut header = tar::Header::new_gnu();
+        header.set_size(4);
+        header.set_mode(0o755);
+        header.set_entry_type(tar::EntryType::Regular);
+        builder
+            .append_data(&mut header, format!("{}/fastskill", dir), &b"good"[..])
+            .unwrap();
+        let tar = builder.into_inner().unwrap();
+        assert_eq!(
+            binary_from_tar(tar.as_slice(), "fastskill").unwrap(),
+            b"good"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_replace_executable() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let exe = dir.path().join("fastskill");
+        std::fs::write(&exe, b"old").unwrap();
+        replace_executable(&exe, b"new").unwrap();
+        assert_eq!(std::fs::read(&exe).unwrap(), b"new");
+        let leftovers = std::fs::read_dir(dir.path()).unwrap().count();
+        assert_eq!(leftovers, 1);
+    }
+}
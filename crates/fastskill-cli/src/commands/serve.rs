@@ -5,7 +5,14 @@ use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
 use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
 use cli_framework::spec::command_tree::CommandSpec;
 use cli_framework::spec::value::ArgValue;
+use fastskill_core::core::api_keys::ApiKeyStore;
+use fastskill_core::core::integrity::IntegrityGuard;
+use fastskill_core::core::registry_store::RegistryStore;
+use fastskill_core::core::replication::{Follower, ReplicationConfig};
+use fastskill_core::http::preflight::{PreflightOptions, PreflightStatus};
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
 use tracing::info;
 
 /// Serve the FastSkill service via HTTP API
@@ -19,6 +26,24 @@ pub struct ServeArgs {
 
     /// Enable mutating (write) endpoints. Read-only by default (ADR-0003).
     enable_write: bool,
+
+    /// Verify served skill content against hashes recorded at startup
+    verify_integrity: bool,
+
+    /// Also probe configured repositories during the startup preflight
+    check_repos: bool,
+
+    /// Skip the startup preflight checks
+    skip_preflight: bool,
+
+    /// Also serve the gRPC services on this port (needs the `grpc` feature)
+    grpc_port: Option<u16>,
+
+    /// Host a registry from this directory (`index/` and `blobs/`)
+    registry: Option<PathBuf>,
+
+    /// Address clients reach the registry at (default: http://HOST:PORT)
+    public_url: Option<String>,
 }
 
 impl IntoCommandSpec for ServeArgs {
@@ -61,6 +86,75 @@ impl IntoCommandSpec for ServeArgs {
                     default: None,
                     ..Default::default()
                 },
+                ArgSpec {
+                    name: "verify-integrity",
+                    long: Some("verify-integrity"),
+                    short: None,
+                    help: "Verify skill content against hashes recorded at startup; quarantine skills that changed on disk",
+                    kind: ArgKind::Flag,
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "check-repos",
+                    long: Some("check-repos"),
+                    short: None,
+                    help: "Also check that configured repositories are reachable before starting",
+                    kind: ArgKind::Flag,
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    conflicts_with: vec!["skip-preflight"],
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "skip-preflight",
+                    long: Some("skip-preflight"),
+                    short: None,
+                    help: "Start without running the preflight checks",
+                    kind: ArgKind::Flag,
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    conflicts_with: vec!["check-repos"],
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "grpc-port",
+                    long: Some("grpc-port"),
+                    short: None,
+                    help: "Also serve the gRPC API on this port (builds with the grpc feature only)",
+                    kind: ArgKind::Option,
+                    value_type: ArgValueType::Int,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "registry",
+                    long: Some("registry"),
+                    short: None,
+                    help: "Host a skill registry from this directory: publish, index and downloads for http-registry clients",
+                    kind: ArgKind::Option,
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "public-url",
+                    long: Some("public-url"),
+                    short: None,
+                    help: "Address clients reach the registry at, used in download URLs (default: http://HOST:PORT)",
+                    kind: ArgKind::Option,
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    requires: vec!["registry"],
+                    ..Default::default()
+                },
             ],
             ..Default::default()
         }
@@ -94,6 +188,21 @@ impl FromArgValueMap for ServeArgs {
                 .get("enable-write")
                 .map(|v| matches!(v, ArgValue::Bool(true)))
                 .unwrap_or(false),
+            verify_integrity: matches!(map.get("verify-integrity"), Some(ArgValue::Bool(true))),
+            check_repos: matches!(map.get("check-repos"), Some(ArgValue::Bool(true))),
+            skip_preflight: matches!(map.get("skip-preflight"), Some(ArgValue::Bool(true))),
+            grpc_port: match map.get("grpc-port") {
+                Some(ArgValue::Int(n)) => Some(*n as u16),
+                _ => None,
+            },
+            registry: match map.get("registry") {
+                Some(ArgValue::Str(s)) => Some(PathBuf::from(s)),
+                _ => None,
+            },
+            public_url: match map.get("public-url") {
+                Some(ArgValue::Str(s)) => Some(s.clone()),
+                _ => None,
+            },
         }
     }
 }
@@ -103,6 +212,23 @@ pub async fn execute_serve(
     skills_dir: Option<std::path::PathBuf>,
     args: ServeArgs,
 ) -> CliResult<()> {
+    let replication = crate::config::load_replication_config()?;
+    let is_follower = matches!(replication, Some(ReplicationConfig::Follower { .. }));
+    if is_follower && args.enable_write {
+        return Err(CliError::Validation(
+            "--enable-write cannot be used on a replication follower; followers serve \
+             read-only traffic and take every change from the primary"
+                .to_string(),
+        ));
+    }
+
+    #[cfg(not(feature = "grpc"))]
+    if args.grpc_port.is_some() {
+        return Err(CliError::Config(
+            "--grpc-port needs a fastskill build with the `grpc` feature".to_string(),
+        ));
+    }
+
     info!(
         "Starting FastSkill HTTP server on {}:{} (write endpoints {})",
         args.host,
@@ -120,6 +246,9 @@ pub async fn execute_serve(
     } else {
         println!("  Write endpoints: disabled (read-only); pass --enable-write to enable");
     }
+    if args.verify_integrity {
+        println!("  Integrity checks: ENABLED (--verify-integrity)");
+    }
 
     // Build the served service directly (rather than reusing the CLI's cached
     // singleton via `FsState::service_with`) so this exact instance can carry
@@ -129,11 +258,55 @@ pub async fn execute_serve(
     // writes (via `POST /skills/install`, `/skills/update`) must land in the
     // served project's `skill-project.toml`/`skills.lock`, not wherever the
     // server process happens to have cwd set.
-    let cfg = crate::config::create_service_config(global, skills_dir)?;
-    let mut service = fastskill_core::FastSkillService::new(cfg)
+    let mut cfg = crate::config::create_service_config(global, skills_dir)?;
+    cfg.storage = crate::config::load_storage_config()?;
+    cfg.hot_reload = crate::config::load_hot_reload_config()?;
+    cfg.tenancy = crate::config::load_tenancy_config()?;
+    let registry = match &args.registry {
+        Some(root) => {
+            if cfg.tenancy.enabled {
+                return Err(CliError::Config(
+                    "--registry cannot be combined with tenancy".to_string(),
+                ));
+            }
+            let public_url = args
+                .public_url
+                .clone()
+                .unwrap_or_else(|| format!("http://{}:{}", args.host, args.port));
+            if args.enable_write
+                && !ApiKeyStore::load(&ApiKeyStore::default_path(&cfg.skill_storage_path))?
+                    .is_enabled()
+            {
+                return Err(CliError::Validation(
+                    "--registry with --enable-write needs API keys, or anyone could publish; \
+                     create one with `fastskill admin keys create <name> --scope write:skills`"
+                        .to_string(),
+                ));
+            }
+            let store = RegistryStore::open(root, &public_url)?;
+            // The index routes and `yank` read the index from the service config
+            cfg.registry_index_path = Some(store.index_path().to_path_buf());
+            println!("  Registry: hosting {} at {}", root.display(), public_url);
+            println!(
+                "    clients: type = \"http-registry\", index_url = \"{}/index\"",
+                public_url.trim_end_matches('/')
+            );
+            if !args.enable_write {
+                println!("    publishing needs --enable-write");
+            }
+            Some(std::sync::Arc::new(store))
+        }
+        None => None,
+    };
+    if is_follower {
+        // Followers import the primary's embeddings instead of computing their own
+        cfg.hot_reload.enabled = false;
+    }
+    let service = fastskill_core::FastSkillService::new(cfg)
         .await
         .map_err(CliError::Service)?;
-    service.initialize().await.map_err(CliError::Service)?;
+    // Inject before initializing: hot reload only re-embeds when the
+    // embedding provider is already present.
     let mut service = crate::config::inject_edge_services(service)?;
 
     if let Ok(current_dir) = std::env::current_dir() {
@@ -141,12 +314,75 @@ pub async fn execute_serve(
             service = service.with_project_root(project_config.project_root);
         }
     }
+    service.initialize().await.map_err(CliError::Service)?;
 
     let service = std::sync::Arc::new(service);
 
-    let server =
+    let mut server =
         fastskill_core::http::server::FastSkillServer::from_ref(&service, &args.host, args.port)
-            .enable_write(args.enable_write);
+            .enable_write(args.enable_write)
+            .verify_integrity(args.verify_integrity);
+    #[cfg(feature = "grpc")]
+    if let Some(port) = args.grpc_port {
+        server = server.grpc_port(port);
+    }
+    if let Some(store) = registry {
+        server = server.registry_store(store);
+    }
+    if service.config().tenancy.enabled {
+        println!("  Tenancy: ENABLED (one skill set per tenant)");
+        // Tenant services need the same edge services as the main one
+        server = server.tenant_setup(std::sync::Arc::new(|tenant_service| {
+            crate::config::inject_edge_services(tenant_service)
+                .map_err(|e| fastskill_core::ServiceError::Config(e.to_string()))
+        }));
+    }
+
+    match replication {
+        Some(ReplicationConfig::Primary { secret_env }) => {
+            println!("  Replication: primary (publishing signed snapshots)");
+            server = server.replication_key(crate::config::load_replication_key(&secret_env)?);
+        }
+        Some(ReplicationConfig::Follower {
+            primary_url,
+            secret_env,
+            poll_interval_secs,
+        }) => {
+            println!(
+                "  Replication: follower of {} (every {}s)",
+                primary_url, poll_interval_secs
+            );
+            let key = crate::config::load_replication_key(&secret_env)?;
+            let mut follower = Follower::new(&primary_url, key)?;
+            if args.verify_integrity {
+                // Share one guard so synced skills are re-recorded, not quarantined
+                let guard = std::sync::Arc::new(
+                    IntegrityGuard::snapshot(&service.config().skill_storage_path)
+                        .map_err(CliError::Service)?,
+                );
+                follower = follower.with_integrity_guard(std::sync::Arc::clone(&guard));
+                server = server.integrity_guard(guard);
+            }
+            // An unreachable primary is not fatal: serve what is already on
+            // disk and catch up on the next poll.
+            match follower.sync_once(&service).await {
+                Ok(report) => info!(
+                    "Initial replication sync: fetched {}, removed {}, {} index entries",
+                    report.fetched, report.removed, report.indexed
+                ),
+                Err(e) => tracing::warn!("Initial replication sync failed: {}", e),
+            }
+            follower.spawn(
+                std::sync::Arc::clone(&service),
+                Duration::from_secs(poll_interval_secs),
+            );
+        }
+        None => {}
+    }
+
+    if !args.skip_preflight {
+        run_preflight(&service, server.addr(), &args).await?;
+    }
 
     // Start the server (this will block until shutdown)
     server
@@ -157,6 +393,36 @@ pub async fn execute_serve(
     Ok(())
 }
 
+/// Check storage, index, port and auth config before binding, and refuse to
+/// start with one consolidated report if anything is broken.
+async fn run_preflight(
+    service: &fastskill_core::FastSkillService,
+    addr: std::net::SocketAddr,
+    args: &ServeArgs,
+) -> CliResult<()> {
+    let mut options = PreflightOptions::new(addr);
+    options.enable_write = args.enable_write;
+    options.check_repositories = args.check_repos;
+
+    let report = fastskill_core::http::preflight::run_preflight(service, &options).await;
+    if report.has_failures() {
+        eprintln!("Preflight checks failed:");
+        eprint!("{}", report.render());
+        return Err(CliError::Config(format!(
+            "{} preflight check(s) failed; fix them or pass --skip-preflight",
+            report.failures().count()
+        )));
+    }
+    for check in report
+        .checks
+        .iter()
+        .filter(|c| c.status == PreflightStatus::Warn)
+    {
+        eprintln!("  [WARN] {}: {}", check.name, check.message);
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::panic, clippy::expect_used)]
 mod tests {
@@ -179,6 +445,12 @@ mod tests {
             host: "localhost".to_string(),
             port: 0,
             enable_write: false,
+            verify_integrity: false,
+            check_repos: false,
+            skip_preflight: false,
+            grpc_port: None,
+            registry: None,
+            public_url: None,
         };
 
         // Note: This test doesn't actually start the server since it would block
@@ -203,9 +475,37 @@ mod tests {
             host: "127.0.0.1".to_string(),
             port: 0,
             enable_write: false,
+            verify_integrity: false,
+            check_repos: false,
+            skip_preflight: false,
+            grpc_port: None,
+            registry: None,
+            public_url: None,
         };
     }
 
+    #[test]
+    fn test_serve_args_parse_registry() {
+        let mut map = HashMap::new();
+        map.insert(
+            "registry".to_string(),
+            ArgValue::Str("/srv/registry".to_string()),
+        );
+        map.insert(
+            "public-url".to_string(),
+            ArgValue::Str("https://skills.example.com".to_string()),
+        );
+        let args = ServeArgs::from_arg_value_map(&map);
+        assert_eq!(args.registry, Some(PathBuf::from("/srv/registry")));
+        assert_eq!(
+            args.public_url.as_deref(),
+            Some("https://skills.example.com")
+        );
+        assert!(ServeArgs::from_arg_value_map(&HashMap::new())
+            .registry
+            .is_none());
+    }
+
     #[tokio::test]
     async fn test_execute_serve_with_custom_port() {
         let temp_dir = TempDir::new().unwrap();
@@ -221,6 +521,12 @@ mod tests {
             host: "localhost".to_string(),
             port: 9999,
             enable_write: false,
+            verify_integrity: false,
+            check_repos: false,
+            skip_preflight: false,
+            grpc_port: None,
+            registry: None,
+            public_url: None,
         };
 
         // Verify args are accepted
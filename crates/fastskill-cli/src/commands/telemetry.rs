@@ -0,0 +1,195 @@
+//! Telemetry commands - inspect and change the anonymous usage reporting opt-in
+//!
+//! See `crate::telemetry` for what is collected.
+
+use crate::error::{CliError, CliResult};
+use crate::telemetry::{Telemetry, TelemetrySource, TELEMETRY_ENV};
+use crate::utils::messages;
+use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
+use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
+use cli_framework::spec::command_tree::CommandSpec;
+use cli_framework::spec::value::ArgValue;
+use std::collections::HashMap;
+
+/// Arguments for `telemetry status`
+#[derive(Debug, Clone)]
+pub struct TelemetryStatusArgs {
+    /// Print the pending batch as JSON
+    pub json: bool,
+}
+
+/// Arguments for `telemetry enable`
+#[derive(Debug, Clone)]
+pub struct TelemetryEnableArgs {
+    /// Where batches are sent
+    pub endpoint: Option<String>,
+}
+
+/// Arguments for `telemetry disable`
+#[derive(Debug, Clone)]
+pub struct TelemetryDisableArgs;
+
+impl IntoCommandSpec for TelemetryStatusArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Show whether anonymous usage reporting is enabled and what is pending",
+            syntax: Some("telemetry status [--json]"),
+            category: Some("setup"),
+            args: vec![ArgSpec {
+                name: "json",
+                kind: ArgKind::Flag,
+                long: Some("json"),
+                value_type: ArgValueType::Bool,
+                cardinality: Cardinality::Optional,
+                help: "Print the pending usage batch as JSON",
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for TelemetryStatusArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        Self {
+            json: matches!(map.get("json"), Some(ArgValue::Bool(true))),
+        }
+    }
+}
+
+impl IntoCommandSpec for TelemetryEnableArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Opt in to anonymous usage reporting",
+            syntax: Some("telemetry enable [--endpoint <URL>]"),
+            category: Some("setup"),
+            args: vec![ArgSpec {
+                name: "endpoint",
+                kind: ArgKind::Option,
+                long: Some("endpoint"),
+                value_type: ArgValueType::String,
+                cardinality: Cardinality::Optional,
+                help: "URL that receives daily usage batches (without one, usage is only aggregated locally)",
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for TelemetryEnableArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        Self {
+            endpoint: match map.get("endpoint") {
+                Some(ArgValue::Str(s)) => Some(s.clone()),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl IntoCommandSpec for TelemetryDisableArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Opt out of usage reporting and delete pending usage data",
+            syntax: Some("telemetry disable"),
+            category: Some("setup"),
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for TelemetryDisableArgs {
+    fn from_arg_value_map(_map: &HashMap<String, ArgValue>) -> Self {
+        Self
+    }
+}
+
+fn telemetry() -> CliResult<Telemetry> {
+    Telemetry::default_location()
+        .ok_or_else(|| CliError::Config("Cannot determine config directory".to_string()))
+}
+
+pub async fn execute_telemetry_status(args: TelemetryStatusArgs) -> CliResult<()> {
+    let telemetry = telemetry()?;
+    let pending = telemetry.pending();
+    if args.json {
+        let json = serde_json::to_string_pretty(&pending)
+            .map_err(|e| CliError::Validation(format!("Failed to serialize output: {}", e)))?;
+        println!("{}", json);
+        return Ok(());
+    }
+
+    let (enabled, source) = telemetry.effective();
+    let why = match source {
+        TelemetrySource::DoNotTrack => " (DO_NOT_TRACK is set)".to_string(),
+        TelemetrySource::Env => format!(" ({} is set)", TELEMETRY_ENV),
+        TelemetrySource::Settings => String::new(),
+    };
+    println!(
+        "Telemetry: {}{}",
+        if enabled { "enabled" } else { "disabled" },
+        why
+    );
+    println!(
+        "Endpoint: {}",
+        telemetry
+            .endpoint()
+            .unwrap_or_else(|| "none (usage stays local)".to_string())
+    );
+    match pending {
+        Some(batch) => {
+            println!(
+                "Pending since {}:",
+                batch.period_start.format("%Y-%m-%d %H:%M UTC")
+            );
+            for (command, count) in &batch.commands {
+                println!("  {:<24} {}", command, count);
+            }
+            for (category, count) in &batch.errors {
+                println!("  error:{:<18} {}", category, count);
+            }
+        }
+        None => println!("Pending: nothing recorded"),
+    }
+    Ok(())
+}
+
+pub async fn execute_telemetry_enable(args: TelemetryEnableArgs) -> CliResult<()> {
+    if let Some(endpoint) = &args.endpoint {
+        url::Url::parse(endpoint)
+            .map_err(|e| CliError::Validation(format!("Invalid endpoint '{}': {}", endpoint, e)))?;
+    }
+    let telemetry = telemetry()?;
+    let mut settings = telemetry.settings();
+    settings.enabled = true;
+    if args.endpoint.is_some() {
+        settings.endpoint = args.endpoint;
+    }
+    telemetry.save_settings(&settings)?;
+    messages::status!("{}", messages::ok("Anonymous usage reporting enabled"));
+    if let (false, source) = telemetry.effective() {
+        let reason = match source {
+            TelemetrySource::DoNotTrack => "DO_NOT_TRACK",
+            _ => TELEMETRY_ENV,
+        };
+        eprintln!(
+            "{}",
+            messages::warning(&format!("{} is set and keeps telemetry off", reason))
+        );
+    }
+    Ok(())
+}
+
+pub async fn execute_telemetry_disable(_args: TelemetryDisableArgs) -> CliResult<()> {
+    let telemetry = telemetry()?;
+    let mut settings = telemetry.settings();
+    settings.enabled = false;
+    telemetry.save_settings(&settings)?;
+    telemetry.clear()?;
+    messages::status!(
+        "{}",
+        messages::ok("Anonymous usage reporting disabled; pending usage data deleted")
+    );
+    Ok(())
+}
@@ -0,0 +1,315 @@
+//! Test command - run the tests a skill ships in its `tests/` directory
+//!
+//! Thin wrapper over `fastskill_core::core::skill_tests`: finds the cases of
+//! each selected skill, runs them (scripts through the execution sandbox,
+//! judged cases through `--judge-command`), prints a summary and optionally
+//! writes a JUnit XML report for CI.
+
+use crate::commands::add::create_skill_from_path;
+use crate::error::{CliError, CliResult};
+use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
+use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
+use cli_framework::spec::command_tree::CommandSpec;
+use cli_framework::spec::value::ArgValue;
+use fastskill_core::core::origin::Origin;
+use fastskill_core::core::skill_tests::{
+    discover, render_junit, CaseOutcome, CommandJudge, DiscoveredCase, SkillTestRunner, SuiteResult,
+};
+use fastskill_core::execution::ExecutionSandbox;
+use fastskill_core::{FastSkillService, ServiceError, SkillDefinition, SkillId};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Arguments for `test`
+#[derive(Debug, Clone, Default)]
+pub struct TestArgs {
+    /// Installed skill ids or skill directories; all installed skills when empty
+    pub skills: Vec<String>,
+    /// Run only the cases with this name
+    pub case: Option<String>,
+    /// Command that grades judged cases
+    pub judge_command: Option<String>,
+    /// Write a JUnit XML report to this file
+    pub junit: Option<PathBuf>,
+    /// Print the results as JSON
+    pub json: bool,
+    /// Exit successfully even when cases fail
+    pub no_fail: bool,
+}
+
+impl IntoCommandSpec for TestArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Run the tests skills ship in their tests/ directory",
+            syntax: Some("test [SKILL|PATH]... [--case NAME] [--judge-command CMD] [--junit FILE]"),
+            category: Some("quality"),
+            args: vec![
+                ArgSpec {
+                    name: "skills",
+                    kind: ArgKind::Positional,
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Repeated,
+                    default: None,
+                    help: "Installed skill ids or skill directories (default: every installed skill with tests)",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "case",
+                    kind: ArgKind::Option,
+                    long: Some("case"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    help: "Run only the cases with this name",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "judge-command",
+                    kind: ArgKind::Option,
+                    long: Some("judge-command"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    help: "Command that grades judged cases (request JSON on stdin, {\"pass\": bool, \"reason\": ...} on stdout)",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "junit",
+                    kind: ArgKind::Option,
+                    long: Some("junit"),
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Optional,
+                    default: None,
+                    help: "Write a JUnit XML report to this file",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "json",
+                    kind: ArgKind::Flag,
+                    long: Some("json"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Print the results as JSON",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "no-fail",
+                    kind: ArgKind::Flag,
+                    long: Some("no-fail"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Exit with status 0 even when cases fail",
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for TestArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        Self {
+            skills: match map.get("skills") {
+                Some(ArgValue::List(items)) => items
+                    .iter()
+                    .filter_map(|i| {
+                        if let ArgValue::Str(s) = i {
+                            Some(s.clone())
+                        } else {
+                            None
+                        }
+                    })
+                    .collect(),
+                Some(ArgValue::Str(s)) => vec![s.clone()],
+                _ => vec![],
+            },
+            case: match map.get("case") {
+                Some(ArgValue::Str(s)) => Some(s.clone()),
+                _ => None,
+            },
+            judge_command: match map.get("judge-command") {
+                Some(ArgValue::Str(s)) => Some(s.clone()),
+                _ => None,
+            },
+            junit: match map.get("junit") {
+                Some(ArgValue::Str(s)) => Some(PathBuf::from(s)),
+                _ => None,
+            },
+            json: matches!(map.get("json"), Some(ArgValue::Bool(true))),
+            no_fail: matches!(map.get("no-fail"), Some(ArgValue::Bool(true))),
+        }
+    }
+}
+
+/// A skill directory given on the command line, or an installed skill
+async fn resolve_skill(service: &FastSkillService, arg: &str) -> CliResult<SkillDefinition> {
+    let path = Path::new(arg);
+    if path.join("SKILL.md").is_file() {
+        let origin = Origin::Local {
+            path: path.to_path_buf(),
+            editable: false,
+        };
+        return create_skill_from_path(path, origin, "local", false);
+    }
+    let skill_id = SkillId::new(arg.to_string())
+        .map_err(|_| CliError::Validation(format!("Invalid skill ID format: {}", arg)))?;
+    service
+        .skill_manager()
+        .get_skill(&skill_id)
+        .await?
+        .ok_or_else(|| {
+            CliError::Validation(format!(
+                "Skill '{}' is not installed and is not a skill directory",
+                arg
+            ))
+        })
+}
+
+fn skill_dir(skill: &SkillDefinition) -> &Path {
+    skill.skill_file.parent().unwrap_or(Path::new("."))
+}
+
+pub async fn execute_test(service: &FastSkillService, args: TestArgs) -> CliResult<()> {
+    let selected = if args.skills.is_empty() {
+        service.skill_manager().list_skills().await?
+    } else {
+        let mut skills = Vec::with_capacity(args.skills.len());
+        for arg in &args.skills {
+            skills.push(resolve_skill(service, arg).await?);
+        }
+        skills
+    };
+
+    let mut runner = SkillTestRunner::new(
+        ExecutionSandbox::new(service.config().execution.clone())
+            .map_err(ServiceError::from)?
+            .with_cancellation(service.cancellation_token().clone()),
+    );
+    if let Some(command) = &args.judge_command {
+        let judge = CommandJudge::from_command_line(command)
+            .ok_or_else(|| CliError::Validation("--judge-command cannot be empty".to_string()))?;
+        runner = runner.with_judge(Box::new(judge));
+    }
+
+    let mut suites = Vec::new();
+    for skill in &selected {
+        let mut cases: Vec<DiscoveredCase> = discover(skill_dir(skill))?;
+        if let Some(name) = &args.case {
+            cases.retain(|case| case.name() == name);
+        }
+        if cases.is_empty() {
+            if !args.skills.is_empty() && args.case.is_none() && !args.json {
+                println!(
+                    "{}: no tests found in {}/tests",
+                    skill.id,
+                    skill_dir(skill).display()
+                );
+            }
+            continue;
+        }
+        if !args.json {
+            println!("{} ({} case(s))", skill.id, cases.len());
+        }
+        let suite = runner.run(skill, &cases).await;
+        if !args.json {
+            print_suite(&suite);
+        }
+        suites.push(suite);
+    }
+
+    if let Some(path) = &args.junit {
+        std::fs::write(path, render_junit(&suites))?;
+        if !args.json {
+            println!("JUnit report written to {}", path.display());
+        }
+    }
+
+    let total: usize = suites.iter().map(|s| s.cases.len()).sum();
+    let failures: usize = suites.iter().map(|s| s.failures() + s.errors()).sum();
+    let skipped: usize = suites.iter().map(SuiteResult::skipped).sum();
+    if args.json {
+        let json = serde_json::to_string_pretty(&suites)
+            .map_err(|e| CliError::Validation(format!("Failed to serialize results: {}", e)))?;
+        println!("{}", json);
+    } else if total == 0 {
+        println!("No skill tests found");
+    } else {
+        println!(
+            "\n{} passed, {} failed, {} skipped",
+            total - failures - skipped,
+            failures,
+            skipped
+        );
+    }
+
+    if failures > 0 && !args.no_fail {
+        return Err(CliError::Validation(format!(
+            "{} of {} skill test case(s) failed",
+            failures, total
+        )));
+    }
+    Ok(())
+}
+
+fn print_suite(suite: &SuiteResult) {
+    for case in &suite.cases {
+        let millis = case.duration.as_millis();
+        match &case.outcome {
+            CaseOutcome::Passed => println!("  ok    {} ({} ms)", case.name, millis),
+            CaseOutcome::Failed { message, details } => {
+                println!("  FAIL  {} ({} ms): {}", case.name, millis, message);
+                for line in details.lines().take(10) {
+                    println!("        {}", line);
+                }
+            }
+            CaseOutcome::Error { message } => {
+                println!("  ERROR {}: {}", case.name, message)
+            }
+            CaseOutcome::Skipped { reason } => println!("  skip  {}: {}", case.name, reason),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_test_args_defaults() {
+        let args = TestArgs::from_arg_value_map(&HashMap::new());
+        assert!(args.skills.is_empty());
+        assert!(args.case.is_none());
+        assert!(args.judge_command.is_none());
+        assert!(args.junit.is_none());
+        assert!(!args.json);
+        assert!(!args.no_fail);
+    }
+
+    #[test]
+    fn test_test_args_parses_options() {
+        let mut map = HashMap::new();
+        map.insert(
+            "skills".to_string(),
+            ArgValue::List(vec![
+                ArgValue::Str("pdf".to_string()),
+                ArgValue::Str("./skills/docx".to_string()),
+            ]),
+        );
+        map.insert(
+            "judge-command".to_string(),
+            ArgValue::Str("python3 judge.py".to_string()),
+        );
+        map.insert(
+            "junit".to_string(),
+            ArgValue::Str("reports/skills.xml".to_string()),
+        );
+        map.insert("no-fail".to_string(), ArgValue::Bool(true));
+        let args = TestArgs::from_arg_value_map(&map);
+        assert_eq!(args.skills, ["pdf", "./skills/docx"]);
+        assert_eq!(args.judge_command.as_deref(), Some("python3 judge.py"));
+        assert_eq!(args.junit, Some(PathBuf::from("reports/skills.xml")));
+        assert!(args.no_fail);
+    }
+}
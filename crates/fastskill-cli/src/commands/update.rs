@@ -2,13 +2,16 @@
 
 use crate::config::create_service_config;
 use crate::error::{manifest_required_message, CliError, CliResult};
+use crate::utils::audit_utils;
+use crate::utils::change_plan::{self, ChangePlan, PlannedChange};
 use crate::utils::messages;
 use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
 use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
 use cli_framework::spec::command_tree::CommandSpec;
 use cli_framework::spec::value::ArgValue;
 use fastskill_core::core::{
-    lock::{global_lock_path, GlobalSkillsLock},
+    audit::AuditDetails,
+    lock::{global_lock_path, GlobalSkillsLock, ProjectSkillsLock},
     manifest::SkillProjectToml,
     project::resolve_project_file,
     AddMode, UpdatePreflight,
@@ -24,7 +27,8 @@ use std::path::PathBuf;
 /// - 'fastskill update' (no skill specified): Updates all skills, modifies skills.lock
 /// - 'fastskill update <skill-id>': Updates specific skill only, modifies skills.lock
 /// - 'fastskill update --check': Check-only mode, no modifications to any files
-/// - 'fastskill update --dry-run': Preview changes without applying them
+/// - 'fastskill update --dry-run': Print the change plan (versions, download sizes,
+///   lock diff) without applying it; add --json for machine-readable output
 ///
 /// Reads dependencies from skill-project.toml and updates installed skills.
 /// Always updates skills.lock with new versions (except in check/dry-run modes).
@@ -40,6 +44,9 @@ pub struct UpdateArgs {
     /// Show what would be updated without actually updating
     dry_run: bool,
 
+    /// Print the --dry-run plan as JSON
+    json: bool,
+
     /// Update to specific version
     version: Option<String>,
 
@@ -55,6 +62,9 @@ pub struct UpdateArgs {
 
     /// Skip reindex after update
     no_reindex: bool,
+
+    /// Fail when an installed skill's peer dependency range is not satisfied
+    strict_peers: bool,
 }
 
 impl IntoCommandSpec for UpdateArgs {
@@ -87,7 +97,16 @@ impl IntoCommandSpec for UpdateArgs {
                     long: Some("dry-run"),
                     value_type: ArgValueType::Bool,
                     cardinality: Cardinality::Optional,
-                    help: "Show what would be updated without actually updating",
+                    help: "Show the version changes, download sizes and lock file diff an update would make, without updating",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "json",
+                    kind: ArgKind::Flag,
+                    long: Some("json"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Print the --dry-run plan as JSON",
                     ..Default::default()
                 },
                 ArgSpec {
@@ -136,12 +155,28 @@ impl IntoCommandSpec for UpdateArgs {
                     help: "Skip reindex after update",
                     ..Default::default()
                 },
+                ArgSpec {
+                    name: "strict-peers",
+                    kind: ArgKind::Flag,
+                    long: Some("strict-peers"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Fail instead of warning when installed skills violate a declared peer dependency range",
+                    ..Default::default()
+                },
             ],
             ..Default::default()
         }
     }
 }
 
+impl UpdateArgs {
+    /// Whether this run changes installed skills (not `--check` or `--dry-run`)
+    pub fn writes_storage(&self) -> bool {
+        !self.check && !self.dry_run
+    }
+}
+
 fn opt_str(v: &ArgValue) -> Option<String> {
     if let ArgValue::Str(s) = v {
         Some(s.clone())
@@ -157,6 +192,7 @@ impl FromArgValueMap for UpdateArgs {
             skill_id: map.get("skill-id").and_then(opt_str),
             check: matches!(map.get("check"), Some(ArgValue::Bool(true))),
             dry_run: matches!(map.get("dry-run"), Some(ArgValue::Bool(true))),
+            json: matches!(map.get("json"), Some(ArgValue::Bool(true))),
             version: map.get("version").and_then(opt_str),
             source: map.get("source").and_then(opt_str),
             strategy: map
@@ -165,6 +201,7 @@ impl FromArgValueMap for UpdateArgs {
                 .unwrap_or_else(|| "latest".to_string()),
             reindex: matches!(map.get("reindex"), Some(ArgValue::Bool(true))),
             no_reindex: matches!(map.get("no-reindex"), Some(ArgValue::Bool(true))),
+            strict_peers: matches!(map.get("strict-peers"), Some(ArgValue::Bool(true))),
         }
     }
 }
@@ -175,6 +212,11 @@ pub async fn execute_update(args: UpdateArgs, global: bool) -> CliResult<()> {
             "--reindex and --no-reindex cannot be used together".to_string(),
         ));
     }
+    if args.json && !args.dry_run {
+        return Err(CliError::Validation(
+            "--json requires --dry-run".to_string(),
+        ));
+    }
     let reindex = args.reindex;
     let no_reindex = args.no_reindex;
     let check = args.check;
@@ -210,14 +252,16 @@ pub async fn execute_update(args: UpdateArgs, global: bool) -> CliResult<()> {
 }
 
 async fn execute_update_global(args: UpdateArgs) -> CliResult<()> {
-    println!("Updating global skills...");
-    println!();
+    if !args.json {
+        println!("Updating global skills...");
+        println!();
+    }
 
     let lock_path = global_lock_path()
         .map_err(|e| CliError::Config(format!("Failed to resolve global lock path: {}", e)))?;
 
     if !lock_path.exists() {
-        println!(
+        messages::status!(
             "{}",
             messages::info(
                 "No global-skills.lock found. Run 'fastskill add --global <skill>' first."
@@ -235,30 +279,42 @@ async fn execute_update_global(args: UpdateArgs) -> CliResult<()> {
         lock.skills.iter().map(|s| s.id.clone()).collect()
     };
 
-    if skill_ids.is_empty() {
-        println!("{}", messages::info("No global skills to update"));
+    if skill_ids.is_empty() && !args.dry_run {
+        messages::status!("{}", messages::info("No global skills to update"));
+        return Ok(());
+    }
+
+    if args.dry_run {
+        // A global update only records the time; no version changes.
+        let changes = skill_ids
+            .iter()
+            .filter_map(|id| lock.skills.iter().find(|s| s.id == *id))
+            .map(|entry| {
+                let version = Some(entry.resolved.version.clone());
+                PlannedChange::install(entry.id.clone(), version.clone(), version)
+            })
+            .collect();
+        ChangePlan::new("update", "global-skills.lock", changes).print(args.json);
         return Ok(());
     }
 
-    if args.check || args.dry_run {
+    if args.check {
         println!("\nGlobal skills (check mode):\n");
         for id in &skill_ids {
             if let Some(entry) = lock.skills.iter().find(|s| s.id == *id) {
                 println!("  • {} @ {}", entry.id, entry.resolved.version);
             }
         }
-        if args.check {
-            let now = chrono::Utc::now();
-            for id in &skill_ids {
-                lock.mark_checked(id, now);
-            }
-            lock.save_to_file(&lock_path)
-                .map_err(|e| CliError::Config(format!("Failed to save global lock: {}", e)))?;
-            println!(
-                "\n{}",
-                messages::info("Updated last_checked_at in global-skills.lock")
-            );
+        let now = chrono::Utc::now();
+        for id in &skill_ids {
+            lock.mark_checked(id, now);
         }
+        lock.save_to_file(&lock_path)
+            .map_err(|e| CliError::Config(format!("Failed to save global lock: {}", e)))?;
+        messages::status!(
+            "\n{}",
+            messages::info("Updated last_checked_at in global-skills.lock")
+        );
         return Ok(());
     }
 
@@ -269,7 +325,7 @@ async fn execute_update_global(args: UpdateArgs) -> CliResult<()> {
         if lock.skills.iter().any(|s| s.id == *id) {
             lock.mark_updated(id, now);
             updated_count += 1;
-            println!("  {}", messages::ok(&format!("Marked {} as updated", id)));
+            messages::status!("  {}", messages::ok(&format!("Marked {} as updated", id)));
         }
     }
 
@@ -277,7 +333,7 @@ async fn execute_update_global(args: UpdateArgs) -> CliResult<()> {
         .map_err(|e| CliError::Config(format!("Failed to save global lock: {}", e)))?;
 
     println!();
-    println!(
+    messages::status!(
         "{}",
         messages::ok(&format!("Updated {} global skill(s)", updated_count))
     );
@@ -302,8 +358,10 @@ async fn execute_update_global(args: UpdateArgs) -> CliResult<()> {
 /// `--check`/`--dry-run` is likewise replaced by `preflight`'s coarser
 /// Updatable/UpToDate/Immutable classification.
 async fn execute_update_project(args: UpdateArgs) -> CliResult<()> {
-    println!("Updating skills...");
-    println!();
+    if !args.json {
+        println!("Updating skills...");
+        println!();
+    }
 
     // T034: Resolve skill-project.toml from project root
     let current_dir = env::current_dir()
@@ -338,7 +396,11 @@ async fn execute_update_project(args: UpdateArgs) -> CliResult<()> {
     entries.sort_by(|a, b| a.id.as_str().cmp(b.id.as_str()));
 
     if entries.is_empty() {
-        println!("{}", messages::info("No skills to update"));
+        if args.dry_run {
+            ChangePlan::new("update", "skills.lock", Vec::new()).print(args.json);
+        } else {
+            messages::status!("{}", messages::info("No skills to update"));
+        }
         return Ok(());
     }
 
@@ -378,7 +440,47 @@ async fn execute_update_project(args: UpdateArgs) -> CliResult<()> {
     service.initialize().await.map_err(CliError::Service)?;
     let service = crate::config::inject_edge_services(service)?;
 
-    if args.check || args.dry_run {
+    if args.dry_run {
+        let locked: HashMap<String, String> = ProjectSkillsLock::load_from_file(&lock_path)
+            .map_err(|e| CliError::Config(format!("Failed to load lock file: {}", e)))?
+            .skills
+            .into_iter()
+            .map(|entry| (entry.id, entry.resolved.version))
+            .collect();
+        let mut changes = Vec::with_capacity(entries.len());
+        for entry in &entries {
+            let from = locked.get(&entry.id).cloned();
+            match service.preflight(&entry.origin).await {
+                Ok(UpdatePreflight::Updatable) => {
+                    let to = service
+                        .target_version(&entry.origin)
+                        .await
+                        .unwrap_or_else(|e| {
+                            change_plan::warn(&format!("{}: {}", entry.id, e));
+                            None
+                        });
+                    let size = service
+                        .download_size(&entry.origin, to.as_deref())
+                        .await
+                        .unwrap_or_else(|e| {
+                            tracing::debug!("No download size for {}: {}", entry.id, e);
+                            None
+                        });
+                    changes.push(
+                        PlannedChange::install(entry.id.clone(), from, to).with_download_size(size),
+                    );
+                }
+                Ok(UpdatePreflight::UpToDate) | Ok(UpdatePreflight::Immutable { .. }) => {
+                    changes.push(PlannedChange::install(entry.id.clone(), from.clone(), from));
+                }
+                Err(e) => change_plan::warn(&format!("{}: {}", entry.id, e)),
+            }
+        }
+        ChangePlan::new("update", "skills.lock", changes).print(args.json);
+        return Ok(());
+    }
+
+    if args.check {
         println!("\nSkills that would be updated:\n");
         let mut any_reported = false;
         for entry in &entries {
@@ -399,14 +501,12 @@ async fn execute_update_project(args: UpdateArgs) -> CliResult<()> {
             }
         }
         if !any_reported {
-            println!("{}", messages::info("No updates available"));
-        }
-        if args.check {
-            println!(
-                "\n{}",
-                messages::info("Run without --check to actually update")
-            );
+            messages::status!("{}", messages::info("No updates available"));
         }
+        messages::status!(
+            "\n{}",
+            messages::info("Run without --check to actually update")
+        );
         return Ok(());
     }
 
@@ -418,13 +518,37 @@ async fn execute_update_project(args: UpdateArgs) -> CliResult<()> {
         match service.preflight(&entry.origin).await {
             Ok(UpdatePreflight::Updatable) => {
                 // Pass the existing groups so update preserves group membership.
-                match service
+                let updated = service
                     .add_from_origin(entry.origin.clone(), AddMode::Update, entry.groups.clone())
-                    .await
-                {
-                    Ok(_outcome) => {
+                    .await;
+                let details = match &updated {
+                    Ok(outcome) => audit_utils::added(outcome),
+                    Err(_) => AuditDetails {
+                        target: Some(entry.id.clone()),
+                        version: None,
+                        source: Some(audit_utils::origin_source(&entry.origin)),
+                    },
+                };
+                audit_utils::record(
+                    &service.config().skill_storage_path,
+                    "skill.update",
+                    "update",
+                    details,
+                    &updated,
+                );
+                match updated {
+                    Ok(outcome) => {
                         updated_count += 1;
-                        println!("  {}", messages::ok(&format!("Updated {}", entry.id)));
+                        messages::status!("  {}", messages::ok(&format!("Updated {}", entry.id)));
+                        if let Some(delta) = &outcome.delta {
+                            println!(
+                                "    Fetched {} of {} files ({}), saved {}",
+                                delta.files_fetched,
+                                delta.files_total,
+                                change_plan::format_size(delta.bytes_fetched),
+                                change_plan::format_size(delta.bytes_saved)
+                            );
+                        }
                     }
                     Err(e) => {
                         eprintln!(
@@ -435,13 +559,13 @@ async fn execute_update_project(args: UpdateArgs) -> CliResult<()> {
                 }
             }
             Ok(UpdatePreflight::UpToDate) => {
-                println!(
+                messages::status!(
                     "  {}",
                     messages::info(&format!("{} is already up to date", entry.id))
                 );
             }
             Ok(UpdatePreflight::Immutable { reason }) => {
-                println!(
+                messages::status!(
                     "  {}",
                     messages::info(&format!("{} is immutable: {}", entry.id, reason))
                 );
@@ -456,12 +580,14 @@ async fn execute_update_project(args: UpdateArgs) -> CliResult<()> {
     }
 
     println!();
-    println!(
+    messages::status!(
         "{}",
         messages::ok(&format!("Updated {} skill(s)", updated_count))
     );
     println!("   Updated skills.lock");
 
+    crate::utils::install_utils::check_installed_peers(&service, args.strict_peers).await?;
+
     Ok(())
 }
 
@@ -504,11 +630,13 @@ mod tests {
             skill_id: None,
             check: false,
             dry_run: false,
+            json: false,
             version: None,
             source: None,
             strategy: "latest".to_string(),
             reindex: false,
             no_reindex: false,
+            strict_peers: false,
         };
 
         let result = execute_update(args, false).await;
@@ -596,11 +724,13 @@ version = "1.0.0"
             skill_id: None,
             check: false,
             dry_run: false,
+            json: false,
             version: None,
             source: None,
             strategy: "invalid-strategy".to_string(),
             reindex: false,
             no_reindex: false,
+            strict_peers: false,
         };
 
         let result = execute_update(args, false).await;
@@ -656,11 +786,13 @@ version = "1.0.0"
             skill_id: None,
             check: true,
             dry_run: false,
+            json: false,
             version: None,
             source: None,
             strategy: "latest".to_string(),
             reindex: false,
             no_reindex: false,
+            strict_peers: false,
         };
 
         // Should succeed in check mode even with no skills
@@ -718,11 +850,13 @@ source = { path = ".claude/skills/test-skill" }
             skill_id: None,
             check: true,
             dry_run: false,
+            json: false,
             version: None,
             source: None,
             strategy: "latest".to_string(),
             reindex: false,
             no_reindex: false,
+            strict_peers: false,
         };
 
         let result = execute_update(args, false).await;
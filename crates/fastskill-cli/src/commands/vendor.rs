@@ -0,0 +1,194 @@
+//! Vendor command - copy every locked skill into vendor/ for offline installs
+//!
+//! Each skill pinned in skills.lock is archived from the skills directory into
+//! `vendor/` next to skill-project.toml, with a `vendor.toml` index recording
+//! its metadata, origin and checksum. `fastskill install --offline` (or
+//! `offline = true` in `[tool.fastskill]`) then installs from there only.
+
+use crate::commands::add::create_skill_from_path;
+use crate::error::{manifest_required_message, CliError, CliResult};
+use crate::utils::messages;
+use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
+use cli_framework::spec::command_tree::CommandSpec;
+use cli_framework::spec::value::ArgValue;
+use fastskill_core::core::{
+    lock::{project_lock_path, ProjectSkillsLock},
+    project::resolve_project_file,
+    vendor::{vendor_dir_for, VendorDir},
+};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Arguments for `vendor`
+#[derive(Debug, Clone)]
+pub struct VendorArgs;
+
+impl IntoCommandSpec for VendorArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Copy every skill in skills.lock into vendor/ for offline installs",
+            syntax: Some("vendor"),
+            category: Some("packages"),
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for VendorArgs {
+    fn from_arg_value_map(_map: &HashMap<String, ArgValue>) -> Self {
+        Self
+    }
+}
+
+pub async fn execute_vendor(_args: VendorArgs) -> CliResult<()> {
+    let current_dir = std::env::current_dir()
+        .map_err(|e| CliError::Config(format!("Failed to get current directory: {}", e)))?;
+    let project_file = resolve_project_file(&current_dir);
+    if !project_file.found {
+        return Err(CliError::Config(manifest_required_message().to_string()));
+    }
+    let lock_path = project_lock_path(&project_file.path);
+    if !lock_path.exists() {
+        return Err(CliError::Config(
+            "skills.lock not found. Run 'fastskill install' first to create it.".to_string(),
+        ));
+    }
+    let lock = ProjectSkillsLock::load_from_file(&lock_path)
+        .map_err(|e| CliError::Config(format!("Failed to load lock file: {}", e)))?;
+    let skills_dir = crate::config::resolve_skills_storage_directory(false)?;
+
+    let vendor = vendor_skills(&lock, &skills_dir, &vendor_dir_for(&project_file.path))?;
+    let removed = vendor.save()?;
+    for archive in &removed {
+        messages::status!(
+            "  {}",
+            messages::info(&format!("Removed stale {}", archive))
+        );
+    }
+    println!(
+        "{}",
+        messages::ok(&format!(
+            "Vendored {} skills into {}",
+            vendor.skills().len(),
+            vendor.root().display()
+        ))
+    );
+    Ok(())
+}
+
+/// Archive the installed copy of every skill in `lock`. Nothing is written
+/// when any locked skill is missing or installed at another version.
+fn vendor_skills(
+    lock: &ProjectSkillsLock,
+    skills_dir: &Path,
+    vendor_root: &Path,
+) -> CliResult<VendorDir> {
+    let mut installed = Vec::with_capacity(lock.skills.len());
+    let mut problems = Vec::new();
+    for locked in &lock.skills {
+        let skill_dir = skills_dir.join(&locked.id);
+        if !skill_dir.join("SKILL.md").is_file() {
+            problems.push(format!("{}: not installed", locked.id));
+            continue;
+        }
+        let skill = create_skill_from_path(&skill_dir, locked.origin.clone(), "vendor", false)?;
+        if skill.version != locked.resolved.version {
+            problems.push(format!(
+                "{}: installed {} but skills.lock pins {}",
+                locked.id, skill.version, locked.resolved.version
+            ));
+            continue;
+        }
+        installed.push((skill, skill_dir));
+    }
+    if !problems.is_empty() {
+        return Err(CliError::Config(format!(
+            "{} locked skill(s) cannot be vendored; run 'fastskill install' first:\n  - {}",
+            problems.len(),
+            problems.join("\n  - ")
+        )));
+    }
+
+    let mut vendor = VendorDir::new(vendor_root);
+    for (skill, skill_dir) in &installed {
+        vendor.add(skill, skill_dir)?;
+        messages::status!("  {} (v{})", skill.id, skill.version);
+    }
+    Ok(vendor)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use fastskill_core::core::lock::ProjectLockedSkillEntry;
+    use fastskill_core::core::origin::{Origin, Resolved};
+    use tempfile::TempDir;
+
+    fn locked(id: &str, version: &str) -> ProjectLockedSkillEntry {
+        ProjectLockedSkillEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            origin: Origin::Repository {
+                repo: "team".to_string(),
+                skill: id.to_string(),
+                version: None,
+            },
+            resolved: Resolved {
+                version: version.to_string(),
+                commit_hash: None,
+                checksum: None,
+            },
+            dependencies: Vec::new(),
+            groups: Vec::new(),
+            extras: Vec::new(),
+            depth: 0,
+            parent_skill: None,
+        }
+    }
+
+    fn write_skill(skills_dir: &Path, id: &str, version: &str) {
+        let dir = skills_dir.join(id);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("SKILL.md"),
+            format!("---\nname: {id}\nversion: {version}\ndescription: Test {id}\n---\n# {id}\n"),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_vendor_skills_archives_every_locked_skill() {
+        let temp_dir = TempDir::new().unwrap();
+        let skills_dir = temp_dir.path().join("skills");
+        write_skill(&skills_dir, "pdf-tools", "1.2.0");
+        write_skill(&skills_dir, "csv-tools", "0.3.0");
+        let mut lock = ProjectSkillsLock::new_empty();
+        lock.skills = vec![locked("pdf-tools", "1.2.0"), locked("csv-tools", "0.3.0")];
+
+        let vendor = vendor_skills(&lock, &skills_dir, &temp_dir.path().join("vendor")).unwrap();
+        vendor.save().unwrap();
+
+        let vendor = VendorDir::open(&temp_dir.path().join("vendor")).unwrap();
+        assert_eq!(vendor.skills().len(), 2);
+        let pdf = vendor.find("pdf-tools", Some("1.2.0")).unwrap();
+        assert_eq!(pdf.origin, lock.skills[0].origin);
+    }
+
+    #[test]
+    fn test_vendor_skills_reports_missing_and_mismatched() {
+        let temp_dir = TempDir::new().unwrap();
+        let skills_dir = temp_dir.path().join("skills");
+        write_skill(&skills_dir, "pdf-tools", "1.1.0");
+        let mut lock = ProjectSkillsLock::new_empty();
+        lock.skills = vec![locked("pdf-tools", "1.2.0"), locked("csv-tools", "0.3.0")];
+
+        let vendor_root = temp_dir.path().join("vendor");
+        let err = vendor_skills(&lock, &skills_dir, &vendor_root).unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("2 locked skill(s)"), "{}", msg);
+        assert!(msg.contains("skills.lock pins 1.2.0"), "{}", msg);
+        assert!(msg.contains("csv-tools: not installed"), "{}", msg);
+        assert!(!vendor_root.join("vendor.toml").exists());
+    }
+}
@@ -0,0 +1,157 @@
+//! Yank command - withdraw a published skill version from the registry index
+//!
+//! A yanked version stays in the index so projects whose `skills.lock` pins
+//! it keep installing, but resolution never picks it for a new install or an
+//! update. `--undo` restores it. The index edited is the one `fastskill serve`
+//! publishes from (`REGISTRY_INDEX_PATH`).
+
+use crate::error::{CliError, CliResult};
+use crate::utils::audit_utils;
+use cli_framework::command::{FromArgValueMap, IntoCommandSpec};
+use cli_framework::spec::arg_spec::{ArgKind, ArgSpec, ArgValueType, Cardinality};
+use cli_framework::spec::command_tree::CommandSpec;
+use cli_framework::spec::value::ArgValue;
+use fastskill_core::core::audit::AuditDetails;
+use fastskill_core::core::registry_index::{set_version_yanked, ScopedSkillName};
+use fastskill_core::FastSkillService;
+use std::collections::HashMap;
+
+/// Arguments for `yank`
+#[derive(Debug, Clone)]
+pub struct YankArgs {
+    /// `org/skill@version`
+    pub skill: String,
+    /// Restore a yanked version instead
+    pub undo: bool,
+}
+
+impl IntoCommandSpec for YankArgs {
+    fn command_spec() -> CommandSpec {
+        CommandSpec {
+            summary: "Withdraw a published skill version so new installs skip it",
+            syntax: Some("yank <ORG/SKILL@VERSION> [--undo]"),
+            category: Some("server"),
+            args: vec![
+                ArgSpec {
+                    name: "skill",
+                    kind: ArgKind::Positional,
+                    value_type: ArgValueType::String,
+                    cardinality: Cardinality::Required,
+                    help: "Published version to yank (e.g. 'acme/web-scraper@1.2.0')",
+                    ..Default::default()
+                },
+                ArgSpec {
+                    name: "undo",
+                    kind: ArgKind::Flag,
+                    long: Some("undo"),
+                    value_type: ArgValueType::Bool,
+                    cardinality: Cardinality::Optional,
+                    help: "Restore a previously yanked version",
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromArgValueMap for YankArgs {
+    fn from_arg_value_map(map: &HashMap<String, ArgValue>) -> Self {
+        Self {
+            skill: match map.get("skill") {
+                Some(ArgValue::Str(s)) => s.clone(),
+                _ => String::new(),
+            },
+            undo: matches!(map.get("undo"), Some(ArgValue::Bool(true))),
+        }
+    }
+}
+
+/// Split `org/skill@version` into the normalized skill id and the version.
+fn parse_skill_version(input: &str) -> CliResult<(String, String)> {
+    let (skill, version) = input
+        .trim()
+        .rsplit_once('@')
+        .filter(|(skill, version)| !skill.is_empty() && !version.trim().is_empty())
+        .ok_or_else(|| {
+            CliError::Validation(format!(
+                "Expected <org/skill>@<version>, got '{}'",
+                input.trim()
+            ))
+        })?;
+    Ok((
+        ScopedSkillName::normalize(skill),
+        version.trim().to_string(),
+    ))
+}
+
+pub async fn execute_yank(service: &FastSkillService, args: YankArgs) -> CliResult<()> {
+    let (skill, version) = parse_skill_version(&args.skill)?;
+    let registry_index_path = service
+        .config()
+        .registry_index_path
+        .as_ref()
+        .ok_or_else(|| {
+            CliError::Config(
+                "No registry index configured; set REGISTRY_INDEX_PATH to the index \
+                 directory `fastskill serve` publishes from"
+                    .to_string(),
+            )
+        })?;
+
+    let yanked = set_version_yanked(registry_index_path, &skill, &version, !args.undo);
+    audit_utils::record(
+        &service.config().skill_storage_path,
+        if args.undo {
+            "registry.unyank"
+        } else {
+            "registry.yank"
+        },
+        "yank",
+        AuditDetails {
+            target: Some(skill.clone()),
+            version: Some(version.clone()),
+            source: None,
+        },
+        &yanked,
+    );
+    let entry = yanked?;
+    if entry.yanked {
+        println!(
+            "Yanked {}@{}; new installs and updates will skip it",
+            skill, entry.vers
+        );
+    } else {
+        println!("Restored {}@{}", skill, entry.vers);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yank_args_parse() {
+        let mut map = HashMap::new();
+        map.insert(
+            "skill".to_string(),
+            ArgValue::Str("acme/web-scraper@1.2.0".to_string()),
+        );
+        map.insert("undo".to_string(), ArgValue::Bool(true));
+        let args = YankArgs::from_arg_value_map(&map);
+        assert_eq!(args.skill, "acme/web-scraper@1.2.0");
+        assert!(args.undo);
+    }
+
+    #[test]
+    fn test_parse_skill_version() {
+        assert_eq!(
+            parse_skill_version("@acme/web-scraper@1.2.0").unwrap(),
+            ("acme/web-scraper".to_string(), "1.2.0".to_string())
+        );
+        assert!(parse_skill_version("acme/web-scraper").is_err());
+        assert!(parse_skill_version("acme/web-scraper@").is_err());
+    }
+}
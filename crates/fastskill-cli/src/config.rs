@@ -1,10 +1,16 @@
 //! Configuration and skills directory resolution for CLI
 
 use crate::error::{CliError, CliResult};
-use fastskill_core::core::manifest::SkillProjectToml;
+use fastskill_core::core::credentials::resolve_secret;
+use fastskill_core::core::manifest::{HttpServerConfigToml, SkillProjectToml};
 use fastskill_core::core::project;
+use fastskill_core::core::registry::{ResolverCache, DEFAULT_RESOLVER_CACHE_TTL_SECS};
+use fastskill_core::core::replication::{ReplicationConfig, ReplicationKey};
 use fastskill_core::core::repository::{RepositoryDefinition, RepositoryManager};
-use fastskill_core::core::service::HttpServerConfig;
+use fastskill_core::core::service::{
+    ClientProfile, HotReloadConfig, HttpServerConfig, RateLimitConfig, StorageConfig,
+};
+use fastskill_core::core::tenancy::TenancyConfig;
 use fastskill_core::{FastSkillService, ServiceConfig};
 use std::env;
 use std::path::PathBuf;
@@ -189,6 +195,14 @@ pub fn create_service_config(
 
     // Load configuration from file if available
     let config_file = crate::config_file::load_config()?;
+    let naming = config_file
+        .as_ref()
+        .map(|config| config.naming)
+        .unwrap_or_default();
+    let link_mode = config_file
+        .as_ref()
+        .map(|config| config.link_mode)
+        .unwrap_or_default();
 
     // Extract embedding config from file
     let embedding_config = config_file
@@ -197,6 +211,10 @@ pub fn create_service_config(
             openai_base_url: embedding.openai_base_url,
             embedding_model: embedding.embedding_model,
             index_path: embedding.index_path,
+            batch_size: embedding.batch_size,
+            chunk_size: embedding.chunk_size,
+            query_cache_size: embedding.query_cache_size,
+            query_cache_ttl: embedding.query_cache_ttl,
         });
 
     // Load server configuration from skill-project.toml
@@ -210,6 +228,14 @@ pub fn create_service_config(
         embedding: embedding_config,
         http_server: http_server_config,
         registry_index_path,
+        naming,
+        link_mode,
+        // One-shot commands exit long before a debounced change is handled;
+        // only `serve` turns the watcher on (see `load_hot_reload_config`).
+        hot_reload: HotReloadConfig {
+            enabled: false,
+            ..Default::default()
+        },
         ..Default::default()
     })
 }
@@ -235,15 +261,31 @@ pub fn inject_edge_services(mut service: FastSkillService) -> CliResult<FastSkil
         }
     }
 
-    let repositories = load_repositories_from_project()?;
-    let repo_manager = RepositoryManager::from_definitions(repositories);
+    let repo_manager = load_repository_manager()?;
     service = service.with_repository_manager(Arc::new(repo_manager));
 
     Ok(service)
 }
 
-/// Load HTTP server configuration from skill-project.toml [tool.fastskill.server]
-pub fn load_server_config() -> CliResult<Option<HttpServerConfig>> {
+/// Build a [`RepositoryManager`] from the project's repositories, with registry
+/// index lookups going through the persistent resolver cache (unless
+/// `FASTSKILL_NO_RESOLVER_CACHE` is set).
+pub fn load_repository_manager() -> CliResult<RepositoryManager> {
+    let repo_manager = RepositoryManager::from_definitions(load_repositories_from_project()?);
+    if env::var_os("FASTSKILL_NO_RESOLVER_CACHE").is_some() {
+        return Ok(repo_manager);
+    }
+    Ok(match ResolverCache::default_path() {
+        Some(path) => repo_manager.with_resolver_cache(Arc::new(ResolverCache::load(
+            path,
+            chrono::Duration::seconds(DEFAULT_RESOLVER_CACHE_TTL_SECS),
+        ))),
+        None => repo_manager,
+    })
+}
+
+/// Read [tool.fastskill.server] from skill-project.toml, if present
+fn load_server_toml() -> CliResult<Option<HttpServerConfigToml>> {
     let current_dir = env::current_dir()
         .map_err(|e| CliError::Config(format!("Failed to get current directory: {}", e)))?;
 
@@ -260,13 +302,127 @@ pub fn load_server_config() -> CliResult<Option<HttpServerConfig>> {
         ))
     })?;
 
-    // Extract [tool.fastskill.server] configuration
-    let server_toml = project
+    Ok(project
         .tool
         .and_then(|t| t.fastskill)
-        .and_then(|f| f.server);
+        .and_then(|f| f.server))
+}
+
+/// Storage backend for `fastskill serve` from [tool.fastskill.server.storage];
+/// filesystem when unset. Other commands always use the filesystem.
+pub fn load_storage_config() -> CliResult<StorageConfig> {
+    let storage = load_server_toml()?
+        .and_then(|server| server.storage)
+        .unwrap_or_default();
+    match &storage {
+        StorageConfig::S3(s3) if s3.bucket.trim().is_empty() => {
+            return Err(CliError::Config(
+                "[tool.fastskill.server.storage] backend = \"s3\" requires a bucket".to_string(),
+            ));
+        }
+        StorageConfig::Postgres(postgres) if postgres.url_env.trim().is_empty() => {
+            return Err(CliError::Config(
+                "[tool.fastskill.server.storage] backend = \"postgres\" requires url_env to name \
+                 an environment variable"
+                    .to_string(),
+            ));
+        }
+        _ => {}
+    }
+    Ok(storage)
+}
+
+/// Hot reload for `fastskill serve` from [tool.fastskill.server.hot_reload];
+/// on with a 1000 ms debounce and batches of up to 100 skills when unset.
+pub fn load_hot_reload_config() -> CliResult<HotReloadConfig> {
+    let mut config = HotReloadConfig::default();
+    if let Some(settings) = load_server_toml()?.and_then(|server| server.hot_reload) {
+        if settings.max_batch == 0 {
+            return Err(CliError::Config(
+                "[tool.fastskill.server.hot_reload] max_batch must be at least 1".to_string(),
+            ));
+        }
+        config.enabled = settings.enabled;
+        config.debounce_ms = settings.debounce_ms;
+        config.max_batch = settings.max_batch;
+    }
+    Ok(config)
+}
+
+/// Tenancy for `fastskill serve` from [tool.fastskill.server.tenancy]; off
+/// when unset. The tenant JWT secret is resolved here, like client API keys.
+pub fn load_tenancy_config() -> CliResult<TenancyConfig> {
+    let mut tenancy = load_server_toml()?
+        .and_then(|server| server.tenancy)
+        .unwrap_or_default();
+    if !tenancy.enabled {
+        return Ok(tenancy);
+    }
+    tenancy
+        .validate()
+        .map_err(|e| CliError::Config(format!("[tool.fastskill.server.tenancy] {}", e)))?;
+    if let Some(jwt) = tenancy.jwt.as_mut() {
+        let secret = resolve_secret(&jwt.secret_env)
+            .filter(|secret| !secret.trim().is_empty())
+            .ok_or_else(|| {
+                CliError::Config(format!(
+                    "{} is not set; export the secret tenant tokens are signed with",
+                    jwt.secret_env
+                ))
+            })?;
+        jwt.secret = Some(secret.trim().to_string());
+    }
+    Ok(tenancy)
+}
+
+/// Replication role for `fastskill serve` from [tool.fastskill.server.replication]
+pub fn load_replication_config() -> CliResult<Option<ReplicationConfig>> {
+    let Some(config) = load_server_toml()?.and_then(|server| server.replication) else {
+        return Ok(None);
+    };
+    let secret_env = match &config {
+        ReplicationConfig::Primary { secret_env } => secret_env,
+        ReplicationConfig::Follower {
+            primary_url,
+            secret_env,
+            poll_interval_secs,
+        } => {
+            if !(primary_url.starts_with("http://") || primary_url.starts_with("https://")) {
+                return Err(CliError::Config(format!(
+                    "replication primary_url must be an http(s) URL, got '{}'",
+                    primary_url
+                )));
+            }
+            if *poll_interval_secs == 0 {
+                return Err(CliError::Config(
+                    "replication poll_interval_secs must be at least 1".to_string(),
+                ));
+            }
+            secret_env
+        }
+    };
+    if secret_env.trim().is_empty() {
+        return Err(CliError::Config(
+            "replication secret_env must name an environment variable".to_string(),
+        ));
+    }
+    Ok(Some(config))
+}
+
+/// Shared replication secret from the variable (or stored credential) `var`
+pub fn load_replication_key(var: &str) -> CliResult<ReplicationKey> {
+    let secret = resolve_secret(var).ok_or_else(|| {
+        CliError::Config(format!(
+            "{} is not set; export the secret shared by the replication primary and its followers",
+            var
+        ))
+    })?;
+    Ok(ReplicationKey::new(secret.trim())?)
+}
 
-    if let Some(server) = server_toml {
+/// Load HTTP server configuration from skill-project.toml [tool.fastskill.server]
+pub fn load_server_config() -> CliResult<Option<HttpServerConfig>> {
+    if let Some(server) = load_server_toml()? {
         // Validate and convert allowed_origins
         let allowed_origins: Vec<String> = server
             .allowed_origins
@@ -281,9 +437,51 @@ pub fn load_server_config() -> CliResult<Option<HttpServerConfig>> {
             })
             .collect();
 
+        let client_profiles: Vec<ClientProfile> = server
+            .clients
+            .into_iter()
+            .filter_map(|client| {
+                // A profile keyed on an unset variable must not fall back to
+                // name-only matching, or any caller could claim it by name.
+                let api_key = match client.api_key_env {
+                    Some(var) => match resolve_secret(&var) {
+                        Some(key) if !key.is_empty() => Some(key),
+                        _ => {
+                            tracing::warn!(
+                                "Client profile '{}' reads its API key from {}, which is not set or stored; skipping",
+                                client.name,
+                                var
+                            );
+                            return None;
+                        }
+                    },
+                    None => None,
+                };
+                Some(ClientProfile {
+                    name: client.name,
+                    api_key,
+                    dynamic_min_relevance: client.dynamic_min_relevance,
+                    max_dynamic_skills: client.max_dynamic_skills,
+                    allowed_scopes: client.allowed_scopes,
+                    teams: client.teams,
+                    role: client.role,
+                })
+            })
+            .collect();
+
+        validate_rate_limits(&server.rate_limit)?;
+        server
+            .injection
+            .validate()
+            .map_err(|e| CliError::Config(e.to_string()))?;
+
         let http_config = HttpServerConfig {
             allowed_origins,
             allowed_headers: server.allowed_headers,
+            client_profiles,
+            rate_limit: server.rate_limit,
+            injection_rules: server.injection,
+            jobs: server.jobs,
         };
 
         Ok(Some(http_config))
@@ -292,6 +490,20 @@ pub fn load_server_config() -> CliResult<Option<HttpServerConfig>> {
     }
 }
 
+/// Reject limits that would refuse every request
+fn validate_rate_limits(config: &RateLimitConfig) -> CliResult<()> {
+    for (name, limit) in [("per_token", config.per_token), ("per_ip", config.per_ip)] {
+        let Some(limit) = limit else { continue };
+        if limit.requests_per_minute == 0 || limit.burst == Some(0) {
+            return Err(CliError::Config(format!(
+                "[tool.fastskill.server.rate_limit.{}] requests_per_minute and burst must be at least 1",
+                name
+            )));
+        }
+    }
+    Ok(())
+}
+
 /// Validate that an origin string is a valid URI origin
 pub fn is_valid_origin(origin: &str) -> bool {
     // Basic validation: must be a non-empty string that looks like a URL
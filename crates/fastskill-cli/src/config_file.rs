@@ -1,8 +1,10 @@
 //! Configuration file parsing for FastSkill CLI
 
 use crate::error::{CliError, CliResult};
+use fastskill_core::core::link::LinkMode;
 use fastskill_core::core::manifest::SkillProjectToml;
 use fastskill_core::core::project;
+use fastskill_core::core::skill_naming::SkillNaming;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
@@ -16,6 +18,18 @@ pub struct EmbeddingConfig {
     /// Optional custom path for vector index database
     #[serde(default)]
     pub index_path: Option<PathBuf>,
+    /// Texts per embeddings API call (default 100)
+    #[serde(default)]
+    pub batch_size: Option<usize>,
+    /// Characters per section chunk for multi-vector indexing (off when unset)
+    #[serde(default)]
+    pub chunk_size: Option<usize>,
+    /// Query embeddings cached in memory (default 1000, 0 disables)
+    #[serde(default)]
+    pub query_cache_size: Option<usize>,
+    /// Seconds a cached query embedding is reused (default 3600)
+    #[serde(default)]
+    pub query_cache_ttl: Option<u64>,
 }
 
 /// Main configuration structure loaded from skill-project.toml
@@ -33,6 +47,12 @@ pub struct FastSkillConfig {
     /// Automatically reindex after add/install/update/remove (default: true)
     #[serde(default = "default_true")]
     pub auto_reindex: bool,
+    /// How `add` names skills and handles id collisions
+    #[serde(default)]
+    pub naming: SkillNaming,
+    /// How editable installs link skills into the skills dir
+    #[serde(default)]
+    pub link_mode: LinkMode,
 }
 
 fn default_true() -> bool {
@@ -88,6 +108,10 @@ pub fn load_config_from_skill_project(current_dir: &Path) -> CliResult<Option<Fa
             openai_base_url: e.openai_base_url,
             embedding_model: e.embedding_model,
             index_path: e.index_path,
+            batch_size: e.batch_size,
+            chunk_size: e.chunk_size,
+            query_cache_size: e.query_cache_size,
+            query_cache_ttl: e.query_cache_ttl,
         });
 
         // Convert HttpServerConfigToml to HttpServerConfig
@@ -101,6 +125,8 @@ pub fn load_config_from_skill_project(current_dir: &Path) -> CliResult<Option<Fa
             skills_directory: config.skills_directory,
             server,
             auto_reindex: config.auto_reindex,
+            naming: config.naming,
+            link_mode: config.link_mode,
         }))
     } else {
         // skill-project.toml exists but no [tool.fastskill] section
@@ -5,6 +5,7 @@
 //! each command closure at registration time — no `Any`-downcasting needed.
 
 use cli_framework::prelude::AppContext;
+use fastskill_core::core::download::DownloadManager;
 use fastskill_core::FastSkillService;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -48,7 +49,13 @@ impl FsState {
                 let cfg = crate::config::create_service_config(global, skills_dir)?;
                 let mut s = FastSkillService::new(cfg)
                     .await
-                    .map_err(CliError::Service)?;
+                    .map_err(CliError::Service)?
+                    .with_cancellation(crate::interrupt::token().clone());
+                let downloads = DownloadManager::new(DownloadManager::default_dir(
+                    &s.config().skill_storage_path,
+                ))
+                .with_progress(crate::utils::download_progress::observer());
+                s = s.with_downloads(downloads);
                 s.initialize().await.map_err(CliError::Service)?;
                 let s = crate::config::inject_edge_services(s)?;
                 Ok(Arc::new(s))
@@ -111,6 +111,11 @@ pub enum CliError {
 
     #[error("Invalid identifier: {0}")]
     InvalidIdentifier(String),
+
+    #[error(
+        "Another fastskill process is running ({0}); wait for it to finish or re-run with --wait"
+    )]
+    ProcessBusy(String),
 }
 
 pub type CliResult<T> = Result<T, CliError>;
@@ -163,6 +168,10 @@ impl CliError {
             CliError::Validation(_) => 1,
             // Skill not found -> exit code 1
             CliError::SkillNotFound(_) => 1,
+            // Interrupted by Ctrl-C -> 130, as shells report SIGINT
+            CliError::Service(fastskill_core::ServiceError::Cancelled) => {
+                crate::interrupt::EXIT_INTERRUPTED
+            }
             // System errors (IO, config, service) -> exit code 2
             CliError::Io(_) | CliError::Config(_) | CliError::Service(_) => 2,
             // Search errors: map underlying service failures to system error code
@@ -193,4 +202,10 @@ mod tests {
         ));
         assert_eq!(error.exit_code(), 1);
     }
+
+    #[test]
+    fn cancelled_service_error_uses_interrupt_exit_code() {
+        let error = CliError::Service(fastskill_core::ServiceError::Cancelled);
+        assert_eq!(error.exit_code(), 130);
+    }
 }
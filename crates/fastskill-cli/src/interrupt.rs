@@ -0,0 +1,37 @@
+//! Ctrl-C handling
+//!
+//! The first Ctrl-C cancels [`token`], which every service built by the CLI
+//! shares, so reindex, install and script runs stop at the next await and
+//! clean up after themselves. A second Ctrl-C exits immediately.
+
+use fastskill_core::core::cancel::CancellationToken;
+use std::sync::OnceLock;
+
+/// Exit status for a run stopped by SIGINT (128 + 2), as shells report it.
+pub const EXIT_INTERRUPTED: i32 = 130;
+
+static TOKEN: OnceLock<CancellationToken> = OnceLock::new();
+
+/// Token cancelled by the first Ctrl-C.
+pub fn token() -> &'static CancellationToken {
+    TOKEN.get_or_init(CancellationToken::new)
+}
+
+/// Whether this run was interrupted.
+pub fn interrupted() -> bool {
+    token().is_cancelled()
+}
+
+/// Listen for Ctrl-C in the background. Must be called inside the runtime.
+pub fn install_handler() {
+    tokio::spawn(async {
+        if tokio::signal::ctrl_c().await.is_err() {
+            return;
+        }
+        eprintln!("Interrupted; stopping (press Ctrl-C again to force quit)");
+        token().cancel();
+        if tokio::signal::ctrl_c().await.is_ok() {
+            std::process::exit(EXIT_INTERRUPTED);
+        }
+    });
+}
@@ -2,7 +2,7 @@
 //!
 //! All commands are registered as typed `builder.register` calls that use
 //! `IntoCommandSpec + FromArgValueMap`. Global flags (--skills-dir, --global,
-//! --verbose) are declared via `builder.global_flag` and read at dispatch time
+//! --verbose, --quiet, --color, --no-color, --wait) are declared via `builder.global_flag` and read at dispatch time
 //! through `ctx.opt_global_args()`.
 //!
 //! `Arc<FsState>` is captured at registration time by each command closure —
@@ -15,14 +15,18 @@ mod commands;
 mod config;
 mod config_file;
 mod context;
+mod diagnostics;
 mod error;
+mod interrupt;
 pub mod runtime_selector;
+mod telemetry;
 mod utils;
 
 use cli_framework::app::context::AppContext;
 use cli_framework::prelude::AppBuilder;
 use cli_framework::spec::value::ArgValue;
 use context::{FsCtx, FsState};
+use fastskill_core::Verbosity;
 use std::sync::Arc;
 
 fn or_exit<T, E: std::fmt::Display>(result: Result<T, E>, msg: &str) -> T {
@@ -60,16 +64,64 @@ fn ctx_skills_dir(ctx: &dyn AppContext) -> Option<std::path::PathBuf> {
         })
 }
 
+/// Take the skills directory lock for a command that changes it
+async fn lock_storage(
+    svc: &fastskill_core::FastSkillService,
+    command: &str,
+) -> error::CliResult<fastskill_core::core::ProcessLock> {
+    utils::storage_lock::lock_storage(&svc.config().skill_storage_path, command).await
+}
+
 use commands::{
-    add, analyze, doctor, eval, init, install, list, marketplace, read, reindex, remove, repos,
-    search, serve, skillopt, update,
+    add, admin, analyze, audit, credentials, diagnostics as diagnostics_cmd, doctor, eval,
+    fixtures, gc, graph, init, install, lint, list, lock, marketplace, migrate, pack, profile,
+    read, reindex, remove, report, repos, retire, search, self_update, serve, skillopt,
+    telemetry as telemetry_cmd, test, update, vendor, yank,
 };
 
+/// Color choice from `--no-color` / `--color <WHEN>` / `--color=<WHEN>`;
+/// `--no-color` wins over `--color`.
+fn color_choice(raw: &[String]) -> Result<Option<bool>, String> {
+    if raw.iter().any(|a| a == "--no-color") {
+        return Ok(Some(false));
+    }
+    let mut choice = None;
+    let mut args = raw.iter();
+    while let Some(a) = args.next() {
+        let value = if a == "--color" {
+            args.next().map(String::as_str)
+        } else {
+            a.strip_prefix("--color=")
+        };
+        if let Some(value) = value {
+            choice = fastskill_core::output::table::parse_color_choice(value)?;
+        }
+    }
+    Ok(choice)
+}
+
 #[tokio::main]
 async fn main() {
+    diagnostics::install_panic_hook();
+    interrupt::install_handler();
     let raw: Vec<String> = std::env::args().collect();
     let verbose = raw.iter().any(|a| a == "--verbose" || a == "-v");
-    fastskill_core::init_logging_with_verbose(verbose);
+    let quiet = raw.iter().any(|a| a == "--quiet" || a == "-q");
+    if verbose && quiet {
+        eprintln!("Error: --quiet and --verbose cannot be used together");
+        std::process::exit(2);
+    }
+    let color = or_exit(color_choice(&raw), "Error");
+    fastskill_core::output::table::set_color_enabled(color);
+    utils::messages::set_quiet(quiet);
+    utils::storage_lock::set_wait(raw.iter().any(|a| a == "--wait"));
+    fastskill_core::init_logging_with_verbosity(if quiet {
+        Verbosity::Quiet
+    } else if verbose {
+        Verbosity::Verbose
+    } else {
+        Verbosity::Normal
+    });
 
     let state = Arc::new(FsState::new());
     let ctx = FsCtx;
@@ -91,7 +143,7 @@ async fn main() {
     // `read`. We rewrite the args here, before dispatch, when the first
     // positional token is not a recognized top-level command or command group.
     // Global flags (and their values) that precede the subcommand are skipped.
-    let raw = {
+    let (raw, command) = {
         // The set of recognized first path segments: every registered command
         // (including built-ins like `spec`/`completion`/`mcp`) and every group
         // node (`analyze`, `repos`, ...). `help` is clap-provided.
@@ -110,11 +162,17 @@ async fn main() {
         for (path, _) in registry.groups() {
             known.insert(path.split('/').next().unwrap_or(path));
         }
+        // Full command paths, for telemetry's per-command counters.
+        let commands: std::collections::HashSet<String> = registry
+            .all_tree_commands()
+            .into_iter()
+            .map(|(path, _)| path.to_string())
+            .collect();
 
         let mut i = 1;
         while i < raw.len() {
             let a = &raw[i];
-            if a == "--skills-dir" {
+            if a == "--skills-dir" || a == "--color" {
                 // value-taking global flag in `--flag value` form
                 i += 2;
                 continue;
@@ -126,19 +184,38 @@ async fn main() {
             }
             break;
         }
-        if i < raw.len() && !known.contains(raw[i].as_str()) {
+        let raw = if i < raw.len() && !known.contains(raw[i].as_str()) {
             let mut rewritten = raw;
             rewritten.insert(i, "read".to_string());
             rewritten
         } else {
             raw
-        }
+        };
+        let command = telemetry::command_path(raw.get(i..).unwrap_or_default(), &commands);
+        (raw, command)
     };
 
-    match app.run_with_args(raw).await {
+    let result = app.run_with_args(raw).await;
+    let error_category = result.as_ref().err().map(|e| {
+        if interrupt::interrupted() {
+            "cancelled"
+        } else {
+            telemetry::error_category(e)
+        }
+    });
+    telemetry::record_run(command.as_deref(), error_category).await;
+    match result {
         Ok(()) => std::process::exit(0),
         Err(e) => {
             eprintln!("Error: {}", e);
+            if interrupt::interrupted() {
+                std::process::exit(interrupt::EXIT_INTERRUPTED);
+            }
+            // User errors (bad input, config, validation) are not bugs; only
+            // offer a bundle for failures we could not classify.
+            if !quiet && matches!(error_category, Some("service" | "other")) {
+                diagnostics::offer_bundle(&format!("error: {}", e), None);
+            }
             std::process::exit(1);
         }
     }
@@ -177,6 +254,43 @@ fn build_app(builder: AppBuilder, state: Arc<FsState>) -> anyhow::Result<AppBuil
             cardinality: Cardinality::Optional,
             help: "Enable verbose output",
             ..Default::default()
+        })
+        .global_flag(ArgSpec {
+            name: "quiet",
+            kind: ArgKind::Flag,
+            long: Some("quiet"),
+            short: Some('q'),
+            value_type: ArgValueType::Bool,
+            cardinality: Cardinality::Optional,
+            help: "Only print warnings, errors and command results",
+            ..Default::default()
+        })
+        .global_flag(ArgSpec {
+            name: "color",
+            kind: ArgKind::Option,
+            long: Some("color"),
+            value_type: ArgValueType::String,
+            cardinality: Cardinality::Optional,
+            help: "When to use colors: auto, always, never (default: auto)",
+            ..Default::default()
+        })
+        .global_flag(ArgSpec {
+            name: "no-color",
+            kind: ArgKind::Flag,
+            long: Some("no-color"),
+            value_type: ArgValueType::Bool,
+            cardinality: Cardinality::Optional,
+            help: "Disable colored output (also set by NO_COLOR)",
+            ..Default::default()
+        })
+        .global_flag(ArgSpec {
+            name: "wait",
+            kind: ArgKind::Flag,
+            long: Some("wait"),
+            value_type: ArgValueType::Bool,
+            cardinality: Cardinality::Optional,
+            help: "Wait for other fastskill processes to finish instead of failing",
+            ..Default::default()
         });
 
     // ── Typed commands (no service) ──────────────────────────────────────────
@@ -187,14 +301,40 @@ fn build_app(builder: AppBuilder, state: Arc<FsState>) -> anyhow::Result<AppBuil
         .register(
             path!["install"],
             |_ctx, args: install::InstallArgs| async move {
+                let _lock = if args.writes_storage() {
+                    utils::storage_lock::lock_resolved_storage(false, "install").await?
+                } else {
+                    None
+                };
                 install::execute_install(args)
                     .await
                     .map_err(anyhow::Error::from)
             },
         )?
+        .register(
+            path!["vendor"],
+            |_ctx, args: vendor::VendorArgs| async move {
+                vendor::execute_vendor(args)
+                    .await
+                    .map_err(anyhow::Error::from)
+            },
+        )?
+        .register(
+            path!["self-update"],
+            |_ctx, args: self_update::SelfUpdateArgs| async move {
+                self_update::execute_self_update(args)
+                    .await
+                    .map_err(anyhow::Error::from)
+            },
+        )?
         .register(path!["update"], |ctx, args: update::UpdateArgs| {
             let global = ctx_global(ctx);
             async move {
+                let _lock = if args.writes_storage() {
+                    utils::storage_lock::lock_resolved_storage(global, "update").await?
+                } else {
+                    None
+                };
                 update::execute_update(args, global)
                     .await
                     .map_err(anyhow::Error::from)
@@ -204,6 +344,7 @@ fn build_app(builder: AppBuilder, state: Arc<FsState>) -> anyhow::Result<AppBuil
     // ── Typed commands that need FsState (service injection) ─────────────────
     let builder = {
         let state_list = Arc::clone(&state);
+        let state_lock = Arc::clone(&state);
         let state_read = Arc::clone(&state);
         builder
             .register(path!["list"], move |ctx, args: list::ListArgs| {
@@ -217,6 +358,22 @@ fn build_app(builder: AppBuilder, state: Arc<FsState>) -> anyhow::Result<AppBuil
                         .map_err(anyhow::Error::from)
                 }
             })?
+            .register(path!["lock"], move |ctx, args: lock::LockArgs| {
+                let global = ctx_global(ctx);
+                let skills_dir = ctx_skills_dir(ctx);
+                let state = Arc::clone(&state_lock);
+                async move {
+                    let svc = state.service_with(global, skills_dir).await?;
+                    let _lock = if args.check || args.frozen {
+                        None
+                    } else {
+                        Some(lock_storage(&svc, "lock").await?)
+                    };
+                    lock::execute_lock(&svc, args, global)
+                        .await
+                        .map_err(anyhow::Error::from)
+                }
+            })?
             .register(path!["read"], move |ctx, args: read::ReadArgs| {
                 let global = ctx_global(ctx);
                 let skills_dir = ctx_skills_dir(ctx);
@@ -323,6 +480,249 @@ fn build_app(builder: AppBuilder, state: Arc<FsState>) -> anyhow::Result<AppBuil
             )?
     };
 
+    // ── admin, audit ─────────────────────────────────────────────────────────
+    let builder = {
+        use cli_framework::spec::command_tree::GroupMetadata;
+        let state_admin = Arc::clone(&state);
+        builder
+            .register_group(
+                &path!["admin"],
+                GroupMetadata {
+                    summary: "Inspect server administration state",
+                    hidden: false,
+                },
+            )?
+            .register(path!["admin", "audit"], {
+                let state = Arc::clone(&state_admin);
+                move |ctx, args: admin::AdminAuditArgs| {
+                    let global = ctx_global(ctx);
+                    let skills_dir = ctx_skills_dir(ctx);
+                    let state = Arc::clone(&state);
+                    async move {
+                        let svc = state.service_with(global, skills_dir).await?;
+                        admin::execute_admin_audit(&svc, args)
+                            .await
+                            .map_err(anyhow::Error::from)
+                    }
+                }
+            })?
+            .register(path!["audit"], {
+                let state = Arc::clone(&state_admin);
+                move |ctx, args: audit::AuditArgs| {
+                    let global = ctx_global(ctx);
+                    let skills_dir = ctx_skills_dir(ctx);
+                    let state = Arc::clone(&state);
+                    async move {
+                        let svc = state.service_with(global, skills_dir).await?;
+                        audit::execute_audit(&svc, args)
+                            .await
+                            .map_err(anyhow::Error::from)
+                    }
+                }
+            })?
+            .register_group(
+                &path!["admin", "keys"],
+                GroupMetadata {
+                    summary: "Manage scoped API keys for the HTTP and gRPC servers",
+                    hidden: false,
+                },
+            )?
+            .register(path!["admin", "keys", "create"], {
+                let state = Arc::clone(&state_admin);
+                move |ctx, args: admin::AdminKeysCreateArgs| {
+                    let global = ctx_global(ctx);
+                    let skills_dir = ctx_skills_dir(ctx);
+                    let state = Arc::clone(&state);
+                    async move {
+                        let svc = state.service_with(global, skills_dir).await?;
+                        admin::execute_admin_keys_create(&svc, args)
+                            .await
+                            .map_err(anyhow::Error::from)
+                    }
+                }
+            })?
+            .register(path!["admin", "keys", "list"], {
+                let state = Arc::clone(&state_admin);
+                move |ctx, args: admin::AdminKeysListArgs| {
+                    let global = ctx_global(ctx);
+                    let skills_dir = ctx_skills_dir(ctx);
+                    let state = Arc::clone(&state);
+                    async move {
+                        let svc = state.service_with(global, skills_dir).await?;
+                        admin::execute_admin_keys_list(&svc, args)
+                            .await
+                            .map_err(anyhow::Error::from)
+                    }
+                }
+            })?
+            .register(path!["admin", "keys", "revoke"], {
+                let state = Arc::clone(&state_admin);
+                move |ctx, args: admin::AdminKeysRevokeArgs| {
+                    let global = ctx_global(ctx);
+                    let skills_dir = ctx_skills_dir(ctx);
+                    let state = Arc::clone(&state);
+                    async move {
+                        let svc = state.service_with(global, skills_dir).await?;
+                        admin::execute_admin_keys_revoke(&svc, args)
+                            .await
+                            .map_err(anyhow::Error::from)
+                    }
+                }
+            })?
+    };
+
+    // ── migrate ──────────────────────────────────────────────────────────────
+    let builder = {
+        use cli_framework::spec::command_tree::GroupMetadata;
+        let state_migrate = Arc::clone(&state);
+        builder
+            .register_group(
+                &path!["migrate"],
+                GroupMetadata {
+                    summary: "Move an existing installation to another storage backend or layout",
+                    hidden: false,
+                },
+            )?
+            .register(path!["migrate", "to-postgres"], {
+                let state = Arc::clone(&state_migrate);
+                move |ctx, args: migrate::MigrateToPostgresArgs| {
+                    let global = ctx_global(ctx);
+                    let skills_dir = ctx_skills_dir(ctx);
+                    let state = Arc::clone(&state);
+                    async move {
+                        let svc = state.service_with(global, skills_dir).await?;
+                        let _lock = lock_storage(&svc, "migrate to-postgres").await?;
+                        migrate::execute_migrate_to_postgres(&svc, args)
+                            .await
+                            .map_err(anyhow::Error::from)
+                    }
+                }
+            })?
+            .register(path!["migrate", "scoped-ids"], {
+                let state = Arc::clone(&state_migrate);
+                move |ctx, args: migrate::MigrateScopedIdsArgs| {
+                    let global = ctx_global(ctx);
+                    let skills_dir = ctx_skills_dir(ctx);
+                    let state = Arc::clone(&state);
+                    async move {
+                        let svc = state.service_with(global, skills_dir).await?;
+                        let _lock = lock_storage(&svc, "migrate scoped-ids").await?;
+                        migrate::execute_migrate_scoped_ids(&svc, args)
+                            .await
+                            .map_err(anyhow::Error::from)
+                    }
+                }
+            })?
+    };
+
+    // ── telemetry ────────────────────────────────────────────────────────────
+    let builder = {
+        use cli_framework::spec::command_tree::GroupMetadata;
+        builder
+            .register_group(
+                &path!["telemetry"],
+                GroupMetadata {
+                    summary: "Opt in to or out of anonymous usage reporting (off by default)",
+                    hidden: false,
+                },
+            )?
+            .register(
+                path!["telemetry", "status"],
+                |_ctx, args: telemetry_cmd::TelemetryStatusArgs| async move {
+                    telemetry_cmd::execute_telemetry_status(args)
+                        .await
+                        .map_err(anyhow::Error::from)
+                },
+            )?
+            .register(
+                path!["telemetry", "enable"],
+                |_ctx, args: telemetry_cmd::TelemetryEnableArgs| async move {
+                    telemetry_cmd::execute_telemetry_enable(args)
+                        .await
+                        .map_err(anyhow::Error::from)
+                },
+            )?
+            .register(
+                path!["telemetry", "disable"],
+                |_ctx, args: telemetry_cmd::TelemetryDisableArgs| async move {
+                    telemetry_cmd::execute_telemetry_disable(args)
+                        .await
+                        .map_err(anyhow::Error::from)
+                },
+            )?
+    };
+
+    // ── diagnostics ──────────────────────────────────────────────────────────
+    let builder = {
+        use cli_framework::spec::command_tree::GroupMetadata;
+        builder
+            .register_group(
+                &path!["diagnostics"],
+                GroupMetadata {
+                    summary: "Collect information to attach to bug reports",
+                    hidden: false,
+                },
+            )?
+            .register(
+                path!["diagnostics", "collect"],
+                |_ctx, args: diagnostics_cmd::DiagnosticsCollectArgs| async move {
+                    diagnostics_cmd::execute_diagnostics_collect(args)
+                        .await
+                        .map_err(anyhow::Error::from)
+                },
+            )?
+    };
+
+    // ── fixtures (hidden, for benchmarks) ────────────────────────────────────
+    let builder = {
+        use cli_framework::spec::command_tree::GroupMetadata;
+        builder
+            .register_group(
+                &path!["fixtures"],
+                GroupMetadata {
+                    summary: "Generate synthetic skill sets for benchmarks",
+                    hidden: true,
+                },
+            )?
+            .register(
+                path!["fixtures", "generate"],
+                |_ctx, args: fixtures::FixturesGenerateArgs| async move {
+                    fixtures::execute_fixtures_generate(args)
+                        .await
+                        .map_err(anyhow::Error::from)
+                },
+            )?
+    };
+
+    // ── credentials ──────────────────────────────────────────────────────────
+    let builder = {
+        use cli_framework::spec::command_tree::GroupMetadata;
+        builder
+            .register_group(
+                &path!["credentials"],
+                GroupMetadata {
+                    summary: "Store registry tokens and API keys in the OS keyring",
+                    hidden: false,
+                },
+            )?
+            .register(
+                path!["credentials", "set"],
+                |_ctx, args: credentials::CredentialsSetArgs| async move {
+                    credentials::execute_credentials_set(args)
+                        .await
+                        .map_err(anyhow::Error::from)
+                },
+            )?
+            .register(
+                path!["credentials", "remove"],
+                |_ctx, args: credentials::CredentialsRemoveArgs| async move {
+                    credentials::execute_credentials_remove(args)
+                        .await
+                        .map_err(anyhow::Error::from)
+                },
+            )?
+    };
+
     // ── marketplace: fully migrated to typed API ─────────────────────────────
     let builder = {
         use cli_framework::spec::command_tree::GroupMetadata;
@@ -344,6 +744,70 @@ fn build_app(builder: AppBuilder, state: Arc<FsState>) -> anyhow::Result<AppBuil
             )?
     };
 
+    // ── pack ─────────────────────────────────────────────────────────────────
+    let builder = {
+        use cli_framework::spec::command_tree::GroupMetadata;
+        builder
+            .register_group(
+                &path!["pack"],
+                GroupMetadata {
+                    summary: "Bundle several skills into one distributable skill pack",
+                    hidden: false,
+                },
+            )?
+            .register(
+                path!["pack", "create"],
+                |_ctx, args: pack::PackCreateArgs| async move {
+                    pack::execute_pack_create(args)
+                        .await
+                        .map_err(anyhow::Error::from)
+                },
+            )?
+    };
+
+    // ── profile ──────────────────────────────────────────────────────────────
+    let builder = {
+        use cli_framework::spec::command_tree::GroupMetadata;
+        let state_profile = Arc::clone(&state);
+        builder
+            .register_group(
+                &path!["profile"],
+                GroupMetadata {
+                    summary: "Switch between named sets of enabled skills",
+                    hidden: false,
+                },
+            )?
+            .register(path!["profile", "apply"], {
+                let state = Arc::clone(&state_profile);
+                move |ctx, args: profile::ProfileApplyArgs| {
+                    let global = ctx_global(ctx);
+                    let skills_dir = ctx_skills_dir(ctx);
+                    let state = Arc::clone(&state);
+                    async move {
+                        let svc = state.service_with(global, skills_dir).await?;
+                        let _lock = lock_storage(&svc, "profile apply").await?;
+                        profile::execute_profile_apply(&svc, args)
+                            .await
+                            .map_err(anyhow::Error::from)
+                    }
+                }
+            })?
+            .register(path!["profile", "save"], {
+                let state = Arc::clone(&state_profile);
+                move |ctx, args: profile::ProfileSaveArgs| {
+                    let global = ctx_global(ctx);
+                    let skills_dir = ctx_skills_dir(ctx);
+                    let state = Arc::clone(&state);
+                    async move {
+                        let svc = state.service_with(global, skills_dir).await?;
+                        profile::execute_profile_save(&svc, args)
+                            .await
+                            .map_err(anyhow::Error::from)
+                    }
+                }
+            })?
+    };
+
     // ── eval: fully migrated to typed API ────────────────────────────────────
     let builder = {
         use cli_framework::spec::command_tree::GroupMetadata;
@@ -451,6 +915,11 @@ fn build_app(builder: AppBuilder, state: Arc<FsState>) -> anyhow::Result<AppBuil
             let state = Arc::clone(&state_add);
             async move {
                 let svc = state.service_with(global, skills_dir).await?;
+                let _lock = if args.dry_run {
+                    None
+                } else {
+                    Some(lock_storage(&svc, "add").await?)
+                };
                 add::execute_add(&svc, args, global)
                     .await
                     .map_err(anyhow::Error::from)
@@ -512,10 +981,21 @@ fn build_app(builder: AppBuilder, state: Arc<FsState>) -> anyhow::Result<AppBuil
                     let state = Arc::clone(&state);
                     async move {
                         let svc = state.service_with(global, skills_dir).await?;
-                        let Some(ctx) = analyze::load_analysis_context(&svc).await? else {
-                            return Ok(());
-                        };
-                        analyze::duplicates::execute_duplicates(ctx, args)
+                        analyze::duplicates::execute_duplicates(&svc, args)
+                            .await
+                            .map_err(anyhow::Error::from)
+                    }
+                }
+            })?
+            .register(path!["analyze", "conflicts"], {
+                let state = Arc::clone(&state_analyze);
+                move |ctx, args: analyze::conflicts::ConflictsArgs| {
+                    let global = ctx_global(ctx);
+                    let skills_dir = ctx_skills_dir(ctx);
+                    let state = Arc::clone(&state);
+                    async move {
+                        let svc = state.service_with(global, skills_dir).await?;
+                        analyze::conflicts::execute_conflicts(&svc, args)
                             .await
                             .map_err(anyhow::Error::from)
                     }
@@ -529,6 +1009,13 @@ fn build_app(builder: AppBuilder, state: Arc<FsState>) -> anyhow::Result<AppBuil
         let state_remove = Arc::clone(&state);
         let state_search = Arc::clone(&state);
         let state_doctor = Arc::clone(&state);
+        let state_report = Arc::clone(&state);
+        let state_yank = Arc::clone(&state);
+        let state_graph = Arc::clone(&state);
+        let state_retire = Arc::clone(&state);
+        let state_gc = Arc::clone(&state);
+        let state_test = Arc::clone(&state);
+        let state_lint = Arc::clone(&state);
         builder
             .register(path!["reindex"], move |ctx, args: reindex::ReindexArgs| {
                 let global = ctx_global(ctx);
@@ -536,6 +1023,7 @@ fn build_app(builder: AppBuilder, state: Arc<FsState>) -> anyhow::Result<AppBuil
                 let state = Arc::clone(&state_reindex);
                 async move {
                     let svc = state.service_with(global, skills_dir).await?;
+                    let _lock = lock_storage(&svc, "reindex").await?;
                     reindex::execute_reindex(&svc, args)
                         .await
                         .map_err(anyhow::Error::from)
@@ -547,6 +1035,11 @@ fn build_app(builder: AppBuilder, state: Arc<FsState>) -> anyhow::Result<AppBuil
                 let state = Arc::clone(&state_remove);
                 async move {
                     let svc = state.service_with(global, skills_dir).await?;
+                    let _lock = if args.dry_run {
+                        None
+                    } else {
+                        Some(lock_storage(&svc, "remove").await?)
+                    };
                     remove::execute_remove(&svc, args, global)
                         .await
                         .map_err(anyhow::Error::from)
@@ -587,6 +1080,93 @@ fn build_app(builder: AppBuilder, state: Arc<FsState>) -> anyhow::Result<AppBuil
                         .map_err(anyhow::Error::from)
                 }
             })?
+            .register(path!["report"], move |ctx, args: report::ReportArgs| {
+                let global = ctx_global(ctx);
+                let skills_dir = ctx_skills_dir(ctx);
+                let state = Arc::clone(&state_report);
+                async move {
+                    let svc = state.service_with(global, skills_dir).await?;
+                    report::execute_report(&svc, args)
+                        .await
+                        .map_err(anyhow::Error::from)
+                }
+            })?
+            .register(path!["graph"], move |ctx, args: graph::GraphArgs| {
+                let global = ctx_global(ctx);
+                let skills_dir = ctx_skills_dir(ctx);
+                let state = Arc::clone(&state_graph);
+                async move {
+                    let svc = state.service_with(global, skills_dir).await?;
+                    graph::execute_graph(&svc, args)
+                        .await
+                        .map_err(anyhow::Error::from)
+                }
+            })?
+            .register(path!["retire"], move |ctx, args: retire::RetireArgs| {
+                let global = ctx_global(ctx);
+                let skills_dir = ctx_skills_dir(ctx);
+                let state = Arc::clone(&state_retire);
+                async move {
+                    let svc = state.service_with(global, skills_dir).await?;
+                    retire::execute_retire(&svc, args)
+                        .await
+                        .map_err(anyhow::Error::from)
+                }
+            })?
+            .register(path!["gc"], move |ctx, args: gc::GcArgs| {
+                let global = ctx_global(ctx);
+                let skills_dir = ctx_skills_dir(ctx);
+                let state = Arc::clone(&state_gc);
+                async move {
+                    let svc = state.service_with(global, skills_dir).await?;
+                    let _lock = if args.prune {
+                        Some(lock_storage(&svc, "gc").await?)
+                    } else {
+                        None
+                    };
+                    gc::execute_gc(&svc, args, global)
+                        .await
+                        .map_err(anyhow::Error::from)
+                }
+            })?
+            .register(path!["test"], move |ctx, args: test::TestArgs| {
+                let global = ctx_global(ctx);
+                let skills_dir = ctx_skills_dir(ctx);
+                let state = Arc::clone(&state_test);
+                async move {
+                    let svc = state.service_with(global, skills_dir).await?;
+                    test::execute_test(&svc, args)
+                        .await
+                        .map_err(anyhow::Error::from)
+                }
+            })?
+            .register(path!["lint"], move |ctx, args: lint::LintArgs| {
+                let global = ctx_global(ctx);
+                let skills_dir = ctx_skills_dir(ctx);
+                let state = Arc::clone(&state_lint);
+                async move {
+                    let svc = state.service_with(global, skills_dir).await?;
+                    let _lock = if args.fix {
+                        Some(lock_storage(&svc, "lint --fix").await?)
+                    } else {
+                        None
+                    };
+                    lint::execute_lint(&svc, args)
+                        .await
+                        .map_err(anyhow::Error::from)
+                }
+            })?
+            .register(path!["yank"], move |ctx, args: yank::YankArgs| {
+                let global = ctx_global(ctx);
+                let skills_dir = ctx_skills_dir(ctx);
+                let state = Arc::clone(&state_yank);
+                async move {
+                    let svc = state.service_with(global, skills_dir).await?;
+                    yank::execute_yank(&svc, args)
+                        .await
+                        .map_err(anyhow::Error::from)
+                }
+            })?
     };
 
     Ok(builder)
@@ -608,4 +1188,24 @@ mod tests {
         fn assert_send_sync<T: Send + Sync>() {}
         assert_send_sync::<FsCtx>();
     }
+
+    #[test]
+    fn test_color_choice() {
+        let args = |list: &[&str]| list.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        assert_eq!(color_choice(&args(&["fastskill", "list"])), Ok(None));
+        assert_eq!(
+            color_choice(&args(&["fastskill", "--color", "always", "list"])),
+            Ok(Some(true))
+        );
+        assert_eq!(
+            color_choice(&args(&[
+                "fastskill",
+                "--color=always",
+                "--no-color",
+                "list"
+            ])),
+            Ok(Some(false))
+        );
+        assert!(color_choice(&args(&["fastskill", "--color=rainbow"])).is_err());
+    }
 }
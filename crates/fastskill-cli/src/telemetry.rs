@@ -0,0 +1,302 @@
+//! Opt-in anonymous usage telemetry
+//!
+//! Off unless the user runs `fastskill telemetry enable` or sets
+//! `FASTSKILL_TELEMETRY=1`; `DO_NOT_TRACK` and `FASTSKILL_TELEMETRY=0` always
+//! win. When on, each run adds one to a per-command counter and, on failure,
+//! to a per-error-category counter in a local batch file. Only registered
+//! command paths (e.g. `repos/skills`) and error categories are recorded,
+//! never arguments, skill ids, paths or messages. The batch is sent to the
+//! configured endpoint at most once a day and cleared after a successful send;
+//! with no endpoint configured it stays local and `telemetry status` shows it.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// `1`/`true`/`on` enables, `0`/`false`/`off` disables, overriding the setting.
+pub const TELEMETRY_ENV: &str = "FASTSKILL_TELEMETRY";
+
+/// Endpoint override for the current process.
+pub const TELEMETRY_ENDPOINT_ENV: &str = "FASTSKILL_TELEMETRY_ENDPOINT";
+
+/// How often a batch is sent.
+const REPORT_INTERVAL_HOURS: i64 = 24;
+
+/// Persisted choice in `<config dir>/fastskill/telemetry.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TelemetrySettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+}
+
+/// Aggregated counters; this is exactly the payload that is sent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageBatch {
+    pub version: String,
+    pub os: String,
+    pub arch: String,
+    pub period_start: DateTime<Utc>,
+    #[serde(default)]
+    pub commands: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub errors: BTreeMap<String, u64>,
+}
+
+impl UsageBatch {
+    fn new() -> Self {
+        Self {
+            version: fastskill_core::VERSION.to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            period_start: Utc::now(),
+            commands: BTreeMap::new(),
+            errors: BTreeMap::new(),
+        }
+    }
+}
+
+/// Where the effective on/off state comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TelemetrySource {
+    DoNotTrack,
+    Env,
+    Settings,
+}
+
+/// Settings and pending batch, both under one directory.
+#[derive(Debug, Clone)]
+pub struct Telemetry {
+    dir: PathBuf,
+}
+
+fn env_value(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|v| !v.trim().is_empty())
+}
+
+impl Telemetry {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// `<dirs::config_dir()>/fastskill`
+    pub fn default_location() -> Option<Self> {
+        dirs::config_dir().map(|d| Self::new(d.join("fastskill")))
+    }
+
+    fn settings_path(&self) -> PathBuf {
+        self.dir.join("telemetry.toml")
+    }
+
+    fn usage_path(&self) -> PathBuf {
+        self.dir.join("telemetry-usage.json")
+    }
+
+    /// Saved settings; a missing or unreadable file means disabled.
+    pub fn settings(&self) -> TelemetrySettings {
+        std::fs::read_to_string(self.settings_path())
+            .ok()
+            .and_then(|s| toml::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save_settings(&self, settings: &TelemetrySettings) -> std::io::Result<()> {
+        let content = toml::to_string(settings)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        write_file(&self.settings_path(), content.as_bytes())
+    }
+
+    /// Whether telemetry is on for this process, and why.
+    pub fn effective(&self) -> (bool, TelemetrySource) {
+        if env_value("DO_NOT_TRACK").is_some_and(|v| v != "0" && v != "false") {
+            return (false, TelemetrySource::DoNotTrack);
+        }
+        match env_value(TELEMETRY_ENV)
+            .map(|v| v.to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("1" | "true" | "on") => (true, TelemetrySource::Env),
+            Some("0" | "false" | "off") => (false, TelemetrySource::Env),
+            _ => (self.settings().enabled, TelemetrySource::Settings),
+        }
+    }
+
+    /// Endpoint from the environment, then settings.
+    pub fn endpoint(&self) -> Option<String> {
+        env_value(TELEMETRY_ENDPOINT_ENV).or(self.settings().endpoint)
+    }
+
+    pub fn pending(&self) -> Option<UsageBatch> {
+        std::fs::read_to_string(self.usage_path())
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+    }
+
+    /// Drop the pending batch.
+    pub fn clear(&self) -> std::io::Result<()> {
+        match std::fs::remove_file(self.usage_path()) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Count one run of `command`, failed with `error_category` if set.
+    pub fn record(&self, command: &str, error_category: Option<&str>) -> std::io::Result<()> {
+        let mut batch = self.pending().unwrap_or_else(UsageBatch::new);
+        *batch.commands.entry(command.to_string()).or_default() += 1;
+        if let Some(category) = error_category {
+            *batch.errors.entry(category.to_string()).or_default() += 1;
+        }
+        let content = serde_json::to_vec_pretty(&batch)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        write_file(&self.usage_path(), &content)
+    }
+
+    /// Send the batch if an endpoint is set and the batch is a day old.
+    async fn flush_if_due(&self) {
+        let (Some(endpoint), Some(batch)) = (self.endpoint(), self.pending()) else {
+            return;
+        };
+        if Utc::now() - batch.period_start < Duration::hours(REPORT_INTERVAL_HOURS) {
+            return;
+        }
+        let client = match reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(3))
+            .user_agent(format!("fastskill/{}", fastskill_core::VERSION))
+            .build()
+        {
+            Ok(client) => client,
+            Err(_) => return,
+        };
+        match client.post(&endpoint).json(&batch).send().await {
+            Ok(response) if response.status().is_success() => {
+                let _ = self.clear();
+            }
+            Ok(response) => tracing::debug!("Telemetry endpoint returned {}", response.status()),
+            Err(e) => tracing::debug!("Telemetry report failed: {}", e),
+        }
+    }
+}
+
+fn write_file(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content)
+}
+
+/// Longest registered command path at the start of `args` (the tokens after
+/// global flags), e.g. `["repos", "skills", "--scope", "acme"]` →
+/// `repos/skills`. Anything that is not a registered path is never returned.
+pub fn command_path(args: &[String], commands: &HashSet<String>) -> Option<String> {
+    let positional: Vec<&str> = args
+        .iter()
+        .map(String::as_str)
+        .filter(|a| !a.starts_with('-'))
+        .take(3)
+        .collect();
+    (1..=positional.len())
+        .rev()
+        .map(|n| positional[..n].join("/"))
+        .find(|path| commands.contains(path))
+}
+
+/// Coarse category of a failed run, taken from the error's message prefix so
+/// only the category, never the message, is recorded.
+pub fn error_category(error: &impl std::fmt::Display) -> &'static str {
+    let message = error.to_string();
+    [
+        ("Configuration error", "config"),
+        ("Invalid skill source", "invalid_source"),
+        ("Git clone failed", "git"),
+        ("Skill validation failed", "skill_validation"),
+        ("IO error", "io"),
+        ("Service error", "service"),
+        ("Search error", "search"),
+        ("Validation error", "validation"),
+        ("skill-project.toml validation error", "manifest"),
+        ("Invalid semantic version", "semver"),
+        ("Invalid identifier", "identifier"),
+        ("Another fastskill process", "busy"),
+    ]
+    .into_iter()
+    .find(|(prefix, _)| message.starts_with(prefix))
+    .map(|(_, category)| category)
+    .unwrap_or("other")
+}
+
+/// Record a finished run and send the batch when due. Never fails the command.
+pub async fn record_run(command: Option<&str>, error_category: Option<&str>) {
+    let Some(telemetry) = Telemetry::default_location() else {
+        return;
+    };
+    if !telemetry.effective().0 {
+        return;
+    }
+    if let Some(command) = command {
+        if let Err(e) = telemetry.record(command, error_category) {
+            tracing::debug!("Failed to record telemetry: {}", e);
+        }
+    }
+    telemetry.flush_if_due().await;
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_record_and_settings_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let telemetry = Telemetry::new(dir.path().join("fastskill"));
+        assert_eq!(telemetry.settings(), TelemetrySettings::default());
+        assert!(telemetry.pending().is_none());
+
+        telemetry
+            .save_settings(&TelemetrySettings {
+                enabled: true,
+                endpoint: Some("https://telemetry.example.com/v1".to_string()),
+            })
+            .unwrap();
+        assert!(telemetry.settings().enabled);
+
+        telemetry.record("install", None).unwrap();
+        telemetry.record("install", Some("config")).unwrap();
+        telemetry.record("repos/skills", None).unwrap();
+        let batch = telemetry.pending().unwrap();
+        assert_eq!(batch.commands["install"], 2);
+        assert_eq!(batch.commands["repos/skills"], 1);
+        assert_eq!(batch.errors["config"], 1);
+
+        telemetry.clear().unwrap();
+        assert!(telemetry.pending().is_none());
+    }
+
+    #[test]
+    fn test_command_path_only_returns_registered_paths() {
+        let commands: HashSet<String> = ["install", "repos/skills", "read"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let args = |list: &[&str]| list.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            command_path(&args(&["repos", "skills", "--scope", "acme"]), &commands).as_deref(),
+            Some("repos/skills")
+        );
+        assert_eq!(
+            command_path(&args(&["read", "private-skill-id"]), &commands).as_deref(),
+            Some("read")
+        );
+        assert!(command_path(&args(&["my-secret-skill"]), &commands).is_none());
+    }
+
+    #[test]
+    fn test_error_category() {
+        assert_eq!(error_category(&"Configuration error: missing"), "config");
+        assert_eq!(error_category(&"something unexpected"), "other");
+    }
+}
@@ -1,9 +1,14 @@
 //! Utility functions for CLI operations
 
+pub mod audit_utils;
+pub mod change_plan;
+pub mod download_progress;
+pub mod install_progress;
 pub mod install_utils;
 pub mod manifest_utils;
 pub mod messages;
 pub mod reindex_utils;
+pub mod storage_lock;
 
 use crate::config::get_skill_search_locations_for_display;
 use crate::error::{CliError, CliResult, SkillNotFoundMessage};
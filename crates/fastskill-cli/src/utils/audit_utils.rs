@@ -0,0 +1,111 @@
+//! Audit records for CLI commands that change skills
+//!
+//! Commands append to the same hash-chained log `fastskill serve` writes
+//! (`<skills dir>/.fastskill/audit.jsonl`), as actor `user:<login>`. Writing
+//! the record never fails the command; a log that cannot be written is a
+//! warning.
+
+use fastskill_core::core::audit::{AuditDetails, AuditEntry, AuditLog};
+use fastskill_core::core::install::AddOutcome;
+use fastskill_core::core::origin::Origin;
+use std::path::Path;
+
+/// `user:<login>` from `USER` (or `USERNAME` on Windows), else `user:unknown`.
+pub fn cli_actor() -> String {
+    let login = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .ok()
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+    format!("user:{}", login.trim())
+}
+
+/// Where a skill came from, as recorded in the audit log.
+pub fn origin_source(origin: &Origin) -> String {
+    match origin {
+        Origin::Git { url, .. } | Origin::ZipUrl { url } => url.clone(),
+        Origin::Local { path, .. } => path.display().to_string(),
+        Origin::Pack { path, skill } => format!("{}#{}", path.display(), skill),
+        Origin::Repository { repo, skill, .. } => format!("{}:{}", repo, skill),
+    }
+}
+
+/// Details of a skill installed through the core install seam.
+pub fn added(outcome: &AddOutcome) -> AuditDetails {
+    AuditDetails {
+        target: Some(outcome.id.clone()),
+        version: Some(outcome.resolved.version.clone()),
+        source: Some(origin_source(&outcome.origin)),
+    }
+}
+
+/// Record the outcome of `command` (e.g. `add`) as `action` (e.g. `skill.add`).
+pub fn record<T, E>(
+    skills_dir: &Path,
+    action: &str,
+    command: &str,
+    details: AuditDetails,
+    result: &Result<T, E>,
+) {
+    let entry =
+        AuditEntry::cli(&cli_actor(), action, command, result.is_ok()).with_details(details);
+    let log = AuditLog::new(AuditLog::default_path(skills_dir));
+    if let Err(e) = log.append(&entry) {
+        tracing::warn!(
+            "Failed to write audit entry to {}: {}",
+            log.path().display(),
+            e
+        );
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::error::{CliError, CliResult};
+    use fastskill_core::core::audit::AuditFilter;
+
+    #[test]
+    fn test_record_appends_chained_cli_entries() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let details = AuditDetails {
+            target: Some("pdf".to_string()),
+            version: Some("1.0.0".to_string()),
+            source: Some("acme:pdf".to_string()),
+        };
+        record(temp_dir.path(), "skill.add", "add", details, &Ok(()));
+        let failed: CliResult<()> = Err(CliError::Validation("nope".to_string()));
+        record(
+            temp_dir.path(),
+            "skill.remove",
+            "remove",
+            AuditDetails::default(),
+            &failed,
+        );
+
+        let log = AuditLog::new(AuditLog::default_path(temp_dir.path()));
+        let entries = log.read(&AuditFilter::default()).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].method, AuditEntry::CLI_METHOD);
+        assert_eq!(entries[0].path, "fastskill add");
+        assert_eq!(entries[0].source.as_deref(), Some("acme:pdf"));
+        assert!(entries[0].actor.starts_with("user:"));
+        assert_eq!(entries[1].outcome(), "failed");
+        assert!(log.verify().unwrap().is_intact());
+    }
+
+    #[test]
+    fn test_origin_source() {
+        let origin = Origin::Repository {
+            repo: "acme".to_string(),
+            skill: "pdf".to_string(),
+            version: None,
+        };
+        assert_eq!(origin_source(&origin), "acme:pdf");
+        let origin = Origin::ZipUrl {
+            url: "https://example.com/pdf.zip".to_string(),
+        };
+        assert_eq!(origin_source(&origin), "https://example.com/pdf.zip");
+    }
+}
@@ -0,0 +1,305 @@
+//! Change plans printed by `--dry-run`
+//!
+//! install, update, remove and add resolve everything they would change, then
+//! hand the result here instead of touching the skills directory,
+//! skill-project.toml or skills.lock. The plan prints as text, or as JSON with
+//! `--json`.
+
+use crate::utils::messages;
+use fastskill_core::core::version::compare_versions;
+use serde::Serialize;
+use std::cmp::Ordering;
+
+/// What a command would do to one skill
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeAction {
+    Add,
+    Upgrade,
+    Downgrade,
+    /// Fetched again; the resulting version is only known after the fetch
+    /// (git, local and zip-url origins) or is not a semantic version
+    Reinstall,
+    Unchanged,
+    Remove,
+}
+
+impl ChangeAction {
+    fn label(self) -> &'static str {
+        match self {
+            ChangeAction::Add => "add",
+            ChangeAction::Upgrade => "upgrade",
+            ChangeAction::Downgrade => "downgrade",
+            ChangeAction::Reinstall => "reinstall",
+            ChangeAction::Unchanged => "unchanged",
+            ChangeAction::Remove => "remove",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlannedChange {
+    pub id: String,
+    pub action: ChangeAction,
+    /// Version in skills.lock before the change
+    pub from: Option<String>,
+    /// Version after the change, when known without fetching
+    pub to: Option<String>,
+    /// Bytes that would be downloaded, when known without downloading
+    pub download_size: Option<u64>,
+}
+
+impl PlannedChange {
+    /// Install or update `id` from `from` (its skills.lock version, if any) to
+    /// `to` (the resolved version, if known).
+    pub fn install(id: impl Into<String>, from: Option<String>, to: Option<String>) -> Self {
+        let action = match (&from, &to) {
+            (None, _) => ChangeAction::Add,
+            (Some(_), None) => ChangeAction::Reinstall,
+            (Some(from), Some(to)) => match compare_versions(to, from) {
+                Ok(Ordering::Greater) => ChangeAction::Upgrade,
+                Ok(Ordering::Less) => ChangeAction::Downgrade,
+                Ok(Ordering::Equal) => ChangeAction::Unchanged,
+                Err(_) if from == to => ChangeAction::Unchanged,
+                Err(_) => ChangeAction::Reinstall,
+            },
+        };
+        Self {
+            id: id.into(),
+            action,
+            from,
+            to,
+            download_size: None,
+        }
+    }
+
+    pub fn remove(id: impl Into<String>, from: Option<String>) -> Self {
+        Self {
+            id: id.into(),
+            action: ChangeAction::Remove,
+            from,
+            to: None,
+            download_size: None,
+        }
+    }
+
+    pub fn with_download_size(mut self, size: Option<u64>) -> Self {
+        self.download_size = size;
+        self
+    }
+
+    /// Line of the skills.lock diff for this change; `None` when the entry
+    /// stays as it is.
+    fn lockfile_line(&self) -> Option<String> {
+        let version = |v: &Option<String>| v.clone().unwrap_or_else(|| "?".to_string());
+        match self.action {
+            ChangeAction::Add => Some(format!("+ {} {}", self.id, version(&self.to))),
+            ChangeAction::Remove => Some(format!("- {} {}", self.id, version(&self.from))),
+            ChangeAction::Unchanged => None,
+            ChangeAction::Upgrade | ChangeAction::Downgrade | ChangeAction::Reinstall => {
+                Some(format!(
+                    "~ {} {} -> {}",
+                    self.id,
+                    version(&self.from),
+                    version(&self.to)
+                ))
+            }
+        }
+    }
+}
+
+/// Everything a command would change, as printed by `--dry-run`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChangePlan {
+    pub command: String,
+    /// Lock file the changes apply to (skills.lock or global-skills.lock)
+    pub lockfile: String,
+    pub changes: Vec<PlannedChange>,
+    /// Sum of the known download sizes
+    pub download_size: u64,
+    /// Number of downloads whose size is not known up front
+    pub unknown_sizes: usize,
+    pub lockfile_diff: Vec<String>,
+}
+
+impl ChangePlan {
+    pub fn new(command: &str, lockfile: &str, changes: Vec<PlannedChange>) -> Self {
+        let downloads = changes
+            .iter()
+            .filter(|c| !matches!(c.action, ChangeAction::Remove | ChangeAction::Unchanged));
+        let download_size = downloads.clone().filter_map(|c| c.download_size).sum();
+        let unknown_sizes = downloads.filter(|c| c.download_size.is_none()).count();
+        let lockfile_diff = changes
+            .iter()
+            .filter_map(PlannedChange::lockfile_line)
+            .collect();
+        Self {
+            command: command.to_string(),
+            lockfile: lockfile.to_string(),
+            changes,
+            download_size,
+            unknown_sizes,
+            lockfile_diff,
+        }
+    }
+
+    /// Print the plan as pretty JSON (`json`) or as text.
+    pub fn print(&self, json: bool) {
+        if json {
+            println!("{}", serde_json::to_string_pretty(self).unwrap_or_default());
+            return;
+        }
+        print!("{}", self.render());
+    }
+
+    fn render(&self) -> String {
+        let mut out = format!(
+            "Dry run: {} would make {} change(s); nothing was written\n\n",
+            self.command,
+            self.changes
+                .iter()
+                .filter(|c| c.action != ChangeAction::Unchanged)
+                .count()
+        );
+        for change in &self.changes {
+            let version = match (&change.from, &change.to, change.action) {
+                (Some(from), _, ChangeAction::Remove) => from.clone(),
+                (Some(from), Some(to), _) if from != to => format!("{} -> {}", from, to),
+                (_, Some(to), _) => to.clone(),
+                (Some(from), None, _) => format!("{} -> ?", from),
+                (None, None, _) => String::new(),
+            };
+            let size = match (change.action, change.download_size) {
+                (ChangeAction::Remove | ChangeAction::Unchanged, _) => String::new(),
+                (_, Some(bytes)) => format!(" ({})", format_size(bytes)),
+                (_, None) => " (size unknown)".to_string(),
+            };
+            out.push_str(
+                format!(
+                    "  {:<10} {} {}{}",
+                    change.action.label(),
+                    change.id,
+                    version,
+                    size
+                )
+                .trim_end(),
+            );
+            out.push('\n');
+        }
+        out.push_str(&format!(
+            "\nDownload size: {}",
+            format_size(self.download_size)
+        ));
+        if self.unknown_sizes > 0 {
+            out.push_str(&format!(
+                " (plus {} download(s) of unknown size)",
+                self.unknown_sizes
+            ));
+        }
+        out.push('\n');
+        if self.lockfile_diff.is_empty() {
+            out.push_str(&format!("\n{}: no changes\n", self.lockfile));
+        } else {
+            out.push_str(&format!("\n{}:\n", self.lockfile));
+            for line in &self.lockfile_diff {
+                out.push_str(&format!("  {}\n", line));
+            }
+        }
+        out
+    }
+}
+
+/// Print a note about a skill the plan could not cover, to stderr so
+/// `--json` output stays parseable.
+pub fn warn(msg: &str) {
+    eprintln!("  {}", messages::warning(msg));
+}
+
+pub(crate) fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_change_classification() {
+        let action = |from: Option<&str>, to: Option<&str>| {
+            PlannedChange::install("s", from.map(String::from), to.map(String::from)).action
+        };
+        assert_eq!(action(None, Some("1.0.0")), ChangeAction::Add);
+        assert_eq!(action(None, None), ChangeAction::Add);
+        assert_eq!(action(Some("1.0.0"), Some("1.2.0")), ChangeAction::Upgrade);
+        assert_eq!(
+            action(Some("1.10.0"), Some("1.9.0")),
+            ChangeAction::Downgrade
+        );
+        assert_eq!(
+            action(Some("1.0.0"), Some("1.0.0")),
+            ChangeAction::Unchanged
+        );
+        assert_eq!(action(Some("1.0.0"), None), ChangeAction::Reinstall);
+        assert_eq!(action(Some("main"), Some("dev")), ChangeAction::Reinstall);
+    }
+
+    #[test]
+    fn test_plan_totals_and_lockfile_diff() {
+        let plan = ChangePlan::new(
+            "install",
+            "skills.lock",
+            vec![
+                PlannedChange::install("a", None, Some("1.0.0".into()))
+                    .with_download_size(Some(2048)),
+                PlannedChange::install("b", Some("1.0.0".into()), Some("2.0.0".into())),
+                PlannedChange::install("c", Some("1.0.0".into()), Some("1.0.0".into()))
+                    .with_download_size(Some(99)),
+                PlannedChange::remove("d", Some("0.3.0".into())),
+            ],
+        );
+        assert_eq!(plan.download_size, 2048);
+        assert_eq!(plan.unknown_sizes, 1);
+        assert_eq!(
+            plan.lockfile_diff,
+            ["+ a 1.0.0", "~ b 1.0.0 -> 2.0.0", "- d 0.3.0"]
+        );
+
+        let text = plan.render();
+        assert!(text.contains("would make 3 change(s)"), "{}", text);
+        assert!(text.contains("add        a 1.0.0 (2.0 KB)"), "{}", text);
+        assert!(
+            text.contains("upgrade    b 1.0.0 -> 2.0.0 (size unknown)"),
+            "{}",
+            text
+        );
+        assert!(text.contains("remove     d 0.3.0\n"), "{}", text);
+        assert!(text.contains("\nskills.lock:\n  + a 1.0.0\n"), "{}", text);
+        assert!(text.contains("Download size: 2.0 KB (plus 1 download(s) of unknown size)"));
+
+        let json = serde_json::to_value(&plan).unwrap();
+        assert_eq!(json["changes"][0]["action"], "add");
+        assert_eq!(json["changes"][0]["download_size"], 2048);
+        assert_eq!(json["changes"][3]["from"], "0.3.0");
+        assert!(json["changes"][3]["to"].is_null());
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MB");
+    }
+}
@@ -0,0 +1,78 @@
+//! Download progress for zip-url installs, drawn on stderr
+//!
+//! On a terminal the line is redrawn in place as bytes arrive; otherwise one
+//! line is printed per finished download. Nothing is printed under `--quiet`.
+
+use crate::utils::change_plan::format_size;
+use crate::utils::messages;
+use fastskill_core::core::download::{DownloadObserver, DownloadProgress};
+use std::io::{IsTerminal, Write};
+use std::sync::Arc;
+
+/// Observer to hand to [`fastskill_core::core::download::DownloadManager::with_progress`]
+pub fn observer() -> DownloadObserver {
+    let interactive = std::io::stderr().is_terminal();
+    Arc::new(move |progress: DownloadProgress| {
+        if messages::is_quiet() {
+            return;
+        }
+        let line = render(&progress);
+        let mut stderr = std::io::stderr().lock();
+        let _ = if interactive {
+            // Redraw in place; the finished line stays
+            let end = if progress.finished { "\n" } else { "" };
+            write!(stderr, "\r{}\x1b[K{}", line, end)
+        } else if progress.finished {
+            writeln!(stderr, "{}", line)
+        } else {
+            Ok(())
+        };
+    })
+}
+
+fn render(progress: &DownloadProgress) -> String {
+    let name = progress
+        .url
+        .rsplit('/')
+        .find(|segment| !segment.is_empty())
+        .unwrap_or(&progress.url);
+    let amount = match progress.total {
+        Some(total) if total > 0 => format!(
+            "{} / {} ({}%)",
+            format_size(progress.downloaded),
+            format_size(total),
+            progress.downloaded * 100 / total
+        ),
+        _ => format_size(progress.downloaded),
+    };
+    let resumed = if progress.resumed_from > 0 {
+        format!(", resumed at {}", format_size(progress.resumed_from))
+    } else {
+        String::new()
+    };
+    format!("  Downloading {} {}{}", name, amount, resumed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let progress = |downloaded, total, resumed_from| DownloadProgress {
+            url: "https://example.com/skills/pdf.zip".to_string(),
+            downloaded,
+            total,
+            resumed_from,
+            finished: false,
+        };
+        assert_eq!(
+            render(&progress(512 * 1024, Some(2048 * 1024), 0)),
+            "  Downloading pdf.zip 512.0 KB / 2.0 MB (25%)"
+        );
+        assert_eq!(
+            render(&progress(3000, None, 1024)),
+            "  Downloading pdf.zip 2.9 KB, resumed at 1.0 KB"
+        );
+    }
+}
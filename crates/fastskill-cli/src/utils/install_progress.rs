@@ -0,0 +1,338 @@
+//! Install progress, reported on stderr
+//!
+//! `install` emits an [`InstallEvent`] as each skill moves through the
+//! pipeline (resolve, download, extract, validate, register) and once more
+//! when the installed skills are indexed. [`InstallProgress`] renders them:
+//! on a terminal as an overall bar plus one spinner per skill in flight,
+//! otherwise as the plain per-skill lines, or with `--json-progress` as one
+//! JSON object per line for CI. Under `--quiet` only failures are printed.
+
+use crate::utils::messages;
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{IsTerminal, Write};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Where a skill is in the install pipeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallStage {
+    /// Working out where to fetch the skill from
+    Resolve,
+    /// Cloning or downloading it
+    Download,
+    /// Unpacking an archive, pack or vendored copy
+    Extract,
+    /// Checking SKILL.md and the skill layout
+    Validate,
+    /// Copying it into the skills directory and registering it
+    Register,
+}
+
+impl fmt::Display for InstallStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            InstallStage::Resolve => "resolving",
+            InstallStage::Download => "downloading",
+            InstallStage::Extract => "extracting",
+            InstallStage::Validate => "validating",
+            InstallStage::Register => "registering",
+        })
+    }
+}
+
+/// Called as an install reaches each stage
+pub type StageObserver<'a> = &'a (dyn Fn(InstallStage) + Send + Sync);
+
+/// One step of an install, as printed by `--json-progress`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum InstallEvent {
+    /// Resolution found `skills` more skills to install (once per round of
+    /// transitive dependencies)
+    Resolved { skills: usize },
+    /// `skill` entered `stage`
+    Stage { skill: String, stage: InstallStage },
+    Installed {
+        skill: String,
+        version: String,
+        depth: u32,
+    },
+    Failed {
+        skill: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        required_by: Option<String>,
+        error: String,
+    },
+    /// Not started because an earlier install failed (`--fail-fast`)
+    Skipped { skill: String },
+    /// Every install has finished
+    Finished {
+        installed: usize,
+        failed: usize,
+        skipped: usize,
+    },
+    /// The installed skills are being added to the search index
+    Indexing,
+}
+
+/// How [`InstallProgress`] renders events
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressMode {
+    /// Overall bar and per-skill spinners (stderr is a terminal)
+    Bars,
+    /// One status line per finished skill
+    Plain,
+    /// Failures only (`--quiet`)
+    Quiet,
+    /// One JSON event per line (`--json-progress`)
+    Json,
+}
+
+impl ProgressMode {
+    /// Mode for this process: `--json-progress`, then `--quiet`, then bars
+    /// when stderr is a terminal and `FASTSKILL_NO_PROGRESS` is not set
+    pub fn detect(json_progress: bool) -> Self {
+        if json_progress {
+            ProgressMode::Json
+        } else if messages::is_quiet() {
+            ProgressMode::Quiet
+        } else if std::env::var("FASTSKILL_NO_PROGRESS").is_err() && std::io::stderr().is_terminal()
+        {
+            ProgressMode::Bars
+        } else {
+            ProgressMode::Plain
+        }
+    }
+}
+
+/// Live bars for [`ProgressMode::Bars`]
+struct Bars {
+    multi: MultiProgress,
+    overall: ProgressBar,
+    skills: Mutex<HashMap<String, ProgressBar>>,
+}
+
+impl Bars {
+    fn new() -> Self {
+        let multi = MultiProgress::with_draw_target(ProgressDrawTarget::stderr());
+        let overall = multi.add(ProgressBar::new(0));
+        overall.set_style(
+            ProgressStyle::with_template("{elapsed:>4} [{bar:30}] {pos}/{len} skills {msg}")
+                .unwrap_or_else(|_| ProgressStyle::default_bar())
+                .progress_chars("=> "),
+        );
+        Self {
+            multi,
+            overall,
+            skills: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn stage(&self, skill: &str, stage: InstallStage) {
+        let mut skills = self.skills.lock().unwrap_or_else(|p| p.into_inner());
+        let spinner = skills.entry(skill.to_string()).or_insert_with(|| {
+            let spinner = self.multi.add(ProgressBar::new_spinner());
+            spinner.set_style(
+                ProgressStyle::with_template("  {spinner} {prefix} {msg}")
+                    .unwrap_or_else(|_| ProgressStyle::default_spinner()),
+            );
+            spinner.set_prefix(skill.to_string());
+            spinner.enable_steady_tick(Duration::from_millis(100));
+            spinner
+        });
+        spinner.set_message(stage.to_string());
+    }
+
+    /// Drop `skill`'s spinner and count it as done
+    fn done(&self, skill: &str) {
+        let spinner = self
+            .skills
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .remove(skill);
+        if let Some(spinner) = spinner {
+            spinner.finish_and_clear();
+            self.multi.remove(&spinner);
+        }
+        self.overall.inc(1);
+    }
+}
+
+impl Drop for Bars {
+    /// Clear what is left on screen when an install stops early
+    fn drop(&mut self) {
+        let _ = self.multi.clear();
+    }
+}
+
+/// Renders install events in one [`ProgressMode`]
+pub struct InstallProgress {
+    mode: ProgressMode,
+    bars: Option<Bars>,
+}
+
+impl InstallProgress {
+    pub fn new(mode: ProgressMode) -> Self {
+        let bars = (mode == ProgressMode::Bars).then(Bars::new);
+        Self { mode, bars }
+    }
+
+    /// Stage observer for `skill`, to hand to the install functions
+    pub fn observer<'a>(&'a self, skill: &'a str) -> impl Fn(InstallStage) + Send + Sync + 'a {
+        move |stage| {
+            self.emit(InstallEvent::Stage {
+                skill: skill.to_string(),
+                stage,
+            })
+        }
+    }
+
+    /// Run `f` (which prints) with the bars hidden
+    pub fn suspend<R>(&self, f: impl FnOnce() -> R) -> R {
+        match &self.bars {
+            Some(bars) => bars.multi.suspend(f),
+            None => f(),
+        }
+    }
+
+    pub fn emit(&self, event: InstallEvent) {
+        match self.mode {
+            ProgressMode::Json => print_json(&event),
+            ProgressMode::Bars | ProgressMode::Plain | ProgressMode::Quiet => self.render(event),
+        }
+    }
+
+    fn render(&self, event: InstallEvent) {
+        match event {
+            InstallEvent::Resolved { skills } => {
+                if let Some(bars) = &self.bars {
+                    bars.overall.inc_length(skills as u64);
+                }
+            }
+            InstallEvent::Stage { skill, stage } => {
+                if let Some(bars) = &self.bars {
+                    bars.stage(&skill, stage);
+                }
+            }
+            InstallEvent::Installed { skill, depth, .. } => {
+                let line = format!(
+                    "  {}",
+                    messages::ok(&format!("Installed {} (depth {})", skill, depth))
+                );
+                match &self.bars {
+                    Some(bars) => {
+                        bars.done(&skill);
+                        let _ = bars.multi.println(line);
+                    }
+                    None => messages::status!("{}", line),
+                }
+            }
+            InstallEvent::Failed {
+                skill,
+                required_by,
+                error,
+            } => {
+                let context = match required_by {
+                    Some(parent) => format!(" (required by {})", parent),
+                    None => String::new(),
+                };
+                let line = format!(
+                    "  {}",
+                    messages::error(&format!(
+                        "Failed to install {}{}: {}",
+                        skill, context, error
+                    ))
+                );
+                match &self.bars {
+                    Some(bars) => {
+                        bars.done(&skill);
+                        bars.multi.suspend(|| eprintln!("{}", line));
+                    }
+                    None => eprintln!("{}", line),
+                }
+            }
+            InstallEvent::Skipped { skill } => {
+                let line = format!(
+                    "  {}",
+                    messages::info(&format!("Skipped {} (--fail-fast)", skill))
+                );
+                match &self.bars {
+                    Some(bars) => {
+                        bars.done(&skill);
+                        let _ = bars.multi.println(line);
+                    }
+                    None => messages::status!("{}", line),
+                }
+            }
+            InstallEvent::Finished { .. } => {
+                if let Some(bars) = &self.bars {
+                    bars.overall.finish_and_clear();
+                }
+            }
+            InstallEvent::Indexing => {
+                messages::status!("{}", messages::info("Indexing installed skills..."));
+            }
+        }
+    }
+}
+
+/// One event as a line of JSON on stderr
+fn print_json(event: &InstallEvent) {
+    match serde_json::to_string(event) {
+        Ok(line) => {
+            let mut stderr = std::io::stderr().lock();
+            let _ = writeln!(stderr, "{}", line);
+        }
+        Err(e) => tracing::debug!("Failed to encode install event: {}", e),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_json() {
+        let json = |event: &InstallEvent| serde_json::to_string(event).unwrap();
+        assert_eq!(
+            json(&InstallEvent::Stage {
+                skill: "pdf".to_string(),
+                stage: InstallStage::Download,
+            }),
+            r#"{"event":"stage","skill":"pdf","stage":"download"}"#
+        );
+        assert_eq!(
+            json(&InstallEvent::Failed {
+                skill: "pdf".to_string(),
+                required_by: None,
+                error: "not found".to_string(),
+            }),
+            r#"{"event":"failed","skill":"pdf","error":"not found"}"#
+        );
+        assert_eq!(
+            json(&InstallEvent::Finished {
+                installed: 2,
+                failed: 1,
+                skipped: 0,
+            }),
+            r#"{"event":"finished","installed":2,"failed":1,"skipped":0}"#
+        );
+        assert_eq!(json(&InstallEvent::Indexing), r#"{"event":"indexing"}"#);
+    }
+
+    #[test]
+    fn test_detect_json_first() {
+        assert_eq!(ProgressMode::detect(true), ProgressMode::Json);
+    }
+
+    #[test]
+    fn test_stage_display() {
+        assert_eq!(InstallStage::Extract.to_string(), "extracting");
+        assert_eq!(InstallStage::Register.to_string(), "registering");
+    }
+}
@@ -1,12 +1,15 @@
 //! Utilities for installing skills from various sources
 
 use crate::error::{CliError, CliResult};
+use crate::utils::install_progress::{InstallStage, StageObserver};
 use chrono::Utc;
+use fastskill_core::core::link::{link_dir, LinkMode};
 use fastskill_core::core::manifest::SkillEntry;
 use fastskill_core::core::origin::{GitRef, Origin};
 use fastskill_core::core::repository::RepositoryManager;
 use fastskill_core::core::skill_manager::SkillUpdate;
 use fastskill_core::core::sources::SourcesManager;
+use fastskill_core::core::vendor::VendorDir;
 use fastskill_core::{FastSkillService, SkillDefinition};
 use std::path::{Path, PathBuf};
 
@@ -66,44 +69,25 @@ pub(crate) fn safe_subdir_join(root: &Path, subdir: &Path) -> CliResult<PathBuf>
     Ok(joined)
 }
 
-/// Create a symlink for editable installs
-/// This is platform-specific: Unix uses tokio::fs::symlink, Windows uses std::os::windows::fs::symlink_dir
-/// On unsupported platforms, returns EditableNotSupported error
-async fn create_editable_symlink(src: &Path, dst: &Path) -> CliResult<()> {
-    #[cfg(unix)]
-    {
-        tokio::fs::symlink(src, dst).await.map_err(CliError::Io)
-    }
-
-    #[cfg(windows)]
-    {
-        use std::os::windows::fs::symlink_dir;
-        symlink_dir(src, dst).map_err(|e| {
-            CliError::Io(std::io::Error::new(
-                e.kind(),
-                format!(
-                    "Windows symlink permission denied: {}. Enable Developer Mode \
-                     (Settings → For developers) or run as Administrator.",
-                    e
-                ),
-            ))
-        })
-    }
-
-    #[cfg(all(not(unix), not(windows)))]
-    {
-        Err(CliError::Io(std::io::Error::other(
-            "Editable installations are not supported on this platform. Use a Unix-based system or Docker.",
-        )))
-    }
-}
-
 /// Install a single skill from a SkillEntry
 pub async fn install_skill_from_entry(
     service: &FastSkillService,
     entry: SkillEntry,
     sources_manager: Option<&SourcesManager>,
 ) -> CliResult<SkillDefinition> {
+    install_skill_from_entry_with_progress(service, entry, sources_manager, &|_: InstallStage| {})
+        .await
+}
+
+/// [`install_skill_from_entry`], calling `on_stage` as the install moves
+/// through its stages
+pub async fn install_skill_from_entry_with_progress(
+    service: &FastSkillService,
+    entry: SkillEntry,
+    sources_manager: Option<&SourcesManager>,
+    on_stage: StageObserver<'_>,
+) -> CliResult<SkillDefinition> {
+    on_stage(InstallStage::Resolve);
     match &entry.origin {
         Origin::Git { url, r#ref, subdir } => {
             let (branch, tag) = match r#ref {
@@ -121,10 +105,13 @@ pub async fn install_skill_from_entry(
                     ));
                 }
             };
-            install_from_git(service, url, branch, tag, subdir.as_ref()).await
+            install_from_git(service, url, branch, tag, subdir.as_ref(), on_stage).await
+        }
+        Origin::Local { path, editable } => {
+            install_from_local(service, path, *editable, on_stage).await
         }
-        Origin::Local { path, editable } => install_from_local(service, path, *editable).await,
-        Origin::ZipUrl { url } => install_from_zip_url(service, url).await,
+        Origin::ZipUrl { url } => install_from_zip_url(service, url, on_stage).await,
+        Origin::Pack { path, skill } => install_from_pack(service, path, skill, on_stage).await,
         Origin::Repository {
             repo,
             skill,
@@ -132,7 +119,15 @@ pub async fn install_skill_from_entry(
         } => {
             if let Some(sources_mgr) = sources_manager {
                 let version_str = version.as_ref().map(|v| v.to_string());
-                install_from_source(service, sources_mgr, repo, skill, version_str.as_deref()).await
+                install_from_source(
+                    service,
+                    sources_mgr,
+                    repo,
+                    skill,
+                    version_str.as_deref(),
+                    on_stage,
+                )
+                .await
             } else {
                 Err(CliError::Config(
                     "Sources manager required for source-based installation".to_string(),
@@ -262,6 +257,7 @@ async fn install_from_git(
     branch: Option<&str>,
     tag: Option<&str>,
     subdir: Option<&PathBuf>,
+    on_stage: StageObserver<'_>,
 ) -> CliResult<SkillDefinition> {
     use crate::commands::add::create_skill_from_path;
 
@@ -272,10 +268,13 @@ async fn install_from_git(
         subdir,
     };
 
+    on_stage(InstallStage::Download);
     let (_temp_dir, skill_path) = clone_and_find_skill(&config).await?;
     let origin = build_git_origin(url, branch, tag, subdir);
+    on_stage(InstallStage::Validate);
     let mut skill_def = create_skill_from_path(&skill_path, origin, "git", false)?;
 
+    on_stage(InstallStage::Register);
     copy_skill_to_storage(service, &skill_path, &mut skill_def).await?;
 
     skill_def.fetched_at = Some(Utc::now());
@@ -285,33 +284,53 @@ async fn install_from_git(
     Ok(skill_def)
 }
 
-/// Setup skill in storage directory (editable or copy)
+/// Setup skill in storage directory: linked per `link_mode` when editable,
+/// else copied
 pub(crate) async fn setup_skill_in_storage(
     skill_path: &Path,
     skill_storage_dir: &Path,
     editable: bool,
+    link_mode: LinkMode,
 ) -> CliResult<()> {
     use crate::commands::add::copy_dir_recursive;
 
+    if editable {
+        let used = link_dir(skill_path, skill_storage_dir, link_mode)
+            .await
+            .map_err(CliError::Service)?;
+        report_link_fallback(link_mode, used);
+        return Ok(());
+    }
+
     if skill_storage_dir.exists() {
         tokio::fs::remove_dir_all(skill_storage_dir)
             .await
             .map_err(CliError::Io)?;
     }
-
-    if editable {
-        create_editable_symlink(skill_path, skill_storage_dir).await?;
-    } else {
-        copy_dir_recursive(skill_path, skill_storage_dir).await?;
-    }
+    copy_dir_recursive(skill_path, skill_storage_dir).await?;
 
     Ok(())
 }
 
+/// Tell the user when `--link-mode auto` could not link an editable skill and
+/// copied it instead, since later edits to new files will not show up.
+pub(crate) fn report_link_fallback(requested: LinkMode, used: LinkMode) {
+    if requested == LinkMode::Auto && used == LinkMode::Copy {
+        eprintln!(
+            "{}",
+            crate::utils::messages::warning(
+                "Could not symlink the editable skill; installed it as a copy. Re-install \
+                 to pick up later edits, or enable symlinks (on Windows: Developer Mode)."
+            )
+        );
+    }
+}
+
 async fn install_from_local(
     service: &FastSkillService,
     path: &PathBuf,
     editable: bool,
+    on_stage: StageObserver<'_>,
 ) -> CliResult<SkillDefinition> {
     use crate::commands::add::create_skill_from_path;
 
@@ -331,13 +350,21 @@ async fn install_from_local(
         path: skill_path.clone(),
         editable,
     };
+    on_stage(InstallStage::Validate);
     let mut skill_def = create_skill_from_path(&skill_path, origin, "local", editable)?;
     let skill_storage_dir = service
         .config()
         .skill_storage_path
         .join(skill_def.id.as_str());
 
-    setup_skill_in_storage(&skill_path, &skill_storage_dir, editable).await?;
+    on_stage(InstallStage::Register);
+    setup_skill_in_storage(
+        &skill_path,
+        &skill_storage_dir,
+        editable,
+        service.config().link_mode,
+    )
+    .await?;
 
     skill_def.skill_file = skill_storage_dir.join("SKILL.md");
     skill_def.fetched_at = Some(Utc::now());
@@ -367,9 +394,13 @@ pub(crate) fn extract_zip(zip_path: &std::path::Path, dst: &std::path::Path) ->
 /// Download an archive from `url` into a fresh temp dir and extract it with the
 /// SEC-3-capped `ZipHandler`, returning the temp dir (kept alive by the caller) and
 /// the directory that contains `SKILL.md`.
-async fn download_and_extract_zip(url: &str) -> CliResult<(tempfile::TempDir, PathBuf)> {
+async fn download_and_extract_zip(
+    url: &str,
+    on_stage: StageObserver<'_>,
+) -> CliResult<(tempfile::TempDir, PathBuf)> {
     use fastskill_core::storage::git::validate_cloned_skill;
 
+    on_stage(InstallStage::Download);
     let response = reqwest::get(url)
         .await
         .map_err(|e| CliError::InvalidSource(format!("Failed to download '{}': {}", url, e)))?
@@ -393,6 +424,7 @@ async fn download_and_extract_zip(url: &str) -> CliResult<(tempfile::TempDir, Pa
         .await
         .map_err(CliError::Io)?;
 
+    on_stage(InstallStage::Extract);
     extract_zip(&zip_path, &extract_path)?;
 
     let skill_path = validate_cloned_skill(&extract_path)
@@ -401,18 +433,130 @@ async fn download_and_extract_zip(url: &str) -> CliResult<(tempfile::TempDir, Pa
     Ok((temp_dir, skill_path))
 }
 
+async fn install_from_pack(
+    service: &FastSkillService,
+    path: &PathBuf,
+    skill: &str,
+    on_stage: StageObserver<'_>,
+) -> CliResult<SkillDefinition> {
+    use crate::commands::add::create_skill_from_path;
+    use fastskill_core::core::pack::extract_pack;
+
+    let pack_path = if path.is_absolute() {
+        path.clone()
+    } else {
+        std::env::current_dir().map_err(CliError::Io)?.join(path)
+    };
+    if !pack_path.is_file() {
+        return Err(CliError::InvalidSource(format!(
+            "Skill pack does not exist: {}",
+            pack_path.display()
+        )));
+    }
+
+    on_stage(InstallStage::Extract);
+    let extracted = extract_pack(&pack_path)?;
+    let skill_path = extracted.skill_dir(skill)?;
+    let origin = Origin::Pack {
+        path: pack_path,
+        skill: skill.to_string(),
+    };
+    on_stage(InstallStage::Validate);
+    let mut skill_def = create_skill_from_path(&skill_path, origin, "pack", false)?;
+    on_stage(InstallStage::Register);
+    copy_skill_to_storage(service, &skill_path, &mut skill_def).await?;
+
+    skill_def.fetched_at = Some(Utc::now());
+
+    register_skill(service, &skill_def, base_skill_update(&skill_def)).await?;
+
+    Ok(skill_def)
+}
+
+/// Install `entry` from the vendor directory instead of its origin. Repository
+/// skills take the newest vendored version their constraint allows; other
+/// origins take `locked_version` (from skills.lock) or the newest vendored
+/// one. The entry's origin is kept, so skills.lock does not change.
+pub async fn install_from_vendor(
+    service: &FastSkillService,
+    vendor: &VendorDir,
+    entry: &SkillEntry,
+    locked_version: Option<&str>,
+) -> CliResult<SkillDefinition> {
+    install_from_vendor_with_progress(
+        service,
+        vendor,
+        entry,
+        locked_version,
+        &|_: InstallStage| {},
+    )
+    .await
+}
+
+/// [`install_from_vendor`], calling `on_stage` as the install moves through
+/// its stages
+pub async fn install_from_vendor_with_progress(
+    service: &FastSkillService,
+    vendor: &VendorDir,
+    entry: &SkillEntry,
+    locked_version: Option<&str>,
+    on_stage: StageObserver<'_>,
+) -> CliResult<SkillDefinition> {
+    use crate::commands::add::create_skill_from_path;
+    use fastskill_core::core::version::sort_versions_desc;
+
+    let vendored = match &entry.origin {
+        Origin::Repository {
+            version: Some(constraint),
+            ..
+        } => {
+            let mut versions = vendor.versions(&entry.id);
+            sort_versions_desc(&mut versions);
+            versions
+                .into_iter()
+                .find(|v| constraint.satisfies(v).unwrap_or(false))
+                .and_then(|v| vendor.find(&entry.id, Some(&v)))
+        }
+        _ => vendor.find(&entry.id, locked_version),
+    }
+    .ok_or_else(|| {
+        CliError::Config(format!(
+            "{} is not in {}; run 'fastskill vendor' while online",
+            entry.id,
+            vendor.root().display()
+        ))
+    })?;
+
+    on_stage(InstallStage::Extract);
+    let extracted = vendor.extract(vendored)?;
+    on_stage(InstallStage::Validate);
+    let mut skill_def =
+        create_skill_from_path(&extracted.path, entry.origin.clone(), "vendor", false)?;
+    on_stage(InstallStage::Register);
+    copy_skill_to_storage(service, &extracted.path, &mut skill_def).await?;
+
+    skill_def.fetched_at = Some(Utc::now());
+
+    register_skill(service, &skill_def, base_skill_update(&skill_def)).await?;
+
+    Ok(skill_def)
+}
+
 async fn install_from_zip_url(
     service: &FastSkillService,
     base_url: &str,
+    on_stage: StageObserver<'_>,
 ) -> CliResult<SkillDefinition> {
     use crate::commands::add::create_skill_from_path;
 
-    let (_temp_dir, skill_path) = download_and_extract_zip(base_url).await?;
+    let (_temp_dir, skill_path) = download_and_extract_zip(base_url, on_stage).await?;
 
     let origin = Origin::ZipUrl {
         url: base_url.to_string(),
     };
+    on_stage(InstallStage::Validate);
     let mut skill_def = create_skill_from_path(&skill_path, origin, "zip", false)?;
+    on_stage(InstallStage::Register);
     copy_skill_to_storage(service, &skill_path, &mut skill_def).await?;
 
     skill_def.fetched_at = Some(Utc::now());
@@ -422,8 +566,45 @@ async fn install_from_zip_url(
     Ok(skill_def)
 }
 
+/// Check the peer dependencies declared by installed skills against the
+/// installed versions. Violations are printed as warnings, or returned as an
+/// error when `strict` is set.
+pub async fn check_installed_peers(service: &FastSkillService, strict: bool) -> CliResult<()> {
+    use crate::utils::messages;
+    use fastskill_core::core::peers::check_peer_dependencies;
+
+    let installed: std::collections::HashMap<String, String> = service
+        .skill_manager()
+        .list_skills()
+        .await
+        .map_err(CliError::Service)?
+        .into_iter()
+        .map(|s| (s.id.to_string(), s.version))
+        .collect();
+    let violations = check_peer_dependencies(&service.config().skill_storage_path, &installed);
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    for violation in &violations {
+        eprintln!(
+            "  {}",
+            messages::warning(&format!("Peer dependency: {}", violation.reason))
+        );
+    }
+    if strict {
+        return Err(CliError::Validation(format!(
+            "{} peer dependency violation(s) (--strict-peers)",
+            violations.len()
+        )));
+    }
+    Ok(())
+}
+
 /// Create and initialize package resolver
-async fn create_package_resolver() -> CliResult<fastskill_core::core::resolver::PackageResolver> {
+pub async fn create_package_resolver() -> CliResult<fastskill_core::core::resolver::PackageResolver>
+{
+    use fastskill_core::core::requirements::Environment;
     use fastskill_core::core::resolver::PackageResolver;
     use std::sync::Arc;
 
@@ -442,7 +623,12 @@ async fn create_package_resolver() -> CliResult<fastskill_core::core::resolver::
         ));
     };
 
-    let mut resolver = PackageResolver::new(sources_mgr.clone());
+    let environment = tokio::task::spawn_blocking(Environment::current)
+        .await
+        .map_err(|e| CliError::Config(format!("Failed to probe runtimes: {}", e)))?;
+    let mut resolver = PackageResolver::new(sources_mgr.clone())
+        .with_locked_versions(load_locked_versions()?)
+        .with_environment(environment.clone());
     resolver
         .build_index()
         .await
@@ -451,6 +637,30 @@ async fn create_package_resolver() -> CliResult<fastskill_core::core::resolver::
     Ok(resolver)
 }
 
+/// Versions pinned by the project's skills.lock, keyed by skill id. Empty when
+/// there is no project or no lock yet.
+fn load_locked_versions() -> CliResult<std::collections::HashMap<String, String>> {
+    use fastskill_core::core::lock::{project_lock_path, ProjectSkillsLock};
+
+    let current_dir = std::env::current_dir()
+        .map_err(|e| CliError::Config(format!("Failed to get current directory: {}", e)))?;
+    let project_file = fastskill_core::core::project::resolve_project_file(&current_dir);
+    if !project_file.found {
+        return Ok(Default::default());
+    }
+    let lock_path = project_lock_path(&project_file.path);
+    if !lock_path.exists() {
+        return Ok(Default::default());
+    }
+    let lock = ProjectSkillsLock::load_from_file(&lock_path)
+        .map_err(|e| CliError::Config(format!("Failed to load lock file: {}", e)))?;
+    Ok(lock
+        .skills
+        .into_iter()
+        .map(|entry| (entry.id, entry.resolved.version))
+        .collect())
+}
+
 /// Install skill based on resolved source configuration.
 ///
 /// Note: this mirrors the pre-Origin behavior of overwriting the installed skill's
@@ -461,6 +671,7 @@ async fn create_package_resolver() -> CliResult<fastskill_core::core::resolver::
 async fn install_from_resolved_source(
     service: &FastSkillService,
     source_config: &fastskill_core::core::sources::SourceConfig,
+    on_stage: StageObserver<'_>,
 ) -> CliResult<SkillDefinition> {
     match source_config {
         fastskill_core::core::sources::SourceConfig::Git {
@@ -468,12 +679,22 @@ async fn install_from_resolved_source(
             branch,
             tag,
             auth: _auth,
-        } => install_from_git(service, url, branch.as_deref(), tag.as_deref(), None).await,
+        } => {
+            install_from_git(
+                service,
+                url,
+                branch.as_deref(),
+                tag.as_deref(),
+                None,
+                on_stage,
+            )
+            .await
+        }
         fastskill_core::core::sources::SourceConfig::Local { path } => {
-            install_from_local(service, path, false).await
+            install_from_local(service, path, false, on_stage).await
         }
         fastskill_core::core::sources::SourceConfig::ZipUrl { base_url, .. } => {
-            install_from_zip_url(service, base_url).await
+            install_from_zip_url(service, base_url, on_stage).await
         }
     }
 }
@@ -484,6 +705,7 @@ async fn install_from_source(
     source_name: &str,
     skill_name: &str,
     version: Option<&str>,
+    on_stage: StageObserver<'_>,
 ) -> CliResult<SkillDefinition> {
     use fastskill_core::core::resolver::ConflictStrategy;
 
@@ -505,7 +727,7 @@ async fn install_from_source(
             CliError::Config(format!("Failed to resolve skill '{}': {}", skill_name, e))
         })?;
 
-    install_from_resolved_source(service, &resolution.candidate.source_config).await
+    install_from_resolved_source(service, &resolution.candidate.source_config, on_stage).await
 }
 
 /// Create SourcesManager from RepositoryManager for marketplace-based repositories
@@ -552,7 +774,8 @@ mod tests {
     async fn test_download_and_extract_zip_download_failure() {
         // PARTIAL-3: an unreachable URL surfaces as a clean InvalidSource error,
         // not a panic. Port 1 reliably refuses connections offline.
-        let result = download_and_extract_zip("http://127.0.0.1:1/nope.zip").await;
+        let result =
+            download_and_extract_zip("http://127.0.0.1:1/nope.zip", &|_: InstallStage| {}).await;
         assert!(matches!(result, Err(CliError::InvalidSource(_))));
     }
 
@@ -602,7 +825,9 @@ mod tests {
         let tmp = tempfile::tempdir().unwrap();
         let src = write_valid_skill(tmp.path(), "src-skill");
         let dst = tmp.path().join("storage").join("test-skill");
-        setup_skill_in_storage(&src, &dst, false).await.unwrap();
+        setup_skill_in_storage(&src, &dst, false, LinkMode::Auto)
+            .await
+            .unwrap();
         assert!(dst.join("SKILL.md").exists());
         assert!(!dst.is_symlink());
     }
@@ -614,10 +839,24 @@ mod tests {
         let src = write_valid_skill(tmp.path(), "src-skill");
         let dst = tmp.path().join("storage").join("test-skill");
         std::fs::create_dir_all(dst.parent().unwrap()).unwrap();
-        setup_skill_in_storage(&src, &dst, true).await.unwrap();
+        setup_skill_in_storage(&src, &dst, true, LinkMode::Auto)
+            .await
+            .unwrap();
         assert!(dst.is_symlink(), "editable install must be a symlink");
     }
 
+    #[tokio::test]
+    async fn test_setup_skill_in_storage_editable_copy_mode() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = write_valid_skill(tmp.path(), "src-skill");
+        let dst = tmp.path().join("storage").join("test-skill");
+        setup_skill_in_storage(&src, &dst, true, LinkMode::Copy)
+            .await
+            .unwrap();
+        assert!(!dst.is_symlink());
+        assert!(dst.join("SKILL.md").is_file());
+    }
+
     #[tokio::test]
     async fn test_setup_skill_in_storage_overwrites_existing() {
         let tmp = tempfile::tempdir().unwrap();
@@ -626,7 +865,9 @@ mod tests {
         // Pre-existing directory with stale content.
         std::fs::create_dir_all(&dst).unwrap();
         std::fs::write(dst.join("stale.txt"), "old").unwrap();
-        setup_skill_in_storage(&src, &dst, false).await.unwrap();
+        setup_skill_in_storage(&src, &dst, false, LinkMode::Auto)
+            .await
+            .unwrap();
         assert!(dst.join("SKILL.md").exists());
         assert!(
             !dst.join("stale.txt").exists(),
@@ -650,6 +891,7 @@ mod tests {
                 editable: false,
             },
             groups: Vec::new(),
+            extras: Vec::new(),
         };
         let def = install_skill_from_entry(&service, entry, None)
             .await
@@ -680,6 +922,7 @@ mod tests {
                 editable: true,
             },
             groups: Vec::new(),
+            extras: Vec::new(),
         };
         let def = install_skill_from_entry(&service, entry, None)
             .await
@@ -701,6 +944,7 @@ mod tests {
                 editable: false,
             },
             groups: Vec::new(),
+            extras: Vec::new(),
         };
         let result = install_skill_from_entry(&service, entry, None).await;
         assert!(matches!(result, Err(CliError::InvalidSource(_))));
@@ -721,11 +965,63 @@ mod tests {
                 version: None,
             },
             groups: Vec::new(),
+            extras: Vec::new(),
         };
         let result = install_skill_from_entry(&service, entry, None).await;
         assert!(matches!(result, Err(CliError::Config(_))));
     }
 
+    // ── install_from_vendor ───────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_install_from_vendor_keeps_origin_and_picks_satisfying_version() {
+        use crate::commands::add::create_skill_from_path;
+        use fastskill_core::core::version::VersionConstraint;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let src = write_valid_skill(tmp.path(), "test-skill");
+        let origin = Origin::Repository {
+            repo: "team".to_string(),
+            skill: "test-skill".to_string(),
+            version: None,
+        };
+        let def = create_skill_from_path(&src, origin, "test", false).unwrap();
+        let mut vendor = VendorDir::new(&tmp.path().join("vendor"));
+        vendor.add(&def, &src).unwrap();
+
+        let storage = tmp.path().join("storage");
+        let service = make_service(&storage).await;
+        let entry = SkillEntry {
+            id: "test-skill".to_string(),
+            origin: Origin::Repository {
+                repo: "team".to_string(),
+                skill: "test-skill".to_string(),
+                version: Some(VersionConstraint::parse("^1.0").unwrap()),
+            },
+            groups: Vec::new(),
+            extras: Vec::new(),
+        };
+        let installed = install_from_vendor(&service, &vendor, &entry, None)
+            .await
+            .unwrap();
+        assert_eq!(installed.version, "1.0.0");
+        assert_eq!(installed.origin, entry.origin);
+        assert!(storage.join("test-skill").join("SKILL.md").is_file());
+
+        let too_new = SkillEntry {
+            origin: Origin::Repository {
+                repo: "team".to_string(),
+                skill: "test-skill".to_string(),
+                version: Some(VersionConstraint::parse("^2.0").unwrap()),
+            },
+            ..entry
+        };
+        let err = install_from_vendor(&service, &vendor, &too_new, None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("fastskill vendor"), "{}", err);
+    }
+
     // ── install_skill_from_entry: ZipUrl (mock HTTP) ───────────────────────────
 
     #[tokio::test]
@@ -752,6 +1048,7 @@ mod tests {
                 url: zip_url.clone(),
             },
             groups: Vec::new(),
+            extras: Vec::new(),
         };
         let def = install_skill_from_entry(&service, entry, None)
             .await
@@ -1,10 +1,11 @@
 //! Utilities for managing skill-project.toml and skills.lock
 
 use fastskill_core::core::{
-    lock::{global_lock_path, GlobalSkillsLock, LockError, ProjectSkillsLock},
+    lock::{global_lock_path, project_lock_path, GlobalSkillsLock, LockError, ProjectSkillsLock},
     manifest::{DependenciesSection, DependencySpec, SkillProjectToml},
     origin::Origin,
     project::resolve_project_file,
+    project_transaction::ProjectTransaction,
     skill_manager::SkillDefinition,
 };
 use fs2::FileExt;
@@ -43,20 +44,12 @@ fn sidecar_path(lock_path: &std::path::Path) -> std::path::PathBuf {
     std::path::PathBuf::from(s)
 }
 
-/// Update skills.lock with installed skill state
-pub fn update_lock_file(
-    lock_path: &Path,
-    skill: &SkillDefinition,
-    groups: Vec<String>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    update_lock_file_with_depth(lock_path, skill, groups, 0, None)
-}
-
 /// Update skills.lock with installed skill state including depth and parent info
 pub fn update_lock_file_with_depth(
     lock_path: &Path,
     skill: &SkillDefinition,
     groups: Vec<String>,
+    extras: Vec<String>,
     depth: u32,
     parent_skill: Option<String>,
 ) -> Result<(), Box<dyn std::error::Error>> {
@@ -79,6 +72,7 @@ pub fn update_lock_file_with_depth(
 
     if let Some(locked_entry) = lock.skills.iter_mut().find(|s| s.id == skill.id.as_str()) {
         locked_entry.groups = groups;
+        locked_entry.extras = extras;
     }
 
     lock.save_to_file(lock_path)
@@ -90,6 +84,23 @@ pub fn update_lock_file_with_depth(
     Ok(())
 }
 
+/// Replace skills.lock wholesale under the sidecar lock (used by `fastskill lock`)
+pub fn write_project_lock(
+    lock_path: &Path,
+    lock: &ProjectSkillsLock,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let sidecar = sidecar_path(lock_path);
+    let _guard = acquire_advisory_lock(&sidecar)
+        .map_err(|e| format!("Failed to acquire lock on skills.lock: {}", e))?;
+
+    lock.save_to_file(lock_path)
+        .map_err(|e| format!("Failed to save lock file: {}", e))?;
+
+    let _ = std::fs::remove_file(&sidecar);
+
+    Ok(())
+}
+
 /// Update the global `global-skills.lock` for a globally installed skill.
 pub fn update_global_lock_file(
     skill: &SkillDefinition,
@@ -145,15 +156,39 @@ pub fn remove_from_global_lock_file(skill_id: &str) -> Result<(), Box<dyn std::e
     Ok(())
 }
 
-/// T028: Add skill to skill-project.toml [dependencies] section.
+/// Load skills.lock for a transaction, or start an empty one if there is none.
+fn load_project_lock(lock_path: &Path) -> Result<ProjectSkillsLock, Box<dyn std::error::Error>> {
+    if lock_path.exists() {
+        Ok(ProjectSkillsLock::load_from_file(lock_path)
+            .map_err(|e| format!("Failed to load lock file: {}", e))?)
+    } else {
+        Ok(ProjectSkillsLock::new_empty())
+    }
+}
+
+/// T028: Add skill to skill-project.toml [dependencies] section and record it
+/// in skills.lock. Both files are written in one transaction, so a failure
+/// leaves neither changed.
 /// Fails if skill-project.toml is not found in the hierarchy (we never auto-create it).
 ///
 /// Local paths are canonicalized to absolute paths before being written to the TOML file.
 /// This ensures consistent behavior regardless of the current working directory when
 /// the skill is added.
-pub fn add_skill_to_project_toml(
+pub fn add_skill_to_project(
     skill: &SkillDefinition,
     groups: Vec<String>,
+    extras: Vec<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    add_skills_to_project(std::slice::from_ref(skill), groups, extras)
+}
+
+/// Same as [`add_skill_to_project`] for several skills at once: every skill is
+/// recorded in the same transaction, so skill-project.toml and skills.lock are
+/// each written once.
+pub fn add_skills_to_project(
+    skills: &[SkillDefinition],
+    groups: Vec<String>,
+    extras: Vec<String>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Resolve project file from current directory
     let current_dir =
@@ -212,48 +247,138 @@ pub fn add_skill_to_project_toml(
     let dep_groups = if groups.is_empty() {
         None
     } else {
-        Some(groups)
+        Some(groups.clone())
     };
-    let origin = match &skill.origin {
-        // Safety net: re-canonicalize a local path to an absolute path before
-        // persisting it, in case the caller passed a relative one.
-        Origin::Local { path, editable } => {
-            let canonical_path = path
-                .canonicalize()
-                .map_err(|e| format!("Failed to canonicalize path '{}': {}", path.display(), e))?;
-            Origin::Local {
-                path: canonical_path,
-                editable: *editable,
+    for skill in skills {
+        let origin = match &skill.origin {
+            // Safety net: re-canonicalize a local path to an absolute path before
+            // persisting it, in case the caller passed a relative one.
+            Origin::Local { path, editable } => {
+                let canonical_path = path.canonicalize().map_err(|e| {
+                    format!("Failed to canonicalize path '{}': {}", path.display(), e)
+                })?;
+                Origin::Local {
+                    path: canonical_path,
+                    editable: *editable,
+                }
             }
+            other => other.clone(),
+        };
+        let dep_spec = DependencySpec::Inline {
+            origin,
+            groups: dep_groups.clone(),
+            extras: if extras.is_empty() {
+                None
+            } else {
+                Some(extras.clone())
+            },
+        };
+
+        // Add or update dependency
+        deps.dependencies.insert(skill.id.to_string(), dep_spec);
+    }
+
+    let lock_path = project_lock_path(&project_file_path);
+    let sidecar = sidecar_path(&lock_path);
+    let _guard = acquire_advisory_lock(&sidecar)
+        .map_err(|e| format!("Failed to acquire lock on skills.lock: {}", e))?;
+
+    let mut lock = load_project_lock(&lock_path)?;
+    for skill in skills {
+        lock.update_skill_with_depth(skill, 0, None);
+        if let Some(locked_entry) = lock.skills.iter_mut().find(|s| s.id == skill.id.as_str()) {
+            locked_entry.groups = groups.clone();
+            locked_entry.extras = extras.clone();
         }
-        other => other.clone(),
-    };
-    let dep_spec = DependencySpec::Inline {
-        origin,
-        groups: dep_groups,
-    };
+    }
 
-    // Add or update dependency
-    deps.dependencies.insert(skill.id.to_string(), dep_spec);
+    let mut txn = ProjectTransaction::new(&project_file_path, &lock_path);
+    txn.stage_manifest(&project)?;
+    txn.stage_lock(&lock)?;
+    txn.commit()
+        .map_err(|e| format!("Failed to save skill-project.toml and skills.lock: {}", e))?;
 
-    // Save project file
-    project
-        .save_to_file(&project_file_path)
-        .map_err(|e| format!("Failed to save skill-project.toml: {}", e))?;
+    let _ = std::fs::remove_file(&sidecar);
+
+    Ok(())
+}
+
+/// Record the extras selected for an already-added skill on its
+/// skill-project.toml dependency and its skills.lock entry.
+pub fn record_skill_extras(
+    skill_id: &str,
+    extras: Vec<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let current_dir =
+        env::current_dir().map_err(|e| format!("Failed to get current directory: {}", e))?;
+    let project_file_result = resolve_project_file(&current_dir);
+    if !project_file_result.found {
+        return Err("skill-project.toml not found in this directory or any parent".into());
+    }
+    let project_file_path = project_file_result.path;
+
+    let mut project = SkillProjectToml::load_from_file(&project_file_path)
+        .map_err(|e| format!("Failed to load skill-project.toml: {}", e))?;
+    let spec = project
+        .dependencies
+        .as_mut()
+        .and_then(|d| d.dependencies.get_mut(skill_id))
+        .ok_or_else(|| format!("'{}' is not a dependency in skill-project.toml", skill_id))?;
+    match spec {
+        DependencySpec::Inline {
+            extras: spec_extras,
+            ..
+        } => *spec_extras = Some(extras.clone()),
+        DependencySpec::Version(_) => {
+            return Err(format!(
+                "'{}' is a version-only dependency; use an inline table to select extras",
+                skill_id
+            )
+            .into())
+        }
+    }
+
+    let lock_path = project_lock_path(&project_file_path);
+    let mut txn = ProjectTransaction::new(&project_file_path, &lock_path);
+    txn.stage_manifest(&project)?;
+
+    let sidecar = sidecar_path(&lock_path);
+    let _guard = acquire_advisory_lock(&sidecar)
+        .map_err(|e| format!("Failed to acquire lock on skills.lock: {}", e))?;
+    if lock_path.exists() {
+        let mut lock = load_project_lock(&lock_path)?;
+        if let Some(entry) = lock.skills.iter_mut().find(|s| s.id == skill_id) {
+            entry.extras = extras;
+        }
+        txn.stage_lock(&lock)?;
+    }
+    txn.commit()
+        .map_err(|e| format!("Failed to save skill-project.toml and skills.lock: {}", e))?;
+    let _ = std::fs::remove_file(&sidecar);
 
     Ok(())
 }
 
-/// T029: Remove skill from skill-project.toml [dependencies] section
-pub fn remove_skill_from_project_toml(skill_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+/// T029: Remove skill from skill-project.toml [dependencies] section and from
+/// skills.lock, writing both in one transaction.
+pub fn remove_skill_from_project(skill_id: &str) -> Result<(), Box<dyn std::error::Error>> {
     // Resolve project file from current directory
     let current_dir =
         env::current_dir().map_err(|e| format!("Failed to get current directory: {}", e))?;
     let project_file_result = resolve_project_file(&current_dir);
     let project_file_path = project_file_result.path;
+    let lock_path = project_lock_path(&project_file_path);
+    let mut txn = ProjectTransaction::new(&project_file_path, &lock_path);
 
-    // If file doesn't exist, nothing to remove
+    // Without a manifest only a stray lock entry can be left to remove
     if !project_file_result.found {
+        if lock_path.exists() {
+            let mut lock = load_project_lock(&lock_path)?;
+            lock.remove_skill(skill_id);
+            txn.stage_lock(&lock)?;
+            txn.commit()
+                .map_err(|e| format!("Failed to save skills.lock: {}", e))?;
+        }
         return Ok(());
     }
 
@@ -287,11 +412,19 @@ pub fn remove_skill_from_project_toml(skill_id: &str) -> Result<(), Box<dyn std:
     if let Some(ref mut deps) = project.dependencies {
         deps.dependencies.remove(skill_id);
     }
+    txn.stage_manifest(&project)?;
 
-    // Save project file
-    project
-        .save_to_file(&project_file_path)
-        .map_err(|e| format!("Failed to save skill-project.toml: {}", e))?;
+    let sidecar = sidecar_path(&lock_path);
+    let _guard = acquire_advisory_lock(&sidecar)
+        .map_err(|e| format!("Failed to acquire lock on skills.lock: {}", e))?;
+    if lock_path.exists() {
+        let mut lock = load_project_lock(&lock_path)?;
+        lock.remove_skill(skill_id);
+        txn.stage_lock(&lock)?;
+    }
+    txn.commit()
+        .map_err(|e| format!("Failed to save skill-project.toml and skills.lock: {}", e))?;
+    let _ = std::fs::remove_file(&sidecar);
 
     Ok(())
 }
@@ -1,4 +1,30 @@
 //! Message formatting utilities for consistent CLI output
+//!
+//! Status lines (`[OK]`, `[INFO]`) go through [`status!`] so `--quiet` can
+//! silence them; warnings and errors are always printed.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Suppress status output for the rest of the process (`--quiet`).
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// `println!` for status output; prints nothing under `--quiet`.
+macro_rules! status {
+    ($($arg:tt)*) => {
+        if !$crate::utils::messages::is_quiet() {
+            println!($($arg)*);
+        }
+    };
+}
+pub(crate) use status;
 
 /// Format a success message
 pub fn ok(msg: &str) -> String {
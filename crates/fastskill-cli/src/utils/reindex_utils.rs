@@ -3,6 +3,18 @@
 use crate::error::CliResult;
 use fastskill_core::FastSkillService;
 
+/// Whether [`maybe_auto_reindex`] will reindex
+pub fn auto_reindex_enabled(
+    service: &FastSkillService,
+    explicit_reindex: bool,
+    explicit_no_reindex: bool,
+    config_auto_reindex: bool,
+) -> bool {
+    !explicit_no_reindex
+        && service.config().embedding.is_some()
+        && (explicit_reindex || config_auto_reindex)
+}
+
 /// Run reindex if conditions are met; failures are non-fatal warnings.
 pub async fn maybe_auto_reindex(
     service: &FastSkillService,
@@ -26,14 +38,20 @@ pub async fn maybe_auto_reindex(
         return Ok(());
     }
 
-    let should_reindex = explicit_reindex || config_auto_reindex;
-    if !should_reindex {
+    if !auto_reindex_enabled(
+        service,
+        explicit_reindex,
+        explicit_no_reindex,
+        config_auto_reindex,
+    ) {
         return Ok(());
     }
 
     let args = crate::commands::reindex::ReindexArgs {
         skills_dir: None,
         force: false,
+        incremental: false,
+        migrate: false,
         max_concurrent: 5,
         progress: false,
         no_progress: true,
@@ -0,0 +1,66 @@
+//! Per-command lock on the skills directory (`--wait`)
+//!
+//! Commands that change the skills directory take its
+//! [`ProcessLock`] before they start, so two of them running in the same
+//! project cannot interleave their skills.lock and storage writes. Without
+//! `--wait` the second one fails straight away and names the first; with it,
+//! it waits until the first is done (Ctrl-C stops the wait).
+
+use crate::error::{CliError, CliResult};
+use fastskill_core::core::ProcessLock;
+use fastskill_core::ServiceError;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static WAIT: AtomicBool = AtomicBool::new(false);
+
+/// Wait for other fastskill processes instead of failing (`--wait`).
+pub fn set_wait(wait: bool) {
+    WAIT.store(wait, Ordering::Relaxed);
+}
+
+/// Lock `skills_dir` for `fastskill <command>`, held until the guard is dropped.
+pub async fn lock_storage(skills_dir: &Path, command: &str) -> CliResult<ProcessLock> {
+    let command = format!("fastskill {}", command);
+    match ProcessLock::try_acquire(skills_dir, &command) {
+        Err(ServiceError::Busy(holder)) if WAIT.load(Ordering::Relaxed) => {
+            if !crate::utils::messages::is_quiet() {
+                eprintln!(
+                    "Waiting for another fastskill process ({}) to finish...",
+                    holder
+                );
+            }
+            Ok(ProcessLock::acquire(skills_dir, &command, crate::interrupt::token()).await?)
+        }
+        Err(ServiceError::Busy(holder)) => Err(CliError::ProcessBusy(holder)),
+        result => Ok(result?),
+    }
+}
+
+/// [`lock_storage`] for commands that resolve the skills directory themselves;
+/// when it cannot be resolved the command reports that on its own.
+pub async fn lock_resolved_storage(global: bool, command: &str) -> CliResult<Option<ProcessLock>> {
+    match crate::config::resolve_skills_storage_directory(global) {
+        Ok(skills_dir) => Ok(Some(lock_storage(&skills_dir, command).await?)),
+        Err(_) => Ok(None),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_lock_storage_reports_busy_with_wait_hint() {
+        let dir = TempDir::new().unwrap();
+        let _held = lock_storage(dir.path(), "install").await.unwrap();
+
+        let err = lock_storage(dir.path(), "remove").await.unwrap_err();
+        assert!(matches!(err, CliError::ProcessBusy(_)));
+        let message = err.to_string();
+        assert!(message.contains("fastskill install"));
+        assert!(message.contains("--wait"));
+    }
+}
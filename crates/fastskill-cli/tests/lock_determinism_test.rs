@@ -39,6 +39,9 @@ fn make_skill(id: &str) -> SkillDefinition {
         },
         commit_hash: Some("abc123".to_string()),
         fetched_at: Some(Utc::now()),
+        access: Default::default(),
+        injection: Default::default(),
+        env: Default::default(),
     }
 }
 
@@ -43,6 +43,9 @@ fn make_skill(id: &str) -> SkillDefinition {
         },
         commit_hash: None,
         fetched_at: Some(Utc::now()),
+        access: Default::default(),
+        injection: Default::default(),
+        env: Default::default(),
     }
 }
 
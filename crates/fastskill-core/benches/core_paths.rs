@@ -0,0 +1,209 @@
+//! Benchmarks for the hot paths behind search and skill loading
+//!
+//! Run with `scripts/bench.sh` to save a named baseline and a summary of the
+//! results under `benchmarks/results/`, or directly with
+//! `cargo bench -p fastskill-core --bench core_paths`. All inputs are
+//! generated deterministically so runs on different machines compare like
+//! for like.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use fastskill_core::core::analysis::cosine_similarity;
+use fastskill_core::core::metadata::parse_yaml_frontmatter;
+use fastskill_core::storage::zip::ZipHandler;
+use fastskill_core::{AnnConfig, IndexedSkill, VectorIndexService, VectorIndexServiceImpl};
+use std::hint::black_box;
+use std::io::{Cursor, Write};
+use tempfile::TempDir;
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+/// Embedding width of `text-embedding-3-small`.
+const EMBEDDING_DIMENSIONS: usize = 1536;
+
+/// Narrower embeddings for the index benches, so 100k rows fit in memory and
+/// on disk on a laptop. Query cost scales linearly with width.
+const INDEX_DIMENSIONS: usize = 256;
+
+const INDEX_SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+
+/// SplitMix64: small, deterministic, no extra dependency.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32 * 2.0 - 1.0
+    }
+
+    fn vector(&mut self, dimensions: usize) -> Vec<f32> {
+        (0..dimensions).map(|_| self.next_f32()).collect()
+    }
+}
+
+fn bench_cosine(c: &mut Criterion) {
+    let mut rng = Rng(1);
+    let a = rng.vector(EMBEDDING_DIMENSIONS);
+    let b = rng.vector(EMBEDDING_DIMENSIONS);
+    c.bench_function("cosine_similarity/1536", |bench| {
+        bench.iter(|| cosine_similarity(black_box(&a), black_box(&b)))
+    });
+}
+
+/// Fill `dir/index.db` with `size` random skills in one transaction.
+fn populate_index(runtime: &tokio::runtime::Runtime, dir: &TempDir, size: usize) {
+    let index = VectorIndexServiceImpl::new(dir.path().join("index.db"));
+    let mut rng = Rng(size as u64);
+    let updated_at = chrono::Utc::now();
+    let skills = (0..size)
+        .map(|i| {
+            let id = format!("skill-{:06}", i);
+            IndexedSkill {
+                skill_path: dir.path().join(&id),
+                frontmatter_json: serde_json::json!({
+                    "name": id,
+                    "description": "Generated for benchmarks",
+                }),
+                embedding: rng.vector(INDEX_DIMENSIONS),
+                chunk_embeddings: Vec::new(),
+                file_hash: format!("{:016x}", rng.next_u64()),
+                updated_at,
+                id,
+            }
+        })
+        .collect();
+    runtime.block_on(index.add_skills(skills)).unwrap();
+}
+
+fn bench_index_query(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let mut group = c.benchmark_group("vector_index_query");
+    group.sample_size(10);
+    let query = Rng(7).vector(INDEX_DIMENSIONS);
+
+    for size in INDEX_SIZES {
+        let dir = TempDir::new().unwrap();
+        populate_index(&runtime, &dir, size);
+
+        let exact = VectorIndexServiceImpl::new(dir.path().join("index.db")).with_ann(AnnConfig {
+            enabled: false,
+            ..Default::default()
+        });
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::new("exact", size), &size, |bench, _| {
+            bench.iter(|| runtime.block_on(exact.search_similar(black_box(&query), 10)))
+        });
+
+        let ann = VectorIndexServiceImpl::new(dir.path().join("index.db")).with_ann(AnnConfig {
+            min_skills: 1,
+            ..Default::default()
+        });
+        // Build (and persist) the graph outside the measurement.
+        runtime.block_on(ann.refresh_search_index()).unwrap();
+        group.bench_with_input(BenchmarkId::new("ann", size), &size, |bench, _| {
+            bench.iter(|| runtime.block_on(ann.search_similar(black_box(&query), 10)))
+        });
+    }
+    group.finish();
+}
+
+fn skill_md(body_paragraphs: usize) -> String {
+    let mut content = String::from(
+        "---\nname: web-scraper\ndescription: Scrape web pages and extract structured data\n\
+         version: 1.2.0\nauthor: Example Org\nlicense: Apache-2.0\n\
+         compatibility: Requires Python 3.10+\nallowed_tools: Bash Read\n\
+         metadata:\n  category: data\n  tags: scraping, html\n---\n\n# Web Scraper\n",
+    );
+    for i in 0..body_paragraphs {
+        content.push_str(&format!(
+            "\n## Step {}\n\nFetch the page, parse the HTML and keep the fields the user asked for. \
+             Respect robots.txt and rate limits.\n",
+            i
+        ));
+    }
+    content
+}
+
+fn bench_frontmatter(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_yaml_frontmatter");
+    for (label, paragraphs) in [("small", 1), ("typical", 20), ("large", 500)] {
+        let content = skill_md(paragraphs);
+        group.throughput(Throughput::Bytes(content.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(label),
+            &content,
+            |bench, content| bench.iter(|| parse_yaml_frontmatter(black_box(content)).unwrap()),
+        );
+    }
+    group.finish();
+}
+
+/// Files of a mid-sized skill package: SKILL.md, scripts and references.
+fn package_files() -> Vec<(String, Vec<u8>)> {
+    let mut rng = Rng(3);
+    let mut files = vec![("SKILL.md".to_string(), skill_md(20).into_bytes())];
+    for i in 0..10 {
+        let script: String = (0..200)
+            .map(|_| format!("echo {}\n", rng.next_u64()))
+            .collect();
+        files.push((format!("scripts/step-{}.sh", i), script.into_bytes()));
+    }
+    for i in 0..5 {
+        files.push((
+            format!("references/doc-{}.md", i),
+            skill_md(100).into_bytes(),
+        ));
+    }
+    files
+}
+
+fn package(files: &[(String, Vec<u8>)]) -> Vec<u8> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, content) in files {
+        zip.start_file(name.as_str(), options).unwrap();
+        zip.write_all(content).unwrap();
+    }
+    zip.finish().unwrap().into_inner()
+}
+
+fn bench_zip(c: &mut Criterion) {
+    let files = package_files();
+    let total: usize = files.iter().map(|(_, content)| content.len()).sum();
+    let archive = package(&files);
+    let handler = ZipHandler::new().unwrap();
+
+    let mut group = c.benchmark_group("zip_package");
+    group.throughput(Throughput::Bytes(total as u64));
+    group.bench_function("pack", |bench| bench.iter(|| package(black_box(&files))));
+    group.bench_function("extract", |bench| {
+        bench.iter_batched(
+            || TempDir::new().unwrap(),
+            |dest| {
+                handler
+                    .extract_reader_to_dir(Cursor::new(&archive), dest.path())
+                    .unwrap();
+                dest
+            },
+            criterion::BatchSize::PerIteration,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_cosine,
+    bench_index_query,
+    bench_frontmatter,
+    bench_zip
+);
+criterion_main!(benches);
@@ -0,0 +1,8 @@
+//! Generates the gRPC service code when the `grpc` feature is enabled.
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-changed=build.rs");
+    #[cfg(feature = "grpc")]
+    tonic_build::compile_protos("proto/fastskill/v1/fastskill.proto")?;
+    Ok(())
+}
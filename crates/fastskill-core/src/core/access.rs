@@ -0,0 +1,174 @@
+//! Who may see a skill served over the HTTP API.
+//!
+//! A skill declares its access in SKILL.md frontmatter:
+//!
+//! ```yaml
+//! owner: data-bot
+//! team: data-platform
+//! visibility: team   # public (default), team or private
+//! ```
+//!
+//! Callers are identified by the client profile their API key selects
+//! (`[[tool.fastskill.server.clients]]`). Public skills are visible to every
+//! caller. A `team` skill is visible to its owner and to profiles listing the
+//! team; a `private` skill only to its owner. Profiles with `role = "admin"`
+//! see every skill. Profiles selected by name alone carry no identity, since
+//! any caller can send the `X-FastSkill-Client` header.
+
+use crate::core::metadata::SkillFrontmatter;
+use crate::core::service::{ClientProfile, ClientRole};
+use serde::{Deserialize, Serialize};
+
+/// Who a skill is visible to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    /// Every caller (the default)
+    #[default]
+    Public,
+    /// The owner and members of the skill's team
+    Team,
+    /// The owner only
+    Private,
+}
+
+/// A skill's owner, team and visibility.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillAccess {
+    /// Client profile name that owns the skill
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team: Option<String>,
+    #[serde(default)]
+    pub visibility: Visibility,
+}
+
+impl SkillAccess {
+    /// Read `owner`, `team` and `visibility` from SKILL.md frontmatter. An
+    /// unrecognised visibility is treated as private, so a typo hides a skill
+    /// rather than publishing it.
+    pub fn from_frontmatter(frontmatter: &SkillFrontmatter) -> Self {
+        let text = |key: &str| {
+            frontmatter
+                .extra
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+        let visibility = match text("visibility").as_deref() {
+            None | Some("public") => Visibility::Public,
+            Some("team") => Visibility::Team,
+            Some("private") => Visibility::Private,
+            Some(other) => {
+                tracing::warn!(
+                    "Unknown visibility '{}' for skill '{}'; treating it as private",
+                    other,
+                    frontmatter.name
+                );
+                Visibility::Private
+            }
+        };
+        Self {
+            owner: text("owner"),
+            team: text("team"),
+            visibility,
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether the caller `profile` identifies (if any) may see the skill.
+    pub fn allows(&self, profile: Option<&ClientProfile>) -> bool {
+        if self.visibility == Visibility::Public {
+            return true;
+        }
+        // Only a profile selected by its API key identifies the caller
+        let Some(profile) = profile.filter(|p| p.api_key.is_some()) else {
+            return false;
+        };
+        if profile.role == ClientRole::Admin {
+            return true;
+        }
+        let owns = self.owner.as_deref() == Some(profile.name.as_str());
+        match self.visibility {
+            Visibility::Public => true,
+            Visibility::Team => {
+                owns || self
+                    .team
+                    .as_ref()
+                    .is_some_and(|team| profile.teams.contains(team))
+            }
+            Visibility::Private => owns,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::metadata::parse_yaml_frontmatter;
+
+    fn access(fields: &str) -> SkillAccess {
+        let content = format!("---\nname: s\ndescription: d\n{}---\n# s\n", fields);
+        SkillAccess::from_frontmatter(&parse_yaml_frontmatter(&content).unwrap())
+    }
+
+    fn keyed(name: &str, teams: &[&str], role: ClientRole) -> ClientProfile {
+        ClientProfile {
+            name: name.to_string(),
+            api_key: Some(format!("{}-key", name)),
+            teams: teams.iter().map(|t| t.to_string()).collect(),
+            role,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_from_frontmatter() {
+        assert!(access("").is_default());
+        let team = access("owner: alice\nteam: data\nvisibility: team\n");
+        assert_eq!(team.owner.as_deref(), Some("alice"));
+        assert_eq!(team.team.as_deref(), Some("data"));
+        assert_eq!(team.visibility, Visibility::Team);
+        assert_eq!(
+            access("visibility: secret\n").visibility,
+            Visibility::Private
+        );
+    }
+
+    #[test]
+    fn test_allows() {
+        let alice = keyed("alice", &[], ClientRole::Member);
+        let bob = keyed("bob", &["data"], ClientRole::Member);
+        let carol = keyed("carol", &["web"], ClientRole::Member);
+        let admin = keyed("ops", &[], ClientRole::Admin);
+
+        let public = access("owner: alice\n");
+        assert!(public.allows(None));
+
+        let team = access("owner: alice\nteam: data\nvisibility: team\n");
+        assert!(!team.allows(None));
+        assert!(team.allows(Some(&alice)));
+        assert!(team.allows(Some(&bob)));
+        assert!(!team.allows(Some(&carol)));
+        assert!(team.allows(Some(&admin)));
+
+        let private = access("owner: alice\nteam: data\nvisibility: private\n");
+        assert!(private.allows(Some(&alice)));
+        assert!(!private.allows(Some(&bob)));
+        assert!(private.allows(Some(&admin)));
+
+        // A profile selected by name alone is not an identity
+        let unkeyed = ClientProfile {
+            api_key: None,
+            ..alice.clone()
+        };
+        assert!(!private.allows(Some(&unkeyed)));
+    }
+}
@@ -0,0 +1,325 @@
+//! Scoped API keys for machine clients of the HTTP and gRPC servers
+//!
+//! Keys live in `<skills dir>/.fastskill/api-keys.toml`, managed with
+//! `fastskill admin keys`. Only a SHA-256 hash of each key is stored; the key
+//! itself is shown once, when it is created. Revoked keys stay in the file
+//! with their revocation time so the history is kept.
+//!
+//! Once the file holds a key, every `/api/v1` request (and every gRPC call)
+//! must present an active key whose scopes cover the route; see
+//! [`crate::http::auth`]. Revoking the last key does not reopen the server:
+//! authentication stays on until the file is deleted.
+
+use crate::core::service::ServiceError;
+use crate::utils::atomic_write;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::OsRng;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Prefix of every generated key, so leaked keys are easy to recognise
+pub const KEY_PREFIX: &str = "fsk_";
+
+/// What a key may do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ApiKeyScope {
+    /// List, read, search and resolve skills
+    #[serde(rename = "read:skills")]
+    ReadSkills,
+    /// Install, update and delete skills and edit the manifest
+    #[serde(rename = "write:skills")]
+    WriteSkills,
+    /// Discover the tools installed skills declare
+    #[serde(rename = "execute:tools")]
+    ExecuteTools,
+    /// Everything, including reindexing, registry maintenance and replication
+    #[serde(rename = "admin")]
+    Admin,
+}
+
+impl ApiKeyScope {
+    pub const ALL: [ApiKeyScope; 4] = [
+        ApiKeyScope::ReadSkills,
+        ApiKeyScope::WriteSkills,
+        ApiKeyScope::ExecuteTools,
+        ApiKeyScope::Admin,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ApiKeyScope::ReadSkills => "read:skills",
+            ApiKeyScope::WriteSkills => "write:skills",
+            ApiKeyScope::ExecuteTools => "execute:tools",
+            ApiKeyScope::Admin => "admin",
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<Self, ServiceError> {
+        Self::ALL
+            .into_iter()
+            .find(|scope| scope.as_str() == s.trim())
+            .ok_or_else(|| {
+                ServiceError::Validation(format!(
+                    "Unknown scope '{}' (expected one of: {})",
+                    s,
+                    Self::ALL.map(ApiKeyScope::as_str).join(", ")
+                ))
+            })
+    }
+}
+
+impl fmt::Display for ApiKeyScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One key record. `hash` is `sha256:<hex>` of the full key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiKey {
+    /// Short public identifier, also embedded in the key itself
+    pub id: String,
+    pub name: String,
+    pub hash: String,
+    pub scopes: Vec<ApiKeyScope>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none()
+    }
+
+    /// Whether the key grants `scope`; `admin` grants every scope.
+    pub fn allows(&self, scope: ApiKeyScope) -> bool {
+        self.scopes
+            .iter()
+            .any(|s| *s == scope || *s == ApiKeyScope::Admin)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ApiKeyFile {
+    #[serde(default)]
+    keys: Vec<ApiKey>,
+}
+
+/// The API key file
+#[derive(Debug, Clone)]
+pub struct ApiKeyStore {
+    path: PathBuf,
+    keys: Vec<ApiKey>,
+}
+
+impl ApiKeyStore {
+    /// Default location next to the audit log: `<skills dir>/.fastskill/api-keys.toml`.
+    pub fn default_path(skills_dir: &Path) -> PathBuf {
+        skills_dir.join(".fastskill").join("api-keys.toml")
+    }
+
+    /// Read the key file; a missing file is an empty store.
+    pub fn load(path: &Path) -> Result<Self, ServiceError> {
+        let keys = if path.exists() {
+            let content = std::fs::read_to_string(path)?;
+            toml::from_str::<ApiKeyFile>(&content)
+                .map_err(|e| {
+                    ServiceError::Config(format!(
+                        "API key file {} is malformed: {}",
+                        path.display(),
+                        e
+                    ))
+                })?
+                .keys
+        } else {
+            Vec::new()
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            keys,
+        })
+    }
+
+    pub fn save(&self) -> Result<(), ServiceError> {
+        let content = toml::to_string_pretty(&ApiKeyFile {
+            keys: self.keys.clone(),
+        })
+        .map_err(|e| ServiceError::Custom(format!("Failed to encode API keys: {}", e)))?;
+        atomic_write(&self.path, content.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// All keys, revoked ones included, oldest first
+    pub fn keys(&self) -> &[ApiKey] {
+        &self.keys
+    }
+
+    /// Whether requests must authenticate: a key was ever created. Stays
+    /// true when every key is revoked, so revoking a leaked last key locks
+    /// the server instead of opening it.
+    pub fn is_enabled(&self) -> bool {
+        !self.keys.is_empty()
+    }
+
+    /// Add a key named `name` with `scopes`. Returns the record and the key
+    /// itself, which is not stored and cannot be shown again.
+    pub fn create(
+        &mut self,
+        name: &str,
+        scopes: &[ApiKeyScope],
+    ) -> Result<(ApiKey, String), ServiceError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(ServiceError::Validation(
+                "API key name must not be empty".to_string(),
+            ));
+        }
+        if scopes.is_empty() {
+            return Err(ServiceError::Validation(
+                "API key needs at least one scope".to_string(),
+            ));
+        }
+        if self.keys.iter().any(|k| k.is_active() && k.name == name) {
+            return Err(ServiceError::Validation(format!(
+                "An active API key named '{}' already exists",
+                name
+            )));
+        }
+
+        let id = loop {
+            let mut id_bytes = [0u8; 4];
+            OsRng.fill_bytes(&mut id_bytes);
+            let id = encode_hex(&id_bytes);
+            if !self.keys.iter().any(|k| k.id == id) {
+                break id;
+            }
+        };
+        let mut secret_bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut secret_bytes);
+        let secret = format!("{}{}_{}", KEY_PREFIX, id, encode_hex(&secret_bytes));
+
+        let mut unique_scopes: Vec<ApiKeyScope> = Vec::new();
+        for scope in scopes {
+            if !unique_scopes.contains(scope) {
+                unique_scopes.push(*scope);
+            }
+        }
+        let key = ApiKey {
+            id,
+            name: name.to_string(),
+            hash: hash_key(&secret),
+            scopes: unique_scopes,
+            created_at: Utc::now(),
+            revoked_at: None,
+        };
+        self.keys.push(key.clone());
+        Ok((key, secret))
+    }
+
+    /// Revoke the active key with id or name `key`.
+    pub fn revoke(&mut self, key: &str) -> Result<ApiKey, ServiceError> {
+        let record = self
+            .keys
+            .iter_mut()
+            .find(|k| k.is_active() && (k.id == key || k.name == key))
+            .ok_or_else(|| {
+                ServiceError::Validation(format!("No active API key with id or name '{}'", key))
+            })?;
+        record.revoked_at = Some(Utc::now());
+        Ok(record.clone())
+    }
+
+    /// The active key `secret` belongs to
+    pub fn authenticate(&self, secret: &str) -> Option<&ApiKey> {
+        if !secret.starts_with(KEY_PREFIX) {
+            return None;
+        }
+        let hash = hash_key(secret);
+        self.keys.iter().find(|k| k.is_active() && k.hash == hash)
+    }
+}
+
+fn hash_key(secret: &str) -> String {
+    format!("sha256:{:x}", Sha256::digest(secret.as_bytes()))
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_create_authenticate_revoke() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = ApiKeyStore::default_path(temp_dir.path());
+        let mut store = ApiKeyStore::load(&path).unwrap();
+        assert!(!store.is_enabled());
+
+        let (key, secret) = store
+            .create("ci", &[ApiKeyScope::ReadSkills, ApiKeyScope::ReadSkills])
+            .unwrap();
+        assert!(secret.starts_with(&format!("fsk_{}_", key.id)));
+        assert_eq!(key.scopes, [ApiKeyScope::ReadSkills]);
+        store.save().unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(!content.contains(&secret), "key stored in clear text");
+        assert!(content.contains("\"read:skills\""), "{}", content);
+
+        let mut store = ApiKeyStore::load(&path).unwrap();
+        assert!(store.is_enabled());
+        assert_eq!(store.authenticate(&secret).unwrap().name, "ci");
+        assert!(store.authenticate("fsk_wrong").is_none());
+        assert!(store.authenticate(&secret[KEY_PREFIX.len()..]).is_none());
+
+        assert!(store.create("ci", &[ApiKeyScope::Admin]).is_err());
+        store.revoke("ci").unwrap();
+        assert!(store.authenticate(&secret).is_none());
+        // Revoking the last key keeps authentication on
+        assert!(store.is_enabled());
+        assert!(!store.keys().iter().any(ApiKey::is_active));
+        assert!(store.revoke(&key.id).is_err());
+        assert_eq!(store.keys().len(), 1);
+    }
+
+    #[test]
+    fn test_scopes() {
+        let key = |scopes: Vec<ApiKeyScope>| ApiKey {
+            id: "0".to_string(),
+            name: "k".to_string(),
+            hash: String::new(),
+            scopes,
+            created_at: Utc::now(),
+            revoked_at: None,
+        };
+        let reader = key(vec![ApiKeyScope::ReadSkills]);
+        assert!(reader.allows(ApiKeyScope::ReadSkills));
+        assert!(!reader.allows(ApiKeyScope::WriteSkills));
+        let admin = key(vec![ApiKeyScope::Admin]);
+        assert!(ApiKeyScope::ALL.iter().all(|s| admin.allows(*s)));
+
+        assert_eq!(
+            ApiKeyScope::parse("execute:tools").unwrap(),
+            ApiKeyScope::ExecuteTools
+        );
+        assert!(ApiKeyScope::parse("write").is_err());
+        let mut store = ApiKeyStore {
+            path: PathBuf::new(),
+            keys: Vec::new(),
+        };
+        assert!(store.create("x", &[]).is_err());
+    }
+}
@@ -0,0 +1,558 @@
+//! Append-only audit log for administrative (mutating) operations
+//!
+//! The HTTP and gRPC servers append one JSON object per line to
+//! `<skills dir>/.fastskill/audit.jsonl` for every request to a write route,
+//! including ones the write gate refused, and the CLI does the same for the
+//! commands that change skills (`add`, `remove`, `update`, `yank`, `retire`).
+//! Entries are never rewritten; the file is opened in append mode for each
+//! record.
+//!
+//! Entries are hash-chained: each carries the SHA-256 of its own contents and
+//! the hash of the entry before it, so [`AuditLog::verify`] detects entries
+//! that were edited, removed or reordered. Appends take an exclusive lock on
+//! the file, keeping the chain linear when the server and the CLI write at
+//! the same time.
+
+use crate::core::service::ServiceError;
+use chrono::{DateTime, Utc};
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// One audited operation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    /// `key:<name>` for a scoped API key, else the client profile name, or
+    /// `anonymous` when no profile matched.
+    pub actor: String,
+    /// Peer address, when the server knows it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_addr: Option<String>,
+    /// Operation name, e.g. `skill.delete`, `index.reindex`.
+    pub action: String,
+    /// Skill id the operation targeted, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// Skill version the operation installed or targeted, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Where the skill came from (registry id, git URL, local path), when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// HTTP method, or [`AuditEntry::CLI_METHOD`] for CLI commands.
+    pub method: String,
+    /// Request path, or the command (`fastskill add`) for CLI commands.
+    pub path: String,
+    /// HTTP status, or 0 (success) / 1 (failure) for CLI commands.
+    pub status: u16,
+    /// Hash of the preceding entry; `None` for the first chained entry.
+    /// Set by [`AuditLog::append`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_hash: Option<String>,
+    /// SHA-256 of this entry without `hash`; absent on entries written before
+    /// the log was chained. Set by [`AuditLog::append`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+}
+
+impl AuditEntry {
+    /// `method` of entries recorded by the CLI.
+    pub const CLI_METHOD: &'static str = "CLI";
+
+    /// Entry for a CLI command run by `actor`, e.g. `cli("user:ana", "skill.add", "add", true)`.
+    pub fn cli(actor: &str, action: &str, command: &str, succeeded: bool) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            actor: actor.to_string(),
+            remote_addr: None,
+            action: action.to_string(),
+            target: None,
+            version: None,
+            source: None,
+            method: Self::CLI_METHOD.to_string(),
+            path: format!("fastskill {}", command),
+            status: if succeeded { 0 } else { 1 },
+            prev_hash: None,
+            hash: None,
+        }
+    }
+
+    /// Fill in the skill details the operation reported; fields already set win.
+    pub fn with_details(mut self, details: AuditDetails) -> Self {
+        self.target = self.target.or(details.target);
+        self.version = self.version.or(details.version);
+        self.source = self.source.or(details.source);
+        self
+    }
+
+    /// `success`, `denied` (401/403) or `failed`.
+    pub fn outcome(&self) -> &'static str {
+        if self.method == Self::CLI_METHOD {
+            return if self.status == 0 {
+                "success"
+            } else {
+                "failed"
+            };
+        }
+        match self.status {
+            200..=399 => "success",
+            401 | 403 => "denied",
+            _ => "failed",
+        }
+    }
+
+    /// The hash [`AuditLog::append`] stores in `hash`: SHA-256 over the
+    /// entry's JSON encoding with `hash` left out (`prev_hash` included).
+    pub fn compute_hash(&self) -> Result<String, ServiceError> {
+        let mut unsealed = self.clone();
+        unsealed.hash = None;
+        let bytes = serde_json::to_vec(&unsealed)
+            .map_err(|e| ServiceError::Custom(format!("Failed to encode audit entry: {}", e)))?;
+        Ok(format!("{:x}", Sha256::digest(bytes)))
+    }
+}
+
+/// Skill details a mutation reports for its audit entry.
+///
+/// Write handlers add this to their response extensions; the audit
+/// middleware merges it into the entry it records.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditDetails {
+    pub target: Option<String>,
+    pub version: Option<String>,
+    pub source: Option<String>,
+}
+
+/// Which entries [`AuditLog::read`] returns.
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub since: Option<DateTime<Utc>>,
+    /// Exact action, or a prefix ending in `.` (e.g. `skill.`).
+    pub action: Option<String>,
+    pub actor: Option<String>,
+    /// Only entries targeting this skill id.
+    pub target: Option<String>,
+}
+
+impl AuditFilter {
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        if let Some(since) = self.since {
+            if entry.timestamp < since {
+                return false;
+            }
+        }
+        if let Some(action) = &self.action {
+            let hit = if action.ends_with('.') {
+                entry.action.starts_with(action.as_str())
+            } else {
+                entry.action == *action
+            };
+            if !hit {
+                return false;
+            }
+        }
+        if let Some(actor) = &self.actor {
+            if entry.actor != *actor {
+                return false;
+            }
+        }
+        if let Some(target) = &self.target {
+            if entry.target.as_deref() != Some(target.as_str()) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// A line of the log that breaks the hash chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+pub struct AuditChainProblem {
+    /// 1-based line number in the log file.
+    pub line: usize,
+    pub message: String,
+}
+
+/// Result of [`AuditLog::verify`].
+#[derive(Debug, Clone, Default, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditVerification {
+    pub entries: usize,
+    /// Entries at the start of the log written before hash chaining.
+    pub unchained: usize,
+    /// Hash of the last entry. The chain cannot show entries cut off the end
+    /// of the log; compare against a head recorded earlier to detect that.
+    pub head: Option<String>,
+    pub problems: Vec<AuditChainProblem>,
+}
+
+impl AuditVerification {
+    pub fn is_intact(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    fn problem(&mut self, line: usize, message: impl Into<String>) {
+        self.problems.push(AuditChainProblem {
+            line,
+            message: message.into(),
+        });
+    }
+}
+
+/// The end of the log as seen by the last append.
+#[derive(Debug, Clone)]
+struct Tail {
+    /// File length it was read at.
+    len: u64,
+    hash: Option<String>,
+    /// Whether the file ends in a newline (false after a torn write).
+    terminated: bool,
+}
+
+/// Audit log file handle. Appends are serialized within the process by a
+/// mutex and across processes by an exclusive file lock.
+#[derive(Debug)]
+pub struct AuditLog {
+    path: PathBuf,
+    /// Guards appends; caches the tail so the file is only rescanned when
+    /// another process appended since.
+    write_lock: Mutex<Option<Tail>>,
+}
+
+impl AuditLog {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            write_lock: Mutex::new(None),
+        }
+    }
+
+    /// Default location next to the vector index: `<skills dir>/.fastskill/audit.jsonl`.
+    pub fn default_path(skills_dir: &Path) -> PathBuf {
+        skills_dir.join(".fastskill").join("audit.jsonl")
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Chain `entry` onto the last entry in the log, append it as one line
+    /// and flush it to disk. `prev_hash` and `hash` of `entry` are replaced.
+    pub fn append(&self, entry: &AuditEntry) -> Result<(), ServiceError> {
+        let mut cached = match self.write_lock.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&self.path)?;
+        file.lock_exclusive()?;
+
+        let len = file.metadata()?.len();
+        let tail = match cached.take() {
+            Some(tail) if tail.len == len => tail,
+            _ => Self::scan_tail(&file, len)?,
+        };
+
+        let mut sealed = entry.clone();
+        sealed.prev_hash = tail.hash;
+        sealed.hash = None;
+        let hash = sealed.compute_hash()?;
+        sealed.hash = Some(hash.clone());
+        let mut line = serde_json::to_string(&sealed)
+            .map_err(|e| ServiceError::Custom(format!("Failed to encode audit entry: {}", e)))?;
+        line.push('\n');
+        // Start on a fresh line after a torn write so this entry stays readable
+        if !tail.terminated {
+            line.insert(0, '\n');
+        }
+
+        // One write per line so concurrent appenders never interleave within it.
+        file.write_all(line.as_bytes())?;
+        file.sync_data()?;
+        *cached = Some(Tail {
+            len: len + line.len() as u64,
+            hash: Some(hash),
+            terminated: true,
+        });
+        FileExt::unlock(&file)?;
+        Ok(())
+    }
+
+    /// Hash of the last readable entry in `file` and whether it ends in a newline.
+    fn scan_tail(file: &File, len: u64) -> Result<Tail, ServiceError> {
+        let mut tail = Tail {
+            len,
+            hash: None,
+            terminated: true,
+        };
+        let mut reader = BufReader::new(file);
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            if reader.read_until(b'\n', &mut line)? == 0 {
+                break;
+            }
+            tail.terminated = line.last() == Some(&b'\n');
+            if let Ok(entry) = serde_json::from_slice::<AuditEntry>(&line) {
+                tail.hash = entry.hash;
+            }
+        }
+        Ok(tail)
+    }
+
+    /// Read entries matching `filter`, oldest first. A missing file is an empty
+    /// log; unparseable lines (e.g. a torn final line after a crash) are skipped.
+    pub fn read(&self, filter: &AuditFilter) -> Result<Vec<AuditEntry>, ServiceError> {
+        let file = match std::fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut entries = Vec::new();
+        for (index, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<AuditEntry>(&line) {
+                Ok(entry) if filter.matches(&entry) => entries.push(entry),
+                Ok(_) => {}
+                Err(e) => tracing::warn!(
+                    "Skipping malformed audit entry at {}:{}: {}",
+                    self.path.display(),
+                    index + 1,
+                    e
+                ),
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Walk the whole log and check the hash chain: every entry's `hash`
+    /// matches its contents and its `prev_hash` names the entry before it.
+    /// Unparseable lines and unchained entries after the first chained one
+    /// are reported too. A missing file is an intact, empty log.
+    pub fn verify(&self) -> Result<AuditVerification, ServiceError> {
+        let mut report = AuditVerification::default();
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(report),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut chained = false;
+        let mut prev: Option<String> = None;
+        for (index, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let line_no = index + 1;
+            let entry = match serde_json::from_str::<AuditEntry>(&line) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    report.problem(line_no, format!("unreadable entry: {}", e));
+                    continue;
+                }
+            };
+            report.entries += 1;
+
+            let Some(hash) = &entry.hash else {
+                if chained {
+                    report.problem(line_no, "entry is not hash-chained");
+                } else {
+                    report.unchained += 1;
+                }
+                prev = None;
+                continue;
+            };
+            chained = true;
+            if entry.compute_hash()? != *hash {
+                report.problem(line_no, "entry contents do not match its hash");
+            }
+            if entry.prev_hash != prev {
+                report.problem(
+                    line_no,
+                    "prev_hash does not match the preceding entry (entries removed or reordered)",
+                );
+            }
+            prev = Some(hash.clone());
+        }
+        report.head = prev;
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(action: &str, actor: &str, status: u16) -> AuditEntry {
+        AuditEntry {
+            timestamp: Utc::now(),
+            actor: actor.to_string(),
+            remote_addr: None,
+            action: action.to_string(),
+            target: Some("alpha".to_string()),
+            version: None,
+            source: None,
+            method: "DELETE".to_string(),
+            path: "/api/v1/skills/alpha".to_string(),
+            status,
+            prev_hash: None,
+            hash: None,
+        }
+    }
+
+    #[test]
+    fn test_append_and_read_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = AuditLog::new(AuditLog::default_path(temp_dir.path()));
+
+        assert!(log.read(&AuditFilter::default()).unwrap().is_empty());
+        log.append(&entry("skill.delete", "ci-agent", 200)).unwrap();
+        log.append(&entry("index.reindex", "anonymous", 403))
+            .unwrap();
+
+        let all = log.read(&AuditFilter::default()).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].action, "skill.delete");
+        assert_eq!(all[0].outcome(), "success");
+        assert_eq!(all[1].outcome(), "denied");
+    }
+
+    #[test]
+    fn test_filter_by_action_prefix_and_actor() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = AuditLog::new(temp_dir.path().join("audit.jsonl"));
+        log.append(&entry("skill.delete", "ci-agent", 200)).unwrap();
+        log.append(&entry("skill.install", "ops", 500)).unwrap();
+        log.append(&entry("index.reindex", "ci-agent", 200))
+            .unwrap();
+
+        let skills = log
+            .read(&AuditFilter {
+                action: Some("skill.".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(skills.len(), 2);
+
+        let ci = log
+            .read(&AuditFilter {
+                actor: Some("ci-agent".to_string()),
+                action: Some("index.reindex".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(ci.len(), 1);
+
+        let future = log
+            .read(&AuditFilter {
+                since: Some(Utc::now() + chrono::Duration::hours(1)),
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(future.is_empty());
+    }
+
+    #[test]
+    fn test_torn_line_is_skipped() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("audit.jsonl");
+        let log = AuditLog::new(path.clone());
+        log.append(&entry("skill.delete", "ops", 200)).unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"timestamp\":\"2026-").unwrap();
+
+        assert_eq!(log.read(&AuditFilter::default()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_chain_verifies_and_detects_tampering() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("audit.jsonl");
+        let log = AuditLog::new(path.clone());
+        for actor in ["ops", "ci-agent", "release"] {
+            log.append(&entry("skill.delete", actor, 200)).unwrap();
+        }
+
+        let entries = log.read(&AuditFilter::default()).unwrap();
+        assert_eq!(entries[0].prev_hash, None);
+        assert_eq!(entries[1].prev_hash, entries[0].hash);
+        let report = log.verify().unwrap();
+        assert!(report.is_intact(), "{:?}", report.problems);
+        assert_eq!(report.entries, 3);
+        assert_eq!(report.head, entries[2].hash);
+
+        let original = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, original.replace("\"ci-agent\"", "\"someone\"")).unwrap();
+        let report = log.verify().unwrap();
+        assert_eq!(report.problems.len(), 1);
+        assert_eq!(report.problems[0].line, 2);
+
+        let without_second: Vec<&str> = original
+            .lines()
+            .enumerate()
+            .filter(|(i, _)| *i != 1)
+            .map(|(_, line)| line)
+            .collect();
+        std::fs::write(&path, without_second.join("\n") + "\n").unwrap();
+        let report = log.verify().unwrap();
+        assert!(!report.is_intact());
+        assert!(report.problems[0].message.contains("prev_hash"));
+    }
+
+    #[test]
+    fn test_append_continues_after_legacy_and_torn_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("audit.jsonl");
+        let legacy = serde_json::to_string(&entry("skill.delete", "ops", 200)).unwrap();
+        std::fs::write(&path, format!("{}\n{{\"timestamp\":\"2026-", legacy)).unwrap();
+
+        let log = AuditLog::new(path);
+        log.append(
+            &AuditEntry::cli("user:ana", "skill.add", "add", true).with_details(AuditDetails {
+                target: Some("beta".to_string()),
+                version: Some("1.2.0".to_string()),
+                source: Some("acme/beta".to_string()),
+            }),
+        )
+        .unwrap();
+
+        let entries = log.read(&AuditFilter::default()).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].version.as_deref(), Some("1.2.0"));
+        assert_eq!(entries[1].outcome(), "success");
+        let report = log.verify().unwrap();
+        assert_eq!(report.entries, 2);
+        assert_eq!(report.unchained, 1);
+        assert_eq!(report.problems.len(), 1);
+        assert_eq!(report.problems[0].line, 2);
+
+        let by_skill = log
+            .read(&AuditFilter {
+                target: Some("beta".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(by_skill.len(), 1);
+        assert_eq!(
+            AuditEntry::cli("user:ana", "skill.remove", "remove", false).outcome(),
+            "failed"
+        );
+    }
+}
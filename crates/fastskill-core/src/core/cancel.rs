@@ -0,0 +1,67 @@
+//! Cancellation of long-running operations
+//!
+//! Every [`FastSkillService`](crate::core::service::FastSkillService) carries a
+//! [`CancellationToken`]. The CLI cancels it on Ctrl-C and
+//! [`shutdown`](crate::core::service::FastSkillService::shutdown) cancels it
+//! too. Reindex, install fetches and similar operations race their slow awaits
+//! (embedding calls, downloads, clones) against the token with [`cancellable`]
+//! and stop with [`ServiceError::Cancelled`], leaving stores as they were
+//! before the interrupted step.
+//!
+//! Dropping a future cancels it as well, which is what happens when an HTTP
+//! client disconnects; child processes are spawned with `kill_on_drop` so
+//! scripts and git do not outlive the request that started them. Scripts run
+//! in a process group of their own, which is killed as a whole (see
+//! [`ExecutionSandbox::with_cancellation`](crate::execution::ExecutionSandbox::with_cancellation)).
+
+use crate::core::service::ServiceError;
+use std::future::Future;
+
+pub use tokio_util::sync::CancellationToken;
+
+/// Run `fut` unless `token` is cancelled first.
+pub async fn cancellable<F: Future>(
+    token: &CancellationToken,
+    fut: F,
+) -> Result<F::Output, ServiceError> {
+    tokio::select! {
+        biased;
+        _ = token.cancelled() => Err(ServiceError::Cancelled),
+        output = fut => Ok(output),
+    }
+}
+
+/// `Err(Cancelled)` once `token` is cancelled, for checks between steps.
+pub fn check(token: &CancellationToken) -> Result<(), ServiceError> {
+    if token.is_cancelled() {
+        Err(ServiceError::Cancelled)
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_cancellable_returns_output_when_not_cancelled() {
+        let token = CancellationToken::new();
+        assert_eq!(cancellable(&token, async { 7 }).await.unwrap(), 7);
+        assert!(check(&token).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_cancellable_stops_pending_future() {
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            canceller.cancel();
+        });
+        let result = cancellable(&token, std::future::pending::<()>()).await;
+        assert!(matches!(result, Err(ServiceError::Cancelled)));
+        assert!(matches!(check(&token), Err(ServiceError::Cancelled)));
+    }
+}
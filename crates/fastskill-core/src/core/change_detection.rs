@@ -1,6 +1,7 @@
 //! Change detection for skills using git and file hashing
 
 use crate::core::build_cache::BuildCache;
+use crate::core::scope::skill_dirs;
 use crate::core::service::ServiceError;
 use std::collections::HashSet;
 use std::path::Path;
@@ -95,37 +96,25 @@ pub fn detect_changed_skills_hash(
 
     let mut changed_skills = Vec::new();
 
-    // Scan for skill directories
-    let entries = std::fs::read_dir(skills_dir).map_err(ServiceError::Io)?;
-
-    for entry in entries {
-        let entry = entry.map_err(ServiceError::Io)?;
-        let path = entry.path();
-
-        if path.is_dir() {
-            if let Some(skill_id) = path.file_name().and_then(|n| n.to_str()) {
-                // Check if SKILL.md exists (indicates this is a skill directory)
-                if path.join("SKILL.md").exists() {
-                    let current_hash = calculate_skill_hash(&path)?;
-
-                    // Check if hash changed
-                    let cached_hash = cache.get_cached_hash(skill_id);
-
-                    if cached_hash
-                        .as_ref()
-                        .map(|h| h != &current_hash)
-                        .unwrap_or(true)
-                    {
-                        debug!(
-                            "Skill '{}' changed (hash: {} -> {})",
-                            skill_id,
-                            cached_hash.as_deref().unwrap_or("none"),
-                            &current_hash
-                        );
-                        changed_skills.push(skill_id.to_string());
-                    }
-                }
-            }
+    // Scan for skill directories (including those in scope directories)
+    for (skill_id, path) in skill_dirs(skills_dir).map_err(ServiceError::Io)? {
+        let current_hash = calculate_skill_hash(&path)?;
+
+        // Check if hash changed
+        let cached_hash = cache.get_cached_hash(&skill_id);
+
+        if cached_hash
+            .as_ref()
+            .map(|h| h != &current_hash)
+            .unwrap_or(true)
+        {
+            debug!(
+                "Skill '{}' changed (hash: {} -> {})",
+                skill_id,
+                cached_hash.as_deref().unwrap_or("none"),
+                &current_hash
+            );
+            changed_skills.push(skill_id);
         }
     }
 
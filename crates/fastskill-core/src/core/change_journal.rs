@@ -0,0 +1,331 @@
+//! Append-only journal of skill content changes for incremental sync
+//!
+//! `<skills dir>/.fastskill/changes.jsonl` holds one JSON object per line: a
+//! skill added, updated (its [`calculate_skill_hash`] hash changed) or
+//! removed, numbered by a sequence that only grows. [`ChangeJournal::sync`]
+//! compares the skills directory with the state the journal describes and
+//! appends what differs, so the journal stays correct however a skill was
+//! changed (API, CLI, hot reload, or an editor). Clients keep the highest
+//! sequence they have seen as a cursor and ask only for what came after it.
+
+use crate::core::change_detection::calculate_skill_hash;
+use crate::core::replication::local_skill_ids;
+use crate::core::service::ServiceError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// What happened to a skill
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Updated,
+    Removed,
+}
+
+/// One journaled change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeEntry {
+    pub seq: u64,
+    pub timestamp: DateTime<Utc>,
+    pub skill_id: String,
+    pub kind: ChangeKind,
+    /// Content hash after the change; absent for removals
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+}
+
+/// A skill that is new or changed since a cursor, with its current hash
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedSkill {
+    pub id: String,
+    pub hash: String,
+}
+
+/// Net changes since a cursor: a skill added and then removed in the window
+/// appears in neither list; one added and then edited is only `added`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangeSet {
+    pub added: Vec<ChangedSkill>,
+    pub updated: Vec<ChangedSkill>,
+    pub removed: Vec<String>,
+    /// Sequence of the newest entry; pass it back as the next `since`
+    pub cursor: u64,
+}
+
+/// Change journal file handle. Syncs are serialized within the process.
+#[derive(Debug)]
+pub struct ChangeJournal {
+    path: PathBuf,
+    write_lock: Mutex<()>,
+}
+
+impl ChangeJournal {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// Default location next to the audit log: `<skills dir>/.fastskill/changes.jsonl`.
+    pub fn default_path(skills_dir: &Path) -> PathBuf {
+        skills_dir.join(".fastskill").join("changes.jsonl")
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Journal every difference between `skills_dir` and the journaled state.
+    /// Returns the appended entries.
+    pub fn sync(&self, skills_dir: &Path) -> Result<Vec<ChangeEntry>, ServiceError> {
+        let mut current = BTreeMap::new();
+        for id in local_skill_ids(skills_dir)? {
+            let hash = calculate_skill_hash(&skills_dir.join(&id))?;
+            current.insert(id, hash);
+        }
+
+        let _guard = match self.write_lock.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let entries = self.read()?;
+        let known = replay(&entries, u64::MAX);
+        let mut seq = entries.last().map_or(0, |e| e.seq);
+        let timestamp = Utc::now();
+        let mut next = |skill_id: &str, kind: ChangeKind, hash: Option<&String>| {
+            seq += 1;
+            ChangeEntry {
+                seq,
+                timestamp,
+                skill_id: skill_id.to_string(),
+                kind,
+                hash: hash.cloned(),
+            }
+        };
+
+        let mut appended = Vec::new();
+        for (id, hash) in &current {
+            match known.get(id) {
+                None => appended.push(next(id, ChangeKind::Added, Some(hash))),
+                Some(old) if old != hash => {
+                    appended.push(next(id, ChangeKind::Updated, Some(hash)))
+                }
+                Some(_) => {}
+            }
+        }
+        for id in known.keys().filter(|id| !current.contains_key(*id)) {
+            appended.push(next(id, ChangeKind::Removed, None));
+        }
+        if !appended.is_empty() {
+            self.append(&appended)?;
+        }
+        Ok(appended)
+    }
+
+    /// Net changes after sequence `since` (0 for everything). A cursor beyond
+    /// the newest entry means the journal was reset; the client must start
+    /// over from 0.
+    pub fn changes_since(&self, since: u64) -> Result<ChangeSet, ServiceError> {
+        let entries = self.read()?;
+        let cursor = entries.last().map_or(0, |e| e.seq);
+        if since > cursor {
+            return Err(ServiceError::Validation(format!(
+                "Unknown cursor {}: the change journal ends at {}; resync with since=0",
+                since, cursor
+            )));
+        }
+
+        let before = replay(&entries, since);
+        let after = replay(&entries, u64::MAX);
+        let mut changes = ChangeSet {
+            cursor,
+            ..Default::default()
+        };
+        let mut touched: Vec<&str> = entries
+            .iter()
+            .filter(|e| e.seq > since)
+            .map(|e| e.skill_id.as_str())
+            .collect();
+        touched.sort_unstable();
+        touched.dedup();
+        for id in touched {
+            match (before.get(id), after.get(id)) {
+                (None, Some(hash)) => changes.added.push(ChangedSkill {
+                    id: id.to_string(),
+                    hash: hash.clone(),
+                }),
+                (Some(old), Some(hash)) if old != hash => changes.updated.push(ChangedSkill {
+                    id: id.to_string(),
+                    hash: hash.clone(),
+                }),
+                (Some(_), None) => changes.removed.push(id.to_string()),
+                _ => {}
+            }
+        }
+        Ok(changes)
+    }
+
+    /// All entries, oldest first. A missing file is an empty journal;
+    /// unparseable lines (e.g. a torn final line after a crash) are skipped.
+    fn read(&self) -> Result<Vec<ChangeEntry>, ServiceError> {
+        let file = match std::fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut entries = Vec::new();
+        for (index, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<ChangeEntry>(&line) {
+                Ok(entry) => entries.push(entry),
+                Err(e) => tracing::warn!(
+                    "Skipping malformed change entry at {}:{}: {}",
+                    self.path.display(),
+                    index + 1,
+                    e
+                ),
+            }
+        }
+        Ok(entries)
+    }
+
+    fn append(&self, entries: &[ChangeEntry]) -> Result<(), ServiceError> {
+        let mut lines = String::new();
+        for entry in entries {
+            lines.push_str(&serde_json::to_string(entry).map_err(|e| {
+                ServiceError::Custom(format!("Failed to encode change entry: {}", e))
+            })?);
+            lines.push('\n');
+        }
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(lines.as_bytes())?;
+        file.sync_data()?;
+        Ok(())
+    }
+}
+
+/// Skill id to hash as of sequence `until` (inclusive)
+fn replay(entries: &[ChangeEntry], until: u64) -> BTreeMap<String, String> {
+    let mut state = BTreeMap::new();
+    for entry in entries.iter().take_while(|e| e.seq <= until) {
+        match (&entry.kind, &entry.hash) {
+            (ChangeKind::Removed, _) => {
+                state.remove(&entry.skill_id);
+            }
+            (_, Some(hash)) => {
+                state.insert(entry.skill_id.clone(), hash.clone());
+            }
+            (_, None) => {}
+        }
+    }
+    state
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_skill(skills_dir: &Path, id: &str, body: &str) {
+        let dir = skills_dir.join(id);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("SKILL.md"),
+            format!("---\nname: {id}\ndescription: Test\n---\n{body}\n"),
+        )
+        .unwrap();
+    }
+
+    fn ids(skills: &[ChangedSkill]) -> Vec<&str> {
+        skills.iter().map(|s| s.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sync_journals_added_updated_removed() {
+        let temp_dir = TempDir::new().unwrap();
+        let skills_dir = temp_dir.path();
+        let journal = ChangeJournal::new(ChangeJournal::default_path(skills_dir));
+        write_skill(skills_dir, "alpha", "one");
+        write_skill(skills_dir, "beta", "one");
+
+        let first = journal.sync(skills_dir).unwrap();
+        assert_eq!(first.len(), 2);
+        assert!(first.iter().all(|e| e.kind == ChangeKind::Added));
+        // Nothing changed: nothing journaled, and the journal itself is not a skill
+        assert!(journal.sync(skills_dir).unwrap().is_empty());
+
+        let cursor = journal.changes_since(0).unwrap().cursor;
+        assert_eq!(cursor, 2);
+        write_skill(skills_dir, "alpha", "two");
+        std::fs::remove_dir_all(skills_dir.join("beta")).unwrap();
+        write_skill(skills_dir, "gamma", "one");
+        journal.sync(skills_dir).unwrap();
+
+        let changes = journal.changes_since(cursor).unwrap();
+        assert_eq!(ids(&changes.added), ["gamma"]);
+        assert_eq!(ids(&changes.updated), ["alpha"]);
+        assert_eq!(changes.removed, ["beta"]);
+        assert_eq!(changes.cursor, 5);
+        assert_eq!(
+            changes.updated[0].hash,
+            calculate_skill_hash(&skills_dir.join("alpha")).unwrap()
+        );
+        assert_eq!(
+            journal.changes_since(5).unwrap(),
+            ChangeSet {
+                cursor: 5,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_changes_since_collapses_entries_in_window() {
+        let temp_dir = TempDir::new().unwrap();
+        let skills_dir = temp_dir.path();
+        let journal = ChangeJournal::new(ChangeJournal::default_path(skills_dir));
+        write_skill(skills_dir, "alpha", "one");
+        journal.sync(skills_dir).unwrap();
+
+        write_skill(skills_dir, "beta", "one");
+        journal.sync(skills_dir).unwrap();
+        write_skill(skills_dir, "beta", "two");
+        write_skill(skills_dir, "temp", "one");
+        journal.sync(skills_dir).unwrap();
+        std::fs::remove_dir_all(skills_dir.join("temp")).unwrap();
+        journal.sync(skills_dir).unwrap();
+
+        let changes = journal.changes_since(1).unwrap();
+        assert_eq!(ids(&changes.added), ["beta"]);
+        assert!(changes.updated.is_empty());
+        assert!(changes.removed.is_empty());
+
+        let everything = journal.changes_since(0).unwrap();
+        assert_eq!(ids(&everything.added), ["alpha", "beta"]);
+    }
+
+    #[test]
+    fn test_changes_since_rejects_unknown_cursor() {
+        let temp_dir = TempDir::new().unwrap();
+        let journal = ChangeJournal::new(temp_dir.path().join("changes.jsonl"));
+        let err = journal.changes_since(3).unwrap_err();
+        assert!(err.to_string().contains("since=0"), "{}", err);
+    }
+}
@@ -0,0 +1,276 @@
+//! Fault injection for resilience testing (`chaos` feature)
+//!
+//! Applications embedding fastskill can turn on realistic failures to check
+//! their retry and fallback handling: slow or failing embedding calls,
+//! downloads that drop, archives whose entries arrive corrupted, and
+//! registry or repository servers answering `500`. Faults are off until
+//! [`configure`] is called and stay on for the whole process until
+//! [`reset`]. Without the feature none of the hooks are compiled in.
+//!
+//! ```ignore
+//! fastskill_core::core::chaos::configure(ChaosConfig {
+//!     upstream_error_rate: 0.5,
+//!     seed: Some(7),
+//!     ..Default::default()
+//! })?;
+//! ```
+
+use crate::core::service::ServiceError;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Faults to inject. Rates are probabilities from 0.0 (never) to 1.0 (always).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChaosConfig {
+    /// Added before every embedding API call
+    pub embedding_delay_ms: u64,
+    /// Embedding API calls that fail after the delay
+    pub embedding_failure_rate: f64,
+    /// Package downloads that fail as if the connection dropped
+    pub download_failure_rate: f64,
+    /// Downloaded archives with one entry's data corrupted
+    pub corrupt_zip_rate: f64,
+    /// Registry and repository responses replaced by `500 Internal Server Error`
+    pub upstream_error_rate: f64,
+    /// Seed for reproducible runs; the clock is used when absent
+    pub seed: Option<u64>,
+}
+
+impl ChaosConfig {
+    fn validate(&self) -> Result<(), ServiceError> {
+        for (name, rate) in [
+            ("embedding_failure_rate", self.embedding_failure_rate),
+            ("download_failure_rate", self.download_failure_rate),
+            ("corrupt_zip_rate", self.corrupt_zip_rate),
+            ("upstream_error_rate", self.upstream_error_rate),
+        ] {
+            if !(0.0..=1.0).contains(&rate) {
+                return Err(ServiceError::Validation(format!(
+                    "Chaos {} must be between 0.0 and 1.0, got {}",
+                    name, rate
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Kind of injected fault
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    EmbeddingFailure,
+    DownloadFailure,
+    CorruptZip,
+    UpstreamError,
+}
+
+/// A configuration and the random stream deciding which calls fail
+#[derive(Debug)]
+struct Injector {
+    config: ChaosConfig,
+    state: u64,
+}
+
+impl Injector {
+    fn new(config: ChaosConfig) -> Self {
+        let state = config.seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_nanos() as u64)
+        });
+        Self { config, state }
+    }
+
+    /// splitmix64: small, fast, and good enough to pick failures
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn roll(&mut self, fault: Fault) -> bool {
+        let rate = match fault {
+            Fault::EmbeddingFailure => self.config.embedding_failure_rate,
+            Fault::DownloadFailure => self.config.download_failure_rate,
+            Fault::CorruptZip => self.config.corrupt_zip_rate,
+            Fault::UpstreamError => self.config.upstream_error_rate,
+        };
+        if rate <= 0.0 {
+            return false;
+        }
+        // 53 random bits as a float in [0, 1)
+        let sample = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        sample < rate
+    }
+}
+
+fn injector() -> &'static Mutex<Option<Injector>> {
+    static INJECTOR: OnceLock<Mutex<Option<Injector>>> = OnceLock::new();
+    INJECTOR.get_or_init(|| Mutex::new(None))
+}
+
+fn with_injector<T>(f: impl FnOnce(&mut Option<Injector>) -> T) -> T {
+    let mut guard = match injector().lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    f(&mut guard)
+}
+
+/// Start injecting the faults in `config`, replacing any earlier configuration.
+pub fn configure(config: ChaosConfig) -> Result<(), ServiceError> {
+    config.validate()?;
+    tracing::warn!("Chaos fault injection enabled: {:?}", config);
+    with_injector(|injector| *injector = Some(Injector::new(config)));
+    Ok(())
+}
+
+/// Stop injecting faults.
+pub fn reset() {
+    with_injector(|injector| *injector = None);
+}
+
+/// The active configuration, if any
+pub fn current() -> Option<ChaosConfig> {
+    with_injector(|injector| injector.as_ref().map(|i| i.config.clone()))
+}
+
+/// Whether to inject `fault` on this call
+pub fn should_inject(fault: Fault) -> bool {
+    let injected = with_injector(|injector| injector.as_mut().is_some_and(|i| i.roll(fault)));
+    if injected {
+        tracing::debug!("Injecting chaos fault {:?}", fault);
+    }
+    injected
+}
+
+/// Delay an embedding call, then possibly fail it.
+pub(crate) async fn embedding_fault() -> Result<(), ServiceError> {
+    let delay =
+        with_injector(|injector| injector.as_ref().map_or(0, |i| i.config.embedding_delay_ms));
+    if delay > 0 {
+        tokio::time::sleep(Duration::from_millis(delay)).await;
+    }
+    if should_inject(Fault::EmbeddingFailure) {
+        return Err(ServiceError::Custom(
+            "OpenAI API request failed: connection reset (injected fault)".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// `status`, or `500 Internal Server Error` when an upstream fault is injected
+pub(crate) fn upstream_status(status: StatusCode) -> StatusCode {
+    if should_inject(Fault::UpstreamError) {
+        StatusCode::INTERNAL_SERVER_ERROR
+    } else {
+        status
+    }
+}
+
+/// Maybe corrupt the data of one entry in a downloaded ZIP archive
+pub(crate) fn maybe_corrupt_zip(bytes: &mut [u8]) {
+    if should_inject(Fault::CorruptZip) {
+        corrupt_first_entry(bytes);
+    }
+}
+
+/// Flip the first data byte of the first non-empty local file entry, so the
+/// archive still opens but that entry fails its CRC or decompression.
+fn corrupt_first_entry(bytes: &mut [u8]) {
+    const LOCAL_HEADER: [u8; 4] = [0x50, 0x4b, 0x03, 0x04];
+    const HEADER_LEN: usize = 30;
+    let u16_at = |b: &[u8], at: usize| usize::from(u16::from_le_bytes([b[at], b[at + 1]]));
+
+    let mut offset = 0;
+    while offset + HEADER_LEN <= bytes.len() && bytes[offset..offset + 4] == LOCAL_HEADER {
+        let compressed = u32::from_le_bytes([
+            bytes[offset + 18],
+            bytes[offset + 19],
+            bytes[offset + 20],
+            bytes[offset + 21],
+        ]) as usize;
+        let data = offset + HEADER_LEN + u16_at(bytes, offset + 26) + u16_at(bytes, offset + 28);
+        if compressed > 0 && data < bytes.len() {
+            bytes[data] ^= 0xff;
+            return;
+        }
+        offset = data + compressed;
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::storage::zip::ZipHandler;
+    use std::io::{Cursor, Write};
+    use tempfile::TempDir;
+    use zip::write::FileOptions;
+    use zip::ZipWriter;
+
+    fn rolls(config: ChaosConfig, fault: Fault, n: usize) -> Vec<bool> {
+        let mut injector = Injector::new(config);
+        (0..n).map(|_| injector.roll(fault)).collect()
+    }
+
+    #[test]
+    fn test_rates_zero_and_one_are_exact() {
+        let config = ChaosConfig {
+            upstream_error_rate: 1.0,
+            ..Default::default()
+        };
+        assert!(rolls(config.clone(), Fault::UpstreamError, 100)
+            .iter()
+            .all(|&b| b));
+        assert!(!rolls(config, Fault::DownloadFailure, 100)
+            .iter()
+            .any(|&b| b));
+    }
+
+    #[test]
+    fn test_seeded_runs_repeat() {
+        let config = ChaosConfig {
+            download_failure_rate: 0.5,
+            seed: Some(42),
+            ..Default::default()
+        };
+        let first = rolls(config.clone(), Fault::DownloadFailure, 200);
+        assert_eq!(first, rolls(config, Fault::DownloadFailure, 200));
+        let failures = first.iter().filter(|&&b| b).count();
+        assert!((60..140).contains(&failures), "{}", failures);
+    }
+
+    #[test]
+    fn test_configure_rejects_out_of_range_rate() {
+        let err = configure(ChaosConfig {
+            corrupt_zip_rate: 1.5,
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("corrupt_zip_rate"), "{}", err);
+    }
+
+    #[test]
+    fn test_corrupted_archive_fails_extraction() {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = FileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        zip.add_directory("skill/", options).unwrap();
+        zip.start_file("skill/SKILL.md", options).unwrap();
+        zip.write_all(b"---\nname: skill\n---\n").unwrap();
+        let mut bytes = zip.finish().unwrap().into_inner();
+
+        corrupt_first_entry(&mut bytes);
+
+        let temp_dir = TempDir::new().unwrap();
+        let result = ZipHandler::new()
+            .unwrap()
+            .extract_reader_to_dir(Cursor::new(bytes), temp_dir.path());
+        assert!(result.is_err());
+    }
+}
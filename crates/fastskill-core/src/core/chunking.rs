@@ -0,0 +1,242 @@
+//! Section chunks of a skill for multi-vector embedding
+//!
+//! A skill's own embedding covers only its name and description, which says
+//! little about the details of a long SKILL.md. With `chunk_size` set in
+//! `[tool.fastskill.embedding]`, reindex also embeds the SKILL.md body and the
+//! text files under `references/` section by section; search then scores a
+//! skill by its best-matching vector (max-sim).
+
+use crate::core::service::ServiceError;
+use std::path::{Path, PathBuf};
+
+/// Upper bound on chunk vectors stored per skill, so one huge skill cannot
+/// dominate embedding cost or search time
+pub const MAX_CHUNKS_PER_SKILL: usize = 64;
+
+/// Extensions of `references/` files that are chunked
+const REFERENCE_EXTENSIONS: &[&str] = &["md", "markdown", "txt"];
+
+/// Chunks of a skill: its SKILL.md body, then each reference file in path
+/// order, capped at [`MAX_CHUNKS_PER_SKILL`]. Each chunk is prefixed with the
+/// skill name (and file, for references) so it embeds in context.
+pub fn skill_chunks(
+    skill_dir: &Path,
+    skill_md: &str,
+    name: &str,
+    chunk_size: usize,
+) -> Result<Vec<String>, ServiceError> {
+    let mut chunks: Vec<String> = split_markdown(strip_frontmatter(skill_md), chunk_size)
+        .into_iter()
+        .map(|chunk| format!("{}\n{}", name, chunk))
+        .collect();
+
+    for file in reference_files(skill_dir) {
+        if chunks.len() >= MAX_CHUNKS_PER_SKILL {
+            break;
+        }
+        let Ok(text) = std::fs::read_to_string(&file) else {
+            tracing::debug!("Skipping non-UTF-8 reference {}", file.display());
+            continue;
+        };
+        let label = file.strip_prefix(skill_dir).unwrap_or(&file).display();
+        chunks.extend(
+            split_markdown(&text, chunk_size)
+                .into_iter()
+                .map(|chunk| format!("{} ({})\n{}", name, label, chunk)),
+        );
+    }
+
+    chunks.truncate(MAX_CHUNKS_PER_SKILL);
+    Ok(chunks)
+}
+
+/// Text files under the skill's `references/` directory, in path order.
+/// Symlinks are not followed.
+pub(crate) fn reference_files(skill_dir: &Path) -> Vec<PathBuf> {
+    let references = skill_dir.join("references");
+    if !references.is_dir() {
+        return Vec::new();
+    }
+    let mut files: Vec<PathBuf> = walkdir::WalkDir::new(&references)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            e.path()
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| REFERENCE_EXTENSIONS.contains(&ext))
+        })
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+    files
+}
+
+/// Split Markdown into chunks of at most `chunk_size` characters. Headings
+/// (outside code fences) start a new section; a section is packed paragraph
+/// by paragraph, and each of its chunks repeats the heading. A paragraph
+/// longer than `chunk_size` is cut at whitespace.
+pub fn split_markdown(text: &str, chunk_size: usize) -> Vec<String> {
+    let chunk_size = chunk_size.max(1);
+    let mut chunks = Vec::new();
+    for (heading, body) in sections(text) {
+        let budget = chunk_size
+            .saturating_sub(heading.chars().count() + 1)
+            .max(1);
+        let mut current = String::new();
+        for paragraph in body.split("\n\n").map(str::trim).filter(|p| !p.is_empty()) {
+            for piece in cut(paragraph, budget) {
+                let len = current.chars().count();
+                if len > 0 && len + 2 + piece.chars().count() > budget {
+                    chunks.push(with_heading(heading, &current));
+                    current.clear();
+                }
+                if !current.is_empty() {
+                    current.push_str("\n\n");
+                }
+                current.push_str(piece);
+            }
+        }
+        if !current.is_empty() {
+            chunks.push(with_heading(heading, &current));
+        } else if !heading.is_empty() && body.trim().is_empty() {
+            // A bare heading still says what the section is about
+            chunks.push(heading.to_string());
+        }
+    }
+    chunks
+}
+
+fn with_heading(heading: &str, text: &str) -> String {
+    if heading.is_empty() {
+        text.to_string()
+    } else {
+        format!("{}\n{}", heading, text)
+    }
+}
+
+/// `(heading, body)` pairs; text before the first heading has an empty heading.
+fn sections(text: &str) -> Vec<(&str, String)> {
+    let mut sections = vec![("", String::new())];
+    let mut in_fence = false;
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        } else if !in_fence && is_heading(trimmed) {
+            sections.push((trimmed.trim_end(), String::new()));
+            continue;
+        }
+        if let Some((_, body)) = sections.last_mut() {
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+    sections.retain(|(heading, body)| !heading.is_empty() || !body.trim().is_empty());
+    sections
+}
+
+fn is_heading(line: &str) -> bool {
+    let hashes = line.chars().take_while(|&c| c == '#').count();
+    (1..=6).contains(&hashes) && line[hashes..].starts_with(' ')
+}
+
+/// Pieces of `text` of at most `max` characters, cut at whitespace where
+/// possible.
+fn cut(text: &str, max: usize) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut rest = text;
+    while rest.chars().count() > max {
+        let limit = rest.char_indices().nth(max).map_or(rest.len(), |(i, _)| i);
+        let at = rest[..limit]
+            .rfind(char::is_whitespace)
+            .filter(|&i| i > 0)
+            .unwrap_or(limit);
+        pieces.push(rest[..at].trim_end());
+        rest = rest[at..].trim_start();
+    }
+    if !rest.is_empty() {
+        pieces.push(rest);
+    }
+    pieces
+}
+
+/// SKILL.md content after its YAML frontmatter (all of it when there is none).
+pub(crate) fn strip_frontmatter(content: &str) -> &str {
+    let Some(rest) = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    else {
+        return content;
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        offset += line.len();
+        if line.trim_end() == "---" {
+            return &rest[offset..];
+        }
+    }
+    content
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_split_markdown_by_heading_and_size() {
+        let text = "Intro line.\n\n# Install\n\nRun pip install.\n\n```\n# not a heading\n```\n\n## Usage\n\nalpha beta gamma delta epsilon\n";
+        let chunks = split_markdown(text, 1000);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0], "Intro line.");
+        assert!(chunks[1].starts_with("# Install\n"));
+        assert!(chunks[1].contains("# not a heading"));
+        assert_eq!(chunks[2], "## Usage\nalpha beta gamma delta epsilon");
+
+        // Small chunks cut long paragraphs at whitespace and repeat the heading
+        let chunks = split_markdown("## Usage\n\nalpha beta gamma delta epsilon\n", 20);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.starts_with("## Usage\n"), "{:?}", chunk);
+            assert!(chunk.chars().count() <= 20, "{:?}", chunk);
+        }
+        let words: Vec<&str> = chunks
+            .iter()
+            .flat_map(|c| c.trim_start_matches("## Usage\n").split_whitespace())
+            .collect();
+        assert_eq!(words, ["alpha", "beta", "gamma", "delta", "epsilon"]);
+    }
+
+    #[test]
+    fn test_skill_chunks_cover_body_and_references() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("references")).unwrap();
+        fs::write(
+            dir.path().join("references/api.md"),
+            "# Endpoints\n\nGET /items",
+        )
+        .unwrap();
+        fs::write(dir.path().join("references/logo.png"), [0u8, 1, 2]).unwrap();
+        let skill_md = "---\nname: shop\ndescription: d\n---\n# Shop\n\nBuy things.\n";
+
+        let chunks = skill_chunks(dir.path(), skill_md, "shop", 500).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0], "shop\n# Shop\nBuy things.");
+        assert!(!chunks[0].contains("description"));
+        assert!(chunks[1].starts_with("shop (references"));
+        assert!(chunks[1].ends_with("# Endpoints\nGET /items"));
+    }
+
+    #[test]
+    fn test_skill_chunks_are_capped() {
+        let dir = TempDir::new().unwrap();
+        let body: String = (0..200).map(|i| format!("# S{}\n\ntext\n\n", i)).collect();
+        let chunks = skill_chunks(dir.path(), &body, "big", 100).unwrap();
+        assert_eq!(chunks.len(), MAX_CHUNKS_PER_SKILL);
+    }
+}
@@ -0,0 +1,134 @@
+//! Skill file content as shared bytes
+//!
+//! Skill files are read once into a [`Bytes`] buffer and handed between
+//! storage, the context resolver and the HTTP layer without being copied
+//! into fresh `String`s at each step. UTF-8 is only checked when text is
+//! actually needed ([`SkillContent::to_str`], [`SkillContent::to_str_lossy`]);
+//! serving a file as-is never validates or re-encodes it.
+
+use bytes::Bytes;
+use std::borrow::Cow;
+use std::path::Path;
+
+/// Contents of a skill file. Cloning shares the buffer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SkillContent {
+    bytes: Bytes,
+}
+
+impl SkillContent {
+    /// Read `path` into a single buffer.
+    pub async fn read(path: &Path) -> std::io::Result<Self> {
+        Ok(Self::from(tokio::fs::read(path).await?))
+    }
+
+    /// Read at most `limit` bytes from the start of `path`.
+    pub async fn read_prefix(path: &Path, limit: usize) -> std::io::Result<Self> {
+        use tokio::io::AsyncReadExt;
+
+        let file = tokio::fs::File::open(path).await?;
+        let mut buf = Vec::with_capacity(limit.min(64 * 1024));
+        file.take(limit as u64).read_to_end(&mut buf).await?;
+        Ok(Self::from(buf))
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// The underlying buffer (a reference-counted handle, not a copy).
+    pub fn bytes(&self) -> Bytes {
+        self.bytes.clone()
+    }
+
+    /// The content as text, validating UTF-8 now.
+    pub fn to_str(&self) -> Result<&str, std::str::Utf8Error> {
+        std::str::from_utf8(&self.bytes)
+    }
+
+    /// The content as text, borrowed when it is valid UTF-8 and with invalid
+    /// sequences replaced otherwise.
+    pub fn to_str_lossy(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.bytes)
+    }
+
+    /// Owned text. Reuses the buffer when nothing else shares it.
+    pub fn into_string(self) -> Result<String, std::string::FromUtf8Error> {
+        String::from_utf8(Vec::from(self.bytes))
+    }
+}
+
+impl From<Bytes> for SkillContent {
+    fn from(bytes: Bytes) -> Self {
+        Self { bytes }
+    }
+}
+
+impl From<Vec<u8>> for SkillContent {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self {
+            bytes: Bytes::from(bytes),
+        }
+    }
+}
+
+impl From<String> for SkillContent {
+    fn from(text: String) -> Self {
+        Self {
+            bytes: Bytes::from(text),
+        }
+    }
+}
+
+impl AsRef<[u8]> for SkillContent {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+/// Serialized as a string; invalid UTF-8 is replaced rather than failing the
+/// whole response.
+impl serde::Serialize for SkillContent {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_str_lossy())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_read_and_prefix() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("SKILL.md");
+        std::fs::write(&path, "---\nname: demo\n---\nBody\n").unwrap();
+
+        let content = SkillContent::read(&path).await.unwrap();
+        assert_eq!(content.to_str().unwrap(), "---\nname: demo\n---\nBody\n");
+        assert!(matches!(content.to_str_lossy(), Cow::Borrowed(_)));
+
+        let prefix = SkillContent::read_prefix(&path, 3).await.unwrap();
+        assert_eq!(prefix.as_bytes(), b"---");
+    }
+
+    #[test]
+    fn test_invalid_utf8_is_only_rejected_when_text_is_needed() {
+        let content = SkillContent::from(vec![b'o', b'k', 0xff]);
+        assert_eq!(content.len(), 3);
+        assert!(content.to_str().is_err());
+        assert_eq!(content.to_str_lossy(), "ok\u{fffd}");
+        assert_eq!(serde_json::to_string(&content).unwrap(), "\"ok\u{fffd}\"");
+        assert!(content.into_string().is_err());
+    }
+}
@@ -1,7 +1,10 @@
+use crate::core::content::SkillContent;
 use crate::core::embedding::EmbeddingService;
+use crate::core::integrity::IntegrityGuard;
+use crate::core::loading::{LoadingLevel, SkillLoader};
 use crate::core::metadata::MetadataService;
 use crate::core::service::{EmbeddingConfig, ServiceError, SkillId};
-use crate::core::skill_manager::SkillManagementService;
+use crate::core::skill_manager::{SkillDefinition, SkillManagementService};
 use crate::core::vector_index::VectorIndexService;
 use crate::security::path::validate_path_within_root;
 use serde::{Deserialize, Serialize};
@@ -11,13 +14,13 @@ use std::sync::Arc;
 const MAX_CONTENT_SIZE: u64 = 512_000;
 const PREVIEW_BODY_LINES: usize = 20;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ResolveScope {
     Local,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default, utoipa::ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ContentMode {
     #[default]
@@ -26,7 +29,7 @@ pub enum ContentMode {
     Full,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct ResolveContextRequest {
     pub prompt: String,
     pub limit: usize,
@@ -35,13 +38,18 @@ pub struct ResolveContextRequest {
     pub include_content: ContentMode,
     #[serde(default = "default_true")]
     pub resolve_paths: bool,
+    /// Pack the results into this many estimated tokens of skill content,
+    /// each at the richest loading level that fits; `include_content` is
+    /// then ignored
+    #[serde(default)]
+    pub max_tokens: Option<usize>,
 }
 
 fn default_true() -> bool {
     true
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct ResolvedSkill {
     pub skill_id: String,
     pub name: String,
@@ -53,14 +61,26 @@ pub struct ResolvedSkill {
     pub assets_dir_path: Option<String>,
     pub content_preview: Option<String>,
     pub content_full: Option<String>,
+    /// With `max_tokens`: the level the skill was loaded at
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loading_level: Option<LoadingLevel>,
+    /// With `max_tokens`: the loaded content
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// With `max_tokens`: estimated tokens of `content`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokens: Option<usize>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct ResolveContextResponse {
     pub query: String,
     pub scope: ResolveScope,
     pub results: Vec<ResolvedSkill>,
     pub allowed_roots: Vec<String>,
+    /// With `max_tokens`: estimated tokens of all loaded content
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_tokens: Option<usize>,
 }
 
 pub struct ContextResolver {
@@ -69,6 +89,7 @@ pub struct ContextResolver {
     vector_index_service: Option<Arc<dyn VectorIndexService>>,
     embedding_config: Option<EmbeddingConfig>,
     skills_root: PathBuf,
+    integrity: Option<Arc<IntegrityGuard>>,
 }
 
 impl ContextResolver {
@@ -85,9 +106,17 @@ impl ContextResolver {
             vector_index_service,
             embedding_config,
             skills_root,
+            integrity: None,
         }
     }
 
+    /// Verify every SKILL.md read for content against `guard`, so tampered
+    /// skills fail with [`ServiceError::Integrity`] instead of being served.
+    pub fn with_integrity_guard(mut self, guard: Arc<IntegrityGuard>) -> Self {
+        self.integrity = Some(guard);
+        self
+    }
+
     pub async fn resolve_context(
         &self,
         request: ResolveContextRequest,
@@ -104,6 +133,17 @@ impl ContextResolver {
             ));
         }
 
+        if request.max_tokens == Some(0) {
+            return Err(ServiceError::Validation(
+                "RESOLVE_BUDGET_ZERO: max_tokens must be greater than 0".to_string(),
+            ));
+        }
+        let content_mode = if request.max_tokens.is_some() {
+            ContentMode::None
+        } else {
+            request.include_content.clone()
+        };
+
         let search_results = self.perform_search(&request).await?;
 
         let mut resolved = Vec::new();
@@ -132,29 +172,17 @@ impl ContextResolver {
                 }
             };
 
-            let (skill_md_path, skill_root_path, references_dir_path, assets_dir_path) =
-                if request.resolve_paths {
-                    self.resolve_paths(&skill_def.skill_file)?
-                } else {
-                    (None, None, None, None)
-                };
-
-            let (content_preview, content_full) = self
-                .read_content(&skill_def.skill_file, &request.include_content)
-                .await?;
-
-            resolved.push(ResolvedSkill {
-                skill_id: skill_id_str,
-                name,
-                description,
-                score,
-                skill_md_path,
-                skill_root_path,
-                references_dir_path,
-                assets_dir_path,
-                content_preview,
-                content_full,
-            });
+            resolved.push(
+                self.resolved_skill(
+                    &skill_def,
+                    name,
+                    description,
+                    score,
+                    request.resolve_paths,
+                    &content_mode,
+                )
+                .await?,
+            );
 
             if resolved.len() >= request.limit {
                 break;
@@ -163,14 +191,98 @@ impl ContextResolver {
 
         let allowed_roots = vec![self.skills_root.to_string_lossy().to_string()];
 
-        Ok(ResolveContextResponse {
+        let mut response = ResolveContextResponse {
             query: request.prompt,
             scope: request.scope,
             results: resolved,
             allowed_roots,
+            total_tokens: None,
+        };
+        if let Some(max_tokens) = request.max_tokens {
+            self.apply_budget(&mut response, max_tokens).await?;
+        }
+        Ok(response)
+    }
+
+    /// The result for `skill_def`, with its paths and content as requested.
+    /// Also used for skills that are injected without a search match (see
+    /// [`crate::core::skill_injection`]).
+    pub async fn resolved_skill(
+        &self,
+        skill_def: &SkillDefinition,
+        name: String,
+        description: String,
+        score: f32,
+        resolve_paths: bool,
+        content_mode: &ContentMode,
+    ) -> Result<ResolvedSkill, ServiceError> {
+        let (skill_md_path, skill_root_path, references_dir_path, assets_dir_path) =
+            if resolve_paths {
+                self.resolve_paths(&skill_def.skill_file)?
+            } else {
+                (None, None, None, None)
+            };
+
+        let (content_preview, content_full) = self.read_content(skill_def, content_mode).await?;
+
+        Ok(ResolvedSkill {
+            skill_id: skill_def.id.to_string(),
+            name,
+            description,
+            score,
+            skill_md_path,
+            skill_root_path,
+            references_dir_path,
+            assets_dir_path,
+            content_preview,
+            content_full,
+            loading_level: None,
+            content: None,
+            tokens: None,
         })
     }
 
+    /// Load the results' content within `max_tokens`, most relevant first
+    /// (see [`SkillLoader::load_for_budget`]). Results that do not fit even
+    /// as metadata are dropped.
+    ///
+    /// Callers that filter results after resolving should resolve without
+    /// `max_tokens` and apply the budget to what is left, so skills that are
+    /// filtered out do not use up the budget.
+    pub async fn apply_budget(
+        &self,
+        response: &mut ResolveContextResponse,
+        max_tokens: usize,
+    ) -> Result<(), ServiceError> {
+        let ids: Vec<SkillId> = response
+            .results
+            .iter()
+            .filter_map(|r| SkillId::new(r.skill_id.clone()).ok())
+            .collect();
+        let mut loader = SkillLoader::new(self.skill_manager.clone(), self.skills_root.clone());
+        if let Some(guard) = &self.integrity {
+            loader = loader.with_integrity_guard(Arc::clone(guard));
+        }
+        let load = loader.load_for_budget(&ids, max_tokens).await?;
+
+        let mut loaded: std::collections::HashMap<String, _> = load
+            .skills
+            .into_iter()
+            .map(|s| (s.skill_id.clone(), s))
+            .collect();
+        response.results.retain_mut(|result| {
+            let Some(skill) = loaded.remove(&result.skill_id) else {
+                return false;
+            };
+            result.loading_level = Some(skill.level);
+            result.tokens = Some(skill.tokens);
+            result.content = Some(skill.content);
+            true
+        });
+        response.total_tokens = Some(load.total_tokens);
+        Ok(())
+    }
+
     async fn perform_search(
         &self,
         request: &ResolveContextRequest,
@@ -321,12 +433,13 @@ impl ContextResolver {
 
     async fn read_content(
         &self,
-        skill_file: &Path,
+        skill_def: &SkillDefinition,
         mode: &ContentMode,
     ) -> Result<(Option<String>, Option<String>), ServiceError> {
         if mode == &ContentMode::None {
             return Ok((None, None));
         }
+        let skill_file = skill_def.skill_file.as_path();
 
         if !skill_file.exists() {
             tracing::warn!(
@@ -348,7 +461,17 @@ impl ContextResolver {
             }
         };
 
-        let content = match tokio::fs::read_to_string(skill_file).await {
+        // Check the size before reading so an oversized file is never loaded
+        if mode == &ContentMode::Full && metadata.len() > MAX_CONTENT_SIZE {
+            tracing::warn!(
+                "RESOLVE_CONTENT_TOO_LARGE: '{}' exceeds {} bytes",
+                skill_file.display(),
+                MAX_CONTENT_SIZE
+            );
+            return Ok((None, None));
+        }
+
+        let content = match SkillContent::read(skill_file).await {
             Ok(c) => c,
             Err(e) => {
                 tracing::warn!(
@@ -359,25 +482,34 @@ impl ContextResolver {
                 return Ok((None, None));
             }
         };
+        if let Some(guard) = &self.integrity {
+            guard.verify(skill_def.id.as_str(), content.as_bytes())?;
+        }
 
+        let not_utf8 = |e: &dyn std::fmt::Display| {
+            tracing::warn!(
+                "RESOLVE_READ_FAILED: cannot read '{}': {}",
+                skill_file.display(),
+                e
+            );
+        };
         match mode {
             ContentMode::None => Ok((None, None)),
-            ContentMode::Preview => {
-                let preview = self.extract_preview(&content);
-                Ok((Some(preview), None))
-            }
-            ContentMode::Full => {
-                if metadata.len() > MAX_CONTENT_SIZE {
-                    tracing::warn!(
-                        "RESOLVE_CONTENT_TOO_LARGE: '{}' exceeds {} bytes",
-                        skill_file.display(),
-                        MAX_CONTENT_SIZE
-                    );
+            ContentMode::Preview => match content.to_str() {
+                Ok(text) => Ok((Some(self.extract_preview(text)), None)),
+                Err(e) => {
+                    not_utf8(&e);
                     Ok((None, None))
-                } else {
-                    Ok((None, Some(content)))
                 }
-            }
+            },
+            // Reuses the read buffer; no copy of the file text
+            ContentMode::Full => match content.into_string() {
+                Ok(text) => Ok((None, Some(text))),
+                Err(e) => {
+                    not_utf8(&e);
+                    Ok((None, None))
+                }
+            },
         }
     }
 
@@ -511,6 +643,7 @@ mod tests {
             scope: ResolveScope::Local,
             include_content: ContentMode::None,
             resolve_paths: true,
+            max_tokens: None,
         };
 
         let result = resolver.resolve_context(request).await;
@@ -538,6 +671,7 @@ mod tests {
             scope: ResolveScope::Local,
             include_content: ContentMode::None,
             resolve_paths: true,
+            max_tokens: None,
         };
 
         let result = resolver.resolve_context(request).await;
@@ -567,6 +701,7 @@ mod tests {
             scope: ResolveScope::Local,
             include_content: ContentMode::None,
             resolve_paths: true,
+            max_tokens: None,
         };
 
         let response = resolver.resolve_context(request).await.unwrap();
@@ -610,4 +745,69 @@ mod tests {
         assert!(refs.is_none());
         assert!(assets.is_none());
     }
+
+    #[tokio::test]
+    async fn test_apply_budget_loads_and_drops_results() {
+        use crate::core::skill_manager::{SkillDefinition, SkillManagementService};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let skill_dir = temp_dir.path().join("pdf");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        let skill_file = skill_dir.join("SKILL.md");
+        std::fs::write(
+            &skill_file,
+            "---\nname: pdf\ndescription: PDF tools\n---\nFill forms",
+        )
+        .unwrap();
+        let manager = Arc::new(crate::core::skill_manager::SkillManager::new());
+        let mut def = SkillDefinition::new(
+            SkillId::new("pdf".to_string()).unwrap(),
+            "pdf".to_string(),
+            "PDF tools".to_string(),
+            "1.0.0".to_string(),
+            crate::core::origin::Origin::Local {
+                path: skill_dir.clone(),
+                editable: false,
+            },
+        );
+        def.skill_file = skill_file;
+        manager.register_skill(def).await.unwrap();
+
+        let resolver = ContextResolver::new(
+            manager.clone(),
+            Arc::new(crate::core::metadata::MetadataServiceImpl::new(manager)),
+            None,
+            None,
+            temp_dir.path().to_path_buf(),
+        );
+        let result = |id: &str| ResolvedSkill {
+            skill_id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            score: 1.0,
+            skill_md_path: None,
+            skill_root_path: None,
+            references_dir_path: None,
+            assets_dir_path: None,
+            content_preview: None,
+            content_full: None,
+            loading_level: None,
+            content: None,
+            tokens: None,
+        };
+        let mut response = ResolveContextResponse {
+            query: "pdf".to_string(),
+            scope: ResolveScope::Local,
+            results: vec![result("pdf"), result("ghost")],
+            allowed_roots: Vec::new(),
+            total_tokens: None,
+        };
+
+        resolver.apply_budget(&mut response, 1_000).await.unwrap();
+        assert_eq!(response.results.len(), 1);
+        let pdf = &response.results[0];
+        assert_eq!(pdf.loading_level, Some(LoadingLevel::Full));
+        assert!(pdf.content.as_deref().unwrap().contains("Fill forms"));
+        assert_eq!(response.total_tokens, pdf.tokens);
+    }
 }
@@ -0,0 +1,417 @@
+//! Credential storage for registry tokens and API keys
+//!
+//! Repository and registry auth names an environment variable (`env_var`).
+//! [`resolve_secret`] reads that variable first and, when it is unset, looks
+//! the same name up in the credential store: the OS keyring (macOS Keychain,
+//! Windows Credential Manager, Secret Service) or, where no keyring is
+//! available, an encrypted file unlocked by `FASTSKILL_CREDENTIALS_PASSPHRASE`.
+
+use crate::core::service::ServiceError;
+use crate::utils::atomic_write;
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Keyring service name all fastskill credentials are stored under.
+pub const KEYRING_SERVICE: &str = "fastskill";
+
+/// Environment variable holding the passphrase for the encrypted file store.
+pub const PASSPHRASE_ENV: &str = "FASTSKILL_CREDENTIALS_PASSPHRASE";
+
+const MAGIC: &[u8; 4] = b"FSKC";
+const FORMAT_VERSION: u8 = 1;
+/// Magic, format version, then Argon2id m_cost, t_cost and p_cost (u32 LE).
+const HEADER_LEN: usize = MAGIC.len() + 1 + 3 * 4;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// Argon2id cost used for new files (OWASP baseline: 19 MiB, 2 passes).
+const KDF_M_COST: u32 = 19 * 1024;
+const KDF_T_COST: u32 = 2;
+const KDF_P_COST: u32 = 1;
+
+/// Upper bounds on costs read from a file, so a doctored header cannot make
+/// every lookup allocate gigabytes or spin for minutes.
+const KDF_MAX_M_COST: u32 = 1024 * 1024;
+const KDF_MAX_T_COST: u32 = 16;
+const KDF_MAX_P_COST: u32 = 16;
+
+/// A place secrets can be stored and looked up by name.
+pub trait CredentialStore: Send + Sync {
+    /// Short backend name for messages ("keyring", "encrypted-file").
+    fn name(&self) -> &'static str;
+
+    /// Look up `key`; `Ok(None)` when nothing is stored under it.
+    fn get(&self, key: &str) -> Result<Option<String>, ServiceError>;
+
+    /// Store `secret` under `key`, replacing any previous value.
+    fn set(&self, key: &str, secret: &str) -> Result<(), ServiceError>;
+
+    /// Remove `key`; returns whether anything was stored.
+    fn delete(&self, key: &str) -> Result<bool, ServiceError>;
+}
+
+/// OS keyring backend.
+#[derive(Debug, Default)]
+pub struct KeyringStore;
+
+impl KeyringStore {
+    fn entry(key: &str) -> Result<keyring::Entry, ServiceError> {
+        keyring::Entry::new(KEYRING_SERVICE, key)
+            .map_err(|e| ServiceError::Custom(format!("Keyring unavailable: {}", e)))
+    }
+
+    /// Whether the platform keyring can be reached at all.
+    pub fn is_available() -> bool {
+        let probe = Self::entry("__fastskill_probe__").map(|entry| entry.get_password());
+        match probe {
+            Ok(Ok(_)) | Ok(Err(keyring::Error::NoEntry)) => true,
+            Ok(Err(e)) => {
+                tracing::debug!("OS keyring not available: {}", e);
+                false
+            }
+            Err(e) => {
+                tracing::debug!("OS keyring not available: {}", e);
+                false
+            }
+        }
+    }
+}
+
+impl CredentialStore for KeyringStore {
+    fn name(&self) -> &'static str {
+        "keyring"
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>, ServiceError> {
+        match Self::entry(key)?.get_password() {
+            Ok(secret) => Ok(Some(secret)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(ServiceError::Custom(format!(
+                "Failed to read '{}' from keyring: {}",
+                key, e
+            ))),
+        }
+    }
+
+    fn set(&self, key: &str, secret: &str) -> Result<(), ServiceError> {
+        Self::entry(key)?.set_password(secret).map_err(|e| {
+            ServiceError::Custom(format!("Failed to store '{}' in keyring: {}", key, e))
+        })
+    }
+
+    fn delete(&self, key: &str) -> Result<bool, ServiceError> {
+        match Self::entry(key)?.delete_credential() {
+            Ok(()) => Ok(true),
+            Err(keyring::Error::NoEntry) => Ok(false),
+            Err(e) => Err(ServiceError::Custom(format!(
+                "Failed to remove '{}' from keyring: {}",
+                key, e
+            ))),
+        }
+    }
+}
+
+/// Encrypted file backend for hosts without a keyring (headless CI, containers).
+///
+/// The file holds `header || salt || nonce || ciphertext`. The header is the
+/// `FSKC` magic, a format version and the Argon2id costs the key was derived
+/// with; the ciphertext is the ChaCha20-Poly1305 encryption of a JSON map of
+/// names to secrets, with the header as associated data. The file is written
+/// atomically with owner-only permissions.
+pub struct EncryptedFileStore {
+    path: PathBuf,
+    passphrase: String,
+}
+
+impl EncryptedFileStore {
+    pub fn new(path: PathBuf, passphrase: String) -> Self {
+        Self { path, passphrase }
+    }
+
+    /// Default location: `<config dir>/fastskill/credentials.enc`.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|d| d.join("fastskill").join("credentials.enc"))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn cipher(&self, salt: &[u8], params: Params) -> Result<ChaCha20Poly1305, ServiceError> {
+        let mut key = [0u8; 32];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(self.passphrase.as_bytes(), salt, &mut key)
+            .map_err(|e| ServiceError::Custom(format!("Failed to derive credential key: {}", e)))?;
+        Ok(ChaCha20Poly1305::new(Key::from_slice(&key)))
+    }
+
+    fn load(&self) -> Result<BTreeMap<String, String>, ServiceError> {
+        let bytes = match std::fs::read(&self.path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+            Err(e) => return Err(ServiceError::Io(e)),
+        };
+        if bytes.len() < HEADER_LEN + SALT_LEN + NONCE_LEN {
+            return Err(ServiceError::Custom(format!(
+                "Credential file {} is truncated",
+                self.path.display()
+            )));
+        }
+        let (header, rest) = bytes.split_at(HEADER_LEN);
+        let params = self.parse_header(header)?;
+        let (salt, rest) = rest.split_at(SALT_LEN);
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        let payload = Payload {
+            msg: ciphertext,
+            aad: header,
+        };
+        let plaintext = self
+            .cipher(salt, params)?
+            .decrypt(Nonce::from_slice(nonce), payload)
+            .map_err(|_| {
+                ServiceError::Custom(format!(
+                    "Failed to decrypt {}: wrong {} or corrupted file",
+                    self.path.display(),
+                    PASSPHRASE_ENV
+                ))
+            })?;
+        serde_json::from_slice(&plaintext).map_err(|e| {
+            ServiceError::Custom(format!(
+                "Credential file {} is malformed: {}",
+                self.path.display(),
+                e
+            ))
+        })
+    }
+
+    fn parse_header(&self, header: &[u8]) -> Result<Params, ServiceError> {
+        let invalid = |reason: String| {
+            ServiceError::Custom(format!(
+                "Credential file {} is not a fastskill credential file: {}",
+                self.path.display(),
+                reason
+            ))
+        };
+        if &header[..MAGIC.len()] != MAGIC {
+            return Err(invalid("bad magic".to_string()));
+        }
+        let version = header[MAGIC.len()];
+        if version != FORMAT_VERSION {
+            return Err(invalid(format!("unsupported format version {}", version)));
+        }
+        let cost = |i: usize| {
+            let start = MAGIC.len() + 1 + i * 4;
+            let mut word = [0u8; 4];
+            word.copy_from_slice(&header[start..start + 4]);
+            u32::from_le_bytes(word)
+        };
+        let (m_cost, t_cost, p_cost) = (cost(0), cost(1), cost(2));
+        if m_cost > KDF_MAX_M_COST || t_cost > KDF_MAX_T_COST || p_cost > KDF_MAX_P_COST {
+            return Err(invalid(format!(
+                "key derivation cost m={} t={} p={} exceeds the supported maximum",
+                m_cost, t_cost, p_cost
+            )));
+        }
+        Params::new(m_cost, t_cost, p_cost, Some(32)).map_err(|e| invalid(e.to_string()))
+    }
+
+    fn save(&self, entries: &BTreeMap<String, String>) -> Result<(), ServiceError> {
+        let plaintext = serde_json::to_vec(entries)
+            .map_err(|e| ServiceError::Custom(format!("Failed to encode credentials: {}", e)))?;
+        let params = Params::new(KDF_M_COST, KDF_T_COST, KDF_P_COST, Some(32))
+            .map_err(|e| ServiceError::Custom(format!("Invalid key derivation cost: {}", e)))?;
+        let mut header = Vec::with_capacity(HEADER_LEN);
+        header.extend_from_slice(MAGIC);
+        header.push(FORMAT_VERSION);
+        for cost in [KDF_M_COST, KDF_T_COST, KDF_P_COST] {
+            header.extend_from_slice(&cost.to_le_bytes());
+        }
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let payload = Payload {
+            msg: plaintext.as_slice(),
+            aad: &header,
+        };
+        let ciphertext = self
+            .cipher(&salt, params)?
+            .encrypt(&nonce, payload)
+            .map_err(|_| ServiceError::Custom("Failed to encrypt credentials".to_string()))?;
+
+        let mut bytes = header;
+        bytes.reserve(SALT_LEN + NONCE_LEN + ciphertext.len());
+        bytes.extend_from_slice(&salt);
+        bytes.extend_from_slice(&nonce);
+        bytes.extend_from_slice(&ciphertext);
+        // atomic_write stages through a tempfile, which is created 0600 on unix.
+        atomic_write(&self.path, &bytes)?;
+        Ok(())
+    }
+}
+
+impl CredentialStore for EncryptedFileStore {
+    fn name(&self) -> &'static str {
+        "encrypted-file"
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>, ServiceError> {
+        Ok(self.load()?.remove(key))
+    }
+
+    fn set(&self, key: &str, secret: &str) -> Result<(), ServiceError> {
+        let mut entries = self.load()?;
+        entries.insert(key.to_string(), secret.to_string());
+        self.save(&entries)
+    }
+
+    fn delete(&self, key: &str) -> Result<bool, ServiceError> {
+        let mut entries = self.load()?;
+        let removed = entries.remove(key).is_some();
+        if removed {
+            self.save(&entries)?;
+        }
+        Ok(removed)
+    }
+}
+
+/// Pick the credential store for this host.
+///
+/// The encrypted file is used when `FASTSKILL_CREDENTIALS_PASSPHRASE` is set,
+/// so headless hosts can opt out of the keyring explicitly; otherwise the OS
+/// keyring is used if it can be reached.
+pub fn default_store() -> Result<Box<dyn CredentialStore>, ServiceError> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        if !passphrase.is_empty() {
+            let path = EncryptedFileStore::default_path().ok_or_else(|| {
+                ServiceError::Config("Cannot determine config directory".to_string())
+            })?;
+            return Ok(Box::new(EncryptedFileStore::new(path, passphrase)));
+        }
+    }
+    if KeyringStore::is_available() {
+        return Ok(Box::new(KeyringStore));
+    }
+    Err(ServiceError::Config(format!(
+        "No OS keyring is available; set {} to use an encrypted credential file instead",
+        PASSPHRASE_ENV
+    )))
+}
+
+/// Resolve the secret named by `env_var`: the environment variable wins, then
+/// the credential store. Store errors are logged and treated as "not found" so
+/// a missing keyring never breaks env-based setups.
+pub fn resolve_secret(env_var: &str) -> Option<String> {
+    if let Ok(value) = std::env::var(env_var) {
+        return Some(value);
+    }
+    let store = match default_store() {
+        Ok(store) => store,
+        Err(e) => {
+            tracing::debug!("Credential store unavailable for {}: {}", env_var, e);
+            return None;
+        }
+    };
+    match store.get(env_var) {
+        Ok(secret) => secret,
+        Err(e) => {
+            tracing::warn!("Failed to read {} from {}: {}", env_var, store.name(), e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_encrypted_file_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("credentials.enc");
+        let store = EncryptedFileStore::new(path.clone(), "hunter2".to_string());
+
+        assert_eq!(store.get("GITHUB_TOKEN").unwrap(), None);
+        store.set("GITHUB_TOKEN", "ghp_secret").unwrap();
+        store.set("REGISTRY_KEY", "rk_secret").unwrap();
+        assert_eq!(
+            store.get("GITHUB_TOKEN").unwrap(),
+            Some("ghp_secret".to_string())
+        );
+
+        let raw = std::fs::read(&path).unwrap();
+        assert!(!raw.windows(10).any(|w| w == b"ghp_secret"));
+
+        assert!(store.delete("GITHUB_TOKEN").unwrap());
+        assert!(!store.delete("GITHUB_TOKEN").unwrap());
+        assert_eq!(
+            store.get("REGISTRY_KEY").unwrap(),
+            Some("rk_secret".to_string())
+        );
+    }
+
+    #[test]
+    fn test_encrypted_file_wrong_passphrase_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("credentials.enc");
+        EncryptedFileStore::new(path.clone(), "right".to_string())
+            .set("GITHUB_TOKEN", "ghp_secret")
+            .unwrap();
+
+        let wrong = EncryptedFileStore::new(path, "wrong".to_string());
+        assert!(wrong.get("GITHUB_TOKEN").is_err());
+    }
+
+    #[test]
+    fn test_encrypted_file_records_kdf_params() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("credentials.enc");
+        EncryptedFileStore::new(path.clone(), "hunter2".to_string())
+            .set("GITHUB_TOKEN", "ghp_secret")
+            .unwrap();
+
+        let raw = std::fs::read(&path).unwrap();
+        assert_eq!(&raw[..4], MAGIC);
+        assert_eq!(raw[4], FORMAT_VERSION);
+        assert_eq!(raw[5..9], KDF_M_COST.to_le_bytes());
+        assert_eq!(raw[9..13], KDF_T_COST.to_le_bytes());
+        assert_eq!(raw[13..17], KDF_P_COST.to_le_bytes());
+    }
+
+    #[test]
+    fn test_encrypted_file_rejects_doctored_header() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("credentials.enc");
+        let store = EncryptedFileStore::new(path.clone(), "hunter2".to_string());
+        store.set("GITHUB_TOKEN", "ghp_secret").unwrap();
+        let original = std::fs::read(&path).unwrap();
+
+        // Lowering the cost changes the derived key and the associated data.
+        let mut cheaper = original.clone();
+        cheaper[9..13].copy_from_slice(&1u32.to_le_bytes());
+        std::fs::write(&path, &cheaper).unwrap();
+        assert!(store.get("GITHUB_TOKEN").is_err());
+
+        // An absurd memory cost is refused before any key is derived.
+        let mut huge = original;
+        huge[5..9].copy_from_slice(&u32::MAX.to_le_bytes());
+        std::fs::write(&path, &huge).unwrap();
+        let err = store.get("GITHUB_TOKEN").unwrap_err();
+        assert!(err.to_string().contains("exceeds the supported maximum"));
+    }
+
+    #[test]
+    fn test_resolve_secret_prefers_env() {
+        std::env::set_var("FASTSKILL_TEST_CREDENTIAL_ENV", "from-env");
+        assert_eq!(
+            resolve_secret("FASTSKILL_TEST_CREDENTIAL_ENV"),
+            Some("from-env".to_string())
+        );
+        std::env::remove_var("FASTSKILL_TEST_CREDENTIAL_ENV");
+    }
+}
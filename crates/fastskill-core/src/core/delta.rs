@@ -0,0 +1,570 @@
+//! Delta updates of git and zip-url skills
+//!
+//! An update normally fetches the whole skill again. When the source lists
+//! the new version's files with a hash each, [`plan`] compares them with the
+//! installed copy: only new and changed files are fetched, the rest are
+//! copied from the installed copy. Files the new version no longer has are
+//! dropped, as with a full download.
+//!
+//! - Git: a partial clone (`--filter=blob:none`) brings the tree, whose blob
+//!   ids are per-file hashes, without file contents. Installed files are
+//!   hashed the same way and only the paths that differ are checked out. A
+//!   server without partial clone support sends every file, and the update
+//!   counts as a full download.
+//! - Zip URL: the server may publish a [`FileManifest`] at
+//!   `<archive url>.files.json`. Changed files are downloaded one by one and
+//!   checked against their SHA-256. Without a manifest, or for an archive
+//!   pinned with `#sha256=` (a checksum of the whole archive, which a delta
+//!   cannot verify), the archive is downloaded in full.
+
+use crate::core::download::DownloadManager;
+use crate::core::install::safe_subdir_join;
+use crate::core::service::ServiceError;
+use crate::security::path::validate_path_component;
+use crate::storage::git::{self, TreeBlob};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use tempfile::TempDir;
+
+/// Suffix of the URL of a zip archive's [`FileManifest`]
+pub const FILE_MANIFEST_SUFFIX: &str = ".files.json";
+
+/// What a delta update fetched and what it reused
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeltaStats {
+    /// Files in the new version
+    pub files_total: usize,
+    /// Files fetched because they are new or changed
+    pub files_fetched: usize,
+    pub bytes_fetched: u64,
+    /// Size of the files copied from the installed version instead
+    pub bytes_saved: u64,
+}
+
+/// A file of the new version, as its source lists it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteFile {
+    /// Path from the skill directory, `/`-separated
+    pub path: String,
+    /// Content hash of the kind the source uses (git blob id or SHA-256)
+    pub hash: String,
+    pub size: u64,
+}
+
+/// Files to copy from the installed version and files to fetch
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeltaPlan {
+    pub reuse: Vec<RemoteFile>,
+    pub fetch: Vec<RemoteFile>,
+}
+
+impl DeltaPlan {
+    pub fn stats(&self) -> DeltaStats {
+        DeltaStats {
+            files_total: self.reuse.len() + self.fetch.len(),
+            files_fetched: self.fetch.len(),
+            bytes_fetched: self.fetch.iter().map(|file| file.size).sum(),
+            bytes_saved: self.reuse.iter().map(|file| file.size).sum(),
+        }
+    }
+}
+
+/// Split `files` into those whose installed copy has the same hash
+/// (`installed` maps paths to hashes) and those to fetch
+pub fn plan(files: Vec<RemoteFile>, installed: &HashMap<String, String>) -> DeltaPlan {
+    let (reuse, fetch) = files.into_iter().partition(|file| {
+        installed
+            .get(&file.path)
+            .is_some_and(|hash| hash.eq_ignore_ascii_case(&file.hash))
+    });
+    DeltaPlan { reuse, fetch }
+}
+
+/// Per-file SHA-256 hashes of the skill in a zip archive, published at
+/// `<archive url>.files.json`:
+///
+/// ```json
+/// {
+///   "checksum": "sha256:<hex of the archive>",
+///   "base_url": "https://cdn.example.com/pdf-tools-1.3.0/",
+///   "files": [{ "path": "SKILL.md", "sha256": "<hex>", "size": 1432 }]
+/// }
+/// ```
+///
+/// Paths are relative to the skill directory (the one holding `SKILL.md`).
+/// Files are served under `base_url`, which may be relative to the archive
+/// URL and defaults to the archive URL without `.zip`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FileManifest {
+    /// `sha256:<hex>` of the archive, recorded in `skills.lock`
+    #[serde(default)]
+    pub checksum: Option<String>,
+    #[serde(default)]
+    pub base_url: Option<String>,
+    pub files: Vec<ManifestFile>,
+}
+
+/// One file of a [`FileManifest`]
+#[derive(Debug, Clone, Deserialize)]
+pub struct ManifestFile {
+    pub path: String,
+    /// Hex SHA-256 of the content, optionally prefixed with `sha256:`
+    pub sha256: String,
+    pub size: u64,
+}
+
+impl FileManifest {
+    /// Parse a manifest, refusing paths that leave the skill directory,
+    /// malformed hashes, duplicates and a manifest without `SKILL.md`
+    pub fn parse(bytes: &[u8]) -> Result<Self, ServiceError> {
+        let mut manifest: Self = serde_json::from_slice(bytes)
+            .map_err(|e| ServiceError::Validation(format!("Invalid file manifest: {e}")))?;
+        let mut seen = HashSet::new();
+        for file in &mut manifest.files {
+            check_relative(&file.path)?;
+            if !seen.insert(file.path.clone()) {
+                return Err(ServiceError::Validation(format!(
+                    "File manifest lists '{}' twice",
+                    file.path
+                )));
+            }
+            file.sha256 = normalize_sha256(&file.sha256).ok_or_else(|| {
+                ServiceError::Validation(format!(
+                    "File manifest has an invalid sha256 for '{}'",
+                    file.path
+                ))
+            })?;
+        }
+        if !seen.contains("SKILL.md") {
+            return Err(ServiceError::Validation(
+                "File manifest does not list SKILL.md".to_string(),
+            ));
+        }
+        manifest.checksum = manifest
+            .checksum
+            .as_deref()
+            .and_then(normalize_sha256)
+            .map(|hex| format!("sha256:{hex}"));
+        Ok(manifest)
+    }
+
+    pub fn remote_files(&self) -> Vec<RemoteFile> {
+        self.files
+            .iter()
+            .map(|file| RemoteFile {
+                path: file.path.clone(),
+                hash: file.sha256.clone(),
+                size: file.size,
+            })
+            .collect()
+    }
+
+    /// Where `path` of the archive at `archive_url` is served
+    pub fn file_url(&self, archive_url: &str, path: &str) -> Result<String, ServiceError> {
+        let invalid = |e: String| ServiceError::Validation(format!("Invalid file URL: {e}"));
+        let mut url = match &self.base_url {
+            Some(base) => url::Url::parse(archive_url)
+                .and_then(|archive| archive.join(base))
+                .map_err(|e| invalid(e.to_string()))?,
+            None => url::Url::parse(archive_url.strip_suffix(".zip").unwrap_or(archive_url))
+                .map_err(|e| invalid(e.to_string()))?,
+        };
+        url.path_segments_mut()
+            .map_err(|()| invalid(format!("'{archive_url}' cannot have a path")))?
+            .pop_if_empty()
+            .extend(path.split('/'));
+        Ok(url.into())
+    }
+}
+
+/// URL of the [`FileManifest`] of the archive at `archive_url`
+pub fn manifest_url(archive_url: &str) -> String {
+    format!("{archive_url}{FILE_MANIFEST_SUFFIX}")
+}
+
+/// Lowercase hex of a SHA-256 given as hex or `sha256:<hex>`
+fn normalize_sha256(value: &str) -> Option<String> {
+    let hex = value.strip_prefix("sha256:").unwrap_or(value);
+    (hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit())).then(|| hex.to_lowercase())
+}
+
+/// Refuse empty and absolute paths and paths with `.` or `..` components
+fn check_relative(path: &str) -> Result<(), ServiceError> {
+    let safe = path
+        .split('/')
+        .all(|part| !part.is_empty() && part != "." && validate_path_component(part).is_ok());
+    if safe {
+        Ok(())
+    } else {
+        Err(ServiceError::Validation(format!(
+            "File manifest lists an unsafe path '{path}'"
+        )))
+    }
+}
+
+/// `path` in the installed skill at `dir`, if it is a regular file of `size` bytes
+fn installed_file(dir: &Path, path: &str, size: u64) -> Option<PathBuf> {
+    let file = safe_subdir_join(dir, Path::new(path)).ok()?;
+    let metadata = std::fs::symlink_metadata(&file).ok()?;
+    (metadata.is_file() && metadata.len() == size).then_some(file)
+}
+
+/// Where `path` goes under `root`, refusing paths that leave it or pass
+/// through a symlink
+fn staged_path(root: &Path, path: &str) -> Result<PathBuf, ServiceError> {
+    let dest = safe_subdir_join(root, Path::new(path))?;
+    for ancestor in dest.ancestors().take_while(|ancestor| *ancestor != root) {
+        if std::fs::symlink_metadata(ancestor).is_ok_and(|m| m.file_type().is_symlink()) {
+            return Err(ServiceError::InvalidOperation(format!(
+                "Refusing to write '{path}' through a symlink"
+            )));
+        }
+    }
+    Ok(dest)
+}
+
+/// Copy the reused files from the installed skill at `installed` to `root`
+async fn copy_reused(
+    installed: &Path,
+    root: &Path,
+    files: &[RemoteFile],
+) -> Result<(), ServiceError> {
+    for file in files {
+        let src = installed_file(installed, &file.path, file.size).ok_or_else(|| {
+            ServiceError::Custom(format!(
+                "Installed file '{}' changed during the update",
+                file.path
+            ))
+        })?;
+        let dest = staged_path(root, &file.path)?;
+        if let Some(parent) = dest.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::copy(&src, &dest).await?;
+    }
+    Ok(())
+}
+
+/// A zip-url skill assembled from the installed copy and changed files
+pub(crate) struct ZipDelta {
+    pub temp_dir: TempDir,
+    /// Directory holding the new version's `SKILL.md`
+    pub skill_path: PathBuf,
+    pub checksum: Option<String>,
+    pub stats: DeltaStats,
+}
+
+/// Fetch the new version of the zip-url skill installed at `installed`,
+/// downloading only new and changed files. `Ok(None)` when the server
+/// publishes no file manifest for `archive_url`.
+pub(crate) async fn fetch_zip_delta(
+    downloads: &DownloadManager,
+    archive_url: &str,
+    installed: &Path,
+) -> Result<Option<ZipDelta>, ServiceError> {
+    let Some(body) = downloads.fetch_optional(&manifest_url(archive_url)).await else {
+        return Ok(None);
+    };
+    let manifest = FileManifest::parse(&body)?;
+    let files = manifest.remote_files();
+
+    let mut installed_hashes = HashMap::new();
+    for file in &files {
+        if let Some(path) = installed_file(installed, &file.path, file.size) {
+            let content = tokio::fs::read(&path).await?;
+            installed_hashes.insert(file.path.clone(), format!("{:x}", Sha256::digest(&content)));
+        }
+    }
+    let plan = plan(files, &installed_hashes);
+
+    let temp_dir = TempDir::new()?;
+    let skill_path = temp_dir.path().join("extracted");
+    tokio::fs::create_dir_all(&skill_path).await?;
+    copy_reused(installed, &skill_path, &plan.reuse).await?;
+    let fetches = plan.fetch.iter().map(|file| {
+        let skill_path = &skill_path;
+        let manifest = &manifest;
+        async move {
+            let url = manifest.file_url(archive_url, &file.path)?;
+            let dest = staged_path(skill_path, &file.path)?;
+            let expected = format!("sha256:{}", file.hash);
+            downloads.download(&url, &dest, Some(&expected)).await
+        }
+    });
+    futures::future::try_join_all(fetches).await?;
+
+    Ok(Some(ZipDelta {
+        temp_dir,
+        skill_path,
+        checksum: manifest.checksum,
+        stats: plan.stats(),
+    }))
+}
+
+/// Clone `url` to update the git skill installed at `installed`, checking
+/// out only the files that differ from it and copying the others over. The
+/// skill's files are complete either way; the stats are `None` when every
+/// file was downloaded because the server ignored the partial clone filter.
+/// Without a delta, a skill in `subdir` gets a sparse checkout of just that
+/// directory.
+pub(crate) async fn fetch_git_delta(
+    url: &str,
+    branch: Option<&str>,
+    tag: Option<&str>,
+    subdir: Option<&Path>,
+    installed: &Path,
+) -> Result<(TempDir, Option<DeltaStats>), ServiceError> {
+    let (temp_dir, filtered) = git::partial_clone(url, branch, tag).await?;
+    if filtered {
+        match checkout_changed(temp_dir.path(), subdir, installed).await {
+            Ok(Some(stats)) => return Ok((temp_dir, Some(stats))),
+            Ok(None) => {}
+            Err(e) => tracing::warn!(
+                "Delta update from {} failed, fetching all files: {}",
+                git::redact_url_credentials(url),
+                e
+            ),
+        }
+    }
+    git::checkout_head_in(temp_dir.path(), subdir).await?;
+    Ok((temp_dir, None))
+}
+
+/// Check out the skill's changed files in the partial clone at `repo` and
+/// copy the unchanged ones from `installed`. `Ok(None)` when the tree has no
+/// skill where one is expected.
+async fn checkout_changed(
+    repo: &Path,
+    subdir: Option<&Path>,
+    installed: &Path,
+) -> Result<Option<DeltaStats>, ServiceError> {
+    let blobs = git::list_tree_blobs(repo).await?;
+    let Some(prefix) = skill_prefix(&blobs, subdir) else {
+        return Ok(None);
+    };
+    let files: Vec<RemoteFile> = blobs
+        .into_iter()
+        .filter_map(|blob| {
+            Some(RemoteFile {
+                path: blob.path.strip_prefix(&prefix)?.to_string(),
+                hash: blob.object,
+                size: blob.size,
+            })
+        })
+        .collect();
+
+    let candidates: Vec<&str> = files
+        .iter()
+        .filter(|file| installed_file(installed, &file.path, file.size).is_some())
+        .map(|file| file.path.as_str())
+        .collect();
+    let hashes = git::hash_objects(installed, &candidates).await?;
+    let installed_hashes: HashMap<String, String> = candidates
+        .iter()
+        .map(|path| path.to_string())
+        .zip(hashes)
+        .collect();
+    let plan = plan(files, &installed_hashes);
+
+    let changed: Vec<String> = plan
+        .fetch
+        .iter()
+        .map(|file| format!("{prefix}{}", file.path))
+        .collect();
+    let changed: Vec<&str> = changed.iter().map(String::as_str).collect();
+    git::checkout_paths(repo, &changed).await?;
+    copy_reused(installed, &repo.join(&prefix), &plan.reuse).await?;
+    Ok(Some(plan.stats()))
+}
+
+/// Tree path prefix (`""` or ending in `/`) of the skill: `subdir` when
+/// given, else the root or the first top-level directory with a `SKILL.md`
+fn skill_prefix(blobs: &[TreeBlob], subdir: Option<&Path>) -> Option<String> {
+    let has_skill = |prefix: &str| {
+        blobs
+            .iter()
+            .any(|blob| blob.path.strip_prefix(prefix) == Some("SKILL.md"))
+    };
+    if let Some(subdir) = subdir {
+        let parts: Vec<&str> = subdir
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(part) => part.to_str(),
+                _ => None,
+            })
+            .collect();
+        let prefix = format!("{}/", parts.join("/"));
+        return (!parts.is_empty() && has_skill(&prefix)).then_some(prefix);
+    }
+    if has_skill("") {
+        return Some(String::new());
+    }
+    blobs
+        .iter()
+        .filter_map(|blob| blob.path.strip_suffix("/SKILL.md"))
+        .filter(|dir| !dir.contains('/'))
+        .min()
+        .map(|dir| format!("{dir}/"))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn remote(path: &str, hash: &str, size: u64) -> RemoteFile {
+        RemoteFile {
+            path: path.to_string(),
+            hash: hash.to_string(),
+            size,
+        }
+    }
+
+    fn blob(path: &str) -> TreeBlob {
+        TreeBlob {
+            path: path.to_string(),
+            object: "0".repeat(40),
+            size: 1,
+        }
+    }
+
+    #[test]
+    fn test_plan_reuses_files_with_the_same_hash() {
+        let installed = HashMap::from([
+            ("SKILL.md".to_string(), "aaa".to_string()),
+            ("scripts/run.py".to_string(), "BBB".to_string()),
+        ]);
+        let files = vec![
+            remote("SKILL.md", "ccc", 100),
+            remote("scripts/run.py", "bbb", 4000),
+            remote("scripts/new.py", "ddd", 50),
+        ];
+
+        let plan = plan(files, &installed);
+        assert_eq!(plan.reuse, vec![remote("scripts/run.py", "bbb", 4000)]);
+        assert_eq!(plan.fetch.len(), 2);
+        assert_eq!(
+            plan.stats(),
+            DeltaStats {
+                files_total: 3,
+                files_fetched: 2,
+                bytes_fetched: 150,
+                bytes_saved: 4000,
+            }
+        );
+    }
+
+    #[test]
+    fn test_file_manifest_parse_normalizes_hashes() {
+        let hash = "AB".repeat(32);
+        let body = format!(
+            r#"{{"checksum": "{hash}", "files": [
+                {{"path": "SKILL.md", "sha256": "sha256:{hash}", "size": 10}},
+                {{"path": "scripts/run.py", "sha256": "{hash}", "size": 20}}
+            ]}}"#
+        );
+        let manifest = FileManifest::parse(body.as_bytes()).unwrap();
+        assert_eq!(manifest.files[0].sha256, "ab".repeat(32));
+        assert_eq!(
+            manifest.checksum,
+            Some(format!("sha256:{}", "ab".repeat(32)))
+        );
+        assert_eq!(manifest.remote_files()[1].path, "scripts/run.py");
+    }
+
+    #[test]
+    fn test_file_manifest_parse_rejects_unsafe_paths() {
+        let hash = "ab".repeat(32);
+        for path in [
+            "../evil.sh",
+            "scripts/../../evil.sh",
+            "/etc/passwd",
+            "a//b",
+            "./SKILL.md",
+        ] {
+            let body = format!(
+                r#"{{"files": [
+                    {{"path": "SKILL.md", "sha256": "{hash}", "size": 1}},
+                    {{"path": "{path}", "sha256": "{hash}", "size": 1}}
+                ]}}"#
+            );
+            let result = FileManifest::parse(body.as_bytes());
+            assert!(
+                matches!(&result, Err(ServiceError::Validation(m)) if m.contains("unsafe path")),
+                "{path} was accepted"
+            );
+        }
+
+        let without_skill_md =
+            format!(r#"{{"files": [{{"path": "run.sh", "sha256": "{hash}", "size": 1}}]}}"#);
+        assert!(FileManifest::parse(without_skill_md.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_file_manifest_file_url() {
+        let manifest = FileManifest {
+            checksum: None,
+            base_url: None,
+            files: Vec::new(),
+        };
+        assert_eq!(
+            manifest
+                .file_url(
+                    "https://cdn.example.com/skills/pdf-1.2.zip",
+                    "scripts/run me.py"
+                )
+                .unwrap(),
+            "https://cdn.example.com/skills/pdf-1.2/scripts/run%20me.py"
+        );
+
+        let manifest = FileManifest {
+            base_url: Some("files/".to_string()),
+            ..manifest
+        };
+        assert_eq!(
+            manifest
+                .file_url("https://cdn.example.com/skills/pdf-1.2.zip", "SKILL.md")
+                .unwrap(),
+            "https://cdn.example.com/skills/files/SKILL.md"
+        );
+    }
+
+    #[test]
+    fn test_skill_prefix() {
+        let root = [blob("SKILL.md"), blob("scripts/run.py")];
+        assert_eq!(skill_prefix(&root, None), Some(String::new()));
+
+        let nested = [blob("README.md"), blob("pdf/SKILL.md"), blob("pdf/run.py")];
+        assert_eq!(skill_prefix(&nested, None), Some("pdf/".to_string()));
+
+        let monorepo = [blob("skills/pdf/SKILL.md"), blob("skills/web/SKILL.md")];
+        assert_eq!(
+            skill_prefix(&monorepo, Some(Path::new("skills/web"))),
+            Some("skills/web/".to_string())
+        );
+        assert_eq!(
+            skill_prefix(&monorepo, Some(Path::new("skills/none"))),
+            None
+        );
+        assert_eq!(skill_prefix(&monorepo, None), None);
+    }
+
+    #[test]
+    fn test_staged_path_stays_under_root() {
+        let root = TempDir::new().unwrap();
+        assert_eq!(
+            staged_path(root.path(), "scripts/run.py").unwrap(),
+            root.path().join("scripts").join("run.py")
+        );
+        assert!(staged_path(root.path(), "../evil.sh").is_err());
+
+        #[cfg(unix)]
+        {
+            let outside = TempDir::new().unwrap();
+            std::os::unix::fs::symlink(outside.path(), root.path().join("scripts")).unwrap();
+            assert!(staged_path(root.path(), "scripts/run.py").is_err());
+        }
+    }
+}
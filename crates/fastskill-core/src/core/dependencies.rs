@@ -263,6 +263,41 @@ impl DependencyGraph {
             .cloned()
             .unwrap_or_default()
     }
+
+    /// Every `(skill, dependency)` edge that lies on a cycle.
+    ///
+    /// An edge `a -> b` is on a cycle when `b` can reach `a` again; edges to
+    /// skills outside the graph never are. Unlike [`Self::detect_cycles`] this
+    /// reports all offending edges, for display.
+    pub fn cycle_edges(&self) -> HashSet<(String, String)> {
+        let mut edges = HashSet::new();
+        for (skill_id, deps) in &self.graph {
+            for dep in deps {
+                if self.graph.contains_key(&dep.skill_id) && self.reaches(&dep.skill_id, skill_id) {
+                    edges.insert((skill_id.clone(), dep.skill_id.clone()));
+                }
+            }
+        }
+        edges
+    }
+
+    /// Whether `target` is reachable from `from` along dependency edges.
+    fn reaches(&self, from: &str, target: &str) -> bool {
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::from([from.to_string()]);
+        while let Some(current) = queue.pop_front() {
+            if current == target {
+                return true;
+            }
+            if !seen.insert(current.clone()) {
+                continue;
+            }
+            if let Some(deps) = self.graph.get(&current) {
+                queue.extend(deps.iter().map(|d| d.skill_id.clone()));
+            }
+        }
+        false
+    }
 }
 
 impl Default for DependencyGraph {
@@ -543,6 +578,27 @@ mod tests {
         assert!(graph.get_dependents("missing").is_empty());
     }
 
+    #[test]
+    fn test_cycle_edges_reports_only_edges_on_cycles() {
+        let dep = |id: &str| Dependency {
+            skill_id: id.to_string(),
+            version_constraint: None,
+        };
+        // a -> b -> c -> a is a cycle; c -> d and d -> external are not.
+        let graph = DependencyGraph::build_graph(vec![
+            ("a".to_string(), vec![dep("b")]),
+            ("b".to_string(), vec![dep("c")]),
+            ("c".to_string(), vec![dep("a"), dep("d")]),
+            ("d".to_string(), vec![dep("external")]),
+        ]);
+        let edges = graph.cycle_edges();
+        assert_eq!(edges.len(), 3);
+        assert!(edges.contains(&("a".to_string(), "b".to_string())));
+        assert!(edges.contains(&("c".to_string(), "a".to_string())));
+        assert!(!edges.contains(&("c".to_string(), "d".to_string())));
+        assert!(DependencyGraph::default().cycle_edges().is_empty());
+    }
+
     #[test]
     fn test_default_graph_is_empty() {
         let graph = DependencyGraph::default();
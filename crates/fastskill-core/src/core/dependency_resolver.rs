@@ -1,12 +1,19 @@
 //! Recursive dependency resolution for the install command
 //!
 //! Implements a breadth-first dependency graph traversal that:
-//! - Deduplicates skills by ID (first-encountered wins)
+//! - Deduplicates skills by ID, recording every requirement on a skill and
+//!   intersecting their version constraints
+//! - Reports a [`DependencyConflict`] with the dependency chain behind each
+//!   requirement when a skill is required from incompatible sources
 //! - Enforces a configurable depth limit to prevent runaway chains
 //! - Detects circular dependencies and breaks cycles with a warning
 //! - Returns skills in topological (dependency-first) order
+//! - Pulls in a skill's `[optional-dependencies.<extra>]` for each extra it was
+//!   requested with (`id[docs]`)
 
 use crate::core::manifest::{ManifestError, SkillEntry, SkillProjectToml};
+use crate::core::origin::Origin;
+use crate::core::version::VersionConstraint;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::Path;
 
@@ -36,6 +43,87 @@ pub enum DependencyResolutionError {
 
     #[error("Failed to load transitive dependencies for {skill}: {error}")]
     TransitiveLoadError { skill: String, error: String },
+
+    #[error("{0}")]
+    Conflict(DependencyConflict),
+}
+
+/// One requirement on a skill, and the path through the dependency graph that
+/// produced it (root entry first, the required skill last).
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionRequirement {
+    pub origin: Origin,
+    pub chain: Vec<String>,
+}
+
+impl VersionRequirement {
+    /// The version constraint this requirement carries, if any.
+    pub fn constraint(&self) -> Option<&VersionConstraint> {
+        match &self.origin {
+            Origin::Repository { version, .. } => version.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for VersionRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.origin {
+            Origin::Repository {
+                repo,
+                skill,
+                version,
+            } => {
+                let constraint = version
+                    .as_ref()
+                    .map(|c| c.to_string())
+                    .unwrap_or_else(|| "*".to_string());
+                write!(f, "{} from '{}/{}'", constraint, repo, skill)?
+            }
+            Origin::Git { url, .. } => write!(f, "git {}", url)?,
+            Origin::Local { path, .. } => write!(f, "local {}", path.display())?,
+            Origin::ZipUrl { url } => write!(f, "zip {}", url)?,
+            Origin::Pack { path, skill } => write!(f, "pack {} ({})", path.display(), skill)?,
+        }
+        write!(f, ", via skill-project.toml -> {}", self.chain.join(" -> "))
+    }
+}
+
+/// A skill whose requirements cannot all be met, listing the chain behind
+/// each requirement so the user can see which dependency to change.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DependencyConflict {
+    pub skill_id: String,
+    /// What cannot be satisfied, e.g. "required from incompatible sources"
+    pub problem: String,
+    pub requirements: Vec<VersionRequirement>,
+}
+
+impl std::fmt::Display for DependencyConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.skill_id, self.problem)?;
+        for requirement in &self.requirements {
+            write!(f, "\n    requires {}", requirement)?;
+        }
+        Ok(())
+    }
+}
+
+/// Path from a root entry down to `id`, following first-encountered parents.
+fn chain_to(parents: &HashMap<String, Option<String>>, id: &str) -> Vec<String> {
+    let mut chain = vec![id.to_string()];
+    let mut current = parents.get(id).and_then(|p| p.clone());
+    while let Some(node) = current {
+        // A cycle in the parent map would otherwise never end; parents only
+        // point at earlier nodes, so this cannot trigger in practice.
+        if chain.contains(&node) {
+            break;
+        }
+        current = parents.get(&node).and_then(|p| p.clone());
+        chain.push(node);
+    }
+    chain.reverse();
+    chain
 }
 
 /// A single item in the install queue, carrying its resolution context
@@ -46,6 +134,9 @@ pub struct SkillInstallItem {
     pub depth: u32,
     /// ID of the skill that pulled this one in, if any
     pub parent_skill: Option<String>,
+    /// Every requirement on this skill found during resolution. For a
+    /// repository origin, `entry`'s constraint is the intersection of them all.
+    pub requirements: Vec<VersionRequirement>,
 }
 
 /// Resolves a dependency graph for a set of root `SkillEntry` items.
@@ -84,15 +175,26 @@ impl DependencyResolver {
         // (back-edge to an ancestor) from a diamond (shared dependency).
         let mut parents: HashMap<String, Option<String>> = HashMap::new();
 
+        // Every requirement seen per skill, merged into the items at the end.
+        let mut requirements: HashMap<String, Vec<VersionRequirement>> = HashMap::new();
+
         // Seed the queue with the direct (depth-0) dependencies
         for entry in initial_entries {
             let id = entry.id.clone();
             if self.visited_skills.insert(id.clone()) {
-                parents.insert(id, None);
+                parents.insert(id.clone(), None);
+                requirements
+                    .entry(id.clone())
+                    .or_default()
+                    .push(VersionRequirement {
+                        origin: entry.origin.clone(),
+                        chain: vec![id],
+                    });
                 self.install_queue.push_back(SkillInstallItem {
                     entry,
                     depth: 0,
                     parent_skill: None,
+                    requirements: Vec::new(),
                 });
             }
         }
@@ -101,6 +203,7 @@ impl DependencyResolver {
 
         while let Some(item) = self.install_queue.pop_front() {
             let skill_id = item.entry.id.clone();
+            let extras = item.entry.extras.clone();
             let current_depth = item.depth;
 
             ordered.push(item);
@@ -121,7 +224,8 @@ impl DependencyResolver {
 
             // Attempt to load the transitive manifest for this skill
             let transitive_manifest = skills_dir.join(&skill_id).join("skill-project.toml");
-            let transitive_entries = match self.load_transitive_dependencies(&transitive_manifest) {
+            let loaded = self.load_transitive_dependencies(&transitive_manifest, &extras);
+            let transitive_entries = match loaded {
                 Ok(entries) => entries,
                 Err(ManifestError::NotFound(_)) => {
                     // Graceful degradation: skill has no manifest → zero deps
@@ -144,8 +248,18 @@ impl DependencyResolver {
 
             for trans_entry in transitive_entries {
                 let trans_id = trans_entry.id.clone();
-
-                // Deduplication: first-encountered wins.
+                let mut chain = chain_to(&parents, &skill_id);
+                chain.push(trans_id.clone());
+                requirements
+                    .entry(trans_id.clone())
+                    .or_default()
+                    .push(VersionRequirement {
+                        origin: trans_entry.origin.clone(),
+                        chain,
+                    });
+
+                // Deduplication: the first-encountered entry supplies the
+                // source; later requirements only narrow its version.
                 if !self.visited_skills.insert(trans_id.clone()) {
                     // Already visited. Only warn when this is a genuine back-edge —
                     // i.e. the dependency points at an ancestor of the current skill
@@ -175,28 +289,80 @@ impl DependencyResolver {
                     entry: trans_entry,
                     depth: current_depth + 1,
                     parent_skill: Some(skill_id.clone()),
+                    requirements: Vec::new(),
                 });
             }
         }
 
+        for item in &mut ordered {
+            item.requirements = requirements.remove(&item.entry.id).unwrap_or_default();
+            merge_requirements(item)?;
+        }
+
         Ok(ordered)
     }
 
-    /// Load skill entries from a `skill-project.toml` at the given path.
+    /// Load skill entries from a `skill-project.toml` at the given path, plus
+    /// the optional dependencies of each requested extra.
     ///
     /// Returns `ManifestError::NotFound` when the file does not exist so the
     /// caller can distinguish "no manifest" from "broken manifest".
     fn load_transitive_dependencies(
         &self,
         manifest_path: &Path,
+        extras: &[String],
     ) -> Result<Vec<SkillEntry>, ManifestError> {
         let project = SkillProjectToml::load_from_file(manifest_path)?;
-        project.to_skill_entries().map_err(ManifestError::Parse)
+        let mut entries = project.to_skill_entries().map_err(ManifestError::Parse)?;
+        for extra in extras {
+            match project.optional_skill_entries(std::slice::from_ref(extra)) {
+                Ok(optional) => {
+                    for entry in optional {
+                        if !entries.iter().any(|e| e.id == entry.id) {
+                            entries.push(entry);
+                        }
+                    }
+                }
+                // A stale extra must not drop the skill's other dependencies.
+                Err(e) => tracing::warn!("{}: {}", manifest_path.display(), e),
+            }
+        }
+        Ok(entries)
     }
 }
 
+/// Check every requirement names the item's source and narrow the item's
+/// version constraint to the intersection of all of them.
+fn merge_requirements(item: &mut SkillInstallItem) -> Result<(), DependencyResolutionError> {
+    let incompatible = item
+        .requirements
+        .iter()
+        .any(|r| !r.origin.is_same_source(&item.entry.origin));
+    if incompatible {
+        return Err(DependencyResolutionError::Conflict(DependencyConflict {
+            skill_id: item.entry.id.clone(),
+            problem: "required from incompatible sources".to_string(),
+            requirements: item.requirements.clone(),
+        }));
+    }
+
+    if let Origin::Repository { version, .. } = &mut item.entry.origin {
+        *version = item
+            .requirements
+            .iter()
+            .filter_map(VersionRequirement::constraint)
+            .fold(None, |acc: Option<VersionConstraint>, c| {
+                Some(match acc {
+                    Some(acc) => acc.intersect(c),
+                    None => c.clone(),
+                })
+            });
+    }
+    Ok(())
+}
+
 #[cfg(test)]
-#[allow(clippy::unwrap_used)]
+#[allow(clippy::unwrap_used, clippy::panic)]
 mod tests {
     use super::*;
     use crate::core::manifest::SkillEntry;
@@ -212,6 +378,7 @@ mod tests {
                 editable: false,
             },
             groups: vec![],
+            extras: Vec::new(),
         }
     }
 
@@ -431,4 +598,129 @@ skill-c = { origin = { type = "local", path = "local/skill-c" } }
         assert_eq!(ids.iter().filter(|id| **id == "skill-b").count(), 1);
         assert_eq!(result.len(), 2);
     }
+
+    #[tokio::test]
+    async fn test_resolve_includes_requested_extras_only() {
+        let dir = TempDir::new().unwrap();
+        let skill_dir = dir.path().join("skill-a");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        std::fs::write(
+            skill_dir.join("skill-project.toml"),
+            r#"[dependencies]
+skill-b = { origin = { type = "local", path = "local/skill-b" } }
+
+[optional-dependencies.docs]
+skill-docs = { origin = { type = "local", path = "local/skill-docs" } }
+
+[optional-dependencies.examples]
+skill-examples = { origin = { type = "local", path = "local/skill-examples" } }
+"#,
+        )
+        .unwrap();
+
+        let mut plain = DependencyResolver::new(5);
+        let result = plain
+            .resolve_dependencies(
+                vec![make_local_entry("skill-a", "local/skill-a")],
+                dir.path(),
+            )
+            .await
+            .unwrap();
+        let ids: Vec<&str> = result.iter().map(|i| i.entry.id.as_str()).collect();
+        assert_eq!(ids, vec!["skill-a", "skill-b"]);
+
+        let mut with_docs = DependencyResolver::new(5);
+        let mut entry = make_local_entry("skill-a", "local/skill-a");
+        entry.extras = vec!["docs".to_string(), "missing".to_string()];
+        let result = with_docs
+            .resolve_dependencies(vec![entry], dir.path())
+            .await
+            .unwrap();
+        let ids: Vec<&str> = result.iter().map(|i| i.entry.id.as_str()).collect();
+        assert_eq!(ids, vec!["skill-a", "skill-b", "skill-docs"]);
+        let docs = result.iter().find(|i| i.entry.id == "skill-docs").unwrap();
+        assert_eq!(docs.depth, 1);
+        assert_eq!(docs.parent_skill.as_deref(), Some("skill-a"));
+        assert!(!result.iter().any(|i| i.entry.id == "skill-examples"));
+    }
+
+    fn write_repo_manifest(dir: &Path, skill_id: &str, deps: &[(&str, &str, &str)]) {
+        let skill_dir = dir.join(skill_id);
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        let mut body = String::from("[dependencies]\n");
+        for (dep, repo, version) in deps {
+            body.push_str(&format!(
+                "{dep} = {{ origin = {{ type = \"repository\", repo = \"{repo}\", skill = \"{dep}\", version = \"{version}\" }} }}\n"
+            ));
+        }
+        std::fs::write(skill_dir.join("skill-project.toml"), body).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_transitive_constraints_are_intersected() {
+        let dir = TempDir::new().unwrap();
+        // app -> web ^1.2 ; app -> tools -> web <1.5.0
+        write_repo_manifest(
+            dir.path(),
+            "app",
+            &[("web", "team", "^1.2"), ("tools", "team", "*")],
+        );
+        write_repo_manifest(dir.path(), "tools", &[("web", "team", "<1.5.0")]);
+
+        let mut resolver = DependencyResolver::new(5);
+        let result = resolver
+            .resolve_dependencies(vec![make_local_entry("app", "local/app")], dir.path())
+            .await
+            .unwrap();
+
+        let web = result.iter().find(|i| i.entry.id == "web").unwrap();
+        assert_eq!(web.requirements.len(), 2);
+        assert_eq!(
+            web.requirements[1].chain,
+            vec!["app".to_string(), "tools".to_string(), "web".to_string()]
+        );
+        let Origin::Repository {
+            version: Some(constraint),
+            ..
+        } = &web.entry.origin
+        else {
+            panic!("web must keep its repository origin with a constraint");
+        };
+        assert!(constraint.satisfies("1.4.0").unwrap());
+        assert!(!constraint.satisfies("1.5.0").unwrap());
+        assert!(!constraint.satisfies("1.1.0").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_incompatible_sources_report_the_chains() {
+        let dir = TempDir::new().unwrap();
+        write_repo_manifest(
+            dir.path(),
+            "app",
+            &[("web", "team", "^1"), ("tools", "team", "*")],
+        );
+        write_repo_manifest(dir.path(), "tools", &[("web", "vendor", "^1")]);
+
+        let mut resolver = DependencyResolver::new(5);
+        let err = resolver
+            .resolve_dependencies(vec![make_local_entry("app", "local/app")], dir.path())
+            .await
+            .unwrap_err();
+
+        let DependencyResolutionError::Conflict(conflict) = &err else {
+            panic!("expected a conflict, got {:?}", err);
+        };
+        assert_eq!(conflict.skill_id, "web");
+        let report = err.to_string();
+        assert!(
+            report.contains("via skill-project.toml -> app -> web"),
+            "{}",
+            report
+        );
+        assert!(
+            report.contains("'vendor/web', via skill-project.toml -> app -> tools -> web"),
+            "{}",
+            report
+        );
+    }
 }
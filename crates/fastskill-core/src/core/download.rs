@@ -0,0 +1,622 @@
+//! Resumable, verified HTTP downloads of skill archives
+//!
+//! [`DownloadManager`] is shared by everything a [`FastSkillService`] downloads
+//! over plain HTTP (currently zip-url skills). Bytes go to a `.part` file in
+//! `<skills dir>/.fastskill/downloads/` next to the response's `ETag` /
+//! `Last-Modified`. A dropped connection is retried with a `Range` request
+//! that continues where the file ends, and so is a later install of the same
+//! URL after the process was interrupted. `If-Range` makes the server send the
+//! whole file again if it changed in between, so a resumed file never mixes
+//! two versions. Finished files are checked against an expected SHA-256 when
+//! one is known and against the server's length otherwise.
+//!
+//! [`FastSkillService`]: crate::core::service::FastSkillService
+
+use crate::core::service::ServiceError;
+use reqwest::header::{CONTENT_RANGE, ETAG, IF_RANGE, LAST_MODIFIED, RANGE};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Semaphore;
+
+/// Downloads running at once unless configured otherwise
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Attempts per download, the first one included
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 4;
+
+/// Bytes between two progress reports
+const PROGRESS_STEP: u64 = 256 * 1024;
+
+/// Largest file [`DownloadManager::fetch_optional`] accepts
+pub const MAX_SIDECAR_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Progress of one download. The core emits neutral data; the caller (CLI)
+/// decides how to render it.
+#[derive(Debug, Clone)]
+pub struct DownloadProgress {
+    pub url: String,
+    /// Bytes on disk so far, resumed ones included
+    pub downloaded: u64,
+    /// Size of the whole file, when the server reports it
+    pub total: Option<u64>,
+    /// Bytes kept from an earlier, interrupted attempt
+    pub resumed_from: u64,
+    pub finished: bool,
+}
+
+/// Callback receiving [`DownloadProgress`] reports
+pub type DownloadObserver = Arc<dyn Fn(DownloadProgress) + Send + Sync>;
+
+/// A finished download
+#[derive(Debug, Clone)]
+pub struct Downloaded {
+    pub path: PathBuf,
+    pub size: u64,
+    /// `sha256:<hex>` of the file
+    pub checksum: String,
+    pub etag: Option<String>,
+    /// Bytes that came from an earlier, interrupted attempt
+    pub resumed_from: u64,
+}
+
+/// What the `.part` file belongs to
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct PartialMeta {
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_modified: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    total: Option<u64>,
+}
+
+impl PartialMeta {
+    /// Value for `If-Range`; a weak ETag cannot be used for ranges
+    fn validator(&self) -> Option<&str> {
+        self.etag
+            .as_deref()
+            .filter(|etag| !etag.starts_with("W/"))
+            .or(self.last_modified.as_deref())
+    }
+}
+
+enum AttemptError {
+    /// Worth another attempt, resuming from what is on disk
+    Retry(String),
+    Fatal(ServiceError),
+}
+
+impl From<std::io::Error> for AttemptError {
+    fn from(e: std::io::Error) -> Self {
+        AttemptError::Fatal(e.into())
+    }
+}
+
+/// Runs downloads with a concurrency limit, resuming and verifying them
+pub struct DownloadManager {
+    client: reqwest::Client,
+    partial_dir: PathBuf,
+    permits: Arc<Semaphore>,
+    max_attempts: u32,
+    retry_delay: Duration,
+    observer: Option<DownloadObserver>,
+    in_flight: Mutex<HashSet<String>>,
+    /// Numbers files of concurrent downloads of one URL
+    spare: AtomicU64,
+}
+
+impl DownloadManager {
+    /// Keep partial downloads in `partial_dir`
+    pub fn new(partial_dir: PathBuf) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            partial_dir,
+            permits: Arc::new(Semaphore::new(DEFAULT_CONCURRENCY)),
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            retry_delay: Duration::from_millis(500),
+            observer: None,
+            in_flight: Mutex::new(HashSet::new()),
+            spare: AtomicU64::new(0),
+        }
+    }
+
+    /// Default partial-download directory: `<skills dir>/.fastskill/downloads`.
+    pub fn default_dir(skills_dir: &Path) -> PathBuf {
+        skills_dir.join(".fastskill").join("downloads")
+    }
+
+    /// Run at most `concurrency` downloads at once (at least one)
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.permits = Arc::new(Semaphore::new(concurrency.max(1)));
+        self
+    }
+
+    /// Try each download up to `attempts` times (at least once)
+    pub fn with_max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Report progress to `observer`
+    pub fn with_progress(mut self, observer: DownloadObserver) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Download `url` to `dest`. With `expected` (`sha256:<hex>`), a file
+    /// with another checksum is discarded and an error returned.
+    pub async fn download(
+        &self,
+        url: &str,
+        dest: &Path,
+        expected: Option<&str>,
+    ) -> Result<Downloaded, ServiceError> {
+        let _permit = self
+            .permits
+            .acquire()
+            .await
+            .map_err(|e| ServiceError::Custom(format!("Download queue closed: {e}")))?;
+        tokio::fs::create_dir_all(&self.partial_dir).await?;
+
+        // A second download of the same URL while one runs gets its own file
+        // rather than resuming into the first one's
+        let key = format!("{:x}", Sha256::digest(url.as_bytes()));
+        let key = key[..16].to_string();
+        let (name, claim) = match InFlight::claim(&self.in_flight, &key) {
+            Some(claim) => (key, Some(claim)),
+            None => (
+                format!("{}-{}", key, self.spare.fetch_add(1, Ordering::Relaxed)),
+                None,
+            ),
+        };
+        let part = self.partial_dir.join(format!("{name}.part"));
+        let meta_path = self.partial_dir.join(format!("{name}.json"));
+
+        let mut attempt = 1;
+        let (meta, resumed_from) = loop {
+            match self.attempt(url, &part, &meta_path).await {
+                Ok(done) => break done,
+                Err(AttemptError::Retry(reason)) if attempt < self.max_attempts => {
+                    tracing::debug!(
+                        "Download of {} interrupted ({}), retrying ({}/{})",
+                        url,
+                        reason,
+                        attempt + 1,
+                        self.max_attempts
+                    );
+                    tokio::time::sleep(self.retry_delay * attempt).await;
+                    attempt += 1;
+                }
+                Err(AttemptError::Retry(reason)) if claim.is_some() => {
+                    return Err(ServiceError::InvalidOperation(format!(
+                        "Failed to download '{url}': {reason} (the partial download is kept \
+                         and resumes on the next attempt)"
+                    )));
+                }
+                Err(AttemptError::Retry(reason)) => {
+                    discard(&part, &meta_path).await;
+                    return Err(ServiceError::InvalidOperation(format!(
+                        "Failed to download '{url}': {reason}"
+                    )));
+                }
+                Err(AttemptError::Fatal(e)) => return Err(e),
+            }
+        };
+
+        let bytes = tokio::fs::read(&part).await?;
+        let checksum = format!("sha256:{:x}", Sha256::digest(&bytes));
+        if let Some(expected) = expected {
+            if !checksum.eq_ignore_ascii_case(expected) {
+                discard(&part, &meta_path).await;
+                return Err(ServiceError::Validation(format!(
+                    "Checksum mismatch for '{url}': expected {expected}, got {checksum}"
+                )));
+            }
+        }
+
+        if let Some(parent) = dest.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        if tokio::fs::rename(&part, dest).await.is_err() {
+            tokio::fs::copy(&part, dest).await?;
+        }
+        discard(&part, &meta_path).await;
+        Ok(Downloaded {
+            path: dest.to_path_buf(),
+            size: bytes.len() as u64,
+            checksum,
+            etag: meta.etag,
+            resumed_from,
+        })
+    }
+
+    /// Body of a small optional file next to a download (such as a file
+    /// manifest), or `None` when the server does not serve it, cannot be
+    /// reached or sends more than [`MAX_SIDECAR_BYTES`].
+    pub async fn fetch_optional(&self, url: &str) -> Option<Vec<u8>> {
+        let response = match self.client.get(url).send().await {
+            Ok(response) if response.status().is_success() => response,
+            Ok(response) => {
+                tracing::debug!("No {} (HTTP {})", url, response.status());
+                return None;
+            }
+            Err(e) => {
+                tracing::debug!("Could not fetch {}: {}", url, e);
+                return None;
+            }
+        };
+        if response.content_length().unwrap_or(0) > MAX_SIDECAR_BYTES {
+            return None;
+        }
+        let body = response.bytes().await.ok()?;
+        (body.len() as u64 <= MAX_SIDECAR_BYTES).then(|| body.to_vec())
+    }
+
+    /// One request: resume `part` if it belongs to the current file, else
+    /// start over. Returns the file's metadata and the resumed byte count.
+    async fn attempt(
+        &self,
+        url: &str,
+        part: &Path,
+        meta_path: &Path,
+    ) -> Result<(PartialMeta, u64), AttemptError> {
+        let stored = read_meta(meta_path).await.filter(|m| m.url == url);
+        let on_disk = tokio::fs::metadata(part).await.map_or(0, |m| m.len());
+        let validator = stored
+            .as_ref()
+            .and_then(|m| m.validator().map(String::from));
+        let offset = if validator.is_some() { on_disk } else { 0 };
+
+        let mut request = self.client.get(url);
+        if let (true, Some(validator)) = (offset > 0, &validator) {
+            request = request
+                .header(RANGE, format!("bytes={offset}-"))
+                .header(IF_RANGE, validator.as_str());
+        }
+        let mut response = request
+            .send()
+            .await
+            .map_err(|e| AttemptError::Retry(e.to_string()))?;
+        let status = response.status();
+        #[cfg(feature = "chaos")]
+        let status = crate::core::chaos::upstream_status(status);
+
+        if status == StatusCode::RANGE_NOT_SATISFIABLE && offset > 0 {
+            if let Some(meta) = stored.filter(|m| m.total == Some(offset)) {
+                // Everything arrived before the last attempt was cut off
+                return Ok((meta, offset));
+            }
+            discard(part, meta_path).await;
+            return Err(AttemptError::Retry(
+                "partial download no longer matches the file".to_string(),
+            ));
+        }
+        if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+            return Err(AttemptError::Retry(format!("HTTP {status}")));
+        }
+        if !status.is_success() {
+            return Err(AttemptError::Fatal(ServiceError::InvalidOperation(
+                format!("Failed to download '{url}': HTTP {status}"),
+            )));
+        }
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(String::from)
+        };
+        let resumed = status == StatusCode::PARTIAL_CONTENT
+            && offset > 0
+            && header(CONTENT_RANGE).and_then(|r| range_start(&r)) == Some(offset);
+        let start = if resumed { offset } else { 0 };
+        let meta = PartialMeta {
+            url: url.to_string(),
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+            total: response.content_length().map(|len| len + start),
+        };
+        let encoded = serde_json::to_vec(&meta)
+            .map_err(|e| AttemptError::Fatal(ServiceError::Custom(e.to_string())))?;
+        tokio::fs::write(meta_path, encoded).await?;
+
+        let mut file = if resumed {
+            tokio::fs::OpenOptions::new()
+                .append(true)
+                .open(part)
+                .await?
+        } else {
+            tokio::fs::File::create(part).await?
+        };
+        let mut downloaded = start;
+        let mut reported = start;
+        self.report(url, downloaded, meta.total, start, false);
+
+        #[cfg(feature = "chaos")]
+        if crate::core::chaos::should_inject(crate::core::chaos::Fault::DownloadFailure) {
+            return Err(AttemptError::Retry(
+                "connection reset (injected fault)".to_string(),
+            ));
+        }
+        loop {
+            match response.chunk().await {
+                Ok(Some(chunk)) => {
+                    file.write_all(&chunk).await?;
+                    downloaded += chunk.len() as u64;
+                    if downloaded - reported >= PROGRESS_STEP {
+                        reported = downloaded;
+                        self.report(url, downloaded, meta.total, start, false);
+                    }
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    file.flush().await?;
+                    return Err(AttemptError::Retry(format!(
+                        "connection lost after {downloaded} bytes: {e}"
+                    )));
+                }
+            }
+        }
+        file.flush().await?;
+        if let Some(total) = meta.total {
+            if downloaded != total {
+                return Err(AttemptError::Retry(format!(
+                    "received {downloaded} of {total} bytes"
+                )));
+            }
+        }
+        self.report(url, downloaded, meta.total, start, true);
+        Ok((meta, start))
+    }
+
+    fn report(&self, url: &str, downloaded: u64, total: Option<u64>, resumed: u64, done: bool) {
+        if let Some(observer) = &self.observer {
+            observer(DownloadProgress {
+                url: url.to_string(),
+                downloaded,
+                total,
+                resumed_from: resumed,
+                finished: done,
+            });
+        }
+    }
+}
+
+/// Marks a URL's `.part` file as in use until dropped
+struct InFlight<'a> {
+    set: &'a Mutex<HashSet<String>>,
+    key: String,
+}
+
+impl<'a> InFlight<'a> {
+    fn claim(set: &'a Mutex<HashSet<String>>, key: &str) -> Option<Self> {
+        let mut keys = set.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        keys.insert(key.to_string()).then(|| InFlight {
+            set,
+            key: key.to_string(),
+        })
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        let mut keys = self
+            .set
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        keys.remove(&self.key);
+    }
+}
+
+/// First byte of a `Content-Range: bytes <start>-<end>/<total>` header
+fn range_start(content_range: &str) -> Option<u64> {
+    content_range
+        .strip_prefix("bytes ")?
+        .split('-')
+        .next()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+async fn read_meta(path: &Path) -> Option<PartialMeta> {
+    let content = tokio::fs::read(path).await.ok()?;
+    serde_json::from_slice(&content).ok()
+}
+
+async fn discard(part: &Path, meta_path: &Path) {
+    let _ = tokio::fs::remove_file(part).await;
+    let _ = tokio::fs::remove_file(meta_path).await;
+}
+
+/// Split a `#sha256=<hex>` fragment off a download URL. Returns the URL to
+/// request and the expected checksum as `sha256:<hex>`.
+pub fn split_checksum(url: &str) -> (&str, Option<String>) {
+    match url.split_once('#') {
+        Some((base, fragment)) => {
+            let expected = fragment
+                .split('&')
+                .find_map(|part| part.strip_prefix("sha256="))
+                .map(|hex| format!("sha256:{}", hex.to_ascii_lowercase()));
+            (base, expected)
+        }
+        None => (url, None),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const BODY: &[u8] = b"0123456789abcdefghij";
+
+    fn checksum(bytes: &[u8]) -> String {
+        format!("sha256:{:x}", Sha256::digest(bytes))
+    }
+
+    fn manager(dir: &TempDir) -> DownloadManager {
+        let mut manager = DownloadManager::new(dir.path().join("partial")).with_max_attempts(2);
+        manager.retry_delay = Duration::ZERO;
+        manager
+    }
+
+    /// Leave the first `len` bytes of BODY behind as an interrupted download
+    async fn seed_partial(manager: &DownloadManager, url: &str, len: usize, etag: &str) {
+        let key = format!("{:x}", Sha256::digest(url.as_bytes()));
+        let dir = &manager.partial_dir;
+        tokio::fs::create_dir_all(dir).await.unwrap();
+        tokio::fs::write(dir.join(format!("{}.part", &key[..16])), &BODY[..len])
+            .await
+            .unwrap();
+        let meta = PartialMeta {
+            url: url.to_string(),
+            etag: Some(etag.to_string()),
+            last_modified: None,
+            total: Some(BODY.len() as u64),
+        };
+        tokio::fs::write(
+            dir.join(format!("{}.json", &key[..16])),
+            serde_json::to_vec(&meta).unwrap(),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_download_verifies_checksum_and_reports_progress() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/skill.zip"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(BODY.to_vec()))
+            .mount(&server)
+            .await;
+        let dir = TempDir::new().unwrap();
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&reports);
+        let manager = manager(&dir).with_progress(Arc::new(move |p: DownloadProgress| {
+            sink.lock()
+                .unwrap()
+                .push((p.downloaded, p.total, p.finished));
+        }));
+        let url = format!("{}/skill.zip", server.uri());
+        let dest = dir.path().join("out").join("skill.zip");
+
+        let done = manager
+            .download(&url, &dest, Some(&checksum(BODY)))
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read(&dest).unwrap(), BODY);
+        assert_eq!(done.checksum, checksum(BODY));
+        assert_eq!(done.resumed_from, 0);
+        let reports = reports.lock().unwrap().clone();
+        assert_eq!(reports.first(), Some(&(0, Some(20), false)));
+        assert_eq!(reports.last(), Some(&(20, Some(20), true)));
+
+        let err = manager
+            .download(&url, &dest, Some("sha256:00"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch"), "{}", err);
+        let leftovers = std::fs::read_dir(dir.path().join("partial")).unwrap();
+        assert_eq!(leftovers.count(), 0, "a bad download must not be resumed");
+    }
+
+    #[tokio::test]
+    async fn test_download_resumes_with_range_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/skill.zip"))
+            .and(header("range", "bytes=8-"))
+            .and(header("if-range", "\"v1\""))
+            .respond_with(
+                ResponseTemplate::new(206)
+                    .set_body_bytes(BODY[8..].to_vec())
+                    .insert_header("content-range", "bytes 8-19/20")
+                    .insert_header("etag", "\"v1\""),
+            )
+            .mount(&server)
+            .await;
+        let dir = TempDir::new().unwrap();
+        let manager = manager(&dir);
+        let url = format!("{}/skill.zip", server.uri());
+        seed_partial(&manager, &url, 8, "\"v1\"").await;
+
+        let dest = dir.path().join("skill.zip");
+        let done = manager
+            .download(&url, &dest, Some(&checksum(BODY)))
+            .await
+            .unwrap();
+        assert_eq!(done.resumed_from, 8);
+        assert_eq!(std::fs::read(&dest).unwrap(), BODY);
+    }
+
+    #[tokio::test]
+    async fn test_download_restarts_when_file_changed() {
+        // The server ignores the range because the ETag no longer matches
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/skill.zip"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_bytes(BODY.to_vec())
+                    .insert_header("etag", "\"v2\""),
+            )
+            .mount(&server)
+            .await;
+        let dir = TempDir::new().unwrap();
+        let manager = manager(&dir);
+        let url = format!("{}/skill.zip", server.uri());
+        seed_partial(&manager, &url, 8, "\"v1\"").await;
+
+        let dest = dir.path().join("skill.zip");
+        let done = manager.download(&url, &dest, None).await.unwrap();
+        assert_eq!(done.resumed_from, 0);
+        assert_eq!(done.etag.as_deref(), Some("\"v2\""));
+        assert_eq!(std::fs::read(&dest).unwrap(), BODY);
+    }
+
+    #[tokio::test]
+    async fn test_download_client_error_is_not_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let dir = TempDir::new().unwrap();
+        let url = format!("{}/missing.zip", server.uri());
+        let err = manager(&dir)
+            .download(&url, &dir.path().join("x.zip"), None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("HTTP 404"), "{}", err);
+    }
+
+    #[test]
+    fn test_split_checksum() {
+        assert_eq!(
+            split_checksum("https://x/s.zip#sha256=ABCD"),
+            ("https://x/s.zip", Some("sha256:abcd".to_string()))
+        );
+        assert_eq!(split_checksum("https://x/s.zip"), ("https://x/s.zip", None));
+        assert_eq!(
+            split_checksum("https://x/s.zip#top"),
+            ("https://x/s.zip", None)
+        );
+        assert_eq!(range_start("bytes 8-19/20"), Some(8));
+        assert_eq!(range_start("bytes */20"), None);
+    }
+}
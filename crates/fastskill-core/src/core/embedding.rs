@@ -2,8 +2,26 @@
 
 use crate::core::service::ServiceError;
 use async_trait::async_trait;
-use reqwest::Client;
+use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Texts sent in one embeddings API call unless configured otherwise
+pub const DEFAULT_EMBEDDING_BATCH_SIZE: usize = 100;
+
+/// Query embeddings kept by the query cache unless configured otherwise
+pub const DEFAULT_QUERY_CACHE_SIZE: usize = 1000;
+
+/// Seconds a cached query embedding is reused unless configured otherwise
+pub const DEFAULT_QUERY_CACHE_TTL_SECS: u64 = 3600;
+
+/// Retries of a rate-limited (`429`) call before giving up
+const MAX_RATE_LIMIT_RETRIES: u32 = 5;
+
+/// Longest wait between rate-limit retries
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
 
 /// Response from OpenAI embeddings API
 #[derive(Debug, Deserialize)]
@@ -14,6 +32,9 @@ struct OpenAIEmbeddingResponse {
 #[derive(Debug, Deserialize)]
 struct OpenAIEmbeddingData {
     embedding: Vec<f32>,
+    /// Position of the input this embedding belongs to
+    #[serde(default)]
+    index: usize,
 }
 
 /// Embedding service trait
@@ -24,6 +45,172 @@ pub trait EmbeddingService: Send + Sync {
 
     /// Generate embeddings for a search query
     async fn embed_query(&self, query: &str) -> Result<Vec<f32>, ServiceError>;
+
+    /// Generate embeddings for several texts, in input order. Providers that
+    /// accept many inputs per call override this; the default embeds one at a time.
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, ServiceError> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for text in texts {
+            embeddings.push(self.embed_text(text).await?);
+        }
+        Ok(embeddings)
+    }
+
+    /// How many texts callers should pass to [`embed_batch`](Self::embed_batch) at once
+    fn batch_size(&self) -> usize {
+        1
+    }
+}
+
+/// LRU cache of query embeddings whose entries expire after a TTL
+///
+/// Discovery prompts repeat ("extract text from PDF"), and search and
+/// `/resolve` build a fresh client per request, so clients created with
+/// [`OpenAIEmbeddingService::from_config`] share one process-wide cache
+/// ([`shared`](Self::shared)). Entries are keyed by provider URL, model and
+/// query text; lookups are counted in the service metrics.
+#[derive(Debug)]
+pub struct QueryEmbeddingCache {
+    max_entries: usize,
+    ttl: Duration,
+    entries: Mutex<CacheEntries>,
+}
+
+static SHARED_QUERY_CACHE: OnceLock<Arc<QueryEmbeddingCache>> = OnceLock::new();
+
+#[derive(Debug, Default)]
+struct CacheEntries {
+    map: HashMap<String, CachedEmbedding>,
+    /// Bumped on every access; the entry used longest ago is evicted first
+    clock: u64,
+}
+
+#[derive(Debug)]
+struct CachedEmbedding {
+    embedding: Vec<f32>,
+    stored_at: Instant,
+    last_used: u64,
+}
+
+impl QueryEmbeddingCache {
+    /// A cache holding at most `max_entries` (at least one) for `ttl` each
+    pub fn new(max_entries: usize, ttl: Duration) -> Self {
+        Self {
+            max_entries: max_entries.max(1),
+            ttl,
+            entries: Mutex::new(CacheEntries::default()),
+        }
+    }
+
+    /// The process-wide cache, created with `config`'s size and TTL by the
+    /// first client that asks for it. `None` when `config` sets
+    /// `query_cache_size` to 0.
+    pub fn shared(config: &crate::core::service::EmbeddingConfig) -> Option<Arc<Self>> {
+        let max_entries = config.query_cache_size.unwrap_or(DEFAULT_QUERY_CACHE_SIZE);
+        if max_entries == 0 {
+            return None;
+        }
+        let cache = SHARED_QUERY_CACHE.get_or_init(|| {
+            let ttl = config
+                .query_cache_ttl
+                .unwrap_or(DEFAULT_QUERY_CACHE_TTL_SECS);
+            Arc::new(Self::new(max_entries, Duration::from_secs(ttl)))
+        });
+        Some(Arc::clone(cache))
+    }
+
+    /// The embedding cached for `key`, unless it expired
+    pub fn get(&self, key: &str) -> Option<Vec<f32>> {
+        self.get_at(key, Instant::now())
+    }
+
+    /// Cache `embedding` for `key`, evicting the least recently used entry when full
+    pub fn insert(&self, key: String, embedding: Vec<f32>) {
+        self.insert_at(key, embedding, Instant::now())
+    }
+
+    /// Entries currently held, expired ones included until they are evicted
+    pub fn len(&self) -> usize {
+        self.lock().map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop every entry, returning how many were held
+    pub fn clear(&self) -> usize {
+        let mut entries = self.lock();
+        let cleared = entries.map.len();
+        entries.map.clear();
+        cleared
+    }
+
+    /// [`clear`](Self::clear) the process-wide cache; 0 when no client has
+    /// created it yet
+    pub fn clear_shared() -> usize {
+        SHARED_QUERY_CACHE.get().map_or(0, |cache| cache.clear())
+    }
+
+    fn get_at(&self, key: &str, now: Instant) -> Option<Vec<f32>> {
+        let mut entries = self.lock();
+        entries.clock += 1;
+        let clock = entries.clock;
+        let fresh = entries
+            .map
+            .get(key)
+            .map(|entry| now.saturating_duration_since(entry.stored_at) < self.ttl);
+        let hit = match fresh {
+            Some(true) => entries.map.get_mut(key).map(|entry| {
+                entry.last_used = clock;
+                entry.embedding.clone()
+            }),
+            Some(false) => {
+                entries.map.remove(key);
+                None
+            }
+            None => None,
+        };
+        crate::core::metrics::global().record_query_cache_lookup(hit.is_some());
+        hit
+    }
+
+    fn insert_at(&self, key: String, embedding: Vec<f32>, now: Instant) {
+        let mut entries = self.lock();
+        entries.clock += 1;
+        let clock = entries.clock;
+        if !entries.map.contains_key(&key) && entries.map.len() >= self.max_entries {
+            let ttl = self.ttl;
+            entries
+                .map
+                .retain(|_, entry| now.saturating_duration_since(entry.stored_at) < ttl);
+            if entries.map.len() >= self.max_entries {
+                let oldest = entries
+                    .map
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.last_used)
+                    .map(|(key, _)| key.clone());
+                if let Some(oldest) = oldest {
+                    entries.map.remove(&oldest);
+                }
+            }
+        }
+        entries.map.insert(
+            key,
+            CachedEmbedding {
+                embedding,
+                stored_at: now,
+                last_used: clock,
+            },
+        );
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CacheEntries> {
+        match self.entries.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
 }
 
 /// OpenAI embedding service implementation
@@ -32,6 +219,11 @@ pub struct OpenAIEmbeddingService {
     base_url: String,
     model: String,
     api_key: String,
+    batch_size: usize,
+    /// First wait after a `429`; doubled on each further retry
+    retry_base_delay: Duration,
+    /// Cache consulted by [`embed_query`](EmbeddingService::embed_query)
+    query_cache: Option<Arc<QueryEmbeddingCache>>,
 }
 
 impl OpenAIEmbeddingService {
@@ -42,42 +234,112 @@ impl OpenAIEmbeddingService {
             base_url,
             model,
             api_key,
+            batch_size: DEFAULT_EMBEDDING_BATCH_SIZE,
+            retry_base_delay: Duration::from_millis(500),
+            query_cache: None,
         }
     }
 
-    /// Create from embedding config and API key
+    /// Create from embedding config and API key, using the shared query cache
     pub fn from_config(config: &crate::core::service::EmbeddingConfig, api_key: String) -> Self {
-        Self::new(
+        let service = Self::new(
             config.openai_base_url.clone(),
             config.embedding_model.clone(),
             api_key,
         )
+        .with_query_cache(QueryEmbeddingCache::shared(config));
+        match config.batch_size {
+            Some(batch_size) => service.with_batch_size(batch_size),
+            None => service,
+        }
+    }
+
+    /// Send at most `batch_size` texts per API call (at least one).
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Reuse query embeddings from `cache` (no caching with `None`).
+    pub fn with_query_cache(mut self, cache: Option<Arc<QueryEmbeddingCache>>) -> Self {
+        self.query_cache = cache;
+        self
     }
 
-    /// Make the actual API call to OpenAI
-    async fn call_openai_api(&self, text: &str) -> Result<Vec<f32>, ServiceError> {
+    #[cfg(test)]
+    fn with_retry_base_delay(mut self, delay: Duration) -> Self {
+        self.retry_base_delay = delay;
+        self
+    }
+
+    /// Make the actual API call to OpenAI, counting it in the service metrics
+    async fn call_openai_api(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, ServiceError> {
+        let result = self.request_embeddings(texts).await;
+        crate::core::metrics::global().record_embedding_call(result.is_ok());
+        result
+    }
+
+    /// Wait before retry `attempt` (0-based): the server's `Retry-After` when
+    /// it sends one, else exponential backoff from `retry_base_delay`.
+    fn retry_delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        retry_after
+            .unwrap_or_else(|| {
+                self.retry_base_delay
+                    .saturating_mul(2u32.saturating_pow(attempt))
+            })
+            .min(MAX_RETRY_DELAY)
+    }
+
+    async fn request_embeddings(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, ServiceError> {
+        #[cfg(feature = "chaos")]
+        crate::core::chaos::embedding_fault().await?;
+
         #[derive(Serialize)]
-        struct OpenAIRequest {
-            input: String,
-            model: String,
+        struct OpenAIRequest<'a> {
+            input: &'a [String],
+            model: &'a str,
         }
 
         let request = OpenAIRequest {
-            input: text.to_string(),
-            model: self.model.clone(),
+            input: texts,
+            model: &self.model,
         };
 
         let url = format!("{}/embeddings", self.base_url.trim_end_matches('/'));
 
-        let response = self
-            .client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| ServiceError::Custom(format!("OpenAI API request failed: {}", e)))?;
+        let mut attempt = 0;
+        let response = loop {
+            let response = self
+                .client
+                .post(&url)
+                .header("Authorization", format!("Bearer {}", self.api_key))
+                .header("Content-Type", "application/json")
+                .json(&request)
+                .send()
+                .await
+                .map_err(|e| ServiceError::Custom(format!("OpenAI API request failed: {}", e)))?;
+
+            if response.status() != StatusCode::TOO_MANY_REQUESTS
+                || attempt >= MAX_RATE_LIMIT_RETRIES
+            {
+                break response;
+            }
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .map(Duration::from_secs);
+            let delay = self.retry_delay(attempt, retry_after);
+            tracing::warn!(
+                "OpenAI API rate limited; retrying in {:.1}s ({}/{})",
+                delay.as_secs_f64(),
+                attempt + 1,
+                MAX_RATE_LIMIT_RETRIES
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        };
 
         if !response.status().is_success() {
             let status = response.status();
@@ -88,18 +350,34 @@ impl OpenAIEmbeddingService {
             )));
         }
 
-        let embedding_response: OpenAIEmbeddingResponse = response
+        let mut embedding_response: OpenAIEmbeddingResponse = response
             .json()
             .await
             .map_err(|e| ServiceError::Custom(format!("Failed to parse OpenAI response: {}", e)))?;
 
-        if embedding_response.data.is_empty() {
-            return Err(ServiceError::Custom(
-                "No embeddings returned from OpenAI".to_string(),
-            ));
+        if embedding_response.data.len() != texts.len() {
+            return Err(ServiceError::Custom(format!(
+                "OpenAI returned {} embeddings for {} inputs",
+                embedding_response.data.len(),
+                texts.len()
+            )));
         }
 
-        Ok(embedding_response.data[0].embedding.clone())
+        // The API documents `data` in input order, but each item carries its
+        // input index; trust the index.
+        embedding_response.data.sort_by_key(|d| d.index);
+        Ok(embedding_response
+            .data
+            .into_iter()
+            .map(|d| d.embedding)
+            .collect())
+    }
+
+    async fn embed_one(&self, text: &str) -> Result<Vec<f32>, ServiceError> {
+        self.call_openai_api(&[text.to_string()])
+            .await?
+            .pop()
+            .ok_or_else(|| ServiceError::Custom("No embeddings returned from OpenAI".to_string()))
     }
 }
 
@@ -108,11 +386,196 @@ impl EmbeddingService for OpenAIEmbeddingService {
     async fn embed_text(&self, text: &str) -> Result<Vec<f32>, ServiceError> {
         // For text content, we might want to limit length or preprocess
         // For now, just call the API directly
-        self.call_openai_api(text).await
+        self.embed_one(text).await
     }
 
     async fn embed_query(&self, query: &str) -> Result<Vec<f32>, ServiceError> {
-        // Queries are typically shorter, so we can pass them through directly
-        self.call_openai_api(query).await
+        let Some(cache) = &self.query_cache else {
+            return self.embed_one(query).await;
+        };
+        let key = format!("{}\n{}\n{}", self.base_url, self.model, query);
+        if let Some(embedding) = cache.get(&key) {
+            return Ok(embedding);
+        }
+        let embedding = self.embed_one(query).await?;
+        cache.insert(key, embedding.clone());
+        Ok(embedding)
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, ServiceError> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for chunk in texts.chunks(self.batch_size) {
+            embeddings.extend(self.call_openai_api(chunk).await?);
+        }
+        Ok(embeddings)
+    }
+
+    fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+    /// Answers with one embedding per input, `[input index, text length]`,
+    /// listed in reverse order to check the index is honoured.
+    struct EchoEmbeddings;
+
+    impl Respond for EchoEmbeddings {
+        fn respond(&self, request: &Request) -> ResponseTemplate {
+            let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+            let inputs = body["input"].as_array().unwrap();
+            let data: Vec<serde_json::Value> = inputs
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(i, text)| {
+                    serde_json::json!({
+                        "index": i,
+                        "embedding": [i as f32, text.as_str().unwrap().len() as f32],
+                    })
+                })
+                .collect();
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "data": data }))
+        }
+    }
+
+    fn service(server: &MockServer) -> OpenAIEmbeddingService {
+        OpenAIEmbeddingService::new(server.uri(), "test-model".to_string(), "key".to_string())
+            .with_retry_base_delay(Duration::from_millis(1))
+    }
+
+    #[tokio::test]
+    async fn test_embed_batch_splits_into_batch_size_calls() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/embeddings"))
+            .respond_with(EchoEmbeddings)
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let texts: Vec<String> = (0..5).map(|i| "x".repeat(i + 1)).collect();
+        let embeddings = service(&server)
+            .with_batch_size(2)
+            .embed_batch(&texts)
+            .await
+            .unwrap();
+        // Batches of [2, 2, 1]; each embedding is [index in batch, length]
+        assert_eq!(
+            embeddings,
+            vec![
+                vec![0.0, 1.0],
+                vec![1.0, 2.0],
+                vec![0.0, 3.0],
+                vec![1.0, 4.0],
+                vec![0.0, 5.0],
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_call_is_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/embeddings"))
+            .respond_with(ResponseTemplate::new(429))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/embeddings"))
+            .respond_with(EchoEmbeddings)
+            .mount(&server)
+            .await;
+
+        let embedding = service(&server).embed_text("abc").await.unwrap();
+        assert_eq!(embedding, vec![0.0, 3.0]);
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_gives_up_after_max_retries() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/embeddings"))
+            .respond_with(ResponseTemplate::new(429))
+            .mount(&server)
+            .await;
+
+        let err = service(&server).embed_text("abc").await.unwrap_err();
+        assert!(err.to_string().contains("429"), "{}", err);
+        assert_eq!(
+            server.received_requests().await.unwrap().len(),
+            MAX_RATE_LIMIT_RETRIES as usize + 1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_repeated_query_is_served_from_cache() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/embeddings"))
+            .respond_with(EchoEmbeddings)
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let cache = Arc::new(QueryEmbeddingCache::new(10, Duration::from_secs(60)));
+        let service = service(&server).with_query_cache(Some(Arc::clone(&cache)));
+        let first = service.embed_query("extract text from PDF").await.unwrap();
+        let second = service.embed_query("extract text from PDF").await.unwrap();
+        assert_eq!(first, second);
+        service.embed_query("fill a form").await.unwrap();
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_query_cache_expires_entries_after_ttl() {
+        let cache = QueryEmbeddingCache::new(10, Duration::from_secs(60));
+        let start = Instant::now();
+        cache.insert_at("q".to_string(), vec![1.0], start);
+
+        assert_eq!(
+            cache.get_at("q", start + Duration::from_secs(59)),
+            Some(vec![1.0])
+        );
+        assert_eq!(cache.get_at("q", start + Duration::from_secs(60)), None);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_query_cache_evicts_least_recently_used() {
+        let cache = QueryEmbeddingCache::new(2, Duration::from_secs(60));
+        let now = Instant::now();
+        cache.insert_at("a".to_string(), vec![1.0], now);
+        cache.insert_at("b".to_string(), vec![2.0], now);
+        assert!(cache.get_at("a", now).is_some());
+        cache.insert_at("c".to_string(), vec![3.0], now);
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get_at("a", now).is_some());
+        assert!(cache.get_at("b", now).is_none());
+        assert!(cache.get_at("c", now).is_some());
+
+        assert_eq!(cache.clear(), 2);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off_and_honours_retry_after() {
+        let service = OpenAIEmbeddingService::new(String::new(), String::new(), String::new());
+        assert_eq!(service.retry_delay(0, None), Duration::from_millis(500));
+        assert_eq!(service.retry_delay(2, None), Duration::from_secs(2));
+        assert_eq!(service.retry_delay(20, None), MAX_RETRY_DELAY);
+        assert_eq!(
+            service.retry_delay(0, Some(Duration::from_secs(7))),
+            Duration::from_secs(7)
+        );
     }
 }
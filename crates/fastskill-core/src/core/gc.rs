@@ -0,0 +1,460 @@
+//! Garbage collection of skill storage
+//!
+//! Removing or updating skills can leave things behind: skill directories no
+//! lock file entry points at, vector index rows for skills that are gone,
+//! partial downloads that were never resumed and build cache entries for
+//! skills no longer built. [`GarbageCollector::scan`] lists them with the
+//! space they take; [`GarbageCollector::prune`] deletes what a scan found.
+//!
+//! Everything is judged against the set of skill ids the caller considers
+//! referenced (the ids in `skills.lock` for `fastskill gc`).
+
+use crate::core::build_cache::BuildCache;
+use crate::core::download::DownloadManager;
+use crate::core::link;
+use crate::core::scope::{is_scope_dir, remove_scope_dir_if_empty};
+use crate::core::service::{ServiceError, SkillId};
+use crate::core::vector_index::VectorIndexService;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// Partial downloads touched more recently than this may belong to a download
+/// still running in another process, so they are left alone.
+pub const PARTIAL_DOWNLOAD_GRACE: Duration = Duration::from_secs(60 * 60);
+
+/// What a piece of garbage is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GarbageKind {
+    /// A skill directory (or editable link) in the skills dir
+    SkillDir,
+    /// A vector index row
+    IndexEntry,
+    /// A `.part` file or its metadata in the downloads dir
+    PartialDownload,
+    /// A build cache entry and its artifact
+    BuildCacheEntry,
+}
+
+impl GarbageKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SkillDir => "skill-dir",
+            Self::IndexEntry => "index-entry",
+            Self::PartialDownload => "partial-download",
+            Self::BuildCacheEntry => "build-cache-entry",
+        }
+    }
+}
+
+impl std::fmt::Display for GarbageKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One unreferenced item found by a scan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Garbage {
+    pub kind: GarbageKind,
+    /// Skill id, or the file name of a partial download
+    pub name: String,
+    /// File or directory that pruning deletes (none for index rows)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// Bytes on disk; index rows count as zero since SQLite keeps the pages
+    pub bytes: u64,
+}
+
+/// Finds and removes storage no referenced skill uses
+pub struct GarbageCollector {
+    skills_dir: PathBuf,
+    referenced: HashSet<String>,
+    index: Option<Arc<dyn VectorIndexService>>,
+    partial_dir: PathBuf,
+    build_cache: Option<PathBuf>,
+}
+
+impl GarbageCollector {
+    /// Collect in `skills_dir`, keeping the skills in `referenced`. Partial
+    /// downloads are looked for in [`DownloadManager::default_dir`].
+    pub fn new(skills_dir: PathBuf, referenced: HashSet<String>) -> Self {
+        Self {
+            partial_dir: DownloadManager::default_dir(&skills_dir),
+            skills_dir,
+            referenced,
+            index: None,
+            build_cache: None,
+        }
+    }
+
+    /// Also collect rows of this vector index
+    pub fn with_index(mut self, index: Arc<dyn VectorIndexService>) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// Look for partial downloads in `dir` instead of the default
+    pub fn with_partial_dir(mut self, dir: PathBuf) -> Self {
+        self.partial_dir = dir;
+        self
+    }
+
+    /// Also collect entries of the build cache at `path`
+    pub fn with_build_cache(mut self, path: PathBuf) -> Self {
+        self.build_cache = Some(path);
+        self
+    }
+
+    fn is_referenced(&self, id: &str) -> bool {
+        self.referenced.contains(id)
+    }
+
+    /// Everything unreferenced, skill directories first
+    pub async fn scan(&self) -> Result<Vec<Garbage>, ServiceError> {
+        let mut garbage = self.scan_skill_dirs()?;
+        garbage.extend(self.scan_index().await?);
+        garbage.extend(self.scan_partial_downloads()?);
+        garbage.extend(self.scan_build_cache()?);
+        Ok(garbage)
+    }
+
+    fn scan_skill_dirs(&self) -> Result<Vec<Garbage>, ServiceError> {
+        let mut garbage = Vec::new();
+        if !self.skills_dir.exists() {
+            return Ok(garbage);
+        }
+        self.scan_dir(&self.skills_dir, None, &mut garbage)?;
+        garbage.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(garbage)
+    }
+
+    /// Unreferenced skills in `dir`, which is the skills dir or (with `scope`
+    /// set) one of its scope directories
+    fn scan_dir(
+        &self,
+        dir: &Path,
+        scope: Option<&str>,
+        garbage: &mut Vec<Garbage>,
+    ) -> Result<(), ServiceError> {
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let name = match scope {
+                Some(scope) => format!("{}/{}", scope, file_name),
+                None => file_name.clone(),
+            };
+            if file_name.starts_with('.') || self.is_referenced(&name) {
+                continue;
+            }
+            let path = entry.path();
+            let file_type = entry.file_type()?;
+            if scope.is_none() && file_type.is_dir() && is_scope_dir(&path) {
+                self.scan_dir(&path, Some(&file_name), garbage)?;
+                continue;
+            }
+            // Only skills (and dangling links to them): other entries in the
+            // skills dir are not ours to delete
+            let is_skill = path.join("SKILL.md").is_file();
+            let bytes = if file_type.is_symlink() && (is_skill || !path.exists()) {
+                0
+            } else if file_type.is_dir() && is_skill {
+                dir_size(&path)
+            } else {
+                continue;
+            };
+            garbage.push(Garbage {
+                kind: GarbageKind::SkillDir,
+                name,
+                path: Some(path),
+                bytes,
+            });
+        }
+        Ok(())
+    }
+
+    async fn scan_index(&self) -> Result<Vec<Garbage>, ServiceError> {
+        let Some(index) = &self.index else {
+            return Ok(Vec::new());
+        };
+        let mut garbage: Vec<Garbage> = index
+            .get_all_skills()
+            .await?
+            .into_iter()
+            .filter(|skill| !self.is_referenced(&skill.id) || !skill.skill_path.exists())
+            .map(|skill| Garbage {
+                kind: GarbageKind::IndexEntry,
+                name: skill.id,
+                path: None,
+                bytes: 0,
+            })
+            .collect();
+        garbage.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(garbage)
+    }
+
+    fn scan_partial_downloads(&self) -> Result<Vec<Garbage>, ServiceError> {
+        let entries = match std::fs::read_dir(&self.partial_dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let now = SystemTime::now();
+        let mut garbage = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            let meta = entry.metadata()?;
+            if !meta.is_file() {
+                continue;
+            }
+            let recent = meta
+                .modified()
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .is_some_and(|age| age < PARTIAL_DOWNLOAD_GRACE);
+            if recent {
+                continue;
+            }
+            garbage.push(Garbage {
+                kind: GarbageKind::PartialDownload,
+                name: entry.file_name().to_string_lossy().to_string(),
+                path: Some(entry.path()),
+                bytes: meta.len(),
+            });
+        }
+        garbage.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(garbage)
+    }
+
+    fn scan_build_cache(&self) -> Result<Vec<Garbage>, ServiceError> {
+        let Some(cache_path) = &self.build_cache else {
+            return Ok(Vec::new());
+        };
+        let cache = BuildCache::load(cache_path)?;
+        let mut garbage: Vec<Garbage> = cache
+            .skills
+            .iter()
+            .filter(|(id, _)| !self.is_referenced(id))
+            .map(|(id, entry)| {
+                let artifact = entry
+                    .artifact_path
+                    .as_deref()
+                    .map(|artifact| resolve_artifact(cache_path, artifact))
+                    .filter(|artifact| artifact.is_file());
+                Garbage {
+                    kind: GarbageKind::BuildCacheEntry,
+                    name: id.clone(),
+                    bytes: artifact
+                        .as_ref()
+                        .and_then(|artifact| std::fs::metadata(artifact).ok())
+                        .map_or(0, |meta| meta.len()),
+                    path: artifact,
+                }
+            })
+            .collect();
+        garbage.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(garbage)
+    }
+
+    /// Delete `garbage` (as returned by [`scan`](Self::scan)). Returns the
+    /// bytes freed.
+    pub async fn prune(&self, garbage: &[Garbage]) -> Result<u64, ServiceError> {
+        let mut freed = 0;
+        let mut stale_cache_ids = Vec::new();
+        for item in garbage {
+            match item.kind {
+                GarbageKind::SkillDir => {
+                    if let Some(path) = &item.path {
+                        link::remove_path(path)?;
+                    }
+                    if let Ok(id) = SkillId::new(item.name.clone()) {
+                        remove_scope_dir_if_empty(&self.skills_dir, &id)?;
+                    }
+                }
+                GarbageKind::PartialDownload => {
+                    if let Some(path) = &item.path {
+                        link::remove_path(path)?;
+                    }
+                }
+                GarbageKind::IndexEntry => {
+                    if let Some(index) = &self.index {
+                        index.remove_skill(&item.name).await?;
+                    }
+                }
+                GarbageKind::BuildCacheEntry => {
+                    if let Some(path) = &item.path {
+                        link::remove_path(path)?;
+                    }
+                    stale_cache_ids.push(item.name.as_str());
+                }
+            }
+            freed += item.bytes;
+        }
+        if let Some(cache_path) = self
+            .build_cache
+            .as_ref()
+            .filter(|_| !stale_cache_ids.is_empty())
+        {
+            let mut cache = BuildCache::load(cache_path)?;
+            for id in stale_cache_ids {
+                cache.skills.remove(id);
+            }
+            cache.save(cache_path)?;
+        }
+        Ok(freed)
+    }
+}
+
+/// Artifact paths in the build cache are relative to the cache file's
+/// directory unless absolute.
+fn resolve_artifact(cache_path: &Path, artifact: &str) -> PathBuf {
+    let artifact = Path::new(artifact);
+    if artifact.is_absolute() {
+        return artifact.to_path_buf();
+    }
+    cache_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(artifact)
+}
+
+/// Total size of the files under `dir`, not following symlinks
+fn dir_size(dir: &Path) -> u64 {
+    walkdir::WalkDir::new(dir)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|meta| meta.len())
+        .sum()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_skill(skills_dir: &Path, id: &str) {
+        let dir = skills_dir.join(id);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("SKILL.md"), format!("---\nname: {id}\n---\n")).unwrap();
+    }
+
+    fn referenced(ids: &[&str]) -> HashSet<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_scan_and_prune_skill_dirs() {
+        let dir = TempDir::new().unwrap();
+        let skills_dir = dir.path().join("skills");
+        write_skill(&skills_dir, "kept");
+        write_skill(&skills_dir, "orphan");
+        std::fs::create_dir_all(skills_dir.join("notes")).unwrap();
+        std::fs::create_dir_all(skills_dir.join(".fastskill")).unwrap();
+
+        let gc = GarbageCollector::new(skills_dir.clone(), referenced(&["kept"]));
+        let garbage = gc.scan().await.unwrap();
+        assert_eq!(garbage.len(), 1);
+        assert_eq!(garbage[0].kind, GarbageKind::SkillDir);
+        assert_eq!(garbage[0].name, "orphan");
+        assert!(garbage[0].bytes > 0);
+
+        let freed = gc.prune(&garbage).await.unwrap();
+        assert_eq!(freed, garbage[0].bytes);
+        assert!(!skills_dir.join("orphan").exists());
+        assert!(skills_dir.join("kept").join("SKILL.md").is_file());
+        assert!(skills_dir.join("notes").is_dir());
+    }
+
+    #[tokio::test]
+    async fn test_scan_and_prune_scoped_skill_dirs() {
+        let dir = TempDir::new().unwrap();
+        let skills_dir = dir.path().join("skills");
+        for id in ["acme/kept", "acme/orphan", "other/orphan"] {
+            let id = SkillId::new(id.to_string()).unwrap();
+            crate::core::scope::ensure_scope_dir(&skills_dir, &id).unwrap();
+            write_skill(&skills_dir, id.as_str());
+        }
+
+        let gc = GarbageCollector::new(skills_dir.clone(), referenced(&["acme/kept"]));
+        let garbage = gc.scan().await.unwrap();
+        let names: Vec<&str> = garbage.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, ["acme/orphan", "other/orphan"]);
+
+        gc.prune(&garbage).await.unwrap();
+        assert!(skills_dir.join("acme/kept/SKILL.md").is_file());
+        assert!(!skills_dir.join("acme/orphan").exists());
+        assert!(!skills_dir.join("other").exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_prune_editable_link_keeps_source() {
+        let dir = TempDir::new().unwrap();
+        let skills_dir = dir.path().join("skills");
+        write_skill(dir.path(), "source");
+        std::fs::create_dir_all(&skills_dir).unwrap();
+        std::os::unix::fs::symlink(dir.path().join("source"), skills_dir.join("linked")).unwrap();
+
+        let gc = GarbageCollector::new(skills_dir.clone(), HashSet::new());
+        let garbage = gc.scan().await.unwrap();
+        assert_eq!(garbage.len(), 1);
+        gc.prune(&garbage).await.unwrap();
+        assert!(!skills_dir.join("linked").exists());
+        assert!(dir.path().join("source").join("SKILL.md").is_file());
+    }
+
+    #[tokio::test]
+    async fn test_partial_downloads_skip_recent_files() {
+        let dir = TempDir::new().unwrap();
+        let partial_dir = dir.path().join("downloads");
+        std::fs::create_dir_all(&partial_dir).unwrap();
+        std::fs::write(partial_dir.join("abc.part"), "partial").unwrap();
+
+        let gc = GarbageCollector::new(dir.path().join("skills"), HashSet::new())
+            .with_partial_dir(partial_dir.clone());
+        assert!(gc.scan().await.unwrap().is_empty());
+
+        let old = SystemTime::now() - PARTIAL_DOWNLOAD_GRACE * 2;
+        std::fs::File::options()
+            .write(true)
+            .open(partial_dir.join("abc.part"))
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+        let garbage = gc.scan().await.unwrap();
+        assert_eq!(garbage.len(), 1);
+        assert_eq!(garbage[0].kind, GarbageKind::PartialDownload);
+        assert_eq!(garbage[0].bytes, 7);
+    }
+
+    #[tokio::test]
+    async fn test_build_cache_entries() {
+        let dir = TempDir::new().unwrap();
+        let cache_path = dir.path().join("build-cache.json");
+        let mut cache = BuildCache::default();
+        std::fs::write(dir.path().join("old-1.0.0.zip"), "zip").unwrap();
+        cache.update_skill("old", "1.0.0", "h1", Path::new("old-1.0.0.zip"), None);
+        cache.update_skill("kept", "1.0.0", "h2", Path::new("kept-1.0.0.zip"), None);
+        cache.save(&cache_path).unwrap();
+
+        let gc = GarbageCollector::new(dir.path().join("skills"), referenced(&["kept"]))
+            .with_build_cache(cache_path.clone());
+        let garbage = gc.scan().await.unwrap();
+        assert_eq!(garbage.len(), 1);
+        assert_eq!(garbage[0].name, "old");
+        assert_eq!(garbage[0].bytes, 3);
+
+        gc.prune(&garbage).await.unwrap();
+        let cache = BuildCache::load(&cache_path).unwrap();
+        assert!(!cache.skills.contains_key("old"));
+        assert!(cache.skills.contains_key("kept"));
+        assert!(!dir.path().join("old-1.0.0.zip").exists());
+    }
+}
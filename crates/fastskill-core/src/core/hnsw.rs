@@ -0,0 +1,554 @@
+//! HNSW graph for approximate nearest neighbour search over skill embeddings
+//!
+//! A hierarchical navigable small world graph (Malkov & Yashunin) over
+//! normalized vectors, so distance is `1 - cosine similarity`. Search visits
+//! a few hundred nodes instead of every skill, which matters once a
+//! collection reaches tens of thousands of skills.
+//!
+//! Only the graph structure is persisted (see [`HnswGraph::save`]); vectors
+//! stay in the SQLite index and are re-attached by [`HnswGraph::sync`], which
+//! also inserts new or changed skills and tombstones removed ones, so a
+//! reindex that touched a handful of skills only re-links those. The graph is
+//! rebuilt from scratch when tombstones pile up or the embedding dimension
+//! changes.
+
+use crate::core::service::{AnnConfig, ServiceError};
+use crate::core::vector_index::IndexedSkill;
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::path::Path;
+
+/// Bumped whenever the on-disk layout changes; older files are rebuilt.
+const FORMAT_VERSION: u32 = 1;
+
+/// Rebuild once this fraction of nodes are tombstones.
+const MAX_DELETED_RATIO: f64 = 0.25;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Node {
+    id: String,
+    file_hash: String,
+    /// Neighbour lists for layers `0..=level`.
+    neighbors: Vec<Vec<u32>>,
+    deleted: bool,
+}
+
+/// Candidate ordered by distance (ties broken by node for determinism).
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    distance: f32,
+    node: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then_with(|| self.node.cmp(&other.node))
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The graph plus the vectors it was built from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HnswGraph {
+    format: u32,
+    m: usize,
+    ef_construction: usize,
+    dimensions: usize,
+    nodes: Vec<Node>,
+    entry_point: Option<usize>,
+    rng_state: u64,
+    /// Normalized vectors by node; empty for tombstones whose vector is gone.
+    #[serde(skip)]
+    vectors: Vec<Vec<f32>>,
+    /// Live node for each skill id.
+    #[serde(skip)]
+    by_id: HashMap<String, usize>,
+}
+
+fn normalize(v: &[f32]) -> Vec<f32> {
+    let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm == 0.0 || !norm.is_finite() {
+        return vec![0.0; v.len()];
+    }
+    v.iter().map(|x| x / norm).collect()
+}
+
+fn distance(a: &[f32], b: &[f32]) -> f32 {
+    1.0 - a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>()
+}
+
+impl HnswGraph {
+    /// Empty graph for vectors of `dimensions` components.
+    pub fn new(config: &AnnConfig, dimensions: usize) -> Self {
+        Self {
+            format: FORMAT_VERSION,
+            m: config.m.max(2),
+            ef_construction: config.ef_construction.max(1),
+            dimensions,
+            nodes: Vec::new(),
+            entry_point: None,
+            rng_state: 0x5EED,
+            vectors: Vec::new(),
+            by_id: HashMap::new(),
+        }
+    }
+
+    /// Build a graph over `skills`.
+    pub fn build(config: &AnnConfig, skills: &[IndexedSkill]) -> Self {
+        let dimensions = skills.first().map_or(0, |s| s.embedding.len());
+        let mut graph = Self::new(config, dimensions);
+        for skill in skills {
+            graph.insert(&skill.id, &skill.file_hash, &skill.embedding);
+        }
+        graph
+    }
+
+    /// Number of live (searchable) nodes.
+    pub fn len(&self) -> usize {
+        self.by_id.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_id.is_empty()
+    }
+
+    fn deleted_count(&self) -> usize {
+        self.nodes.iter().filter(|n| n.deleted).count()
+    }
+
+    /// Load a saved graph; `None` if missing, unreadable, from another format
+    /// version or built with different construction parameters. Call
+    /// [`sync`](Self::sync) before searching.
+    pub fn load(path: &Path, config: &AnnConfig) -> Option<Self> {
+        let content = std::fs::read(path).ok()?;
+        let graph: Self = serde_json::from_slice(&content).ok()?;
+        let compatible = graph.format == FORMAT_VERSION
+            && graph.m == config.m.max(2)
+            && graph.ef_construction == config.ef_construction.max(1)
+            && graph.entry_point.is_none_or(|e| e < graph.nodes.len())
+            && graph.nodes.iter().all(|n| {
+                !n.neighbors.is_empty()
+                    && n.neighbors
+                        .iter()
+                        .flatten()
+                        .all(|&x| (x as usize) < graph.nodes.len())
+            });
+        compatible.then_some(graph)
+    }
+
+    /// Write the graph atomically (temp file + rename).
+    pub fn save(&self, path: &Path) -> Result<(), ServiceError> {
+        let parent = path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        std::fs::create_dir_all(parent)?;
+        let content = serde_json::to_vec(self)
+            .map_err(|e| ServiceError::Custom(format!("Failed to serialize ANN index: {}", e)))?;
+        let mut file = tempfile::NamedTempFile::new_in(parent)?;
+        std::io::Write::write_all(&mut file, &content)?;
+        file.persist(path)
+            .map_err(|e| ServiceError::Custom(format!("Failed to save ANN index: {}", e)))?;
+        Ok(())
+    }
+
+    /// Bring the graph in line with the current index contents: attach
+    /// vectors, tombstone removed or changed skills and insert new ones.
+    /// Returns `true` if the graph changed (and should be saved).
+    pub fn sync(&mut self, config: &AnnConfig, skills: &[IndexedSkill]) -> bool {
+        let current: HashMap<&str, &IndexedSkill> =
+            skills.iter().map(|s| (s.id.as_str(), s)).collect();
+        let dimensions_changed = skills.iter().any(|s| s.embedding.len() != self.dimensions);
+        if dimensions_changed && !skills.is_empty() {
+            *self = Self::build(config, skills);
+            return true;
+        }
+
+        let mut changed = false;
+        self.vectors = vec![Vec::new(); self.nodes.len()];
+        self.by_id.clear();
+        for (i, node) in self.nodes.iter_mut().enumerate() {
+            match current.get(node.id.as_str()) {
+                Some(skill) if !node.deleted && skill.file_hash == node.file_hash => {
+                    self.vectors[i] = normalize(&skill.embedding);
+                    self.by_id.insert(node.id.clone(), i);
+                }
+                _ => {
+                    changed |= !node.deleted;
+                    node.deleted = true;
+                }
+            }
+        }
+
+        if self.by_id.is_empty()
+            || self.deleted_count() as f64 > MAX_DELETED_RATIO * self.nodes.len() as f64
+        {
+            changed |= !self.nodes.is_empty() || !skills.is_empty();
+            *self = Self::build(config, skills);
+            return changed;
+        }
+        // Tombstones have no vector, so enter through the highest live node.
+        if self.entry_point.is_none_or(|e| self.vectors[e].is_empty()) {
+            self.entry_point = self
+                .by_id
+                .values()
+                .copied()
+                .max_by_key(|&i| (self.nodes[i].neighbors.len(), Reverse(i)));
+            changed = true;
+        }
+
+        for skill in skills {
+            if !self.by_id.contains_key(&skill.id) {
+                self.insert(&skill.id, &skill.file_hash, &skill.embedding);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    fn next_random(&mut self) -> u64 {
+        // SplitMix64: deterministic levels make builds reproducible.
+        self.rng_state = self.rng_state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.rng_state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn random_level(&mut self) -> usize {
+        let uniform = ((self.next_random() >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+        let ml = 1.0 / (self.m as f64).ln();
+        ((-uniform.ln() * ml).floor() as usize).min(16)
+    }
+
+    fn max_neighbors(&self, layer: usize) -> usize {
+        if layer == 0 {
+            self.m * 2
+        } else {
+            self.m
+        }
+    }
+
+    fn top_layer(&self) -> usize {
+        self.entry_point
+            .map_or(0, |e| self.nodes[e].neighbors.len() - 1)
+    }
+
+    fn node_distance(&self, query: &[f32], node: usize) -> Option<f32> {
+        let v = &self.vectors[node];
+        (!v.is_empty()).then(|| distance(query, v))
+    }
+
+    /// Greedy walk on one layer towards `query`.
+    fn greedy_closest(&self, query: &[f32], mut current: Candidate, layer: usize) -> Candidate {
+        loop {
+            let mut improved = false;
+            if let Some(links) = self.nodes[current.node].neighbors.get(layer) {
+                for &n in links {
+                    let n = n as usize;
+                    if let Some(d) = self.node_distance(query, n) {
+                        let candidate = Candidate {
+                            distance: d,
+                            node: n,
+                        };
+                        if candidate < current {
+                            current = candidate;
+                            improved = true;
+                        }
+                    }
+                }
+            }
+            if !improved {
+                return current;
+            }
+        }
+    }
+
+    /// Best-first search on one layer keeping `ef` results, nearest first.
+    /// Tombstones are traversed but still returned; callers filter them.
+    fn search_layer(
+        &self,
+        query: &[f32],
+        entry: Candidate,
+        ef: usize,
+        layer: usize,
+    ) -> Vec<Candidate> {
+        let mut visited = HashSet::from([entry.node]);
+        let mut frontier = BinaryHeap::from([Reverse(entry)]);
+        let mut results = BinaryHeap::from([entry]);
+        while let Some(Reverse(closest)) = frontier.pop() {
+            let worst = results.peek().map_or(f32::INFINITY, |c| c.distance);
+            if closest.distance > worst && results.len() >= ef {
+                break;
+            }
+            let Some(links) = self.nodes[closest.node].neighbors.get(layer) else {
+                continue;
+            };
+            for &n in links {
+                let n = n as usize;
+                if !visited.insert(n) {
+                    continue;
+                }
+                let Some(d) = self.node_distance(query, n) else {
+                    continue;
+                };
+                let candidate = Candidate {
+                    distance: d,
+                    node: n,
+                };
+                let worst = results.peek().map_or(f32::INFINITY, |c| c.distance);
+                if results.len() < ef || d < worst {
+                    frontier.push(Reverse(candidate));
+                    results.push(candidate);
+                    if results.len() > ef {
+                        results.pop();
+                    }
+                }
+            }
+        }
+        results.into_sorted_vec()
+    }
+
+    /// Keep the `limit` candidates nearest to `base` among `candidates`.
+    fn closest(&self, base: usize, candidates: &[usize], limit: usize) -> Vec<u32> {
+        let mut scored: Vec<Candidate> = candidates
+            .iter()
+            .filter_map(|&n| {
+                let d = self.node_distance(&self.vectors[base], n)?;
+                Some(Candidate {
+                    distance: d,
+                    node: n,
+                })
+            })
+            .collect();
+        scored.sort();
+        scored
+            .into_iter()
+            .take(limit)
+            .map(|c| c.node as u32)
+            .collect()
+    }
+
+    fn insert(&mut self, id: &str, file_hash: &str, embedding: &[f32]) {
+        if embedding.len() != self.dimensions {
+            return;
+        }
+        let vector = normalize(embedding);
+        let level = self.random_level();
+        let node = self.nodes.len();
+        self.nodes.push(Node {
+            id: id.to_string(),
+            file_hash: file_hash.to_string(),
+            neighbors: vec![Vec::new(); level + 1],
+            deleted: false,
+        });
+        self.vectors.push(vector);
+        self.by_id.insert(id.to_string(), node);
+
+        let Some(entry) = self.entry_point.filter(|&e| !self.vectors[e].is_empty()) else {
+            self.entry_point = Some(node);
+            return;
+        };
+        let top = self.top_layer();
+        let query = self.vectors[node].clone();
+        let mut current = Candidate {
+            distance: distance(&query, &self.vectors[entry]),
+            node: entry,
+        };
+        for layer in (level + 1..=top).rev() {
+            current = self.greedy_closest(&query, current, layer);
+        }
+        for layer in (0..=level.min(top)).rev() {
+            let found = self.search_layer(&query, current, self.ef_construction, layer);
+            let candidates: Vec<usize> = found.iter().map(|c| c.node).collect();
+            let links = self.closest(node, &candidates, self.m);
+            self.nodes[node].neighbors[layer] = links.clone();
+            let max = self.max_neighbors(layer);
+            for &n in &links {
+                let n = n as usize;
+                self.nodes[n].neighbors[layer].push(node as u32);
+                if self.nodes[n].neighbors[layer].len() > max {
+                    let existing: Vec<usize> = self.nodes[n].neighbors[layer]
+                        .iter()
+                        .map(|&x| x as usize)
+                        .collect();
+                    self.nodes[n].neighbors[layer] = self.closest(n, &existing, max);
+                }
+            }
+            if let Some(best) = found.first() {
+                current = *best;
+            }
+        }
+        if level > top {
+            self.entry_point = Some(node);
+        }
+    }
+
+    /// Up to `k` live skills nearest to `query`, as `(skill id, similarity)`,
+    /// most similar first. `ef` (at least `k`) trades speed for recall.
+    pub fn search(&self, query: &[f32], k: usize, ef: usize) -> Vec<(&str, f32)> {
+        if query.len() != self.dimensions || k == 0 {
+            return Vec::new();
+        }
+        let Some(entry) = self.entry_point.filter(|&e| !self.vectors[e].is_empty()) else {
+            return Vec::new();
+        };
+        let query = normalize(query);
+        let mut current = Candidate {
+            distance: distance(&query, &self.vectors[entry]),
+            node: entry,
+        };
+        for layer in (1..=self.top_layer()).rev() {
+            current = self.greedy_closest(&query, current, layer);
+        }
+        self.search_layer(&query, current, ef.max(k), 0)
+            .into_iter()
+            .filter(|c| !self.nodes[c.node].deleted)
+            .take(k)
+            .map(|c| (self.nodes[c.node].id.as_str(), 1.0 - c.distance))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn config() -> AnnConfig {
+        AnnConfig {
+            min_skills: 0,
+            ..Default::default()
+        }
+    }
+
+    fn skills(count: usize, dimensions: usize, seed: u64) -> Vec<IndexedSkill> {
+        let mut state = seed;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((state >> 33) as f32 / (1u64 << 31) as f32) - 0.5
+        };
+        (0..count)
+            .map(|i| IndexedSkill {
+                id: format!("skill-{}", i),
+                skill_path: format!("/skills/skill-{}", i).into(),
+                frontmatter_json: serde_json::json!({}),
+                embedding: (0..dimensions).map(|_| next()).collect(),
+                chunk_embeddings: Vec::new(),
+                file_hash: format!("hash-{}", i),
+                updated_at: chrono::Utc::now(),
+            })
+            .collect()
+    }
+
+    fn exact_top(skills: &[IndexedSkill], query: &[f32], k: usize) -> Vec<String> {
+        let q = normalize(query);
+        let mut scored: Vec<(f32, &str)> = skills
+            .iter()
+            .map(|s| (1.0 - distance(&q, &normalize(&s.embedding)), s.id.as_str()))
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        scored
+            .into_iter()
+            .take(k)
+            .map(|(_, id)| id.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_recall_against_exact_search() {
+        let data = skills(2000, 32, 1);
+        let graph = HnswGraph::build(&config(), &data);
+        assert_eq!(graph.len(), 2000);
+
+        let queries = skills(50, 32, 99);
+        let mut hits = 0;
+        for q in &queries {
+            let expected = exact_top(&data, &q.embedding, 10);
+            let found: Vec<&str> = graph
+                .search(&q.embedding, 10, 64)
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect();
+            hits += expected
+                .iter()
+                .filter(|id| found.contains(&id.as_str()))
+                .count();
+        }
+        let recall = hits as f64 / (queries.len() * 10) as f64;
+        assert!(recall >= 0.9, "recall {} below 0.9", recall);
+    }
+
+    #[test]
+    fn test_save_load_and_incremental_sync() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("index.hnsw");
+        let mut data = skills(300, 8, 2);
+        let graph = HnswGraph::build(&config(), &data);
+        graph.save(&path).unwrap();
+
+        // One skill changed, one removed, one added.
+        data[0].file_hash = "hash-0-edited".to_string();
+        data.remove(1);
+        data.extend(skills(301, 8, 3).into_iter().skip(300));
+
+        let mut loaded = HnswGraph::load(&path, &config()).unwrap();
+        assert!(loaded.sync(&config(), &data));
+        assert_eq!(loaded.len(), data.len());
+        // Existing nodes are kept; only the edited and new skills are inserted.
+        assert_eq!(loaded.nodes.len(), 302);
+        assert_eq!(loaded.deleted_count(), 2);
+        let found = loaded.search(&data[0].embedding, 1, 32);
+        assert_eq!(found[0].0, "skill-0");
+        assert!(!loaded
+            .search(&data[5].embedding, 50, 64)
+            .iter()
+            .any(|(id, _)| *id == "skill-1"));
+
+        // Unchanged contents mean nothing to save.
+        assert!(!loaded.sync(&config(), &data));
+    }
+
+    #[test]
+    fn test_load_rejects_other_parameters_and_garbage() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("index.hnsw");
+        HnswGraph::build(&config(), &skills(10, 4, 4))
+            .save(&path)
+            .unwrap();
+        let other = AnnConfig { m: 32, ..config() };
+        assert!(HnswGraph::load(&path, &other).is_none());
+
+        std::fs::write(&path, b"{not json").unwrap();
+        assert!(HnswGraph::load(&path, &config()).is_none());
+    }
+
+    #[test]
+    fn test_dimension_change_rebuilds() {
+        let mut graph = HnswGraph::build(&config(), &skills(20, 4, 5));
+        let wider = skills(20, 6, 6);
+        assert!(graph.sync(&config(), &wider));
+        assert_eq!(graph.len(), 20);
+        assert_eq!(graph.deleted_count(), 0);
+        assert_eq!(graph.search(&wider[3].embedding, 1, 16)[0].0, "skill-3");
+    }
+}
@@ -6,6 +6,10 @@
 //! skills dir → upsert Manifest → write Lock → reindex-if-provider). `mode` only
 //! governs the id-conflict policy. `add`/`update` are one operation.
 
+use crate::core::access::SkillAccess;
+use crate::core::cancel::{self, cancellable};
+use crate::core::delta::{self, DeltaStats};
+use crate::core::link::{self, LinkMode};
 use crate::core::lock::{project_lock_path, ProjectSkillsLock};
 use crate::core::manifest::{
     DependenciesSection, DependencySpec, ProjectContext, SkillProjectToml,
@@ -13,10 +17,16 @@ use crate::core::manifest::{
 use crate::core::metadata::{parse_yaml_frontmatter, SkillFrontmatter};
 use crate::core::origin::{GitRef, Origin, Resolved};
 use crate::core::project::{detect_context_from_content, resolve_project_file};
+use crate::core::project_transaction::{ProjectTransaction, TransactionError};
 use crate::core::repository::RepositoryManager;
+use crate::core::requirements;
 use crate::core::service::{FastSkillService, ServiceError, SkillId};
+use crate::core::skill_env;
+use crate::core::skill_injection::InjectionRule;
 use crate::core::skill_manager::SkillDefinition;
+use crate::core::skill_naming::SkillNaming;
 use crate::core::version::{is_newer, newest_version, VersionConstraint};
+use crate::execution::runtime::declared_environment;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use tempfile::TempDir;
@@ -39,6 +49,19 @@ pub struct AddOutcome {
     pub resolved: Resolved,
     /// Whether the auto-reindex ran (false = skipped, e.g. no embedding provider).
     pub reindexed: bool,
+    /// The id the naming strategy produced, when it was taken and `id` is a
+    /// suffixed variant of it
+    /// ([`IdCollision::Suffix`](crate::core::skill_naming::IdCollision::Suffix)).
+    pub renamed_from: Option<String>,
+    /// How an editable skill was linked into the skills dir (see
+    /// [`LinkMode`]); `None` for a copied install
+    pub linked: Option<LinkMode>,
+    /// What an update fetched when only changed files were downloaded (see
+    /// [`delta`]); `None` for a full download
+    pub delta: Option<DeltaStats>,
+    /// Required variables the skill declares under `env` that are not set in
+    /// this environment; its scripts fail until they are
+    pub missing_env: Vec<String>,
 }
 
 /// The outcome of the update preflight (ADR-0005 §Q6). Only `Updatable` proceeds
@@ -56,6 +79,8 @@ pub struct Fetched {
     pub temp_dir: TempDir,
     pub skill_path: PathBuf,
     pub resolved: Resolved,
+    /// Set when only changed files were fetched
+    pub delta: Option<DeltaStats>,
 }
 
 impl FastSkillService {
@@ -70,20 +95,47 @@ impl FastSkillService {
         mode: AddMode,
         groups: Vec<String>,
     ) -> Result<AddOutcome, ServiceError> {
-        let fetched = self.fetch(&origin).await?;
-        self.commit(fetched, origin, mode, groups).await
+        let naming = self.config().naming;
+        let link_mode = self.config().link_mode;
+        self.add_from_origin_with_naming(origin, mode, groups, naming, link_mode)
+            .await
+    }
+
+    /// [`add_from_origin`](Self::add_from_origin) with explicit [`SkillNaming`]
+    /// and [`LinkMode`] instead of the configured ones. On `Update` the id
+    /// already recorded in `skills.lock` for the origin's source wins over the
+    /// strategy, so a skill added under a suffixed id keeps it. `link_mode`
+    /// only applies to editable local installs.
+    pub async fn add_from_origin_with_naming(
+        &self,
+        origin: Origin,
+        mode: AddMode,
+        groups: Vec<String>,
+        naming: SkillNaming,
+        link_mode: LinkMode,
+    ) -> Result<AddOutcome, ServiceError> {
+        // A cancelled fetch drops its temp dir; once fetched, cancellation is
+        // only honoured before commit so manifest, lock and disk stay in step.
+        let fetched = cancellable(self.cancellation_token(), self.fetch(&origin, mode)).await??;
+        cancel::check(self.cancellation_token())?;
+        self.commit(fetched, origin, mode, groups, naming, link_mode)
+            .await
     }
 
     /// Fetch a skill described by `origin` into a temp dir, capturing the resolved
     /// facts. The only per-variant step (git clone / local copy-or-unzip / remote
-    /// zip download / registry download).
-    async fn fetch(&self, origin: &Origin) -> Result<Fetched, ServiceError> {
+    /// zip download / registry download). An update of a git or zip-url skill
+    /// fetches only the files that changed when the source allows it.
+    async fn fetch(&self, origin: &Origin, mode: AddMode) -> Result<Fetched, ServiceError> {
+        let installed = self.delta_base(origin, mode);
         match origin {
             Origin::Git { url, r#ref, subdir } => {
-                self.fetch_git(url, r#ref, subdir.as_deref()).await
+                self.fetch_git(url, r#ref, subdir.as_deref(), installed.as_deref())
+                    .await
             }
             Origin::Local { path, editable } => self.fetch_local(path, *editable).await,
-            Origin::ZipUrl { url } => self.fetch_zip_url(url).await,
+            Origin::ZipUrl { url } => self.fetch_zip_url(url, installed.as_deref()).await,
+            Origin::Pack { path, skill } => self.fetch_pack(path, skill).await,
             Origin::Repository {
                 repo,
                 skill,
@@ -92,11 +144,24 @@ impl FastSkillService {
         }
     }
 
+    /// The installed copy an update of `origin` can reuse unchanged files
+    /// from: the locked skill's directory, unless it is a link
+    fn delta_base(&self, origin: &Origin, mode: AddMode) -> Option<PathBuf> {
+        if mode != AddMode::Update || !matches!(origin, Origin::Git { .. } | Origin::ZipUrl { .. })
+        {
+            return None;
+        }
+        let id = SkillId::new(self.locked_id_for_source(origin)?).ok()?;
+        let dir = self.config().skill_storage_path.join(id.as_str());
+        (dir.is_dir() && !dir.is_symlink()).then_some(dir)
+    }
+
     async fn fetch_git(
         &self,
         url: &str,
         git_ref: &GitRef,
         subdir: Option<&Path>,
+        installed: Option<&Path>,
     ) -> Result<Fetched, ServiceError> {
         let (branch, tag) = match git_ref {
             GitRef::Default => (None, None),
@@ -113,7 +178,20 @@ impl FastSkillService {
             }
         };
 
-        let temp_dir = crate::storage::git::clone_repository(url, branch, tag, None).await?;
+        let (temp_dir, delta) = match installed {
+            Some(installed) => delta::fetch_git_delta(url, branch, tag, subdir, installed).await?,
+            // A skill in a subdirectory only needs that part of the repository
+            None => match subdir {
+                Some(subdir) => (
+                    crate::storage::git::clone_repository_subdir(url, branch, tag, subdir).await?,
+                    None,
+                ),
+                None => (
+                    crate::storage::git::clone_repository(url, branch, tag, None).await?,
+                    None,
+                ),
+            },
+        };
 
         let skill_base = if let Some(subdir) = subdir {
             let joined = safe_subdir_join(temp_dir.path(), subdir)?;
@@ -141,6 +219,7 @@ impl FastSkillService {
                 commit_hash: Some(commit_hash),
                 checksum: None,
             },
+            delta,
         })
     }
 
@@ -193,28 +272,91 @@ impl FastSkillService {
                 commit_hash: None,
                 checksum: None,
             },
+            delta: None,
         })
     }
 
-    async fn fetch_zip_url(&self, url: &str) -> Result<Fetched, ServiceError> {
-        let response = reqwest::get(url)
-            .await
-            .map_err(|e| {
-                ServiceError::InvalidOperation(format!("Failed to download '{url}': {e}"))
-            })?
-            .error_for_status()
-            .map_err(|e| {
-                ServiceError::InvalidOperation(format!("Failed to download '{url}': {e}"))
-            })?;
-        let bytes = response
-            .bytes()
-            .await
-            .map_err(|e| ServiceError::InvalidOperation(format!("Failed to read '{url}': {e}")))?;
+    async fn fetch_pack(&self, path: &Path, skill: &str) -> Result<Fetched, ServiceError> {
+        let pack_path = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            std::env::current_dir()?.join(path)
+        };
+        if !pack_path.is_file() {
+            return Err(ServiceError::InvalidOperation(format!(
+                "Skill pack does not exist: {}",
+                pack_path.display()
+            )));
+        }
+
+        let extracted = crate::core::pack::extract_pack(&pack_path)?;
+        let skill_path = extracted.skill_dir(skill)?;
+        let frontmatter = read_skill_frontmatter(&skill_path).await?;
+        let (id, version) = derive_skill_id_and_version(&skill_path, &frontmatter)?;
+        if id.as_str() != skill {
+            return Err(ServiceError::Validation(format!(
+                "Skill pack lists '{}' but its skill declares id '{}'",
+                skill, id
+            )));
+        }
+
+        Ok(Fetched {
+            temp_dir: extracted.temp_dir,
+            skill_path,
+            resolved: Resolved {
+                version,
+                commit_hash: None,
+                checksum: None,
+            },
+            delta: None,
+        })
+    }
 
+    async fn fetch_zip_url(
+        &self,
+        url: &str,
+        installed: Option<&Path>,
+    ) -> Result<Fetched, ServiceError> {
+        // A `#sha256=<hex>` fragment pins the archive's checksum
+        let (download_url, expected) = crate::core::download::split_checksum(url);
+        // A pinned checksum covers the whole archive, which a delta never downloads
+        if let (Some(installed), None) = (installed, &expected) {
+            match delta::fetch_zip_delta(self.downloads(), download_url, installed).await {
+                Ok(Some(zip)) => {
+                    let frontmatter = read_skill_frontmatter(&zip.skill_path).await?;
+                    let (_, version) = derive_skill_id_and_version(&zip.skill_path, &frontmatter)?;
+                    return Ok(Fetched {
+                        temp_dir: zip.temp_dir,
+                        skill_path: zip.skill_path,
+                        resolved: Resolved {
+                            version,
+                            commit_hash: None,
+                            checksum: zip.checksum,
+                        },
+                        delta: Some(zip.stats),
+                    });
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!(
+                    "Delta update from {} failed, downloading the archive: {}",
+                    download_url,
+                    e
+                ),
+            }
+        }
         let temp_dir = TempDir::new()?;
         let zip_path = temp_dir.path().join("package.zip");
         let extract_path = temp_dir.path().join("extracted");
-        tokio::fs::write(&zip_path, &bytes).await?;
+        let downloaded = self
+            .downloads()
+            .download(download_url, &zip_path, expected.as_deref())
+            .await?;
+        #[cfg(feature = "chaos")]
+        {
+            let mut bytes = tokio::fs::read(&zip_path).await?;
+            crate::core::chaos::maybe_corrupt_zip(&mut bytes);
+            tokio::fs::write(&zip_path, &bytes).await?;
+        }
         tokio::fs::create_dir_all(&extract_path).await?;
 
         let zip_handler = crate::storage::zip::ZipHandler::new()?;
@@ -230,8 +372,9 @@ impl FastSkillService {
             resolved: Resolved {
                 version,
                 commit_hash: None,
-                checksum: None,
+                checksum: Some(downloaded.checksum),
             },
+            delta: None,
         })
     }
 
@@ -300,6 +443,7 @@ impl FastSkillService {
                 commit_hash: None,
                 checksum: None,
             },
+            delta: None,
         })
     }
 
@@ -314,33 +458,62 @@ impl FastSkillService {
         origin: Origin,
         mode: AddMode,
         groups: Vec<String>,
+        naming: SkillNaming,
+        link_mode: LinkMode,
     ) -> Result<AddOutcome, ServiceError> {
         let Fetched {
             temp_dir,
             skill_path,
             resolved,
+            delta,
         } = fetched;
 
         let frontmatter = read_skill_frontmatter(&skill_path).await?;
-        let (id, _version) = derive_skill_id_and_version(&skill_path, &frontmatter)?;
-
-        let existing = self.skill_manager().get_skill(&id).await?;
-        if mode == AddMode::Fresh && existing.is_some() {
-            return Err(ServiceError::AlreadyIndexed(id.into_string()));
-        }
+        // Refuse before anything is written, whatever the origin
+        let requires = requirements::declared_requirements(&skill_path, &frontmatter)?;
+        requirements::ensure_satisfied(&frontmatter.name, &requires).await?;
+        let env = skill_env::declared_env(&skill_path, &frontmatter)?;
+
+        let (id, renamed_from) = match mode {
+            AddMode::Fresh => {
+                let base = naming
+                    .strategy
+                    .skill_id(&skill_path, &frontmatter, &origin)?;
+                let id = self
+                    .claim_skill_id(&base, &origin, naming.on_collision)
+                    .await?;
+                let renamed_from = (id != base).then(|| base.into_string());
+                (id, renamed_from)
+            }
+            AddMode::Update => match self.locked_id_for_source(&origin) {
+                Some(locked) => (SkillId::new(locked)?, None),
+                None => (
+                    naming
+                        .strategy
+                        .skill_id(&skill_path, &frontmatter, &origin)?,
+                    None,
+                ),
+            },
+        };
 
+        crate::core::scope::ensure_scope_dir(&self.config().skill_storage_path, &id)?;
         let storage_dir = self.config().skill_storage_path.join(id.as_str());
         let editable = matches!(&origin, Origin::Local { editable: true, .. });
-        if editable {
-            symlink_into_storage(&skill_path, &storage_dir).await?;
+        let linked = if editable {
+            Some(link::link_dir(&skill_path, &storage_dir, link_mode).await?)
         } else {
             move_or_copy_into_storage(&skill_path, &storage_dir).await?;
-        }
+            None
+        };
         // The fetched contents now live at `storage_dir` (moved, copied, or
-        // symlinked-to); the temp dir (if anything of it remains) can go.
+        // linked-to); the temp dir (if anything of it remains) can go.
         drop(temp_dir);
+        self.storage().skill_stored(id.as_str()).await?;
 
         let fetched_at = chrono::Utc::now();
+        let access = SkillAccess::from_frontmatter(&frontmatter);
+        let injection = InjectionRule::from_frontmatter(&frontmatter);
+        let execution_environment = declared_environment(&frontmatter);
         let mut skill_def = SkillDefinition::new(
             id.clone(),
             frontmatter.name,
@@ -350,6 +523,10 @@ impl FastSkillService {
         );
         skill_def.skill_file = storage_dir.join("SKILL.md");
         skill_def.author = frontmatter.author;
+        skill_def.access = access;
+        skill_def.injection = injection;
+        skill_def.env = env;
+        skill_def.execution_environment = execution_environment;
         skill_def.commit_hash = resolved.commit_hash.clone();
         skill_def.fetched_at = Some(fetched_at);
 
@@ -357,7 +534,9 @@ impl FastSkillService {
             .force_register_skill(skill_def.clone())
             .await?;
 
-        self.upsert_manifest_and_lock(&skill_def, &groups)?;
+        let missing_env = skill_def.env.missing(skill_env::process_env);
+        let lock_path = self.upsert_manifest_and_lock(&skill_def, &groups)?;
+        self.storage().lock_written(&lock_path).await?;
 
         let reindexed = match self.reindex(None, None).await {
             Ok(outcome) => outcome.reindexed,
@@ -375,18 +554,22 @@ impl FastSkillService {
             origin,
             resolved,
             reindexed,
+            renamed_from,
+            linked,
+            delta,
+            missing_env,
         })
     }
 
     /// Upsert the skill-project.toml `[dependencies]` entry and the project
-    /// `skills.lock` entry for a just-installed skill. Resolves the project file
-    /// from the current working directory (mirrors the CLI's
-    /// `manifest_utils::add_skill_to_project_toml` / `update_lock_file`).
+    /// `skills.lock` entry for a just-installed skill in one transaction.
+    /// Resolves the project file from the current working directory (mirrors
+    /// the CLI's `manifest_utils::add_skill_to_project`). Returns the lock path.
     fn upsert_manifest_and_lock(
         &self,
         skill_def: &SkillDefinition,
         groups: &[String],
-    ) -> Result<(), ServiceError> {
+    ) -> Result<PathBuf, ServiceError> {
         // Resolve the project from the injected root (the served project, for the
         // `serve` path) if present; otherwise walk up from the process cwd, which
         // is correct for a CLI invocation. Never resolve solely from cwd on the
@@ -428,20 +611,25 @@ impl FastSkillService {
                 dependencies: HashMap::new(),
             });
         }
+        let existing = project
+            .dependencies
+            .as_ref()
+            .and_then(|d| d.dependencies.get(&skill_def.id.to_string()));
         // Effective groups: explicit `groups` win; an empty list preserves whatever
         // the skill already had (so `update` never silently drops group membership).
         let effective_groups: Option<Vec<String>> = if !groups.is_empty() {
             Some(groups.to_vec())
         } else {
-            project
-                .dependencies
-                .as_ref()
-                .and_then(|d| d.dependencies.get(&skill_def.id.to_string()))
-                .and_then(|spec| match spec {
-                    DependencySpec::Inline { groups, .. } => groups.clone(),
-                    DependencySpec::Version(_) => None,
-                })
+            existing.and_then(|spec| match spec {
+                DependencySpec::Inline { groups, .. } => groups.clone(),
+                DependencySpec::Version(_) => None,
+            })
         };
+        // Selected extras are likewise carried over from the existing entry.
+        let extras: Option<Vec<String>> = existing.and_then(|spec| match spec {
+            DependencySpec::Inline { extras, .. } => extras.clone(),
+            DependencySpec::Version(_) => None,
+        });
 
         if let Some(deps) = project.dependencies.as_mut() {
             // Safety net: re-canonicalize a local path to an absolute path before
@@ -461,14 +649,11 @@ impl FastSkillService {
                 DependencySpec::Inline {
                     origin: origin_for_manifest,
                     groups: effective_groups.clone(),
+                    extras: extras.clone(),
                 },
             );
         }
 
-        project
-            .save_to_file(&project_file_path)
-            .map_err(|e| ServiceError::Config(format!("Failed to save skill-project.toml: {e}")))?;
-
         let lock_path = project_lock_path(&project_file_path);
         let mut lock = if lock_path.exists() {
             ProjectSkillsLock::load_from_file(&lock_path)
@@ -477,19 +662,30 @@ impl FastSkillService {
             ProjectSkillsLock::new_empty()
         };
         lock.update_skill(skill_def);
-        // Mirror the manifest's groups onto the lock entry (update_skill does not
-        // carry them from the manifest).
+        // Mirror the manifest's groups and extras onto the lock entry (update_skill
+        // does not carry them from the manifest).
         if let Some(entry) = lock
             .skills
             .iter_mut()
             .find(|s| s.id == skill_def.id.as_str())
         {
             entry.groups = effective_groups.clone().unwrap_or_default();
+            entry.extras = extras.unwrap_or_default();
         }
-        lock.save_to_file(&lock_path)
-            .map_err(|e| ServiceError::Config(format!("Failed to save skills.lock: {e}")))?;
 
-        Ok(())
+        // Both files or neither: a failure between the two saves would leave
+        // the manifest declaring a skill the lock does not pin.
+        let save_error = |e: TransactionError| {
+            ServiceError::Config(format!(
+                "Failed to save skill-project.toml and skills.lock: {e}"
+            ))
+        };
+        let mut txn = ProjectTransaction::new(&project_file_path, &lock_path);
+        txn.stage_manifest(&project).map_err(save_error)?;
+        txn.stage_lock(&lock).map_err(save_error)?;
+        txn.commit().map_err(save_error)?;
+
+        Ok(lock_path)
     }
 
     /// Update preflight (ADR-0005 §Q6): decide whether the recorded origin has
@@ -519,48 +715,31 @@ impl FastSkillService {
                     Ok(UpdatePreflight::Updatable)
                 }
             }
-            Origin::ZipUrl { .. } => Ok(UpdatePreflight::Updatable),
-            Origin::Repository {
-                repo,
-                skill,
-                version,
-            } => {
-                let repo_manager = self.repository_manager().ok_or_else(|| {
-                    ServiceError::Config(
-                        "No repositories configured; cannot preflight an Origin::Repository \
-                         update"
-                            .to_string(),
-                    )
-                })?;
-                let repo_name = resolve_repo_name(repo_manager, repo)?;
-                let client = repo_manager.get_client(&repo_name).await?;
-                let available = client
-                    .get_versions(skill)
-                    .await
-                    .map_err(|e| ServiceError::Config(format!("Failed to get versions: {e}")))?;
-                let candidates: Vec<String> = match version {
-                    Some(constraint) => available
-                        .into_iter()
-                        .filter(|v| constraint.satisfies(v).unwrap_or(false))
-                        .collect(),
-                    None => available,
-                };
-                let Some(target_version) = newest_version(&candidates) else {
+            Origin::ZipUrl { .. } | Origin::Pack { .. } => Ok(UpdatePreflight::Updatable),
+            Origin::Repository { skill, .. } => {
+                let Some(target_version) = self.target_version(origin).await? else {
                     // Nothing satisfies the constraint: no update to offer.
                     return Ok(UpdatePreflight::UpToDate);
                 };
 
                 // Best-effort: the currently-installed `SkillId` is normally the
-                // last path segment of the registry reference (`scope/id`).
-                let local_id = skill.rsplit('/').next().unwrap_or(skill.as_str());
-                let installed_version = match SkillId::new(local_id.to_string()) {
-                    Ok(id) => self
+                // registry reference itself (`scope/id`), or its last path
+                // segment for installs made before ids were scoped.
+                let mut installed_version = None;
+                let last_segment = skill.rsplit('/').next().unwrap_or(skill.as_str());
+                for local_id in [skill.as_str(), last_segment] {
+                    let Ok(id) = SkillId::new(local_id.to_string()) else {
+                        continue;
+                    };
+                    installed_version = self
                         .skill_manager()
                         .get_skill(&id)
                         .await?
-                        .map(|s| s.version),
-                    Err(_) => None,
-                };
+                        .map(|s| s.version);
+                    if installed_version.is_some() {
+                        break;
+                    }
+                }
 
                 match installed_version {
                     Some(current) if !is_newer(&target_version, &current).unwrap_or(true) => {
@@ -571,6 +750,79 @@ impl FastSkillService {
             }
         }
     }
+
+    /// Version an update of a repository origin would install: the newest
+    /// version allowed by its constraint. `None` for other origins, which are
+    /// not version-resolved, and when nothing satisfies the constraint.
+    pub async fn target_version(&self, origin: &Origin) -> Result<Option<String>, ServiceError> {
+        let Origin::Repository {
+            repo,
+            skill,
+            version,
+        } = origin
+        else {
+            return Ok(None);
+        };
+        let repo_manager = self.repository_manager().ok_or_else(|| {
+            ServiceError::Config(
+                "No repositories configured; cannot resolve an Origin::Repository version"
+                    .to_string(),
+            )
+        })?;
+        let repo_name = resolve_repo_name(repo_manager, repo)?;
+        let client = repo_manager.get_client(&repo_name).await?;
+        let available = client
+            .get_versions(skill)
+            .await
+            .map_err(|e| ServiceError::Config(format!("Failed to get versions: {e}")))?;
+        let candidates: Vec<String> = match version {
+            Some(constraint) => available
+                .into_iter()
+                .filter(|v| constraint.satisfies(v).unwrap_or(false))
+                .collect(),
+            None => available,
+        };
+        Ok(newest_version(&candidates))
+    }
+
+    /// Bytes a fetch of `origin` at `version` would download, when that is
+    /// known up front: the package size a repository reports, or the size of
+    /// a local directory copy. `None` for git, zip-url and pack origins,
+    /// editable installs, and repositories that do not report sizes.
+    pub async fn download_size(
+        &self,
+        origin: &Origin,
+        version: Option<&str>,
+    ) -> Result<Option<u64>, ServiceError> {
+        match origin {
+            Origin::Repository { repo, skill, .. } => {
+                let (Some(version), Some(repo_manager)) = (version, self.repository_manager())
+                else {
+                    return Ok(None);
+                };
+                let repo_name = resolve_repo_name(repo_manager, repo)?;
+                let client = repo_manager.get_client(&repo_name).await?;
+                client
+                    .download_size(skill, version)
+                    .await
+                    .map_err(|e| ServiceError::Config(format!("Failed to get package size: {e}")))
+            }
+            Origin::Local {
+                path,
+                editable: false,
+            } => Ok(Some(
+                walkdir::WalkDir::new(path)
+                    .follow_links(false)
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                    .filter_map(|e| e.metadata().ok())
+                    .filter(|m| m.is_file())
+                    .map(|m| m.len())
+                    .sum(),
+            )),
+            _ => Ok(None),
+        }
+    }
 }
 
 // ── Free helper functions ─────────────────────────────────────────────────────
@@ -590,7 +842,7 @@ fn resolve_repo_name(repo_manager: &RepositoryManager, repo: &str) -> Result<Str
 }
 
 /// Read and parse `SKILL.md`'s frontmatter from a fetched skill directory.
-async fn read_skill_frontmatter(skill_path: &Path) -> Result<SkillFrontmatter, ServiceError> {
+pub async fn read_skill_frontmatter(skill_path: &Path) -> Result<SkillFrontmatter, ServiceError> {
     let content = tokio::fs::read_to_string(skill_path.join("SKILL.md")).await?;
     parse_yaml_frontmatter(&content)
 }
@@ -599,7 +851,7 @@ async fn read_skill_frontmatter(skill_path: &Path) -> Result<SkillFrontmatter, S
 /// `[metadata]` wins when present, else `SKILL.md` frontmatter (`metadata.id`/
 /// `.version` sub-map, else `name`/top-level `version`, else `"1.0.0"`). Mirrors
 /// `fastskill-cli`'s `create_skill_from_path` precedence.
-fn derive_skill_id_and_version(
+pub fn derive_skill_id_and_version(
     skill_path: &Path,
     frontmatter: &SkillFrontmatter,
 ) -> Result<(SkillId, String), ServiceError> {
@@ -642,7 +894,7 @@ fn derive_skill_id_and_version(
 /// Safely join an untrusted `subdir` (from a git tree reference) onto a trusted
 /// clone `root`, rejecting path traversal. Mirrors the CLI's
 /// `install_utils::safe_subdir_join`.
-fn safe_subdir_join(root: &Path, subdir: &Path) -> Result<PathBuf, ServiceError> {
+pub(crate) fn safe_subdir_join(root: &Path, subdir: &Path) -> Result<PathBuf, ServiceError> {
     use std::path::Component;
 
     let mut joined = root.to_path_buf();
@@ -704,24 +956,13 @@ async fn git_head_commit(repo_dir: &Path) -> Result<String, ServiceError> {
     Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
 
-/// Remove whatever currently sits at `path` (file, symlink, or directory), if
-/// anything, so a fresh move/copy/symlink can take its place.
-async fn remove_existing_storage_path(path: &Path) -> Result<(), ServiceError> {
-    if path.is_symlink() || path.is_file() {
-        tokio::fs::remove_file(path).await?;
-    } else if path.exists() {
-        tokio::fs::remove_dir_all(path).await?;
-    }
-    Ok(())
-}
-
 /// Move `skill_path` into `storage_dir` (same-filesystem rename when possible,
 /// falling back to a recursive copy across filesystems/temp-dir boundaries).
 async fn move_or_copy_into_storage(
     skill_path: &Path,
     storage_dir: &Path,
 ) -> Result<(), ServiceError> {
-    remove_existing_storage_path(storage_dir).await?;
+    link::remove_path(storage_dir)?;
     if let Some(parent) = storage_dir.parent() {
         tokio::fs::create_dir_all(parent).await?;
     }
@@ -732,31 +973,6 @@ async fn move_or_copy_into_storage(
     copy_dir_recursive(skill_path, storage_dir).await
 }
 
-/// Symlink `storage_dir` -> `skill_path` (editable local installs).
-async fn symlink_into_storage(skill_path: &Path, storage_dir: &Path) -> Result<(), ServiceError> {
-    remove_existing_storage_path(storage_dir).await?;
-    if let Some(parent) = storage_dir.parent() {
-        tokio::fs::create_dir_all(parent).await?;
-    }
-    #[cfg(unix)]
-    {
-        tokio::fs::symlink(skill_path, storage_dir).await?;
-    }
-    #[cfg(windows)]
-    {
-        std::os::windows::fs::symlink_dir(skill_path, storage_dir)?;
-    }
-    #[cfg(all(not(unix), not(windows)))]
-    {
-        return Err(ServiceError::InvalidOperation(
-            "Editable installations are not supported on this platform. Use a Unix-based \
-             system or Docker."
-                .to_string(),
-        ));
-    }
-    Ok(())
-}
-
 /// Recursively copy a directory from `src` to `dst`, rejecting symlink entries
 /// (SEC-4: a symlink inside the source tree must not be silently dereferenced
 /// and its target's contents exfiltrated into the copy).
@@ -787,6 +1003,7 @@ async fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), ServiceError>
 #[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
 mod tests {
     use super::*;
+    use crate::core::skill_naming::IdCollision;
     use crate::{FastSkillService, ServiceConfig};
     use tempfile::TempDir as TestTempDir;
 
@@ -874,6 +1091,53 @@ mod tests {
             .is_some());
     }
 
+    #[tokio::test]
+    #[allow(clippy::await_holding_lock)]
+    async fn test_add_from_origin_pack_records_pack_origin() {
+        let _lock = crate::test_utils::DIR_MUTEX
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let (tmp, _guard, skills_dir) = setup_project();
+        let src = write_valid_skill(&tmp.path().join("bundle"), "test-skill");
+        let pack_path = tmp.path().join("team.zip");
+        crate::core::pack::build_pack(
+            crate::core::pack::PackInfo {
+                name: "team".to_string(),
+                version: "1.0.0".to_string(),
+                description: None,
+            },
+            &[src],
+            &pack_path,
+        )
+        .await
+        .unwrap();
+        let service = make_service(&skills_dir).await;
+
+        let origin = Origin::Pack {
+            path: pack_path.clone(),
+            skill: "test-skill".to_string(),
+        };
+        let outcome = service
+            .add_from_origin(origin.clone(), AddMode::Fresh, vec![])
+            .await
+            .expect("pack add should succeed");
+        assert_eq!(outcome.id, "test-skill");
+        assert!(skills_dir.join("test-skill/SKILL.md").exists());
+
+        let lock = ProjectSkillsLock::load_from_file(&tmp.path().join("skills.lock"))
+            .expect("lock should load");
+        assert_eq!(lock.skills[0].origin, origin);
+
+        let missing = Origin::Pack {
+            path: pack_path,
+            skill: "not-in-pack".to_string(),
+        };
+        assert!(service
+            .add_from_origin(missing, AddMode::Fresh, vec![])
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     #[allow(clippy::await_holding_lock)]
     async fn test_add_from_origin_records_and_preserves_groups() {
@@ -994,6 +1258,65 @@ mod tests {
         assert_eq!(skill.version, "2.0.0");
     }
 
+    #[tokio::test]
+    #[allow(clippy::await_holding_lock)]
+    async fn test_add_from_origin_suffixes_colliding_id() {
+        let _lock = crate::test_utils::DIR_MUTEX
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let (tmp, _guard, skills_dir) = setup_project();
+        // Two different sources that both declare `test-skill`
+        let first = write_valid_skill(tmp.path(), "first");
+        let second = write_valid_skill(tmp.path(), "second");
+        let service = make_service(&skills_dir).await;
+        let suffix = SkillNaming {
+            on_collision: IdCollision::Suffix,
+            ..Default::default()
+        };
+        let local = |path: &PathBuf| Origin::Local {
+            path: path.clone(),
+            editable: false,
+        };
+
+        service
+            .add_from_origin(local(&first), AddMode::Fresh, vec![])
+            .await
+            .expect("first add should succeed");
+        let outcome = service
+            .add_from_origin_with_naming(
+                local(&second),
+                AddMode::Fresh,
+                vec![],
+                suffix,
+                LinkMode::Auto,
+            )
+            .await
+            .expect("colliding add should be suffixed");
+        assert_eq!(outcome.id, "test-skill-2");
+        assert_eq!(outcome.renamed_from.as_deref(), Some("test-skill"));
+        assert!(skills_dir.join("test-skill-2").join("SKILL.md").exists());
+
+        // Adding the same source again is not a collision to de-collide
+        let again = service
+            .add_from_origin_with_naming(
+                local(&second),
+                AddMode::Fresh,
+                vec![],
+                suffix,
+                LinkMode::Auto,
+            )
+            .await;
+        assert!(matches!(again, Err(ServiceError::AlreadyIndexed(id)) if id == "test-skill-2"));
+
+        // Updating keeps the suffixed id recorded in skills.lock
+        let updated = service
+            .add_from_origin(local(&second), AddMode::Update, vec![])
+            .await
+            .expect("update should succeed");
+        assert_eq!(updated.id, "test-skill-2");
+        assert!(updated.renamed_from.is_none());
+    }
+
     #[cfg(unix)]
     #[tokio::test]
     #[allow(clippy::await_holding_lock)]
@@ -1016,6 +1339,42 @@ mod tests {
 
         let storage_path = skills_dir.join(&outcome.id);
         assert!(storage_path.is_symlink(), "editable install must symlink");
+        assert_eq!(outcome.linked, Some(LinkMode::Symlink));
+    }
+
+    #[tokio::test]
+    #[allow(clippy::await_holding_lock)]
+    async fn test_add_from_origin_local_editable_copy_mode() {
+        let _lock = crate::test_utils::DIR_MUTEX
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let (tmp, _guard, skills_dir) = setup_project();
+        let src = write_valid_skill(tmp.path(), "src-skill");
+        let service = make_service(&skills_dir).await;
+
+        let origin = Origin::Local {
+            path: src.clone(),
+            editable: true,
+        };
+        let outcome = service
+            .add_from_origin_with_naming(
+                origin,
+                AddMode::Fresh,
+                vec![],
+                SkillNaming::default(),
+                LinkMode::Copy,
+            )
+            .await
+            .expect("editable add should succeed");
+
+        let storage_path = skills_dir.join(&outcome.id);
+        assert!(!storage_path.is_symlink());
+        assert!(storage_path.join("SKILL.md").is_file());
+        assert_eq!(outcome.linked, Some(LinkMode::Copy));
+        assert!(
+            src.join("SKILL.md").exists(),
+            "source must be left in place"
+        );
     }
 
     #[tokio::test]
@@ -1034,6 +1393,35 @@ mod tests {
         assert!(matches!(result, Err(ServiceError::InvalidOperation(_))));
     }
 
+    #[tokio::test]
+    #[allow(clippy::await_holding_lock)]
+    async fn test_add_from_origin_refuses_unmet_requirements() {
+        let _lock = crate::test_utils::DIR_MUTEX
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let (tmp, _guard, skills_dir) = setup_project();
+        let src = tmp.path().join("future-skill");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(
+            src.join("SKILL.md"),
+            "---\nname: future-skill\ndescription: d\nrequires:\n  fastskill: \">=999.0\"\n---\n",
+        )
+        .unwrap();
+        let service = make_service(&skills_dir).await;
+
+        let origin = Origin::Local {
+            path: src,
+            editable: false,
+        };
+        let err = service
+            .add_from_origin(origin, AddMode::Fresh, vec![])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ServiceError::Validation(_)));
+        assert!(err.to_string().contains("fastskill self-update"), "{}", err);
+        assert!(!skills_dir.join("future-skill").exists());
+    }
+
     // ── add_from_origin: ZipUrl, end-to-end (mock HTTP) ───────────────────────
 
     fn build_skill_zip() -> Vec<u8> {
@@ -1079,6 +1467,147 @@ mod tests {
             .expect("zip-url add should succeed");
         assert_eq!(outcome.id, "test-skill");
         assert!(skills_dir.join("test-skill/SKILL.md").exists());
+        let checksum = {
+            use sha2::Digest;
+            format!("sha256:{:x}", sha2::Sha256::digest(build_skill_zip()))
+        };
+        assert_eq!(outcome.resolved.checksum, Some(checksum));
+    }
+
+    #[tokio::test]
+    #[allow(clippy::await_holding_lock)]
+    async fn test_add_from_origin_zip_url_checksum_fragment() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let _lock = crate::test_utils::DIR_MUTEX
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let (_tmp, _guard, skills_dir) = setup_project();
+        let service = make_service(&skills_dir).await;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/pkg.zip"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(build_skill_zip()))
+            .mount(&server)
+            .await;
+
+        let origin = Origin::ZipUrl {
+            url: format!("{}/pkg.zip#sha256={}", server.uri(), "ab".repeat(32)),
+        };
+        let result = service
+            .add_from_origin(origin, AddMode::Fresh, vec![])
+            .await;
+        assert!(
+            matches!(&result, Err(ServiceError::Validation(m)) if m.contains("Checksum mismatch")),
+            "{:?}",
+            result.map(|o| o.id)
+        );
+        assert!(!skills_dir.join("test-skill").exists());
+    }
+
+    #[tokio::test]
+    #[allow(clippy::await_holding_lock)]
+    async fn test_update_zip_url_fetches_only_changed_files() {
+        use sha2::Digest;
+        use std::io::Write;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+        use zip::write::FileOptions;
+
+        let _lock = crate::test_utils::DIR_MUTEX
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let (_tmp, _guard, skills_dir) = setup_project();
+        let service = make_service(&skills_dir).await;
+
+        let script = "#!/bin/sh\necho hello\n";
+        let mut archive = Vec::new();
+        {
+            let mut writer = zip::ZipWriter::new(std::io::Cursor::new(&mut archive));
+            let opts = FileOptions::default().compression_method(zip::CompressionMethod::Stored);
+            writer.start_file("test-skill/SKILL.md", opts).unwrap();
+            writer.write_all(VALID_SKILL_MD.as_bytes()).unwrap();
+            writer
+                .start_file("test-skill/scripts/run.sh", opts)
+                .unwrap();
+            writer.write_all(script.as_bytes()).unwrap();
+            writer.finish().unwrap();
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/pkg.zip"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(archive))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let origin = Origin::ZipUrl {
+            url: format!("{}/pkg.zip", server.uri()),
+        };
+        service
+            .add_from_origin(origin.clone(), AddMode::Fresh, vec![])
+            .await
+            .expect("zip-url add should succeed");
+
+        let updated =
+            "---\nname: test-skill\nversion: \"2.0.0\"\ndescription: updated\n---\nBody\n";
+        let manifest = serde_json::json!({
+            "files": [
+                {
+                    "path": "SKILL.md",
+                    "sha256": format!("{:x}", sha2::Sha256::digest(updated)),
+                    "size": updated.len(),
+                },
+                {
+                    "path": "scripts/run.sh",
+                    "sha256": format!("{:x}", sha2::Sha256::digest(script)),
+                    "size": script.len(),
+                },
+            ]
+        });
+        Mock::given(method("GET"))
+            .and(path("/pkg.zip.files.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(manifest))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/pkg/SKILL.md"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(updated))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/pkg/scripts/run.sh"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(script))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let outcome = service
+            .add_from_origin(origin, AddMode::Update, vec![])
+            .await
+            .expect("delta update should succeed");
+        assert_eq!(outcome.resolved.version, "2.0.0");
+        assert_eq!(
+            outcome.delta,
+            Some(DeltaStats {
+                files_total: 2,
+                files_fetched: 1,
+                bytes_fetched: updated.len() as u64,
+                bytes_saved: script.len() as u64,
+            })
+        );
+        let installed = skills_dir.join("test-skill");
+        assert_eq!(
+            std::fs::read_to_string(installed.join("SKILL.md")).unwrap(),
+            updated
+        );
+        assert_eq!(
+            std::fs::read_to_string(installed.join("scripts/run.sh")).unwrap(),
+            script
+        );
     }
 
     // ── add_from_origin: Repository without a repository manager ─────────────
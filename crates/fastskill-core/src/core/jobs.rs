@@ -0,0 +1,779 @@
+//! Background jobs for long-running server operations
+//!
+//! Reindexing and publishing can outlast an HTTP client's timeout. With
+//! `?async=true` their endpoints submit a [`Job`] to the server's [`JobQueue`]
+//! and answer `202 Accepted` straight away; clients poll
+//! `GET /api/v1/jobs/{id}` for status, progress and the result, and cancel
+//! with `POST /api/v1/jobs/{id}/cancel`.
+//!
+//! Jobs run one at a time, in the order they were submitted. Every status
+//! change is published on the [`EventBus`] as [`SkillEvent::JobUpdated`].
+//! Jobs are kept in memory; with `[tool.fastskill.server.jobs] persist = true`
+//! they are also written to a SQLite [`JobStore`], so finished jobs can still
+//! be polled after a restart. Jobs a previous process left queued or running
+//! are marked failed when the store is loaded.
+//!
+//! Cancelling stops a job at its next await point, like
+//! [`cancellable`](crate::core::cancel::cancellable). Before a step that must
+//! not be cut short (writing a published package), a job calls
+//! [`JobHandle::begin_commit`]; cancel requests are refused from then on.
+
+use crate::core::cancel::{cancellable, CancellationToken};
+use crate::core::service::ServiceError;
+use crate::events::{EventBus, SkillEvent};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::sync::Semaphore;
+
+/// Finished jobs kept for polling unless configured otherwise
+pub const DEFAULT_MAX_FINISHED_JOBS: usize = 100;
+
+/// How long a store write waits for a lock held by another connection
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Job settings for `fastskill serve` (`[tool.fastskill.server.jobs]`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobsConfig {
+    /// Keep jobs in SQLite so they survive a restart (default: false)
+    #[serde(default)]
+    pub persist: bool,
+    /// Database file (default: `<skills dir>/.fastskill/jobs.db`)
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+    /// Finished jobs kept for polling; the oldest are dropped (default: 100)
+    #[serde(default = "default_max_finished")]
+    pub max_finished: usize,
+}
+
+fn default_max_finished() -> usize {
+    DEFAULT_MAX_FINISHED_JOBS
+}
+
+impl Default for JobsConfig {
+    fn default() -> Self {
+        Self {
+            persist: false,
+            path: None,
+            max_finished: DEFAULT_MAX_FINISHED_JOBS,
+        }
+    }
+}
+
+/// The operation a job runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    Reindex,
+    Publish,
+}
+
+impl fmt::Display for JobKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Reindex => "reindex",
+            Self::Publish => "publish",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    /// Waiting for the job ahead of it
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    pub fn is_finished(self) -> bool {
+        matches!(self, Self::Succeeded | Self::Failed | Self::Cancelled)
+    }
+
+    /// Event type a change to this status is published under
+    pub fn event_type(self) -> &'static str {
+        match self {
+            Self::Queued => "job:queued",
+            Self::Running => "job:running",
+            Self::Succeeded => "job:succeeded",
+            Self::Failed => "job:failed",
+            Self::Cancelled => "job:cancelled",
+        }
+    }
+}
+
+impl fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Queued => "queued",
+            Self::Running => "running",
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+        })
+    }
+}
+
+/// A submitted job and how far it got
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Job {
+    pub id: String,
+    pub kind: JobKind,
+    pub status: JobStatus,
+    /// Percent done, 0 to 100
+    pub progress: u8,
+    /// What the job is doing, or why it stopped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// What the operation returned, once it succeeded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Why a job could not be cancelled
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum JobError {
+    #[error("Job not found: {0}")]
+    NotFound(String),
+    #[error("Job {id} already finished ({status})")]
+    Finished { id: String, status: JobStatus },
+    #[error("Job {0} is saving its result and can no longer be cancelled")]
+    Committing(String),
+}
+
+struct Entry {
+    job: Job,
+    token: CancellationToken,
+    /// Set by [`JobHandle::begin_commit`]; cancel requests are refused
+    committing: bool,
+}
+
+/// Runs submitted jobs one at a time and keeps their state for polling
+pub struct JobQueue {
+    entries: Mutex<HashMap<String, Entry>>,
+    /// One permit, so jobs run one at a time in submission order
+    slot: Semaphore,
+    events: Option<Arc<EventBus>>,
+    store: Option<JobStore>,
+    max_finished: usize,
+}
+
+impl Default for JobQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JobQueue {
+    /// An in-memory queue that publishes no events
+    pub fn new() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            slot: Semaphore::new(1),
+            events: None,
+            store: None,
+            max_finished: DEFAULT_MAX_FINISHED_JOBS,
+        }
+    }
+
+    /// Publish status changes on `bus`
+    pub fn with_events(mut self, bus: Arc<EventBus>) -> Self {
+        self.events = Some(bus);
+        self
+    }
+
+    /// Keep at most `max_finished` finished jobs (at least one)
+    pub fn with_max_finished(mut self, max_finished: usize) -> Self {
+        self.max_finished = max_finished.max(1);
+        self
+    }
+
+    /// Persist jobs in `store`, starting from the jobs it holds. Jobs a
+    /// previous process left queued or running are marked failed.
+    pub async fn with_store(mut self, store: JobStore) -> Result<Self, ServiceError> {
+        let loaded = {
+            let store = store.clone();
+            tokio::task::spawn_blocking(move || store.load())
+                .await
+                .map_err(|e| ServiceError::Custom(format!("Job store task failed: {}", e)))??
+        };
+        let mut interrupted = Vec::new();
+        {
+            let mut entries = self.lock();
+            for mut job in loaded {
+                if !job.status.is_finished() {
+                    job.status = JobStatus::Failed;
+                    job.error = Some("Interrupted by a server restart".to_string());
+                    job.finished_at = Some(Utc::now());
+                    interrupted.push(job.clone());
+                }
+                let entry = Entry {
+                    job,
+                    token: CancellationToken::new(),
+                    committing: false,
+                };
+                entries.insert(entry.job.id.clone(), entry);
+            }
+        }
+        self.store = Some(store);
+        for job in &interrupted {
+            self.persist(job).await;
+        }
+        self.evict().await;
+        Ok(self)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Entry>> {
+        match self.entries.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<Job> {
+        self.lock().get(id).map(|entry| entry.job.clone())
+    }
+
+    /// Every job, newest first
+    pub fn list(&self) -> Vec<Job> {
+        let mut jobs: Vec<Job> = self.lock().values().map(|e| e.job.clone()).collect();
+        jobs.sort_by_key(|job| Reverse(job.created_at));
+        jobs
+    }
+
+    /// Queue `work` as a job of `kind` and return it. `work` gets a
+    /// [`JobHandle`] for progress; what it returns becomes the job's result.
+    pub async fn submit<F, Fut>(self: &Arc<Self>, kind: JobKind, work: F) -> Job
+    where
+        F: FnOnce(JobHandle) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<serde_json::Value, ServiceError>> + Send + 'static,
+    {
+        let job = Job {
+            id: uuid::Uuid::new_v4().to_string(),
+            kind,
+            status: JobStatus::Queued,
+            progress: 0,
+            message: None,
+            result: None,
+            error: None,
+            created_at: Utc::now(),
+            started_at: None,
+            finished_at: None,
+        };
+        let token = CancellationToken::new();
+        self.lock().insert(
+            job.id.clone(),
+            Entry {
+                job: job.clone(),
+                token: token.clone(),
+                committing: false,
+            },
+        );
+        self.announce(&job).await;
+
+        let handle = JobHandle {
+            id: job.id.clone(),
+            queue: Arc::clone(self),
+            token,
+        };
+        tokio::spawn(Arc::clone(self).run(handle, work));
+        job
+    }
+
+    async fn run<F, Fut>(self: Arc<Self>, handle: JobHandle, work: F)
+    where
+        F: FnOnce(JobHandle) -> Fut,
+        Fut: Future<Output = Result<serde_json::Value, ServiceError>>,
+    {
+        let id = handle.id.clone();
+        let token = handle.token.clone();
+        let _permit = match cancellable(&token, self.slot.acquire()).await {
+            Ok(Ok(permit)) => permit,
+            Ok(Err(e)) => {
+                self.finish(&id, Err(ServiceError::Custom(e.to_string())))
+                    .await;
+                return;
+            }
+            Err(cancelled) => {
+                self.finish(&id, Err(cancelled)).await;
+                return;
+            }
+        };
+
+        if let Some(job) = self.update(&id, |job| {
+            job.status = JobStatus::Running;
+            job.started_at = Some(Utc::now());
+        }) {
+            self.announce(&job).await;
+        }
+        let result = cancellable(&token, work(handle)).await.and_then(|r| r);
+        self.finish(&id, result).await;
+    }
+
+    async fn finish(&self, id: &str, result: Result<serde_json::Value, ServiceError>) {
+        let job = self.update(id, |job| {
+            job.finished_at = Some(Utc::now());
+            match result {
+                Ok(value) => {
+                    job.status = JobStatus::Succeeded;
+                    job.progress = 100;
+                    job.message = None;
+                    job.result = Some(value);
+                }
+                Err(ServiceError::Cancelled) => {
+                    job.status = JobStatus::Cancelled;
+                    job.message = Some("Cancelled".to_string());
+                }
+                Err(e) => {
+                    job.status = JobStatus::Failed;
+                    job.error = Some(e.to_string());
+                }
+            }
+        });
+        if let Some(job) = job {
+            self.announce(&job).await;
+        }
+        self.evict().await;
+    }
+
+    fn update(&self, id: &str, change: impl FnOnce(&mut Job)) -> Option<Job> {
+        let mut entries = self.lock();
+        let entry = entries.get_mut(id)?;
+        change(&mut entry.job);
+        Some(entry.job.clone())
+    }
+
+    /// Ask job `id` to stop: a queued job never starts, a running one stops
+    /// at its next await point. The job shows `cancelled` once it has.
+    pub fn cancel(&self, id: &str) -> Result<Job, JobError> {
+        let mut entries = self.lock();
+        let entry = entries
+            .get_mut(id)
+            .ok_or_else(|| JobError::NotFound(id.to_string()))?;
+        if entry.job.status.is_finished() {
+            return Err(JobError::Finished {
+                id: id.to_string(),
+                status: entry.job.status,
+            });
+        }
+        if entry.committing {
+            return Err(JobError::Committing(id.to_string()));
+        }
+        entry.token.cancel();
+        entry.job.message = Some("Cancellation requested".to_string());
+        Ok(entry.job.clone())
+    }
+
+    /// Drop the oldest finished jobs beyond `max_finished`
+    async fn evict(&self) {
+        let evicted: Vec<String> = {
+            let mut entries = self.lock();
+            let mut finished: Vec<(DateTime<Utc>, String)> = entries
+                .values()
+                .filter(|e| e.job.status.is_finished())
+                .map(|e| {
+                    (
+                        e.job.finished_at.unwrap_or(e.job.created_at),
+                        e.job.id.clone(),
+                    )
+                })
+                .collect();
+            if finished.len() <= self.max_finished {
+                return;
+            }
+            finished.sort();
+            let excess = finished.len() - self.max_finished;
+            finished
+                .into_iter()
+                .take(excess)
+                .map(|(_, id)| {
+                    entries.remove(&id);
+                    id
+                })
+                .collect()
+        };
+        if let Some(store) = self.store.clone() {
+            let result = tokio::task::spawn_blocking(move || store.remove(&evicted)).await;
+            if let Err(e) = result
+                .map_err(|e| e.to_string())
+                .and_then(|r| r.map_err(|e| e.to_string()))
+            {
+                tracing::warn!("Failed to drop old jobs from the job store: {}", e);
+            }
+        }
+    }
+
+    async fn persist(&self, job: &Job) {
+        let Some(store) = self.store.clone() else {
+            return;
+        };
+        let saved = job.clone();
+        let result = tokio::task::spawn_blocking(move || store.save(&saved)).await;
+        if let Err(e) = result
+            .map_err(|e| e.to_string())
+            .and_then(|r| r.map_err(|e| e.to_string()))
+        {
+            tracing::warn!("Failed to save job {}: {}", job.id, e);
+        }
+    }
+
+    /// Save `job` and publish its new status
+    async fn announce(&self, job: &Job) {
+        self.persist(job).await;
+        if let Some(bus) = &self.events {
+            let event = SkillEvent::JobUpdated {
+                job: Box::new(job.clone()),
+            };
+            if let Err(e) = bus.publish_event(event).await {
+                tracing::warn!("Failed to publish job event: {}", e);
+            }
+        }
+    }
+}
+
+/// What a job's work uses to report progress and protect its final step
+#[derive(Clone)]
+pub struct JobHandle {
+    id: String,
+    queue: Arc<JobQueue>,
+    token: CancellationToken,
+}
+
+impl JobHandle {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Record `percent` done (at most 100) and what the job is doing
+    pub fn progress(&self, percent: u8, message: impl Into<String>) {
+        let message = message.into();
+        self.queue.update(&self.id, |job| {
+            job.progress = percent.min(100);
+            job.message = Some(message);
+        });
+    }
+
+    /// [`progress`](Self::progress) as `done` of `total` steps
+    pub fn progress_of(&self, done: usize, total: usize, message: impl Into<String>) {
+        let percent = match total {
+            0 => 100,
+            total => done.min(total) * 100 / total,
+        };
+        self.progress(percent as u8, message);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Refuse cancel requests from here on. Fails with `Cancelled` when one
+    /// already came in.
+    pub fn begin_commit(&self) -> Result<(), ServiceError> {
+        let mut entries = self.queue.lock();
+        if self.token.is_cancelled() {
+            return Err(ServiceError::Cancelled);
+        }
+        if let Some(entry) = entries.get_mut(&self.id) {
+            entry.committing = true;
+        }
+        Ok(())
+    }
+}
+
+/// SQLite file holding one JSON record per job
+#[derive(Debug, Clone)]
+pub struct JobStore {
+    path: PathBuf,
+}
+
+impl JobStore {
+    pub fn default_path(skills_dir: &Path) -> PathBuf {
+        skills_dir.join(".fastskill").join("jobs.db")
+    }
+
+    /// Open the store at `path`, creating the file and its table as needed
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, ServiceError> {
+        let store = Self { path: path.into() };
+        if let Some(parent) = store.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        store
+            .connect()?
+            .execute(
+                "CREATE TABLE IF NOT EXISTS jobs (
+                    id TEXT PRIMARY KEY,
+                    created_at TEXT NOT NULL,
+                    job_json TEXT NOT NULL
+                )",
+                [],
+            )
+            .map_err(|e| ServiceError::Custom(format!("Failed to create job table: {}", e)))?;
+        Ok(store)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn connect(&self) -> Result<rusqlite::Connection, ServiceError> {
+        let conn = rusqlite::Connection::open(&self.path)
+            .map_err(|e| ServiceError::Custom(format!("Failed to open job store: {}", e)))?;
+        conn.busy_timeout(BUSY_TIMEOUT)
+            .map_err(|e| ServiceError::Custom(format!("Failed to configure job store: {}", e)))?;
+        Ok(conn)
+    }
+
+    pub fn save(&self, job: &Job) -> Result<(), ServiceError> {
+        let json = serde_json::to_string(job)
+            .map_err(|e| ServiceError::Custom(format!("Failed to encode job: {}", e)))?;
+        self.connect()?
+            .execute(
+                "INSERT OR REPLACE INTO jobs (id, created_at, job_json) VALUES (?, ?, ?)",
+                rusqlite::params![job.id, job.created_at.to_rfc3339(), json],
+            )
+            .map_err(|e| ServiceError::Custom(format!("Failed to save job: {}", e)))?;
+        Ok(())
+    }
+
+    /// Every stored job, oldest first. Records that do not parse are skipped.
+    pub fn load(&self) -> Result<Vec<Job>, ServiceError> {
+        let conn = self.connect()?;
+        let mut stmt = conn
+            .prepare("SELECT id, job_json FROM jobs ORDER BY created_at")
+            .map_err(|e| ServiceError::Custom(format!("Failed to read jobs: {}", e)))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(|e| ServiceError::Custom(format!("Failed to read jobs: {}", e)))?;
+        let mut jobs = Vec::new();
+        for row in rows {
+            let (id, json) =
+                row.map_err(|e| ServiceError::Custom(format!("Failed to read jobs: {}", e)))?;
+            match serde_json::from_str(&json) {
+                Ok(job) => jobs.push(job),
+                Err(e) => tracing::warn!("Skipping unreadable job {}: {}", id, e),
+            }
+        }
+        Ok(jobs)
+    }
+
+    pub fn remove(&self, ids: &[String]) -> Result<(), ServiceError> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        let mut conn = self.connect()?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| ServiceError::Custom(format!("Failed to begin transaction: {}", e)))?;
+        for id in ids {
+            tx.execute("DELETE FROM jobs WHERE id = ?", [id])
+                .map_err(|e| ServiceError::Custom(format!("Failed to remove job: {}", e)))?;
+        }
+        tx.commit()
+            .map_err(|e| ServiceError::Custom(format!("Failed to remove jobs: {}", e)))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Poll until job `id` satisfies `done`
+    async fn wait_until(queue: &JobQueue, id: &str, done: impl Fn(&Job) -> bool) -> Job {
+        for _ in 0..200 {
+            let job = queue.get(id).expect("job should exist");
+            if done(&job) {
+                return job;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("job {} did not get there in time", id);
+    }
+
+    async fn wait_finished(queue: &JobQueue, id: &str) -> Job {
+        wait_until(queue, id, |job| job.status.is_finished()).await
+    }
+
+    #[tokio::test]
+    async fn test_job_runs_to_success_and_publishes_events() {
+        let bus = Arc::new(EventBus::new());
+        let mut events = bus.subscribe();
+        let queue = Arc::new(JobQueue::new().with_events(Arc::clone(&bus)));
+
+        let job = queue
+            .submit(JobKind::Reindex, |handle| async move {
+                handle.progress_of(1, 2, "half way");
+                Ok(serde_json::json!({ "count": 2 }))
+            })
+            .await;
+        assert_eq!(job.status, JobStatus::Queued);
+
+        let done = wait_finished(&queue, &job.id).await;
+        assert_eq!(done.status, JobStatus::Succeeded);
+        assert_eq!(done.progress, 100);
+        assert_eq!(done.result, Some(serde_json::json!({ "count": 2 })));
+        assert!(done.started_at.is_some() && done.finished_at.is_some());
+
+        // The last event may be published just after the status is visible
+        let mut statuses = Vec::new();
+        while statuses.len() < 3 {
+            let event = tokio::time::timeout(Duration::from_secs(2), events.recv())
+                .await
+                .expect("job event should arrive")
+                .unwrap();
+            if let SkillEvent::JobUpdated { job } = event {
+                statuses.push(job.status);
+            }
+        }
+        assert_eq!(
+            statuses,
+            vec![JobStatus::Queued, JobStatus::Running, JobStatus::Succeeded]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_job_records_error() {
+        let queue = Arc::new(JobQueue::new());
+        let job = queue
+            .submit(JobKind::Publish, |_| async {
+                Err(ServiceError::Validation("bad package".to_string()))
+            })
+            .await;
+        let done = wait_finished(&queue, &job.id).await;
+        assert_eq!(done.status, JobStatus::Failed);
+        assert!(done.error.unwrap().contains("bad package"));
+    }
+
+    #[tokio::test]
+    async fn test_jobs_run_one_at_a_time_and_queued_job_can_be_cancelled() {
+        let queue = Arc::new(JobQueue::new());
+        let first = queue
+            .submit(JobKind::Reindex, |_| async {
+                std::future::pending::<()>().await;
+                Ok(serde_json::Value::Null)
+            })
+            .await;
+        let second = queue
+            .submit(JobKind::Reindex, |_| async { Ok(serde_json::Value::Null) })
+            .await;
+        wait_until(&queue, &first.id, |job| job.status == JobStatus::Running).await;
+        assert_eq!(queue.get(&second.id).unwrap().status, JobStatus::Queued);
+
+        queue.cancel(&second.id).unwrap();
+        assert_eq!(
+            wait_finished(&queue, &second.id).await.status,
+            JobStatus::Cancelled
+        );
+
+        queue.cancel(&first.id).unwrap();
+        assert_eq!(
+            wait_finished(&queue, &first.id).await.status,
+            JobStatus::Cancelled
+        );
+        assert!(matches!(
+            queue.cancel(&first.id),
+            Err(JobError::Finished {
+                status: JobStatus::Cancelled,
+                ..
+            })
+        ));
+        assert!(matches!(queue.cancel("nope"), Err(JobError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_committing_job_cannot_be_cancelled() {
+        let queue = Arc::new(JobQueue::new());
+        let release = Arc::new(tokio::sync::Notify::new());
+        let wait = Arc::clone(&release);
+        let job = queue
+            .submit(JobKind::Publish, move |handle| async move {
+                handle.begin_commit()?;
+                handle.progress(50, "writing");
+                wait.notified().await;
+                Ok(serde_json::Value::Null)
+            })
+            .await;
+        wait_until(&queue, &job.id, |job| job.progress == 50).await;
+
+        assert!(matches!(
+            queue.cancel(&job.id),
+            Err(JobError::Committing(_))
+        ));
+        release.notify_one();
+        assert_eq!(
+            wait_finished(&queue, &job.id).await.status,
+            JobStatus::Succeeded
+        );
+    }
+
+    #[tokio::test]
+    async fn test_finished_jobs_beyond_limit_are_dropped() {
+        let queue = Arc::new(JobQueue::new().with_max_finished(2));
+        let mut ids = Vec::new();
+        for _ in 0..3 {
+            let job = queue
+                .submit(JobKind::Reindex, |_| async { Ok(serde_json::Value::Null) })
+                .await;
+            wait_finished(&queue, &job.id).await;
+            ids.push(job.id);
+        }
+        assert!(queue.get(&ids[0]).is_none());
+        assert_eq!(queue.list().len(), 2);
+        assert_eq!(queue.list()[0].id, ids[2]);
+    }
+
+    #[tokio::test]
+    async fn test_store_keeps_jobs_and_fails_interrupted_ones() {
+        let dir = TempDir::new().unwrap();
+        let path = JobStore::default_path(dir.path());
+        let queue = Arc::new(
+            JobQueue::new()
+                .with_store(JobStore::open(&path).unwrap())
+                .await
+                .unwrap(),
+        );
+        let done = queue
+            .submit(JobKind::Reindex, |_| async { Ok(serde_json::json!(1)) })
+            .await;
+        wait_finished(&queue, &done.id).await;
+        let stuck = queue
+            .submit(JobKind::Reindex, |_| async {
+                std::future::pending::<()>().await;
+                Ok(serde_json::Value::Null)
+            })
+            .await;
+        // Running means the first job is done, store writes included
+        wait_until(&queue, &stuck.id, |job| job.status == JobStatus::Running).await;
+
+        // A new process opens the same store
+        let reopened = JobQueue::new()
+            .with_store(JobStore::open(&path).unwrap())
+            .await
+            .unwrap();
+        let done = reopened.get(&done.id).unwrap();
+        assert_eq!(done.status, JobStatus::Succeeded);
+        assert_eq!(done.result, Some(serde_json::json!(1)));
+        let stuck = reopened.get(&stuck.id).unwrap();
+        assert_eq!(stuck.status, JobStatus::Failed);
+        assert!(stuck.error.unwrap().contains("restart"));
+    }
+}
@@ -0,0 +1,336 @@
+//! How an editable install links a skill into the skills directory.
+//!
+//! An editable (`add -e`) skill is not copied: its directory in the skills
+//! dir points at the source folder so edits show up without re-adding. A
+//! directory symlink does that everywhere except on Windows without Developer
+//! Mode or admin rights, where creating one fails. [`LinkMode::Auto`] (the
+//! default) therefore falls back to an NTFS junction, which needs no
+//! privilege, and then to a copy. `link_mode` in `[tool.fastskill]` or
+//! `--link-mode` on `add` and `install` pins one strategy instead; only
+//! [`LinkMode::Hardlink`] shares file contents with the source without a
+//! directory link.
+
+use crate::core::service::ServiceError;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Strategy for linking an editable skill into the skills directory.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LinkMode {
+    /// Symlink, else junction (Windows), else copy (the default).
+    #[default]
+    Auto,
+    /// A directory symlink; fails on Windows without the symlink privilege.
+    Symlink,
+    /// An NTFS junction. Junctions only exist on Windows; elsewhere this is
+    /// a directory symlink.
+    Junction,
+    /// A copy of every file; edits to the source need a re-install.
+    Copy,
+    /// Hard links to the source files where the filesystem allows, else
+    /// copies. A hard link aliases the source file: edits show up on both
+    /// sides, in either direction, unless the editor replaces the file.
+    /// Files added later need a re-install.
+    Hardlink,
+}
+
+impl LinkMode {
+    pub fn parse(s: &str) -> Result<Self, ServiceError> {
+        match s.trim() {
+            "auto" => Ok(Self::Auto),
+            "symlink" => Ok(Self::Symlink),
+            "junction" => Ok(Self::Junction),
+            "copy" => Ok(Self::Copy),
+            "hardlink" => Ok(Self::Hardlink),
+            other => Err(ServiceError::Validation(format!(
+                "unknown link mode '{}' (expected auto, symlink, junction, copy or hardlink)",
+                other
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Symlink => "symlink",
+            Self::Junction => "junction",
+            Self::Copy => "copy",
+            Self::Hardlink => "hardlink",
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl std::fmt::Display for LinkMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Make `dst` point at the directory `src` with `mode`, replacing whatever
+/// sits at `dst`. Returns the strategy that was used, never
+/// [`LinkMode::Auto`].
+pub async fn link_dir(src: &Path, dst: &Path, mode: LinkMode) -> Result<LinkMode, ServiceError> {
+    let (src, dst) = (src.to_path_buf(), dst.to_path_buf());
+    tokio::task::spawn_blocking(move || link_dir_blocking(&src, &dst, mode))
+        .await
+        .map_err(|e| ServiceError::Custom(format!("Linking skill failed: {}", e)))?
+}
+
+fn link_dir_blocking(src: &Path, dst: &Path, mode: LinkMode) -> Result<LinkMode, ServiceError> {
+    remove_path(dst)?;
+    if let Some(parent) = dst.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    match mode {
+        LinkMode::Symlink => {
+            symlink(src, dst)?;
+            Ok(LinkMode::Symlink)
+        }
+        LinkMode::Junction => {
+            junction(src, dst)?;
+            Ok(LinkMode::Junction)
+        }
+        LinkMode::Copy => {
+            copy_tree(src, dst, false)?;
+            Ok(LinkMode::Copy)
+        }
+        LinkMode::Hardlink => {
+            copy_tree(src, dst, true)?;
+            Ok(LinkMode::Hardlink)
+        }
+        LinkMode::Auto => {
+            let symlink_err = match symlink(src, dst) {
+                Ok(()) => return Ok(LinkMode::Symlink),
+                Err(e) => e,
+            };
+            if cfg!(windows) {
+                match junction(src, dst) {
+                    Ok(()) => return Ok(LinkMode::Junction),
+                    Err(e) => tracing::debug!("Junction to {} failed: {}", src.display(), e),
+                }
+            }
+            tracing::warn!(
+                "Cannot symlink {} ({}); installing it as a copy instead. Re-run the install \
+                 to pick up files added to the source later.",
+                src.display(),
+                symlink_err
+            );
+            remove_path(dst)?;
+            copy_tree(src, dst, false)?;
+            Ok(LinkMode::Copy)
+        }
+    }
+}
+
+fn symlink(src: &Path, dst: &Path) -> Result<(), ServiceError> {
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(src, dst)?;
+        Ok(())
+    }
+    #[cfg(windows)]
+    {
+        std::os::windows::fs::symlink_dir(src, dst).map_err(|e| {
+            ServiceError::Io(std::io::Error::new(
+                e.kind(),
+                format!(
+                    "Windows symlink permission denied: {}. Enable Developer Mode \
+                     (Settings → For developers), run as Administrator, or use \
+                     --link-mode junction.",
+                    e
+                ),
+            ))
+        })
+    }
+    #[cfg(all(not(unix), not(windows)))]
+    {
+        let _ = (src, dst);
+        Err(ServiceError::InvalidOperation(
+            "Symlinks are not supported on this platform; use --link-mode copy.".to_string(),
+        ))
+    }
+}
+
+/// An NTFS junction via `mklink /J`, which unlike a symlink needs no
+/// privilege. Junction targets must be absolute local paths.
+#[cfg(windows)]
+fn junction(src: &Path, dst: &Path) -> Result<(), ServiceError> {
+    let target = std::path::absolute(src)?;
+    let output = std::process::Command::new("cmd")
+        .arg("/C")
+        .arg("mklink")
+        .arg("/J")
+        .arg(dst)
+        .arg(&target)
+        .output()?;
+    if !output.status.success() {
+        return Err(ServiceError::Custom(format!(
+            "mklink /J failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn junction(src: &Path, dst: &Path) -> Result<(), ServiceError> {
+    symlink(src, dst)
+}
+
+/// Recreate the tree under `src` at `dst`, copying files or, with
+/// `hard_link`, hard-linking them where possible. Symlinks in the source are
+/// refused rather than followed, as for every other copy into the skills dir.
+fn copy_tree(src: &Path, dst: &Path, hard_link: bool) -> Result<(), ServiceError> {
+    for entry in walkdir::WalkDir::new(src).follow_links(false) {
+        let entry = entry.map_err(|e| ServiceError::Custom(e.to_string()))?;
+        let relative = entry
+            .path()
+            .strip_prefix(src)
+            .map_err(|e| ServiceError::Custom(e.to_string()))?;
+        let target: PathBuf = dst.join(relative);
+        let ty = entry.file_type();
+        if ty.is_symlink() {
+            return Err(ServiceError::Validation(format!(
+                "refusing to copy symlink: {}",
+                entry.path().display()
+            )));
+        }
+        if ty.is_dir() {
+            std::fs::create_dir_all(&target)?;
+        } else if !(hard_link && std::fs::hard_link(entry.path(), &target).is_ok()) {
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Remove whatever sits at `path` (file, directory, symlink or junction), if
+/// anything. Links are removed without touching what they point at.
+pub fn remove_path(path: &Path) -> Result<(), ServiceError> {
+    let meta = match std::fs::symlink_metadata(path) {
+        Ok(meta) => meta,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+    if meta.file_type().is_symlink() {
+        // Directory symlinks and junctions are directories to Windows
+        #[cfg(windows)]
+        {
+            if std::fs::remove_dir(path).is_ok() {
+                return Ok(());
+            }
+        }
+        std::fs::remove_file(path)?;
+    } else if meta.is_dir() {
+        std::fs::remove_dir_all(path)?;
+    } else {
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn source(dir: &TempDir) -> PathBuf {
+        let src = dir.path().join("src-skill");
+        std::fs::create_dir_all(src.join("references")).unwrap();
+        std::fs::write(src.join("SKILL.md"), "---\nname: s\n---\n").unwrap();
+        std::fs::write(src.join("references").join("a.md"), "A").unwrap();
+        src
+    }
+
+    #[test]
+    fn test_link_mode_parse() {
+        for mode in [
+            LinkMode::Auto,
+            LinkMode::Symlink,
+            LinkMode::Junction,
+            LinkMode::Copy,
+            LinkMode::Hardlink,
+        ] {
+            assert_eq!(LinkMode::parse(mode.as_str()).unwrap(), mode);
+        }
+        assert!(LinkMode::parse("hard-link").is_err());
+        assert!(LinkMode::default().is_default());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_link_dir_symlink_and_auto() {
+        let dir = TempDir::new().unwrap();
+        let src = source(&dir);
+        let dst = dir.path().join("skills").join("s");
+
+        let used = link_dir(&src, &dst, LinkMode::Auto).await.unwrap();
+        assert_eq!(used, LinkMode::Symlink);
+        assert!(dst.is_symlink());
+
+        // Replacing an existing link leaves its target alone
+        let used = link_dir(&src, &dst, LinkMode::Junction).await.unwrap();
+        assert_eq!(used, LinkMode::Junction);
+        assert!(dst.is_symlink());
+        assert!(src.join("SKILL.md").exists());
+    }
+
+    #[tokio::test]
+    async fn test_link_dir_copy() {
+        let dir = TempDir::new().unwrap();
+        let src = source(&dir);
+        let dst = dir.path().join("skills").join("s");
+        std::fs::create_dir_all(&dst).unwrap();
+        std::fs::write(dst.join("stale.md"), "old").unwrap();
+
+        let used = link_dir(&src, &dst, LinkMode::Copy).await.unwrap();
+        assert_eq!(used, LinkMode::Copy);
+        assert!(!dst.is_symlink());
+        assert!(!dst.join("stale.md").exists());
+        assert_eq!(
+            std::fs::read_to_string(dst.join("references").join("a.md")).unwrap(),
+            "A"
+        );
+
+        // A copy never writes through to the source
+        std::fs::write(dst.join("references").join("a.md"), "changed").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(src.join("references").join("a.md")).unwrap(),
+            "A"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_link_dir_hardlink_aliases_source() {
+        let dir = TempDir::new().unwrap();
+        let src = source(&dir);
+        let dst = dir.path().join("skills").join("s");
+
+        let used = link_dir(&src, &dst, LinkMode::Hardlink).await.unwrap();
+        assert_eq!(used, LinkMode::Hardlink);
+        assert!(!dst.is_symlink());
+        std::fs::write(dst.join("references").join("a.md"), "changed").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(src.join("references").join("a.md")).unwrap(),
+            "changed"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_copy_refuses_symlinks_in_source() {
+        let dir = TempDir::new().unwrap();
+        let src = source(&dir);
+        std::os::unix::fs::symlink("/etc/passwd", src.join("references").join("leak.md")).unwrap();
+        let dst = dir.path().join("skills").join("s");
+
+        let err = link_dir(&src, &dst, LinkMode::Copy).await.unwrap_err();
+        assert!(matches!(err, ServiceError::Validation(_)), "{}", err);
+    }
+}
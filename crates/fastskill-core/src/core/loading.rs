@@ -0,0 +1,487 @@
+//! Loading levels and token-budgeted skill loading
+//!
+//! Every token of skill text handed to an agent comes out of its context
+//! window. A skill can be loaded at one of four [`LoadingLevel`]s, from its
+//! name and description up to SKILL.md plus its `references/` files, and
+//! [`SkillLoader::load_for_budget`] packs a ranked list of skills into a token
+//! budget: every skill that fits gets its metadata first, then the most
+//! relevant skills are upgraded to the richest level the remaining budget
+//! allows. Token counts are estimates ([`estimate_tokens`]).
+
+use crate::core::chunking::{reference_files, strip_frontmatter};
+use crate::core::integrity::IntegrityGuard;
+use crate::core::service::{ServiceError, SkillId};
+use crate::core::skill_manager::{SkillDefinition, SkillManagementService};
+use crate::security::path::validate_path_within_root;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Files larger than this are never loaded; the skill stays at metadata level
+const MAX_FILE_SIZE: u64 = 512_000;
+/// SKILL.md body lines kept at [`LoadingLevel::Summary`]
+const SUMMARY_BODY_LINES: usize = 20;
+
+/// How much of a skill is loaded, from least to most
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum LoadingLevel {
+    /// Name and description
+    Metadata,
+    /// Metadata and the first lines of the SKILL.md body
+    Summary,
+    /// The whole SKILL.md
+    Full,
+    /// SKILL.md followed by the text files under `references/`
+    FullWithReferences,
+}
+
+impl std::fmt::Display for LoadingLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            LoadingLevel::Metadata => "metadata",
+            LoadingLevel::Summary => "summary",
+            LoadingLevel::Full => "full",
+            LoadingLevel::FullWithReferences => "full_with_references",
+        })
+    }
+}
+
+/// Estimated LLM tokens in `text`: one per four characters, rounded up.
+///
+/// The same rough ratio as `token_estimate` in skill metadata; close enough
+/// for English prose and Markdown without bundling a model tokenizer.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// A skill loaded at one level
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoadedSkill {
+    pub skill_id: String,
+    pub level: LoadingLevel,
+    /// Estimated tokens of `content`
+    pub tokens: usize,
+    pub content: String,
+}
+
+/// Skills packed into a token budget
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BudgetedLoad {
+    pub max_tokens: usize,
+    /// Estimated tokens of all loaded content; never above `max_tokens`
+    pub total_tokens: usize,
+    /// Loaded skills, in the order they were requested
+    pub skills: Vec<LoadedSkill>,
+    /// Requested skills left out: unknown, or too large even as metadata
+    pub omitted: Vec<String>,
+}
+
+/// One skill rendered at every level it can be loaded at, least first
+struct Renderings {
+    skill_id: String,
+    levels: Vec<(LoadingLevel, String, usize)>,
+}
+
+/// Loads skill content at a [`LoadingLevel`] or within a token budget
+pub struct SkillLoader {
+    skill_manager: Arc<dyn SkillManagementService>,
+    skills_root: PathBuf,
+    integrity: Option<Arc<IntegrityGuard>>,
+}
+
+impl SkillLoader {
+    pub fn new(skill_manager: Arc<dyn SkillManagementService>, skills_root: PathBuf) -> Self {
+        Self {
+            skill_manager,
+            skills_root,
+            integrity: None,
+        }
+    }
+
+    /// Verify each SKILL.md against `guard` before any of it is loaded.
+    pub fn with_integrity_guard(mut self, guard: Arc<IntegrityGuard>) -> Self {
+        self.integrity = Some(guard);
+        self
+    }
+
+    /// Load `skill_id` at `level`, or at the richest level below it that is
+    /// available (a skill without references has no
+    /// [`LoadingLevel::FullWithReferences`]; an unreadable SKILL.md leaves
+    /// only metadata).
+    pub async fn load(
+        &self,
+        skill_id: &SkillId,
+        level: LoadingLevel,
+    ) -> Result<LoadedSkill, ServiceError> {
+        let def = self
+            .skill_manager
+            .get_skill(skill_id)
+            .await?
+            .ok_or_else(|| ServiceError::SkillNotFound(skill_id.to_string()))?;
+        let renderings = self.render(&def).await?;
+        let (level, content, tokens) = renderings
+            .levels
+            .into_iter()
+            .take_while(|(l, _, _)| *l <= level)
+            .last()
+            .ok_or_else(|| ServiceError::Custom(format!("Nothing to load for '{}'", skill_id)))?;
+        Ok(LoadedSkill {
+            skill_id: renderings.skill_id,
+            level,
+            tokens,
+            content,
+        })
+    }
+
+    /// Pack `skill_ids`, most relevant first, into `max_tokens`.
+    ///
+    /// Every skill whose metadata still fits is included at
+    /// [`LoadingLevel::Metadata`]; then, in the same order, each is raised to
+    /// the richest level the remaining budget covers. A highly ranked skill
+    /// is therefore loaded in full before a lower one gets its summary, but
+    /// never at the cost of dropping the lower one altogether.
+    pub async fn load_for_budget(
+        &self,
+        skill_ids: &[SkillId],
+        max_tokens: usize,
+    ) -> Result<BudgetedLoad, ServiceError> {
+        let mut load = BudgetedLoad {
+            max_tokens,
+            ..Default::default()
+        };
+        let mut seen = HashSet::new();
+        let mut candidates = Vec::new();
+        for skill_id in skill_ids {
+            if !seen.insert(skill_id.as_str()) {
+                continue;
+            }
+            match self.skill_manager.get_skill(skill_id).await? {
+                Some(def) => candidates.push(self.render(&def).await?),
+                None => load.omitted.push(skill_id.to_string()),
+            }
+        }
+
+        let mut remaining = max_tokens;
+        let mut chosen: Vec<(Renderings, usize)> = Vec::new();
+        for renderings in candidates {
+            match renderings.levels.first() {
+                Some((_, _, tokens)) if *tokens <= remaining => {
+                    remaining -= tokens;
+                    chosen.push((renderings, 0));
+                }
+                _ => load.omitted.push(renderings.skill_id),
+            }
+        }
+        for (renderings, current) in &mut chosen {
+            let spent = renderings.levels[*current].2;
+            if let Some(richer) = (*current + 1..renderings.levels.len())
+                .rev()
+                .find(|&i| renderings.levels[i].2 <= remaining + spent)
+            {
+                remaining = remaining + spent - renderings.levels[richer].2;
+                *current = richer;
+            }
+        }
+
+        for (renderings, current) in chosen {
+            let skill_id = renderings.skill_id;
+            let Some((level, content, tokens)) = renderings.levels.into_iter().nth(current) else {
+                continue;
+            };
+            load.total_tokens += tokens;
+            load.skills.push(LoadedSkill {
+                skill_id,
+                level,
+                tokens,
+                content,
+            });
+        }
+        Ok(load)
+    }
+
+    async fn render(&self, def: &SkillDefinition) -> Result<Renderings, ServiceError> {
+        let metadata = format!("# {}\n\n{}", def.name, def.description.trim());
+        let mut levels = vec![(LoadingLevel::Metadata, metadata.clone())];
+
+        if let Some(skill_md) = self.read_text(&def.skill_file).await {
+            if let Some(guard) = &self.integrity {
+                guard.verify(def.id.as_str(), skill_md.as_bytes())?;
+            }
+            let body: Vec<&str> = strip_frontmatter(&skill_md)
+                .trim_start()
+                .lines()
+                .take(SUMMARY_BODY_LINES)
+                .collect();
+            if !body.is_empty() {
+                levels.push((
+                    LoadingLevel::Summary,
+                    format!("{}\n\n{}", metadata, body.join("\n").trim_end()),
+                ));
+            }
+
+            let mut with_references = skill_md.clone();
+            let mut has_references = false;
+            if let Some(skill_dir) = def.skill_file.parent() {
+                for file in reference_files(skill_dir) {
+                    let Some(text) = self.read_text(&file).await else {
+                        continue;
+                    };
+                    let label = file.strip_prefix(skill_dir).unwrap_or(&file).display();
+                    with_references.push_str(&format!("\n\n## {}\n\n{}", label, text.trim_end()));
+                    has_references = true;
+                }
+            }
+            levels.push((LoadingLevel::Full, skill_md));
+            if has_references {
+                levels.push((LoadingLevel::FullWithReferences, with_references));
+            }
+        }
+
+        Ok(Renderings {
+            skill_id: def.id.to_string(),
+            levels: levels
+                .into_iter()
+                .map(|(level, content)| {
+                    let tokens = estimate_tokens(&content);
+                    (level, content, tokens)
+                })
+                .collect(),
+        })
+    }
+
+    /// UTF-8 text of a file under the skills root; `None` (with a warning)
+    /// when it escapes the root, is too large or cannot be read.
+    async fn read_text(&self, path: &std::path::Path) -> Option<String> {
+        if let Err(e) = validate_path_within_root(path, &self.skills_root) {
+            tracing::warn!("Not loading '{}': {}", path.display(), e);
+            return None;
+        }
+        match tokio::fs::metadata(path).await {
+            Ok(meta) if meta.len() > MAX_FILE_SIZE => {
+                tracing::warn!(
+                    "Not loading '{}': larger than {} bytes",
+                    path.display(),
+                    MAX_FILE_SIZE
+                );
+                return None;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::warn!("Cannot read '{}': {}", path.display(), e);
+                return None;
+            }
+        }
+        match tokio::fs::read_to_string(path).await {
+            Ok(text) => Some(text),
+            Err(e) => {
+                tracing::warn!("Cannot read '{}': {}", path.display(), e);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::origin::Origin;
+    use crate::core::skill_manager::SkillManager;
+    use tempfile::TempDir;
+
+    /// Skill id, SKILL.md body and `references/` files (name, text)
+    type SkillFixture<'a> = (&'a str, &'a str, &'a [(&'a str, &'a str)]);
+
+    async fn loader_with_skills(dir: &TempDir, skills: &[SkillFixture<'_>]) -> SkillLoader {
+        let manager = Arc::new(SkillManager::new());
+        for (id, body, references) in skills {
+            let skill_dir = dir.path().join(id);
+            std::fs::create_dir_all(skill_dir.join("references")).unwrap();
+            let skill_file = skill_dir.join("SKILL.md");
+            std::fs::write(
+                &skill_file,
+                format!("---\nname: {id}\ndescription: {id} tools\n---\n{body}"),
+            )
+            .unwrap();
+            for (name, text) in *references {
+                std::fs::write(skill_dir.join("references").join(name), text).unwrap();
+            }
+            let mut def = SkillDefinition::new(
+                SkillId::new(id.to_string()).unwrap(),
+                id.to_string(),
+                format!("{id} tools"),
+                "1.0.0".to_string(),
+                Origin::Local {
+                    path: skill_dir.clone(),
+                    editable: false,
+                },
+            );
+            def.skill_file = skill_file;
+            manager.register_skill(def).await.unwrap();
+        }
+        SkillLoader::new(manager, dir.path().to_path_buf())
+    }
+
+    fn ids(ids: &[&str]) -> Vec<SkillId> {
+        ids.iter()
+            .map(|id| SkillId::new(id.to_string()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+        assert_eq!(
+            serde_json::to_string(&LoadingLevel::FullWithReferences).unwrap(),
+            "\"full_with_references\""
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_levels() {
+        let dir = TempDir::new().unwrap();
+        let body = (1..=40).map(|i| format!("line {i}\n")).collect::<String>();
+        let loader = loader_with_skills(
+            &dir,
+            &[
+                ("pdf", &body, &[("forms.md", "# Forms\nFill them in")]),
+                ("csv", "Split on commas", &[]),
+            ],
+        )
+        .await;
+        let load = |id: &str, level| {
+            let id = SkillId::new(id.to_string()).unwrap();
+            let loader = &loader;
+            async move { loader.load(&id, level).await.unwrap() }
+        };
+
+        let meta = load("pdf", LoadingLevel::Metadata).await;
+        assert_eq!(meta.content, "# pdf\n\npdf tools");
+        let summary = load("pdf", LoadingLevel::Summary).await;
+        assert!(summary.content.contains("line 20"));
+        assert!(!summary.content.contains("line 21"));
+        let full = load("pdf", LoadingLevel::FullWithReferences).await;
+        assert_eq!(full.level, LoadingLevel::FullWithReferences);
+        assert!(full.content.contains("line 40"));
+        assert!(full.content.contains("## references/forms.md"));
+        assert_eq!(full.tokens, estimate_tokens(&full.content));
+
+        // No references: the richest level available is Full
+        let csv = load("csv", LoadingLevel::FullWithReferences).await;
+        assert_eq!(csv.level, LoadingLevel::Full);
+
+        let missing = loader
+            .load(
+                &SkillId::new("nope".to_string()).unwrap(),
+                LoadingLevel::Full,
+            )
+            .await;
+        assert!(matches!(missing, Err(ServiceError::SkillNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_load_for_budget_packs_by_relevance() {
+        let dir = TempDir::new().unwrap();
+        let long = "Use this skill carefully. ".repeat(40);
+        let loader = loader_with_skills(
+            &dir,
+            &[
+                ("pdf", &long, &[("forms.md", &long)]),
+                ("csv", &long, &[]),
+                ("xml", &long, &[]),
+            ],
+        )
+        .await;
+
+        // Room for everything
+        let all = loader
+            .load_for_budget(&ids(&["pdf", "csv", "xml"]), 100_000)
+            .await
+            .unwrap();
+        let levels: Vec<_> = all.skills.iter().map(|s| s.level).collect();
+        assert_eq!(
+            levels,
+            [
+                LoadingLevel::FullWithReferences,
+                LoadingLevel::Full,
+                LoadingLevel::Full
+            ]
+        );
+
+        // Room for one full skill plus the others' metadata: the top-ranked
+        // skill gets the budget, the rest stay listed
+        let pdf_full = loader
+            .load(&ids(&["pdf"])[0], LoadingLevel::Full)
+            .await
+            .unwrap()
+            .tokens;
+        let tight = loader
+            .load_for_budget(&ids(&["pdf", "csv", "xml", "pdf", "nope"]), pdf_full + 20)
+            .await
+            .unwrap();
+        let levels: Vec<_> = tight
+            .skills
+            .iter()
+            .map(|s| (s.skill_id.as_str(), s.level))
+            .collect();
+        assert_eq!(
+            levels,
+            [
+                ("pdf", LoadingLevel::Full),
+                ("csv", LoadingLevel::Metadata),
+                ("xml", LoadingLevel::Metadata)
+            ]
+        );
+        assert!(tight.total_tokens <= tight.max_tokens);
+        assert_eq!(
+            tight.total_tokens,
+            tight.skills.iter().map(|s| s.tokens).sum::<usize>()
+        );
+        assert_eq!(tight.omitted, ["nope"]);
+
+        // Too small for any metadata
+        let none = loader
+            .load_for_budget(&ids(&["pdf", "csv"]), 2)
+            .await
+            .unwrap();
+        assert!(none.skills.is_empty());
+        assert_eq!(none.omitted, ["pdf", "csv"]);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_references_outside_root_are_not_loaded() {
+        let dir = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        std::fs::write(outside.path().join("secret.md"), "top secret").unwrap();
+        let loader = loader_with_skills(&dir, &[("pdf", "Body", &[])]).await;
+        std::os::unix::fs::symlink(
+            outside.path().join("secret.md"),
+            dir.path().join("pdf").join("references").join("leak.md"),
+        )
+        .unwrap();
+
+        let loaded = loader
+            .load(&ids(&["pdf"])[0], LoadingLevel::FullWithReferences)
+            .await
+            .unwrap();
+        assert_eq!(loaded.level, LoadingLevel::Full);
+        assert!(!loaded.content.contains("top secret"));
+    }
+}
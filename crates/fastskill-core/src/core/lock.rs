@@ -4,10 +4,12 @@
 //! - `ProjectSkillsLock`: deterministic, timestamp-free, for `skills.lock` at project root
 //! - `GlobalSkillsLock`: operational, with timestamps, for `global-skills.lock` in user config dir
 
+use crate::core::manifest::SkillEntry;
 use crate::core::origin::{Origin, Resolved};
 use crate::core::skill_manager::SkillDefinition;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
 
 /// The lock format version. Bumped to "3.0" for the `Origin`/`Resolved` reshape
@@ -38,6 +40,9 @@ pub struct ProjectLockedSkillEntry {
     pub dependencies: Vec<String>,
     #[serde(default)]
     pub groups: Vec<String>,
+    /// Extras (optional dependency groups) selected for this skill
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extras: Vec<String>,
     /// Depth in the dependency tree (0 = direct dependency)
     #[serde(default)]
     pub depth: u32,
@@ -70,6 +75,7 @@ impl ProjectSkillsLock {
             return Err(LockError::NotFound(path.to_path_buf()));
         }
         let safe_path = path.canonicalize().map_err(LockError::Io)?;
+        remove_interrupted_saves(&safe_path);
         let content = std::fs::read_to_string(&safe_path).map_err(LockError::Io)?;
         check_lock_format_version(&content)?;
         let lock: ProjectSkillsLock =
@@ -78,15 +84,28 @@ impl ProjectSkillsLock {
     }
 
     pub fn save_to_file(&self, path: &Path) -> Result<(), LockError> {
-        let mut lock = self.clone();
-        lock.sort_entries();
-        lock.metadata.fastskill_version = Some(env!("CARGO_PKG_VERSION").to_string());
-        let content =
-            toml::to_string_pretty(&lock).map_err(|e| LockError::Serialize(e.to_string()))?;
+        let content = self.to_file_string()?;
         crate::utils::atomic_write(path, content.as_bytes()).map_err(LockError::Io)?;
         Ok(())
     }
 
+    /// The exact content `save_to_file` writes: canonical form stamped with
+    /// this release's `fastskill_version`.
+    pub fn to_file_string(&self) -> Result<String, LockError> {
+        let mut lock = self.clone();
+        lock.metadata.fastskill_version = Some(env!("CARGO_PKG_VERSION").to_string());
+        lock.to_canonical_string()
+    }
+
+    /// Serialize with entries sorted by id, exactly as `save_to_file` would
+    /// write it apart from the `fastskill_version` stamp. Two locks with the
+    /// same pins always produce the same string.
+    pub fn to_canonical_string(&self) -> Result<String, LockError> {
+        let mut lock = self.clone();
+        lock.sort_entries();
+        toml::to_string_pretty(&lock).map_err(|e| LockError::Serialize(e.to_string()))
+    }
+
     pub fn from_installed_skills(skills: &[SkillDefinition]) -> Self {
         let mut lock = Self::new_empty();
         for skill in skills {
@@ -117,6 +136,7 @@ impl ProjectSkillsLock {
             },
             dependencies: skill.dependencies.clone().unwrap_or_default(),
             groups: Vec::new(),
+            extras: Vec::new(),
             depth,
             parent_skill,
         };
@@ -181,6 +201,268 @@ impl ProjectSkillsLock {
     }
 }
 
+// ── Lock verification ────────────────────────────────────────────────────────
+
+/// One way `skills.lock` disagrees with `skill-project.toml` or with what is
+/// installed. Produced by [`verify_lock`]; an empty report means the lock is
+/// in sync.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LockDrift {
+    /// Declared in the manifest but has no lock entry.
+    Unlocked { skill_id: String },
+    /// Locked, but neither declared nor pulled in by a locked skill.
+    Undeclared { skill_id: String },
+    /// The manifest points the skill at a different source than the lock.
+    OriginChanged { skill_id: String },
+    /// The locked version no longer satisfies the manifest's constraint.
+    ConstraintViolated {
+        skill_id: String,
+        constraint: String,
+        locked: String,
+    },
+    /// Locked but not installed.
+    NotInstalled { skill_id: String },
+    /// Installed at a different version or commit than the lock pins.
+    InstalledMismatch {
+        skill_id: String,
+        locked: String,
+        installed: String,
+    },
+    /// Installed but has no lock entry.
+    InstalledUnlocked { skill_id: String },
+}
+
+impl LockDrift {
+    pub fn skill_id(&self) -> &str {
+        match self {
+            LockDrift::Unlocked { skill_id }
+            | LockDrift::Undeclared { skill_id }
+            | LockDrift::OriginChanged { skill_id }
+            | LockDrift::ConstraintViolated { skill_id, .. }
+            | LockDrift::NotInstalled { skill_id }
+            | LockDrift::InstalledMismatch { skill_id, .. }
+            | LockDrift::InstalledUnlocked { skill_id } => skill_id,
+        }
+    }
+}
+
+impl std::fmt::Display for LockDrift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LockDrift::Unlocked { skill_id } => {
+                write!(
+                    f,
+                    "{}: declared in skill-project.toml but not locked",
+                    skill_id
+                )
+            }
+            LockDrift::Undeclared { skill_id } => {
+                write!(f, "{}: locked but no longer declared", skill_id)
+            }
+            LockDrift::OriginChanged { skill_id } => {
+                write!(
+                    f,
+                    "{}: source in skill-project.toml differs from the lock",
+                    skill_id
+                )
+            }
+            LockDrift::ConstraintViolated {
+                skill_id,
+                constraint,
+                locked,
+            } => write!(
+                f,
+                "{}: locked version {} does not satisfy {}",
+                skill_id, locked, constraint
+            ),
+            LockDrift::NotInstalled { skill_id } => {
+                write!(f, "{}: locked but not installed", skill_id)
+            }
+            LockDrift::InstalledMismatch {
+                skill_id,
+                locked,
+                installed,
+            } => write!(
+                f,
+                "{}: installed {} but lock pins {}",
+                skill_id, installed, locked
+            ),
+            LockDrift::InstalledUnlocked { skill_id } => {
+                write!(f, "{}: installed but not locked", skill_id)
+            }
+        }
+    }
+}
+
+/// Lock entries still reachable from the manifest: direct entries that are
+/// declared, plus transitive entries whose parent is itself reachable.
+fn reachable_entries(lock: &ProjectSkillsLock, declared: &[SkillEntry]) -> BTreeSet<String> {
+    let mut kept: BTreeSet<String> = lock
+        .skills
+        .iter()
+        .filter(|e| e.depth == 0 && declared.iter().any(|d| d.id == e.id))
+        .map(|e| e.id.clone())
+        .collect();
+    loop {
+        let before = kept.len();
+        for entry in &lock.skills {
+            if entry.depth > 0
+                && entry
+                    .parent_skill
+                    .as_deref()
+                    .is_some_and(|parent| kept.contains(parent))
+            {
+                kept.insert(entry.id.clone());
+            }
+        }
+        if kept.len() == before {
+            return kept;
+        }
+    }
+}
+
+fn pin_label(resolved: &Resolved) -> String {
+    match &resolved.commit_hash {
+        Some(commit) => format!("{} ({})", resolved.version, commit),
+        None => resolved.version.clone(),
+    }
+}
+
+/// Compare the lock against the manifest's declared skills and the installed
+/// skills. The report is sorted by skill id so CI output is stable.
+pub fn verify_lock(
+    lock: &ProjectSkillsLock,
+    declared: &[SkillEntry],
+    installed: &[SkillDefinition],
+) -> Vec<LockDrift> {
+    let mut drift = Vec::new();
+    let locked: HashMap<&str, &ProjectLockedSkillEntry> =
+        lock.skills.iter().map(|e| (e.id.as_str(), e)).collect();
+    let reachable = reachable_entries(lock, declared);
+
+    for entry in declared {
+        let Some(locked_entry) = locked.get(entry.id.as_str()) else {
+            drift.push(LockDrift::Unlocked {
+                skill_id: entry.id.clone(),
+            });
+            continue;
+        };
+        if !entry.origin.is_same_source(&locked_entry.origin) {
+            drift.push(LockDrift::OriginChanged {
+                skill_id: entry.id.clone(),
+            });
+        } else if let Origin::Repository {
+            version: Some(constraint),
+            ..
+        } = &entry.origin
+        {
+            if !matches!(
+                constraint.satisfies(&locked_entry.resolved.version),
+                Ok(true)
+            ) {
+                drift.push(LockDrift::ConstraintViolated {
+                    skill_id: entry.id.clone(),
+                    constraint: constraint.to_string(),
+                    locked: locked_entry.resolved.version.clone(),
+                });
+            }
+        }
+    }
+
+    for entry in &lock.skills {
+        if !reachable.contains(&entry.id) {
+            drift.push(LockDrift::Undeclared {
+                skill_id: entry.id.clone(),
+            });
+        }
+        match installed.iter().find(|s| s.id.as_str() == entry.id) {
+            None => drift.push(LockDrift::NotInstalled {
+                skill_id: entry.id.clone(),
+            }),
+            Some(skill) => {
+                let commit_differs = matches!(
+                    (&entry.resolved.commit_hash, &skill.commit_hash),
+                    (Some(a), Some(b)) if a != b
+                );
+                if skill.version != entry.resolved.version || commit_differs {
+                    let installed_pin = Resolved {
+                        version: skill.version.clone(),
+                        commit_hash: skill.commit_hash.clone(),
+                        checksum: None,
+                    };
+                    drift.push(LockDrift::InstalledMismatch {
+                        skill_id: entry.id.clone(),
+                        locked: pin_label(&entry.resolved),
+                        installed: pin_label(&installed_pin),
+                    });
+                }
+            }
+        }
+    }
+
+    for skill in installed {
+        if !locked.contains_key(skill.id.as_str()) {
+            drift.push(LockDrift::InstalledUnlocked {
+                skill_id: skill.id.to_string(),
+            });
+        }
+    }
+
+    drift.sort_by(|a, b| a.skill_id().cmp(b.skill_id()));
+    drift
+}
+
+/// Bring the lock back in line with the manifest without touching the network.
+///
+/// Undeclared entries are dropped, declared skills that are installed but
+/// unlocked are added, and direct entries pick up the manifest's origin,
+/// groups and extras. Existing pins are kept unless `repin` is set, in which
+/// case every entry is re-pinned to the installed version and commit.
+/// Declared skills that are not installed stay unlocked — there is nothing to
+/// pin them to until they are installed.
+pub fn relock(
+    lock: &ProjectSkillsLock,
+    declared: &[SkillEntry],
+    installed: &[SkillDefinition],
+    repin: bool,
+) -> ProjectSkillsLock {
+    let reachable = reachable_entries(lock, declared);
+    let mut next = lock.clone();
+    next.skills.retain(|e| reachable.contains(&e.id));
+
+    for entry in declared {
+        let skill = installed.iter().find(|s| s.id.as_str() == entry.id);
+        let existing = next.skills.iter().position(|e| e.id == entry.id);
+        let needs_pin = match existing {
+            None => true,
+            Some(i) => !entry.origin.is_same_source(&next.skills[i].origin),
+        };
+        if needs_pin {
+            if let Some(skill) = skill {
+                next.update_skill_with_depth(skill, 0, None);
+            }
+        }
+        if let Some(locked_entry) = next.skills.iter_mut().find(|e| e.id == entry.id) {
+            locked_entry.origin = entry.origin.clone();
+            locked_entry.groups = entry.groups.clone();
+            locked_entry.extras = entry.extras.clone();
+        }
+    }
+
+    if repin {
+        for locked_entry in &mut next.skills {
+            if let Some(skill) = installed.iter().find(|s| s.id.as_str() == locked_entry.id) {
+                locked_entry.resolved.version = skill.version.clone();
+                locked_entry.resolved.commit_hash = skill.commit_hash.clone();
+            }
+        }
+    }
+
+    next.sort_entries();
+    next
+}
+
 // ── Global Lock ───────────────────────────────────────────────────────────────
 
 /// Metadata for the global user-scoped lock file.
@@ -239,6 +521,7 @@ impl GlobalSkillsLock {
             return Err(LockError::NotFound(path.to_path_buf()));
         }
         let safe_path = path.canonicalize().map_err(LockError::Io)?;
+        remove_interrupted_saves(&safe_path);
         let content = std::fs::read_to_string(&safe_path).map_err(LockError::Io)?;
         check_lock_format_version(&content)?;
         let lock: GlobalSkillsLock =
@@ -340,6 +623,28 @@ pub enum LockError {
     UnsupportedVersion { found: String },
 }
 
+/// Remove temp files left beside a lockfile by saves that were killed before
+/// their rename. Saves go through `atomic_write`, so the lockfile itself is
+/// always intact; cleanup failures are logged and never block the load.
+fn remove_interrupted_saves(lock_path: &Path) {
+    let Some(dir) = lock_path.parent() else {
+        return;
+    };
+    match crate::utils::remove_orphaned_temp_files(dir, crate::utils::ORPHANED_TEMP_MIN_AGE) {
+        Ok(0) => {}
+        Ok(n) => tracing::info!(
+            "Removed {} temp file(s) left by interrupted lockfile saves in {}",
+            n,
+            dir.display()
+        ),
+        Err(e) => tracing::warn!(
+            "Failed to clean up interrupted lockfile saves in {}: {}",
+            dir.display(),
+            e
+        ),
+    }
+}
+
 /// Lightweight pre-check: read only `metadata.version` out of the raw TOML text,
 /// before attempting to deserialize the full lock structure. A pre-Origin lock
 /// file's `[[skills]]` entries won't match the current shape at all (e.g. a
@@ -419,6 +724,9 @@ mod tests {
             },
             commit_hash: Some("abc123".to_string()),
             fetched_at: Some(Utc::now()),
+            access: Default::default(),
+            injection: Default::default(),
+            env: Default::default(),
         }
     }
 
@@ -649,4 +957,122 @@ depth = 0
         let reloaded = ProjectSkillsLock::load_from_file(&lock_path).unwrap();
         assert_eq!(reloaded.skills.len(), 1);
     }
+
+    fn declared(id: &str) -> SkillEntry {
+        SkillEntry {
+            id: id.to_string(),
+            origin: make_skill(id).origin,
+            groups: Vec::new(),
+            extras: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_verify_lock_in_sync_reports_nothing() {
+        let skill = make_skill("alpha");
+        let lock = ProjectSkillsLock::from_installed_skills(std::slice::from_ref(&skill));
+        assert!(verify_lock(&lock, &[declared("alpha")], &[skill]).is_empty());
+    }
+
+    #[test]
+    fn test_verify_lock_reports_each_kind_of_drift() {
+        let mut lock = ProjectSkillsLock::new_empty();
+        lock.update_skill(&make_skill("stale"));
+        lock.update_skill(&make_skill("moved"));
+        lock.update_skill(&make_skill("bumped"));
+
+        let mut moved = declared("moved");
+        moved.origin = Origin::ZipUrl {
+            url: "https://example.com/moved.zip".to_string(),
+        };
+        let mut bumped = make_skill("bumped");
+        bumped.version = "2.0.0".to_string();
+        let extra = make_skill("extra");
+
+        let drift = verify_lock(
+            &lock,
+            &[declared("new"), moved, declared("bumped")],
+            &[make_skill("moved"), bumped, extra],
+        );
+        assert_eq!(
+            drift,
+            vec![
+                LockDrift::InstalledMismatch {
+                    skill_id: "bumped".to_string(),
+                    locked: "1.0.0 (abc123)".to_string(),
+                    installed: "2.0.0 (abc123)".to_string(),
+                },
+                LockDrift::InstalledUnlocked {
+                    skill_id: "extra".to_string()
+                },
+                LockDrift::OriginChanged {
+                    skill_id: "moved".to_string()
+                },
+                LockDrift::Unlocked {
+                    skill_id: "new".to_string()
+                },
+                LockDrift::Undeclared {
+                    skill_id: "stale".to_string()
+                },
+                LockDrift::NotInstalled {
+                    skill_id: "stale".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_verify_lock_keeps_transitive_entries_of_declared_parents() {
+        let mut lock = ProjectSkillsLock::new_empty();
+        lock.update_skill(&make_skill("parent"));
+        lock.update_skill_with_depth(&make_skill("child"), 1, Some("parent".to_string()));
+        let installed = [make_skill("parent"), make_skill("child")];
+
+        assert!(verify_lock(&lock, &[declared("parent")], &installed).is_empty());
+
+        let drift = verify_lock(&lock, &[], &installed);
+        let undeclared: Vec<&str> = drift
+            .iter()
+            .filter(|d| matches!(d, LockDrift::Undeclared { .. }))
+            .map(LockDrift::skill_id)
+            .collect();
+        assert_eq!(undeclared, vec!["child", "parent"]);
+    }
+
+    #[test]
+    fn test_relock_keeps_pins_unless_repin() {
+        let mut lock = ProjectSkillsLock::new_empty();
+        lock.update_skill(&make_skill("alpha"));
+        lock.update_skill(&make_skill("stale"));
+        let mut alpha = make_skill("alpha");
+        alpha.version = "1.1.0".to_string();
+        let installed = [alpha, make_skill("beta")];
+        let manifest = [declared("alpha"), declared("beta")];
+
+        let relocked = relock(&lock, &manifest, &installed, false);
+        let ids: Vec<&str> = relocked.skills.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["alpha", "beta"]);
+        assert_eq!(relocked.skills[0].resolved.version, "1.0.0");
+
+        let repinned = relock(&lock, &manifest, &installed, true);
+        assert_eq!(repinned.skills[0].resolved.version, "1.1.0");
+        assert!(verify_lock(&repinned, &manifest, &installed).is_empty());
+    }
+
+    #[test]
+    fn test_relock_is_idempotent_in_canonical_form() {
+        let skill = make_skill("alpha");
+        let lock = ProjectSkillsLock::from_installed_skills(std::slice::from_ref(&skill));
+        let manifest = [declared("alpha")];
+        let once = relock(&lock, &manifest, std::slice::from_ref(&skill), false);
+        let twice = relock(&once, &manifest, std::slice::from_ref(&skill), false);
+        assert_eq!(
+            once.to_canonical_string().unwrap(),
+            twice.to_canonical_string().unwrap()
+        );
+        assert_eq!(
+            lock.to_canonical_string().unwrap(),
+            once.to_canonical_string().unwrap()
+        );
+    }
 }
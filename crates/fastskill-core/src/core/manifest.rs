@@ -1,7 +1,9 @@
 //! Skills manifest management for declarative skill control
 
+use crate::core::link::LinkMode;
 use crate::core::origin::Origin;
-use crate::core::version::VersionConstraint;
+use crate::core::skill_naming::SkillNaming;
+use crate::core::version::{ResolutionStrategy, VersionConstraint};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -27,6 +29,9 @@ pub struct SkillEntry {
     pub origin: Origin,
     #[serde(default)]
     pub groups: Vec<String>,
+    /// Optional dependency groups of this skill to install alongside it
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extras: Vec<String>,
 }
 
 impl SkillsManifest {
@@ -125,6 +130,22 @@ pub struct SkillProjectToml {
     #[serde(default)]
     #[serde(rename = "tool")]
     pub tool: Option<ToolSection>,
+    /// Optional dependency groups (skill-level), keyed by extra name:
+    /// `[optional-dependencies.docs]`. Consumers opt in with `id[docs]`.
+    #[serde(default, rename = "optional-dependencies")]
+    pub optional_dependencies: Option<HashMap<String, DependenciesSection>>,
+    /// Peer dependencies (skill-level): skill id → version constraint the
+    /// installed peer must satisfy. Checked, never installed.
+    #[serde(default, rename = "peer-dependencies")]
+    pub peer_dependencies: Option<HashMap<String, String>>,
+    /// Engine and runtime versions the skill needs (skill-level):
+    /// `[requires]` with `fastskill`, `python` and `node` constraints.
+    #[serde(default)]
+    pub requires: Option<crate::core::requirements::Requirements>,
+    /// Environment variables the skill's scripts read (skill-level):
+    /// `[env.NAME]` with `description`, `required`, `secret` and `default`.
+    #[serde(default)]
+    pub env: Option<crate::core::skill_env::EnvDeclarations>,
 }
 
 /// Metadata section for skill or project metadata
@@ -147,6 +168,10 @@ pub struct MetadataSection {
     /// Optional: Project name (project-level only)
     #[serde(default)]
     pub name: Option<String>,
+    /// Optional: Globs left out of the skill's archive, on top of `.fsignore`
+    /// (skill-level only)
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
 }
 
 /// Dependencies section containing skill dependencies
@@ -158,7 +183,7 @@ pub struct DependenciesSection {
 }
 
 /// Dependency specification - can be a simple version string or inline table with origin details
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum DependencySpec {
     /// Simple version string: "1.0.0"
@@ -168,6 +193,9 @@ pub enum DependencySpec {
         origin: Origin,
         #[serde(default)]
         groups: Option<Vec<String>>,
+        /// Optional dependency groups of the dependency to pull in
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        extras: Option<Vec<String>>,
     },
 }
 
@@ -199,12 +227,26 @@ pub struct FastSkillToolConfig {
     /// Skip transitive dependency resolution entirely (default: false)
     #[serde(default)]
     pub skip_transitive: bool,
+    /// How `install` picks among satisfying versions: `maximum` (default),
+    /// `minimum` or `locked`
+    #[serde(default, skip_serializing_if = "ResolutionStrategy::is_default")]
+    pub resolution: ResolutionStrategy,
     /// Optional evaluation configuration
     #[serde(default)]
     pub eval: Option<EvalConfigToml>,
     /// Auto-run reindex after mutating commands when embedding is configured (default: true)
     #[serde(default = "default_auto_reindex")]
     pub auto_reindex: bool,
+    /// Install from the `vendor/` directory only, never the network (default: false)
+    #[serde(default)]
+    pub offline: bool,
+    /// How `add` names skills and handles id collisions (`[tool.fastskill.naming]`)
+    #[serde(default, skip_serializing_if = "SkillNaming::is_default")]
+    pub naming: SkillNaming,
+    /// How editable installs link skills into the skills dir: `auto`
+    /// (default), `symlink`, `junction` or `copy`
+    #[serde(default, skip_serializing_if = "LinkMode::is_default")]
+    pub link_mode: LinkMode,
 }
 
 /// Evaluation configuration in TOML format ([tool.fastskill.eval])
@@ -265,6 +307,86 @@ pub struct HttpServerConfigToml {
     /// Optional: allow list of request headers (default: ["Content-Type", "Authorization"])
     #[serde(default = "default_allowed_headers_toml")]
     pub allowed_headers: Vec<String>,
+    /// Per-client discovery profiles ([[tool.fastskill.server.clients]])
+    #[serde(default)]
+    pub clients: Vec<ClientProfileToml>,
+    /// Storage backend for `fastskill serve` ([tool.fastskill.server.storage])
+    #[serde(default)]
+    pub storage: Option<crate::core::service::StorageConfig>,
+    /// Re-embed edited skills while serving ([tool.fastskill.server.hot_reload])
+    #[serde(default)]
+    pub hot_reload: Option<HotReloadConfigToml>,
+    /// Primary/follower replication ([tool.fastskill.server.replication])
+    #[serde(default)]
+    pub replication: Option<crate::core::replication::ReplicationConfig>,
+    /// Per-token and per-IP request limits ([tool.fastskill.server.rate_limit])
+    #[serde(default)]
+    pub rate_limit: crate::core::service::RateLimitConfig,
+    /// One isolated skill set per tenant ([tool.fastskill.server.tenancy])
+    #[serde(default)]
+    pub tenancy: Option<crate::core::tenancy::TenancyConfig>,
+    /// Per-skill injection rules for `/resolve`
+    /// ([tool.fastskill.server.injection."<skill id>"])
+    #[serde(
+        default,
+        skip_serializing_if = "crate::core::skill_injection::InjectionRules::is_empty"
+    )]
+    pub injection: crate::core::skill_injection::InjectionRules,
+    /// Background jobs for `?async=true` requests ([tool.fastskill.server.jobs])
+    #[serde(default)]
+    pub jobs: crate::core::jobs::JobsConfig,
+}
+
+/// Hot reload settings for `fastskill serve` in TOML format
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HotReloadConfigToml {
+    /// Watch the skills directory (default: true)
+    #[serde(default = "default_hot_reload_enabled")]
+    pub enabled: bool,
+    /// Quiet period after the last change before re-embedding (default: 1000)
+    #[serde(default = "default_hot_reload_debounce_ms")]
+    pub debounce_ms: u64,
+    /// Re-embed early once this many skills changed in one burst (default: 100)
+    #[serde(default = "default_hot_reload_max_batch")]
+    pub max_batch: usize,
+}
+
+fn default_hot_reload_enabled() -> bool {
+    true
+}
+
+fn default_hot_reload_debounce_ms() -> u64 {
+    1000
+}
+
+fn default_hot_reload_max_batch() -> usize {
+    100
+}
+
+/// Per-client discovery profile in TOML format
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientProfileToml {
+    /// Profile name, sent by the client in the `X-FastSkill-Client` header
+    pub name: String,
+    /// Environment variable holding the client's API key; when set, the profile is
+    /// selected only by that key rather than by name
+    #[serde(default)]
+    pub api_key_env: Option<String>,
+    /// Minimum relevance score for returned skills (0.0-1.0)
+    #[serde(default)]
+    pub dynamic_min_relevance: Option<f32>,
+    /// Maximum number of skills returned per request
+    #[serde(default)]
+    pub max_dynamic_skills: Option<usize>,
+    /// Skill ids the client may discover; a trailing `*` matches a prefix
+    #[serde(default)]
+    pub allowed_scopes: Vec<String>,
+    /// Teams whose `visibility: team` skills the client may see
+    #[serde(default)]
+    pub teams: Vec<String>,
+    /// `member` (default) or `admin`, which sees every skill
+    #[serde(default)]
+    pub role: crate::core::service::ClientRole,
 }
 
 fn default_allowed_headers_toml() -> Vec<String> {
@@ -278,6 +400,18 @@ pub struct EmbeddingConfigToml {
     pub embedding_model: String,
     #[serde(default)]
     pub index_path: Option<PathBuf>,
+    /// Texts per embeddings API call (default 100)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_size: Option<usize>,
+    /// Characters per section chunk for multi-vector indexing (off when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk_size: Option<usize>,
+    /// Query embeddings cached in memory (default 1000, 0 disables)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_cache_size: Option<usize>,
+    /// Seconds a cached query embedding is reused (default 3600)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_cache_ttl: Option<u64>,
 }
 
 /// Repository definition with name, type, priority, authentication, and connection details
@@ -423,14 +557,18 @@ impl SkillProjectToml {
 
     /// Save skill-project.toml to file
     pub fn save_to_file(&self, path: &Path) -> Result<(), ManifestError> {
-        let content =
-            toml::to_string_pretty(self).map_err(|e| ManifestError::Serialize(e.to_string()))?;
+        let content = self.to_toml_string()?;
 
         crate::utils::atomic_write(path, content.as_bytes()).map_err(ManifestError::Io)?;
 
         Ok(())
     }
 
+    /// The TOML `save_to_file` writes
+    pub fn to_toml_string(&self) -> Result<String, ManifestError> {
+        toml::to_string_pretty(self).map_err(|e| ManifestError::Serialize(e.to_string()))
+    }
+
     /// Validate required sections based on context
     /// T060: Enhanced error messages with context information
     pub fn validate_for_context(&self, context: ProjectContext) -> Result<(), String> {
@@ -503,37 +641,122 @@ impl SkillProjectToml {
 
         if let Some(ref deps_section) = self.dependencies {
             for (skill_id, dep_spec) in &deps_section.dependencies {
-                let (origin, groups) = match dep_spec {
-                    DependencySpec::Version(version_str) => {
-                        // Version-only dependency: resolved against the "default" repository,
-                        // preserving today's implicit-source behavior.
-                        let constraint = VersionConstraint::parse(version_str).map_err(|e| {
-                            format!("Invalid version '{}' for {}: {}", version_str, skill_id, e)
-                        })?;
-                        (
-                            Origin::Repository {
-                                repo: "default".to_string(),
-                                skill: skill_id.clone(),
-                                version: Some(constraint),
-                            },
-                            Vec::new(),
-                        )
-                    }
-                    DependencySpec::Inline { origin, groups } => {
-                        (origin.clone(), groups.clone().unwrap_or_default())
-                    }
-                };
-
-                entries.push(SkillEntry {
-                    id: skill_id.clone(),
-                    origin,
-                    groups,
-                });
+                entries.push(dependency_to_skill_entry(skill_id, dep_spec)?);
             }
         }
 
         Ok(entries)
     }
+
+    /// Skill entries from the `[optional-dependencies.<extra>]` tables named by
+    /// `extras`. Fails on an extra this skill does not declare.
+    pub fn optional_skill_entries(&self, extras: &[String]) -> Result<Vec<SkillEntry>, String> {
+        let mut entries = Vec::new();
+        for extra in extras {
+            let section = self
+                .optional_dependencies
+                .as_ref()
+                .and_then(|groups| groups.get(extra))
+                .ok_or_else(|| {
+                    let mut known: Vec<&str> = self
+                        .optional_dependencies
+                        .iter()
+                        .flat_map(|groups| groups.keys().map(String::as_str))
+                        .collect();
+                    known.sort_unstable();
+                    format!(
+                        "Unknown extra '{}' (declared: {})",
+                        extra,
+                        if known.is_empty() {
+                            "none".to_string()
+                        } else {
+                            known.join(", ")
+                        }
+                    )
+                })?;
+            for (skill_id, dep_spec) in &section.dependencies {
+                if !entries.iter().any(|e: &SkillEntry| &e.id == skill_id) {
+                    entries.push(dependency_to_skill_entry(skill_id, dep_spec)?);
+                }
+            }
+        }
+        Ok(entries)
+    }
+}
+
+fn dependency_to_skill_entry(
+    skill_id: &str,
+    dep_spec: &DependencySpec,
+) -> Result<SkillEntry, String> {
+    let (origin, groups, extras) = match dep_spec {
+        DependencySpec::Version(version_str) => {
+            // Version-only dependency: resolved against the "default" repository,
+            // preserving today's implicit-source behavior.
+            let constraint = VersionConstraint::parse(version_str).map_err(|e| {
+                format!("Invalid version '{}' for {}: {}", version_str, skill_id, e)
+            })?;
+            (
+                Origin::Repository {
+                    repo: "default".to_string(),
+                    skill: skill_id.to_string(),
+                    version: Some(constraint),
+                },
+                Vec::new(),
+                Vec::new(),
+            )
+        }
+        DependencySpec::Inline {
+            origin,
+            groups,
+            extras,
+        } => (
+            origin.clone(),
+            groups.clone().unwrap_or_default(),
+            extras.clone().unwrap_or_default(),
+        ),
+    };
+
+    Ok(SkillEntry {
+        id: skill_id.to_string(),
+        origin,
+        groups,
+        extras,
+    })
+}
+
+/// Split a `skill-id[extra1,extra2]` requirement into the id and its extras.
+/// A version suffix may follow the brackets (`web[docs]@1.2.0`); it is kept on
+/// the returned id. Without brackets the input is returned unchanged.
+pub fn parse_skill_extras(spec: &str) -> Result<(String, Vec<String>), String> {
+    let Some(open) = spec.find('[') else {
+        return Ok((spec.to_string(), Vec::new()));
+    };
+    let close = spec[open..]
+        .find(']')
+        .map(|i| open + i)
+        .ok_or_else(|| format!("Unclosed '[' in '{}'", spec))?;
+    let id = &spec[..open];
+    let rest = &spec[close + 1..];
+    if id.is_empty() || !(rest.is_empty() || rest.starts_with('@')) {
+        return Err(format!(
+            "Invalid extras syntax '{}': expected <skill>[extra,...]",
+            spec
+        ));
+    }
+    let mut extras: Vec<String> = Vec::new();
+    for extra in spec[open + 1..close].split(',').map(str::trim) {
+        if extra.is_empty()
+            || !extra
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(format!("Invalid extra name '{}' in '{}'", extra, spec));
+        }
+        if !extras.iter().any(|e| e == extra) {
+            extras.push(extra.to_string());
+        }
+    }
+    Ok((format!("{}{}", id, rest), extras))
 }
 
 /// Canonical conversion from manifest RepositoryDefinition to the runtime type.
@@ -759,4 +982,149 @@ mod tests {
             crate::core::repository::RepositoryType::GitMarketplace
         ));
     }
+
+    #[test]
+    fn test_parse_skill_extras() {
+        assert_eq!(
+            parse_skill_extras("web").unwrap(),
+            ("web".to_string(), Vec::<String>::new())
+        );
+        assert_eq!(
+            parse_skill_extras("web[docs, examples,docs]@1.2.0").unwrap(),
+            (
+                "web@1.2.0".to_string(),
+                vec!["docs".to_string(), "examples".to_string()]
+            )
+        );
+        assert!(parse_skill_extras("web[docs").is_err());
+        assert!(parse_skill_extras("[docs]").is_err());
+        assert!(parse_skill_extras("web[]").is_err());
+        assert!(parse_skill_extras("web[docs]extra").is_err());
+    }
+
+    #[test]
+    fn test_optional_dependencies_round_trip() {
+        let project: SkillProjectToml = toml::from_str(
+            r#"
+            [metadata]
+            id = "web"
+            version = "1.0.0"
+
+            [dependencies]
+            api = { origin = { type = "local", path = "../api" }, extras = ["docs"] }
+
+            [optional-dependencies.docs]
+            web-docs = "^1.0"
+            "#,
+        )
+        .unwrap();
+
+        let entries = project.to_skill_entries().unwrap();
+        assert_eq!(entries[0].extras, vec!["docs".to_string()]);
+
+        let docs = project
+            .optional_skill_entries(&["docs".to_string()])
+            .unwrap();
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].id, "web-docs");
+        let err = project
+            .optional_skill_entries(&["examples".to_string()])
+            .unwrap_err();
+        assert!(err.contains("declared: docs"), "{err}");
+
+        let serialized = toml::to_string_pretty(&project).unwrap();
+        assert!(serialized.contains("[optional-dependencies.docs]"));
+        let reparsed: SkillProjectToml = toml::from_str(&serialized).unwrap();
+        assert!(reparsed.optional_dependencies.unwrap().contains_key("docs"));
+    }
+
+    #[test]
+    fn test_server_storage_backend_parses() {
+        use crate::core::service::{S3StorageConfig, StorageConfig};
+
+        let project: SkillProjectToml = toml::from_str(
+            r#"
+            [dependencies]
+
+            [tool.fastskill.server.storage]
+            backend = "s3"
+            bucket = "team-skills"
+            prefix = "prod"
+            endpoint = "http://minio:9000"
+            force_path_style = true
+            "#,
+        )
+        .unwrap();
+        let storage = project
+            .tool
+            .and_then(|t| t.fastskill)
+            .and_then(|f| f.server)
+            .and_then(|s| s.storage)
+            .unwrap();
+        assert_eq!(
+            storage,
+            StorageConfig::S3(S3StorageConfig {
+                bucket: "team-skills".to_string(),
+                prefix: "prod".to_string(),
+                region: None,
+                endpoint: Some("http://minio:9000".to_string()),
+                force_path_style: true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_server_hot_reload_settings_parse() {
+        let project: SkillProjectToml = toml::from_str(
+            r#"
+            [dependencies]
+
+            [tool.fastskill.server.hot_reload]
+            debounce_ms = 250
+            max_batch = 20
+            "#,
+        )
+        .unwrap();
+        let hot_reload = project
+            .tool
+            .and_then(|t| t.fastskill)
+            .and_then(|f| f.server)
+            .and_then(|s| s.hot_reload)
+            .unwrap();
+        assert_eq!(
+            hot_reload,
+            HotReloadConfigToml {
+                enabled: true,
+                debounce_ms: 250,
+                max_batch: 20,
+            }
+        );
+    }
+
+    #[test]
+    fn test_server_postgres_backend_defaults_url_env() {
+        use crate::core::service::{PostgresStorageConfig, StorageConfig};
+
+        let project: SkillProjectToml = toml::from_str(
+            r#"
+            [dependencies]
+
+            [tool.fastskill.server.storage]
+            backend = "postgres"
+            "#,
+        )
+        .unwrap();
+        let storage = project
+            .tool
+            .and_then(|t| t.fastskill)
+            .and_then(|f| f.server)
+            .and_then(|s| s.storage)
+            .unwrap();
+        assert_eq!(
+            storage,
+            StorageConfig::Postgres(PostgresStorageConfig {
+                url_env: "DATABASE_URL".to_string(),
+            })
+        );
+    }
 }
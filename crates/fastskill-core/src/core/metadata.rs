@@ -93,22 +93,7 @@ impl MetadataServiceImpl {
 
     /// Score skills based on relevance to query
     fn score_skill(&self, skill: &SkillDefinition, query: &str) -> f32 {
-        let query_lower = query.to_lowercase();
-        let mut score = 0.0;
-
-        // Exact name match gets highest score
-        if skill.name.to_lowercase() == query_lower {
-            score += 1.0;
-        } else if skill.name.to_lowercase().contains(&query_lower) {
-            score += 0.8;
-        }
-
-        // Description match
-        if skill.description.to_lowercase().contains(&query_lower) {
-            score += 0.6;
-        }
-
-        score
+        text_score(&skill.name, &skill.description, query)
     }
 
     /// Parse YAML frontmatter from SKILL.md content
@@ -117,6 +102,26 @@ impl MetadataServiceImpl {
     }
 }
 
+/// Relevance of a skill's name and description to a text query
+pub(crate) fn text_score(name: &str, description: &str, query: &str) -> f32 {
+    let query_lower = query.to_lowercase();
+    let mut score = 0.0;
+
+    // Exact name match gets highest score
+    if name.to_lowercase() == query_lower {
+        score += 1.0;
+    } else if name.to_lowercase().contains(&query_lower) {
+        score += 0.8;
+    }
+
+    // Description match
+    if description.to_lowercase().contains(&query_lower) {
+        score += 0.6;
+    }
+
+    score
+}
+
 /// Parse YAML frontmatter from SKILL.md content (standalone function)
 /// This can be used by CLI and other modules that need to parse skill frontmatter
 pub fn parse_yaml_frontmatter(content: &str) -> Result<SkillFrontmatter, ServiceError> {
@@ -0,0 +1,392 @@
+//! Process-wide service metrics in the Prometheus text format
+//!
+//! Components record into [`global`] as they work: the HTTP layer counts
+//! requests and their latency, `/resolve` counts the skills it returns for
+//! injection, the OpenAI embedding client counts calls, failures and query
+//! embedding cache hits, and filesystem storage counts metadata cache hits
+//! and misses. `GET /metrics`
+//! renders everything with [`Metrics::render`], adding gauges such as the
+//! vector index size that are read at scrape time.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+/// Upper bounds (seconds) of the request latency histogram buckets
+const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// The metrics of this process
+pub fn global() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(Metrics::default)
+}
+
+#[derive(Debug, Default, Clone)]
+struct Histogram {
+    /// Cumulative count per entry of [`LATENCY_BUCKETS`]
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+impl Histogram {
+    fn observe(&mut self, seconds: f64) {
+        for (bucket, bound) in self.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if seconds <= bound {
+                *bucket += 1;
+            }
+        }
+        self.count += 1;
+        self.sum += seconds;
+    }
+}
+
+/// `(method, route)`; the route is the matched template, never the raw path,
+/// so skill ids do not multiply the series.
+type RouteKey = (String, String);
+
+/// Counters and histograms recorded since startup
+#[derive(Debug, Default)]
+pub struct Metrics {
+    requests: Mutex<BTreeMap<(String, String, u16), u64>>,
+    latency: Mutex<BTreeMap<RouteKey, Histogram>>,
+    skill_injections: AtomicU64,
+    embedding_calls: AtomicU64,
+    embedding_failures: AtomicU64,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    query_cache_hits: AtomicU64,
+    query_cache_misses: AtomicU64,
+}
+
+/// Values read at scrape time rather than recorded as they happen
+#[derive(Debug, Clone, Default)]
+pub struct Gauges {
+    pub skills_installed: Option<usize>,
+    pub vector_index_size: Option<usize>,
+    /// `(limit, rejected)` pairs, e.g. `("ip", 3)`
+    pub rate_limited: Vec<(&'static str, u64)>,
+}
+
+impl Metrics {
+    /// Count one HTTP request to `route` and observe its latency.
+    pub fn record_request(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        let mut requests = match self.requests.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        *requests
+            .entry((method.to_string(), route.to_string(), status))
+            .or_insert(0) += 1;
+        drop(requests);
+
+        let mut latency = match self.latency.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        latency
+            .entry((method.to_string(), route.to_string()))
+            .or_default()
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Count skills handed to an agent for injection into its context.
+    pub fn record_skill_injections(&self, count: usize) {
+        self.skill_injections
+            .fetch_add(count as u64, Ordering::Relaxed);
+    }
+
+    /// Count one call to the embedding API.
+    pub fn record_embedding_call(&self, succeeded: bool) {
+        self.embedding_calls.fetch_add(1, Ordering::Relaxed);
+        if !succeeded {
+            self.embedding_failures.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Count one metadata cache lookup.
+    pub fn record_cache_lookup(&self, hit: bool) {
+        let counter = if hit {
+            &self.cache_hits
+        } else {
+            &self.cache_misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count one query embedding cache lookup.
+    pub fn record_query_cache_lookup(&self, hit: bool) {
+        let counter = if hit {
+            &self.query_cache_hits
+        } else {
+            &self.query_cache_misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn embedding_calls(&self) -> (u64, u64) {
+        (
+            self.embedding_calls.load(Ordering::Relaxed),
+            self.embedding_failures.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Everything recorded, plus `gauges`, in the Prometheus text exposition format.
+    pub fn render(&self, gauges: &Gauges) -> String {
+        let mut out = String::new();
+
+        header(
+            &mut out,
+            "fastskill_http_requests_total",
+            "counter",
+            "HTTP API requests by method, route and status",
+        );
+        let requests = match self.requests.lock() {
+            Ok(guard) => guard.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        };
+        for ((method, route, status), count) in &requests {
+            let _ = writeln!(
+                out,
+                "fastskill_http_requests_total{{method=\"{}\",route=\"{}\",status=\"{}\"}} {}",
+                escape(method),
+                escape(route),
+                status,
+                count
+            );
+        }
+
+        header(
+            &mut out,
+            "fastskill_http_request_duration_seconds",
+            "histogram",
+            "HTTP API request latency by method and route",
+        );
+        let latency = match self.latency.lock() {
+            Ok(guard) => guard.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        };
+        for ((method, route), histogram) in &latency {
+            let labels = format!("method=\"{}\",route=\"{}\"", escape(method), escape(route));
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(histogram.buckets) {
+                let _ = writeln!(
+                    out,
+                    "fastskill_http_request_duration_seconds_bucket{{{},le=\"{}\"}} {}",
+                    labels, bound, count
+                );
+            }
+            let _ = writeln!(
+                out,
+                "fastskill_http_request_duration_seconds_bucket{{{},le=\"+Inf\"}} {}",
+                labels, histogram.count
+            );
+            let _ = writeln!(
+                out,
+                "fastskill_http_request_duration_seconds_sum{{{}}} {}",
+                labels, histogram.sum
+            );
+            let _ = writeln!(
+                out,
+                "fastskill_http_request_duration_seconds_count{{{}}} {}",
+                labels, histogram.count
+            );
+        }
+
+        single(
+            &mut out,
+            "fastskill_skill_injections_total",
+            "counter",
+            "Skills returned by /resolve for injection into an agent's context",
+            self.skill_injections.load(Ordering::Relaxed),
+        );
+        let (calls, failures) = self.embedding_calls();
+        single(
+            &mut out,
+            "fastskill_embedding_requests_total",
+            "counter",
+            "Calls to the embedding API",
+            calls,
+        );
+        single(
+            &mut out,
+            "fastskill_embedding_failures_total",
+            "counter",
+            "Calls to the embedding API that failed",
+            failures,
+        );
+
+        let caches = [
+            (
+                "metadata",
+                self.cache_hits.load(Ordering::Relaxed),
+                self.cache_misses.load(Ordering::Relaxed),
+            ),
+            (
+                "embedding_query",
+                self.query_cache_hits.load(Ordering::Relaxed),
+                self.query_cache_misses.load(Ordering::Relaxed),
+            ),
+        ];
+        header(
+            &mut out,
+            "fastskill_cache_lookups_total",
+            "counter",
+            "Cache lookups by cache (metadata, embedding_query) and result",
+        );
+        for (cache, hits, misses) in caches {
+            let _ = writeln!(
+                out,
+                "fastskill_cache_lookups_total{{cache=\"{}\",result=\"hit\"}} {}",
+                cache, hits
+            );
+            let _ = writeln!(
+                out,
+                "fastskill_cache_lookups_total{{cache=\"{}\",result=\"miss\"}} {}",
+                cache, misses
+            );
+        }
+        header(
+            &mut out,
+            "fastskill_cache_hit_ratio",
+            "gauge",
+            "Share of each cache's lookups that hit since startup",
+        );
+        for (cache, hits, misses) in caches {
+            let ratio = if hits + misses == 0 {
+                0.0
+            } else {
+                hits as f64 / (hits + misses) as f64
+            };
+            let _ = writeln!(
+                out,
+                "fastskill_cache_hit_ratio{{cache=\"{}\"}} {}",
+                cache, ratio
+            );
+        }
+
+        if let Some(count) = gauges.skills_installed {
+            single(
+                &mut out,
+                "fastskill_skills",
+                "gauge",
+                "Skills installed",
+                count as u64,
+            );
+        }
+        if let Some(size) = gauges.vector_index_size {
+            single(
+                &mut out,
+                "fastskill_vector_index_skills",
+                "gauge",
+                "Skills in the vector index",
+                size as u64,
+            );
+        }
+        if !gauges.rate_limited.is_empty() {
+            header(
+                &mut out,
+                "fastskill_rate_limited_requests_total",
+                "counter",
+                "Requests rejected with 429 by each rate limit",
+            );
+            for (limit, count) in &gauges.rate_limited {
+                let _ = writeln!(
+                    out,
+                    "fastskill_rate_limited_requests_total{{limit=\"{}\"}} {}",
+                    limit, count
+                );
+            }
+        }
+        out
+    }
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+fn single(out: &mut String, name: &str, kind: &str, help: &str, value: u64) {
+    header(out, name, kind, help);
+    let _ = writeln!(out, "{} {}", name, value);
+}
+
+/// Escape a label value (backslash, quote, newline)
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_requests_and_latency_histogram() {
+        let metrics = Metrics::default();
+        metrics.record_request("GET", "/api/v1/skills/{id}", 200, Duration::from_millis(20));
+        metrics.record_request("GET", "/api/v1/skills/{id}", 200, Duration::from_secs(3));
+        metrics.record_request("POST", "/api/v1/search", 429, Duration::from_millis(1));
+
+        let text = metrics.render(&Gauges::default());
+        assert!(text.contains(
+            "fastskill_http_requests_total{method=\"GET\",route=\"/api/v1/skills/{id}\",status=\"200\"} 2"
+        ));
+        assert!(text.contains(
+            "fastskill_http_requests_total{method=\"POST\",route=\"/api/v1/search\",status=\"429\"} 1"
+        ));
+        let labels = "method=\"GET\",route=\"/api/v1/skills/{id}\"";
+        assert!(text.contains(&format!(
+            "fastskill_http_request_duration_seconds_bucket{{{labels},le=\"0.025\"}} 1"
+        )));
+        assert!(text.contains(&format!(
+            "fastskill_http_request_duration_seconds_bucket{{{labels},le=\"5\"}} 2"
+        )));
+        assert!(text.contains(&format!(
+            "fastskill_http_request_duration_seconds_count{{{labels}}} 2"
+        )));
+    }
+
+    #[test]
+    fn test_render_counters_and_gauges() {
+        let metrics = Metrics::default();
+        metrics.record_skill_injections(3);
+        metrics.record_embedding_call(true);
+        metrics.record_embedding_call(false);
+        metrics.record_cache_lookup(true);
+        metrics.record_cache_lookup(true);
+        metrics.record_cache_lookup(true);
+        metrics.record_cache_lookup(false);
+        metrics.record_query_cache_lookup(true);
+        metrics.record_query_cache_lookup(false);
+
+        let text = metrics.render(&Gauges {
+            skills_installed: Some(12),
+            vector_index_size: Some(10),
+            rate_limited: vec![("token", 0), ("ip", 4)],
+        });
+        assert!(text.contains("fastskill_skill_injections_total 3\n"));
+        assert!(text.contains("fastskill_embedding_requests_total 2\n"));
+        assert!(text.contains("fastskill_embedding_failures_total 1\n"));
+        assert!(text.contains("fastskill_cache_hit_ratio{cache=\"metadata\"} 0.75\n"));
+        assert!(text.contains(
+            "fastskill_cache_lookups_total{cache=\"embedding_query\",result=\"miss\"} 1\n"
+        ));
+        assert!(text.contains("fastskill_cache_hit_ratio{cache=\"embedding_query\"} 0.5\n"));
+        assert!(text.contains("fastskill_skills 12\n"));
+        assert!(text.contains("fastskill_vector_index_skills 10\n"));
+        assert!(text.contains("fastskill_rate_limited_requests_total{limit=\"ip\"} 4\n"));
+        assert!(text.contains("# TYPE fastskill_http_request_duration_seconds histogram\n"));
+    }
+
+    #[test]
+    fn test_escape_label_values() {
+        assert_eq!(escape("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+}
@@ -1,49 +1,103 @@
 //! Core service layer modules
 
+pub mod access;
 pub mod analysis;
+pub mod api_keys;
+pub mod audit;
 pub mod build_cache;
+pub mod cancel;
 pub mod change_detection;
+pub mod change_journal;
+#[cfg(feature = "chaos")]
+pub mod chaos;
+pub mod chunking;
+pub mod content;
 pub mod context_resolver;
+pub mod credentials;
+pub mod delta;
 pub mod dependencies;
 pub mod dependency_resolver;
+pub mod download;
 pub mod embedding;
 pub mod frontmatter;
+pub mod gc;
+pub mod hnsw;
 pub mod install;
+pub mod integrity;
+pub mod jobs;
+pub mod link;
+pub mod loading;
 pub mod lock;
 pub mod manifest;
 pub mod metadata;
+pub mod metrics;
 pub mod origin;
 pub mod origin_infer;
+pub mod pack;
+pub mod packaging;
+pub mod peers;
+pub mod process_lock;
+pub mod profiles;
 pub mod project;
 pub mod project_config;
+pub mod project_transaction;
 pub mod reconciliation;
 pub mod registry;
 pub mod registry_index;
+pub mod registry_store;
 pub mod reindex;
+pub mod replication;
+pub mod report;
 pub mod repository;
+pub mod requirements;
 pub mod resolver;
+pub mod retirement;
 pub mod routing;
+pub mod scope;
 pub mod service;
+pub mod skill_env;
+pub mod skill_injection;
 pub mod skill_manager;
+pub mod skill_naming;
+pub mod skill_tests;
 pub mod sources;
+pub mod tenancy;
+pub mod tools;
 pub mod update;
 pub mod validation;
 pub mod vector_index;
+pub mod vendor;
 pub mod version;
 pub mod version_bump;
 
 // Re-export main types for convenience
 // Note: Selective re-exports to avoid conflicts
+// access
+pub use access::{SkillAccess, Visibility};
+
+// audit
+pub use audit::{
+    AuditChainProblem, AuditDetails, AuditEntry, AuditFilter, AuditLog, AuditVerification,
+};
+
 pub use build_cache::{BuildCache, SkillCacheEntry};
 pub use change_detection::{
     calculate_skill_hash, detect_changed_skills_git, detect_changed_skills_hash,
 };
+// credentials
+pub use credentials::{
+    default_store, resolve_secret, CredentialStore, EncryptedFileStore, KeyringStore,
+};
+
 // dependencies
 pub use dependencies::{Dependency, DependencyError, DependencyGraph};
 pub use dependency_resolver::{DependencyResolutionError, DependencyResolver, SkillInstallItem};
 
 // embedding
-pub use embedding::{EmbeddingService, OpenAIEmbeddingService};
+pub use embedding::{EmbeddingService, OpenAIEmbeddingService, QueryEmbeddingCache};
+
+// loading
+pub use loading::{estimate_tokens, BudgetedLoad, LoadedSkill, LoadingLevel, SkillLoader};
 
 // lock
 pub use lock::{
@@ -54,10 +108,11 @@ pub use lock::{
 
 // manifest
 pub use manifest::{
-    AuthConfig, AuthType, DependenciesSection, DependencySpec, EmbeddingConfigToml, EvalConfigToml,
-    FastSkillToolConfig, FileResolutionResult, HttpServerConfigToml, ManifestError,
-    ManifestMetadata, MetadataSection, ProjectContext, RepositoryConnection, RepositoryDefinition,
-    RepositoryType, SkillEntry, SkillProjectToml, SkillsManifest, ToolSection,
+    AuthConfig, AuthType, ClientProfileToml, DependenciesSection, DependencySpec,
+    EmbeddingConfigToml, EvalConfigToml, FastSkillToolConfig, FileResolutionResult,
+    HttpServerConfigToml, ManifestError, ManifestMetadata, MetadataSection, ProjectContext,
+    RepositoryConnection, RepositoryDefinition, RepositoryType, SkillEntry, SkillProjectToml,
+    SkillsManifest, ToolSection,
 };
 
 // metadata
@@ -71,11 +126,29 @@ pub use origin::{GitRef, Origin, Resolved};
 // origin_infer (the Origin-ref inference seam, spec 003 Phase 3)
 pub use origin_infer::{is_skill_id, parse_git_url, parse_skill_id_ref, GitUrlInfo};
 
+// peers
+pub use peers::{check_peer_dependencies, PeerViolation};
+
+// process_lock
+pub use process_lock::ProcessLock;
+
+// profiles
+pub use profiles::{EnablementOutcome, ProfileApplication, SkillProfile, SkillProfiles};
+
+// jobs
+pub use jobs::{Job, JobHandle, JobKind, JobQueue, JobStatus, JobStore, JobsConfig};
+
 // install seam
 pub use install::{AddMode, AddOutcome, Fetched, UpdatePreflight};
 
+// integrity
+pub use integrity::{IntegrityError, IntegrityGuard};
+
 // reindex seam
-pub use reindex::{ReindexOutcome, ReindexProgress};
+pub use reindex::{ReindexMode, ReindexOutcome, ReindexProgress};
+
+// skill health report
+pub use report::{post_report, write_report, HealthReport, ReportFormat, ReportOptions};
 
 // project_config
 pub use project_config::{load_project_config, ProjectConfig};
@@ -101,17 +174,30 @@ pub use resolver::{
 };
 
 // routing
-pub use routing::{QueryContext, RoutedSkill, RoutingService, RoutingServiceImpl};
+pub use routing::{
+    QueryContext, RouteCandidate, RouteDecision, RouteOutcome, RouteRequest, RoutedSkill,
+    RoutingPolicy, RoutingService, RoutingServiceImpl,
+};
 
 // service
 pub use service::{
-    CacheConfig, EmbeddingConfig, FastSkillService, HotReloadConfig, HttpServerConfig,
-    SecurityConfig, ServiceConfig, ServiceError, SkillId,
+    CacheConfig, ClientProfile, ClientRole, EmbeddingConfig, FastSkillService, HotReloadConfig,
+    HttpServerConfig, RateLimit, RateLimitConfig, SecurityConfig, ServiceConfig, ServiceError,
+    SkillId,
 };
 
+// skill_env
+pub use skill_env::{EnvDeclarations, EnvVarSpec};
+
+// skill_injection
+pub use skill_injection::{InjectionDecision, InjectionRule, InjectionRules};
+
 // skill_manager
 pub use skill_manager::{SkillDefinition, SkillManagementService, SkillManager, SkillUpdate};
 
+// skill_naming
+pub use skill_naming::{IdCollision, IdStrategy, SkillNaming};
+
 // sources
 pub use sources::{
     MarketplaceJson, MarketplaceSkill, SkillInfo, SourceAuth, SourceConfig, SourceDefinition,
@@ -128,11 +214,15 @@ pub use validation::{
 };
 
 // vector_index
-pub use vector_index::{IndexedSkill, SkillMatch, VectorIndexService, VectorIndexServiceImpl};
+pub use vector_index::{
+    IndexMismatch, IndexedSkill, SkillMatch, VectorIndexMetadata, VectorIndexService,
+    VectorIndexServiceImpl,
+};
 
 // version
 pub use version::{
-    compare_versions, is_newer, newest_version, sort_versions_desc, VersionConstraint, VersionError,
+    compare_versions, is_newer, newest_version, oldest_version, sort_versions_desc,
+    ResolutionStrategy, VersionConstraint, VersionError,
 };
 
 // version_bump
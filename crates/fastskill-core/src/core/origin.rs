@@ -39,6 +39,8 @@ pub enum Origin {
     },
     /// A remote zip archive fetched over HTTP(S).
     ZipUrl { url: String },
+    /// One skill out of a local skill pack archive (see [`crate::core::pack`]).
+    Pack { path: PathBuf, skill: String },
     /// A reference *into* a configured [`Repository`](crate::core::manifest). `repo`
     /// is the concrete Repository name; `version` is the only place ADR-0004
     /// versioning applies (`None` = newest allowed).
@@ -50,6 +52,45 @@ pub enum Origin {
     },
 }
 
+impl Origin {
+    /// Whether two origins name the same source. Version constraints are left
+    /// out on purpose — they are checked against concrete versions separately —
+    /// and local paths are not compared, since the same directory is spelled
+    /// relative to whichever manifest mentions it.
+    pub fn is_same_source(&self, other: &Origin) -> bool {
+        match (self, other) {
+            (
+                Origin::Git {
+                    url: a_url,
+                    r#ref: a_ref,
+                    subdir: a_subdir,
+                },
+                Origin::Git {
+                    url: b_url,
+                    r#ref: b_ref,
+                    subdir: b_subdir,
+                },
+            ) => a_url == b_url && a_ref == b_ref && a_subdir == b_subdir,
+            (Origin::Local { .. }, Origin::Local { .. }) => true,
+            (Origin::ZipUrl { url: a }, Origin::ZipUrl { url: b }) => a == b,
+            (Origin::Pack { skill: a, .. }, Origin::Pack { skill: b, .. }) => a == b,
+            (
+                Origin::Repository {
+                    repo: a_repo,
+                    skill: a_skill,
+                    ..
+                },
+                Origin::Repository {
+                    repo: b_repo,
+                    skill: b_skill,
+                    ..
+                },
+            ) => a_repo == b_repo && a_skill == b_skill,
+            _ => false,
+        }
+    }
+}
+
 /// The git ref an [`Origin::Git`] points at. A sum type so illegal combinations
 /// (a branch *and* a tag) are unrepresentable; `Default` means the repository's
 /// default branch.
@@ -144,6 +185,20 @@ mod tests {
         assert_eq!(roundtrip(&o), o);
     }
 
+    #[test]
+    fn pack_roundtrips() {
+        let o = Origin::Pack {
+            path: PathBuf::from("/tmp/team.zip"),
+            skill: "pdf-tools".into(),
+        };
+        let json = serde_json::to_string(&o).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"pack","path":"/tmp/team.zip","skill":"pdf-tools"}"#
+        );
+        assert_eq!(roundtrip(&o), o);
+    }
+
     #[test]
     fn repository_latest_omits_version() {
         let o = Origin::Repository {
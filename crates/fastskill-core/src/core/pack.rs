@@ -0,0 +1,345 @@
+//! Skill packs: several skills distributed as one zip
+//!
+//! A pack is a zip with a `skill-pack.toml` manifest at its root and one
+//! directory per skill under `skills/`. Installing a pack expands it into
+//! individually managed skills whose origin is [`Origin::Pack`], so each one
+//! can be updated or removed on its own while still recording which pack it
+//! came from.
+//!
+//! [`Origin::Pack`]: crate::core::origin::Origin::Pack
+
+use crate::core::install::{derive_skill_id_and_version, read_skill_frontmatter, safe_subdir_join};
+use crate::core::packaging::{package_files, write_files};
+use crate::core::service::{ServiceError, SkillId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Name of the manifest at the root of a pack archive
+pub const PACK_MANIFEST_FILE: &str = "skill-pack.toml";
+
+/// Directory inside the archive that holds one subdirectory per skill
+const SKILLS_DIR: &str = "skills";
+
+/// Contents of `skill-pack.toml`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackManifest {
+    pub pack: PackInfo,
+    #[serde(default)]
+    pub skills: Vec<PackEntry>,
+}
+
+/// The `[pack]` table
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackInfo {
+    pub name: String,
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// One `[[skills]]` entry: a skill and its directory inside the archive
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackEntry {
+    pub id: String,
+    pub version: String,
+    pub path: PathBuf,
+}
+
+impl PackManifest {
+    /// The entry for `skill`, if the pack contains it
+    pub fn entry(&self, skill: &str) -> Option<&PackEntry> {
+        self.skills.iter().find(|entry| entry.id == skill)
+    }
+
+    fn validate(&self) -> Result<(), ServiceError> {
+        if self.pack.name.trim().is_empty() || self.pack.version.trim().is_empty() {
+            return Err(ServiceError::Validation(format!(
+                "{} needs a non-empty [pack] name and version",
+                PACK_MANIFEST_FILE
+            )));
+        }
+        if self.skills.is_empty() {
+            return Err(ServiceError::Validation(format!(
+                "Skill pack '{}' contains no skills",
+                self.pack.name
+            )));
+        }
+        let mut seen = HashSet::new();
+        for entry in &self.skills {
+            SkillId::new(entry.id.clone())?;
+            if !seen.insert(entry.id.as_str()) {
+                return Err(ServiceError::Validation(format!(
+                    "Skill pack '{}' lists '{}' more than once",
+                    self.pack.name, entry.id
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn parse(content: &str) -> Result<Self, ServiceError> {
+        let manifest: PackManifest = toml::from_str(content).map_err(|e| {
+            ServiceError::Validation(format!("Failed to parse {}: {}", PACK_MANIFEST_FILE, e))
+        })?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+}
+
+fn zip_error(path: &Path) -> impl Fn(zip::result::ZipError) -> ServiceError + '_ {
+    move |e| ServiceError::Validation(format!("Invalid ZIP file {}: {}", path.display(), e))
+}
+
+/// Whether the zip at `path` is a skill pack (has `skill-pack.toml` at its root)
+pub fn is_pack(path: &Path) -> Result<bool, ServiceError> {
+    let file = std::fs::File::open(path)?;
+    let mut archive = zip::ZipArchive::new(file).map_err(zip_error(path))?;
+    let found = archive.by_name(PACK_MANIFEST_FILE).is_ok();
+    Ok(found)
+}
+
+/// Read and validate `skill-pack.toml` without extracting the pack
+pub fn read_pack_manifest(path: &Path) -> Result<PackManifest, ServiceError> {
+    let file = std::fs::File::open(path)?;
+    let mut archive = zip::ZipArchive::new(file).map_err(zip_error(path))?;
+    let mut entry = archive.by_name(PACK_MANIFEST_FILE).map_err(|_| {
+        ServiceError::Validation(format!(
+            "{} is not a skill pack: no {} at its root",
+            path.display(),
+            PACK_MANIFEST_FILE
+        ))
+    })?;
+    let mut content = String::new();
+    entry.read_to_string(&mut content)?;
+    PackManifest::parse(&content)
+}
+
+/// Write a pack of `skill_dirs` to `output`. Each skill's id and version come
+/// from its `skill-project.toml` or `SKILL.md`, the same way `add` reads them.
+/// Each skill's files are chosen by [`package_files`], so its `.fsignore` and
+/// `exclude` globs apply and symlinks are left out.
+pub async fn build_pack(
+    info: PackInfo,
+    skill_dirs: &[PathBuf],
+    output: &Path,
+) -> Result<PackManifest, ServiceError> {
+    let mut skills = Vec::with_capacity(skill_dirs.len());
+    for dir in skill_dirs {
+        let frontmatter = read_skill_frontmatter(dir).await?;
+        let (id, version) = derive_skill_id_and_version(dir, &frontmatter)?;
+        skills.push(PackEntry {
+            path: Path::new(SKILLS_DIR).join(id.as_str()),
+            id: id.to_string(),
+            version,
+        });
+    }
+    let manifest = PackManifest { pack: info, skills };
+    manifest.validate()?;
+
+    let manifest_toml = toml::to_string_pretty(&manifest).map_err(|e| {
+        ServiceError::Custom(format!("Failed to encode {}: {}", PACK_MANIFEST_FILE, e))
+    })?;
+    let zip_error = zip_error(output);
+    let mut writer = zip::ZipWriter::new(std::fs::File::create(output)?);
+    let options =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    writer
+        .start_file(PACK_MANIFEST_FILE, options)
+        .map_err(&zip_error)?;
+    writer.write_all(manifest_toml.as_bytes())?;
+
+    for (dir, entry) in skill_dirs.iter().zip(&manifest.skills) {
+        let files = package_files(dir)?;
+        write_files(&mut writer, dir, &files, &entry.path, options)?;
+    }
+    writer.finish().map_err(&zip_error)?;
+    Ok(manifest)
+}
+
+/// A pack extracted into a temporary directory
+pub struct ExtractedPack {
+    pub temp_dir: TempDir,
+    pub manifest: PackManifest,
+}
+
+impl ExtractedPack {
+    /// Directory of `skill` inside the extracted pack. Entry paths come from
+    /// the archive, so they are checked to stay inside it.
+    pub fn skill_dir(&self, skill: &str) -> Result<PathBuf, ServiceError> {
+        let entry = self.manifest.entry(skill).ok_or_else(|| {
+            ServiceError::InvalidOperation(format!(
+                "Skill pack '{}' does not contain '{}'",
+                self.manifest.pack.name, skill
+            ))
+        })?;
+        let dir = safe_subdir_join(self.temp_dir.path(), &entry.path)?;
+        if !dir.join("SKILL.md").is_file() {
+            return Err(ServiceError::Validation(format!(
+                "Skill pack '{}' has no SKILL.md at {}",
+                self.manifest.pack.name,
+                entry.path.display()
+            )));
+        }
+        Ok(dir)
+    }
+}
+
+/// Extract the pack at `path` into a temporary directory. Entries that would
+/// land outside it are rejected by the zip extraction.
+pub fn extract_pack(path: &Path) -> Result<ExtractedPack, ServiceError> {
+    let temp_dir = TempDir::new()?;
+    crate::storage::zip::ZipHandler::new()?.extract_to_dir(path, temp_dir.path())?;
+    let content =
+        std::fs::read_to_string(temp_dir.path().join(PACK_MANIFEST_FILE)).map_err(|_| {
+            ServiceError::Validation(format!(
+                "{} is not a skill pack: no {} at its root",
+                path.display(),
+                PACK_MANIFEST_FILE
+            ))
+        })?;
+    let manifest = PackManifest::parse(&content)?;
+    Ok(ExtractedPack { temp_dir, manifest })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn write_skill(dir: &Path, id: &str, version: &str) -> PathBuf {
+        let skill_dir = dir.join(id);
+        std::fs::create_dir_all(skill_dir.join("references")).unwrap();
+        std::fs::write(
+            skill_dir.join("SKILL.md"),
+            format!("---\nname: {id}\nversion: {version}\ndescription: Test {id}\n---\n# {id}\n"),
+        )
+        .unwrap();
+        std::fs::write(skill_dir.join("references").join("notes.md"), "notes").unwrap();
+        skill_dir
+    }
+
+    fn info() -> PackInfo {
+        PackInfo {
+            name: "team-standard".to_string(),
+            version: "1.0.0".to_string(),
+            description: None,
+        }
+    }
+
+    fn write_zip(path: &Path, entries: &[(&str, &str)]) {
+        let mut writer = zip::ZipWriter::new(std::fs::File::create(path).unwrap());
+        for (name, content) in entries {
+            writer
+                .start_file(*name, zip::write::FileOptions::default())
+                .unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_build_and_extract_pack_roundtrip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dirs = vec![
+            write_skill(temp_dir.path(), "pdf-tools", "1.2.0"),
+            write_skill(temp_dir.path(), "csv-tools", "0.3.0"),
+        ];
+        let output = temp_dir.path().join("team.zip");
+
+        let manifest = build_pack(info(), &dirs, &output).await.unwrap();
+        assert_eq!(manifest.skills.len(), 2);
+        assert!(is_pack(&output).unwrap());
+        assert_eq!(read_pack_manifest(&output).unwrap(), manifest);
+
+        let extracted = extract_pack(&output).unwrap();
+        let dir = extracted.skill_dir("csv-tools").unwrap();
+        assert!(dir.join("references").join("notes.md").is_file());
+        assert_eq!(
+            extracted.manifest.entry("csv-tools").unwrap().version,
+            "0.3.0"
+        );
+        assert!(extracted.skill_dir("missing").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_build_pack_honors_fsignore() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = write_skill(temp_dir.path(), "pdf-tools", "1.0.0");
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+        std::fs::write(dir.join(".git").join("HEAD"), "ref").unwrap();
+        std::fs::write(
+            dir.join(crate::core::packaging::IGNORE_FILE),
+            "references/\n",
+        )
+        .unwrap();
+        let output = temp_dir.path().join("team.zip");
+
+        build_pack(info(), &[dir], &output).await.unwrap();
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&output).unwrap()).unwrap();
+        assert!(archive.by_name("skills/pdf-tools/SKILL.md").is_ok());
+        assert!(archive
+            .by_name("skills/pdf-tools/references/notes.md")
+            .is_err());
+        assert!(archive.by_name("skills/pdf-tools/.git/HEAD").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_build_pack_rejects_duplicate_ids() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = write_skill(temp_dir.path(), "pdf-tools", "1.0.0");
+        let output = temp_dir.path().join("dup.zip");
+        let err = build_pack(info(), &[dir.clone(), dir], &output)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("more than once"), "{}", err);
+    }
+
+    #[test]
+    fn test_plain_skill_zip_is_not_a_pack() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("skill.zip");
+        write_zip(&path, &[("SKILL.md", "---\nname: x\n---\n")]);
+        assert!(!is_pack(&path).unwrap());
+        assert!(read_pack_manifest(&path).is_err());
+        assert!(extract_pack(&path).is_err());
+    }
+
+    #[test]
+    fn test_pack_entry_path_traversal_rejected() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("evil.zip");
+        write_zip(
+            &path,
+            &[
+                (
+                    PACK_MANIFEST_FILE,
+                    "[pack]\nname = \"evil\"\nversion = \"1.0.0\"\n\n[[skills]]\nid = \"escape\"\nversion = \"1.0.0\"\npath = \"../escape\"\n",
+                ),
+                ("skills/escape/SKILL.md", "---\nname: escape\n---\n"),
+            ],
+        );
+        let extracted = extract_pack(&path).unwrap();
+        assert!(extracted.skill_dir("escape").is_err());
+    }
+
+    #[test]
+    fn test_pack_archive_entry_traversal_rejected() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("slip.zip");
+        write_zip(
+            &path,
+            &[
+                (
+                    PACK_MANIFEST_FILE,
+                    "[pack]\nname = \"slip\"\nversion = \"1.0.0\"\n\n[[skills]]\nid = \"slip\"\nversion = \"1.0.0\"\npath = \"skills/slip\"\n",
+                ),
+                ("../../outside/SKILL.md", "---\nname: slip\n---\n"),
+            ],
+        );
+        assert!(extract_pack(&path).is_err());
+        assert!(!temp_dir.path().join("outside").exists());
+    }
+}
@@ -0,0 +1,367 @@
+//! Which files of a skill directory go into its archive
+//!
+//! Packaging leaves out `.git/` and `node_modules/`, then whatever the skill
+//! excludes itself: patterns in a `.fsignore` at the skill root (gitignore
+//! syntax) followed by the `exclude` globs under `[metadata]` in its
+//! `skill-project.toml`. As in gitignore, the last matching pattern wins, so
+//! `!node_modules/` brings the default exclusion back in, and nothing under an
+//! excluded directory can be re-included. Symlinks are never packaged.
+
+use crate::core::manifest::SkillProjectToml;
+use crate::core::service::ServiceError;
+use regex::Regex;
+use std::io::{Seek, Write};
+use std::path::{Path, PathBuf};
+
+/// Name of the ignore file at the root of a skill directory
+pub const IGNORE_FILE: &str = ".fsignore";
+
+/// Excluded unless a skill's own patterns re-include them
+const DEFAULT_EXCLUDES: &[&str] = &[".git/", "node_modules/"];
+
+#[derive(Debug, Clone)]
+struct Rule {
+    regex: Regex,
+    negated: bool,
+    dir_only: bool,
+}
+
+/// Compiled exclusion patterns, matched against `/`-separated paths relative
+/// to the skill root.
+#[derive(Debug, Clone, Default)]
+pub struct ExcludeRules {
+    rules: Vec<Rule>,
+}
+
+impl ExcludeRules {
+    /// Compile gitignore-style `patterns`. Blank lines and `#` comments are
+    /// skipped.
+    pub fn parse<'a>(patterns: impl IntoIterator<Item = &'a str>) -> Result<Self, ServiceError> {
+        let mut rules = Vec::new();
+        for line in patterns {
+            if let Some(rule) = compile(line)? {
+                rules.push(rule);
+            }
+        }
+        Ok(Self { rules })
+    }
+
+    /// The defaults plus `.fsignore` and the `exclude` globs of the skill at
+    /// `skill_dir`.
+    pub fn load(skill_dir: &Path) -> Result<Self, ServiceError> {
+        let mut patterns: Vec<String> = DEFAULT_EXCLUDES.iter().map(|p| p.to_string()).collect();
+        let ignore_file = skill_dir.join(IGNORE_FILE);
+        if ignore_file.is_file() {
+            let content = std::fs::read_to_string(&ignore_file)?;
+            patterns.extend(content.lines().map(str::to_string));
+        }
+        let toml_path = skill_dir.join("skill-project.toml");
+        if toml_path.is_file() {
+            let content = std::fs::read_to_string(&toml_path)?;
+            let project: SkillProjectToml = toml::from_str(&content).map_err(|e| {
+                ServiceError::Validation(format!("Failed to parse skill-project.toml: {e}"))
+            })?;
+            if let Some(exclude) = project.metadata.and_then(|m| m.exclude) {
+                patterns.extend(exclude);
+            }
+        }
+        Self::parse(patterns.iter().map(String::as_str))
+    }
+
+    /// Whether `relative` (a file, or a directory when `is_dir`) is excluded.
+    /// Parent directories are not consulted; [`package_files`] prunes them.
+    pub fn is_excluded(&self, relative: &Path, is_dir: bool) -> bool {
+        let path = to_slash_path(relative);
+        let mut excluded = false;
+        for rule in &self.rules {
+            if rule.dir_only && !is_dir {
+                continue;
+            }
+            if rule.regex.is_match(&path) {
+                excluded = !rule.negated;
+            }
+        }
+        excluded
+    }
+}
+
+fn to_slash_path(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn compile(line: &str) -> Result<Option<Rule>, ServiceError> {
+    let pattern = line.trim_end();
+    if pattern.is_empty() || pattern.starts_with('#') {
+        return Ok(None);
+    }
+    let (negated, pattern) = match pattern.strip_prefix('!') {
+        Some(rest) => (true, rest),
+        None => (false, pattern),
+    };
+    let dir_only = pattern.ends_with('/');
+    let pattern = pattern.trim_end_matches('/');
+    // A slash anywhere but at the end anchors the pattern to the skill root
+    let anchored = pattern.contains('/');
+    let pattern = pattern.trim_start_matches('/');
+    if pattern.is_empty() {
+        return Ok(None);
+    }
+    let prefix = if anchored { "^" } else { "^(?:.*/)?" };
+    let source = format!("{}{}$", prefix, glob_to_regex(pattern));
+    let regex = Regex::new(&source).map_err(|e| {
+        ServiceError::Validation(format!("Invalid exclude pattern '{}': {}", line, e))
+    })?;
+    Ok(Some(Rule {
+        regex,
+        negated,
+        dir_only,
+    }))
+}
+
+/// Translate gitignore glob syntax: `*` and `?` stop at `/`, `**/` matches
+/// any number of directories, a trailing `**` everything below, `[...]` a
+/// character class and `\` escapes the next character.
+fn glob_to_regex(pattern: &str) -> String {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                if chars.get(i + 2) == Some(&'/') {
+                    out.push_str("(?:.*/)?");
+                    i += 3;
+                } else {
+                    out.push_str(".*");
+                    i += 2;
+                }
+                continue;
+            }
+            '*' => out.push_str("[^/]*"),
+            '?' => out.push_str("[^/]"),
+            '[' => match class_to_regex(&chars[i + 1..]) {
+                Some((class, len)) => {
+                    out.push_str(&class);
+                    i += len + 1;
+                    continue;
+                }
+                None => out.push_str(r"\["),
+            },
+            '\\' if i + 1 < chars.len() => {
+                out.push_str(&regex::escape(&chars[i + 1].to_string()));
+                i += 2;
+                continue;
+            }
+            c => out.push_str(&regex::escape(&c.to_string())),
+        }
+        i += 1;
+    }
+    out
+}
+
+/// The regex for a `[...]` class whose body starts at `rest`, and how many
+/// characters of `rest` it used (including the closing `]`); `None` when the
+/// class is never closed.
+fn class_to_regex(rest: &[char]) -> Option<(String, usize)> {
+    let mut out = String::from("[");
+    let mut i = 0;
+    if matches!(rest.first(), Some('!') | Some('^')) {
+        out.push('^');
+        i += 1;
+    }
+    let body_start = i;
+    while i < rest.len() {
+        let c = rest[i];
+        if c == ']' && i > body_start {
+            out.push(']');
+            return Some((out, i + 1));
+        }
+        if matches!(c, '\\' | '[' | ']' | '&' | '~') {
+            out.push('\\');
+        }
+        out.push(c);
+        i += 1;
+    }
+    None
+}
+
+/// Paths (relative to `skill_dir`, sorted) of the files that go into the
+/// skill's archive.
+pub fn package_files(skill_dir: &Path) -> Result<Vec<PathBuf>, ServiceError> {
+    let rules = ExcludeRules::load(skill_dir)?;
+    let mut files = Vec::new();
+    let walker = walkdir::WalkDir::new(skill_dir)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|entry| match entry.path().strip_prefix(skill_dir) {
+            Ok(relative) if !relative.as_os_str().is_empty() => {
+                !rules.is_excluded(relative, entry.file_type().is_dir())
+            }
+            _ => true,
+        });
+    for entry in walker {
+        let entry = entry.map_err(|e| ServiceError::Custom(e.to_string()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        if let Ok(relative) = entry.path().strip_prefix(skill_dir) {
+            files.push(relative.to_path_buf());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Write `files` of `skill_dir` into `writer`, each under `prefix`.
+pub(crate) fn write_files<W: Write + Seek>(
+    writer: &mut zip::ZipWriter<W>,
+    skill_dir: &Path,
+    files: &[PathBuf],
+    prefix: &Path,
+    options: zip::write::FileOptions,
+) -> Result<(), ServiceError> {
+    for file in files {
+        let name = to_slash_path(&prefix.join(file));
+        writer
+            .start_file(name, options)
+            .map_err(|e| ServiceError::Custom(format!("Failed to write archive: {}", e)))?;
+        writer.write_all(&std::fs::read(skill_dir.join(file))?)?;
+    }
+    Ok(())
+}
+
+/// Zip the skill at `skill_dir` into `output`, with its files at the archive
+/// root as `add` expects. Returns the files that were packaged.
+pub fn package_skill(skill_dir: &Path, output: &Path) -> Result<Vec<PathBuf>, ServiceError> {
+    if !skill_dir.join("SKILL.md").is_file() {
+        return Err(ServiceError::Validation(format!(
+            "{} is not a skill directory: no SKILL.md",
+            skill_dir.display()
+        )));
+    }
+    let files = package_files(skill_dir)?;
+    let mut writer = zip::ZipWriter::new(std::fs::File::create(output)?);
+    let options =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    write_files(&mut writer, skill_dir, &files, Path::new(""), options)?;
+    writer
+        .finish()
+        .map_err(|e| ServiceError::Custom(format!("Failed to write archive: {}", e)))?;
+    Ok(files)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, relative: &str, content: &str) {
+        let path = dir.join(relative);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn names(files: &[PathBuf]) -> Vec<String> {
+        files.iter().map(|f| to_slash_path(f)).collect()
+    }
+
+    #[test]
+    fn test_exclude_rules_gitignore_semantics() {
+        let rules = ExcludeRules::parse([
+            "# comment",
+            "*.log",
+            "/build",
+            "tests/fixtures/",
+            "docs/**/draft-?.md",
+            "!keep.log",
+            "[Tt]mp/",
+        ])
+        .unwrap();
+        let excluded = |p: &str| rules.is_excluded(Path::new(p), false);
+        let excluded_dir = |p: &str| rules.is_excluded(Path::new(p), true);
+
+        assert!(excluded("debug.log"));
+        assert!(excluded("scripts/out/debug.log"));
+        assert!(!excluded("keep.log"));
+        assert!(excluded("build"));
+        assert!(!excluded("scripts/build"));
+        assert!(excluded_dir("tests/fixtures"));
+        assert!(!excluded("tests/fixtures"));
+        assert!(excluded("docs/draft-1.md"));
+        assert!(excluded("docs/a/b/draft-2.md"));
+        assert!(!excluded("docs/draft-10.md"));
+        assert!(excluded_dir("tmp"));
+        assert!(excluded_dir("references/Tmp"));
+        assert!(!excluded("SKILL.md"));
+    }
+
+    #[test]
+    fn test_package_files_honors_fsignore_and_exclude() {
+        let dir = TempDir::new().unwrap();
+        let skill = dir.path();
+        write(skill, "SKILL.md", "---\nname: s\n---\n");
+        write(skill, "references/guide.md", "guide");
+        write(skill, "references/guide.md.bak", "old");
+        write(skill, ".git/HEAD", "ref: refs/heads/main");
+        write(skill, "node_modules/left-pad/index.js", "");
+        write(skill, "tests/fixtures/big.bin", "x");
+        write(skill, "tests/check.sh", "#!/bin/sh");
+        write(skill, IGNORE_FILE, "tests/fixtures/\n*.bak\n");
+        write(
+            skill,
+            "skill-project.toml",
+            "[metadata]\nid = \"s\"\nversion = \"1.0.0\"\nexclude = [\"tests/*.sh\"]\n",
+        );
+
+        let files = package_files(skill).unwrap();
+        assert_eq!(
+            names(&files),
+            [
+                IGNORE_FILE,
+                "SKILL.md",
+                "references/guide.md",
+                "skill-project.toml"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_default_excludes_can_be_reincluded() {
+        let dir = TempDir::new().unwrap();
+        write(dir.path(), "SKILL.md", "---\nname: s\n---\n");
+        write(dir.path(), "node_modules/dep/index.js", "");
+        write(dir.path(), IGNORE_FILE, "!node_modules/\n");
+
+        let files = package_files(dir.path()).unwrap();
+        assert!(names(&files).contains(&"node_modules/dep/index.js".to_string()));
+    }
+
+    #[test]
+    fn test_package_skill_writes_listed_files() {
+        let dir = TempDir::new().unwrap();
+        let skill = dir.path().join("skill");
+        write(&skill, "SKILL.md", "---\nname: s\n---\n");
+        write(&skill, "references/a.md", "A");
+        write(&skill, ".git/config", "");
+        let output = dir.path().join("s.zip");
+
+        let files = package_skill(&skill, &output).unwrap();
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&output).unwrap()).unwrap();
+        assert_eq!(archive.len(), files.len());
+        let mut content = String::new();
+        archive
+            .by_name("references/a.md")
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "A");
+        assert!(archive.by_name(".git/config").is_err());
+
+        assert!(package_skill(dir.path(), &dir.path().join("x.zip")).is_err());
+    }
+}
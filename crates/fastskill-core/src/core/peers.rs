@@ -0,0 +1,145 @@
+//! Peer dependency compatibility checks
+//!
+//! A skill lists the versions of other skills it works with under
+//! `[peer-dependencies]` in its own `skill-project.toml`. Peers are never
+//! installed on the skill's behalf; this module only checks the peers that are
+//! already installed against the declared ranges.
+
+use crate::core::manifest::{ManifestError, SkillProjectToml};
+use crate::core::version::VersionConstraint;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// An installed peer that does not satisfy a declared range.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PeerViolation {
+    /// Skill that declares the peer dependency
+    pub skill_id: String,
+    pub peer_id: String,
+    /// Declared version range, as written
+    pub constraint: String,
+    /// Version of the peer that is installed
+    pub installed_version: String,
+    pub reason: String,
+}
+
+/// Peer ranges declared by the skill installed at `<skills_dir>/<skill_id>`.
+/// A skill without a `skill-project.toml` declares none.
+pub fn declared_peers(
+    skills_dir: &Path,
+    skill_id: &str,
+) -> Result<BTreeMap<String, String>, ManifestError> {
+    let manifest = skills_dir.join(skill_id).join("skill-project.toml");
+    match SkillProjectToml::load_from_file(&manifest) {
+        Ok(project) => Ok(project
+            .peer_dependencies
+            .unwrap_or_default()
+            .into_iter()
+            .collect()),
+        Err(ManifestError::NotFound(_)) => Ok(BTreeMap::new()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Check every installed skill's peer ranges against `installed` (skill id →
+/// installed version). Peers that are not installed are skipped. Results are
+/// ordered by declaring skill, then peer.
+pub fn check_peer_dependencies(
+    skills_dir: &Path,
+    installed: &HashMap<String, String>,
+) -> Vec<PeerViolation> {
+    let mut skill_ids: Vec<&String> = installed.keys().collect();
+    skill_ids.sort();
+
+    let mut violations = Vec::new();
+    for skill_id in skill_ids {
+        let peers = match declared_peers(skills_dir, skill_id) {
+            Ok(peers) => peers,
+            Err(e) => {
+                tracing::warn!("Could not read peer dependencies of '{}': {}", skill_id, e);
+                continue;
+            }
+        };
+        for (peer_id, constraint) in peers {
+            let Some(installed_version) = installed.get(&peer_id) else {
+                continue;
+            };
+            let reason = match VersionConstraint::parse(&constraint) {
+                Ok(req) => match req.satisfies(installed_version) {
+                    Ok(true) => continue,
+                    Ok(false) => format!(
+                        "{} requires {} {} but {} is installed",
+                        skill_id, peer_id, constraint, installed_version
+                    ),
+                    Err(e) => format!(
+                        "{} {} cannot be checked against {}: {}",
+                        peer_id, installed_version, constraint, e
+                    ),
+                },
+                Err(e) => format!(
+                    "{} declares an invalid range for {}: {}",
+                    skill_id, peer_id, e
+                ),
+            };
+            violations.push(PeerViolation {
+                skill_id: skill_id.clone(),
+                peer_id,
+                constraint,
+                installed_version: installed_version.clone(),
+                reason,
+            });
+        }
+    }
+    violations
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_peers(dir: &Path, skill_id: &str, peers: &str) {
+        let skill_dir = dir.join(skill_id);
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        std::fs::write(
+            skill_dir.join("skill-project.toml"),
+            format!(
+                "[metadata]\nid = \"{skill_id}\"\nversion = \"1.0.0\"\n\n[peer-dependencies]\n{peers}"
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_check_peer_dependencies() {
+        let dir = TempDir::new().unwrap();
+        write_peers(
+            dir.path(),
+            "charts",
+            "web = \"^2.0\"\napi = \">=1.0\"\ntheme = \"^1.0\"\n",
+        );
+        write_peers(dir.path(), "lint", "web = \"not a range\"\n");
+
+        let installed: HashMap<String, String> = [
+            ("charts", "1.0.0"),
+            ("lint", "0.3.0"),
+            ("web", "1.4.0"),
+            ("api", "1.2.0"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let violations = check_peer_dependencies(dir.path(), &installed);
+        assert_eq!(violations.len(), 2, "{violations:?}");
+        assert_eq!(violations[0].skill_id, "charts");
+        assert_eq!(violations[0].peer_id, "web");
+        assert_eq!(violations[0].installed_version, "1.4.0");
+        assert!(violations[0].reason.contains("requires web ^2.0"));
+        // `theme` is not installed and `api` is in range: neither is reported.
+        assert_eq!(violations[1].skill_id, "lint");
+        assert!(violations[1].reason.contains("invalid range"));
+    }
+}
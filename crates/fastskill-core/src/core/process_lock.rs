@@ -0,0 +1,212 @@
+//! Cross-process lock on a skills directory
+//!
+//! Commands that change a skills directory (install, add, remove, update,
+//! reindex, ...) read skills.lock and the storage dir, work for a while and
+//! write both back. Two of them running at once in the same project used to
+//! interleave those writes and lose each other's changes. [`ProcessLock`]
+//! holds an exclusive advisory lock (`fs2`) on `.fastskill/process.lock` in
+//! the skills directory for the whole command, and records which process
+//! holds it so the next one can say what it is waiting for.
+//!
+//! The lock is released when the guard is dropped or the process exits, so a
+//! crashed run never leaves it behind. The file itself is never removed:
+//! deleting a locked file lets a third process lock a fresh one next to the
+//! waiter that still holds the old inode.
+//!
+//! Short critical sections inside one write (a transaction commit, saving the
+//! ANN graph) use [`lock_sidecar`] instead, which simply blocks until the
+//! other process is done.
+
+use crate::core::cancel::{cancellable, CancellationToken};
+use crate::core::service::ServiceError;
+use fs2::FileExt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Lock file name, created in the skills directory's `.fastskill/`
+pub const PROCESS_LOCK_FILE: &str = "process.lock";
+
+/// How often a waiting process retries the lock
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Exclusive lock on a skills directory, released on drop
+#[derive(Debug)]
+pub struct ProcessLock {
+    file: File,
+    path: PathBuf,
+}
+
+impl ProcessLock {
+    /// Path of the lock file for `skills_dir`
+    pub fn path_for(skills_dir: &Path) -> PathBuf {
+        skills_dir.join(".fastskill").join(PROCESS_LOCK_FILE)
+    }
+
+    /// Take the lock, or fail with [`ServiceError::Busy`] naming the process
+    /// that holds it. `command` is recorded for that message, e.g.
+    /// `"fastskill install"`.
+    pub fn try_acquire(skills_dir: &Path, command: &str) -> Result<Self, ServiceError> {
+        let path = Self::path_for(skills_dir);
+        let file = open_lock_file(&path)?;
+        match file.try_lock_exclusive() {
+            Ok(()) => Self::locked(file, path, command),
+            Err(e) if is_contended(&e) => Err(ServiceError::Busy(holder(&path))),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Take the lock, waiting for the current holder to release it. Stops
+    /// with [`ServiceError::Cancelled`] when `token` is cancelled first.
+    pub async fn acquire(
+        skills_dir: &Path,
+        command: &str,
+        token: &CancellationToken,
+    ) -> Result<Self, ServiceError> {
+        loop {
+            match Self::try_acquire(skills_dir, command) {
+                Err(ServiceError::Busy(_)) => {
+                    cancellable(token, tokio::time::sleep(POLL_INTERVAL)).await?
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// The lock file this guard holds
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn locked(mut file: File, path: PathBuf, command: &str) -> Result<Self, ServiceError> {
+        // Only the holder writes, so waiters read a complete record.
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        writeln!(file, "{} {}", std::process::id(), command)?;
+        file.flush()?;
+        Ok(Self { file, path })
+    }
+}
+
+impl Drop for ProcessLock {
+    fn drop(&mut self) {
+        let _ = FileExt::unlock(&self.file);
+    }
+}
+
+/// Block until this process holds an exclusive lock on `path`, creating it if
+/// needed. The lock is released when the returned file is dropped.
+pub(crate) fn lock_sidecar(path: &Path) -> io::Result<File> {
+    let file = open_lock_file(path)?;
+    file.lock_exclusive()?;
+    Ok(file)
+}
+
+fn open_lock_file(path: &Path) -> io::Result<File> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+fn is_contended(e: &io::Error) -> bool {
+    e.kind() == io::ErrorKind::WouldBlock
+        || e.raw_os_error() == fs2::lock_contended_error().raw_os_error()
+}
+
+/// "pid 1234, `fastskill install`" from the lock file, as far as it can be read
+fn holder(path: &Path) -> String {
+    let record = fs::read_to_string(path).unwrap_or_default();
+    let mut parts = record.trim().splitn(2, ' ');
+    match (parts.next().filter(|p| !p.is_empty()), parts.next()) {
+        (Some(pid), Some(command)) => format!("pid {}, `{}`", pid, command),
+        (Some(pid), None) => format!("pid {}", pid),
+        _ => format!("lock held on {}", path.display()),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_second_process_lock_is_busy_and_names_holder() {
+        let dir = TempDir::new().unwrap();
+        let _held = ProcessLock::try_acquire(dir.path(), "fastskill install").unwrap();
+
+        match ProcessLock::try_acquire(dir.path(), "fastskill remove") {
+            Err(ServiceError::Busy(holder)) => {
+                assert!(holder.contains(&std::process::id().to_string()));
+                assert!(holder.contains("fastskill install"));
+            }
+            other => panic!("expected Busy, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_lock_is_free_again_after_drop_and_file_is_kept() {
+        let dir = TempDir::new().unwrap();
+        let held = ProcessLock::try_acquire(dir.path(), "fastskill install").unwrap();
+        let path = held.path().to_path_buf();
+        drop(held);
+
+        assert!(path.exists());
+        let again = ProcessLock::try_acquire(dir.path(), "fastskill add").unwrap();
+        assert_eq!(
+            fs::read_to_string(again.path()).unwrap().trim(),
+            format!("{} fastskill add", std::process::id())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_acquire_waits_for_release() {
+        let dir = TempDir::new().unwrap();
+        let held = ProcessLock::try_acquire(dir.path(), "fastskill install").unwrap();
+        let release = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(250)).await;
+            drop(held);
+        });
+
+        let token = CancellationToken::new();
+        let lock = ProcessLock::acquire(dir.path(), "fastskill update", &token)
+            .await
+            .unwrap();
+        release.await.unwrap();
+        assert_eq!(lock.path(), ProcessLock::path_for(dir.path()));
+    }
+
+    #[tokio::test]
+    async fn test_acquire_stops_when_cancelled() {
+        let dir = TempDir::new().unwrap();
+        let _held = ProcessLock::try_acquire(dir.path(), "fastskill install").unwrap();
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let result = ProcessLock::acquire(dir.path(), "fastskill update", &token).await;
+        assert!(matches!(result, Err(ServiceError::Cancelled)));
+    }
+
+    #[test]
+    fn test_holder_without_record_names_the_lock_file() {
+        let dir = TempDir::new().unwrap();
+        let path = ProcessLock::path_for(dir.path());
+        let _file = lock_sidecar(&path).unwrap();
+
+        match ProcessLock::try_acquire(dir.path(), "fastskill gc") {
+            Err(ServiceError::Busy(holder)) => {
+                assert!(holder.contains(PROCESS_LOCK_FILE))
+            }
+            other => panic!("expected Busy, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,459 @@
+//! Skill profiles: named sets of skills that are enabled together
+//!
+//! Projects of different kinds want different skills (web development, data
+//! engineering, ...). A profile in `.claude/skill-profiles.toml` lists the
+//! skills to keep enabled:
+//!
+//! ```toml
+//! [profiles.web-dev]
+//! description = "Frontend work"
+//! skills = ["frontend-design", "acme/react-testing"]
+//! ```
+//!
+//! Applying a profile enables its skills and disables every other installed
+//! skill. A disabled skill keeps its directory, but its SKILL.md is renamed to
+//! [`DISABLED_SKILL_FILE`], so agents and fastskill's own indexing stop
+//! loading it. It stays in skill-project.toml and skills.lock.
+//!
+//! [`FastSkillService::set_skills_enabled`] renames every file of a batch
+//! first and then reconciles the skill registry, storage and search index in
+//! one pass, instead of once per skill.
+
+use crate::core::scope::{dirs_holding, skill_dirs};
+use crate::core::service::{FastSkillService, ServiceError, SkillId};
+use crate::core::skill_manager::SkillDefinition;
+use crate::utils::atomic_write;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Profile file name, in the project's `.claude/` directory
+pub const PROFILES_FILE: &str = "skill-profiles.toml";
+
+/// What a disabled skill's SKILL.md is renamed to
+pub const DISABLED_SKILL_FILE: &str = "SKILL.md.disabled";
+
+/// One named set of skills
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillProfile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Ids of the skills the profile enables
+    #[serde(default)]
+    pub skills: Vec<String>,
+}
+
+/// The profiles of a project, by name
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillProfiles {
+    #[serde(default)]
+    pub profiles: BTreeMap<String, SkillProfile>,
+}
+
+impl SkillProfiles {
+    /// `<project root>/.claude/skill-profiles.toml`
+    pub fn default_path(project_root: &Path) -> PathBuf {
+        project_root.join(".claude").join(PROFILES_FILE)
+    }
+
+    /// Read the profile file; a missing file has no profiles.
+    pub fn load(path: &Path) -> Result<Self, ServiceError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)?;
+        toml::from_str(&content).map_err(|e| {
+            ServiceError::Config(format!(
+                "Profile file {} is malformed: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ServiceError> {
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| ServiceError::Custom(format!("Failed to encode profiles: {}", e)))?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        atomic_write(path, content.as_bytes())?;
+        Ok(())
+    }
+
+    /// The profile called `name`; the error lists the defined ones.
+    pub fn get(&self, name: &str) -> Result<&SkillProfile, ServiceError> {
+        self.profiles.get(name).ok_or_else(|| {
+            let known = if self.profiles.is_empty() {
+                "none are defined".to_string()
+            } else {
+                format!(
+                    "defined: {}",
+                    self.profiles.keys().cloned().collect::<Vec<_>>().join(", ")
+                )
+            };
+            ServiceError::Validation(format!("No profile named '{}' ({})", name, known))
+        })
+    }
+
+    /// Add or replace the profile called `name`
+    pub fn insert(&mut self, name: &str, profile: SkillProfile) -> Result<(), ServiceError> {
+        validate_profile_name(name)?;
+        self.profiles.insert(name.to_string(), profile);
+        Ok(())
+    }
+}
+
+/// Profile names are letters, digits, `-`, `_` and `.`
+pub fn validate_profile_name(name: &str) -> Result<(), ServiceError> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(ServiceError::Validation(format!(
+            "Invalid profile name '{}': use letters, digits, '-', '_' and '.'",
+            name
+        )))
+    }
+}
+
+/// Disabled skill directories under `skills_dir`, as `(id, path)` sorted by id
+pub fn disabled_skill_dirs(skills_dir: &Path) -> std::io::Result<Vec<(String, PathBuf)>> {
+    let mut dirs = dirs_holding(skills_dir, DISABLED_SKILL_FILE)?;
+    // A reinstall writes a fresh SKILL.md next to the disabled one
+    dirs.retain(|(_, dir)| !dir.join("SKILL.md").is_file());
+    Ok(dirs)
+}
+
+/// What [`FastSkillService::set_skills_enabled`] changed, ids sorted
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct EnablementOutcome {
+    pub enabled: Vec<String>,
+    pub disabled: Vec<String>,
+    /// Skills that already were in the requested state
+    pub unchanged: Vec<String>,
+}
+
+/// What applying a profile did
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ProfileApplication {
+    #[serde(flatten)]
+    pub changes: EnablementOutcome,
+    /// Skills the profile lists that are not installed
+    pub not_installed: Vec<String>,
+}
+
+impl FastSkillService {
+    /// Enable the skills in `enable` and disable the ones in `disable`. Every
+    /// id is checked before any file is renamed; an id that is neither an
+    /// enabled nor a disabled skill fails the whole batch. After the renames,
+    /// the skill registry is updated in one step and disabled skills are
+    /// dropped from the search index, which is refreshed once. Re-enabled
+    /// skills are embedded again by the next incremental reindex.
+    pub async fn set_skills_enabled(
+        &self,
+        enable: &[SkillId],
+        disable: &[SkillId],
+    ) -> Result<EnablementOutcome, ServiceError> {
+        if let Some(id) = enable.iter().find(|id| disable.contains(id)) {
+            return Err(ServiceError::Validation(format!(
+                "Skill '{}' cannot be both enabled and disabled",
+                id
+            )));
+        }
+
+        let skills_dir = &self.config().skill_storage_path;
+        let mut outcome = EnablementOutcome::default();
+        let mut definitions: Vec<SkillDefinition> = Vec::new();
+        let mut disabled: Vec<SkillId> = Vec::new();
+        let mut renames: Vec<(PathBuf, PathBuf)> = Vec::new();
+        for id in enable {
+            let dir = skills_dir.join(id.as_str());
+            let (skill_file, disabled_file) = (dir.join("SKILL.md"), dir.join(DISABLED_SKILL_FILE));
+            if skill_file.is_file() {
+                outcome.unchanged.push(id.to_string());
+            } else if disabled_file.is_file() {
+                // Parsed now so a broken SKILL.md fails before anything moves
+                let mut skill = Self::skill_definition_from_file(&disabled_file).await?;
+                skill.skill_file = skill_file.clone();
+                definitions.push(skill);
+                renames.push((disabled_file, skill_file));
+                outcome.enabled.push(id.to_string());
+            } else {
+                return Err(ServiceError::SkillNotFound(id.to_string()));
+            }
+        }
+        for id in disable {
+            let dir = skills_dir.join(id.as_str());
+            let (skill_file, disabled_file) = (dir.join("SKILL.md"), dir.join(DISABLED_SKILL_FILE));
+            if skill_file.is_file() {
+                renames.push((skill_file, disabled_file));
+                disabled.push(id.clone());
+                outcome.disabled.push(id.to_string());
+            } else if disabled_file.is_file() {
+                outcome.unchanged.push(id.to_string());
+            } else {
+                return Err(ServiceError::SkillNotFound(id.to_string()));
+            }
+        }
+
+        for (done, (from, to)) in renames.iter().enumerate() {
+            if let Err(e) = std::fs::rename(from, to) {
+                for (from, to) in renames[..done].iter().rev() {
+                    let _ = std::fs::rename(to, from);
+                }
+                return Err(e.into());
+            }
+        }
+
+        // One reconciliation pass for the whole batch
+        self.skill_manager()
+            .set_enabled_batch(definitions, &disabled)
+            .await?;
+        for id in outcome.enabled.iter().chain(&outcome.disabled) {
+            self.storage().skill_stored(id).await?;
+        }
+        if let Some(index) = self.vector_index_service().filter(|_| !disabled.is_empty()) {
+            for id in &disabled {
+                index.remove_skill(id.as_str()).await?;
+            }
+            index.refresh_search_index().await?;
+        }
+
+        outcome.enabled.sort();
+        outcome.disabled.sort();
+        outcome.unchanged.sort();
+        Ok(outcome)
+    }
+
+    /// Enable the skills of `profile` and disable every other installed skill,
+    /// in one [`set_skills_enabled`](Self::set_skills_enabled) batch. Skills
+    /// the profile lists but that are not installed are reported, not an error.
+    pub async fn apply_profile(
+        &self,
+        profile: &SkillProfile,
+    ) -> Result<ProfileApplication, ServiceError> {
+        let skills_dir = &self.config().skill_storage_path;
+        let installed: Vec<String> = skill_dirs(skills_dir)?
+            .into_iter()
+            .chain(disabled_skill_dirs(skills_dir)?)
+            .map(|(id, _)| id)
+            .collect();
+
+        let mut enable = Vec::new();
+        let mut not_installed = Vec::new();
+        for id in &profile.skills {
+            let id = SkillId::new(id.clone())?;
+            if installed.iter().any(|installed| installed == id.as_str()) {
+                enable.push(id);
+            } else {
+                not_installed.push(id.to_string());
+            }
+        }
+        let mut disable = Vec::new();
+        for id in &installed {
+            if !profile.skills.contains(id) {
+                disable.push(SkillId::new(id.clone())?);
+            }
+        }
+
+        let changes = self.set_skills_enabled(&enable, &disable).await?;
+        not_installed.sort();
+        Ok(ProfileApplication {
+            changes,
+            not_installed,
+        })
+    }
+
+    /// A profile of the skills that are enabled now
+    pub fn current_profile(
+        &self,
+        description: Option<String>,
+    ) -> Result<SkillProfile, ServiceError> {
+        let skills = skill_dirs(&self.config().skill_storage_path)?
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        Ok(SkillProfile {
+            description,
+            skills,
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::scope::ensure_scope_dir;
+    use crate::core::service::ServiceConfig;
+    use tempfile::TempDir;
+
+    fn write_skill(skills_dir: &Path, id: &str) {
+        let skill_id = SkillId::new(id.to_string()).unwrap();
+        ensure_scope_dir(skills_dir, &skill_id).unwrap();
+        let dir = skills_dir.join(id);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("SKILL.md"),
+            format!("---\nname: {id}\ndescription: The {id} skill\n---\n"),
+        )
+        .unwrap();
+    }
+
+    async fn service(skills_dir: &Path) -> FastSkillService {
+        let config = ServiceConfig {
+            skill_storage_path: skills_dir.to_path_buf(),
+            ..Default::default()
+        };
+        let mut service = FastSkillService::new(config).await.unwrap();
+        service.initialize().await.unwrap();
+        service
+    }
+
+    async fn registered(service: &FastSkillService) -> Vec<String> {
+        let mut ids: Vec<String> = service
+            .skill_manager()
+            .list_skills()
+            .await
+            .unwrap()
+            .iter()
+            .map(|s| s.id.to_string())
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    fn ids(ids: &[&str]) -> Vec<SkillId> {
+        ids.iter()
+            .map(|id| SkillId::new(id.to_string()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_profiles_round_trip_and_missing_file() {
+        let temp = TempDir::new().unwrap();
+        let path = SkillProfiles::default_path(temp.path());
+        assert_eq!(
+            SkillProfiles::load(&path).unwrap(),
+            SkillProfiles::default()
+        );
+
+        let mut profiles = SkillProfiles::default();
+        profiles
+            .insert(
+                "web-dev",
+                SkillProfile {
+                    description: Some("Frontend work".to_string()),
+                    skills: vec!["frontend-design".to_string()],
+                },
+            )
+            .unwrap();
+        profiles.save(&path).unwrap();
+        assert!(temp.path().join(".claude/skill-profiles.toml").is_file());
+        let loaded = SkillProfiles::load(&path).unwrap();
+        assert_eq!(loaded, profiles);
+
+        let err = loaded.get("data-eng").unwrap_err().to_string();
+        assert!(err.contains("web-dev"), "{}", err);
+        assert!(profiles
+            .insert("../escape", SkillProfile::default())
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_apply_profile_disables_the_rest_in_one_batch() {
+        let temp = TempDir::new().unwrap();
+        let skills_dir = temp.path().join("skills");
+        for id in ["frontend-design", "acme/react-testing", "sql-tuning"] {
+            write_skill(&skills_dir, id);
+        }
+        let service = service(&skills_dir).await;
+
+        let profile = SkillProfile {
+            description: None,
+            skills: vec![
+                "frontend-design".to_string(),
+                "acme/react-testing".to_string(),
+                "not-installed".to_string(),
+            ],
+        };
+        let applied = service.apply_profile(&profile).await.unwrap();
+        assert_eq!(applied.changes.disabled, ["sql-tuning"]);
+        assert_eq!(applied.not_installed, ["not-installed"]);
+        assert!(skills_dir
+            .join("sql-tuning")
+            .join(DISABLED_SKILL_FILE)
+            .is_file());
+        assert!(!skills_dir.join("sql-tuning/SKILL.md").exists());
+        assert_eq!(
+            registered(&service).await,
+            ["acme/react-testing", "frontend-design"]
+        );
+        assert_eq!(
+            service.current_profile(None).unwrap().skills,
+            ["acme/react-testing", "frontend-design"]
+        );
+
+        // Switching profiles re-enables the disabled skill
+        let data = SkillProfile {
+            description: None,
+            skills: vec!["sql-tuning".to_string()],
+        };
+        let applied = service.apply_profile(&data).await.unwrap();
+        assert_eq!(applied.changes.enabled, ["sql-tuning"]);
+        assert_eq!(
+            applied.changes.disabled,
+            ["acme/react-testing", "frontend-design"]
+        );
+        assert!(skills_dir.join("sql-tuning/SKILL.md").is_file());
+        assert_eq!(registered(&service).await, ["sql-tuning"]);
+        assert_eq!(
+            disabled_skill_dirs(&skills_dir)
+                .unwrap()
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>(),
+            ["acme/react-testing", "frontend-design"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_skills_enabled_checks_every_id_first() {
+        let temp = TempDir::new().unwrap();
+        let skills_dir = temp.path().join("skills");
+        write_skill(&skills_dir, "alpha");
+        write_skill(&skills_dir, "beta");
+        let service = service(&skills_dir).await;
+
+        let result = service
+            .set_skills_enabled(&[], &ids(&["alpha", "missing"]))
+            .await;
+        assert!(matches!(result, Err(ServiceError::SkillNotFound(_))));
+        assert!(skills_dir.join("alpha/SKILL.md").is_file());
+
+        let result = service
+            .set_skills_enabled(&ids(&["alpha"]), &ids(&["alpha"]))
+            .await;
+        assert!(matches!(result, Err(ServiceError::Validation(_))));
+
+        let outcome = service
+            .set_skills_enabled(&ids(&["beta"]), &ids(&["alpha"]))
+            .await
+            .unwrap();
+        assert_eq!(outcome.disabled, ["alpha"]);
+        assert_eq!(outcome.unchanged, ["beta"]);
+
+        // Ids that would leave the skills directory are rejected up front
+        assert!(SkillId::new("../alpha".to_string()).is_err());
+        let escape = SkillProfile {
+            description: None,
+            skills: vec!["../outside".to_string()],
+        };
+        assert!(service.apply_profile(&escape).await.is_err());
+        assert!(skills_dir.join("beta/SKILL.md").is_file());
+    }
+}
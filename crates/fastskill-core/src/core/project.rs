@@ -1,7 +1,16 @@
 //! Project-level file resolution and context detection
 
-use super::manifest::{FileResolutionResult, ProjectContext};
-use std::path::Path;
+use super::manifest::{
+    DependencySpec, FileResolutionResult, ManifestError, ProjectContext, SkillProjectToml,
+};
+use super::origin::Origin;
+use super::version::VersionConstraint;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// File name of the workspace manifest at a monorepo root
+pub const WORKSPACE_FILE: &str = "skill-workspace.toml";
 
 /// Resolve skill-project.toml file from project root
 ///
@@ -99,6 +108,301 @@ pub fn detect_context_from_content(project: &super::manifest::SkillProjectToml)
     ProjectContext::Ambiguous
 }
 
+// ============================================================================
+// Workspaces (skill-workspace.toml)
+// ============================================================================
+
+/// Root structure for skill-workspace.toml
+///
+/// ```toml
+/// [workspace]
+/// members = ["agents/support", "skills/*"]
+/// exclude = ["skills/scratch"]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillWorkspaceToml {
+    pub workspace: WorkspaceSection,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceSection {
+    /// Member directories relative to the workspace root. A trailing `/*`
+    /// takes every direct subdirectory that has a skill-project.toml.
+    pub members: Vec<String>,
+    /// Directories matched by a `/*` member pattern to leave out
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+/// One member directory of a workspace
+#[derive(Debug, Clone)]
+pub struct WorkspaceMember {
+    /// Canonical member directory
+    pub dir: PathBuf,
+    pub project: SkillProjectToml,
+    pub context: ProjectContext,
+}
+
+impl WorkspaceMember {
+    /// `[metadata].id` of a skill-level member (a skill authored in the workspace)
+    pub fn skill_id(&self) -> Option<&str> {
+        if self.context != ProjectContext::Skill {
+            return None;
+        }
+        self.project.metadata.as_ref()?.id.as_deref()
+    }
+
+    fn skill_version(&self) -> Option<&str> {
+        self.project.metadata.as_ref()?.version.as_deref()
+    }
+}
+
+/// A monorepo of skill projects sharing one skills directory and one
+/// skills.lock at the workspace root
+///
+/// The root skill-project.toml, when present, configures `[tool.fastskill]`
+/// for the whole workspace and may declare dependencies of its own.
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    /// Canonical workspace root (the directory holding skill-workspace.toml)
+    pub root: PathBuf,
+    /// skill-project.toml at the root, if any
+    pub root_project: Option<SkillProjectToml>,
+    pub members: Vec<WorkspaceMember>,
+}
+
+impl Workspace {
+    /// Load the workspace whose skill-workspace.toml is in `root`
+    pub fn load(root: &Path) -> Result<Self, ManifestError> {
+        let root = root.canonicalize().map_err(ManifestError::Io)?;
+        let manifest_path = root.join(WORKSPACE_FILE);
+        if !manifest_path.exists() {
+            return Err(ManifestError::NotFound(manifest_path));
+        }
+        let content = std::fs::read_to_string(&manifest_path).map_err(ManifestError::Io)?;
+        let manifest: SkillWorkspaceToml = toml::from_str(&content)
+            .map_err(|e| ManifestError::Parse(format!("{}: {}", WORKSPACE_FILE, e)))?;
+
+        let root_file = root.join("skill-project.toml");
+        let root_project = if root_file.exists() {
+            Some(SkillProjectToml::load_from_file(&root_file)?)
+        } else {
+            None
+        };
+
+        let excluded: Vec<PathBuf> = manifest
+            .workspace
+            .exclude
+            .iter()
+            .filter_map(|e| root.join(e).canonicalize().ok())
+            .collect();
+        let mut members: Vec<WorkspaceMember> = Vec::new();
+        for pattern in &manifest.workspace.members {
+            let dirs = if let Some(parent) = pattern.strip_suffix("/*") {
+                let parent = member_dir(&root, parent)?;
+                let mut dirs: Vec<PathBuf> = std::fs::read_dir(&parent)
+                    .map_err(ManifestError::Io)?
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|dir| dir.join("skill-project.toml").is_file())
+                    .filter_map(|dir| dir.canonicalize().ok())
+                    .filter(|dir| !excluded.contains(dir))
+                    .collect();
+                dirs.sort();
+                dirs
+            } else {
+                let dir = member_dir(&root, pattern)?;
+                if !dir.join("skill-project.toml").is_file() {
+                    return Err(ManifestError::Parse(format!(
+                        "{}: member '{}' has no skill-project.toml",
+                        WORKSPACE_FILE, pattern
+                    )));
+                }
+                vec![dir]
+            };
+            for dir in dirs {
+                if dir == root || members.iter().any(|m| m.dir == dir) {
+                    continue;
+                }
+                let project_file = dir.join("skill-project.toml");
+                let project = SkillProjectToml::load_from_file(&project_file)?;
+                members.push(WorkspaceMember {
+                    context: detect_context(&project_file),
+                    project,
+                    dir,
+                });
+            }
+        }
+
+        Ok(Self {
+            root,
+            root_project,
+            members,
+        })
+    }
+
+    /// Find the workspace `start` belongs to: the nearest skill-workspace.toml
+    /// walking up from `start`, provided `start` is its root or lies inside
+    /// one of its members.
+    pub fn discover(start: &Path) -> Result<Option<Self>, ManifestError> {
+        let Ok(start) = start.canonicalize() else {
+            return Ok(None);
+        };
+        let Some(root) = start
+            .ancestors()
+            .find(|dir| dir.join(WORKSPACE_FILE).is_file())
+        else {
+            return Ok(None);
+        };
+        let workspace = Self::load(root)?;
+        if start == workspace.root || workspace.member_containing(&start).is_some() {
+            Ok(Some(workspace))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// The member whose directory contains `path`
+    pub fn member_containing(&self, path: &Path) -> Option<&WorkspaceMember> {
+        self.members.iter().find(|m| path.starts_with(&m.dir))
+    }
+
+    /// Shared lock file of all members
+    pub fn lock_path(&self) -> PathBuf {
+        self.root.join("skills.lock")
+    }
+
+    /// Dependencies of the root project and every project-level member,
+    /// merged into one set for the shared skills.lock.
+    ///
+    /// Local paths are made absolute against the member that declares them.
+    /// A version dependency on a skill authored by a skill-level member
+    /// resolves to that member's directory, provided its version satisfies
+    /// the constraint. Members declaring the same skill must agree on it.
+    pub fn dependencies(&self) -> Result<HashMap<String, DependencySpec>, String> {
+        let mut sources: Vec<(String, &Path, &SkillProjectToml)> = Vec::new();
+        if let Some(project) = &self.root_project {
+            sources.push((
+                "the workspace root".to_string(),
+                self.root.as_path(),
+                project,
+            ));
+        }
+        for member in &self.members {
+            if member.context != ProjectContext::Skill {
+                sources.push((self.label(member), member.dir.as_path(), &member.project));
+            }
+        }
+
+        let mut merged: HashMap<String, (DependencySpec, String)> = HashMap::new();
+        for (label, dir, project) in sources {
+            let Some(deps) = &project.dependencies else {
+                continue;
+            };
+            for (id, spec) in &deps.dependencies {
+                let spec = self.resolve_dependency(id, spec, dir, &label)?;
+                match merged.get(id) {
+                    Some((existing, other)) if *existing != spec => {
+                        return Err(format!(
+                            "'{}' is declared differently by {} and {}; workspace members \
+                             share one skills.lock, so their declarations must match",
+                            id, other, label
+                        ));
+                    }
+                    Some(_) => {}
+                    None => {
+                        merged.insert(id.clone(), (spec, label.clone()));
+                    }
+                }
+            }
+        }
+        Ok(merged
+            .into_iter()
+            .map(|(id, (spec, _))| (id, spec))
+            .collect())
+    }
+
+    fn resolve_dependency(
+        &self,
+        id: &str,
+        spec: &DependencySpec,
+        dir: &Path,
+        label: &str,
+    ) -> Result<DependencySpec, String> {
+        match spec {
+            DependencySpec::Version(constraint) => {
+                let Some(member) = self.members.iter().find(|m| m.skill_id() == Some(id)) else {
+                    return Ok(spec.clone());
+                };
+                let version = member.skill_version().unwrap_or_default();
+                let satisfied = VersionConstraint::parse(constraint)
+                    .and_then(|c| c.satisfies(version))
+                    .map_err(|e| format!("Invalid version '{}' for {}: {}", constraint, id, e))?;
+                if !satisfied {
+                    return Err(format!(
+                        "{} requires {} {}, but workspace member {} is at {}",
+                        label,
+                        id,
+                        constraint,
+                        self.label(member),
+                        version
+                    ));
+                }
+                Ok(DependencySpec::Inline {
+                    origin: Origin::Local {
+                        path: member.dir.clone(),
+                        editable: false,
+                    },
+                    groups: None,
+                    extras: None,
+                })
+            }
+            DependencySpec::Inline {
+                origin: Origin::Local { path, editable },
+                groups,
+                extras,
+            } if path.is_relative() => {
+                let joined = dir.join(path);
+                Ok(DependencySpec::Inline {
+                    origin: Origin::Local {
+                        path: joined.canonicalize().unwrap_or(joined),
+                        editable: *editable,
+                    },
+                    groups: groups.clone(),
+                    extras: extras.clone(),
+                })
+            }
+            DependencySpec::Inline { .. } => Ok(spec.clone()),
+        }
+    }
+
+    /// Member directory relative to the root, for messages
+    pub fn label(&self, member: &WorkspaceMember) -> String {
+        member
+            .dir
+            .strip_prefix(&self.root)
+            .unwrap_or(&member.dir)
+            .display()
+            .to_string()
+    }
+}
+
+/// Canonical member directory, which must stay inside the workspace root
+fn member_dir(root: &Path, relative: &str) -> Result<PathBuf, ManifestError> {
+    let dir = root.join(relative).canonicalize().map_err(|e| {
+        ManifestError::Parse(format!(
+            "{}: member '{}' cannot be read: {}",
+            WORKSPACE_FILE, relative, e
+        ))
+    })?;
+    if !dir.starts_with(root) {
+        return Err(ManifestError::Parse(format!(
+            "{}: member '{}' is outside the workspace root",
+            WORKSPACE_FILE, relative
+        )));
+    }
+    Ok(dir)
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -192,4 +496,134 @@ mod tests {
         assert!(result.found);
         assert_eq!(result.path, subdir_file);
     }
+
+    /// Root with a project-level member `app` and a skill-level member
+    /// `skills/pdf` (pdf 1.2.0).
+    fn write_workspace(root: &Path, app_deps: &str) {
+        fs::write(
+            root.join(WORKSPACE_FILE),
+            "[workspace]\nmembers = [\"app\", \"skills/*\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("skill-project.toml"),
+            "[dependencies]\nshared = \"1.0.0\"\n\n[tool.fastskill]\nskills_directory = \".skills\"\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("app")).unwrap();
+        fs::write(
+            root.join("app/skill-project.toml"),
+            format!("[dependencies]\n{}", app_deps),
+        )
+        .unwrap();
+        let pdf = root.join("skills/pdf");
+        fs::create_dir_all(&pdf).unwrap();
+        fs::write(pdf.join("SKILL.md"), "# pdf\n").unwrap();
+        fs::write(
+            pdf.join("skill-project.toml"),
+            "[metadata]\nid = \"pdf\"\nversion = \"1.2.0\"\n",
+        )
+        .unwrap();
+        // Matched by skills/* but not a member: no skill-project.toml
+        fs::create_dir_all(root.join("skills/notes")).unwrap();
+    }
+
+    #[test]
+    fn test_workspace_load_and_discover() {
+        let temp_dir = TempDir::new().unwrap();
+        write_workspace(temp_dir.path(), "");
+
+        let workspace = Workspace::discover(&temp_dir.path().join("app"))
+            .unwrap()
+            .unwrap();
+        let labels: Vec<String> = workspace
+            .members
+            .iter()
+            .map(|m| workspace.label(m))
+            .collect();
+        assert_eq!(labels, ["app", "skills/pdf"]);
+        assert_eq!(workspace.members[1].skill_id(), Some("pdf"));
+        assert_eq!(workspace.lock_path(), workspace.root.join("skills.lock"));
+
+        // Inside the root but not in a member
+        fs::create_dir_all(temp_dir.path().join("docs")).unwrap();
+        assert!(Workspace::discover(&temp_dir.path().join("docs"))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_workspace_dependencies_resolve_members_and_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        write_workspace(
+            temp_dir.path(),
+            "shared = \"1.0.0\"\npdf = \"^1.0\"\nlocal-tool = { origin = { type = \"local\", path = \"../tools/local-tool\" } }\n",
+        );
+        fs::create_dir_all(temp_dir.path().join("tools/local-tool")).unwrap();
+
+        let workspace = Workspace::load(temp_dir.path()).unwrap();
+        let deps = workspace.dependencies().unwrap();
+
+        assert_eq!(deps.len(), 3);
+        assert_eq!(deps["shared"], DependencySpec::Version("1.0.0".to_string()));
+        let local_path = |id: &str| match &deps[id] {
+            DependencySpec::Inline {
+                origin: Origin::Local { path, .. },
+                ..
+            } => Some(path.clone()),
+            _ => None,
+        };
+        assert_eq!(
+            local_path("pdf").unwrap(),
+            workspace.root.join("skills/pdf")
+        );
+        assert_eq!(
+            local_path("local-tool").unwrap(),
+            workspace.root.join("tools/local-tool")
+        );
+    }
+
+    #[test]
+    fn test_workspace_dependencies_reject_conflicts() {
+        let temp_dir = TempDir::new().unwrap();
+        write_workspace(temp_dir.path(), "shared = \"2.0.0\"\n");
+        let err = Workspace::load(temp_dir.path())
+            .unwrap()
+            .dependencies()
+            .unwrap_err();
+        assert!(err.contains("'shared' is declared differently"), "{}", err);
+
+        let temp_dir = TempDir::new().unwrap();
+        write_workspace(temp_dir.path(), "pdf = \"^2\"\n");
+        let err = Workspace::load(temp_dir.path())
+            .unwrap()
+            .dependencies()
+            .unwrap_err();
+        assert!(
+            err.contains("workspace member skills/pdf is at 1.2.0"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_workspace_member_outside_root_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("repo");
+        fs::create_dir_all(temp_dir.path().join("elsewhere")).unwrap();
+        fs::write(
+            temp_dir.path().join("elsewhere/skill-project.toml"),
+            "[dependencies]\n",
+        )
+        .unwrap();
+        fs::create_dir_all(&root).unwrap();
+        fs::write(
+            root.join(WORKSPACE_FILE),
+            "[workspace]\nmembers = [\"../elsewhere\"]\n",
+        )
+        .unwrap();
+
+        let err = Workspace::load(&root).unwrap_err();
+        assert!(err.to_string().contains("outside the workspace root"));
+    }
 }
@@ -0,0 +1,390 @@
+//! Transactional update of skill-project.toml and skills.lock
+//!
+//! Commands that change both files used to save them one after the other, so
+//! a failure in between left a manifest that no longer matched its lock. A
+//! [`ProjectTransaction`] stages both contents first and then:
+//!
+//! 1. writes each staged file to a temp file next to its target,
+//! 2. copies the current targets to `<file>.bak`,
+//! 3. records the pair in a journal (`.fastskill-txn.json` next to the lock),
+//! 4. renames the temp files over the targets,
+//! 5. removes the journal and the backups.
+//!
+//! If a rename fails the backups are put back (a target that did not exist
+//! before is removed again), so the pair is either all old or all new. A
+//! process killed during step 4 leaves the journal behind; the next
+//! transaction on the same project, or [`recover_interrupted`], restores the
+//! previous pair from it.
+//!
+//! Commits and recoveries hold an exclusive lock on `.fastskill-txn.lock`
+//! next to the journal, so a second process cannot mistake a commit that is
+//! still running for an interrupted one and roll it back.
+
+use crate::core::lock::{LockError, ProjectSkillsLock};
+use crate::core::manifest::{ManifestError, SkillProjectToml};
+use crate::core::process_lock::lock_sidecar;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use tempfile::NamedTempFile;
+
+/// Journal file name, created in the lock file's directory
+pub const JOURNAL_FILE: &str = ".fastskill-txn.json";
+
+/// Lock file serialising commits on the same project, next to the journal
+pub const TXN_LOCK_FILE: &str = ".fastskill-txn.lock";
+
+const BACKUP_SUFFIX: &str = ".bak";
+
+#[derive(Debug, thiserror::Error)]
+pub enum TransactionError {
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("Failed to serialize skill-project.toml: {0}")]
+    Manifest(#[from] ManifestError),
+
+    #[error("Failed to serialize skills.lock: {0}")]
+    Lock(#[from] LockError),
+
+    #[error("Unreadable transaction journal {path}: {message}")]
+    Journal { path: PathBuf, message: String },
+
+    /// The commit failed and both files were restored to their previous content.
+    #[error("{cause}; skill-project.toml and skills.lock were left unchanged")]
+    RolledBack { cause: io::Error },
+
+    /// The commit failed and so did the restore; the journal and backups are
+    /// kept so the next transaction (or `recover_interrupted`) can retry.
+    #[error("{cause}; restoring the previous files also failed: {restore}")]
+    RestoreFailed {
+        cause: io::Error,
+        restore: io::Error,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Journal {
+    files: Vec<JournalEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JournalEntry {
+    target: PathBuf,
+    /// `None` when the target did not exist before the transaction
+    backup: Option<PathBuf>,
+}
+
+/// Staged contents for a project's skill-project.toml and skills.lock,
+/// written together by [`ProjectTransaction::commit`].
+#[derive(Debug)]
+pub struct ProjectTransaction {
+    manifest_path: PathBuf,
+    lock_path: PathBuf,
+    manifest: Option<String>,
+    lock: Option<String>,
+    #[cfg(test)]
+    fail_rename_at: Option<usize>,
+}
+
+impl ProjectTransaction {
+    pub fn new(manifest_path: impl Into<PathBuf>, lock_path: impl Into<PathBuf>) -> Self {
+        Self {
+            manifest_path: manifest_path.into(),
+            lock_path: lock_path.into(),
+            manifest: None,
+            lock: None,
+            #[cfg(test)]
+            fail_rename_at: None,
+        }
+    }
+
+    /// Stage the new skill-project.toml
+    pub fn stage_manifest(&mut self, project: &SkillProjectToml) -> Result<(), TransactionError> {
+        self.manifest = Some(project.to_toml_string()?);
+        Ok(())
+    }
+
+    /// Stage the new skills.lock
+    pub fn stage_lock(&mut self, lock: &ProjectSkillsLock) -> Result<(), TransactionError> {
+        self.lock = Some(lock.to_file_string()?);
+        Ok(())
+    }
+
+    /// Write every staged file, or none of them.
+    pub fn commit(self) -> Result<(), TransactionError> {
+        let journal_path = journal_path(&self.lock_path);
+        let _txn_lock = lock_sidecar(&journal_path.with_file_name(TXN_LOCK_FILE))?;
+        recover_journal(&journal_path)?;
+
+        let staged: Vec<(&Path, &str)> = [
+            (self.manifest_path.as_path(), self.manifest.as_deref()),
+            (self.lock_path.as_path(), self.lock.as_deref()),
+        ]
+        .into_iter()
+        .filter_map(|(path, content)| content.map(|c| (path, c)))
+        .collect();
+        if staged.is_empty() {
+            return Ok(());
+        }
+
+        let mut temps = Vec::with_capacity(staged.len());
+        for (target, content) in &staged {
+            temps.push(write_temp(target, content)?);
+        }
+
+        let mut journal = Journal { files: Vec::new() };
+        for (target, _) in &staged {
+            let backup = if target.exists() {
+                let backup = backup_path(target);
+                if let Err(e) = fs::copy(target, &backup) {
+                    remove_backups(&journal);
+                    return Err(e.into());
+                }
+                Some(backup)
+            } else {
+                None
+            };
+            journal.files.push(JournalEntry {
+                target: target.to_path_buf(),
+                backup,
+            });
+        }
+        let journal_bytes = serde_json::to_vec_pretty(&journal).map_err(io::Error::other)?;
+        if let Err(e) = crate::utils::atomic_write(&journal_path, &journal_bytes) {
+            remove_backups(&journal);
+            return Err(e.into());
+        }
+
+        for (index, temp) in temps.into_iter().enumerate() {
+            let target = &journal.files[index].target;
+            if let Err(cause) = self.persist(index, temp, target) {
+                return match restore(&journal) {
+                    Ok(()) => {
+                        let _ = fs::remove_file(&journal_path);
+                        Err(TransactionError::RolledBack { cause })
+                    }
+                    Err(restore) => Err(TransactionError::RestoreFailed { cause, restore }),
+                };
+            }
+        }
+
+        fs::remove_file(&journal_path)?;
+        remove_backups(&journal);
+        Ok(())
+    }
+
+    fn persist(&self, index: usize, temp: NamedTempFile, target: &Path) -> io::Result<()> {
+        #[cfg(test)]
+        if self.fail_rename_at == Some(index) {
+            return Err(io::Error::other("injected rename failure"));
+        }
+        #[cfg(not(test))]
+        let _ = index;
+        temp.persist(target).map(|_| ()).map_err(|e| e.error)
+    }
+}
+
+/// Restore the pair left by a transaction that was killed mid-commit.
+/// `lock_path` locates the journal. Returns whether anything was restored.
+pub fn recover_interrupted(lock_path: &Path) -> Result<bool, TransactionError> {
+    let journal_path = journal_path(lock_path);
+    let _txn_lock = lock_sidecar(&journal_path.with_file_name(TXN_LOCK_FILE))?;
+    recover_journal(&journal_path)
+}
+
+fn recover_journal(journal_path: &Path) -> Result<bool, TransactionError> {
+    let content = match fs::read(journal_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e.into()),
+    };
+    let journal: Journal =
+        serde_json::from_slice(&content).map_err(|e| TransactionError::Journal {
+            path: journal_path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+    restore(&journal)?;
+    fs::remove_file(journal_path)?;
+    tracing::warn!(
+        "Restored skill-project.toml and skills.lock from an interrupted update ({})",
+        journal_path.display()
+    );
+    Ok(true)
+}
+
+/// Put every journaled target back to its pre-transaction state.
+fn restore(journal: &Journal) -> io::Result<()> {
+    for entry in &journal.files {
+        match &entry.backup {
+            Some(backup) => match fs::rename(backup, &entry.target) {
+                Ok(()) => {}
+                // Already restored by an earlier, partially completed recovery
+                Err(e) if e.kind() == io::ErrorKind::NotFound && entry.target.exists() => {}
+                Err(e) => return Err(e),
+            },
+            None => match fs::remove_file(&entry.target) {
+                Ok(()) => {}
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e),
+            },
+        }
+    }
+    Ok(())
+}
+
+fn remove_backups(journal: &Journal) {
+    for backup in journal.files.iter().filter_map(|e| e.backup.as_ref()) {
+        let _ = fs::remove_file(backup);
+    }
+}
+
+fn journal_path(lock_path: &Path) -> PathBuf {
+    match lock_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.join(JOURNAL_FILE),
+        _ => PathBuf::from(JOURNAL_FILE),
+    }
+}
+
+fn backup_path(target: &Path) -> PathBuf {
+    let mut path = target.as_os_str().to_owned();
+    path.push(BACKUP_SUFFIX);
+    PathBuf::from(path)
+}
+
+/// Write `content` to a synced temp file in `target`'s directory, so the final
+/// rename stays on one filesystem.
+fn write_temp(target: &Path, content: &str) -> io::Result<NamedTempFile> {
+    let parent = match target.parent() {
+        Some(p) if !p.as_os_str().is_empty() => {
+            fs::create_dir_all(p)?;
+            p.to_path_buf()
+        }
+        _ => PathBuf::from("."),
+    };
+    let mut temp = tempfile::Builder::new()
+        .prefix(crate::utils::TEMP_FILE_PREFIX)
+        .tempfile_in(parent)?;
+    temp.write_all(content.as_bytes())?;
+    temp.flush()?;
+    temp.as_file().sync_all()?;
+    Ok(temp)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn project_with_id(id: &str) -> SkillProjectToml {
+        toml::from_str(&format!(
+            "[metadata]\nid = \"{id}\"\nversion = \"1.0.0\"\n\n[dependencies]\n"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_commit_writes_both_files_and_cleans_up() {
+        let dir = TempDir::new().unwrap();
+        let manifest_path = dir.path().join("skill-project.toml");
+        let lock_path = dir.path().join("skills.lock");
+
+        let mut txn = ProjectTransaction::new(&manifest_path, &lock_path);
+        txn.stage_manifest(&project_with_id("app")).unwrap();
+        txn.stage_lock(&ProjectSkillsLock::new_empty()).unwrap();
+        txn.commit().unwrap();
+
+        assert!(std::fs::read_to_string(&manifest_path)
+            .unwrap()
+            .contains("app"));
+        assert!(ProjectSkillsLock::load_from_file(&lock_path).is_ok());
+        let leftovers: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .filter(|name| {
+                name != "skill-project.toml" && name != "skills.lock" && name != TXN_LOCK_FILE
+            })
+            .collect();
+        assert!(leftovers.is_empty(), "left behind: {leftovers:?}");
+    }
+
+    #[test]
+    fn test_commit_waits_for_a_running_commit() {
+        let dir = TempDir::new().unwrap();
+        let manifest_path = dir.path().join("skill-project.toml");
+        let lock_path = dir.path().join("skills.lock");
+        let running = lock_sidecar(&dir.path().join(TXN_LOCK_FILE)).unwrap();
+
+        let mut txn = ProjectTransaction::new(&manifest_path, &lock_path);
+        txn.stage_lock(&ProjectSkillsLock::new_empty()).unwrap();
+        let committer = std::thread::spawn(move || txn.commit());
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        assert!(!lock_path.exists());
+
+        drop(running);
+        committer.join().unwrap().unwrap();
+        assert!(lock_path.exists());
+    }
+
+    #[test]
+    fn test_failed_second_rename_restores_previous_pair() {
+        let dir = TempDir::new().unwrap();
+        let manifest_path = dir.path().join("skill-project.toml");
+        let lock_path = dir.path().join("skills.lock");
+        std::fs::write(&manifest_path, "old manifest").unwrap();
+
+        let mut txn = ProjectTransaction::new(&manifest_path, &lock_path);
+        txn.stage_manifest(&project_with_id("app")).unwrap();
+        txn.stage_lock(&ProjectSkillsLock::new_empty()).unwrap();
+        txn.fail_rename_at = Some(1);
+        let err = txn.commit().unwrap_err();
+
+        assert!(matches!(err, TransactionError::RolledBack { .. }));
+        assert_eq!(
+            std::fs::read_to_string(&manifest_path).unwrap(),
+            "old manifest"
+        );
+        assert!(!lock_path.exists());
+        assert!(!dir.path().join(JOURNAL_FILE).exists());
+        assert!(!backup_path(&manifest_path).exists());
+    }
+
+    #[test]
+    fn test_interrupted_commit_is_recovered_from_journal() {
+        let dir = TempDir::new().unwrap();
+        let manifest_path = dir.path().join("skill-project.toml");
+        let lock_path = dir.path().join("skills.lock");
+
+        // State a killed commit leaves: new manifest renamed in, backup and
+        // journal still present, lock not yet written.
+        std::fs::write(&manifest_path, "new manifest").unwrap();
+        std::fs::write(backup_path(&manifest_path), "old manifest").unwrap();
+        let journal = Journal {
+            files: vec![
+                JournalEntry {
+                    target: manifest_path.clone(),
+                    backup: Some(backup_path(&manifest_path)),
+                },
+                JournalEntry {
+                    target: lock_path.clone(),
+                    backup: None,
+                },
+            ],
+        };
+        std::fs::write(
+            dir.path().join(JOURNAL_FILE),
+            serde_json::to_vec(&journal).unwrap(),
+        )
+        .unwrap();
+
+        assert!(recover_interrupted(&lock_path).unwrap());
+        assert_eq!(
+            std::fs::read_to_string(&manifest_path).unwrap(),
+            "old manifest"
+        );
+        assert!(!lock_path.exists());
+        assert!(!recover_interrupted(&lock_path).unwrap());
+    }
+}
@@ -66,6 +66,7 @@ fn origin_display(origin: &Origin) -> String {
         Origin::Git { url, .. } => url.clone(),
         Origin::Local { path, .. } => path.display().to_string(),
         Origin::ZipUrl { url } => url.clone(),
+        Origin::Pack { path, skill } => format!("{} ({skill})", path.display()),
         Origin::Repository { repo, skill, .. } => format!("{repo}/{skill}"),
     }
 }
@@ -1,10 +1,12 @@
 //! Registry module for skill package registries
 
 pub mod auth;
+pub mod cache;
 pub mod client;
 pub mod config;
 
 pub use auth::{ApiKey, Auth, GitHubPat, SshKey};
+pub use cache::{ResolverCache, DEFAULT_RESOLVER_CACHE_TTL_SECS};
 pub use client::{IndexEntry, RegistryClient};
 pub use config::{
     AuthConfig, DefaultRegistryConfig, RegistriesConfig, RegistryConfig, RegistryConfigManager,
@@ -1,7 +1,10 @@
 //! Authentication for registry access
+//!
+//! Secrets are looked up by the configured env var name: the environment first,
+//! then the credential store (see [`crate::core::credentials`]).
 
+use crate::core::credentials::resolve_secret;
 use crate::core::service::ServiceError;
-use std::env;
 use std::path::PathBuf;
 
 /// Authentication trait for registry access
@@ -22,7 +25,7 @@ pub struct GitHubPat {
 
 impl GitHubPat {
     pub fn new(env_var: String) -> Self {
-        let token = env::var(&env_var).ok();
+        let token = resolve_secret(&env_var);
         Self { token, env_var }
     }
 }
@@ -32,7 +35,7 @@ impl Auth for GitHubPat {
     fn get_auth_header(&self) -> Result<String, ServiceError> {
         let token = self.token.as_ref().ok_or_else(|| {
             ServiceError::Custom(format!(
-                "GitHub token not found. Set {} environment variable",
+                "GitHub token not found. Set {0} environment variable or run `fastskill credentials set {0}`",
                 self.env_var
             ))
         })?;
@@ -40,7 +43,7 @@ impl Auth for GitHubPat {
     }
 
     fn is_configured(&self) -> bool {
-        self.token.is_some() || env::var(&self.env_var).is_ok()
+        self.token.is_some()
     }
 }
 
@@ -78,7 +81,7 @@ pub struct ApiKey {
 
 impl ApiKey {
     pub fn new(env_var: String) -> Self {
-        let key = env::var(&env_var).ok();
+        let key = resolve_secret(&env_var);
         Self { key, env_var }
     }
 }
@@ -86,21 +89,16 @@ impl ApiKey {
 #[async_trait::async_trait]
 impl Auth for ApiKey {
     fn get_auth_header(&self) -> Result<String, ServiceError> {
-        let key = if let Some(ref k) = self.key {
-            k.as_str()
-        } else if let Ok(k) = env::var(&self.env_var) {
-            // Store in self for future use (but we can't mutate, so just use the value)
-            return Ok(format!("Bearer {}", k));
-        } else {
-            return Err(ServiceError::Custom(format!(
-                "API key not found. Set {} environment variable",
+        let key = self.key.as_ref().ok_or_else(|| {
+            ServiceError::Custom(format!(
+                "API key not found. Set {0} environment variable or run `fastskill credentials set {0}`",
                 self.env_var
-            )));
-        };
+            ))
+        })?;
         Ok(format!("Bearer {}", key))
     }
 
     fn is_configured(&self) -> bool {
-        self.key.is_some() || env::var(&self.env_var).is_ok()
+        self.key.is_some()
     }
 }
@@ -0,0 +1,245 @@
+//! Persistent resolver cache for registry index lookups
+//!
+//! Resolving a project against an http-registry fetches each skill's index file
+//! (all versions plus metadata), usually more than once per install (version
+//! selection, then download). The cache keeps those index entries on disk at
+//! `<cache dir>/fastskill/resolver-cache.json`, keyed by registry index URL and
+//! skill id. An entry is reused until its TTL expires or the registry's
+//! generation marker (`<index_url>/generation`, rewritten on every publish)
+//! changes, which drops everything cached for that registry.
+
+use crate::core::registry::client::IndexEntry;
+use crate::core::service::ServiceError;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+/// On-disk format version; a file with another version is discarded.
+const CACHE_FORMAT_VERSION: u32 = 1;
+
+/// How long a cached index stays fresh when the registry publishes no generation.
+pub const DEFAULT_RESOLVER_CACHE_TTL_SECS: i64 = 600;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    version: u32,
+    #[serde(default)]
+    registries: BTreeMap<String, RegistryBucket>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RegistryBucket {
+    /// Generation the registry reported when these entries were fetched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    generation: Option<String>,
+    #[serde(default)]
+    skills: BTreeMap<String, CachedSkill>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedSkill {
+    fetched_at: DateTime<Utc>,
+    entries: Vec<IndexEntry>,
+}
+
+/// Disk-backed cache of registry index entries (skill id → versions + metadata).
+#[derive(Debug)]
+pub struct ResolverCache {
+    path: PathBuf,
+    ttl: Duration,
+    state: Mutex<CacheFile>,
+}
+
+impl ResolverCache {
+    /// Default location: `<dirs::cache_dir()>/fastskill/resolver-cache.json`.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::cache_dir().map(|d| d.join("fastskill").join("resolver-cache.json"))
+    }
+
+    /// Open the cache at `path`. A missing, unreadable or outdated file starts
+    /// an empty cache rather than failing the install.
+    pub fn load(path: PathBuf, ttl: Duration) -> Self {
+        let state = match std::fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str::<CacheFile>(&content) {
+                Ok(file) if file.version == CACHE_FORMAT_VERSION => file,
+                Ok(_) => CacheFile::default(),
+                Err(e) => {
+                    tracing::warn!(
+                        "Ignoring unreadable resolver cache {}: {}",
+                        path.display(),
+                        e
+                    );
+                    CacheFile::default()
+                }
+            },
+            Err(_) => CacheFile::default(),
+        };
+        Self {
+            path,
+            ttl,
+            state: Mutex::new(state),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn state(&self) -> MutexGuard<'_, CacheFile> {
+        match self.state.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Cached index entries for `skill_id`, if fresh for `generation`.
+    pub fn get(
+        &self,
+        registry: &str,
+        generation: Option<&str>,
+        skill_id: &str,
+    ) -> Option<Vec<IndexEntry>> {
+        let state = self.state();
+        let bucket = state.registries.get(registry)?;
+        if bucket.generation.as_deref() != generation {
+            return None;
+        }
+        let cached = bucket.skills.get(skill_id)?;
+        if Utc::now() - cached.fetched_at > self.ttl {
+            return None;
+        }
+        Some(cached.entries.clone())
+    }
+
+    /// Record freshly fetched entries and persist the cache. A generation change
+    /// evicts every other skill cached for `registry`.
+    pub fn insert(
+        &self,
+        registry: &str,
+        generation: Option<&str>,
+        skill_id: &str,
+        entries: Vec<IndexEntry>,
+    ) -> Result<(), ServiceError> {
+        let mut state = self.state();
+        state.version = CACHE_FORMAT_VERSION;
+        let bucket = state.registries.entry(registry.to_string()).or_default();
+        if bucket.generation.as_deref() != generation {
+            bucket.skills.clear();
+            bucket.generation = generation.map(str::to_string);
+        }
+        bucket.skills.insert(
+            skill_id.to_string(),
+            CachedSkill {
+                fetched_at: Utc::now(),
+                entries,
+            },
+        );
+        self.save(&state)
+    }
+
+    /// Drop every cached entry and remove the cache file.
+    pub fn clear(&self) -> Result<(), ServiceError> {
+        let mut state = self.state();
+        *state = CacheFile::default();
+        match std::fs::remove_file(&self.path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save(&self, state: &CacheFile) -> Result<(), ServiceError> {
+        let content = serde_json::to_vec(state).map_err(|e| {
+            ServiceError::Custom(format!("Failed to serialize resolver cache: {}", e))
+        })?;
+        crate::utils::atomic_write(&self.path, &content)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    const REGISTRY: &str = "https://registry.example.com/index";
+
+    fn entries(vers: &str) -> Vec<IndexEntry> {
+        vec![IndexEntry {
+            name: "acme/web".to_string(),
+            vers: vers.to_string(),
+            deps: Vec::new(),
+            cksum: "sha256:aa".to_string(),
+            features: HashMap::new(),
+            yanked: false,
+            links: None,
+            download_url: "https://registry.example.com/dl".to_string(),
+            metadata: None,
+        }]
+    }
+
+    #[test]
+    fn test_insert_persists_across_loads() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("resolver-cache.json");
+        let cache = ResolverCache::load(path.clone(), Duration::minutes(10));
+        assert!(cache.get(REGISTRY, None, "acme/web").is_none());
+        cache
+            .insert(REGISTRY, Some("g1"), "acme/web", entries("1.0.0"))
+            .unwrap();
+
+        let reloaded = ResolverCache::load(path, Duration::minutes(10));
+        let hit = reloaded.get(REGISTRY, Some("g1"), "acme/web").unwrap();
+        assert_eq!(hit[0].vers, "1.0.0");
+        assert!(reloaded
+            .get("https://other.example.com/index", Some("g1"), "acme/web")
+            .is_none());
+    }
+
+    #[test]
+    fn test_generation_change_invalidates_registry() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = ResolverCache::load(
+            temp_dir.path().join("resolver-cache.json"),
+            Duration::minutes(10),
+        );
+        cache
+            .insert(REGISTRY, Some("g1"), "acme/web", entries("1.0.0"))
+            .unwrap();
+        cache
+            .insert(REGISTRY, Some("g1"), "acme/api", entries("2.0.0"))
+            .unwrap();
+
+        assert!(cache.get(REGISTRY, Some("g2"), "acme/web").is_none());
+        cache
+            .insert(REGISTRY, Some("g2"), "acme/web", entries("1.1.0"))
+            .unwrap();
+        assert!(cache.get(REGISTRY, Some("g2"), "acme/api").is_none());
+        assert_eq!(
+            cache.get(REGISTRY, Some("g2"), "acme/web").unwrap()[0].vers,
+            "1.1.0"
+        );
+    }
+
+    #[test]
+    fn test_expired_entries_and_corrupt_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("resolver-cache.json");
+        let cache = ResolverCache::load(path.clone(), Duration::zero());
+        cache
+            .insert(REGISTRY, None, "acme/web", entries("1.0.0"))
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        assert!(cache.get(REGISTRY, None, "acme/web").is_none());
+
+        std::fs::write(&path, b"{not json").unwrap();
+        let cache = ResolverCache::load(path.clone(), Duration::minutes(10));
+        assert!(cache.get(REGISTRY, None, "acme/web").is_none());
+        cache.clear().unwrap();
+        assert!(!path.exists());
+    }
+}
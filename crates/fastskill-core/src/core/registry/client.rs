@@ -2,6 +2,7 @@
 
 use crate::core::metadata::SkillMetadata;
 use crate::core::registry::auth::Auth;
+use crate::core::registry::cache::ResolverCache;
 use crate::core::registry::config::RegistryConfig;
 use crate::core::registry_index::{Dependency as RegistryDependency, IndexMetadata};
 use crate::core::service::ServiceError;
@@ -9,12 +10,17 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use sha2::Digest;
 use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::OnceCell;
 
 /// Registry client for querying and downloading skills
 pub struct RegistryClient {
     config: RegistryConfig,
     client: Client,
     auth: Option<Box<dyn Auth>>,
+    resolver_cache: Option<Arc<ResolverCache>>,
+    /// Registry generation, fetched once per client when the cache is enabled.
+    generation: OnceCell<Option<String>>,
 }
 
 /// Index entry for a skill version
@@ -33,6 +39,31 @@ pub struct IndexEntry {
     pub metadata: Option<IndexMetadata>,
 }
 
+/// Parse a registry index file (newline-delimited JSON). Malformed lines are
+/// reported and skipped; the content comes from the network, so nothing in it
+/// may abort the caller.
+pub fn parse_index_entries(content: &str) -> Vec<IndexEntry> {
+    let mut entries = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        match serde_json::from_str::<IndexEntry>(line) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => {
+                // Log error but continue parsing other lines
+                eprintln!(
+                    "Warning: Failed to parse index entry: {} (line: {})",
+                    e, line
+                );
+            }
+        }
+    }
+    entries
+}
+
 // IndexMetadata is defined in registry_index.rs
 
 // Dependency is defined in registry_index.rs
@@ -66,9 +97,47 @@ impl RegistryClient {
             config,
             client,
             auth,
+            resolver_cache: None,
+            generation: OnceCell::new(),
         })
     }
 
+    /// Serve index lookups from `cache` while fresh, and record fetched ones in it.
+    pub fn with_resolver_cache(mut self, cache: Arc<ResolverCache>) -> Self {
+        self.resolver_cache = Some(cache);
+        self
+    }
+
+    fn authorized(&self, mut request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        if let Some(ref auth) = self.auth {
+            if auth.is_configured() {
+                if let Ok(header_value) = auth.get_auth_header() {
+                    request = request.header("Authorization", header_value);
+                }
+            }
+        }
+        request
+    }
+
+    /// The registry's generation marker (`<index_url>/generation`), rewritten
+    /// by the registry on every publish. `None` when the registry doesn't
+    /// publish one or it can't be fetched; cached entries then expire by TTL.
+    async fn registry_generation(&self) -> Option<String> {
+        self.generation
+            .get_or_init(|| async {
+                let url = self.get_index_url("generation");
+                let response = self.authorized(self.client.get(&url)).send().await.ok()?;
+                if !response.status().is_success() {
+                    return None;
+                }
+                let body = response.text().await.ok()?;
+                let generation = body.trim();
+                (!generation.is_empty()).then(|| generation.to_string())
+            })
+            .await
+            .clone()
+    }
+
     /// Get the index URL for a skill (flat layout: scope/skill-name)
     fn get_index_url(&self, skill_id: &str) -> String {
         // Flat layout: use skill_id directly (e.g., "dev-user/test-skill")
@@ -83,31 +152,49 @@ impl RegistryClient {
     /// Get skill information from registry
     /// Returns all versions for the skill (reads single file with newline-delimited JSON)
     pub async fn get_skill(&self, name: &str) -> Result<Vec<IndexEntry>, ServiceError> {
-        let url = self.get_index_url(name);
-
-        let mut request = self.client.get(&url);
+        let Some(cache) = self.resolver_cache.as_ref() else {
+            return self.fetch_skill_index(name).await;
+        };
 
-        // Add authentication if available
-        if let Some(ref auth) = self.auth {
-            if auth.is_configured() {
-                if let Ok(header_value) = auth.get_auth_header() {
-                    request = request.header("Authorization", header_value);
-                }
+        let generation = self.registry_generation().await;
+        if let Some(entries) = cache.get(&self.config.index_url, generation.as_deref(), name) {
+            return Ok(entries);
+        }
+        let entries = self.fetch_skill_index(name).await?;
+        // Unknown skills aren't cached, so a first publish is picked up immediately.
+        if !entries.is_empty() {
+            if let Err(e) = cache.insert(
+                &self.config.index_url,
+                generation.as_deref(),
+                name,
+                entries.clone(),
+            ) {
+                tracing::warn!("Failed to update resolver cache: {}", e);
             }
         }
+        Ok(entries)
+    }
 
-        let response = request
+    /// Fetch a skill's index file from the registry, bypassing the cache
+    async fn fetch_skill_index(&self, name: &str) -> Result<Vec<IndexEntry>, ServiceError> {
+        let url = self.get_index_url(name);
+
+        let response = self
+            .authorized(self.client.get(&url))
             .send()
             .await
             .map_err(|e| ServiceError::Custom(format!("Failed to fetch skill index: {}", e)))?;
 
-        if !response.status().is_success() {
-            if response.status() == 404 {
+        let status = response.status();
+        #[cfg(feature = "chaos")]
+        let status = crate::core::chaos::upstream_status(status);
+        if !status.is_success() {
+            if status == 404 {
                 return Ok(Vec::new()); // Skill not found
             }
             return Err(ServiceError::Custom(format!(
                 "Failed to fetch skill index: HTTP {}",
-                response.status()
+                status
             )));
         }
 
@@ -116,27 +203,7 @@ impl RegistryClient {
             .await
             .map_err(|e| ServiceError::Custom(format!("Failed to read index file: {}", e)))?;
 
-        // Parse line-by-line (newline-delimited JSON)
-        let mut entries = Vec::new();
-        for line in content.lines() {
-            let line = line.trim();
-            if line.is_empty() {
-                continue;
-            }
-
-            match serde_json::from_str::<IndexEntry>(line) {
-                Ok(entry) => entries.push(entry),
-                Err(e) => {
-                    // Log error but continue parsing other lines
-                    eprintln!(
-                        "Warning: Failed to parse index entry: {} (line: {})",
-                        e, line
-                    );
-                }
-            }
-        }
-
-        Ok(entries)
+        Ok(parse_index_entries(&content))
     }
 
     /// Get index entry for a specific skill version
@@ -158,13 +225,21 @@ impl RegistryClient {
         Ok(versions)
     }
 
-    /// Get latest version for a skill (excluding pre-releases by default)
+    /// Get latest version for a skill (excluding pre-releases by default).
+    /// Yanked versions are never the latest.
     pub async fn get_latest_version(
         &self,
         name: &str,
         include_pre_release: bool,
     ) -> Result<Option<String>, ServiceError> {
-        let versions = self.get_versions(name).await?;
+        let mut versions: Vec<String> = self
+            .get_skill(name)
+            .await?
+            .into_iter()
+            .filter(|e| !e.yanked)
+            .map(|e| e.vers)
+            .collect();
+        crate::core::version::sort_versions_desc(&mut versions);
 
         if versions.is_empty() {
             return Ok(None);
@@ -231,33 +306,38 @@ impl RegistryClient {
             )));
         }
 
-        let mut request = self.client.get(&entry.download_url);
-
-        // Add authentication if available
-        if let Some(ref auth) = self.auth {
-            if auth.is_configured() {
-                if let Ok(header_value) = auth.get_auth_header() {
-                    request = request.header("Authorization", header_value);
-                }
-            }
-        }
-
-        let response = request
+        let response = self
+            .authorized(self.client.get(&entry.download_url))
             .send()
             .await
             .map_err(|e| ServiceError::Custom(format!("Failed to download package: {}", e)))?;
 
-        if !response.status().is_success() {
+        let status = response.status();
+        #[cfg(feature = "chaos")]
+        let status = crate::core::chaos::upstream_status(status);
+        if !status.is_success() {
             return Err(ServiceError::Custom(format!(
                 "Failed to download package: HTTP {}",
-                response.status()
+                status
             )));
         }
 
+        #[cfg(feature = "chaos")]
+        if crate::core::chaos::should_inject(crate::core::chaos::Fault::DownloadFailure) {
+            return Err(ServiceError::Custom(
+                "Failed to read package data: connection reset (injected fault)".to_string(),
+            ));
+        }
         let bytes = response
             .bytes()
             .await
             .map_err(|e| ServiceError::Custom(format!("Failed to read package data: {}", e)))?;
+        #[cfg(feature = "chaos")]
+        let bytes = {
+            let mut bytes = bytes.to_vec();
+            crate::core::chaos::maybe_corrupt_zip(&mut bytes);
+            bytes
+        };
 
         // Verify checksum
         let calculated = format!("sha256:{:x}", sha2::Sha256::digest(&bytes));
@@ -271,6 +351,32 @@ impl RegistryClient {
         Ok(bytes.to_vec())
     }
 
+    /// Size in bytes of a skill package, from the `Content-Length` of a HEAD
+    /// request to its download URL. `None` when the version is not in the
+    /// index or the server does not report a length.
+    pub async fn download_size(
+        &self,
+        name: &str,
+        version: &str,
+    ) -> Result<Option<u64>, ServiceError> {
+        let Some(entry) = self.get_version(name, version).await? else {
+            return Ok(None);
+        };
+        let response = self
+            .authorized(self.client.head(&entry.download_url))
+            .send()
+            .await
+            .map_err(|e| ServiceError::Custom(format!("Failed to query package size: {}", e)))?;
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+        Ok(response
+            .headers()
+            .get(reqwest::header::CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok()))
+    }
+
     /// Search skills in registry (basic implementation - scans index)
     pub async fn search(&self, _query: &str) -> Result<Vec<SkillMetadata>, ServiceError> {
         // For now, return empty - full search requires scanning entire index
@@ -460,6 +566,18 @@ mod tests {
         assert_eq!(latest, Some("1.0.0".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_get_latest_version_skips_yanked() {
+        let server = MockServer::start().await;
+        let mut yanked = make_entry("p", "2.0.0", "http://x/dl", "sha256:bb");
+        yanked.yanked = true;
+        let entries = vec![make_entry("p", "1.0.0", "http://x/dl", "sha256:aa"), yanked];
+        mount_index(&server, "p", &entries).await;
+        let client = RegistryClient::new(config_for(&server.uri(), None)).unwrap();
+        let latest = client.get_latest_version("p", false).await.unwrap();
+        assert_eq!(latest, Some("1.0.0".to_string()));
+    }
+
     #[tokio::test]
     async fn test_get_latest_version_includes_prerelease() {
         let server = MockServer::start().await;
@@ -572,6 +690,26 @@ mod tests {
         assert!(client.download("n", "1.0.0").await.is_err());
     }
 
+    #[tokio::test]
+    async fn test_download_size_from_head_request() {
+        let server = MockServer::start().await;
+        let dl_url = format!("{}/dl", server.uri());
+        let entries = vec![make_entry("s", "1.0.0", &dl_url, "sha256:aa")];
+        mount_index(&server, "s", &entries).await;
+        Mock::given(method("HEAD"))
+            .and(path("/dl"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0u8; 2048]))
+            .mount(&server)
+            .await;
+
+        let client = RegistryClient::new(config_for(&server.uri(), None)).unwrap();
+        assert_eq!(
+            client.download_size("s", "1.0.0").await.unwrap(),
+            Some(2048)
+        );
+        assert_eq!(client.download_size("s", "9.9.9").await.unwrap(), None);
+    }
+
     #[tokio::test]
     async fn test_download_http_error() {
         let server = MockServer::start().await;
@@ -588,6 +726,81 @@ mod tests {
         assert!(client.download("e", "1.0.0").await.is_err());
     }
 
+    #[tokio::test]
+    async fn test_resolver_cache_serves_repeat_lookups() {
+        let server = MockServer::start().await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join("resolver-cache.json");
+        let entries = vec![make_entry("c", "1.0.0", "http://x/dl", "sha256:aa")];
+        Mock::given(method("GET"))
+            .and(path("/generation"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("g1\n"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/c"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(index_body(&entries)))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let cache = Arc::new(ResolverCache::load(
+            cache_path.clone(),
+            chrono::Duration::minutes(10),
+        ));
+        let client = RegistryClient::new(config_for(&server.uri(), None))
+            .unwrap()
+            .with_resolver_cache(cache);
+        assert_eq!(client.get_versions("c").await.unwrap(), vec!["1.0.0"]);
+        assert!(client.get_version("c", "1.0.0").await.unwrap().is_some());
+
+        // A new process reuses the persisted entries for the same generation.
+        let reloaded = Arc::new(ResolverCache::load(
+            cache_path,
+            chrono::Duration::minutes(10),
+        ));
+        let client = RegistryClient::new(config_for(&server.uri(), None))
+            .unwrap()
+            .with_resolver_cache(reloaded);
+        assert_eq!(client.get_skill("c").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_resolver_cache_refetches_on_new_generation() {
+        let server = MockServer::start().await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache = Arc::new(ResolverCache::load(
+            temp_dir.path().join("resolver-cache.json"),
+            chrono::Duration::minutes(10),
+        ));
+        cache
+            .insert(
+                &server.uri(),
+                Some("g1"),
+                "c",
+                vec![make_entry("c", "1.0.0", "http://x/dl", "sha256:aa")],
+            )
+            .unwrap();
+        Mock::given(method("GET"))
+            .and(path("/generation"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("g2"))
+            .mount(&server)
+            .await;
+        let fresh = vec![
+            make_entry("c", "1.0.0", "http://x/dl", "sha256:aa"),
+            make_entry("c", "1.1.0", "http://x/dl", "sha256:bb"),
+        ];
+        mount_index(&server, "c", &fresh).await;
+
+        let client = RegistryClient::new(config_for(&server.uri(), None))
+            .unwrap()
+            .with_resolver_cache(cache);
+        assert_eq!(
+            client.get_versions("c").await.unwrap(),
+            vec!["1.1.0", "1.0.0"]
+        );
+    }
+
     #[tokio::test]
     async fn test_search_returns_empty() {
         let client = RegistryClient::new(config_for("http://example.com/index", None)).unwrap();
@@ -102,9 +102,23 @@ pub fn update_skill_version(
 
     writeln!(file, "{}", line).map_err(ServiceError::Io)?;
 
+    bump_generation(registry_path)?;
+
     Ok(())
 }
 
+/// Name of the registry generation marker at the index root. Skill index
+/// files live at `{org}/{package}`, so a top-level file can't collide.
+pub const GENERATION_FILE: &str = "generation";
+
+/// Rewrite the generation marker so clients drop their cached index entries.
+/// Called after every index change; the value is opaque to clients.
+pub fn bump_generation(registry_path: &Path) -> Result<(), ServiceError> {
+    let generation = Utc::now().format("%Y%m%dT%H%M%S%.9fZ").to_string();
+    crate::utils::atomic_write(&registry_path.join(GENERATION_FILE), generation.as_bytes())
+        .map_err(ServiceError::Io)
+}
+
 /// Read all versions for a skill from the index file
 /// Parses newline-delimited JSON format
 pub fn read_skill_versions(
@@ -130,9 +144,55 @@ pub fn read_skill_versions(
 
     let content = fs::read_to_string(&safe_index_path).map_err(ServiceError::Io)?;
 
+    Ok(parse_index_lines(&content, skill_id))
+}
+
+/// Mark a published version as yanked (or restore it with `yanked = false`).
+/// The index line is rewritten in place rather than appended, so the file
+/// keeps one entry per version; malformed lines are left untouched. Returns
+/// the updated entry, or `SkillNotFound` if the version was never published.
+pub fn set_version_yanked(
+    registry_path: &Path,
+    skill_id: &str,
+    version: &str,
+    yanked: bool,
+) -> Result<VersionEntry, ServiceError> {
+    let not_found = || ServiceError::SkillNotFound(format!("{}@{}", skill_id, version));
+    let index_path = get_skill_index_path(registry_path, skill_id)?;
+    let content = match fs::read_to_string(&index_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Err(not_found()),
+        Err(e) => return Err(ServiceError::Io(e)),
+    };
+
+    let mut updated = None;
+    let mut rewritten = String::with_capacity(content.len());
+    for line in content.lines() {
+        match serde_json::from_str::<VersionEntry>(line.trim()) {
+            Ok(mut entry) if entry.vers == version => {
+                entry.yanked = yanked;
+                let line = serde_json::to_string(&entry).map_err(|e| {
+                    ServiceError::Custom(format!("Failed to serialize index entry: {}", e))
+                })?;
+                rewritten.push_str(&line);
+                updated = Some(entry);
+            }
+            _ => rewritten.push_str(line),
+        }
+        rewritten.push('\n');
+    }
+    let entry = updated.ok_or_else(not_found)?;
+
+    crate::utils::atomic_write(&index_path, rewritten.as_bytes()).map_err(ServiceError::Io)?;
+    bump_generation(registry_path)?;
+    Ok(entry)
+}
+
+/// Parse the newline-delimited JSON of a skill's index file. Malformed lines
+/// are logged and skipped so one corrupted entry doesn't hide the others.
+pub fn parse_index_lines(content: &str, skill_id: &str) -> Vec<VersionEntry> {
     let mut entries = Vec::new();
 
-    // Parse line-by-line (newline-delimited JSON)
     for line in content.lines() {
         let line = line.trim();
         if line.is_empty() {
@@ -154,7 +214,7 @@ pub fn read_skill_versions(
         }
     }
 
-    Ok(entries)
+    entries
 }
 
 /// Version metadata entry
@@ -224,7 +284,7 @@ pub struct ListSkillsOptions {
 }
 
 /// Summary of a skill from the registry index
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct SkillSummary {
     pub id: String,
     pub scope: String,
@@ -238,6 +298,9 @@ pub struct SkillSummary {
     pub published_at: Option<DateTime<Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub versions: Option<Vec<String>>,
+    /// Set while the skill is retired and awaiting removal from the index
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retired: Option<crate::core::retirement::Retirement>,
 }
 
 /// Serialize DateTime<Utc> as ISO 8601 string
@@ -353,7 +416,9 @@ pub async fn scan_registry_index(
 
         // Try to determine skill_id from path
         // Path format: {registry_path}/{scope}/{name}
-        let relative_path = path.strip_prefix(registry_path).map_err(|e| {
+        // WalkDir yields paths under the canonical root, so strip that rather
+        // than `registry_path`, which may be relative or go through a symlink.
+        let relative_path = path.strip_prefix(&canonical_registry).map_err(|e| {
             ServiceError::Io(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
                 format!("Failed to strip prefix: {}", e),
@@ -365,6 +430,10 @@ pub async fn scan_registry_index(
             .filter_map(|c| c.as_os_str().to_str())
             .collect();
 
+        if parts == [GENERATION_FILE] {
+            continue;
+        }
+
         // Must have at least 2 parts (scope/name)
         if parts.len() < 2 {
             warn!("Skipping invalid path structure: {:?}", path);
@@ -405,12 +474,18 @@ pub async fn scan_registry_index(
         }
     }
 
+    let retirements =
+        crate::core::retirement::load_retirements(registry_path).unwrap_or_else(|e| {
+            error!("Failed to read skill retirements: {}", e);
+            Default::default()
+        });
     let mut summaries = Vec::new();
 
     for (skill_id, entries) in skill_map {
         let (scope, name) = extract_scope(&skill_id).ok_or_else(|| {
             ServiceError::Custom(format!("Invalid skill_id format: {}", skill_id))
         })?;
+        let retired = retirements.get(&skill_id).cloned();
 
         if options.all_versions {
             // Return one summary per version
@@ -454,6 +529,7 @@ pub async fn scan_registry_index(
                     latest_version: entry.vers.clone(),
                     published_at,
                     versions: None,
+                    retired: retired.clone(),
                 });
             }
         } else {
@@ -498,6 +574,7 @@ pub async fn scan_registry_index(
                     latest_version: latest_entry.vers.clone(),
                     published_at,
                     versions,
+                    retired,
                 });
             }
         }
@@ -607,6 +684,7 @@ mod tests {
         };
 
         update_skill_version(skill_id, "1.0.0", &metadata, registry_path).unwrap();
+        assert!(registry_path.join(GENERATION_FILE).exists());
 
         // Read file and verify it's compact JSON (not pretty-printed)
         let index_path = get_skill_index_path(registry_path, skill_id).unwrap();
@@ -0,0 +1,380 @@
+//! Blob storage behind `fastskill serve --registry`
+//!
+//! A self-hosted registry keeps two directories under one root: `index/`, the
+//! crates.io-style index managed by [`crate::core::registry_index`], and
+//! `blobs/`, the published skill ZIPs at `{scope}/{name}/{version}.zip`.
+//! Publishing validates the uploaded package, stores its ZIP and appends the
+//! version to the index with a `download_url` pointing back at the server, so
+//! `http-registry` clients resolve and download from it like any registry.
+
+use crate::core::install::derive_skill_id_and_version;
+use crate::core::metadata::parse_yaml_frontmatter;
+use crate::core::registry_index::{
+    read_skill_versions, set_version_yanked, update_skill_version, IndexMetadata, VersionEntry,
+    VersionMetadata,
+};
+use crate::core::service::{ServiceError, SkillId};
+use crate::storage::zip::ZipHandler;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+/// Directory of the registry index under the registry root
+pub const INDEX_DIR: &str = "index";
+
+/// Directory of the published ZIPs under the registry root
+pub const BLOBS_DIR: &str = "blobs";
+
+/// Largest skill ZIP a publish accepts
+pub const MAX_PUBLISH_BYTES: usize = 50 * 1024 * 1024;
+
+/// Index and blobs of a self-hosted registry
+pub struct RegistryStore {
+    index_path: PathBuf,
+    blobs_path: PathBuf,
+    public_url: String,
+    /// Serializes index writes, so two uploads of one version can't both pass
+    /// the duplicate check and a yank's rewrite can't drop a version appended
+    /// while it ran
+    index_lock: Mutex<()>,
+}
+
+impl RegistryStore {
+    /// Open the registry under `root`, creating `index/` and `blobs/`.
+    /// `public_url` is the server address clients reach, e.g.
+    /// `https://skills.example.com`; download URLs in the index start with it.
+    pub fn open(root: &Path, public_url: &str) -> Result<Self, ServiceError> {
+        let index_path = root.join(INDEX_DIR);
+        let blobs_path = root.join(BLOBS_DIR);
+        std::fs::create_dir_all(&index_path)?;
+        std::fs::create_dir_all(&blobs_path)?;
+        Ok(Self {
+            index_path,
+            blobs_path,
+            public_url: public_url.trim_end_matches('/').to_string(),
+            index_lock: Mutex::new(()),
+        })
+    }
+
+    /// The registry index directory
+    pub fn index_path(&self) -> &Path {
+        &self.index_path
+    }
+
+    fn lock_index(&self) -> Result<MutexGuard<'_, ()>, ServiceError> {
+        self.index_lock
+            .lock()
+            .map_err(|_| ServiceError::Custom("Registry index lock poisoned".to_string()))
+    }
+
+    /// URL clients download `skill@version` from
+    pub fn download_url(&self, skill: &SkillId, version: &str) -> String {
+        format!(
+            "{}/api/v1/registry/download/{}/{}",
+            self.public_url, skill, version
+        )
+    }
+
+    /// Where the ZIP of `scope/name@version` is stored. Every component is
+    /// validated, so the path always stays under `blobs/`.
+    fn blob_path(&self, scope: &str, name: &str, version: &str) -> Result<PathBuf, ServiceError> {
+        let skill = SkillId::scoped(scope, name)?;
+        let version = semver::Version::parse(version)
+            .map_err(|e| ServiceError::Validation(format!("Invalid version '{}': {}", version, e)))?
+            .to_string();
+        Ok(self
+            .blobs_path
+            .join(scope)
+            .join(skill.name())
+            .join(format!("{}.zip", version)))
+    }
+
+    /// The published ZIP of `scope/name@version`
+    pub fn read_blob(
+        &self,
+        scope: &str,
+        name: &str,
+        version: &str,
+    ) -> Result<Vec<u8>, ServiceError> {
+        let path = self.blob_path(scope, name, version)?;
+        match std::fs::read(&path) {
+            Ok(bytes) => Ok(bytes),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(ServiceError::SkillNotFound(
+                format!("{}/{}@{}", scope, name, version),
+            )),
+            Err(e) => Err(ServiceError::Io(e)),
+        }
+    }
+
+    /// Publish a skill ZIP (SKILL.md at its root or in one top-level folder).
+    ///
+    /// The id and version come from the package like on install. A package
+    /// whose id has no scope is published under `scope`; a scoped id must
+    /// match `scope` when one is given. Versions must be semver and can't be
+    /// published twice; yank a version instead of replacing it.
+    pub fn publish(&self, scope: Option<&str>, bytes: &[u8]) -> Result<VersionEntry, ServiceError> {
+        if bytes.is_empty() {
+            return Err(ServiceError::Validation("Package is empty".to_string()));
+        }
+        if bytes.len() > MAX_PUBLISH_BYTES {
+            return Err(ServiceError::Validation(format!(
+                "Package is larger than {} bytes",
+                MAX_PUBLISH_BYTES
+            )));
+        }
+
+        let temp_dir = tempfile::TempDir::new()?;
+        ZipHandler::new()?.extract_reader_to_dir(Cursor::new(bytes), temp_dir.path())?;
+        let skill_dir =
+            crate::storage::git::validate_cloned_skill(temp_dir.path()).map_err(|_| {
+                ServiceError::Validation(
+                    "Package does not contain a SKILL.md at its root or in a top-level folder"
+                        .to_string(),
+                )
+            })?;
+        let content = std::fs::read_to_string(skill_dir.join("SKILL.md"))?;
+        let frontmatter = parse_yaml_frontmatter(&content)?;
+        let (id, version) = derive_skill_id_and_version(&skill_dir, &frontmatter)?;
+        let skill = match (id.scope(), scope) {
+            (Some(declared), Some(scope)) if declared != scope => {
+                return Err(ServiceError::Validation(format!(
+                    "Package id '{}' does not belong to scope '{}'",
+                    id, scope
+                )))
+            }
+            (Some(_), _) => id,
+            (None, Some(scope)) => SkillId::scoped(scope, id.name())?,
+            (None, None) => {
+                return Err(ServiceError::Validation(format!(
+                    "Package id '{}' has no scope; publish it with a scope",
+                    id
+                )))
+            }
+        };
+        let version = semver::Version::parse(&version)
+            .map_err(|e| ServiceError::Validation(format!("Invalid version '{}': {}", version, e)))?
+            .to_string();
+
+        let _guard = self.lock_index()?;
+        if read_skill_versions(&self.index_path, skill.as_str())?
+            .iter()
+            .any(|entry| entry.vers == version)
+        {
+            return Err(ServiceError::AlreadyIndexed(format!(
+                "{}@{}",
+                skill, version
+            )));
+        }
+
+        let blob = self.blob_path(skill.scope().unwrap_or_default(), skill.name(), &version)?;
+        if let Some(parent) = blob.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        crate::utils::atomic_write(&blob, bytes)?;
+
+        let metadata = VersionMetadata {
+            name: skill.to_string(),
+            vers: version.clone(),
+            deps: Vec::new(),
+            cksum: format!("sha256:{:x}", Sha256::digest(bytes)),
+            features: HashMap::new(),
+            yanked: false,
+            links: None,
+            download_url: self.download_url(&skill, &version),
+            published_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            metadata: Some(IndexMetadata {
+                description: Some(frontmatter.description.clone()),
+                author: frontmatter.author.clone(),
+                license: frontmatter.license.clone(),
+                repository: frontmatter
+                    .metadata
+                    .as_ref()
+                    .and_then(|m| m.get("repository").cloned()),
+            }),
+        };
+        update_skill_version(skill.as_str(), &version, &metadata, &self.index_path)?;
+        tracing::info!("Published {}@{}", skill, version);
+
+        read_skill_versions(&self.index_path, skill.as_str())?
+            .into_iter()
+            .find(|entry| entry.vers == version)
+            .ok_or_else(|| ServiceError::SkillNotFound(format!("{}@{}", skill, version)))
+    }
+
+    /// Yank or restore `skill@version`. Runs under the same lock as
+    /// [`publish`](Self::publish), which appends to the file this rewrites.
+    pub fn set_yanked(
+        &self,
+        skill: &str,
+        version: &str,
+        yanked: bool,
+    ) -> Result<VersionEntry, ServiceError> {
+        let _guard = self.lock_index()?;
+        set_version_yanked(&self.index_path, skill, version, yanked)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn skill_zip(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in files {
+            writer
+                .start_file(*name, zip::write::FileOptions::default())
+                .unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn skill_md(name: &str, version: &str) -> String {
+        format!(
+            "---\nname: {}\ndescription: Extracts text\nversion: {}\nlicense: MIT\n---\n# {}\n",
+            name, version, name
+        )
+    }
+
+    fn store(dir: &TempDir) -> RegistryStore {
+        RegistryStore::open(dir.path(), "http://registry.test/").unwrap()
+    }
+
+    #[test]
+    fn test_publish_writes_blob_and_index_entry() {
+        let dir = TempDir::new().unwrap();
+        let store = store(&dir);
+        let bytes = skill_zip(&[("pdf/SKILL.md", &skill_md("pdf", "1.2.0"))]);
+
+        let entry = store.publish(Some("acme"), &bytes).unwrap();
+        assert_eq!(entry.name, "acme/pdf");
+        assert_eq!(entry.vers, "1.2.0");
+        assert_eq!(
+            entry.download_url,
+            "http://registry.test/api/v1/registry/download/acme/pdf/1.2.0"
+        );
+        assert_eq!(entry.cksum, format!("sha256:{:x}", Sha256::digest(&bytes)));
+        let metadata = entry.metadata.unwrap();
+        assert_eq!(metadata.license.as_deref(), Some("MIT"));
+        assert_eq!(store.read_blob("acme", "pdf", "1.2.0").unwrap(), bytes);
+        assert!(dir.path().join("index/acme/pdf").is_file());
+        assert!(dir.path().join("index/generation").is_file());
+    }
+
+    #[test]
+    fn test_publish_rejects_duplicate_version() {
+        let dir = TempDir::new().unwrap();
+        let store = store(&dir);
+        let bytes = skill_zip(&[("SKILL.md", &skill_md("pdf", "1.0.0"))]);
+        store.publish(Some("acme"), &bytes).unwrap();
+        assert!(matches!(
+            store.publish(Some("acme"), &bytes),
+            Err(ServiceError::AlreadyIndexed(_))
+        ));
+
+        let next = skill_zip(&[("SKILL.md", &skill_md("pdf", "1.1.0"))]);
+        store.publish(Some("acme"), &next).unwrap();
+        assert_eq!(
+            read_skill_versions(store.index_path(), "acme/pdf")
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_yank_during_publishes_keeps_every_version() {
+        let dir = TempDir::new().unwrap();
+        let store = store(&dir);
+        let first = skill_zip(&[("SKILL.md", &skill_md("pdf", "1.0.0"))]);
+        store.publish(Some("acme"), &first).unwrap();
+
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                for minor in 1..=20 {
+                    let bytes =
+                        skill_zip(&[("SKILL.md", &skill_md("pdf", &format!("1.{}.0", minor)))]);
+                    store.publish(Some("acme"), &bytes).unwrap();
+                }
+            });
+            scope.spawn(|| {
+                for round in 0..40 {
+                    store
+                        .set_yanked("acme/pdf", "1.0.0", round % 2 == 0)
+                        .unwrap();
+                }
+            });
+        });
+
+        let versions = read_skill_versions(store.index_path(), "acme/pdf").unwrap();
+        assert_eq!(versions.len(), 21, "a publish was lost to a yank");
+        let first = versions.iter().find(|v| v.vers == "1.0.0").unwrap();
+        assert!(!first.yanked);
+        assert!(matches!(
+            store.set_yanked("acme/pdf", "9.9.9", true),
+            Err(ServiceError::SkillNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_publish_scope_rules() {
+        let dir = TempDir::new().unwrap();
+        let store = store(&dir);
+        let unscoped = skill_zip(&[("SKILL.md", &skill_md("pdf", "1.0.0"))]);
+        assert!(store.publish(None, &unscoped).is_err());
+
+        let scoped = skill_zip(&[(
+            "SKILL.md",
+            "---\nname: pdf\ndescription: d\nversion: 1.0.0\nmetadata:\n  id: acme/pdf\n---\n",
+        )]);
+        assert!(store.publish(Some("other"), &scoped).is_err());
+        assert_eq!(store.publish(None, &scoped).unwrap().name, "acme/pdf");
+    }
+
+    #[test]
+    fn test_publish_rejects_invalid_packages() {
+        let dir = TempDir::new().unwrap();
+        let store = store(&dir);
+        assert!(store.publish(Some("acme"), b"").is_err());
+        assert!(store.publish(Some("acme"), b"not a zip").is_err());
+        let no_skill = skill_zip(&[("README.md", "hi")]);
+        assert!(store.publish(Some("acme"), &no_skill).is_err());
+        let bad_version = skill_zip(&[("SKILL.md", &skill_md("pdf", "latest"))]);
+        assert!(store.publish(Some("acme"), &bad_version).is_err());
+        let valid = skill_zip(&[("SKILL.md", &skill_md("pdf", "1.0.0"))]);
+        assert!(store.publish(Some("../acme"), &valid).is_err());
+    }
+
+    #[test]
+    fn test_publish_rejects_traversal_entries() {
+        let dir = TempDir::new().unwrap();
+        let store = store(&dir);
+        let bytes = skill_zip(&[
+            ("SKILL.md", &skill_md("pdf", "1.0.0")),
+            ("../../escaped.txt", "x"),
+        ]);
+        assert!(store.publish(Some("acme"), &bytes).is_err());
+        assert!(read_skill_versions(store.index_path(), "acme/pdf")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_read_blob_rejects_traversal() {
+        let dir = TempDir::new().unwrap();
+        let store = store(&dir);
+        std::fs::write(dir.path().join("secret.zip"), b"secret").unwrap();
+        assert!(store.read_blob("..", "..", "1.0.0").is_err());
+        assert!(store.read_blob("acme", "../..", "1.0.0").is_err());
+        assert!(store.read_blob("acme", "pdf", "../../../secret").is_err());
+        assert!(matches!(
+            store.read_blob("acme", "pdf", "1.0.0"),
+            Err(ServiceError::SkillNotFound(_))
+        ));
+    }
+}
@@ -6,13 +6,18 @@
 //! silently**. The CLI/serve edge injects the provider via
 //! [`FastSkillService::with_embedding_service`].
 
+use crate::core::cancel::{cancellable, check};
+use crate::core::change_detection::calculate_skill_hash;
+use crate::core::chunking::skill_chunks;
 use crate::core::embedding::EmbeddingService;
 use crate::core::metadata::parse_yaml_frontmatter;
 use crate::core::service::{FastSkillService, ServiceError};
-use crate::core::vector_index::VectorIndexService;
+use crate::core::vector_index::{IndexedSkill, VectorIndexService};
+use futures::StreamExt;
 use sha2::{Digest, Sha256};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Progress datum emitted per skill as reindex proceeds. The core emits neutral
 /// data; the caller (CLI) decides how to render it (HTTP passes no observer).
@@ -23,33 +28,105 @@ pub struct ReindexProgress {
     pub skill_id: String,
 }
 
+/// Which skills a reindex re-embeds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReindexMode {
+    /// Only skills whose directory hash ([`calculate_skill_hash`]) differs
+    /// from the indexed `file_hash`, plus new ones
+    #[default]
+    Incremental,
+    /// Every skill, regardless of hashes
+    Full,
+    /// Every skill, into a new index that replaces the current one only when
+    /// every skill was embedded (switching embedding models; see
+    /// [`VectorIndexService::begin_migration`])
+    Migrate,
+}
+
 /// Outcome of a reindex call. `reindexed=false` + a `reason` means it was skipped
 /// (no provider), which is a success, not a failure (ADR-0002).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ReindexOutcome {
     pub reindexed: bool,
+    /// Skills (re-)embedded
     pub count: usize,
+    /// Skills left alone because their hash was unchanged
+    pub unchanged: usize,
+    /// Index entries dropped because the skill is no longer on disk
+    pub removed: usize,
     pub reason: Option<String>,
 }
 
 impl ReindexOutcome {
     pub(crate) fn skipped(reason: &str) -> Self {
         ReindexOutcome {
-            reindexed: false,
-            count: 0,
             reason: Some(reason.to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+/// Embedding batches a reindex keeps in flight at once unless told otherwise.
+pub const DEFAULT_REINDEX_CONCURRENCY: usize = 5;
+
+/// How a reindex runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReindexOptions {
+    pub mode: ReindexMode,
+    /// Embedding batches in flight at once (at least one). Each batch holds up
+    /// to [`EmbeddingService::batch_size`] skills.
+    pub concurrency: usize,
+}
+
+impl Default for ReindexOptions {
+    fn default() -> Self {
+        Self {
+            mode: ReindexMode::default(),
+            concurrency: DEFAULT_REINDEX_CONCURRENCY,
         }
     }
 }
 
 impl FastSkillService {
-    /// Reindex the vector index for `skills_dir` (defaults to the configured skill
-    /// storage path). Skips silently with an outcome reason when no embedding
-    /// provider is injected. `observer`, if provided, is called once per skill.
+    /// Incrementally reindex the vector index for `skills_dir` (defaults to the
+    /// configured skill storage path). Skips silently with an outcome reason
+    /// when no embedding provider is injected. `observer`, if provided, is
+    /// called once per skill.
     pub async fn reindex(
         &self,
         skills_dir: Option<&Path>,
         observer: Option<&(dyn Fn(ReindexProgress) + Send + Sync)>,
+    ) -> Result<ReindexOutcome, ServiceError> {
+        self.reindex_with_mode(skills_dir, observer, ReindexMode::Incremental)
+            .await
+    }
+
+    /// [`reindex`](Self::reindex) with an explicit [`ReindexMode`].
+    pub async fn reindex_with_mode(
+        &self,
+        skills_dir: Option<&Path>,
+        observer: Option<&(dyn Fn(ReindexProgress) + Send + Sync)>,
+        mode: ReindexMode,
+    ) -> Result<ReindexOutcome, ServiceError> {
+        let options = ReindexOptions {
+            mode,
+            ..Default::default()
+        };
+        self.reindex_with_options(skills_dir, observer, options)
+            .await
+    }
+
+    /// [`reindex`](Self::reindex) with explicit [`ReindexOptions`].
+    ///
+    /// Skills are read and hashed first; the ones needing new embeddings are
+    /// then embedded in batches of [`EmbeddingService::batch_size`], with up to
+    /// `options.concurrency` batches in flight. Progress is reported as each
+    /// skill settles, so skill order in the observer is not the directory order.
+    pub async fn reindex_with_options(
+        &self,
+        skills_dir: Option<&Path>,
+        observer: Option<&(dyn Fn(ReindexProgress) + Send + Sync)>,
+        options: ReindexOptions,
     ) -> Result<ReindexOutcome, ServiceError> {
         // No provider ⇒ skip silently (the common, non-configured case).
         let Some(embedding_service) = self.embedding_service() else {
@@ -74,16 +151,24 @@ impl FastSkillService {
 
         let skill_files = find_skill_files(&dir)?;
 
+        // Embeddings from another model cannot share an index with the stored
+        // ones; only a migration may write them, and into a new index.
+        let migrate = options.mode == ReindexMode::Migrate;
+        let target: Arc<dyn VectorIndexService> = if migrate {
+            vector_index_service.begin_migration().await?
+        } else {
+            if let Some(mismatch) = vector_index_service.mismatch().await? {
+                return Err(mismatch.into());
+            }
+            vector_index_service.clone()
+        };
+
         if skill_files.is_empty() {
             tracing::info!("No skills found in {}", dir.display());
-            return Ok(ReindexOutcome {
-                reindexed: true,
-                count: 0,
-                reason: None,
-            });
         }
 
         let total = skill_files.len();
+        let chunk_size = self.config().embedding.as_ref().and_then(|e| e.chunk_size);
 
         // Collect current skill IDs so stale index entries (skills removed from
         // disk since the last reindex) can be pruned below.
@@ -92,50 +177,141 @@ impl FastSkillService {
             .filter_map(|f| skill_id_from_path(f))
             .collect();
 
-        let mut count = 0usize;
-        for (idx, skill_file) in skill_files.into_iter().enumerate() {
-            let skill_id = skill_id_from_path(&skill_file).unwrap_or_else(|| "unknown".to_string());
-
+        let mut outcome = ReindexOutcome {
+            reindexed: true,
+            ..Default::default()
+        };
+        let mut settled = 0;
+        let mut failed = 0;
+        let mut report = |skill_id: &str| {
+            settled += 1;
             if let Some(obs) = observer {
                 obs(ReindexProgress {
-                    current: idx + 1,
+                    current: settled,
                     total,
-                    skill_id: skill_id.clone(),
+                    skill_id: skill_id.to_string(),
                 });
             }
+        };
 
-            match index_skill_file(
-                &skill_file,
-                &skill_id,
-                embedding_service.as_ref(),
-                vector_index_service.as_ref(),
-            )
-            .await
+        let embedded = async {
+            // Pass 1: hash and parse every skill, keeping the ones to re-embed.
+            let mut pending = Vec::new();
+            for skill_file in skill_files {
+                check(self.cancellation_token())?;
+                let skill_id =
+                    skill_id_from_path(&skill_file).unwrap_or_else(|| "unknown".to_string());
+                match prepare_skill_file(
+                    &skill_file,
+                    &skill_id,
+                    target.as_ref(),
+                    options.mode,
+                    chunk_size,
+                )
+                .await
+                {
+                    Ok(Some(skill)) => pending.push(skill),
+                    Ok(None) => {
+                        outcome.unchanged += 1;
+                        report(&skill_id);
+                    }
+                    Err(e) => {
+                        // A single skill failing to index should not abort the whole
+                        // reindex run; log and continue with the rest.
+                        tracing::warn!("Failed to reindex skill {}: {}", skill_id, e);
+                        failed += 1;
+                        report(&skill_id);
+                    }
+                }
+            }
+
+            // Pass 2: embed in provider-sized batches, several batches at a time.
+            // Cancellation stops the run between (or during) embedding calls;
+            // skills indexed so far stay in the index.
+            let batch_size = embedding_service.batch_size().max(1);
+            let mut batches = Vec::new();
+            let mut pending = pending.into_iter().peekable();
+            while pending.peek().is_some() {
+                batches.push(pending.by_ref().take(batch_size).collect::<Vec<_>>());
+            }
+            let mut results =
+                futures::stream::iter(batches.into_iter().map(|batch| {
+                    embed_and_store(batch, embedding_service.as_ref(), target.as_ref())
+                }))
+                .buffer_unordered(options.concurrency.max(1));
+            while let Some(batch_results) =
+                cancellable(self.cancellation_token(), results.next()).await?
             {
-                Ok(true) => count += 1,
-                Ok(false) => {
-                    // Unchanged hash: nothing to do.
+                for (skill_id, result) in batch_results {
+                    match result {
+                        Ok(()) => outcome.count += 1,
+                        Err(e) => {
+                            tracing::warn!("Failed to reindex skill {}: {}", skill_id, e);
+                            failed += 1;
+                        }
+                    }
+                    report(&skill_id);
                 }
-                Err(e) => {
-                    // A single skill failing to index should not abort the whole
-                    // reindex run; log and continue with the rest.
-                    tracing::warn!("Failed to reindex skill {}: {}", skill_id, e);
+            }
+            Ok::<(), ServiceError>(())
+        }
+        .await;
+
+        if migrate {
+            // The current index stays in place unless every skill made it
+            // into the new one.
+            let result = match embedded {
+                Ok(()) if failed == 0 => vector_index_service.commit_migration().await,
+                Ok(()) => Err(ServiceError::Custom(format!(
+                    "Migration stopped: {} skill(s) failed to embed; the previous index is kept",
+                    failed
+                ))),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                if let Err(abort_error) = vector_index_service.abort_migration().await {
+                    tracing::warn!("Failed to remove the migration index: {}", abort_error);
                 }
+                return Err(e);
             }
+            tracing::info!("Migrated vector index to the configured embedding model");
+        } else {
+            embedded?;
+            self.remove_stale_entries(
+                vector_index_service.as_ref(),
+                &current_skill_ids,
+                &mut outcome,
+            )
+            .await;
         }
 
-        // Cleanup: remove skills from the index that are no longer on disk.
+        // Update the ANN graph now rather than on the first search.
+        if let Err(e) = vector_index_service.refresh_search_index().await {
+            tracing::warn!("Failed to refresh search index: {}", e);
+        }
+
+        Ok(outcome)
+    }
+
+    /// Remove skills from the index that are no longer on disk.
+    async fn remove_stale_entries(
+        &self,
+        vector_index_service: &dyn VectorIndexService,
+        current_skill_ids: &HashSet<String>,
+        outcome: &mut ReindexOutcome,
+    ) {
         match vector_index_service.get_all_skills().await {
             Ok(all_indexed_skills) => {
                 for indexed_skill in all_indexed_skills {
                     if !current_skill_ids.contains(&indexed_skill.id) {
                         tracing::info!("Removing stale index entry: {}", indexed_skill.id);
-                        if let Err(e) = vector_index_service.remove_skill(&indexed_skill.id).await {
-                            tracing::warn!(
+                        match vector_index_service.remove_skill(&indexed_skill.id).await {
+                            Ok(()) => outcome.removed += 1,
+                            Err(e) => tracing::warn!(
                                 "Failed to remove stale index entry {}: {}",
                                 indexed_skill.id,
                                 e
-                            );
+                            ),
                         }
                     }
                 }
@@ -144,25 +320,19 @@ impl FastSkillService {
                 tracing::warn!("Failed to retrieve all indexed skills for cleanup: {}", e);
             }
         }
-
-        Ok(ReindexOutcome {
-            reindexed: true,
-            count,
-            reason: None,
-        })
     }
 }
 
-/// Derive a skill ID from a `SKILL.md` path: the name of its parent directory.
-fn skill_id_from_path(skill_file: &Path) -> Option<String> {
+/// Derive a skill ID from a `SKILL.md` path: the name of its parent directory,
+/// scoped when that directory sits in a scope directory.
+pub(crate) fn skill_id_from_path(skill_file: &Path) -> Option<String> {
     skill_file
         .parent()
-        .and_then(|parent_dir| parent_dir.file_name())
-        .map(|name| name.to_string_lossy().to_string())
+        .and_then(crate::core::scope::skill_id_for_dir)
 }
 
 /// Find all `SKILL.md` files under `skills_dir`.
-fn find_skill_files(skills_dir: &Path) -> Result<Vec<PathBuf>, ServiceError> {
+pub(crate) fn find_skill_files(skills_dir: &Path) -> Result<Vec<PathBuf>, ServiceError> {
     if !skills_dir.exists() {
         return Err(ServiceError::Config(format!(
             "Skills directory does not exist: {}",
@@ -181,51 +351,172 @@ fn find_skill_files(skills_dir: &Path) -> Result<Vec<PathBuf>, ServiceError> {
     Ok(skill_files)
 }
 
-/// Index a single skill file. Returns `Ok(true)` if the index was updated,
-/// `Ok(false)` if the skill was already up to date (unchanged file hash).
-async fn index_skill_file(
-    skill_file: &Path,
-    skill_id: &str,
-    embedding_service: &dyn EmbeddingService,
-    vector_index_service: &dyn VectorIndexService,
-) -> Result<bool, ServiceError> {
-    let file_hash = calculate_file_hash(skill_file)?;
+/// A skill whose embedding is out of date, read and ready to embed.
+struct PendingSkill {
+    skill_id: String,
+    skill_dir: PathBuf,
+    frontmatter: serde_json::Value,
+    embedding_text: String,
+    /// Section chunks to embed alongside `embedding_text` (chunked indexing)
+    chunks: Vec<String>,
+    file_hash: String,
+}
 
-    // Skip re-embedding when the file is unchanged since the last index write.
-    if let Ok(Some(indexed_skill)) = vector_index_service.get_skill_by_id(skill_id).await {
-        if indexed_skill.file_hash == file_hash {
-            return Ok(false);
-        }
+impl PendingSkill {
+    /// Texts to embed: the skill text, then its chunks
+    fn texts(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.embedding_text).chain(&self.chunks)
     }
+}
 
+/// Read a skill file for embedding. Returns `Ok(None)` if the skill is already
+/// up to date (incremental mode and an unchanged skill directory hash). With a
+/// `chunk_size`, the skill's sections are chunked for multi-vector indexing.
+async fn prepare_skill_file(
+    skill_file: &Path,
+    skill_id: &str,
+    vector_index_service: &dyn VectorIndexService,
+    mode: ReindexMode,
+    chunk_size: Option<usize>,
+) -> Result<Option<PendingSkill>, ServiceError> {
     let skill_dir = skill_file.parent().ok_or_else(|| {
         ServiceError::Validation("Skill file has no parent directory".to_string())
     })?;
+    let file_hash = calculate_skill_hash(skill_dir)?;
+
+    // Skip re-embedding when nothing in the skill changed since the last index write.
+    if mode == ReindexMode::Incremental {
+        if let Ok(Some(indexed_skill)) = vector_index_service.get_skill_by_id(skill_id).await {
+            if indexed_skill.file_hash == file_hash {
+                return Ok(None);
+            }
+        }
+    }
 
     let content = std::fs::read_to_string(skill_file)?;
     let frontmatter = parse_yaml_frontmatter(&content)?;
 
     let frontmatter_json = serde_json::to_value(&frontmatter)
         .map_err(|e| ServiceError::Validation(format!("Failed to serialize frontmatter: {}", e)))?;
+    let chunks = match chunk_size {
+        Some(chunk_size) => skill_chunks(skill_dir, &content, &frontmatter.name, chunk_size)?,
+        None => Vec::new(),
+    };
+
+    Ok(Some(PendingSkill {
+        skill_id: skill_id.to_string(),
+        skill_dir: skill_dir.to_path_buf(),
+        frontmatter: frontmatter_json,
+        embedding_text: format!("{}\n{}", frontmatter.name, frontmatter.description),
+        chunks,
+        file_hash,
+    }))
+}
 
-    let embedding_text = format!("{}\n{}", frontmatter.name, frontmatter.description);
-    let embedding = embedding_service.embed_text(&embedding_text).await?;
-
+/// Store `skill` with `embeddings`, one per [`PendingSkill::texts`] entry.
+async fn store_skill(
+    vector_index_service: &dyn VectorIndexService,
+    skill: PendingSkill,
+    mut embeddings: Vec<Vec<f32>>,
+) -> Result<(), ServiceError> {
+    if embeddings.len() != 1 + skill.chunks.len() {
+        return Err(ServiceError::Custom(format!(
+            "embedding provider returned {} embeddings for {} texts of skill {}",
+            embeddings.len(),
+            1 + skill.chunks.len(),
+            skill.skill_id
+        )));
+    }
+    let chunk_embeddings = embeddings.split_off(1);
+    let embedding = embeddings.remove(0);
     vector_index_service
-        .add_or_update_skill(
-            skill_id,
-            skill_dir.to_path_buf(),
-            frontmatter_json,
+        .add_or_update_indexed_skill(IndexedSkill {
+            id: skill.skill_id,
+            skill_path: skill.skill_dir,
+            frontmatter_json: skill.frontmatter,
             embedding,
-            &file_hash,
-        )
-        .await?;
+            chunk_embeddings,
+            file_hash: skill.file_hash,
+            updated_at: chrono::Utc::now(),
+        })
+        .await
+}
 
+/// Embed one batch with a single [`EmbeddingService::embed_batch`] call and
+/// store the results. Returns each skill's outcome; when the embedding call
+/// fails, every skill in the batch carries its error. Chunk texts ride in the
+/// same call as their skill's text.
+async fn embed_and_store(
+    batch: Vec<PendingSkill>,
+    embedding_service: &dyn EmbeddingService,
+    vector_index_service: &dyn VectorIndexService,
+) -> Vec<(String, Result<(), String>)> {
+    let texts: Vec<String> = batch.iter().flat_map(|s| s.texts()).cloned().collect();
+    let embeddings = match embedding_service.embed_batch(&texts).await {
+        Ok(embeddings) if embeddings.len() == texts.len() => embeddings,
+        Ok(embeddings) => {
+            let error = format!(
+                "embedding provider returned {} embeddings for {} texts",
+                embeddings.len(),
+                texts.len()
+            );
+            return batch
+                .into_iter()
+                .map(|s| (s.skill_id, Err(error.clone())))
+                .collect();
+        }
+        Err(e) => {
+            let error = e.to_string();
+            return batch
+                .into_iter()
+                .map(|s| (s.skill_id, Err(error.clone())))
+                .collect();
+        }
+    };
+
+    let mut results = Vec::with_capacity(batch.len());
+    let mut embeddings = embeddings.into_iter();
+    for skill in batch {
+        let skill_embeddings: Vec<Vec<f32>> =
+            embeddings.by_ref().take(1 + skill.chunks.len()).collect();
+        let skill_id = skill.skill_id.clone();
+        let result = store_skill(vector_index_service, skill, skill_embeddings)
+            .await
+            .map_err(|e| e.to_string());
+        results.push((skill_id, result));
+    }
+    results
+}
+
+/// Index a single skill file. Returns `Ok(true)` if the index was updated,
+/// `Ok(false)` if the skill was already up to date (incremental mode and an
+/// unchanged skill directory hash).
+#[cfg(feature = "hot-reload")]
+pub(crate) async fn index_skill_file(
+    skill_file: &Path,
+    skill_id: &str,
+    embedding_service: &dyn EmbeddingService,
+    vector_index_service: &dyn VectorIndexService,
+    mode: ReindexMode,
+    chunk_size: Option<usize>,
+) -> Result<bool, ServiceError> {
+    let Some(skill) =
+        prepare_skill_file(skill_file, skill_id, vector_index_service, mode, chunk_size).await?
+    else {
+        return Ok(false);
+    };
+    let embeddings = if skill.chunks.is_empty() {
+        vec![embedding_service.embed_text(&skill.embedding_text).await?]
+    } else {
+        let texts: Vec<String> = skill.texts().cloned().collect();
+        embedding_service.embed_batch(&texts).await?
+    };
+    store_skill(vector_index_service, skill, embeddings).await?;
     Ok(true)
 }
 
 /// Calculate the SHA256 hash of a file's contents.
-fn calculate_file_hash(file_path: &Path) -> Result<String, ServiceError> {
+pub(crate) fn calculate_file_hash(file_path: &Path) -> Result<String, ServiceError> {
     let content = std::fs::read(file_path)?;
     let mut hasher = Sha256::new();
     hasher.update(&content);
@@ -330,6 +621,10 @@ Test skill content"#,
                 openai_base_url: "https://api.openai.com/v1".to_string(),
                 embedding_model: "text-embedding-3-small".to_string(),
                 index_path: None,
+                batch_size: None,
+                chunk_size: None,
+                query_cache_size: None,
+                query_cache_ttl: None,
             }),
             ..Default::default()
         };
@@ -378,6 +673,100 @@ Test skill content"#,
         assert_eq!(mock_embedding.call_count(), 2);
     }
 
+    #[tokio::test]
+    async fn test_reindex_stops_when_cancelled() {
+        let temp_dir = TempDir::new().unwrap();
+        let skills_dir = temp_dir.path().join("skills");
+        fs::create_dir_all(&skills_dir).unwrap();
+        create_test_skill(&skills_dir, "skill-one", "Skill One", "First test skill");
+
+        let config = ServiceConfig {
+            skill_storage_path: skills_dir.clone(),
+            embedding: Some(EmbeddingConfig {
+                openai_base_url: "https://api.openai.com/v1".to_string(),
+                embedding_model: "text-embedding-3-small".to_string(),
+                index_path: None,
+                batch_size: None,
+                chunk_size: None,
+                query_cache_size: None,
+                query_cache_ttl: None,
+            }),
+            ..Default::default()
+        };
+
+        let token = crate::core::cancel::CancellationToken::new();
+        let mock_embedding = Arc::new(MockEmbeddingService::new());
+        let mut service = FastSkillService::new(config)
+            .await
+            .unwrap()
+            .with_embedding_service(mock_embedding.clone())
+            .with_cancellation(token.clone());
+        service.initialize().await.unwrap();
+
+        token.cancel();
+        let result = service.reindex(Some(&skills_dir), None).await;
+
+        assert!(matches!(result, Err(ServiceError::Cancelled)));
+        assert_eq!(mock_embedding.call_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_incremental_reindex_hashes_whole_skill_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let skills_dir = temp_dir.path().join("skills");
+        fs::create_dir_all(&skills_dir).unwrap();
+        create_test_skill(&skills_dir, "skill-one", "Skill One", "First test skill");
+        create_test_skill(&skills_dir, "skill-two", "Skill Two", "Second test skill");
+
+        let config = ServiceConfig {
+            skill_storage_path: skills_dir.clone(),
+            embedding: Some(EmbeddingConfig {
+                openai_base_url: "https://api.openai.com/v1".to_string(),
+                embedding_model: "text-embedding-3-small".to_string(),
+                index_path: None,
+                batch_size: None,
+                chunk_size: None,
+                query_cache_size: None,
+                query_cache_ttl: None,
+            }),
+            ..Default::default()
+        };
+        let mock_embedding = Arc::new(MockEmbeddingService::new());
+        let mut service = FastSkillService::new(config)
+            .await
+            .unwrap()
+            .with_embedding_service(mock_embedding.clone());
+        service.initialize().await.unwrap();
+        service.reindex(Some(&skills_dir), None).await.unwrap();
+        assert_eq!(mock_embedding.call_count(), 2);
+
+        // A change outside SKILL.md still marks the skill as changed.
+        let scripts = skills_dir.join("skill-two").join("scripts");
+        fs::create_dir_all(&scripts).unwrap();
+        fs::write(scripts.join("run.sh"), "echo hi\n").unwrap();
+        let outcome = service.reindex(Some(&skills_dir), None).await.unwrap();
+        assert_eq!((outcome.count, outcome.unchanged), (1, 1));
+        assert_eq!(mock_embedding.call_count(), 3);
+
+        let expected = calculate_skill_hash(&skills_dir.join("skill-two")).unwrap();
+        let indexed = service
+            .vector_index_service()
+            .unwrap()
+            .get_skill_by_id("skill-two")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(indexed.file_hash, expected);
+
+        // Full mode re-embeds everything.
+        let outcome = service
+            .reindex_with_mode(Some(&skills_dir), None, ReindexMode::Full)
+            .await
+            .unwrap();
+        assert_eq!((outcome.count, outcome.unchanged), (2, 0));
+        assert_eq!(mock_embedding.call_count(), 5);
+    }
+
     #[tokio::test]
     async fn test_reindex_removes_stale_entries() {
         let temp_dir = TempDir::new().unwrap();
@@ -394,6 +783,10 @@ Test skill content"#,
                 openai_base_url: "https://api.openai.com/v1".to_string(),
                 embedding_model: "text-embedding-3-small".to_string(),
                 index_path: None,
+                batch_size: None,
+                chunk_size: None,
+                query_cache_size: None,
+                query_cache_ttl: None,
             }),
             ..Default::default()
         };
@@ -412,6 +805,8 @@ Test skill content"#,
 
         let outcome2 = service.reindex(Some(&skills_dir), None).await.unwrap();
         assert_eq!(outcome2.count, 0);
+        assert_eq!(outcome2.unchanged, 1);
+        assert_eq!(outcome2.removed, 1);
 
         let vector_index = service.vector_index_service().unwrap();
         assert!(vector_index
@@ -438,6 +833,10 @@ Test skill content"#,
                 openai_base_url: "https://api.openai.com/v1".to_string(),
                 embedding_model: "text-embedding-3-small".to_string(),
                 index_path: None,
+                batch_size: None,
+                chunk_size: None,
+                query_cache_size: None,
+                query_cache_ttl: None,
             }),
             ..Default::default()
         };
@@ -464,6 +863,10 @@ Test skill content"#,
                 openai_base_url: "https://api.openai.com/v1".to_string(),
                 embedding_model: "text-embedding-3-small".to_string(),
                 index_path: None,
+                batch_size: None,
+                chunk_size: None,
+                query_cache_size: None,
+                query_cache_ttl: None,
             }),
             ..Default::default()
         };
@@ -480,4 +883,256 @@ Test skill content"#,
 
         assert!(result.is_err());
     }
+
+    /// Mock provider that takes two texts per call and records each batch size.
+    struct BatchingEmbeddingService {
+        batches: std::sync::Mutex<Vec<usize>>,
+    }
+
+    #[async_trait]
+    impl EmbeddingService for BatchingEmbeddingService {
+        async fn embed_text(&self, text: &str) -> Result<Vec<f32>, ServiceError> {
+            Ok(vec![text.len() as f32, 0.0, 0.0])
+        }
+
+        async fn embed_query(&self, query: &str) -> Result<Vec<f32>, ServiceError> {
+            self.embed_text(query).await
+        }
+
+        async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, ServiceError> {
+            self.batches.lock().unwrap().push(texts.len());
+            Ok(texts
+                .iter()
+                .map(|t| vec![t.len() as f32, 0.0, 0.0])
+                .collect())
+        }
+
+        fn batch_size(&self) -> usize {
+            2
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reindex_embeds_in_provider_batches() {
+        let temp_dir = TempDir::new().unwrap();
+        let skills_dir = temp_dir.path().join("skills");
+        fs::create_dir_all(&skills_dir).unwrap();
+        for i in 1..=3 {
+            let id = format!("skill-{}", i);
+            create_test_skill(&skills_dir, &id, &id, "Batched skill");
+        }
+
+        let config = ServiceConfig {
+            skill_storage_path: skills_dir.clone(),
+            embedding: Some(EmbeddingConfig {
+                openai_base_url: "https://api.openai.com/v1".to_string(),
+                embedding_model: "text-embedding-3-small".to_string(),
+                index_path: None,
+                batch_size: None,
+                chunk_size: None,
+                query_cache_size: None,
+                query_cache_ttl: None,
+            }),
+            ..Default::default()
+        };
+
+        let mock_embedding = Arc::new(BatchingEmbeddingService {
+            batches: std::sync::Mutex::new(Vec::new()),
+        });
+        let mut service = FastSkillService::new(config)
+            .await
+            .unwrap()
+            .with_embedding_service(mock_embedding.clone());
+        service.initialize().await.unwrap();
+
+        let progress = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let progress_clone = progress.clone();
+        let observer = move |p: ReindexProgress| {
+            progress_clone.lock().unwrap().push(p.current);
+        };
+        let options = ReindexOptions {
+            mode: ReindexMode::Full,
+            concurrency: 2,
+        };
+        let outcome = service
+            .reindex_with_options(Some(&skills_dir), Some(&observer), options)
+            .await
+            .unwrap();
+
+        assert_eq!(outcome.count, 3);
+        let mut batches = mock_embedding.batches.lock().unwrap().clone();
+        batches.sort();
+        assert_eq!(batches, vec![1, 2]);
+        assert_eq!(*progress.lock().unwrap(), vec![1, 2, 3]);
+        let vector_index = service.vector_index_service().unwrap();
+        for i in 1..=3 {
+            let id = format!("skill-{}", i);
+            assert!(vector_index.get_skill_by_id(&id).await.unwrap().is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reindex_with_chunk_size_stores_chunk_embeddings() {
+        let temp_dir = TempDir::new().unwrap();
+        let skills_dir = temp_dir.path().join("skills");
+        create_test_skill(&skills_dir, "big-skill", "Big Skill", "A long skill");
+        let references = skills_dir.join("big-skill").join("references");
+        fs::create_dir_all(&references).unwrap();
+        fs::write(references.join("api.md"), "# API\n\nGET /items lists items").unwrap();
+
+        let config = ServiceConfig {
+            skill_storage_path: skills_dir.clone(),
+            embedding: Some(EmbeddingConfig {
+                openai_base_url: "https://api.openai.com/v1".to_string(),
+                embedding_model: "text-embedding-3-small".to_string(),
+                index_path: None,
+                batch_size: None,
+                chunk_size: Some(500),
+                query_cache_size: None,
+                query_cache_ttl: None,
+            }),
+            ..Default::default()
+        };
+        let mock_embedding = Arc::new(MockEmbeddingService::new());
+        let mut service = FastSkillService::new(config)
+            .await
+            .unwrap()
+            .with_embedding_service(mock_embedding.clone());
+        service.initialize().await.unwrap();
+
+        let outcome = service
+            .reindex_with_mode(Some(&skills_dir), None, ReindexMode::Full)
+            .await
+            .unwrap();
+        assert_eq!(outcome.count, 1);
+
+        // One vector for the skill, one for its SKILL.md section, one for the reference
+        let indexed = service
+            .vector_index_service()
+            .unwrap()
+            .get_skill_by_id("big-skill")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(indexed.chunk_embeddings.len(), 2);
+        assert_ne!(indexed.chunk_embeddings[0], indexed.embedding);
+    }
+    /// Provider for a model with wider embeddings; fails on texts containing
+    /// `fail_on`
+    struct WideEmbeddingService {
+        fail_on: Option<&'static str>,
+    }
+
+    #[async_trait]
+    impl EmbeddingService for WideEmbeddingService {
+        async fn embed_text(&self, text: &str) -> Result<Vec<f32>, ServiceError> {
+            match self.fail_on {
+                Some(marker) if text.contains(marker) => {
+                    Err(ServiceError::Custom("provider error".to_string()))
+                }
+                _ => Ok(vec![text.len() as f32, 0.0, 0.0, 1.0]),
+            }
+        }
+
+        async fn embed_query(&self, query: &str) -> Result<Vec<f32>, ServiceError> {
+            self.embed_text(query).await
+        }
+    }
+
+    async fn service_with_model(
+        skills_dir: &Path,
+        model: &str,
+        embedding: Arc<dyn EmbeddingService>,
+    ) -> FastSkillService {
+        let config = ServiceConfig {
+            skill_storage_path: skills_dir.to_path_buf(),
+            embedding: Some(EmbeddingConfig {
+                openai_base_url: "https://api.openai.com/v1".to_string(),
+                embedding_model: model.to_string(),
+                index_path: None,
+                batch_size: None,
+                chunk_size: None,
+                query_cache_size: None,
+                query_cache_ttl: None,
+            }),
+            ..Default::default()
+        };
+        let mut service = FastSkillService::new(config)
+            .await
+            .unwrap()
+            .with_embedding_service(embedding);
+        service.initialize().await.unwrap();
+        service
+    }
+
+    #[tokio::test]
+    async fn test_migrate_reindex_replaces_index_for_new_model() {
+        let temp_dir = TempDir::new().unwrap();
+        let skills_dir = temp_dir.path().join("skills");
+        create_test_skill(&skills_dir, "skill-one", "Skill One", "First test skill");
+        create_test_skill(&skills_dir, "skill-two", "Skill Two", "Second test skill");
+
+        let small = service_with_model(
+            &skills_dir,
+            "text-embedding-3-small",
+            Arc::new(MockEmbeddingService::new()),
+        )
+        .await;
+        small.reindex(Some(&skills_dir), None).await.unwrap();
+
+        // Switching models: plain reindexes refuse the old index
+        let failing = service_with_model(
+            &skills_dir,
+            "text-embedding-3-large",
+            Arc::new(WideEmbeddingService {
+                fail_on: Some("Skill Two"),
+            }),
+        )
+        .await;
+        let err = failing
+            .reindex_with_mode(Some(&skills_dir), None, ReindexMode::Full)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("--migrate"), "{}", err);
+
+        // A migration with a failing skill keeps the old index
+        let err = failing
+            .reindex_with_mode(Some(&skills_dir), None, ReindexMode::Migrate)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("previous index is kept"),
+            "{}",
+            err
+        );
+        let index = failing.vector_index_service().unwrap();
+        let metadata = index.metadata().await.unwrap().unwrap();
+        assert_eq!(metadata.model.as_deref(), Some("text-embedding-3-small"));
+        assert_eq!(metadata.dimensions, 3);
+        assert!(!skills_dir
+            .join(".fastskill")
+            .join("index.migrating.db")
+            .exists());
+
+        let large = service_with_model(
+            &skills_dir,
+            "text-embedding-3-large",
+            Arc::new(WideEmbeddingService { fail_on: None }),
+        )
+        .await;
+        let outcome = large
+            .reindex_with_mode(Some(&skills_dir), None, ReindexMode::Migrate)
+            .await
+            .unwrap();
+        assert_eq!(outcome.count, 2);
+        let index = large.vector_index_service().unwrap();
+        assert!(index.mismatch().await.unwrap().is_none());
+        assert_eq!(index.metadata().await.unwrap().unwrap().dimensions, 4);
+        let skill = index.get_skill_by_id("skill-one").await.unwrap().unwrap();
+        assert_eq!(skill.embedding.len(), 4);
+
+        // Incremental reindexes work again and find nothing to do
+        let outcome = large.reindex(Some(&skills_dir), None).await.unwrap();
+        assert_eq!((outcome.count, outcome.unchanged), (0, 2));
+    }
 }
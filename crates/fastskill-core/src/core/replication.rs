@@ -0,0 +1,671 @@
+//! Read replicas: follower nodes that mirror a primary for discovery traffic
+//!
+//! A primary `serve` publishes a snapshot at `GET /api/v1/replication/snapshot`:
+//! the content hash of every skill plus the vector index entries, signed with
+//! HMAC-SHA256 under a secret shared with its followers. A follower polls the
+//! snapshot, verifies the signature, downloads only the skills whose hash
+//! differs (`GET /api/v1/replication/skills/{id}/archive`), checks every
+//! extracted skill against the signed hash, and imports the index entries so
+//! it can answer search without embedding anything itself. Followers always
+//! serve read-only.
+//!
+//! The snapshot is deterministic (sorted, no timestamps), so its signature
+//! doubles as the ETag: an unchanged primary answers `304 Not Modified`.
+
+use crate::core::change_detection::calculate_skill_hash;
+use crate::core::integrity::IntegrityGuard;
+use crate::core::service::{FastSkillService, ServiceError, SkillId};
+use crate::core::vector_index::{IndexedSkill, VectorIndexService};
+use crate::storage::zip::ZipHandler;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Response header carrying the snapshot signature
+pub const SIGNATURE_HEADER: &str = "x-fastskill-signature";
+
+/// Snapshot layout version; followers refuse anything else
+pub const SNAPSHOT_FORMAT: u32 = 1;
+
+/// Staging area under the skills directory for archives being verified.
+/// Dot-prefixed so directory scans never pick it up as a skill.
+const STAGING_DIR: &str = ".fastskill-replica";
+
+/// Shortest accepted shared secret
+const MIN_SECRET_LEN: usize = 16;
+
+/// Replication role for `fastskill serve` (`[tool.fastskill.server.replication]`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "role", rename_all = "lowercase")]
+pub enum ReplicationConfig {
+    /// Publish signed snapshots for followers
+    Primary {
+        /// Environment variable holding the shared secret
+        #[serde(default = "default_secret_env")]
+        secret_env: String,
+    },
+    /// Mirror `primary_url` and serve read-only
+    Follower {
+        /// Base URL of the primary server, e.g. `http://primary:8080`
+        primary_url: String,
+        /// Environment variable holding the shared secret
+        #[serde(default = "default_secret_env")]
+        secret_env: String,
+        /// Seconds between snapshot polls (default: 60)
+        #[serde(default = "default_poll_interval_secs")]
+        poll_interval_secs: u64,
+    },
+}
+
+fn default_secret_env() -> String {
+    "FASTSKILL_REPLICATION_SECRET".to_string()
+}
+
+fn default_poll_interval_secs() -> u64 {
+    60
+}
+
+/// Shared secret used to sign and verify snapshots
+#[derive(Clone)]
+pub struct ReplicationKey(Arc<[u8]>);
+
+impl fmt::Debug for ReplicationKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ReplicationKey(..)")
+    }
+}
+
+impl ReplicationKey {
+    pub fn new(secret: impl AsRef<[u8]>) -> Result<Self, ServiceError> {
+        let secret = secret.as_ref();
+        if secret.len() < MIN_SECRET_LEN {
+            return Err(ServiceError::Config(format!(
+                "replication secret must be at least {} bytes",
+                MIN_SECRET_LEN
+            )));
+        }
+        Ok(Self(Arc::from(secret)))
+    }
+
+    fn mac(&self) -> Result<HmacSha256, ServiceError> {
+        HmacSha256::new_from_slice(&self.0)
+            .map_err(|e| ServiceError::Custom(format!("Invalid replication secret: {}", e)))
+    }
+
+    /// Signature of `body` in the form `sha256=<hex>`
+    pub fn sign(&self, body: &[u8]) -> Result<String, ServiceError> {
+        let mut mac = self.mac()?;
+        mac.update(body);
+        Ok(format!("sha256={:x}", mac.finalize().into_bytes()))
+    }
+
+    /// Check `signature` (as produced by [`Self::sign`]) in constant time
+    pub fn verify(&self, body: &[u8], signature: &str) -> bool {
+        let Some(expected) = signature.strip_prefix("sha256=").and_then(decode_hex) else {
+            return false;
+        };
+        let Ok(mut mac) = self.mac() else {
+            return false;
+        };
+        mac.update(body);
+        mac.verify_slice(&expected).is_ok()
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Everything a follower needs to mirror a primary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub format: u32,
+    pub skills: Vec<SnapshotSkill>,
+    /// Index entries; `skill_path` is relative to the skills directory
+    #[serde(default)]
+    pub index: Vec<IndexedSkill>,
+}
+
+/// One skill on the primary and its [`calculate_skill_hash`] hash
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotSkill {
+    pub id: String,
+    pub hash: String,
+}
+
+/// Skill directories (those holding a SKILL.md) directly under `skills_dir`
+/// or in its scope directories, sorted by id. Dot-prefixed directories are
+/// skipped.
+pub(crate) fn local_skill_ids(skills_dir: &Path) -> Result<Vec<String>, ServiceError> {
+    Ok(crate::core::scope::skill_dirs(skills_dir)?
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect())
+}
+
+/// Describe the skills in `skills_dir` and the entries of `index`
+pub async fn build_snapshot(
+    skills_dir: &Path,
+    index: Option<&dyn VectorIndexService>,
+) -> Result<Snapshot, ServiceError> {
+    let mut skills = Vec::new();
+    for id in local_skill_ids(skills_dir)? {
+        let hash = calculate_skill_hash(&skills_dir.join(&id))?;
+        skills.push(SnapshotSkill { id, hash });
+    }
+
+    let mut entries = match index {
+        Some(index) => index.get_all_skills().await?,
+        None => Vec::new(),
+    };
+    entries.sort_by(|a, b| a.id.cmp(&b.id));
+    for entry in &mut entries {
+        // Followers rewrite this to their own skills directory; the primary's
+        // layout is none of their business.
+        entry.skill_path = PathBuf::from(&entry.id);
+    }
+
+    Ok(Snapshot {
+        format: SNAPSHOT_FORMAT,
+        skills,
+        index: entries,
+    })
+}
+
+/// Zip the files of one skill directory in memory. Symlinks are left out,
+/// matching [`calculate_skill_hash`].
+pub fn pack_skill(skill_dir: &Path) -> Result<Vec<u8>, ServiceError> {
+    let zip_error = |e: zip::result::ZipError| {
+        ServiceError::Custom(format!("Failed to pack {}: {}", skill_dir.display(), e))
+    };
+
+    let mut files: Vec<PathBuf> = walkdir::WalkDir::new(skill_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for file in files {
+        let relative = file.strip_prefix(skill_dir).unwrap_or(&file);
+        let name = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        writer.start_file(name, options).map_err(zip_error)?;
+        writer.write_all(&std::fs::read(&file)?)?;
+    }
+    Ok(writer.finish().map_err(zip_error)?.into_inner())
+}
+
+/// Skills a follower must download, and local skills the primary no longer has
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SyncPlan {
+    pub fetch: Vec<SnapshotSkill>,
+    pub remove: Vec<String>,
+}
+
+/// Compare `snapshot` with the skills in `skills_dir`
+pub fn plan_sync(skills_dir: &Path, snapshot: &Snapshot) -> Result<SyncPlan, ServiceError> {
+    let mut plan = SyncPlan::default();
+    for skill in &snapshot.skills {
+        SkillId::new(skill.id.clone())?;
+        let local = skills_dir.join(&skill.id);
+        let current = local
+            .join("SKILL.md")
+            .is_file()
+            .then(|| calculate_skill_hash(&local))
+            .transpose()?;
+        if current.as_deref() != Some(skill.hash.as_str()) {
+            plan.fetch.push(skill.clone());
+        }
+    }
+
+    let wanted: BTreeSet<&str> = snapshot.skills.iter().map(|s| s.id.as_str()).collect();
+    plan.remove = local_skill_ids(skills_dir)?
+        .into_iter()
+        .filter(|id| !wanted.contains(id.as_str()))
+        .collect();
+    Ok(plan)
+}
+
+/// Extract `archive` for `skill` into a staging directory, check it against
+/// the signed hash, then swap it in place of the local copy.
+pub fn install_archive(
+    skills_dir: &Path,
+    skill: &SnapshotSkill,
+    archive: &[u8],
+) -> Result<(), ServiceError> {
+    let skill_id = SkillId::new(skill.id.clone())?;
+    let staging = skills_dir.join(STAGING_DIR).join(skill_id.as_str());
+    if staging.exists() {
+        std::fs::remove_dir_all(&staging)?;
+    }
+    std::fs::create_dir_all(&staging)?;
+
+    let extracted = ZipHandler::new()?
+        .extract_reader_to_dir(Cursor::new(archive), &staging)
+        .and_then(|()| calculate_skill_hash(&staging));
+    let hash = match extracted {
+        Ok(hash) => hash,
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+    };
+    if hash != skill.hash {
+        let _ = std::fs::remove_dir_all(&staging);
+        return Err(ServiceError::Validation(format!(
+            "archive for {} does not match the snapshot hash",
+            skill.id
+        )));
+    }
+
+    let target = skills_dir.join(skill_id.as_str());
+    remove_path(&target)?;
+    crate::core::scope::ensure_scope_dir(skills_dir, &skill_id)?;
+    std::fs::rename(&staging, &target)?;
+    Ok(())
+}
+
+/// Remove a skill directory, or a symlink standing in for one
+fn remove_path(path: &Path) -> Result<(), ServiceError> {
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.is_dir() => std::fs::remove_dir_all(path)?,
+        Ok(_) => std::fs::remove_file(path)?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e.into()),
+    }
+    Ok(())
+}
+
+/// What one follower sync changed
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SyncReport {
+    /// The primary answered 304; nothing was compared
+    pub not_modified: bool,
+    pub fetched: usize,
+    pub removed: usize,
+    pub indexed: usize,
+}
+
+/// Pulls snapshots from a primary and applies them to the local service
+pub struct Follower {
+    client: reqwest::Client,
+    base_url: String,
+    key: ReplicationKey,
+    /// Signature of the last snapshot applied in full
+    applied: Option<String>,
+    /// Guard of the server this follower feeds; synced skills are forgotten
+    /// so their new content is not taken for tampering
+    integrity: Option<Arc<IntegrityGuard>>,
+}
+
+impl Follower {
+    pub fn new(primary_url: &str, key: ReplicationKey) -> Result<Self, ServiceError> {
+        let client = reqwest::Client::builder()
+            .user_agent(concat!("fastskill/", env!("CARGO_PKG_VERSION")))
+            .timeout(Duration::from_secs(60))
+            .build()
+            .map_err(|e| ServiceError::Custom(format!("Failed to create HTTP client: {}", e)))?;
+        Ok(Self {
+            client,
+            base_url: primary_url.trim_end_matches('/').to_string(),
+            key,
+            applied: None,
+            integrity: None,
+        })
+    }
+
+    /// Forget each skill this follower installs or removes in `guard`, the
+    /// integrity guard of the server answering from the same skills directory
+    pub fn with_integrity_guard(mut self, guard: Arc<IntegrityGuard>) -> Self {
+        self.integrity = Some(guard);
+        self
+    }
+
+    fn forget_integrity(&self, skill_id: &str) {
+        if let Some(guard) = &self.integrity {
+            guard.forget(skill_id);
+        }
+    }
+
+    /// Fetch the primary's snapshot and bring `service` in line with it
+    pub async fn sync_once(
+        &mut self,
+        service: &FastSkillService,
+    ) -> Result<SyncReport, ServiceError> {
+        let url = format!("{}/api/v1/replication/snapshot", self.base_url);
+        let mut request = self.client.get(&url);
+        if let Some(signature) = &self.applied {
+            request = request.header(reqwest::header::IF_NONE_MATCH, format!("\"{}\"", signature));
+        }
+        let response = request.send().await.map_err(|e| {
+            ServiceError::Custom(format!("Failed to reach primary at {}: {}", url, e))
+        })?;
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(SyncReport {
+                not_modified: true,
+                ..Default::default()
+            });
+        }
+        if !response.status().is_success() {
+            return Err(ServiceError::Custom(format!(
+                "Primary returned {} for {}",
+                response.status(),
+                url
+            )));
+        }
+        let signature = response
+            .headers()
+            .get(SIGNATURE_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                ServiceError::Validation(format!("Primary snapshot has no {}", SIGNATURE_HEADER))
+            })?;
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| ServiceError::Custom(format!("Failed to read snapshot: {}", e)))?;
+        if !self.key.verify(&body, &signature) {
+            return Err(ServiceError::Validation(
+                "snapshot signature does not match; primary and follower must share the \
+                 replication secret"
+                    .to_string(),
+            ));
+        }
+        let snapshot: Snapshot = serde_json::from_slice(&body)
+            .map_err(|e| ServiceError::Validation(format!("Invalid snapshot: {}", e)))?;
+        if snapshot.format != SNAPSHOT_FORMAT {
+            return Err(ServiceError::Validation(format!(
+                "unsupported snapshot format {} (expected {})",
+                snapshot.format, SNAPSHOT_FORMAT
+            )));
+        }
+
+        let report = self.apply(service, &snapshot).await?;
+        self.applied = Some(signature);
+        Ok(report)
+    }
+
+    async fn fetch_archive(&self, skill_id: &str) -> Result<Vec<u8>, ServiceError> {
+        let url = format!(
+            "{}/api/v1/replication/skills/{}/archive",
+            self.base_url, skill_id
+        );
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| ServiceError::Custom(format!("Failed to download {}: {}", url, e)))?;
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| ServiceError::Custom(format!("Failed to download {}: {}", url, e)))?;
+        Ok(bytes.to_vec())
+    }
+
+    async fn apply(
+        &self,
+        service: &FastSkillService,
+        snapshot: &Snapshot,
+    ) -> Result<SyncReport, ServiceError> {
+        let skills_dir = &service.config().skill_storage_path;
+        std::fs::create_dir_all(skills_dir)?;
+        let plan = plan_sync(skills_dir, snapshot)?;
+        let mut report = SyncReport::default();
+        let event_bus = service.event_bus();
+
+        for skill in &plan.fetch {
+            let archive = self.fetch_archive(&skill.id).await?;
+            install_archive(skills_dir, skill, &archive)?;
+            self.forget_integrity(&skill.id);
+            service
+                .reload_skill_from_file(&skills_dir.join(&skill.id).join("SKILL.md"))
+                .await?;
+            event_bus
+                .publish_skill_reloaded(skill.id.clone(), true, None)
+                .await?;
+            report.fetched += 1;
+        }
+
+        for id in &plan.remove {
+            remove_path(&skills_dir.join(id))?;
+            self.forget_integrity(id);
+            if let Ok(skill_id) = SkillId::new(id.clone()) {
+                crate::core::scope::remove_scope_dir_if_empty(skills_dir, &skill_id)?;
+                match service.skill_manager().unregister_skill(&skill_id).await {
+                    Ok(()) | Err(ServiceError::SkillNotFound(_)) => {}
+                    Err(e) => return Err(e),
+                }
+            }
+            event_bus.publish_skill_unregistered(id.clone()).await?;
+            report.removed += 1;
+        }
+
+        if let Some(index) = service.vector_index_service() {
+            report.indexed = import_index(index.as_ref(), skills_dir, &snapshot.index).await?;
+        }
+
+        if report.fetched + report.removed > 0 {
+            tracing::info!(
+                "Replication: fetched {} skills, removed {}, updated {} index entries",
+                report.fetched,
+                report.removed,
+                report.indexed
+            );
+        }
+        Ok(report)
+    }
+
+    /// Sync every `interval` until the task is aborted. Failures are logged
+    /// and retried on the next tick; the follower keeps serving what it has.
+    pub fn spawn(
+        mut self,
+        service: Arc<FastSkillService>,
+        interval: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                if let Err(e) = self.sync_once(&service).await {
+                    tracing::warn!("Replication sync from {} failed: {}", self.base_url, e);
+                }
+            }
+        })
+    }
+}
+
+/// Bring `index` in line with the primary's entries. Returns how many
+/// entries were written or removed.
+async fn import_index(
+    index: &dyn VectorIndexService,
+    skills_dir: &Path,
+    entries: &[IndexedSkill],
+) -> Result<usize, ServiceError> {
+    let mut changed = 0;
+    let mut wanted = BTreeSet::new();
+    for entry in entries {
+        SkillId::new(entry.id.clone())?;
+        wanted.insert(entry.id.as_str());
+        let current = index.get_skill_by_id(&entry.id).await?;
+        if current.is_some_and(|c| c.file_hash == entry.file_hash) {
+            continue;
+        }
+        index
+            .add_or_update_indexed_skill(IndexedSkill {
+                skill_path: skills_dir.join(&entry.id),
+                updated_at: chrono::Utc::now(),
+                ..entry.clone()
+            })
+            .await?;
+        changed += 1;
+    }
+    for stale in index.get_all_skills().await? {
+        if !wanted.contains(stale.id.as_str()) {
+            index.remove_skill(&stale.id).await?;
+            changed += 1;
+        }
+    }
+    if changed > 0 {
+        index.refresh_search_index().await?;
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const SECRET: &str = "0123456789abcdef0123456789abcdef";
+
+    fn write_skill(dir: &Path, id: &str, description: &str) {
+        let skill_dir = dir.join(id);
+        std::fs::create_dir_all(skill_dir.join("scripts")).unwrap();
+        std::fs::write(
+            skill_dir.join("SKILL.md"),
+            format!("---\nname: {id}\ndescription: {description}\n---\n"),
+        )
+        .unwrap();
+        std::fs::write(skill_dir.join("scripts").join("run.sh"), "echo hi\n").unwrap();
+    }
+
+    #[test]
+    fn test_signature_round_trip_and_wrong_secret() {
+        let key = ReplicationKey::new(SECRET).unwrap();
+        let signature = key.sign(b"{\"format\":1}").unwrap();
+        assert!(signature.starts_with("sha256="));
+        assert!(key.verify(b"{\"format\":1}", &signature));
+        assert!(!key.verify(b"{\"format\":2}", &signature));
+        assert!(!key.verify(b"{\"format\":1}", "sha256=zz"));
+
+        let other = ReplicationKey::new("another-secret-of-enough-length").unwrap();
+        assert!(!other.verify(b"{\"format\":1}", &signature));
+        assert!(ReplicationKey::new("short").is_err());
+    }
+
+    #[test]
+    fn test_pack_and_install_round_trip() {
+        let primary = TempDir::new().unwrap();
+        let follower = TempDir::new().unwrap();
+        write_skill(primary.path(), "pdf-tools", "Extract text");
+        write_skill(follower.path(), "stale-skill", "Gone from the primary");
+
+        let skill = SnapshotSkill {
+            id: "pdf-tools".to_string(),
+            hash: calculate_skill_hash(&primary.path().join("pdf-tools")).unwrap(),
+        };
+        let snapshot = Snapshot {
+            format: SNAPSHOT_FORMAT,
+            skills: vec![skill.clone()],
+            index: Vec::new(),
+        };
+        let plan = plan_sync(follower.path(), &snapshot).unwrap();
+        assert_eq!(plan.fetch, vec![skill.clone()]);
+        assert_eq!(plan.remove, vec!["stale-skill".to_string()]);
+
+        let archive = pack_skill(&primary.path().join("pdf-tools")).unwrap();
+        install_archive(follower.path(), &skill, &archive).unwrap();
+        assert!(follower.path().join("pdf-tools/scripts/run.sh").is_file());
+        assert!(plan_sync(follower.path(), &snapshot)
+            .unwrap()
+            .fetch
+            .is_empty());
+    }
+
+    #[test]
+    fn test_install_rejects_hash_mismatch() {
+        let primary = TempDir::new().unwrap();
+        let follower = TempDir::new().unwrap();
+        write_skill(primary.path(), "pdf-tools", "Extract text");
+        let archive = pack_skill(&primary.path().join("pdf-tools")).unwrap();
+        let skill = SnapshotSkill {
+            id: "pdf-tools".to_string(),
+            hash: "sha256:not-the-real-hash".to_string(),
+        };
+
+        assert!(matches!(
+            install_archive(follower.path(), &skill, &archive),
+            Err(ServiceError::Validation(_))
+        ));
+        assert!(!follower.path().join("pdf-tools").exists());
+    }
+
+    #[test]
+    fn test_install_rejects_path_traversal() {
+        let follower = TempDir::new().unwrap();
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        writer
+            .start_file("../../escaped.txt", zip::write::FileOptions::default())
+            .unwrap();
+        writer.write_all(b"owned").unwrap();
+        let archive = writer.finish().unwrap().into_inner();
+        let skill = SnapshotSkill {
+            id: "pdf-tools".to_string(),
+            hash: "sha256:irrelevant".to_string(),
+        };
+
+        assert!(install_archive(follower.path(), &skill, &archive).is_err());
+        assert!(!follower.path().join("escaped.txt").exists());
+        assert!(!follower
+            .path()
+            .join(STAGING_DIR)
+            .join("escaped.txt")
+            .exists());
+        assert!(!follower.path().join("pdf-tools").exists());
+    }
+
+    #[test]
+    fn test_plan_rejects_unsafe_skill_ids() {
+        let follower = TempDir::new().unwrap();
+        let snapshot = Snapshot {
+            format: SNAPSHOT_FORMAT,
+            skills: vec![SnapshotSkill {
+                id: "../outside".to_string(),
+                hash: "sha256:x".to_string(),
+            }],
+            index: Vec::new(),
+        };
+        assert!(plan_sync(follower.path(), &snapshot).is_err());
+    }
+
+    #[test]
+    fn test_replication_config_parses_roles() {
+        let follower: ReplicationConfig =
+            toml::from_str("role = \"follower\"\nprimary_url = \"http://primary:8080\"").unwrap();
+        assert_eq!(
+            follower,
+            ReplicationConfig::Follower {
+                primary_url: "http://primary:8080".to_string(),
+                secret_env: "FASTSKILL_REPLICATION_SECRET".to_string(),
+                poll_interval_secs: 60,
+            }
+        );
+        let primary: ReplicationConfig = toml::from_str("role = \"primary\"").unwrap();
+        assert!(matches!(primary, ReplicationConfig::Primary { .. }));
+    }
+}
@@ -0,0 +1,705 @@
+//! Skill health report.
+//!
+//! Builds a point-in-time summary of the skills directory — skills added or
+//! changed within a reporting window, validation failures, near-duplicate pairs
+//! and vector index health — and renders it as Markdown, HTML or JSON. Reports
+//! are meant to be produced on a schedule (cron, CI) by `fastskill report` and
+//! either written to a directory or posted to a webhook.
+//!
+//! Usage trends are not part of the report: the service records no per-skill
+//! usage data that a report could aggregate.
+
+use crate::core::analysis::skill_similarity;
+use crate::core::change_detection::calculate_skill_hash;
+use crate::core::metadata::parse_yaml_frontmatter;
+use crate::core::reindex::{find_skill_files, skill_id_from_path};
+use crate::core::service::{FastSkillService, ServiceError};
+use crate::validation::{SkillValidator, ValidationRules};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Output format of a rendered report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+    Json,
+}
+
+impl ReportFormat {
+    /// Parse a format name (`markdown`/`md`, `html`, `json`).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "markdown" | "md" => Some(ReportFormat::Markdown),
+            "html" => Some(ReportFormat::Html),
+            "json" => Some(ReportFormat::Json),
+            _ => None,
+        }
+    }
+
+    /// File extension used when writing a report to a directory.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Html => "html",
+            ReportFormat::Json => "json",
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "markdown",
+            ReportFormat::Html => "html",
+            ReportFormat::Json => "json",
+        }
+    }
+}
+
+/// Inputs controlling what a report covers.
+#[derive(Debug, Clone)]
+pub struct ReportOptions {
+    /// Start of the reporting window; skills created or modified after this
+    /// instant are listed as new/updated.
+    pub since: DateTime<Utc>,
+    /// Minimum embedding similarity for a pair to be reported as a duplicate.
+    pub duplicate_threshold: f32,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self {
+            since: Utc::now() - chrono::Duration::days(7),
+            duplicate_threshold: 0.95,
+        }
+    }
+}
+
+/// A skill listed in the new/updated sections.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportedSkill {
+    pub id: String,
+    pub name: String,
+    pub version: Option<String>,
+    pub modified_at: DateTime<Utc>,
+}
+
+/// A skill that failed validation.
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationFailure {
+    pub skill_id: String,
+    pub errors: Vec<String>,
+}
+
+/// Two indexed skills whose embeddings are at least `duplicate_threshold` similar.
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicatePair {
+    pub skill_a: String,
+    pub skill_b: String,
+    pub similarity: f32,
+}
+
+/// State of the vector index relative to the skills directory.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IndexHealth {
+    /// Whether a vector index is configured at all.
+    pub enabled: bool,
+    /// Number of entries in the index.
+    pub indexed: usize,
+    /// Skills on disk with no index entry.
+    pub missing: Vec<String>,
+    /// Index entries whose skill is no longer on disk.
+    pub stale: Vec<String>,
+    /// Index entries whose SKILL.md changed since it was embedded.
+    pub outdated: Vec<String>,
+}
+
+impl IndexHealth {
+    /// `true` when the index is enabled and in sync with the skills directory.
+    pub fn is_healthy(&self) -> bool {
+        self.enabled && self.missing.is_empty() && self.stale.is_empty() && self.outdated.is_empty()
+    }
+}
+
+/// Skill health report for one reporting window.
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    pub generated_at: DateTime<Utc>,
+    pub since: DateTime<Utc>,
+    pub skills_directory: PathBuf,
+    pub skills_total: usize,
+    pub new_skills: Vec<ReportedSkill>,
+    pub updated_skills: Vec<ReportedSkill>,
+    pub validation_failures: Vec<ValidationFailure>,
+    pub duplicate_threshold: f32,
+    pub duplicates: Vec<DuplicatePair>,
+    pub index: IndexHealth,
+}
+
+impl FastSkillService {
+    /// Build a health report for the configured skills directory.
+    pub async fn health_report(
+        &self,
+        options: &ReportOptions,
+    ) -> Result<HealthReport, ServiceError> {
+        let skills_dir = self.config().skill_storage_path.clone();
+        let skill_files = find_skill_files(&skills_dir)?;
+
+        let mut new_skills = Vec::new();
+        let mut updated_skills = Vec::new();
+        let mut validation_failures = Vec::new();
+        let mut hashes_on_disk: HashMap<String, String> = HashMap::new();
+        let validator = SkillValidator::new().with_rules(ValidationRules::load(
+            &ValidationRules::default_path(&skills_dir),
+        )?);
+
+        for skill_file in &skill_files {
+            let Some(skill_id) = skill_id_from_path(skill_file) else {
+                continue;
+            };
+            let Some(skill_dir) = skill_file.parent() else {
+                continue;
+            };
+
+            let mut errors = Vec::new();
+            let frontmatter = match std::fs::read_to_string(skill_file) {
+                Ok(content) => match parse_yaml_frontmatter(&content) {
+                    Ok(fm) => Some(fm),
+                    Err(e) => {
+                        errors.push(e.to_string());
+                        None
+                    }
+                },
+                Err(e) => {
+                    errors.push(format!("Cannot read SKILL.md: {}", e));
+                    None
+                }
+            };
+
+            let validation = validator.validate_skill_directory(skill_dir).await?;
+            errors.extend(validation.errors.into_iter().map(|e| e.message));
+            if !errors.is_empty() {
+                validation_failures.push(ValidationFailure {
+                    skill_id: skill_id.clone(),
+                    errors,
+                });
+            }
+
+            // Same hash reindex stores, so changes anywhere in the skill count.
+            if let Ok(hash) = calculate_skill_hash(skill_dir) {
+                hashes_on_disk.insert(skill_id.clone(), hash);
+            }
+
+            let (created, modified) = file_times(skill_file);
+            let reported = ReportedSkill {
+                id: skill_id,
+                name: frontmatter
+                    .as_ref()
+                    .map(|fm| fm.name.clone())
+                    .unwrap_or_default(),
+                version: frontmatter.and_then(|fm| fm.version),
+                modified_at: modified,
+            };
+            if created >= options.since {
+                new_skills.push(reported);
+            } else if modified >= options.since {
+                updated_skills.push(reported);
+            }
+        }
+
+        new_skills.sort_by(|a, b| a.id.cmp(&b.id));
+        updated_skills.sort_by(|a, b| a.id.cmp(&b.id));
+        validation_failures.sort_by(|a, b| a.skill_id.cmp(&b.skill_id));
+
+        let (index, duplicates) = match self.vector_index_service() {
+            Some(vector_index) => {
+                let indexed = vector_index.get_all_skills().await?;
+                let mut health = IndexHealth {
+                    enabled: true,
+                    indexed: indexed.len(),
+                    ..Default::default()
+                };
+                let indexed_ids: HashSet<&str> = indexed.iter().map(|s| s.id.as_str()).collect();
+                for entry in &indexed {
+                    match hashes_on_disk.get(&entry.id) {
+                        None => health.stale.push(entry.id.clone()),
+                        Some(hash) if *hash != entry.file_hash => {
+                            health.outdated.push(entry.id.clone())
+                        }
+                        Some(_) => {}
+                    }
+                }
+                health.missing = hashes_on_disk
+                    .keys()
+                    .filter(|id| !indexed_ids.contains(id.as_str()))
+                    .cloned()
+                    .collect();
+                health.missing.sort();
+                health.stale.sort();
+                health.outdated.sort();
+
+                let embeddings: Vec<(&str, &[f32])> = indexed
+                    .iter()
+                    .map(|s| (s.id.as_str(), s.embedding.as_slice()))
+                    .collect();
+                let duplicates = find_duplicate_pairs(&embeddings, options.duplicate_threshold);
+                (health, duplicates)
+            }
+            None => (IndexHealth::default(), Vec::new()),
+        };
+
+        Ok(HealthReport {
+            generated_at: Utc::now(),
+            since: options.since,
+            skills_directory: skills_dir,
+            skills_total: skill_files.len(),
+            new_skills,
+            updated_skills,
+            validation_failures,
+            duplicate_threshold: options.duplicate_threshold,
+            duplicates,
+            index,
+        })
+    }
+}
+
+/// Creation and modification time of a file, falling back to "now" when the
+/// platform cannot report them. Creation falls back to modification time on
+/// filesystems without birth times.
+fn file_times(path: &Path) -> (DateTime<Utc>, DateTime<Utc>) {
+    let Ok(meta) = std::fs::metadata(path) else {
+        let now = Utc::now();
+        return (now, now);
+    };
+    let modified = meta
+        .modified()
+        .map(DateTime::<Utc>::from)
+        .unwrap_or_else(|_| Utc::now());
+    let created = meta
+        .created()
+        .map(DateTime::<Utc>::from)
+        .unwrap_or(modified);
+    (created, modified)
+}
+
+/// All pairs with similarity `>= threshold`, highest first. Each pair is
+/// reported once, with ids in alphabetical order.
+fn find_duplicate_pairs(embeddings: &[(&str, &[f32])], threshold: f32) -> Vec<DuplicatePair> {
+    let mut pairs = Vec::new();
+    for (i, (id_a, emb_a)) in embeddings.iter().enumerate() {
+        for (id_b, emb_b) in embeddings.iter().skip(i + 1) {
+            let similarity = skill_similarity(emb_a, emb_b);
+            if similarity >= threshold {
+                let (skill_a, skill_b) = if id_a <= id_b {
+                    (id_a, id_b)
+                } else {
+                    (id_b, id_a)
+                };
+                pairs.push(DuplicatePair {
+                    skill_a: skill_a.to_string(),
+                    skill_b: skill_b.to_string(),
+                    similarity,
+                });
+            }
+        }
+    }
+    pairs.sort_by(|a, b| {
+        b.similarity
+            .partial_cmp(&a.similarity)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.skill_a.cmp(&b.skill_a))
+    });
+    pairs
+}
+
+impl HealthReport {
+    /// Render the report in the requested format.
+    pub fn render(&self, format: ReportFormat) -> Result<String, ServiceError> {
+        match format {
+            ReportFormat::Markdown => Ok(self.render_markdown()),
+            ReportFormat::Html => Ok(self.render_html()),
+            ReportFormat::Json => serde_json::to_string_pretty(self)
+                .map_err(|e| ServiceError::Custom(format!("Failed to serialize report: {}", e))),
+        }
+    }
+
+    /// Default file name for this report, e.g. `skill-health-2026-10-16.md`.
+    pub fn file_name(&self, format: ReportFormat) -> String {
+        format!(
+            "skill-health-{}.{}",
+            self.generated_at.format("%Y-%m-%d"),
+            format.extension()
+        )
+    }
+
+    fn summary_lines(&self) -> Vec<String> {
+        let index = if !self.index.enabled {
+            "disabled (no embedding provider configured)".to_string()
+        } else if self.index.is_healthy() {
+            format!("healthy ({} entries)", self.index.indexed)
+        } else {
+            format!(
+                "{} entries, {} missing, {} stale, {} outdated",
+                self.index.indexed,
+                self.index.missing.len(),
+                self.index.stale.len(),
+                self.index.outdated.len()
+            )
+        };
+        vec![
+            format!("Skills: {}", self.skills_total),
+            format!("New: {}", self.new_skills.len()),
+            format!("Updated: {}", self.updated_skills.len()),
+            format!("Validation failures: {}", self.validation_failures.len()),
+            format!(
+                "Duplicates (>= {:.2}): {}",
+                self.duplicate_threshold,
+                self.duplicates.len()
+            ),
+            format!("Index: {}", index),
+        ]
+    }
+
+    fn render_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# Skill health report");
+        let _ = writeln!(out);
+        let _ = writeln!(
+            out,
+            "Period: {} to {}",
+            self.since.format("%Y-%m-%d %H:%M UTC"),
+            self.generated_at.format("%Y-%m-%d %H:%M UTC")
+        );
+        let _ = writeln!(out);
+        let _ = writeln!(out, "## Summary");
+        let _ = writeln!(out);
+        for line in self.summary_lines() {
+            let _ = writeln!(out, "- {}", line);
+        }
+
+        for (title, skills) in [
+            ("New skills", &self.new_skills),
+            ("Updated skills", &self.updated_skills),
+        ] {
+            let _ = writeln!(out);
+            let _ = writeln!(out, "## {}", title);
+            let _ = writeln!(out);
+            if skills.is_empty() {
+                let _ = writeln!(out, "None.");
+            }
+            for skill in skills {
+                let _ = writeln!(
+                    out,
+                    "- `{}` {} ({}, {})",
+                    skill.id,
+                    skill.name,
+                    skill.version.as_deref().unwrap_or("unversioned"),
+                    skill.modified_at.format("%Y-%m-%d")
+                );
+            }
+        }
+
+        let _ = writeln!(out);
+        let _ = writeln!(out, "## Validation failures");
+        let _ = writeln!(out);
+        if self.validation_failures.is_empty() {
+            let _ = writeln!(out, "None.");
+        }
+        for failure in &self.validation_failures {
+            let _ = writeln!(
+                out,
+                "- `{}`: {}",
+                failure.skill_id,
+                failure.errors.join("; ")
+            );
+        }
+
+        let _ = writeln!(out);
+        let _ = writeln!(out, "## Potential duplicates");
+        let _ = writeln!(out);
+        if self.duplicates.is_empty() {
+            let _ = writeln!(out, "None.");
+        }
+        for pair in &self.duplicates {
+            let _ = writeln!(
+                out,
+                "- `{}` / `{}`: {:.3}",
+                pair.skill_a, pair.skill_b, pair.similarity
+            );
+        }
+
+        if self.index.enabled && !self.index.is_healthy() {
+            let _ = writeln!(out);
+            let _ = writeln!(out, "## Index health");
+            let _ = writeln!(out);
+            for (label, ids) in [
+                ("Missing", &self.index.missing),
+                ("Stale", &self.index.stale),
+                ("Outdated", &self.index.outdated),
+            ] {
+                if !ids.is_empty() {
+                    let _ = writeln!(out, "- {}: {}", label, ids.join(", "));
+                }
+            }
+        }
+        out
+    }
+
+    fn render_html(&self) -> String {
+        use crate::utils::html_escape;
+
+        fn list(out: &mut String, items: &[String]) {
+            if items.is_empty() {
+                out.push_str("<p>None.</p>\n");
+                return;
+            }
+            out.push_str("<ul>\n");
+            for item in items {
+                let _ = writeln!(out, "<li>{}</li>", item);
+            }
+            out.push_str("</ul>\n");
+        }
+
+        let mut out = String::new();
+        out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        out.push_str("<title>Skill health report</title>\n</head>\n<body>\n");
+        out.push_str("<h1>Skill health report</h1>\n");
+        let _ = writeln!(
+            out,
+            "<p>Period: {} to {}</p>",
+            self.since.format("%Y-%m-%d %H:%M UTC"),
+            self.generated_at.format("%Y-%m-%d %H:%M UTC")
+        );
+
+        out.push_str("<h2>Summary</h2>\n");
+        let summary: Vec<String> = self
+            .summary_lines()
+            .iter()
+            .map(|l| html_escape(l))
+            .collect();
+        list(&mut out, &summary);
+
+        for (title, skills) in [
+            ("New skills", &self.new_skills),
+            ("Updated skills", &self.updated_skills),
+        ] {
+            let _ = writeln!(out, "<h2>{}</h2>", title);
+            let items: Vec<String> = skills
+                .iter()
+                .map(|s| {
+                    format!(
+                        "<code>{}</code> {} ({}, {})",
+                        html_escape(&s.id),
+                        html_escape(&s.name),
+                        html_escape(s.version.as_deref().unwrap_or("unversioned")),
+                        s.modified_at.format("%Y-%m-%d")
+                    )
+                })
+                .collect();
+            list(&mut out, &items);
+        }
+
+        out.push_str("<h2>Validation failures</h2>\n");
+        let items: Vec<String> = self
+            .validation_failures
+            .iter()
+            .map(|f| {
+                format!(
+                    "<code>{}</code>: {}",
+                    html_escape(&f.skill_id),
+                    html_escape(&f.errors.join("; "))
+                )
+            })
+            .collect();
+        list(&mut out, &items);
+
+        out.push_str("<h2>Potential duplicates</h2>\n");
+        let items: Vec<String> = self
+            .duplicates
+            .iter()
+            .map(|p| {
+                format!(
+                    "<code>{}</code> / <code>{}</code>: {:.3}",
+                    html_escape(&p.skill_a),
+                    html_escape(&p.skill_b),
+                    p.similarity
+                )
+            })
+            .collect();
+        list(&mut out, &items);
+
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+/// Write a rendered report into `dir` (created if missing) and return its path.
+pub fn write_report(
+    report: &HealthReport,
+    format: ReportFormat,
+    dir: &Path,
+) -> Result<PathBuf, ServiceError> {
+    let rendered = report.render(format)?;
+    let path = dir.join(report.file_name(format));
+    crate::utils::atomic_write(&path, rendered.as_bytes())?;
+    Ok(path)
+}
+
+/// POST a report to a webhook.
+///
+/// The body is JSON with the rendered report under `text` (the field chat
+/// incoming-webhooks display) plus the structured report under `report`, so the
+/// same endpoint works for chat channels and programmatic consumers.
+pub async fn post_report(
+    report: &HealthReport,
+    format: ReportFormat,
+    webhook_url: &str,
+) -> Result<(), ServiceError> {
+    let body = serde_json::json!({
+        "text": report.render(format)?,
+        "format": format.as_str(),
+        "report": report,
+    });
+
+    let response = reqwest::Client::new()
+        .post(webhook_url)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| ServiceError::Custom(format!("Webhook request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        return Err(ServiceError::Custom(format!(
+            "Webhook returned {}",
+            response.status()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::service::ServiceConfig;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_skill(skills_dir: &Path, id: &str, frontmatter: &str) {
+        let dir = skills_dir.join(id);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            format!("---\n{}\n---\n\n# {}\n", frontmatter, id),
+        )
+        .unwrap();
+    }
+
+    async fn service_for(skills_dir: &Path) -> FastSkillService {
+        let config = ServiceConfig {
+            skill_storage_path: skills_dir.to_path_buf(),
+            ..Default::default()
+        };
+        FastSkillService::new(config).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_health_report_lists_new_skills_and_failures() {
+        let temp_dir = TempDir::new().unwrap();
+        create_skill(
+            temp_dir.path(),
+            "good-skill",
+            "name: good-skill\ndescription: Does a thing\nversion: 1.0.0",
+        );
+        create_skill(temp_dir.path(), "broken-skill", "name: [unterminated");
+
+        let service = service_for(temp_dir.path()).await;
+        let report = service
+            .health_report(&ReportOptions::default())
+            .await
+            .unwrap();
+
+        assert_eq!(report.skills_total, 2);
+        assert_eq!(report.new_skills.len(), 2);
+        assert!(report.updated_skills.is_empty());
+        assert_eq!(report.validation_failures.len(), 1);
+        assert_eq!(report.validation_failures[0].skill_id, "broken-skill");
+        assert!(!report.index.enabled);
+    }
+
+    #[tokio::test]
+    async fn test_health_report_window_excludes_old_skills() {
+        let temp_dir = TempDir::new().unwrap();
+        create_skill(
+            temp_dir.path(),
+            "old-skill",
+            "name: old-skill\ndescription: Old\nversion: 1.0.0",
+        );
+
+        let service = service_for(temp_dir.path()).await;
+        let options = ReportOptions {
+            since: Utc::now() + chrono::Duration::hours(1),
+            ..Default::default()
+        };
+        let report = service.health_report(&options).await.unwrap();
+
+        assert!(report.new_skills.is_empty());
+        assert!(report.updated_skills.is_empty());
+    }
+
+    #[test]
+    fn test_find_duplicate_pairs_orders_and_dedupes() {
+        let a = [1.0, 0.0];
+        let b = [0.99, 0.01];
+        let c = [0.0, 1.0];
+        let pairs = find_duplicate_pairs(&[("b", &b), ("a", &a), ("c", &c)], 0.95);
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].skill_a, "a");
+        assert_eq!(pairs[0].skill_b, "b");
+    }
+
+    #[tokio::test]
+    async fn test_render_formats() {
+        let temp_dir = TempDir::new().unwrap();
+        create_skill(
+            temp_dir.path(),
+            "html-skill",
+            "name: <b>html</b>\ndescription: Escaped\nversion: 1.0.0",
+        );
+        let service = service_for(temp_dir.path()).await;
+        let report = service
+            .health_report(&ReportOptions::default())
+            .await
+            .unwrap();
+
+        let md = report.render(ReportFormat::Markdown).unwrap();
+        assert!(md.starts_with("# Skill health report"));
+        assert!(md.contains("`html-skill`"));
+
+        let html = report.render(ReportFormat::Html).unwrap();
+        assert!(html.contains("&lt;b&gt;html&lt;/b&gt;"));
+        assert!(!html.contains("<b>html</b>"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&report.render(ReportFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["skills_total"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_write_report_uses_dated_file_name() {
+        let skills = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        let service = service_for(skills.path()).await;
+        let report = service
+            .health_report(&ReportOptions::default())
+            .await
+            .unwrap();
+
+        let path = write_report(&report, ReportFormat::Markdown, out.path()).unwrap();
+        assert!(path.exists());
+        assert_eq!(path.extension().unwrap(), "md");
+    }
+}
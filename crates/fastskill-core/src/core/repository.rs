@@ -4,9 +4,13 @@
 
 pub mod client;
 
-pub use client::{CratesRegistryClient, RepositoryClient, RepositoryClientError};
+pub use client::{
+    CratesRegistryClient, RepositoryClient, RepositoryClientError, VendorRepositoryClient,
+};
 
+use crate::core::registry::ResolverCache;
 use crate::core::service::ServiceError;
+use crate::core::vendor::VendorDir;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -116,6 +120,9 @@ pub struct RepositoryManager {
     config_path: PathBuf,
     repositories: HashMap<String, RepositoryDefinition>,
     clients: Arc<RwLock<HashMap<String, Arc<dyn RepositoryClient + Send + Sync>>>>,
+    resolver_cache: Option<Arc<ResolverCache>>,
+    /// When set, every client resolves from this vendor directory only
+    vendor: Option<Arc<VendorDir>>,
 }
 
 impl RepositoryManager {
@@ -125,6 +132,8 @@ impl RepositoryManager {
             config_path,
             repositories: HashMap::new(),
             clients: Arc::new(RwLock::new(HashMap::new())),
+            resolver_cache: None,
+            vendor: None,
         }
     }
 
@@ -156,9 +165,30 @@ impl RepositoryManager {
             config_path,
             repositories: repo_map,
             clients: Arc::new(RwLock::new(HashMap::new())),
+            resolver_cache: None,
+            vendor: None,
         }
     }
 
+    /// Share `cache` with every http-registry client this manager creates, so
+    /// repeated installs reuse registry index lookups
+    pub fn with_resolver_cache(mut self, cache: Arc<ResolverCache>) -> Self {
+        self.resolver_cache = Some(cache);
+        self
+    }
+
+    /// Resolve exclusively from `vendor`: every repository name is served by
+    /// a [`VendorRepositoryClient`] and no network client is ever created.
+    pub fn offline(mut self, vendor: VendorDir) -> Self {
+        self.vendor = Some(Arc::new(vendor));
+        self
+    }
+
+    /// The vendor directory, when this manager is offline
+    pub fn vendor(&self) -> Option<&VendorDir> {
+        self.vendor.as_deref()
+    }
+
     /// Load repositories from TOML file
     /// Loads from repositories.toml only
     pub fn load(&mut self) -> Result<(), ServiceError> {
@@ -231,6 +261,10 @@ impl RepositoryManager {
                 metadata: None,
                 dependencies: None,
                 tool: None,
+                optional_dependencies: None,
+                peer_dependencies: None,
+                requires: None,
+                env: None,
             }
         };
 
@@ -251,8 +285,12 @@ impl RepositoryManager {
                     server: None,
                     install_depth: 5,
                     skip_transitive: false,
+                    resolution: Default::default(),
                     eval: None,
                     auto_reindex: true,
+                    offline: false,
+                    naming: Default::default(),
+                    link_mode: Default::default(),
                 }),
             });
         } else if let Some(ref mut tool) = project.tool {
@@ -264,8 +302,12 @@ impl RepositoryManager {
                     server: None,
                     install_depth: 5,
                     skip_transitive: false,
+                    resolution: Default::default(),
                     eval: None,
                     auto_reindex: true,
+                    offline: false,
+                    naming: Default::default(),
+                    link_mode: Default::default(),
                 });
             } else if let Some(ref mut fastskill) = tool.fastskill {
                 fastskill.repositories = Some(manifest_repos);
@@ -401,6 +443,10 @@ impl RepositoryManager {
         &self,
         name: &str,
     ) -> Result<Arc<dyn RepositoryClient + Send + Sync>, ServiceError> {
+        if let Some(vendor) = &self.vendor {
+            return Ok(Arc::new(VendorRepositoryClient::new(Arc::clone(vendor))));
+        }
+
         // Check cache first
         {
             let clients = self.clients.read().await;
@@ -415,7 +461,8 @@ impl RepositoryManager {
             .get(name)
             .ok_or_else(|| ServiceError::Custom(format!("Repository '{}' not found", name)))?;
 
-        let client_arc = client::create_client(repo).await?;
+        let client_arc =
+            client::create_client_with_cache(repo, self.resolver_cache.clone()).await?;
 
         // Cache it
         let mut clients = self.clients.write().await;
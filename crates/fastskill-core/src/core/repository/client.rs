@@ -1,11 +1,12 @@
 //! Repository client abstraction for unified skill access
 
 use crate::core::metadata::SkillMetadata;
-use crate::core::registry::{RegistryClient, RegistryConfig as OldRegistryConfig};
+use crate::core::registry::{RegistryClient, RegistryConfig as OldRegistryConfig, ResolverCache};
 use crate::core::registry_index::{ListSkillsOptions, SkillSummary};
 use crate::core::repository::{RepositoryConfig, RepositoryDefinition, RepositoryType};
 use crate::core::service::{ServiceError, SkillId};
 use crate::core::sources::{SourceConfig, SourceDefinition, SourcesManager};
+use crate::core::vendor::{VendorDir, VendoredSkill};
 use reqwest::Client;
 use std::sync::Arc;
 
@@ -41,17 +42,41 @@ pub trait RepositoryClient: Send + Sync {
 
     /// Get all versions for a skill
     async fn get_versions(&self, id: &str) -> Result<Vec<String>, RepositoryClientError>;
+
+    /// Size in bytes of a skill package, when it is known without downloading
+    async fn download_size(
+        &self,
+        _id: &str,
+        _version: &str,
+    ) -> Result<Option<u64>, RepositoryClientError> {
+        Ok(None)
+    }
 }
 
 /// Create a repository client from a repository definition
 pub async fn create_client(
     repo: &RepositoryDefinition,
+) -> Result<Arc<dyn RepositoryClient + Send + Sync>, ServiceError> {
+    create_client_with_cache(repo, None).await
+}
+
+/// Create a repository client whose registry index lookups go through
+/// `resolver_cache` (http-registry repositories only).
+pub async fn create_client_with_cache(
+    repo: &RepositoryDefinition,
+    resolver_cache: Option<Arc<ResolverCache>>,
 ) -> Result<Arc<dyn RepositoryClient + Send + Sync>, ServiceError> {
     match repo.repo_type {
         RepositoryType::GitMarketplace | RepositoryType::ZipUrl | RepositoryType::Local => {
             Ok(Arc::new(MarketplaceRepositoryClient::new(repo)?))
         }
-        RepositoryType::HttpRegistry => Ok(Arc::new(CratesRegistryClient::new(repo)?)),
+        RepositoryType::HttpRegistry => {
+            let client = CratesRegistryClient::new(repo)?;
+            Ok(Arc::new(match resolver_cache {
+                Some(cache) => client.with_resolver_cache(cache),
+                None => client,
+            }))
+        }
     }
 }
 
@@ -328,6 +353,12 @@ impl CratesRegistryClient {
         })
     }
 
+    /// Cache registry index lookups (versions + metadata) in `cache`
+    pub fn with_resolver_cache(mut self, cache: Arc<ResolverCache>) -> Self {
+        self.registry_client = self.registry_client.with_resolver_cache(cache);
+        self
+    }
+
     /// Fetch skills from the registry HTTP API endpoint
     pub async fn fetch_skills(
         &self,
@@ -335,8 +366,10 @@ impl CratesRegistryClient {
     ) -> Result<Vec<SkillSummary>, RepositoryClientError> {
         use crate::core::registry::auth::Auth;
 
-        // Build the API endpoint URL
-        let base_url = self.index_url.trim_end_matches('/');
+        // Build the API endpoint URL. The index itself may live under
+        // `<server>/index` (as `fastskill serve` mounts it); the API does not.
+        let index_url = self.index_url.trim_end_matches('/');
+        let base_url = index_url.strip_suffix("/index").unwrap_or(index_url);
         let mut url = format!("{}/api/v1/registry/index/skills", base_url);
 
         // Add query parameters
@@ -402,6 +435,8 @@ impl CratesRegistryClient {
 
         // Handle HTTP status codes
         let status = response.status();
+        #[cfg(feature = "chaos")]
+        let status = crate::core::chaos::upstream_status(status);
         match status.as_u16() {
             200 => {
                 // Parse JSON response
@@ -533,6 +568,97 @@ impl RepositoryClient for CratesRegistryClient {
             .map_err(RepositoryClientError::Service)?;
         Ok(versions)
     }
+
+    async fn download_size(
+        &self,
+        id: &str,
+        version: &str,
+    ) -> Result<Option<u64>, RepositoryClientError> {
+        self.registry_client
+            .download_size(id, version)
+            .await
+            .map_err(RepositoryClientError::Service)
+    }
+}
+
+/// Client that serves skills from a vendor directory only (offline mode)
+pub struct VendorRepositoryClient {
+    vendor: Arc<VendorDir>,
+}
+
+impl VendorRepositoryClient {
+    pub fn new(vendor: Arc<VendorDir>) -> Self {
+        Self { vendor }
+    }
+
+    fn metadata(skill: &VendoredSkill) -> Option<SkillMetadata> {
+        SkillId::new(skill.id.clone()).ok().map(|id| SkillMetadata {
+            id,
+            name: skill.name.clone(),
+            description: skill.description.clone(),
+            version: skill.version.clone(),
+            author: skill.author.clone(),
+            token_estimate: skill.description.len() / 4,
+            last_updated: skill.vendored_at,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl RepositoryClient for VendorRepositoryClient {
+    async fn list_skills(&self) -> Result<Vec<SkillMetadata>, RepositoryClientError> {
+        Ok(self
+            .vendor
+            .skills()
+            .iter()
+            .filter_map(Self::metadata)
+            .collect())
+    }
+
+    async fn get_skill(
+        &self,
+        id: &str,
+        version: Option<&str>,
+    ) -> Result<Option<SkillMetadata>, RepositoryClientError> {
+        Ok(self.vendor.find(id, version).and_then(Self::metadata))
+    }
+
+    async fn search(&self, query: &str) -> Result<Vec<SkillMetadata>, RepositoryClientError> {
+        let query = query.to_lowercase();
+        Ok(self
+            .vendor
+            .skills()
+            .iter()
+            .filter(|s| {
+                s.id.to_lowercase().contains(&query)
+                    || s.name.to_lowercase().contains(&query)
+                    || s.description.to_lowercase().contains(&query)
+            })
+            .filter_map(Self::metadata)
+            .collect())
+    }
+
+    async fn download(&self, id: &str, version: &str) -> Result<Vec<u8>, RepositoryClientError> {
+        let skill = self.vendor.find(id, Some(version)).ok_or_else(|| {
+            RepositoryClientError::Client(format!("{} {} is not vendored", id, version))
+        })?;
+        Ok(self.vendor.read_archive(skill)?)
+    }
+
+    async fn get_versions(&self, id: &str) -> Result<Vec<String>, RepositoryClientError> {
+        Ok(self.vendor.versions(id))
+    }
+
+    async fn download_size(
+        &self,
+        id: &str,
+        version: &str,
+    ) -> Result<Option<u64>, RepositoryClientError> {
+        match self.vendor.find(id, Some(version)) {
+            Some(skill) => Ok(Some(self.vendor.archive_size(skill)?)),
+            None => Ok(None),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -811,6 +937,7 @@ mod tests {
             latest_version: "1.0.0".to_string(),
             published_at: None,
             versions: None,
+            retired: None,
         }
     }
 
@@ -859,6 +986,24 @@ mod tests {
         assert!(result.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_fetch_skills_from_index_mount() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(SKILLS_PATH))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .mount(&server)
+            .await;
+
+        let index_url = format!("{}/index/", server.uri());
+        let client = CratesRegistryClient::new(&http_registry(&index_url, None)).unwrap();
+        let result = client
+            .fetch_skills(&ListSkillsOptions::default())
+            .await
+            .unwrap();
+        assert!(result.is_empty());
+    }
+
     async fn assert_status_maps_to_err(status: u16) {
         let server = MockServer::start().await;
         Mock::given(method("GET"))
@@ -955,4 +1100,41 @@ mod tests {
             CratesRegistryClient::new(&http_registry("http://example.com/index", None)).unwrap();
         assert!(client.search("q").await.unwrap().is_empty());
     }
+
+    // ── VendorRepositoryClient ────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_vendor_client_serves_vendored_skills_only() {
+        use crate::core::origin::Origin;
+        use crate::core::skill_manager::SkillDefinition;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let skill_dir = temp_dir.path().join("pdf-tools");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        std::fs::write(skill_dir.join("SKILL.md"), "---\nname: pdf-tools\n---\n").unwrap();
+        let skill = SkillDefinition::new(
+            SkillId::new("pdf-tools".to_string()).unwrap(),
+            "pdf-tools".to_string(),
+            "Work with PDFs".to_string(),
+            "1.0.0".to_string(),
+            Origin::Repository {
+                repo: "team".to_string(),
+                skill: "pdf-tools".to_string(),
+                version: None,
+            },
+        );
+        let mut vendor = VendorDir::new(&temp_dir.path().join("vendor"));
+        vendor.add(&skill, &skill_dir).unwrap();
+
+        let client = VendorRepositoryClient::new(Arc::new(vendor));
+        assert_eq!(client.get_versions("pdf-tools").await.unwrap(), ["1.0.0"]);
+        assert_eq!(client.search("pdf").await.unwrap().len(), 1);
+        assert!(!client
+            .download("pdf-tools", "1.0.0")
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(client.download("pdf-tools", "2.0.0").await.is_err());
+        assert!(client.get_skill("csv-tools", None).await.unwrap().is_none());
+    }
 }
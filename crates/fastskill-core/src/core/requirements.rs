@@ -0,0 +1,384 @@
+//! Engine and runtime versions a skill requires
+//!
+//! A skill states the FastSkill version and the runtimes its scripts need
+//! under `requires` in its SKILL.md frontmatter:
+//!
+//! ```yaml
+//! requires:
+//!   fastskill: ">=0.9"
+//!   python: ">=3.10"
+//!   node: "^20"
+//! ```
+//!
+//! or under `[requires]` in its own `skill-project.toml`, which wins field by
+//! field. Values use the same constraint syntax as skill dependencies.
+//! Installing a skill whose requirements the environment does not meet fails;
+//! the resolver skips registry versions that declare unmet requirements.
+
+use crate::core::manifest::{ManifestError, SkillProjectToml};
+use crate::core::metadata::SkillFrontmatter;
+use crate::core::service::ServiceError;
+use crate::core::version::VersionConstraint;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Version constraints a skill places on its environment
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Requirements {
+    /// FastSkill (this engine)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fastskill: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub python: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<String>,
+}
+
+impl Requirements {
+    pub fn is_empty(&self) -> bool {
+        self.fastskill.is_none() && self.python.is_none() && self.node.is_none()
+    }
+
+    /// `self` with every field `other` sets replaced by `other`'s
+    pub fn overridden_by(self, other: Requirements) -> Self {
+        Self {
+            fastskill: other.fastskill.or(self.fastskill),
+            python: other.python.or(self.python),
+            node: other.node.or(self.node),
+        }
+    }
+
+    /// The declared constraints, in `fastskill`, `python`, `node` order
+    pub fn entries(&self) -> Vec<(Runtime, &str)> {
+        [
+            (Runtime::FastSkill, &self.fastskill),
+            (Runtime::Python, &self.python),
+            (Runtime::Node, &self.node),
+        ]
+        .into_iter()
+        .filter_map(|(runtime, constraint)| constraint.as_deref().map(|c| (runtime, c)))
+        .collect()
+    }
+
+    /// Constraints that do not parse, as `(runtime, message)`
+    pub fn invalid(&self) -> Vec<(Runtime, String)> {
+        self.entries()
+            .into_iter()
+            .filter_map(|(runtime, constraint)| {
+                VersionConstraint::parse(constraint)
+                    .err()
+                    .map(|e| (runtime, format!("requires.{}: {}", runtime.key(), e)))
+            })
+            .collect()
+    }
+}
+
+/// Something a skill can require a version of
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Runtime {
+    FastSkill,
+    Python,
+    Node,
+}
+
+impl Runtime {
+    /// Key under `requires`
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::FastSkill => "fastskill",
+            Self::Python => "python",
+            Self::Node => "node",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::FastSkill => "FastSkill",
+            Self::Python => "Python",
+            Self::Node => "Node.js",
+        }
+    }
+
+    /// Commands tried, in order, to find the runtime on PATH
+    fn commands(&self) -> &'static [&'static str] {
+        match self {
+            Self::FastSkill => &[],
+            Self::Python => &["python3", "python"],
+            Self::Node => &["node"],
+        }
+    }
+}
+
+/// Versions found in the environment; `None` when a runtime is not installed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Environment {
+    pub fastskill: String,
+    pub python: Option<String>,
+    pub node: Option<String>,
+}
+
+impl Environment {
+    /// This FastSkill and the Python and Node.js on PATH. Probed once per
+    /// process; call from blocking context, as it runs the interpreters.
+    pub fn current() -> &'static Environment {
+        static CURRENT: OnceLock<Environment> = OnceLock::new();
+        CURRENT.get_or_init(|| Environment {
+            fastskill: env!("CARGO_PKG_VERSION").to_string(),
+            python: probe(Runtime::Python),
+            node: probe(Runtime::Node),
+        })
+    }
+
+    pub fn version_of(&self, runtime: Runtime) -> Option<&str> {
+        match runtime {
+            Runtime::FastSkill => Some(&self.fastskill),
+            Runtime::Python => self.python.as_deref(),
+            Runtime::Node => self.node.as_deref(),
+        }
+    }
+}
+
+/// First version printed by `<command> --version` among the runtime's commands
+fn probe(runtime: Runtime) -> Option<String> {
+    runtime.commands().iter().find_map(|command| {
+        let output = std::process::Command::new(command)
+            .arg("--version")
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        // Python 2 printed its version to stderr
+        let text = if output.stdout.is_empty() {
+            output.stderr
+        } else {
+            output.stdout
+        };
+        normalize_version(&String::from_utf8_lossy(&text))
+    })
+}
+
+/// `Python 3.12.1`, `v20.11.0` or `3.13.0rc1` as a full `MAJOR.MINOR.PATCH`
+/// (`3.12.1`, `20.11.0`, `3.13.0`); missing components count as 0.
+pub fn normalize_version(raw: &str) -> Option<String> {
+    let word = raw.split_whitespace().find(|w| {
+        w.trim_start_matches('v')
+            .starts_with(|c: char| c.is_ascii_digit())
+    })?;
+    let mut parts: Vec<u64> = Vec::new();
+    for part in word.trim_start_matches('v').split('.').take(3) {
+        let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+        let Ok(n) = digits.parse() else { break };
+        parts.push(n);
+        if digits.len() < part.len() {
+            break;
+        }
+    }
+    if parts.is_empty() {
+        return None;
+    }
+    parts.resize(3, 0);
+    Some(format!("{}.{}.{}", parts[0], parts[1], parts[2]))
+}
+
+/// A requirement the environment does not meet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RequirementViolation {
+    pub runtime: Runtime,
+    /// Declared constraint, as written
+    pub constraint: String,
+    /// Version found, if the runtime is installed at all
+    pub found: Option<String>,
+    /// What is wrong and how to fix it
+    pub message: String,
+}
+
+/// Requirements in `requirements` that `env` does not meet. A constraint that
+/// does not parse is a violation too.
+pub fn check(requirements: &Requirements, env: &Environment) -> Vec<RequirementViolation> {
+    let mut violations = Vec::new();
+    for (runtime, constraint) in requirements.entries() {
+        let found = env.version_of(runtime).map(str::to_string);
+        let name = runtime.display_name();
+        let message = match VersionConstraint::parse(constraint) {
+            Err(e) => format!(
+                "invalid requires.{} constraint '{}': {}",
+                runtime.key(),
+                constraint,
+                e
+            ),
+            Ok(parsed) => match &found {
+                None => format!(
+                    "requires {} {} but {} was not found on PATH (tried {}); install {} {} \
+                     and make sure it is on PATH",
+                    name,
+                    constraint,
+                    name,
+                    runtime.commands().join(", "),
+                    name,
+                    constraint
+                ),
+                Some(version) if parsed.satisfies(version).unwrap_or(false) => continue,
+                Some(version) if runtime == Runtime::FastSkill => format!(
+                    "requires FastSkill {} but this is FastSkill {}; upgrade with \
+                     'fastskill self-update'",
+                    constraint, version
+                ),
+                Some(version) => format!(
+                    "requires {} {} but {} {} is installed; install a matching version \
+                     and put it first on PATH",
+                    name, constraint, name, version
+                ),
+            },
+        };
+        violations.push(RequirementViolation {
+            runtime,
+            constraint: constraint.to_string(),
+            found,
+            message,
+        });
+    }
+    violations
+}
+
+/// Requirements under `requires` in `frontmatter`; none without the key
+pub fn frontmatter_requirements(
+    frontmatter: &SkillFrontmatter,
+) -> Result<Requirements, ServiceError> {
+    let Some(raw) = frontmatter.extra.get("requires") else {
+        return Ok(Requirements::default());
+    };
+    serde_yaml::from_value(raw.clone())
+        .map_err(|e| ServiceError::Validation(format!("Invalid requires in SKILL.md: {}", e)))
+}
+
+/// Requirements of the skill in `skill_dir`: the frontmatter's, overridden by
+/// `[requires]` in the skill's own `skill-project.toml`
+pub fn declared_requirements(
+    skill_dir: &Path,
+    frontmatter: &SkillFrontmatter,
+) -> Result<Requirements, ServiceError> {
+    let from_frontmatter = frontmatter_requirements(frontmatter)?;
+    let manifest = skill_dir.join("skill-project.toml");
+    match SkillProjectToml::load_from_file(&manifest) {
+        Ok(project) => Ok(from_frontmatter.overridden_by(project.requires.unwrap_or_default())),
+        Err(ManifestError::NotFound(_)) => Ok(from_frontmatter),
+        Err(e) => Err(ServiceError::Validation(format!(
+            "Invalid skill-project.toml in {}: {}",
+            skill_dir.display(),
+            e
+        ))),
+    }
+}
+
+/// Fail with every unmet requirement of `skill` when the current environment
+/// does not satisfy `requirements`
+pub async fn ensure_satisfied(
+    skill: &str,
+    requirements: &Requirements,
+) -> Result<(), ServiceError> {
+    if requirements.is_empty() {
+        return Ok(());
+    }
+    let env = tokio::task::spawn_blocking(Environment::current)
+        .await
+        .map_err(|e| ServiceError::Custom(format!("Probing runtimes failed: {}", e)))?;
+    let violations = check(requirements, env);
+    if violations.is_empty() {
+        return Ok(());
+    }
+    let details: Vec<String> = violations.iter().map(|v| v.message.clone()).collect();
+    Err(ServiceError::Validation(format!(
+        "Skill '{}' {}",
+        skill,
+        details.join("; it also ")
+    )))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::metadata::parse_yaml_frontmatter;
+    use tempfile::TempDir;
+
+    fn env(python: Option<&str>) -> Environment {
+        Environment {
+            fastskill: "0.9.0".to_string(),
+            python: python.map(str::to_string),
+            node: None,
+        }
+    }
+
+    #[test]
+    fn test_normalize_version() {
+        assert_eq!(
+            normalize_version("Python 3.12.1\n").as_deref(),
+            Some("3.12.1")
+        );
+        assert_eq!(normalize_version("v20.11.0").as_deref(), Some("20.11.0"));
+        assert_eq!(
+            normalize_version("Python 3.13.0rc1").as_deref(),
+            Some("3.13.0")
+        );
+        assert_eq!(normalize_version("3.10").as_deref(), Some("3.10.0"));
+        assert_eq!(normalize_version("command not found"), None);
+    }
+
+    #[test]
+    fn test_check_reports_unmet_requirements() {
+        let requirements = Requirements {
+            fastskill: Some(">=1.0".to_string()),
+            python: Some(">=3.10".to_string()),
+            node: Some(">=18".to_string()),
+        };
+        let violations = check(&requirements, &env(Some("3.8.10")));
+        assert_eq!(violations.len(), 3);
+        assert!(violations[0].message.contains("fastskill self-update"));
+        assert_eq!(violations[1].found.as_deref(), Some("3.8.10"));
+        assert!(violations[2].message.contains("not found on PATH"));
+
+        let met = Requirements {
+            fastskill: Some("^0.9".to_string()),
+            python: Some(">=3.10".to_string()),
+            node: None,
+        };
+        assert!(check(&met, &env(Some("3.12.1"))).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_constraint_is_reported() {
+        let requirements = Requirements {
+            python: Some("three".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(requirements.invalid().len(), 1);
+        let violations = check(&requirements, &env(Some("3.12.1")));
+        assert!(violations[0].message.contains("invalid requires.python"));
+    }
+
+    #[test]
+    fn test_declared_requirements_manifest_overrides_frontmatter() {
+        let dir = TempDir::new().unwrap();
+        let content = "---\nname: s\ndescription: d\nrequires:\n  fastskill: \">=0.5\"\n  \
+                       python: \">=3.8\"\n---\n";
+        std::fs::write(
+            dir.path().join("skill-project.toml"),
+            "[metadata]\nid = \"s\"\nversion = \"1.0.0\"\n\n[requires]\npython = \">=3.11\"\n",
+        )
+        .unwrap();
+        let frontmatter = parse_yaml_frontmatter(content).unwrap();
+
+        let requirements = declared_requirements(dir.path(), &frontmatter).unwrap();
+        assert_eq!(requirements.fastskill.as_deref(), Some(">=0.5"));
+        assert_eq!(requirements.python.as_deref(), Some(">=3.11"));
+        assert_eq!(requirements.node, None);
+
+        let unknown = "---\nname: s\ndescription: d\nrequires:\n  ruby: \"3\"\n---\n";
+        let frontmatter = parse_yaml_frontmatter(unknown).unwrap();
+        assert!(frontmatter_requirements(&frontmatter).is_err());
+    }
+}
@@ -1,6 +1,7 @@
 //! Package resolver for unified skill resolution across multiple sources
 
 use crate::core::dependencies::Dependency;
+use crate::core::requirements::{self, Environment, Requirements};
 use crate::core::sources::{SourceConfig, SourcesManager};
 use crate::core::version::{VersionConstraint, VersionError};
 use std::collections::{HashMap, HashSet};
@@ -18,6 +19,10 @@ pub struct SkillCandidate {
     pub source_config: SourceConfig,
     pub download_url: Option<String>,
     pub commit_hash: Option<String>,
+    /// Withdrawn by the publisher; only selected when skills.lock pins it
+    pub yanked: bool,
+    /// Engine and runtime versions this version needs
+    pub requires: Option<Requirements>,
 }
 
 /// Conflict resolution strategy
@@ -55,12 +60,20 @@ pub enum ResolverError {
 
     #[error("Source error: {0}")]
     SourceError(String),
+
+    #[error("Requirements not met: {0}")]
+    RequirementsNotMet(String),
 }
 
 /// Package resolver for unified skill resolution
 pub struct PackageResolver {
     sources_manager: Arc<SourcesManager>,
     skill_index: HashMap<String, Vec<SkillCandidate>>,
+    /// Skill id to the version skills.lock pins it at
+    locked_versions: HashMap<String, String>,
+    /// Environment candidates' `requires` are checked against; unchecked
+    /// when unset
+    environment: Option<Environment>,
 }
 
 impl PackageResolver {
@@ -69,9 +82,45 @@ impl PackageResolver {
         Self {
             sources_manager,
             skill_index: HashMap::new(),
+            locked_versions: HashMap::new(),
+            environment: None,
+        }
+    }
+
+    /// Versions pinned by skills.lock (skill id to version). A yanked version
+    /// stays resolvable for the skill that pins it exactly; every other
+    /// yanked version is skipped.
+    pub fn with_locked_versions(mut self, locked_versions: HashMap<String, String>) -> Self {
+        self.locked_versions = locked_versions;
+        self
+    }
+
+    /// Skip candidates whose `requires` this environment does not meet
+    pub fn with_environment(mut self, environment: Environment) -> Self {
+        self.environment = Some(environment);
+        self
+    }
+
+    /// What is unmet about `candidate`'s requirements; empty when nothing is
+    /// or no environment was set
+    fn unmet_requirements(&self, candidate: &SkillCandidate) -> Vec<String> {
+        match (&self.environment, &candidate.requires) {
+            (Some(env), Some(requires)) => requirements::check(requires, env)
+                .into_iter()
+                .map(|violation| violation.message)
+                .collect(),
+            _ => Vec::new(),
         }
     }
 
+    fn is_selectable(&self, candidate: &SkillCandidate) -> bool {
+        !candidate.yanked
+            || self
+                .locked_versions
+                .get(&candidate.id)
+                .is_some_and(|locked| *locked == candidate.version)
+    }
+
     /// Build the unified skill index from all sources
     pub async fn build_index(&mut self) -> Result<(), ResolverError> {
         self.skill_index.clear();
@@ -105,6 +154,8 @@ impl PackageResolver {
                 source_config: source_def.source.clone(),
                 download_url: None,
                 commit_hash: None,
+                yanked: skill_info.yanked,
+                requires: skill_info.requires,
             };
 
             self.skill_index
@@ -160,6 +211,17 @@ impl PackageResolver {
             return Err(ResolverError::NotFound(skill_id.to_string()));
         }
 
+        let filtered_candidates: Vec<&SkillCandidate> = filtered_candidates
+            .into_iter()
+            .filter(|c| self.is_selectable(c))
+            .collect();
+        if filtered_candidates.is_empty() {
+            return Err(ResolverError::ConstraintNotSatisfied(format!(
+                "Every version of skill '{}' has been yanked",
+                skill_id
+            )));
+        }
+
         // Filter by version constraint if specified
         let constraint_filtered: Vec<&SkillCandidate> = if let Some(constraint) = version_constraint
         {
@@ -179,6 +241,30 @@ impl PackageResolver {
             )));
         }
 
+        let requirement_filtered: Vec<&SkillCandidate> = constraint_filtered
+            .iter()
+            .filter(|c| self.unmet_requirements(c).is_empty())
+            .copied()
+            .collect();
+        if requirement_filtered.is_empty() {
+            // Explain with the newest version the user could otherwise have had
+            let newest = constraint_filtered
+                .iter()
+                .max_by(|a, b| {
+                    crate::core::version::compare_versions(&a.version, &b.version)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .copied()
+                .ok_or_else(|| ResolverError::NotFound(skill_id.to_string()))?;
+            return Err(ResolverError::RequirementsNotMet(format!(
+                "no version of skill '{}' works in this environment; {} {}",
+                skill_id,
+                newest.version,
+                self.unmet_requirements(newest).join("; it also ")
+            )));
+        }
+        let constraint_filtered = requirement_filtered;
+
         // Resolve conflict if multiple candidates
         let selected = if constraint_filtered.len() == 1 {
             constraint_filtered[0]
@@ -214,11 +300,17 @@ impl PackageResolver {
         })
     }
 
-    /// Get all available versions of a skill
+    /// Get all available versions of a skill. Yanked versions are left out
+    /// unless skills.lock pins them.
     pub fn get_available_versions(&self, skill_id: &str) -> Vec<&SkillCandidate> {
         self.skill_index
             .get(skill_id)
-            .map(|candidates| candidates.iter().collect())
+            .map(|candidates| {
+                candidates
+                    .iter()
+                    .filter(|c| self.is_selectable(c))
+                    .collect()
+            })
             .unwrap_or_default()
     }
 
@@ -368,6 +460,15 @@ mod tests {
             },
             download_url: None,
             commit_hash: None,
+            yanked: false,
+            requires: None,
+        }
+    }
+
+    fn yanked(id: &str, version: &str, source: &str) -> SkillCandidate {
+        SkillCandidate {
+            yanked: true,
+            ..candidate(id, version, source)
         }
     }
 
@@ -496,6 +597,74 @@ mod tests {
         assert!(matches!(err, ResolverError::MultipleCandidates));
     }
 
+    #[test]
+    fn test_resolve_skill_skips_yanked_unless_locked() {
+        let r = resolver_with(vec![(
+            "a",
+            vec![candidate("a", "1.0.0", "s1"), yanked("a", "1.1.0", "s1")],
+        )]);
+        let res = r
+            .resolve_skill("a", None, None, ConflictStrategy::HighestVersion)
+            .unwrap();
+        assert_eq!(res.candidate.version, "1.0.0");
+        let exact = VersionConstraint::parse("1.1.0").unwrap();
+        let err = r
+            .resolve_skill("a", Some(&exact), None, ConflictStrategy::Priority)
+            .unwrap_err();
+        assert!(matches!(err, ResolverError::ConstraintNotSatisfied(_)));
+        assert_eq!(r.get_available_versions("a").len(), 1);
+
+        let r = r.with_locked_versions(HashMap::from([("a".to_string(), "1.1.0".to_string())]));
+        let res = r
+            .resolve_skill("a", Some(&exact), None, ConflictStrategy::Priority)
+            .unwrap();
+        assert_eq!(res.candidate.version, "1.1.0");
+        assert_eq!(r.get_available_versions("a").len(), 2);
+    }
+
+    #[test]
+    fn test_resolve_skill_all_yanked() {
+        let r = resolver_with(vec![("a", vec![yanked("a", "1.0.0", "s1")])]);
+        let err = r
+            .resolve_skill("a", None, None, ConflictStrategy::Priority)
+            .unwrap_err();
+        assert!(err.to_string().contains("yanked"), "{}", err);
+    }
+
+    #[test]
+    fn test_resolve_skill_skips_unmet_requirements() {
+        let needs = |fastskill: &str| Requirements {
+            fastskill: Some(fastskill.to_string()),
+            ..Default::default()
+        };
+        let env = Environment {
+            fastskill: "0.9.0".to_string(),
+            python: None,
+            node: None,
+        };
+        let newer = SkillCandidate {
+            requires: Some(needs(">=1.0")),
+            ..candidate("a", "2.0.0", "s1")
+        };
+        let older = SkillCandidate {
+            requires: Some(needs(">=0.8")),
+            ..candidate("a", "1.0.0", "s1")
+        };
+        let r =
+            resolver_with(vec![("a", vec![newer.clone(), older])]).with_environment(env.clone());
+        let res = r
+            .resolve_skill("a", None, None, ConflictStrategy::HighestVersion)
+            .unwrap();
+        assert_eq!(res.candidate.version, "1.0.0");
+
+        let r = resolver_with(vec![("a", vec![newer])]).with_environment(env);
+        let err = r
+            .resolve_skill("a", None, None, ConflictStrategy::Priority)
+            .unwrap_err();
+        assert!(matches!(err, ResolverError::RequirementsNotMet(_)));
+        assert!(err.to_string().contains("fastskill self-update"), "{}", err);
+    }
+
     // ---- introspection helpers ----------------------------------------------
 
     #[test]
@@ -0,0 +1,375 @@
+//! Skill retirement: announce that a skill is going away, find who depends
+//! on it, and remove it from the registry index once the grace period ends
+//!
+//! Retirements are recorded in `<registry index>/.retirements.json`, which
+//! the index scan skips as a hidden file. A retired skill keeps installing
+//! until its `remove_after` date; registry listings carry the retirement so
+//! clients can warn. [`purge_expired`] deletes the index files of skills whose
+//! grace period has passed.
+
+use crate::core::lock::ProjectSkillsLock;
+use crate::core::registry_index::{
+    bump_generation, get_skill_index_path, read_skill_versions, ScopedSkillName,
+};
+use crate::core::service::ServiceError;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Retirement records at the index root; the leading dot keeps the index
+/// scan from reading it as a skill.
+pub const RETIREMENTS_FILE: &str = ".retirements.json";
+
+/// A skill announced for removal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Retirement {
+    pub retired_at: DateTime<Utc>,
+    /// When [`purge_expired`] may delete the skill from the index
+    pub remove_after: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Skill to move to instead
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replacement: Option<String>,
+}
+
+/// A published skill whose versions declare a dependency on the retired one
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Dependent {
+    pub skill_id: String,
+    /// Non-yanked versions with the dependency, in index order
+    pub versions: Vec<String>,
+    /// Version requirement of the most recently published of those versions
+    pub req: String,
+}
+
+/// A project whose `skills.lock` includes the retired skill
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AffectedProject {
+    pub lock_path: PathBuf,
+    pub locked_version: String,
+    /// Declared by the project rather than pulled in by another skill
+    pub direct: bool,
+    /// The skill that pulled it in, for transitive dependencies
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required_by: Option<String>,
+}
+
+/// What retiring a skill affects
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetirementReport {
+    pub skill_id: String,
+    pub retirement: Retirement,
+    pub dependents: Vec<Dependent>,
+    pub affected_projects: Vec<AffectedProject>,
+}
+
+/// All retirement records, keyed by skill id. A missing file means none.
+pub fn load_retirements(
+    registry_path: &Path,
+) -> Result<BTreeMap<String, Retirement>, ServiceError> {
+    let path = registry_path.join(RETIREMENTS_FILE);
+    match std::fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).map_err(|e| {
+            ServiceError::Custom(format!("Failed to parse {}: {}", path.display(), e))
+        }),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(e) => Err(ServiceError::Io(e)),
+    }
+}
+
+fn save_retirements(
+    registry_path: &Path,
+    retirements: &BTreeMap<String, Retirement>,
+) -> Result<(), ServiceError> {
+    let json = serde_json::to_string_pretty(retirements)
+        .map_err(|e| ServiceError::Custom(format!("Failed to encode retirements: {}", e)))?;
+    crate::utils::atomic_write(&registry_path.join(RETIREMENTS_FILE), json.as_bytes())
+        .map_err(ServiceError::Io)?;
+    bump_generation(registry_path)
+}
+
+/// Mark a published skill retired, to be removed `grace` from now. Retiring
+/// it again replaces the earlier record.
+pub fn retire_skill(
+    registry_path: &Path,
+    skill_id: &str,
+    grace: Duration,
+    reason: Option<String>,
+    replacement: Option<String>,
+) -> Result<Retirement, ServiceError> {
+    if grace < Duration::zero() {
+        return Err(ServiceError::Validation(
+            "Grace period cannot be negative".to_string(),
+        ));
+    }
+    let skill_id = ScopedSkillName::normalize(skill_id);
+    if read_skill_versions(registry_path, &skill_id)?.is_empty() {
+        return Err(ServiceError::SkillNotFound(skill_id));
+    }
+
+    let retired_at = Utc::now();
+    let retirement = Retirement {
+        retired_at,
+        remove_after: retired_at + grace,
+        reason,
+        replacement: replacement.map(|id| ScopedSkillName::normalize(&id)),
+    };
+    let mut retirements = load_retirements(registry_path)?;
+    retirements.insert(skill_id, retirement.clone());
+    save_retirements(registry_path, &retirements)?;
+    Ok(retirement)
+}
+
+/// Withdraw a retirement. Returns whether the skill was retired.
+pub fn unretire_skill(registry_path: &Path, skill_id: &str) -> Result<bool, ServiceError> {
+    let mut retirements = load_retirements(registry_path)?;
+    if retirements
+        .remove(&ScopedSkillName::normalize(skill_id))
+        .is_none()
+    {
+        return Ok(false);
+    }
+    save_retirements(registry_path, &retirements)?;
+    Ok(true)
+}
+
+/// Every `{org}/{package}` index file in the registry
+fn indexed_skill_ids(registry_path: &Path) -> Vec<String> {
+    WalkDir::new(registry_path)
+        .min_depth(2)
+        .max_depth(2)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(registry_path).ok()?;
+            let parts: Vec<&str> = relative.iter().filter_map(|c| c.to_str()).collect();
+            match parts.as_slice() {
+                [org, package] if !org.starts_with('.') && !package.starts_with('.') => {
+                    Some(format!("{}/{}", org, package))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Published skills with a non-yanked version that depends on `skill_id`
+pub fn find_dependents(
+    registry_path: &Path,
+    skill_id: &str,
+) -> Result<Vec<Dependent>, ServiceError> {
+    let skill_id = ScopedSkillName::normalize(skill_id);
+    let mut dependents = Vec::new();
+    let mut ids = indexed_skill_ids(registry_path);
+    ids.sort();
+    for id in ids.into_iter().filter(|id| *id != skill_id) {
+        let mut found: Option<Dependent> = None;
+        for entry in read_skill_versions(registry_path, &id)? {
+            if entry.yanked {
+                continue;
+            }
+            let Some(dep) = entry
+                .deps
+                .iter()
+                .find(|d| ScopedSkillName::normalize(&d.name) == skill_id)
+            else {
+                continue;
+            };
+            let dependent = found.get_or_insert_with(|| Dependent {
+                skill_id: id.clone(),
+                versions: Vec::new(),
+                req: String::new(),
+            });
+            dependent.versions.push(entry.vers.clone());
+            dependent.req = dep.req.clone();
+        }
+        dependents.extend(found);
+    }
+    Ok(dependents)
+}
+
+/// Projects, identified by their `skills.lock`, that lock `skill_id`
+pub fn find_affected_projects(
+    lock_paths: &[PathBuf],
+    skill_id: &str,
+) -> Result<Vec<AffectedProject>, ServiceError> {
+    let skill_id = ScopedSkillName::normalize(skill_id);
+    let mut affected = Vec::new();
+    for lock_path in lock_paths {
+        let lock = ProjectSkillsLock::load_from_file(lock_path).map_err(|e| {
+            ServiceError::Custom(format!("Failed to load {}: {}", lock_path.display(), e))
+        })?;
+        affected.extend(
+            lock.skills
+                .iter()
+                .filter(|entry| ScopedSkillName::normalize(&entry.id) == skill_id)
+                .map(|entry| AffectedProject {
+                    lock_path: lock_path.clone(),
+                    locked_version: entry.resolved.version.clone(),
+                    direct: entry.depth == 0,
+                    required_by: entry.parent_skill.clone(),
+                }),
+        );
+    }
+    Ok(affected)
+}
+
+/// Delete the index files of retired skills whose grace period ended before
+/// `now`, and drop their records. Returns the removed skill ids.
+pub fn purge_expired(
+    registry_path: &Path,
+    now: DateTime<Utc>,
+) -> Result<Vec<String>, ServiceError> {
+    let mut retirements = load_retirements(registry_path)?;
+    let expired: Vec<String> = retirements
+        .iter()
+        .filter(|(_, retirement)| retirement.remove_after <= now)
+        .map(|(id, _)| id.clone())
+        .collect();
+    if expired.is_empty() {
+        return Ok(expired);
+    }
+    for id in &expired {
+        let index_path = get_skill_index_path(registry_path, id)?;
+        match std::fs::remove_file(&index_path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(ServiceError::Io(e)),
+        }
+        retirements.remove(id);
+        tracing::info!("Removed retired skill {} from the registry index", id);
+    }
+    save_retirements(registry_path, &retirements)?;
+    Ok(expired)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::registry_index::{update_skill_version, Dependency, VersionMetadata};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn publish(registry: &Path, id: &str, version: &str, deps: &[(&str, &str)], yanked: bool) {
+        let metadata = VersionMetadata {
+            name: id.to_string(),
+            vers: version.to_string(),
+            deps: deps
+                .iter()
+                .map(|(name, req)| Dependency {
+                    name: name.to_string(),
+                    req: req.to_string(),
+                    features: Vec::new(),
+                    optional: false,
+                    default_features: true,
+                    target: None,
+                    kind: None,
+                })
+                .collect(),
+            cksum: "sha256:00".to_string(),
+            features: HashMap::new(),
+            yanked,
+            links: None,
+            download_url: format!("https://example.com/{id}-{version}.zip"),
+            published_at: "2024-01-01T00:00:00Z".to_string(),
+            metadata: None,
+        };
+        update_skill_version(id, version, &metadata, registry).unwrap();
+    }
+
+    #[test]
+    fn test_retire_and_find_dependents() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry = temp_dir.path();
+        publish(registry, "acme/old", "1.0.0", &[], false);
+        publish(
+            registry,
+            "acme/app",
+            "1.0.0",
+            &[("acme/old", "^1.0")],
+            false,
+        );
+        publish(
+            registry,
+            "acme/app",
+            "1.1.0",
+            &[("@acme/old", "^1.1")],
+            false,
+        );
+        publish(registry, "acme/gone", "1.0.0", &[("acme/old", "*")], true);
+        publish(registry, "acme/other", "1.0.0", &[], false);
+
+        let retirement = retire_skill(
+            registry,
+            "@acme/old",
+            Duration::days(30),
+            Some("Superseded".to_string()),
+            Some("acme:new".to_string()),
+        )
+        .unwrap();
+        assert_eq!(
+            retirement.remove_after - retirement.retired_at,
+            Duration::days(30)
+        );
+        assert_eq!(retirement.replacement.as_deref(), Some("acme/new"));
+        assert_eq!(load_retirements(registry).unwrap()["acme/old"], retirement);
+
+        // Yanked versions do not count as dependents
+        let dependents = find_dependents(registry, "acme/old").unwrap();
+        assert_eq!(
+            dependents,
+            [Dependent {
+                skill_id: "acme/app".to_string(),
+                versions: vec!["1.0.0".to_string(), "1.1.0".to_string()],
+                req: "^1.1".to_string(),
+            }]
+        );
+
+        assert!(unretire_skill(registry, "acme/old").unwrap());
+        assert!(!unretire_skill(registry, "acme/old").unwrap());
+    }
+
+    #[test]
+    fn test_retire_unknown_skill_is_not_found() {
+        let temp_dir = TempDir::new().unwrap();
+        let err = retire_skill(
+            temp_dir.path(),
+            "acme/missing",
+            Duration::days(1),
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ServiceError::SkillNotFound(_)), "{}", err);
+    }
+
+    #[test]
+    fn test_purge_expired_removes_only_due_skills() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry = temp_dir.path();
+        publish(registry, "acme/due", "1.0.0", &[], false);
+        publish(registry, "acme/later", "1.0.0", &[], false);
+        retire_skill(registry, "acme/due", Duration::zero(), None, None).unwrap();
+        retire_skill(registry, "acme/later", Duration::days(7), None, None).unwrap();
+
+        let removed = purge_expired(registry, Utc::now()).unwrap();
+        assert_eq!(removed, ["acme/due"]);
+        assert!(read_skill_versions(registry, "acme/due")
+            .unwrap()
+            .is_empty());
+        assert!(!read_skill_versions(registry, "acme/later")
+            .unwrap()
+            .is_empty());
+        let retirements = load_retirements(registry).unwrap();
+        assert_eq!(retirements.keys().collect::<Vec<_>>(), ["acme/later"]);
+    }
+}
@@ -1,9 +1,17 @@
 //! Routing and context management service implementation
+//!
+//! [`RoutingService::route`] picks skills for a query under a
+//! [`RoutingPolicy`] (minimum confidence, how many skills, tie-breaking by a
+//! priority list and preferred tags, a fallback skill) and says why each
+//! candidate was chosen or rejected. It backs `POST /api/v1/route`.
 
-use crate::core::metadata::{MetadataService, SkillMetadata};
+use crate::core::metadata::{MetadataService, SkillFrontmatter, SkillMetadata};
 use crate::core::service::ServiceError;
+use crate::core::skill_manager::SkillDefinition;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashSet;
 use std::sync::Arc;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +27,129 @@ pub trait RoutingService: Send + Sync {
         query: &str,
         context: Option<QueryContext>,
     ) -> Result<Vec<RoutedSkill>, ServiceError>;
+
+    /// Pick skills among `skills` for `query` under `policy`. Skills that
+    /// match nothing in the query are left out of the decision.
+    async fn route(
+        &self,
+        query: &str,
+        policy: &RoutingPolicy,
+        skills: &[SkillDefinition],
+    ) -> Result<RouteDecision, ServiceError>;
+}
+
+/// How [`RoutingService::route`] chooses skills
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(default)]
+pub struct RoutingPolicy {
+    /// Lowest confidence (0.0–1.0) a skill needs to be chosen
+    pub min_confidence: f32,
+    /// Most skills to choose
+    pub max_skills: usize,
+    /// Skill ids in order of preference; breaks ties between equal confidences
+    pub priority: Vec<String>,
+    /// Tags that break the remaining ties: the skill with more of them wins
+    pub prefer_tags: Vec<String>,
+    /// Skill chosen when no skill reaches `min_confidence`
+    pub fallback: Option<String>,
+}
+
+impl Default for RoutingPolicy {
+    fn default() -> Self {
+        Self {
+            min_confidence: 0.3,
+            max_skills: 3,
+            priority: Vec::new(),
+            prefer_tags: Vec::new(),
+            fallback: None,
+        }
+    }
+}
+
+impl RoutingPolicy {
+    pub fn validate(&self) -> Result<(), ServiceError> {
+        if !(0.0..=1.0).contains(&self.min_confidence) {
+            return Err(ServiceError::Validation(format!(
+                "min_confidence must be between 0.0 and 1.0, got {}",
+                self.min_confidence
+            )));
+        }
+        if self.max_skills == 0 {
+            return Err(ServiceError::Validation(
+                "max_skills must be greater than 0".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Order two candidates of equal confidence: priority list first, then
+    /// preferred tags, then skill id. Also returns what decided it.
+    fn tie_break(&self, a: &RouteCandidate, b: &RouteCandidate) -> (Ordering, &'static str) {
+        let rank = |c: &RouteCandidate| {
+            self.priority
+                .iter()
+                .position(|id| *id == c.skill_id)
+                .unwrap_or(usize::MAX)
+        };
+        let order = rank(a).cmp(&rank(b));
+        if order != Ordering::Equal {
+            return (order, "priority");
+        }
+        let preferred = |c: &RouteCandidate| {
+            c.tags
+                .iter()
+                .filter(|tag| self.prefer_tags.iter().any(|p| p.eq_ignore_ascii_case(tag)))
+                .count()
+        };
+        let order = preferred(b).cmp(&preferred(a));
+        if order != Ordering::Equal {
+            return (order, "preferred tags");
+        }
+        (a.skill_id.cmp(&b.skill_id), "skill id")
+    }
+}
+
+/// Body of `POST /api/v1/route`
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RouteRequest {
+    pub query: String,
+    #[serde(default)]
+    pub policy: RoutingPolicy,
+}
+
+/// Why a candidate ended up chosen or rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteOutcome {
+    Selected,
+    /// Chosen as the policy's fallback because nothing else qualified
+    Fallback,
+    BelowMinConfidence,
+    /// Confident enough, but `max_skills` were already chosen
+    OverMaxSkills,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RouteCandidate {
+    pub skill_id: String,
+    pub name: String,
+    /// How well the skill matches the query, 0.0–1.0
+    pub confidence: f32,
+    /// Tags from the skill's frontmatter
+    pub tags: Vec<String>,
+    pub outcome: RouteOutcome,
+    /// The outcome in words, e.g. "confidence 0.75 meets the minimum 0.30"
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RouteDecision {
+    pub query: String,
+    /// Chosen skills, best first
+    pub chosen: Vec<RouteCandidate>,
+    /// Matching skills that were not chosen, best first
+    pub rejected: Vec<RouteCandidate>,
+    pub fallback_used: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -122,4 +253,275 @@ impl RoutingService for RoutingServiceImpl {
 
         Ok(routed_skills)
     }
+
+    async fn route(
+        &self,
+        query: &str,
+        policy: &RoutingPolicy,
+        skills: &[SkillDefinition],
+    ) -> Result<RouteDecision, ServiceError> {
+        policy.validate()?;
+        let fallback = match &policy.fallback {
+            Some(id) => Some(skills.iter().find(|s| s.id.as_str() == id).ok_or_else(|| {
+                ServiceError::Validation(format!("Fallback skill '{}' is not available", id))
+            })?),
+            None => None,
+        };
+
+        let mut candidates = Vec::with_capacity(skills.len());
+        for skill in skills {
+            candidates.push(self.candidate(skill, query).await);
+        }
+        candidates.sort_by(|a, b| {
+            b.confidence
+                .total_cmp(&a.confidence)
+                .then_with(|| policy.tie_break(a, b).0)
+        });
+
+        let mut chosen: Vec<RouteCandidate> = Vec::new();
+        let mut rejected = Vec::new();
+        for mut candidate in candidates {
+            if candidate.confidence <= 0.0 {
+                continue;
+            }
+            if candidate.confidence < policy.min_confidence {
+                candidate.outcome = RouteOutcome::BelowMinConfidence;
+                candidate.reason = format!(
+                    "confidence {:.2} is below the minimum {:.2}",
+                    candidate.confidence, policy.min_confidence
+                );
+            } else if chosen.len() >= policy.max_skills {
+                candidate.outcome = RouteOutcome::OverMaxSkills;
+                candidate.reason = format!("max_skills ({}) already chosen", policy.max_skills);
+                if let Some(last) = chosen
+                    .last()
+                    .filter(|last| last.confidence == candidate.confidence)
+                {
+                    candidate.reason.push_str(&format!(
+                        "; tied with '{}' at {:.2}, which ranks first by {}",
+                        last.skill_id,
+                        candidate.confidence,
+                        policy.tie_break(last, &candidate).1
+                    ));
+                }
+            } else {
+                candidate.outcome = RouteOutcome::Selected;
+                candidate.reason = format!(
+                    "confidence {:.2} meets the minimum {:.2}",
+                    candidate.confidence, policy.min_confidence
+                );
+                chosen.push(candidate);
+                continue;
+            }
+            rejected.push(candidate);
+        }
+
+        let mut fallback_used = false;
+        if let (true, Some(skill)) = (chosen.is_empty(), fallback) {
+            let mut candidate = match rejected
+                .iter()
+                .position(|c| c.skill_id == skill.id.as_str())
+            {
+                Some(index) => rejected.remove(index),
+                None => self.candidate(skill, query).await,
+            };
+            candidate.outcome = RouteOutcome::Fallback;
+            candidate.reason = format!(
+                "no skill reached the minimum confidence {:.2}; fallback skill",
+                policy.min_confidence
+            );
+            chosen.push(candidate);
+            fallback_used = true;
+        }
+
+        Ok(RouteDecision {
+            query: query.to_string(),
+            chosen,
+            rejected,
+            fallback_used,
+        })
+    }
+}
+
+impl RoutingServiceImpl {
+    /// Score `skill` against `query`; the outcome is filled in by the caller.
+    async fn candidate(&self, skill: &SkillDefinition, query: &str) -> RouteCandidate {
+        let tags = match self
+            .metadata_service
+            .get_skill_frontmatter(skill.id.as_str())
+            .await
+        {
+            Ok(frontmatter) => frontmatter_tags(&frontmatter),
+            Err(_) => Vec::new(),
+        };
+        RouteCandidate {
+            skill_id: skill.id.to_string(),
+            name: skill.name.clone(),
+            confidence: confidence(query, skill, &tags),
+            tags,
+            outcome: RouteOutcome::BelowMinConfidence,
+            reason: String::new(),
+        }
+    }
+}
+
+/// Confidence in [0, 1] that `skill` fits `query`: the whole query found in
+/// the name (1.0 exact, 0.9 contained) or description (0.8), otherwise 0.75
+/// times the share of query terms found in the id, name, description or tags.
+fn confidence(query: &str, skill: &SkillDefinition, tags: &[String]) -> f32 {
+    let query = query.trim().to_lowercase();
+    let name = skill.name.to_lowercase();
+    let description = skill.description.to_lowercase();
+    let phrase = if name == query {
+        1.0
+    } else if name.contains(&query) {
+        0.9
+    } else if description.contains(&query) {
+        0.8
+    } else {
+        0.0
+    };
+
+    let query_terms = terms(&query);
+    if query_terms.is_empty() {
+        return phrase;
+    }
+    let mut known: HashSet<String> = terms(skill.id.as_str()).into_iter().collect();
+    known.extend(terms(&name));
+    known.extend(terms(&description));
+    for tag in tags {
+        known.extend(terms(tag));
+    }
+    let matched = query_terms.iter().filter(|t| known.contains(*t)).count();
+    let coverage = matched as f32 / query_terms.len() as f32;
+    f32::max(phrase, 0.75 * coverage)
+}
+
+/// Lowercase words of two or more letters or digits, without repeats
+fn terms(text: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        let word = word.to_lowercase();
+        if word.chars().count() >= 2 && !terms.contains(&word) {
+            terms.push(word);
+        }
+    }
+    terms
+}
+
+/// Tags from `tags:` (a list or comma-separated string) or `metadata.tags`
+pub(crate) fn frontmatter_tags(frontmatter: &SkillFrontmatter) -> Vec<String> {
+    let mut raw: Vec<String> = Vec::new();
+    match frontmatter.extra.get("tags") {
+        Some(serde_yaml::Value::Sequence(items)) => {
+            raw.extend(items.iter().filter_map(|v| v.as_str()).map(str::to_string));
+        }
+        Some(serde_yaml::Value::String(list)) => raw.extend(list.split(',').map(str::to_string)),
+        _ => {}
+    }
+    if let Some(list) = frontmatter.metadata.as_ref().and_then(|m| m.get("tags")) {
+        raw.extend(list.split(',').map(str::to_string));
+    }
+
+    let mut tags: Vec<String> = Vec::new();
+    for tag in raw {
+        let tag = tag.trim().to_lowercase();
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::metadata::parse_yaml_frontmatter;
+    use crate::core::origin::Origin;
+    use crate::core::service::SkillId;
+
+    fn skill(id: &str, name: &str, description: &str) -> SkillDefinition {
+        SkillDefinition::new(
+            SkillId::new(id.to_string()).unwrap(),
+            name.to_string(),
+            description.to_string(),
+            "1.0.0".to_string(),
+            Origin::Local {
+                path: id.into(),
+                editable: false,
+            },
+        )
+    }
+
+    fn candidate(id: &str, tags: &[&str]) -> RouteCandidate {
+        RouteCandidate {
+            skill_id: id.to_string(),
+            name: id.to_string(),
+            confidence: 0.5,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            outcome: RouteOutcome::Selected,
+            reason: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_confidence() {
+        let pdf = skill(
+            "pdf-tools",
+            "PDF Tools",
+            "Extract text and tables from PDF files",
+        );
+        assert_eq!(confidence("pdf tools", &pdf, &[]), 1.0);
+        assert_eq!(confidence("PDF", &pdf, &[]), 0.9);
+        assert_eq!(confidence("tables from pdf", &pdf, &[]), 0.8);
+        assert_eq!(confidence("extract pdf scanned invoices", &pdf, &[]), 0.375);
+        assert_eq!(
+            confidence("extract invoices", &pdf, &["invoices".to_string()]),
+            0.75
+        );
+        assert_eq!(confidence("spreadsheet", &pdf, &[]), 0.0);
+    }
+
+    #[test]
+    fn test_frontmatter_tags() {
+        let frontmatter = parse_yaml_frontmatter(
+            "---\nname: pdf\ndescription: PDFs\ntags: [Documents, pdf]\nmetadata:\n  tags: \"pdf, ocr\"\n---\n",
+        )
+        .unwrap();
+        assert_eq!(frontmatter_tags(&frontmatter), ["documents", "pdf", "ocr"]);
+    }
+
+    #[test]
+    fn test_tie_break_by_priority_then_tags() {
+        let policy = RoutingPolicy {
+            priority: vec!["zeta".to_string()],
+            prefer_tags: vec!["OCR".to_string()],
+            ..Default::default()
+        };
+        let zeta = candidate("zeta", &[]);
+        let alpha = candidate("alpha", &[]);
+        let ocr = candidate("ocr-reader", &["ocr"]);
+        assert_eq!(
+            policy.tie_break(&zeta, &alpha),
+            (Ordering::Less, "priority")
+        );
+        assert_eq!(
+            policy.tie_break(&alpha, &ocr),
+            (Ordering::Greater, "preferred tags")
+        );
+        assert_eq!(policy.tie_break(&alpha, &alpha).1, "skill id");
+    }
+
+    #[test]
+    fn test_policy_validation() {
+        assert!(RoutingPolicy::default().validate().is_ok());
+        let policy = |min_confidence, max_skills| RoutingPolicy {
+            min_confidence,
+            max_skills,
+            ..Default::default()
+        };
+        assert!(policy(1.5, 3).validate().is_err());
+        assert!(policy(0.5, 0).validate().is_err());
+    }
 }
@@ -0,0 +1,424 @@
+//! Scoped skill ids on disk.
+//!
+//! A [`SkillId`] may take a `scope/name` form so that `acme/tool` and
+//! `otherorg/tool` can be installed side by side. A scoped skill lives at
+//! `<skills dir>/<scope>/<name>`, which is what joining the id onto the skills
+//! dir gives. The scope directory holds a [`SCOPE_MARKER`] file: it lets a
+//! skill directory be mapped back to its id without knowing the skills dir,
+//! and tells scope directories apart from skills that happen to contain
+//! nested skill folders (those keep their plain directory-name ids).
+//!
+//! Installs made before ids were scoped keep their plain ids;
+//! [`FastSkillService::migrate_to_scoped_ids`] moves the ones added by a
+//! `scope/name` registry reference under their scope.
+
+use crate::core::lock::{project_lock_path, ProjectSkillsLock};
+use crate::core::manifest::SkillProjectToml;
+use crate::core::origin::Origin;
+use crate::core::project_transaction::{ProjectTransaction, TransactionError};
+use crate::core::service::{FastSkillService, ServiceError, SkillId};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Marker file that makes a directory under the skills dir a scope directory
+pub const SCOPE_MARKER: &str = ".fastskill-scope";
+
+/// Whether `dir` is a scope directory
+pub fn is_scope_dir(dir: &Path) -> bool {
+    dir.join(SCOPE_MARKER).is_file()
+}
+
+/// Id of the skill stored in `skill_dir`: `<scope>/<name>` when its parent is
+/// a scope directory, otherwise the directory name.
+pub fn skill_id_for_dir(skill_dir: &Path) -> Option<String> {
+    let name = skill_dir.file_name()?.to_string_lossy().to_string();
+    let scope = skill_dir
+        .parent()
+        .filter(|parent| is_scope_dir(parent))
+        .and_then(Path::file_name);
+    Some(match scope {
+        Some(scope) => format!("{}/{}", scope.to_string_lossy(), name),
+        None => name,
+    })
+}
+
+/// Create the scope directory a scoped `id` is stored in. Unscoped ids need
+/// nothing. Fails when an unscoped skill already uses the scope's name.
+pub fn ensure_scope_dir(skills_dir: &Path, id: &SkillId) -> Result<(), ServiceError> {
+    let Some(scope) = id.scope() else {
+        return Ok(());
+    };
+    let dir = skills_dir.join(scope);
+    if dir.join("SKILL.md").exists() {
+        return Err(ServiceError::Validation(format!(
+            "Cannot install '{}': '{}' is the directory of the installed skill '{}'",
+            id, scope, scope
+        )));
+    }
+    std::fs::create_dir_all(&dir)?;
+    let marker = dir.join(SCOPE_MARKER);
+    if !marker.is_file() {
+        std::fs::write(marker, "")?;
+    }
+    Ok(())
+}
+
+/// Remove the scope directory of `id` once no skill is left in it
+pub fn remove_scope_dir_if_empty(skills_dir: &Path, id: &SkillId) -> Result<(), ServiceError> {
+    let Some(scope) = id.scope() else {
+        return Ok(());
+    };
+    let dir = skills_dir.join(scope);
+    if !is_scope_dir(&dir) {
+        return Ok(());
+    }
+    let only_marker = std::fs::read_dir(&dir)?
+        .filter_map(Result::ok)
+        .all(|entry| entry.file_name() == SCOPE_MARKER);
+    if only_marker {
+        std::fs::remove_dir_all(&dir)?;
+    }
+    Ok(())
+}
+
+/// Skill directories (holding a SKILL.md) directly under `skills_dir` and
+/// inside its scope directories, as `(id, path)` sorted by id. Dot-prefixed
+/// entries are skipped.
+pub fn skill_dirs(skills_dir: &Path) -> std::io::Result<Vec<(String, PathBuf)>> {
+    dirs_holding(skills_dir, "SKILL.md")
+}
+
+/// [`skill_dirs`] for directories holding `file_name` instead of a SKILL.md
+pub(crate) fn dirs_holding(
+    skills_dir: &Path,
+    file_name: &str,
+) -> std::io::Result<Vec<(String, PathBuf)>> {
+    let mut dirs = Vec::new();
+    if !skills_dir.is_dir() {
+        return Ok(dirs);
+    }
+    for entry in std::fs::read_dir(skills_dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        let path = entry.path();
+        if name.starts_with('.') || !path.is_dir() {
+            continue;
+        }
+        if path.join(file_name).is_file() {
+            dirs.push((name, path));
+        } else if is_scope_dir(&path) {
+            for child in std::fs::read_dir(&path)? {
+                let child = child?;
+                let child_name = child.file_name().to_string_lossy().to_string();
+                let child_path = child.path();
+                if !child_name.starts_with('.') && child_path.join(file_name).is_file() {
+                    dirs.push((format!("{}/{}", name, child_name), child_path));
+                }
+            }
+        }
+    }
+    dirs.sort();
+    Ok(dirs)
+}
+
+/// An unscoped install that [`FastSkillService::migrate_to_scoped_ids`]
+/// moves under its registry scope
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ScopedIdMove {
+    pub from: String,
+    pub to: String,
+}
+
+/// The moves that scope every unscoped skill in `lock` added by a
+/// `scope/name` registry reference. The installed id is kept as the name, as
+/// a fresh add would install it.
+pub fn plan_scoped_id_moves(lock: &ProjectSkillsLock) -> Result<Vec<ScopedIdMove>, ServiceError> {
+    let mut moves = Vec::new();
+    for entry in &lock.skills {
+        let Origin::Repository { skill, .. } = &entry.origin else {
+            continue;
+        };
+        let Some((scope, _)) = skill.split_once('/') else {
+            continue;
+        };
+        if SkillId::new(entry.id.clone())?.scope().is_some() {
+            continue;
+        }
+        moves.push(ScopedIdMove {
+            from: entry.id.clone(),
+            to: SkillId::scoped(scope, &entry.id)?.into_string(),
+        });
+    }
+    Ok(moves)
+}
+
+impl FastSkillService {
+    /// Move the unscoped registry installs of the project whose manifest is
+    /// `project_file` to scoped ids: the skill directory, the registered skill,
+    /// the `skill-project.toml` dependency key and the `skills.lock` entry
+    /// (including references from other entries). Every target is checked
+    /// before anything moves; with `dry_run` nothing is changed.
+    pub async fn migrate_to_scoped_ids(
+        &self,
+        project_file: &Path,
+        dry_run: bool,
+    ) -> Result<Vec<ScopedIdMove>, ServiceError> {
+        let lock_path = project_lock_path(project_file);
+        if !lock_path.exists() {
+            return Ok(Vec::new());
+        }
+        let mut lock = ProjectSkillsLock::load_from_file(&lock_path)
+            .map_err(|e| ServiceError::Config(format!("Failed to load skills.lock: {e}")))?;
+        let moves = plan_scoped_id_moves(&lock)?;
+        if dry_run || moves.is_empty() {
+            return Ok(moves);
+        }
+
+        let skills_dir = &self.config().skill_storage_path;
+        for m in &moves {
+            let to = SkillId::new(m.to.clone())?;
+            let scope_dir = skills_dir.join(to.scope().unwrap_or_default());
+            if skills_dir.join(&m.to).exists() || scope_dir.join("SKILL.md").exists() {
+                return Err(ServiceError::Validation(format!(
+                    "Cannot move '{}' to '{}': {} is already taken",
+                    m.from,
+                    m.to,
+                    skills_dir.join(&m.to).display()
+                )));
+            }
+        }
+
+        let mut project = SkillProjectToml::load_from_file(project_file)
+            .map_err(|e| ServiceError::Config(format!("Failed to load skill-project.toml: {e}")))?;
+        for m in &moves {
+            let from = SkillId::new(m.from.clone())?;
+            let to = SkillId::new(m.to.clone())?;
+            ensure_scope_dir(skills_dir, &to)?;
+            let from_dir = skills_dir.join(&m.from);
+            if from_dir.exists() {
+                std::fs::rename(&from_dir, skills_dir.join(&m.to))?;
+            }
+            if let Some(mut skill) = self.skill_manager().get_skill(&from).await? {
+                skill.id = to.clone();
+                skill.skill_file = skills_dir.join(&m.to).join("SKILL.md");
+                self.skill_manager().force_register_skill(skill).await?;
+                self.skill_manager().unregister_skill(&from).await?;
+            }
+            self.storage().skill_removed(&m.from).await?;
+            self.storage().skill_stored(&m.to).await?;
+
+            if let Some(deps) = project.dependencies.as_mut() {
+                if let Some(spec) = deps.dependencies.remove(&m.from) {
+                    deps.dependencies.insert(m.to.clone(), spec);
+                }
+            }
+            for entry in &mut lock.skills {
+                if entry.id == m.from {
+                    entry.id = m.to.clone();
+                }
+                if entry.parent_skill.as_deref() == Some(m.from.as_str()) {
+                    entry.parent_skill = Some(m.to.clone());
+                }
+                for dep in &mut entry.dependencies {
+                    if *dep == m.from {
+                        *dep = m.to.clone();
+                    }
+                }
+            }
+        }
+
+        let save_error = |e: TransactionError| {
+            ServiceError::Config(format!(
+                "Skills were moved but skill-project.toml and skills.lock could not be \
+                 saved: {e}"
+            ))
+        };
+        let mut txn = ProjectTransaction::new(project_file, &lock_path);
+        txn.stage_manifest(&project).map_err(save_error)?;
+        txn.stage_lock(&lock).map_err(save_error)?;
+        txn.commit().map_err(save_error)?;
+        self.storage().lock_written(&lock_path).await?;
+        Ok(moves)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_skill(dir: &Path) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join("SKILL.md"), "---\nname: x\ndescription: y\n---\n").unwrap();
+    }
+
+    #[test]
+    fn test_skill_id_for_dir() {
+        let temp = TempDir::new().unwrap();
+        let id = SkillId::new("acme/tool".to_string()).unwrap();
+        ensure_scope_dir(temp.path(), &id).unwrap();
+        write_skill(&temp.path().join("acme/tool"));
+        write_skill(&temp.path().join("plain"));
+        write_skill(&temp.path().join("plain/nested"));
+
+        assert_eq!(
+            skill_id_for_dir(&temp.path().join("acme/tool")).as_deref(),
+            Some("acme/tool")
+        );
+        assert_eq!(
+            skill_id_for_dir(&temp.path().join("plain")).as_deref(),
+            Some("plain")
+        );
+        // Without a marker a parent is not a scope
+        assert_eq!(
+            skill_id_for_dir(&temp.path().join("plain/nested")).as_deref(),
+            Some("nested")
+        );
+    }
+
+    #[test]
+    fn test_skill_dirs_lists_scoped_and_unscoped() {
+        let temp = TempDir::new().unwrap();
+        for id in ["acme/tool", "otherorg/tool"] {
+            let id = SkillId::new(id.to_string()).unwrap();
+            ensure_scope_dir(temp.path(), &id).unwrap();
+            write_skill(&temp.path().join(id.as_str()));
+        }
+        write_skill(&temp.path().join("tool"));
+        write_skill(&temp.path().join(".staging/tool"));
+        std::fs::create_dir_all(temp.path().join("not-a-scope/inner")).unwrap();
+
+        let ids: Vec<String> = skill_dirs(temp.path())
+            .unwrap()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(ids, ["acme/tool", "otherorg/tool", "tool"]);
+        assert!(skill_dirs(&temp.path().join("missing")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_ensure_scope_dir_refuses_unscoped_skill_name() {
+        let temp = TempDir::new().unwrap();
+        write_skill(&temp.path().join("acme"));
+        let id = SkillId::new("acme/tool".to_string()).unwrap();
+        assert!(ensure_scope_dir(temp.path(), &id).is_err());
+        assert!(!temp.path().join("acme").join(SCOPE_MARKER).exists());
+
+        let plain = SkillId::new("tool".to_string()).unwrap();
+        ensure_scope_dir(temp.path(), &plain).unwrap();
+        assert!(!temp.path().join("tool").exists());
+    }
+
+    #[test]
+    fn test_remove_scope_dir_if_empty() {
+        let temp = TempDir::new().unwrap();
+        let a = SkillId::new("acme/a".to_string()).unwrap();
+        let b = SkillId::new("acme/b".to_string()).unwrap();
+        for id in [&a, &b] {
+            ensure_scope_dir(temp.path(), id).unwrap();
+            write_skill(&temp.path().join(id.as_str()));
+        }
+
+        std::fs::remove_dir_all(temp.path().join("acme/a")).unwrap();
+        remove_scope_dir_if_empty(temp.path(), &a).unwrap();
+        assert!(temp.path().join("acme").is_dir());
+
+        std::fs::remove_dir_all(temp.path().join("acme/b")).unwrap();
+        remove_scope_dir_if_empty(temp.path(), &b).unwrap();
+        assert!(!temp.path().join("acme").exists());
+    }
+
+    fn lock_entry(id: &str, registry_ref: &str) -> crate::core::lock::ProjectLockedSkillEntry {
+        crate::core::lock::ProjectLockedSkillEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            origin: Origin::Repository {
+                repo: "main".to_string(),
+                skill: registry_ref.to_string(),
+                version: None,
+            },
+            resolved: crate::core::origin::Resolved {
+                version: "1.0.0".to_string(),
+                commit_hash: None,
+                checksum: None,
+            },
+            dependencies: vec![],
+            groups: vec![],
+            extras: vec![],
+            depth: 0,
+            parent_skill: None,
+        }
+    }
+
+    #[test]
+    fn test_plan_scoped_id_moves() {
+        let mut lock = ProjectSkillsLock::new_empty();
+        lock.skills.push(lock_entry("tool", "acme/tool"));
+        lock.skills.push(lock_entry("plain", "plain"));
+        lock.skills.push(lock_entry("other/tool", "other/tool"));
+
+        let moves = plan_scoped_id_moves(&lock).unwrap();
+        assert_eq!(
+            moves,
+            [ScopedIdMove {
+                from: "tool".to_string(),
+                to: "acme/tool".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migrate_to_scoped_ids_moves_skill_manifest_and_lock() {
+        let temp = TempDir::new().unwrap();
+        let skills_dir = temp.path().join("skills");
+        write_skill(&skills_dir.join("tool"));
+        let project_file = temp.path().join("skill-project.toml");
+        std::fs::write(&project_file, "[dependencies]\ntool = \"1.0.0\"\n").unwrap();
+        let mut lock = ProjectSkillsLock::new_empty();
+        lock.skills.push(lock_entry("tool", "acme/tool"));
+        let mut dependent = lock_entry("helper", "helper");
+        dependent.dependencies = vec!["tool".to_string()];
+        lock.skills.push(dependent);
+        lock.save_to_file(&project_lock_path(&project_file))
+            .unwrap();
+
+        let config = crate::core::service::ServiceConfig {
+            skill_storage_path: skills_dir.clone(),
+            ..Default::default()
+        };
+        let service = FastSkillService::new(config).await.unwrap();
+
+        let planned = service
+            .migrate_to_scoped_ids(&project_file, true)
+            .await
+            .unwrap();
+        assert_eq!(planned.len(), 1);
+        assert!(skills_dir.join("tool/SKILL.md").is_file());
+
+        service
+            .migrate_to_scoped_ids(&project_file, false)
+            .await
+            .unwrap();
+        assert!(skills_dir.join("acme/tool/SKILL.md").is_file());
+        assert!(is_scope_dir(&skills_dir.join("acme")));
+        assert!(!skills_dir.join("tool").exists());
+
+        let project = SkillProjectToml::load_from_file(&project_file).unwrap();
+        let deps = project.dependencies.unwrap().dependencies;
+        assert!(deps.contains_key("acme/tool") && !deps.contains_key("tool"));
+        let lock = ProjectSkillsLock::load_from_file(&project_lock_path(&project_file)).unwrap();
+        let ids: Vec<&str> = lock.skills.iter().map(|s| s.id.as_str()).collect();
+        assert!(ids.contains(&"acme/tool"));
+        let helper = lock.skills.iter().find(|s| s.id == "helper").unwrap();
+        assert_eq!(helper.dependencies, ["acme/tool"]);
+
+        // Nothing left to move
+        assert!(service
+            .migrate_to_scoped_ids(&project_file, false)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -1,6 +1,7 @@
 //! Main FastSkill service implementation
 
 use crate::execution::ExecutionConfig;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::sync::Arc;
 use tracing::info;
@@ -14,14 +15,128 @@ pub struct HttpServerConfig {
     /// Optional: allow list of request headers
     /// Default: ["Content-Type", "Authorization"] if unset
     pub allowed_headers: Vec<String>,
+
+    /// Per-client discovery profiles applied by `/search` and `/resolve`
+    pub client_profiles: Vec<ClientProfile>,
+
+    /// Request rate limits for `/api/v1` routes
+    pub rate_limit: RateLimitConfig,
+
+    /// Per-skill injection rules applied by `/resolve`
+    pub injection_rules: crate::core::skill_injection::InjectionRules,
+
+    /// Background job settings for `?async=true` requests
+    pub jobs: crate::core::jobs::JobsConfig,
+}
+
+/// Request rate limits for the HTTP API (`[tool.fastskill.server.rate_limit]`).
+///
+/// Both limits apply independently: a request carrying an API key must pass
+/// the per-token limit for that key and the per-IP limit for its address.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    /// Limit per API key (`X-API-Key` or `Authorization: Bearer`)
+    #[serde(default)]
+    pub per_token: Option<RateLimit>,
+    /// Limit per client IP address
+    #[serde(default)]
+    pub per_ip: Option<RateLimit>,
+}
+
+impl RateLimitConfig {
+    /// Whether any limit is configured
+    pub fn is_enabled(&self) -> bool {
+        self.per_token.is_some() || self.per_ip.is_some()
+    }
+}
+
+/// A token-bucket limit: `requests_per_minute` sustained, with up to `burst`
+/// requests at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimit {
+    pub requests_per_minute: u32,
+    /// Bucket size; defaults to `requests_per_minute`
+    #[serde(default)]
+    pub burst: Option<u32>,
+}
+
+impl RateLimit {
+    /// Bucket size in requests
+    pub fn burst_size(&self) -> u32 {
+        self.burst.unwrap_or(self.requests_per_minute)
+    }
+}
+
+/// Discovery limits for one client/agent of the HTTP API.
+///
+/// A profile with an `api_key` is selected only by that key (`X-API-Key` or
+/// `Authorization: Bearer`); a profile without one is selected by name via the
+/// `X-FastSkill-Client` header.
+#[derive(Debug, Clone, Default)]
+pub struct ClientProfile {
+    /// Profile name (matched against `X-FastSkill-Client`)
+    pub name: String,
+    /// API key identifying the client; resolved from the environment at load time
+    pub api_key: Option<String>,
+    /// Drop matches scoring below this relevance
+    pub dynamic_min_relevance: Option<f32>,
+    /// Upper bound on the number of skills returned
+    pub max_dynamic_skills: Option<usize>,
+    /// Skill ids this client may discover; a trailing `*` matches a prefix
+    /// (e.g. `acme/*`). Empty means all skills.
+    pub allowed_scopes: Vec<String>,
+    /// Teams whose `visibility: team` skills this client may see
+    pub teams: Vec<String>,
+    /// Access to skills that are not public
+    pub role: ClientRole,
+}
+
+/// What a keyed client may see besides public skills (see
+/// [`SkillAccess`](crate::core::access::SkillAccess)).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClientRole {
+    /// Skills it owns and skills of its teams
+    #[default]
+    Member,
+    /// Every skill
+    Admin,
+}
+
+impl ClientProfile {
+    /// Whether `skill_id` falls inside this profile's allowed scopes.
+    pub fn allows_skill(&self, skill_id: &str) -> bool {
+        self.allowed_scopes.is_empty()
+            || self
+                .allowed_scopes
+                .iter()
+                .any(|scope| match scope.strip_suffix('*') {
+                    Some(prefix) => skill_id.starts_with(prefix),
+                    None => skill_id == scope,
+                })
+    }
+
+    /// Whether a match with `score` passes the profile's relevance floor.
+    pub fn meets_relevance(&self, score: f32) -> bool {
+        self.dynamic_min_relevance.is_none_or(|min| score >= min)
+    }
+
+    /// Cap a requested result count at `max_dynamic_skills`.
+    pub fn cap_limit(&self, limit: usize) -> usize {
+        self.max_dynamic_skills.map_or(limit, |max| limit.min(max))
+    }
 }
 
 /// Main service configuration
 #[derive(Debug, Clone)]
 pub struct ServiceConfig {
-    /// Base directory for skill storage
+    /// Base directory for skill storage (the local cache for remote backends)
     pub skill_storage_path: PathBuf,
 
+    /// Where skills are stored; filesystem unless a server deployment opts
+    /// into object storage
+    pub storage: StorageConfig,
+
     /// Execution configuration
     pub execution: ExecutionConfig,
 
@@ -34,6 +149,9 @@ pub struct ServiceConfig {
     /// Embedding configuration
     pub embedding: Option<EmbeddingConfig>,
 
+    /// Approximate nearest neighbour search over the vector index
+    pub ann: AnnConfig,
+
     /// Security configuration
     pub security: SecurityConfig,
 
@@ -42,23 +160,97 @@ pub struct ServiceConfig {
 
     /// HTTP server configuration
     pub http_server: Option<HttpServerConfig>,
+
+    /// How added skills are named and what happens when a name is taken
+    pub naming: crate::core::skill_naming::SkillNaming,
+
+    /// How editable installs link skills into the skills directory
+    pub link_mode: crate::core::link::LinkMode,
+
+    /// One isolated skill set per tenant when serving
+    pub tenancy: crate::core::tenancy::TenancyConfig,
 }
 
 impl Default for ServiceConfig {
     fn default() -> Self {
         Self {
             skill_storage_path: PathBuf::from("./skills"),
+            storage: StorageConfig::default(),
             execution: ExecutionConfig::default(),
             hot_reload: HotReloadConfig::default(),
             cache: CacheConfig::default(),
             embedding: None,
+            ann: AnnConfig::default(),
             security: SecurityConfig::default(),
             registry_index_path: None,
             http_server: None,
+            naming: Default::default(),
+            link_mode: Default::default(),
+            tenancy: Default::default(),
         }
     }
 }
 
+/// Storage backend selection (`[tool.fastskill.server.storage]`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "lowercase")]
+pub enum StorageConfig {
+    /// Skills live only in `skill_storage_path`
+    #[default]
+    Filesystem,
+    /// Skills live in an S3-compatible bucket; `skill_storage_path` is a
+    /// read-through cache. Requires the `s3-storage` feature.
+    S3(S3StorageConfig),
+    /// Metadata, lock state, usage and embeddings live in Postgres; skill
+    /// files stay in `skill_storage_path`. Requires the `postgres` feature.
+    Postgres(PostgresStorageConfig),
+}
+
+/// S3 / MinIO bucket holding the shared skill corpus. Credentials come from
+/// the standard AWS environment (variables, profile, instance role), never
+/// from the manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct S3StorageConfig {
+    pub bucket: String,
+
+    /// Key prefix under which skills are stored (`<prefix>/<skill-id>/...`)
+    #[serde(default)]
+    pub prefix: String,
+
+    /// Region; falls back to the AWS environment
+    #[serde(default)]
+    pub region: Option<String>,
+
+    /// Custom endpoint for S3-compatible stores such as MinIO
+    #[serde(default)]
+    pub endpoint: Option<String>,
+
+    /// Address buckets by path rather than subdomain (needed by most MinIO setups)
+    #[serde(default)]
+    pub force_path_style: bool,
+}
+
+/// Postgres database shared by server replicas. The connection string comes
+/// from the environment, never from the manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PostgresStorageConfig {
+    /// Environment variable holding the connection URL
+    #[serde(default = "default_database_url_env")]
+    pub url_env: String,
+}
+
+impl Default for PostgresStorageConfig {
+    fn default() -> Self {
+        Self {
+            url_env: default_database_url_env(),
+        }
+    }
+}
+
+fn default_database_url_env() -> String {
+    "DATABASE_URL".to_string()
+}
+
 /// Hot reloading configuration
 #[derive(Debug, Clone)]
 pub struct HotReloadConfig {
@@ -71,6 +263,9 @@ pub struct HotReloadConfig {
     /// Debounce duration for file changes (ms)
     pub debounce_ms: u64,
 
+    /// Re-embed a burst early once this many skills have changed
+    pub max_batch: usize,
+
     /// Automatically reload on file changes
     pub auto_reload: bool,
 }
@@ -81,6 +276,7 @@ impl Default for HotReloadConfig {
             enabled: true,
             watch_paths: vec![PathBuf::from("./skills")],
             debounce_ms: 1000,
+            max_batch: 100,
             auto_reload: true,
         }
     }
@@ -109,6 +305,37 @@ impl Default for CacheConfig {
     }
 }
 
+/// HNSW approximate nearest neighbour settings for embedding search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnnConfig {
+    /// Use the HNSW graph at all (exact search otherwise)
+    pub enabled: bool,
+
+    /// Collections smaller than this are searched exactly
+    pub min_skills: usize,
+
+    /// Links per node; higher improves recall at the cost of memory and build time
+    pub m: usize,
+
+    /// Candidate list size while building the graph
+    pub ef_construction: usize,
+
+    /// Candidate list size while searching; higher improves recall, slower
+    pub ef_search: usize,
+}
+
+impl Default for AnnConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_skills: 5000,
+            m: 16,
+            ef_construction: 200,
+            ef_search: 64,
+        }
+    }
+}
+
 /// Embedding configuration
 #[derive(Debug, Clone)]
 pub struct EmbeddingConfig {
@@ -120,6 +347,23 @@ pub struct EmbeddingConfig {
 
     /// Custom path for vector index database
     pub index_path: Option<PathBuf>,
+
+    /// Texts per embeddings API call; defaults to
+    /// [`DEFAULT_EMBEDDING_BATCH_SIZE`](crate::core::embedding::DEFAULT_EMBEDDING_BATCH_SIZE)
+    pub batch_size: Option<usize>,
+
+    /// Characters per section chunk; when set, skills are also indexed chunk
+    /// by chunk ([`chunking`](crate::core::chunking)). Off by default.
+    pub chunk_size: Option<usize>,
+
+    /// Query embeddings kept in the in-process query cache; defaults to
+    /// [`DEFAULT_QUERY_CACHE_SIZE`](crate::core::embedding::DEFAULT_QUERY_CACHE_SIZE),
+    /// `0` turns the cache off
+    pub query_cache_size: Option<usize>,
+
+    /// Seconds a cached query embedding is reused; defaults to
+    /// [`DEFAULT_QUERY_CACHE_TTL_SECS`](crate::core::embedding::DEFAULT_QUERY_CACHE_TTL_SECS)
+    pub query_cache_ttl: Option<u64>,
 }
 
 /// Security configuration
@@ -166,22 +410,37 @@ impl SkillId {
                 "Skill ID too long (max 255 characters)".to_string(),
             ));
         }
-        // Reject forward slashes (scope should be handled separately)
-        if id.contains('/') {
+        // An optional scope: `scope/name`, with a single slash
+        if id.split('/').count() > 2 || id.split('/').any(str::is_empty) {
             return Err(ServiceError::Validation(
-                "Skill ID cannot contain forward slashes. Scope should be handled separately during publishing.".to_string(),
+                "Skill ID must be 'name' or 'scope/name' with non-empty parts".to_string(),
             ));
         }
         // Basic validation for allowed characters (alphanumeric, dash, underscore)
         if !id
             .chars()
-            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '/')
         {
             return Err(ServiceError::Validation("Skill ID contains invalid characters (only alphanumeric, dash, underscore allowed)".to_string()));
         }
         Ok(Self(id))
     }
 
+    /// The id `scope/name`
+    pub fn scoped(scope: &str, name: &str) -> Result<Self, ServiceError> {
+        Self::new(format!("{}/{}", scope, name))
+    }
+
+    /// The scope of a `scope/name` id
+    pub fn scope(&self) -> Option<&str> {
+        self.0.split_once('/').map(|(scope, _)| scope)
+    }
+
+    /// The id without its scope
+    pub fn name(&self) -> &str {
+        self.0.split_once('/').map_or(&self.0, |(_, name)| name)
+    }
+
     /// Get the string value
     pub fn as_str(&self) -> &str {
         &self.0
@@ -268,6 +527,18 @@ pub enum ServiceError {
     #[error("Skill already indexed: {0}")]
     AlreadyIndexed(String),
 
+    #[error("Operation cancelled")]
+    Cancelled,
+
+    /// Another process holds the skills directory's
+    /// [`ProcessLock`](crate::core::process_lock::ProcessLock)
+    #[error("Another fastskill process is running ({0})")]
+    Busy(String),
+
+    /// Served content no longer matches the hash recorded at startup
+    #[error(transparent)]
+    Integrity(#[from] crate::core::integrity::IntegrityError),
+
     #[error("Custom error: {0}")]
     Custom(String),
 }
@@ -311,6 +582,15 @@ pub struct FastSkillService {
     /// Hot reload manager
     hot_reload_manager: Option<Arc<crate::storage::hot_reload::HotReloadManager>>,
 
+    /// Skill lifecycle events (hot reload publishes `SkillReloaded` here)
+    event_bus: Arc<crate::events::EventBus>,
+
+    /// Cancels long-running operations (see [`crate::core::cancel`])
+    cancellation: crate::core::cancel::CancellationToken,
+
+    /// Resumable downloads of zip-url skills (see [`crate::core::download`])
+    downloads: Arc<crate::core::download::DownloadManager>,
+
     /// Service state
     initialized: bool,
 }
@@ -323,13 +603,57 @@ fn should_skip_directory(name: &str) -> bool {
     name.starts_with('.') || SKIPPED_DIRS.contains(&name)
 }
 
+type Backends = (
+    Arc<dyn crate::storage::StorageBackend>,
+    Option<Arc<dyn crate::core::vector_index::VectorIndexService>>,
+);
+
 impl FastSkillService {
+    /// Storage backend and, when embeddings are configured, the vector index
+    /// for `config`. Postgres mode serves both from one connection.
+    async fn build_backends(config: &ServiceConfig) -> Result<Backends, ServiceError> {
+        #[cfg(feature = "postgres")]
+        if let StorageConfig::Postgres(postgres) = &config.storage {
+            let store = Arc::new(crate::storage::PostgresStore::from_config(postgres).await?);
+            let storage = crate::storage::PostgresStorage::new(
+                Arc::clone(&store),
+                config.skill_storage_path.clone(),
+            )
+            .await?;
+            let index = config
+                .embedding
+                .as_ref()
+                .map(|_| store as Arc<dyn crate::core::vector_index::VectorIndexService>);
+            return Ok((Arc::new(storage), index));
+        }
+        let storage = Self::build_storage_backend(config).await?;
+        Ok((storage, Self::build_vector_index_service(config)))
+    }
+
     async fn build_storage_backend(
         config: &ServiceConfig,
     ) -> Result<Arc<dyn crate::storage::StorageBackend>, ServiceError> {
-        Ok(Arc::new(
-            crate::storage::FilesystemStorage::new(config.skill_storage_path.clone()).await?,
-        ))
+        match &config.storage {
+            StorageConfig::Filesystem => Ok(Arc::new(
+                crate::storage::FilesystemStorage::new(config.skill_storage_path.clone()).await?,
+            )),
+            #[cfg(feature = "s3-storage")]
+            StorageConfig::S3(s3) => Ok(Arc::new(
+                crate::storage::S3Storage::new(s3, config.skill_storage_path.clone()).await?,
+            )),
+            #[cfg(not(feature = "s3-storage"))]
+            StorageConfig::S3(_) => Err(ServiceError::Config(
+                "S3 storage is configured but this build of fastskill does not include the \
+                 s3-storage feature"
+                    .to_string(),
+            )),
+            // With the feature enabled, `build_backends` handles Postgres first
+            StorageConfig::Postgres(_) => Err(ServiceError::Config(
+                "Postgres storage is configured but this build of fastskill does not include the \
+                 postgres feature"
+                    .to_string(),
+            )),
+        }
     }
 
     fn build_vector_index_service(
@@ -340,7 +664,8 @@ impl FastSkillService {
                 crate::core::vector_index::VectorIndexServiceImpl::with_config(
                     embedding_config,
                     &config.skill_storage_path,
-                ),
+                )
+                .with_ann(config.ann),
             ) as Arc<dyn crate::core::vector_index::VectorIndexService>
         })
     }
@@ -350,24 +675,26 @@ impl FastSkillService {
         crate::init_logging();
         info!("Initializing FastSkill service v{}", crate::VERSION);
 
-        let storage = Self::build_storage_backend(&config).await?;
+        let (storage, vector_index_service) = Self::build_backends(&config).await?;
         let event_bus = Arc::new(crate::events::EventBus::new());
         let skill_manager = Arc::new(crate::core::skill_manager::SkillManager::new());
         let metadata_service = Arc::new(crate::core::metadata::MetadataServiceImpl::new(
             skill_manager.clone(),
         ));
-        let vector_index_service = Self::build_vector_index_service(&config);
         let hot_reload_manager = if config.hot_reload.enabled {
             Some(Arc::new(crate::storage::hot_reload::HotReloadManager::new(
                 storage.clone(),
                 event_bus.clone(),
+                crate::storage::hot_reload::ReloadSchedule {
+                    quiet: std::time::Duration::from_millis(config.hot_reload.debounce_ms),
+                    max_batch: config.hot_reload.max_batch,
+                },
             )?))
         } else {
             None
         };
 
         Ok(Self {
-            config,
             skill_manager,
             metadata_service,
             vector_index_service,
@@ -376,10 +703,40 @@ impl FastSkillService {
             project_root: None,
             storage,
             hot_reload_manager,
+            event_bus,
+            cancellation: crate::core::cancel::CancellationToken::new(),
+            downloads: Arc::new(crate::core::download::DownloadManager::new(
+                crate::core::download::DownloadManager::default_dir(&config.skill_storage_path),
+            )),
             initialized: false,
+            config,
         })
     }
 
+    /// Use `token` to cancel this service's long-running operations (the CLI
+    /// passes one it cancels on Ctrl-C).
+    pub fn with_cancellation(mut self, token: crate::core::cancel::CancellationToken) -> Self {
+        self.cancellation = token;
+        self
+    }
+
+    /// Token long-running operations check; cancelling it stops them.
+    pub fn cancellation_token(&self) -> &crate::core::cancel::CancellationToken {
+        &self.cancellation
+    }
+
+    /// Download zip-url skills with `downloads` (the CLI passes one that
+    /// renders progress).
+    pub fn with_downloads(mut self, downloads: crate::core::download::DownloadManager) -> Self {
+        self.downloads = Arc::new(downloads);
+        self
+    }
+
+    /// The downloader zip-url installs go through
+    pub fn downloads(&self) -> &crate::core::download::DownloadManager {
+        &self.downloads
+    }
+
     /// Inject an embedding provider (edge-constructed, holds the API key). Enables
     /// the core reindex seam; without it reindex skips silently.
     pub fn with_embedding_service(
@@ -434,16 +791,29 @@ impl FastSkillService {
         // Initialize storage
         self.storage.initialize().await?;
 
-        // Initialize hot reload if enabled
+        // Bring stored skill metadata up to the current schema
+        self.storage.migrate_metadata().await?;
+
+        // Initialize hot reload if enabled. The storage path is always
+        // watched; edited skills are re-embedded when a provider is injected.
         if let Some(hot_reload) = &self.hot_reload_manager {
-            hot_reload
-                .enable_hot_reloading(self.config.hot_reload.watch_paths.clone())
-                .await?;
+            let (paths, target) = self.hot_reload_watch();
+            hot_reload.enable_hot_reloading(paths, target).await?;
         }
 
         // Auto-index skills from filesystem
         self.auto_index_skills_from_filesystem().await?;
 
+        // Searches fail against an index built with another embedding
+        // model; say so at startup rather than on the first query
+        if let Some(vector_index) = &self.vector_index_service {
+            match vector_index.mismatch().await {
+                Ok(Some(mismatch)) => tracing::warn!("{}", mismatch),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Failed to read vector index metadata: {}", e),
+            }
+        }
+
         self.initialized = true;
         info!("Service initialization complete");
 
@@ -454,6 +824,9 @@ impl FastSkillService {
     pub async fn shutdown(&mut self) -> Result<(), ServiceError> {
         info!("Shutting down service...");
 
+        // Stop in-flight reindexes and installs
+        self.cancellation.cancel();
+
         // Disable hot reloading
         if let Some(hot_reload) = &self.hot_reload_manager {
             hot_reload.disable_hot_reloading().await?;
@@ -468,6 +841,76 @@ impl FastSkillService {
         Ok(())
     }
 
+    /// Paths hot reload watches and where edited skills are re-embedded
+    fn hot_reload_watch(
+        &self,
+    ) -> (
+        Vec<PathBuf>,
+        Option<crate::storage::hot_reload::ReembedTarget>,
+    ) {
+        let mut paths = self.config.hot_reload.watch_paths.clone();
+        if !paths.contains(&self.config.skill_storage_path) {
+            paths.push(self.config.skill_storage_path.clone());
+        }
+        let target = self
+            .embedding_service
+            .clone()
+            .zip(self.vector_index_service.clone())
+            .filter(|_| self.config.hot_reload.auto_reload)
+            .map(
+                |(embedding, index)| crate::storage::hot_reload::ReembedTarget {
+                    embedding,
+                    index,
+                    chunk_size: self.config.embedding.as_ref().and_then(|e| e.chunk_size),
+                },
+            );
+        (paths, target)
+    }
+
+    /// Whether hot reload is watching the skills directory; `None` when it is
+    /// not configured (`hot_reload.enabled = false`)
+    pub async fn hot_reload_active(&self) -> Option<bool> {
+        match &self.hot_reload_manager {
+            Some(hot_reload) => Some(hot_reload.is_watching().await),
+            None => None,
+        }
+    }
+
+    /// Start or stop hot reloading on a running service, returning whether it
+    /// now watches. Starting has no effect without an embedding provider to
+    /// re-embed into.
+    pub async fn set_hot_reload(&self, enabled: bool) -> Result<bool, ServiceError> {
+        let Some(hot_reload) = &self.hot_reload_manager else {
+            return Err(ServiceError::InvalidOperation(
+                "hot reload is not configured; set hot_reload.enabled in the config".to_string(),
+            ));
+        };
+        if enabled {
+            let (paths, target) = self.hot_reload_watch();
+            hot_reload.enable_hot_reloading(paths, target).await?;
+        } else {
+            hot_reload.disable_hot_reloading().await?;
+        }
+        Ok(hot_reload.is_watching().await)
+    }
+
+    /// Drop the storage metadata cache and the process-wide query embedding
+    /// cache, returning how many query embeddings were dropped
+    pub async fn clear_caches(&self) -> Result<usize, ServiceError> {
+        self.storage.clear_cache().await?;
+        Ok(crate::core::embedding::QueryEmbeddingCache::clear_shared())
+    }
+
+    /// Event bus for skill lifecycle events
+    pub fn event_bus(&self) -> Arc<crate::events::EventBus> {
+        self.event_bus.clone()
+    }
+
+    /// Get the storage backend
+    pub fn storage(&self) -> Arc<dyn crate::storage::StorageBackend> {
+        self.storage.clone()
+    }
+
     /// Get skill manager service
     pub fn skill_manager(&self) -> Arc<dyn crate::core::skill_manager::SkillManagementService> {
         self.skill_manager.clone()
@@ -508,6 +951,14 @@ impl FastSkillService {
         )
     }
 
+    /// Get skill loader for level- and token-budgeted skill content
+    pub fn skill_loader(&self) -> crate::core::loading::SkillLoader {
+        crate::core::loading::SkillLoader::new(
+            self.skill_manager.clone(),
+            self.config.skill_storage_path.clone(),
+        )
+    }
+
     /// Check if service is initialized
     pub fn is_initialized(&self) -> bool {
         self.initialized
@@ -567,6 +1018,30 @@ impl FastSkillService {
         &self,
         skill_file: &std::path::Path,
     ) -> Result<(), ServiceError> {
+        let skill = Self::skill_definition_from_file(skill_file).await?;
+
+        // Try to register the skill (ignore if it is already indexed)
+        match self.skill_manager.register_skill(skill).await {
+            Ok(_) => Ok(()),
+            Err(ServiceError::AlreadyIndexed(_)) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Register the skill at `skill_file` again, replacing any definition
+    /// already held for its id (used after its files were swapped on disk).
+    pub(crate) async fn reload_skill_from_file(
+        &self,
+        skill_file: &std::path::Path,
+    ) -> Result<SkillId, ServiceError> {
+        let skill = Self::skill_definition_from_file(skill_file).await?;
+        self.skill_manager.force_register_skill(skill).await
+    }
+
+    /// Build a skill definition from a SKILL.md on disk
+    pub(crate) async fn skill_definition_from_file(
+        skill_file: &std::path::Path,
+    ) -> Result<crate::core::skill_manager::SkillDefinition, ServiceError> {
         // Read the SKILL.md file
         let content = tokio::fs::read_to_string(skill_file)
             .await
@@ -580,14 +1055,21 @@ impl FastSkillService {
             .parent()
             .ok_or_else(|| ServiceError::Custom("SKILL.md has no parent directory".to_string()))?;
 
-        // Use directory name as skill ID
-        let skill_id_str = skill_dir
-            .file_name()
-            .and_then(|n| n.to_str())
-            .ok_or_else(|| ServiceError::Custom("Invalid skill directory name".to_string()))?
-            .to_string();
+        // Use directory name (with its scope, inside a scope directory) as skill ID
+        let skill_id_str = crate::core::scope::skill_id_for_dir(skill_dir)
+            .ok_or_else(|| ServiceError::Custom("Invalid skill directory name".to_string()))?;
         let skill_id = SkillId::new(skill_id_str)?;
 
+        let access = crate::core::access::SkillAccess::from_frontmatter(&frontmatter);
+        let injection = crate::core::skill_injection::InjectionRule::from_frontmatter(&frontmatter);
+        let execution_environment = crate::execution::runtime::declared_environment(&frontmatter);
+        // A bad `env` section must not hide the skill; it fails when installed
+        let env =
+            crate::core::skill_env::declared_env(skill_dir, &frontmatter).unwrap_or_else(|e| {
+                tracing::warn!("{}", e);
+                Default::default()
+            });
+
         // Create skill definition from frontmatter. This is a directory-scan
         // registration path with no real provenance to record — the skill IS a
         // local directory on disk, so `Origin::Local` is the accurate (and
@@ -605,6 +1087,10 @@ impl FastSkillService {
         );
 
         // Set additional fields
+        skill.access = access;
+        skill.injection = injection;
+        skill.env = env;
+        skill.execution_environment = execution_environment;
         skill.author = frontmatter.author;
         skill.skill_file = skill_file.to_path_buf();
 
@@ -612,12 +1098,7 @@ impl FastSkillService {
         skill.created_at = chrono::Utc::now();
         skill.updated_at = chrono::Utc::now();
 
-        // Try to register the skill (ignore if it is already indexed)
-        match self.skill_manager.register_skill(skill).await {
-            Ok(_) => Ok(()),
-            Err(ServiceError::AlreadyIndexed(_)) => Ok(()),
-            Err(e) => Err(e),
-        }
+        Ok(skill)
     }
 }
 
@@ -657,20 +1138,65 @@ mod tests {
         assert!(!service.is_initialized());
     }
 
+    #[tokio::test]
+    async fn test_set_hot_reload_needs_configured_manager() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = ServiceConfig {
+            skill_storage_path: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        config.hot_reload.enabled = false;
+        let service = FastSkillService::new(config.clone()).await.unwrap();
+        assert_eq!(service.hot_reload_active().await, None);
+        assert!(matches!(
+            service.set_hot_reload(true).await,
+            Err(ServiceError::InvalidOperation(_))
+        ));
+
+        // Configured, but with no embedding provider there is nothing to watch for
+        config.hot_reload.enabled = true;
+        let service = FastSkillService::new(config).await.unwrap();
+        assert!(!service.set_hot_reload(true).await.unwrap());
+        assert_eq!(service.hot_reload_active().await, Some(false));
+        assert!(!service.set_hot_reload(false).await.unwrap());
+    }
+
     #[test]
     fn test_skill_id_new_validates_input() {
         assert!(SkillId::new("valid-id".to_string()).is_ok());
         assert!(SkillId::new("valid_id_123".to_string()).is_ok());
         assert!(SkillId::new("".to_string()).is_err());
-        assert!(SkillId::new("bad/id".to_string()).is_err());
         assert!(SkillId::new("id with spaces".to_string()).is_err());
     }
 
+    #[test]
+    fn test_skill_id_scoped_form() {
+        let id = SkillId::new("acme/tool".to_string()).unwrap();
+        assert_eq!(id.scope(), Some("acme"));
+        assert_eq!(id.name(), "tool");
+        assert_eq!(SkillId::scoped("acme", "tool").unwrap(), id);
+
+        let plain = SkillId::new("tool".to_string()).unwrap();
+        assert_eq!(plain.scope(), None);
+        assert_eq!(plain.name(), "tool");
+
+        for bad in [
+            "a/b/c",
+            "/tool",
+            "acme/",
+            "acme//tool",
+            "../tool",
+            "acme/..",
+        ] {
+            assert!(SkillId::new(bad.to_string()).is_err(), "{}", bad);
+        }
+    }
+
     #[test]
     fn test_skill_id_try_from_validates_input() {
         // TryFrom should validate input
         assert!(SkillId::try_from("valid-id".to_string()).is_ok());
         assert!(SkillId::try_from("".to_string()).is_err());
-        assert!(SkillId::try_from("bad/id".to_string()).is_err());
+        assert!(SkillId::try_from("bad//id".to_string()).is_err());
     }
 }
@@ -0,0 +1,295 @@
+//! Environment variables a skill's scripts read
+//!
+//! A skill that calls an external API declares the variables it needs under
+//! `env` in its SKILL.md frontmatter:
+//!
+//! ```yaml
+//! env:
+//!   WEATHER_API_KEY:
+//!     description: API key from weatherapi.com
+//!     secret: true
+//!   WEATHER_UNITS:
+//!     description: metric or imperial
+//!     required: false
+//!     default: metric
+//! ```
+//!
+//! or under `[env.NAME]` in its own `skill-project.toml`, which replaces the
+//! frontmatter's declaration of the same name. Variables are required unless
+//! `required: false`; a secret may not carry a default, so no secret value is
+//! ever written into a skill. Installing warns about required variables that
+//! are not set; running a script fails on them, and passes every declared
+//! variable that has a value to the script's runtime (including Docker).
+
+use crate::core::manifest::{ManifestError, SkillProjectToml};
+use crate::core::metadata::SkillFrontmatter;
+use crate::core::service::ServiceError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// One declared environment variable
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EnvVarSpec {
+    /// What the variable is for and where to get a value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether scripts refuse to run without it
+    #[serde(default = "default_required")]
+    pub required: bool,
+    /// Whether the value is a credential; its value is never printed
+    #[serde(default)]
+    pub secret: bool,
+    /// Value used when the variable is not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+impl Default for EnvVarSpec {
+    fn default() -> Self {
+        Self {
+            description: None,
+            required: true,
+            secret: false,
+            default: None,
+        }
+    }
+}
+
+/// A skill's declared environment variables, by name
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EnvDeclarations(pub BTreeMap<String, EnvVarSpec>);
+
+impl EnvDeclarations {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &EnvVarSpec)> {
+        self.0.iter().map(|(name, spec)| (name.as_str(), spec))
+    }
+
+    /// `self` with every variable `other` declares replaced by `other`'s
+    pub fn overridden_by(mut self, other: EnvDeclarations) -> Self {
+        self.0.extend(other.0);
+        self
+    }
+
+    /// What is wrong with the declarations: names that are not valid
+    /// variable names, and secrets with a default value
+    pub fn invalid(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (name, spec) in self.iter() {
+            if !is_valid_name(name) {
+                problems.push(format!(
+                    "env.{}: not a valid variable name (letters, digits and '_', \
+                     not starting with a digit)",
+                    name
+                ));
+            }
+            if spec.secret && spec.default.is_some() {
+                problems.push(format!(
+                    "env.{}: a secret cannot have a default value",
+                    name
+                ));
+            }
+        }
+        problems
+    }
+
+    /// Required variables without a default that `lookup` has no non-empty
+    /// value for
+    pub fn missing(&self, lookup: impl Fn(&str) -> Option<String>) -> Vec<String> {
+        self.iter()
+            .filter(|(name, spec)| {
+                spec.required && spec.default.is_none() && value_of(&lookup, name).is_none()
+            })
+            .map(|(name, _)| name.to_string())
+            .collect()
+    }
+
+    /// Value of every declared variable that has one, from `lookup` or the
+    /// default; the names of required variables without a value as the error
+    pub fn resolve(
+        &self,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<Vec<(String, String)>, Vec<String>> {
+        let missing = self.missing(&lookup);
+        if !missing.is_empty() {
+            return Err(missing);
+        }
+        Ok(self
+            .iter()
+            .filter_map(|(name, spec)| {
+                value_of(&lookup, name)
+                    .or_else(|| spec.default.clone())
+                    .map(|value| (name.to_string(), value))
+            })
+            .collect())
+    }
+}
+
+fn value_of(lookup: &impl Fn(&str) -> Option<String>, name: &str) -> Option<String> {
+    lookup(name).filter(|value| !value.is_empty())
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Declarations under `env` in `frontmatter`; none without the key
+pub fn frontmatter_env(frontmatter: &SkillFrontmatter) -> Result<EnvDeclarations, ServiceError> {
+    let Some(raw) = frontmatter.extra.get("env") else {
+        return Ok(EnvDeclarations::default());
+    };
+    serde_yaml::from_value(raw.clone())
+        .map_err(|e| ServiceError::Validation(format!("Invalid env in SKILL.md: {}", e)))
+}
+
+/// Declarations of the skill in `skill_dir`: the frontmatter's, overridden by
+/// `[env]` in the skill's own `skill-project.toml`. Invalid declarations fail.
+pub fn declared_env(
+    skill_dir: &Path,
+    frontmatter: &SkillFrontmatter,
+) -> Result<EnvDeclarations, ServiceError> {
+    let from_frontmatter = frontmatter_env(frontmatter)?;
+    let manifest = skill_dir.join("skill-project.toml");
+    let env = match SkillProjectToml::load_from_file(&manifest) {
+        Ok(project) => from_frontmatter.overridden_by(project.env.unwrap_or_default()),
+        Err(ManifestError::NotFound(_)) => from_frontmatter,
+        Err(e) => {
+            return Err(ServiceError::Validation(format!(
+                "Invalid skill-project.toml in {}: {}",
+                skill_dir.display(),
+                e
+            )))
+        }
+    };
+    let problems = env.invalid();
+    if !problems.is_empty() {
+        return Err(ServiceError::Validation(format!(
+            "Invalid env declarations in {}: {}",
+            skill_dir.display(),
+            problems.join("; ")
+        )));
+    }
+    Ok(env)
+}
+
+/// Value of `name` in this process's environment
+pub fn process_env(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::metadata::parse_yaml_frontmatter;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    const SKILL_MD: &str = "---\nname: weather\ndescription: Use when asked about the weather\n\
+        env:\n  WEATHER_API_KEY:\n    description: API key\n    secret: true\n  \
+        WEATHER_UNITS:\n    required: false\n    default: metric\n---\n# Weather\n";
+
+    fn lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_frontmatter_env() {
+        let frontmatter = parse_yaml_frontmatter(SKILL_MD).unwrap();
+        let env = frontmatter_env(&frontmatter).unwrap();
+        let key = &env.0["WEATHER_API_KEY"];
+        assert!(key.required && key.secret);
+        assert_eq!(key.description.as_deref(), Some("API key"));
+        let units = &env.0["WEATHER_UNITS"];
+        assert!(!units.required && !units.secret);
+        assert_eq!(units.default.as_deref(), Some("metric"));
+    }
+
+    #[test]
+    fn test_missing_and_resolve() {
+        let frontmatter = parse_yaml_frontmatter(SKILL_MD).unwrap();
+        let env = frontmatter_env(&frontmatter).unwrap();
+
+        assert_eq!(env.missing(lookup(&[])), vec!["WEATHER_API_KEY"]);
+        // An empty value counts as unset
+        assert_eq!(
+            env.resolve(lookup(&[("WEATHER_API_KEY", "")])),
+            Err(vec!["WEATHER_API_KEY".to_string()])
+        );
+        assert_eq!(
+            env.resolve(lookup(&[("WEATHER_API_KEY", "k")])).unwrap(),
+            vec![
+                ("WEATHER_API_KEY".to_string(), "k".to_string()),
+                ("WEATHER_UNITS".to_string(), "metric".to_string()),
+            ]
+        );
+        assert_eq!(
+            env.resolve(lookup(&[("WEATHER_API_KEY", "k"), ("WEATHER_UNITS", "si")]))
+                .unwrap()[1]
+                .1,
+            "si"
+        );
+    }
+
+    #[test]
+    fn test_invalid_declarations() {
+        let mut env = EnvDeclarations::default();
+        env.0.insert("1BAD-NAME".to_string(), EnvVarSpec::default());
+        env.0.insert(
+            "TOKEN".to_string(),
+            EnvVarSpec {
+                secret: true,
+                default: Some("hunter2".to_string()),
+                ..Default::default()
+            },
+        );
+        let problems = env.invalid();
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].contains("1BAD-NAME"));
+        assert!(problems[1].contains("secret cannot have a default"));
+    }
+
+    #[test]
+    fn test_declared_env_manifest_override() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("skill-project.toml"),
+            "[metadata]\nid = \"weather\"\nversion = \"1.0.0\"\n\n\
+             [env.WEATHER_API_KEY]\ndescription = \"Key from the dashboard\"\nrequired = false\n",
+        )
+        .unwrap();
+        let frontmatter = parse_yaml_frontmatter(SKILL_MD).unwrap();
+        let env = declared_env(dir.path(), &frontmatter).unwrap();
+        let key = &env.0["WEATHER_API_KEY"];
+        assert!(!key.required && !key.secret);
+        assert_eq!(key.description.as_deref(), Some("Key from the dashboard"));
+        assert!(env.0.contains_key("WEATHER_UNITS"));
+        assert!(env.missing(lookup(&[])).is_empty());
+    }
+
+    #[test]
+    fn test_declared_env_rejects_secret_default() {
+        let dir = TempDir::new().unwrap();
+        let content = "---\nname: weather\ndescription: Use when asked about the weather\n\
+            env:\n  WEATHER_API_KEY:\n    secret: true\n    default: abc\n---\n";
+        let frontmatter = parse_yaml_frontmatter(content).unwrap();
+        let err = declared_env(dir.path(), &frontmatter).unwrap_err();
+        assert!(err.to_string().contains("secret cannot have a default"));
+    }
+}
@@ -0,0 +1,458 @@
+//! Per-skill injection rules for `/resolve`
+//!
+//! A client profile's `dynamic_min_relevance` is one floor for every skill.
+//! Injection rules tune single skills on top of it:
+//!
+//! - `boost`: added to the relevance score before the floor is applied
+//!   (negative values are a penalty)
+//! - `always`: inject whenever the caller may see the skill, even without a
+//!   search match
+//! - `never`: never inject
+//! - `required_keywords`: inject only when the prompt contains at least one
+//!
+//! Rules come from an `injection:` mapping in SKILL.md frontmatter and from
+//! `[tool.fastskill.server.injection."<skill id>"]`, where a trailing `*`
+//! matches an id prefix. Server settings win over frontmatter field by field.
+//! Every decision is logged at debug level with its reason (target
+//! `fastskill::injection`).
+
+use crate::core::context_resolver::ResolvedSkill;
+use crate::core::metadata::SkillFrontmatter;
+use crate::core::service::ServiceError;
+use crate::core::skill_manager::SkillDefinition;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Injection settings for one skill; unset fields fall back to the next source
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InjectionRule {
+    /// Added to the relevance score (-1.0 to 1.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub boost: Option<f32>,
+    /// Inject whenever the caller may see the skill
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub always: Option<bool>,
+    /// Never inject the skill
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub never: Option<bool>,
+    /// Inject only when the prompt contains one of these (case-insensitive)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_keywords: Option<Vec<String>>,
+}
+
+impl InjectionRule {
+    /// Read the `injection:` mapping of SKILL.md frontmatter. A malformed
+    /// mapping is ignored with a warning.
+    pub fn from_frontmatter(frontmatter: &SkillFrontmatter) -> Self {
+        let Some(value) = frontmatter.extra.get("injection") else {
+            return Self::default();
+        };
+        let rule = serde_yaml::from_value::<Self>(value.clone())
+            .map_err(|e| e.to_string())
+            .and_then(|rule| rule.problem().map_or(Ok(rule), Err));
+        rule.unwrap_or_else(|e| {
+            tracing::warn!(
+                "Ignoring injection rules of skill '{}': {}",
+                frontmatter.name,
+                e
+            );
+            Self::default()
+        })
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Reject a boost outside -1.0..=1.0 and `always` together with `never`
+    pub fn validate(&self) -> Result<(), ServiceError> {
+        self.problem()
+            .map_or(Ok(()), |p| Err(ServiceError::Validation(p)))
+    }
+
+    fn problem(&self) -> Option<String> {
+        if let Some(boost) = self.boost.filter(|b| !(-1.0..=1.0).contains(b)) {
+            return Some(format!("injection boost {} is outside -1.0 to 1.0", boost));
+        }
+        (self.always == Some(true) && self.never == Some(true))
+            .then(|| "injection rule sets both `always` and `never`".to_string())
+    }
+
+    /// This rule, with the fields it leaves unset taken from `fallback`
+    fn or(&self, fallback: &Self) -> Self {
+        Self {
+            boost: self.boost.or(fallback.boost),
+            always: self.always.or(fallback.always),
+            never: self.never.or(fallback.never),
+            required_keywords: self
+                .required_keywords
+                .clone()
+                .or_else(|| fallback.required_keywords.clone()),
+        }
+    }
+
+    fn is_always(&self) -> bool {
+        self.always == Some(true)
+    }
+
+    /// Whether a skill with this rule and relevance `score` is injected for
+    /// `prompt`, and why
+    pub fn decide(
+        &self,
+        prompt: &str,
+        score: f32,
+        min_relevance: Option<f32>,
+    ) -> InjectionDecision {
+        let boost = self.boost.unwrap_or(0.0);
+        let adjusted = (score + boost).clamp(0.0, 1.0);
+        let decision = |inject: bool, reason: String| InjectionDecision {
+            inject,
+            score: adjusted,
+            reason,
+        };
+
+        if self.never == Some(true) {
+            return decision(false, "rule `never`".to_string());
+        }
+        if let Some(keywords) = self.required_keywords.as_ref().filter(|k| !k.is_empty()) {
+            let prompt = prompt.to_lowercase();
+            if !keywords
+                .iter()
+                .any(|keyword| prompt.contains(&keyword.to_lowercase()))
+            {
+                return decision(
+                    false,
+                    format!("prompt has none of the required keywords {:?}", keywords),
+                );
+            }
+        }
+        if self.is_always() {
+            return decision(true, "rule `always`".to_string());
+        }
+        let scored = if boost == 0.0 {
+            format!("score {:.3}", adjusted)
+        } else {
+            format!("score {:.3} ({:.3} {:+.3} boost)", adjusted, score, boost)
+        };
+        match min_relevance {
+            Some(min) if adjusted < min => {
+                decision(false, format!("{} below min relevance {:.3}", scored, min))
+            }
+            Some(min) => decision(true, format!("{} meets min relevance {:.3}", scored, min)),
+            None => decision(true, scored),
+        }
+    }
+}
+
+/// The outcome of [`InjectionRule::decide`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct InjectionDecision {
+    pub inject: bool,
+    /// Relevance score after the boost, 0.0-1.0
+    pub score: f32,
+    pub reason: String,
+}
+
+/// Server-side rules by skill id (`[tool.fastskill.server.injection]`); a
+/// trailing `*` matches an id prefix
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct InjectionRules {
+    pub rules: BTreeMap<String, InjectionRule>,
+}
+
+impl InjectionRules {
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Check every rule; the error names the offending pattern
+    pub fn validate(&self) -> Result<(), ServiceError> {
+        for (pattern, rule) in &self.rules {
+            if let Some(problem) = rule.problem() {
+                return Err(ServiceError::Validation(format!(
+                    "[tool.fastskill.server.injection.\"{}\"]: {}",
+                    pattern, problem
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The server rule for `skill_id`: an exact id beats a prefix pattern,
+    /// and a longer prefix beats a shorter one
+    fn server_rule(&self, skill_id: &str) -> Option<&InjectionRule> {
+        if let Some(rule) = self.rules.get(skill_id) {
+            return Some(rule);
+        }
+        self.rules
+            .iter()
+            .filter_map(|(pattern, rule)| {
+                let prefix = pattern.strip_suffix('*')?;
+                skill_id.starts_with(prefix).then_some((prefix.len(), rule))
+            })
+            .max_by_key(|(len, _)| *len)
+            .map(|(_, rule)| rule)
+    }
+
+    /// The effective rule for `skill`: the server rule over its frontmatter
+    pub fn rule_for(&self, skill: &SkillDefinition) -> InjectionRule {
+        match self.server_rule(skill.id.as_str()) {
+            Some(rule) => rule.or(&skill.injection),
+            None => skill.injection.clone(),
+        }
+    }
+
+    /// Skills in `skills` whose rule is `always` but that are not in
+    /// `results` yet
+    pub fn missing_always<'a>(
+        &self,
+        results: &[ResolvedSkill],
+        skills: &'a [SkillDefinition],
+    ) -> Vec<&'a SkillDefinition> {
+        skills
+            .iter()
+            .filter(|skill| {
+                self.rule_for(skill).is_always()
+                    && !results.iter().any(|r| r.skill_id == skill.id.as_str())
+            })
+            .collect()
+    }
+
+    /// Rescore `results` and keep the ones the rules inject, `always` skills
+    /// first and the rest by adjusted score. `skills` are the definitions
+    /// the rules are read from; a result without one only gets the floor.
+    pub fn apply(
+        &self,
+        prompt: &str,
+        results: &mut Vec<ResolvedSkill>,
+        skills: &[SkillDefinition],
+        min_relevance: Option<f32>,
+    ) {
+        let by_id: HashMap<&str, &SkillDefinition> =
+            skills.iter().map(|s| (s.id.as_str(), s)).collect();
+        let mut kept = Vec::with_capacity(results.len());
+        for mut result in results.drain(..) {
+            let rule = by_id
+                .get(result.skill_id.as_str())
+                .map(|skill| self.rule_for(skill))
+                .unwrap_or_default();
+            let decision = rule.decide(prompt, result.score, min_relevance);
+            tracing::debug!(
+                target: "fastskill::injection",
+                skill = %result.skill_id,
+                inject = decision.inject,
+                "{} skill '{}': {}",
+                if decision.inject { "Injecting" } else { "Skipping" },
+                result.skill_id,
+                decision.reason
+            );
+            if decision.inject {
+                result.score = decision.score;
+                kept.push((rule.is_always(), result));
+            }
+        }
+        kept.sort_by(|(a_always, a), (b_always, b)| {
+            b_always
+                .cmp(a_always)
+                .then_with(|| b.score.total_cmp(&a.score))
+        });
+        results.extend(kept.into_iter().map(|(_, result)| result));
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::origin::Origin;
+    use crate::core::service::SkillId;
+
+    fn skill(id: &str, injection: InjectionRule) -> SkillDefinition {
+        let mut skill = SkillDefinition::new(
+            SkillId::new(id.to_string()).unwrap(),
+            id.to_string(),
+            format!("{} skill", id),
+            "1.0.0".to_string(),
+            Origin::Local {
+                path: std::path::PathBuf::from(format!("./skills/{id}")),
+                editable: false,
+            },
+        );
+        skill.injection = injection;
+        skill
+    }
+
+    fn result(id: &str, score: f32) -> ResolvedSkill {
+        ResolvedSkill {
+            skill_id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            score,
+            skill_md_path: None,
+            skill_root_path: None,
+            references_dir_path: None,
+            assets_dir_path: None,
+            content_preview: None,
+            content_full: None,
+            loading_level: None,
+            content: None,
+            tokens: None,
+        }
+    }
+
+    fn ids(results: &[ResolvedSkill]) -> Vec<&str> {
+        results.iter().map(|r| r.skill_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_decide_reasons() {
+        let boosted = InjectionRule {
+            boost: Some(0.2),
+            ..Default::default()
+        };
+        let decision = boosted.decide("prompt", 0.4, Some(0.5));
+        assert!(decision.inject);
+        assert!((decision.score - 0.6).abs() < 1e-6);
+        assert!(
+            decision.reason.contains("+0.200 boost"),
+            "{}",
+            decision.reason
+        );
+
+        let penalized = InjectionRule {
+            boost: Some(-0.3),
+            ..Default::default()
+        };
+        let decision = penalized.decide("prompt", 0.6, Some(0.5));
+        assert!(!decision.inject);
+        assert!(decision.reason.contains("below min relevance"));
+
+        let keywords = InjectionRule {
+            required_keywords: Some(vec!["Terraform".to_string()]),
+            ..Default::default()
+        };
+        assert!(!keywords.decide("write a dockerfile", 0.9, None).inject);
+        assert!(keywords.decide("fix my terraform plan", 0.9, None).inject);
+
+        let never = InjectionRule {
+            never: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(never.decide("x", 1.0, None).reason, "rule `never`");
+        let always = InjectionRule {
+            always: Some(true),
+            ..Default::default()
+        };
+        assert!(always.decide("x", 0.0, Some(0.9)).inject);
+    }
+
+    #[test]
+    fn test_server_rules_override_frontmatter_by_field() {
+        let rules: InjectionRules = toml::from_str(
+            r#"
+            "acme/*" = { boost = 0.1 }
+            "acme/web*" = { boost = 0.3 }
+            "acme/web-scraper" = { never = true }
+            "#,
+        )
+        .unwrap();
+        rules.validate().unwrap();
+
+        let frontmatter = InjectionRule {
+            boost: Some(-0.5),
+            required_keywords: Some(vec!["scrape".to_string()]),
+            ..Default::default()
+        };
+        let rule = rules.rule_for(&skill("acme/web-scraper", frontmatter.clone()));
+        assert_eq!(rule.never, Some(true));
+        assert_eq!(rule.boost, Some(-0.5));
+        assert_eq!(rule.required_keywords, frontmatter.required_keywords);
+
+        assert_eq!(
+            rules.rule_for(&skill("acme/webhooks", frontmatter)).boost,
+            Some(0.3)
+        );
+        assert_eq!(
+            rules
+                .rule_for(&skill("acme/sql", InjectionRule::default()))
+                .boost,
+            Some(0.1)
+        );
+        assert!(rules
+            .rule_for(&skill("other", InjectionRule::default()))
+            .is_default());
+    }
+
+    #[test]
+    fn test_validate_rejects_contradictions() {
+        let rules: InjectionRules =
+            toml::from_str(r#"pdf = { always = true, never = true }"#).unwrap();
+        assert!(rules
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains(r#"injection."pdf""#));
+        let rule = InjectionRule {
+            boost: Some(1.5),
+            ..Default::default()
+        };
+        assert!(rule.validate().is_err());
+    }
+
+    #[test]
+    fn test_apply_reorders_and_filters() {
+        let skills = vec![
+            skill("docs", InjectionRule::default()),
+            skill(
+                "style",
+                InjectionRule {
+                    always: Some(true),
+                    ..Default::default()
+                },
+            ),
+            skill(
+                "pptx",
+                InjectionRule {
+                    boost: Some(0.4),
+                    ..Default::default()
+                },
+            ),
+            skill(
+                "legacy",
+                InjectionRule {
+                    never: Some(true),
+                    ..Default::default()
+                },
+            ),
+        ];
+        let rules = InjectionRules::default();
+        let mut results = vec![
+            result("docs", 0.8),
+            result("legacy", 0.9),
+            result("pptx", 0.5),
+        ];
+
+        let missing = rules.missing_always(&results, &skills);
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].id.as_str(), "style");
+        results.push(result("style", 0.0));
+
+        rules.apply("make slides", &mut results, &skills, Some(0.3));
+        assert_eq!(ids(&results), ["style", "pptx", "docs"]);
+        assert!((results[1].score - 0.9).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_rule_from_frontmatter() {
+        let content = "---\nname: pdf\ndescription: PDF tools\ninjection:\n  boost: 0.2\n  required_keywords: [pdf]\n---\n";
+        let frontmatter = crate::core::metadata::parse_yaml_frontmatter(content).unwrap();
+        let rule = InjectionRule::from_frontmatter(&frontmatter);
+        assert_eq!(rule.boost, Some(0.2));
+        assert_eq!(rule.required_keywords, Some(vec!["pdf".to_string()]));
+
+        let content = "---\nname: pdf\ndescription: PDF tools\ninjection:\n  boost: 3\n---\n";
+        let frontmatter = crate::core::metadata::parse_yaml_frontmatter(content).unwrap();
+        assert!(InjectionRule::from_frontmatter(&frontmatter).is_default());
+    }
+}
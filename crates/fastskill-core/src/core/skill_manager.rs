@@ -1,7 +1,10 @@
 //! Skill management service implementation
 
+use crate::core::access::SkillAccess;
 use crate::core::origin::Origin;
 use crate::core::service::{ServiceError, SkillId};
+use crate::core::skill_env::EnvDeclarations;
+use crate::core::skill_injection::InjectionRule;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -37,6 +40,18 @@ pub struct SkillDefinition {
     // Resolved facts a fetch produced (read by the lock)
     pub commit_hash: Option<String>,
     pub fetched_at: Option<DateTime<Utc>>,
+
+    // Who may see the skill over HTTP (SKILL.md `owner`/`team`/`visibility`)
+    #[serde(default, skip_serializing_if = "SkillAccess::is_default")]
+    pub access: SkillAccess,
+
+    // How `/resolve` injects the skill (SKILL.md `injection`)
+    #[serde(default, skip_serializing_if = "InjectionRule::is_default")]
+    pub injection: InjectionRule,
+
+    // Environment variables its scripts read (SKILL.md `env`)
+    #[serde(default, skip_serializing_if = "EnvDeclarations::is_empty")]
+    pub env: EnvDeclarations,
 }
 
 impl SkillDefinition {
@@ -66,6 +81,9 @@ impl SkillDefinition {
             origin,
             commit_hash: None,
             fetched_at: None,
+            access: SkillAccess::default(),
+            injection: InjectionRule::default(),
+            env: EnvDeclarations::default(),
         }
     }
 
@@ -84,6 +102,48 @@ pub trait SkillManagementService: Send + Sync {
     ) -> Result<(), ServiceError>;
     async fn unregister_skill(&self, skill_id: &SkillId) -> Result<(), ServiceError>;
     async fn list_skills(&self) -> Result<Vec<SkillDefinition>, ServiceError>;
+
+    /// Number of registered skills
+    async fn count_skills(&self) -> Result<usize, ServiceError> {
+        Ok(self.list_skills().await?.len())
+    }
+
+    /// Up to `limit` skills with ids greater than `after`, ordered by id. Only
+    /// the returned page is cloned, so large collections can be listed in
+    /// bounded memory.
+    async fn list_skills_page(
+        &self,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<SkillDefinition>, ServiceError> {
+        let mut skills = self.list_skills().await?;
+        skills.retain(|skill| after.is_none_or(|after| skill.id.as_str() > after));
+        skills.sort_by(|a, b| a.id.as_str().cmp(b.id.as_str()));
+        skills.truncate(limit);
+        Ok(skills)
+    }
+
+    /// Apply a batch of enable/disable changes: `enabled` skills are
+    /// registered (replacing any existing definition) and `disabled` ones
+    /// unregistered; ids that are not registered are ignored. The default
+    /// applies them one by one; implementations should apply the whole batch
+    /// at once so readers never see half of it.
+    async fn set_enabled_batch(
+        &self,
+        enabled: Vec<SkillDefinition>,
+        disabled: &[SkillId],
+    ) -> Result<(), ServiceError> {
+        for skill in enabled {
+            self.force_register_skill(skill).await?;
+        }
+        for skill_id in disabled {
+            match self.unregister_skill(skill_id).await {
+                Ok(()) | Err(ServiceError::SkillNotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -194,4 +254,112 @@ impl SkillManagementService for SkillManager {
         let skills = self.skills.read().await;
         Ok(skills.values().cloned().collect())
     }
+
+    async fn count_skills(&self) -> Result<usize, ServiceError> {
+        Ok(self.skills.read().await.len())
+    }
+
+    async fn list_skills_page(
+        &self,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<SkillDefinition>, ServiceError> {
+        let skills = self.skills.read().await;
+        let mut ids: Vec<&SkillId> = skills
+            .keys()
+            .filter(|id| after.is_none_or(|after| id.as_str() > after))
+            .collect();
+        ids.sort_unstable_by(|a, b| a.as_str().cmp(b.as_str()));
+        Ok(ids
+            .into_iter()
+            .take(limit)
+            .filter_map(|id| skills.get(id).cloned())
+            .collect())
+    }
+
+    async fn set_enabled_batch(
+        &self,
+        enabled: Vec<SkillDefinition>,
+        disabled: &[SkillId],
+    ) -> Result<(), ServiceError> {
+        let mut skills = self.skills.write().await;
+        for skill in enabled {
+            skills.insert(skill.id.clone(), skill);
+        }
+        for skill_id in disabled {
+            skills.remove(skill_id);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_list_skills_page_orders_by_id() {
+        let manager = SkillManager::new();
+        for id in ["charlie", "alpha", "delta", "bravo"] {
+            let skill = SkillDefinition::new(
+                SkillId::new(id.to_string()).unwrap(),
+                id.to_string(),
+                format!("{} skill", id),
+                "1.0.0".to_string(),
+                Origin::Local {
+                    path: std::path::PathBuf::from(format!("./skills/{id}")),
+                    editable: false,
+                },
+            );
+            manager.register_skill(skill).await.unwrap();
+        }
+        assert_eq!(manager.count_skills().await.unwrap(), 4);
+
+        let first = manager.list_skills_page(None, 3).await.unwrap();
+        let first: Vec<_> = first.iter().map(|s| s.id.to_string()).collect();
+        assert_eq!(first, vec!["alpha", "bravo", "charlie"]);
+
+        let rest = manager.list_skills_page(Some("charlie"), 3).await.unwrap();
+        let rest: Vec<_> = rest.iter().map(|s| s.id.to_string()).collect();
+        assert_eq!(rest, vec!["delta"]);
+    }
+
+    #[tokio::test]
+    async fn test_set_enabled_batch_registers_and_unregisters() {
+        let manager = SkillManager::new();
+        let skill = |id: &str| {
+            SkillDefinition::new(
+                SkillId::new(id.to_string()).unwrap(),
+                id.to_string(),
+                format!("{} skill", id),
+                "1.0.0".to_string(),
+                Origin::Local {
+                    path: std::path::PathBuf::from(format!("./skills/{id}")),
+                    editable: false,
+                },
+            )
+        };
+        manager.register_skill(skill("alpha")).await.unwrap();
+        manager.register_skill(skill("bravo")).await.unwrap();
+
+        let disabled = [
+            SkillId::new("alpha".to_string()).unwrap(),
+            SkillId::new("missing".to_string()).unwrap(),
+        ];
+        manager
+            .set_enabled_batch(vec![skill("charlie"), skill("bravo")], &disabled)
+            .await
+            .unwrap();
+
+        let mut ids: Vec<_> = manager
+            .list_skills()
+            .await
+            .unwrap()
+            .iter()
+            .map(|s| s.id.to_string())
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec!["bravo", "charlie"]);
+    }
 }
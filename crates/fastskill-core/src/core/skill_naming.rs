@@ -0,0 +1,495 @@
+//! How `add` names an installed skill, and what it does when the name is taken.
+//!
+//! A skill's id is also its directory in the skills dir and its key in
+//! `skill-project.toml` and `skills.lock`. By default it is the id the skill
+//! declares (`skill-project.toml` `[metadata] id`, else SKILL.md
+//! `metadata.id`, else `name`), under the registry scope when the skill was
+//! added by a `scope/name` reference, so `acme/tool` and `otherorg/tool` can
+//! be installed side by side. Skills copied out of folders with generic
+//! names often declare the same id, so `[tool.fastskill.naming]` (or
+//! `fastskill add --id-strategy/--on-collision`) can pick another
+//! [`IdStrategy`] and have a taken id de-collided with a numeric suffix.
+
+use crate::core::install::derive_skill_id_and_version;
+use crate::core::lock::{project_lock_path, ProjectSkillsLock};
+use crate::core::metadata::SkillFrontmatter;
+use crate::core::origin::Origin;
+use crate::core::project::resolve_project_file;
+use crate::core::service::{FastSkillService, ServiceError, SkillId};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+/// Highest numeric suffix tried before giving up on a taken id
+const MAX_ID_SUFFIX: usize = 999;
+
+/// How a skill's id is generated when it is added.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IdStrategy {
+    /// The id the skill declares (the default), scoped like its registry
+    /// reference.
+    #[default]
+    Declared,
+    /// The name of the folder, archive or repository the skill came from.
+    Folder,
+    /// A slug of the SKILL.md `name`.
+    NameSlug,
+    /// `<scope>-<declared id>-<hash>`: the source's owner or parent folder,
+    /// the declared id and a short hash of the source, so the same skill
+    /// added from two places gets two stable ids.
+    ScopedHash,
+}
+
+impl IdStrategy {
+    pub fn parse(s: &str) -> Result<Self, ServiceError> {
+        match s.trim() {
+            "declared" => Ok(Self::Declared),
+            "folder" => Ok(Self::Folder),
+            "name-slug" => Ok(Self::NameSlug),
+            "scoped-hash" => Ok(Self::ScopedHash),
+            other => Err(ServiceError::Validation(format!(
+                "unknown id strategy '{}' (expected declared, folder, name-slug or scoped-hash)",
+                other
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Declared => "declared",
+            Self::Folder => "folder",
+            Self::NameSlug => "name-slug",
+            Self::ScopedHash => "scoped-hash",
+        }
+    }
+
+    /// The id this strategy gives the skill fetched to `skill_path` from `origin`.
+    pub fn skill_id(
+        &self,
+        skill_path: &Path,
+        frontmatter: &SkillFrontmatter,
+        origin: &Origin,
+    ) -> Result<SkillId, ServiceError> {
+        let id = match self {
+            Self::Declared => {
+                let declared = derive_skill_id_and_version(skill_path, frontmatter)?.0;
+                return match registry_scope(origin) {
+                    Some(scope) if declared.scope().is_none() => {
+                        SkillId::scoped(scope, declared.as_str())
+                    }
+                    _ => Ok(declared),
+                };
+            }
+            Self::Folder => slugify(&source_folder_name(origin)),
+            Self::NameSlug => slugify(&frontmatter.name),
+            Self::ScopedHash => {
+                let declared = derive_skill_id_and_version(skill_path, frontmatter)?.0;
+                let hash = format!("{:x}", Sha256::digest(source_key(origin).as_bytes()));
+                [slugify(&source_scope(origin)), slugify(declared.as_str())]
+                    .into_iter()
+                    .filter(|part| !part.is_empty())
+                    .chain(std::iter::once(hash[..8].to_string()))
+                    .collect::<Vec<_>>()
+                    .join("-")
+            }
+        };
+        if id.is_empty() {
+            return Err(ServiceError::Validation(format!(
+                "Cannot derive a skill id with the '{}' strategy; choose another id strategy",
+                self
+            )));
+        }
+        SkillId::new(id)
+    }
+}
+
+impl std::fmt::Display for IdStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What `add` does when the generated id is already installed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IdCollision {
+    /// Refuse the add (the default); `--force` overwrites instead.
+    #[default]
+    Error,
+    /// Install under the first free `<id>-2`, `<id>-3`, …
+    Suffix,
+}
+
+impl IdCollision {
+    pub fn parse(s: &str) -> Result<Self, ServiceError> {
+        match s.trim() {
+            "error" => Ok(Self::Error),
+            "suffix" => Ok(Self::Suffix),
+            other => Err(ServiceError::Validation(format!(
+                "unknown collision policy '{}' (expected error or suffix)",
+                other
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Suffix => "suffix",
+        }
+    }
+}
+
+impl std::fmt::Display for IdCollision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Id generation and collision handling for `add` (`[tool.fastskill.naming]`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillNaming {
+    #[serde(default)]
+    pub strategy: IdStrategy,
+    #[serde(default)]
+    pub on_collision: IdCollision,
+}
+
+impl SkillNaming {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Lowercase `s`, turning every run of other characters into one `-`.
+pub fn slugify(s: &str) -> String {
+    let mut slug = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Path segments of a URL or scp-style git address, host excluded.
+fn url_segments(url: &str) -> Vec<String> {
+    let path = match url::Url::parse(url) {
+        Ok(parsed) => parsed.path().to_string(),
+        // `git@host:owner/repo.git`
+        Err(_) => url
+            .split_once(':')
+            .map_or(url, |(_, path)| path)
+            .to_string(),
+    };
+    path.split('/')
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn file_stem(path: &Path) -> String {
+    let name = if path.extension().is_some_and(|e| e == "zip") {
+        path.file_stem()
+    } else {
+        path.file_name()
+    };
+    name.map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// The folder, archive or repository name a skill was added from.
+fn source_folder_name(origin: &Origin) -> String {
+    match origin {
+        Origin::Local { path, .. } => file_stem(path),
+        Origin::Git {
+            subdir: Some(subdir),
+            ..
+        } => file_stem(subdir),
+        Origin::Git { url, .. } => url_segments(url)
+            .last()
+            .map(|repo| repo.trim_end_matches(".git").to_string())
+            .unwrap_or_default(),
+        Origin::ZipUrl { url } => url_segments(url)
+            .last()
+            .map(|name| file_stem(Path::new(name)))
+            .unwrap_or_default(),
+        Origin::Pack { skill, .. } => skill.clone(),
+        Origin::Repository { skill, .. } => skill.rsplit('/').next().unwrap_or(skill).to_string(),
+    }
+}
+
+/// The owner of a skill's source: the git owner, registry scope, parent folder
+/// or download host.
+fn source_scope(origin: &Origin) -> String {
+    match origin {
+        Origin::Local { path, .. } => path
+            .parent()
+            .and_then(Path::file_name)
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        Origin::Git { url, .. } => {
+            let segments = url_segments(url);
+            segments
+                .len()
+                .checked_sub(2)
+                .and_then(|i| segments.get(i))
+                .cloned()
+                .unwrap_or_default()
+        }
+        Origin::ZipUrl { url } => url::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string))
+            .unwrap_or_default(),
+        Origin::Pack { path, .. } => file_stem(path),
+        Origin::Repository { repo, skill, .. } => match skill.split_once('/') {
+            Some((scope, _)) => scope.to_string(),
+            None => repo.clone(),
+        },
+    }
+}
+
+/// The scope of a `scope/name` registry reference.
+fn registry_scope(origin: &Origin) -> Option<&str> {
+    match origin {
+        Origin::Repository { skill, .. } => skill.split_once('/').map(|(scope, _)| scope),
+        _ => None,
+    }
+}
+
+/// Identifies the source itself, leaving out refs and version constraints.
+fn source_key(origin: &Origin) -> String {
+    match origin {
+        Origin::Local { path, .. } => format!("local:{}", path.display()),
+        Origin::Git { url, subdir, .. } => match subdir {
+            Some(subdir) => format!("git:{}#{}", url, subdir.display()),
+            None => format!("git:{}", url),
+        },
+        Origin::ZipUrl { url } => format!("zip:{}", url),
+        Origin::Pack { path, skill } => format!("pack:{}#{}", path.display(), skill),
+        Origin::Repository { repo, skill, .. } => format!("repo:{}/{}", repo, skill),
+    }
+}
+
+/// Whether `a` and `b` install from the same place. Unlike
+/// [`Origin::is_same_source`], local paths are compared.
+fn same_source(a: &Origin, b: &Origin) -> bool {
+    match (a, b) {
+        (Origin::Local { path: a, .. }, Origin::Local { path: b, .. }) => {
+            a == b || matches!((a.canonicalize(), b.canonicalize()), (Ok(a), Ok(b)) if a == b)
+        }
+        _ => a.is_same_source(b),
+    }
+}
+
+impl FastSkillService {
+    /// The id `skills.lock` records for a skill installed from `origin`'s
+    /// source, if any. Resolves the project the same way installs do.
+    pub(crate) fn locked_id_for_source(&self, origin: &Origin) -> Option<String> {
+        let start_dir = match self.project_root() {
+            Some(root) => root.clone(),
+            None => std::env::current_dir().ok()?,
+        };
+        let project_file = resolve_project_file(&start_dir);
+        if !project_file.found {
+            return None;
+        }
+        let lock_path = project_lock_path(&project_file.path);
+        let lock = ProjectSkillsLock::load_from_file(&lock_path).ok()?;
+        lock.skills
+            .into_iter()
+            .find(|entry| same_source(&entry.origin, origin))
+            .map(|entry| entry.id)
+    }
+
+    async fn skill_id_taken(&self, id: &SkillId) -> Result<bool, ServiceError> {
+        let storage_dir = self.config().skill_storage_path.join(id.as_str());
+        // A scope named like an installed unscoped skill cannot get a directory
+        let scope_is_skill = id.scope().is_some_and(|scope| {
+            self.config()
+                .skill_storage_path
+                .join(scope)
+                .join("SKILL.md")
+                .exists()
+        });
+        Ok(storage_dir.exists()
+            || scope_is_skill
+            || storage_dir.is_symlink()
+            || self.skill_manager().get_skill(id).await?.is_some())
+    }
+
+    /// The id a fresh add from `origin` installs under, given the id its
+    /// strategy generated. A free `base` is returned as is. A taken one is an
+    /// [`AlreadyIndexed`](ServiceError::AlreadyIndexed) error under
+    /// [`IdCollision::Error`]; under [`IdCollision::Suffix`] the first free
+    /// `<base>-N` is returned, unless the project already has this source
+    /// installed, which is still an error.
+    pub async fn claim_skill_id(
+        &self,
+        base: &SkillId,
+        origin: &Origin,
+        on_collision: IdCollision,
+    ) -> Result<SkillId, ServiceError> {
+        if !self.skill_id_taken(base).await? {
+            return Ok(base.clone());
+        }
+        if on_collision == IdCollision::Error {
+            return Err(ServiceError::AlreadyIndexed(base.to_string()));
+        }
+        if let Some(installed) = self.locked_id_for_source(origin) {
+            return Err(ServiceError::AlreadyIndexed(installed));
+        }
+        for n in 2..=MAX_ID_SUFFIX {
+            let candidate = SkillId::new(format!("{}-{}", base, n))?;
+            if !self.skill_id_taken(&candidate).await? {
+                return Ok(candidate);
+            }
+        }
+        Err(ServiceError::Validation(format!(
+            "No free id left for '{}' (tried suffixes up to -{})",
+            base, MAX_ID_SUFFIX
+        )))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::metadata::parse_yaml_frontmatter;
+    use crate::core::service::ServiceConfig;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn frontmatter(name: &str) -> SkillFrontmatter {
+        parse_yaml_frontmatter(&format!(
+            "---\nname: {}\ndescription: test\n---\nbody\n",
+            name
+        ))
+        .unwrap()
+    }
+
+    fn local(path: &str) -> Origin {
+        Origin::Local {
+            path: PathBuf::from(path),
+            editable: false,
+        }
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("PDF Tools (v2)"), "pdf-tools-v2");
+        assert_eq!(slugify("--web_scraper--"), "web-scraper");
+        assert_eq!(slugify("  "), "");
+    }
+
+    #[test]
+    fn test_strategies_generate_ids() {
+        let tmp = TempDir::new().unwrap();
+        let fm = frontmatter("skill");
+        let origin = Origin::Git {
+            url: "https://github.com/acme/Web-Tools.git".to_string(),
+            r#ref: Default::default(),
+            subdir: None,
+        };
+
+        let id = |strategy: IdStrategy, fm: &SkillFrontmatter, origin: &Origin| {
+            strategy
+                .skill_id(tmp.path(), fm, origin)
+                .unwrap()
+                .into_string()
+        };
+        assert_eq!(id(IdStrategy::Declared, &fm, &origin), "skill");
+        assert_eq!(id(IdStrategy::Folder, &fm, &origin), "web-tools");
+        assert_eq!(
+            id(IdStrategy::NameSlug, &frontmatter("PDF Tools"), &origin),
+            "pdf-tools"
+        );
+        assert_eq!(
+            id(IdStrategy::Folder, &fm, &local("/work/pdf/skill.zip")),
+            "skill"
+        );
+
+        let scoped = id(IdStrategy::ScopedHash, &fm, &origin);
+        assert!(scoped.starts_with("acme-skill-"), "{}", scoped);
+        assert_eq!(scoped.len(), "acme-skill-".len() + 8);
+        // Stable for one source, different for another
+        assert_eq!(scoped, id(IdStrategy::ScopedHash, &fm, &origin));
+        assert_ne!(
+            id(IdStrategy::ScopedHash, &fm, &local("/work/acme/skill")),
+            id(IdStrategy::ScopedHash, &fm, &local("/work/other/skill"))
+        );
+
+        // Registry references keep their scope
+        let repo = |skill: &str| Origin::Repository {
+            repo: "main".to_string(),
+            skill: skill.to_string(),
+            version: None,
+        };
+        assert_eq!(
+            id(IdStrategy::Declared, &fm, &repo("acme/tool")),
+            "acme/skill"
+        );
+        assert_eq!(id(IdStrategy::Declared, &fm, &repo("tool")), "skill");
+        assert_eq!(id(IdStrategy::Folder, &fm, &repo("acme/tool")), "tool");
+    }
+
+    #[test]
+    fn test_naming_parse_and_toml() {
+        assert_eq!(
+            IdStrategy::parse("name-slug").unwrap(),
+            IdStrategy::NameSlug
+        );
+        assert!(IdStrategy::parse("uuid").is_err());
+        assert_eq!(IdCollision::parse("suffix").unwrap(), IdCollision::Suffix);
+
+        let naming: SkillNaming = toml::from_str("on_collision = \"suffix\"").unwrap();
+        assert_eq!(naming.strategy, IdStrategy::Declared);
+        assert_eq!(naming.on_collision, IdCollision::Suffix);
+        assert!(SkillNaming::default().is_default());
+    }
+
+    #[tokio::test]
+    async fn test_claim_skill_id_suffixes_taken_ids() {
+        let tmp = TempDir::new().unwrap();
+        let config = ServiceConfig {
+            skill_storage_path: tmp.path().to_path_buf(),
+            ..Default::default()
+        };
+        let service = FastSkillService::new(config).await.unwrap();
+        std::fs::create_dir_all(tmp.path().join("skill")).unwrap();
+        std::fs::create_dir_all(tmp.path().join("skill-2")).unwrap();
+
+        let base = SkillId::new("skill".to_string()).unwrap();
+        let origin = local("/elsewhere/skill");
+        assert!(matches!(
+            service
+                .claim_skill_id(&base, &origin, IdCollision::Error)
+                .await,
+            Err(ServiceError::AlreadyIndexed(_))
+        ));
+        let claimed = service
+            .claim_skill_id(&base, &origin, IdCollision::Suffix)
+            .await
+            .unwrap();
+        assert_eq!(claimed.as_str(), "skill-3");
+
+        let free = SkillId::new("other".to_string()).unwrap();
+        let claimed = service
+            .claim_skill_id(&free, &origin, IdCollision::Error)
+            .await
+            .unwrap();
+        assert_eq!(claimed, free);
+
+        // `skill/tool` would need the installed skill's directory as its scope
+        std::fs::write(tmp.path().join("skill/SKILL.md"), "").unwrap();
+        let scoped = SkillId::new("skill/tool".to_string()).unwrap();
+        assert!(service
+            .claim_skill_id(&scoped, &origin, IdCollision::Error)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,1055 @@
+//! Skill test harness.
+//!
+//! A skill can carry its own tests in a `tests/` directory next to SKILL.md:
+//!
+//! - `*.toml` files with `[[case]]` tables and `*.csv` files with a
+//!   `name,prompt,expected[,script][,match]` header hold prompt cases. A case
+//!   with a `script` runs that skill script with the prompt in `PARAM_PROMPT`
+//!   and compares its stdout to `expected`; a case without one (or with
+//!   `match = "judge"`) is graded by a [`Judge`].
+//! - `test_*.sh`, `test_*.py` and `test_*.js` files are script checks that pass
+//!   when they exit with status 0.
+//!
+//! Scripts run through [`ExecutionSandbox::execute_skill_script`], so they use
+//! the runtime the skill declares. Results render as JUnit XML for CI.
+
+use crate::core::service::ServiceError;
+use crate::core::skill_manager::SkillDefinition;
+use crate::execution::{
+    ExecutionContext, ExecutionError, ExecutionResult, ExecutionSandbox, ScriptDefinition,
+    ScriptLanguage,
+};
+use crate::output::escape_xml;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::{Component, Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+
+/// Directory inside a skill that holds its tests
+pub const TESTS_DIR: &str = "tests";
+
+/// How a prompt case's output is compared to `expected`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchMode {
+    /// Output equals `expected` once surrounding whitespace is trimmed
+    #[default]
+    Exact,
+    /// Output contains `expected`
+    Contains,
+    /// Output matches the regular expression in `expected`
+    Regex,
+    /// A [`Judge`] decides
+    Judge,
+}
+
+impl MatchMode {
+    /// Parse a match mode name (`exact`, `contains`, `regex`, `judge`).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "exact" => Some(MatchMode::Exact),
+            "contains" => Some(MatchMode::Contains),
+            "regex" => Some(MatchMode::Regex),
+            "judge" => Some(MatchMode::Judge),
+            _ => None,
+        }
+    }
+}
+
+/// A prompt with its expected output
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PromptCase {
+    pub name: String,
+    pub prompt: String,
+    #[serde(default)]
+    pub expected: Option<String>,
+    /// Skill script (relative to the skill directory) that produces the output
+    #[serde(default)]
+    pub script: Option<String>,
+    /// Extra `PARAM_*` variables for `script`
+    #[serde(default)]
+    pub params: HashMap<String, String>,
+    #[serde(default, rename = "match")]
+    pub match_mode: MatchMode,
+}
+
+/// One test found in a skill's `tests/` directory
+#[derive(Debug, Clone, PartialEq)]
+pub enum TestCase {
+    Prompt(PromptCase),
+    /// A `test_*` script that passes when it exits with status 0
+    Script {
+        name: String,
+        path: PathBuf,
+    },
+}
+
+/// A test case with the file it was read from
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscoveredCase {
+    /// File name inside `tests/`, e.g. `cases.toml`
+    pub source: String,
+    pub case: TestCase,
+}
+
+impl DiscoveredCase {
+    pub fn name(&self) -> &str {
+        match &self.case {
+            TestCase::Prompt(case) => &case.name,
+            TestCase::Script { name, .. } => name,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CaseFile {
+    #[serde(default)]
+    case: Vec<PromptCase>,
+}
+
+/// Find the tests of the skill in `skill_dir`. A skill without a `tests/`
+/// directory has no tests; files are read in name order.
+pub fn discover(skill_dir: &Path) -> Result<Vec<DiscoveredCase>, ServiceError> {
+    let tests_dir = skill_dir.join(TESTS_DIR);
+    if !tests_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files: Vec<PathBuf> = std::fs::read_dir(&tests_dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .collect();
+    files.sort();
+
+    let mut cases = Vec::new();
+    for path in files {
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        let prompt_cases = match extension.as_str() {
+            "toml" => parse_toml_cases(&std::fs::read_to_string(&path)?),
+            "csv" => parse_csv_cases(&std::fs::read_to_string(&path)?),
+            _ => {
+                if file_name.starts_with("test_") && language_for(&path).is_some() {
+                    let name = path
+                        .file_stem()
+                        .map(|stem| stem.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    cases.push(DiscoveredCase {
+                        source: file_name,
+                        case: TestCase::Script { name, path },
+                    });
+                }
+                continue;
+            }
+        }
+        .map_err(|e| ServiceError::Validation(format!("{}/{}: {}", TESTS_DIR, file_name, e)))?;
+        cases.extend(prompt_cases.into_iter().map(|case| DiscoveredCase {
+            source: file_name.clone(),
+            case: TestCase::Prompt(case),
+        }));
+    }
+    Ok(cases)
+}
+
+/// Parse `[[case]]` tables from a TOML case file
+pub fn parse_toml_cases(content: &str) -> Result<Vec<PromptCase>, String> {
+    let file: CaseFile = toml::from_str(content).map_err(|e| e.to_string())?;
+    for case in &file.case {
+        check_case(case)?;
+    }
+    Ok(file.case)
+}
+
+/// Parse a CSV case file. The header names the columns: `name`, `prompt`
+/// and `expected` are required, `script` and `match` are optional.
+pub fn parse_csv_cases(content: &str) -> Result<Vec<PromptCase>, String> {
+    let mut rows = parse_csv(content)?.into_iter();
+    let Some(header) = rows.next() else {
+        return Ok(Vec::new());
+    };
+    let column = |name: &str| header.iter().position(|h| h.trim() == name);
+    let (Some(name_col), Some(prompt_col), Some(expected_col)) =
+        (column("name"), column("prompt"), column("expected"))
+    else {
+        return Err("header must name the 'name', 'prompt' and 'expected' columns".to_string());
+    };
+    let script_col = column("script");
+    let match_col = column("match");
+
+    let mut cases = Vec::new();
+    for (index, row) in rows.enumerate() {
+        let line = index + 2;
+        let field = |col: usize| row.get(col).cloned().unwrap_or_default();
+        let optional = |col: Option<usize>| col.map(field).filter(|value| !value.is_empty());
+        let match_mode = match optional(match_col) {
+            Some(value) => MatchMode::parse(&value)
+                .ok_or_else(|| format!("row {}: unknown match mode '{}'", line, value))?,
+            None => MatchMode::default(),
+        };
+        let case = PromptCase {
+            name: field(name_col),
+            prompt: field(prompt_col),
+            expected: Some(field(expected_col)),
+            script: optional(script_col),
+            params: HashMap::new(),
+            match_mode,
+        };
+        check_case(&case).map_err(|e| format!("row {}: {}", line, e))?;
+        cases.push(case);
+    }
+    Ok(cases)
+}
+
+fn check_case(case: &PromptCase) -> Result<(), String> {
+    if case.name.trim().is_empty() {
+        return Err("case name cannot be empty".to_string());
+    }
+    if case.script.is_some() && case.match_mode != MatchMode::Judge && case.expected.is_none() {
+        return Err(format!(
+            "case '{}' runs a script but has no 'expected' output",
+            case.name
+        ));
+    }
+    if case.match_mode == MatchMode::Regex {
+        let pattern = case.expected.as_deref().unwrap_or("");
+        regex::Regex::new(pattern)
+            .map_err(|e| format!("case '{}' has an invalid regex: {}", case.name, e))?;
+    }
+    Ok(())
+}
+
+/// Split CSV text into rows of fields. Fields may be double-quoted, with `""`
+/// for a literal quote; quoted fields may span lines. Blank lines are skipped.
+fn parse_csv(content: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut rows = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => row.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                let row = std::mem::take(&mut row);
+                if !(row.len() == 1 && row[0].is_empty()) {
+                    rows.push(row);
+                }
+            }
+            _ => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err("unterminated quoted field".to_string());
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+fn language_for(path: &Path) -> Option<ScriptLanguage> {
+    match path.extension().and_then(|ext| ext.to_str())? {
+        "sh" => Some(ScriptLanguage::Shell),
+        "py" => Some(ScriptLanguage::Python),
+        "js" => Some(ScriptLanguage::NodeJS),
+        _ => None,
+    }
+}
+
+/// Resolve a case's `script` inside the skill directory, refusing absolute
+/// paths and anything that climbs out of it
+fn resolve_script(skill_dir: &Path, script: &str) -> Result<PathBuf, String> {
+    let relative = Path::new(script);
+    if relative
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(format!(
+            "script '{}' must be a relative path inside the skill directory",
+            script
+        ));
+    }
+    let path = skill_dir.join(relative);
+    let resolved = path
+        .canonicalize()
+        .map_err(|_| format!("script '{}' not found", script))?;
+    let base = skill_dir
+        .canonicalize()
+        .map_err(|e| format!("cannot resolve skill directory: {}", e))?;
+    if !resolved.starts_with(&base) {
+        return Err(format!(
+            "script '{}' resolves outside the skill directory",
+            script
+        ));
+    }
+    Ok(resolved)
+}
+
+/// What a judge is asked to grade
+#[derive(Debug, Clone, Serialize)]
+pub struct JudgeRequest<'a> {
+    pub skill: &'a str,
+    pub case: &'a str,
+    pub prompt: &'a str,
+    pub expected: Option<&'a str>,
+    /// Output of the case's script; `None` when the case has no script and
+    /// the judge is expected to run the prompt itself
+    pub output: Option<&'a str>,
+}
+
+/// A judge's decision
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Verdict {
+    pub pass: bool,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Grades prompt cases that plain string matching cannot, typically by asking
+/// an LLM
+#[async_trait]
+pub trait Judge: Send + Sync {
+    async fn judge(&self, request: &JudgeRequest<'_>) -> Result<Verdict, ServiceError>;
+}
+
+/// A judge that runs an external command: the [`JudgeRequest`] is written to
+/// its stdin as JSON and it prints a [`Verdict`] (`{"pass": bool, "reason":
+/// "..."}`) on stdout
+#[derive(Debug, Clone)]
+pub struct CommandJudge {
+    program: String,
+    args: Vec<String>,
+}
+
+impl CommandJudge {
+    pub fn new(program: impl Into<String>, args: Vec<String>) -> Self {
+        Self {
+            program: program.into(),
+            args,
+        }
+    }
+
+    /// Build from a whitespace-separated command line such as
+    /// `python3 judge.py --model gpt-4o`
+    pub fn from_command_line(command: &str) -> Option<Self> {
+        let mut words = command.split_whitespace().map(str::to_string);
+        let program = words.next()?;
+        Some(Self::new(program, words.collect()))
+    }
+}
+
+#[async_trait]
+impl Judge for CommandJudge {
+    async fn judge(&self, request: &JudgeRequest<'_>) -> Result<Verdict, ServiceError> {
+        let input = serde_json::to_vec(request)
+            .map_err(|e| ServiceError::Custom(format!("Failed to encode judge request: {}", e)))?;
+        let mut child = tokio::process::Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                ServiceError::Custom(format!("Failed to start judge '{}': {}", self.program, e))
+            })?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // A judge may exit without reading its input; its exit status
+            // says what went wrong
+            if let Err(e) = stdin.write_all(&input).await {
+                if e.kind() != std::io::ErrorKind::BrokenPipe {
+                    return Err(e.into());
+                }
+            }
+        }
+        let output = child.wait_with_output().await?;
+        if !output.status.success() {
+            return Err(ServiceError::Custom(format!(
+                "Judge '{}' failed ({}): {}",
+                self.program,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        serde_json::from_slice(&output.stdout).map_err(|e| {
+            ServiceError::Custom(format!(
+                "Judge '{}' printed an invalid verdict: {}",
+                self.program, e
+            ))
+        })
+    }
+}
+
+/// Result of one test case
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum CaseOutcome {
+    Passed,
+    /// The case ran and did not meet its expectation
+    Failed {
+        message: String,
+        details: String,
+    },
+    /// The case could not be run (bad script path, judge error, ...)
+    Error {
+        message: String,
+    },
+    Skipped {
+        reason: String,
+    },
+}
+
+/// A test case together with how it went
+#[derive(Debug, Clone, Serialize)]
+pub struct CaseResult {
+    pub name: String,
+    pub source: String,
+    #[serde(flatten)]
+    pub outcome: CaseOutcome,
+    #[serde(with = "duration_secs")]
+    pub duration: Duration,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub stdout: String,
+}
+
+/// The results of one skill's tests
+#[derive(Debug, Clone, Serialize)]
+pub struct SuiteResult {
+    pub skill_id: String,
+    pub timestamp: DateTime<Utc>,
+    pub cases: Vec<CaseResult>,
+}
+
+impl SuiteResult {
+    pub fn count(&self, matches: impl Fn(&CaseOutcome) -> bool) -> usize {
+        self.cases
+            .iter()
+            .filter(|case| matches(&case.outcome))
+            .count()
+    }
+
+    pub fn failures(&self) -> usize {
+        self.count(|outcome| matches!(outcome, CaseOutcome::Failed { .. }))
+    }
+
+    pub fn errors(&self) -> usize {
+        self.count(|outcome| matches!(outcome, CaseOutcome::Error { .. }))
+    }
+
+    pub fn skipped(&self) -> usize {
+        self.count(|outcome| matches!(outcome, CaseOutcome::Skipped { .. }))
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.cases.iter().map(|case| case.duration).sum()
+    }
+}
+
+mod duration_secs {
+    use serde::Serializer;
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(duration.as_secs_f64())
+    }
+}
+
+/// Runs a skill's test cases
+pub struct SkillTestRunner {
+    sandbox: ExecutionSandbox,
+    judge: Option<Box<dyn Judge>>,
+}
+
+impl SkillTestRunner {
+    pub fn new(sandbox: ExecutionSandbox) -> Self {
+        Self {
+            sandbox,
+            judge: None,
+        }
+    }
+
+    /// Grade judge cases (and prompt cases without a script) with `judge`.
+    /// Without one those cases are skipped.
+    pub fn with_judge(mut self, judge: Box<dyn Judge>) -> Self {
+        self.judge = Some(judge);
+        self
+    }
+
+    /// Run `cases` of `skill` in order
+    pub async fn run(&self, skill: &SkillDefinition, cases: &[DiscoveredCase]) -> SuiteResult {
+        let timestamp = Utc::now();
+        let mut results = Vec::with_capacity(cases.len());
+        for case in cases {
+            let start = Instant::now();
+            let (outcome, stdout) = match &case.case {
+                TestCase::Prompt(prompt) => self.run_prompt_case(skill, prompt).await,
+                TestCase::Script { path, .. } => self.run_script_check(skill, path).await,
+            };
+            results.push(CaseResult {
+                name: case.name().to_string(),
+                source: case.source.clone(),
+                outcome,
+                duration: start.elapsed(),
+                stdout,
+            });
+        }
+        SuiteResult {
+            skill_id: skill.id.to_string(),
+            timestamp,
+            cases: results,
+        }
+    }
+
+    async fn run_prompt_case(
+        &self,
+        skill: &SkillDefinition,
+        case: &PromptCase,
+    ) -> (CaseOutcome, String) {
+        let Some(script) = &case.script else {
+            return (self.judge_case(skill, case, None).await, String::new());
+        };
+        let skill_dir = skill.skill_file.parent().unwrap_or(Path::new("."));
+        let path = match resolve_script(skill_dir, script) {
+            Ok(path) => path,
+            Err(message) => return (CaseOutcome::Error { message }, String::new()),
+        };
+        let mut parameters = case.params.clone();
+        parameters.insert("PROMPT".to_string(), case.prompt.clone());
+        let result = match self.execute(skill, path, parameters).await {
+            Ok(result) => result,
+            Err(e) => {
+                let message = e.to_string();
+                return (CaseOutcome::Error { message }, String::new());
+            }
+        };
+        if !result.success {
+            let outcome = CaseOutcome::Failed {
+                message: exit_message(&result),
+                details: result.stderr.clone(),
+            };
+            return (outcome, result.stdout);
+        }
+
+        let output = result.stdout.trim_end();
+        let expected = case.expected.as_deref().unwrap_or("");
+        let outcome = match case.match_mode {
+            MatchMode::Judge => self.judge_case(skill, case, Some(output)).await,
+            mode => match_output(mode, expected, output),
+        };
+        (outcome, result.stdout)
+    }
+
+    async fn judge_case(
+        &self,
+        skill: &SkillDefinition,
+        case: &PromptCase,
+        output: Option<&str>,
+    ) -> CaseOutcome {
+        let Some(judge) = &self.judge else {
+            return CaseOutcome::Skipped {
+                reason: "needs a judge (none configured)".to_string(),
+            };
+        };
+        let request = JudgeRequest {
+            skill: skill.id.as_str(),
+            case: &case.name,
+            prompt: &case.prompt,
+            expected: case.expected.as_deref(),
+            output,
+        };
+        match judge.judge(&request).await {
+            Ok(verdict) if verdict.pass => CaseOutcome::Passed,
+            Ok(verdict) => CaseOutcome::Failed {
+                message: verdict
+                    .reason
+                    .unwrap_or_else(|| "rejected by judge".to_string()),
+                details: output.unwrap_or_default().to_string(),
+            },
+            Err(e) => CaseOutcome::Error {
+                message: e.to_string(),
+            },
+        }
+    }
+
+    async fn run_script_check(
+        &self,
+        skill: &SkillDefinition,
+        path: &Path,
+    ) -> (CaseOutcome, String) {
+        match self
+            .execute(skill, path.to_path_buf(), HashMap::new())
+            .await
+        {
+            Ok(result) if result.success => (CaseOutcome::Passed, result.stdout),
+            Ok(result) => {
+                let outcome = CaseOutcome::Failed {
+                    message: exit_message(&result),
+                    details: result.stderr.clone(),
+                };
+                (outcome, result.stdout)
+            }
+            Err(e) => {
+                let message = e.to_string();
+                (CaseOutcome::Error { message }, String::new())
+            }
+        }
+    }
+
+    async fn execute(
+        &self,
+        skill: &SkillDefinition,
+        path: PathBuf,
+        parameters: HashMap<String, String>,
+    ) -> Result<ExecutionResult, ExecutionError> {
+        let language = language_for(&path).ok_or_else(|| {
+            ExecutionError::InvalidScript(format!(
+                "{} is not a .sh, .py or .js script",
+                path.display()
+            ))
+        })?;
+        let skill_dir = skill.skill_file.parent().map(Path::to_path_buf);
+        let script = ScriptDefinition {
+            path,
+            content: None,
+            language,
+            parameters,
+            working_directory: skill_dir.clone(),
+        };
+        let context = ExecutionContext {
+            skill_id: skill.id.to_string(),
+            user_id: None,
+            session_id: format!("test-{}", Utc::now().timestamp_millis()),
+            parameters: HashMap::new(),
+            working_directory: skill_dir,
+            environment_variables: HashMap::new(),
+        };
+        self.sandbox
+            .execute_skill_script(skill, script, context)
+            .await
+    }
+}
+
+fn exit_message(result: &ExecutionResult) -> String {
+    match result.exit_code {
+        Some(code) => format!("script exited with status {}", code),
+        None => "script was terminated".to_string(),
+    }
+}
+
+/// Compare a script's output with the expected output
+fn match_output(mode: MatchMode, expected: &str, output: &str) -> CaseOutcome {
+    let (pass, what) = match mode {
+        MatchMode::Exact => (output.trim() == expected.trim(), "output differs from"),
+        MatchMode::Contains => (output.contains(expected), "output does not contain"),
+        MatchMode::Regex => (
+            regex::Regex::new(expected).is_ok_and(|re| re.is_match(output)),
+            "output does not match",
+        ),
+        MatchMode::Judge => unreachable!("judge cases are graded by the judge"),
+    };
+    if pass {
+        CaseOutcome::Passed
+    } else {
+        CaseOutcome::Failed {
+            message: format!("{} expected {:?}", what, expected),
+            details: output.to_string(),
+        }
+    }
+}
+
+/// Render suites as a JUnit XML report (one `<testsuite>` per skill)
+pub fn render_junit(suites: &[SuiteResult]) -> String {
+    let total = |f: fn(&SuiteResult) -> usize| suites.iter().map(f).sum::<usize>();
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        out,
+        "<testsuites name=\"fastskill\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.3}\">",
+        total(|s| s.cases.len()),
+        total(SuiteResult::failures),
+        total(SuiteResult::errors),
+        total(SuiteResult::skipped),
+        suites.iter().map(|s| s.duration().as_secs_f64()).sum::<f64>()
+    );
+    for suite in suites {
+        let _ = writeln!(
+            out,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.3}\" timestamp=\"{}\">",
+            escape_xml(&suite.skill_id),
+            suite.cases.len(),
+            suite.failures(),
+            suite.errors(),
+            suite.skipped(),
+            suite.duration().as_secs_f64(),
+            suite.timestamp.format("%Y-%m-%dT%H:%M:%S")
+        );
+        for case in &suite.cases {
+            let _ = write!(
+                out,
+                "    <testcase name=\"{}\" classname=\"{}.{}\" time=\"{:.3}\"",
+                escape_xml(&case.name),
+                escape_xml(&suite.skill_id),
+                escape_xml(&case.source),
+                case.duration.as_secs_f64()
+            );
+            if case.outcome == CaseOutcome::Passed && case.stdout.is_empty() {
+                out.push_str("/>\n");
+                continue;
+            }
+            out.push_str(">\n");
+            match &case.outcome {
+                CaseOutcome::Passed => {}
+                CaseOutcome::Failed { message, details } => {
+                    let _ = writeln!(
+                        out,
+                        "      <failure message=\"{}\">{}</failure>",
+                        escape_xml(message),
+                        escape_xml(details)
+                    );
+                }
+                CaseOutcome::Error { message } => {
+                    let _ = writeln!(out, "      <error message=\"{}\"/>", escape_xml(message));
+                }
+                CaseOutcome::Skipped { reason } => {
+                    let _ = writeln!(out, "      <skipped message=\"{}\"/>", escape_xml(reason));
+                }
+            }
+            if !case.stdout.is_empty() {
+                let _ = writeln!(
+                    out,
+                    "      <system-out>{}</system-out>",
+                    escape_xml(&case.stdout)
+                );
+            }
+            out.push_str("    </testcase>\n");
+        }
+        out.push_str("  </testsuite>\n");
+    }
+    out.push_str("</testsuites>\n");
+    out
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::origin::Origin;
+    use crate::core::service::SkillId;
+    use crate::execution::ExecutionConfig;
+    use tempfile::TempDir;
+
+    fn skill_in(dir: &Path) -> SkillDefinition {
+        let mut skill = SkillDefinition::new(
+            SkillId::new("greeter".to_string()).unwrap(),
+            "greeter".to_string(),
+            "Greets".to_string(),
+            "1.0.0".to_string(),
+            Origin::Local {
+                path: dir.to_path_buf(),
+                editable: false,
+            },
+        );
+        skill.skill_file = dir.join("SKILL.md");
+        skill
+    }
+
+    fn runner() -> SkillTestRunner {
+        SkillTestRunner::new(ExecutionSandbox::new(ExecutionConfig::default()).unwrap())
+    }
+
+    struct FixedJudge(bool);
+
+    #[async_trait]
+    impl Judge for FixedJudge {
+        async fn judge(&self, request: &JudgeRequest<'_>) -> Result<Verdict, ServiceError> {
+            Ok(Verdict {
+                pass: self.0,
+                reason: Some(format!("judged {}", request.case)),
+            })
+        }
+    }
+
+    #[test]
+    fn test_parse_toml_cases() {
+        let cases = parse_toml_cases(
+            r#"
+[[case]]
+name = "greets"
+prompt = "Say hi to Ada"
+expected = "hello Ada"
+script = "scripts/greet.sh"
+
+[[case]]
+name = "tone"
+prompt = "Be polite"
+match = "judge"
+"#,
+        )
+        .unwrap();
+        assert_eq!(cases.len(), 2);
+        assert_eq!(cases[0].script.as_deref(), Some("scripts/greet.sh"));
+        assert_eq!(cases[0].match_mode, MatchMode::Exact);
+        assert_eq!(cases[1].match_mode, MatchMode::Judge);
+        assert!(cases[1].expected.is_none());
+
+        let err = parse_toml_cases("[[case]]\nname = \"x\"\nprompt = \"p\"\nscript = \"a.sh\"\n")
+            .unwrap_err();
+        assert!(err.contains("no 'expected'"));
+        assert!(parse_toml_cases("[[case]]\nname = \"x\"\nprompt = \"p\"\nbogus = 1\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_csv_cases() {
+        let cases = parse_csv_cases(
+            "name,prompt,expected,match\n\
+             plain,hi,hello,\n\
+             quoted,\"say \"\"hi\"\", then\nwave\",\"a,b\",contains\n\n",
+        )
+        .unwrap();
+        assert_eq!(cases.len(), 2);
+        assert_eq!(cases[0].match_mode, MatchMode::Exact);
+        assert_eq!(cases[1].prompt, "say \"hi\", then\nwave");
+        assert_eq!(cases[1].expected.as_deref(), Some("a,b"));
+        assert_eq!(cases[1].match_mode, MatchMode::Contains);
+
+        assert!(parse_csv_cases("name,prompt\nx,y\n").is_err());
+        assert!(parse_csv_cases("name,prompt,expected,match\nx,y,z,fuzzy\n").is_err());
+        assert!(parse_csv_cases("name,prompt,expected\nx,\"open\n").is_err());
+    }
+
+    #[test]
+    fn test_discover_reads_case_files_and_scripts() {
+        let temp = TempDir::new().unwrap();
+        let tests = temp.path().join(TESTS_DIR);
+        std::fs::create_dir_all(&tests).unwrap();
+        std::fs::write(
+            tests.join("cases.toml"),
+            "[[case]]\nname = \"a\"\nprompt = \"p\"\n",
+        )
+        .unwrap();
+        std::fs::write(tests.join("more.csv"), "name,prompt,expected\nb,p,e\n").unwrap();
+        std::fs::write(tests.join("test_lint.sh"), "exit 0\n").unwrap();
+        std::fs::write(tests.join("helper.sh"), "exit 0\n").unwrap();
+        std::fs::write(tests.join("README.md"), "notes\n").unwrap();
+
+        let cases = discover(temp.path()).unwrap();
+        let names: Vec<&str> = cases.iter().map(DiscoveredCase::name).collect();
+        assert_eq!(names, ["a", "b", "test_lint"]);
+        assert_eq!(cases[1].source, "more.csv");
+
+        assert!(discover(&temp.path().join("missing")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_resolve_script_stays_inside_skill() {
+        let temp = TempDir::new().unwrap();
+        let skill_dir = temp.path().join("skill");
+        std::fs::create_dir_all(skill_dir.join("scripts")).unwrap();
+        std::fs::write(skill_dir.join("scripts/run.sh"), "exit 0\n").unwrap();
+        std::fs::write(temp.path().join("outside.sh"), "exit 0\n").unwrap();
+
+        assert!(resolve_script(&skill_dir, "scripts/run.sh").is_ok());
+        assert!(resolve_script(&skill_dir, "../outside.sh").is_err());
+        assert!(resolve_script(&skill_dir, "scripts/../../outside.sh").is_err());
+        let absolute = temp.path().join("outside.sh");
+        assert!(resolve_script(&skill_dir, absolute.to_str().unwrap()).is_err());
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(&absolute, skill_dir.join("link.sh")).unwrap();
+            assert!(resolve_script(&skill_dir, "link.sh").is_err());
+        }
+    }
+
+    #[test]
+    fn test_match_output() {
+        assert_eq!(
+            match_output(MatchMode::Exact, "hello", "hello\n"),
+            CaseOutcome::Passed
+        );
+        assert!(matches!(
+            match_output(MatchMode::Exact, "hello", "hello there"),
+            CaseOutcome::Failed { .. }
+        ));
+        assert_eq!(
+            match_output(MatchMode::Contains, "there", "hello there"),
+            CaseOutcome::Passed
+        );
+        assert_eq!(
+            match_output(MatchMode::Regex, r"^hello \w+$", "hello Ada"),
+            CaseOutcome::Passed
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_runner_runs_scripts_and_judges() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("greeter");
+        let tests = dir.join(TESTS_DIR);
+        std::fs::create_dir_all(&tests).unwrap();
+        std::fs::write(dir.join("greet.sh"), "echo \"hello $PARAM_PROMPT\"\n").unwrap();
+        std::fs::write(
+            tests.join("cases.toml"),
+            r#"
+[[case]]
+name = "greets"
+prompt = "Ada"
+expected = "hello Ada"
+script = "greet.sh"
+
+[[case]]
+name = "wrong"
+prompt = "Bob"
+expected = "hello Ada"
+script = "greet.sh"
+
+[[case]]
+name = "escape"
+prompt = "x"
+expected = "x"
+script = "../greet.sh"
+
+[[case]]
+name = "graded"
+prompt = "Be nice"
+"#,
+        )
+        .unwrap();
+        std::fs::write(tests.join("test_fails.sh"), "echo oops >&2\nexit 3\n").unwrap();
+        let skill = skill_in(&dir);
+        let cases = discover(&dir).unwrap();
+
+        let suite = runner().run(&skill, &cases).await;
+        let outcomes: Vec<&CaseOutcome> = suite.cases.iter().map(|c| &c.outcome).collect();
+        assert_eq!(outcomes[0], &CaseOutcome::Passed);
+        assert!(
+            matches!(outcomes[1], CaseOutcome::Failed { details, .. } if details == "hello Bob")
+        );
+        assert!(matches!(outcomes[2], CaseOutcome::Error { .. }));
+        assert!(matches!(outcomes[3], CaseOutcome::Skipped { .. }));
+        assert!(
+            matches!(outcomes[4], CaseOutcome::Failed { message, details } if message.contains('3') && details.contains("oops"))
+        );
+        assert_eq!(
+            (suite.failures(), suite.errors(), suite.skipped()),
+            (2, 1, 1)
+        );
+
+        let judged = runner()
+            .with_judge(Box::new(FixedJudge(false)))
+            .run(&skill, &cases[3..4])
+            .await;
+        assert_eq!(
+            judged.cases[0].outcome,
+            CaseOutcome::Failed {
+                message: "judged graded".to_string(),
+                details: String::new(),
+            }
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_command_judge_reads_verdict() {
+        let temp = TempDir::new().unwrap();
+        let script = temp.path().join("judge.sh");
+        std::fs::write(
+            &script,
+            "input=$(cat)\ncase \"$input\" in\n  *'\"case\":\"good\"'*) echo '{\"pass\": true}' ;;\n  *) echo '{\"pass\": false, \"reason\": \"bad\"}' ;;\nesac\n",
+        )
+        .unwrap();
+        let judge = CommandJudge::from_command_line(&format!("sh {}", script.display())).unwrap();
+        let request = |case| JudgeRequest {
+            skill: "s",
+            case,
+            prompt: "p",
+            expected: None,
+            output: Some("o"),
+        };
+
+        assert!(judge.judge(&request("good")).await.unwrap().pass);
+        let verdict = judge.judge(&request("other")).await.unwrap();
+        assert!(!verdict.pass);
+        assert_eq!(verdict.reason.as_deref(), Some("bad"));
+
+        let failing = CommandJudge::new("sh", vec!["-c".to_string(), "exit 1".to_string()]);
+        assert!(failing.judge(&request("good")).await.is_err());
+    }
+
+    #[test]
+    fn test_render_junit() {
+        let suite = SuiteResult {
+            skill_id: "greeter".to_string(),
+            timestamp: Utc::now(),
+            cases: vec![
+                CaseResult {
+                    name: "ok".to_string(),
+                    source: "cases.toml".to_string(),
+                    outcome: CaseOutcome::Passed,
+                    duration: Duration::from_millis(5),
+                    stdout: String::new(),
+                },
+                CaseResult {
+                    name: "a<b".to_string(),
+                    source: "cases.toml".to_string(),
+                    outcome: CaseOutcome::Failed {
+                        message: "output differs from expected \"x\"".to_string(),
+                        details: "y & z".to_string(),
+                    },
+                    duration: Duration::from_millis(10),
+                    stdout: "y & z".to_string(),
+                },
+                CaseResult {
+                    name: "later".to_string(),
+                    source: "cases.csv".to_string(),
+                    outcome: CaseOutcome::Skipped {
+                        reason: "needs a judge".to_string(),
+                    },
+                    duration: Duration::ZERO,
+                    stdout: String::new(),
+                },
+            ],
+        };
+        let xml = render_junit(&[suite]);
+        assert!(xml.starts_with("<?xml"));
+        assert!(xml.contains("tests=\"3\" failures=\"1\" errors=\"0\" skipped=\"1\""));
+        assert!(
+            xml.contains("<testcase name=\"ok\" classname=\"greeter.cases.toml\" time=\"0.005\"/>")
+        );
+        assert!(xml.contains("name=\"a&lt;b\""));
+        assert!(xml.contains(
+            "<failure message=\"output differs from expected &quot;x&quot;\">y &amp; z</failure>"
+        ));
+        assert!(xml.contains("<skipped message=\"needs a judge\"/>"));
+        assert!(xml.contains("<system-out>y &amp; z</system-out>"));
+        assert!(xml.trim_end().ends_with("</testsuites>"));
+    }
+}
@@ -4,6 +4,8 @@ use std::path::{Path, PathBuf};
 
 use super::model::SkillInfo;
 use super::SourcesError;
+use crate::core::metadata::parse_yaml_frontmatter;
+use crate::core::requirements::declared_requirements;
 
 /// Scan a local directory for skills
 pub(super) async fn scan_local_source(
@@ -72,6 +74,11 @@ pub(super) fn extract_skill_info_from_path(
 
     // Extract frontmatter (simple YAML frontmatter parser)
     let (id, name, description, version) = parse_skill_frontmatter(&content, skill_path)?;
+    // Malformed requirements surface when the skill is installed
+    let requires = parse_yaml_frontmatter(&content)
+        .ok()
+        .and_then(|frontmatter| declared_requirements(skill_path, &frontmatter).ok())
+        .filter(|requires| !requires.is_empty());
 
     Ok(SkillInfo {
         id,
@@ -79,6 +86,8 @@ pub(super) fn extract_skill_info_from_path(
         description,
         version: Some(version),
         source_name: source_name.to_string(),
+        yanked: false,
+        requires,
     })
 }
 
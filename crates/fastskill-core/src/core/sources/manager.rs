@@ -254,6 +254,8 @@ impl SourcesManager {
                         .unwrap_or_else(|| "1.0.0".to_string()),
                     author: owner_name.clone(),
                     download_url,
+                    yanked: false,
+                    requires: None,
                 });
             }
         }
@@ -458,6 +460,8 @@ impl SourcesManager {
                 description: skill.description.clone(),
                 version: Some(skill.version.clone()),
                 source_name: source_name.to_string(),
+                yanked: skill.yanked,
+                requires: skill.requires.clone(),
             })
             .collect())
     }
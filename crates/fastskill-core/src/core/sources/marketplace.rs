@@ -1,17 +1,18 @@
 //! Marketplace JSON structures and caching.
 
+use crate::core::requirements::Requirements;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Marketplace.json structure
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct MarketplaceJson {
     pub version: String,
     pub skills: Vec<MarketplaceSkill>,
 }
 
 /// Skill entry in marketplace.json
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct MarketplaceSkill {
     pub id: String,
     pub name: String,
@@ -21,6 +22,16 @@ pub struct MarketplaceSkill {
     pub author: Option<String>,
     #[serde(default)]
     pub download_url: Option<String>,
+    /// Withdrawn by the publisher; resolution skips it unless skills.lock pins it
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub yanked: bool,
+    /// Engine and runtime versions this version needs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requires: Option<Requirements>,
+}
+
+fn is_false(b: &bool) -> bool {
+    !*b
 }
 
 /// Claude Code marketplace.json format structures
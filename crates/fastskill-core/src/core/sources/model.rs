@@ -111,6 +111,10 @@ pub struct SkillInfo {
     pub description: String,
     pub version: Option<String>,
     pub source_name: String,
+    /// The version has been yanked by its publisher
+    pub yanked: bool,
+    /// Engine and runtime versions the skill needs, when the source lists them
+    pub requires: Option<crate::core::requirements::Requirements>,
 }
 
 #[cfg(test)]
@@ -0,0 +1,367 @@
+//! Multi-tenant serving: one isolated skill set per tenant in one process
+//!
+//! With `[tool.fastskill.server.tenancy]` enabled, every `/api/v1` request is
+//! served by the tenant's own [`FastSkillService`], built on first use from the
+//! server's configuration with the storage paths moved into the tenant's
+//! directory (`<skills dir>/.fastskill/tenants/<tenant>` by default). Skills,
+//! vector index, registry index, API keys, audit log and change journal are
+//! therefore never shared between tenants. The tenant comes from a header or,
+//! when a JWT secret is configured, from a claim of a signed token (see
+//! [`crate::http::tenancy`]).
+
+use crate::core::service::{FastSkillService, ServiceConfig, ServiceError, StorageConfig};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Header naming the tenant when tenants do not come from a JWT
+pub const DEFAULT_TENANT_HEADER: &str = "X-FastSkill-Tenant";
+
+/// Tenancy settings (`[tool.fastskill.server.tenancy]`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TenancyConfig {
+    /// Serve one isolated skill set per tenant
+    pub enabled: bool,
+    /// Header naming the tenant; ignored when `jwt` is set
+    pub header: String,
+    /// Take the tenant from a claim of a signed JWT instead of the header
+    pub jwt: Option<TenantJwtConfig>,
+    /// Tenants created on first request. Other tenants are served only once
+    /// their directory exists.
+    pub tenants: Vec<String>,
+    /// Directory holding one directory per tenant; defaults to
+    /// `<skills dir>/.fastskill/tenants`
+    pub root: Option<PathBuf>,
+}
+
+impl Default for TenancyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            header: DEFAULT_TENANT_HEADER.to_string(),
+            jwt: None,
+            tenants: Vec::new(),
+            root: None,
+        }
+    }
+}
+
+/// HS256-signed bearer tokens carrying the tenant id
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TenantJwtConfig {
+    /// Environment variable holding the signing secret
+    pub secret_env: String,
+    /// Claim holding the tenant id
+    pub claim: String,
+    /// The secret, resolved from `secret_env` when the configuration is loaded
+    #[serde(skip)]
+    pub secret: Option<String>,
+}
+
+impl Default for TenantJwtConfig {
+    fn default() -> Self {
+        Self {
+            secret_env: "FASTSKILL_TENANT_JWT_SECRET".to_string(),
+            claim: "tenant".to_string(),
+            secret: None,
+        }
+    }
+}
+
+impl fmt::Debug for TenantJwtConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TenantJwtConfig")
+            .field("secret_env", &self.secret_env)
+            .field("claim", &self.claim)
+            .field("secret", &self.secret.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+impl TenancyConfig {
+    /// Check the settings a server cannot start without
+    pub fn validate(&self) -> Result<(), ServiceError> {
+        if self.header.trim().is_empty() {
+            return Err(ServiceError::Config(
+                "tenancy header must not be empty".to_string(),
+            ));
+        }
+        if let Some(jwt) = &self.jwt {
+            if jwt.claim.trim().is_empty() {
+                return Err(ServiceError::Config(
+                    "tenancy jwt claim must not be empty".to_string(),
+                ));
+            }
+        }
+        for tenant in &self.tenants {
+            TenantId::parse(tenant)?;
+        }
+        Ok(())
+    }
+
+    /// Directory holding every tenant's directory
+    pub fn tenants_root(&self, base: &ServiceConfig) -> PathBuf {
+        self.root
+            .clone()
+            .unwrap_or_else(|| base.skill_storage_path.join(".fastskill").join("tenants"))
+    }
+
+    pub fn tenant_dir(&self, base: &ServiceConfig, tenant: &TenantId) -> PathBuf {
+        self.tenants_root(base).join(tenant.as_str())
+    }
+
+    /// Whether `tenant` may be served: listed in `tenants` or already provisioned
+    pub fn is_known(&self, base: &ServiceConfig, tenant: &TenantId) -> bool {
+        self.tenants.iter().any(|t| t == tenant.as_str()) || self.tenant_dir(base, tenant).is_dir()
+    }
+
+    /// `base` with every storage location moved into the tenant's directory.
+    ///
+    /// Filesystem skills, the vector index and the registry index live under
+    /// the tenant directory; S3 keys get a `tenants/<tenant>` prefix. Postgres
+    /// storage is shared by design and cannot be split per tenant.
+    pub fn tenant_config(
+        &self,
+        base: &ServiceConfig,
+        tenant: &TenantId,
+    ) -> Result<ServiceConfig, ServiceError> {
+        let dir = self.tenant_dir(base, tenant);
+        let mut config = base.clone();
+        config.storage = match &base.storage {
+            StorageConfig::Filesystem => StorageConfig::Filesystem,
+            StorageConfig::S3(s3) => {
+                let mut s3 = s3.clone();
+                let prefix = s3.prefix.trim_matches('/');
+                s3.prefix = if prefix.is_empty() {
+                    format!("tenants/{}", tenant)
+                } else {
+                    format!("{}/tenants/{}", prefix, tenant)
+                };
+                StorageConfig::S3(s3)
+            }
+            StorageConfig::Postgres(_) => {
+                return Err(ServiceError::Config(
+                    "tenancy is not supported with the postgres storage backend".to_string(),
+                ))
+            }
+        };
+        if let Some(embedding) = config.embedding.as_mut() {
+            embedding.index_path = None;
+        }
+        config.registry_index_path = base
+            .registry_index_path
+            .as_ref()
+            .map(|_| dir.join(".fastskill").join("registry-index"));
+        config.skill_storage_path = dir;
+        config.tenancy = TenancyConfig::default();
+        Ok(config)
+    }
+}
+
+/// A tenant id: 1–64 ASCII letters, digits, `-` or `_`, starting with a
+/// letter or digit, so it is always a single safe path component.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TenantId(String);
+
+impl TenantId {
+    pub fn parse(id: &str) -> Result<Self, ServiceError> {
+        let id = id.trim();
+        let valid = (1..=64).contains(&id.len())
+            && id.starts_with(|c: char| c.is_ascii_alphanumeric())
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(ServiceError::Validation(format!(
+                "Invalid tenant id '{}' (1-64 letters, digits, '-' or '_')",
+                id
+            )));
+        }
+        Ok(Self(id.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for TenantId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Applied to each tenant service before it is initialized, e.g. to attach the
+/// embedding provider the CLI edge injects into the main service
+pub type TenantSetup =
+    Arc<dyn Fn(FastSkillService) -> Result<FastSkillService, ServiceError> + Send + Sync>;
+
+/// The tenant services of one server, built on first use and kept for the
+/// life of the process
+pub struct TenantServices {
+    base: ServiceConfig,
+    setup: Option<TenantSetup>,
+    services: tokio::sync::Mutex<HashMap<TenantId, Arc<FastSkillService>>>,
+}
+
+impl TenantServices {
+    pub fn new(base: ServiceConfig) -> Self {
+        Self {
+            base,
+            setup: None,
+            services: tokio::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_setup(mut self, setup: TenantSetup) -> Self {
+        self.setup = Some(setup);
+        self
+    }
+
+    pub fn tenancy(&self) -> &TenancyConfig {
+        &self.base.tenancy
+    }
+
+    pub fn is_known(&self, tenant: &TenantId) -> bool {
+        self.base.tenancy.is_known(&self.base, tenant)
+    }
+
+    /// The initialized service of `tenant`, creating its directory when the
+    /// tenant is listed in the configuration.
+    pub async fn get(&self, tenant: &TenantId) -> Result<Arc<FastSkillService>, ServiceError> {
+        let mut services = self.services.lock().await;
+        if let Some(service) = services.get(tenant) {
+            return Ok(Arc::clone(service));
+        }
+        if !self.is_known(tenant) {
+            return Err(ServiceError::Validation(format!(
+                "Unknown tenant '{}'",
+                tenant
+            )));
+        }
+
+        let config = self.base.tenancy.tenant_config(&self.base, tenant)?;
+        let dir = config.skill_storage_path.clone();
+        std::fs::create_dir_all(&dir)?;
+        let mut service = FastSkillService::new(config).await?;
+        if let Some(setup) = &self.setup {
+            service = setup(service)?;
+        }
+        let mut service = service.with_project_root(dir);
+        service.initialize().await?;
+        tracing::info!("Serving tenant '{}'", tenant);
+
+        let service = Arc::new(service);
+        services.insert(tenant.clone(), Arc::clone(&service));
+        Ok(service)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::service::S3StorageConfig;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_tenant_id() {
+        assert_eq!(TenantId::parse(" acme-1 ").unwrap().as_str(), "acme-1");
+        for bad in ["", "../acme", ".hidden", "a/b", "-x", &"a".repeat(65)] {
+            assert!(TenantId::parse(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_tenant_config_moves_storage() {
+        let base = ServiceConfig {
+            skill_storage_path: PathBuf::from("/srv/skills"),
+            registry_index_path: Some(PathBuf::from("/srv/index")),
+            storage: StorageConfig::S3(S3StorageConfig {
+                bucket: "skills".to_string(),
+                prefix: "prod/".to_string(),
+                region: None,
+                endpoint: None,
+                force_path_style: false,
+            }),
+            tenancy: TenancyConfig {
+                enabled: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let tenant = TenantId::parse("acme").unwrap();
+        let config = base.tenancy.tenant_config(&base, &tenant).unwrap();
+        let dir = PathBuf::from("/srv/skills/.fastskill/tenants/acme");
+        assert_eq!(config.skill_storage_path, dir);
+        assert_eq!(
+            config.registry_index_path,
+            Some(dir.join(".fastskill").join("registry-index"))
+        );
+        assert!(!config.tenancy.enabled);
+        assert!(
+            matches!(&config.storage, StorageConfig::S3(s3) if s3.prefix == "prod/tenants/acme"),
+            "{:?}",
+            config.storage
+        );
+
+        let postgres = ServiceConfig {
+            storage: StorageConfig::Postgres(Default::default()),
+            ..base
+        };
+        assert!(postgres.tenancy.tenant_config(&postgres, &tenant).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_tenant_services_are_isolated() {
+        let dir = TempDir::new().unwrap();
+        let base = ServiceConfig {
+            skill_storage_path: dir.path().to_path_buf(),
+            tenancy: TenancyConfig {
+                enabled: true,
+                tenants: vec!["acme".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let acme = TenantId::parse("acme").unwrap();
+        let globex = TenantId::parse("globex").unwrap();
+        let skill_dir = base.tenancy.tenant_dir(&base, &acme).join("pdf");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        std::fs::write(
+            skill_dir.join("SKILL.md"),
+            "---\nname: pdf\ndescription: PDF tools\n---\n# PDF\n",
+        )
+        .unwrap();
+
+        let tenants = TenantServices::new(base.clone());
+        assert!(tenants.get(&globex).await.is_err());
+        std::fs::create_dir_all(base.tenancy.tenant_dir(&base, &globex)).unwrap();
+
+        let acme_service = tenants.get(&acme).await.unwrap();
+        let globex_service = tenants.get(&globex).await.unwrap();
+        assert_eq!(
+            acme_service
+                .skill_manager()
+                .list_skills()
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(globex_service
+            .skill_manager()
+            .list_skills()
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(Arc::ptr_eq(
+            &acme_service,
+            &tenants.get(&acme).await.unwrap()
+        ));
+    }
+}
@@ -0,0 +1,187 @@
+//! Tools that skills declare for agent frameworks
+//!
+//! A skill lists the tools it provides under `tools` in its SKILL.md
+//! frontmatter, each with a JSON Schema for its arguments:
+//!
+//! ```yaml
+//! capabilities: [documents]
+//! tools:
+//!   - name: create_presentation
+//!     description: Create a presentation from an outline
+//!     capabilities: [presentations]
+//!     input_schema:
+//!       type: object
+//!       properties:
+//!         title: { type: string }
+//!       required: [title]
+//! ```
+//!
+//! A tool's capabilities are its own plus the skill-level `capabilities`.
+
+use crate::core::metadata::SkillFrontmatter;
+use crate::core::service::ServiceError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Longest tool name agent frameworks accept for function calling
+const MAX_TOOL_NAME_LEN: usize = 64;
+
+/// A tool declared in a skill's frontmatter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AvailableTool {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    /// JSON Schema of the tool's arguments
+    #[serde(default = "empty_object_schema")]
+    pub input_schema: serde_json::Value,
+    /// JSON Schema of the tool's result, when the skill declares one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_schema: Option<serde_json::Value>,
+}
+
+fn empty_object_schema() -> serde_json::Value {
+    serde_json::json!({ "type": "object", "properties": {} })
+}
+
+impl AvailableTool {
+    /// Whether the tool lists `capability`, ignoring case
+    pub fn has_capability(&self, capability: &str) -> bool {
+        self.capabilities
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(capability))
+    }
+
+    fn validate(&self) -> Result<(), ServiceError> {
+        let valid_name = !self.name.is_empty()
+            && self.name.len() <= MAX_TOOL_NAME_LEN
+            && self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !valid_name {
+            return Err(ServiceError::Validation(format!(
+                "Invalid tool name '{}': use 1-{} letters, digits, '_' or '-'",
+                self.name, MAX_TOOL_NAME_LEN
+            )));
+        }
+        for (field, schema) in [
+            ("input_schema", Some(&self.input_schema)),
+            ("output_schema", self.output_schema.as_ref()),
+        ] {
+            let Some(schema) = schema else { continue };
+            if !schema.is_object() {
+                return Err(ServiceError::Validation(format!(
+                    "Tool '{}': {} must be a JSON Schema object",
+                    self.name, field
+                )));
+            }
+        }
+        if let Some(kind) = self.input_schema.get("type") {
+            if kind != "object" {
+                return Err(ServiceError::Validation(format!(
+                    "Tool '{}': input_schema must have type object, not {}",
+                    self.name, kind
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Tools declared in `frontmatter`, with the skill-level capabilities merged
+/// into each. A skill without a `tools` key declares none.
+pub fn declared_tools(frontmatter: &SkillFrontmatter) -> Result<Vec<AvailableTool>, ServiceError> {
+    let Some(raw) = frontmatter.extra.get("tools") else {
+        return Ok(Vec::new());
+    };
+    let mut tools: Vec<AvailableTool> = serde_yaml::from_value(raw.clone())
+        .map_err(|e| ServiceError::Validation(format!("Invalid tools in SKILL.md: {}", e)))?;
+    let skill_capabilities: Vec<String> = match frontmatter.extra.get("capabilities") {
+        Some(raw) => serde_yaml::from_value(raw.clone()).map_err(|e| {
+            ServiceError::Validation(format!("Invalid capabilities in SKILL.md: {}", e))
+        })?,
+        None => Vec::new(),
+    };
+
+    let mut names = HashSet::new();
+    for tool in &mut tools {
+        tool.validate()?;
+        if !names.insert(tool.name.clone()) {
+            return Err(ServiceError::Validation(format!(
+                "Tool '{}' is declared more than once",
+                tool.name
+            )));
+        }
+        for capability in &skill_capabilities {
+            if !tool.has_capability(capability) {
+                tool.capabilities.push(capability.clone());
+            }
+        }
+    }
+    Ok(tools)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::metadata::parse_yaml_frontmatter;
+
+    fn tools(yaml: &str) -> Result<Vec<AvailableTool>, ServiceError> {
+        let content = format!("---\nname: deck\ndescription: Decks\n{yaml}---\n# Deck\n");
+        declared_tools(&parse_yaml_frontmatter(&content).unwrap())
+    }
+
+    #[test]
+    fn test_declared_tools_merges_skill_capabilities() {
+        let tools = tools(
+            "capabilities: [documents]\ntools:\n  - name: create_presentation\n    description: Create a deck\n    capabilities: [presentations, Documents]\n    input_schema:\n      type: object\n      properties:\n        title: { type: string }\n      required: [title]\n  - name: list_templates\n",
+        )
+        .unwrap();
+        assert_eq!(tools.len(), 2);
+        assert_eq!(tools[0].capabilities, ["presentations", "Documents"]);
+        assert_eq!(tools[0].input_schema["required"][0], "title");
+        assert!(tools[1].has_capability("DOCUMENTS"));
+        assert_eq!(tools[1].input_schema["type"], "object");
+    }
+
+    #[test]
+    fn test_skill_without_tools_declares_none() {
+        assert!(tools("capabilities: [documents]\n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_declared_tools_rejects_invalid_declarations() {
+        let bad_name = tools("tools:\n  - name: \"create deck\"\n").unwrap_err();
+        assert!(
+            bad_name.to_string().contains("Invalid tool name"),
+            "{}",
+            bad_name
+        );
+
+        let bad_schema = tools("tools:\n  - name: deck\n    input_schema: [1, 2]\n").unwrap_err();
+        assert!(
+            bad_schema.to_string().contains("JSON Schema object"),
+            "{}",
+            bad_schema
+        );
+
+        let not_object =
+            tools("tools:\n  - name: deck\n    input_schema: { type: string }\n").unwrap_err();
+        assert!(
+            not_object.to_string().contains("type object"),
+            "{}",
+            not_object
+        );
+
+        let duplicate = tools("tools:\n  - name: deck\n  - name: deck\n").unwrap_err();
+        assert!(
+            duplicate.to_string().contains("more than once"),
+            "{}",
+            duplicate
+        );
+    }
+}
@@ -1,9 +1,12 @@
 //! Vector index service for storing and searching skill embeddings
 
-use crate::core::service::ServiceError;
+use crate::core::hnsw::HnswGraph;
+use crate::core::service::{AnnConfig, ServiceError};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 /// A skill stored in the vector index
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,12 +19,109 @@ pub struct IndexedSkill {
     pub frontmatter_json: serde_json::Value,
     /// Vector embedding
     pub embedding: Vec<f32>,
-    /// SHA256 hash of the SKILL.md file
+    /// Embeddings of the skill's section chunks (see [`chunking`](crate::core::chunking));
+    /// empty unless chunked indexing is configured
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub chunk_embeddings: Vec<Vec<f32>>,
+    /// SHA256 hash of the skill directory ([`calculate_skill_hash`](crate::core::change_detection::calculate_skill_hash))
     pub file_hash: String,
     /// Last updated timestamp
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+impl IndexedSkill {
+    /// Similarity of the skill to `query`: the best match among its own
+    /// embedding and its chunk embeddings (max-sim).
+    pub fn similarity(&self, query: &[f32]) -> f32 {
+        self.chunk_embeddings
+            .iter()
+            .map(|chunk| VectorIndexServiceImpl::cosine_similarity(query, chunk))
+            .fold(
+                VectorIndexServiceImpl::cosine_similarity(query, &self.embedding),
+                f32::max,
+            )
+    }
+}
+
+/// Version of the index layout, recorded in [`VectorIndexMetadata::version`]. An
+/// index with another version has to be rebuilt with `reindex --migrate`.
+pub const INDEX_FORMAT_VERSION: u32 = 1;
+
+/// What the embeddings in an index were made with
+///
+/// Recorded with the first embedding written to an index. Embeddings from
+/// another model, or of another length, are not comparable with the stored
+/// ones, so such an index is refused until it is migrated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VectorIndexMetadata {
+    /// Embedding model name; `None` when the index was written without a
+    /// configured model
+    pub model: Option<String>,
+    /// Length of every embedding in the index
+    pub dimensions: usize,
+    /// [`INDEX_FORMAT_VERSION`] the index was written with
+    pub version: u32,
+}
+
+impl VectorIndexMetadata {
+    /// Why the index cannot be used with `model`, if it cannot
+    pub fn mismatch(&self, model: Option<&str>) -> Option<IndexMismatch> {
+        if self.version != INDEX_FORMAT_VERSION {
+            return Some(IndexMismatch::Version {
+                indexed: self.version,
+                supported: INDEX_FORMAT_VERSION,
+            });
+        }
+        match (self.model.as_deref(), model) {
+            (Some(indexed), Some(configured)) if indexed != configured => {
+                Some(IndexMismatch::Model {
+                    indexed: indexed.to_string(),
+                    configured: configured.to_string(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// [`IndexMismatch::Dimensions`] unless `dimensions` is the index's
+    fn check_dimensions(&self, dimensions: usize) -> Result<(), IndexMismatch> {
+        if dimensions == self.dimensions {
+            Ok(())
+        } else {
+            Err(IndexMismatch::Dimensions {
+                indexed: self.dimensions,
+                got: dimensions,
+            })
+        }
+    }
+}
+
+/// Why an index's embeddings cannot be compared with new ones
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum IndexMismatch {
+    #[error(
+        "The vector index was built with embedding model '{indexed}' but '{configured}' is \
+         configured; run `fastskill reindex --migrate` to re-embed every skill"
+    )]
+    Model { indexed: String, configured: String },
+    #[error(
+        "The vector index holds {indexed}-dimension embeddings, not {got}; run \
+         `fastskill reindex --migrate` to re-embed every skill"
+    )]
+    Dimensions { indexed: usize, got: usize },
+    #[error(
+        "The vector index has format version {indexed} but this fastskill supports \
+         {supported}; run `fastskill reindex --migrate` to rebuild it"
+    )]
+    Version { indexed: u32, supported: u32 },
+}
+
+impl From<IndexMismatch> for ServiceError {
+    fn from(mismatch: IndexMismatch) -> Self {
+        ServiceError::Config(mismatch.to_string())
+    }
+}
+
 /// Search result with similarity score
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SkillMatch {
@@ -44,6 +144,19 @@ pub trait VectorIndexService: Send + Sync {
         file_hash: &str,
     ) -> Result<(), ServiceError>;
 
+    /// Add or update a skill together with its chunk embeddings. Backends that
+    /// store one vector per skill keep only [`IndexedSkill::embedding`].
+    async fn add_or_update_indexed_skill(&self, skill: IndexedSkill) -> Result<(), ServiceError> {
+        self.add_or_update_skill(
+            &skill.id,
+            skill.skill_path,
+            skill.frontmatter_json,
+            skill.embedding,
+            &skill.file_hash,
+        )
+        .await
+    }
+
     /// Search for similar skills using vector similarity
     async fn search_similar(
         &self,
@@ -58,19 +171,362 @@ pub trait VectorIndexService: Send + Sync {
     async fn remove_skill(&self, skill_id: &str) -> Result<(), ServiceError>;
 
     /// Get all skills in the index
+    ///
+    /// Holds every embedding in memory at once; for large collections prefer
+    /// [`batches`] over [`get_skills_page`](Self::get_skills_page).
     async fn get_all_skills(&self) -> Result<Vec<IndexedSkill>, ServiceError>;
+
+    /// Number of skills in the index
+    async fn count_skills(&self) -> Result<usize, ServiceError> {
+        Ok(self.get_all_skills().await?.len())
+    }
+
+    /// Up to `limit` skills with ids greater than `after`, ordered by id
+    async fn get_skills_page(
+        &self,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<IndexedSkill>, ServiceError> {
+        let mut skills = self.get_all_skills().await?;
+        skills.retain(|skill| after.is_none_or(|after| skill.id.as_str() > after));
+        skills.sort_by(|a, b| a.id.cmp(&b.id));
+        skills.truncate(limit);
+        Ok(skills)
+    }
+
+    /// Bring any search acceleration structure up to date with the index
+    /// (called after a reindex so the next search does not pay for it)
+    async fn refresh_search_index(&self) -> Result<(), ServiceError> {
+        Ok(())
+    }
+
+    /// Model, dimensions and format version of the stored embeddings; `None`
+    /// for an empty index or a backend that does not record them
+    async fn metadata(&self) -> Result<Option<VectorIndexMetadata>, ServiceError> {
+        Ok(None)
+    }
+
+    /// Why the stored embeddings cannot be used with the configured model,
+    /// if they cannot
+    async fn mismatch(&self) -> Result<Option<IndexMismatch>, ServiceError> {
+        Ok(None)
+    }
+
+    /// An empty index to rebuild this one in (`reindex --migrate`). This
+    /// index is left alone until [`commit_migration`](Self::commit_migration)
+    /// replaces it with the new one.
+    async fn begin_migration(&self) -> Result<Arc<dyn VectorIndexService>, ServiceError> {
+        Err(ServiceError::Config(
+            "This vector index backend does not support migration".to_string(),
+        ))
+    }
+
+    /// Replace this index with the one from [`begin_migration`](Self::begin_migration)
+    async fn commit_migration(&self) -> Result<(), ServiceError> {
+        Err(ServiceError::Config(
+            "This vector index backend does not support migration".to_string(),
+        ))
+    }
+
+    /// Drop the index from [`begin_migration`](Self::begin_migration), keeping this one
+    async fn abort_migration(&self) -> Result<(), ServiceError> {
+        Ok(())
+    }
+}
+
+/// Collections larger than this are streamed in batches rather than loaded
+/// whole by commands that can work incrementally.
+pub const LARGE_COLLECTION_THRESHOLD: usize = 2_000;
+
+/// Skills fetched per batch when streaming the index.
+pub const DEFAULT_BATCH_SIZE: usize = 500;
+
+/// Batched iteration over the index in id order, holding one batch at a time.
+pub struct IndexedSkillBatches<'a> {
+    index: &'a dyn VectorIndexService,
+    after: Option<String>,
+    batch_size: usize,
+    done: bool,
+}
+
+impl IndexedSkillBatches<'_> {
+    /// Next batch of at most `batch_size` skills, or `None` once exhausted
+    pub async fn next_batch(&mut self) -> Result<Option<Vec<IndexedSkill>>, ServiceError> {
+        if self.done {
+            return Ok(None);
+        }
+        let batch = self
+            .index
+            .get_skills_page(self.after.as_deref(), self.batch_size)
+            .await?;
+        self.done = batch.len() < self.batch_size;
+        match batch.last() {
+            Some(last) => {
+                self.after = Some(last.id.clone());
+                Ok(Some(batch))
+            }
+            None => {
+                self.done = true;
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Iterate over `index` in batches of `batch_size`, starting after the skill
+/// id `after` (from the beginning when `None`).
+pub fn batches<'a>(
+    index: &'a dyn VectorIndexService,
+    after: Option<&str>,
+    batch_size: usize,
+) -> IndexedSkillBatches<'a> {
+    IndexedSkillBatches {
+        index,
+        after: after.map(str::to_string),
+        batch_size: batch_size.max(1),
+        done: false,
+    }
+}
+
+/// How long a connection waits on a lock held by another writer before failing.
+const BUSY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Open the index database with crash-safe settings.
+///
+/// SQLite's rollback journal records each write transaction; if the process is
+/// killed mid-write, the next open finds the hot journal and rolls the partial
+/// write back. `synchronous = FULL` makes that guarantee hold across power loss
+/// regardless of how SQLite was compiled, and the busy timeout retries writes
+/// that collide with a concurrent reindex instead of failing them.
+fn open_connection(db_path: &std::path::Path) -> Result<rusqlite::Connection, ServiceError> {
+    let conn = rusqlite::Connection::open(db_path)
+        .map_err(|e| ServiceError::Custom(format!("Failed to open database: {}", e)))?;
+    conn.busy_timeout(BUSY_TIMEOUT)
+        .map_err(|e| ServiceError::Custom(format!("Failed to configure database: {}", e)))?;
+    conn.execute_batch("PRAGMA synchronous = FULL;")
+        .map_err(|e| ServiceError::Custom(format!("Failed to configure database: {}", e)))?;
+    Ok(conn)
+}
+
+const SELECT_SKILL: &str = "SELECT id, skill_path, frontmatter_json, embedding_json, file_hash, \
+     updated_at, chunk_embeddings_json FROM skills";
+
+const INSERT_SKILL: &str = "INSERT OR REPLACE INTO skills (id, skill_path, frontmatter_json, \
+     embedding_json, file_hash, updated_at, chunk_embeddings_json) VALUES (?, ?, ?, ?, ?, ?, ?)";
+
+/// Column values of a skill, in [`INSERT_SKILL`] order.
+struct SkillRow {
+    id: String,
+    skill_path: String,
+    frontmatter: String,
+    embedding: String,
+    file_hash: String,
+    updated_at: String,
+    chunk_embeddings: String,
+}
+
+impl SkillRow {
+    fn new(skill: &IndexedSkill) -> Result<Self, ServiceError> {
+        fn json<T: Serialize + ?Sized>(value: &T, what: &str) -> Result<String, ServiceError> {
+            serde_json::to_string(value)
+                .map_err(|e| ServiceError::Custom(format!("Failed to serialize {}: {}", what, e)))
+        }
+        Ok(Self {
+            id: skill.id.clone(),
+            skill_path: skill.skill_path.to_string_lossy().to_string(),
+            frontmatter: json(&skill.frontmatter_json, "frontmatter")?,
+            embedding: json(&skill.embedding, "embedding")?,
+            file_hash: skill.file_hash.clone(),
+            updated_at: skill.updated_at.to_rfc3339(),
+            chunk_embeddings: json(&skill.chunk_embeddings, "chunk embeddings")?,
+        })
+    }
+
+    fn insert(&self, stmt: &mut rusqlite::Statement<'_>) -> Result<(), ServiceError> {
+        stmt.execute(rusqlite::params![
+            self.id,
+            self.skill_path,
+            self.frontmatter,
+            self.embedding,
+            self.file_hash,
+            self.updated_at,
+            self.chunk_embeddings
+        ])
+        .map_err(|e| ServiceError::Custom(format!("Failed to insert skill: {}", e)))?;
+        Ok(())
+    }
+}
+
+/// Length shared by a skill's embedding and its chunk embeddings
+fn skill_dimensions(skill: &IndexedSkill) -> Result<usize, ServiceError> {
+    let dimensions = skill.embedding.len();
+    if let Some(chunk) = skill
+        .chunk_embeddings
+        .iter()
+        .find(|chunk| chunk.len() != dimensions)
+    {
+        return Err(ServiceError::Validation(format!(
+            "Skill {} has a {}-dimension embedding and a {}-dimension chunk embedding",
+            skill.id,
+            dimensions,
+            chunk.len()
+        )));
+    }
+    Ok(dimensions)
+}
+
+fn read_metadata(conn: &rusqlite::Connection) -> Result<Option<VectorIndexMetadata>, ServiceError> {
+    let row = conn.query_row(
+        "SELECT model, dimensions, version FROM index_metadata WHERE id = 1",
+        [],
+        |row| {
+            Ok(VectorIndexMetadata {
+                model: row.get(0)?,
+                dimensions: usize::try_from(row.get::<_, i64>(1)?).unwrap_or(0),
+                version: u32::try_from(row.get::<_, i64>(2)?).unwrap_or(0),
+            })
+        },
+    );
+    match row {
+        Ok(metadata) => Ok(Some(metadata)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(ServiceError::Custom(format!(
+            "Failed to read index metadata: {}",
+            e
+        ))),
+    }
+}
+
+/// Metadata of the index, checked against a write of `dimensions`-long
+/// embeddings from `model`. The first write records it; an index written
+/// before metadata was recorded takes the length of its stored embeddings.
+fn checked_metadata(
+    conn: &rusqlite::Connection,
+    model: Option<&str>,
+    dimensions: usize,
+) -> Result<VectorIndexMetadata, ServiceError> {
+    let metadata = match read_metadata(conn)? {
+        Some(metadata) => metadata,
+        None => {
+            let stored: Option<usize> = conn
+                .query_row("SELECT embedding_json FROM skills LIMIT 1", [], |row| {
+                    row.get::<_, String>(0)
+                })
+                .ok()
+                .and_then(|json| serde_json::from_str::<Vec<f32>>(&json).ok())
+                .map(|embedding| embedding.len());
+            let metadata = VectorIndexMetadata {
+                model: model.map(str::to_string),
+                dimensions: stored.unwrap_or(dimensions),
+                version: INDEX_FORMAT_VERSION,
+            };
+            conn.execute(
+                "INSERT OR REPLACE INTO index_metadata (id, model, dimensions, version) \
+                 VALUES (1, ?, ?, ?)",
+                rusqlite::params![
+                    metadata.model,
+                    metadata.dimensions as i64,
+                    metadata.version as i64
+                ],
+            )
+            .map_err(|e| ServiceError::Custom(format!("Failed to write index metadata: {}", e)))?;
+            metadata
+        }
+    };
+    if let Some(mismatch) = metadata.mismatch(model) {
+        return Err(mismatch.into());
+    }
+    metadata.check_dimensions(dimensions)?;
+    Ok(metadata)
+}
+
+/// Read a row selected with [`SELECT_SKILL`].
+fn read_indexed_skill(row: &rusqlite::Row<'_>) -> rusqlite::Result<IndexedSkill> {
+    let conversion = |e: Box<dyn std::error::Error + Send + Sync>| {
+        rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, e)
+    };
+    let id: String = row.get(0)?;
+    let skill_path: String = row.get(1)?;
+    let frontmatter_str: String = row.get(2)?;
+    let embedding_str: String = row.get(3)?;
+    let file_hash: String = row.get(4)?;
+    let updated_at_str: String = row.get(5)?;
+    let chunks_str: String = row.get(6)?;
+
+    let frontmatter_json: serde_json::Value =
+        serde_json::from_str(&frontmatter_str).map_err(|e| conversion(Box::new(e)))?;
+    let embedding: Vec<f32> =
+        serde_json::from_str(&embedding_str).map_err(|e| conversion(Box::new(e)))?;
+    let updated_at = chrono::DateTime::parse_from_rfc3339(&updated_at_str)
+        .map_err(|e| conversion(Box::new(e)))?
+        .with_timezone(&chrono::Utc);
+    let chunk_embeddings: Vec<Vec<f32>> =
+        serde_json::from_str(&chunks_str).map_err(|e| conversion(Box::new(e)))?;
+
+    Ok(IndexedSkill {
+        id,
+        skill_path: PathBuf::from(skill_path),
+        frontmatter_json,
+        embedding,
+        chunk_embeddings,
+        file_hash,
+        updated_at,
+    })
 }
 
 /// SQLite-based vector index service implementation
+///
+/// Collections of at least [`AnnConfig::min_skills`] are searched through an
+/// HNSW graph persisted next to the database (`index.hnsw` beside
+/// `index.db`); smaller ones are scanned exactly. The model, dimensions and
+/// format version of the embeddings are kept in the `index_metadata` table
+/// ([`VectorIndexMetadata`]).
 pub struct VectorIndexServiceImpl {
     /// Path to the SQLite database file
     db_path: PathBuf,
+    /// Embedding model new embeddings come from
+    model: Option<String>,
+    /// Approximate search settings
+    ann: AnnConfig,
+    /// Graph loaded by the first approximate search, kept for later ones
+    ann_graph: Arc<Mutex<Option<HnswGraph>>>,
 }
 
 impl VectorIndexServiceImpl {
     /// Create a new vector index service
     pub fn new(db_path: PathBuf) -> Self {
-        Self { db_path }
+        Self {
+            db_path,
+            model: None,
+            ann: AnnConfig::default(),
+            ann_graph: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Use `ann` for approximate search
+    pub fn with_ann(mut self, ann: AnnConfig) -> Self {
+        self.ann = ann;
+        self
+    }
+
+    /// Record `model` as the source of new embeddings, and refuse an index
+    /// built with another one
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    fn ann_path(&self) -> PathBuf {
+        self.db_path.with_extension("hnsw")
+    }
+
+    /// Where `reindex --migrate` builds the replacement index
+    fn migration_path(&self) -> PathBuf {
+        self.db_path.with_extension("migrating.db")
+    }
+
+    fn use_ann(&self, skill_count: usize) -> bool {
+        self.ann.enabled && skill_count > 0 && skill_count >= self.ann.min_skills
     }
 
     /// Create a new service with default index path
@@ -88,7 +544,7 @@ impl VectorIndexServiceImpl {
             .index_path
             .clone()
             .unwrap_or_else(|| skill_dir.join(".fastskill").join("index.db"));
-        Self::new(index_path)
+        Self::new(index_path).with_model(config.embedding_model.clone())
     }
 
     /// Ensure the database schema is created
@@ -104,8 +560,7 @@ impl VectorIndexServiceImpl {
                 })?;
             }
 
-            let conn = rusqlite::Connection::open(&db_path)
-                .map_err(|e| ServiceError::Custom(format!("Failed to open database: {}", e)))?;
+            let conn = open_connection(&db_path)?;
 
             conn.execute(
                 "CREATE TABLE IF NOT EXISTS skills (
@@ -114,12 +569,30 @@ impl VectorIndexServiceImpl {
                     frontmatter_json TEXT NOT NULL,
                     embedding_json TEXT NOT NULL,
                     file_hash TEXT NOT NULL,
-                    updated_at TEXT NOT NULL
+                    updated_at TEXT NOT NULL,
+                    chunk_embeddings_json TEXT NOT NULL DEFAULT '[]'
                 )",
                 [],
             )
             .map_err(|e| ServiceError::Custom(format!("Failed to create schema: {}", e)))?;
 
+            // Indexes created before chunked embeddings lack the column
+            let has_chunks: bool = conn
+                .query_row(
+                    "SELECT COUNT(*) FROM pragma_table_info('skills') WHERE name = 'chunk_embeddings_json'",
+                    [],
+                    |row| row.get::<_, i64>(0),
+                )
+                .map(|count| count > 0)
+                .map_err(|e| ServiceError::Custom(format!("Failed to inspect schema: {}", e)))?;
+            if !has_chunks {
+                conn.execute(
+                    "ALTER TABLE skills ADD COLUMN chunk_embeddings_json TEXT NOT NULL DEFAULT '[]'",
+                    [],
+                )
+                .map_err(|e| ServiceError::Custom(format!("Failed to migrate schema: {}", e)))?;
+            }
+
             // Create index for faster lookups
             conn.execute(
                 "CREATE INDEX IF NOT EXISTS idx_updated_at ON skills(updated_at)",
@@ -127,6 +600,69 @@ impl VectorIndexServiceImpl {
             )
             .map_err(|e| ServiceError::Custom(format!("Failed to create index: {}", e)))?;
 
+            conn.execute(
+                "CREATE TABLE IF NOT EXISTS index_metadata (
+                    id INTEGER PRIMARY KEY CHECK (id = 1),
+                    model TEXT,
+                    dimensions INTEGER NOT NULL,
+                    version INTEGER NOT NULL
+                )",
+                [],
+            )
+            .map_err(|e| ServiceError::Custom(format!("Failed to create schema: {}", e)))?;
+
+            Ok(())
+        })
+        .await
+        .map_err(|e| ServiceError::Custom(format!("Database task failed: {}", e)))?
+    }
+
+    /// Insert or replace many skills in one transaction
+    ///
+    /// [`VectorIndexService::add_or_update_skill`] commits (and syncs) each
+    /// skill on its own; this is for loading large collections at once.
+    pub async fn add_skills(&self, skills: Vec<IndexedSkill>) -> Result<(), ServiceError> {
+        self.ensure_schema().await?;
+
+        let mut dimensions = None;
+        for skill in &skills {
+            let skill_dimensions = skill_dimensions(skill)?;
+            match dimensions {
+                None => dimensions = Some(skill_dimensions),
+                Some(d) if d != skill_dimensions => {
+                    return Err(IndexMismatch::Dimensions {
+                        indexed: d,
+                        got: skill_dimensions,
+                    }
+                    .into())
+                }
+                Some(_) => {}
+            }
+        }
+        let Some(dimensions) = dimensions else {
+            return Ok(());
+        };
+
+        let db_path = self.db_path.clone();
+        let model = self.model.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = open_connection(&db_path)?;
+            let tx = conn
+                .transaction()
+                .map_err(|e| ServiceError::Custom(format!("Failed to begin transaction: {}", e)))?;
+            checked_metadata(&tx, model.as_deref(), dimensions)?;
+            {
+                let mut stmt = tx.prepare(INSERT_SKILL).map_err(|e| {
+                    ServiceError::Custom(format!("Failed to prepare insert: {}", e))
+                })?;
+                for skill in &skills {
+                    SkillRow::new(skill)?.insert(&mut stmt)?;
+                }
+            }
+            tx.commit()
+                .map_err(|e| ServiceError::Custom(format!("Failed to commit skills: {}", e)))?;
+
             Ok(())
         })
         .await
@@ -134,7 +670,7 @@ impl VectorIndexServiceImpl {
     }
 
     /// Calculate cosine similarity between two vectors
-    fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    pub(crate) fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
         if a.len() != b.len() {
             return 0.0;
         }
@@ -170,37 +706,33 @@ impl VectorIndexService for VectorIndexServiceImpl {
         embedding: Vec<f32>,
         file_hash: &str,
     ) -> Result<(), ServiceError> {
+        self.add_or_update_indexed_skill(IndexedSkill {
+            id: skill_id.to_string(),
+            skill_path,
+            frontmatter_json,
+            embedding,
+            chunk_embeddings: Vec::new(),
+            file_hash: file_hash.to_string(),
+            updated_at: chrono::Utc::now(),
+        })
+        .await
+    }
+
+    async fn add_or_update_indexed_skill(&self, skill: IndexedSkill) -> Result<(), ServiceError> {
         self.ensure_schema().await?;
 
         let db_path = self.db_path.clone();
-        let skill_id = skill_id.to_string();
-        let skill_path_str = skill_path.to_string_lossy().to_string();
-        let frontmatter_str = serde_json::to_string(&frontmatter_json)
-            .map_err(|e| ServiceError::Custom(format!("Failed to serialize frontmatter: {}", e)))?;
-        let embedding_str = serde_json::to_string(&embedding)
-            .map_err(|e| ServiceError::Custom(format!("Failed to serialize embedding: {}", e)))?;
-        let file_hash = file_hash.to_string();
-        let updated_at = chrono::Utc::now().to_rfc3339();
+        let model = self.model.clone();
+        let dimensions = skill_dimensions(&skill)?;
+        let row = SkillRow::new(&skill)?;
 
         tokio::task::spawn_blocking(move || {
-            let conn = rusqlite::Connection::open(&db_path)
-                .map_err(|e| ServiceError::Custom(format!("Failed to open database: {}", e)))?;
-
-            conn.execute(
-                "INSERT OR REPLACE INTO skills (id, skill_path, frontmatter_json, embedding_json, file_hash, updated_at)
-                 VALUES (?, ?, ?, ?, ?, ?)",
-                rusqlite::params![
-                    skill_id,
-                    skill_path_str,
-                    frontmatter_str,
-                    embedding_str,
-                    file_hash,
-                    updated_at
-                ],
-            )
-            .map_err(|e| ServiceError::Custom(format!("Failed to insert skill: {}", e)))?;
-
-            Ok(())
+            let conn = open_connection(&db_path)?;
+            checked_metadata(&conn, model.as_deref(), dimensions)?;
+            let mut stmt = conn
+                .prepare(INSERT_SKILL)
+                .map_err(|e| ServiceError::Custom(format!("Failed to prepare insert: {}", e)))?;
+            row.insert(&mut stmt)
         })
         .await
         .map_err(|e| ServiceError::Custom(format!("Database task failed: {}", e)))?
@@ -211,61 +743,33 @@ impl VectorIndexService for VectorIndexServiceImpl {
         query_embedding: &[f32],
         limit: usize,
     ) -> Result<Vec<SkillMatch>, ServiceError> {
-        self.ensure_schema().await?;
-
-        let db_path = self.db_path.clone();
-        let query_embedding = query_embedding.to_vec();
-
-        let skills = tokio::task::spawn_blocking(move || {
-            let conn = rusqlite::Connection::open(&db_path)
-                .map_err(|e| ServiceError::Custom(format!("Failed to open database: {}", e)))?;
+        // Scores against embeddings of another model or length are meaningless
+        if let Some(metadata) = self.metadata().await? {
+            if let Some(mismatch) = metadata.mismatch(self.model.as_deref()) {
+                return Err(mismatch.into());
+            }
+            metadata.check_dimensions(query_embedding.len())?;
+        }
 
-            let mut stmt = conn
-                .prepare("SELECT id, skill_path, frontmatter_json, embedding_json, file_hash, updated_at FROM skills")
-                .map_err(|e| ServiceError::Custom(format!("Failed to prepare query: {}", e)))?;
+        let skills = self.get_all_skills().await?;
 
-            let skill_iter = stmt.query_map([], |row| {
-                let id: String = row.get(0)?;
-                let skill_path: String = row.get(1)?;
-                let frontmatter_str: String = row.get(2)?;
-                let embedding_str: String = row.get(3)?;
-                let file_hash: String = row.get(4)?;
-                let updated_at_str: String = row.get(5)?;
-
-                let frontmatter_json: serde_json::Value = serde_json::from_str(&frontmatter_str)
-                    .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e)))?;
-                let embedding: Vec<f32> = serde_json::from_str(&embedding_str)
-                    .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e)))?;
-                let updated_at = chrono::DateTime::parse_from_rfc3339(&updated_at_str)
-                    .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e)))?
-                    .with_timezone(&chrono::Utc);
-
-                Ok(IndexedSkill {
-                    id,
-                    skill_path: PathBuf::from(skill_path),
-                    frontmatter_json,
-                    embedding,
-                    file_hash,
-                    updated_at,
-                })
+        if self.use_ann(skills.len()) {
+            let ann = self.ann;
+            let path = self.ann_path();
+            let cache = Arc::clone(&self.ann_graph);
+            let query_embedding = query_embedding.to_vec();
+            return tokio::task::spawn_blocking(move || {
+                ann_search(&cache, &path, &ann, skills, &query_embedding, limit)
             })
-            .map_err(|e| ServiceError::Custom(format!("Failed to query skills: {}", e)))?;
-
-            let mut skills = Vec::new();
-            for skill in skill_iter {
-                skills.push(skill.map_err(|e| ServiceError::Custom(format!("Failed to parse skill: {}", e)))?);
-            }
-
-            Ok::<Vec<IndexedSkill>, ServiceError>(skills)
-        })
-        .await
-        .map_err(|e| ServiceError::Custom(format!("Database task failed: {}", e)))??;
+            .await
+            .map_err(|e| ServiceError::Custom(format!("ANN search task failed: {}", e)))?;
+        }
 
         // Calculate similarities and sort
         let mut matches: Vec<SkillMatch> = skills
             .into_iter()
             .map(|skill| {
-                let similarity = Self::cosine_similarity(&query_embedding, &skill.embedding);
+                let similarity = skill.similarity(query_embedding);
                 SkillMatch { skill, similarity }
             })
             .collect();
@@ -291,42 +795,20 @@ impl VectorIndexService for VectorIndexServiceImpl {
         let skill_id = skill_id.to_string();
 
         let skill = tokio::task::spawn_blocking(move || {
-            let conn = rusqlite::Connection::open(&db_path)
-                .map_err(|e| ServiceError::Custom(format!("Failed to open database: {}", e)))?;
+            let conn = open_connection(&db_path)?;
 
             let mut stmt = conn
-                .prepare("SELECT id, skill_path, frontmatter_json, embedding_json, file_hash, updated_at FROM skills WHERE id = ?")
+                .prepare(&format!("{} WHERE id = ?", SELECT_SKILL))
                 .map_err(|e| ServiceError::Custom(format!("Failed to prepare query: {}", e)))?;
 
-            let mut rows = stmt.query_map([skill_id], |row| {
-                let id: String = row.get(0)?;
-                let skill_path: String = row.get(1)?;
-                let frontmatter_str: String = row.get(2)?;
-                let embedding_str: String = row.get(3)?;
-                let file_hash: String = row.get(4)?;
-                let updated_at_str: String = row.get(5)?;
-
-                let frontmatter_json: serde_json::Value = serde_json::from_str(&frontmatter_str)
-                    .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e)))?;
-                let embedding: Vec<f32> = serde_json::from_str(&embedding_str)
-                    .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e)))?;
-                let updated_at = chrono::DateTime::parse_from_rfc3339(&updated_at_str)
-                    .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e)))?
-                    .with_timezone(&chrono::Utc);
-
-                Ok(IndexedSkill {
-                    id,
-                    skill_path: PathBuf::from(skill_path),
-                    frontmatter_json,
-                    embedding,
-                    file_hash,
-                    updated_at,
-                })
-            })
-            .map_err(|e| ServiceError::Custom(format!("Failed to query skill: {}", e)))?;
+            let mut rows = stmt
+                .query_map([skill_id], read_indexed_skill)
+                .map_err(|e| ServiceError::Custom(format!("Failed to query skill: {}", e)))?;
 
             match rows.next() {
-                Some(result) => Ok(Some(result.map_err(|e| ServiceError::Custom(format!("Failed to parse skill: {}", e)))?)),
+                Some(result) => Ok(Some(result.map_err(|e| {
+                    ServiceError::Custom(format!("Failed to parse skill: {}", e))
+                })?)),
                 None => Ok(None),
             }
         })
@@ -343,8 +825,7 @@ impl VectorIndexService for VectorIndexServiceImpl {
         let skill_id = skill_id.to_string();
 
         tokio::task::spawn_blocking(move || {
-            let conn = rusqlite::Connection::open(&db_path)
-                .map_err(|e| ServiceError::Custom(format!("Failed to open database: {}", e)))?;
+            let conn = open_connection(&db_path)?;
 
             conn.execute("DELETE FROM skills WHERE id = ?", [skill_id])
                 .map_err(|e| ServiceError::Custom(format!("Failed to delete skill: {}", e)))?;
@@ -360,51 +841,601 @@ impl VectorIndexService for VectorIndexServiceImpl {
 
         let db_path = self.db_path.clone();
 
-        let skills = tokio::task::spawn_blocking(move || {
-            let conn = rusqlite::Connection::open(&db_path)
-                .map_err(|e| ServiceError::Custom(format!("Failed to open database: {}", e)))?;
+        tokio::task::spawn_blocking(move || {
+            let conn = open_connection(&db_path)?;
 
             let mut stmt = conn
-                .prepare("SELECT id, skill_path, frontmatter_json, embedding_json, file_hash, updated_at FROM skills")
+                .prepare(SELECT_SKILL)
                 .map_err(|e| ServiceError::Custom(format!("Failed to prepare query: {}", e)))?;
 
-            let skill_iter = stmt.query_map([], |row| {
-                let id: String = row.get(0)?;
-                let skill_path: String = row.get(1)?;
-                let frontmatter_str: String = row.get(2)?;
-                let embedding_str: String = row.get(3)?;
-                let file_hash: String = row.get(4)?;
-                let updated_at_str: String = row.get(5)?;
-
-                let frontmatter_json: serde_json::Value = serde_json::from_str(&frontmatter_str)
-                    .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e)))?;
-                let embedding: Vec<f32> = serde_json::from_str(&embedding_str)
-                    .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e)))?;
-                let updated_at = chrono::DateTime::parse_from_rfc3339(&updated_at_str)
-                    .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e)))?
-                    .with_timezone(&chrono::Utc);
-
-                Ok(IndexedSkill {
-                    id,
-                    skill_path: PathBuf::from(skill_path),
-                    frontmatter_json,
-                    embedding,
-                    file_hash,
-                    updated_at,
-                })
-            })
-            .map_err(|e| ServiceError::Custom(format!("Failed to query skills: {}", e)))?;
+            let skill_iter = stmt
+                .query_map([], read_indexed_skill)
+                .map_err(|e| ServiceError::Custom(format!("Failed to query skills: {}", e)))?;
 
             let mut skills = Vec::new();
             for skill in skill_iter {
-                skills.push(skill.map_err(|e| ServiceError::Custom(format!("Failed to parse skill: {}", e)))?);
+                skills.push(
+                    skill.map_err(|e| {
+                        ServiceError::Custom(format!("Failed to parse skill: {}", e))
+                    })?,
+                );
             }
 
             Ok::<Vec<IndexedSkill>, ServiceError>(skills)
         })
         .await
-        .map_err(|e| ServiceError::Custom(format!("Database task failed: {}", e)))??;
+        .map_err(|e| ServiceError::Custom(format!("Database task failed: {}", e)))?
+    }
 
-        Ok(skills)
+    async fn count_skills(&self) -> Result<usize, ServiceError> {
+        self.ensure_schema().await?;
+
+        let db_path = self.db_path.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let conn = open_connection(&db_path)?;
+            let count: i64 = conn
+                .query_row("SELECT COUNT(*) FROM skills", [], |row| row.get(0))
+                .map_err(|e| ServiceError::Custom(format!("Failed to count skills: {}", e)))?;
+            Ok(usize::try_from(count).unwrap_or(0))
+        })
+        .await
+        .map_err(|e| ServiceError::Custom(format!("Database task failed: {}", e)))?
+    }
+
+    async fn get_skills_page(
+        &self,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<IndexedSkill>, ServiceError> {
+        self.ensure_schema().await?;
+
+        let db_path = self.db_path.clone();
+        let after = after.unwrap_or_default().to_string();
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+
+        tokio::task::spawn_blocking(move || {
+            let conn = open_connection(&db_path)?;
+
+            // Keyset pagination on the primary key: each page is an index
+            // range scan, however deep into the collection it starts.
+            let mut stmt = conn
+                .prepare(&format!(
+                    "{} WHERE id > ?1 ORDER BY id LIMIT ?2",
+                    SELECT_SKILL
+                ))
+                .map_err(|e| ServiceError::Custom(format!("Failed to prepare query: {}", e)))?;
+
+            let skill_iter = stmt
+                .query_map(rusqlite::params![after, limit], read_indexed_skill)
+                .map_err(|e| ServiceError::Custom(format!("Failed to query skills: {}", e)))?;
+
+            let mut skills = Vec::new();
+            for skill in skill_iter {
+                skills.push(
+                    skill.map_err(|e| {
+                        ServiceError::Custom(format!("Failed to parse skill: {}", e))
+                    })?,
+                );
+            }
+
+            Ok::<Vec<IndexedSkill>, ServiceError>(skills)
+        })
+        .await
+        .map_err(|e| ServiceError::Custom(format!("Database task failed: {}", e)))?
+    }
+
+    async fn refresh_search_index(&self) -> Result<(), ServiceError> {
+        let skills = self.get_all_skills().await?;
+        if !self.use_ann(skills.len()) {
+            return Ok(());
+        }
+        let ann = self.ann;
+        let path = self.ann_path();
+        let cache = Arc::clone(&self.ann_graph);
+        tokio::task::spawn_blocking(move || {
+            let mut slot = lock_graph(&cache)?;
+            synced_graph(&mut slot, &path, &ann, &skills);
+            Ok::<(), ServiceError>(())
+        })
+        .await
+        .map_err(|e| ServiceError::Custom(format!("ANN index task failed: {}", e)))?
+    }
+
+    async fn metadata(&self) -> Result<Option<VectorIndexMetadata>, ServiceError> {
+        // Nothing indexed yet; don't create the database just to look
+        if !self.db_path.exists() {
+            return Ok(None);
+        }
+        self.ensure_schema().await?;
+
+        let db_path = self.db_path.clone();
+
+        tokio::task::spawn_blocking(move || read_metadata(&open_connection(&db_path)?))
+            .await
+            .map_err(|e| ServiceError::Custom(format!("Database task failed: {}", e)))?
+    }
+
+    async fn mismatch(&self) -> Result<Option<IndexMismatch>, ServiceError> {
+        Ok(self
+            .metadata()
+            .await?
+            .and_then(|metadata| metadata.mismatch(self.model.as_deref())))
+    }
+
+    async fn begin_migration(&self) -> Result<Arc<dyn VectorIndexService>, ServiceError> {
+        // Left over by a migration that did not finish
+        self.abort_migration().await?;
+        let mut staged = VectorIndexServiceImpl::new(self.migration_path());
+        staged.model = self.model.clone();
+        staged.ensure_schema().await?;
+        Ok(Arc::new(staged))
+    }
+
+    async fn commit_migration(&self) -> Result<(), ServiceError> {
+        let staged = self.migration_path();
+        if !staged.exists() {
+            return Err(ServiceError::Config(format!(
+                "No migrated index at {}",
+                staged.display()
+            )));
+        }
+        std::fs::rename(&staged, &self.db_path)?;
+        // The graph indexed the old embeddings
+        *lock_graph(&self.ann_graph)? = None;
+        match std::fs::remove_file(self.ann_path()) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    async fn abort_migration(&self) -> Result<(), ServiceError> {
+        match std::fs::remove_file(self.migration_path()) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Candidates fetched per requested result from the ANN graph when skills
+/// have chunk embeddings, before re-ranking by max-sim
+const ANN_CHUNK_CANDIDATE_FACTOR: usize = 4;
+
+fn lock_graph(
+    cache: &Mutex<Option<HnswGraph>>,
+) -> Result<std::sync::MutexGuard<'_, Option<HnswGraph>>, ServiceError> {
+    cache
+        .lock()
+        .map_err(|_| ServiceError::Custom("ANN index lock poisoned".to_string()))
+}
+
+/// The cached graph (loaded from `path` on first use), synced with `skills`
+/// and saved back when that changed it.
+fn synced_graph<'a>(
+    slot: &'a mut Option<HnswGraph>,
+    path: &Path,
+    config: &AnnConfig,
+    skills: &[IndexedSkill],
+) -> &'a HnswGraph {
+    let graph = slot.get_or_insert_with(|| {
+        HnswGraph::load(path, config).unwrap_or_else(|| HnswGraph::new(config, 0))
+    });
+    if graph.sync(config, skills) {
+        // Other processes syncing the same skills dir save in turn.
+        let saved = ann_save_lock(path).and_then(|_guard| graph.save(path));
+        if let Err(e) = saved {
+            // Searching still works; the next run just re-syncs from scratch.
+            tracing::warn!("Failed to save ANN index {}: {}", path.display(), e);
+        }
+    }
+    graph
+}
+
+/// Lock on `<index.hnsw>.lock`, held while the graph is written
+fn ann_save_lock(path: &Path) -> Result<std::fs::File, ServiceError> {
+    let mut lock_path = path.as_os_str().to_owned();
+    lock_path.push(".lock");
+    Ok(crate::core::process_lock::lock_sidecar(Path::new(
+        &lock_path,
+    ))?)
+}
+
+fn ann_search(
+    cache: &Mutex<Option<HnswGraph>>,
+    path: &Path,
+    config: &AnnConfig,
+    skills: Vec<IndexedSkill>,
+    query_embedding: &[f32],
+    limit: usize,
+) -> Result<Vec<SkillMatch>, ServiceError> {
+    // The graph holds each skill's own embedding only; when skills carry
+    // chunk embeddings, fetch extra candidates and rank them by max-sim.
+    let candidates = if skills.iter().any(|s| !s.chunk_embeddings.is_empty()) {
+        limit.saturating_mul(ANN_CHUNK_CANDIDATE_FACTOR)
+    } else {
+        limit
+    };
+    let hits: Vec<String> = {
+        let mut slot = lock_graph(cache)?;
+        synced_graph(&mut slot, path, config, &skills)
+            .search(query_embedding, candidates, config.ef_search)
+            .into_iter()
+            .map(|(id, _)| id.to_string())
+            .collect()
+    };
+    let mut by_id: HashMap<String, IndexedSkill> =
+        skills.into_iter().map(|s| (s.id.clone(), s)).collect();
+    let mut matches: Vec<SkillMatch> = hits
+        .iter()
+        .filter_map(|id| by_id.remove(id))
+        .map(|skill| SkillMatch {
+            similarity: skill.similarity(query_embedding),
+            skill,
+        })
+        .collect();
+    matches.sort_by(|a, b| {
+        b.similarity
+            .partial_cmp(&a.similarity)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    matches.truncate(limit);
+    Ok(matches)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    async fn index_with_entry(temp_dir: &TempDir) -> VectorIndexServiceImpl {
+        let index = VectorIndexServiceImpl::with_default_path(temp_dir.path());
+        index
+            .add_or_update_skill(
+                "committed",
+                temp_dir.path().join("committed"),
+                serde_json::json!({"name": "committed"}),
+                vec![1.0, 0.0],
+                "hash-1",
+            )
+            .await
+            .unwrap();
+        index
+    }
+
+    #[tokio::test]
+    async fn test_add_skills_inserts_batch() {
+        let temp_dir = TempDir::new().unwrap();
+        let index = index_with_entry(&temp_dir).await;
+        let skills = (0..3)
+            .map(|i| IndexedSkill {
+                id: format!("batch-{}", i),
+                skill_path: temp_dir.path().join(format!("batch-{}", i)),
+                frontmatter_json: serde_json::json!({"name": i}),
+                embedding: vec![i as f32, 1.0],
+                chunk_embeddings: Vec::new(),
+                file_hash: "hash".to_string(),
+                updated_at: chrono::Utc::now(),
+            })
+            .collect();
+        index.add_skills(skills).await.unwrap();
+
+        let all = index.get_all_skills().await.unwrap();
+        assert_eq!(all.len(), 4);
+        let batch = index.get_skill_by_id("batch-2").await.unwrap().unwrap();
+        assert_eq!(batch.embedding, vec![2.0, 1.0]);
+    }
+
+    #[tokio::test]
+    async fn test_batches_page_through_index_in_id_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let index = VectorIndexServiceImpl::new(temp_dir.path().join("index.db"));
+        let skills = [4, 0, 3, 1, 2]
+            .into_iter()
+            .map(|i| IndexedSkill {
+                id: format!("skill-{}", i),
+                skill_path: temp_dir.path().join(format!("skill-{}", i)),
+                frontmatter_json: serde_json::json!({"name": i}),
+                embedding: vec![i as f32],
+                chunk_embeddings: Vec::new(),
+                file_hash: "hash".to_string(),
+                updated_at: chrono::Utc::now(),
+            })
+            .collect();
+        index.add_skills(skills).await.unwrap();
+        assert_eq!(index.count_skills().await.unwrap(), 5);
+
+        let mut pages = batches(&index, None, 2);
+        let mut sizes = Vec::new();
+        let mut ids = Vec::new();
+        while let Some(batch) = pages.next_batch().await.unwrap() {
+            sizes.push(batch.len());
+            ids.extend(batch.into_iter().map(|s| s.id));
+        }
+        assert_eq!(sizes, vec![2, 2, 1]);
+        assert_eq!(
+            ids,
+            vec!["skill-0", "skill-1", "skill-2", "skill-3", "skill-4"]
+        );
+
+        let rest = index.get_skills_page(Some("skill-2"), 10).await.unwrap();
+        let rest: Vec<_> = rest.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(rest, vec!["skill-3", "skill-4"]);
+    }
+
+    #[tokio::test]
+    async fn test_ann_search_matches_exact_and_persists_graph() {
+        let temp_dir = TempDir::new().unwrap();
+        let ann = AnnConfig {
+            min_skills: 1,
+            ..Default::default()
+        };
+        let index = VectorIndexServiceImpl::with_default_path(temp_dir.path()).with_ann(ann);
+        for (i, embedding) in [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.7, 0.7, 0.0]]
+            .into_iter()
+            .enumerate()
+        {
+            let id = format!("skill-{}", i);
+            index
+                .add_or_update_skill(
+                    &id,
+                    temp_dir.path().join(&id),
+                    serde_json::json!({"name": id}),
+                    embedding.to_vec(),
+                    "hash",
+                )
+                .await
+                .unwrap();
+        }
+
+        let matches = index.search_similar(&[0.9, 0.1, 0.0], 2).await.unwrap();
+        let ids: Vec<&str> = matches.iter().map(|m| m.skill.id.as_str()).collect();
+        assert_eq!(ids, ["skill-0", "skill-2"]);
+        assert!(temp_dir
+            .path()
+            .join(".fastskill")
+            .join("index.hnsw")
+            .exists());
+
+        // A fresh service picks up the saved graph and sees later removals.
+        index.remove_skill("skill-0").await.unwrap();
+        let reopened = VectorIndexServiceImpl::with_default_path(temp_dir.path()).with_ann(ann);
+        let matches = reopened.search_similar(&[0.9, 0.1, 0.0], 1).await.unwrap();
+        assert_eq!(matches[0].skill.id, "skill-2");
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_write_is_rolled_back() {
+        let temp_dir = TempDir::new().unwrap();
+        let index = index_with_entry(&temp_dir).await;
+
+        // A writer that dies mid-transaction never commits; its partial rows must
+        // not be visible to the next connection.
+        {
+            let conn = open_connection(&index.db_path).unwrap();
+            conn.execute_batch(
+                "BEGIN IMMEDIATE;
+                 INSERT INTO skills (id, skill_path, frontmatter_json, embedding_json, file_hash, updated_at)
+                 VALUES ('partial', '/tmp/partial', '{}', '[]', 'x', 'now');
+                 DELETE FROM skills WHERE id = 'committed';",
+            )
+            .unwrap();
+        }
+
+        assert!(index.get_skill_by_id("partial").await.unwrap().is_none());
+        assert!(index.get_skill_by_id("committed").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_write_waits_for_concurrent_writer() {
+        let temp_dir = TempDir::new().unwrap();
+        let index = index_with_entry(&temp_dir).await;
+
+        let db_path = index.db_path.clone();
+        let (locked_tx, locked_rx) = std::sync::mpsc::channel();
+        let holder = std::thread::spawn(move || {
+            let conn = open_connection(&db_path).unwrap();
+            conn.execute_batch("BEGIN IMMEDIATE;").unwrap();
+            locked_tx.send(()).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(200));
+            conn.execute_batch("COMMIT;").unwrap();
+        });
+        locked_rx.recv().unwrap();
+
+        // Without a busy timeout this would fail immediately with SQLITE_BUSY.
+        index
+            .add_or_update_skill(
+                "second",
+                temp_dir.path().join("second"),
+                serde_json::json!({"name": "second"}),
+                vec![0.0, 1.0],
+                "hash-2",
+            )
+            .await
+            .unwrap();
+        holder.join().unwrap();
+
+        assert!(index.get_skill_by_id("second").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_search_scores_skills_by_best_chunk() {
+        let temp_dir = TempDir::new().unwrap();
+        let index = VectorIndexServiceImpl::new(temp_dir.path().join("index.db"));
+        index
+            .add_or_update_indexed_skill(IndexedSkill {
+                id: "chunked".to_string(),
+                skill_path: temp_dir.path().join("chunked"),
+                frontmatter_json: serde_json::json!({"name": "chunked"}),
+                embedding: vec![1.0, 0.0],
+                chunk_embeddings: vec![vec![0.0, 1.0]],
+                file_hash: "hash".to_string(),
+                updated_at: chrono::Utc::now(),
+            })
+            .await
+            .unwrap();
+        index
+            .add_or_update_skill(
+                "plain",
+                temp_dir.path().join("plain"),
+                serde_json::json!({"name": "plain"}),
+                vec![0.6, 0.8],
+                "hash",
+            )
+            .await
+            .unwrap();
+
+        // The query matches the chunked skill's section, not its summary
+        let matches = index.search_similar(&[0.0, 1.0], 2).await.unwrap();
+        assert_eq!(matches[0].skill.id, "chunked");
+        assert!((matches[0].similarity - 1.0).abs() < 1e-6);
+        assert_eq!(matches[0].skill.chunk_embeddings, vec![vec![0.0, 1.0]]);
+        assert!(matches[1].skill.chunk_embeddings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_index_without_chunk_column_is_migrated() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("index.db");
+        {
+            let conn = open_connection(&db_path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE skills (
+                    id TEXT PRIMARY KEY,
+                    skill_path TEXT NOT NULL,
+                    frontmatter_json TEXT NOT NULL,
+                    embedding_json TEXT NOT NULL,
+                    file_hash TEXT NOT NULL,
+                    updated_at TEXT NOT NULL
+                );
+                INSERT INTO skills VALUES
+                    ('old', '/tmp/old', '{}', '[1.0, 0.0]', 'x', '2024-01-01T00:00:00Z');",
+            )
+            .unwrap();
+        }
+
+        let index = VectorIndexServiceImpl::new(db_path);
+        let old = index.get_skill_by_id("old").await.unwrap().unwrap();
+        assert_eq!(old.embedding, vec![1.0, 0.0]);
+        assert!(old.chunk_embeddings.is_empty());
+    }
+    #[tokio::test]
+    async fn test_mixed_dimension_insert_is_refused() {
+        let temp_dir = TempDir::new().unwrap();
+        let index = index_with_entry(&temp_dir).await;
+        let metadata = index.metadata().await.unwrap().unwrap();
+        assert_eq!(metadata.dimensions, 2);
+        assert_eq!(metadata.version, INDEX_FORMAT_VERSION);
+
+        let err = index
+            .add_or_update_skill(
+                "wide",
+                temp_dir.path().join("wide"),
+                serde_json::json!({"name": "wide"}),
+                vec![1.0, 0.0, 0.0],
+                "hash",
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("--migrate"), "{}", err);
+        assert!(index.get_skill_by_id("wide").await.unwrap().is_none());
+
+        let err = index.search_similar(&[1.0, 0.0, 0.0], 1).await.unwrap_err();
+        assert!(err.to_string().contains("2-dimension"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_model_change_is_detected() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("index.db");
+        let old = VectorIndexServiceImpl::new(db_path.clone()).with_model("small");
+        old.add_or_update_skill(
+            "a",
+            temp_dir.path().join("a"),
+            serde_json::json!({}),
+            vec![1.0],
+            "h",
+        )
+        .await
+        .unwrap();
+        assert!(old.mismatch().await.unwrap().is_none());
+
+        let new = VectorIndexServiceImpl::new(db_path).with_model("large");
+        assert_eq!(
+            new.mismatch().await.unwrap(),
+            Some(IndexMismatch::Model {
+                indexed: "small".to_string(),
+                configured: "large".to_string(),
+            })
+        );
+        assert!(new.search_similar(&[1.0], 1).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_legacy_index_adopts_stored_dimensions() {
+        let temp_dir = TempDir::new().unwrap();
+        let index = VectorIndexServiceImpl::new(temp_dir.path().join("index.db"));
+        index.ensure_schema().await.unwrap();
+        {
+            let conn = open_connection(&index.db_path).unwrap();
+            conn.execute_batch(
+                "INSERT INTO skills (id, skill_path, frontmatter_json, embedding_json, file_hash, updated_at)
+                 VALUES ('old', '/tmp/old', '{}', '[1.0, 0.0]', 'x', '2024-01-01T00:00:00Z');",
+            )
+            .unwrap();
+        }
+        assert!(index.metadata().await.unwrap().is_none());
+
+        let err = index
+            .add_or_update_skill(
+                "new",
+                temp_dir.path().join("new"),
+                serde_json::json!({}),
+                vec![1.0],
+                "h",
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("2-dimension"), "{}", err);
+        assert_eq!(index.metadata().await.unwrap().unwrap().dimensions, 2);
+    }
+
+    #[tokio::test]
+    async fn test_migration_replaces_index_only_on_commit() {
+        let temp_dir = TempDir::new().unwrap();
+        let index = index_with_entry(&temp_dir).await;
+
+        let staged = index.begin_migration().await.unwrap();
+        staged
+            .add_or_update_skill(
+                "wide",
+                temp_dir.path().join("wide"),
+                serde_json::json!({}),
+                vec![0.0, 0.0, 1.0],
+                "h",
+            )
+            .await
+            .unwrap();
+        index.abort_migration().await.unwrap();
+        assert!(index.get_skill_by_id("committed").await.unwrap().is_some());
+        assert_eq!(index.metadata().await.unwrap().unwrap().dimensions, 2);
+
+        let staged = index.begin_migration().await.unwrap();
+        assert_eq!(staged.count_skills().await.unwrap(), 0);
+        staged
+            .add_or_update_skill(
+                "wide",
+                temp_dir.path().join("wide"),
+                serde_json::json!({}),
+                vec![0.0, 0.0, 1.0],
+                "h",
+            )
+            .await
+            .unwrap();
+        index.commit_migration().await.unwrap();
+        assert!(index.get_skill_by_id("committed").await.unwrap().is_none());
+        assert_eq!(index.metadata().await.unwrap().unwrap().dimensions, 3);
+        let matches = index.search_similar(&[0.0, 0.0, 1.0], 1).await.unwrap();
+        assert_eq!(matches[0].skill.id, "wide");
     }
 }
@@ -0,0 +1,378 @@
+//! Vendored skills for offline installs
+//!
+//! `fastskill vendor` copies every skill pinned in `skills.lock` into a
+//! `vendor/` directory next to `skill-project.toml`: one zip per skill version
+//! plus a `vendor.toml` index recording each skill's metadata, original origin
+//! and content hash. In offline mode installs resolve from this directory only
+//! and never reach a repository, git remote or URL.
+
+use crate::core::change_detection::calculate_skill_hash;
+use crate::core::origin::Origin;
+use crate::core::replication::pack_skill;
+use crate::core::service::{ServiceError, SkillId};
+use crate::core::skill_manager::SkillDefinition;
+use crate::core::version::newest_version;
+use crate::security::path::validate_path_component;
+use crate::storage::zip::ZipHandler;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Name of the vendor directory, relative to the project root
+pub const VENDOR_DIR: &str = "vendor";
+
+/// Name of the index inside the vendor directory
+pub const VENDOR_INDEX_FILE: &str = "vendor.toml";
+
+/// Contents of `vendor.toml`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VendorIndex {
+    #[serde(default)]
+    pub skills: Vec<VendoredSkill>,
+}
+
+/// One `[[skills]]` entry: a vendored skill version and its archive
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VendoredSkill {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Where the skill was installed from before it was vendored
+    pub origin: Origin,
+    /// Content hash of the skill directory, checked on every extraction
+    pub checksum: String,
+    /// Archive file name inside the vendor directory
+    pub archive: String,
+    pub vendored_at: DateTime<Utc>,
+}
+
+/// Vendor directory for the project whose manifest is `project_file`
+pub fn vendor_dir_for(project_file: &Path) -> PathBuf {
+    project_file
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(VENDOR_DIR)
+}
+
+/// A vendored skill extracted into a temporary directory
+#[derive(Debug)]
+pub struct ExtractedVendorSkill {
+    pub temp_dir: TempDir,
+    /// Skill directory inside `temp_dir`, named after the skill id
+    pub path: PathBuf,
+}
+
+/// A vendor directory and its index
+#[derive(Debug, Clone)]
+pub struct VendorDir {
+    root: PathBuf,
+    index: VendorIndex,
+}
+
+impl VendorDir {
+    /// Start an empty vendor directory at `root`. Nothing is written until
+    /// [`VendorDir::save`].
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            index: VendorIndex::default(),
+        }
+    }
+
+    /// Open the vendor directory at `root`, failing when it has no index
+    pub fn open(root: &Path) -> Result<Self, ServiceError> {
+        let index_path = root.join(VENDOR_INDEX_FILE);
+        let content = std::fs::read_to_string(&index_path).map_err(|_| {
+            ServiceError::Validation(format!(
+                "No vendored skills at {}: run 'fastskill vendor' first",
+                root.display()
+            ))
+        })?;
+        let index: VendorIndex = toml::from_str(&content).map_err(|e| {
+            ServiceError::Validation(format!("Failed to parse {}: {}", index_path.display(), e))
+        })?;
+        Ok(Self {
+            root: root.to_path_buf(),
+            index,
+        })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn skills(&self) -> &[VendoredSkill] {
+        &self.index.skills
+    }
+
+    /// Every vendored version of `id`
+    pub fn versions(&self, id: &str) -> Vec<String> {
+        self.index
+            .skills
+            .iter()
+            .filter(|skill| skill.id == id)
+            .map(|skill| skill.version.clone())
+            .collect()
+    }
+
+    /// The vendored `version` of `id`, or its newest vendored version
+    pub fn find(&self, id: &str, version: Option<&str>) -> Option<&VendoredSkill> {
+        let version = match version {
+            Some(version) => version.to_string(),
+            None => newest_version(&self.versions(id))?,
+        };
+        self.index
+            .skills
+            .iter()
+            .find(|skill| skill.id == id && skill.version == version)
+    }
+
+    /// Archive the installed `skill` from `skill_dir` and record it in the
+    /// index, replacing an earlier copy of the same version.
+    pub fn add(&mut self, skill: &SkillDefinition, skill_dir: &Path) -> Result<(), ServiceError> {
+        let archive = archive_name(skill.id.as_str(), &skill.version)?;
+        let bytes = pack_skill(skill_dir)?;
+        let checksum = calculate_skill_hash(skill_dir)?;
+        std::fs::create_dir_all(&self.root)?;
+        std::fs::write(self.root.join(&archive), bytes)?;
+
+        self.index
+            .skills
+            .retain(|s| !(s.id == skill.id.as_str() && s.version == skill.version));
+        self.index.skills.push(VendoredSkill {
+            id: skill.id.to_string(),
+            name: skill.name.clone(),
+            description: skill.description.clone(),
+            version: skill.version.clone(),
+            author: skill.author.clone(),
+            origin: skill.origin.clone(),
+            checksum,
+            archive,
+            vendored_at: Utc::now(),
+        });
+        self.index
+            .skills
+            .sort_by(|a, b| (&a.id, &a.version).cmp(&(&b.id, &b.version)));
+        Ok(())
+    }
+
+    /// Write `vendor.toml` and delete archives it no longer lists. Returns the
+    /// names of the deleted archives.
+    pub fn save(&self) -> Result<Vec<String>, ServiceError> {
+        std::fs::create_dir_all(&self.root)?;
+        let content = toml::to_string_pretty(&self.index).map_err(|e| {
+            ServiceError::Custom(format!("Failed to encode {}: {}", VENDOR_INDEX_FILE, e))
+        })?;
+        std::fs::write(self.root.join(VENDOR_INDEX_FILE), content)?;
+
+        let listed: BTreeSet<&str> = self
+            .index
+            .skills
+            .iter()
+            .map(|s| s.archive.as_str())
+            .collect();
+        let mut removed = Vec::new();
+        for entry in std::fs::read_dir(&self.root)? {
+            let path = entry?.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if path.is_file() && name.ends_with(".zip") && !listed.contains(name) {
+                std::fs::remove_file(&path)?;
+                removed.push(name.to_string());
+            }
+        }
+        removed.sort();
+        Ok(removed)
+    }
+
+    /// Raw archive bytes of a vendored skill
+    pub fn read_archive(&self, skill: &VendoredSkill) -> Result<Vec<u8>, ServiceError> {
+        // `archive` comes from vendor.toml, which is a checked-in file anyone
+        // can edit, so it must name a file directly inside the vendor dir.
+        let name = validate_path_component(&skill.archive).map_err(|e| {
+            ServiceError::Validation(format!("Invalid archive for {}: {}", skill.id, e))
+        })?;
+        Ok(std::fs::read(self.root.join(name))?)
+    }
+
+    /// Size in bytes of a vendored skill's archive
+    pub fn archive_size(&self, skill: &VendoredSkill) -> Result<u64, ServiceError> {
+        let name = validate_path_component(&skill.archive).map_err(|e| {
+            ServiceError::Validation(format!("Invalid archive for {}: {}", skill.id, e))
+        })?;
+        Ok(std::fs::metadata(self.root.join(name))?.len())
+    }
+
+    /// Extract a vendored skill and check it against its recorded checksum.
+    /// Entries that would land outside the temporary directory are rejected
+    /// by the zip extraction.
+    pub fn extract(&self, skill: &VendoredSkill) -> Result<ExtractedVendorSkill, ServiceError> {
+        let skill_id = SkillId::new(skill.id.clone())?;
+        let bytes = self.read_archive(skill)?;
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join(skill_id.as_str());
+        std::fs::create_dir_all(&path)?;
+        ZipHandler::new()?.extract_reader_to_dir(Cursor::new(bytes), &path)?;
+        if calculate_skill_hash(&path)? != skill.checksum {
+            return Err(ServiceError::Validation(format!(
+                "Vendored {} {} does not match its checksum in {}",
+                skill.id, skill.version, VENDOR_INDEX_FILE
+            )));
+        }
+        Ok(ExtractedVendorSkill { temp_dir, path })
+    }
+}
+
+/// `<id>-<version>.zip`, with the `/` of a scoped id as `+` (which ids cannot
+/// contain) so every archive sits directly in the vendor directory
+fn archive_name(id: &str, version: &str) -> Result<String, ServiceError> {
+    validate_path_component(&format!("{}-{}.zip", id.replace('/', "+"), version))
+        .map_err(|e| ServiceError::Validation(format!("Cannot vendor {} {}: {}", id, version, e)))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_skill(dir: &Path, id: &str, version: &str) -> (SkillDefinition, PathBuf) {
+        let skill_dir = dir.join(id);
+        std::fs::create_dir_all(skill_dir.join("references")).unwrap();
+        std::fs::write(
+            skill_dir.join("SKILL.md"),
+            format!("---\nname: {id}\nversion: {version}\ndescription: Test {id}\n---\n# {id}\n"),
+        )
+        .unwrap();
+        std::fs::write(skill_dir.join("references").join("notes.md"), "notes").unwrap();
+        let skill = SkillDefinition::new(
+            SkillId::new(id.to_string()).unwrap(),
+            id.to_string(),
+            format!("Test {id}"),
+            version.to_string(),
+            Origin::Repository {
+                repo: "team".to_string(),
+                skill: id.to_string(),
+                version: None,
+            },
+        );
+        (skill, skill_dir)
+    }
+
+    #[test]
+    fn test_vendor_roundtrip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().join(VENDOR_DIR);
+        let (old, old_dir) = write_skill(&temp_dir.path().join("old"), "pdf-tools", "1.2.0");
+        let (new, new_dir) = write_skill(&temp_dir.path().join("new"), "pdf-tools", "1.10.0");
+
+        let mut vendor = VendorDir::new(&root);
+        vendor.add(&old, &old_dir).unwrap();
+        vendor.add(&new, &new_dir).unwrap();
+        vendor.save().unwrap();
+
+        let vendor = VendorDir::open(&root).unwrap();
+        assert_eq!(vendor.skills().len(), 2);
+        assert_eq!(vendor.find("pdf-tools", None).unwrap().version, "1.10.0");
+        let skill = vendor.find("pdf-tools", Some("1.2.0")).unwrap();
+        assert_eq!(skill.origin, old.origin);
+
+        let size = vendor.archive_size(skill).unwrap();
+        assert_eq!(size, vendor.read_archive(skill).unwrap().len() as u64);
+
+        let extracted = vendor.extract(skill).unwrap();
+        assert!(extracted.path.ends_with("pdf-tools"));
+        assert!(extracted.path.join("references").join("notes.md").is_file());
+        assert!(vendor.find("csv-tools", None).is_none());
+    }
+
+    #[test]
+    fn test_save_removes_unlisted_archives() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().join(VENDOR_DIR);
+        let (skill, skill_dir) = write_skill(temp_dir.path(), "pdf-tools", "1.0.0");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("stale-0.1.0.zip"), b"old").unwrap();
+        std::fs::write(root.join("README.md"), b"keep").unwrap();
+
+        let mut vendor = VendorDir::new(&root);
+        vendor.add(&skill, &skill_dir).unwrap();
+        assert_eq!(vendor.save().unwrap(), ["stale-0.1.0.zip"]);
+        assert!(root.join("pdf-tools-1.0.0.zip").is_file());
+        assert!(root.join("README.md").is_file());
+    }
+
+    #[test]
+    fn test_open_without_index_fails() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let err = VendorDir::open(temp_dir.path()).unwrap_err();
+        assert!(err.to_string().contains("fastskill vendor"), "{}", err);
+    }
+
+    #[test]
+    fn test_extract_rejects_tampered_archive() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().join(VENDOR_DIR);
+        let (skill, skill_dir) = write_skill(temp_dir.path(), "pdf-tools", "1.0.0");
+        let mut vendor = VendorDir::new(&root);
+        vendor.add(&skill, &skill_dir).unwrap();
+
+        std::fs::write(skill_dir.join("SKILL.md"), "---\nname: changed\n---\n").unwrap();
+        let bytes = pack_skill(&skill_dir).unwrap();
+        std::fs::write(root.join("pdf-tools-1.0.0.zip"), bytes).unwrap();
+        let err = vendor.extract(&vendor.skills()[0]).unwrap_err();
+        assert!(err.to_string().contains("checksum"), "{}", err);
+    }
+
+    #[test]
+    fn test_archive_name_of_scoped_id() {
+        assert_eq!(
+            archive_name("acme/pdf-tools", "1.0.0").unwrap(),
+            "acme+pdf-tools-1.0.0.zip"
+        );
+        assert!(archive_name("../pdf-tools", "1.0.0").is_err());
+    }
+
+    #[test]
+    fn test_archive_name_traversal_rejected() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().join(VENDOR_DIR);
+        let (skill, skill_dir) = write_skill(temp_dir.path(), "pdf-tools", "1.0.0");
+        let mut vendor = VendorDir::new(&root);
+        vendor.add(&skill, &skill_dir).unwrap();
+
+        let mut evil = vendor.skills()[0].clone();
+        evil.archive = "../pdf-tools/SKILL.md".to_string();
+        assert!(vendor.read_archive(&evil).is_err());
+        assert!(vendor.archive_size(&evil).is_err());
+        assert!(vendor.extract(&evil).is_err());
+    }
+
+    #[test]
+    fn test_archive_entry_traversal_rejected() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().join(VENDOR_DIR);
+        let (skill, skill_dir) = write_skill(temp_dir.path(), "pdf-tools", "1.0.0");
+        let mut vendor = VendorDir::new(&root);
+        vendor.add(&skill, &skill_dir).unwrap();
+
+        let mut writer =
+            zip::ZipWriter::new(std::fs::File::create(root.join("pdf-tools-1.0.0.zip")).unwrap());
+        writer
+            .start_file("../../outside/SKILL.md", zip::write::FileOptions::default())
+            .unwrap();
+        writer.write_all(b"---\nname: slip\n---\n").unwrap();
+        writer.finish().unwrap();
+
+        assert!(vendor.extract(&vendor.skills()[0]).is_err());
+        assert!(!temp_dir.path().join("outside").exists());
+    }
+}
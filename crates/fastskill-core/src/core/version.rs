@@ -84,6 +84,23 @@ impl VersionConstraint {
         })?;
         Ok(self.req.matches(&ver))
     }
+
+    /// The constraint a version must meet to satisfy both `self` and `other`.
+    ///
+    /// `VersionReq` matches only when every comparator matches, so the
+    /// intersection is the union of both comparator lists. The result may be
+    /// unsatisfiable (`^1, ^2`); that only shows once candidates are filtered.
+    pub fn intersect(&self, other: &VersionConstraint) -> VersionConstraint {
+        let mut comparators = self.req.comparators.clone();
+        for comparator in &other.req.comparators {
+            if !comparators.contains(comparator) {
+                comparators.push(comparator.clone());
+            }
+        }
+        VersionConstraint {
+            req: VersionReq { comparators },
+        }
+    }
 }
 
 impl std::fmt::Display for VersionConstraint {
@@ -144,6 +161,76 @@ pub fn newest_version(versions: &[String]) -> Option<String> {
     sorted.first().cloned()
 }
 
+/// Return the oldest version string by semver, or `None` for an empty input.
+///
+/// Unparseable versions are only chosen when nothing parses.
+pub fn oldest_version(versions: &[String]) -> Option<String> {
+    versions
+        .iter()
+        .filter_map(|v| Version::parse(v).ok().map(|parsed| (parsed, v)))
+        .min_by(|a, b| a.0.cmp(&b.0))
+        .map(|(_, v)| v.clone())
+        .or_else(|| versions.first().cloned())
+}
+
+/// How the resolver picks among the versions that satisfy a constraint
+/// (`[tool.fastskill] resolution`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResolutionStrategy {
+    /// Newest satisfying version (the default).
+    #[default]
+    Maximum,
+    /// Oldest satisfying version, so installs only move when a constraint does.
+    Minimum,
+    /// Only the version recorded in skills.lock; anything unlocked fails.
+    Locked,
+}
+
+impl ResolutionStrategy {
+    pub fn parse(s: &str) -> Result<Self, VersionError> {
+        match s.trim() {
+            "maximum" | "max" => Ok(Self::Maximum),
+            "minimum" | "min" => Ok(Self::Minimum),
+            "locked" => Ok(Self::Locked),
+            other => Err(VersionError::InvalidConstraint(format!(
+                "unknown resolution strategy '{}' (expected maximum, minimum or locked)",
+                other
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Maximum => "maximum",
+            Self::Minimum => "minimum",
+            Self::Locked => "locked",
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Pick from `candidates`, which already satisfy the constraint. `locked`
+    /// is the version skills.lock records for the skill, if any.
+    pub fn select(&self, candidates: &[String], locked: Option<&str>) -> Option<String> {
+        match self {
+            Self::Maximum => newest_version(candidates),
+            Self::Minimum => oldest_version(candidates),
+            Self::Locked => locked
+                .filter(|v| candidates.iter().any(|c| c == v))
+                .map(str::to_string),
+        }
+    }
+}
+
+impl std::fmt::Display for ResolutionStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -341,6 +428,29 @@ mod tests {
         assert!(!constraint.satisfies("0.9.0").unwrap());
     }
 
+    #[test]
+    fn test_intersect_requires_both_constraints() {
+        let caret = VersionConstraint::parse("^1.2").unwrap();
+        let below = VersionConstraint::parse("<1.5.0").unwrap();
+        let both = caret.intersect(&below);
+        assert!(both.satisfies("1.4.9").unwrap());
+        assert!(!both.satisfies("1.5.0").unwrap());
+        assert!(!both.satisfies("1.1.0").unwrap());
+
+        let any = VersionConstraint::parse("*").unwrap();
+        assert_eq!(any.intersect(&caret), caret);
+        assert_eq!(caret.intersect(&caret), caret);
+        assert_eq!(
+            VersionConstraint::parse(&both.to_string()).unwrap(),
+            both,
+            "intersection must round-trip through its string form"
+        );
+
+        let disjoint = caret.intersect(&VersionConstraint::parse("^2").unwrap());
+        assert!(!disjoint.satisfies("1.9.0").unwrap());
+        assert!(!disjoint.satisfies("2.0.0").unwrap());
+    }
+
     #[test]
     fn test_sort_versions_desc_semver_multidigit() {
         // String sort would place "1.9.0" above "1.10.0"; semver must not.
@@ -397,4 +507,55 @@ mod tests {
     fn test_newest_version_empty() {
         assert_eq!(newest_version(&[]), None);
     }
+
+    #[test]
+    fn test_oldest_version() {
+        let versions = vec![
+            "1.10.0".to_string(),
+            "not-semver".to_string(),
+            "1.9.0".to_string(),
+        ];
+        assert_eq!(oldest_version(&versions), Some("1.9.0".to_string()));
+        assert_eq!(oldest_version(&[]), None);
+    }
+
+    #[test]
+    fn test_resolution_strategy_select() {
+        let candidates = vec![
+            "1.2.0".to_string(),
+            "1.10.0".to_string(),
+            "1.3.0".to_string(),
+        ];
+        assert_eq!(
+            ResolutionStrategy::Maximum.select(&candidates, None),
+            Some("1.10.0".to_string())
+        );
+        assert_eq!(
+            ResolutionStrategy::Minimum.select(&candidates, Some("1.3.0")),
+            Some("1.2.0".to_string())
+        );
+        assert_eq!(
+            ResolutionStrategy::Locked.select(&candidates, Some("1.3.0")),
+            Some("1.3.0".to_string())
+        );
+        assert_eq!(ResolutionStrategy::Locked.select(&candidates, None), None);
+        assert_eq!(
+            ResolutionStrategy::Locked.select(&candidates, Some("2.0.0")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_resolution_strategy_parse() {
+        assert_eq!(
+            ResolutionStrategy::parse("minimum").unwrap(),
+            ResolutionStrategy::Minimum
+        );
+        assert_eq!(
+            ResolutionStrategy::parse("max").unwrap(),
+            ResolutionStrategy::Maximum
+        );
+        assert!(ResolutionStrategy::parse("newest").is_err());
+        assert_eq!(ResolutionStrategy::Locked.to_string(), "locked");
+    }
 }
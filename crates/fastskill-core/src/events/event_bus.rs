@@ -1,5 +1,6 @@
 //! Event bus for skill lifecycle events
 
+use crate::core::jobs::Job;
 use crate::core::service::ServiceError;
 use crate::core::skill_manager::SkillDefinition;
 use async_trait::async_trait;
@@ -37,6 +38,12 @@ pub enum SkillEvent {
         error_message: Option<String>,
     },
 
+    /// A batch of skills re-embedded together by hot reload
+    SkillsReloaded {
+        skill_ids: Vec<String>,
+        failed: Vec<ReloadFailure>,
+    },
+
     /// Skill validation failed
     SkillValidationFailed {
         skill_id: String,
@@ -49,6 +56,9 @@ pub enum SkillEvent {
     /// Hot reload disabled
     HotReloadDisabled,
 
+    /// A background job was queued, started or finished
+    JobUpdated { job: Box<Job> },
+
     /// Custom event
     Custom {
         event_type: String,
@@ -64,6 +74,13 @@ pub struct SkillUpdate {
     pub version: Option<String>,
 }
 
+/// A skill that could not be reloaded in a batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReloadFailure {
+    pub skill_id: String,
+    pub error_message: String,
+}
+
 /// Hot reload configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HotReloadConfig {
@@ -199,9 +216,11 @@ impl EventBus {
             SkillEvent::SkillUpdated { .. } => "skill:updated",
             SkillEvent::SkillUnregistered { .. } => "skill:unregistered",
             SkillEvent::SkillReloaded { .. } => "skill:reloaded",
+            SkillEvent::SkillsReloaded { .. } => "skills:reloaded",
             SkillEvent::SkillValidationFailed { .. } => "skill:validation:failed",
             SkillEvent::HotReloadEnabled { .. } => "hot-reload:enabled",
             SkillEvent::HotReloadDisabled => "hot-reload:disabled",
+            SkillEvent::JobUpdated { job } => job.status.event_type(),
             SkillEvent::Custom { event_type, .. } => event_type.as_str(),
         }
         .to_string();
@@ -289,6 +308,17 @@ impl EventHandler for LoggingEventHandler {
                     );
                 }
             }
+            SkillEvent::SkillsReloaded { skill_ids, failed } => {
+                if failed.is_empty() {
+                    info!("Skills reloaded: {}", skill_ids.len());
+                } else {
+                    warn!(
+                        "[ERROR] Skills reloaded: {}, failed: {}",
+                        skill_ids.len(),
+                        failed.len()
+                    );
+                }
+            }
             SkillEvent::SkillValidationFailed { skill_id, errors } => {
                 warn!(
                     "[ERROR] Skill validation failed: {} - {} errors",
@@ -305,6 +335,9 @@ impl EventHandler for LoggingEventHandler {
             SkillEvent::HotReloadDisabled => {
                 info!("Hot reload disabled");
             }
+            SkillEvent::JobUpdated { job } => {
+                debug!("Job {} ({}) {}", job.id, job.kind, job.status);
+            }
             SkillEvent::Custom { event_type, data } => {
                 debug!("Custom event: {} - {:?}", event_type, data);
             }
@@ -346,9 +379,11 @@ impl EventHandler for MetricsEventHandler {
             SkillEvent::SkillUpdated { .. } => "skill:updated".to_string(),
             SkillEvent::SkillUnregistered { .. } => "skill:unregistered".to_string(),
             SkillEvent::SkillReloaded { .. } => "skill:reloaded".to_string(),
+            SkillEvent::SkillsReloaded { .. } => "skills:reloaded".to_string(),
             SkillEvent::SkillValidationFailed { .. } => "skill:validation:failed".to_string(),
             SkillEvent::HotReloadEnabled { .. } => "hot-reload:enabled".to_string(),
             SkillEvent::HotReloadDisabled => "hot-reload:disabled".to_string(),
+            SkillEvent::JobUpdated { job } => job.status.event_type().to_string(),
             SkillEvent::Custom { event_type, .. } => event_type.clone(),
         };
 
@@ -408,6 +443,16 @@ impl EventBus {
         .await
     }
 
+    /// Publish one event for a batch of reloaded skills
+    pub async fn publish_skills_reloaded(
+        &self,
+        skill_ids: Vec<String>,
+        failed: Vec<ReloadFailure>,
+    ) -> Result<usize, ServiceError> {
+        self.publish_event(SkillEvent::SkillsReloaded { skill_ids, failed })
+            .await
+    }
+
     /// Publish skill validation failed event
     pub async fn publish_skill_validation_failed(
         &self,
@@ -775,6 +820,17 @@ mod tests {
                 success: false,
                 error_message: Some("e".to_string()),
             },
+            SkillEvent::SkillsReloaded {
+                skill_ids: vec!["s".to_string()],
+                failed: vec![],
+            },
+            SkillEvent::SkillsReloaded {
+                skill_ids: vec![],
+                failed: vec![ReloadFailure {
+                    skill_id: "s".to_string(),
+                    error_message: "e".to_string(),
+                }],
+            },
             SkillEvent::SkillValidationFailed {
                 skill_id: "s".to_string(),
                 errors: vec!["e".to_string()],
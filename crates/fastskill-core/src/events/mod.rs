@@ -4,6 +4,6 @@ pub mod event_bus;
 
 // Re-export main types
 pub use event_bus::{
-    EventBus, EventHandler, HotReloadConfig, LoggingEventHandler, MetricsEventHandler, SkillEvent,
-    SkillUpdate,
+    EventBus, EventHandler, HotReloadConfig, LoggingEventHandler, MetricsEventHandler,
+    ReloadFailure, SkillEvent, SkillUpdate,
 };
@@ -0,0 +1,864 @@
+//! Script execution environment with sandboxing support
+
+mod process;
+pub mod runtime;
+
+use crate::core::cancel::CancellationToken;
+use crate::core::skill_env;
+use crate::core::skill_manager::SkillDefinition;
+use process::{run_process, Ending, ProcessOutput};
+use runtime::{runtime_for, ExecutionRuntime, RuntimeKind, RuntimeSkill, SystemRuntime};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::process::Command as TokioCommand;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ExecutionError {
+    #[error("Execution failed: {0}")]
+    Failed(String),
+
+    #[error("Script not found: {0}")]
+    ScriptNotFound(String),
+
+    #[error("Invalid script content: {0}")]
+    InvalidScript(String),
+
+    #[error("Execution timeout after {0:?}")]
+    Timeout(Duration),
+
+    #[error("Execution cancelled")]
+    Cancelled,
+
+    #[error("Resource limit exceeded: {0}")]
+    ResourceExceeded(String),
+
+    #[error("Security violation: {0}")]
+    SecurityViolation(String),
+
+    #[error("Skill '{skill}' needs environment variables that are not set: {variables}")]
+    MissingEnvironment { skill: String, variables: String },
+
+    #[error("Unknown execution environment '{0}' (expected system, python, node, docker or docker:<image>)")]
+    UnknownRuntime(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Execution environment configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionConfig {
+    /// Default timeout for script execution
+    pub default_timeout: Duration,
+
+    /// How long a timed-out or cancelled script gets to exit after SIGTERM
+    /// before it and its process group are killed
+    #[serde(default = "default_kill_grace_period")]
+    pub kill_grace_period: Duration,
+
+    /// Maximum memory per execution (MB)
+    pub max_memory_mb: usize,
+
+    /// Network access policy
+    pub network_policy: NetworkPolicy,
+
+    /// File system access level
+    pub filesystem_access: FileSystemAccess,
+
+    /// Allowed commands for shell execution
+    pub allowed_commands: Vec<String>,
+
+    /// Allowed script roots - scripts must be under one of these paths if configured
+    pub allowed_script_roots: Vec<PathBuf>,
+
+    /// Environment variables to set
+    pub environment_variables: HashMap<String, String>,
+}
+
+fn default_kill_grace_period() -> Duration {
+    Duration::from_secs(2)
+}
+
+impl Default for ExecutionConfig {
+    fn default() -> Self {
+        Self {
+            default_timeout: Duration::from_secs(30),
+            kill_grace_period: default_kill_grace_period(),
+            max_memory_mb: 100,
+            network_policy: NetworkPolicy::Restricted {
+                allowed_domains: vec![],
+            },
+            filesystem_access: FileSystemAccess::WorkingDirectory,
+            allowed_commands: vec![
+                "python".to_string(),
+                "python3".to_string(),
+                "node".to_string(),
+            ],
+            allowed_script_roots: Vec::new(),
+            environment_variables: HashMap::new(),
+        }
+    }
+}
+
+/// Network access policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum NetworkPolicy {
+    /// No network access allowed
+    None,
+    /// Only localhost allowed
+    Localhost,
+    /// Restricted to specific domains
+    Restricted { allowed_domains: Vec<String> },
+    /// Full network access
+    Full,
+}
+
+/// File system access levels
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum FileSystemAccess {
+    /// No file system access
+    None,
+    /// Only working directory
+    WorkingDirectory,
+    /// Read-only access to specific paths
+    ReadOnly { paths: Vec<PathBuf> },
+    /// Full access (dangerous)
+    Full,
+}
+
+/// Script definition for execution
+#[derive(Debug, Clone)]
+pub struct ScriptDefinition {
+    /// Path to the script file
+    pub path: PathBuf,
+
+    /// Script content (if not reading from file)
+    pub content: Option<String>,
+
+    /// Script language/interpreter
+    pub language: ScriptLanguage,
+
+    /// Execution parameters
+    pub parameters: HashMap<String, String>,
+
+    /// Working directory for execution
+    pub working_directory: Option<PathBuf>,
+}
+
+/// Supported script languages
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ScriptLanguage {
+    Python,
+    NodeJS,
+    Shell,
+    Rust,
+}
+
+impl ScriptLanguage {
+    fn get_command(&self) -> &'static str {
+        match self {
+            ScriptLanguage::Python => "python3",
+            ScriptLanguage::NodeJS => "node",
+            ScriptLanguage::Shell => "sh",
+            ScriptLanguage::Rust => "cargo",
+        }
+    }
+}
+
+/// Execution context
+#[derive(Debug, Clone)]
+pub struct ExecutionContext {
+    /// Skill ID that initiated the execution
+    pub skill_id: String,
+
+    /// User ID (if available)
+    pub user_id: Option<String>,
+
+    /// Session ID
+    pub session_id: String,
+
+    /// Input parameters for the execution
+    pub parameters: HashMap<String, String>,
+
+    /// Working directory override
+    pub working_directory: Option<PathBuf>,
+
+    /// Environment variables override
+    pub environment_variables: HashMap<String, String>,
+}
+
+/// Execution result
+#[derive(Debug, Clone)]
+pub struct ExecutionResult {
+    /// Whether execution was successful
+    pub success: bool,
+
+    /// Standard output from execution
+    pub stdout: String,
+
+    /// Standard error from execution
+    pub stderr: String,
+
+    /// Exit code (if applicable)
+    pub exit_code: Option<i32>,
+
+    /// Execution time taken
+    pub execution_time: Duration,
+
+    /// Resources used (approximate)
+    pub resources_used: ResourceUsage,
+}
+
+/// How a script run ended
+///
+/// A stopped script (timed out, or cancelled through the sandbox's token)
+/// still reports what it wrote before it was stopped, so callers can show
+/// partial progress. [`into_result`](Self::into_result) turns the stopped
+/// cases into errors for callers that only want finished runs.
+#[derive(Debug, Clone)]
+pub enum ToolResult {
+    /// The script exited by itself, successfully or not
+    Completed(ExecutionResult),
+    /// The script was still running after `timeout` and was stopped
+    TimedOut {
+        timeout: Duration,
+        partial: ExecutionResult,
+    },
+    /// The run was cancelled and the script stopped
+    Cancelled { partial: ExecutionResult },
+}
+
+impl ToolResult {
+    fn from_process(output: ProcessOutput, execution_time: Duration, timeout: Duration) -> Self {
+        let result = ExecutionResult {
+            success: matches!(output.ending, Ending::Exited(status) if status.success()),
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            exit_code: output.exit_code,
+            execution_time,
+            resources_used: ResourceUsage::default(), // Would track actual usage in sandboxed version
+        };
+        match output.ending {
+            Ending::Exited(_) => ToolResult::Completed(result),
+            Ending::TimedOut => ToolResult::TimedOut {
+                timeout,
+                partial: result,
+            },
+            Ending::Cancelled => ToolResult::Cancelled { partial: result },
+        }
+    }
+
+    /// Output of the run, complete or up to where it was stopped
+    pub fn output(&self) -> &ExecutionResult {
+        match self {
+            ToolResult::Completed(result)
+            | ToolResult::TimedOut {
+                partial: result, ..
+            }
+            | ToolResult::Cancelled { partial: result } => result,
+        }
+    }
+
+    /// The result of a finished run; [`ExecutionError::Timeout`] or
+    /// [`ExecutionError::Cancelled`] for a stopped one
+    pub fn into_result(self) -> Result<ExecutionResult, ExecutionError> {
+        match self {
+            ToolResult::Completed(result) => Ok(result),
+            ToolResult::TimedOut { timeout, .. } => Err(ExecutionError::Timeout(timeout)),
+            ToolResult::Cancelled { .. } => Err(ExecutionError::Cancelled),
+        }
+    }
+}
+
+/// Resource usage tracking
+#[derive(Debug, Clone, Default)]
+pub struct ResourceUsage {
+    /// Memory used (MB)
+    pub memory_mb: f64,
+
+    /// CPU time used (seconds)
+    pub cpu_seconds: f64,
+}
+
+/// Security sandbox for script execution
+///
+/// Scripts run until they exit, their timeout passes or the sandbox's
+/// cancellation token is cancelled; see [`ToolResult`] for how each ends.
+pub struct ExecutionSandbox {
+    config: ExecutionConfig,
+    /// Where virtualenvs and node_modules are kept; next to the skill
+    /// directories (`<skills dir>/.fastskill/runtimes`) when unset
+    runtimes_dir: Option<PathBuf>,
+    /// Stops every running script when cancelled
+    cancellation: CancellationToken,
+}
+
+impl ExecutionSandbox {
+    /// Create a new execution sandbox with the given configuration
+    pub fn new(config: ExecutionConfig) -> Result<Self, ExecutionError> {
+        Ok(Self {
+            config,
+            runtimes_dir: None,
+            cancellation: CancellationToken::new(),
+        })
+    }
+
+    /// Keep per-skill runtime state (virtualenvs, node_modules) under `dir`
+    pub fn with_runtimes_dir(mut self, dir: PathBuf) -> Self {
+        self.runtimes_dir = Some(dir);
+        self
+    }
+
+    /// Stop running scripts when `token` is cancelled (the service's token,
+    /// cancelled on Ctrl-C and shutdown)
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = token;
+        self
+    }
+
+    /// Execute a script with the provided context, using the interpreters on
+    /// PATH
+    pub async fn execute_script(
+        &self,
+        script: ScriptDefinition,
+        context: ExecutionContext,
+    ) -> Result<ExecutionResult, ExecutionError> {
+        let script_dir = script
+            .path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let skill = RuntimeSkill {
+            id: &context.skill_id,
+            dir: &script_dir,
+        };
+        let timeout_duration = self.config.default_timeout;
+        self.run(&SystemRuntime, skill, script, &context, timeout_duration)
+            .await?
+            .into_result()
+    }
+
+    /// Execute one of `skill`'s scripts in the runtime its
+    /// `execution_environment` names, preparing the runtime first. The
+    /// skill's `timeout` (seconds) replaces the default timeout.
+    pub async fn execute_skill_script(
+        &self,
+        skill: &SkillDefinition,
+        script: ScriptDefinition,
+        context: ExecutionContext,
+    ) -> Result<ExecutionResult, ExecutionError> {
+        self.run_skill_script(skill, script, context)
+            .await?
+            .into_result()
+    }
+
+    /// [`execute_skill_script`](Self::execute_skill_script), reporting a
+    /// timed-out or cancelled script as a [`ToolResult`] with its partial
+    /// output instead of an error
+    pub async fn run_skill_script(
+        &self,
+        skill: &SkillDefinition,
+        script: ScriptDefinition,
+        mut context: ExecutionContext,
+    ) -> Result<ToolResult, ExecutionError> {
+        let kind = RuntimeKind::parse(skill.execution_environment.as_deref())?;
+        // Declared variables come from the context, then this process, then
+        // their defaults, and are passed on explicitly so Docker sees them too
+        let declared = skill
+            .env
+            .resolve(|name| {
+                context
+                    .environment_variables
+                    .get(name)
+                    .cloned()
+                    .or_else(|| skill_env::process_env(name))
+            })
+            .map_err(|missing| ExecutionError::MissingEnvironment {
+                skill: skill.id.to_string(),
+                variables: missing.join(", "),
+            })?;
+        for (name, value) in declared {
+            context.environment_variables.entry(name).or_insert(value);
+        }
+        let skill_dir = skill.skill_file.parent().ok_or_else(|| {
+            ExecutionError::ScriptNotFound(skill.skill_file.to_string_lossy().to_string())
+        })?;
+        let runtimes_dir = match &self.runtimes_dir {
+            Some(dir) => dir.clone(),
+            None => runtime::default_runtimes_dir(skill_dir.parent().unwrap_or(skill_dir)),
+        };
+        let runtime = runtime_for(kind, &runtimes_dir, &self.config);
+        let runtime_skill = RuntimeSkill {
+            id: skill.id.as_str(),
+            dir: skill_dir,
+        };
+        let timeout_duration = skill
+            .timeout
+            .map(Duration::from_secs)
+            .unwrap_or(self.config.default_timeout);
+        self.run(
+            runtime.as_ref(),
+            runtime_skill,
+            script,
+            &context,
+            timeout_duration,
+        )
+        .await
+    }
+
+    async fn run(
+        &self,
+        runtime: &dyn ExecutionRuntime,
+        skill: RuntimeSkill<'_>,
+        script: ScriptDefinition,
+        context: &ExecutionContext,
+        timeout_duration: Duration,
+    ) -> Result<ToolResult, ExecutionError> {
+        // Validate script for security
+        self.validate_script(&script)?;
+        if !script.path.exists() {
+            return Err(ExecutionError::ScriptNotFound(
+                script.path.to_string_lossy().to_string(),
+            ));
+        }
+        runtime.prepare(skill).await?;
+
+        let start_time = std::time::Instant::now();
+
+        // Sandboxing beyond what the runtime provides (Docker) is not done yet
+        let output = self
+            .execute_in_user_environment(runtime, skill, script, context, timeout_duration)
+            .await?;
+
+        Ok(ToolResult::from_process(
+            output,
+            start_time.elapsed(),
+            timeout_duration,
+        ))
+    }
+
+    /// Validate script for security issues
+    fn validate_script(&self, script: &ScriptDefinition) -> Result<(), ExecutionError> {
+        // Basic validation - check for dangerous patterns
+        if let Some(content) = &script.content {
+            // Check for dangerous imports or system calls
+            let dangerous_patterns = [
+                "import os",
+                "import subprocess",
+                "import sys",
+                "exec(",
+                "eval(",
+                "system(",
+                "popen(",
+                "rm -rf",
+                "sudo",
+                "chmod 777",
+                "chown",
+            ];
+
+            for pattern in &dangerous_patterns {
+                if content.contains(pattern) {
+                    return Err(ExecutionError::SecurityViolation(format!(
+                        "Dangerous pattern detected: {}",
+                        pattern
+                    )));
+                }
+            }
+        }
+
+        // Validate file paths if accessing files
+        if script.path.exists() {
+            // Check path traversal - ensure script is under allowed roots if configured
+            if !self.config.allowed_script_roots.is_empty() {
+                let canonical_path = script.path.canonicalize().map_err(|e| {
+                    ExecutionError::SecurityViolation(format!(
+                        "Failed to canonicalize script path: {}",
+                        e
+                    ))
+                })?;
+
+                let is_allowed = self.config.allowed_script_roots.iter().any(|root| {
+                    if let Ok(canonical_root) = root.canonicalize() {
+                        canonical_path.starts_with(&canonical_root)
+                    } else {
+                        false
+                    }
+                });
+
+                if !is_allowed {
+                    return Err(ExecutionError::SecurityViolation(format!(
+                        "Script path '{}' is outside allowed roots: {:?}",
+                        script.path.display(),
+                        self.config.allowed_script_roots
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Launch the script through `runtime` and wait for it
+    async fn execute_in_user_environment(
+        &self,
+        runtime: &dyn ExecutionRuntime,
+        skill: RuntimeSkill<'_>,
+        script: ScriptDefinition,
+        context: &ExecutionContext,
+        timeout_duration: Duration,
+    ) -> Result<ProcessOutput, ExecutionError> {
+        // Parameters, then context variables, as environment variables
+        let mut env: Vec<(String, OsString)> = script
+            .parameters
+            .iter()
+            .map(|(key, value)| (format!("PARAM_{}", key), value.into()))
+            .collect();
+        env.extend(
+            context
+                .environment_variables
+                .iter()
+                .map(|(key, value)| (key.clone(), value.into())),
+        );
+
+        // The script's process group dies with this future, on timeout and
+        // on cancellation
+        let command = runtime.command(skill, &script, env)?;
+        let on_stop = command.stop_command();
+        run_process(
+            command.into_command(),
+            on_stop,
+            timeout_duration,
+            self.config.kill_grace_period,
+            &self.cancellation,
+        )
+        .await
+    }
+
+    /// Execute a command directly
+    pub async fn execute_command(
+        &self,
+        command: String,
+        args: Vec<String>,
+        context: ExecutionContext,
+    ) -> Result<ExecutionResult, ExecutionError> {
+        // Check if command is allowed
+        if !self.config.allowed_commands.is_empty() {
+            let command_name = command.split('/').next_back().unwrap_or(&command);
+            if !self
+                .config
+                .allowed_commands
+                .contains(&command_name.to_string())
+            {
+                return Err(ExecutionError::SecurityViolation(format!(
+                    "Command '{}' is not in allowed commands list",
+                    command_name
+                )));
+            }
+        }
+
+        let start_time = std::time::Instant::now();
+
+        // Execute command in user's environment
+        let output = self
+            .execute_command_in_user_environment(command, args, context)
+            .await?;
+
+        ToolResult::from_process(output, start_time.elapsed(), self.config.default_timeout)
+            .into_result()
+    }
+
+    /// Execute command in user's environment (fallback implementation)
+    async fn execute_command_in_user_environment(
+        &self,
+        command: String,
+        args: Vec<String>,
+        context: ExecutionContext,
+    ) -> Result<ProcessOutput, ExecutionError> {
+        let mut cmd = TokioCommand::new(command);
+        cmd.args(args);
+
+        // Set working directory
+        if let Some(working_dir) = &context.working_directory {
+            cmd.current_dir(working_dir);
+        }
+
+        run_process(
+            cmd,
+            None,
+            self.config.default_timeout,
+            self.config.kill_grace_period,
+            &self.cancellation,
+        )
+        .await
+    }
+
+    /// Get current configuration
+    pub fn config(&self) -> &ExecutionConfig {
+        &self.config
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_validate_script_rejects_path_traversal() {
+        let temp_dir = TempDir::new().unwrap();
+        let allowed_root = temp_dir.path().join("allowed");
+        std::fs::create_dir_all(&allowed_root).unwrap();
+
+        let config = ExecutionConfig {
+            allowed_script_roots: vec![allowed_root.clone()],
+            ..Default::default()
+        };
+
+        let sandbox = ExecutionSandbox::new(config).unwrap();
+
+        // Create a script outside the allowed root
+        let outside_path = temp_dir.path().join("outside").join("script.py");
+        std::fs::create_dir_all(outside_path.parent().unwrap()).unwrap();
+        std::fs::write(&outside_path, "print('test')").unwrap();
+
+        let script = ScriptDefinition {
+            path: outside_path,
+            content: None,
+            language: ScriptLanguage::Python,
+            parameters: std::collections::HashMap::new(),
+            working_directory: None,
+        };
+
+        let result = sandbox.validate_script(&script);
+        assert!(matches!(result, Err(ExecutionError::SecurityViolation(_))));
+    }
+
+    #[test]
+    fn test_validate_script_accepts_path_inside_allowed_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let allowed_root = temp_dir.path().join("allowed");
+        std::fs::create_dir_all(&allowed_root).unwrap();
+
+        let config = ExecutionConfig {
+            allowed_script_roots: vec![allowed_root.clone()],
+            ..Default::default()
+        };
+
+        let sandbox = ExecutionSandbox::new(config).unwrap();
+
+        // Create a script inside the allowed root
+        let inside_path = allowed_root.join("script.py");
+        std::fs::write(&inside_path, "print('test')").unwrap();
+
+        let script = ScriptDefinition {
+            path: inside_path,
+            content: None,
+            language: ScriptLanguage::Python,
+            parameters: std::collections::HashMap::new(),
+            working_directory: None,
+        };
+
+        let result = sandbox.validate_script(&script);
+        assert!(result.is_ok());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_execute_skill_script_picks_runtime() {
+        let temp_dir = TempDir::new().unwrap();
+        let skill_dir = temp_dir.path().join("greeter");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        let script_path = skill_dir.join("greet.sh");
+        std::fs::write(&script_path, "echo \"hello $PARAM_NAME\"\n").unwrap();
+
+        let mut skill = SkillDefinition::new(
+            crate::core::service::SkillId::new("greeter".to_string()).unwrap(),
+            "greeter".to_string(),
+            "Greets".to_string(),
+            "1.0.0".to_string(),
+            crate::core::origin::Origin::Local {
+                path: skill_dir.clone(),
+                editable: false,
+            },
+        );
+        skill.skill_file = skill_dir.join("SKILL.md");
+        let script = ScriptDefinition {
+            path: script_path,
+            content: None,
+            language: ScriptLanguage::Shell,
+            parameters: HashMap::from([("NAME".to_string(), "world".to_string())]),
+            working_directory: None,
+        };
+        let context = ExecutionContext {
+            skill_id: "greeter".to_string(),
+            user_id: None,
+            session_id: "s".to_string(),
+            parameters: HashMap::new(),
+            working_directory: None,
+            environment_variables: HashMap::new(),
+        };
+        let sandbox = ExecutionSandbox::new(ExecutionConfig::default()).unwrap();
+
+        let result = sandbox
+            .execute_skill_script(&skill, script.clone(), context.clone())
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.stdout.trim(), "hello world");
+
+        skill.execution_environment = Some("jvm".to_string());
+        let err = sandbox
+            .execute_skill_script(&skill, script, context)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ExecutionError::UnknownRuntime(_)));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_execute_skill_script_injects_declared_env() {
+        let temp_dir = TempDir::new().unwrap();
+        let skill_dir = temp_dir.path().join("greeter");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        let script_path = skill_dir.join("greet.sh");
+        std::fs::write(
+            &script_path,
+            "echo \"$FASTSKILL_TEST_GREETING $FASTSKILL_TEST_NAME\"\n",
+        )
+        .unwrap();
+
+        let mut skill = SkillDefinition::new(
+            crate::core::service::SkillId::new("greeter".to_string()).unwrap(),
+            "greeter".to_string(),
+            "Greets".to_string(),
+            "1.0.0".to_string(),
+            crate::core::origin::Origin::Local {
+                path: skill_dir.clone(),
+                editable: false,
+            },
+        );
+        skill.skill_file = skill_dir.join("SKILL.md");
+        skill.env.0.insert(
+            "FASTSKILL_TEST_GREETING".to_string(),
+            skill_env::EnvVarSpec {
+                secret: true,
+                ..Default::default()
+            },
+        );
+        skill.env.0.insert(
+            "FASTSKILL_TEST_NAME".to_string(),
+            skill_env::EnvVarSpec {
+                required: false,
+                default: Some("world".to_string()),
+                ..Default::default()
+            },
+        );
+        let script = ScriptDefinition {
+            path: script_path,
+            content: None,
+            language: ScriptLanguage::Shell,
+            parameters: HashMap::new(),
+            working_directory: None,
+        };
+        let mut context = ExecutionContext {
+            skill_id: "greeter".to_string(),
+            user_id: None,
+            session_id: "s".to_string(),
+            parameters: HashMap::new(),
+            working_directory: None,
+            environment_variables: HashMap::new(),
+        };
+        let sandbox = ExecutionSandbox::new(ExecutionConfig::default()).unwrap();
+
+        let err = sandbox
+            .execute_skill_script(&skill, script.clone(), context.clone())
+            .await
+            .unwrap_err();
+        match err {
+            ExecutionError::MissingEnvironment { skill, variables } => {
+                assert_eq!(skill, "greeter");
+                assert_eq!(variables, "FASTSKILL_TEST_GREETING");
+            }
+            other => panic!("expected MissingEnvironment, got {:?}", other),
+        }
+
+        context
+            .environment_variables
+            .insert("FASTSKILL_TEST_GREETING".to_string(), "hello".to_string());
+        let result = sandbox
+            .execute_skill_script(&skill, script, context)
+            .await
+            .unwrap();
+        assert_eq!(result.stdout.trim(), "hello world");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_skill_script_reports_timeout_with_partial_output() {
+        let temp_dir = TempDir::new().unwrap();
+        let skill_dir = temp_dir.path().join("slow");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        let script_path = skill_dir.join("slow.sh");
+        std::fs::write(&script_path, "echo working\nsleep 30\necho done\n").unwrap();
+
+        let mut skill = SkillDefinition::new(
+            crate::core::service::SkillId::new("slow".to_string()).unwrap(),
+            "slow".to_string(),
+            "Takes its time".to_string(),
+            "1.0.0".to_string(),
+            crate::core::origin::Origin::Local {
+                path: skill_dir.clone(),
+                editable: false,
+            },
+        );
+        skill.skill_file = skill_dir.join("SKILL.md");
+        let script = ScriptDefinition {
+            path: script_path,
+            content: None,
+            language: ScriptLanguage::Shell,
+            parameters: HashMap::new(),
+            working_directory: None,
+        };
+        let context = ExecutionContext {
+            skill_id: "slow".to_string(),
+            user_id: None,
+            session_id: "s".to_string(),
+            parameters: HashMap::new(),
+            working_directory: None,
+            environment_variables: HashMap::new(),
+        };
+        let sandbox = ExecutionSandbox::new(ExecutionConfig {
+            default_timeout: Duration::from_millis(300),
+            kill_grace_period: Duration::from_millis(200),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let result = sandbox
+            .run_skill_script(&skill, script.clone(), context.clone())
+            .await
+            .unwrap();
+        match &result {
+            ToolResult::TimedOut { timeout, partial } => {
+                assert_eq!(*timeout, Duration::from_millis(300));
+                assert!(!partial.success);
+                assert_eq!(partial.stdout, "working\n");
+            }
+            other => panic!("expected TimedOut, got {:?}", other),
+        }
+        assert!(matches!(
+            result.into_result(),
+            Err(ExecutionError::Timeout(_))
+        ));
+
+        let err = sandbox
+            .execute_skill_script(&skill, script, context)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ExecutionError::Timeout(_)));
+    }
+}
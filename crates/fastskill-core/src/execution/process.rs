@@ -0,0 +1,372 @@
+//! Running a script process under a timeout and a cancellation token
+//!
+//! On Unix each script starts in a process group of its own, so stopping it
+//! also stops what it spawned: the commands of a shell pipeline, processes
+//! started by `npm`, the `docker run` client. A script that outlives its
+//! timeout, or whose token is cancelled, gets SIGTERM first and SIGKILL once
+//! the grace period is over; the output it wrote until then is kept. When the
+//! future running a script is dropped (an HTTP client disconnecting) the
+//! group is killed at once. On other platforms only the script's own process
+//! is killed.
+//!
+//! Some scripts run outside the group: a Docker container belongs to the
+//! daemon, not to the `docker run` client. A stopped script's stop command
+//! (`docker rm --force <name>` for containers) takes care of those, and runs
+//! on every platform.
+
+use super::ExecutionError;
+use crate::core::cancel::CancellationToken;
+use std::process::{Command as StdCommand, ExitStatus, Stdio};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::process::{Child, Command as TokioCommand};
+
+/// How long output is still read after a stopped script has gone, for
+/// descendants that left its group but hold on to the pipes
+const DRAIN_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// How a process run ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Ending {
+    Exited(ExitStatus),
+    TimedOut,
+    Cancelled,
+}
+
+/// Output of one process run; what was written until it stopped when it
+/// did not exit by itself
+#[derive(Debug)]
+pub(super) struct ProcessOutput {
+    pub ending: Ending,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+    /// Exit code, when the process exited with one
+    pub exit_code: Option<i32>,
+}
+
+/// The process group of a running script. Kills the group and runs the
+/// stop command when dropped unless [`disarm`](Self::disarm)ed.
+struct ProcessGroup {
+    #[cfg_attr(not(unix), allow(dead_code))]
+    pid: Option<u32>,
+    on_stop: Option<StdCommand>,
+}
+
+impl ProcessGroup {
+    /// Ask the script to stop (SIGTERM to the group)
+    #[cfg(unix)]
+    fn terminate(&self, _child: &mut Child) {
+        self.signal(libc::SIGTERM);
+    }
+
+    /// Stop the script; there is no gentler way without process groups
+    #[cfg(not(unix))]
+    fn terminate(&self, child: &mut Child) {
+        let _ = child.start_kill();
+    }
+
+    /// Stop the script and everything left in its group
+    fn kill(&mut self, child: &mut Child) {
+        #[cfg(unix)]
+        self.signal(libc::SIGKILL);
+        let _ = child.start_kill();
+        self.run_on_stop();
+    }
+
+    /// Spawn the stop command, once. It is not waited for here, since
+    /// `Drop` cannot wait; a thread reaps it.
+    fn run_on_stop(&mut self) {
+        let Some(mut cmd) = self.on_stop.take() else {
+            return;
+        };
+        cmd.stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        match cmd.spawn() {
+            Ok(mut stop) => {
+                std::thread::spawn(move || stop.wait());
+            }
+            Err(e) => tracing::warn!("Failed to run the stop command of a script: {}", e),
+        }
+    }
+
+    #[cfg(unix)]
+    fn signal(&self, signal: libc::c_int) {
+        let Some(pid) = self.pid.and_then(|pid| libc::pid_t::try_from(pid).ok()) else {
+            return;
+        };
+        // SAFETY: killpg only sends a signal; a group that is gone fails with ESRCH
+        unsafe {
+            libc::killpg(pid, signal);
+        }
+    }
+
+    fn disarm(&mut self) {
+        self.pid = None;
+        self.on_stop = None;
+    }
+}
+
+impl Drop for ProcessGroup {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        self.signal(libc::SIGKILL);
+        self.run_on_stop();
+    }
+}
+
+/// Append everything `pipe` yields to `buf`. Bytes read before the future
+/// is dropped stay in `buf`.
+async fn drain<R: AsyncRead + Unpin>(pipe: Option<&mut R>, buf: &mut Vec<u8>) {
+    if let Some(pipe) = pipe {
+        let _ = pipe.read_to_end(buf).await;
+    }
+}
+
+/// Run `cmd` to completion, or until `timeout` passes or `cancellation` is
+/// cancelled; a stopped process gets `grace` to exit after SIGTERM, then
+/// `on_stop` runs
+pub(super) async fn run_process(
+    mut cmd: TokioCommand,
+    on_stop: Option<StdCommand>,
+    timeout: Duration,
+    grace: Duration,
+    cancellation: &CancellationToken,
+) -> Result<ProcessOutput, ExecutionError> {
+    if cancellation.is_cancelled() {
+        return Ok(ProcessOutput {
+            ending: Ending::Cancelled,
+            stdout: Vec::new(),
+            stderr: Vec::new(),
+            exit_code: None,
+        });
+    }
+
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    #[cfg(unix)]
+    cmd.process_group(0);
+    let mut child = cmd.spawn()?;
+    let mut group = ProcessGroup {
+        pid: child.id(),
+        on_stop,
+    };
+    let mut stdout_pipe = child.stdout.take();
+    let mut stderr_pipe = child.stderr.take();
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+
+    let ending = {
+        let run = async {
+            let (status, _, _) = tokio::join!(
+                child.wait(),
+                drain(stdout_pipe.as_mut(), &mut stdout),
+                drain(stderr_pipe.as_mut(), &mut stderr)
+            );
+            status
+        };
+        tokio::select! {
+            status = run => Ending::Exited(status?),
+            _ = tokio::time::sleep(timeout) => Ending::TimedOut,
+            _ = cancellation.cancelled() => Ending::Cancelled,
+        }
+    };
+
+    let ending = match ending {
+        Ending::Exited(status) => {
+            group.disarm();
+            return Ok(ProcessOutput {
+                ending,
+                stdout,
+                stderr,
+                exit_code: status.code(),
+            });
+        }
+        // The script exited but something it started kept the pipes open
+        Ending::TimedOut => match child.try_wait()? {
+            Some(status) => Ending::Exited(status),
+            None => Ending::TimedOut,
+        },
+        Ending::Cancelled => Ending::Cancelled,
+    };
+
+    // Cooperative stop first, then a hard one
+    group.terminate(&mut child);
+    let stopped = tokio::time::timeout(grace, async {
+        let (status, _, _) = tokio::join!(
+            child.wait(),
+            drain(stdout_pipe.as_mut(), &mut stdout),
+            drain(stderr_pipe.as_mut(), &mut stderr)
+        );
+        status
+    })
+    .await;
+    let status = match stopped {
+        Ok(status) => status.ok(),
+        Err(_) => {
+            group.kill(&mut child);
+            let status = child.wait().await.ok();
+            let _ = tokio::time::timeout(DRAIN_TIMEOUT, async {
+                tokio::join!(
+                    drain(stdout_pipe.as_mut(), &mut stdout),
+                    drain(stderr_pipe.as_mut(), &mut stderr)
+                )
+            })
+            .await;
+            status
+        }
+    };
+    // Nothing of the group may outlive the run
+    group.kill(&mut child);
+    group.disarm();
+
+    Ok(ProcessOutput {
+        ending,
+        stdout,
+        stderr,
+        exit_code: status.and_then(|status| status.code()),
+    })
+}
+
+#[cfg(all(test, unix))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn shell(script: &str) -> TokioCommand {
+        let mut cmd = TokioCommand::new("sh");
+        cmd.arg("-c").arg(script);
+        cmd
+    }
+
+    #[tokio::test]
+    async fn test_run_process_exits() {
+        let output = run_process(
+            shell("echo out; echo err >&2; exit 3"),
+            None,
+            Duration::from_secs(10),
+            Duration::from_secs(1),
+            &CancellationToken::new(),
+        )
+        .await
+        .unwrap();
+        assert!(matches!(output.ending, Ending::Exited(_)));
+        assert_eq!(output.exit_code, Some(3));
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "out\n");
+        assert_eq!(String::from_utf8_lossy(&output.stderr), "err\n");
+    }
+
+    #[tokio::test]
+    async fn test_timeout_kills_group_and_keeps_partial_output() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let marker = dir.path().join("survived");
+        // The background child ignores SIGTERM, so only the group SIGKILL stops it
+        let script = format!(
+            "echo started; (trap '' TERM; sleep 2; touch '{}') & wait",
+            marker.display()
+        );
+        let start = std::time::Instant::now();
+        let output = run_process(
+            shell(&script),
+            None,
+            Duration::from_millis(300),
+            Duration::from_millis(200),
+            &CancellationToken::new(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(output.ending, Ending::TimedOut);
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "started\n");
+        assert!(start.elapsed() < Duration::from_secs(2));
+
+        tokio::time::sleep(Duration::from_millis(2500)).await;
+        assert!(!marker.exists(), "a process of the group outlived the run");
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_stops_script() {
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            canceller.cancel();
+        });
+        let output = run_process(
+            shell("echo partial; sleep 30"),
+            None,
+            Duration::from_secs(60),
+            Duration::from_secs(1),
+            &token,
+        )
+        .await
+        .unwrap();
+        assert_eq!(output.ending, Ending::Cancelled);
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "partial\n");
+    }
+
+    fn touch(path: &std::path::Path) -> StdCommand {
+        let mut cmd = StdCommand::new("touch");
+        cmd.arg(path);
+        cmd
+    }
+
+    async fn wait_for(path: &std::path::Path) -> bool {
+        for _ in 0..50 {
+            if path.exists() {
+                return true;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        false
+    }
+
+    #[tokio::test]
+    async fn test_stop_command_runs_only_for_stopped_scripts() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let exited = dir.path().join("exited");
+        run_process(
+            shell("true"),
+            Some(touch(&exited)),
+            Duration::from_secs(10),
+            Duration::from_secs(1),
+            &CancellationToken::new(),
+        )
+        .await
+        .unwrap();
+
+        let timed_out = dir.path().join("timed-out");
+        let output = run_process(
+            shell("sleep 30"),
+            Some(touch(&timed_out)),
+            Duration::from_millis(100),
+            Duration::from_millis(100),
+            &CancellationToken::new(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(output.ending, Ending::TimedOut);
+        assert!(wait_for(&timed_out).await);
+        assert!(!exited.exists());
+    }
+
+    #[tokio::test]
+    async fn test_dropped_run_runs_stop_command() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let marker = dir.path().join("stopped");
+        let token = CancellationToken::new();
+        let run = run_process(
+            shell("sleep 30"),
+            Some(touch(&marker)),
+            Duration::from_secs(60),
+            Duration::from_secs(1),
+            &token,
+        );
+        // An HTTP client disconnecting drops the future mid-run
+        assert!(tokio::time::timeout(Duration::from_millis(100), run)
+            .await
+            .is_err());
+        assert!(wait_for(&marker).await);
+    }
+}
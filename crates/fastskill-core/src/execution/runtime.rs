@@ -0,0 +1,686 @@
+//! Execution runtimes: how a skill's scripts are launched
+//!
+//! A skill picks its runtime with `execution_environment` in its SKILL.md
+//! frontmatter:
+//!
+//! - `system` (the default): the interpreter for the script's language, from PATH
+//! - `python`: a virtualenv of the skill's own, with the pinned dependencies of
+//!   its `requirements.txt` installed; rebuilt when that file changes
+//! - `node`: `node`, with the dependencies of the skill's `package.json`
+//!   installed outside the skill directory and exposed through `NODE_PATH`
+//! - `docker` or `docker:<image>`: a throwaway container with the skill
+//!   directory mounted read-only at `/skill`
+//!
+//! Virtualenvs and `node_modules` live under the runtimes directory,
+//! `<skills dir>/.fastskill/runtimes/` unless the sandbox is given another.
+
+use super::{ExecutionConfig, ExecutionError, NetworkPolicy, ScriptDefinition, ScriptLanguage};
+use crate::core::metadata::SkillFrontmatter;
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use std::ffi::OsString;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tokio::process::Command as TokioCommand;
+use tokio::sync::Mutex;
+
+/// Recorded in a prepared environment: the hash of the dependency files it
+/// was built from
+const STAMP_FILE: &str = ".fastskill-deps.sha256";
+
+/// Where containers see the skill directory
+const CONTAINER_SKILL_DIR: &str = "/skill";
+
+/// Runtime a skill's scripts run in, as named by `execution_environment`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuntimeKind {
+    System,
+    PythonVenv,
+    Node,
+    /// `None` picks an image for the script's language
+    Docker {
+        image: Option<String>,
+    },
+}
+
+impl RuntimeKind {
+    /// Parse an `execution_environment` value; unset means `system`
+    pub fn parse(value: Option<&str>) -> Result<Self, ExecutionError> {
+        let value = value.map(str::trim).unwrap_or_default();
+        match value.to_ascii_lowercase().as_str() {
+            "" | "system" | "shell" => Ok(Self::System),
+            "python" | "venv" | "python-venv" => Ok(Self::PythonVenv),
+            "node" | "nodejs" => Ok(Self::Node),
+            "docker" => Ok(Self::Docker { image: None }),
+            _ => match value.split_once(':') {
+                Some((kind, image))
+                    if kind.eq_ignore_ascii_case("docker") && !image.trim().is_empty() =>
+                {
+                    Ok(Self::Docker {
+                        image: Some(image.trim().to_string()),
+                    })
+                }
+                _ => Err(ExecutionError::UnknownRuntime(value.to_string())),
+            },
+        }
+    }
+}
+
+impl fmt::Display for RuntimeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::System => f.write_str("system"),
+            Self::PythonVenv => f.write_str("python"),
+            Self::Node => f.write_str("node"),
+            Self::Docker { image: None } => f.write_str("docker"),
+            Self::Docker { image: Some(image) } => write!(f, "docker:{}", image),
+        }
+    }
+}
+
+/// `execution_environment` from a skill's frontmatter, if set
+pub fn declared_environment(frontmatter: &SkillFrontmatter) -> Option<String> {
+    frontmatter
+        .extra
+        .get("execution_environment")
+        .and_then(|value| value.as_str())
+        .map(str::to_string)
+}
+
+/// The skill whose script is being run
+#[derive(Debug, Clone, Copy)]
+pub struct RuntimeSkill<'a> {
+    pub id: &'a str,
+    pub dir: &'a Path,
+}
+
+/// A process to spawn on the host
+#[derive(Debug, Clone, Default)]
+pub struct RuntimeCommand {
+    pub program: OsString,
+    pub args: Vec<OsString>,
+    /// Set on the spawned process, on top of the inherited environment
+    pub env: Vec<(String, OsString)>,
+    pub working_directory: Option<PathBuf>,
+    /// Run when the script is stopped rather than exiting by itself, for what
+    /// killing its process group does not reach
+    pub on_stop: Option<Box<RuntimeCommand>>,
+}
+
+impl RuntimeCommand {
+    fn new(program: impl Into<OsString>) -> Self {
+        Self {
+            program: program.into(),
+            ..Default::default()
+        }
+    }
+
+    fn arg(mut self, arg: impl Into<OsString>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    pub(super) fn into_command(self) -> TokioCommand {
+        let mut cmd = TokioCommand::new(self.program);
+        cmd.args(self.args).kill_on_drop(true);
+        for (key, value) in self.env {
+            cmd.env(key, value);
+        }
+        if let Some(dir) = self.working_directory {
+            cmd.current_dir(dir);
+        }
+        cmd
+    }
+
+    /// The [`on_stop`](Self::on_stop) command, ready to spawn
+    pub(super) fn stop_command(&self) -> Option<std::process::Command> {
+        self.on_stop.as_ref().map(|stop| {
+            let mut cmd = std::process::Command::new(&stop.program);
+            cmd.args(&stop.args);
+            cmd
+        })
+    }
+}
+
+/// Launches scripts for skills in one kind of environment
+#[async_trait]
+pub trait ExecutionRuntime: Send + Sync {
+    fn kind(&self) -> RuntimeKind;
+
+    /// Get the skill's environment ready (create it, install dependencies).
+    /// Cheap when nothing changed since the last call.
+    async fn prepare(&self, skill: RuntimeSkill<'_>) -> Result<(), ExecutionError>;
+
+    /// The command running `script`, which sees `env` as its environment
+    /// variables
+    fn command(
+        &self,
+        skill: RuntimeSkill<'_>,
+        script: &ScriptDefinition,
+        env: Vec<(String, OsString)>,
+    ) -> Result<RuntimeCommand, ExecutionError>;
+}
+
+/// The runtime for `kind`, keeping its state under `runtimes_dir`
+pub fn runtime_for(
+    kind: RuntimeKind,
+    runtimes_dir: &Path,
+    config: &ExecutionConfig,
+) -> Box<dyn ExecutionRuntime> {
+    match kind {
+        RuntimeKind::System => Box::new(SystemRuntime),
+        RuntimeKind::PythonVenv => Box::new(PythonVenvRuntime::new(runtimes_dir.join("python"))),
+        RuntimeKind::Node => Box::new(NodeRuntime::new(runtimes_dir.join("node"))),
+        RuntimeKind::Docker { image } => Box::new(DockerRuntime {
+            image,
+            network: config.network_policy.clone(),
+            max_memory_mb: config.max_memory_mb,
+        }),
+    }
+}
+
+/// Default runtimes directory: `<skills dir>/.fastskill/runtimes`
+pub fn default_runtimes_dir(skills_dir: &Path) -> PathBuf {
+    skills_dir.join(".fastskill").join("runtimes")
+}
+
+/// Interpreter and script path, run on the host
+fn interpreter_command(
+    interpreter: impl Into<OsString>,
+    script: &ScriptDefinition,
+    env: Vec<(String, OsString)>,
+) -> RuntimeCommand {
+    RuntimeCommand {
+        env,
+        working_directory: script.working_directory.clone(),
+        ..RuntimeCommand::new(interpreter).arg(&script.path)
+    }
+}
+
+/// `dir` first on the inherited PATH
+fn path_with(dir: &Path) -> OsString {
+    let mut paths = vec![dir.to_path_buf()];
+    if let Some(path) = std::env::var_os("PATH") {
+        paths.extend(std::env::split_paths(&path));
+    }
+    std::env::join_paths(paths).unwrap_or_else(|_| dir.as_os_str().to_os_string())
+}
+
+/// Hash of the dependency files in `files` that exist; `None` when none do
+async fn deps_hash(files: &[PathBuf]) -> Result<Option<String>, ExecutionError> {
+    let mut hasher = Sha256::new();
+    let mut any = false;
+    for file in files {
+        match tokio::fs::read(file).await {
+            Ok(content) => {
+                any = true;
+                hasher.update(
+                    file.file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .as_bytes(),
+                );
+                hasher.update(&content);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(any.then(|| format!("{:x}", hasher.finalize())))
+}
+
+async fn is_current(env_dir: &Path, hash: &str) -> bool {
+    tokio::fs::read_to_string(env_dir.join(STAMP_FILE))
+        .await
+        .is_ok_and(|stamp| stamp.trim() == hash)
+}
+
+/// Run a setup step to completion; its output becomes the error on failure
+async fn run_setup(mut cmd: TokioCommand, what: &str) -> Result<(), ExecutionError> {
+    let output = cmd
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| ExecutionError::Failed(format!("{} failed to start: {}", what, e)))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    Err(ExecutionError::Failed(format!(
+        "{} failed: {}",
+        what,
+        String::from_utf8_lossy(&output.stderr).trim()
+    )))
+}
+
+/// Serializes environment setup, so two scripts of a skill starting together
+/// do not both build its virtualenv
+fn setup_lock() -> &'static Mutex<()> {
+    static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+    LOCK.get_or_init(|| Mutex::new(()))
+}
+
+/// The interpreter for the script's language, from PATH
+pub struct SystemRuntime;
+
+#[async_trait]
+impl ExecutionRuntime for SystemRuntime {
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::System
+    }
+
+    async fn prepare(&self, _skill: RuntimeSkill<'_>) -> Result<(), ExecutionError> {
+        Ok(())
+    }
+
+    fn command(
+        &self,
+        _skill: RuntimeSkill<'_>,
+        script: &ScriptDefinition,
+        env: Vec<(String, OsString)>,
+    ) -> Result<RuntimeCommand, ExecutionError> {
+        Ok(interpreter_command(
+            script.language.get_command(),
+            script,
+            env,
+        ))
+    }
+}
+
+/// One virtualenv per skill with its `requirements.txt` installed
+pub struct PythonVenvRuntime {
+    venvs_dir: PathBuf,
+}
+
+impl PythonVenvRuntime {
+    pub fn new(venvs_dir: PathBuf) -> Self {
+        Self { venvs_dir }
+    }
+
+    pub fn venv_dir(&self, skill_id: &str) -> PathBuf {
+        self.venvs_dir.join(skill_id)
+    }
+
+    fn bin_dir(venv: &Path) -> PathBuf {
+        if cfg!(windows) {
+            venv.join("Scripts")
+        } else {
+            venv.join("bin")
+        }
+    }
+
+    fn python(venv: &Path) -> PathBuf {
+        Self::bin_dir(venv).join(if cfg!(windows) {
+            "python.exe"
+        } else {
+            "python"
+        })
+    }
+}
+
+#[async_trait]
+impl ExecutionRuntime for PythonVenvRuntime {
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::PythonVenv
+    }
+
+    async fn prepare(&self, skill: RuntimeSkill<'_>) -> Result<(), ExecutionError> {
+        let _guard = setup_lock().lock().await;
+        let venv = self.venv_dir(skill.id);
+        let requirements = skill.dir.join("requirements.txt");
+        let hash = deps_hash(std::slice::from_ref(&requirements))
+            .await?
+            .unwrap_or_default();
+        let python = Self::python(&venv);
+        if python.exists() && is_current(&venv, &hash).await {
+            return Ok(());
+        }
+
+        if !python.exists() {
+            tokio::fs::create_dir_all(&self.venvs_dir).await?;
+            let mut created = Err(ExecutionError::Failed(
+                "Python was not found on PATH (tried python3, python)".to_string(),
+            ));
+            for interpreter in ["python3", "python"] {
+                let mut cmd = TokioCommand::new(interpreter);
+                cmd.arg("-m").arg("venv").arg(&venv);
+                created =
+                    run_setup(cmd, &format!("Creating the virtualenv for '{}'", skill.id)).await;
+                if created.is_ok() {
+                    break;
+                }
+            }
+            created?;
+        }
+        if requirements.is_file() {
+            let mut cmd = TokioCommand::new(&python);
+            cmd.args(["-m", "pip", "install", "--disable-pip-version-check", "-r"])
+                .arg(&requirements);
+            run_setup(
+                cmd,
+                &format!("Installing requirements.txt for '{}'", skill.id),
+            )
+            .await?;
+        }
+        tokio::fs::write(venv.join(STAMP_FILE), hash).await?;
+        Ok(())
+    }
+
+    fn command(
+        &self,
+        skill: RuntimeSkill<'_>,
+        script: &ScriptDefinition,
+        mut env: Vec<(String, OsString)>,
+    ) -> Result<RuntimeCommand, ExecutionError> {
+        let venv = self.venv_dir(skill.id);
+        env.push(("VIRTUAL_ENV".to_string(), venv.clone().into_os_string()));
+        env.push(("PATH".to_string(), path_with(&Self::bin_dir(&venv))));
+        Ok(match script.language {
+            ScriptLanguage::Python => interpreter_command(Self::python(&venv), script, env),
+            // Other scripts find the virtualenv's python first on PATH
+            _ => interpreter_command(script.language.get_command(), script, env),
+        })
+    }
+}
+
+/// `node`, with `package.json` dependencies installed per skill
+pub struct NodeRuntime {
+    modules_dir: PathBuf,
+}
+
+impl NodeRuntime {
+    const MANIFESTS: [&'static str; 2] = ["package.json", "package-lock.json"];
+
+    pub fn new(modules_dir: PathBuf) -> Self {
+        Self { modules_dir }
+    }
+
+    pub fn install_dir(&self, skill_id: &str) -> PathBuf {
+        self.modules_dir.join(skill_id)
+    }
+}
+
+#[async_trait]
+impl ExecutionRuntime for NodeRuntime {
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::Node
+    }
+
+    async fn prepare(&self, skill: RuntimeSkill<'_>) -> Result<(), ExecutionError> {
+        let manifests: Vec<PathBuf> = Self::MANIFESTS.iter().map(|f| skill.dir.join(f)).collect();
+        if !manifests[0].is_file() {
+            return Ok(());
+        }
+        let _guard = setup_lock().lock().await;
+        let install_dir = self.install_dir(skill.id);
+        let hash = deps_hash(&manifests).await?.unwrap_or_default();
+        if install_dir.join("node_modules").is_dir() && is_current(&install_dir, &hash).await {
+            return Ok(());
+        }
+
+        // Install next to copies of the manifests, keeping the skill
+        // directory (which may be a link to the author's checkout) untouched
+        tokio::fs::create_dir_all(&install_dir).await?;
+        let mut locked = false;
+        for (manifest, name) in manifests.iter().zip(Self::MANIFESTS) {
+            if manifest.is_file() {
+                tokio::fs::copy(manifest, install_dir.join(name)).await?;
+                locked = name == "package-lock.json";
+            } else {
+                let _ = tokio::fs::remove_file(install_dir.join(name)).await;
+            }
+        }
+        let npm = if cfg!(windows) { "npm.cmd" } else { "npm" };
+        let mut cmd = TokioCommand::new(npm);
+        cmd.arg(if locked { "ci" } else { "install" })
+            .args(["--omit=dev", "--no-audit", "--no-fund"])
+            .current_dir(&install_dir);
+        run_setup(
+            cmd,
+            &format!("Installing package.json dependencies for '{}'", skill.id),
+        )
+        .await?;
+        tokio::fs::write(install_dir.join(STAMP_FILE), hash).await?;
+        Ok(())
+    }
+
+    fn command(
+        &self,
+        skill: RuntimeSkill<'_>,
+        script: &ScriptDefinition,
+        mut env: Vec<(String, OsString)>,
+    ) -> Result<RuntimeCommand, ExecutionError> {
+        let modules = self.install_dir(skill.id).join("node_modules");
+        env.push(("PATH".to_string(), path_with(&modules.join(".bin"))));
+        env.push(("NODE_PATH".to_string(), modules.into_os_string()));
+        Ok(interpreter_command(
+            script.language.get_command(),
+            script,
+            env,
+        ))
+    }
+}
+
+/// A `docker run --rm` container per script
+pub struct DockerRuntime {
+    image: Option<String>,
+    network: NetworkPolicy,
+    max_memory_mb: usize,
+}
+
+impl DockerRuntime {
+    fn default_image(language: &ScriptLanguage) -> Result<&'static str, ExecutionError> {
+        match language {
+            ScriptLanguage::Python => Ok("python:3-slim"),
+            ScriptLanguage::NodeJS => Ok("node:lts-slim"),
+            ScriptLanguage::Shell => Ok("alpine:3"),
+            ScriptLanguage::Rust => Err(ExecutionError::Failed(
+                "no default Docker image for Rust scripts; name one with \
+                 'execution_environment: docker:<image>'"
+                    .to_string(),
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl ExecutionRuntime for DockerRuntime {
+    fn kind(&self) -> RuntimeKind {
+        RuntimeKind::Docker {
+            image: self.image.clone(),
+        }
+    }
+
+    async fn prepare(&self, _skill: RuntimeSkill<'_>) -> Result<(), ExecutionError> {
+        // The image is pulled by `docker run` on first use
+        Ok(())
+    }
+
+    fn command(
+        &self,
+        skill: RuntimeSkill<'_>,
+        script: &ScriptDefinition,
+        env: Vec<(String, OsString)>,
+    ) -> Result<RuntimeCommand, ExecutionError> {
+        let skill_dir = skill.dir.canonicalize()?;
+        let script_path = script.path.canonicalize()?;
+        let relative = script_path.strip_prefix(&skill_dir).map_err(|_| {
+            ExecutionError::SecurityViolation(format!(
+                "Script '{}' is outside the skill directory and cannot be mounted",
+                script.path.display()
+            ))
+        })?;
+        let image = match &self.image {
+            Some(image) => image.as_str(),
+            None => Self::default_image(&script.language)?,
+        };
+
+        let mut mount = skill_dir.into_os_string();
+        mount.push(format!(":{}:ro", CONTAINER_SKILL_DIR));
+        // Killing the `docker run` client leaves the container running; the
+        // name lets a stopped run remove it
+        let name = format!("fastskill-{}", uuid::Uuid::new_v4().simple());
+        let mut command = RuntimeCommand::new("docker")
+            .arg("run")
+            .arg("--rm")
+            .arg("--name")
+            .arg(&name)
+            // An init process as PID 1 hands the SIGTERM that `docker run`
+            // proxies on timeout to the script, which would ignore it as PID 1
+            .arg("--init")
+            .arg("--volume")
+            .arg(mount)
+            .arg("--workdir")
+            .arg(CONTAINER_SKILL_DIR)
+            .arg("--memory")
+            .arg(format!("{}m", self.max_memory_mb));
+        // Docker cannot filter by domain, so only the no-network policies
+        // narrow the default bridge network
+        if matches!(self.network, NetworkPolicy::None | NetworkPolicy::Localhost) {
+            command = command.arg("--network").arg("none");
+        }
+        // Values are read from the docker process's environment, which keeps
+        // them out of the command line
+        for (key, _) in &env {
+            command = command.arg("--env").arg(key);
+        }
+        let container_script = Path::new(CONTAINER_SKILL_DIR).join(relative);
+        command = command
+            .arg(image)
+            .arg(script.language.get_command())
+            .arg(container_script.to_string_lossy().replace('\\', "/"));
+        command.env = env;
+        command.on_stop = Some(Box::new(
+            RuntimeCommand::new("docker")
+                .arg("rm")
+                .arg("--force")
+                .arg(name),
+        ));
+        Ok(command)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn script(path: PathBuf, language: ScriptLanguage) -> ScriptDefinition {
+        ScriptDefinition {
+            path,
+            content: None,
+            language,
+            parameters: HashMap::new(),
+            working_directory: None,
+        }
+    }
+
+    #[test]
+    fn test_runtime_kind_parse() {
+        assert_eq!(RuntimeKind::parse(None).unwrap(), RuntimeKind::System);
+        assert_eq!(
+            RuntimeKind::parse(Some("Python")).unwrap(),
+            RuntimeKind::PythonVenv
+        );
+        assert_eq!(RuntimeKind::parse(Some("node")).unwrap(), RuntimeKind::Node);
+        assert_eq!(
+            RuntimeKind::parse(Some("docker:python:3.12-slim")).unwrap(),
+            RuntimeKind::Docker {
+                image: Some("python:3.12-slim".to_string())
+            }
+        );
+        assert_eq!(
+            RuntimeKind::parse(Some("docker:python:3.12-slim"))
+                .unwrap()
+                .to_string(),
+            "docker:python:3.12-slim"
+        );
+        assert!(matches!(
+            RuntimeKind::parse(Some("jvm")),
+            Err(ExecutionError::UnknownRuntime(_))
+        ));
+    }
+
+    #[test]
+    fn test_python_venv_command_uses_skill_venv() {
+        let runtimes = TempDir::new().unwrap();
+        let runtime = PythonVenvRuntime::new(runtimes.path().join("python"));
+        let skill = RuntimeSkill {
+            id: "pdf",
+            dir: Path::new("/skills/pdf"),
+        };
+        let cmd = runtime
+            .command(
+                skill,
+                &script(
+                    PathBuf::from("/skills/pdf/scripts/run.py"),
+                    ScriptLanguage::Python,
+                ),
+                vec![],
+            )
+            .unwrap();
+        assert!(Path::new(&cmd.program).starts_with(runtime.venv_dir("pdf")));
+        assert!(cmd.env.iter().any(|(key, _)| key == "VIRTUAL_ENV"));
+    }
+
+    #[test]
+    fn test_docker_command_mounts_skill_read_only() {
+        let skill_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(skill_dir.path().join("scripts")).unwrap();
+        let script_path = skill_dir.path().join("scripts").join("run.py");
+        std::fs::write(&script_path, "print('hi')").unwrap();
+        let runtime = DockerRuntime {
+            image: None,
+            network: NetworkPolicy::None,
+            max_memory_mb: 256,
+        };
+        let skill = RuntimeSkill {
+            id: "pdf",
+            dir: skill_dir.path(),
+        };
+
+        let cmd = runtime
+            .command(
+                skill,
+                &script(script_path, ScriptLanguage::Python),
+                vec![("PARAM_NAME".to_string(), OsString::from("secret"))],
+            )
+            .unwrap();
+        let args: Vec<String> = cmd
+            .args
+            .iter()
+            .map(|a| a.to_string_lossy().to_string())
+            .collect();
+        assert_eq!(cmd.program, "docker");
+        assert!(args.iter().any(|a| a.ends_with(":/skill:ro")));
+        assert!(args.iter().any(|a| a == "--init"));
+        let name_at = args.iter().position(|a| a == "--name").unwrap() + 1;
+        let name = &args[name_at];
+        assert!(name.starts_with("fastskill-"));
+        let stop = cmd.on_stop.as_ref().unwrap();
+        assert_eq!(stop.program, "docker");
+        assert_eq!(stop.args, ["rm", "--force", name.as_str()]);
+        assert!(args.windows(2).any(|w| w == ["--network", "none"]));
+        assert!(args.windows(2).any(|w| w == ["--env", "PARAM_NAME"]));
+        assert!(!args.iter().any(|a| a.contains("secret")));
+        assert_eq!(
+            args[args.len() - 3..],
+            ["python:3-slim", "python3", "/skill/scripts/run.py"]
+        );
+
+        // A script outside the skill cannot be mounted
+        let outside = TempDir::new().unwrap();
+        let outside_script = outside.path().join("run.py");
+        std::fs::write(&outside_script, "").unwrap();
+        assert!(matches!(
+            runtime.command(
+                skill,
+                &script(outside_script, ScriptLanguage::Python),
+                vec![]
+            ),
+            Err(ExecutionError::SecurityViolation(_))
+        ));
+    }
+}
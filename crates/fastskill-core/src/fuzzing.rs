@@ -0,0 +1,38 @@
+//! Entry points for the cargo-fuzz targets in `crates/fastskill-core/fuzz`
+//!
+//! Each function feeds arbitrary bytes to a parser that handles user or
+//! network content. They return nothing: the property under test is that the
+//! parser answers with a value or a structured error and never panics.
+//! Only compiled with the `fuzzing` feature.
+
+use crate::core::metadata::parse_yaml_frontmatter;
+use crate::core::registry::client::parse_index_entries;
+use crate::core::registry_index::parse_index_lines;
+use crate::storage::zip::ZipHandler;
+use std::io::Cursor;
+
+/// `SKILL.md` frontmatter. Non-UTF-8 input is decoded lossily, as a skill
+/// loader reading untrusted files would see it after validation.
+pub fn frontmatter(data: &[u8]) {
+    let content = String::from_utf8_lossy(data);
+    let _ = parse_yaml_frontmatter(&content);
+}
+
+/// Skill package extraction into a fresh temporary directory.
+pub fn zip_extract(data: &[u8]) {
+    let Ok(dest) = tempfile::TempDir::new() else {
+        return;
+    };
+    let Ok(handler) = ZipHandler::new() else {
+        return;
+    };
+    let _ = handler.extract_reader_to_dir(Cursor::new(data), dest.path());
+}
+
+/// Registry index files, as read from disk by the server and fetched over
+/// HTTP by the client.
+pub fn registry_index(data: &[u8]) {
+    let content = String::from_utf8_lossy(data);
+    let _ = parse_index_lines(&content, "fuzz/skill");
+    let _ = parse_index_entries(&content);
+}
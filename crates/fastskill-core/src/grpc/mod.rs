@@ -0,0 +1,515 @@
+//! gRPC interface mirroring the REST API (`grpc` feature)
+//!
+//! Serves the `SkillManagement`, `Discovery` and `ToolCalling` services from
+//! `proto/fastskill/v1/fastskill.proto`. Each RPC resolves the caller's client
+//! profile from request metadata the same way the REST server does from
+//! headers, then calls the shared [`facade`](crate::http::facade), so both
+//! transports list, search and filter skills identically. Mutating RPCs sit
+//! behind the same write gate and audit log as the REST write routes, and
+//! every RPC needs the same API-key scope as its REST route (see
+//! [`crate::http::auth`]).
+
+use crate::core::api_keys::ApiKeyScope;
+use crate::core::audit::{AuditDetails, AuditEntry};
+use crate::core::service::ClientProfile;
+use crate::http::auth::{authorize, AuthenticatedKey};
+use crate::http::client_profile::resolve_client_profile;
+use crate::http::errors::{HttpError, HttpResult};
+use crate::http::facade;
+use crate::http::handlers::AppState;
+use crate::http::models::{
+    InstallSkillRequest as RestInstallRequest, ListSkillsQuery, SearchRequest, SkillResponse,
+};
+use std::future::Future;
+use std::net::SocketAddr;
+use tonic::{Request, Response, Status};
+
+/// Code generated from `fastskill.proto`
+#[allow(clippy::all, clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+pub mod proto {
+    tonic::include_proto!("fastskill.v1");
+}
+
+use proto::discovery_server::{Discovery, DiscoveryServer};
+use proto::skill_management_server::{SkillManagement, SkillManagementServer};
+use proto::tool_calling_server::{ToolCalling, ToolCallingServer};
+
+impl From<HttpError> for Status {
+    fn from(err: HttpError) -> Self {
+        let message = err.to_string();
+        match err {
+            HttpError::Unauthorized(_) => Status::unauthenticated(message),
+            HttpError::Forbidden(_) => Status::permission_denied(message),
+            HttpError::BadRequest(_) | HttpError::ValidationError(_) => {
+                Status::invalid_argument(message)
+            }
+            HttpError::NotFound(_) => Status::not_found(message),
+            HttpError::Conflict(_) => Status::already_exists(message),
+            HttpError::ServiceUnavailable(_) => Status::unavailable(message),
+            HttpError::InternalServerError(_)
+            | HttpError::ServiceError(_)
+            | HttpError::Integrity(_) => Status::internal(message),
+        }
+    }
+}
+
+impl From<SkillResponse> for proto::Skill {
+    fn from(skill: SkillResponse) -> Self {
+        Self {
+            id: skill.id,
+            name: skill.name,
+            description: skill.description,
+            metadata_json: skill.metadata.to_string(),
+            created_at: skill.created_at,
+            updated_at: skill.updated_at,
+        }
+    }
+}
+
+/// Implements every FastSkill gRPC service over the shared server state.
+#[derive(Clone)]
+pub struct GrpcApi {
+    state: AppState,
+}
+
+impl GrpcApi {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    fn profile<T>(&self, request: &Request<T>) -> Option<&ClientProfile> {
+        let headers = request.metadata().clone().into_headers();
+        resolve_client_profile(self.state.service.config(), &headers)
+    }
+
+    /// Check the request's API key against `scope` when keys are configured.
+    fn authorize<T>(
+        &self,
+        request: &Request<T>,
+        scope: ApiKeyScope,
+    ) -> HttpResult<Option<AuthenticatedKey>> {
+        let headers = request.metadata().clone().into_headers();
+        authorize(&self.state, &headers, scope)
+    }
+
+    /// Run a mutating RPC behind the API-key check and the write gate and record it in the audit
+    /// log, as the REST write routes are. `details` are known up front; `op` reports the rest
+    /// (e.g. the installed version) alongside its result.
+    async fn write<T, R, F>(
+        &self,
+        request: &Request<T>,
+        path: &str,
+        action: &str,
+        details: AuditDetails,
+        op: F,
+    ) -> Result<R, Status>
+    where
+        F: Future<Output = HttpResult<(R, AuditDetails)>>,
+    {
+        let key = self.authorize(request, ApiKeyScope::WriteSkills);
+        let result = match &key {
+            Err(e) => Err(e.clone()),
+            Ok(_) if !self.state.enable_write => Err(HttpError::Forbidden(
+                "write operations disabled; start server with --enable-write".to_string(),
+            )),
+            Ok(_) => op.await,
+        };
+
+        if let Some(log) = self.state.audit.clone() {
+            let entry = AuditEntry {
+                timestamp: chrono::Utc::now(),
+                actor: match key {
+                    Ok(Some(key)) => format!("key:{}", key.name),
+                    _ => self
+                        .profile(request)
+                        .map(|p| p.name.clone())
+                        .unwrap_or_else(|| "anonymous".to_string()),
+                },
+                remote_addr: request.remote_addr().map(|addr| addr.to_string()),
+                action: action.to_string(),
+                target: None,
+                version: None,
+                source: None,
+                method: "POST".to_string(),
+                path: path.to_string(),
+                status: result
+                    .as_ref()
+                    .map_or_else(|e| e.status_code().as_u16(), |_| 200),
+                prev_hash: None,
+                hash: None,
+            }
+            .with_details(details);
+            let entry = match &result {
+                Ok((_, reported)) => entry.with_details(reported.clone()),
+                Err(_) => entry,
+            };
+            match tokio::task::spawn_blocking(move || log.append(&entry)).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => tracing::error!("Failed to write audit entry: {}", e),
+                Err(e) => tracing::error!("Audit writer task failed: {}", e),
+            }
+        }
+        result.map(|(value, _)| value).map_err(Status::from)
+    }
+}
+
+#[tonic::async_trait]
+impl SkillManagement for GrpcApi {
+    async fn list_skills(
+        &self,
+        request: Request<proto::ListSkillsRequest>,
+    ) -> Result<Response<proto::ListSkillsResponse>, Status> {
+        self.authorize(&request, ApiKeyScope::ReadSkills)?;
+        let query = ListSkillsQuery {
+            limit: request.get_ref().limit.map(|limit| limit as usize),
+            after: request.get_ref().after.clone(),
+        };
+        let page = facade::list_skills(&self.state, self.profile(&request), &query).await?;
+        Ok(Response::new(proto::ListSkillsResponse {
+            skills: page.skills.into_iter().map(proto::Skill::from).collect(),
+            total: page.total as u64,
+            next_cursor: page.next_cursor,
+        }))
+    }
+
+    async fn get_skill(
+        &self,
+        request: Request<proto::GetSkillRequest>,
+    ) -> Result<Response<proto::Skill>, Status> {
+        self.authorize(&request, ApiKeyScope::ReadSkills)?;
+        let skill =
+            facade::get_skill(&self.state, self.profile(&request), &request.get_ref().id).await?;
+        Ok(Response::new(skill.into()))
+    }
+
+    async fn install_skill(
+        &self,
+        request: Request<proto::InstallSkillRequest>,
+    ) -> Result<Response<proto::InstallSkillResponse>, Status> {
+        let install = RestInstallRequest {
+            origin: request.get_ref().origin.clone(),
+            groups: request.get_ref().groups.clone(),
+        };
+        let details = AuditDetails {
+            source: Some(install.origin.clone()),
+            ..Default::default()
+        };
+        let installed = self
+            .write(
+                &request,
+                "/fastskill.v1.SkillManagement/InstallSkill",
+                "skill.install",
+                details,
+                async {
+                    let installed = facade::install_skill(&self.state, install).await?;
+                    let reported = AuditDetails {
+                        target: Some(installed.id.clone()),
+                        version: Some(installed.resolved_version.clone()),
+                        source: None,
+                    };
+                    Ok::<_, HttpError>((installed, reported))
+                },
+            )
+            .await?;
+        Ok(Response::new(proto::InstallSkillResponse {
+            id: installed.id,
+            resolved_version: installed.resolved_version,
+            reindexed: installed.reindexed,
+            renamed_from: installed.renamed_from,
+        }))
+    }
+
+    async fn delete_skill(
+        &self,
+        request: Request<proto::DeleteSkillRequest>,
+    ) -> Result<Response<proto::DeleteSkillResponse>, Status> {
+        let id = request.get_ref().id.clone();
+        self.write(
+            &request,
+            "/fastskill.v1.SkillManagement/DeleteSkill",
+            "skill.delete",
+            AuditDetails {
+                target: Some(id.clone()),
+                ..Default::default()
+            },
+            async {
+                facade::delete_skill(&self.state, &id).await?;
+                Ok::<_, HttpError>(((), AuditDetails::default()))
+            },
+        )
+        .await?;
+        Ok(Response::new(proto::DeleteSkillResponse {}))
+    }
+}
+
+#[tonic::async_trait]
+impl Discovery for GrpcApi {
+    async fn search_skills(
+        &self,
+        request: Request<proto::SearchSkillsRequest>,
+    ) -> Result<Response<proto::SearchSkillsResponse>, Status> {
+        self.authorize(&request, ApiKeyScope::ReadSkills)?;
+        let search = SearchRequest {
+            query: request.get_ref().query.clone(),
+            limit: request.get_ref().limit,
+            semantic: request.get_ref().semantic,
+        };
+        let found = facade::search_skills(&self.state, self.profile(&request), search).await?;
+        Ok(Response::new(proto::SearchSkillsResponse {
+            skills: found
+                .skills
+                .into_iter()
+                .map(|m| proto::SkillMatch {
+                    skill: Some(m.skill.into()),
+                    score: m.score,
+                    relevance: m.relevance,
+                })
+                .collect(),
+            query: found.query,
+        }))
+    }
+}
+
+#[tonic::async_trait]
+impl ToolCalling for GrpcApi {
+    async fn list_tools(
+        &self,
+        request: Request<proto::ListToolsRequest>,
+    ) -> Result<Response<proto::ListToolsResponse>, Status> {
+        self.authorize(&request, ApiKeyScope::ExecuteTools)?;
+        let listed = facade::list_tools(
+            &self.state,
+            self.profile(&request),
+            request.get_ref().capability.as_deref(),
+        )
+        .await?;
+        Ok(Response::new(proto::ListToolsResponse {
+            tools: listed
+                .tools
+                .into_iter()
+                .map(|tool| proto::Tool {
+                    name: tool.name,
+                    description: tool.description,
+                    capabilities: tool.capabilities,
+                    input_schema_json: tool.input_schema.to_string(),
+                    output_schema_json: tool.output_schema.map(|schema| schema.to_string()),
+                    skill_id: tool.skill_id,
+                    skill_version: tool.skill_version,
+                })
+                .collect(),
+            errors: listed
+                .errors
+                .into_iter()
+                .map(|e| proto::ToolError {
+                    skill_id: e.skill_id,
+                    message: e.message,
+                })
+                .collect(),
+        }))
+    }
+}
+
+/// Serve the gRPC services on `addr` until the process stops.
+pub async fn serve(state: AppState, addr: SocketAddr) -> Result<(), tonic::transport::Error> {
+    let api = GrpcApi::new(state);
+    tonic::transport::Server::builder()
+        .add_service(SkillManagementServer::new(api.clone()))
+        .add_service(DiscoveryServer::new(api.clone()))
+        .add_service(ToolCallingServer::new(api))
+        .serve(addr)
+        .await
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::api_keys::ApiKeyStore;
+    use crate::core::service::HttpServerConfig;
+    use crate::http::auth::ApiKeys;
+    use crate::{FastSkillService, ServiceConfig};
+    use std::fs;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn write_skill(root: &std::path::Path, id: &str, extra: &str) {
+        let dir = root.join(id);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            format!(
+                "---\nname: {id}\ndescription: The {id} skill\nversion: 1.0.0\n{extra}---\n# {id}\n"
+            ),
+        )
+        .unwrap();
+    }
+
+    /// State over two skills: public `alpha` and private `beta` owned by `owner`.
+    async fn state(dir: &TempDir) -> AppState {
+        // The indexer skips hidden directories, and TempDir names start with `.`
+        let store = dir.path().join("store");
+        write_skill(&store, "alpha", "");
+        write_skill(
+            &store,
+            "beta",
+            "owner: owner\nvisibility: private\ntools:\n  - name: beta_run\n    description: Run beta\n    input_schema:\n      type: object\n",
+        );
+        let config = ServiceConfig {
+            skill_storage_path: store,
+            http_server: Some(HttpServerConfig {
+                client_profiles: vec![ClientProfile {
+                    name: "owner".to_string(),
+                    api_key: Some("owner-key".to_string()),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let mut service = FastSkillService::new(config).await.unwrap();
+        service.initialize().await.unwrap();
+        AppState::new(Arc::new(service)).unwrap()
+    }
+
+    fn keyed<T>(message: T) -> Request<T> {
+        let mut request = Request::new(message);
+        request
+            .metadata_mut()
+            .insert("x-api-key", "owner-key".parse().unwrap());
+        request
+    }
+
+    #[tokio::test]
+    async fn test_list_and_get_apply_visibility() {
+        let dir = TempDir::new().unwrap();
+        let api = GrpcApi::new(state(&dir).await);
+
+        let anonymous = api
+            .list_skills(Request::new(proto::ListSkillsRequest::default()))
+            .await
+            .unwrap()
+            .into_inner();
+        let ids: Vec<_> = anonymous.skills.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["alpha"]);
+        assert_eq!(anonymous.total, 2);
+        let metadata: serde_json::Value =
+            serde_json::from_str(&anonymous.skills[0].metadata_json).unwrap();
+        assert_eq!(metadata["version"], "1.0.0");
+
+        let owner = api
+            .list_skills(keyed(proto::ListSkillsRequest::default()))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(owner.skills.len(), 2);
+
+        let hidden = api
+            .get_skill(Request::new(proto::GetSkillRequest {
+                id: "beta".to_string(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(hidden.code(), tonic::Code::NotFound);
+        let beta = api
+            .get_skill(keyed(proto::GetSkillRequest {
+                id: "beta".to_string(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(beta.name, "beta");
+    }
+
+    #[tokio::test]
+    async fn test_search_and_tools_match_rest_facade() {
+        let dir = TempDir::new().unwrap();
+        let api = GrpcApi::new(state(&dir).await);
+
+        let found = api
+            .search_skills(keyed(proto::SearchSkillsRequest {
+                query: "skill".to_string(),
+                limit: None,
+                semantic: Some(false),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(found.skills.len(), 2);
+        assert!(found.skills.iter().all(|m| m.relevance == "keyword"));
+
+        let invalid = api
+            .search_skills(Request::new(proto::SearchSkillsRequest::default()))
+            .await
+            .unwrap_err();
+        assert_eq!(invalid.code(), tonic::Code::InvalidArgument);
+
+        let tools = api
+            .list_tools(keyed(proto::ListToolsRequest::default()))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(tools.tools.len(), 1);
+        assert_eq!(tools.tools[0].name, "beta_run");
+        assert_eq!(tools.tools[0].skill_id, "beta");
+        let schema: serde_json::Value =
+            serde_json::from_str(&tools.tools[0].input_schema_json).unwrap();
+        assert_eq!(schema["type"], "object");
+
+        let anonymous = api
+            .list_tools(Request::new(proto::ListToolsRequest::default()))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(anonymous.tools.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_writes_are_gated() {
+        let dir = TempDir::new().unwrap();
+        let api = GrpcApi::new(state(&dir).await);
+
+        let err = api
+            .delete_skill(Request::new(proto::DeleteSkillRequest {
+                id: "alpha".to_string(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+        assert!(dir.path().join("store/alpha/SKILL.md").exists());
+    }
+
+    #[tokio::test]
+    async fn test_api_keys_apply_to_rpcs() {
+        let dir = TempDir::new().unwrap();
+        let keys_path = ApiKeyStore::default_path(&dir.path().join("store"));
+        let api = GrpcApi::new(
+            state(&dir)
+                .await
+                .with_api_keys(Arc::new(ApiKeys::new(keys_path.clone()))),
+        );
+        let mut store = ApiKeyStore::load(&keys_path).unwrap();
+        let (_, secret) = store.create("reader", &[ApiKeyScope::ReadSkills]).unwrap();
+        store.save().unwrap();
+        let with_key = |key: &str| {
+            let mut request = Request::new(proto::ListSkillsRequest::default());
+            request
+                .metadata_mut()
+                .insert("x-api-key", key.parse().unwrap());
+            request
+        };
+
+        let err = api
+            .list_skills(Request::new(proto::ListSkillsRequest::default()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unauthenticated);
+        assert!(api.list_skills(with_key(&secret)).await.is_ok());
+
+        let mut tools = Request::new(proto::ListToolsRequest::default());
+        tools
+            .metadata_mut()
+            .insert("x-api-key", secret.parse().unwrap());
+        let err = api.list_tools(tools).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+    }
+}
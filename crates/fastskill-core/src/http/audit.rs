@@ -0,0 +1,218 @@
+//! Audit middleware for write routes
+//!
+//! Layered outside the write gate so refused attempts are recorded too.
+//! Handlers that know more about the skill they changed (its version, where
+//! it came from) add an [`AuditDetails`] to their response extensions.
+
+use crate::core::audit::{AuditDetails, AuditEntry};
+use crate::http::auth::AuthenticatedKey;
+use crate::http::client_profile::resolve_client_profile;
+use crate::http::handlers::AppState;
+use axum::{
+    extract::{ConnectInfo, MatchedPath, Request, State},
+    http::Method,
+    middleware::Next,
+    response::Response,
+};
+use std::net::SocketAddr;
+
+/// Map a write route to its audit action name and the skill id it targets.
+///
+/// `matched` is the route template (`/api/v1/skills/{id}`), `path` the
+/// concrete request path; matching is on the suffix so the version prefix
+/// doesn't matter.
+pub fn audit_action(method: &Method, matched: &str, path: &str) -> (String, Option<String>) {
+    let target = || {
+        path.rsplit('/')
+            .next()
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+    let action = match (method.as_str(), matched) {
+        ("DELETE", m) if m.ends_with("/manifest/skills/{id}") => "manifest.remove",
+        ("PUT", m) if m.ends_with("/manifest/skills/{id}") => "manifest.update",
+        ("POST", m) if m.ends_with("/manifest/skills") => "manifest.add",
+        ("DELETE", m) if m.ends_with("/skills/{id}") => "skill.delete",
+        ("POST", m) if m.ends_with("/skills/install") => "skill.install",
+        ("POST", m) if m.ends_with("/skills/update") || m.ends_with("/skills/upgrade") => {
+            "skill.update"
+        }
+        ("POST", m) if m.ends_with("/reindex/{id}") => "index.reindex",
+        ("POST", m) if m.ends_with("/reindex") => return ("index.reindex".to_string(), None),
+        ("POST", m) if m.ends_with("/registry/refresh") => {
+            return ("registry.refresh".to_string(), None)
+        }
+        ("POST", m) if m.ends_with("/registry/index/yank") => {
+            return ("registry.yank".to_string(), None)
+        }
+        ("POST", m) if m.ends_with("/registry/index/unyank") => {
+            return ("registry.unyank".to_string(), None)
+        }
+        ("POST", m) if m.ends_with("/admin/cache/clear") => {
+            return ("admin.cache_clear".to_string(), None)
+        }
+        ("PUT", m) if m.ends_with("/admin/hot-reload") => {
+            return ("admin.hot_reload".to_string(), None)
+        }
+        ("POST", m) if m.ends_with("/admin/drain") => return ("admin.drain".to_string(), None),
+        ("DELETE", m) if m.ends_with("/admin/drain") => return ("admin.resume".to_string(), None),
+        ("POST", m) if m.ends_with("/jobs/{id}/cancel") => {
+            let job = path.rsplit('/').nth(1).map(str::to_string);
+            return ("job.cancel".to_string(), job);
+        }
+        _ => return (format!("{} {}", method, matched), None),
+    };
+    let target = if matched.ends_with("{id}") {
+        target()
+    } else {
+        None
+    };
+    (action.to_string(), target)
+}
+
+/// Record every request that reaches a write route, with its final status.
+pub async fn audit_write(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let Some(log) = state.audit.clone() else {
+        return next.run(req).await;
+    };
+
+    let method = req.method().clone();
+    let path = req.uri().path().to_string();
+    let matched = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|m| m.as_str().to_string())
+        .unwrap_or_else(|| path.clone());
+    let remote_addr = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.to_string());
+    let actor = match req.extensions().get::<AuthenticatedKey>() {
+        Some(key) => format!("key:{}", key.name),
+        None => resolve_client_profile(state.service.config(), req.headers())
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| "anonymous".to_string()),
+    };
+    let (action, target) = audit_action(&method, &matched, &path);
+
+    let response = next.run(req).await;
+
+    let entry = AuditEntry {
+        timestamp: chrono::Utc::now(),
+        actor,
+        remote_addr,
+        action,
+        target,
+        version: None,
+        source: None,
+        method: method.to_string(),
+        path,
+        status: response.status().as_u16(),
+        prev_hash: None,
+        hash: None,
+    };
+    let entry = match response.extensions().get::<AuditDetails>() {
+        Some(details) => entry.with_details(details.clone()),
+        None => entry,
+    };
+    let result = tokio::task::spawn_blocking(move || log.append(&entry)).await;
+    match result {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => tracing::error!("Failed to write audit entry: {}", e),
+        Err(e) => tracing::error!("Audit writer task failed: {}", e),
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_action_mapping() {
+        let cases = [
+            (
+                Method::DELETE,
+                "/api/v1/skills/{id}",
+                "/api/v1/skills/alpha",
+                "skill.delete",
+                Some("alpha"),
+            ),
+            (
+                Method::POST,
+                "/api/v1/skills/install",
+                "/api/v1/skills/install",
+                "skill.install",
+                None,
+            ),
+            (
+                Method::POST,
+                "/skills/upgrade",
+                "/skills/upgrade",
+                "skill.update",
+                None,
+            ),
+            (
+                Method::POST,
+                "/api/v1/reindex/{id}",
+                "/api/v1/reindex/beta",
+                "index.reindex",
+                Some("beta"),
+            ),
+            (
+                Method::POST,
+                "/api/v1/reindex",
+                "/api/v1/reindex",
+                "index.reindex",
+                None,
+            ),
+            (
+                Method::POST,
+                "/api/v1/jobs/{id}/cancel",
+                "/api/v1/jobs/3f2a/cancel",
+                "job.cancel",
+                Some("3f2a"),
+            ),
+            (
+                Method::POST,
+                "/api/v1/registry/index/unyank",
+                "/api/v1/registry/index/unyank",
+                "registry.unyank",
+                None,
+            ),
+            (
+                Method::POST,
+                "/api/v1/admin/reindex",
+                "/api/v1/admin/reindex",
+                "index.reindex",
+                None,
+            ),
+            (
+                Method::DELETE,
+                "/api/v1/admin/drain",
+                "/api/v1/admin/drain",
+                "admin.resume",
+                None,
+            ),
+            (
+                Method::PUT,
+                "/api/v1/manifest/skills/{id}",
+                "/api/v1/manifest/skills/gamma",
+                "manifest.update",
+                Some("gamma"),
+            ),
+            (
+                Method::DELETE,
+                "/api/v1/manifest/skills/{id}",
+                "/api/v1/manifest/skills/gamma",
+                "manifest.remove",
+                Some("gamma"),
+            ),
+        ];
+        for (method, matched, path, action, target) in cases {
+            let (got_action, got_target) = audit_action(&method, matched, path);
+            assert_eq!(got_action, action, "{} {}", method, matched);
+            assert_eq!(got_target.as_deref(), target, "{} {}", method, matched);
+        }
+    }
+}
@@ -0,0 +1,370 @@
+//! API-key authentication for `/api/v1` and the gRPC services
+//!
+//! Off until a key is created in the key file ([`ApiKeyStore`]), and on from
+//! then until the file is deleted, even when every key is revoked. While on,
+//! each request needs an active key, in `X-API-Key` or `Authorization: Bearer`, whose
+//! scopes cover the route ([`required_scope`]). Keys of client profiles keep
+//! working for the discovery scopes `read:skills` and `execute:tools`.
+
+use crate::core::api_keys::{ApiKeyScope, ApiKeyStore};
+use crate::core::service::ServiceError;
+use crate::http::client_profile::request_api_key;
+use crate::http::errors::HttpError;
+use crate::http::handlers::AppState;
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, Method},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/// The key file as the server sees it, re-read whenever it changes on disk so
+/// keys created or revoked with `fastskill admin keys` apply to the next
+/// request without a restart.
+#[derive(Debug)]
+pub struct ApiKeys {
+    path: PathBuf,
+    cached: Mutex<Option<(Option<SystemTime>, Arc<ApiKeyStore>)>>,
+}
+
+impl ApiKeys {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            cached: Mutex::new(None),
+        }
+    }
+
+    pub fn current(&self) -> Result<Arc<ApiKeyStore>, ServiceError> {
+        let modified = std::fs::metadata(&self.path)
+            .and_then(|m| m.modified())
+            .ok();
+        let mut cached = match self.cached.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if let Some((stamp, store)) = cached.as_ref() {
+            if *stamp == modified {
+                return Ok(Arc::clone(store));
+            }
+        }
+        let store = Arc::new(ApiKeyStore::load(&self.path)?);
+        *cached = Some((modified, Arc::clone(&store)));
+        Ok(store)
+    }
+}
+
+/// The key a request authenticated with; added to the request extensions so
+/// the audit log can name it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthenticatedKey {
+    pub id: String,
+    pub name: String,
+}
+
+/// Scope a request to `path` needs.
+///
+/// Reads need `read:skills`, tool discovery `execute:tools`, skill and
+/// manifest changes `write:skills`, and everything else (reindex, registry
+/// maintenance, replication, audit export, the `/admin/` routes) `admin`.
+pub fn required_scope(method: &Method, path: &str) -> ApiKeyScope {
+    let route = match path.split_once("/v1/") {
+        Some((_, rest)) => format!("/{}", rest.trim_end_matches('/')),
+        None => path.trim_end_matches('/').to_string(),
+    };
+    if route.starts_with("/admin/")
+        || route.starts_with("/replication/")
+        || route == "/audit"
+        || route.starts_with("/audit/")
+    {
+        return ApiKeyScope::Admin;
+    }
+    if *method == Method::GET || *method == Method::HEAD {
+        return if route == "/tools" {
+            ApiKeyScope::ExecuteTools
+        } else {
+            ApiKeyScope::ReadSkills
+        };
+    }
+    match route.as_str() {
+        "/search" | "/resolve" | "/route" | "/validate" => ApiKeyScope::ReadSkills,
+        "/skills/install" | "/skills/update" | "/skills/upgrade" | "/registry/publish" => {
+            ApiKeyScope::WriteSkills
+        }
+        r if r.starts_with("/manifest/skills") => ApiKeyScope::WriteSkills,
+        r if *method == Method::DELETE && r.starts_with("/skills/") => ApiKeyScope::WriteSkills,
+        _ => ApiKeyScope::Admin,
+    }
+}
+
+/// Check the key in `headers` against `scope`.
+///
+/// `Ok(None)` when authentication is off or a client-profile key was used,
+/// `Ok(Some(key))` for a valid key with the scope, 401 for a missing, unknown
+/// or revoked key and 403 for a key without the scope.
+pub fn authorize(
+    state: &AppState,
+    headers: &HeaderMap,
+    scope: ApiKeyScope,
+) -> Result<Option<AuthenticatedKey>, HttpError> {
+    let Some(keys) = &state.api_keys else {
+        return Ok(None);
+    };
+    let store = keys.current()?;
+    if !store.is_enabled() {
+        return Ok(None);
+    }
+    let Some(secret) = request_api_key(headers) else {
+        return Err(HttpError::Unauthorized(
+            "API key required (X-API-Key or Authorization: Bearer)".to_string(),
+        ));
+    };
+
+    if let Some(key) = store.authenticate(secret) {
+        if !key.allows(scope) {
+            return Err(HttpError::Forbidden(format!(
+                "API key '{}' lacks the {} scope",
+                key.name, scope
+            )));
+        }
+        return Ok(Some(AuthenticatedKey {
+            id: key.id.clone(),
+            name: key.name.clone(),
+        }));
+    }
+
+    let profile_key = state
+        .service
+        .config()
+        .http_server
+        .as_ref()
+        .is_some_and(|http| {
+            http.client_profiles
+                .iter()
+                .any(|p| p.api_key.as_deref() == Some(secret))
+        });
+    if profile_key {
+        return match scope {
+            ApiKeyScope::ReadSkills | ApiKeyScope::ExecuteTools => Ok(None),
+            _ => Err(HttpError::Forbidden(format!(
+                "client profile keys cannot be used for {} routes",
+                scope
+            ))),
+        };
+    }
+    Err(HttpError::Unauthorized(
+        "invalid or revoked API key".to_string(),
+    ))
+}
+
+/// Refuse `/api/v1` requests without a key for the route's scope; a no-op
+/// while no API key is configured.
+pub async fn authenticate(State(state): State<AppState>, mut req: Request, next: Next) -> Response {
+    let scope = required_scope(req.method(), req.uri().path());
+    match authorize(&state, req.headers(), scope) {
+        Ok(Some(key)) => {
+            req.extensions_mut().insert(key);
+            next.run(req).await
+        }
+        Ok(None) => next.run(req).await,
+        Err(e) => {
+            tracing::debug!("Refused {} {}: {}", req.method(), req.uri().path(), e);
+            e.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::service::{ClientProfile, HttpServerConfig};
+    use crate::{FastSkillService, ServiceConfig};
+    use axum::http::StatusCode;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_required_scope() {
+        let scope = |method: Method, path: &str| required_scope(&method, path);
+        assert_eq!(
+            scope(Method::GET, "/api/v1/skills"),
+            ApiKeyScope::ReadSkills
+        );
+        assert_eq!(
+            scope(Method::POST, "/api/v1/search"),
+            ApiKeyScope::ReadSkills
+        );
+        assert_eq!(
+            scope(Method::POST, "/api/v1/route"),
+            ApiKeyScope::ReadSkills
+        );
+        assert_eq!(
+            scope(Method::GET, "/api/v1/tools"),
+            ApiKeyScope::ExecuteTools
+        );
+        assert_eq!(
+            scope(Method::POST, "/api/v1/skills/install"),
+            ApiKeyScope::WriteSkills
+        );
+        assert_eq!(
+            scope(Method::DELETE, "/api/v1/skills/pdf"),
+            ApiKeyScope::WriteSkills
+        );
+        assert_eq!(
+            scope(Method::PUT, "/api/v1/manifest/skills/pdf"),
+            ApiKeyScope::WriteSkills
+        );
+        assert_eq!(scope(Method::POST, "/api/v1/reindex"), ApiKeyScope::Admin);
+        assert_eq!(
+            scope(Method::POST, "/api/v1/registry/index/yank"),
+            ApiKeyScope::Admin
+        );
+        assert_eq!(
+            scope(Method::POST, "/api/v1/registry/publish"),
+            ApiKeyScope::WriteSkills
+        );
+        assert_eq!(
+            scope(Method::GET, "/api/v1/registry/download/acme/pdf/1.0.0"),
+            ApiKeyScope::ReadSkills
+        );
+        assert_eq!(
+            scope(Method::GET, "/api/v1/replication/snapshot"),
+            ApiKeyScope::Admin
+        );
+        assert_eq!(scope(Method::GET, "/api/v1/audit"), ApiKeyScope::Admin);
+        assert_eq!(
+            scope(Method::GET, "/api/v1/audit/verify"),
+            ApiKeyScope::Admin
+        );
+        assert_eq!(
+            scope(Method::GET, "/api/v1/admin/hot-reload"),
+            ApiKeyScope::Admin
+        );
+        assert_eq!(
+            scope(Method::POST, "/api/v1/admin/drain"),
+            ApiKeyScope::Admin
+        );
+        assert_eq!(scope(Method::GET, "/skills"), ApiKeyScope::ReadSkills);
+    }
+
+    async fn state(dir: &TempDir) -> AppState {
+        let config = ServiceConfig {
+            skill_storage_path: dir.path().to_path_buf(),
+            http_server: Some(HttpServerConfig {
+                client_profiles: vec![ClientProfile {
+                    name: "ide".to_string(),
+                    api_key: Some("profile-key".to_string()),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let service = FastSkillService::new(config).await.unwrap();
+        AppState::new(Arc::new(service))
+            .unwrap()
+            .with_api_keys(Arc::new(ApiKeys::new(ApiKeyStore::default_path(
+                dir.path(),
+            ))))
+    }
+
+    fn headers(key: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-api-key", key.parse().unwrap());
+        headers
+    }
+
+    #[tokio::test]
+    async fn test_authorize_scopes_and_revocation() {
+        let dir = TempDir::new().unwrap();
+        let state = state(&dir).await;
+        let status = |r: Result<Option<AuthenticatedKey>, HttpError>| {
+            r.map(|_| StatusCode::OK)
+                .unwrap_or_else(|e| e.status_code())
+        };
+
+        // No keys yet: open
+        assert_eq!(
+            status(authorize(&state, &HeaderMap::new(), ApiKeyScope::Admin)),
+            StatusCode::OK
+        );
+
+        let path = ApiKeyStore::default_path(dir.path());
+        let mut store = ApiKeyStore::load(&path).unwrap();
+        let (_, reader) = store.create("reader", &[ApiKeyScope::ReadSkills]).unwrap();
+        store.save().unwrap();
+
+        let read = |key: &str| authorize(&state, &headers(key), ApiKeyScope::ReadSkills);
+        assert_eq!(read(&reader).unwrap().unwrap().name, "reader");
+        assert_eq!(
+            status(authorize(
+                &state,
+                &HeaderMap::new(),
+                ApiKeyScope::ReadSkills
+            )),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(status(read("fsk_bogus")), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            status(authorize(
+                &state,
+                &headers(&reader),
+                ApiKeyScope::WriteSkills
+            )),
+            StatusCode::FORBIDDEN
+        );
+
+        // Client profile keys keep their discovery access
+        assert_eq!(read("profile-key").unwrap(), None);
+        assert_eq!(
+            status(authorize(
+                &state,
+                &headers("profile-key"),
+                ApiKeyScope::WriteSkills
+            )),
+            StatusCode::FORBIDDEN
+        );
+
+        // Revocation applies once the file is re-read; drop the cached copy
+        // in case both writes landed within one mtime tick
+        let mut store = ApiKeyStore::load(&path).unwrap();
+        let (_, admin) = store.create("ops", &[ApiKeyScope::Admin]).unwrap();
+        store.revoke("reader").unwrap();
+        store.save().unwrap();
+        state
+            .api_keys
+            .as_ref()
+            .unwrap()
+            .cached
+            .lock()
+            .unwrap()
+            .take();
+        assert_eq!(status(read(&reader)), StatusCode::UNAUTHORIZED);
+        assert_eq!(read(&admin).unwrap().unwrap().name, "ops");
+
+        // Revoking the last key does not open the server again
+        let mut store = ApiKeyStore::load(&path).unwrap();
+        store.revoke("ops").unwrap();
+        store.save().unwrap();
+        state
+            .api_keys
+            .as_ref()
+            .unwrap()
+            .cached
+            .lock()
+            .unwrap()
+            .take();
+        assert_eq!(status(read(&admin)), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            status(authorize(
+                &state,
+                &HeaderMap::new(),
+                ApiKeyScope::ReadSkills
+            )),
+            StatusCode::UNAUTHORIZED
+        );
+    }
+}
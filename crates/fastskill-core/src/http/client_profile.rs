@@ -0,0 +1,168 @@
+//! Per-client discovery profiles
+//!
+//! Selects the [`ClientProfile`] for a request so discovery endpoints can apply
+//! that client's relevance floor, result cap and allowed scopes, and hide
+//! skills it may not see.
+
+use crate::core::service::{ClientProfile, FastSkillService, ServiceConfig, ServiceError};
+use crate::core::skill_manager::SkillDefinition;
+use axum::http::HeaderMap;
+use std::collections::HashSet;
+
+/// Header naming the calling client for profiles without an API key.
+pub const CLIENT_HEADER: &str = "x-fastskill-client";
+
+/// Header carrying a client API key (alternative to `Authorization: Bearer`).
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Select the client profile for a request, if any.
+///
+/// An API key (from `X-API-Key` or `Authorization: Bearer`) is matched first
+/// against keyed profiles. Otherwise `X-FastSkill-Client` is matched against
+/// profiles that have no key; keyed profiles are never selected by name.
+pub fn resolve_client_profile<'a>(
+    config: &'a ServiceConfig,
+    headers: &HeaderMap,
+) -> Option<&'a ClientProfile> {
+    let profiles = &config.http_server.as_ref()?.client_profiles;
+    if profiles.is_empty() {
+        return None;
+    }
+
+    if let Some(key) = request_api_key(headers) {
+        if let Some(profile) = profiles.iter().find(|p| p.api_key.as_deref() == Some(key)) {
+            return Some(profile);
+        }
+    }
+
+    let name = headers.get(CLIENT_HEADER).and_then(|v| v.to_str().ok())?;
+    profiles
+        .iter()
+        .find(|p| p.api_key.is_none() && p.name == name.trim())
+}
+
+/// The API key a request carries in `X-API-Key` or `Authorization: Bearer`.
+pub fn request_api_key(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(API_KEY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .or_else(|| {
+            headers
+                .get(axum::http::header::AUTHORIZATION)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.strip_prefix("Bearer "))
+        })
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+}
+
+/// Ids of the installed skills visible to the caller `profile` identifies
+/// (see [`SkillAccess`](crate::core::access::SkillAccess)).
+pub async fn visible_skill_ids(
+    service: &FastSkillService,
+    profile: Option<&ClientProfile>,
+) -> Result<HashSet<String>, ServiceError> {
+    Ok(visible_skills(service, profile)
+        .await?
+        .into_iter()
+        .map(|skill| skill.id.into_string())
+        .collect())
+}
+
+/// The installed skills visible to the caller `profile` identifies
+pub async fn visible_skills(
+    service: &FastSkillService,
+    profile: Option<&ClientProfile>,
+) -> Result<Vec<SkillDefinition>, ServiceError> {
+    let mut skills = service.skill_manager().list_skills().await?;
+    skills.retain(|skill| skill.access.allows(profile));
+    Ok(skills)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::service::HttpServerConfig;
+
+    fn config() -> ServiceConfig {
+        ServiceConfig {
+            http_server: Some(HttpServerConfig {
+                client_profiles: vec![
+                    ClientProfile {
+                        name: "ci-agent".to_string(),
+                        max_dynamic_skills: Some(2),
+                        ..Default::default()
+                    },
+                    ClientProfile {
+                        name: "trusted".to_string(),
+                        api_key: Some("secret-key".to_string()),
+                        dynamic_min_relevance: Some(0.3),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, value.parse().unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn test_no_headers_selects_no_profile() {
+        assert!(resolve_client_profile(&config(), &HeaderMap::new()).is_none());
+    }
+
+    #[test]
+    fn test_selects_by_client_header() {
+        let config = config();
+        let profile =
+            resolve_client_profile(&config, &headers(&[(CLIENT_HEADER, "ci-agent")])).unwrap();
+        assert_eq!(profile.name, "ci-agent");
+    }
+
+    #[test]
+    fn test_selects_by_api_key_or_bearer() {
+        let config = config();
+        let by_header =
+            resolve_client_profile(&config, &headers(&[(API_KEY_HEADER, "secret-key")])).unwrap();
+        assert_eq!(by_header.name, "trusted");
+        let by_bearer =
+            resolve_client_profile(&config, &headers(&[("authorization", "Bearer secret-key")]))
+                .unwrap();
+        assert_eq!(by_bearer.name, "trusted");
+    }
+
+    #[test]
+    fn test_keyed_profile_not_selectable_by_name() {
+        assert!(
+            resolve_client_profile(&config(), &headers(&[(CLIENT_HEADER, "trusted")])).is_none()
+        );
+    }
+
+    #[test]
+    fn test_profile_filters() {
+        let profile = ClientProfile {
+            name: "scoped".to_string(),
+            dynamic_min_relevance: Some(0.5),
+            max_dynamic_skills: Some(3),
+            allowed_scopes: vec!["acme/*".to_string(), "pdf".to_string()],
+            ..Default::default()
+        };
+        assert!(profile.allows_skill("acme/review"));
+        assert!(profile.allows_skill("pdf"));
+        assert!(!profile.allows_skill("pdf-tools"));
+        assert!(!profile.allows_skill("other/review"));
+        assert!(profile.meets_relevance(0.5));
+        assert!(!profile.meets_relevance(0.49));
+        assert_eq!(profile.cap_limit(10), 3);
+        assert_eq!(profile.cap_limit(2), 2);
+    }
+}
@@ -0,0 +1,115 @@
+//! Draining the server before a restart (`/api/v1/admin/drain`)
+//!
+//! While the server drains, new `/api/v1` requests get `503 Service
+//! Unavailable` with a `Retry-After` header so a load balancer sends them to
+//! another node; requests already running finish normally. Admin routes stay
+//! reachable, so an operator can watch the in-flight count fall to zero
+//! before stopping the process, or resume serving.
+
+use crate::http::handlers::AppState;
+use crate::http::models::{ApiResponse, ErrorResponse};
+use axum::{
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Seconds a refused client is asked to wait before retrying
+const DRAIN_RETRY_AFTER_SECS: u64 = 5;
+
+/// Whether the server is draining, and how many requests it is still serving
+#[derive(Debug, Default)]
+pub struct Drain {
+    draining: AtomicBool,
+    in_flight: AtomicUsize,
+}
+
+impl Drain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    /// Start (`true`) or stop (`false`) refusing new requests
+    pub fn set_draining(&self, draining: bool) {
+        self.draining.store(draining, Ordering::SeqCst);
+    }
+
+    /// Non-admin `/api/v1` requests currently being served
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    fn enter(self: &Arc<Self>) -> InFlight {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        InFlight(Arc::clone(self))
+    }
+}
+
+/// Counts one request as in flight until dropped, also when the handler panics
+struct InFlight(Arc<Drain>);
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Whether `path` is an `/admin/` route, with or without the `/api/v1` prefix
+fn is_admin_route(path: &str) -> bool {
+    let route = path.split_once("/v1/").map_or(path, |(_, rest)| rest);
+    route.trim_start_matches('/').starts_with("admin/")
+}
+
+/// Refuse new requests while draining and count the ones being served. Admin
+/// routes are neither refused nor counted.
+pub async fn drain_gate(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    if is_admin_route(req.uri().path()) {
+        return next.run(req).await;
+    }
+    if state.drain.is_draining() {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::RETRY_AFTER, DRAIN_RETRY_AFTER_SECS.to_string())],
+            Json(ApiResponse::<()>::error(ErrorResponse {
+                code: "DRAINING".to_string(),
+                message: "server is draining; retry against another node".to_string(),
+                details: None,
+            })),
+        )
+            .into_response();
+    }
+    let _in_flight = state.drain.enter();
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_flight_guard_counts_requests() {
+        let drain = Arc::new(Drain::new());
+        let first = drain.enter();
+        let second = drain.enter();
+        assert_eq!(drain.in_flight(), 2);
+        drop(first);
+        drop(second);
+        assert_eq!(drain.in_flight(), 0);
+    }
+
+    #[test]
+    fn test_is_admin_route() {
+        assert!(is_admin_route("/api/v1/admin/drain"));
+        assert!(is_admin_route("/admin/cache/clear"));
+        assert!(!is_admin_route("/api/v1/skills"));
+        assert!(!is_admin_route("/api/v1/skills/admin/content"));
+    }
+}
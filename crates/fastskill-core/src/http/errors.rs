@@ -33,6 +33,9 @@ pub enum HttpError {
 
     /// Service unavailable (e.g., missing API key for semantic search)
     ServiceUnavailable(String),
+
+    /// Served content failed its integrity check
+    Integrity(String),
 }
 
 impl HttpError {
@@ -48,6 +51,7 @@ impl HttpError {
                 StatusCode::INTERNAL_SERVER_ERROR
             }
             HttpError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            HttpError::Integrity(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 
@@ -63,6 +67,7 @@ impl HttpError {
             HttpError::InternalServerError(_) => "INTERNAL_SERVER_ERROR",
             HttpError::ServiceError(_) => "SERVICE_ERROR",
             HttpError::ServiceUnavailable(_) => "SERVICE_UNAVAILABLE",
+            HttpError::Integrity(_) => "INTEGRITY_ERROR",
         }
     }
 }
@@ -81,6 +86,7 @@ impl std::fmt::Display for HttpError {
             HttpError::InternalServerError(msg) => write!(f, "Internal Server Error: {}", msg),
             HttpError::ServiceError(msg) => write!(f, "Service Error: {}", msg),
             HttpError::ServiceUnavailable(msg) => write!(f, "Service Unavailable: {}", msg),
+            HttpError::Integrity(msg) => write!(f, "Integrity Error: {}", msg),
         }
     }
 }
@@ -103,7 +109,8 @@ impl IntoResponse for HttpError {
             | HttpError::Conflict(msg)
             | HttpError::InternalServerError(msg)
             | HttpError::ServiceError(msg)
-            | HttpError::ServiceUnavailable(msg) => (msg, None),
+            | HttpError::ServiceUnavailable(msg)
+            | HttpError::Integrity(msg) => (msg, None),
         };
 
         let body = Json(json!({
@@ -137,6 +144,29 @@ impl From<crate::core::service::ServiceError> for HttpError {
             crate::core::service::ServiceError::Event(msg) => HttpError::InternalServerError(msg),
             crate::core::service::ServiceError::InvalidOperation(msg) => HttpError::BadRequest(msg),
             crate::core::service::ServiceError::AlreadyIndexed(msg) => HttpError::BadRequest(msg),
+            crate::core::service::ServiceError::Cancelled => {
+                HttpError::ServiceUnavailable("operation cancelled".to_string())
+            }
+            crate::core::service::ServiceError::Busy(holder) => HttpError::ServiceUnavailable(
+                format!("another fastskill process is running ({})", holder),
+            ),
+            crate::core::service::ServiceError::Integrity(err) => err.into(),
+        }
+    }
+}
+
+impl From<crate::core::integrity::IntegrityError> for HttpError {
+    fn from(err: crate::core::integrity::IntegrityError) -> Self {
+        HttpError::Integrity(err.to_string())
+    }
+}
+
+impl From<crate::core::jobs::JobError> for HttpError {
+    fn from(err: crate::core::jobs::JobError) -> Self {
+        match err {
+            crate::core::jobs::JobError::NotFound(_) => HttpError::NotFound(err.to_string()),
+            crate::core::jobs::JobError::Finished { .. }
+            | crate::core::jobs::JobError::Committing(_) => HttpError::Conflict(err.to_string()),
         }
     }
 }
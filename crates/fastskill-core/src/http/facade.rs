@@ -0,0 +1,462 @@
+//! Transport-neutral API operations
+//!
+//! The REST handlers and the gRPC services (`grpc` feature) both call these,
+//! so listing, lookup, search, tool discovery and install/delete behave the
+//! same over either transport. Each takes the caller's client profile, already
+//! resolved from request headers or gRPC metadata, and returns the REST
+//! response models; transports only translate requests and responses.
+
+use crate::core::embedding::EmbeddingService;
+use crate::core::install::AddMode;
+use crate::core::metadata::parse_yaml_frontmatter;
+use crate::core::service::{ClientProfile, ServiceError, SkillId};
+use crate::core::skill_manager::SkillDefinition;
+use crate::core::tools::{declared_tools, AvailableTool};
+use crate::core::vector_index::LARGE_COLLECTION_THRESHOLD;
+use crate::http::client_profile::visible_skill_ids;
+use crate::http::errors::{HttpError, HttpResult};
+use crate::http::handlers::AppState;
+use crate::http::models::*;
+use crate::OpenAIEmbeddingService;
+use validator::Validate;
+
+fn skill_metadata_json(skill: &SkillDefinition) -> serde_json::Value {
+    let origin = serde_json::to_value(&skill.origin).unwrap_or(serde_json::Value::Null);
+    serde_json::json!({
+        "id": skill.id,
+        "name": skill.name,
+        "description": skill.description,
+        "version": skill.version,
+        "author": skill.author,
+        "created_at": skill.created_at.to_rfc3339(),
+        "updated_at": skill.updated_at.to_rfc3339(),
+        "skill_file": skill.skill_file,
+        "reference_files": skill.reference_files,
+        "script_files": skill.script_files,
+        "asset_files": skill.asset_files,
+        "origin": origin
+    })
+}
+
+fn skill_response(skill: &SkillDefinition) -> SkillResponse {
+    SkillResponse {
+        id: skill.id.to_string(),
+        name: skill.name.clone(),
+        description: skill.description.clone(),
+        metadata: skill_metadata_json(skill),
+        created_at: Some(skill.created_at.to_rfc3339()),
+        updated_at: Some(skill.updated_at.to_rfc3339()),
+    }
+}
+
+/// One page of skills in id order (see `GET /api/v1/skills`).
+///
+/// Skills the caller may not see are left out of the page, so a page can be
+/// shorter than `limit`; `total` still counts every installed skill.
+pub async fn list_skills(
+    state: &AppState,
+    profile: Option<&ClientProfile>,
+    query: &ListSkillsQuery,
+) -> HttpResult<SkillsListResponse> {
+    let skill_manager = state.service.skill_manager();
+    let total = skill_manager.count_skills().await?;
+    let limit = query.limit.unwrap_or(LARGE_COLLECTION_THRESHOLD).max(1);
+
+    // One extra skill tells whether another page follows.
+    let mut skills = skill_manager
+        .list_skills_page(query.after.as_deref(), limit.saturating_add(1))
+        .await?;
+    let next_cursor = if skills.len() > limit {
+        skills.truncate(limit);
+        skills.last().map(|skill| skill.id.to_string())
+    } else {
+        None
+    };
+    skills.retain(|skill| skill.access.allows(profile));
+
+    let skills: Vec<SkillResponse> = skills.iter().map(skill_response).collect();
+    Ok(SkillsListResponse {
+        count: skills.len(),
+        skills,
+        total,
+        next_cursor,
+    })
+}
+
+/// One skill by id. A skill the caller may not see is reported as not found.
+pub async fn get_skill(
+    state: &AppState,
+    profile: Option<&ClientProfile>,
+    skill_id: &str,
+) -> HttpResult<SkillResponse> {
+    let skill_id_parsed = SkillId::new(skill_id.to_string())
+        .map_err(|_| HttpError::BadRequest("Invalid skill ID format".to_string()))?;
+    let skills = state.service.skill_manager().list_skills().await?;
+    let skill = skills
+        .into_iter()
+        .find(|s| s.id == skill_id_parsed && s.access.allows(profile))
+        .ok_or_else(|| HttpError::NotFound(format!("Skill not found: {}", skill_id)))?;
+    Ok(skill_response(&skill))
+}
+
+/// Search skills by meaning when an embedding index is available, else by
+/// name and description substring.
+///
+/// The caller's client profile, if any, applies its relevance floor, result
+/// cap and allowed scopes. Skills the caller may not see are never returned.
+pub async fn search_skills(
+    state: &AppState,
+    profile: Option<&ClientProfile>,
+    request: SearchRequest,
+) -> HttpResult<SearchResponse> {
+    request.validate().map_err(|e| {
+        HttpError::ValidationError(
+            e.field_errors()
+                .into_iter()
+                .map(|(field, errors)| {
+                    (
+                        field.to_string(),
+                        errors
+                            .iter()
+                            .map(|e| e.message.clone().unwrap_or_default().to_string())
+                            .collect(),
+                    )
+                })
+                .collect(),
+        )
+    })?;
+
+    let limit = request.limit.unwrap_or(10).clamp(1, 50) as usize;
+    let limit = profile.map_or(limit, |p| p.cap_limit(limit));
+
+    // Determine search mode
+    let use_semantic = request.semantic != Some(false)
+        && state.service.config().embedding.is_some()
+        && state.service.vector_index_service().is_some();
+
+    let skills: Vec<SkillMatchResponse> = if use_semantic {
+        // Semantic search requires OPENAI_API_KEY
+        let api_key = std::env::var("OPENAI_API_KEY").map_err(|_| {
+            HttpError::ServiceUnavailable(
+                "OPENAI_API_KEY environment variable is required for semantic search".to_string(),
+            )
+        })?;
+
+        let embedding_config = state.service.config().embedding.as_ref().ok_or_else(|| {
+            HttpError::ServiceError("Embedding configuration is not available".to_string())
+        })?;
+        let vector_index_service = state.service.vector_index_service().ok_or_else(|| {
+            HttpError::ServiceError("Vector index service is not available".to_string())
+        })?;
+
+        // Initialize embedding service
+        let embedding_service: OpenAIEmbeddingService =
+            OpenAIEmbeddingService::from_config(embedding_config, api_key);
+
+        // Generate query embedding
+        let query_embedding = embedding_service
+            .embed_query(&request.query)
+            .await
+            .map_err(|e| {
+                HttpError::ServiceUnavailable(format!("Failed to generate query embedding: {}", e))
+            })?;
+
+        // Search vector index. Scope filtering happens after ranking, so fetch
+        // extra candidates to still fill `limit` for scoped profiles.
+        let candidates = match profile {
+            Some(p) if !p.allowed_scopes.is_empty() => 50.max(limit),
+            _ => limit,
+        };
+        let matches = vector_index_service
+            .search_similar(&query_embedding, candidates)
+            .await
+            .map_err(|e| HttpError::ServiceError(format!("Vector search failed: {}", e)))?;
+        let visible = visible_skill_ids(&state.service, profile).await?;
+
+        // Convert matches to SkillMatchResponse
+        matches
+            .into_iter()
+            .filter(|m| {
+                visible.contains(&m.skill.id)
+                    && profile.is_none_or(|p| {
+                        p.allows_skill(&m.skill.id) && p.meets_relevance(m.similarity)
+                    })
+            })
+            .take(limit)
+            .map(|m| SkillMatchResponse {
+                skill: SkillResponse {
+                    id: m.skill.id.clone(),
+                    name: m
+                        .skill
+                        .frontmatter_json
+                        .get("name")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or(&m.skill.id)
+                        .to_string(),
+                    description: m
+                        .skill
+                        .frontmatter_json
+                        .get("description")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("")
+                        .to_string(),
+                    metadata: m.skill.frontmatter_json.clone(),
+                    created_at: None,
+                    updated_at: Some(m.skill.updated_at.to_rfc3339()),
+                },
+                score: m.similarity,
+                relevance: if m.similarity >= 0.8 {
+                    "high".to_string()
+                } else if m.similarity >= 0.5 {
+                    "medium".to_string()
+                } else {
+                    "low".to_string()
+                },
+            })
+            .collect()
+    } else {
+        // Text fallback: search by substring in name/description
+        let skills_list = state
+            .service
+            .skill_manager()
+            .list_skills()
+            .await
+            .map_err(|e| HttpError::ServiceError(format!("Failed to list skills: {}", e)))?;
+
+        let query_lower = request.query.to_lowercase();
+        skills_list
+            .into_iter()
+            .filter(|s| {
+                s.name.to_lowercase().contains(&query_lower)
+                    || s.description.to_lowercase().contains(&query_lower)
+            })
+            .filter(|s| s.access.allows(profile))
+            .filter(|s| profile.is_none_or(|p| p.allows_skill(s.id.as_str())))
+            .take(limit)
+            .map(|s| SkillMatchResponse {
+                skill: SkillResponse {
+                    id: s.id.to_string(),
+                    name: s.name,
+                    description: s.description,
+                    metadata: serde_json::json!({}),
+                    created_at: None,
+                    updated_at: None,
+                },
+                score: 1.0,
+                relevance: "keyword".to_string(),
+            })
+            .collect()
+    };
+
+    Ok(SearchResponse {
+        count: skills.len(),
+        query: request.query,
+        skills,
+    })
+}
+
+/// Tools declared by every installed skill the caller may see, optionally
+/// only those with `capability`.
+///
+/// A skill whose SKILL.md cannot be read or declares invalid tools is
+/// reported under `errors` instead of failing the whole listing.
+pub async fn list_tools(
+    state: &AppState,
+    profile: Option<&ClientProfile>,
+    capability: Option<&str>,
+) -> HttpResult<ToolsListResponse> {
+    let mut skills = state.service.skill_manager().list_skills().await?;
+    skills.retain(|skill| skill.access.allows(profile));
+    skills.sort_by(|a, b| a.id.as_str().cmp(b.id.as_str()));
+    let capability = capability.map(str::trim).filter(|c| !c.is_empty());
+
+    let mut tools = Vec::new();
+    let mut errors = Vec::new();
+    for skill in &skills {
+        match skill_tools(state, skill).await {
+            Ok(declared) => tools.extend(
+                declared
+                    .into_iter()
+                    .filter(|tool| capability.is_none_or(|c| tool.has_capability(c)))
+                    .map(|tool| ToolResponse {
+                        name: tool.name,
+                        description: tool.description,
+                        capabilities: tool.capabilities,
+                        input_schema: tool.input_schema,
+                        output_schema: tool.output_schema,
+                        skill_id: skill.id.to_string(),
+                        skill_version: skill.version.clone(),
+                    }),
+            ),
+            Err(message) => errors.push(ToolErrorResponse {
+                skill_id: skill.id.to_string(),
+                message,
+            }),
+        }
+    }
+
+    Ok(ToolsListResponse {
+        count: tools.len(),
+        tools,
+        errors,
+    })
+}
+
+async fn skill_tools(
+    state: &AppState,
+    skill: &SkillDefinition,
+) -> Result<Vec<AvailableTool>, String> {
+    let skill_file = if skill.skill_file.is_absolute() {
+        skill.skill_file.clone()
+    } else {
+        state
+            .service
+            .config()
+            .skill_storage_path
+            .join(skill.id.as_str())
+            .join("SKILL.md")
+    };
+    let content = tokio::fs::read(&skill_file)
+        .await
+        .map_err(|e| format!("Failed to read SKILL.md: {}", e))?;
+    state
+        .verify_integrity(skill.id.as_str(), &content)
+        .map_err(|e| e.to_string())?;
+    let frontmatter =
+        parse_yaml_frontmatter(&String::from_utf8_lossy(&content)).map_err(|e| e.to_string())?;
+    declared_tools(&frontmatter).map_err(|e| e.to_string())
+}
+
+/// Fresh-install a skill from an Origin ref string (core install seam,
+/// ADR-0005). A ref that cannot be classified is a bad request; an id that is
+/// already installed is a conflict.
+pub async fn install_skill(
+    state: &AppState,
+    request: InstallSkillRequest,
+) -> HttpResult<InstallSkillResponse> {
+    // A bad ref / no-default-repo is a client error (400), not a 500 — surface
+    // it distinctly from the blanket `ServiceError` → `HttpError` conversion
+    // below (which maps `Config` to 500, appropriate for the *fetch* path but
+    // not for classifying the ref itself).
+    let origin = state
+        .service
+        .infer_origin(&request.origin)
+        .await
+        .map_err(|e| HttpError::BadRequest(e.to_string()))?;
+
+    match state
+        .service
+        .add_from_origin(origin, AddMode::Fresh, request.groups)
+        .await
+    {
+        Ok(outcome) => {
+            state.forget_integrity(&outcome.id);
+            Ok(InstallSkillResponse {
+                id: outcome.id,
+                resolved_version: outcome.resolved.version,
+                reindexed: outcome.reindexed,
+                renamed_from: outcome.renamed_from,
+            })
+        }
+        // ADR-0005 §Q6 / spec 003 §2: a Fresh conflict on an already-installed
+        // id is a 409, not a generic 400 (which the blanket ServiceError→HttpError
+        // mapping would otherwise give it).
+        Err(ServiceError::AlreadyIndexed(id)) => Err(HttpError::Conflict(format!(
+            "Skill '{}' is already installed",
+            id
+        ))),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Remove a skill: drop it from the project manifest and lock, delete its
+/// directory and unregister it.
+pub async fn delete_skill(state: &AppState, skill_id: &str) -> HttpResult<()> {
+    let skill_id_parsed = SkillId::new(skill_id.to_string())
+        .map_err(|_| HttpError::BadRequest("Invalid skill ID format".to_string()))?;
+
+    let skills = state.service.skill_manager().list_skills().await?;
+    let skill = skills
+        .into_iter()
+        .find(|s| s.id == skill_id_parsed)
+        .ok_or_else(|| HttpError::NotFound(format!("Skill not found: {}", skill_id)))?;
+
+    let project_path = &state.project_file_path;
+    let lock_path = if let Some(parent) = project_path.parent() {
+        let safe_parent = if parent.exists() {
+            parent.canonicalize().map_err(|e| {
+                HttpError::InternalServerError(format!("Failed to resolve parent path: {}", e))
+            })?
+        } else {
+            parent.to_path_buf()
+        };
+        safe_parent.join("skills.lock")
+    } else {
+        std::path::PathBuf::from("skills.lock")
+    };
+
+    if project_path.exists() {
+        let mut project = crate::core::manifest::SkillProjectToml::load_from_file(project_path)
+            .map_err(|e| {
+                HttpError::InternalServerError(format!("Failed to load project: {}", e))
+            })?;
+        if let Some(ref mut deps) = project.dependencies {
+            deps.dependencies.remove(skill_id);
+        }
+        let mut txn =
+            crate::core::project_transaction::ProjectTransaction::new(project_path, &lock_path);
+        txn.stage_manifest(&project).map_err(|e| {
+            HttpError::InternalServerError(format!("Failed to save project: {}", e))
+        })?;
+        if lock_path.exists() {
+            let mut lock = crate::core::lock::ProjectSkillsLock::load_from_file(&lock_path)
+                .map_err(|e| {
+                    HttpError::InternalServerError(format!("Failed to load lock: {}", e))
+                })?;
+            lock.remove_skill(skill_id);
+            txn.stage_lock(&lock).map_err(|e| {
+                HttpError::InternalServerError(format!("Failed to save lock: {}", e))
+            })?;
+        }
+        txn.commit().map_err(|e| {
+            HttpError::InternalServerError(format!("Failed to save project and lock: {}", e))
+        })?;
+        if lock_path.exists() {
+            state
+                .service
+                .storage()
+                .lock_written(&lock_path)
+                .await
+                .map_err(|e| HttpError::InternalServerError(e.to_string()))?;
+        }
+    }
+
+    let skill_dir = skill.skill_file.parent().ok_or_else(|| {
+        HttpError::InternalServerError("Skill file has no parent dir".to_string())
+    })?;
+    if skill_dir.exists() {
+        tokio::fs::remove_dir_all(skill_dir).await.map_err(|e| {
+            HttpError::InternalServerError(format!("Failed to remove skill dir: {}", e))
+        })?;
+    }
+    crate::core::scope::remove_scope_dir_if_empty(
+        &state.service.config().skill_storage_path,
+        &skill_id_parsed,
+    )
+    .map_err(|e| HttpError::InternalServerError(e.to_string()))?;
+    state
+        .service
+        .storage()
+        .skill_removed(skill_id)
+        .await
+        .map_err(|e| HttpError::InternalServerError(e.to_string()))?;
+
+    state
+        .service
+        .skill_manager()
+        .unregister_skill(&skill_id_parsed)
+        .await
+        .map_err(|e| HttpError::InternalServerError(e.to_string()))?;
+    state.forget_integrity(skill_id);
+    Ok(())
+}
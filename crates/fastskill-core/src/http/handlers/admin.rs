@@ -0,0 +1,155 @@
+//! Admin endpoint handlers (`/api/v1/admin/...`)
+//!
+//! Operator controls for a running server: reindexing, dropping caches,
+//! turning hot reload on and off, and draining before a restart. Every
+//! `/admin/` route needs an `admin`-scoped API key once keys are configured
+//! (see `crate::http::auth::required_scope`); the mutating ones are also
+//! write-gated and audited like the other write routes.
+
+use crate::core::reindex::ReindexMode;
+use crate::http::errors::HttpResult;
+use crate::http::handlers::AppState;
+use crate::http::models::*;
+use axum::{
+    extract::{Query, State},
+    response::Response,
+    Json,
+};
+
+/// POST /api/v1/admin/reindex - Reindex all skills, incrementally unless
+/// `mode` is `full` or `migrate`
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/reindex",
+    tag = "admin",
+    params(AsyncQuery),
+    request_body = AdminReindexRequest,
+    responses(
+        (status = 200, description = "Reindex outcome", body = ApiResponse<ReindexOutcomeResponse>),
+        (status = 202, description = "Queued as a background job (`?async=true`)", body = ApiResponse<JobResponse>)
+    )
+)]
+pub async fn reindex(
+    State(state): State<AppState>,
+    Query(query): Query<AsyncQuery>,
+    Json(request): Json<AdminReindexRequest>,
+) -> HttpResult<Response> {
+    let mode = match request.mode.unwrap_or(AdminReindexMode::Incremental) {
+        AdminReindexMode::Incremental => ReindexMode::Incremental,
+        AdminReindexMode::Full => ReindexMode::Full,
+        AdminReindexMode::Migrate => ReindexMode::Migrate,
+    };
+    super::reindex::run_reindex(&state, mode, query.run_async).await
+}
+
+/// POST /api/v1/admin/cache/clear - Drop the skill metadata and query
+/// embedding caches
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/cache/clear",
+    tag = "admin",
+    responses((status = 200, description = "Cleared caches", body = ApiResponse<CacheClearResponse>))
+)]
+pub async fn clear_cache(
+    State(state): State<AppState>,
+) -> HttpResult<Json<ApiResponse<CacheClearResponse>>> {
+    let query_embeddings = state.service.clear_caches().await?;
+    tracing::info!(
+        "Admin: cleared caches ({} query embeddings)",
+        query_embeddings
+    );
+    Ok(Json(ApiResponse::success(CacheClearResponse {
+        cleared: vec!["metadata".to_string(), "embedding_query".to_string()],
+        query_embeddings,
+    })))
+}
+
+/// GET /api/v1/admin/hot-reload - Whether hot reload is watching the skills
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/hot-reload",
+    tag = "admin",
+    responses((status = 200, description = "Hot reload state", body = ApiResponse<HotReloadResponse>))
+)]
+pub async fn get_hot_reload(
+    State(state): State<AppState>,
+) -> HttpResult<Json<ApiResponse<HotReloadResponse>>> {
+    let active = state.service.hot_reload_active().await;
+    Ok(Json(ApiResponse::success(HotReloadResponse {
+        configured: active.is_some(),
+        active: active.unwrap_or(false),
+    })))
+}
+
+/// PUT /api/v1/admin/hot-reload - Turn hot reload on or off. 400 when the
+/// server was started with hot reload disabled in its config.
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/hot-reload",
+    tag = "admin",
+    request_body = HotReloadRequest,
+    responses(
+        (status = 200, description = "Hot reload state", body = ApiResponse<HotReloadResponse>),
+        (status = 400, description = "Hot reload is not configured", body = crate::http::openapi::UntypedApiResponse)
+    )
+)]
+pub async fn set_hot_reload(
+    State(state): State<AppState>,
+    Json(request): Json<HotReloadRequest>,
+) -> HttpResult<Json<ApiResponse<HotReloadResponse>>> {
+    let active = state.service.set_hot_reload(request.enabled).await?;
+    tracing::info!("Admin: hot reload {}", if active { "on" } else { "off" });
+    Ok(Json(ApiResponse::success(HotReloadResponse {
+        configured: true,
+        active,
+    })))
+}
+
+fn drain_response(state: &AppState) -> Json<ApiResponse<DrainResponse>> {
+    Json(ApiResponse::success(DrainResponse {
+        draining: state.drain.is_draining(),
+        in_flight: state.drain.in_flight(),
+    }))
+}
+
+/// GET /api/v1/admin/drain - Whether the server is draining, and how many
+/// requests it is still serving
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/drain",
+    tag = "admin",
+    responses((status = 200, description = "Drain state", body = ApiResponse<DrainResponse>))
+)]
+pub async fn get_drain(State(state): State<AppState>) -> Json<ApiResponse<DrainResponse>> {
+    drain_response(&state)
+}
+
+/// POST /api/v1/admin/drain - Refuse new requests with 503 while the ones in
+/// flight finish
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/drain",
+    tag = "admin",
+    responses((status = 200, description = "Drain state", body = ApiResponse<DrainResponse>))
+)]
+pub async fn start_drain(State(state): State<AppState>) -> Json<ApiResponse<DrainResponse>> {
+    state.drain.set_draining(true);
+    tracing::info!(
+        "Admin: draining, {} requests in flight",
+        state.drain.in_flight()
+    );
+    drain_response(&state)
+}
+
+/// DELETE /api/v1/admin/drain - Stop draining and serve requests again
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/drain",
+    tag = "admin",
+    responses((status = 200, description = "Drain state", body = ApiResponse<DrainResponse>))
+)]
+pub async fn stop_drain(State(state): State<AppState>) -> Json<ApiResponse<DrainResponse>> {
+    state.drain.set_draining(false);
+    tracing::info!("Admin: serving requests again");
+    drain_response(&state)
+}
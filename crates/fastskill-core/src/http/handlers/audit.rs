@@ -0,0 +1,93 @@
+//! Audit log export endpoint handlers
+//!
+//! Both routes need the `admin` scope once API keys are configured.
+
+use crate::core::audit::{AuditFilter, AuditVerification};
+use crate::http::errors::{HttpError, HttpResult};
+use crate::http::handlers::AppState;
+use crate::http::models::{ApiResponse, AuditQuery};
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::Response,
+};
+
+/// GET /api/v1/audit - Export audit entries as JSON Lines, oldest first
+///
+/// Entries are exported with their `prev_hash`/`hash`, so an unfiltered
+/// export can be checked offline the same way `GET /api/v1/audit/verify`
+/// checks the log on disk.
+#[utoipa::path(
+    get,
+    path = "/api/v1/audit",
+    tag = "audit",
+    params(AuditQuery),
+    responses(
+        (status = 200, description = "Matching entries, one JSON object per line", body = String, content_type = "application/x-ndjson"),
+        (status = 404, description = "The audit log is not enabled", body = crate::http::openapi::UntypedApiResponse)
+    )
+)]
+pub async fn export_audit(
+    State(state): State<AppState>,
+    Query(query): Query<AuditQuery>,
+) -> HttpResult<Response> {
+    let log = state
+        .audit
+        .clone()
+        .ok_or_else(|| HttpError::NotFound("Audit log is not enabled".to_string()))?;
+    let filter = AuditFilter {
+        since: query.since,
+        action: query.action,
+        actor: query.actor,
+        target: query.skill,
+    };
+    let mut entries = tokio::task::spawn_blocking(move || log.read(&filter))
+        .await
+        .map_err(|e| {
+            HttpError::InternalServerError(format!("Audit reader task failed: {}", e))
+        })??;
+    if let Some(limit) = query.limit {
+        let skip = entries.len().saturating_sub(limit);
+        entries.drain(..skip);
+    }
+
+    let mut body = String::new();
+    for entry in &entries {
+        let line = serde_json::to_string(entry).map_err(|e| {
+            HttpError::InternalServerError(format!("Failed to encode audit entry: {}", e))
+        })?;
+        body.push_str(&line);
+        body.push('\n');
+    }
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/x-ndjson")
+        .body(Body::from(body))
+        .map_err(|e| HttpError::InternalServerError(format!("Failed to build response: {}", e)))
+}
+
+/// GET /api/v1/audit/verify - Check the audit log's hash chain
+#[utoipa::path(
+    get,
+    path = "/api/v1/audit/verify",
+    tag = "audit",
+    responses(
+        (status = 200, description = "Result of the hash chain check", body = ApiResponse<AuditVerification>),
+        (status = 404, description = "The audit log is not enabled", body = crate::http::openapi::UntypedApiResponse)
+    )
+)]
+pub async fn verify_audit(
+    State(state): State<AppState>,
+) -> HttpResult<axum::Json<ApiResponse<AuditVerification>>> {
+    let log = state
+        .audit
+        .clone()
+        .ok_or_else(|| HttpError::NotFound("Audit log is not enabled".to_string()))?;
+    let report = tokio::task::spawn_blocking(move || log.verify())
+        .await
+        .map_err(|e| {
+            HttpError::InternalServerError(format!("Audit reader task failed: {}", e))
+        })??;
+    Ok(axum::Json(ApiResponse::success(report)))
+}
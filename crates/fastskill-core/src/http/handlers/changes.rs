@@ -0,0 +1,59 @@
+//! Differential sync endpoint handler
+
+use crate::core::change_journal::ChangedSkill;
+use crate::http::client_profile::{resolve_client_profile, visible_skill_ids};
+use crate::http::errors::{HttpError, HttpResult};
+use crate::http::handlers::AppState;
+use crate::http::models::{ApiResponse, ChangesQuery, SkillChangeResponse, SkillChangesResponse};
+use axum::extract::{Query, State};
+use axum::http::HeaderMap;
+
+/// GET /api/v1/skills/changes?since=<cursor> - Skills added, updated or removed since a cursor
+///
+/// The skills directory is checked against the change journal first, so the
+/// answer covers edits made outside the API too. Without `since` every
+/// current skill is listed as added. Keep the returned `cursor` for the next
+/// call; an unknown cursor (the journal was reset) is a 400 asking for a
+/// resync from 0. Added and updated skills the caller may not see are left
+/// out; removed skills no longer carry access rules and are always listed.
+#[utoipa::path(
+    get,
+    path = "/api/v1/skills/changes",
+    tag = "skills",
+    params(ChangesQuery),
+    responses((status = 200, description = "Skills changed since the cursor", body = ApiResponse<SkillChangesResponse>))
+)]
+pub async fn list_changes(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<ChangesQuery>,
+) -> HttpResult<axum::Json<ApiResponse<SkillChangesResponse>>> {
+    let journal = state.changes.clone();
+    let skills_dir = state.service.config().skill_storage_path.clone();
+    let since = query.since.unwrap_or(0);
+    let changes = tokio::task::spawn_blocking(move || {
+        journal.sync(&skills_dir)?;
+        journal.changes_since(since)
+    })
+    .await
+    .map_err(|e| HttpError::InternalServerError(format!("Change journal task failed: {}", e)))??;
+
+    let profile = resolve_client_profile(state.service.config(), &headers);
+    let visible = visible_skill_ids(&state.service, profile).await?;
+    let to_response = |skills: Vec<ChangedSkill>| {
+        skills
+            .into_iter()
+            .filter(|skill| visible.contains(&skill.id))
+            .map(|skill| SkillChangeResponse {
+                id: skill.id,
+                hash: skill.hash,
+            })
+            .collect()
+    };
+    Ok(axum::Json(ApiResponse::success(SkillChangesResponse {
+        added: to_response(changes.added),
+        updated: to_response(changes.updated),
+        removed: changes.removed,
+        cursor: changes.cursor,
+    })))
+}
@@ -0,0 +1,244 @@
+//! Dependency graph endpoint handler
+
+use crate::core::dependencies::{Dependency, DependencyGraph};
+use crate::core::lock::{project_lock_path, ProjectSkillsLock};
+use crate::core::skill_manager::SkillDefinition;
+use crate::http::client_profile::resolve_client_profile;
+use crate::http::errors::{HttpError, HttpResult};
+use crate::http::handlers::AppState;
+use crate::http::models::{
+    ApiResponse, DependencyGraphResponse, GraphConflict, GraphEdge, GraphNode,
+};
+use axum::extract::State;
+use axum::http::HeaderMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// GET /api/v1/graph - Resolved dependency graph of the project's skills
+///
+/// Nodes and edges come from `skills.lock`, which already records what
+/// `install` resolved (each entry's depth, the skill that pulled it in, and any
+/// declared dependencies); installed skills missing from the lock are added as
+/// unlocked nodes. Conflicts are annotated rather than resolved, so dashboards
+/// can render them as-is. Installed skills the caller may not see are removed
+/// from the graph, together with every edge that touches them.
+#[utoipa::path(
+    get,
+    path = "/api/v1/graph",
+    tag = "project",
+    responses((status = 200, description = "Dependency graph with its conflicts", body = ApiResponse<DependencyGraphResponse>))
+)]
+pub async fn get_graph(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> HttpResult<axum::Json<ApiResponse<DependencyGraphResponse>>> {
+    let lock_path = project_lock_path(&state.project_file_path);
+    let lock = if lock_path.exists() {
+        Some(ProjectSkillsLock::load_from_file(&lock_path).map_err(|e| {
+            HttpError::InternalServerError(format!("Failed to load skills.lock: {}", e))
+        })?)
+    } else {
+        None
+    };
+    let installed = state.service.skill_manager().list_skills().await?;
+    let mut graph = build_graph(lock.as_ref(), &installed);
+    let profile = resolve_client_profile(state.service.config(), &headers);
+    let hidden: HashSet<&str> = installed
+        .iter()
+        .filter(|skill| !skill.access.allows(profile))
+        .map(|skill| skill.id.as_str())
+        .collect();
+    hide_skills(&mut graph, &hidden);
+    Ok(axum::Json(ApiResponse::success(graph)))
+}
+
+/// Drop the `hidden` skills' nodes and every edge to or from them. The graph
+/// is built from every installed skill first, so the conflicts that remain
+/// are the same ones a caller who sees everything gets.
+fn hide_skills(graph: &mut DependencyGraphResponse, hidden: &HashSet<&str>) {
+    if hidden.is_empty() {
+        return;
+    }
+    graph
+        .nodes
+        .retain(|node| !hidden.contains(node.id.as_str()));
+    graph
+        .edges
+        .retain(|edge| !hidden.contains(edge.from.as_str()) && !hidden.contains(edge.to.as_str()));
+    graph.conflict_count = graph
+        .nodes
+        .iter()
+        .filter(|n| !n.conflicts.is_empty())
+        .count()
+        + graph
+            .edges
+            .iter()
+            .filter(|e| !e.conflicts.is_empty())
+            .count();
+}
+
+fn conflict(kind: &str, message: String) -> GraphConflict {
+    GraphConflict {
+        kind: kind.to_string(),
+        message,
+    }
+}
+
+/// Build the dependency graph of `lock` and the `installed` skills; shared
+/// with `fastskill graph`, which renders it as text, DOT or Mermaid.
+pub fn build_graph(
+    lock: Option<&ProjectSkillsLock>,
+    installed: &[SkillDefinition],
+) -> DependencyGraphResponse {
+    let installed_versions: HashMap<String, &str> = installed
+        .iter()
+        .map(|s| (s.id.to_string(), s.version.as_str()))
+        .collect();
+    let locked = lock.map(|l| l.skills.as_slice()).unwrap_or_default();
+
+    let mut nodes: BTreeMap<String, GraphNode> = BTreeMap::new();
+    for entry in locked {
+        let mut conflicts = Vec::new();
+        match installed_versions.get(&entry.id) {
+            None => conflicts.push(conflict(
+                "not_installed",
+                format!("{} is in skills.lock but not installed", entry.id),
+            )),
+            Some(version) if *version != entry.resolved.version => conflicts.push(conflict(
+                "version_drift",
+                format!(
+                    "{} is locked at {} but {} is installed",
+                    entry.id, entry.resolved.version, version
+                ),
+            )),
+            Some(_) => {}
+        }
+        nodes.insert(
+            entry.id.clone(),
+            GraphNode {
+                id: entry.id.clone(),
+                name: Some(entry.name.clone()),
+                version: Some(entry.resolved.version.clone()),
+                depth: Some(entry.depth),
+                locked: true,
+                installed: installed_versions.contains_key(&entry.id),
+                conflicts,
+            },
+        );
+    }
+    for skill in installed {
+        nodes
+            .entry(skill.id.to_string())
+            .or_insert_with(|| GraphNode {
+                id: skill.id.to_string(),
+                name: Some(skill.name.clone()),
+                version: Some(skill.version.clone()),
+                depth: None,
+                locked: false,
+                installed: true,
+                conflicts: Vec::new(),
+            });
+    }
+
+    // Edges: declared dependencies (`id` or `id@constraint`) first, so their
+    // constraints are kept, then "pulled in by" links recorded by the resolver.
+    let mut edges: Vec<GraphEdge> = Vec::new();
+    let mut seen: HashSet<(String, String)> = HashSet::new();
+    let mut adjacency: BTreeMap<String, Vec<Dependency>> = BTreeMap::new();
+    for entry in locked {
+        for spec in &entry.dependencies {
+            let (to, constraint) = match spec.split_once('@') {
+                Some((id, c)) => (id.trim().to_string(), Some(c.trim().to_string())),
+                None => (spec.trim().to_string(), None),
+            };
+            if to.is_empty() || !seen.insert((entry.id.clone(), to.clone())) {
+                continue;
+            }
+            let mut conflicts = Vec::new();
+            match Dependency::parse(spec) {
+                Ok(dep) => {
+                    let resolved = nodes.get(&to).and_then(|n| n.version.as_deref());
+                    if let (Some(req), Some(version)) = (&dep.version_constraint, resolved) {
+                        if !req.satisfies(version).unwrap_or(false) {
+                            conflicts.push(conflict(
+                                "version_mismatch",
+                                format!(
+                                    "{} requires {} {} but {} is resolved",
+                                    entry.id, to, req, version
+                                ),
+                            ));
+                        }
+                    }
+                    adjacency.entry(entry.id.clone()).or_default().push(dep);
+                }
+                Err(e) => conflicts.push(conflict("invalid_constraint", e.to_string())),
+            }
+            edges.push(GraphEdge {
+                from: entry.id.clone(),
+                to,
+                constraint,
+                conflicts,
+            });
+        }
+    }
+    for entry in locked {
+        let Some(parent) = &entry.parent_skill else {
+            continue;
+        };
+        if seen.insert((parent.clone(), entry.id.clone())) {
+            edges.push(GraphEdge {
+                from: parent.clone(),
+                to: entry.id.clone(),
+                constraint: None,
+                conflicts: Vec::new(),
+            });
+            adjacency
+                .entry(parent.clone())
+                .or_default()
+                .push(Dependency {
+                    skill_id: entry.id.clone(),
+                    version_constraint: None,
+                });
+        }
+    }
+
+    for edge in &mut edges {
+        if !nodes.contains_key(&edge.to) {
+            edge.conflicts.push(conflict(
+                "missing",
+                format!(
+                    "{} depends on {}, which is neither locked nor installed",
+                    edge.from, edge.to
+                ),
+            ));
+        }
+    }
+    let cycle_edges = DependencyGraph::build_graph(adjacency.into_iter().collect()).cycle_edges();
+    for edge in &mut edges {
+        if cycle_edges.contains(&(edge.from.clone(), edge.to.clone())) {
+            edge.conflicts.push(conflict(
+                "cycle",
+                format!("{} -> {} is part of a dependency cycle", edge.from, edge.to),
+            ));
+        }
+    }
+    // Missing targets still get a node so every edge has both ends.
+    for edge in &edges {
+        nodes.entry(edge.to.clone()).or_insert_with(|| GraphNode {
+            id: edge.to.clone(),
+            name: None,
+            version: None,
+            depth: None,
+            locked: false,
+            installed: false,
+            conflicts: Vec::new(),
+        });
+    }
+
+    let conflict_count = nodes.values().filter(|n| !n.conflicts.is_empty()).count()
+        + edges.iter().filter(|e| !e.conflicts.is_empty()).count();
+    DependencyGraphResponse {
+        nodes: nodes.into_values().collect(),
+        edges,
+        conflict_count,
+    }
+}
@@ -0,0 +1,88 @@
+//! Background job endpoint handlers
+//!
+//! Reindex and publish endpoints called with `?async=true` queue a job on
+//! [`AppState::jobs`] and answer `202 Accepted` through [`accepted`]. These
+//! handlers let clients poll and cancel those jobs (see `crate::core::jobs`).
+
+use crate::core::jobs::{Job, JobError};
+use crate::http::errors::{HttpError, HttpResult};
+use crate::http::handlers::AppState;
+use crate::http::models::*;
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+
+/// `202 Accepted` for a queued job, with `Location` pointing at its status
+pub(super) fn accepted(job: Job) -> Response {
+    let location = format!("/api/v1/jobs/{}", job.id);
+    (
+        StatusCode::ACCEPTED,
+        [(header::LOCATION, location)],
+        Json(ApiResponse::success(JobResponse::from(job))),
+    )
+        .into_response()
+}
+
+/// GET /api/v1/jobs - Queued, running and recently finished jobs, newest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/jobs",
+    tag = "server",
+    responses((status = 200, description = "Known jobs", body = ApiResponse<JobsListResponse>))
+)]
+pub async fn list_jobs(
+    State(state): State<AppState>,
+) -> HttpResult<Json<ApiResponse<JobsListResponse>>> {
+    let jobs: Vec<JobResponse> = state.jobs.list().into_iter().map(Into::into).collect();
+    let count = jobs.len();
+    Ok(Json(ApiResponse::success(JobsListResponse { jobs, count })))
+}
+
+/// GET /api/v1/jobs/{id} - Status, progress and, once done, result of a job
+#[utoipa::path(
+    get,
+    path = "/api/v1/jobs/{id}",
+    tag = "server",
+    params(("id" = String, Path, description = "Job id")),
+    responses(
+        (status = 200, description = "The job", body = ApiResponse<JobResponse>),
+        (status = 404, description = "No such job, or it finished long enough ago to be dropped")
+    )
+)]
+pub async fn get_job(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> HttpResult<Json<ApiResponse<JobResponse>>> {
+    let job = state
+        .jobs
+        .get(&id)
+        .ok_or_else(|| HttpError::from(JobError::NotFound(id)))?;
+    Ok(Json(ApiResponse::success(job.into())))
+}
+
+/// POST /api/v1/jobs/{id}/cancel - Stop a queued or running job
+///
+/// The job shows `cancelled` once it has stopped. 409 when it already
+/// finished, or when it is saving its result (a publish writing the package).
+#[utoipa::path(
+    post,
+    path = "/api/v1/jobs/{id}/cancel",
+    tag = "server",
+    params(("id" = String, Path, description = "Job id")),
+    responses(
+        (status = 200, description = "Cancellation requested", body = ApiResponse<JobResponse>),
+        (status = 404, description = "No such job"),
+        (status = 409, description = "The job finished or can no longer be cancelled")
+    )
+)]
+pub async fn cancel_job(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> HttpResult<Json<ApiResponse<JobResponse>>> {
+    let job = state.jobs.cancel(&id)?;
+    tracing::info!("Cancellation requested for job {} ({})", job.id, job.kind);
+    Ok(Json(ApiResponse::success(job.into())))
+}
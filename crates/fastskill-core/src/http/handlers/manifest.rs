@@ -1,7 +1,9 @@
 //! Manifest (skill-project.toml) endpoint handlers
 
+use crate::core::audit::AuditDetails;
 use crate::core::manifest::{DependenciesSection, DependencySpec, SkillProjectToml};
 use crate::core::origin::Origin;
+use crate::core::project_transaction::ProjectTransaction;
 use crate::core::repository::RepositoryManager;
 use crate::core::sources::{MarketplaceSkill, SourcesManager};
 use crate::http::errors::{HttpError, HttpResult};
@@ -9,7 +11,7 @@ use crate::http::handlers::AppState;
 use crate::http::models::*;
 use axum::{
     extract::{Path, State},
-    Json,
+    Extension, Json,
 };
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -51,6 +53,7 @@ fn dep_source_type(spec: &DependencySpec) -> &'static str {
             Origin::Git { .. } => "git",
             Origin::Local { .. } => "local",
             Origin::ZipUrl { .. } => "zip-url",
+            Origin::Pack { .. } => "pack",
             Origin::Repository { .. } => "source",
         },
     }
@@ -67,6 +70,7 @@ fn origin_location(origin: &Origin) -> String {
         },
         Origin::Local { path, .. } => path.to_string_lossy().to_string(),
         Origin::ZipUrl { url } => url.clone(),
+        Origin::Pack { path, skill } => format!("{} ({})", path.display(), skill),
         Origin::Repository { repo, skill, .. } => format!("{} / {}", repo, skill),
     }
 }
@@ -101,6 +105,12 @@ fn get_lock_path(project_path: &std::path::Path) -> Result<PathBuf, HttpError> {
 }
 
 /// GET /api/project - Full skill-project.toml view (metadata, skills_directory, skills with type/location)
+#[utoipa::path(
+    get,
+    path = "/api/v1/project",
+    tag = "project",
+    responses((status = 200, description = "skill-project.toml metadata and dependencies", body = crate::http::openapi::UntypedApiResponse))
+)]
 pub async fn get_project(
     State(state): State<AppState>,
 ) -> HttpResult<axum::Json<ApiResponse<serde_json::Value>>> {
@@ -160,6 +170,12 @@ pub async fn get_project(
 }
 
 /// GET /api/manifest/skills - List all skills from skill-project.toml
+#[utoipa::path(
+    get,
+    path = "/api/v1/manifest/skills",
+    tag = "project",
+    responses((status = 200, description = "Skills declared in skill-project.toml", body = ApiResponse<Vec<ManifestSkillResponse>>))
+)]
 pub async fn list_manifest_skills(
     State(state): State<AppState>,
 ) -> HttpResult<axum::Json<ApiResponse<Vec<ManifestSkillResponse>>>> {
@@ -188,7 +204,7 @@ pub async fn list_manifest_skills(
                         }
                     };
                     let (groups, editable) = match spec {
-                        DependencySpec::Inline { groups, origin } => (
+                        DependencySpec::Inline { groups, origin, .. } => (
                             groups.clone().unwrap_or_default(),
                             matches!(origin, Origin::Local { editable: true, .. }),
                         ),
@@ -211,10 +227,20 @@ pub async fn list_manifest_skills(
 }
 
 /// POST /api/manifest/skills - Add skill to skill-project.toml
+#[utoipa::path(
+    post,
+    path = "/api/v1/manifest/skills",
+    tag = "project",
+    request_body = AddSkillRequest,
+    responses((status = 200, description = "Skill added to skill-project.toml", body = ApiResponse<ManifestSkillResponse>))
+)]
 pub async fn add_skill_to_manifest(
     State(state): State<AppState>,
     Json(request): Json<AddSkillRequest>,
-) -> HttpResult<axum::Json<ApiResponse<ManifestSkillResponse>>> {
+) -> HttpResult<(
+    Extension<AuditDetails>,
+    axum::Json<ApiResponse<ManifestSkillResponse>>,
+)> {
     let project_path = &state.project_file_path;
 
     // Load project or create new
@@ -240,6 +266,10 @@ pub async fn add_skill_to_manifest(
             metadata: None,
             dependencies: None,
             tool: None,
+            optional_dependencies: None,
+            peer_dependencies: None,
+            requires: None,
+            env: None,
         }
     };
 
@@ -285,6 +315,11 @@ pub async fn add_skill_to_manifest(
     // Save project
     save_project(&project, project_path)?;
 
+    let details = AuditDetails {
+        target: Some(request.skill_id.clone()),
+        version: Some(marketplace_skill.version.clone()),
+        source: Some(request.source_name.clone()),
+    };
     let response = ManifestSkillResponse {
         id: request.skill_id.clone(),
         version: Some(marketplace_skill.version),
@@ -293,10 +328,17 @@ pub async fn add_skill_to_manifest(
         source_type: "source".to_string(),
     };
 
-    Ok(Json(ApiResponse::success(response)))
+    Ok((Extension(details), Json(ApiResponse::success(response))))
 }
 
 /// DELETE /api/manifest/skills/:id - Remove skill from skill-project.toml
+#[utoipa::path(
+    delete,
+    path = "/api/v1/manifest/skills/{id}",
+    tag = "project",
+    params(("id" = String, Path, description = "Skill id")),
+    responses((status = 200, description = "Skill removed from skill-project.toml", body = crate::http::openapi::UntypedApiResponse))
+)]
 pub async fn remove_skill_from_manifest(
     Path(skill_id): Path<String>,
     State(state): State<AppState>,
@@ -317,29 +359,56 @@ pub async fn remove_skill_from_manifest(
         deps.dependencies.remove(&skill_id);
     }
 
-    save_project(&project, project_path)?;
-
-    // Remove from lock file if it exists
+    // Save the project and the lock (if it exists) together
+    let mut txn = ProjectTransaction::new(project_path, &lock_path);
+    txn.stage_manifest(&project).map_err(|e| {
+        HttpError::InternalServerError(format!("Failed to save skill-project.toml: {}", e))
+    })?;
     if lock_path.exists() {
         use crate::core::lock::ProjectSkillsLock;
         let mut lock = ProjectSkillsLock::load_from_file(&lock_path).map_err(|e| {
             HttpError::InternalServerError(format!("Failed to load lock file: {}", e))
         })?;
         lock.remove_skill(&skill_id);
-        lock.save_to_file(&lock_path).map_err(|e| {
+        txn.stage_lock(&lock).map_err(|e| {
             HttpError::InternalServerError(format!("Failed to save lock file: {}", e))
         })?;
     }
+    txn.commit().map_err(|e| {
+        HttpError::InternalServerError(format!(
+            "Failed to save skill-project.toml and skills.lock: {}",
+            e
+        ))
+    })?;
+    if lock_path.exists() {
+        state
+            .service
+            .storage()
+            .lock_written(&lock_path)
+            .await
+            .map_err(|e| HttpError::InternalServerError(e.to_string()))?;
+    }
 
     Ok(Json(ApiResponse::success(())))
 }
 
 /// PUT /api/manifest/skills/:id - Update skill in skill-project.toml
+#[utoipa::path(
+    put,
+    path = "/api/v1/manifest/skills/{id}",
+    tag = "project",
+    params(("id" = String, Path, description = "Skill id")),
+    request_body = UpdateSkillRequest,
+    responses((status = 200, description = "Updated manifest entry", body = ApiResponse<ManifestSkillResponse>))
+)]
 pub async fn update_skill_in_manifest(
     Path(skill_id): Path<String>,
     State(state): State<AppState>,
     Json(request): Json<UpdateSkillRequest>,
-) -> HttpResult<axum::Json<ApiResponse<ManifestSkillResponse>>> {
+) -> HttpResult<(
+    Extension<AuditDetails>,
+    axum::Json<ApiResponse<ManifestSkillResponse>>,
+)> {
     let project_path = &state.project_file_path;
 
     if !project_path.exists() {
@@ -384,8 +453,13 @@ pub async fn update_skill_in_manifest(
         editable: request.editable.unwrap_or(false),
         source_type: "source".to_string(),
     };
+    let details = AuditDetails {
+        target: Some(response.id.clone()),
+        version: response.version.clone(),
+        source: None,
+    };
 
-    Ok(Json(ApiResponse::success(response)))
+    Ok((Extension(details), Json(ApiResponse::success(response))))
 }
 
 /// Helper function to find skill in sources
@@ -1,12 +1,21 @@
 //! HTTP request handlers
 
+pub mod admin;
+pub mod audit;
+pub mod changes;
+pub mod graph;
+pub mod jobs;
 pub mod manifest;
 pub mod registry;
 pub mod reindex;
+pub mod replication;
 pub mod resolve;
+pub mod route;
 pub mod search;
 pub mod skills;
 pub mod status;
+pub mod tools;
+pub mod validate;
 
 // Re-export AppState (used by all handlers)
 pub use status::AppState;
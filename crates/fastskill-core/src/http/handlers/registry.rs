@@ -1,5 +1,6 @@
 //! Registry endpoint handlers
 
+use crate::core::audit::AuditDetails;
 use crate::core::repository::RepositoryManager;
 use crate::core::sources::{MarketplaceJson, SourceConfig, SourceDefinition, SourcesManager};
 use crate::http::errors::{HttpError, HttpResult};
@@ -7,7 +8,7 @@ use crate::http::handlers::AppState;
 use crate::http::models::*;
 use axum::{
     extract::{Path, State},
-    Json,
+    Extension, Json,
 };
 use std::collections::HashSet;
 
@@ -149,6 +150,12 @@ async fn get_sources_manager_from_repos(
 }
 
 /// GET /api/v1/registry/sources - List all configured sources/repositories
+#[utoipa::path(
+    get,
+    path = "/api/v1/registry/sources",
+    tag = "registry",
+    responses((status = 200, description = "Configured sources", body = ApiResponse<Vec<SourceResponse>>))
+)]
 pub async fn list_sources(
     State(state): State<AppState>,
 ) -> HttpResult<axum::Json<ApiResponse<Vec<SourceResponse>>>> {
@@ -195,6 +202,12 @@ pub async fn list_sources(
 }
 
 /// GET /api/v1/registry/skills - Get all skills grouped by source
+#[utoipa::path(
+    get,
+    path = "/api/v1/registry/skills",
+    tag = "registry",
+    responses((status = 200, description = "Skills offered by every source", body = ApiResponse<RegistrySkillsResponse>))
+)]
 pub async fn list_all_skills(
     State(state): State<AppState>,
 ) -> HttpResult<axum::Json<ApiResponse<RegistrySkillsResponse>>> {
@@ -281,6 +294,13 @@ pub async fn list_all_skills(
 }
 
 /// GET /api/v1/registry/sources/:name/skills - Get skills from a specific source
+#[utoipa::path(
+    get,
+    path = "/api/v1/registry/sources/{name}/skills",
+    tag = "registry",
+    params(("name" = String, Path, description = "Source name")),
+    responses((status = 200, description = "Skills offered by the source", body = ApiResponse<SourceSkillsResponse>))
+)]
 pub async fn list_source_skills(
     Path(source_name): Path<String>,
     State(state): State<AppState>,
@@ -347,6 +367,13 @@ pub async fn list_source_skills(
 }
 
 /// GET /api/v1/registry/sources/:name/marketplace - Get raw marketplace.json
+#[utoipa::path(
+    get,
+    path = "/api/v1/registry/sources/{name}/marketplace",
+    tag = "registry",
+    params(("name" = String, Path, description = "Source name")),
+    responses((status = 200, description = "The source's marketplace.json", body = ApiResponse<MarketplaceJson>))
+)]
 pub async fn get_marketplace(
     Path(source_name): Path<String>,
     State(state): State<AppState>,
@@ -382,6 +409,12 @@ pub async fn get_marketplace(
 }
 
 /// POST /api/v1/registry/refresh - Refresh sources cache
+#[utoipa::path(
+    post,
+    path = "/api/v1/registry/refresh",
+    tag = "registry",
+    responses((status = 200, description = "Skills offered by every source after the refresh", body = ApiResponse<RegistrySkillsResponse>))
+)]
 pub async fn refresh_sources(
     State(state): State<AppState>,
 ) -> HttpResult<axum::Json<ApiResponse<RegistrySkillsResponse>>> {
@@ -416,6 +449,13 @@ impl SourceDefinition {
 /// descending (newest first) by the same semver ordering `VersionConstraint`
 /// uses (unparseable versions sort lowest). No registry configured, or an
 /// unknown id, is an empty `versions` list — a valid answer, never a 404.
+#[utoipa::path(
+    get,
+    path = "/api/v1/registry/skills/{id}/versions",
+    tag = "registry",
+    params(("id" = String, Path, description = "Skill id")),
+    responses((status = 200, description = "Available versions, newest first", body = ApiResponse<SkillVersionsResponse>))
+)]
 pub async fn list_skill_versions(
     State(state): State<AppState>,
     Path(skill_id): Path<String>,
@@ -470,6 +510,17 @@ pub async fn list_skill_versions(
 ///   - scope: Filter by scope (optional)
 ///   - all_versions: Include all versions (default: false)
 ///   - include_pre_release: Include pre-release versions (default: false)
+#[utoipa::path(
+    get,
+    path = "/api/v1/registry/index/skills",
+    tag = "registry",
+    params(
+        ("scope" = Option<String>, Query, description = "Only skills of this organization"),
+        ("all_versions" = Option<bool>, Query, description = "List every version"),
+        ("include_pre_release" = Option<bool>, Query, description = "Include pre-release versions")
+    ),
+    responses((status = 200, description = "Skills in the registry index", body = Vec<crate::core::registry_index::SkillSummary>))
+)]
 pub async fn list_index_skills(
     State(state): State<AppState>,
     axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
@@ -537,6 +588,227 @@ pub async fn list_index_skills(
     Ok(Json(summaries))
 }
 
+/// POST /api/v1/registry/index/yank - Withdraw a published version
+///
+/// The version stays in the index so projects whose skills.lock pins it keep
+/// installing, but resolvers stop selecting it for new installs.
+#[utoipa::path(
+    post,
+    path = "/api/v1/registry/index/yank",
+    tag = "registry",
+    request_body = YankRequest,
+    responses((status = 200, description = "The version, now yanked", body = ApiResponse<YankResponse>))
+)]
+pub async fn yank_version(
+    State(state): State<AppState>,
+    Json(request): Json<YankRequest>,
+) -> HttpResult<(Extension<AuditDetails>, Json<ApiResponse<YankResponse>>)> {
+    set_yanked(&state, request, true)
+}
+
+/// POST /api/v1/registry/index/unyank - Restore a yanked version
+#[utoipa::path(
+    post,
+    path = "/api/v1/registry/index/unyank",
+    tag = "registry",
+    request_body = YankRequest,
+    responses((status = 200, description = "The version, no longer yanked", body = ApiResponse<YankResponse>))
+)]
+pub async fn unyank_version(
+    State(state): State<AppState>,
+    Json(request): Json<YankRequest>,
+) -> HttpResult<(Extension<AuditDetails>, Json<ApiResponse<YankResponse>>)> {
+    set_yanked(&state, request, false)
+}
+
+fn set_yanked(
+    state: &AppState,
+    request: YankRequest,
+    yanked: bool,
+) -> HttpResult<(Extension<AuditDetails>, Json<ApiResponse<YankResponse>>)> {
+    use crate::core::registry_index::{set_version_yanked, ScopedSkillName};
+
+    let registry_index_path = state
+        .service
+        .config()
+        .registry_index_path
+        .as_ref()
+        .ok_or_else(|| HttpError::NotFound("Registry index path not configured".to_string()))?;
+    let skill = ScopedSkillName::normalize(&request.skill);
+    let version = request.version.trim();
+    if version.is_empty() {
+        return Err(HttpError::BadRequest("version is required".to_string()));
+    }
+
+    // A hosted registry's index also takes publishes; its store orders the writes
+    let entry = match &state.registry {
+        Some(store) => store.set_yanked(&skill, version, yanked)?,
+        None => set_version_yanked(registry_index_path, &skill, version, yanked)?,
+    };
+    tracing::info!(
+        "{} {}@{}",
+        if yanked { "Yanked" } else { "Unyanked" },
+        skill,
+        version
+    );
+    let details = AuditDetails {
+        target: Some(skill.clone()),
+        version: Some(entry.vers.clone()),
+        source: None,
+    };
+    Ok((
+        Extension(details),
+        Json(ApiResponse::success(YankResponse {
+            skill,
+            version: entry.vers,
+            yanked: entry.yanked,
+        })),
+    ))
+}
+
+fn registry_store(
+    state: &AppState,
+) -> HttpResult<std::sync::Arc<crate::core::registry_store::RegistryStore>> {
+    state.registry.clone().ok_or_else(|| {
+        HttpError::NotFound("Registry mode is not enabled (serve --registry)".to_string())
+    })
+}
+
+/// POST /api/v1/registry/publish - Publish a skill package to the hosted registry
+///
+/// The body is the skill ZIP, with SKILL.md at its root or in one top-level
+/// folder. The id and version come from the package; an unscoped id is
+/// published under the `scope` query parameter. 404 unless the server runs with `--registry`,
+/// 403 while API key authentication is off, 409 if the version was already published.
+///
+/// With `?async=true` the package is checked and stored by a background job
+/// (`202 Accepted`); the job fails instead of the request returning 409, and
+/// cannot be cancelled once it starts writing the package.
+#[utoipa::path(
+    post,
+    path = "/api/v1/registry/publish",
+    tag = "registry",
+    params(("scope" = Option<String>, Query, description = "Scope for packages whose id has none"), AsyncQuery),
+    request_body(content = Vec<u8>, description = "Skill ZIP", content_type = "application/zip"),
+    responses(
+        (status = 200, description = "The published version", body = ApiResponse<PublishResponse>),
+        (status = 202, description = "Queued as a background job (`?async=true`)", body = ApiResponse<JobResponse>),
+        (status = 403, description = "API key authentication is off, so anyone could publish"),
+        (status = 409, description = "The version was already published")
+    )
+)]
+pub async fn publish_package(
+    State(state): State<AppState>,
+    axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
+    axum::extract::Query(run): axum::extract::Query<AsyncQuery>,
+    body: axum::body::Bytes,
+) -> HttpResult<(Extension<AuditDetails>, axum::response::Response)> {
+    use crate::core::jobs::JobKind;
+    use crate::core::service::ServiceError;
+    use axum::response::IntoResponse;
+
+    let store = registry_store(&state)?;
+    // Without keys the auth middleware lets every request through
+    let keys_enabled = match &state.api_keys {
+        Some(keys) => keys.current()?.is_enabled(),
+        None => false,
+    };
+    if !keys_enabled {
+        return Err(HttpError::Forbidden(
+            "Publishing needs API key authentication; create a write:skills key with \
+             `fastskill admin keys create` first"
+                .to_string(),
+        ));
+    }
+    let scope = params
+        .get("scope")
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+    if run.run_async {
+        let job = state
+            .jobs
+            .submit(JobKind::Publish, move |handle| async move {
+                handle.progress(0, "Checking and storing the package");
+                handle.begin_commit()?;
+                let entry =
+                    tokio::task::spawn_blocking(move || store.publish(scope.as_deref(), &body))
+                        .await
+                        .map_err(|e| {
+                            ServiceError::Custom(format!("Publish task failed: {}", e))
+                        })??;
+                tracing::info!("Published {} {} (background job)", entry.name, entry.vers);
+                serde_json::to_value(PublishResponse {
+                    skill: entry.name,
+                    version: entry.vers,
+                    cksum: entry.cksum,
+                    download_url: entry.download_url,
+                })
+                .map_err(|e| ServiceError::Custom(format!("Failed to encode result: {}", e)))
+            })
+            .await;
+        return Ok((
+            Extension(AuditDetails::default()),
+            super::jobs::accepted(job),
+        ));
+    }
+    let entry = tokio::task::spawn_blocking(move || store.publish(scope.as_deref(), &body))
+        .await
+        .map_err(|e| HttpError::InternalServerError(format!("Publish task failed: {}", e)))?
+        .map_err(|e| match e {
+            ServiceError::AlreadyIndexed(msg) => {
+                HttpError::Conflict(format!("Version already published: {}", msg))
+            }
+            e => HttpError::from(e),
+        })?;
+
+    let details = AuditDetails {
+        target: Some(entry.name.clone()),
+        version: Some(entry.vers.clone()),
+        source: None,
+    };
+    Ok((
+        Extension(details),
+        Json(ApiResponse::success(PublishResponse {
+            skill: entry.name,
+            version: entry.vers,
+            cksum: entry.cksum,
+            download_url: entry.download_url,
+        }))
+        .into_response(),
+    ))
+}
+
+/// GET /api/v1/registry/download/{scope}/{name}/{version} - A published package
+///
+/// The ZIP the index entry's `download_url` points at. 404 unless the server
+/// runs with `--registry`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/registry/download/{scope}/{name}/{version}",
+    tag = "registry",
+    params(
+        ("scope" = String, Path, description = "Skill scope"),
+        ("name" = String, Path, description = "Skill name"),
+        ("version" = String, Path, description = "Published version")
+    ),
+    responses(
+        (status = 200, description = "The package (`application/zip`)"),
+        (status = 404, description = "No such version, or registry mode is off")
+    )
+)]
+pub async fn download_package(
+    State(state): State<AppState>,
+    Path((scope, name, version)): Path<(String, String, String)>,
+) -> HttpResult<axum::response::Response> {
+    let store = registry_store(&state)?;
+    let bytes = store.read_blob(&scope, &name, &version)?;
+    axum::response::Response::builder()
+        .status(axum::http::StatusCode::OK)
+        .header(axum::http::header::CONTENT_TYPE, "application/zip")
+        .body(axum::body::Body::from(bytes))
+        .map_err(|e| HttpError::InternalServerError(format!("Failed to build response: {}", e)))
+}
+
 /// GET /index/:skill_id - Serve registry index file for a skill (flat layout)
 /// This endpoint serves the index file from the registry_index_path
 /// Format: /index/{scope}/{skill-name} (e.g., /index/dev-user/test-skill)
@@ -575,12 +847,12 @@ pub async fn serve_index_file(
         )));
     }
 
-    // Read the index file (use canonical path)
-    match tokio::fs::read_to_string(&canonical_index_path).await {
+    // Read the index file (use canonical path) and pass the bytes through as-is
+    match crate::core::content::SkillContent::read(&canonical_index_path).await {
         Ok(content) => Ok(axum::response::Response::builder()
             .status(axum::http::StatusCode::OK)
             .header("Content-Type", "application/json")
-            .body(axum::body::Body::from(content))
+            .body(axum::body::Body::from(content.bytes()))
             .map_err(|e| {
                 HttpError::InternalServerError(format!("Failed to build response: {}", e))
             })?),
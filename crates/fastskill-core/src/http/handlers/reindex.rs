@@ -7,37 +7,97 @@
 //! embedding provider is configured the seam skips silently — that is surfaced
 //! here as `200` with `reindexed: false` and a `reason`, not an error.
 //!
+//! With `?async=true` the reindex runs as a background job instead: the
+//! response is `202 Accepted` with the job, whose progress follows the
+//! seam's observer and whose result is the outcome below (see `super::jobs`).
+//!
 //! The core seam has no single-skill mode (it always walks the whole skills
 //! directory); rather than growing the core seam for an HTTP-only convenience,
 //! `POST /reindex/{id}` reindexes the *whole* index, same as `POST /reindex`.
 //! This is a deliberate, documented simplification, not an oversight.
 
+use crate::core::jobs::JobKind;
+use crate::core::reindex::{ReindexMode, ReindexOutcome, ReindexProgress};
+use crate::core::service::ServiceError;
 use crate::http::errors::HttpResult;
 use crate::http::handlers::AppState;
 use crate::http::models::*;
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
+    response::{IntoResponse, Response},
     Json,
 };
 
-fn outcome_response(
-    outcome: crate::core::reindex::ReindexOutcome,
-) -> axum::Json<ApiResponse<ReindexOutcomeResponse>> {
-    Json(ApiResponse::success(ReindexOutcomeResponse {
+fn outcome_data(outcome: ReindexOutcome) -> ReindexOutcomeResponse {
+    ReindexOutcomeResponse {
         reindexed: outcome.reindexed,
         count: outcome.count,
+        unchanged: outcome.unchanged,
+        removed: outcome.removed,
         reason: outcome.reason,
-    }))
+    }
+}
+
+fn outcome_response(outcome: ReindexOutcome) -> Json<ApiResponse<ReindexOutcomeResponse>> {
+    Json(ApiResponse::success(outcome_data(outcome)))
+}
+
+/// Reindex in `mode`, in the background when `run_async`: `202` with the job,
+/// otherwise `200` with the outcome
+pub(super) async fn run_reindex(
+    state: &AppState,
+    mode: ReindexMode,
+    run_async: bool,
+) -> HttpResult<Response> {
+    if !run_async {
+        let outcome = state.service.reindex_with_mode(None, None, mode).await?;
+        return Ok(outcome_response(outcome).into_response());
+    }
+    let service = state.service.clone();
+    let job = state
+        .jobs
+        .submit(JobKind::Reindex, move |handle| async move {
+            let observer = |p: ReindexProgress| {
+                handle.progress_of(p.current, p.total, format!("Embedded {}", p.skill_id));
+            };
+            let outcome = service
+                .reindex_with_mode(None, Some(&observer), mode)
+                .await?;
+            serde_json::to_value(outcome_data(outcome))
+                .map_err(|e| ServiceError::Custom(format!("Failed to encode outcome: {}", e)))
+        })
+        .await;
+    Ok(super::jobs::accepted(job))
+}
+
+/// `force: true` re-embeds every skill; otherwise only changed ones.
+fn reindex_mode(request: &ReindexRequest) -> ReindexMode {
+    if request.force.unwrap_or(false) {
+        ReindexMode::Full
+    } else {
+        ReindexMode::Incremental
+    }
 }
 
 /// POST /api/v1/reindex - Reindex all skills (skips silently, 200, when no
 /// embedding provider is configured; ADR-0002).
+#[utoipa::path(
+    post,
+    path = "/api/v1/reindex",
+    tag = "server",
+    params(AsyncQuery),
+    request_body = ReindexRequest,
+    responses(
+        (status = 200, description = "Reindex outcome", body = ApiResponse<ReindexOutcomeResponse>),
+        (status = 202, description = "Queued as a background job (`?async=true`)", body = ApiResponse<JobResponse>)
+    )
+)]
 pub async fn reindex_all(
     State(state): State<AppState>,
-    Json(_request): Json<ReindexRequest>,
-) -> HttpResult<axum::Json<ApiResponse<ReindexOutcomeResponse>>> {
-    let outcome = state.service.reindex(None, None).await?;
-    Ok(outcome_response(outcome))
+    Query(query): Query<AsyncQuery>,
+    Json(request): Json<ReindexRequest>,
+) -> HttpResult<Response> {
+    run_reindex(&state, reindex_mode(&request), query.run_async).await
 }
 
 /// POST /api/v1/reindex/{id} - Reindex a single skill.
@@ -45,11 +105,22 @@ pub async fn reindex_all(
 /// The core reindex seam has no single-skill mode (see module docs); `id` is
 /// accepted for URL/API-contract compatibility but the whole index is
 /// reindexed, same as `POST /reindex`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/reindex/{id}",
+    tag = "server",
+    params(("id" = String, Path, description = "Skill id"), AsyncQuery),
+    request_body = ReindexRequest,
+    responses(
+        (status = 200, description = "Reindex outcome", body = ApiResponse<ReindexOutcomeResponse>),
+        (status = 202, description = "Queued as a background job (`?async=true`)", body = ApiResponse<JobResponse>)
+    )
+)]
 pub async fn reindex_skill(
     State(state): State<AppState>,
     Path(_skill_id): Path<String>,
-    Json(_request): Json<ReindexRequest>,
-) -> HttpResult<axum::Json<ApiResponse<ReindexOutcomeResponse>>> {
-    let outcome = state.service.reindex(None, None).await?;
-    Ok(outcome_response(outcome))
+    Query(query): Query<AsyncQuery>,
+    Json(request): Json<ReindexRequest>,
+) -> HttpResult<Response> {
+    run_reindex(&state, reindex_mode(&request), query.run_async).await
 }
@@ -0,0 +1,105 @@
+//! Replication endpoint handlers (primary side)
+//!
+//! Both routes answer 404 unless the server runs as a replication primary.
+
+use crate::core::replication::{build_snapshot, pack_skill, ReplicationKey, SIGNATURE_HEADER};
+use crate::core::service::SkillId;
+use crate::http::errors::{HttpError, HttpResult};
+use crate::http::handlers::AppState;
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::Response,
+};
+
+fn replication_key(state: &AppState) -> HttpResult<&ReplicationKey> {
+    state
+        .replication
+        .as_ref()
+        .ok_or_else(|| HttpError::NotFound("replication is not enabled on this server".to_string()))
+}
+
+/// GET /api/v1/replication/snapshot - Signed list of skill hashes and index entries
+///
+/// The signature is also the ETag; a matching `If-None-Match` gets `304`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/replication/snapshot",
+    tag = "replication",
+    responses(
+        (status = 200, description = "Signed JSON list of skill hashes and index entries"),
+        (status = 304, description = "Unchanged since the `If-None-Match` ETag"),
+        (status = 404, description = "The server is not a replication primary")
+    )
+)]
+pub async fn get_snapshot(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> HttpResult<Response> {
+    let key = replication_key(&state)?;
+    let index = state.service.vector_index_service();
+    let snapshot =
+        build_snapshot(&state.service.config().skill_storage_path, index.as_deref()).await?;
+    let body = serde_json::to_vec(&snapshot)
+        .map_err(|e| HttpError::InternalServerError(format!("Failed to encode snapshot: {}", e)))?;
+    let signature = key.sign(&body)?;
+    let etag = format!("\"{}\"", signature);
+
+    let unchanged = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v == etag);
+    let response = Response::builder().header(header::ETAG, &etag);
+    let response = if unchanged {
+        response
+            .status(StatusCode::NOT_MODIFIED)
+            .body(Body::empty())
+    } else {
+        response
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, &signature)
+            .body(Body::from(body))
+    };
+    response.map_err(|e| HttpError::InternalServerError(format!("Failed to build response: {}", e)))
+}
+
+/// GET /api/v1/replication/skills/{id}/archive - Zip of one skill directory
+#[utoipa::path(
+    get,
+    path = "/api/v1/replication/skills/{id}/archive",
+    tag = "replication",
+    params(("id" = String, Path, description = "Skill id")),
+    responses(
+        (status = 200, description = "Zip of the skill directory (`application/zip`)"),
+        (status = 404, description = "No such skill, or the server is not a replication primary")
+    )
+)]
+pub async fn get_skill_archive(
+    State(state): State<AppState>,
+    Path(skill_id): Path<String>,
+) -> HttpResult<Response> {
+    replication_key(&state)?;
+    let skill_id = SkillId::new(skill_id)?;
+    let skill_dir = state
+        .service
+        .config()
+        .skill_storage_path
+        .join(skill_id.as_str());
+    if !skill_dir.join("SKILL.md").is_file() {
+        return Err(HttpError::NotFound(format!(
+            "Skill not found: {}",
+            skill_id
+        )));
+    }
+
+    let archive = tokio::task::spawn_blocking(move || pack_skill(&skill_dir))
+        .await
+        .map_err(|e| HttpError::InternalServerError(format!("Packing task failed: {}", e)))??;
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/zip")
+        .body(Body::from(archive))
+        .map_err(|e| HttpError::InternalServerError(format!("Failed to build response: {}", e)))
+}
@@ -1,16 +1,45 @@
 //! Resolve endpoint handler
 
-use crate::core::context_resolver::ResolveContextRequest;
+use crate::core::context_resolver::{ContentMode, ResolveContextRequest};
+use crate::core::service::ServiceError;
+use crate::core::skill_injection::InjectionRules;
+use crate::http::client_profile::{resolve_client_profile, visible_skills};
 use crate::http::errors::{HttpError, HttpResult};
 use crate::http::handlers::AppState;
 use crate::http::models::ApiResponse;
-use axum::{extract::State, Json};
+use axum::{extract::State, http::HeaderMap, Json};
 use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Integrity failures keep their `INTEGRITY_ERROR` code; anything else the
+/// resolver reports is a service error.
+fn resolve_error(err: ServiceError) -> HttpError {
+    match err {
+        ServiceError::Integrity(err) => err.into(),
+        err => HttpError::ServiceError(err.to_string()),
+    }
+}
 
 /// POST /api/resolve - Resolve skills with canonical paths and optional content
+///
+/// When the caller matches a client profile, its relevance floor, result cap and
+/// allowed scopes are applied to the results. Skills the caller may not see
+/// are never returned. Per-skill injection rules (see
+/// [`crate::core::skill_injection`]) then rescore, drop or add skills. With
+/// `max_tokens`, the token budget is spent only on the results that remain
+/// after filtering. In integrity mode, every SKILL.md read for content is
+/// verified first; a tampered skill fails the request with `INTEGRITY_ERROR`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/resolve",
+    tag = "search",
+    request_body = ResolveContextRequest,
+    responses((status = 200, description = "Skills relevant to the prompt", body = ApiResponse<crate::core::context_resolver::ResolveContextResponse>))
+)]
 pub async fn resolve_context(
     State(state): State<AppState>,
-    Json(request): Json<ResolveContextRequest>,
+    headers: HeaderMap,
+    Json(mut request): Json<ResolveContextRequest>,
 ) -> HttpResult<axum::Json<ApiResponse<crate::core::context_resolver::ResolveContextResponse>>> {
     if request.prompt.trim().is_empty() {
         let mut errs = HashMap::new();
@@ -30,11 +59,84 @@ pub async fn resolve_context(
         return Err(HttpError::ValidationError(errs));
     }
 
-    let resolver = state.service.context_resolver();
-    let response = resolver
+    let profile = resolve_client_profile(state.service.config(), &headers);
+    let limit = profile.map_or(request.limit, |p| p.cap_limit(request.limit));
+    let mut visible = visible_skills(&state.service, profile).await?;
+    if let Some(p) = profile {
+        visible.retain(|skill| p.allows_skill(skill.id.as_str()));
+    }
+    if let Some(p) = profile {
+        // Scope filtering happens after ranking; widen the candidate pool so
+        // scoped profiles can still fill `limit`.
+        if !p.allowed_scopes.is_empty() {
+            request.limit = request.limit.max(50);
+        }
+    }
+
+    let max_tokens = request.max_tokens.take();
+    if let Some(max_tokens) = max_tokens {
+        if max_tokens == 0 {
+            let mut errs = HashMap::new();
+            errs.insert(
+                "max_tokens".to_string(),
+                vec!["RESOLVE_BUDGET_ZERO: max_tokens must be greater than 0".to_string()],
+            );
+            return Err(HttpError::ValidationError(errs));
+        }
+        request.include_content = ContentMode::None;
+    }
+    let resolve_paths = request.resolve_paths;
+    let content_mode = request.include_content.clone();
+
+    let mut resolver = state.service.context_resolver();
+    if let Some(guard) = &state.integrity {
+        resolver = resolver.with_integrity_guard(Arc::clone(guard));
+    }
+    let mut response = resolver
         .resolve_context(request)
         .await
-        .map_err(|e| HttpError::ServiceError(e.to_string()))?;
+        .map_err(resolve_error)?;
+
+    response
+        .results
+        .retain(|r| visible.iter().any(|skill| skill.id.as_str() == r.skill_id));
+
+    let no_rules = InjectionRules::default();
+    let rules = state
+        .service
+        .config()
+        .http_server
+        .as_ref()
+        .map_or(&no_rules, |http| &http.injection_rules);
+    for skill in rules.missing_always(&response.results, &visible) {
+        let result = resolver
+            .resolved_skill(
+                skill,
+                skill.name.clone(),
+                skill.description.clone(),
+                0.0,
+                resolve_paths,
+                &content_mode,
+            )
+            .await
+            .map_err(resolve_error)?;
+        response.results.push(result);
+    }
+    let min_relevance = profile.and_then(|p| p.dynamic_min_relevance);
+    rules.apply(
+        &response.query,
+        &mut response.results,
+        &visible,
+        min_relevance,
+    );
+    response.results.truncate(limit);
+    if let Some(max_tokens) = max_tokens {
+        resolver
+            .apply_budget(&mut response, max_tokens)
+            .await
+            .map_err(resolve_error)?;
+    }
+    crate::core::metrics::global().record_skill_injections(response.results.len());
 
     Ok(axum::Json(ApiResponse::success(response)))
 }
@@ -0,0 +1,63 @@
+//! Route endpoint handler
+
+use crate::core::routing::{RouteDecision, RouteRequest};
+use crate::http::client_profile::resolve_client_profile;
+use crate::http::errors::{HttpError, HttpResult};
+use crate::http::handlers::AppState;
+use crate::http::models::ApiResponse;
+use axum::{extract::State, http::HeaderMap, Json};
+use std::collections::HashMap;
+
+/// POST /api/v1/route - Choose skills for a query under a routing policy
+///
+/// Only skills the caller may see are candidates. A client profile's result
+/// cap lowers `max_skills` and its relevance floor raises `min_confidence`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/route",
+    tag = "search",
+    request_body = RouteRequest,
+    responses((status = 200, description = "Chosen and rejected skills with reasons", body = ApiResponse<RouteDecision>))
+)]
+pub async fn route_skills(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(mut request): Json<RouteRequest>,
+) -> HttpResult<Json<ApiResponse<RouteDecision>>> {
+    if request.query.trim().is_empty() {
+        let mut errs = HashMap::new();
+        errs.insert(
+            "query".to_string(),
+            vec!["ROUTE_EMPTY_QUERY: query cannot be empty".to_string()],
+        );
+        return Err(HttpError::ValidationError(errs));
+    }
+    request.policy.validate()?;
+
+    let profile = resolve_client_profile(state.service.config(), &headers);
+    if let Some(p) = profile {
+        request.policy.max_skills = p.cap_limit(request.policy.max_skills);
+        if let Some(min) = p.dynamic_min_relevance {
+            request.policy.min_confidence = request.policy.min_confidence.max(min.min(1.0));
+        }
+    }
+
+    let skills: Vec<_> = state
+        .service
+        .skill_manager()
+        .list_skills()
+        .await?
+        .into_iter()
+        .filter(|skill| {
+            skill.access.allows(profile)
+                && profile.is_none_or(|p| p.allows_skill(skill.id.as_str()))
+        })
+        .collect();
+
+    let decision = state
+        .service
+        .routing_service()
+        .route(&request.query, &request.policy, &skills)
+        .await?;
+    Ok(Json(ApiResponse::success(decision)))
+}
@@ -1,89 +1,69 @@
 //! Skills CRUD endpoint handlers
 
+use crate::core::audit::AuditDetails;
+use crate::core::content::SkillContent;
 use crate::core::install::{AddMode, UpdatePreflight};
 use crate::core::manifest::SkillProjectToml;
 use crate::core::origin::Origin;
-use crate::core::service::ServiceError;
 use crate::core::version::VersionConstraint;
+use crate::http::client_profile::resolve_client_profile;
 use crate::http::errors::{HttpError, HttpResult};
+use crate::http::facade;
 use crate::http::handlers::AppState;
 use crate::http::models::*;
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
-    Json,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
 };
 
-fn skill_metadata_json(skill: &crate::core::skill_manager::SkillDefinition) -> serde_json::Value {
-    let origin = serde_json::to_value(&skill.origin).unwrap_or(serde_json::Value::Null);
-    serde_json::json!({
-        "id": skill.id,
-        "name": skill.name,
-        "description": skill.description,
-        "version": skill.version,
-        "author": skill.author,
-        "created_at": skill.created_at.to_rfc3339(),
-        "updated_at": skill.updated_at.to_rfc3339(),
-        "skill_file": skill.skill_file,
-        "reference_files": skill.reference_files,
-        "script_files": skill.script_files,
-        "asset_files": skill.asset_files,
-        "origin": origin
-    })
-}
-
-/// GET /api/skills - List all skills
+/// GET /api/skills - List skills in id order
+///
+/// Collections of up to
+/// [`LARGE_COLLECTION_THRESHOLD`](crate::core::vector_index::LARGE_COLLECTION_THRESHOLD)
+/// skills come back in one response; larger ones are paged (`?limit=`,
+/// `?after=` with the previous `nextCursor`) so only one page of definitions
+/// is cloned per request.
+/// Skills the caller may not see are left out of the page, so a page can be
+/// shorter than `limit`; `total` still counts every installed skill.
+#[utoipa::path(
+    get,
+    path = "/api/v1/skills",
+    tag = "skills",
+    params(ListSkillsQuery),
+    responses((status = 200, description = "A page of installed skills", body = ApiResponse<SkillsListResponse>))
+)]
 pub async fn list_skills(
     State(state): State<AppState>,
+    Query(query): Query<ListSkillsQuery>,
+    headers: HeaderMap,
 ) -> HttpResult<axum::Json<ApiResponse<SkillsListResponse>>> {
-    let skills = state.service.skill_manager().list_skills().await?;
-
-    let skill_responses: Vec<SkillResponse> = skills
-        .clone()
-        .into_iter()
-        .map(|skill| SkillResponse {
-            id: skill.id.to_string(),
-            name: skill.name.clone(),
-            description: skill.description.clone(),
-            metadata: skill_metadata_json(&skill),
-            created_at: Some(skill.created_at.to_rfc3339()),
-            updated_at: Some(skill.updated_at.to_rfc3339()),
-        })
-        .collect();
-
-    let response = SkillsListResponse {
-        skills: skill_responses,
-        count: skills.len(),
-        total: skills.len(),
-    };
-
+    let profile = resolve_client_profile(state.service.config(), &headers);
+    let response = facade::list_skills(&state, profile, &query).await?;
     Ok(axum::Json(ApiResponse::success(response)))
 }
 
 /// GET /api/skills/{id} - Get skill details
+///
+/// A skill the caller may not see is reported as not found.
+#[utoipa::path(
+    get,
+    path = "/api/v1/skills/{id}",
+    tag = "skills",
+    params(("id" = String, Path, description = "Skill id")),
+    responses(
+        (status = 200, description = "The skill", body = ApiResponse<SkillResponse>),
+        (status = 404, description = "No such skill", body = crate::http::openapi::UntypedApiResponse)
+    )
+)]
 pub async fn get_skill(
     State(state): State<AppState>,
     Path(skill_id): Path<String>,
+    headers: HeaderMap,
 ) -> HttpResult<axum::Json<ApiResponse<SkillResponse>>> {
-    let skills = state.service.skill_manager().list_skills().await?;
-    let skill_id_parsed = crate::core::service::SkillId::new(skill_id.clone())
-        .map_err(|_| HttpError::BadRequest("Invalid skill ID format".to_string()))?;
-    let skill = skills
-        .into_iter()
-        .find(|s| s.id == skill_id_parsed)
-        .ok_or_else(|| HttpError::NotFound(format!("Skill not found: {}", skill_id)))?;
-
-    let metadata = skill_metadata_json(&skill);
-
-    let response = SkillResponse {
-        id: skill.id.to_string(),
-        name: skill.name.clone(),
-        description: skill.description.clone(),
-        metadata,
-        created_at: Some(skill.created_at.to_rfc3339()),
-        updated_at: Some(skill.updated_at.to_rfc3339()),
-    };
-
+    let profile = resolve_client_profile(state.service.config(), &headers);
+    let response = facade::get_skill(&state, profile, &skill_id).await?;
     Ok(axum::Json(ApiResponse::success(response)))
 }
 
@@ -94,18 +74,35 @@ pub async fn get_skill(
 /// skills directory, or the request is rejected — this endpoint must never
 /// become a directory-traversal primitive, even though `serve` itself is not a
 /// security boundary (ADR-0003).
+///
+/// A raw request whose `Accept` header asks for `text/markdown` or
+/// `text/plain` gets the file bytes as the response body, without the JSON
+/// envelope and without UTF-8 validation or re-encoding. A skill the caller
+/// may not see is reported as not found.
+#[utoipa::path(
+    get,
+    path = "/api/v1/skills/{id}/content",
+    tag = "skills",
+    params(("id" = String, Path, description = "Skill id"), ContentQuery),
+    responses(
+        (status = 200, description = "The skill's SKILL.md; the file itself with `Accept: text/markdown`", body = ApiResponse<SkillContentResponse>),
+        (status = 404, description = "No such skill", body = crate::http::openapi::UntypedApiResponse)
+    )
+)]
 pub async fn get_skill_content(
     State(state): State<AppState>,
     Path(skill_id): Path<String>,
     Query(query): Query<ContentQuery>,
-) -> HttpResult<axum::Json<ApiResponse<SkillContentResponse>>> {
+    headers: HeaderMap,
+) -> HttpResult<Response> {
     let skill_id_parsed = crate::core::service::SkillId::new(skill_id.clone())
         .map_err(|_| HttpError::BadRequest("Invalid skill ID format".to_string()))?;
 
+    let profile = resolve_client_profile(state.service.config(), &headers);
     let skills = state.service.skill_manager().list_skills().await?;
     let skill = skills
         .into_iter()
-        .find(|s| s.id == skill_id_parsed)
+        .find(|s| s.id == skill_id_parsed && s.access.allows(profile))
         .ok_or_else(|| HttpError::NotFound(format!("Skill not found: {}", skill_id)))?;
 
     // `skill_file` may be stored relative (e.g. `./skills/{id}/SKILL.md`) or
@@ -130,9 +127,30 @@ pub async fn get_skill_content(
                 }
             })?;
 
-    let content = tokio::fs::read_to_string(&confined)
+    let content = SkillContent::read(&confined)
         .await
         .map_err(|_| HttpError::NotFound(format!("Skill file not found on disk: {}", skill_id)))?;
+    state.verify_integrity(skill.id.as_str(), content.as_bytes())?;
+    // Usage counting is best-effort; it never fails the read
+    if let Err(e) = state
+        .service
+        .storage()
+        .skill_used(skill.id.as_str(), "content")
+        .await
+    {
+        tracing::warn!("Failed to record usage of {}: {}", skill_id, e);
+    }
+
+    let format = query.format.unwrap_or_default();
+    if format == ContentFormat::Raw && wants_plain_text(&headers) {
+        return Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "text/markdown; charset=utf-8")
+            .body(axum::body::Body::from(content.bytes()))
+            .map_err(|e| {
+                HttpError::InternalServerError(format!("Failed to build response: {}", e))
+            });
+    }
 
     // Report the skills-dir-relative path, not the absolute server path — the UI
     // only needs the logical location, and leaking the server's directory layout
@@ -151,17 +169,32 @@ pub async fn get_skill_content(
         .or_else(|| confined.file_name().map(std::path::PathBuf::from))
         .unwrap_or_else(|| confined.clone());
 
-    let format = query.format.unwrap_or_default();
     let rendered_content = match format {
         ContentFormat::Raw => content,
-        ContentFormat::Html => render_skill_markdown_html(&content),
+        ContentFormat::Html => {
+            SkillContent::from(render_skill_markdown_html(&content.to_str_lossy()))
+        }
     };
 
     Ok(axum::Json(ApiResponse::success(SkillContentResponse {
         path: display_path.to_string_lossy().to_string(),
         format: format.as_str().to_string(),
         content: rendered_content,
-    })))
+    }))
+    .into_response())
+}
+
+/// Whether the client asked for the file itself rather than the JSON envelope.
+fn wants_plain_text(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|media| media.split(';').next().unwrap_or_default().trim())
+        .any(|media| {
+            media.eq_ignore_ascii_case("text/markdown") || media.eq_ignore_ascii_case("text/plain")
+        })
 }
 
 /// Render `SKILL.md` Markdown to sanitized HTML (spec 003 v2 / Phase 4 §5
@@ -175,72 +208,18 @@ fn render_skill_markdown_html(markdown: &str) -> String {
 }
 
 /// DELETE /api/skills/{id} - Delete skill (remove from manifest and storage, unregister)
+#[utoipa::path(
+    delete,
+    path = "/api/v1/skills/{id}",
+    tag = "skills",
+    params(("id" = String, Path, description = "Skill id")),
+    responses((status = 200, description = "Skill removed", body = crate::http::openapi::UntypedApiResponse))
+)]
 pub async fn delete_skill(
     State(state): State<AppState>,
     Path(skill_id): Path<String>,
 ) -> HttpResult<axum::Json<ApiResponse<serde_json::Value>>> {
-    let skill_id_parsed = crate::core::service::SkillId::new(skill_id.clone())
-        .map_err(|_| HttpError::BadRequest("Invalid skill ID format".to_string()))?;
-
-    let skills = state.service.skill_manager().list_skills().await?;
-    let skill = skills
-        .into_iter()
-        .find(|s| s.id == skill_id_parsed)
-        .ok_or_else(|| HttpError::NotFound(format!("Skill not found: {}", skill_id)))?;
-
-    let project_path = &state.project_file_path;
-    let lock_path = if let Some(parent) = project_path.parent() {
-        let safe_parent = if parent.exists() {
-            parent.canonicalize().map_err(|e| {
-                HttpError::InternalServerError(format!("Failed to resolve parent path: {}", e))
-            })?
-        } else {
-            parent.to_path_buf()
-        };
-        safe_parent.join("skills.lock")
-    } else {
-        std::path::PathBuf::from("skills.lock")
-    };
-
-    if project_path.exists() {
-        let mut project = crate::core::manifest::SkillProjectToml::load_from_file(project_path)
-            .map_err(|e| {
-                HttpError::InternalServerError(format!("Failed to load project: {}", e))
-            })?;
-        if let Some(ref mut deps) = project.dependencies {
-            deps.dependencies.remove(&skill_id);
-        }
-        project.save_to_file(project_path).map_err(|e| {
-            HttpError::InternalServerError(format!("Failed to save project: {}", e))
-        })?;
-        if lock_path.exists() {
-            let mut lock = crate::core::lock::ProjectSkillsLock::load_from_file(&lock_path)
-                .map_err(|e| {
-                    HttpError::InternalServerError(format!("Failed to load lock: {}", e))
-                })?;
-            lock.remove_skill(&skill_id);
-            lock.save_to_file(&lock_path).map_err(|e| {
-                HttpError::InternalServerError(format!("Failed to save lock: {}", e))
-            })?;
-        }
-    }
-
-    let skill_dir = skill.skill_file.parent().ok_or_else(|| {
-        HttpError::InternalServerError("Skill file has no parent dir".to_string())
-    })?;
-    if skill_dir.exists() {
-        tokio::fs::remove_dir_all(skill_dir).await.map_err(|e| {
-            HttpError::InternalServerError(format!("Failed to remove skill dir: {}", e))
-        })?;
-    }
-
-    state
-        .service
-        .skill_manager()
-        .unregister_skill(&skill_id_parsed)
-        .await
-        .map_err(|e| HttpError::InternalServerError(e.to_string()))?;
-
+    facade::delete_skill(&state, &skill_id).await?;
     Ok(axum::Json(ApiResponse::success(serde_json::json!({
         "message": "Skill removed"
     }))))
@@ -252,42 +231,36 @@ pub async fn delete_skill(
 /// detection of its own — then `AddMode::Fresh` fails with a 409 if the
 /// resolved id is already installed; other seam errors map to 400/500 via the
 /// blanket `ServiceError` → `HttpError` conversion.
+#[utoipa::path(
+    post,
+    path = "/api/v1/skills/install",
+    tag = "skills",
+    request_body = InstallSkillRequest,
+    responses(
+        (status = 201, description = "Skill installed", body = ApiResponse<InstallSkillResponse>),
+        (status = 409, description = "A skill with that id is already installed", body = crate::http::openapi::UntypedApiResponse)
+    )
+)]
 pub async fn install_skill(
     State(state): State<AppState>,
     Json(request): Json<InstallSkillRequest>,
-) -> HttpResult<(StatusCode, axum::Json<ApiResponse<InstallSkillResponse>>)> {
-    // A bad ref / no-default-repo is a client error (400), not a 500 — surface
-    // it distinctly from the blanket `ServiceError` → `HttpError` conversion
-    // below (which maps `Config` to 500, appropriate for the *fetch* path but
-    // not for classifying the ref itself).
-    let origin = state
-        .service
-        .infer_origin(&request.origin)
-        .await
-        .map_err(|e| HttpError::BadRequest(e.to_string()))?;
-
-    match state
-        .service
-        .add_from_origin(origin, AddMode::Fresh, request.groups)
-        .await
-    {
-        Ok(outcome) => {
-            let response = InstallSkillResponse {
-                id: outcome.id,
-                resolved_version: outcome.resolved.version,
-                reindexed: outcome.reindexed,
-            };
-            Ok((StatusCode::CREATED, Json(ApiResponse::success(response))))
-        }
-        // ADR-0005 §Q6 / spec 003 §2: a Fresh conflict on an already-installed
-        // id is a 409, not a generic 400 (which the blanket ServiceError→HttpError
-        // mapping would otherwise give it).
-        Err(ServiceError::AlreadyIndexed(id)) => Err(HttpError::Conflict(format!(
-            "Skill '{}' is already installed",
-            id
-        ))),
-        Err(e) => Err(e.into()),
-    }
+) -> HttpResult<(
+    StatusCode,
+    Extension<AuditDetails>,
+    axum::Json<ApiResponse<InstallSkillResponse>>,
+)> {
+    let source = request.origin.clone();
+    let response = facade::install_skill(&state, request).await?;
+    let details = AuditDetails {
+        target: Some(response.id.clone()),
+        version: Some(response.resolved_version.clone()),
+        source: Some(source),
+    };
+    Ok((
+        StatusCode::CREATED,
+        Extension(details),
+        Json(ApiResponse::success(response)),
+    ))
 }
 
 /// POST /api/v1/skills/update (and its back-compat alias `/skills/upgrade`) -
@@ -299,6 +272,13 @@ pub async fn install_skill(
 /// `check: true` reports the preflight verdict without applying anything.
 /// Always 200 with a per-skill result list — a per-skill failure does not fail
 /// the whole request.
+#[utoipa::path(
+    post,
+    path = "/api/v1/skills/update",
+    tag = "skills",
+    request_body = UpdateSkillsRequest,
+    responses((status = 200, description = "Outcome for each skill", body = ApiResponse<Vec<SkillUpdateResult>>))
+)]
 pub async fn update_skills(
     State(state): State<AppState>,
     Json(payload): Json<Option<UpdateSkillsRequest>>,
@@ -368,12 +348,15 @@ pub async fn update_skills(
                 .add_from_origin(pinned_origin, AddMode::Update, groups)
                 .await
             {
-                Ok(outcome) => SkillUpdateResult {
-                    id: entry_id,
-                    outcome: "updated".to_string(),
-                    reason: None,
-                    resolved_version: Some(outcome.resolved.version),
-                },
+                Ok(outcome) => {
+                    state.forget_integrity(&entry_id);
+                    SkillUpdateResult {
+                        id: entry_id,
+                        outcome: "updated".to_string(),
+                        reason: None,
+                        resolved_version: Some(outcome.resolved.version),
+                    }
+                }
                 Err(e) => SkillUpdateResult {
                     id: entry_id,
                     outcome: "error".to_string(),
@@ -409,12 +392,15 @@ pub async fn update_skills(
                     .add_from_origin(entry.origin.clone(), AddMode::Update, entry.groups.clone())
                     .await
                 {
-                    Ok(outcome) => results.push(SkillUpdateResult {
-                        id: entry.id,
-                        outcome: "updated".to_string(),
-                        reason: None,
-                        resolved_version: Some(outcome.resolved.version),
-                    }),
+                    Ok(outcome) => {
+                        state.forget_integrity(&entry.id);
+                        results.push(SkillUpdateResult {
+                            id: entry.id,
+                            outcome: "updated".to_string(),
+                            reason: None,
+                            resolved_version: Some(outcome.resolved.version),
+                        })
+                    }
                     Err(e) => results.push(SkillUpdateResult {
                         id: entry.id,
                         outcome: "error".to_string(),
@@ -1,8 +1,18 @@
 //! Status and root endpoint handlers
 
+use crate::core::audit::AuditLog;
+use crate::core::change_journal::ChangeJournal;
+use crate::core::integrity::{IntegrityError, IntegrityGuard};
+use crate::core::jobs::JobQueue;
+use crate::core::registry_store::RegistryStore;
+use crate::core::replication::ReplicationKey;
 use crate::core::service::FastSkillService;
+use crate::http::auth::ApiKeys;
+use crate::http::drain::Drain;
 use crate::http::errors::HttpResult;
-use crate::http::models::{ApiResponse, StatusResponse};
+use crate::http::models::{ApiResponse, RateLimitRejections, StatusResponse};
+use crate::http::rate_limit::RateLimiter;
+use crate::utils::html_escape;
 use axum::{extract::State, response::Html};
 use std::sync::Arc;
 use std::time::SystemTime;
@@ -17,10 +27,33 @@ pub struct AppState {
     pub skills_directory: std::path::PathBuf,
     /// When false, mutating (write) endpoints are gated and return 403.
     pub enable_write: bool,
+    /// When set, served skill content is verified against recorded hashes.
+    pub integrity: Option<Arc<IntegrityGuard>>,
+    /// When set, requests to write routes are appended to this audit log.
+    pub audit: Option<Arc<AuditLog>>,
+    /// When set, signed snapshots are served to follower nodes.
+    pub replication: Option<ReplicationKey>,
+    /// When set, `/api/v1` requests over the configured limits get 429.
+    pub rate_limiter: Option<Arc<RateLimiter>>,
+    /// Journal of skill content changes behind `/skills/changes`.
+    pub changes: Arc<ChangeJournal>,
+    /// When set, requests must carry an API key once the key file has one.
+    pub api_keys: Option<Arc<ApiKeys>>,
+    /// When set, the server hosts a registry: packages are published to and
+    /// downloaded from this store.
+    pub registry: Option<Arc<RegistryStore>>,
+    /// Set by `/admin/drain`; while draining, new requests get 503.
+    pub drain: Arc<Drain>,
+    /// Background jobs behind `?async=true` requests and `/jobs`.
+    pub jobs: Arc<JobQueue>,
 }
 
 impl AppState {
     pub fn new(service: Arc<FastSkillService>) -> Result<Self, Box<dyn std::error::Error>> {
+        let changes = ChangeJournal::new(ChangeJournal::default_path(
+            &service.config().skill_storage_path,
+        ));
+        let jobs = JobQueue::new().with_events(service.event_bus());
         Ok(Self {
             service,
             start_time: SystemTime::now(),
@@ -28,6 +61,15 @@ impl AppState {
             project_root: std::path::PathBuf::from("."),
             skills_directory: std::path::PathBuf::from(".claude/skills"),
             enable_write: false,
+            integrity: None,
+            audit: None,
+            replication: None,
+            rate_limiter: None,
+            changes: Arc::new(changes),
+            api_keys: None,
+            registry: None,
+            drain: Arc::new(Drain::new()),
+            jobs: Arc::new(jobs),
         })
     }
 
@@ -37,6 +79,69 @@ impl AppState {
         self
     }
 
+    /// Verify skill content against `guard` before serving it.
+    pub fn with_integrity_guard(mut self, guard: Arc<IntegrityGuard>) -> Self {
+        self.integrity = Some(guard);
+        self
+    }
+
+    /// Record write-route requests in `log`.
+    pub fn with_audit_log(mut self, log: Arc<AuditLog>) -> Self {
+        self.audit = Some(log);
+        self
+    }
+
+    /// Serve replication snapshots signed with `key`.
+    pub fn with_replication_key(mut self, key: ReplicationKey) -> Self {
+        self.replication = Some(key);
+        self
+    }
+
+    /// Apply `limiter` to `/api/v1` requests.
+    pub fn with_rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Authenticate `/api/v1` requests against the scoped keys in `keys`.
+    pub fn with_api_keys(mut self, keys: Arc<ApiKeys>) -> Self {
+        self.api_keys = Some(keys);
+        self
+    }
+
+    /// Host a registry: accept publishes into `store` and serve its packages.
+    pub fn with_registry_store(mut self, store: Arc<RegistryStore>) -> Self {
+        self.registry = Some(store);
+        self
+    }
+
+    /// Share `drain` with other states, so draining covers every tenant.
+    pub fn with_drain(mut self, drain: Arc<Drain>) -> Self {
+        self.drain = drain;
+        self
+    }
+
+    /// Run background jobs on `queue`, e.g. one backed by a job store.
+    pub fn with_job_queue(mut self, queue: Arc<JobQueue>) -> Self {
+        self.jobs = queue;
+        self
+    }
+
+    /// Verify content read for `skill_id`; a no-op unless integrity mode is on.
+    pub fn verify_integrity(&self, skill_id: &str, content: &[u8]) -> Result<(), IntegrityError> {
+        match &self.integrity {
+            Some(guard) => guard.verify(skill_id, content),
+            None => Ok(()),
+        }
+    }
+
+    /// Re-baseline `skill_id` after the server itself changed it.
+    pub fn forget_integrity(&self, skill_id: &str) {
+        if let Some(guard) = &self.integrity {
+            guard.forget(skill_id);
+        }
+    }
+
     pub fn with_project_file_path(mut self, path: std::path::PathBuf) -> Self {
         self.project_file_path = path;
         self
@@ -67,23 +172,6 @@ impl AppState {
     }
 }
 
-/// Minimal HTML-escape for text interpolated into the dashboard page.
-/// Escapes the five HTML-significant characters to prevent stored/reflected XSS.
-fn html_escape(input: &str) -> String {
-    let mut out = String::with_capacity(input.len());
-    for c in input.chars() {
-        match c {
-            '&' => out.push_str("&amp;"),
-            '<' => out.push_str("&lt;"),
-            '>' => out.push_str("&gt;"),
-            '"' => out.push_str("&quot;"),
-            '\'' => out.push_str("&#x27;"),
-            other => out.push(other),
-        }
-    }
-    out
-}
-
 /// GET / - Root endpoint with HTML dashboard
 pub async fn root(State(state): State<AppState>) -> Html<String> {
     let skills: Vec<_> = (state.service.skill_manager().list_skills().await).unwrap_or_default();
@@ -260,6 +348,12 @@ pub async fn root(State(state): State<AppState>) -> Html<String> {
 }
 
 /// GET /api/status - Service status endpoint
+#[utoipa::path(
+    get,
+    path = "/api/v1/status",
+    tag = "server",
+    responses((status = 200, description = "Server status", body = ApiResponse<StatusResponse>))
+)]
 pub async fn status(
     State(state): State<AppState>,
 ) -> HttpResult<axum::Json<ApiResponse<StatusResponse>>> {
@@ -277,6 +371,13 @@ pub async fn status(
         uptime_seconds: state.uptime_seconds(),
         writable: state.enable_write,
         embedding_provider: state.service.embedding_service().is_some(),
+        rate_limit_rejections: state
+            .rate_limiter
+            .as_ref()
+            .map(|limiter| RateLimitRejections {
+                per_token: limiter.metrics().rejected_per_token(),
+                per_ip: limiter.metrics().rejected_per_ip(),
+            }),
     };
 
     Ok(axum::Json(ApiResponse::success(response)))
@@ -285,7 +386,7 @@ pub async fn status(
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
-    use super::html_escape;
+    use crate::utils::html_escape;
 
     #[test]
     fn html_escape_neutralizes_script_injection() {
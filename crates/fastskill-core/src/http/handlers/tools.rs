@@ -0,0 +1,34 @@
+//! Tool discovery endpoint handler
+
+use crate::http::client_profile::resolve_client_profile;
+use crate::http::errors::HttpResult;
+use crate::http::facade;
+use crate::http::handlers::AppState;
+use crate::http::models::{ApiResponse, ListToolsQuery, ToolsListResponse};
+use axum::extract::{Query, State};
+use axum::http::HeaderMap;
+
+/// GET /api/v1/tools - Tools declared by every installed skill
+///
+/// Lets an agent framework build its tool list in one call: each tool comes
+/// with its JSON Schemas and the id and version of the skill that provides
+/// it. `?capability=` keeps only tools with that capability. A skill whose
+/// SKILL.md cannot be read or declares invalid tools is reported under
+/// `errors` instead of failing the whole listing. Skills the caller may not
+/// see are left out.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tools",
+    tag = "tools",
+    params(ListToolsQuery),
+    responses((status = 200, description = "Tools declared by installed skills", body = ApiResponse<ToolsListResponse>))
+)]
+pub async fn list_tools(
+    State(state): State<AppState>,
+    Query(query): Query<ListToolsQuery>,
+    headers: HeaderMap,
+) -> HttpResult<axum::Json<ApiResponse<ToolsListResponse>>> {
+    let profile = resolve_client_profile(state.service.config(), &headers);
+    let response = facade::list_tools(&state, profile, query.capability.as_deref()).await?;
+    Ok(axum::Json(ApiResponse::success(response)))
+}
@@ -0,0 +1,151 @@
+//! Validate endpoint handler: pre-publish checks of an uploaded skill
+
+use crate::core::install::derive_skill_id_and_version;
+use crate::core::metadata::parse_yaml_frontmatter;
+use crate::core::origin::Origin;
+use crate::core::service::ServiceError;
+use crate::core::skill_manager::SkillDefinition;
+use crate::http::errors::{HttpError, HttpResult};
+use crate::http::models::{ApiResponse, ValidateSkillRequest, ValidateSkillResponse};
+use crate::storage::zip::ZipHandler;
+use crate::validation::{SkillValidator, ValidationResult};
+use axum::body::Bytes;
+use axum::http::{header, HeaderMap};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Largest request body `POST /api/v1/validate` accepts
+pub const MAX_VALIDATE_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+/// POST /api/v1/validate - Qualitative validation of a skill that is not installed
+///
+/// The body is either a skill ZIP (`Content-Type: application/zip` or
+/// `application/octet-stream`), with SKILL.md at its root or in one top-level
+/// folder, or JSON `{"skillMd": "..."}`. The skill is unpacked into a
+/// temporary directory that is removed before responding; nothing is
+/// installed or indexed. A skill that fails validation is still a `200` with
+/// `isValid: false`; `400` means the upload itself could not be read.
+#[utoipa::path(
+    post,
+    path = "/api/v1/validate",
+    tag = "skills",
+    request_body(
+        content = ValidateSkillRequest,
+        description = "JSON with an inline SKILL.md, or a skill ZIP sent as application/zip",
+        content_type = "application/json"
+    ),
+    responses((status = 200, description = "Validation result", body = ApiResponse<ValidateSkillResponse>))
+)]
+pub async fn validate_skill(
+    headers: HeaderMap,
+    body: Bytes,
+) -> HttpResult<axum::Json<ApiResponse<ValidateSkillResponse>>> {
+    let mime = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(';').next())
+        .map(|v| v.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+
+    let temp_dir = TempDir::new().map_err(ServiceError::Io)?;
+    let skill_dir = match mime.as_str() {
+        "application/json" => {
+            let request: ValidateSkillRequest = serde_json::from_slice(&body)
+                .map_err(|e| HttpError::BadRequest(format!("Invalid JSON body: {}", e)))?;
+            write_inline_skill(&request.skill_md, temp_dir.path())?
+        }
+        "application/zip" | "application/x-zip-compressed" | "application/octet-stream" => {
+            extract_skill_zip(&body, temp_dir.path())?
+        }
+        other => {
+            return Err(HttpError::BadRequest(format!(
+                "Unsupported Content-Type '{}': upload a skill ZIP (application/zip) \
+                 or JSON with an inline skillMd",
+                other
+            )))
+        }
+    };
+
+    let response = validate_skill_dir(&skill_dir).await?;
+    Ok(axum::Json(ApiResponse::success(response)))
+}
+
+fn write_inline_skill(skill_md: &str, temp_dir: &Path) -> Result<PathBuf, ServiceError> {
+    if skill_md.trim().is_empty() {
+        return Err(ServiceError::Validation(
+            "skillMd cannot be empty".to_string(),
+        ));
+    }
+    let skill_dir = temp_dir.join("skill");
+    std::fs::create_dir_all(&skill_dir)?;
+    std::fs::write(skill_dir.join("SKILL.md"), skill_md)?;
+    Ok(skill_dir)
+}
+
+/// Unpack an uploaded ZIP and find its skill. Entries that would land
+/// outside the temporary directory are rejected by the extraction.
+fn extract_skill_zip(bytes: &[u8], temp_dir: &Path) -> Result<PathBuf, ServiceError> {
+    if bytes.is_empty() {
+        return Err(ServiceError::Validation(
+            "Request body is empty".to_string(),
+        ));
+    }
+    let extract_dir = temp_dir.join("extracted");
+    std::fs::create_dir_all(&extract_dir)?;
+    ZipHandler::new()?.extract_reader_to_dir(Cursor::new(bytes), &extract_dir)?;
+    crate::storage::git::validate_cloned_skill(&extract_dir).map_err(|_| {
+        ServiceError::Validation(
+            "ZIP does not contain a SKILL.md at its root or in a top-level folder".to_string(),
+        )
+    })
+}
+
+async fn validate_skill_dir(skill_dir: &Path) -> Result<ValidateSkillResponse, ServiceError> {
+    let skill_file = skill_dir.join("SKILL.md");
+    let content = match tokio::fs::read_to_string(&skill_file).await {
+        Ok(content) => content,
+        Err(e) => return Ok(rejected(format!("Cannot read SKILL.md: {}", e))),
+    };
+    let frontmatter = match parse_yaml_frontmatter(&content) {
+        Ok(frontmatter) => frontmatter,
+        Err(e) => return Ok(rejected(format!("Invalid SKILL.md frontmatter: {}", e))),
+    };
+    let (id, version) = match derive_skill_id_and_version(skill_dir, &frontmatter) {
+        Ok(derived) => derived,
+        Err(e) => return Ok(rejected(e.to_string())),
+    };
+
+    let mut skill = SkillDefinition::new(
+        id,
+        frontmatter.name,
+        frontmatter.description,
+        version,
+        Origin::Local {
+            path: skill_dir.to_path_buf(),
+            editable: false,
+        },
+    );
+    skill.skill_file = skill_file;
+    skill.author = frontmatter.author;
+
+    let result = SkillValidator::new()
+        .qualitatively_validate_skill(&skill)
+        .await?;
+    Ok(response(Some(&skill), result))
+}
+
+fn rejected(message: String) -> ValidateSkillResponse {
+    response(None, ValidationResult::invalid(&message))
+}
+
+fn response(skill: Option<&SkillDefinition>, result: ValidationResult) -> ValidateSkillResponse {
+    ValidateSkillResponse {
+        skill_id: skill.map(|s| s.id.to_string()),
+        name: skill.map(|s| s.name.clone()),
+        is_valid: result.is_valid,
+        score: result.score,
+        errors: result.errors,
+        warnings: result.warnings,
+    }
+}
@@ -0,0 +1,73 @@
+//! Prometheus metrics: request tracking middleware and `GET /metrics`
+
+use crate::core::metrics::{self, Gauges};
+use crate::http::handlers::AppState;
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{header, HeaderValue},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::time::Instant;
+
+/// Content type of the Prometheus text exposition format
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Count every API request and observe its latency, labelled by route template.
+pub async fn track_requests(req: Request, next: Next) -> Response {
+    let started = Instant::now();
+    let method = req.method().to_string();
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|m| m.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+
+    let response = next.run(req).await;
+    metrics::global().record_request(
+        &method,
+        &route,
+        response.status().as_u16(),
+        started.elapsed(),
+    );
+    response
+}
+
+/// GET /metrics - Service metrics in the Prometheus text format
+pub async fn get_metrics(State(state): State<AppState>) -> Response {
+    let skills_installed = state
+        .service
+        .skill_manager()
+        .list_skills()
+        .await
+        .ok()
+        .map(|skills| skills.len());
+    let vector_index_size = match state.service.vector_index_service() {
+        Some(index) => index.count_skills().await.ok(),
+        None => None,
+    };
+    let rate_limited = state
+        .rate_limiter
+        .as_ref()
+        .map(|limiter| {
+            vec![
+                ("token", limiter.metrics().rejected_per_token()),
+                ("ip", limiter.metrics().rejected_per_ip()),
+            ]
+        })
+        .unwrap_or_default();
+
+    let body = metrics::global().render(&Gauges {
+        skills_installed,
+        vector_index_size,
+        rate_limited,
+    });
+    (
+        [(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static(PROMETHEUS_CONTENT_TYPE),
+        )],
+        body,
+    )
+        .into_response()
+}
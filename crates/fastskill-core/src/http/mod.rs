@@ -3,10 +3,20 @@
 //! This module provides a REST API server using Axum with full CRUD operations
 //! for skills management.
 
+pub mod audit;
+pub mod auth;
+pub mod client_profile;
+pub mod drain;
 pub mod errors;
+pub mod facade;
 pub mod handlers;
+pub mod metrics;
 pub mod models;
+pub mod openapi;
+pub mod preflight;
+pub mod rate_limit;
 pub mod server;
+pub mod tenancy;
 
 pub use models::{ApiResponse, ErrorResponse};
 /// Re-export commonly used types
@@ -1,10 +1,11 @@
 //! Request and response models for the HTTP API
 
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
 /// Generic API response wrapper
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiResponse<T> {
     pub success: bool,
@@ -43,7 +44,7 @@ impl<T> ApiResponse<T> {
 }
 
 /// Response metadata
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ResponseMeta {
     pub total_count: Option<i64>,
@@ -53,7 +54,7 @@ pub struct ResponseMeta {
 }
 
 /// Error response
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ErrorResponse {
     pub code: String,
@@ -62,7 +63,7 @@ pub struct ErrorResponse {
 }
 
 /// Skill definition for API responses
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SkillResponse {
     pub id: String,
@@ -74,16 +75,30 @@ pub struct SkillResponse {
 }
 
 /// Skill list response
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SkillsListResponse {
     pub skills: Vec<SkillResponse>,
     pub count: usize,
     pub total: usize,
+    /// Pass as `?after=` to fetch the next page; absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Query parameters for `GET /api/v1/skills`.
+#[derive(Debug, Deserialize, Clone, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(rename_all = "camelCase")]
+pub struct ListSkillsQuery {
+    /// Maximum skills per page
+    pub limit: Option<usize>,
+    /// Return skills whose id sorts after this one (a previous `nextCursor`)
+    pub after: Option<String>,
 }
 
 /// Skill creation/update request
-#[derive(Debug, Deserialize, Validate, Clone)]
+#[derive(Debug, Deserialize, Validate, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SkillRequest {
     #[validate(length(min = 1, max = 100))]
@@ -96,7 +111,7 @@ pub struct SkillRequest {
 }
 
 /// Search request
-#[derive(Debug, Deserialize, Validate, Clone)]
+#[derive(Debug, Deserialize, Validate, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SearchRequest {
     #[validate(length(min = 1, max = 1000))]
@@ -109,7 +124,7 @@ pub struct SearchRequest {
 }
 
 /// Search response
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SearchResponse {
     pub skills: Vec<SkillMatchResponse>,
@@ -118,7 +133,7 @@ pub struct SearchResponse {
 }
 
 /// Skill match in search results
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SkillMatchResponse {
     pub skill: SkillResponse,
@@ -127,7 +142,7 @@ pub struct SkillMatchResponse {
 }
 
 /// Reindex request
-#[derive(Debug, Deserialize, Validate, Clone)]
+#[derive(Debug, Deserialize, Validate, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ReindexRequest {
     pub force: Option<bool>,
@@ -135,7 +150,7 @@ pub struct ReindexRequest {
 }
 
 /// Reindex response
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ReindexResponse {
     pub success_count: usize,
@@ -147,16 +162,127 @@ pub struct ReindexResponse {
 /// Outcome of a call into the core reindex seam (ADR-0002/0005). `reindexed:
 /// false` + a `reason` means the reindex was skipped (e.g. no embedding
 /// provider configured) — a success, not a failure.
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ReindexOutcomeResponse {
     pub reindexed: bool,
     pub count: usize,
+    pub unchanged: usize,
+    pub removed: usize,
     pub reason: Option<String>,
 }
 
+/// How `POST /admin/reindex` rebuilds the index
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AdminReindexMode {
+    /// Re-embed only skills whose content changed
+    Incremental,
+    /// Re-embed every skill
+    Full,
+    /// Re-embed every skill into a new index that replaces the current one
+    /// only when all succeed (after changing the embedding model)
+    Migrate,
+}
+
+/// Admin reindex request; `mode` defaults to `incremental`
+#[derive(Debug, Deserialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminReindexRequest {
+    pub mode: Option<AdminReindexMode>,
+}
+
+/// `?async=true` on reindex and publish endpoints: queue a background job
+/// and answer `202 Accepted` with it instead of waiting for the outcome
+#[derive(Debug, Deserialize, Clone, Copy, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AsyncQuery {
+    /// Run as a background job and poll `/api/v1/jobs/{id}` for the outcome
+    #[serde(rename = "async", default)]
+    pub run_async: bool,
+}
+
+/// A background job (`/api/v1/jobs`)
+#[derive(Debug, Serialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct JobResponse {
+    pub id: String,
+    /// `reindex` or `publish`
+    pub kind: String,
+    /// `queued`, `running`, `succeeded`, `failed` or `cancelled`
+    pub status: String,
+    /// Percent done, 0 to 100
+    pub progress: u8,
+    pub message: Option<String>,
+    /// The endpoint's response data, once the job succeeded
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<crate::core::jobs::Job> for JobResponse {
+    fn from(job: crate::core::jobs::Job) -> Self {
+        Self {
+            id: job.id,
+            kind: job.kind.to_string(),
+            status: job.status.to_string(),
+            progress: job.progress,
+            message: job.message,
+            result: job.result,
+            error: job.error,
+            created_at: job.created_at,
+            started_at: job.started_at,
+            finished_at: job.finished_at,
+        }
+    }
+}
+
+/// Jobs known to the server, newest first
+#[derive(Debug, Serialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct JobsListResponse {
+    pub jobs: Vec<JobResponse>,
+    pub count: usize,
+}
+
+/// Caches dropped by `POST /admin/cache/clear`
+#[derive(Debug, Serialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheClearResponse {
+    /// Names of the cleared caches, as labelled in `/metrics`
+    pub cleared: Vec<String>,
+    /// Query embeddings that were dropped
+    pub query_embeddings: usize,
+}
+
+/// Turn hot reload on or off
+#[derive(Debug, Deserialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HotReloadRequest {
+    pub enabled: bool,
+}
+
+/// Hot reload state. `configured` is false when the server was started with
+/// `hot_reload.enabled = false`; it cannot be turned on then.
+#[derive(Debug, Serialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HotReloadResponse {
+    pub configured: bool,
+    pub active: bool,
+}
+
+/// Drain state: whether new requests are refused, and how many are still running
+#[derive(Debug, Serialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DrainResponse {
+    pub draining: bool,
+    pub in_flight: usize,
+}
+
 /// Status response
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct StatusResponse {
     pub status: String,
@@ -170,10 +296,21 @@ pub struct StatusResponse {
     /// Whether an embedding provider is injected (reindex/semantic search
     /// available rather than skipping silently / falling back to keyword search).
     pub embedding_provider: bool,
+    /// Requests refused with 429 since startup; absent when rate limiting is off.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limit_rejections: Option<RateLimitRejections>,
+}
+
+/// Requests rejected by each rate limit
+#[derive(Debug, Serialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitRejections {
+    pub per_token: u64,
+    pub per_ip: u64,
 }
 
 /// Source response for registry
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SourceResponse {
     pub name: String,
@@ -184,7 +321,7 @@ pub struct SourceResponse {
 }
 
 /// Marketplace skill response
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct MarketplaceSkillResponse {
     pub id: String,
@@ -198,7 +335,7 @@ pub struct MarketplaceSkillResponse {
 }
 
 /// Source skills response
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SourceSkillsResponse {
     pub source_name: String,
@@ -207,7 +344,7 @@ pub struct SourceSkillsResponse {
 }
 
 /// Registry skills response (all sources)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RegistrySkillsResponse {
     pub sources: Vec<SourceSkillsResponse>,
@@ -216,7 +353,7 @@ pub struct RegistrySkillsResponse {
 }
 
 /// Manifest skill response
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ManifestSkillResponse {
     pub id: String,
@@ -227,7 +364,7 @@ pub struct ManifestSkillResponse {
 }
 
 /// Add skill to manifest request
-#[derive(Debug, Deserialize, Validate, Clone)]
+#[derive(Debug, Deserialize, Validate, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AddSkillRequest {
     #[validate(length(min = 1))]
@@ -241,7 +378,7 @@ pub struct AddSkillRequest {
 }
 
 /// Update skill in manifest request
-#[derive(Debug, Deserialize, Validate, Clone)]
+#[derive(Debug, Deserialize, Validate, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateSkillRequest {
     pub groups: Option<Vec<String>>,
@@ -254,7 +391,7 @@ pub struct UpdateSkillRequest {
 /// `scope/skill[@version]` id) that the server classifies via the core
 /// `infer_origin` seam (ADR-0005 / spec 003 Phase 3) — the UI performs no
 /// detection of its own; it just sends what the user typed.
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct InstallSkillRequest {
     pub origin: String,
@@ -263,12 +400,16 @@ pub struct InstallSkillRequest {
 }
 
 /// POST /api/v1/skills/install response (201) / success shape.
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct InstallSkillResponse {
     pub id: String,
     pub resolved_version: String,
     pub reindexed: bool,
+    /// The id the skill would have had, when it was taken and `id` carries a
+    /// de-collision suffix (`[tool.fastskill.naming] on_collision = "suffix"`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub renamed_from: Option<String>,
 }
 
 /// POST /api/v1/skills/update request body. `skill_id` omitted (or `"all"`)
@@ -277,7 +418,7 @@ pub struct InstallSkillResponse {
 /// pins a single `repository`-origin skill to an exact version: only valid
 /// together with `skill_id` and only when that skill's recorded `Origin` is
 /// `Repository` (see `handlers::skills::update_skills`).
-#[derive(Debug, Deserialize, Clone, Default)]
+#[derive(Debug, Deserialize, Clone, Default, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateSkillsRequest {
     pub skill_id: Option<String>,
@@ -287,7 +428,8 @@ pub struct UpdateSkillsRequest {
 }
 
 /// Query parameters for `GET /api/v1/skills/{id}/content`.
-#[derive(Debug, Deserialize, Clone, Default)]
+#[derive(Debug, Deserialize, Clone, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
 #[serde(rename_all = "camelCase")]
 pub struct ContentQuery {
     pub format: Option<ContentFormat>,
@@ -298,7 +440,7 @@ pub struct ContentQuery {
 /// server-side (`comrak`) and allowlist-sanitizes the result (`ammonia`) before
 /// returning it — safe to assign to `innerHTML` (no `<script>`, no `on*`
 /// handlers, no `javascript:`/`data:` URLs).
-#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ContentFormat {
     #[default]
@@ -321,19 +463,21 @@ impl ContentFormat {
 /// `?format=` value (`"raw"` or `"html"`); `content` is the raw file text for
 /// `raw`, or sanitized HTML for `html` (spec 003 §5 / SEC-7 — the UI still
 /// HTML-escapes/renders `raw` content itself; `html` content is already safe to
-/// insert directly).
-#[derive(Debug, Serialize, Clone)]
+/// insert directly). Clients that send `Accept: text/markdown` for `raw` get
+/// the file itself instead of this envelope.
+#[derive(Debug, Serialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SkillContentResponse {
     pub path: String,
     pub format: String,
-    pub content: String,
+    #[schema(value_type = String)]
+    pub content: crate::core::content::SkillContent,
 }
 
 /// A single version available for a skill in the registry (spec 003 v2 /
 /// Phase 4 version picker). `repo` is the concrete Repository/source name that
 /// offers this version, when known.
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct VersionInfo {
     pub version: String,
@@ -345,15 +489,43 @@ pub struct VersionInfo {
 /// descending (newest first); empty (not 404) when the registry has no
 /// candidates for `id` (no registry configured, or the id is unknown there) —
 /// see `handlers::registry::list_skill_versions`.
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SkillVersionsResponse {
     pub id: String,
     pub versions: Vec<VersionInfo>,
 }
 
+/// Body of `POST /api/v1/registry/index/yank` and `POST /api/v1/registry/index/unyank`.
+#[derive(Debug, Deserialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct YankRequest {
+    /// Skill id in the registry index, `org/package` (`@org/package` also accepted)
+    pub skill: String,
+    pub version: String,
+}
+
+/// A registry index version after a yank or unyank
+#[derive(Debug, Serialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct YankResponse {
+    pub skill: String,
+    pub version: String,
+    pub yanked: bool,
+}
+
+/// A version published with `POST /api/v1/registry/publish`
+#[derive(Debug, Serialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishResponse {
+    pub skill: String,
+    pub version: String,
+    pub cksum: String,
+    pub download_url: String,
+}
+
 /// Per-skill outcome of a `POST /api/v1/skills/update` call.
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SkillUpdateResult {
     pub id: String,
@@ -362,3 +534,160 @@ pub struct SkillUpdateResult {
     pub reason: Option<String>,
     pub resolved_version: Option<String>,
 }
+
+/// A skill in the `GET /api/v1/graph` response: every `skills.lock` entry and
+/// every installed skill, plus dependencies that are neither.
+#[derive(Debug, Serialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphNode {
+    pub id: String,
+    pub name: Option<String>,
+    /// Locked version when the skill is in `skills.lock`, else the installed one.
+    pub version: Option<String>,
+    /// Depth in the resolved tree (0 = direct dependency), when locked.
+    pub depth: Option<u32>,
+    pub locked: bool,
+    pub installed: bool,
+    /// Empty when the node is consistent.
+    pub conflicts: Vec<GraphConflict>,
+}
+
+/// A dependency edge (`from` depends on `to`) in the `GET /api/v1/graph` response.
+#[derive(Debug, Serialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    /// The declared version constraint, when there is one.
+    pub constraint: Option<String>,
+    /// Empty when the edge is satisfied.
+    pub conflicts: Vec<GraphConflict>,
+}
+
+/// Why a node or edge is inconsistent.
+#[derive(Debug, Serialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphConflict {
+    /// Nodes: `"not_installed"`, `"version_drift"`. Edges: `"missing"`,
+    /// `"version_mismatch"`, `"invalid_constraint"`, `"cycle"`.
+    pub kind: String,
+    pub message: String,
+}
+
+/// GET /api/v1/graph response
+#[derive(Debug, Serialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyGraphResponse {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+    /// Number of nodes and edges with at least one conflict.
+    pub conflict_count: usize,
+}
+
+/// Query parameters for `GET /api/v1/tools`.
+#[derive(Debug, Deserialize, Clone, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(rename_all = "camelCase")]
+pub struct ListToolsQuery {
+    /// Keep only tools with this capability (case-insensitive)
+    pub capability: Option<String>,
+}
+
+/// A tool in the `GET /api/v1/tools` response, with the skill that provides it.
+#[derive(Debug, Serialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolResponse {
+    pub name: String,
+    pub description: String,
+    pub capabilities: Vec<String>,
+    /// JSON Schema of the tool's arguments
+    pub input_schema: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_schema: Option<serde_json::Value>,
+    pub skill_id: String,
+    pub skill_version: String,
+}
+
+/// A skill whose tool declarations could not be read.
+#[derive(Debug, Serialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolErrorResponse {
+    pub skill_id: String,
+    pub message: String,
+}
+
+/// GET /api/v1/tools response
+#[derive(Debug, Serialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolsListResponse {
+    pub tools: Vec<ToolResponse>,
+    pub count: usize,
+    /// Skills left out because their SKILL.md or tool declarations are invalid.
+    pub errors: Vec<ToolErrorResponse>,
+}
+
+/// Query parameters for `GET /api/v1/audit`.
+#[derive(Debug, Deserialize, Clone, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuditQuery {
+    /// Only entries at or after this time (RFC 3339)
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    /// Exact action, or a prefix ending in `.` (e.g. `skill.`)
+    pub action: Option<String>,
+    pub actor: Option<String>,
+    /// Only entries targeting this skill id
+    pub skill: Option<String>,
+    /// At most the N most recent matching entries
+    pub limit: Option<usize>,
+}
+
+/// Query parameters for `GET /api/v1/skills/changes`.
+#[derive(Debug, Deserialize, Clone, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ChangesQuery {
+    /// Cursor from a previous response; omit for a full listing
+    pub since: Option<u64>,
+}
+
+/// A skill added or updated since the cursor.
+#[derive(Debug, Serialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillChangeResponse {
+    pub id: String,
+    /// Content hash of the skill directory
+    pub hash: String,
+}
+
+/// Response for `GET /api/v1/skills/changes`.
+#[derive(Debug, Serialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillChangesResponse {
+    pub added: Vec<SkillChangeResponse>,
+    pub updated: Vec<SkillChangeResponse>,
+    pub removed: Vec<String>,
+    /// Pass back as `since` on the next call
+    pub cursor: u64,
+}
+
+/// POST /api/v1/validate JSON body: a SKILL.md sent inline. Skills with
+/// more than a SKILL.md are uploaded as a ZIP instead.
+#[derive(Debug, Deserialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidateSkillRequest {
+    pub skill_md: String,
+}
+
+/// POST /api/v1/validate response: the qualitative validation of the skill.
+/// `skillId`/`name` are absent when the frontmatter could not be parsed.
+#[derive(Debug, Serialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidateSkillResponse {
+    /// Id the skill would be installed under
+    pub skill_id: Option<String>,
+    pub name: Option<String>,
+    pub is_valid: bool,
+    /// Quality score from 0.0 to 1.0
+    pub score: f64,
+    pub errors: Vec<crate::validation::ValidationError>,
+    pub warnings: Vec<crate::validation::ValidationWarning>,
+}
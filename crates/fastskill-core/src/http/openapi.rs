@@ -0,0 +1,185 @@
+//! OpenAPI description of the `/api/v1` routes
+//!
+//! The document is derived from the `#[utoipa::path]` annotations on the
+//! handlers and the `ToSchema` derives on the models, and is served at
+//! `GET /api/v1/openapi.json` like any other read route. With the `swagger`
+//! feature the server also mounts Swagger UI over it at `/swagger-ui`.
+
+use crate::http::handlers::{
+    admin, audit, changes, graph, jobs, manifest, registry, reindex, replication, resolve, route,
+    search, skills, status, tools, validate,
+};
+use crate::http::models::{ErrorResponse, ResponseMeta};
+use axum::Json;
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi, ToSchema};
+
+/// Path Swagger UI is served under when the `swagger` feature is on
+pub const SWAGGER_UI_PATH: &str = "/swagger-ui";
+
+/// Path of the document, as Swagger UI fetches it
+pub const OPENAPI_JSON_PATH: &str = "/api/v1/openapi.json";
+
+/// The envelope of responses whose `data` has no fixed shape (the project
+/// view, plain messages) and of every error response.
+#[derive(ToSchema)]
+#[allow(dead_code)]
+pub struct UntypedApiResponse {
+    success: bool,
+    data: Option<serde_json::Value>,
+    error: Option<ErrorResponse>,
+    meta: Option<ResponseMeta>,
+}
+
+/// Declares the two ways of sending an API key
+struct ApiKeySecurity;
+
+impl Modify for ApiKeySecurity {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-API-Key"))),
+        );
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "FastSkill API",
+        description = "Skill management over HTTP. Every response is an envelope with \
+            `success`, `data`, `error` and `meta`; failed requests set `error`. API keys, \
+            when configured, go in `X-API-Key` or `Authorization: Bearer`. Write routes \
+            answer 403 unless the server runs with `--enable-write`."
+    ),
+    paths(
+        skills::list_skills,
+        skills::get_skill,
+        skills::get_skill_content,
+        skills::delete_skill,
+        skills::install_skill,
+        skills::update_skills,
+        changes::list_changes,
+        validate::validate_skill,
+        audit::export_audit,
+        audit::verify_audit,
+        manifest::get_project,
+        manifest::list_manifest_skills,
+        manifest::add_skill_to_manifest,
+        manifest::update_skill_in_manifest,
+        manifest::remove_skill_from_manifest,
+        graph::get_graph,
+        search::search_skills,
+        resolve::resolve_context,
+        route::route_skills,
+        status::status,
+        tools::list_tools,
+        reindex::reindex_all,
+        reindex::reindex_skill,
+        jobs::list_jobs,
+        jobs::get_job,
+        jobs::cancel_job,
+        registry::list_sources,
+        registry::list_all_skills,
+        registry::list_source_skills,
+        registry::get_marketplace,
+        registry::refresh_sources,
+        registry::list_skill_versions,
+        registry::list_index_skills,
+        registry::yank_version,
+        registry::unyank_version,
+        registry::publish_package,
+        registry::download_package,
+        replication::get_snapshot,
+        replication::get_skill_archive,
+        admin::reindex,
+        admin::clear_cache,
+        admin::get_hot_reload,
+        admin::set_hot_reload,
+        admin::get_drain,
+        admin::start_drain,
+        admin::stop_drain,
+        openapi_json,
+    ),
+    components(schemas(UntypedApiResponse)),
+    modifiers(&ApiKeySecurity),
+    security(("api_key" = []), ("bearer" = [])),
+    tags(
+        (name = "skills", description = "Installed skills"),
+        (name = "search", description = "Finding the skills for a query or prompt"),
+        (name = "project", description = "skill-project.toml and its dependency graph"),
+        (name = "registry", description = "Sources, marketplaces and the registry index"),
+        (name = "tools", description = "Tools declared by installed skills"),
+        (name = "audit", description = "The audit log (admin scope)"),
+        (name = "replication", description = "Snapshots for replicas (admin scope)"),
+        (name = "admin", description = "Reindexing, caches, hot reload and draining (admin scope)"),
+        (name = "server", description = "Status, reindexing, background jobs and this document")
+    )
+)]
+pub struct ApiDoc;
+
+/// GET /api/v1/openapi.json - OpenAPI 3.1 description of this API
+#[utoipa::path(
+    get,
+    path = "/api/v1/openapi.json",
+    tag = "server",
+    responses((status = 200, description = "The OpenAPI document"))
+)]
+pub async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+/// Swagger UI over [`OPENAPI_JSON_PATH`], mounted next to the console UI
+#[cfg(feature = "swagger")]
+pub fn swagger_ui() -> utoipa_swagger_ui::SwaggerUi {
+    utoipa_swagger_ui::SwaggerUi::new(SWAGGER_UI_PATH)
+        .config(utoipa_swagger_ui::Config::from(OPENAPI_JSON_PATH))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_openapi_document_covers_routes() {
+        let doc = ApiDoc::openapi();
+        for path in [
+            "/api/v1/skills",
+            "/api/v1/skills/{id}",
+            "/api/v1/skills/{id}/content",
+            "/api/v1/skills/install",
+            "/api/v1/search",
+            "/api/v1/resolve",
+            "/api/v1/route",
+            "/api/v1/registry/index/yank",
+            "/api/v1/registry/publish",
+            "/api/v1/registry/download/{scope}/{name}/{version}",
+            "/api/v1/manifest/skills/{id}",
+            "/api/v1/admin/hot-reload",
+            "/api/v1/admin/drain",
+            "/api/v1/jobs/{id}",
+            "/api/v1/jobs/{id}/cancel",
+            "/api/v1/openapi.json",
+        ] {
+            assert!(doc.paths.paths.contains_key(path), "missing {}", path);
+        }
+
+        let json = serde_json::to_value(&doc).unwrap();
+        let schemas = &json["components"]["schemas"];
+        assert!(schemas.get("SkillResponse").is_some());
+        assert!(schemas.get("RouteDecision").is_some());
+        // Field names follow the serde renames
+        assert!(schemas["SkillsListResponse"]["properties"]
+            .get("nextCursor")
+            .is_some());
+        assert!(json["components"]["securitySchemes"]
+            .get("api_key")
+            .is_some());
+    }
+}
@@ -0,0 +1,526 @@
+//! Startup preflight checks for the HTTP server
+//!
+//! `fastskill serve` runs these before binding so a misconfigured deployment
+//! fails at startup with one report listing every problem, instead of
+//! failing lazily on the first request that touches the broken piece. Each
+//! check is independent; a failing check never stops the others from running.
+
+use crate::core::credentials::resolve_secret;
+use crate::core::manifest::{HttpServerConfigToml, SkillProjectToml};
+use crate::core::repository::{RepositoryAuth, RepositoryConfig, RepositoryDefinition};
+use crate::core::service::FastSkillService;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::time::Duration;
+
+/// Default timeout for each repository reachability probe.
+pub const DEFAULT_REPOSITORY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Outcome of a single preflight check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PreflightStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// One preflight check with an optional hint telling the operator how to fix it.
+#[derive(Debug, Clone, Serialize)]
+pub struct PreflightCheck {
+    pub name: &'static str,
+    pub status: PreflightStatus,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+impl PreflightCheck {
+    fn pass(name: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: PreflightStatus::Pass,
+            message: message.into(),
+            hint: None,
+        }
+    }
+
+    fn warn(name: &'static str, message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: PreflightStatus::Warn,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn fail(name: &'static str, message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: PreflightStatus::Fail,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+/// What the server is about to do, so checks can judge what matters.
+#[derive(Debug, Clone)]
+pub struct PreflightOptions {
+    /// Address the server will bind
+    pub addr: SocketAddr,
+    /// Write endpoints enabled (`--enable-write`); storage must then be writable
+    pub enable_write: bool,
+    /// Probe configured repositories over the network (off by default)
+    pub check_repositories: bool,
+    /// Timeout for each repository probe
+    pub repository_timeout: Duration,
+}
+
+impl PreflightOptions {
+    pub fn new(addr: SocketAddr) -> Self {
+        Self {
+            addr,
+            enable_write: false,
+            check_repositories: false,
+            repository_timeout: DEFAULT_REPOSITORY_TIMEOUT,
+        }
+    }
+}
+
+/// All preflight results, in the order the checks ran.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PreflightReport {
+    pub checks: Vec<PreflightCheck>,
+}
+
+impl PreflightReport {
+    pub fn has_failures(&self) -> bool {
+        self.checks
+            .iter()
+            .any(|c| c.status == PreflightStatus::Fail)
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &PreflightCheck> {
+        self.checks
+            .iter()
+            .filter(|c| c.status == PreflightStatus::Fail)
+    }
+
+    /// Human-readable report, one line per check plus an indented hint.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for check in &self.checks {
+            let tag = match check.status {
+                PreflightStatus::Pass => "[PASS]",
+                PreflightStatus::Warn => "[WARN]",
+                PreflightStatus::Fail => "[FAIL]",
+            };
+            out.push_str(&format!("{} {}: {}\n", tag, check.name, check.message));
+            if check.status != PreflightStatus::Pass {
+                if let Some(hint) = &check.hint {
+                    out.push_str(&format!("       -> {}\n", hint));
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Run every preflight check against the service that is about to be served.
+pub async fn run_preflight(
+    service: &FastSkillService,
+    options: &PreflightOptions,
+) -> PreflightReport {
+    let mut checks = vec![
+        check_storage(&service.config().skill_storage_path, options.enable_write),
+        check_index(service).await,
+        check_port(options.addr),
+    ];
+
+    let server_config = std::env::current_dir()
+        .ok()
+        .and_then(|dir| crate::core::load_project_config(&dir).ok())
+        .and_then(|cfg| SkillProjectToml::load_from_file(&cfg.project_file_path).ok())
+        .and_then(|project| project.tool)
+        .and_then(|tool| tool.fastskill)
+        .and_then(|fastskill| fastskill.server);
+    checks.extend(check_server_auth(server_config.as_ref(), resolve_secret));
+
+    if options.check_repositories {
+        if let Some(manager) = service.repository_manager() {
+            for repo in manager.list_repositories() {
+                checks.push(check_repository(repo, options.repository_timeout).await);
+            }
+        }
+    }
+
+    PreflightReport { checks }
+}
+
+/// The storage directory must exist, and be writable when writes are enabled.
+fn check_storage(path: &Path, enable_write: bool) -> PreflightCheck {
+    const NAME: &str = "storage";
+    if !path.is_dir() {
+        return PreflightCheck::fail(
+            NAME,
+            format!("skills directory {} does not exist", path.display()),
+            "create it, run `fastskill init`, or pass --skills-dir",
+        );
+    }
+
+    let probe = path.join(format!(".fastskill-preflight-{}", std::process::id()));
+    let writable = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+        .map(|_| {
+            let _ = std::fs::remove_file(&probe);
+        });
+    match writable {
+        Ok(()) => PreflightCheck::pass(NAME, format!("{} is writable", path.display())),
+        Err(e) if enable_write => PreflightCheck::fail(
+            NAME,
+            format!("{} is not writable: {}", path.display(), e),
+            "fix the directory permissions or drop --enable-write",
+        ),
+        Err(e) => PreflightCheck::warn(
+            NAME,
+            format!("{} is not writable: {}", path.display(), e),
+            "read-only serving works, but reindexing and write endpoints will fail",
+        ),
+    }
+}
+
+/// Embedding width of the models we know, so a stale index is caught before a
+/// search compares vectors of different lengths.
+fn known_dimensions(model: &str) -> Option<usize> {
+    match model {
+        "text-embedding-3-small" | "text-embedding-ada-002" => Some(1536),
+        "text-embedding-3-large" => Some(3072),
+        _ => None,
+    }
+}
+
+/// The vector index must open and hold embeddings the configured model produces.
+async fn check_index(service: &FastSkillService) -> PreflightCheck {
+    const NAME: &str = "index";
+    let Some(embedding) = service.config().embedding.as_ref() else {
+        return PreflightCheck::pass(NAME, "no embedding config; semantic search disabled");
+    };
+    let Some(index) = service.vector_index_service() else {
+        return PreflightCheck::pass(NAME, "no vector index configured");
+    };
+
+    let sample = match index.get_skills_page(None, 1).await {
+        Ok(page) => page,
+        Err(e) => {
+            return PreflightCheck::fail(
+                NAME,
+                format!("vector index cannot be read: {}", e),
+                "run `fastskill reindex --force` to rebuild it",
+            )
+        }
+    };
+    let Some(first) = sample.first() else {
+        return PreflightCheck::warn(
+            NAME,
+            "vector index is empty",
+            "run `fastskill reindex` so semantic search returns results",
+        );
+    };
+
+    let width = first.embedding.len();
+    match known_dimensions(&embedding.embedding_model) {
+        Some(expected) if expected != width => PreflightCheck::fail(
+            NAME,
+            format!(
+                "index holds {}-dimension embeddings but {} produces {}",
+                width, embedding.embedding_model, expected
+            ),
+            "the embedding model changed; run `fastskill reindex --force`",
+        ),
+        Some(_) => {
+            PreflightCheck::pass(NAME, format!("index matches {}", embedding.embedding_model))
+        }
+        None => PreflightCheck::pass(
+            NAME,
+            format!(
+                "index holds {}-dimension embeddings (model {} not checked)",
+                width, embedding.embedding_model
+            ),
+        ),
+    }
+}
+
+/// The address must be free to bind. The probe listener is dropped at once,
+/// so another process can still race us to it — this only catches the common
+/// case of a second server on the same port.
+fn check_port(addr: SocketAddr) -> PreflightCheck {
+    const NAME: &str = "port";
+    match std::net::TcpListener::bind(addr) {
+        Ok(_) => PreflightCheck::pass(NAME, format!("{} is available", addr)),
+        Err(e) if e.kind() == std::io::ErrorKind::AddrInUse => PreflightCheck::fail(
+            NAME,
+            format!("{} is already in use", addr),
+            "stop the other process or pass a different --port",
+        ),
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => PreflightCheck::fail(
+            NAME,
+            format!("not allowed to bind {}: {}", addr, e),
+            "ports below 1024 need elevated privileges; pick a higher --port",
+        ),
+        Err(e) => PreflightCheck::fail(
+            NAME,
+            format!("cannot bind {}: {}", addr, e),
+            "check --host names a local interface",
+        ),
+    }
+}
+
+/// `[tool.fastskill.server]` must be usable as written. At runtime invalid
+/// origins and client profiles with an unset key are skipped with a log line;
+/// here they fail, because a skipped keyed profile silently denies that client.
+fn check_server_auth(
+    server: Option<&HttpServerConfigToml>,
+    resolve: impl Fn(&str) -> Option<String>,
+) -> Vec<PreflightCheck> {
+    const NAME: &str = "auth";
+    let Some(server) = server else {
+        return vec![PreflightCheck::pass(
+            NAME,
+            "no [tool.fastskill.server] section; CORS denies all origins",
+        )];
+    };
+
+    let mut checks = Vec::new();
+    for origin in &server.allowed_origins {
+        let valid = origin
+            .strip_prefix("http://")
+            .or_else(|| origin.strip_prefix("https://"))
+            .is_some_and(|rest| !rest.is_empty());
+        if !valid {
+            checks.push(PreflightCheck::fail(
+                NAME,
+                format!(
+                    "allowed_origins entry '{}' is not an http(s) origin",
+                    origin
+                ),
+                "use the form https://host[:port]",
+            ));
+        }
+    }
+
+    let mut names = HashSet::new();
+    for client in &server.clients {
+        if !names.insert(client.name.as_str()) {
+            checks.push(PreflightCheck::fail(
+                NAME,
+                format!("client profile '{}' is defined more than once", client.name),
+                "give each [[tool.fastskill.server.clients]] entry a unique name",
+            ));
+        }
+        if let Some(var) = &client.api_key_env {
+            if resolve(var).is_none_or(|key| key.is_empty()) {
+                checks.push(PreflightCheck::fail(
+                    NAME,
+                    format!(
+                        "client profile '{}' reads its API key from {}, which is not set",
+                        client.name, var
+                    ),
+                    format!(
+                        "export {} or store it with `fastskill credentials set`",
+                        var
+                    ),
+                ));
+            }
+        }
+        if let Some(relevance) = client.dynamic_min_relevance {
+            if !(0.0..=1.0).contains(&relevance) {
+                checks.push(PreflightCheck::fail(
+                    NAME,
+                    format!(
+                        "client profile '{}' has dynamic_min_relevance {} outside 0.0-1.0",
+                        client.name, relevance
+                    ),
+                    "set dynamic_min_relevance between 0.0 and 1.0",
+                ));
+            }
+        }
+    }
+
+    if checks.is_empty() {
+        checks.push(PreflightCheck::pass(
+            NAME,
+            format!(
+                "{} origin(s) and {} client profile(s) configured",
+                server.allowed_origins.len(),
+                server.clients.len()
+            ),
+        ));
+    }
+    checks
+}
+
+/// Probe one repository. Unreachable repositories only warn: the server can
+/// still serve installed skills, only installs from that repository break.
+async fn check_repository(repo: &RepositoryDefinition, timeout: Duration) -> PreflightCheck {
+    const NAME: &str = "repository";
+    if let Some(var) = auth_secret_var(repo.auth.as_ref()) {
+        if resolve_secret(var).is_none() {
+            return PreflightCheck::warn(
+                NAME,
+                format!("{}: credential {} is not set", repo.name, var),
+                format!(
+                    "export {} or store it with `fastskill credentials set`",
+                    var
+                ),
+            );
+        }
+    }
+
+    let url = match &repo.config {
+        RepositoryConfig::Local { path } => {
+            return if path.is_dir() {
+                PreflightCheck::pass(NAME, format!("{}: {} exists", repo.name, path.display()))
+            } else {
+                PreflightCheck::warn(
+                    NAME,
+                    format!("{}: {} does not exist", repo.name, path.display()),
+                    "fix the path with `fastskill repos` or remove the repository",
+                )
+            };
+        }
+        RepositoryConfig::GitMarketplace { url, .. } => url,
+        RepositoryConfig::HttpRegistry { index_url } => index_url,
+        RepositoryConfig::ZipUrl { base_url } => base_url,
+    };
+    if !(url.starts_with("http://") || url.starts_with("https://")) {
+        return PreflightCheck::pass(NAME, format!("{}: {} not probed", repo.name, url));
+    }
+
+    let client = match reqwest::Client::builder().timeout(timeout).build() {
+        Ok(client) => client,
+        Err(e) => {
+            return PreflightCheck::warn(
+                NAME,
+                format!("{}: cannot build HTTP client: {}", repo.name, e),
+                "check TLS and proxy settings",
+            )
+        }
+    };
+    match client.head(url.as_str()).send().await {
+        Ok(response) if !response.status().is_server_error() => {
+            PreflightCheck::pass(NAME, format!("{}: {} reachable", repo.name, url))
+        }
+        Ok(response) => PreflightCheck::warn(
+            NAME,
+            format!("{}: {} returned {}", repo.name, url, response.status()),
+            "the repository server is failing; installs from it will fail",
+        ),
+        Err(e) => PreflightCheck::warn(
+            NAME,
+            format!("{}: {} unreachable: {}", repo.name, url, e),
+            "check network access or the repository URL",
+        ),
+    }
+}
+
+/// Environment variable (or credential-store key) a repository's auth reads.
+fn auth_secret_var(auth: Option<&RepositoryAuth>) -> Option<&str> {
+    match auth? {
+        RepositoryAuth::Pat { env_var } | RepositoryAuth::ApiKey { env_var } => Some(env_var),
+        RepositoryAuth::Basic { password_env, .. } => Some(password_env),
+        RepositoryAuth::SshKey { .. } | RepositoryAuth::Ssh { .. } => None,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::manifest::ClientProfileToml;
+    use tempfile::TempDir;
+
+    fn client(name: &str, api_key_env: Option<&str>) -> ClientProfileToml {
+        ClientProfileToml {
+            name: name.to_string(),
+            api_key_env: api_key_env.map(str::to_string),
+            dynamic_min_relevance: None,
+            max_dynamic_skills: None,
+            allowed_scopes: Vec::new(),
+            teams: Vec::new(),
+            role: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_storage_missing_dir_fails() {
+        let tmp = TempDir::new().unwrap();
+        let check = check_storage(&tmp.path().join("missing"), false);
+        assert_eq!(check.status, PreflightStatus::Fail);
+        assert!(check.hint.is_some());
+    }
+
+    #[test]
+    fn test_storage_writable_dir_passes_and_leaves_no_probe() {
+        let tmp = TempDir::new().unwrap();
+        let check = check_storage(tmp.path(), true);
+        assert_eq!(check.status, PreflightStatus::Pass);
+        assert_eq!(std::fs::read_dir(tmp.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_port_in_use_fails() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let check = check_port(listener.local_addr().unwrap());
+        assert_eq!(check.status, PreflightStatus::Fail);
+        assert!(check.message.contains("already in use"));
+    }
+
+    #[test]
+    fn test_server_auth_reports_every_problem() {
+        let server = HttpServerConfigToml {
+            allowed_origins: vec!["https://app.example.com".into(), "example.com".into()],
+            allowed_headers: Vec::new(),
+            clients: vec![
+                client("ci", Some("CI_KEY")),
+                client("ci", None),
+                client("bot", Some("BOT_KEY")),
+            ],
+            storage: None,
+            hot_reload: None,
+            replication: None,
+            rate_limit: Default::default(),
+            tenancy: None,
+            injection: Default::default(),
+            jobs: Default::default(),
+        };
+        let resolve = |var: &str| (var == "CI_KEY").then(|| "secret".to_string());
+
+        let checks = check_server_auth(Some(&server), resolve);
+        let messages: Vec<&str> = checks.iter().map(|c| c.message.as_str()).collect();
+        assert_eq!(checks.len(), 3, "{:?}", messages);
+        assert!(checks.iter().all(|c| c.status == PreflightStatus::Fail));
+        assert!(messages[0].contains("example.com"));
+        assert!(messages[1].contains("more than once"));
+        assert!(messages[2].contains("BOT_KEY"));
+    }
+
+    #[test]
+    fn test_report_renders_hints_only_for_problems() {
+        let report = PreflightReport {
+            checks: vec![
+                PreflightCheck::pass("storage", "ok"),
+                PreflightCheck::fail("port", "busy", "pick another port"),
+            ],
+        };
+        assert!(report.has_failures());
+        assert_eq!(
+            report.render(),
+            "[PASS] storage: ok\n[FAIL] port: busy\n       -> pick another port\n"
+        );
+    }
+}
@@ -0,0 +1,380 @@
+//! Per-token and per-IP rate limiting for `/api/v1` routes
+//!
+//! Each limit is a token bucket keyed on the caller's API key or IP address.
+//! A request over either limit gets `429 Too Many Requests` with a
+//! `Retry-After` header, and the rejection is counted for `/status`.
+
+use crate::core::service::{RateLimit, RateLimitConfig};
+use crate::http::client_profile::request_api_key;
+use crate::http::handlers::AppState;
+use crate::http::models::{ApiResponse, ErrorResponse};
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Most buckets tracked per limit. Once reached, refilled buckets are
+/// dropped, then the least recently used ones, so clients rotating keys or
+/// addresses cannot grow the table without bound.
+const MAX_TRACKED_KEYS: usize = 10_000;
+/// Buckets evicted at once when the table is full of recently used ones, so
+/// the next inserts do not each pay for a full scan.
+const EVICT_BATCH: usize = MAX_TRACKED_KEYS / 10;
+
+/// Which limit rejected a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitKind {
+    PerToken,
+    PerIp,
+}
+
+/// Counters of requests rejected by each limit
+#[derive(Debug, Default)]
+pub struct RateLimitMetrics {
+    rejected_per_token: AtomicU64,
+    rejected_per_ip: AtomicU64,
+}
+
+impl RateLimitMetrics {
+    pub fn rejected_per_token(&self) -> u64 {
+        self.rejected_per_token.load(Ordering::Relaxed)
+    }
+
+    pub fn rejected_per_ip(&self) -> u64 {
+        self.rejected_per_ip.load(Ordering::Relaxed)
+    }
+
+    fn record(&self, kind: RateLimitKind) {
+        let counter = match kind {
+            RateLimitKind::PerToken => &self.rejected_per_token,
+            RateLimitKind::PerIp => &self.rejected_per_ip,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// A request refused by the rate limiter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimited {
+    pub kind: RateLimitKind,
+    /// Whole seconds until the caller's next request would be accepted
+    pub retry_after_secs: u64,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+#[derive(Debug)]
+struct Buckets {
+    capacity: f64,
+    /// Tokens added per second
+    refill_rate: f64,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl Buckets {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            capacity: f64::from(limit.burst_size().max(1)),
+            refill_rate: f64::from(limit.requests_per_minute.max(1)) / 60.0,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Take one token from `key`'s bucket, or return how long until one is available.
+    fn take(&self, key: &str, now: Instant) -> Result<(), Duration> {
+        let mut buckets = match self.buckets.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if buckets.len() >= MAX_TRACKED_KEYS && !buckets.contains_key(key) {
+            buckets.retain(|_, bucket| self.refill(bucket, now) < self.capacity);
+            if buckets.len() >= MAX_TRACKED_KEYS {
+                let mut updated: Vec<Instant> = buckets.values().map(|b| b.updated).collect();
+                let evict = buckets.len() - MAX_TRACKED_KEYS + EVICT_BATCH;
+                let (_, cutoff, _) = updated.select_nth_unstable(evict - 1);
+                let cutoff = *cutoff;
+                buckets.retain(|_, bucket| bucket.updated > cutoff);
+            }
+        }
+
+        let bucket = buckets.entry(key.to_string()).or_insert(Bucket {
+            tokens: self.capacity,
+            updated: now,
+        });
+        bucket.tokens = self.refill(bucket, now);
+        bucket.updated = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / self.refill_rate,
+            ))
+        }
+    }
+
+    fn refill(&self, bucket: &Bucket, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        (bucket.tokens + elapsed * self.refill_rate).min(self.capacity)
+    }
+}
+
+/// Token-bucket limits shared by every request to the API
+#[derive(Debug)]
+pub struct RateLimiter {
+    per_token: Option<Buckets>,
+    per_ip: Option<Buckets>,
+    metrics: RateLimitMetrics,
+}
+
+impl RateLimiter {
+    pub fn new(config: &RateLimitConfig) -> Self {
+        Self {
+            per_token: config.per_token.map(Buckets::new),
+            per_ip: config.per_ip.map(Buckets::new),
+            metrics: RateLimitMetrics::default(),
+        }
+    }
+
+    pub fn metrics(&self) -> &RateLimitMetrics {
+        &self.metrics
+    }
+
+    /// Whether a per-IP limit is configured, which needs the peer address
+    pub fn limits_per_ip(&self) -> bool {
+        self.per_ip.is_some()
+    }
+
+    /// Admit or refuse a request carrying `token` from `ip`. Either may be
+    /// absent, in which case that limit does not apply.
+    pub fn check(&self, token: Option<&str>, ip: Option<IpAddr>) -> Result<(), RateLimited> {
+        self.check_at(token, ip, Instant::now())
+    }
+
+    fn check_at(
+        &self,
+        token: Option<&str>,
+        ip: Option<IpAddr>,
+        now: Instant,
+    ) -> Result<(), RateLimited> {
+        // Per IP first, so a request refused there does not use up a
+        // per-token slot as well
+        if let (Some(buckets), Some(ip)) = (&self.per_ip, ip) {
+            self.take(buckets, &ip.to_string(), now, RateLimitKind::PerIp)?;
+        }
+        if let (Some(buckets), Some(token)) = (&self.per_token, token) {
+            self.take(buckets, token, now, RateLimitKind::PerToken)?;
+        }
+        Ok(())
+    }
+
+    fn take(
+        &self,
+        buckets: &Buckets,
+        key: &str,
+        now: Instant,
+        kind: RateLimitKind,
+    ) -> Result<(), RateLimited> {
+        buckets.take(key, now).map_err(|wait| {
+            self.metrics.record(kind);
+            RateLimited {
+                kind,
+                retry_after_secs: wait.as_secs_f64().ceil().max(1.0) as u64,
+            }
+        })
+    }
+}
+
+/// `429 Too Many Requests` with `Retry-After`
+pub fn too_many_requests(limited: RateLimited) -> Response {
+    let scope = match limited.kind {
+        RateLimitKind::PerToken => "API key",
+        RateLimitKind::PerIp => "client address",
+    };
+    (
+        StatusCode::TOO_MANY_REQUESTS,
+        [(header::RETRY_AFTER, limited.retry_after_secs.to_string())],
+        Json(ApiResponse::<()>::error(ErrorResponse {
+            code: "RATE_LIMITED".to_string(),
+            message: format!(
+                "rate limit exceeded for this {}; retry after {} seconds",
+                scope, limited.retry_after_secs
+            ),
+            details: None,
+        })),
+    )
+        .into_response()
+}
+
+/// `500 Internal Server Error` for a per-IP limit that cannot see the peer
+fn peer_address_unavailable() -> Response {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiResponse::<()>::error(ErrorResponse {
+            code: "RATE_LIMIT_UNAVAILABLE".to_string(),
+            message: "per-IP rate limiting is configured but the server cannot see client \
+                      addresses"
+                .to_string(),
+            details: None,
+        })),
+    )
+        .into_response()
+}
+
+/// Refuse requests over the configured limits; a no-op when none are set.
+///
+/// The per-IP limit reads the peer address from `ConnectInfo<SocketAddr>`,
+/// which [`FastSkillServer::serve`](crate::http::server::FastSkillServer::serve)
+/// installs. A router served some other way without it gets a 500 rather
+/// than an unlimited API.
+pub async fn rate_limit(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let Some(limiter) = state.rate_limiter.clone() else {
+        return next.run(req).await;
+    };
+
+    let ip = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    if ip.is_none() && limiter.limits_per_ip() {
+        tracing::error!(
+            "Per-IP rate limit is configured but {} {} has no peer address; refusing it",
+            req.method(),
+            req.uri().path()
+        );
+        return peer_address_unavailable();
+    }
+    if let Err(limited) = limiter.check(request_api_key(req.headers()), ip) {
+        tracing::debug!(
+            "Rate limited {} {} ({:?})",
+            req.method(),
+            req.uri().path(),
+            limited.kind
+        );
+        return too_many_requests(limited);
+    }
+    next.run(req).await
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn limiter(per_token: Option<(u32, u32)>, per_ip: Option<(u32, u32)>) -> RateLimiter {
+        let limit = |(requests_per_minute, burst)| RateLimit {
+            requests_per_minute,
+            burst: Some(burst),
+        };
+        RateLimiter::new(&RateLimitConfig {
+            per_token: per_token.map(limit),
+            per_ip: per_ip.map(limit),
+        })
+    }
+
+    #[test]
+    fn test_burst_then_refill() {
+        let limiter = limiter(Some((60, 3)), None);
+        let start = Instant::now();
+        for _ in 0..3 {
+            assert!(limiter.check_at(Some("key"), None, start).is_ok());
+        }
+        let limited = limiter.check_at(Some("key"), None, start).unwrap_err();
+        assert_eq!(limited.kind, RateLimitKind::PerToken);
+        assert_eq!(limited.retry_after_secs, 1);
+
+        // 60/min refills one request per second
+        let later = start + Duration::from_secs(1);
+        assert!(limiter.check_at(Some("key"), None, later).is_ok());
+        assert!(limiter.check_at(Some("key"), None, later).is_err());
+        assert_eq!(limiter.metrics().rejected_per_token(), 2);
+        assert_eq!(limiter.metrics().rejected_per_ip(), 0);
+    }
+
+    #[test]
+    fn test_callers_have_separate_buckets() {
+        let limiter = limiter(Some((6, 1)), Some((6, 2)));
+        let now = Instant::now();
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        assert!(limiter.check_at(Some("a"), Some(ip), now).is_ok());
+        // Another key from the same address still passes the token limit,
+        // but the address has now used its burst of two
+        assert!(limiter.check_at(Some("b"), Some(ip), now).is_ok());
+        let limited = limiter.check_at(Some("c"), Some(ip), now).unwrap_err();
+        assert_eq!(limited.kind, RateLimitKind::PerIp);
+        // 6/min refills one request every ten seconds
+        assert_eq!(limited.retry_after_secs, 10);
+
+        let other: IpAddr = "10.0.0.2".parse().unwrap();
+        assert!(limiter.check_at(None, Some(other), now).is_ok());
+        assert_eq!(limiter.metrics().rejected_per_ip(), 1);
+    }
+
+    #[test]
+    fn test_per_ip_rejection_keeps_token_slot() {
+        let limiter = limiter(Some((60, 1)), Some((60, 1)));
+        let now = Instant::now();
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let other: IpAddr = "10.0.0.2".parse().unwrap();
+        assert!(limiter.check_at(None, Some(ip), now).is_ok());
+        let limited = limiter.check_at(Some("key"), Some(ip), now).unwrap_err();
+        assert_eq!(limited.kind, RateLimitKind::PerIp);
+        // The refused request did not spend the key's only token
+        assert!(limiter.check_at(Some("key"), Some(other), now).is_ok());
+    }
+
+    #[test]
+    fn test_rotating_tokens_stay_bounded() {
+        // 1/min: no bucket refills while the keys rotate, so only eviction
+        // keeps the table bounded
+        let limiter = limiter(Some((1, 5)), None);
+        let start = Instant::now();
+        let keys = MAX_TRACKED_KEYS * 2;
+        for i in 0..keys {
+            let now = start + Duration::from_millis(i as u64);
+            assert!(limiter
+                .check_at(Some(&format!("key-{i}")), None, now)
+                .is_ok());
+        }
+        let buckets = limiter.per_token.as_ref().unwrap().buckets.lock().unwrap();
+        assert!(
+            buckets.len() <= MAX_TRACKED_KEYS,
+            "{} buckets tracked",
+            buckets.len()
+        );
+        // Evicted buckets are the least recently used ones
+        assert!(buckets.contains_key(&format!("key-{}", keys - 1)));
+        assert!(!buckets.contains_key("key-0"));
+    }
+
+    #[test]
+    fn test_unkeyed_requests_skip_per_token_limit() {
+        let limiter = limiter(Some((1, 1)), None);
+        let now = Instant::now();
+        for _ in 0..5 {
+            assert!(limiter.check_at(None, None, now).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_too_many_requests_sets_retry_after() {
+        let response = too_many_requests(RateLimited {
+            kind: RateLimitKind::PerIp,
+            retry_after_secs: 7,
+        });
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "7");
+    }
+}
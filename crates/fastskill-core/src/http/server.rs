@@ -1,13 +1,28 @@
 //! Axum HTTP server implementation
 
-use crate::core::service::FastSkillService;
+use crate::core::api_keys::ApiKeyStore;
+use crate::core::audit::AuditLog;
+use crate::core::integrity::IntegrityGuard;
+use crate::core::jobs::{JobQueue, JobStore};
+use crate::core::registry_store::RegistryStore;
+use crate::core::replication::ReplicationKey;
+use crate::core::service::{FastSkillService, ServiceError};
+use crate::core::tenancy::{TenantServices, TenantSetup};
+use crate::http::audit::audit_write;
+use crate::http::auth::{authenticate, ApiKeys};
+use crate::http::drain::{drain_gate, Drain};
 use crate::http::handlers::{
-    manifest, registry, reindex, resolve, search, skills, status, AppState,
+    admin, audit, changes, graph, jobs, manifest, registry, reindex, replication, resolve, route,
+    search, skills, status, tools, validate, AppState,
 };
+use crate::http::metrics::{get_metrics, track_requests};
 use crate::http::models::{ApiResponse, ErrorResponse};
+use crate::http::openapi::openapi_json;
+use crate::http::rate_limit::{rate_limit, RateLimiter};
+use crate::http::tenancy::{TenantAppBuilder, TenantRouter};
 use axum::{
     body::Body,
-    extract::{Request, State},
+    extract::{DefaultBodyLimit, Request, State},
     http::{header, HeaderName, HeaderValue, Method, StatusCode},
     middleware::{self, Next},
     response::{IntoResponse, Response},
@@ -190,6 +205,19 @@ pub struct FastSkillServer {
     addr: SocketAddr,
     /// When false (default), mutating routes are gated and return 403 (ADR-0003).
     enable_write: bool,
+    /// When true, served skill content is checked against hashes recorded at startup.
+    verify_integrity: bool,
+    /// Guard shared with a replication follower; recorded at startup when unset.
+    integrity: Option<Arc<IntegrityGuard>>,
+    /// When set, signed snapshots are published for follower nodes.
+    replication: Option<ReplicationKey>,
+    /// When set, the gRPC services are served on this address as well.
+    #[cfg(feature = "grpc")]
+    grpc_addr: Option<SocketAddr>,
+    /// Applied to each tenant's service when tenancy is enabled.
+    tenant_setup: Option<TenantSetup>,
+    /// When set, the server hosts a registry backed by this store.
+    registry: Option<Arc<RegistryStore>>,
 }
 
 impl FastSkillServer {
@@ -210,6 +238,13 @@ impl FastSkillServer {
             service,
             addr,
             enable_write: false,
+            verify_integrity: false,
+            integrity: None,
+            replication: None,
+            #[cfg(feature = "grpc")]
+            grpc_addr: None,
+            tenant_setup: None,
+            registry: None,
         }
     }
 
@@ -221,6 +256,51 @@ impl FastSkillServer {
         self
     }
 
+    /// Verify each skill's `SKILL.md` against the hash recorded at startup
+    /// before serving it, quarantining skills that changed on disk.
+    pub fn verify_integrity(mut self, verify_integrity: bool) -> Self {
+        self.verify_integrity = verify_integrity;
+        self
+    }
+
+    /// Verify served content with `guard` instead of recording a new one at
+    /// startup. A replication follower shares its guard with the server so
+    /// synced skills are re-recorded rather than quarantined.
+    pub fn integrity_guard(mut self, guard: Arc<IntegrityGuard>) -> Self {
+        self.verify_integrity = true;
+        self.integrity = Some(guard);
+        self
+    }
+
+    /// Act as a replication primary: publish snapshots signed with `key` for
+    /// follower nodes.
+    pub fn replication_key(mut self, key: ReplicationKey) -> Self {
+        self.replication = Some(key);
+        self
+    }
+
+    /// Also serve the gRPC services (see [`crate::grpc`]) on `port`, on the
+    /// same host as the HTTP API.
+    #[cfg(feature = "grpc")]
+    pub fn grpc_port(mut self, port: u16) -> Self {
+        self.grpc_addr = Some(SocketAddr::new(self.addr.ip(), port));
+        self
+    }
+
+    /// Prepare each tenant's service with `setup` before it is initialized,
+    /// when `[tool.fastskill.server.tenancy]` is enabled.
+    pub fn tenant_setup(mut self, setup: TenantSetup) -> Self {
+        self.tenant_setup = Some(setup);
+        self
+    }
+
+    /// Host a registry: accept publishes into `store`, serve its packages and
+    /// require API keys on the raw `/index` routes as well.
+    pub fn registry_store(mut self, store: Arc<RegistryStore>) -> Self {
+        self.registry = Some(store);
+        self
+    }
+
     /// Parse and normalize host:port into a SocketAddr
     fn parse_address(host: &str, port: u16) -> Result<SocketAddr, String> {
         // Normalize common hostnames for SocketAddr compatibility
@@ -276,25 +356,51 @@ impl FastSkillServer {
             service: service_arc,
             addr,
             enable_write: false,
+            verify_integrity: false,
+            integrity: None,
+            replication: None,
+            #[cfg(feature = "grpc")]
+            grpc_addr: None,
+            tenant_setup: None,
+            registry: None,
         }
     }
 
     /// READ routes under /api/v1/ — pure reads, always mounted (ADR-0003).
     ///
-    /// list/get skills, project view, search, resolve, status, the registry
-    /// browse (GET) routes, and the manifest read. Never mutate state.
+    /// list/get skills, skill changes since a cursor, project view, dependency graph, search, resolve, route, status, tool
+    /// discovery, validation of an uploaded skill, the registry browse (GET) routes,
+    /// registry package downloads (404 unless the server hosts a registry),
+    /// the manifest read, the replication snapshot/archive routes (404 unless
+    /// the server is a replication primary), background job status and the
+    /// OpenAPI document. Never mutate state.
     fn create_read_routes_v1() -> Router<AppState> {
         Router::new()
             .route("/skills", get(skills::list_skills))
+            .route("/skills/changes", get(changes::list_changes))
+            .route("/audit", get(audit::export_audit))
+            .route("/audit/verify", get(audit::verify_audit))
             .route("/skills/{id}", get(skills::get_skill))
             .route("/skills/{id}/content", get(skills::get_skill_content))
             .route("/project", get(manifest::get_project))
+            .route("/graph", get(graph::get_graph))
             .route("/search", post(search::search_skills))
             .route("/resolve", post(resolve::resolve_context))
+            .route("/route", post(route::route_skills))
+            .route(
+                "/validate",
+                post(validate::validate_skill)
+                    .layer(DefaultBodyLimit::max(validate::MAX_VALIDATE_BODY_BYTES)),
+            )
             .route("/status", get(status::status))
+            .route("/tools", get(tools::list_tools))
             .route("/registry/index/skills", get(registry::list_index_skills))
             .route("/registry/sources", get(registry::list_sources))
             .route("/registry/skills", get(registry::list_all_skills))
+            .route(
+                "/registry/download/{scope}/{name}/{version}",
+                get(registry::download_package),
+            )
             .route(
                 "/registry/skills/{id}/versions",
                 get(registry::list_skill_versions),
@@ -308,14 +414,24 @@ impl FastSkillServer {
                 get(registry::get_marketplace),
             )
             .route("/manifest/skills", get(manifest::list_manifest_skills))
+            .route("/admin/hot-reload", get(admin::get_hot_reload))
+            .route("/admin/drain", get(admin::get_drain))
+            .route("/jobs", get(jobs::list_jobs))
+            .route("/jobs/{id}", get(jobs::get_job))
+            .route("/replication/snapshot", get(replication::get_snapshot))
+            .route(
+                "/replication/skills/{id}/archive",
+                get(replication::get_skill_archive),
+            )
+            .route("/openapi.json", get(openapi_json))
     }
 
     /// WRITE routes under /api/v1/ — anything that is not a pure read (ADR-0003).
     ///
     /// These paths are ALWAYS registered but wrapped in the write-gate middleware
     /// so they return 403 (not 404) when `--enable-write` is off. Includes:
-    /// install/update/delete skills, reindex, registry refresh, and manifest
-    /// mutators. (`POST /skills` create + `PUT /skills/{id}` field-edit removed
+    /// install/update/delete skills, reindex, registry refresh, yank and
+    /// publish, job cancellation, and manifest mutators. (`POST /skills` create + `PUT /skills/{id}` field-edit removed
     /// per PARTIAL-1 / spec 003.) `/skills/upgrade` is kept mounted alongside
     /// `/skills/update` as a back-compat alias (spec 003 §2) — same handler.
     fn create_write_routes_v1() -> Router<AppState> {
@@ -326,7 +442,23 @@ impl FastSkillServer {
             .route("/skills/upgrade", post(skills::update_skills))
             .route("/reindex", post(reindex::reindex_all))
             .route("/reindex/{id}", post(reindex::reindex_skill))
+            .route("/admin/reindex", post(admin::reindex))
+            .route("/admin/cache/clear", post(admin::clear_cache))
+            .route("/jobs/{id}/cancel", post(jobs::cancel_job))
+            .route("/admin/hot-reload", put(admin::set_hot_reload))
+            .route(
+                "/admin/drain",
+                post(admin::start_drain).delete(admin::stop_drain),
+            )
             .route("/registry/refresh", post(registry::refresh_sources))
+            .route("/registry/index/yank", post(registry::yank_version))
+            .route("/registry/index/unyank", post(registry::unyank_version))
+            .route(
+                "/registry/publish",
+                post(registry::publish_package).layer(DefaultBodyLimit::max(
+                    crate::core::registry_store::MAX_PUBLISH_BYTES,
+                )),
+            )
             .route("/manifest/skills", post(manifest::add_skill_to_manifest))
             .route(
                 "/manifest/skills/{id}",
@@ -354,6 +486,64 @@ impl FastSkillServer {
             .route("/styles.css", get(serve_embedded_static))
     }
 
+    /// Prometheus scrape endpoint, served at the root next to the UI
+    fn create_metrics_routes() -> Router<AppState> {
+        Router::new().route("/metrics", get(get_metrics))
+    }
+
+    /// `/api/v1` routes over `state`: reads, gated and audited writes, API-key
+    /// checks and rate limiting.
+    fn api_routes_v1(state: AppState) -> Router {
+        // WRITE routes are always registered, but wrapped in the write-gate
+        // middleware so they return 403 (discoverable) rather than 404 when
+        // writes are disabled (ADR-0003 / WRITE-GATE). The audit layer sits
+        // outside the gate so refused attempts are recorded as well.
+        let write_router = Self::create_write_routes_v1()
+            .route_layer(middleware::from_fn_with_state(state.clone(), write_gate))
+            .route_layer(middleware::from_fn_with_state(state.clone(), audit_write));
+
+        // Rate limiting wraps every v1 route, inside tracing and request metrics so
+        // 429s are logged and counted. API-key checks run inside the rate limit,
+        // so guessing keys is throttled too. The drain gate is outermost: while
+        // draining, new requests are refused before any other work.
+        Router::new()
+            .merge(Self::create_read_routes_v1())
+            .merge(write_router)
+            .layer(middleware::from_fn_with_state(state.clone(), authenticate))
+            .layer(middleware::from_fn_with_state(state.clone(), rate_limit))
+            .layer(middleware::from_fn_with_state(state.clone(), drain_gate))
+            .with_state(state)
+    }
+
+    /// Builds each tenant's v1 routes over a state of its own: project, audit
+    /// log and API keys live in the tenant directory, while write access,
+    /// integrity checks, the rate limiter and draining follow the server.
+    fn tenant_app_builder(
+        &self,
+        rate_limiter: Option<Arc<RateLimiter>>,
+        drain: Arc<Drain>,
+    ) -> TenantAppBuilder {
+        let enable_write = self.enable_write;
+        let verify_integrity = self.verify_integrity;
+        Arc::new(move |service: Arc<FastSkillService>| {
+            let dir = service.config().skill_storage_path.clone();
+            let mut state = AppState::new(service)
+                .map_err(|e| ServiceError::Custom(e.to_string()))?
+                .with_project_config(dir.clone(), dir.join("skill-project.toml"), dir.clone())
+                .with_enable_write(enable_write)
+                .with_drain(Arc::clone(&drain))
+                .with_audit_log(Arc::new(AuditLog::new(AuditLog::default_path(&dir))))
+                .with_api_keys(Arc::new(ApiKeys::new(ApiKeyStore::default_path(&dir))));
+            if verify_integrity {
+                state = state.with_integrity_guard(Arc::new(IntegrityGuard::snapshot(&dir)?));
+            }
+            if let Some(limiter) = &rate_limiter {
+                state = state.with_rate_limiter(Arc::clone(limiter));
+            }
+            Ok(Self::api_routes_v1(state))
+        })
+    }
+
     /// Start the server using cli-framework ApiServerBuilder
     pub async fn serve(self) -> Result<(), Box<dyn std::error::Error>> {
         // Load project configuration (same as previous create_router logic)
@@ -377,38 +567,160 @@ impl FastSkillServer {
             );
         }
         state = state.with_enable_write(self.enable_write);
+        if let Some(guard) = &self.integrity {
+            info!("Integrity mode on: {} skills recorded", guard.len());
+            state = state.with_integrity_guard(Arc::clone(guard));
+        } else if self.verify_integrity {
+            let guard = IntegrityGuard::snapshot(&state.skills_directory)?;
+            info!(
+                "Integrity mode on: recorded hashes for {} skills",
+                guard.len()
+            );
+            state = state.with_integrity_guard(Arc::new(guard));
+        }
+        if let Some(key) = &self.replication {
+            info!("Replication primary: publishing signed snapshots");
+            state = state.with_replication_key(key.clone());
+        }
+        if let Some(store) = &self.registry {
+            info!("Hosting a registry at {}", store.index_path().display());
+            state = state.with_registry_store(Arc::clone(store));
+        }
+        if let Some(limits) = self
+            .service
+            .config()
+            .http_server
+            .as_ref()
+            .map(|http| http.rate_limit)
+            .filter(|limits| limits.is_enabled())
+        {
+            info!(
+                "Rate limiting /api/v1: per token {:?}, per IP {:?}",
+                limits.per_token, limits.per_ip
+            );
+            state = state.with_rate_limiter(Arc::new(RateLimiter::new(&limits)));
+        }
+        let audit_path = AuditLog::default_path(&self.service.config().skill_storage_path);
+        info!("Auditing write operations to {}", audit_path.display());
+        state = state.with_audit_log(Arc::new(AuditLog::new(audit_path)));
+        let api_keys = ApiKeys::new(ApiKeyStore::default_path(
+            &self.service.config().skill_storage_path,
+        ));
+        match api_keys.current() {
+            Ok(store) if store.is_enabled() => {
+                let active = store.keys().iter().filter(|k| k.is_active()).count();
+                info!(
+                    "API key authentication on: {} active keys in {}",
+                    active,
+                    store.path().display()
+                );
+                if active == 0 {
+                    tracing::warn!(
+                        "Every API key in {} is revoked; requests that need a key will be refused",
+                        store.path().display()
+                    );
+                }
+            }
+            Ok(_) => {}
+            Err(e) => tracing::error!("{}; every /api/v1 request will fail", e),
+        }
+        state = state.with_api_keys(Arc::new(api_keys));
+        let jobs = self
+            .service
+            .config()
+            .http_server
+            .as_ref()
+            .map(|http| http.jobs.clone())
+            .unwrap_or_default();
+        let mut queue = JobQueue::new()
+            .with_events(self.service.event_bus())
+            .with_max_finished(jobs.max_finished);
+        if jobs.persist {
+            let path = jobs.path.unwrap_or_else(|| {
+                JobStore::default_path(&self.service.config().skill_storage_path)
+            });
+            info!("Keeping background jobs in {}", path.display());
+            queue = queue.with_store(JobStore::open(path)?).await?;
+        }
+        state = state.with_job_queue(Arc::new(queue));
+
+        // With tenancy on, each request is served by its tenant's own copy of
+        // the v1 routes and state (see `crate::http::tenancy`)
+        let tenancy = self.service.config().tenancy.clone();
+        let api_router = if tenancy.enabled {
+            tenancy.validate()?;
+            if self.replication.is_some() {
+                return Err("replication cannot be combined with tenancy".into());
+            }
+            if self.registry.is_some() {
+                return Err("a hosted registry cannot be combined with tenancy".into());
+            }
+            #[cfg(feature = "grpc")]
+            if self.grpc_addr.is_some() {
+                return Err("the gRPC API is not available with tenancy".into());
+            }
+            info!(
+                "Tenancy on: tenant skill sets under {}",
+                tenancy.tenants_root(self.service.config()).display()
+            );
+            let mut services = TenantServices::new(self.service.config().clone());
+            if let Some(setup) = self.tenant_setup.clone() {
+                services = services.with_setup(setup);
+            }
+            let tenants = Arc::new(TenantRouter::new(
+                services,
+                self.tenant_app_builder(state.rate_limiter.clone(), Arc::clone(&state.drain)),
+            ));
+            Router::new().fallback(move |req: Request| {
+                let tenants = Arc::clone(&tenants);
+                async move { tenants.dispatch(req).await }
+            })
+        } else {
+            Self::api_routes_v1(state.clone())
+        };
 
-        // WRITE routes are always registered, but wrapped in the write-gate
-        // middleware so they return 403 (discoverable) rather than 404 when
-        // writes are disabled (ADR-0003 / WRITE-GATE).
-        let write_router = Self::create_write_routes_v1()
-            .route_layer(middleware::from_fn_with_state(state.clone(), write_gate));
-
-        // Build versioned v1 router with compression (applied to fastskill routes only)
-        let v1_router = Router::new()
-            .merge(Self::create_read_routes_v1())
-            .merge(write_router)
+        // Build versioned v1 router with compression (applied to fastskill routes only).
+        let v1_router = api_router
+            .layer(middleware::from_fn(track_requests))
             .layer(TraceLayer::new_for_http())
-            .layer(CompressionLayer::new())
-            .with_state(state.clone());
-
-        // Raw index surface mounted at /index (unchanged URL contract)
-        let index_router = Self::create_registry_index_routes_v1().with_state(state.clone());
-
-        // Console UI served as root fallback
-        let ui_router = Self::create_ui_routes().with_state(state.clone());
+            .layer(CompressionLayer::new());
+
+        // Raw index surface mounted at /index (unchanged URL contract). A
+        // hosted registry keeps it behind the same API keys as /api/v1.
+        let mut index_router = Self::create_registry_index_routes_v1();
+        if self.registry.is_some() {
+            index_router =
+                index_router.layer(middleware::from_fn_with_state(state.clone(), authenticate));
+        }
+        let index_router = index_router.with_state(state.clone());
+
+        // Console UI and /metrics served as root fallback
+        let ui_router = Self::create_ui_routes().merge(Self::create_metrics_routes());
+        #[cfg(feature = "swagger")]
+        let ui_router = {
+            info!(
+                "Serving Swagger UI at {}",
+                crate::http::openapi::SWAGGER_UI_PATH
+            );
+            ui_router.merge(crate::http::openapi::swagger_ui())
+        };
+        let ui_router = ui_router.with_state(state.clone());
 
         let cors_layer = build_cors_layer(self.service.config());
 
-        let server = ApiServerBuilder::new()
+        let mut builder = ApiServerBuilder::new()
             .version(ApiVersion {
                 name: ApiVersionName::parse("v1")?,
                 router: v1_router,
                 stability: Stability::Stable,
                 deprecation: None,
             })
-            .default_version(DefaultVersion::Pinned(ApiVersionName::parse("v1")?))
-            .mount("/index", index_router)
+            .default_version(DefaultVersion::Pinned(ApiVersionName::parse("v1")?));
+        // The raw index belongs to the server's own skills dir, not to a tenant
+        if !tenancy.enabled {
+            builder = builder.mount("/index", index_router);
+        }
+        let server = builder
             .cors(cors_layer)
             .root_fallback(ui_router)
             .health_version(env!("CARGO_PKG_VERSION"))
@@ -416,9 +728,22 @@ impl FastSkillServer {
 
         info!("Starting FastSkill HTTP server on {}", self.addr);
 
-        let addr_str = self.addr.to_string();
         println!("  Listening on: http://{}", self.addr);
-        server.serve(&addr_str).await?;
+        let app = server.into_router();
+
+        #[cfg(feature = "grpc")]
+        if let Some(grpc_addr) = self.grpc_addr {
+            info!("Starting FastSkill gRPC server on {}", grpc_addr);
+            println!("  gRPC listening on: {}", grpc_addr);
+            // Whichever server stops first (normally with an error) stops both
+            tokio::select! {
+                result = serve_with_connect_info(app, self.addr) => result?,
+                result = crate::grpc::serve(state, grpc_addr) => result?,
+            }
+            return Ok(());
+        }
+
+        serve_with_connect_info(app, self.addr).await?;
 
         Ok(())
     }
@@ -428,3 +753,45 @@ impl FastSkillServer {
         self.addr
     }
 }
+
+/// Serve `app` on `addr` with each connection's peer address available as
+/// `ConnectInfo<SocketAddr>`, which the per-IP rate limit and the audit log
+/// read. Stops accepting connections on Ctrl-C or SIGTERM and lets in-flight
+/// requests finish.
+async fn serve_with_connect_info(app: Router, addr: SocketAddr) -> std::io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal())
+    .await
+}
+
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::warn!("Cannot listen for Ctrl-C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::warn!("Cannot listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+    info!("Shutting down FastSkill HTTP server");
+}
@@ -0,0 +1,289 @@
+//! Tenant selection and dispatch for multi-tenant servers
+//!
+//! Each `/api/v1` request is routed to the tenant's own router and
+//! [`AppState`](crate::http::handlers::AppState), built on first use (see
+//! [`crate::core::tenancy`]). The tenant is read from the configured header,
+//! or, when a JWT secret is configured, from a claim of an HS256-signed
+//! `Authorization: Bearer` token; the header is then ignored.
+
+use crate::core::service::{FastSkillService, ServiceError};
+use crate::core::tenancy::{TenancyConfig, TenantId, TenantJwtConfig, TenantServices};
+use crate::http::errors::HttpError;
+use axum::{
+    extract::Request,
+    http::{header::AUTHORIZATION, HeaderMap, Uri},
+    response::{IntoResponse, Response},
+    Router,
+};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+/// Builds the `/api/v1` router serving one tenant's service
+pub type TenantAppBuilder =
+    Arc<dyn Fn(Arc<FastSkillService>) -> Result<Router, ServiceError> + Send + Sync>;
+
+/// The tenant a request is for
+pub fn request_tenant(tenancy: &TenancyConfig, headers: &HeaderMap) -> Result<TenantId, HttpError> {
+    let raw = match &tenancy.jwt {
+        Some(jwt) => {
+            let token = headers
+                .get(AUTHORIZATION)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.strip_prefix("Bearer "))
+                .map(str::trim)
+                .ok_or_else(|| {
+                    HttpError::Unauthorized(
+                        "tenant token required (Authorization: Bearer <JWT>)".to_string(),
+                    )
+                })?;
+            tenant_claim(jwt, token).map_err(HttpError::Unauthorized)?
+        }
+        None => headers
+            .get(tenancy.header.as_str())
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| HttpError::BadRequest(format!("{} header required", tenancy.header)))?,
+    };
+    TenantId::parse(&raw).map_err(|e| HttpError::BadRequest(e.to_string()))
+}
+
+/// Verify an HS256 JWT (signature, a required `exp` and any `nbf`) and read
+/// the tenant claim
+fn tenant_claim(jwt: &TenantJwtConfig, token: &str) -> Result<String, String> {
+    let secret = jwt
+        .secret
+        .as_deref()
+        .ok_or_else(|| format!("tenant JWT secret is not set ({})", jwt.secret_env))?;
+    let parts: Vec<&str> = token.split('.').collect();
+    let (header, payload, signature) = match parts.as_slice() {
+        [header, payload, signature] => (*header, *payload, *signature),
+        _ => return Err("malformed tenant token".to_string()),
+    };
+    let decode = |part: &str| -> Result<serde_json::Value, String> {
+        URL_SAFE_NO_PAD
+            .decode(part)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .ok_or_else(|| "malformed tenant token".to_string())
+    };
+
+    if decode(header)?["alg"] != "HS256" {
+        return Err("tenant token must be signed with HS256".to_string());
+    }
+    let signature = URL_SAFE_NO_PAD
+        .decode(signature)
+        .map_err(|_| "malformed tenant token".to_string())?;
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .map_err(|_| "invalid tenant JWT secret".to_string())?;
+    mac.update(format!("{}.{}", header, payload).as_bytes());
+    mac.verify_slice(&signature)
+        .map_err(|_| "invalid tenant token signature".to_string())?;
+
+    let claims = decode(payload)?;
+    let now = chrono::Utc::now().timestamp() as f64;
+    let exp = claims
+        .get("exp")
+        .and_then(|v| v.as_f64())
+        .ok_or_else(|| "tenant token has no 'exp' claim".to_string())?;
+    if exp <= now {
+        return Err("tenant token has expired".to_string());
+    }
+    if let Some(nbf) = claims.get("nbf") {
+        match nbf.as_f64() {
+            Some(nbf) if nbf <= now => {}
+            Some(_) => return Err("tenant token is not valid yet".to_string()),
+            None => return Err("malformed tenant token".to_string()),
+        }
+    }
+    claims
+        .get(&jwt.claim)
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| format!("tenant token has no '{}' claim", jwt.claim))
+}
+
+/// Routes `/api/v1` requests to the requesting tenant's router
+pub struct TenantRouter {
+    services: TenantServices,
+    build: TenantAppBuilder,
+    routers: tokio::sync::Mutex<HashMap<TenantId, Router>>,
+}
+
+impl TenantRouter {
+    pub fn new(services: TenantServices, build: TenantAppBuilder) -> Self {
+        Self {
+            services,
+            build,
+            routers: tokio::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn router(&self, tenant: &TenantId) -> Result<Router, HttpError> {
+        if let Some(router) = self.routers.lock().await.get(tenant) {
+            return Ok(router.clone());
+        }
+        if !self.services.is_known(tenant) {
+            return Err(HttpError::NotFound(format!("Unknown tenant '{}'", tenant)));
+        }
+        let service = self.services.get(tenant).await?;
+        let router = (self.build)(service)?;
+        Ok(self
+            .routers
+            .lock()
+            .await
+            .entry(tenant.clone())
+            .or_insert(router)
+            .clone())
+    }
+
+    /// Serve `req` with its tenant's router: 400 without a tenant, 401 for a
+    /// bad token, 404 for a tenant that is neither listed nor provisioned.
+    pub async fn dispatch(&self, mut req: Request) -> Response {
+        let tenant = match request_tenant(self.services.tenancy(), req.headers()) {
+            Ok(tenant) => tenant,
+            Err(e) => return e.into_response(),
+        };
+        let router = match self.router(&tenant).await {
+            Ok(router) => router,
+            Err(e) => return e.into_response(),
+        };
+
+        // The tenant routers are unprefixed, like the single-tenant v1 router
+        if let Some(rest) = req.uri().path().strip_prefix("/api/v1") {
+            let path_and_query = match req.uri().query() {
+                Some(query) => format!("{}?{}", rest, query),
+                None => rest.to_string(),
+            };
+            if let Ok(uri) = path_and_query.parse::<Uri>() {
+                *req.uri_mut() = uri;
+            }
+        }
+        match router.oneshot(req).await {
+            Ok(response) => response,
+            Err(infallible) => match infallible {},
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "tenant-secret";
+
+    fn jwt_tenancy() -> TenancyConfig {
+        TenancyConfig {
+            enabled: true,
+            jwt: Some(TenantJwtConfig {
+                secret: Some(SECRET.to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn token(secret: &str, claims: serde_json::Value) -> String {
+        let header = URL_SAFE_NO_PAD.encode(br#"{"alg":"HS256","typ":"JWT"}"#);
+        let payload = URL_SAFE_NO_PAD.encode(claims.to_string());
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(format!("{}.{}", header, payload).as_bytes());
+        let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+        format!("{}.{}.{}", header, payload, signature)
+    }
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, value.parse().unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn test_tenant_from_header() {
+        let tenancy = TenancyConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        let tenant = request_tenant(&tenancy, &headers(&[("x-fastskill-tenant", "acme")])).unwrap();
+        assert_eq!(tenant.as_str(), "acme");
+        assert!(request_tenant(&tenancy, &HeaderMap::new()).is_err());
+        assert!(request_tenant(&tenancy, &headers(&[("x-fastskill-tenant", "../etc")])).is_err());
+    }
+
+    #[test]
+    fn test_tenant_from_signed_jwt() {
+        let tenancy = jwt_tenancy();
+        let bearer = |token: String| format!("Bearer {}", token);
+        let exp = chrono::Utc::now().timestamp() + 600;
+        let valid = bearer(token(
+            SECRET,
+            serde_json::json!({"tenant": "acme", "exp": exp}),
+        ));
+        let tenant = request_tenant(
+            &tenancy,
+            &headers(&[("authorization", &valid), ("x-fastskill-tenant", "globex")]),
+        )
+        .unwrap();
+        assert_eq!(tenant.as_str(), "acme");
+
+        let status = |value: &str| {
+            request_tenant(&tenancy, &headers(&[("authorization", value)]))
+                .unwrap_err()
+                .status_code()
+        };
+        let forged = bearer(token(
+            "other",
+            serde_json::json!({"tenant": "acme", "exp": exp}),
+        ));
+        assert_eq!(status(&forged), axum::http::StatusCode::UNAUTHORIZED);
+        let expired = bearer(token(
+            SECRET,
+            serde_json::json!({"tenant": "acme", "exp": 1}),
+        ));
+        assert_eq!(status(&expired), axum::http::StatusCode::UNAUTHORIZED);
+        let no_claim = bearer(token(
+            SECRET,
+            serde_json::json!({"sub": "acme", "exp": exp}),
+        ));
+        assert_eq!(status(&no_claim), axum::http::StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            status("Bearer not.a-token"),
+            axum::http::StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[test]
+    fn test_tenant_jwt_requires_exp_and_honours_nbf() {
+        let tenancy = jwt_tenancy();
+        let claim = |claims: serde_json::Value| {
+            let bearer = format!("Bearer {}", token(SECRET, claims));
+            request_tenant(&tenancy, &headers(&[("authorization", &bearer)]))
+        };
+        let now = chrono::Utc::now().timestamp();
+
+        let err = claim(serde_json::json!({"tenant": "acme"})).unwrap_err();
+        assert_eq!(err.status_code(), axum::http::StatusCode::UNAUTHORIZED);
+        assert!(err.to_string().contains("'exp'"), "{}", err);
+
+        let early =
+            claim(serde_json::json!({"tenant": "acme", "exp": now + 600, "nbf": now + 300}))
+                .unwrap_err();
+        assert_eq!(early.status_code(), axum::http::StatusCode::UNAUTHORIZED);
+        assert!(early.to_string().contains("not valid yet"), "{}", early);
+
+        let bad_nbf = claim(serde_json::json!({"tenant": "acme", "exp": now + 600, "nbf": "soon"}));
+        assert!(bad_nbf.is_err());
+
+        let started =
+            claim(serde_json::json!({"tenant": "acme", "exp": now + 600, "nbf": now - 1})).unwrap();
+        assert_eq!(started.as_str(), "acme");
+    }
+}
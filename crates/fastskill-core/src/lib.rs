@@ -47,6 +47,10 @@
 pub mod core;
 pub mod events;
 pub mod execution;
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
+#[cfg(feature = "grpc")]
+pub mod grpc;
 pub mod http;
 pub mod output;
 pub mod search;
@@ -64,12 +68,15 @@ pub use core::manifest::SkillProjectToml;
 pub use core::metadata::{
     parse_yaml_frontmatter, MetadataService, SkillFrontmatter, SkillMetadata,
 };
-pub use core::routing::{RoutedSkill, RoutingService};
+pub use core::routing::{RouteDecision, RoutedSkill, RoutingPolicy, RoutingService};
 pub use core::service::SkillId;
-pub use core::service::{EmbeddingConfig, FastSkillService, ServiceConfig, ServiceError};
+pub use core::service::{
+    AnnConfig, EmbeddingConfig, FastSkillService, ServiceConfig, ServiceError,
+};
 pub use core::skill_manager::{SkillDefinition, SkillManagementService};
 pub use core::vector_index::{
-    IndexedSkill, SkillMatch, VectorIndexService, VectorIndexServiceImpl,
+    IndexMismatch, IndexedSkill, SkillMatch, VectorIndexMetadata, VectorIndexService,
+    VectorIndexServiceImpl,
 };
 
 // Re-export search and output types
@@ -84,21 +91,55 @@ pub fn init_logging() {
     init_logging_with_verbose(false)
 }
 
+/// How much diagnostic logging the service layer emits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    /// Errors only
+    Quiet,
+    /// Warnings and errors
+    #[default]
+    Normal,
+    /// Informational progress as well
+    Verbose,
+}
+
 /// Initialize logging for the service layer with optional verbose mode
 pub fn init_logging_with_verbose(verbose: bool) {
+    init_logging_with_verbosity(if verbose {
+        Verbosity::Verbose
+    } else {
+        Verbosity::Normal
+    })
+}
+
+/// Initialize logging at `verbosity`; `RUST_LOG` still overrides the level.
+/// Log lines carry ANSI colors only when [`output::table::color_enabled`].
+/// Debug-level events are also kept in [`output::log_tail`] for diagnostics.
+pub fn init_logging_with_verbosity(verbosity: Verbosity) {
     // Only initialize logging once
     static INIT: std::sync::Once = std::sync::Once::new();
     INIT.call_once(|| {
-        use tracing_subscriber::EnvFilter;
+        use tracing_subscriber::filter::Targets;
+        use tracing_subscriber::layer::SubscriberExt;
+        use tracing_subscriber::{EnvFilter, Layer};
 
-        let default_level = if verbose {
-            "fastskill=info"
-        } else {
-            "fastskill=warn"
+        let default_level = match verbosity {
+            Verbosity::Quiet => "fastskill=error",
+            Verbosity::Normal => "fastskill=warn",
+            Verbosity::Verbose => "fastskill=info",
         };
         let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| default_level.into());
 
-        let subscriber = tracing_subscriber::fmt().with_env_filter(filter).finish();
+        let subscriber = tracing_subscriber::registry()
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .with_ansi(output::table::color_enabled())
+                    .with_filter(filter),
+            )
+            .with(
+                output::log_tail::RecentLogs
+                    .with_filter(Targets::new().with_target("fastskill", tracing::Level::DEBUG)),
+            );
 
         // This will fail silently if already initialized
         let _ = tracing::subscriber::set_global_default(subscriber);
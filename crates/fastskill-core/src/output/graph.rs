@@ -0,0 +1,361 @@
+//! Dependency graph rendering: an indented text tree, Graphviz DOT, or Mermaid
+//!
+//! All three render the same [`DependencyGraphResponse`] that `GET /api/v1/graph`
+//! returns. Edges carry their declared version constraint as a label, and
+//! edges on a dependency cycle are drawn in red so they stand out in docs.
+
+use crate::http::models::{DependencyGraphResponse, GraphEdge, GraphNode};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::{self, Write as _};
+
+const CYCLE_COLOR: &str = "#d62728";
+
+/// Output format of a rendered dependency graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GraphFormat {
+    #[default]
+    Text,
+    Dot,
+    Mermaid,
+}
+
+impl fmt::Display for GraphFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphFormat::Text => write!(f, "text"),
+            GraphFormat::Dot => write!(f, "dot"),
+            GraphFormat::Mermaid => write!(f, "mermaid"),
+        }
+    }
+}
+
+impl std::str::FromStr for GraphFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" | "tree" => Ok(GraphFormat::Text),
+            "dot" | "graphviz" => Ok(GraphFormat::Dot),
+            "mermaid" => Ok(GraphFormat::Mermaid),
+            _ => Err(format!(
+                "Invalid format '{}'. Supported formats: text, dot, mermaid",
+                s
+            )),
+        }
+    }
+}
+
+/// Render `graph` in `format`.
+pub fn render_graph(graph: &DependencyGraphResponse, format: GraphFormat) -> String {
+    match format {
+        GraphFormat::Text => render_text(graph),
+        GraphFormat::Dot => render_dot(graph),
+        GraphFormat::Mermaid => render_mermaid(graph),
+    }
+}
+
+fn is_cycle(edge: &GraphEdge) -> bool {
+    edge.conflicts.iter().any(|c| c.kind == "cycle")
+}
+
+fn node_label(node: &GraphNode) -> String {
+    match &node.version {
+        Some(version) => format!("{} {}", node.id, version),
+        None => node.id.clone(),
+    }
+}
+
+/// Why a node is drawn as a problem, if it is one
+fn node_problem(node: &GraphNode) -> Option<&str> {
+    if !node.locked && !node.installed {
+        Some("missing")
+    } else {
+        node.conflicts.first().map(|c| c.kind.as_str())
+    }
+}
+
+/// Indented tree from each root (a node nothing depends on, or a locked
+/// direct dependency). A skill reached again on the same path is a cycle and
+/// is not expanded twice.
+fn render_text(graph: &DependencyGraphResponse) -> String {
+    if graph.nodes.is_empty() {
+        return "No skills installed or locked\n".to_string();
+    }
+    let nodes: BTreeMap<&str, &GraphNode> =
+        graph.nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+    let mut children: BTreeMap<&str, Vec<&GraphEdge>> = BTreeMap::new();
+    for edge in &graph.edges {
+        children.entry(edge.from.as_str()).or_default().push(edge);
+    }
+    let targets: HashSet<&str> = graph.edges.iter().map(|e| e.to.as_str()).collect();
+    let mut roots: Vec<&GraphNode> = graph
+        .nodes
+        .iter()
+        .filter(|n| n.depth == Some(0) || !targets.contains(n.id.as_str()))
+        .collect();
+    // A graph that is one big cycle has no root; start from every node.
+    if roots.is_empty() {
+        roots = graph.nodes.iter().collect();
+    }
+
+    let mut out = String::new();
+    for root in roots {
+        let _ = writeln!(out, "{}", describe(root, None));
+        let mut path = vec![root.id.as_str()];
+        write_children(&mut out, &nodes, &children, &mut path, "");
+    }
+    out
+}
+
+fn describe(node: &GraphNode, edge: Option<&GraphEdge>) -> String {
+    let mut line = node_label(node);
+    if let Some(constraint) = edge.and_then(|e| e.constraint.as_deref()) {
+        let _ = write!(line, " ({})", constraint);
+    }
+    let mut problems: Vec<&str> = node_problem(node).into_iter().collect();
+    if let Some(edge) = edge {
+        problems.extend(
+            edge.conflicts
+                .iter()
+                .map(|c| c.kind.as_str())
+                .filter(|kind| *kind != "missing"),
+        );
+    }
+    if !problems.is_empty() {
+        let _ = write!(line, " [{}]", problems.join(", "));
+    }
+    line
+}
+
+fn write_children<'a>(
+    out: &mut String,
+    nodes: &BTreeMap<&str, &'a GraphNode>,
+    children: &BTreeMap<&str, Vec<&'a GraphEdge>>,
+    path: &mut Vec<&'a str>,
+    prefix: &str,
+) {
+    let Some(edges) = path.last().and_then(|id| children.get(id)) else {
+        return;
+    };
+    for (index, edge) in edges.iter().enumerate() {
+        let last = index + 1 == edges.len();
+        let branch = if last { "└── " } else { "├── " };
+        let Some(node) = nodes.get(edge.to.as_str()) else {
+            continue;
+        };
+        let _ = writeln!(out, "{}{}{}", prefix, branch, describe(node, Some(edge)));
+        if path.contains(&edge.to.as_str()) {
+            continue;
+        }
+        path.push(edge.to.as_str());
+        let nested = format!("{}{}", prefix, if last { "    " } else { "│   " });
+        write_children(out, nodes, children, path, &nested);
+        path.pop();
+    }
+}
+
+/// Quote a DOT identifier or label
+fn dot_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn render_dot(graph: &DependencyGraphResponse) -> String {
+    let mut out = String::from("digraph skills {\n    rankdir=LR;\n    node [shape=box];\n");
+    for node in &graph.nodes {
+        let mut attrs = vec![format!("label={}", dot_quote(&node_label(node)))];
+        if let Some(problem) = node_problem(node) {
+            attrs.push("style=dashed".to_string());
+            attrs.push(format!("tooltip={}", dot_quote(problem)));
+        }
+        let _ = writeln!(out, "    {} [{}];", dot_quote(&node.id), attrs.join(", "));
+    }
+    for edge in &graph.edges {
+        let mut attrs = Vec::new();
+        if let Some(constraint) = &edge.constraint {
+            attrs.push(format!("label={}", dot_quote(constraint)));
+        }
+        if is_cycle(edge) {
+            attrs.push(format!("color={}", dot_quote(CYCLE_COLOR)));
+            attrs.push(format!("fontcolor={}", dot_quote(CYCLE_COLOR)));
+            attrs.push("penwidth=2".to_string());
+        }
+        let attrs = if attrs.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", attrs.join(", "))
+        };
+        let _ = writeln!(
+            out,
+            "    {} -> {}{};",
+            dot_quote(&edge.from),
+            dot_quote(&edge.to),
+            attrs
+        );
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Mermaid label text; quotes would end the label
+fn mermaid_text(value: &str) -> String {
+    value.replace('"', "#quot;")
+}
+
+fn render_mermaid(graph: &DependencyGraphResponse) -> String {
+    // Skill ids contain characters Mermaid does not allow in node ids
+    // (`/`, `@`, `.`), so nodes get positional ids and the skill id as label.
+    let ids: BTreeMap<&str, String> = graph
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(index, node)| (node.id.as_str(), format!("n{}", index)))
+        .collect();
+
+    let mut out = String::from("graph LR\n");
+    let mut problems = Vec::new();
+    for node in &graph.nodes {
+        let id = &ids[node.id.as_str()];
+        let _ = writeln!(out, "    {}[\"{}\"]", id, mermaid_text(&node_label(node)));
+        if node_problem(node).is_some() {
+            problems.push(id.as_str());
+        }
+    }
+    let mut cycles = Vec::new();
+    for (index, edge) in graph.edges.iter().enumerate() {
+        let (Some(from), Some(to)) = (ids.get(edge.from.as_str()), ids.get(edge.to.as_str()))
+        else {
+            continue;
+        };
+        match &edge.constraint {
+            Some(constraint) => {
+                let _ = writeln!(
+                    out,
+                    "    {} -->|\"{}\"| {}",
+                    from,
+                    mermaid_text(constraint),
+                    to
+                );
+            }
+            None => {
+                let _ = writeln!(out, "    {} --> {}", from, to);
+            }
+        }
+        if is_cycle(edge) {
+            cycles.push(index.to_string());
+        }
+    }
+    if !problems.is_empty() {
+        let _ = writeln!(out, "    classDef problem stroke-dasharray: 5 5");
+        let _ = writeln!(out, "    class {} problem", problems.join(","));
+    }
+    if !cycles.is_empty() {
+        let _ = writeln!(
+            out,
+            "    linkStyle {} stroke:{},stroke-width:2px",
+            cycles.join(","),
+            CYCLE_COLOR
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::http::models::GraphConflict;
+
+    fn node(id: &str, depth: Option<u32>) -> GraphNode {
+        GraphNode {
+            id: id.to_string(),
+            name: None,
+            version: depth.map(|_| "1.0.0".to_string()),
+            depth,
+            locked: depth.is_some(),
+            installed: depth.is_some(),
+            conflicts: Vec::new(),
+        }
+    }
+
+    fn edge(from: &str, to: &str, constraint: Option<&str>, cycle: bool) -> GraphEdge {
+        GraphEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            constraint: constraint.map(str::to_string),
+            conflicts: if cycle {
+                vec![GraphConflict {
+                    kind: "cycle".to_string(),
+                    message: String::new(),
+                }]
+            } else {
+                Vec::new()
+            },
+        }
+    }
+
+    /// app -> acme/lib (^1.0) -> util -> acme/lib (a cycle), plus app -> ghost
+    fn sample() -> DependencyGraphResponse {
+        DependencyGraphResponse {
+            nodes: vec![
+                node("acme/lib", Some(1)),
+                node("app", Some(0)),
+                node("ghost", None),
+                node("util", Some(2)),
+            ],
+            edges: vec![
+                edge("app", "acme/lib", Some("^1.0"), false),
+                edge("app", "ghost", None, false),
+                edge("acme/lib", "util", None, true),
+                edge("util", "acme/lib", Some(">=1.0"), true),
+            ],
+            conflict_count: 3,
+        }
+    }
+
+    #[test]
+    fn test_render_text_tree_stops_at_cycles() {
+        let text = render_graph(&sample(), GraphFormat::Text);
+        assert_eq!(
+            text,
+            "app 1.0.0\n\
+             ├── acme/lib 1.0.0 (^1.0)\n\
+             │   └── util 1.0.0 [cycle]\n\
+             │       └── acme/lib 1.0.0 (>=1.0) [cycle]\n\
+             └── ghost [missing]\n"
+        );
+    }
+
+    #[test]
+    fn test_render_dot_labels_constraints_and_highlights_cycles() {
+        let dot = render_graph(&sample(), GraphFormat::Dot);
+        assert!(dot.starts_with("digraph skills {\n"));
+        assert!(dot.contains("\"app\" -> \"acme/lib\" [label=\"^1.0\"];"));
+        assert!(dot.contains("\"app\" -> \"ghost\";"));
+        assert!(dot.contains(
+            "\"util\" -> \"acme/lib\" [label=\">=1.0\", color=\"#d62728\", fontcolor=\"#d62728\", penwidth=2];"
+        ));
+        assert!(dot.contains("\"ghost\" [label=\"ghost\", style=dashed, tooltip=\"missing\"];"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_render_mermaid_uses_safe_node_ids() {
+        let mermaid = render_graph(&sample(), GraphFormat::Mermaid);
+        assert!(mermaid.starts_with("graph LR\n"));
+        assert!(mermaid.contains("    n0[\"acme/lib 1.0.0\"]\n"));
+        assert!(mermaid.contains("    n1 -->|\"^1.0\"| n0\n"));
+        assert!(mermaid.contains("    n1 --> n2\n"));
+        assert!(mermaid.contains("    class n2 problem\n"));
+        assert!(mermaid.contains("    linkStyle 2,3 stroke:#d62728,stroke-width:2px\n"));
+    }
+
+    #[test]
+    fn test_graph_format_from_str() {
+        assert_eq!("DOT".parse::<GraphFormat>().unwrap(), GraphFormat::Dot);
+        assert_eq!(
+            "mermaid".parse::<GraphFormat>().unwrap(),
+            GraphFormat::Mermaid
+        );
+        assert!("svg".parse::<GraphFormat>().is_err());
+    }
+}
@@ -0,0 +1,102 @@
+//! In-memory tail of recent log events
+//!
+//! [`crate::init_logging_with_verbosity`] installs [`RecentLogs`] next to the
+//! console formatter. It keeps the last [`CAPACITY`] `fastskill*` events at
+//! debug level and above, whatever the console verbosity, so a diagnostics
+//! bundle written after a failure can include what led up to it.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::{Mutex, OnceLock};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// Number of lines kept.
+pub const CAPACITY: usize = 200;
+
+fn buffer() -> &'static Mutex<VecDeque<String>> {
+    static BUFFER: OnceLock<Mutex<VecDeque<String>>> = OnceLock::new();
+    BUFFER.get_or_init(|| Mutex::new(VecDeque::with_capacity(CAPACITY)))
+}
+
+/// Append a line, dropping the oldest once full.
+pub fn push_line(line: String) {
+    if let Ok(mut lines) = buffer().lock() {
+        if lines.len() == CAPACITY {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+}
+
+/// Recorded lines, oldest first.
+pub fn recent_lines() -> Vec<String> {
+    buffer()
+        .lock()
+        .map(|lines| lines.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+#[derive(Default)]
+struct LineVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for LineVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// Layer that records formatted events into the shared tail.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RecentLogs;
+
+impl<S: Subscriber> Layer<S> for RecentLogs {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = LineVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        push_line(format!(
+            "{} {:>5} {}: {}{}",
+            chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ"),
+            metadata.level(),
+            metadata.target(),
+            visitor.message,
+            visitor.fields
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tail_keeps_most_recent_lines() {
+        for i in 0..CAPACITY + 5 {
+            push_line(format!("log-tail-test {}", i));
+        }
+        let lines = recent_lines();
+        assert_eq!(lines.len(), CAPACITY);
+        assert_eq!(
+            lines.last().map(String::as_str),
+            Some(format!("log-tail-test {}", CAPACITY + 4).as_str())
+        );
+        assert!(!lines.iter().any(|l| l == "log-tail-test 0"));
+    }
+}
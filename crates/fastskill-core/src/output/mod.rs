@@ -4,10 +4,17 @@
 //! by multiple CLI commands to ensure consistent output styling.
 
 use crate::core::origin::Origin;
+use crate::core::skill_env::{process_env, EnvVarSpec};
 use crate::core::SkillDefinition;
+use crate::search::explain::RankedBy;
 use crate::search::SearchResultItem;
+pub mod graph;
+pub mod log_tail;
+pub mod table;
+
 use serde_json;
 use std::fmt;
+use table::{truncate_to_width, Table};
 
 /// Short origin-type label (git/local/zip-url/repository) for display.
 fn origin_type_label(origin: &Origin) -> &'static str {
@@ -15,6 +22,7 @@ fn origin_type_label(origin: &Origin) -> &'static str {
         Origin::Git { .. } => "git",
         Origin::Local { .. } => "local",
         Origin::ZipUrl { .. } => "zip-url",
+        Origin::Pack { .. } => "pack",
         Origin::Repository { .. } => "repository",
     }
 }
@@ -25,6 +33,7 @@ fn origin_location_label(origin: &Origin) -> String {
         Origin::Git { url, .. } => url.clone(),
         Origin::Local { path, .. } => path.display().to_string(),
         Origin::ZipUrl { url } => url.clone(),
+        Origin::Pack { path, skill } => format!("{} ({skill})", path.display()),
         Origin::Repository { repo, skill, .. } => format!("{repo}/{skill}"),
     }
 }
@@ -44,6 +53,10 @@ pub struct ListRow {
     pub missing_from_folder: bool,
     pub missing_from_lock: bool,
     pub missing_from_manifest: bool,
+    /// Installed version differs from skills.lock or violates the manifest constraint
+    pub version_mismatch: bool,
+    /// Locked version or violated constraint when `version_mismatch` is set
+    pub expected_version: Option<String>,
 }
 
 /// Supported output formats
@@ -191,11 +204,7 @@ fn format_search_results_as_table(
     // Add rows
     for item in results {
         let desc = item.description.as_deref().unwrap_or("No description");
-        let desc_str = if desc.len() > 50 {
-            format!("{}...", &desc[..47])
-        } else {
-            desc.to_string()
-        };
+        let desc_str = truncate_to_width(desc, 50);
 
         let row = if let Some(sim) = item.similarity {
             format!(
@@ -248,10 +257,59 @@ fn format_search_results_as_table(
         )
     };
     output.push_str(&footer);
+    output.push_str(&format_explanations(results));
 
     Ok(output)
 }
 
+/// Score breakdowns of the results that carry one (`search --explain`)
+fn format_explanations(results: &[SearchResultItem]) -> String {
+    let mut output = String::new();
+    for item in results {
+        let Some(explanation) = &item.explanation else {
+            continue;
+        };
+        let ranked_by = match explanation.ranked_by {
+            RankedBy::Embedding => "embedding similarity",
+            RankedBy::Text => "text score",
+        };
+        let list = |words: &[String]| {
+            if words.is_empty() {
+                "-".to_string()
+            } else {
+                words.join(", ")
+            }
+        };
+        output.push_str(&format!("\n{} ({})\n", item.name, item.id));
+        output.push_str(&format!(
+            "  score:                {:.3} ({})\n",
+            explanation.score, ranked_by
+        ));
+        if let Some(similarity) = explanation.embedding_similarity {
+            output.push_str(&format!("  embedding similarity: {:.3}\n", similarity));
+        }
+        output.push_str(&format!(
+            "  text score:           {:.3}\n",
+            explanation.text_score
+        ));
+        output.push_str(&format!(
+            "  keyword overlap:      {:.0}% (matched: {}; missing: {})\n",
+            explanation.keyword_overlap * 100.0,
+            list(&explanation.matched_keywords),
+            list(&explanation.missing_keywords)
+        ));
+        output.push_str(&format!(
+            "  tag matches:          {}\n",
+            list(&explanation.tag_matches)
+        ));
+        output.push_str(&format!(
+            "  recency boost:        +{:.3}\n",
+            explanation.recency_boost
+        ));
+    }
+    output
+}
+
 /// Format search results as JSON
 fn format_search_results_as_json(results: &[SearchResultItem]) -> Result<String, String> {
     serde_json::to_string_pretty(results).map_err(|e| format!("Failed to serialize to JSON: {}", e))
@@ -284,6 +342,7 @@ fn format_search_results_as_grid(
         }
         output.push('\n');
     }
+    output.push_str(&format_explanations(results));
 
     Ok(output)
 }
@@ -322,6 +381,29 @@ fn format_search_results_as_xml(results: &[SearchResultItem]) -> Result<String,
             ));
         }
 
+        if let Some(explanation) = &item.explanation {
+            xml.push_str(&format!(
+                "    <explanation score=\"{:.3}\" text_score=\"{:.3}\" keyword_overlap=\"{:.3}\" recency_boost=\"{:.3}\">\n",
+                explanation.score,
+                explanation.text_score,
+                explanation.keyword_overlap,
+                explanation.recency_boost
+            ));
+            for keyword in &explanation.matched_keywords {
+                xml.push_str(&format!(
+                    "      <matched_keyword>{}</matched_keyword>\n",
+                    escape_xml(keyword)
+                ));
+            }
+            for tag in &explanation.tag_matches {
+                xml.push_str(&format!(
+                    "      <tag_match>{}</tag_match>\n",
+                    escape_xml(tag)
+                ));
+            }
+            xml.push_str("    </explanation>\n");
+        }
+
         xml.push_str("  </skill>\n");
     }
 
@@ -359,9 +441,8 @@ fn format_list_table(rows: &[ListRow], details: bool) -> Result<String, String>
         return Ok("No skills found.".to_string());
     }
 
-    let mut output = String::new();
-    if details {
-        let headers = [
+    let mut table = if details {
+        Table::new([
             "ID",
             "Name",
             "Description",
@@ -372,99 +453,38 @@ fn format_list_table(rows: &[ListRow], details: bool) -> Result<String, String>
             "Source Path",
             "Type",
             "Flags",
-        ];
-        let mut col_widths = vec![0; headers.len()];
-        for (i, h) in headers.iter().enumerate() {
-            col_widths[i] = h.len();
-        }
-        for row in rows {
-            col_widths[0] = col_widths[0].max(row.id.len());
-            col_widths[1] = col_widths[1].max(row.name.len());
-            col_widths[2] = col_widths[2].max(row.description.len());
-            col_widths[3] = col_widths[3].max(row.version.as_deref().unwrap_or("-").len());
-            col_widths[4] = col_widths[4].max(1);
-            col_widths[5] = col_widths[5].max(1);
-            col_widths[6] = col_widths[6].max(1);
-            col_widths[7] = col_widths[7].max(row.source_path.as_deref().unwrap_or("-").len());
-            col_widths[8] = col_widths[8].max(row.source_type.as_deref().unwrap_or("-").len());
-            let flags = build_list_flags_str(row);
-            col_widths[9] = col_widths[9].max(flags.len());
-        }
-
-        let header_row: Vec<String> = headers
-            .iter()
-            .enumerate()
-            .map(|(i, h)| format!("{:width$}", *h, width = col_widths[i]))
-            .collect();
-        output.push('\n');
-        output.push_str(&header_row.join("  "));
-        output.push('\n');
-        output.push_str(&"-".repeat(header_row.join("  ").len()));
-        output.push('\n');
-
-        for row in rows {
-            let version = row.version.as_deref().unwrap_or("-");
-            let in_manifest = if row.in_manifest { "Y" } else { "-" };
-            let in_lock = if row.in_lock { "Y" } else { "-" };
-            let installed = if row.installed { "Y" } else { "-" };
-            let source_path = row.source_path.as_deref().unwrap_or("-");
-            let source_type = row.source_type.as_deref().unwrap_or("-");
-            let flags = build_list_flags_str(row);
-            let line = [
-                format!("{:width$}", row.id, width = col_widths[0]),
-                format!("{:width$}", row.name, width = col_widths[1]),
-                format!("{:width$}", row.description, width = col_widths[2]),
-                format!("{:width$}", version, width = col_widths[3]),
-                format!("{:width$}", in_manifest, width = col_widths[4]),
-                format!("{:width$}", in_lock, width = col_widths[5]),
-                format!("{:width$}", installed, width = col_widths[6]),
-                format!("{:width$}", source_path, width = col_widths[7]),
-                format!("{:width$}", source_type, width = col_widths[8]),
-                format!("{:width$}", flags, width = col_widths[9]),
-            ];
-            output.push_str(&line.join("  "));
-            output.push('\n');
-        }
+        ])
     } else {
-        let headers = ["ID", "Name", "Description", "Flags"];
-        let mut col_widths = vec![0; headers.len()];
-        for (i, h) in headers.iter().enumerate() {
-            col_widths[i] = h.len();
-        }
-        for row in rows {
-            col_widths[0] = col_widths[0].max(row.id.len());
-            col_widths[1] = col_widths[1].max(row.name.len());
-            col_widths[2] = col_widths[2].max(row.description.len());
-            let flags = build_list_flags_str(row);
-            col_widths[3] = col_widths[3].max(flags.len());
-        }
-
-        let header_row: Vec<String> = headers
-            .iter()
-            .enumerate()
-            .map(|(i, h)| format!("{:width$}", *h, width = col_widths[i]))
-            .collect();
-        output.push('\n');
-        output.push_str(&header_row.join("  "));
-        output.push('\n');
-        output.push_str(&"-".repeat(header_row.join("  ").len()));
-        output.push('\n');
-
-        for row in rows {
-            let flags = build_list_flags_str(row);
-            let line = [
-                format!("{:width$}", row.id, width = col_widths[0]),
-                format!("{:width$}", row.name, width = col_widths[1]),
-                format!("{:width$}", row.description, width = col_widths[2]),
-                format!("{:width$}", flags, width = col_widths[3]),
-            ];
-            output.push_str(&line.join("  "));
-            output.push('\n');
+        Table::new(["ID", "Name", "Description", "Flags"])
+    };
+    for row in rows {
+        let flags = build_list_flags_str(row);
+        if details {
+            let mark = |present: bool| if present { "Y" } else { "-" };
+            table.push_row([
+                row.id.as_str(),
+                row.name.as_str(),
+                row.description.as_str(),
+                row.version.as_deref().unwrap_or("-"),
+                mark(row.in_manifest),
+                mark(row.in_lock),
+                mark(row.installed),
+                row.source_path.as_deref().unwrap_or("-"),
+                row.source_type.as_deref().unwrap_or("-"),
+                flags.as_str(),
+            ]);
+        } else {
+            table.push_row([
+                row.id.as_str(),
+                row.name.as_str(),
+                row.description.as_str(),
+                flags.as_str(),
+            ]);
         }
     }
+    table.sort_by_column(0);
 
-    output.push('\n');
-    Ok(output)
+    Ok(format!("\n{}\n", table.render()))
 }
 
 /// Format list results as grid (simple list format)
@@ -486,6 +506,10 @@ fn format_list_grid(rows: &[ListRow], _details: bool) -> Result<String, String>
                 row.source_type.as_deref().unwrap_or("unknown")
             ));
         }
+        let flags = build_list_flags_str(row);
+        if flags != "-" {
+            output.push_str(&format!(" - {}", flags));
+        }
         output.push('\n');
     }
 
@@ -568,6 +592,16 @@ fn format_show_table(skills: &[SkillDefinition]) -> Result<String, String> {
             "  Source: {}\n",
             origin_location_label(&skill.origin)
         ));
+        if !skill.env.is_empty() {
+            output.push_str("  Environment:\n");
+            for (name, spec) in skill.env.iter() {
+                output.push_str(&format!("    {} ({})", name, env_var_flags(name, spec)));
+                if let Some(description) = &spec.description {
+                    output.push_str(&format!(" - {}", description));
+                }
+                output.push('\n');
+            }
+        }
         output.push('\n');
     }
 
@@ -615,6 +649,16 @@ fn format_show_xml(skills: &[SkillDefinition]) -> Result<String, String> {
             "    <source_url>{}</source_url>\n",
             escape_xml(&origin_location_label(&skill.origin))
         ));
+        for (name, spec) in skill.env.iter() {
+            xml.push_str(&format!(
+                "    <env name=\"{}\" required=\"{}\" secret=\"{}\" set=\"{}\">{}</env>\n",
+                escape_xml(name),
+                spec.required,
+                spec.secret,
+                process_env(name).is_some(),
+                escape_xml(spec.description.as_deref().unwrap_or(""))
+            ));
+        }
         xml.push_str("  </skill>\n");
     }
 
@@ -622,16 +666,44 @@ fn format_show_xml(skills: &[SkillDefinition]) -> Result<String, String> {
     Ok(xml)
 }
 
+/// `required, secret, set` for a declared variable. Values are never shown,
+/// only whether this environment has one.
+fn env_var_flags(name: &str, spec: &EnvVarSpec) -> String {
+    let required = if spec.required {
+        "required"
+    } else {
+        "optional"
+    };
+    let mut flags = vec![required.to_string()];
+    if spec.secret {
+        flags.push("secret".to_string());
+    }
+    if let Some(default) = &spec.default {
+        flags.push(format!("default: {}", default));
+    }
+    flags.push(match process_env(name) {
+        Some(_) => "set".to_string(),
+        None => "not set".to_string(),
+    });
+    flags.join(", ")
+}
+
 fn build_list_flags_str(row: &ListRow) -> String {
     let mut parts = Vec::new();
     if row.missing_from_folder {
-        parts.push("missing from folder");
+        parts.push("missing from folder".to_string());
     }
     if row.missing_from_lock {
-        parts.push("missing from lock");
+        parts.push("missing from lock".to_string());
     }
     if row.missing_from_manifest {
-        parts.push("missing from manifest");
+        parts.push("missing from manifest".to_string());
+    }
+    if row.version_mismatch {
+        parts.push(format!(
+            "version mismatch (expected {})",
+            row.expected_version.as_deref().unwrap_or("?")
+        ));
     }
     if parts.is_empty() {
         "-".to_string()
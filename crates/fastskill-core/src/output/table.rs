@@ -0,0 +1,322 @@
+//! Plain-text table rendering shared by CLI commands
+//!
+//! Columns are sized by terminal display width rather than byte length, so
+//! CJK, emoji and accented names line up and truncation never splits a
+//! character. Cells are separated by two spaces under a header row and a
+//! dashed rule; the header is bolded only when colors are enabled.
+
+use std::cmp::Ordering;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicU8, Ordering as AtomicOrdering};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+const COLOR_AUTO: u8 = 0;
+const COLOR_ALWAYS: u8 = 1;
+const COLOR_NEVER: u8 = 2;
+
+static COLOR_CHOICE: AtomicU8 = AtomicU8::new(COLOR_AUTO);
+
+/// Force colors on or off (e.g. from `--no-color`); `None` restores detection.
+pub fn set_color_enabled(enabled: Option<bool>) {
+    let choice = match enabled {
+        None => COLOR_AUTO,
+        Some(true) => COLOR_ALWAYS,
+        Some(false) => COLOR_NEVER,
+    };
+    COLOR_CHOICE.store(choice, AtomicOrdering::Relaxed);
+}
+
+/// Parse a `--color` value: `auto` (detect), `always` or `never`.
+pub fn parse_color_choice(value: &str) -> Result<Option<bool>, String> {
+    match value {
+        "auto" => Ok(None),
+        "always" => Ok(Some(true)),
+        "never" => Ok(Some(false)),
+        other => Err(format!(
+            "Invalid color choice '{}'. Use auto, always or never",
+            other
+        )),
+    }
+}
+
+fn env_flag_set(name: &str) -> bool {
+    std::env::var_os(name).is_some_and(|v| !v.is_empty() && v != "0" && v != "false")
+}
+
+/// Whether output may contain ANSI styling: an explicit choice wins, otherwise
+/// colors are used only on a terminal, outside CI, and when `NO_COLOR` is
+/// unset or empty.
+pub fn color_enabled() -> bool {
+    match COLOR_CHOICE.load(AtomicOrdering::Relaxed) {
+        COLOR_ALWAYS => true,
+        COLOR_NEVER => false,
+        _ => {
+            std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+                && !env_flag_set("CI")
+                && std::io::stdout().is_terminal()
+        }
+    }
+}
+
+/// Number of terminal columns `s` occupies.
+pub fn display_width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
+
+/// Shorten `s` to at most `max` columns, ending in `...` when cut.
+pub fn truncate_to_width(s: &str, max: usize) -> String {
+    if display_width(s) <= max {
+        return s.to_string();
+    }
+    let budget = max.saturating_sub(3);
+    let mut out = String::new();
+    let mut width = 0;
+    for c in s.chars() {
+        let w = UnicodeWidthChar::width(c).unwrap_or(0);
+        if width + w > budget {
+            break;
+        }
+        width += w;
+        out.push(c);
+    }
+    out.push_str(&".".repeat(max.min(3)));
+    out
+}
+
+/// Case-insensitive ordering with a byte-wise tie-break, so results do not
+/// depend on the user's locale and equal-looking keys still sort the same way
+/// on every run.
+pub fn compare_text(a: &str, b: &str) -> Ordering {
+    a.to_lowercase()
+        .cmp(&b.to_lowercase())
+        .then_with(|| a.cmp(b))
+}
+
+fn pad(s: &str, width: usize, align: Align) -> String {
+    let fill = " ".repeat(width.saturating_sub(display_width(s)));
+    match align {
+        Align::Left => format!("{}{}", s, fill),
+        Align::Right => format!("{}{}", fill, s),
+    }
+}
+
+/// Keep a cell on one line: newlines and other control characters would
+/// break the column layout.
+fn sanitize(cell: &str) -> String {
+    cell.chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect()
+}
+
+/// Horizontal alignment of a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone)]
+struct Column {
+    header: String,
+    align: Align,
+    max_width: Option<usize>,
+}
+
+/// A table built row by row and rendered to a string.
+#[derive(Debug, Clone)]
+pub struct Table {
+    columns: Vec<Column>,
+    rows: Vec<Vec<String>>,
+    color: bool,
+}
+
+impl Table {
+    pub fn new<I, S>(headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            columns: headers
+                .into_iter()
+                .map(|h| Column {
+                    header: h.into(),
+                    align: Align::Left,
+                    max_width: None,
+                })
+                .collect(),
+            rows: Vec::new(),
+            color: color_enabled(),
+        }
+    }
+
+    pub fn align(mut self, column: usize, align: Align) -> Self {
+        if let Some(c) = self.columns.get_mut(column) {
+            c.align = align;
+        }
+        self
+    }
+
+    /// Truncate cells in `column` to `width` display columns.
+    pub fn max_width(mut self, column: usize, width: usize) -> Self {
+        if let Some(c) = self.columns.get_mut(column) {
+            c.max_width = Some(width);
+        }
+        self
+    }
+
+    /// Override color detection for this table.
+    pub fn color(mut self, enabled: bool) -> Self {
+        self.color = enabled;
+        self
+    }
+
+    /// Append a row; missing cells render empty and extra cells are dropped.
+    pub fn push_row<I, S>(&mut self, cells: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut row: Vec<String> = cells
+            .into_iter()
+            .take(self.columns.len())
+            .map(|c| sanitize(c.as_ref()))
+            .collect();
+        row.resize(self.columns.len(), String::new());
+        self.rows.push(row);
+    }
+
+    /// Stable sort of the rows by `column` (see [`compare_text`]).
+    pub fn sort_by_column(&mut self, column: usize) {
+        if column < self.columns.len() {
+            self.rows
+                .sort_by(|a, b| compare_text(&a[column], &b[column]));
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn cell(&self, column: usize, value: &str) -> String {
+        match self.columns[column].max_width {
+            Some(max) => truncate_to_width(value, max),
+            None => value.to_string(),
+        }
+    }
+
+    /// Header, dashed rule and rows, each line ending in `\n`.
+    pub fn render(&self) -> String {
+        let rows: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .map(|(i, v)| self.cell(i, v))
+                    .collect()
+            })
+            .collect();
+        let widths: Vec<usize> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                rows.iter()
+                    .map(|r| display_width(&r[i]))
+                    .fold(display_width(&c.header), usize::max)
+            })
+            .collect();
+
+        let header = self
+            .columns
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| pad(&c.header, *w, c.align))
+            .collect::<Vec<_>>()
+            .join("  ");
+        let mut out = String::new();
+        if self.color {
+            out.push_str(&format!("\x1b[1m{}\x1b[0m\n", header));
+        } else {
+            out.push_str(&header);
+            out.push('\n');
+        }
+        out.push_str(&"-".repeat(display_width(&header)));
+        out.push('\n');
+        for row in &rows {
+            let line = row
+                .iter()
+                .zip(self.columns.iter().zip(&widths))
+                .map(|(v, (c, w))| pad(v, *w, c.align))
+                .collect::<Vec<_>>()
+                .join("  ");
+            out.push_str(&line);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_aligns_by_display_width() {
+        let mut table = Table::new(["ID", "Count"])
+            .align(1, Align::Right)
+            .color(false);
+        table.push_row(["日本語", "7"]);
+        table.push_row(["web", "12"]);
+        let out = table.render();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], "ID      Count");
+        assert_eq!(lines[1], "-------------");
+        assert_eq!(lines[2], "日本語      7");
+        assert_eq!(lines[3], "web        12");
+    }
+
+    #[test]
+    fn test_truncate_never_splits_characters() {
+        assert_eq!(truncate_to_width("short", 10), "short");
+        assert_eq!(truncate_to_width("abcdefghij", 8), "abcde...");
+        // Each CJK character is two columns wide.
+        assert_eq!(truncate_to_width("数据处理技能", 8), "数据...");
+        assert_eq!(truncate_to_width("café au lait", 7), "café...");
+
+        let mut table = Table::new(["Description"]).max_width(0, 8).color(false);
+        table.push_row(["line one\nline two"]);
+        assert!(table.render().contains("line ..."));
+    }
+
+    #[test]
+    fn test_parse_color_choice() {
+        assert_eq!(parse_color_choice("auto").unwrap(), None);
+        assert_eq!(parse_color_choice("always").unwrap(), Some(true));
+        assert_eq!(parse_color_choice("never").unwrap(), Some(false));
+        assert!(parse_color_choice("sometimes").is_err());
+    }
+
+    #[test]
+    fn test_sort_is_case_insensitive_and_stable() {
+        let mut table = Table::new(["ID", "N"]).color(false);
+        for (id, n) in [("beta", "1"), ("Alpha", "2"), ("alpha", "3"), ("beta", "4")] {
+            table.push_row([id, n]);
+        }
+        table.sort_by_column(0);
+        let order: Vec<String> = table
+            .render()
+            .lines()
+            .skip(2)
+            .map(|l| l.split_whitespace().collect::<Vec<_>>().join(" "))
+            .collect();
+        assert_eq!(order, ["Alpha 2", "alpha 3", "beta 1", "beta 4"]);
+    }
+}
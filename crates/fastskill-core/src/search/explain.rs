@@ -0,0 +1,191 @@
+//! Score breakdowns for `search --explain`
+//!
+//! Local results are ordered by embedding similarity (semantic search) or by
+//! the text score (text search). A breakdown shows that score next to the
+//! signals behind a match: which query keywords the skill's name, description
+//! and tags pick up, which tags the query names, and how recently the skill
+//! changed. Tag matches and the recency boost are reported only; they do not
+//! reorder results.
+
+use crate::core::metadata::{text_score, SkillFrontmatter};
+use crate::core::routing::frontmatter_tags;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Largest recency boost, for a skill updated just now
+pub const MAX_RECENCY_BOOST: f32 = 0.1;
+
+/// Days after which the recency boost has halved
+pub const RECENCY_HALF_LIFE_DAYS: f32 = 30.0;
+
+/// Score that ordered the results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RankedBy {
+    Embedding,
+    Text,
+}
+
+/// Why a skill matched a query, and how strongly
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScoreBreakdown {
+    pub ranked_by: RankedBy,
+    /// The score the results were ordered by
+    pub score: f32,
+    /// Cosine similarity of the query and skill embeddings (semantic search)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embedding_similarity: Option<f32>,
+    /// Name and description score used by text search
+    pub text_score: f32,
+    /// Share of the query's keywords found in the id, name, description or tags
+    pub keyword_overlap: f32,
+    pub matched_keywords: Vec<String>,
+    pub missing_keywords: Vec<String>,
+    /// The skill's tags that appear in the query
+    pub tag_matches: Vec<String>,
+    /// [`MAX_RECENCY_BOOST`] for a skill updated now, halving every
+    /// [`RECENCY_HALF_LIFE_DAYS`]
+    pub recency_boost: f32,
+}
+
+/// What a breakdown looks at
+#[derive(Debug, Clone)]
+pub struct ExplainedSkill<'a> {
+    pub id: &'a str,
+    pub name: &'a str,
+    pub description: &'a str,
+    pub tags: &'a [String],
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Tags of a skill as stored in the vector index (frontmatter as JSON)
+pub fn tags_from_json(frontmatter: &serde_json::Value) -> Vec<String> {
+    serde_json::from_value::<SkillFrontmatter>(frontmatter.clone())
+        .map(|frontmatter| frontmatter_tags(&frontmatter))
+        .unwrap_or_default()
+}
+
+/// Words of the query worth matching: lowercased, without surrounding
+/// punctuation, longer than two characters (as text search counts them)
+pub fn keywords(query: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    for word in query.split_whitespace() {
+        let word = word
+            .trim_matches(|c: char| !c.is_alphanumeric())
+            .to_lowercase();
+        if word.chars().count() > 2 && !words.contains(&word) {
+            words.push(word);
+        }
+    }
+    words
+}
+
+/// Recency boost for a skill last updated at `updated_at`
+pub fn recency_boost(updated_at: DateTime<Utc>, now: DateTime<Utc>) -> f32 {
+    let age_days = (now - updated_at).num_seconds().max(0) as f32 / 86_400.0;
+    MAX_RECENCY_BOOST * 0.5_f32.powf(age_days / RECENCY_HALF_LIFE_DAYS)
+}
+
+/// Break down how `skill` matches `query`. `embedding_similarity` is set for
+/// semantic search results, and then is the ranking score.
+pub fn explain(
+    query: &str,
+    skill: &ExplainedSkill<'_>,
+    embedding_similarity: Option<f32>,
+    now: DateTime<Utc>,
+) -> ScoreBreakdown {
+    let haystack = format!(
+        "{} {} {} {}",
+        skill.id,
+        skill.name,
+        skill.description,
+        skill.tags.join(" ")
+    )
+    .to_lowercase();
+    let (matched_keywords, missing_keywords): (Vec<String>, Vec<String>) = keywords(query)
+        .into_iter()
+        .partition(|word| haystack.contains(word.as_str()));
+    let total = matched_keywords.len() + missing_keywords.len();
+    let keyword_overlap = if total == 0 {
+        0.0
+    } else {
+        matched_keywords.len() as f32 / total as f32
+    };
+
+    let query_keywords = keywords(query);
+    let query_lower = query.to_lowercase();
+    let tag_matches = skill
+        .tags
+        .iter()
+        .filter(|tag| {
+            query_keywords.contains(*tag) || (tag.contains(' ') && query_lower.contains(*tag))
+        })
+        .cloned()
+        .collect();
+
+    let text_score = text_score(skill.name, skill.description, query);
+    let (ranked_by, score) = match embedding_similarity {
+        Some(similarity) => (RankedBy::Embedding, similarity),
+        None => (RankedBy::Text, text_score),
+    };
+    ScoreBreakdown {
+        ranked_by,
+        score,
+        embedding_similarity,
+        text_score,
+        keyword_overlap,
+        matched_keywords,
+        missing_keywords,
+        tag_matches,
+        recency_boost: recency_boost(skill.updated_at, now),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_explain_breaks_down_text_match() {
+        let now = Utc::now();
+        let tags = vec!["pdf".to_string(), "documents".to_string()];
+        let skill = ExplainedSkill {
+            id: "pdf-tools",
+            name: "PDF Tools",
+            description: "Extract text from PDF files",
+            tags: &tags,
+            updated_at: now - Duration::days(30),
+        };
+
+        let breakdown = explain("extract tables from pdf", &skill, None, now);
+        assert_eq!(breakdown.ranked_by, RankedBy::Text);
+        assert_eq!(breakdown.score, breakdown.text_score);
+        assert_eq!(breakdown.matched_keywords, ["extract", "from", "pdf"]);
+        assert_eq!(breakdown.missing_keywords, ["tables"]);
+        assert!((breakdown.keyword_overlap - 0.75).abs() < 1e-6);
+        assert_eq!(breakdown.tag_matches, ["pdf"]);
+        assert!((breakdown.recency_boost - MAX_RECENCY_BOOST / 2.0).abs() < 1e-3);
+
+        let semantic = explain("extract tables from pdf", &skill, Some(0.82), now);
+        assert_eq!(semantic.ranked_by, RankedBy::Embedding);
+        assert_eq!(semantic.score, 0.82);
+    }
+
+    #[test]
+    fn test_keywords_and_tags_from_json() {
+        assert_eq!(
+            keywords("Find the PDF, now!"),
+            ["find", "the", "pdf", "now"]
+        );
+        assert!(keywords("a an").is_empty());
+
+        let json = serde_json::json!({
+            "name": "s",
+            "description": "d",
+            "tags": ["PDF", "docs"],
+        });
+        assert_eq!(tags_from_json(&json), ["pdf", "docs"]);
+    }
+}
@@ -3,7 +3,9 @@
 //! This module handles searching through skills that are installed locally,
 //! using either embedding-based semantic search or fallback text search.
 
+use super::explain::{explain, tags_from_json, ExplainedSkill};
 use super::{SearchError, SearchQuery, SearchResultItem};
+use crate::core::routing::frontmatter_tags;
 use crate::{EmbeddingService, FastSkillService};
 
 /// Execute local search query
@@ -14,18 +16,19 @@ pub async fn execute_local_search(
     let results = match query.embedding {
         Some(false) => {
             // --embedding false: use text search only
-            perform_text_search(service, &query.query, query.limit).await?
+            perform_text_search(service, &query.query, query.limit, query.explain).await?
         }
         Some(true) => {
             // --embedding true: use embedding search only, no fallback
-            perform_embedding_search(service, &query.query, query.limit).await?
+            perform_embedding_search(service, &query.query, query.limit, query.explain).await?
         }
         None => {
             // No flag: try embedding, fall back to text on config error
-            match perform_embedding_search(service, &query.query, query.limit).await {
+            match perform_embedding_search(service, &query.query, query.limit, query.explain).await
+            {
                 Ok(r) => r,
                 Err(SearchError::Config(_)) => {
-                    perform_text_search(service, &query.query, query.limit).await?
+                    perform_text_search(service, &query.query, query.limit, query.explain).await?
                 }
                 Err(e) => return Err(e),
             }
@@ -40,7 +43,9 @@ async fn perform_text_search(
     service: &FastSkillService,
     query: &str,
     limit: usize,
+    with_explanation: bool,
 ) -> Result<Vec<SearchResultItem>, SearchError> {
+    let now = chrono::Utc::now();
     let meta_list = service
         .metadata_service()
         .search_skills(query)
@@ -64,6 +69,25 @@ async fn perform_text_search(
             .map(std::path::Path::to_path_buf)
             .unwrap_or_else(|| skill_def.skill_file.clone());
 
+        let explanation = if with_explanation {
+            let tags = service
+                .metadata_service()
+                .get_skill_frontmatter(meta.id.as_str())
+                .await
+                .map(|frontmatter| frontmatter_tags(&frontmatter))
+                .unwrap_or_default();
+            let skill = ExplainedSkill {
+                id: meta.id.as_str(),
+                name: &meta.name,
+                description: &meta.description,
+                tags: &tags,
+                updated_at: meta.last_updated,
+            };
+            Some(explain(query, &skill, None, now))
+        } else {
+            None
+        };
+
         let result_item = SearchResultItem {
             id: meta.id.as_str().to_string(),
             name: if meta.name.is_empty() {
@@ -80,6 +104,7 @@ async fn perform_text_search(
             similarity: Some(1.0), // Text search has no similarity score
             path: Some(skill_path.to_string_lossy().to_string()),
             repository: None,
+            explanation,
         };
 
         results.push(result_item);
@@ -92,6 +117,7 @@ async fn perform_embedding_search(
     service: &FastSkillService,
     query: &str,
     limit: usize,
+    with_explanation: bool,
 ) -> Result<Vec<SearchResultItem>, SearchError> {
     let embedding_config = service
         .config()
@@ -125,6 +151,7 @@ async fn perform_embedding_search(
         .map_err(|e| SearchError::Validation(format!("Vector search failed: {}", e)))?;
 
     // Convert to SearchResultItem
+    let now = chrono::Utc::now();
     let results = matches
         .into_iter()
         .map(|skill_match| {
@@ -143,6 +170,18 @@ async fn perform_embedding_search(
                 .and_then(|v| v.as_str())
                 .map(|s| s.to_string());
 
+            let explanation = with_explanation.then(|| {
+                let tags = tags_from_json(&skill_match.skill.frontmatter_json);
+                let skill = ExplainedSkill {
+                    id: &skill_match.skill.id,
+                    name: &name,
+                    description: description.as_deref().unwrap_or_default(),
+                    tags: &tags,
+                    updated_at: skill_match.skill.updated_at,
+                };
+                explain(query, &skill, Some(skill_match.similarity), now)
+            });
+
             SearchResultItem {
                 id: skill_match.skill.id,
                 name,
@@ -151,6 +190,7 @@ async fn perform_embedding_search(
                 similarity: Some(skill_match.similarity),
                 path: Some(skill_match.skill.skill_path.to_string_lossy().to_string()),
                 repository: None,
+                explanation,
             }
         })
         .collect();
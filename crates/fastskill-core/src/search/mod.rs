@@ -7,6 +7,7 @@
 //! The module is designed to be CLI-agnostic and can be used by other entry points
 //! like HTTP API endpoints.
 
+pub mod explain;
 pub mod local;
 pub mod remote;
 
@@ -35,6 +36,8 @@ pub struct SearchQuery {
     pub limit: usize,
     /// Whether to use embedding search (for local search only)
     pub embedding: Option<bool>,
+    /// Attach a score breakdown to each result (for local search only)
+    pub explain: bool,
 }
 
 /// Unified search result item that works across all search scopes
@@ -54,6 +57,9 @@ pub struct SearchResultItem {
     pub path: Option<String>,
     /// Optional repository name (for remote results)
     pub repository: Option<String>,
+    /// Why the skill matched, when the query asked for explanations
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<explain::ScoreBreakdown>,
 }
 
 impl fmt::Display for SearchResultItem {
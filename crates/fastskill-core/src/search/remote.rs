@@ -49,6 +49,7 @@ pub async fn execute_remote_search(
                                 similarity: None, // Remote search doesn't provide similarity scores
                                 path: None,
                                 repository: Some(repo.name.clone()),
+                                explanation: None,
                             };
                             all_results.push(result_item);
                         }
@@ -188,6 +189,7 @@ index_url = "not-a-valid-url"
             scope: super::super::SearchScope::Remote,
             limit: 10,
             embedding: None,
+            explain: false,
         }
     }
 
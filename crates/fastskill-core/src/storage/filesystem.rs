@@ -1,15 +1,16 @@
 //! Filesystem storage backend
 
+use crate::core::content::SkillContent;
 use crate::core::metadata::SkillMetadata;
 use crate::core::service::ServiceError;
+use crate::storage::metadata_schema::{StoredSkillMetadata, SKILL_METADATA_SCHEMA_VERSION};
 use async_trait::async_trait;
-use serde_json;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::fs;
 use tokio::sync::RwLock;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 /// Filesystem storage backend for skills
 pub struct FilesystemStorage {
@@ -63,6 +64,7 @@ impl FilesystemStorage {
             if let Some(metadata) = cache.get(skill_id) {
                 let mut hits = self.cache_hits.write().await;
                 *hits += 1;
+                crate::core::metrics::global().record_cache_lookup(true);
                 return Ok(Some(metadata.clone()));
             }
         }
@@ -70,6 +72,7 @@ impl FilesystemStorage {
         // Cache miss - load from disk
         let mut misses = self.cache_misses.write().await;
         *misses += 1;
+        crate::core::metrics::global().record_cache_lookup(false);
 
         let metadata_path = self.get_skill_metadata_path(skill_id);
 
@@ -82,8 +85,9 @@ impl FilesystemStorage {
             .await
             .map_err(|e| ServiceError::Custom(format!("Failed to read metadata file: {}", e)))?;
 
-        let metadata: SkillMetadata = serde_json::from_str(&content)
+        let (stored, _) = StoredSkillMetadata::parse(&content)
             .map_err(|e| ServiceError::Custom(format!("Failed to parse metadata JSON: {}", e)))?;
+        let metadata = stored.metadata;
 
         // Cache the metadata
         {
@@ -109,13 +113,23 @@ impl FilesystemStorage {
             })?;
         }
 
+        // Keep fields and the schema version of an existing file a newer
+        // release may have written
+        let stored = match fs::read_to_string(&metadata_path).await {
+            Ok(existing) => match StoredSkillMetadata::parse(&existing) {
+                Ok((stored, _)) => stored.with_metadata(metadata.clone()),
+                Err(_) => StoredSkillMetadata::new(metadata.clone()),
+            },
+            Err(_) => StoredSkillMetadata::new(metadata.clone()),
+        };
+
         // Serialize metadata to JSON
-        let content = serde_json::to_string_pretty(metadata)
+        let content = stored
+            .to_json()
             .map_err(|e| ServiceError::Custom(format!("Failed to serialize metadata: {}", e)))?;
 
         // Write to disk
-        fs::write(&metadata_path, &content)
-            .await
+        crate::utils::atomic_write(&metadata_path, content.as_bytes())
             .map_err(|e| ServiceError::Custom(format!("Failed to write metadata file: {}", e)))?;
 
         // Update cache
@@ -129,14 +143,17 @@ impl FilesystemStorage {
     }
 
     /// Load skill content (SKILL.md)
-    pub async fn load_skill_content(&self, skill_id: &str) -> Result<Option<String>, ServiceError> {
+    pub async fn load_skill_content(
+        &self,
+        skill_id: &str,
+    ) -> Result<Option<SkillContent>, ServiceError> {
         let content_path = self.get_skill_content_path(skill_id);
 
         if !content_path.exists() {
             return Ok(None);
         }
 
-        let content = fs::read_to_string(&content_path)
+        let content = SkillContent::read(&content_path)
             .await
             .map_err(|e| ServiceError::Custom(format!("Failed to read skill content: {}", e)))?;
 
@@ -147,7 +164,7 @@ impl FilesystemStorage {
     pub async fn save_skill_content(
         &self,
         skill_id: &str,
-        content: &str,
+        content: impl AsRef<[u8]>,
     ) -> Result<(), ServiceError> {
         let content_path = self.get_skill_content_path(skill_id);
 
@@ -159,7 +176,7 @@ impl FilesystemStorage {
         }
 
         // Write content to disk
-        fs::write(&content_path, content)
+        fs::write(&content_path, content.as_ref())
             .await
             .map_err(|e| ServiceError::Custom(format!("Failed to write skill content: {}", e)))?;
 
@@ -167,35 +184,62 @@ impl FilesystemStorage {
         Ok(())
     }
 
-    /// List all skill IDs in storage
+    /// List all skill IDs in storage (scoped ones included)
     pub async fn list_skill_ids(&self) -> Result<Vec<String>, ServiceError> {
-        let mut skill_ids = Vec::new();
-
-        // Read base directory
-        let mut read_dir = fs::read_dir(&self.base_path).await.map_err(|e| {
+        let skill_dirs = crate::core::scope::skill_dirs(&self.base_path).map_err(|e| {
             ServiceError::Custom(format!("Failed to read storage directory: {}", e))
         })?;
+        Ok(skill_dirs.into_iter().map(|(id, _)| id).collect())
+    }
 
-        // Collect all subdirectories (skill directories)
-        while let Some(entry) = read_dir
-            .next_entry()
-            .await
-            .map_err(|e| ServiceError::Custom(format!("Failed to read directory entry: {}", e)))?
-        {
-            let path = entry.path();
-
-            if path.is_dir() {
-                if let Some(skill_id) = path.file_name() {
-                    // Check if this directory has a valid skill structure
-                    let skill_file = path.join("SKILL.md");
-                    if skill_file.exists() {
-                        skill_ids.push(skill_id.to_string_lossy().to_string());
-                    }
+    /// Rewrite every `metadata.json` older than the current schema version.
+    /// Files from a newer release are left as they are, and unreadable files
+    /// are logged and skipped so one bad skill does not block startup.
+    /// Returns the number of files upgraded.
+    pub async fn migrate_metadata(&self) -> Result<usize, ServiceError> {
+        let mut migrated = 0;
+        for skill_id in self.list_skill_ids().await? {
+            let metadata_path = self.get_skill_metadata_path(&skill_id);
+            let content = match fs::read_to_string(&metadata_path).await {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => {
+                    warn!("Skipping metadata migration for {}: {}", skill_id, e);
+                    continue;
+                }
+            };
+            let (stored, found) = match StoredSkillMetadata::parse(&content) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    warn!("Skipping metadata migration for {}: {}", skill_id, e);
+                    continue;
                 }
+            };
+            if found >= SKILL_METADATA_SCHEMA_VERSION {
+                continue;
             }
+
+            let content = stored.to_json().map_err(|e| {
+                ServiceError::Custom(format!("Failed to serialize metadata: {}", e))
+            })?;
+            crate::utils::atomic_write(&metadata_path, content.as_bytes()).map_err(|e| {
+                ServiceError::Custom(format!("Failed to write metadata file: {}", e))
+            })?;
+            self.metadata_cache.write().await.remove(&skill_id);
+            debug!(
+                "Migrated metadata for {} from schema {} to {}",
+                skill_id, found, SKILL_METADATA_SCHEMA_VERSION
+            );
+            migrated += 1;
         }
 
-        Ok(skill_ids)
+        if migrated > 0 {
+            info!(
+                "Migrated {} skill metadata file(s) to schema {}",
+                migrated, SKILL_METADATA_SCHEMA_VERSION
+            );
+        }
+        Ok(migrated)
     }
 
     /// Delete a skill from storage
@@ -289,4 +333,46 @@ impl crate::storage::StorageBackend for FilesystemStorage {
         self.clear_cache().await;
         Ok(())
     }
+
+    async fn migrate_metadata(&self) -> Result<usize, ServiceError> {
+        self.migrate_metadata().await
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_migrate_metadata_stamps_legacy_files_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let skill_dir = temp_dir.path().join("pdf-tools");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        std::fs::write(skill_dir.join("SKILL.md"), "# PDF Tools\n").unwrap();
+        std::fs::write(
+            skill_dir.join("metadata.json"),
+            r#"{"id":"pdf-tools","name":"PDF Tools","description":"Extract text",
+                "version":"1.0.0","author":null,"last_updated":"2025-01-01T00:00:00Z",
+                "custom":"kept"}"#,
+        )
+        .unwrap();
+
+        let storage = FilesystemStorage::new(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        assert_eq!(storage.migrate_metadata().await.unwrap(), 1);
+        assert_eq!(storage.migrate_metadata().await.unwrap(), 0);
+
+        let written: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(skill_dir.join("metadata.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(written["schema_version"], SKILL_METADATA_SCHEMA_VERSION);
+        assert_eq!(written["custom"], "kept");
+
+        let metadata = storage.load_skill_metadata("pdf-tools").await.unwrap();
+        assert_eq!(metadata.unwrap().version, "1.0.0");
+    }
 }
@@ -71,6 +71,11 @@ impl GitVersion {
     fn is_supported(&self) -> bool {
         self.major >= 2
     }
+
+    /// `git sparse-checkout` (cone mode) arrived in git 2.25
+    fn supports_sparse_checkout(&self) -> bool {
+        (self.major, self.minor) >= (2, 25)
+    }
 }
 
 impl std::fmt::Display for GitVersion {
@@ -135,6 +140,11 @@ pub(crate) async fn check_git_version() -> Result<(), ServiceError> {
     Ok(())
 }
 
+/// The git version [`check_git_version`] found, once it has run
+fn cached_git_version() -> Option<&'static GitVersion> {
+    GIT_VERSION.get()?.as_ref().ok()
+}
+
 /// Parse git version from output string (e.g., "git version 2.34.1")
 pub(crate) fn parse_git_version(version_str: &str) -> Result<GitVersion, ServiceError> {
     // Expected format: "git version X.Y.Z" or "git version X.Y.Z (extra info)"
@@ -254,7 +264,8 @@ pub(crate) async fn execute_git_command(
     cwd: Option<&Path>,
 ) -> Result<CommandOutput, ServiceError> {
     let mut cmd = Command::new("git");
-    cmd.args(args);
+    // Don't leave a clone running after a timeout or cancelled install
+    cmd.args(args).kill_on_drop(true);
     if let Some(cwd) = cwd {
         cmd.current_dir(cwd);
     }
@@ -301,6 +312,14 @@ pub(crate) fn is_network_error(stderr: &str) -> bool {
         || lower_stderr.contains("name resolution")
 }
 
+/// Whether a clone failed because the server cannot serve shallow clones
+/// (e.g. the dumb HTTP transport)
+pub(crate) fn is_shallow_unsupported(stderr: &str) -> bool {
+    let lower_stderr = stderr.to_lowercase();
+    lower_stderr.contains("does not support shallow")
+        || lower_stderr.contains("dumb http transport does not support")
+}
+
 /// Execute git command with retry logic for network errors
 pub(crate) async fn execute_git_command_with_retry(
     args: &[&str],
@@ -435,21 +454,10 @@ pub async fn clone_repository(
     // Build clone command arguments (protocol allowlist + `--` end-of-options, SEC-11).
     let clone_args = build_clone_args(url, dest, branch, tag);
 
-    // Execute clone with retry (5 minute timeout, max 3 attempts).
-    // `execute_git_command_with_retry` already returns Err on any non-zero exit,
-    // so map that Err into the structured CloneFailed with URL context (BUG-12).
-    let clone_timeout = Duration::from_secs(300); // 5 minutes
-    match execute_git_command_with_retry(&clone_args, clone_timeout, None, 3).await {
-        Ok(_) => {}
-        Err(e) => {
-            // Clean up on failure
-            drop(temp_dir);
-            return Err(GitError::CloneFailed {
-                url: safe_url,
-                stderr: e.to_string(),
-            }
-            .into());
-        }
+    // Clean up on failure
+    if let Err(e) = run_clone(&clone_args, &safe_url).await {
+        drop(temp_dir);
+        return Err(e);
     }
 
     // Checkout branch or tag if specified (already handled by --branch flag, but verify)
@@ -465,6 +473,163 @@ pub async fn clone_repository(
     Ok(temp_dir)
 }
 
+/// Run `git clone` with retry (5 minute timeout, max 3 attempts). A server
+/// that cannot serve shallow clones gets the same clone again without
+/// `--depth=1`, i.e. with full history.
+async fn run_clone(clone_args: &[&str], safe_url: &str) -> Result<(), ServiceError> {
+    let clone_timeout = Duration::from_secs(300); // 5 minutes
+    let result = match execute_git_command_with_retry(clone_args, clone_timeout, None, 3).await {
+        Err(e) if is_shallow_unsupported(&e.to_string()) => {
+            warn!(
+                "{} does not support shallow clones, cloning full history",
+                safe_url
+            );
+            let full_args: Vec<&str> = clone_args
+                .iter()
+                .copied()
+                .filter(|arg| *arg != "--depth=1")
+                .collect();
+            execute_git_command_with_retry(&full_args, clone_timeout, None, 3).await
+        }
+        result => result,
+    };
+    // `execute_git_command_with_retry` already returns Err on any non-zero exit,
+    // so map that Err into the structured CloneFailed with URL context (BUG-12).
+    result.map(|_| ()).map_err(|e| {
+        GitError::CloneFailed {
+            url: safe_url.to_string(),
+            stderr: e.to_string(),
+        }
+        .into()
+    })
+}
+
+/// Clone only the part of a repository a skill in `subdir` needs.
+///
+/// Skills often live in a subdirectory of a large monorepo. This makes a
+/// shallow clone without file contents and checks out `subdir` with a
+/// cone-mode sparse checkout, so only the skill's files (and the files at the
+/// repository root) are downloaded. Falls back to [`clone_repository`] when
+/// the partial clone fails, and to a checkout of the whole tree when git is
+/// older than 2.25 or the sparse checkout fails.
+///
+/// # Errors
+///
+/// Returns `ServiceError::InvalidOperation` if `subdir` is absolute or has
+/// `..` components, and the errors of [`clone_repository`] otherwise.
+pub async fn clone_repository_subdir(
+    url: &str,
+    branch: Option<&str>,
+    tag: Option<&str>,
+    subdir: &Path,
+) -> Result<TempDir, ServiceError> {
+    let pattern = sparse_pattern(subdir)?;
+    let temp_dir = match partial_clone(url, branch, tag).await {
+        Ok((temp_dir, _)) => temp_dir,
+        Err(e) => {
+            warn!(
+                "Partial clone of {} failed, cloning all files: {}",
+                redact_url_credentials(url),
+                e
+            );
+            return clone_repository(url, branch, tag, None).await;
+        }
+    };
+    checkout_sparse(temp_dir.path(), &pattern).await?;
+    Ok(temp_dir)
+}
+
+/// `subdir` as a `/`-separated cone pattern. Only plain relative components
+/// are allowed, so the checkout cannot reach outside the repository.
+pub(crate) fn sparse_pattern(subdir: &Path) -> Result<String, ServiceError> {
+    use std::path::Component;
+
+    let mut parts = Vec::new();
+    for component in subdir.components() {
+        let part = match component {
+            Component::Normal(part) => part.to_str(),
+            _ => None,
+        };
+        let part = part
+            .filter(|part| crate::security::path::validate_path_component(part).is_ok())
+            .ok_or_else(|| {
+                ServiceError::InvalidOperation(format!(
+                    "Subdirectory '{}' must be a relative path without '..' components",
+                    subdir.display()
+                ))
+            })?;
+        parts.push(part);
+    }
+    if parts.is_empty() {
+        return Err(ServiceError::InvalidOperation(format!(
+            "Subdirectory '{}' is empty",
+            subdir.display()
+        )));
+    }
+    Ok(parts.join("/"))
+}
+
+/// Check out `HEAD` in a clone made with `--no-checkout`, limited to the
+/// directory `pattern` (see [`sparse_pattern`]) when git supports sparse
+/// checkout. Falls back to the whole tree.
+async fn checkout_sparse(repo_path: &Path, pattern: &str) -> Result<(), ServiceError> {
+    if cached_git_version().is_some_and(GitVersion::supports_sparse_checkout) {
+        let sparse = match set_sparse_checkout(repo_path, pattern).await {
+            Ok(()) => checkout_head(repo_path).await,
+            Err(e) => Err(e),
+        };
+        match sparse {
+            Ok(()) => return Ok(()),
+            Err(e) => {
+                warn!("Sparse checkout failed, checking out all files: {}", e);
+                let output = execute_git_command(
+                    &["config", "core.sparseCheckout", "false"],
+                    Duration::from_secs(60),
+                    Some(repo_path),
+                )
+                .await?;
+                if output.exit_code != 0 {
+                    return Err(ServiceError::Custom(format!(
+                        "Failed to turn off sparse checkout: {}",
+                        output.stderr
+                    )));
+                }
+            }
+        }
+    } else {
+        debug!("git is older than 2.25, checking out all files");
+    }
+    checkout_head(repo_path).await
+}
+
+async fn set_sparse_checkout(repo_path: &Path, pattern: &str) -> Result<(), ServiceError> {
+    for args in [
+        &["sparse-checkout", "init", "--cone"][..],
+        &["sparse-checkout", "set", "--", pattern][..],
+    ] {
+        let output = execute_git_command(args, Duration::from_secs(60), Some(repo_path)).await?;
+        if output.exit_code != 0 {
+            return Err(ServiceError::Custom(format!(
+                "git {} failed: {}",
+                args[..2].join(" "),
+                output.stderr
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// [`checkout_head`], limited to `subdir` with a sparse checkout when given
+pub(crate) async fn checkout_head_in(
+    repo_path: &Path,
+    subdir: Option<&Path>,
+) -> Result<(), ServiceError> {
+    match subdir {
+        Some(subdir) => checkout_sparse(repo_path, &sparse_pattern(subdir)?).await,
+        None => checkout_head(repo_path).await,
+    }
+}
+
 /// Checkout a specific branch or tag in a git repository.
 ///
 /// # Arguments
@@ -574,6 +739,166 @@ pub fn validate_cloned_skill(cloned_path: &Path) -> Result<PathBuf, ServiceError
     ))
 }
 
+/// A file in a commit's tree, as listed by `git ls-tree -r -l`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TreeBlob {
+    /// Path from the repository root, `/`-separated
+    pub path: String,
+    /// Blob id (the hash of the file's content)
+    pub object: String,
+    pub size: u64,
+}
+
+/// Arguments for a shallow clone without file contents and without a
+/// checkout; contents are fetched when paths are checked out.
+pub(crate) fn build_partial_clone_args<'a>(
+    url: &'a str,
+    dest: &'a str,
+    branch: Option<&'a str>,
+    tag: Option<&'a str>,
+) -> Vec<&'a str> {
+    let mut args = build_clone_args(url, dest, branch, tag);
+    let end_of_options = args.len() - 3;
+    args.splice(
+        end_of_options..end_of_options,
+        ["--filter=blob:none", "--no-checkout"],
+    );
+    args
+}
+
+/// Clone `url` with its tree but without file contents. Returns the clone and
+/// whether contents were actually left out: a server without partial clone
+/// support ignores the filter and sends everything.
+pub(crate) async fn partial_clone(
+    url: &str,
+    branch: Option<&str>,
+    tag: Option<&str>,
+) -> Result<(TempDir, bool), ServiceError> {
+    check_git_version().await?;
+    let temp_dir = TempDir::new().map_err(|e| {
+        ServiceError::Custom(format!("Failed to create temporary directory: {}", e))
+    })?;
+    let safe_url = redact_url_credentials(url);
+    info!("Cloning repository without file contents: {}", safe_url);
+
+    let dest = temp_dir.path().to_str().ok_or_else(|| {
+        ServiceError::Custom("Failed to convert temp directory path to string".to_string())
+    })?;
+    let clone_args = build_partial_clone_args(url, dest, branch, tag);
+    run_clone(&clone_args, &safe_url).await?;
+
+    // `--missing=print` lists objects the clone lacks (prefixed `?`) without
+    // fetching them
+    let output = execute_git_command(
+        &["rev-list", "--objects", "--missing=print", "HEAD"],
+        Duration::from_secs(60),
+        Some(temp_dir.path()),
+    )
+    .await?;
+    let filtered = output.exit_code == 0 && output.stdout.lines().any(|line| line.starts_with('?'));
+    Ok((temp_dir, filtered))
+}
+
+/// Parse `git ls-tree -r -l -z` output, keeping blobs (files and symlinks)
+pub(crate) fn parse_ls_tree(output: &str) -> Vec<TreeBlob> {
+    output
+        .split('\0')
+        .filter_map(|record| {
+            let (meta, path) = record.split_once('\t')?;
+            let fields: Vec<&str> = meta.split_whitespace().collect();
+            let [_mode, kind, object, size] = fields[..] else {
+                return None;
+            };
+            if kind != "blob" {
+                return None;
+            }
+            Some(TreeBlob {
+                path: path.to_string(),
+                object: object.to_string(),
+                size: size.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Every file in `HEAD`'s tree of the repository at `repo_path`
+pub(crate) async fn list_tree_blobs(repo_path: &Path) -> Result<Vec<TreeBlob>, ServiceError> {
+    let output = execute_git_command(
+        &["ls-tree", "-r", "-l", "-z", "HEAD"],
+        Duration::from_secs(60),
+        Some(repo_path),
+    )
+    .await?;
+    if output.exit_code != 0 {
+        return Err(ServiceError::Custom(format!(
+            "git ls-tree failed: {}",
+            output.stderr
+        )));
+    }
+    Ok(parse_ls_tree(&output.stdout))
+}
+
+/// Blob ids of `files` (relative to `dir`), as git would store them
+pub(crate) async fn hash_objects(dir: &Path, files: &[&str]) -> Result<Vec<String>, ServiceError> {
+    let mut hashes = Vec::with_capacity(files.len());
+    for chunk in files.chunks(200) {
+        let mut args = vec!["hash-object", "--no-filters", "--"];
+        args.extend(chunk);
+        let output = execute_git_command(&args, Duration::from_secs(60), Some(dir)).await?;
+        if output.exit_code != 0 {
+            return Err(ServiceError::Custom(format!(
+                "git hash-object failed: {}",
+                output.stderr
+            )));
+        }
+        hashes.extend(output.stdout.lines().map(str::to_string));
+    }
+    if hashes.len() != files.len() {
+        return Err(ServiceError::Custom(format!(
+            "git hash-object returned {} hashes for {} files",
+            hashes.len(),
+            files.len()
+        )));
+    }
+    Ok(hashes)
+}
+
+/// Check out `paths` (from the repository root) of `HEAD`. In a partial clone
+/// this fetches just their contents.
+pub(crate) async fn checkout_paths(repo_path: &Path, paths: &[&str]) -> Result<(), ServiceError> {
+    for chunk in paths.chunks(200) {
+        let mut args = vec!["--literal-pathspecs", "checkout", "HEAD", "--"];
+        args.extend(chunk);
+        let output = execute_git_command(&args, Duration::from_secs(300), Some(repo_path)).await?;
+        if output.exit_code != 0 {
+            return Err(GitError::CheckoutFailed {
+                ref_name: "HEAD".to_string(),
+                stderr: output.stderr,
+            }
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Check out all of `HEAD` in a clone made with `--no-checkout`
+pub(crate) async fn checkout_head(repo_path: &Path) -> Result<(), ServiceError> {
+    let output = execute_git_command(
+        &["reset", "--quiet", "--hard", "HEAD"],
+        Duration::from_secs(300),
+        Some(repo_path),
+    )
+    .await?;
+    if output.exit_code != 0 {
+        return Err(GitError::CheckoutFailed {
+            ref_name: "HEAD".to_string(),
+            stderr: output.stderr,
+        }
+        .into());
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
@@ -627,6 +952,76 @@ mod tests {
         assert!(args.windows(2).any(|w| w == ["--branch", "v1.0.0"]));
     }
 
+    #[test]
+    fn test_build_partial_clone_args_keeps_end_of_options_last() {
+        let args = build_partial_clone_args("https://h/r.git", "/d", Some("main"), None);
+        assert!(args.contains(&"--filter=blob:none"));
+        assert!(args.contains(&"--no-checkout"));
+        assert_eq!(&args[args.len() - 3..], ["--", "https://h/r.git", "/d"]);
+    }
+
+    #[test]
+    fn test_sparse_pattern_rejects_escaping_subdirs() {
+        assert_eq!(
+            sparse_pattern(Path::new("skills/web-scraper")).unwrap(),
+            "skills/web-scraper"
+        );
+        assert_eq!(
+            sparse_pattern(Path::new("skills/./inner/")).unwrap(),
+            "skills/inner"
+        );
+        for subdir in ["../outside", "skills/../../etc", "/etc/passwd", "", "."] {
+            assert!(
+                matches!(
+                    sparse_pattern(Path::new(subdir)),
+                    Err(ServiceError::InvalidOperation(_))
+                ),
+                "{subdir:?} must be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sparse_checkout_needs_git_2_25() {
+        assert!(!GitVersion::new(2, 24, 9).supports_sparse_checkout());
+        assert!(GitVersion::new(2, 25, 0).supports_sparse_checkout());
+        assert!(GitVersion::new(3, 0, 0).supports_sparse_checkout());
+    }
+
+    #[test]
+    fn test_is_shallow_unsupported() {
+        assert!(is_shallow_unsupported(
+            "Git command failed: fatal: dumb http transport does not support shallow capabilities"
+        ));
+        assert!(is_shallow_unsupported(
+            "fatal: Server does not support shallow clients"
+        ));
+        assert!(!is_shallow_unsupported("fatal: repository not found"));
+    }
+
+    #[test]
+    fn test_parse_ls_tree_keeps_blobs_only() {
+        let output = "100644 blob aaaa     120\tskill/SKILL.md\0\
+                      160000 commit bbbb       -\tvendor/lib\0\
+                      100755 blob cccc      42\tskill/scripts/run me.sh\0";
+        let blobs = parse_ls_tree(output);
+        assert_eq!(
+            blobs,
+            vec![
+                TreeBlob {
+                    path: "skill/SKILL.md".to_string(),
+                    object: "aaaa".to_string(),
+                    size: 120,
+                },
+                TreeBlob {
+                    path: "skill/scripts/run me.sh".to_string(),
+                    object: "cccc".to_string(),
+                    size: 42,
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_build_checkout_args_has_end_of_options() {
         // SEC-12: `--` before ref_name so a "--foo" ref is a positional, not a flag.
@@ -1,32 +1,432 @@
 //! Hot reloading system for skill updates
+//!
+//! Watches the skill directories and re-embeds a skill when its SKILL.md is
+//! created or edited, so search follows edits without a manual reindex.
+//! Changes are batched by a [`ReloadSchedule`]: a burst of writes (an editor
+//! saving, a `git pull`) is handled once the quiet period passes with no
+//! further events, or as soon as `max_batch` skills are pending. Each batch
+//! refreshes the search index once and is announced on the [`EventBus`] as a
+//! single `SkillsReloaded` event. Deleted skills are left for the next full
+//! reindex to prune.
 
+use crate::core::embedding::EmbeddingService;
 use crate::core::service::ServiceError;
+use crate::core::vector_index::VectorIndexService;
+use crate::events::EventBus;
 use crate::storage::StorageBackend;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
+
+/// When pending changes are flushed for re-embedding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReloadSchedule {
+    /// Quiet period after the last change before the batch is flushed
+    pub quiet: Duration,
+    /// Flush early once this many skills are pending, so a large checkout
+    /// starts re-embedding before the tree settles
+    pub max_batch: usize,
+}
+
+/// Where re-embedded skills are written
+#[derive(Clone)]
+pub struct ReembedTarget {
+    pub embedding: Arc<dyn EmbeddingService>,
+    pub index: Arc<dyn VectorIndexService>,
+    /// Chunk size for multi-vector indexing, as configured for reindex
+    pub chunk_size: Option<usize>,
+}
 
 pub struct HotReloadManager {
     #[allow(dead_code)]
     storage: Arc<dyn StorageBackend>,
-    #[allow(dead_code)]
-    event_bus: Arc<crate::events::EventBus>,
+    #[cfg_attr(not(feature = "hot-reload"), allow(dead_code))]
+    event_bus: Arc<EventBus>,
+    #[cfg_attr(not(feature = "hot-reload"), allow(dead_code))]
+    schedule: ReloadSchedule,
+    #[cfg(feature = "hot-reload")]
+    active: tokio::sync::Mutex<Option<ActiveWatch>>,
+}
+
+#[cfg(feature = "hot-reload")]
+struct ActiveWatch {
+    /// Dropping the watcher stops the OS notifications
+    _watcher: notify::RecommendedWatcher,
+    task: tokio::task::JoinHandle<()>,
 }
 
 impl HotReloadManager {
     pub fn new(
         storage: Arc<dyn StorageBackend>,
-        event_bus: Arc<crate::events::EventBus>,
+        event_bus: Arc<EventBus>,
+        schedule: ReloadSchedule,
     ) -> Result<Self, ServiceError> {
-        Ok(Self { storage, event_bus })
+        Ok(Self {
+            storage,
+            event_bus,
+            schedule,
+            #[cfg(feature = "hot-reload")]
+            active: tokio::sync::Mutex::new(None),
+        })
+    }
+
+    /// Watch `paths` (missing ones are skipped) and re-embed changed skills
+    /// into `target`. Without a target there is nothing to update, so no
+    /// watcher is started.
+    #[cfg(feature = "hot-reload")]
+    pub async fn enable_hot_reloading(
+        &self,
+        paths: Vec<PathBuf>,
+        target: Option<ReembedTarget>,
+    ) -> Result<(), ServiceError> {
+        use notify::{RecursiveMode, Watcher};
+
+        let Some(target) = target else {
+            tracing::debug!("Hot reload: no embedding provider, not watching skills");
+            return Ok(());
+        };
+        self.disable_hot_reloading().await?;
+
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut watcher =
+            notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+                if let Ok(event) = result {
+                    let _ = tx.send(event);
+                }
+            })
+            .map_err(|e| ServiceError::Custom(format!("Failed to start file watcher: {}", e)))?;
+
+        let mut watched = Vec::new();
+        for path in paths {
+            if !path.is_dir() {
+                tracing::debug!("Hot reload: skipping missing path {}", path.display());
+                continue;
+            }
+            watcher
+                .watch(&path, RecursiveMode::Recursive)
+                .map_err(|e| {
+                    ServiceError::Custom(format!("Failed to watch {}: {}", path.display(), e))
+                })?;
+            watched.push(path.to_string_lossy().to_string());
+        }
+        if watched.is_empty() {
+            return Ok(());
+        }
+
+        let task = tokio::spawn(debounce_loop(
+            rx,
+            self.schedule,
+            target,
+            Arc::clone(&self.event_bus),
+        ));
+        *self.active.lock().await = Some(ActiveWatch {
+            _watcher: watcher,
+            task,
+        });
+
+        tracing::info!("Hot reload watching {}", watched.join(", "));
+        self.event_bus
+            .publish_hot_reload_enabled(crate::events::HotReloadConfig {
+                watch_paths: watched,
+                debounce_ms: u64::try_from(self.schedule.quiet.as_millis()).unwrap_or(u64::MAX),
+                auto_reload: true,
+                max_concurrent_reloads: 1,
+            })
+            .await?;
+        Ok(())
     }
 
     /// Enable hot reloading for specified paths
-    pub async fn enable_hot_reloading(&self, _paths: Vec<PathBuf>) -> Result<(), ServiceError> {
+    #[cfg(not(feature = "hot-reload"))]
+    pub async fn enable_hot_reloading(
+        &self,
+        _paths: Vec<PathBuf>,
+        _target: Option<ReembedTarget>,
+    ) -> Result<(), ServiceError> {
         Ok(())
     }
 
     /// Disable hot reloading
     pub async fn disable_hot_reloading(&self) -> Result<(), ServiceError> {
+        #[cfg(feature = "hot-reload")]
+        if let Some(active) = self.active.lock().await.take() {
+            active.task.abort();
+            self.event_bus.publish_hot_reload_disabled().await?;
+        }
         Ok(())
     }
+
+    /// Whether a watcher is currently running
+    #[cfg(feature = "hot-reload")]
+    pub async fn is_watching(&self) -> bool {
+        self.active.lock().await.is_some()
+    }
+
+    /// Whether a watcher is currently running
+    #[cfg(not(feature = "hot-reload"))]
+    pub async fn is_watching(&self) -> bool {
+        false
+    }
+}
+
+/// Changed SKILL.md files waiting for the next flush, deduplicated
+#[cfg(feature = "hot-reload")]
+struct ReloadBatch {
+    pending: std::collections::BTreeSet<PathBuf>,
+    max_batch: usize,
+}
+
+#[cfg(feature = "hot-reload")]
+impl ReloadBatch {
+    fn new(max_batch: usize) -> Self {
+        Self {
+            pending: std::collections::BTreeSet::new(),
+            max_batch: max_batch.max(1),
+        }
+    }
+
+    /// Record the SKILL.md files among `paths`. Returns true once the batch
+    /// is full and should be flushed without waiting for the quiet period.
+    fn add(&mut self, paths: impl IntoIterator<Item = PathBuf>) -> bool {
+        self.pending.extend(
+            paths
+                .into_iter()
+                .filter(|path| path.file_name().is_some_and(|name| name == "SKILL.md")),
+        );
+        self.pending.len() >= self.max_batch
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    fn take(&mut self) -> Vec<PathBuf> {
+        std::mem::take(&mut self.pending).into_iter().collect()
+    }
+}
+
+/// Collect SKILL.md changes and re-embed them as one batch when the quiet
+/// period passes or the batch fills up.
+#[cfg(feature = "hot-reload")]
+async fn debounce_loop(
+    mut rx: tokio::sync::mpsc::UnboundedReceiver<notify::Event>,
+    schedule: ReloadSchedule,
+    target: ReembedTarget,
+    event_bus: Arc<EventBus>,
+) {
+    use notify::EventKind;
+
+    let mut batch = ReloadBatch::new(schedule.max_batch);
+    loop {
+        let next = if batch.is_empty() {
+            rx.recv().await
+        } else {
+            match tokio::time::timeout(schedule.quiet, rx.recv()).await {
+                Ok(next) => next,
+                Err(_) => {
+                    reembed_changed(batch.take(), &target, &event_bus).await;
+                    continue;
+                }
+            }
+        };
+        let Some(event) = next else {
+            return;
+        };
+        if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+            && batch.add(event.paths)
+        {
+            reembed_changed(batch.take(), &target, &event_bus).await;
+        }
+    }
+}
+
+/// Re-embed the skills whose SKILL.md files changed, refresh the search
+/// index once and publish the batch as one event. Returns how many index
+/// entries were updated.
+#[cfg(feature = "hot-reload")]
+async fn reembed_changed(
+    changed: Vec<PathBuf>,
+    target: &ReembedTarget,
+    event_bus: &EventBus,
+) -> usize {
+    use crate::core::reindex::{index_skill_file, skill_id_from_path, ReindexMode};
+    use crate::events::ReloadFailure;
+
+    let mut reloaded = Vec::new();
+    let mut failed = Vec::new();
+    for skill_file in changed {
+        // Renamed away or deleted before the debounce elapsed
+        if !skill_file.is_file() {
+            continue;
+        }
+        let Some(skill_id) = skill_id_from_path(&skill_file) else {
+            continue;
+        };
+        let result = index_skill_file(
+            &skill_file,
+            &skill_id,
+            target.embedding.as_ref(),
+            target.index.as_ref(),
+            ReindexMode::Incremental,
+            target.chunk_size,
+        )
+        .await;
+        match result {
+            // Touched but unchanged (same directory hash): nothing to announce
+            Ok(false) => {}
+            Ok(true) => reloaded.push(skill_id),
+            Err(e) => {
+                tracing::warn!("Hot reload: failed to re-embed {}: {}", skill_id, e);
+                failed.push(ReloadFailure {
+                    skill_id,
+                    error_message: e.to_string(),
+                });
+            }
+        }
+    }
+
+    let updated = reloaded.len();
+    if updated > 0 {
+        tracing::info!("Hot reload: re-embedded {}", reloaded.join(", "));
+        if let Err(e) = target.index.refresh_search_index().await {
+            tracing::warn!("Hot reload: failed to refresh search index: {}", e);
+        }
+    }
+    if updated > 0 || !failed.is_empty() {
+        if let Err(e) = event_bus.publish_skills_reloaded(reloaded, failed).await {
+            tracing::warn!("Hot reload: failed to publish reload event: {}", e);
+        }
+    }
+    updated
+}
+
+#[cfg(all(test, feature = "hot-reload"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::vector_index::VectorIndexServiceImpl;
+    use crate::events::SkillEvent;
+    use async_trait::async_trait;
+    use tempfile::TempDir;
+
+    struct LengthEmbedding;
+
+    #[async_trait]
+    impl EmbeddingService for LengthEmbedding {
+        async fn embed_text(&self, text: &str) -> Result<Vec<f32>, ServiceError> {
+            Ok(vec![text.len() as f32, 1.0])
+        }
+
+        async fn embed_query(&self, query: &str) -> Result<Vec<f32>, ServiceError> {
+            self.embed_text(query).await
+        }
+    }
+
+    fn write_skill(dir: &std::path::Path, description: &str) -> PathBuf {
+        std::fs::create_dir_all(dir).unwrap();
+        let skill_file = dir.join("SKILL.md");
+        std::fs::write(
+            &skill_file,
+            format!("---\nname: pdf-tools\ndescription: {description}\n---\n\n# PDF\n"),
+        )
+        .unwrap();
+        skill_file
+    }
+
+    #[tokio::test]
+    async fn test_reembed_changed_updates_index_and_publishes() {
+        let temp_dir = TempDir::new().unwrap();
+        let index = Arc::new(VectorIndexServiceImpl::new(
+            temp_dir.path().join("index.db"),
+        ));
+        let target = ReembedTarget {
+            embedding: Arc::new(LengthEmbedding),
+            index: index.clone(),
+            chunk_size: None,
+        };
+        let bus = EventBus::new();
+        let mut events = bus.subscribe();
+        let skill_file = write_skill(&temp_dir.path().join("pdf-tools"), "Extract text");
+
+        let updated = reembed_changed(vec![skill_file.clone()], &target, &bus).await;
+        assert_eq!(updated, 1);
+        let first = index.get_skill_by_id("pdf-tools").await.unwrap().unwrap();
+        assert!(matches!(
+            events.try_recv().unwrap(),
+            SkillEvent::SkillsReloaded { skill_ids, failed }
+                if skill_ids == vec!["pdf-tools".to_string()] && failed.is_empty()
+        ));
+
+        // Same content again: nothing to re-embed or announce
+        assert_eq!(
+            reembed_changed(vec![skill_file.clone()], &target, &bus).await,
+            0
+        );
+        assert!(events.try_recv().is_err());
+
+        write_skill(
+            &temp_dir.path().join("pdf-tools"),
+            "Extract text and tables",
+        );
+        assert_eq!(reembed_changed(vec![skill_file], &target, &bus).await, 1);
+        let second = index.get_skill_by_id("pdf-tools").await.unwrap().unwrap();
+        assert_ne!(first.embedding, second.embedding);
+    }
+
+    #[tokio::test]
+    async fn test_reembed_changed_skips_deleted_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = ReembedTarget {
+            embedding: Arc::new(LengthEmbedding),
+            index: Arc::new(VectorIndexServiceImpl::new(
+                temp_dir.path().join("index.db"),
+            )),
+            chunk_size: None,
+        };
+        let bus = EventBus::new();
+        let missing = temp_dir.path().join("gone").join("SKILL.md");
+        assert_eq!(reembed_changed(vec![missing], &target, &bus).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_reembed_changed_publishes_one_event_per_batch() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = ReembedTarget {
+            embedding: Arc::new(LengthEmbedding),
+            index: Arc::new(VectorIndexServiceImpl::new(
+                temp_dir.path().join("index.db"),
+            )),
+            chunk_size: None,
+        };
+        let bus = EventBus::new();
+        let mut events = bus.subscribe();
+        let changed = ["alpha", "beta", "gamma"]
+            .iter()
+            .map(|name| write_skill(&temp_dir.path().join(name), name))
+            .collect();
+
+        assert_eq!(reembed_changed(changed, &target, &bus).await, 3);
+        assert!(matches!(
+            events.try_recv().unwrap(),
+            SkillEvent::SkillsReloaded { skill_ids, failed }
+                if skill_ids == ["alpha", "beta", "gamma"] && failed.is_empty()
+        ));
+        assert!(events.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_reload_batch_dedupes_and_fills_at_max_batch() {
+        let mut batch = ReloadBatch::new(2);
+        assert!(!batch.add([
+            PathBuf::from("/skills/a/SKILL.md"),
+            PathBuf::from("/skills/a/SKILL.md"),
+            PathBuf::from("/skills/a/notes.md"),
+        ]));
+        assert!(batch.add([PathBuf::from("/skills/b/SKILL.md")]));
+        assert_eq!(batch.take().len(), 2);
+        assert!(batch.is_empty());
+
+        // A zero limit still batches at least one skill
+        assert!(ReloadBatch::new(0).add([PathBuf::from("/skills/c/SKILL.md")]));
+    }
 }
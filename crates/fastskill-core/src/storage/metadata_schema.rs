@@ -0,0 +1,158 @@
+//! Versioned on-disk form of skill metadata (`<skill>/metadata.json`)
+//!
+//! Every file carries a `schema_version`. Older files are upgraded one step
+//! at a time before the typed parse, so a field rename or new required field
+//! only needs a new step in [`upgrade`]. Files written by a newer release are
+//! read best-effort: known fields are parsed and everything else is kept in
+//! [`StoredSkillMetadata::extra`] so a save does not drop it. Files without a
+//! `schema_version` predate versioning and count as version 0.
+
+use crate::core::metadata::SkillMetadata;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Schema version this release writes
+pub const SKILL_METADATA_SCHEMA_VERSION: u32 = 1;
+
+/// `metadata.json` as stored: the metadata plus its schema version and any
+/// fields this release does not know about.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredSkillMetadata {
+    #[serde(default)]
+    pub schema_version: u32,
+
+    #[serde(flatten)]
+    pub metadata: SkillMetadata,
+
+    /// Fields written by a newer release (or by hand), preserved on save
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl StoredSkillMetadata {
+    /// Wrap `metadata` at the current schema version
+    pub fn new(metadata: SkillMetadata) -> Self {
+        Self {
+            schema_version: SKILL_METADATA_SCHEMA_VERSION,
+            metadata,
+            extra: Map::new(),
+        }
+    }
+
+    /// Parse `metadata.json`, upgrading older schemas. Returns the stored
+    /// metadata (at the current version, or the file's version if newer)
+    /// and the version the file was written with.
+    pub fn parse(content: &str) -> Result<(Self, u32), serde_json::Error> {
+        let mut value: Value = serde_json::from_str(content)?;
+        let found = schema_version_of(&value);
+        if found < SKILL_METADATA_SCHEMA_VERSION {
+            upgrade(&mut value, found);
+        }
+        let stored: Self = serde_json::from_value(value)?;
+        Ok((stored, found))
+    }
+
+    /// Replace the metadata, keeping unknown fields and never lowering the
+    /// schema version of a file a newer release wrote.
+    pub fn with_metadata(self, metadata: SkillMetadata) -> Self {
+        Self {
+            schema_version: self.schema_version.max(SKILL_METADATA_SCHEMA_VERSION),
+            metadata,
+            extra: self.extra,
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+fn schema_version_of(value: &Value) -> u32 {
+    value
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .and_then(|v| u32::try_from(v).ok())
+        .unwrap_or(0)
+}
+
+/// Apply the upgrade steps from `from` to the current version in place.
+fn upgrade(value: &mut Value, from: u32) {
+    let Some(object) = value.as_object_mut() else {
+        return;
+    };
+    for version in from..SKILL_METADATA_SCHEMA_VERSION {
+        if version == 0 {
+            upgrade_v0(object);
+        }
+    }
+    object.insert(
+        "schema_version".to_string(),
+        Value::from(SKILL_METADATA_SCHEMA_VERSION),
+    );
+}
+
+/// v0 → v1: unversioned files could omit `token_estimate`; derive it from
+/// the description the same way `From<&SkillDefinition>` does.
+fn upgrade_v0(object: &mut Map<String, Value>) {
+    if !object.contains_key("token_estimate") {
+        let estimate = object
+            .get("description")
+            .and_then(Value::as_str)
+            .map_or(0, |d| d.len() / 4);
+        object.insert("token_estimate".to_string(), Value::from(estimate));
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const LEGACY: &str = r#"{
+        "id": "pdf-tools",
+        "name": "PDF Tools",
+        "description": "Extract text from PDFs",
+        "version": "1.0.0",
+        "author": null,
+        "last_updated": "2025-01-01T00:00:00Z"
+    }"#;
+
+    #[test]
+    fn test_parse_upgrades_unversioned_metadata() {
+        let (stored, found) = StoredSkillMetadata::parse(LEGACY).unwrap();
+        assert_eq!(found, 0);
+        assert_eq!(stored.schema_version, SKILL_METADATA_SCHEMA_VERSION);
+        assert_eq!(
+            stored.metadata.token_estimate,
+            "Extract text from PDFs".len() / 4
+        );
+        assert!(stored.extra.is_empty());
+    }
+
+    #[test]
+    fn test_newer_schema_keeps_unknown_fields_and_version() {
+        let newer = r#"{
+            "schema_version": 7,
+            "id": "pdf-tools",
+            "name": "PDF Tools",
+            "description": "Extract text from PDFs",
+            "version": "1.0.0",
+            "author": null,
+            "token_estimate": 5,
+            "last_updated": "2025-01-01T00:00:00Z",
+            "license": {"spdx": "MIT"}
+        }"#;
+        let (stored, found) = StoredSkillMetadata::parse(newer).unwrap();
+        assert_eq!(found, 7);
+        assert_eq!(stored.metadata.name, "PDF Tools");
+
+        let mut metadata = stored.metadata.clone();
+        metadata.version = "1.1.0".to_string();
+        let saved = stored.with_metadata(metadata).to_json().unwrap();
+
+        let value: Value = serde_json::from_str(&saved).unwrap();
+        assert_eq!(value["schema_version"], 7);
+        assert_eq!(value["version"], "1.1.0");
+        assert_eq!(value["license"]["spdx"], "MIT");
+    }
+}
@@ -2,23 +2,57 @@
 
 use crate::core::service::ServiceError;
 use async_trait::async_trait;
+use std::path::Path;
 
 // Re-export storage types
 pub use filesystem::{FilesystemStorage, StorageStats};
+#[cfg(feature = "postgres")]
+pub use postgres::{PostgresStorage, PostgresStore};
+#[cfg(feature = "s3-storage")]
+pub use s3::S3Storage;
 
 #[async_trait]
 pub trait StorageBackend: Send + Sync {
     async fn initialize(&self) -> Result<(), ServiceError>;
     async fn clear_cache(&self) -> Result<(), ServiceError>;
+    /// Upgrade stored skill metadata to the current schema; returns how many
+    /// entries were rewritten
+    async fn migrate_metadata(&self) -> Result<usize, ServiceError> {
+        Ok(0)
+    }
+    /// A skill directory under the storage path was written; remote backends
+    /// publish it
+    async fn skill_stored(&self, _skill_id: &str) -> Result<(), ServiceError> {
+        Ok(())
+    }
+    /// A skill was removed; remote backends delete their copy
+    async fn skill_removed(&self, _skill_id: &str) -> Result<(), ServiceError> {
+        Ok(())
+    }
+    /// The project's skills.lock at `lock_path` was rewritten; database
+    /// backends store a copy
+    async fn lock_written(&self, _lock_path: &Path) -> Result<(), ServiceError> {
+        Ok(())
+    }
+    /// A skill was used (`event` names how, e.g. `content`); database
+    /// backends count it
+    async fn skill_used(&self, _skill_id: &str, _event: &str) -> Result<(), ServiceError> {
+        Ok(())
+    }
     // Add other methods as needed
 }
 
 pub mod filesystem;
 pub mod git;
 pub mod hot_reload;
+pub mod metadata_schema;
+#[cfg(feature = "postgres")]
+pub mod postgres;
+#[cfg(feature = "s3-storage")]
+pub mod s3;
 pub mod vector_index;
 pub mod zip;
 
 // Re-export main types
 pub use hot_reload::HotReloadManager;
-pub use zip::ZipHandler;
+pub use zip::{ZipHandler, ZipLimits, ZipViolation};
@@ -0,0 +1,593 @@
+//! Postgres-backed deployment mode for highly-available servers
+//!
+//! Several `serve` replicas share one database instead of one disk: skill
+//! metadata, the project lock, usage counters and the vector index live in
+//! Postgres. Skill files stay in `skill_storage_path`, which replicas are
+//! expected to share (a network volume). The connection string is read from
+//! an environment variable, never from the manifest.
+//!
+//! Embeddings use a pgvector `vector` column when the extension can be
+//! enabled, so similarity search runs in the database. Without it they are
+//! stored as `real[]` and ranked here, like the SQLite index.
+
+use crate::core::lock::ProjectSkillsLock;
+use crate::core::metadata::SkillMetadata;
+use crate::core::service::{PostgresStorageConfig, ServiceError};
+use crate::core::vector_index::{
+    IndexedSkill, SkillMatch, VectorIndexService, VectorIndexServiceImpl,
+};
+use crate::storage::metadata_schema::StoredSkillMetadata;
+use crate::storage::FilesystemStorage;
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio_postgres::{Client, NoTls, Row};
+use tracing::{debug, info, warn};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS fastskill_skill_metadata (
+    skill_id TEXT PRIMARY KEY,
+    schema_version INTEGER NOT NULL,
+    document JSONB NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE TABLE IF NOT EXISTS fastskill_lock (
+    project TEXT PRIMARY KEY,
+    lock JSONB NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE TABLE IF NOT EXISTS fastskill_usage (
+    skill_id TEXT NOT NULL,
+    event TEXT NOT NULL,
+    count BIGINT NOT NULL DEFAULT 0,
+    last_used TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (skill_id, event)
+);
+";
+
+const INDEXED_SKILL_COLUMNS: &str =
+    "skill_id, skill_path, frontmatter, embedding::real[], file_hash, updated_at";
+
+/// Connection to the fastskill tables
+pub struct PostgresStore {
+    client: Client,
+    pgvector: bool,
+}
+
+impl PostgresStore {
+    /// Connect using the URL in the environment variable `config.url_env`
+    pub async fn from_config(config: &PostgresStorageConfig) -> Result<Self, ServiceError> {
+        let url = std::env::var(&config.url_env).map_err(|_| {
+            ServiceError::Config(format!(
+                "Postgres storage is configured but {} is not set",
+                config.url_env
+            ))
+        })?;
+        Self::connect(&url).await
+    }
+
+    /// Connect to `url` and create any missing tables
+    pub async fn connect(url: &str) -> Result<Self, ServiceError> {
+        let (client, connection) = tokio_postgres::connect(url, NoTls)
+            .await
+            .map_err(|e| pg_error("connect", e))?;
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                warn!("Postgres connection closed: {}", e);
+            }
+        });
+
+        client
+            .batch_execute(SCHEMA)
+            .await
+            .map_err(|e| pg_error("create schema", e))?;
+
+        // The service account may not be allowed to create extensions; the
+        // real[] fallback keeps the index working without pgvector.
+        if let Err(e) = client
+            .batch_execute("CREATE EXTENSION IF NOT EXISTS vector")
+            .await
+        {
+            debug!("pgvector unavailable: {}", e);
+        }
+        let has_extension = client
+            .query_opt("SELECT 1 FROM pg_extension WHERE extname = 'vector'", &[])
+            .await
+            .map_err(|e| pg_error("check pgvector", e))?
+            .is_some();
+        let column = if has_extension { "vector" } else { "REAL[]" };
+        client
+            .batch_execute(&format!(
+                "CREATE TABLE IF NOT EXISTS fastskill_embeddings (
+                    skill_id TEXT PRIMARY KEY,
+                    skill_path TEXT NOT NULL,
+                    frontmatter JSONB NOT NULL,
+                    embedding {} NOT NULL,
+                    file_hash TEXT NOT NULL,
+                    updated_at TIMESTAMPTZ NOT NULL
+                )",
+                column
+            ))
+            .await
+            .map_err(|e| pg_error("create embeddings table", e))?;
+
+        // An existing table keeps the column type it was created with
+        let pgvector = client
+            .query_opt(
+                "SELECT udt_name FROM information_schema.columns \
+                 WHERE table_name = 'fastskill_embeddings' AND column_name = 'embedding'",
+                &[],
+            )
+            .await
+            .map_err(|e| pg_error("inspect embeddings table", e))?
+            .is_some_and(|row| row.get::<_, String>(0) == "vector");
+
+        info!(
+            "Connected to Postgres (embeddings: {})",
+            if pgvector { "pgvector" } else { "real[]" }
+        );
+        Ok(Self { client, pgvector })
+    }
+
+    /// Whether similarity search runs in the database
+    pub fn uses_pgvector(&self) -> bool {
+        self.pgvector
+    }
+
+    /// Insert or replace a skill's metadata document
+    pub async fn put_metadata(
+        &self,
+        skill_id: &str,
+        stored: &StoredSkillMetadata,
+    ) -> Result<(), ServiceError> {
+        let document = serde_json::to_value(stored).map_err(|e| {
+            ServiceError::Custom(format!(
+                "Failed to serialize metadata for {}: {}",
+                skill_id, e
+            ))
+        })?;
+        let schema_version = i32::try_from(stored.schema_version).unwrap_or(i32::MAX);
+        self.client
+            .execute(
+                "INSERT INTO fastskill_skill_metadata (skill_id, schema_version, document, updated_at)
+                 VALUES ($1, $2, $3, now())
+                 ON CONFLICT (skill_id) DO UPDATE
+                 SET schema_version = EXCLUDED.schema_version,
+                     document = EXCLUDED.document,
+                     updated_at = now()",
+                &[&skill_id, &schema_version, &document],
+            )
+            .await
+            .map_err(|e| pg_error("save metadata", e))?;
+        Ok(())
+    }
+
+    /// A skill's metadata document, upgraded to the current schema
+    pub async fn get_metadata(
+        &self,
+        skill_id: &str,
+    ) -> Result<Option<StoredSkillMetadata>, ServiceError> {
+        let Some(row) = self
+            .client
+            .query_opt(
+                "SELECT document FROM fastskill_skill_metadata WHERE skill_id = $1",
+                &[&skill_id],
+            )
+            .await
+            .map_err(|e| pg_error("load metadata", e))?
+        else {
+            return Ok(None);
+        };
+        let document: serde_json::Value = row.get(0);
+        let (stored, _) = StoredSkillMetadata::parse(&document.to_string()).map_err(|e| {
+            ServiceError::Custom(format!("Failed to parse metadata for {}: {}", skill_id, e))
+        })?;
+        Ok(Some(stored))
+    }
+
+    /// Replace the stored lock of `project`
+    pub async fn put_lock(
+        &self,
+        project: &str,
+        lock: &ProjectSkillsLock,
+    ) -> Result<(), ServiceError> {
+        let document = serde_json::to_value(lock)
+            .map_err(|e| ServiceError::Custom(format!("Failed to serialize skills.lock: {}", e)))?;
+        self.client
+            .execute(
+                "INSERT INTO fastskill_lock (project, lock, updated_at) VALUES ($1, $2, now())
+                 ON CONFLICT (project) DO UPDATE SET lock = EXCLUDED.lock, updated_at = now()",
+                &[&project, &document],
+            )
+            .await
+            .map_err(|e| pg_error("save lock", e))?;
+        Ok(())
+    }
+
+    /// The stored lock of `project`
+    pub async fn get_lock(&self, project: &str) -> Result<Option<ProjectSkillsLock>, ServiceError> {
+        let Some(row) = self
+            .client
+            .query_opt(
+                "SELECT lock FROM fastskill_lock WHERE project = $1",
+                &[&project],
+            )
+            .await
+            .map_err(|e| pg_error("load lock", e))?
+        else {
+            return Ok(None);
+        };
+        let document: serde_json::Value = row.get(0);
+        serde_json::from_value(document)
+            .map(Some)
+            .map_err(|e| ServiceError::Custom(format!("Failed to parse stored lock: {}", e)))
+    }
+
+    /// Count one `event` (e.g. `content`) for `skill_id`
+    pub async fn record_usage(&self, skill_id: &str, event: &str) -> Result<(), ServiceError> {
+        self.client
+            .execute(
+                "INSERT INTO fastskill_usage (skill_id, event, count, last_used)
+                 VALUES ($1, $2, 1, now())
+                 ON CONFLICT (skill_id, event) DO UPDATE
+                 SET count = fastskill_usage.count + 1, last_used = now()",
+                &[&skill_id, &event],
+            )
+            .await
+            .map_err(|e| pg_error("record usage", e))?;
+        Ok(())
+    }
+
+    /// Drop a skill's metadata and embedding; usage history is kept
+    pub async fn delete_skill(&self, skill_id: &str) -> Result<(), ServiceError> {
+        for table in ["fastskill_skill_metadata", "fastskill_embeddings"] {
+            self.client
+                .execute(
+                    &format!("DELETE FROM {} WHERE skill_id = $1", table),
+                    &[&skill_id],
+                )
+                .await
+                .map_err(|e| pg_error("delete skill", e))?;
+        }
+        Ok(())
+    }
+
+    fn embedding_param(&self, index: usize) -> String {
+        if self.pgvector {
+            format!("${}::real[]::vector", index)
+        } else {
+            format!("${}::real[]", index)
+        }
+    }
+}
+
+#[async_trait]
+impl VectorIndexService for PostgresStore {
+    async fn add_or_update_skill(
+        &self,
+        skill_id: &str,
+        skill_path: PathBuf,
+        frontmatter_json: serde_json::Value,
+        embedding: Vec<f32>,
+        file_hash: &str,
+    ) -> Result<(), ServiceError> {
+        let sql = format!(
+            "INSERT INTO fastskill_embeddings
+                 (skill_id, skill_path, frontmatter, embedding, file_hash, updated_at)
+             VALUES ($1, $2, $3, {}, $5, now())
+             ON CONFLICT (skill_id) DO UPDATE
+             SET skill_path = EXCLUDED.skill_path,
+                 frontmatter = EXCLUDED.frontmatter,
+                 embedding = EXCLUDED.embedding,
+                 file_hash = EXCLUDED.file_hash,
+                 updated_at = now()",
+            self.embedding_param(4)
+        );
+        let skill_path = skill_path.to_string_lossy().to_string();
+        self.client
+            .execute(
+                &sql,
+                &[
+                    &skill_id,
+                    &skill_path,
+                    &frontmatter_json,
+                    &embedding,
+                    &file_hash,
+                ],
+            )
+            .await
+            .map_err(|e| pg_error("save embedding", e))?;
+        Ok(())
+    }
+
+    async fn search_similar(
+        &self,
+        query_embedding: &[f32],
+        limit: usize,
+    ) -> Result<Vec<SkillMatch>, ServiceError> {
+        if !self.pgvector {
+            let mut matches: Vec<SkillMatch> = self
+                .get_all_skills()
+                .await?
+                .into_iter()
+                .map(|skill| {
+                    let similarity = VectorIndexServiceImpl::cosine_similarity(
+                        query_embedding,
+                        &skill.embedding,
+                    );
+                    SkillMatch { skill, similarity }
+                })
+                .collect();
+            matches.sort_by(|a, b| {
+                b.similarity
+                    .partial_cmp(&a.similarity)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            matches.truncate(limit);
+            return Ok(matches);
+        }
+
+        let distance = format!("embedding <=> {}", self.embedding_param(1));
+        let sql = format!(
+            "SELECT {}, (1 - ({}))::real FROM fastskill_embeddings ORDER BY {} LIMIT $2",
+            INDEXED_SKILL_COLUMNS, distance, distance
+        );
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        let query = query_embedding.to_vec();
+        let rows = self
+            .client
+            .query(&sql, &[&query, &limit])
+            .await
+            .map_err(|e| pg_error("search embeddings", e))?;
+        Ok(rows
+            .iter()
+            .map(|row| SkillMatch {
+                skill: indexed_skill(row),
+                similarity: row.get(6),
+            })
+            .collect())
+    }
+
+    async fn get_skill_by_id(&self, skill_id: &str) -> Result<Option<IndexedSkill>, ServiceError> {
+        let sql = format!(
+            "SELECT {} FROM fastskill_embeddings WHERE skill_id = $1",
+            INDEXED_SKILL_COLUMNS
+        );
+        let row = self
+            .client
+            .query_opt(&sql, &[&skill_id])
+            .await
+            .map_err(|e| pg_error("load embedding", e))?;
+        Ok(row.as_ref().map(indexed_skill))
+    }
+
+    async fn remove_skill(&self, skill_id: &str) -> Result<(), ServiceError> {
+        self.client
+            .execute(
+                "DELETE FROM fastskill_embeddings WHERE skill_id = $1",
+                &[&skill_id],
+            )
+            .await
+            .map_err(|e| pg_error("delete embedding", e))?;
+        Ok(())
+    }
+
+    async fn get_all_skills(&self) -> Result<Vec<IndexedSkill>, ServiceError> {
+        let sql = format!(
+            "SELECT {} FROM fastskill_embeddings ORDER BY skill_id",
+            INDEXED_SKILL_COLUMNS
+        );
+        let rows = self
+            .client
+            .query(&sql, &[])
+            .await
+            .map_err(|e| pg_error("load embeddings", e))?;
+        Ok(rows.iter().map(indexed_skill).collect())
+    }
+
+    async fn count_skills(&self) -> Result<usize, ServiceError> {
+        let row = self
+            .client
+            .query_one("SELECT COUNT(*) FROM fastskill_embeddings", &[])
+            .await
+            .map_err(|e| pg_error("count embeddings", e))?;
+        Ok(usize::try_from(row.get::<_, i64>(0)).unwrap_or(0))
+    }
+
+    async fn get_skills_page(
+        &self,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<IndexedSkill>, ServiceError> {
+        let sql = format!(
+            "SELECT {} FROM fastskill_embeddings WHERE skill_id > $1 ORDER BY skill_id LIMIT $2",
+            INDEXED_SKILL_COLUMNS
+        );
+        let after = after.unwrap_or_default();
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        let rows = self
+            .client
+            .query(&sql, &[&after, &limit])
+            .await
+            .map_err(|e| pg_error("page embeddings", e))?;
+        Ok(rows.iter().map(indexed_skill).collect())
+    }
+}
+
+/// Storage backend for Postgres mode: skill files on the (shared) storage
+/// path, metadata, lock state and usage in the database
+pub struct PostgresStorage {
+    store: Arc<PostgresStore>,
+    files: FilesystemStorage,
+    storage_path: PathBuf,
+}
+
+impl PostgresStorage {
+    pub async fn new(
+        store: Arc<PostgresStore>,
+        storage_path: PathBuf,
+    ) -> Result<Self, ServiceError> {
+        Ok(Self {
+            files: FilesystemStorage::new(storage_path.clone()).await?,
+            store,
+            storage_path,
+        })
+    }
+
+    /// Load metadata from the database, falling back to `metadata.json`
+    pub async fn load_skill_metadata(
+        &self,
+        skill_id: &str,
+    ) -> Result<Option<SkillMetadata>, ServiceError> {
+        match self.store.get_metadata(skill_id).await? {
+            Some(stored) => Ok(Some(stored.metadata)),
+            None => self.files.load_skill_metadata(skill_id).await,
+        }
+    }
+
+    /// Save metadata to `metadata.json` and the database
+    pub async fn save_skill_metadata(
+        &self,
+        skill_id: &str,
+        metadata: &SkillMetadata,
+    ) -> Result<(), ServiceError> {
+        self.files.save_skill_metadata(skill_id, metadata).await?;
+        self.publish_metadata(skill_id).await?;
+        Ok(())
+    }
+
+    /// Copy a skill's `metadata.json` into the database; `false` if the
+    /// skill has none
+    pub async fn publish_metadata(&self, skill_id: &str) -> Result<bool, ServiceError> {
+        match read_metadata_file(&self.storage_path.join(skill_id))? {
+            Some(stored) => {
+                self.store.put_metadata(skill_id, &stored).await?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+#[async_trait]
+impl crate::storage::StorageBackend for PostgresStorage {
+    async fn initialize(&self) -> Result<(), ServiceError> {
+        crate::storage::StorageBackend::initialize(&self.files).await
+    }
+
+    async fn clear_cache(&self) -> Result<(), ServiceError> {
+        self.files.clear_cache().await;
+        Ok(())
+    }
+
+    async fn migrate_metadata(&self) -> Result<usize, ServiceError> {
+        let migrated = self.files.migrate_metadata().await?;
+        let mut published = 0;
+        for skill_id in self.files.list_skill_ids().await? {
+            if self.publish_metadata(&skill_id).await? {
+                published += 1;
+            }
+        }
+        debug!("Published metadata of {} skills to Postgres", published);
+        Ok(migrated)
+    }
+
+    async fn skill_stored(&self, skill_id: &str) -> Result<(), ServiceError> {
+        self.publish_metadata(skill_id).await.map(|_| ())
+    }
+
+    async fn skill_removed(&self, skill_id: &str) -> Result<(), ServiceError> {
+        self.store.delete_skill(skill_id).await
+    }
+
+    async fn lock_written(&self, lock_path: &Path) -> Result<(), ServiceError> {
+        let lock = ProjectSkillsLock::load_from_file(lock_path)
+            .map_err(|e| ServiceError::Custom(format!("Failed to load skills.lock: {}", e)))?;
+        self.store.put_lock(&project_key(lock_path), &lock).await
+    }
+
+    async fn skill_used(&self, skill_id: &str, event: &str) -> Result<(), ServiceError> {
+        self.store.record_usage(skill_id, event).await
+    }
+}
+
+/// Key under which a project's lock is stored: its directory, canonicalized
+/// so every replica mounting the same path agrees on it
+pub fn project_key(lock_path: &Path) -> String {
+    let dir = lock_path.parent().unwrap_or(lock_path);
+    dir.canonicalize()
+        .unwrap_or_else(|_| dir.to_path_buf())
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Parse `<skill_dir>/metadata.json`, keeping its schema version and extras
+pub fn read_metadata_file(skill_dir: &Path) -> Result<Option<StoredSkillMetadata>, ServiceError> {
+    let path = skill_dir.join("metadata.json");
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| ServiceError::Custom(format!("Failed to read {}: {}", path.display(), e)))?;
+    let (stored, _) = StoredSkillMetadata::parse(&content)
+        .map_err(|e| ServiceError::Custom(format!("Failed to parse {}: {}", path.display(), e)))?;
+    Ok(Some(stored))
+}
+
+fn indexed_skill(row: &Row) -> IndexedSkill {
+    IndexedSkill {
+        id: row.get(0),
+        skill_path: PathBuf::from(row.get::<_, String>(1)),
+        frontmatter_json: row.get(2),
+        embedding: row.get(3),
+        chunk_embeddings: Vec::new(),
+        file_hash: row.get(4),
+        updated_at: row.get(5),
+    }
+}
+
+fn pg_error(action: &str, error: tokio_postgres::Error) -> ServiceError {
+    ServiceError::Custom(format!("Postgres {} failed: {}", action, error))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::storage::metadata_schema::SKILL_METADATA_SCHEMA_VERSION;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_read_metadata_file_upgrades_legacy_document() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("metadata.json"),
+            r#"{"id":"pdf-tools","name":"PDF Tools","description":"Extract text",
+                "version":"1.0.0","author":null,"last_updated":"2025-01-01T00:00:00Z"}"#,
+        )
+        .unwrap();
+
+        let stored = read_metadata_file(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(stored.schema_version, SKILL_METADATA_SCHEMA_VERSION);
+        assert_eq!(stored.metadata.name, "PDF Tools");
+        assert!(read_metadata_file(&temp_dir.path().join("missing"))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_project_key_is_the_canonical_project_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("sub")).unwrap();
+        let nested = temp_dir.path().join("sub").join("..").join("skills.lock");
+        assert_eq!(
+            project_key(&nested),
+            temp_dir
+                .path()
+                .canonicalize()
+                .unwrap()
+                .to_string_lossy()
+                .to_string()
+        );
+    }
+}
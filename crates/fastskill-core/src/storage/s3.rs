@@ -0,0 +1,455 @@
+//! S3-backed skill storage for server deployments
+//!
+//! The bucket is the source of truth; `skill_storage_path` is a local
+//! read-through cache that the rest of the service (auto-indexing, reindex,
+//! content serving) reads as if it were filesystem storage. On startup the
+//! cache is brought in line with the bucket, downloading only objects whose
+//! ETag changed. Installs and removals made through the service are written
+//! through to the bucket, so every replica started afterwards sees them.
+//!
+//! Objects are stored as `<prefix>/<skill-id>/<path inside the skill>`. Keys
+//! that would resolve outside the cache directory are rejected.
+
+use crate::core::content::SkillContent;
+use crate::core::metadata::SkillMetadata;
+use crate::core::service::{S3StorageConfig, ServiceError};
+use crate::storage::FilesystemStorage;
+use async_trait::async_trait;
+use aws_sdk_s3::error::DisplayErrorContext;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::Client;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+/// ETags of the cached objects, keyed by path relative to the cache root
+const INDEX_FILE: &str = ".fastskill-s3-index.json";
+
+/// S3 storage backend with a local read-through cache
+pub struct S3Storage {
+    client: Client,
+    bucket: String,
+    prefix: String,
+    cache_dir: PathBuf,
+    cache: FilesystemStorage,
+    etags: RwLock<HashMap<String, String>>,
+}
+
+/// What a sync from the bucket changed in the local cache
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    pub downloaded: usize,
+    pub removed: usize,
+    pub unchanged: usize,
+}
+
+impl S3Storage {
+    /// Connect to the bucket described by `config`, caching under `cache_dir`
+    pub async fn new(config: &S3StorageConfig, cache_dir: PathBuf) -> Result<Self, ServiceError> {
+        let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest());
+        if let Some(region) = &config.region {
+            loader = loader.region(aws_config::Region::new(region.clone()));
+        }
+        let shared = loader.load().await;
+        let mut builder =
+            aws_sdk_s3::config::Builder::from(&shared).force_path_style(config.force_path_style);
+        if let Some(endpoint) = &config.endpoint {
+            builder = builder.endpoint_url(endpoint);
+        }
+
+        let cache = FilesystemStorage::new(cache_dir.clone()).await?;
+        let etags = load_index(&cache_dir);
+        info!(
+            "Initialized S3 storage at s3://{}/{} (cache: {})",
+            config.bucket,
+            config.prefix,
+            cache_dir.display()
+        );
+
+        Ok(Self {
+            client: Client::from_conf(builder.build()),
+            bucket: config.bucket.clone(),
+            prefix: config.prefix.trim_matches('/').to_string(),
+            cache_dir,
+            cache,
+            etags: RwLock::new(etags),
+        })
+    }
+
+    /// Bring the local cache in line with the bucket
+    pub async fn sync_from_remote(&self) -> Result<SyncReport, ServiceError> {
+        let remote = self.list_remote("").await?;
+        let mut report = SyncReport::default();
+        let mut etags = self.etags.write().await;
+
+        for (relative, etag) in &remote {
+            let local = self.cache_dir.join(relative);
+            if etags.get(relative) == Some(etag) && local.exists() {
+                report.unchanged += 1;
+                continue;
+            }
+            let (bytes, etag) = self
+                .get_object(relative)
+                .await?
+                .ok_or_else(|| s3_error("download", relative, "object disappeared"))?;
+            write_cached(&local, &bytes)?;
+            etags.insert(relative.clone(), etag);
+            report.downloaded += 1;
+        }
+
+        // Drop cached files whose objects were deleted by another replica.
+        // Only files that came from the bucket are tracked, so local-only
+        // files are never touched.
+        let stale: Vec<String> = etags
+            .keys()
+            .filter(|relative| !remote.contains_key(*relative))
+            .cloned()
+            .collect();
+        for relative in stale {
+            match std::fs::remove_file(self.cache_dir.join(&relative)) {
+                Ok(()) => report.removed += 1,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => warn!("Failed to remove stale cached file {}: {}", relative, e),
+            }
+            etags.remove(&relative);
+        }
+
+        save_index(&self.cache_dir, &etags)?;
+        info!(
+            "Synced skills from s3://{}/{}: {} downloaded, {} removed, {} unchanged",
+            self.bucket, self.prefix, report.downloaded, report.removed, report.unchanged
+        );
+        Ok(report)
+    }
+
+    /// Load SKILL.md, fetching it from the bucket on a cache miss
+    pub async fn load_skill_content(
+        &self,
+        skill_id: &str,
+    ) -> Result<Option<SkillContent>, ServiceError> {
+        if !self.fetch_if_missing(skill_id, "SKILL.md").await? {
+            return Ok(None);
+        }
+        self.cache.load_skill_content(skill_id).await
+    }
+
+    /// Load metadata.json, fetching it from the bucket on a cache miss
+    pub async fn load_skill_metadata(
+        &self,
+        skill_id: &str,
+    ) -> Result<Option<SkillMetadata>, ServiceError> {
+        if !self.fetch_if_missing(skill_id, "metadata.json").await? {
+            return Ok(None);
+        }
+        self.cache.load_skill_metadata(skill_id).await
+    }
+
+    /// Save SKILL.md to the cache and the bucket
+    pub async fn save_skill_content(
+        &self,
+        skill_id: &str,
+        content: impl AsRef<[u8]>,
+    ) -> Result<(), ServiceError> {
+        self.cache.save_skill_content(skill_id, content).await?;
+        self.upload_file(&format!("{}/SKILL.md", skill_id)).await
+    }
+
+    /// Save metadata.json to the cache and the bucket
+    pub async fn save_skill_metadata(
+        &self,
+        skill_id: &str,
+        metadata: &SkillMetadata,
+    ) -> Result<(), ServiceError> {
+        self.cache.save_skill_metadata(skill_id, metadata).await?;
+        self.upload_file(&format!("{}/metadata.json", skill_id))
+            .await
+    }
+
+    /// Upload every file of a cached skill and delete objects it no longer has
+    pub async fn upload_skill(&self, skill_id: &str) -> Result<(), ServiceError> {
+        let skill_dir = self.cache_dir.join(skill_id);
+        let mut local = Vec::new();
+        for entry in walkdir::WalkDir::new(&skill_dir).follow_links(true) {
+            let entry = entry.map_err(|e| {
+                ServiceError::Custom(format!("Failed to read {}: {}", skill_dir.display(), e))
+            })?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let Ok(inner) = entry.path().strip_prefix(&skill_dir) else {
+                continue;
+            };
+            local.push(format!("{}/{}", skill_id, key_path(inner)));
+        }
+
+        // Replicas only see a scoped id if the scope directory's marker is there
+        if let Some((scope, _)) = skill_id.split_once('/') {
+            let marker = format!("{}/{}", scope, crate::core::scope::SCOPE_MARKER);
+            if self.cache_dir.join(&marker).is_file() {
+                local.push(marker);
+            }
+        }
+
+        for relative in &local {
+            self.upload_file(relative).await?;
+        }
+        for relative in self.list_remote(&format!("{}/", skill_id)).await?.keys() {
+            if !local.contains(relative) {
+                self.delete_object(relative).await?;
+            }
+        }
+        debug!("Uploaded skill {} ({} files)", skill_id, local.len());
+        Ok(())
+    }
+
+    /// Delete every object of a skill from the bucket and the cache
+    pub async fn delete_skill(&self, skill_id: &str) -> Result<(), ServiceError> {
+        for relative in self.list_remote(&format!("{}/", skill_id)).await?.keys() {
+            self.delete_object(relative).await?;
+        }
+        self.cache.delete_skill(skill_id).await?;
+        debug!("Deleted skill {} from s3://{}", skill_id, self.bucket);
+        Ok(())
+    }
+
+    fn object_key(&self, relative: &str) -> String {
+        if self.prefix.is_empty() {
+            relative.to_string()
+        } else {
+            format!("{}/{}", self.prefix, relative)
+        }
+    }
+
+    /// Objects under `<prefix>/<sub_prefix>`, keyed by path relative to the
+    /// cache root, with their ETags
+    async fn list_remote(
+        &self,
+        sub_prefix: &str,
+    ) -> Result<BTreeMap<String, String>, ServiceError> {
+        let prefix = match self.prefix.as_str() {
+            "" => sub_prefix.to_string(),
+            p => format!("{}/{}", p, sub_prefix),
+        };
+        let mut objects = BTreeMap::new();
+        let mut pages = self
+            .client
+            .list_objects_v2()
+            .bucket(&self.bucket)
+            .prefix(&prefix)
+            .into_paginator()
+            .send();
+        while let Some(page) = pages.next().await {
+            let page = page.map_err(|e| s3_error("list", &prefix, DisplayErrorContext(&e)))?;
+            for object in page.contents() {
+                let Some(key) = object.key() else { continue };
+                let Some(relative) = relative_key(&self.prefix, key) else {
+                    warn!("Ignoring object with unsafe key: {}", key);
+                    continue;
+                };
+                let etag = object.e_tag().unwrap_or_default().to_string();
+                objects.insert(relative, etag);
+            }
+        }
+        Ok(objects)
+    }
+
+    async fn get_object(&self, relative: &str) -> Result<Option<(Vec<u8>, String)>, ServiceError> {
+        let output = match self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(self.object_key(relative))
+            .send()
+            .await
+        {
+            Ok(output) => output,
+            Err(e) => {
+                let e = e.into_service_error();
+                if e.is_no_such_key() {
+                    return Ok(None);
+                }
+                return Err(s3_error("download", relative, DisplayErrorContext(&e)));
+            }
+        };
+        let etag = output.e_tag().unwrap_or_default().to_string();
+        let bytes = output
+            .body
+            .collect()
+            .await
+            .map_err(|e| s3_error("download", relative, e))?
+            .into_bytes();
+        Ok(Some((bytes.to_vec(), etag)))
+    }
+
+    /// Download `<skill_id>/<file>` into the cache if it is not there yet.
+    /// Returns whether the file is now cached.
+    async fn fetch_if_missing(&self, skill_id: &str, file: &str) -> Result<bool, ServiceError> {
+        let relative = format!("{}/{}", skill_id, file);
+        if relative_key("", &relative).is_none() {
+            return Ok(false);
+        }
+        let local = self.cache_dir.join(&relative);
+        if local.exists() {
+            return Ok(true);
+        }
+        let Some((bytes, etag)) = self.get_object(&relative).await? else {
+            return Ok(false);
+        };
+        write_cached(&local, &bytes)?;
+        let mut etags = self.etags.write().await;
+        etags.insert(relative, etag);
+        save_index(&self.cache_dir, &etags)?;
+        Ok(true)
+    }
+
+    async fn upload_file(&self, relative: &str) -> Result<(), ServiceError> {
+        let body = ByteStream::from_path(self.cache_dir.join(relative))
+            .await
+            .map_err(|e| s3_error("read", relative, e))?;
+        let output = self
+            .client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(self.object_key(relative))
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| s3_error("upload", relative, DisplayErrorContext(&e)))?;
+        let mut etags = self.etags.write().await;
+        etags.insert(
+            relative.to_string(),
+            output.e_tag().unwrap_or_default().to_string(),
+        );
+        save_index(&self.cache_dir, &etags)
+    }
+
+    async fn delete_object(&self, relative: &str) -> Result<(), ServiceError> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(self.object_key(relative))
+            .send()
+            .await
+            .map_err(|e| s3_error("delete", relative, DisplayErrorContext(&e)))?;
+        let mut etags = self.etags.write().await;
+        etags.remove(relative);
+        save_index(&self.cache_dir, &etags)
+    }
+}
+
+#[async_trait]
+impl crate::storage::StorageBackend for S3Storage {
+    async fn initialize(&self) -> Result<(), ServiceError> {
+        crate::storage::StorageBackend::initialize(&self.cache).await?;
+        self.sync_from_remote().await?;
+        Ok(())
+    }
+
+    async fn clear_cache(&self) -> Result<(), ServiceError> {
+        self.cache.clear_cache().await;
+        Ok(())
+    }
+
+    async fn migrate_metadata(&self) -> Result<usize, ServiceError> {
+        self.cache.migrate_metadata().await
+    }
+
+    async fn skill_stored(&self, skill_id: &str) -> Result<(), ServiceError> {
+        self.upload_skill(skill_id).await
+    }
+
+    async fn skill_removed(&self, skill_id: &str) -> Result<(), ServiceError> {
+        self.delete_skill(skill_id).await
+    }
+}
+
+/// Strip the bucket prefix from `key` and check that what is left is a
+/// plain relative path of at least `<skill-id>/<file>`. `None` for keys
+/// outside the prefix or that could escape the cache directory.
+fn relative_key(prefix: &str, key: &str) -> Option<String> {
+    let relative = if prefix.is_empty() {
+        key
+    } else {
+        key.strip_prefix(prefix)?.strip_prefix('/')?
+    };
+    let segments: Vec<&str> = relative.split('/').collect();
+    let safe = segments
+        .iter()
+        .all(|s| !s.is_empty() && *s != "." && *s != ".." && !s.contains('\\'));
+    // Directory placeholders ("skill/") and top-level keys are not skill files
+    if !safe || segments.len() < 2 {
+        return None;
+    }
+    Some(relative.to_string())
+}
+
+/// Forward-slash form of a relative path, for object keys
+fn key_path(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn write_cached(path: &Path, bytes: &[u8]) -> Result<(), ServiceError> {
+    crate::utils::atomic_write(path, bytes).map_err(|e| {
+        ServiceError::Custom(format!(
+            "Failed to write cached file {}: {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+fn load_index(cache_dir: &Path) -> HashMap<String, String> {
+    std::fs::read(cache_dir.join(INDEX_FILE))
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default()
+}
+
+fn save_index(cache_dir: &Path, etags: &HashMap<String, String>) -> Result<(), ServiceError> {
+    let sorted: BTreeMap<&String, &String> = etags.iter().collect();
+    let bytes = serde_json::to_vec_pretty(&sorted)
+        .map_err(|e| ServiceError::Custom(format!("Failed to serialize S3 cache index: {}", e)))?;
+    write_cached(&cache_dir.join(INDEX_FILE), &bytes)
+}
+
+fn s3_error(action: &str, relative: &str, error: impl std::fmt::Display) -> ServiceError {
+    ServiceError::Custom(format!("S3 {} failed for {}: {}", action, relative, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_key_strips_prefix() {
+        assert_eq!(
+            relative_key("team/skills", "team/skills/pdf/SKILL.md").as_deref(),
+            Some("pdf/SKILL.md")
+        );
+        assert_eq!(
+            relative_key("", "pdf/scripts/run.sh").as_deref(),
+            Some("pdf/scripts/run.sh")
+        );
+        assert_eq!(relative_key("team/skills", "other/pdf/SKILL.md"), None);
+        assert_eq!(relative_key("team", "teamx/pdf/SKILL.md"), None);
+    }
+
+    #[test]
+    fn test_relative_key_rejects_paths_outside_the_cache() {
+        assert_eq!(relative_key("skills", "skills/../../etc/passwd"), None);
+        assert_eq!(relative_key("skills", "skills/pdf/../../../x"), None);
+        assert_eq!(relative_key("", "/etc/passwd"), None);
+        assert_eq!(relative_key("", "pdf/..\\..\\x"), None);
+        assert_eq!(relative_key("", "pdf/./SKILL.md"), None);
+    }
+
+    #[test]
+    fn test_relative_key_skips_placeholders_and_top_level_keys() {
+        assert_eq!(relative_key("", "pdf/"), None);
+        assert_eq!(relative_key("", "README.md"), None);
+    }
+}
@@ -1,23 +1,25 @@
 //! ZIP package handling for skill distribution
 
 use crate::core::service::ServiceError;
-use crate::security::path::normalize_path;
+use crate::security::path::{normalize_path, validate_path_within_root};
+use serde::{Deserialize, Serialize};
 use std::io;
 use std::io::Read;
-use std::path::Path;
+use std::path::{Component, Path};
 
 // -----------------------------------------------------------------------------
 // Decompression (ZIP bomb) DoS ceiling — SEC-3
 // -----------------------------------------------------------------------------
 //
-// These are a fixed *DoS ceiling* meant to protect the host from a decompression
+// These are the default *DoS ceiling* meant to protect the host from a decompression
 // bomb, deliberately sized comfortably above the entire real corpus of 1311
 // skills (package size: p99 1.27 MB, max 5.66 MB; largest single file: 4.0 MB;
 // max file count: 316). They are intentionally distinct from the
 // content-validation limits that define a *valid* skill (`MAX_CONTENT_SIZE`
 // in `context_resolver.rs`, the `SKILL.md` cap in `file_structure.rs`): those
 // bound what a well-formed skill may contain, while these merely bound what an
-// extraction may cost the host.
+// extraction may cost the host. Callers that need tighter or looser bounds pass
+// their own [`ZipLimits`].
 
 /// Maximum total uncompressed bytes an archive may expand to (~9x largest real skill).
 pub(crate) const MAX_TOTAL_UNCOMPRESSED: u64 = 50 * 1024 * 1024; // 50 MiB
@@ -27,95 +29,282 @@ pub(crate) const MAX_ENTRY_UNCOMPRESSED: u64 = 10 * 1024 * 1024; // 10 MiB
 pub(crate) const MAX_ENTRIES: usize = 10_000;
 /// Maximum tolerated per-entry compression ratio (uncompressed / compressed).
 pub(crate) const MAX_RATIO: u64 = 100;
+/// Maximum number of path components in an entry name. Deeply nested entries
+/// make no sense in a skill and can exhaust path length limits on extraction.
+pub(crate) const MAX_PATH_DEPTH: usize = 32;
+
+/// Bounds an archive must stay within to be validated or extracted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ZipLimits {
+    /// Total uncompressed bytes across all entries
+    pub max_total_uncompressed: u64,
+    /// Uncompressed bytes of a single entry
+    pub max_entry_uncompressed: u64,
+    /// Entries in the archive, directories included
+    pub max_entries: usize,
+    /// Uncompressed size / compressed size of a single entry
+    pub max_compression_ratio: u64,
+    /// Path components in an entry name
+    pub max_path_depth: usize,
+}
 
-/// Pre-flight ZIP checks: reject archives that exceed the entry-count cap or that
-/// declare an oversized/over-compressed entry, *before* extracting anything.
-///
-/// This is a cheap header-only scan (it reads no entry data) and is shared by
-/// [`ZipHandler::validate_package`] and [`ZipHandler::extract_to_dir`]. The real
-/// cumulative-byte budget is still enforced during extraction in `extract_to_dir`,
-/// because the declared `size()` can lie.
-pub(crate) fn preflight_zip_archive<R: Read + io::Seek>(
+impl Default for ZipLimits {
+    fn default() -> Self {
+        Self {
+            max_total_uncompressed: MAX_TOTAL_UNCOMPRESSED,
+            max_entry_uncompressed: MAX_ENTRY_UNCOMPRESSED,
+            max_entries: MAX_ENTRIES,
+            max_compression_ratio: MAX_RATIO,
+            max_path_depth: MAX_PATH_DEPTH,
+        }
+    }
+}
+
+/// Why an archive is rejected. Converts into `ServiceError::Validation` with
+/// the same message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, thiserror::Error)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ZipViolation {
+    #[error("ZIP archive has too many entries ({count} > {limit} limit)")]
+    TooManyEntries { count: usize, limit: usize },
+
+    #[error("ZIP entry '{entry}' declares size {size} bytes exceeding per-entry limit {limit}")]
+    EntryTooLarge {
+        entry: String,
+        size: u64,
+        limit: u64,
+    },
+
+    #[error(
+        "ZIP entry '{entry}' has compression ratio {ratio} exceeding limit {limit} (possible ZIP bomb)"
+    )]
+    CompressionRatio {
+        entry: String,
+        ratio: u64,
+        limit: u64,
+    },
+
+    #[error(
+        "ZIP extraction exceeds total uncompressed limit of {limit} bytes (decompression bomb) at entry '{entry}'"
+    )]
+    TotalTooLarge { entry: String, limit: u64 },
+
+    #[error("ZIP entry '{entry}' is nested {depth} levels deep, exceeding limit {limit}")]
+    PathTooDeep {
+        entry: String,
+        depth: usize,
+        limit: usize,
+    },
+
+    #[error("Path traversal attempt detected in ZIP entry: '{entry}' resolves outside extraction directory")]
+    PathTraversal { entry: String },
+
+    #[error("Symlink entry rejected for security: {entry}")]
+    Symlink { entry: String },
+}
+
+impl ZipViolation {
+    /// Stable name of the violation, as in its serialized `kind`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ZipViolation::TooManyEntries { .. } => "too_many_entries",
+            ZipViolation::EntryTooLarge { .. } => "entry_too_large",
+            ZipViolation::CompressionRatio { .. } => "compression_ratio",
+            ZipViolation::TotalTooLarge { .. } => "total_too_large",
+            ZipViolation::PathTooDeep { .. } => "path_too_deep",
+            ZipViolation::PathTraversal { .. } => "path_traversal",
+            ZipViolation::Symlink { .. } => "symlink",
+        }
+    }
+}
+
+impl From<ZipViolation> for ServiceError {
+    fn from(violation: ZipViolation) -> Self {
+        ServiceError::Validation(violation.to_string())
+    }
+}
+
+/// Header-only check of one entry name: traversal and nesting depth
+fn check_entry_path(entry: &str, limits: &ZipLimits) -> Option<ZipViolation> {
+    let path = Path::new(entry);
+    let normalized = normalize_path(path);
+    if normalized.as_os_str().is_empty()
+        || path.has_root()
+        || path.components().any(|c| matches!(c, Component::Prefix(_)))
+    {
+        return Some(ZipViolation::PathTraversal {
+            entry: entry.to_string(),
+        });
+    }
+    let depth = normalized.components().count();
+    (depth > limits.max_path_depth).then(|| ZipViolation::PathTooDeep {
+        entry: entry.to_string(),
+        depth,
+        limit: limits.max_path_depth,
+    })
+}
+
+/// Header-only scan for limit and path violations. Stops at the first one
+/// unless `all` is set.
+fn scan_archive<R: Read + io::Seek>(
     archive: &mut zip::ZipArchive<R>,
-) -> Result<(), ServiceError> {
-    if archive.len() > MAX_ENTRIES {
-        return Err(ServiceError::Validation(format!(
-            "ZIP archive has too many entries ({} > {} limit)",
-            archive.len(),
-            MAX_ENTRIES
-        )));
+    limits: &ZipLimits,
+    all: bool,
+) -> Result<Vec<ZipViolation>, ServiceError> {
+    let mut violations = Vec::new();
+    if archive.len() > limits.max_entries {
+        violations.push(ZipViolation::TooManyEntries {
+            count: archive.len(),
+            limit: limits.max_entries,
+        });
+        if !all {
+            return Ok(violations);
+        }
     }
 
+    let mut declared_total: u64 = 0;
     for i in 0..archive.len() {
         let file = archive
             .by_index(i)
             .map_err(|e| ServiceError::Validation(format!("Failed to read ZIP entry: {}", e)))?;
+        let entry = file.name().to_string();
+
+        violations.extend(check_entry_path(&entry, limits));
+
+        if !file.is_dir() {
+            let declared = file.size();
+            if declared > limits.max_entry_uncompressed {
+                violations.push(ZipViolation::EntryTooLarge {
+                    entry: entry.clone(),
+                    size: declared,
+                    limit: limits.max_entry_uncompressed,
+                });
+            }
 
-        if file.is_dir() {
-            continue;
-        }
+            let compressed = file.compressed_size();
+            if compressed > 0 && declared / compressed > limits.max_compression_ratio {
+                violations.push(ZipViolation::CompressionRatio {
+                    entry: entry.clone(),
+                    ratio: declared / compressed,
+                    limit: limits.max_compression_ratio,
+                });
+            }
 
-        let declared = file.size();
-        if declared > MAX_ENTRY_UNCOMPRESSED {
-            return Err(ServiceError::Validation(format!(
-                "ZIP entry '{}' declares size {} bytes exceeding per-entry limit {}",
-                file.name(),
-                declared,
-                MAX_ENTRY_UNCOMPRESSED
-            )));
+            declared_total = declared_total.saturating_add(declared);
+            if declared_total > limits.max_total_uncompressed
+                && !violations
+                    .iter()
+                    .any(|v| matches!(v, ZipViolation::TotalTooLarge { .. }))
+            {
+                violations.push(ZipViolation::TotalTooLarge {
+                    entry: entry.clone(),
+                    limit: limits.max_total_uncompressed,
+                });
+            }
         }
 
-        let compressed = file.compressed_size();
-        if compressed > 0 && declared / compressed > MAX_RATIO {
-            return Err(ServiceError::Validation(format!(
-                "ZIP entry '{}' has compression ratio {} exceeding limit {} (possible ZIP bomb)",
-                file.name(),
-                declared / compressed,
-                MAX_RATIO
-            )));
+        if !all && !violations.is_empty() {
+            violations.truncate(1);
+            return Ok(violations);
         }
     }
 
-    Ok(())
+    Ok(violations)
+}
+
+/// Pre-flight ZIP checks: reject archives that exceed the entry-count cap, that
+/// declare an oversized/over-compressed entry or more bytes than the total
+/// budget, or whose entry names climb out of the extraction root or nest too
+/// deep, *before* extracting anything.
+///
+/// This is a cheap header-only scan (it reads no entry data) and is shared by
+/// [`ZipHandler::validate_package`] and [`ZipHandler::extract_to_dir`]. The real
+/// cumulative-byte budget is still enforced during extraction in `extract_to_dir`,
+/// because the declared `size()` can lie.
+pub(crate) fn preflight_zip_archive<R: Read + io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    limits: &ZipLimits,
+) -> Result<(), ServiceError> {
+    match scan_archive(archive, limits, false)?.into_iter().next() {
+        Some(violation) => Err(violation.into()),
+        None => Ok(()),
+    }
 }
 
-pub struct ZipHandler;
+#[derive(Debug, Clone, Default)]
+pub struct ZipHandler {
+    limits: ZipLimits,
+}
 
 impl ZipHandler {
     pub fn new() -> Result<Self, ServiceError> {
-        Ok(Self)
+        Ok(Self::default())
+    }
+
+    /// Handler enforcing `limits` instead of the defaults
+    pub fn with_limits(limits: ZipLimits) -> Self {
+        Self { limits }
+    }
+
+    pub fn limits(&self) -> &ZipLimits {
+        &self.limits
+    }
+
+    fn open(zip_path: &Path) -> Result<zip::ZipArchive<std::fs::File>, ServiceError> {
+        let file = std::fs::File::open(zip_path).map_err(ServiceError::Io)?;
+        zip::ZipArchive::new(file)
+            .map_err(|e| ServiceError::Validation(format!("Invalid ZIP file: {}", e)))
     }
 
     /// Validate ZIP package structure
     pub async fn validate_package(&self, zip_path: &Path) -> Result<(), ServiceError> {
-        let file = std::fs::File::open(zip_path).map_err(ServiceError::Io)?;
-        let mut archive = zip::ZipArchive::new(file)
-            .map_err(|e| ServiceError::Validation(format!("Invalid ZIP file: {}", e)))?;
-        preflight_zip_archive(&mut archive)
+        preflight_zip_archive(&mut Self::open(zip_path)?, &self.limits)
+    }
+
+    /// Every limit and path violation in the package, from its headers alone.
+    /// An empty list means the package passes [`Self::validate_package`].
+    pub fn package_violations(&self, zip_path: &Path) -> Result<Vec<ZipViolation>, ServiceError> {
+        scan_archive(&mut Self::open(zip_path)?, &self.limits, true)
     }
 
     /// Safely extract a ZIP file to a destination directory
     ///
     /// This function protects against ZIP slip attacks by:
     /// - Normalizing the output path for each entry
-    /// - Verifying that the normalized path stays within the extraction directory
+    /// - Verifying with [`validate_path_within_root`] that the path stays
+    ///   within the extraction directory, symlinks resolved
     /// - Rejecting entries that would create files outside the extraction root
     /// - Rejecting symlink entries
     ///
+    /// and against decompression bombs by enforcing the handler's [`ZipLimits`].
+    ///
     /// # Arguments
     /// * `zip_path` - Path to the ZIP file to extract
     /// * `dest_dir` - Destination directory for extraction (must exist)
     ///
     /// # Errors
     /// Returns `ServiceError::Io` for I/O errors
-    /// Returns `ServiceError::Validation` if path traversal is detected
+    /// Returns `ServiceError::Validation` if path traversal is detected or a
+    /// limit is exceeded
     pub fn extract_to_dir(&self, zip_path: &Path, dest_dir: &Path) -> Result<(), ServiceError> {
         let file = std::fs::File::open(zip_path).map_err(ServiceError::Io)?;
-        let mut archive = zip::ZipArchive::new(file)
+        self.extract_reader_to_dir(file, dest_dir)
+    }
+
+    /// Same as [`Self::extract_to_dir`], reading the archive from memory or any
+    /// other seekable source. Malformed archives yield `ServiceError::Validation`.
+    pub fn extract_reader_to_dir<R: Read + io::Seek>(
+        &self,
+        reader: R,
+        dest_dir: &Path,
+    ) -> Result<(), ServiceError> {
+        let mut archive = zip::ZipArchive::new(reader)
             .map_err(|e| ServiceError::Validation(format!("Invalid ZIP file: {}", e)))?;
 
-        // Reject decompression bombs up front (entry-count / declared-size / ratio caps).
-        preflight_zip_archive(&mut archive)?;
+        // Reject decompression bombs and escaping names up front (entry-count /
+        // declared-size / ratio / depth caps).
+        preflight_zip_archive(&mut archive, &self.limits)?;
 
         // Canonicalize the destination directory for reliable path comparison
         let dest_canonical = dest_dir.canonicalize().map_err(|e| {
@@ -144,32 +333,26 @@ impl ZipHandler {
             // Reject symlink entries
             #[cfg(unix)]
             if matches!(file.unix_mode(), Some(mode) if (mode & 0o170000) == 0o120000) {
-                return Err(ServiceError::Validation(format!(
-                    "Symlink entry rejected for security: {}",
-                    entry_name
-                )));
+                return Err(ZipViolation::Symlink { entry: entry_name }.into());
             }
 
             // Normalize the entry name to resolve . and .. components before any I/O
             let normalized_entry_name = normalize_path(Path::new(&entry_name));
             if normalized_entry_name.as_os_str().is_empty() {
-                return Err(ServiceError::Validation(format!(
-                    "Path traversal attempt detected in ZIP entry: '{}'",
-                    entry_name
-                )));
+                return Err(ZipViolation::PathTraversal { entry: entry_name }.into());
             }
 
-            // Build the output path using the normalized entry name
-            let outpath = dest_dir.join(&normalized_entry_name);
-
-            // Ensure the normalized path is within the destination directory before any I/O
-            let outpath_str = outpath.to_string_lossy().to_string();
-            let dest_str = dest_canonical.to_string_lossy().to_string();
-            if !outpath_str.starts_with(&dest_str) {
-                return Err(ServiceError::Validation(format!(
-                    "Path traversal attempt detected in ZIP entry: '{}' would resolve outside extraction directory",
-                    entry_name
-                )));
+            // Build the output path from the canonical destination so the
+            // containment check below also holds for relative or symlinked
+            // destinations.
+            let outpath = dest_canonical.join(&normalized_entry_name);
+
+            // Ensure the path is within the destination directory before any I/O.
+            // This resolves symlinks already on disk (an existing entry, or the
+            // parent of a new one) and compares components, not strings:
+            // `/tmp/dest-evil` starts with `/tmp/dest`.
+            if validate_path_within_root(&outpath, &dest_canonical).is_err() {
+                return Err(ZipViolation::PathTraversal { entry: entry_name }.into());
             }
 
             // For directories, we need to check if they exist or can be created.
@@ -221,14 +404,18 @@ impl ZipHandler {
                 // Enforce the real cumulative uncompressed budget. Copy through a
                 // `take` limited to the remaining budget (+1 byte to detect overflow);
                 // if the entry writes past the budget, reject as a decompression bomb.
-                let remaining = MAX_TOTAL_UNCOMPRESSED.saturating_sub(total_uncompressed);
+                let remaining = self
+                    .limits
+                    .max_total_uncompressed
+                    .saturating_sub(total_uncompressed);
                 let mut limited = file.by_ref().take(remaining.saturating_add(1));
                 let written = io::copy(&mut limited, &mut outfile).map_err(ServiceError::Io)?;
                 if written > remaining {
-                    return Err(ServiceError::Validation(format!(
-                        "ZIP extraction exceeds total uncompressed limit of {} bytes (decompression bomb) at entry '{}'",
-                        MAX_TOTAL_UNCOMPRESSED, entry_name
-                    )));
+                    return Err(ZipViolation::TotalTooLarge {
+                        entry: entry_name,
+                        limit: self.limits.max_total_uncompressed,
+                    }
+                    .into());
                 }
                 total_uncompressed = total_uncompressed.saturating_add(written);
                 // Now canonicalize the file path to validate
@@ -242,10 +429,7 @@ impl ZipHandler {
 
             // Ensure the resolved path is within the destination directory
             if !outpath_canonical.starts_with(&dest_canonical) {
-                return Err(ServiceError::Validation(format!(
-                    "Path traversal attempt detected in ZIP entry: '{}' resolves outside extraction directory",
-                    entry_name
-                )));
+                return Err(ZipViolation::PathTraversal { entry: entry_name }.into());
             }
         }
 
@@ -329,9 +513,10 @@ mod tests {
             _ => unreachable!("Expected ServiceError::Validation for path traversal"),
         }
 
-        // Ensure no file was created outside the extraction directory
+        // Ensure no file was created outside the extraction directory; the
+        // pre-flight rejects the archive before any entry is written
         assert!(!extract_dir.path().join("../../../evil.txt").exists());
-        assert!(extract_dir.path().join("normal.txt").exists());
+        assert!(!extract_dir.path().join("normal.txt").exists());
     }
 
     #[test]
@@ -411,8 +596,8 @@ mod tests {
         let result = handler.extract_to_dir(&zip_path, extract_dir.path());
 
         assert!(result.is_err());
-        // Mixed traversal should be rejected
-        assert!(extract_dir.path().join("safe/file.txt").exists());
+        // Mixed traversal is rejected before anything is extracted
+        assert!(!extract_dir.path().join("safe/file.txt").exists());
     }
 
     #[test]
@@ -516,8 +701,9 @@ mod tests {
     #[test]
     fn test_extract_rejects_total_budget_exceeded() {
         // Six 9 MiB Stored entries: each is under the 10 MiB per-entry cap and has
-        // ratio 1 (so pre-flight passes), but the cumulative 54 MiB exceeds the
-        // 50 MiB total budget enforced at the io::copy.
+        // ratio 1, but the cumulative 54 MiB exceeds the 50 MiB total budget.
+        // Pre-flight rejects the declared total; the io::copy budget backs it up
+        // for archives whose declared sizes lie.
         let chunk = vec![b'x'; 9 * 1024 * 1024];
         let entries: Vec<(&str, &[u8])> = vec![
             ("a.bin", &chunk),
@@ -766,9 +952,8 @@ mod tests {
         assert!(!outside.path().join("file.txt").exists());
     }
 
-    /// A *directory* entry routed through a pre-existing symlink escapes only as
-    /// far as the post-creation canonicalization check (the directory branch has
-    /// no early parent guard), which must reject it as resolving outside the root.
+    /// A *directory* entry routed through a pre-existing symlink must be
+    /// rejected as resolving outside the root before the directory is created.
     #[cfg(unix)]
     #[test]
     fn test_extract_rejects_dir_entry_symlink_escape() {
@@ -792,4 +977,125 @@ mod tests {
             other => unreachable!("expected Validation error, got {other:?}"),
         }
     }
+
+    /// A destination reached through a symlink is still a valid extraction
+    /// root; only entries escaping its canonical path are rejected.
+    #[cfg(unix)]
+    #[test]
+    fn test_extract_into_symlinked_dest() {
+        let base = TempDir::new().unwrap();
+        let real = base.path().join("real");
+        std::fs::create_dir_all(&real).unwrap();
+        let link = base.path().join("link");
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+
+        let (_t, zip_path) = create_test_zip(&[("SKILL.md", b"x"), ("scripts/run.sh", b"y")]);
+        let handler = ZipHandler::new().unwrap();
+        handler.extract_to_dir(&zip_path, &link).unwrap();
+
+        assert!(real.join("SKILL.md").exists());
+        assert!(real.join("scripts/run.sh").exists());
+    }
+
+    #[test]
+    fn test_extract_reader_rejects_path_traversal() {
+        let (_t, zip_path) = create_test_zip(&[("../evil.txt", b"malicious")]);
+        let bytes = std::fs::read(&zip_path).unwrap();
+        let base = TempDir::new().unwrap();
+        let dest = base.path().join("dest");
+        std::fs::create_dir_all(&dest).unwrap();
+
+        let handler = ZipHandler::new().unwrap();
+        let result = handler.extract_reader_to_dir(std::io::Cursor::new(bytes), &dest);
+
+        assert!(matches!(result, Err(ServiceError::Validation(_))));
+        assert!(!base.path().join("evil.txt").exists());
+    }
+
+    #[test]
+    fn test_custom_limits_are_enforced() {
+        let (_t, zip_path) = create_test_zip(&[("a.txt", b"a"), ("b.txt", b"b"), ("c.txt", b"c")]);
+        let handler = ZipHandler::with_limits(ZipLimits {
+            max_entries: 2,
+            ..Default::default()
+        });
+        let extract_dir = TempDir::new().unwrap();
+        let result = handler.extract_to_dir(&zip_path, extract_dir.path());
+        match result {
+            Err(ServiceError::Validation(msg)) => assert!(msg.contains("too many entries")),
+            other => unreachable!("expected Validation error, got {other:?}"),
+        }
+        assert!(!extract_dir.path().join("a.txt").exists());
+    }
+
+    #[test]
+    fn test_extract_rejects_deep_paths() {
+        let (_t, zip_path) = create_test_zip(&[("a/b/c/d/e.txt", b"deep")]);
+        let handler = ZipHandler::with_limits(ZipLimits {
+            max_path_depth: 4,
+            ..Default::default()
+        });
+        let extract_dir = TempDir::new().unwrap();
+        let result = handler.extract_to_dir(&zip_path, extract_dir.path());
+        match result {
+            Err(ServiceError::Validation(msg)) => assert!(msg.contains("nested 5 levels")),
+            other => unreachable!("expected Validation error, got {other:?}"),
+        }
+        assert!(!extract_dir.path().join("a").exists());
+    }
+
+    #[test]
+    fn test_package_violations_lists_every_problem() {
+        let (_t, zip_path) = create_test_zip(&[
+            ("SKILL.md", b"ok"),
+            ("../evil.txt", b"malicious"),
+            ("x/../../evil.txt", b"malicious"),
+            ("a/b/c.txt", b"deep"),
+        ]);
+        let handler = ZipHandler::with_limits(ZipLimits {
+            max_path_depth: 2,
+            max_total_uncompressed: 4,
+            ..Default::default()
+        });
+        let violations = handler.package_violations(&zip_path).unwrap();
+        assert_eq!(
+            violations,
+            vec![
+                ZipViolation::PathTraversal {
+                    entry: "../evil.txt".to_string()
+                },
+                ZipViolation::TotalTooLarge {
+                    entry: "../evil.txt".to_string(),
+                    limit: 4
+                },
+                ZipViolation::PathTraversal {
+                    entry: "x/../../evil.txt".to_string()
+                },
+                ZipViolation::PathTooDeep {
+                    entry: "a/b/c.txt".to_string(),
+                    depth: 3,
+                    limit: 2
+                },
+            ]
+        );
+
+        let (_t, clean) = create_test_zip(&[("SKILL.md", b"ok")]);
+        assert!(ZipHandler::new()
+            .unwrap()
+            .package_violations(&clean)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_validate_package_rejects_path_traversal() {
+        let (_t, zip_path) = create_test_zip(&[("SKILL.md", b"ok"), ("../evil.txt", b"x")]);
+        let result = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(ZipHandler::new().unwrap().validate_package(&zip_path));
+        match result {
+            Err(ServiceError::Validation(msg)) => assert!(msg.contains("Path traversal")),
+            other => unreachable!("expected Validation error, got {other:?}"),
+        }
+    }
 }
@@ -3,6 +3,13 @@
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Name prefix of the temp files `atomic_write` stages content in.
+pub(crate) const TEMP_FILE_PREFIX: &str = ".fastskill-tmp-";
+
+/// Temp files younger than this may belong to a writer that is still running.
+pub(crate) const ORPHANED_TEMP_MIN_AGE: Duration = Duration::from_secs(300);
 
 /// Write `bytes` to `path` atomically: write to a **per-writer unique** temp file
 /// in the same directory → sync → atomic rename over `path`.
@@ -33,7 +40,7 @@ pub(crate) fn atomic_write(path: &Path, bytes: &[u8]) -> io::Result<()> {
 
     // Unique temp file owned by this writer.
     let mut tmp = tempfile::Builder::new()
-        .prefix(".fastskill-tmp-")
+        .prefix(TEMP_FILE_PREFIX)
         .tempfile_in(&parent)?;
 
     tmp.write_all(bytes)?;
@@ -44,9 +51,78 @@ pub(crate) fn atomic_write(path: &Path, bytes: &[u8]) -> io::Result<()> {
     // Atomically move the completed temp file over the target.
     tmp.persist(path).map_err(|e| e.error)?;
 
+    // Sync the directory so the rename itself survives a power loss.
+    sync_dir(&parent)?;
+
     Ok(())
 }
 
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> io::Result<()> {
+    fs::File::open(dir)?.sync_all()
+}
+
+/// Directories cannot be opened for syncing on Windows; `MoveFileEx` used by the
+/// rename is already durable there.
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+/// Remove temp files left in `dir` by `atomic_write` calls that were killed
+/// before their rename. Returns the number of files removed.
+///
+/// A killed writer never touches its target, so the target is always intact;
+/// the orphaned temp file is the only residue. Files younger than `min_age` are
+/// left alone since they may belong to a writer that is still running.
+pub(crate) fn remove_orphaned_temp_files(dir: &Path, min_age: Duration) -> io::Result<usize> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e),
+    };
+
+    let mut removed = 0;
+    for entry in entries {
+        let entry = entry?;
+        if !entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with(TEMP_FILE_PREFIX)
+        {
+            continue;
+        }
+        let metadata = entry.metadata()?;
+        let age = metadata
+            .modified()
+            .ok()
+            .and_then(|m| m.elapsed().ok())
+            .unwrap_or_default();
+        if metadata.is_file() && age >= min_age {
+            fs::remove_file(entry.path())?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// Minimal HTML-escape for text interpolated into generated HTML (dashboard, reports).
+/// Escapes the five HTML-significant characters to prevent stored/reflected XSS.
+pub(crate) fn html_escape(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#x27;"),
+            other => out.push(other),
+        }
+    }
+    out
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -152,4 +228,58 @@ mod tests {
         let new_content = fs::read_to_string(&path).unwrap();
         assert_eq!(new_content, "new content");
     }
+
+    /// Stage a temp file exactly as `atomic_write` does, then abandon it without
+    /// renaming or deleting it — the state a writer killed mid-write leaves behind.
+    fn simulate_killed_writer(dir: &Path, partial: &[u8]) -> PathBuf {
+        use io::Write;
+        let mut tmp = tempfile::Builder::new()
+            .prefix(TEMP_FILE_PREFIX)
+            .tempfile_in(dir)
+            .unwrap();
+        tmp.write_all(partial).unwrap();
+        // A killed process runs no destructors, so the temp file is never cleaned up.
+        let (_, path) = tmp.keep().unwrap();
+        path
+    }
+
+    #[test]
+    fn test_killed_writer_leaves_target_intact_and_orphan_is_removed() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("skills.lock");
+        atomic_write(&path, b"committed content").unwrap();
+
+        let orphan = simulate_killed_writer(tmp.path(), b"half of the new con");
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "committed content");
+        assert_eq!(
+            remove_orphaned_temp_files(tmp.path(), Duration::ZERO).unwrap(),
+            1
+        );
+        assert!(!orphan.exists());
+        assert!(path.exists(), "recovery must never touch the target");
+    }
+
+    #[test]
+    fn test_remove_orphaned_temp_files_spares_recent_writers() {
+        let tmp = TempDir::new().unwrap();
+        let in_flight = simulate_killed_writer(tmp.path(), b"still writing");
+        fs::write(tmp.path().join("other.txt"), b"unrelated").unwrap();
+
+        let removed = remove_orphaned_temp_files(tmp.path(), ORPHANED_TEMP_MIN_AGE).unwrap();
+
+        assert_eq!(removed, 0);
+        assert!(in_flight.exists());
+        assert!(tmp.path().join("other.txt").exists());
+    }
+
+    #[test]
+    fn test_remove_orphaned_temp_files_missing_dir() {
+        let tmp = TempDir::new().unwrap();
+        let missing = tmp.path().join("does-not-exist");
+        assert_eq!(
+            remove_orphaned_temp_files(&missing, Duration::ZERO).unwrap(),
+            0
+        );
+    }
 }
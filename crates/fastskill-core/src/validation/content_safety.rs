@@ -1,16 +1,18 @@
 //! Content safety validation (dangerous pattern checks in SKILL.md and scripts).
 //!
 //! IMPORTANT: this is a **heuristic advisory signal, not a sandbox**. The
-//! dangerous-pattern check is naive substring matching and is trivially bypassed
-//! (whitespace variation, `from os import ...`, obfuscation/base64, etc.) while
-//! also producing false positives on legitimate scripts. Matches are therefore
-//! surfaced as **warnings** that inform the user; they do **not** fail
-//! validation. FastSkill does not vet skill safety — treat all third-party
-//! skills as untrusted code and run them in a sandboxed/containerized
-//! environment rather than relying on this validator.
+//! dangerous-pattern rules ([`crate::validation::rules`]) are line-by-line
+//! regular expressions and are trivially bypassed (obfuscation, base64,
+//! building commands from strings, etc.) while also producing false positives
+//! on legitimate scripts. Matches of the built-in rules are therefore surfaced
+//! as **warnings** that inform the user; only rules an operator configures
+//! with `severity = "error"` fail validation. FastSkill does not vet skill
+//! safety — treat all third-party skills as untrusted code and run them in a
+//! sandboxed/containerized environment rather than relying on this validator.
 
 use crate::core::service::ServiceError;
 use crate::validation::result::{ErrorSeverity, ValidationResult};
+use crate::validation::rules::{RuleSeverity, ScriptLanguage, ValidationRules};
 use std::path::Path;
 use tokio::fs;
 
@@ -23,7 +25,9 @@ pub(crate) enum PatternCheckContext<'a> {
 /// Parameters for a single dangerous-pattern check.
 pub(crate) struct DangerousPatternCheck<'a> {
     pub content: &'a str,
-    pub patterns: &'a [String],
+    pub rules: &'a ValidationRules,
+    /// Skill being checked, for per-skill allowlists
+    pub skill_id: Option<&'a str>,
     pub field: &'a str,
     pub context: PatternCheckContext<'a>,
 }
@@ -32,23 +36,25 @@ pub(crate) fn add_dangerous_pattern_errors(
     mut result: ValidationResult,
     check: DangerousPatternCheck<'_>,
 ) -> ValidationResult {
-    let message = |p: &str| match &check.context {
-        PatternCheckContext::SkillFile => {
-            format!("Potentially dangerous pattern found in SKILL.md: {}", p)
-        }
-        PatternCheckContext::ScriptFile(path) => format!(
-            "Potentially dangerous pattern found in script {}: {}",
-            path.display(),
-            p
+    let (location, language) = match &check.context {
+        PatternCheckContext::SkillFile => ("SKILL.md".to_string(), None),
+        PatternCheckContext::ScriptFile(path) => (
+            format!("script {}", path.display()),
+            ScriptLanguage::detect(path, check.content),
         ),
     };
-    // Advisory only (SEC-8): these substring matches are a heuristic, not a
-    // sandbox — trivially bypassed and prone to false positives. Surface them as
-    // warnings so they inform without failing validation.
-    for pattern in check.patterns {
-        if check.content.contains(pattern) {
-            result = result.with_warning(check.field, &message(pattern));
-        }
+    // Advisory by default (SEC-8): these matches are a heuristic, not a
+    // sandbox — trivially bypassed and prone to false positives. Only rules
+    // configured as errors fail validation.
+    for found in check.rules.scan(check.content, check.skill_id, language) {
+        let message = format!(
+            "Potentially dangerous pattern found in {} (line {}): {} [{}]",
+            location, found.line, found.rule.message, found.rule.id
+        );
+        result = match found.rule.severity {
+            RuleSeverity::Warning => result.with_warning(check.field, &message),
+            RuleSeverity::Error => result.with_error(check.field, &message, ErrorSeverity::Error),
+        };
     }
     result
 }
@@ -56,7 +62,8 @@ pub(crate) fn add_dangerous_pattern_errors(
 pub(crate) async fn validate_skill_file_content(
     path: &Path,
     result: ValidationResult,
-    patterns: &[String],
+    rules: &ValidationRules,
+    skill_id: Option<&str>,
 ) -> Result<ValidationResult, ServiceError> {
     let content = match fs::read_to_string(path).await {
         Ok(c) => c,
@@ -72,7 +79,8 @@ pub(crate) async fn validate_skill_file_content(
         result,
         DangerousPatternCheck {
             content: &content,
-            patterns,
+            rules,
+            skill_id,
             field: "content",
             context: PatternCheckContext::SkillFile,
         },
@@ -91,7 +99,8 @@ pub(crate) async fn validate_skill_file_content(
 pub(crate) async fn validate_script_file_content(
     path: &Path,
     result: ValidationResult,
-    patterns: &[String],
+    rules: &ValidationRules,
+    skill_id: Option<&str>,
 ) -> Result<ValidationResult, ServiceError> {
     let content = match fs::read_to_string(path).await {
         Ok(c) => c,
@@ -110,7 +119,8 @@ pub(crate) async fn validate_script_file_content(
         result,
         DangerousPatternCheck {
             content: &content,
-            patterns,
+            rules,
+            skill_id,
             field: "script_content",
             context: PatternCheckContext::ScriptFile(path),
         },
@@ -118,24 +128,6 @@ pub(crate) async fn validate_script_file_content(
     Ok(result)
 }
 
-pub(crate) fn default_dangerous_patterns() -> Vec<String> {
-    vec![
-        "import os".to_string(),
-        "import subprocess".to_string(),
-        "import sys".to_string(),
-        "exec(".to_string(),
-        "eval(".to_string(),
-        "system(".to_string(),
-        "popen(".to_string(),
-        "rm -rf".to_string(),
-        "sudo".to_string(),
-        "chmod 777".to_string(),
-        "chown".to_string(),
-        "su ".to_string(),
-        "passwd".to_string(),
-    ]
-}
-
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -145,12 +137,13 @@ mod tests {
     /// blocking error, and must leave `is_valid == true`.
     #[test]
     fn test_dangerous_pattern_is_warning_not_error() {
-        let patterns = default_dangerous_patterns();
+        let rules = ValidationRules::builtin();
         let result = add_dangerous_pattern_errors(
             ValidationResult::valid(),
             DangerousPatternCheck {
                 content: "import os\nsudo rm -rf /",
-                patterns: &patterns,
+                rules: &rules,
+                skill_id: None,
                 field: "content",
                 context: PatternCheckContext::SkillFile,
             },
@@ -171,12 +164,13 @@ mod tests {
 
     #[test]
     fn test_no_dangerous_pattern_leaves_result_clean() {
-        let patterns = default_dangerous_patterns();
+        let rules = ValidationRules::builtin();
         let result = add_dangerous_pattern_errors(
             ValidationResult::valid(),
             DangerousPatternCheck {
                 content: "This skill formats markdown documents.",
-                patterns: &patterns,
+                rules: &rules,
+                skill_id: None,
                 field: "content",
                 context: PatternCheckContext::SkillFile,
             },
@@ -186,4 +180,30 @@ mod tests {
         assert!(result.errors.is_empty());
         assert!(result.warnings.is_empty());
     }
+
+    #[test]
+    fn test_error_rule_fails_validation() {
+        let rules = ValidationRules::from_toml_str(
+            "[[rules]]\nid = \"no-curl\"\npattern = 'curl\\s'\nseverity = \"error\"",
+        )
+        .unwrap();
+        let script = Path::new("scripts/setup.sh");
+        let result = add_dangerous_pattern_errors(
+            ValidationResult::valid(),
+            DangerousPatternCheck {
+                content: "#!/bin/sh\n# sudo is not needed\ncurl https://example.com\n",
+                rules: &rules,
+                skill_id: Some("setup"),
+                field: "script_content",
+                context: PatternCheckContext::ScriptFile(script),
+            },
+        );
+
+        assert!(!result.is_valid);
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0]
+            .message
+            .contains("script scripts/setup.sh (line 3): curl\\s [no-curl]"));
+        assert!(result.warnings.is_empty(), "comments are not scanned");
+    }
 }
@@ -20,14 +20,12 @@ pub(crate) fn validate_required_fields(
 ) -> ValidationResult {
     for field in required_fields {
         match field.as_str() {
-            "name" => {
-                if skill.name.trim().is_empty() {
-                    result = result.with_error(
-                        "name",
-                        "Skill name cannot be empty",
-                        ErrorSeverity::Critical,
-                    );
-                }
+            "name" if skill.name.trim().is_empty() => {
+                result = result.with_error(
+                    "name",
+                    "Skill name cannot be empty",
+                    ErrorSeverity::Critical,
+                );
             }
             "description" => {
                 if skill.description.trim().is_empty() {
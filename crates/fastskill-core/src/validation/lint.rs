@@ -0,0 +1,673 @@
+//! Style lints for SKILL.md (`fastskill lint`)
+//!
+//! Validation decides whether a skill can be installed; lints are style
+//! guidance for authors. Rules:
+//!
+//! - `frontmatter`: the YAML block is missing or unreadable, lacks `name` or
+//!   `description` (errors), or lacks `version` or `license` (warnings)
+//! - `description-trigger`: the description does not say when to use the skill
+//! - `heading-structure`: a missing space after `#`, no level-1 title, more
+//!   than one, skipped levels and empty headings
+//! - `broken-link`: a relative link or a `` `scripts/...` `` style path that
+//!   names a missing file
+//! - `link-outside-skill`: a link that resolves outside the skill directory
+//! - `line-length`: prose lines over the limit; code blocks, tables and lines
+//!   with URLs are exempt
+//! - `trailing-whitespace` and `final-newline`
+//!
+//! Fixes are applied only where the intent is unambiguous: the heading space,
+//! skipped heading levels (lowered to one below the previous heading), links
+//! whose target exists under a different letter case or with `\` separators,
+//! trailing whitespace (Markdown's two-space line break is kept) and the final
+//! newline.
+
+use crate::core::service::ServiceError;
+use crate::validation::rules::RuleSeverity;
+use regex::Regex;
+use serde::Serialize;
+use std::path::{Component, Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Default limit for `line-length`
+pub const DEFAULT_MAX_LINE_LENGTH: usize = 120;
+
+/// Frontmatter fields every skill should declare besides the required ones
+const RECOMMENDED_FIELDS: [&str; 2] = ["version", "license"];
+
+/// Words and phrases that tell an agent when a skill applies
+const TRIGGER_WORDS: [&str; 3] = ["when", "whenever", "if"];
+const TRIGGER_PHRASES: [&str; 5] = ["use for", "use to", "use this", "used for", "used to"];
+
+static LINK_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    #[allow(clippy::expect_used)]
+    Regex::new(r#"\]\(\s*<?([^)\s>]+)>?(?:\s+"[^"]*")?\s*\)"#).expect("Invalid link regex")
+});
+static CODE_PATH_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    #[allow(clippy::expect_used)]
+    Regex::new(r"`((?:\./)?(?:scripts|references|assets)[/\\][^`\s]+)`")
+        .expect("Invalid code path regex")
+});
+
+/// One problem found in a SKILL.md
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LintFinding {
+    pub rule: &'static str,
+    pub severity: RuleSeverity,
+    /// 1-based line in SKILL.md; none for whole-file problems
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    pub message: String,
+    /// Whether `--fix` corrects it
+    pub fixable: bool,
+}
+
+/// Lint result for one skill
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillLint {
+    pub skill_dir: PathBuf,
+    pub skill_file: PathBuf,
+    /// Problems left in the file (after fixing, with `fix`)
+    pub findings: Vec<LintFinding>,
+    /// Problems corrected by `fix`
+    pub fixed: usize,
+}
+
+impl SkillLint {
+    pub fn errors(&self) -> usize {
+        self.count(RuleSeverity::Error)
+    }
+
+    pub fn warnings(&self) -> usize {
+        self.count(RuleSeverity::Warning)
+    }
+
+    fn count(&self, severity: RuleSeverity) -> usize {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == severity)
+            .count()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LintOptions {
+    pub max_line_length: usize,
+    /// Rewrite SKILL.md with the safe fixes applied
+    pub fix: bool,
+}
+
+impl Default for LintOptions {
+    fn default() -> Self {
+        Self {
+            max_line_length: DEFAULT_MAX_LINE_LENGTH,
+            fix: false,
+        }
+    }
+}
+
+/// Lint the SKILL.md in `skill_dir`, fixing it first when `options.fix` is set
+pub fn lint_skill(skill_dir: &Path, options: &LintOptions) -> Result<SkillLint, ServiceError> {
+    let skill_file = skill_dir.join("SKILL.md");
+    let content = std::fs::read_to_string(&skill_file).map_err(|e| {
+        ServiceError::Validation(format!("Cannot read {}: {}", skill_file.display(), e))
+    })?;
+    let (mut findings, fixed_content) = lint_content(&content, skill_dir, options.max_line_length);
+    let mut fixed = 0;
+    if let Some(fixed_content) = fixed_content.filter(|_| options.fix) {
+        std::fs::write(&skill_file, &fixed_content)?;
+        fixed = findings.iter().filter(|f| f.fixable).count();
+        findings = lint_content(&fixed_content, skill_dir, options.max_line_length).0;
+    }
+    Ok(SkillLint {
+        skill_dir: skill_dir.to_path_buf(),
+        skill_file,
+        findings,
+        fixed,
+    })
+}
+
+/// Findings for `content`, plus the content with every fix applied when that
+/// differs from the input
+pub fn lint_content(
+    content: &str,
+    skill_dir: &Path,
+    max_line_length: usize,
+) -> (Vec<LintFinding>, Option<String>) {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let mut findings = Vec::new();
+
+    let body_start = check_frontmatter(&lines, &mut findings);
+    check_headings(&mut lines, body_start, &mut findings);
+    check_links(&mut lines, body_start, skill_dir, &mut findings);
+    check_line_length(&lines, body_start, max_line_length, &mut findings);
+    check_whitespace(&mut lines, &mut findings);
+    if !content.is_empty() && !content.ends_with('\n') {
+        findings.push(finding(
+            "final-newline",
+            RuleSeverity::Warning,
+            None,
+            "file does not end with a newline".to_string(),
+            true,
+        ));
+    }
+    findings.sort_by_key(|f| f.line.unwrap_or(0));
+
+    let newline = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut fixed = lines.join(newline);
+    fixed.push_str(newline);
+    let fixed = (findings.iter().any(|f| f.fixable) && fixed != content).then_some(fixed);
+    (findings, fixed)
+}
+
+fn finding(
+    rule: &'static str,
+    severity: RuleSeverity,
+    line: Option<usize>,
+    message: String,
+    fixable: bool,
+) -> LintFinding {
+    LintFinding {
+        rule,
+        severity,
+        line,
+        message,
+        fixable,
+    }
+}
+
+/// Check the frontmatter block; returns the index of the first body line
+fn check_frontmatter(lines: &[String], findings: &mut Vec<LintFinding>) -> usize {
+    let close = if lines.first().map(|l| l.trim()) == Some("---") {
+        lines.iter().skip(1).position(|l| l.trim() == "---")
+    } else {
+        None
+    };
+    let Some(close) = close.map(|i| i + 1) else {
+        findings.push(finding(
+            "frontmatter",
+            RuleSeverity::Error,
+            Some(1),
+            "SKILL.md has no `---` delimited YAML frontmatter block at the top".to_string(),
+            false,
+        ));
+        return 0;
+    };
+
+    let yaml = lines[1..close].join("\n");
+    let mapping = match serde_yaml::from_str::<serde_yaml::Mapping>(&yaml) {
+        Ok(mapping) => mapping,
+        Err(e) => {
+            findings.push(finding(
+                "frontmatter",
+                RuleSeverity::Error,
+                Some(1),
+                format!("frontmatter is not a YAML mapping: {}", e),
+                false,
+            ));
+            return close + 1;
+        }
+    };
+    let field_line = |field: &str| {
+        lines[1..close]
+            .iter()
+            .position(|l| l.starts_with(&format!("{}:", field)))
+            .map(|i| i + 2)
+    };
+    let text = |field: &str| {
+        mapping
+            .get(field)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    };
+
+    for field in ["name", "description"] {
+        if text(field).is_none() {
+            findings.push(finding(
+                "frontmatter",
+                RuleSeverity::Error,
+                field_line(field).or(Some(1)),
+                format!("required field `{}` is missing or empty", field),
+                false,
+            ));
+        }
+    }
+    for field in RECOMMENDED_FIELDS {
+        if !mapping.contains_key(field) {
+            findings.push(finding(
+                "frontmatter",
+                RuleSeverity::Warning,
+                Some(1),
+                format!("recommended field `{}` is missing", field),
+                false,
+            ));
+        }
+    }
+    if let Some(description) = text("description") {
+        if !has_trigger(description) {
+            findings.push(finding(
+                "description-trigger",
+                RuleSeverity::Warning,
+                field_line("description"),
+                "description does not say when to use the skill; add a phrase such as \
+                 \"Use when ...\""
+                    .to_string(),
+                false,
+            ));
+        }
+    }
+    close + 1
+}
+
+/// Whether a description tells an agent when the skill applies
+fn has_trigger(description: &str) -> bool {
+    let lower = description.to_lowercase();
+    lower
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| TRIGGER_WORDS.contains(&word))
+        || TRIGGER_PHRASES.iter().any(|phrase| lower.contains(phrase))
+}
+
+/// Whether a line opens or closes a fenced code block
+fn is_fence(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("```") || line.starts_with("~~~")
+}
+
+/// Indices of body lines outside fenced code blocks
+fn prose_lines(lines: &[String], body_start: usize) -> Vec<usize> {
+    let mut in_fence = false;
+    let mut prose = Vec::new();
+    for (i, line) in lines.iter().enumerate().skip(body_start) {
+        if is_fence(line) {
+            in_fence = !in_fence;
+        } else if !in_fence {
+            prose.push(i);
+        }
+    }
+    prose
+}
+
+fn check_headings(lines: &mut [String], body_start: usize, findings: &mut Vec<LintFinding>) {
+    let mut previous_level = 0;
+    let mut titles = 0;
+    let mut first_heading = true;
+    for i in prose_lines(lines, body_start) {
+        let hashes = lines[i].chars().take_while(|c| *c == '#').count();
+        if hashes == 0 || hashes > 6 {
+            continue;
+        }
+        let rest = lines[i][hashes..].to_string();
+        let missing_space = rest.starts_with(|c: char| c.is_alphanumeric());
+        if missing_space {
+            findings.push(finding(
+                "heading-structure",
+                RuleSeverity::Warning,
+                Some(i + 1),
+                format!("missing space after `{}`", "#".repeat(hashes)),
+                true,
+            ));
+        } else if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+            continue;
+        }
+        let mut fix = missing_space;
+        if rest.trim().is_empty() {
+            findings.push(finding(
+                "heading-structure",
+                RuleSeverity::Warning,
+                Some(i + 1),
+                "empty heading".to_string(),
+                false,
+            ));
+        }
+
+        let mut level = hashes;
+        if first_heading && level != 1 {
+            findings.push(finding(
+                "heading-structure",
+                RuleSeverity::Warning,
+                Some(i + 1),
+                "the first heading should be a level-1 title".to_string(),
+                false,
+            ));
+        } else if previous_level > 0 && level > previous_level + 1 {
+            findings.push(finding(
+                "heading-structure",
+                RuleSeverity::Warning,
+                Some(i + 1),
+                format!("heading level skips from {} to {}", previous_level, level),
+                true,
+            ));
+            level = previous_level + 1;
+            fix = true;
+        }
+        if level == 1 {
+            titles += 1;
+            if titles == 2 {
+                findings.push(finding(
+                    "heading-structure",
+                    RuleSeverity::Warning,
+                    Some(i + 1),
+                    "more than one level-1 heading".to_string(),
+                    false,
+                ));
+            }
+        }
+        first_heading = false;
+        previous_level = level;
+        if fix {
+            lines[i] = format!("{} {}", "#".repeat(level), rest.trim_start());
+        }
+    }
+    if first_heading {
+        findings.push(finding(
+            "heading-structure",
+            RuleSeverity::Warning,
+            None,
+            "SKILL.md has no headings".to_string(),
+            false,
+        ));
+    }
+}
+
+/// Where a relative link in the skill points
+#[derive(Debug, PartialEq)]
+enum LinkTarget {
+    Exists,
+    Missing,
+    /// Climbs out of the skill directory or is absolute
+    Outside,
+}
+
+fn resolve_link(skill_dir: &Path, target: &str) -> LinkTarget {
+    let relative = Path::new(target);
+    let mut depth = 0usize;
+    for component in relative.components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir if depth > 0 => depth -= 1,
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                return LinkTarget::Outside
+            }
+        }
+    }
+    let path = skill_dir.join(relative);
+    match (path.canonicalize(), skill_dir.canonicalize()) {
+        (Ok(resolved), Ok(root)) if resolved.starts_with(&root) => LinkTarget::Exists,
+        // A symlink that leads out of the skill
+        (Ok(_), Ok(_)) => LinkTarget::Outside,
+        _ => LinkTarget::Missing,
+    }
+}
+
+/// The spelling of a missing relative target that exists with `/`
+/// separators or a different letter case, when exactly one does
+fn corrected_target(skill_dir: &Path, target: &str) -> Option<String> {
+    let (prefix, rest) = match target.strip_prefix("./") {
+        Some(rest) => ("./", rest),
+        None => ("", target),
+    };
+    let mut dir = skill_dir.to_path_buf();
+    let mut parts = Vec::new();
+    for part in rest.split(['/', '\\']).filter(|p| !p.is_empty()) {
+        if part == "." || part == ".." {
+            return None;
+        }
+        let mut matches = std::fs::read_dir(&dir)
+            .ok()?
+            .filter_map(|e| e.ok())
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|name| name.eq_ignore_ascii_case(part));
+        let name = matches.next()?;
+        if matches.next().is_some() {
+            return None;
+        }
+        dir.push(&name);
+        parts.push(name);
+    }
+    let corrected = format!("{}{}", prefix, parts.join("/"));
+    (corrected != target && resolve_link(skill_dir, &corrected) == LinkTarget::Exists)
+        .then_some(corrected)
+}
+
+/// Whether a link target is a file path relative to the skill
+fn is_relative_target(target: &str) -> bool {
+    !(target.is_empty()
+        || target.starts_with('#')
+        || target.contains("://")
+        || target.starts_with("mailto:")
+        || target.starts_with("tel:"))
+}
+
+fn check_links(
+    lines: &mut [String],
+    body_start: usize,
+    skill_dir: &Path,
+    findings: &mut Vec<LintFinding>,
+) {
+    for i in prose_lines(lines, body_start) {
+        let line = lines[i].clone();
+        let targets = LINK_REGEX
+            .captures_iter(&line)
+            .chain(CODE_PATH_REGEX.captures_iter(&line))
+            .filter_map(|c| c.get(1))
+            .map(|m| m.as_str())
+            .filter(|t| is_relative_target(t));
+        for raw in targets {
+            let target = raw.split(['#', '?']).next().unwrap_or(raw);
+            match resolve_link(skill_dir, target) {
+                LinkTarget::Exists => {}
+                LinkTarget::Outside => findings.push(finding(
+                    "link-outside-skill",
+                    RuleSeverity::Error,
+                    Some(i + 1),
+                    format!("`{}` points outside the skill directory", target),
+                    false,
+                )),
+                LinkTarget::Missing => {
+                    let corrected = corrected_target(skill_dir, target);
+                    let message = match &corrected {
+                        Some(c) => format!("`{}` does not exist; did you mean `{}`?", target, c),
+                        None => format!("`{}` does not exist", target),
+                    };
+                    findings.push(finding(
+                        "broken-link",
+                        RuleSeverity::Error,
+                        Some(i + 1),
+                        message,
+                        corrected.is_some(),
+                    ));
+                    if let Some(corrected) = corrected {
+                        lines[i] = lines[i].replacen(target, &corrected, 1);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn check_line_length(
+    lines: &[String],
+    body_start: usize,
+    max_line_length: usize,
+    findings: &mut Vec<LintFinding>,
+) {
+    for i in prose_lines(lines, body_start) {
+        let line = &lines[i];
+        let length = line.trim_end().chars().count();
+        if length <= max_line_length || line.trim_start().starts_with('|') || line.contains("://") {
+            continue;
+        }
+        findings.push(finding(
+            "line-length",
+            RuleSeverity::Warning,
+            Some(i + 1),
+            format!(
+                "line is {} characters long (limit {})",
+                length, max_line_length
+            ),
+            false,
+        ));
+    }
+}
+
+/// Trailing whitespace other than Markdown's two-space hard line break
+fn trailing_whitespace(line: &str) -> Option<&str> {
+    let trimmed = line.trim_end();
+    let trailing = &line[trimmed.len()..];
+    if trailing.is_empty() || (trailing == "  " && !trimmed.is_empty()) {
+        return None;
+    }
+    Some(trimmed)
+}
+
+fn check_whitespace(lines: &mut [String], findings: &mut Vec<LintFinding>) {
+    for (i, line) in lines.iter_mut().enumerate() {
+        if let Some(trimmed) = trailing_whitespace(line) {
+            let trimmed = trimmed.to_string();
+            findings.push(finding(
+                "trailing-whitespace",
+                RuleSeverity::Warning,
+                Some(i + 1),
+                "trailing whitespace".to_string(),
+                true,
+            ));
+            *line = trimmed;
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const FRONTMATTER: &str = "---\nname: pdf\ndescription: Fill PDF forms. Use when the user \
+                               sends a PDF.\nversion: 1.0.0\nlicense: MIT\n---\n";
+
+    fn skill(body: &str) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("SKILL.md"),
+            format!("{}{}", FRONTMATTER, body),
+        )
+        .unwrap();
+        dir
+    }
+
+    fn rules(findings: &[LintFinding]) -> Vec<&'static str> {
+        findings.iter().map(|f| f.rule).collect()
+    }
+
+    #[test]
+    fn test_clean_skill_has_no_findings() {
+        let dir = skill("# PDF\n\nSee [forms](references/forms.md).\n\n## Usage\n\nRun it.\n");
+        std::fs::create_dir(dir.path().join("references")).unwrap();
+        std::fs::write(dir.path().join("references/forms.md"), "x").unwrap();
+        let lint = lint_skill(dir.path(), &LintOptions::default()).unwrap();
+        assert!(lint.findings.is_empty(), "{:?}", lint.findings);
+    }
+
+    #[test]
+    fn test_frontmatter_completeness_and_trigger() {
+        let (findings, _) = lint_content(
+            "---\nname: pdf\ndescription: Fills PDF forms.\n---\n# PDF\n",
+            Path::new("."),
+            DEFAULT_MAX_LINE_LENGTH,
+        );
+        assert_eq!(
+            rules(&findings),
+            vec!["frontmatter", "frontmatter", "description-trigger"]
+        );
+        assert_eq!(findings[2].line, Some(3));
+
+        let (findings, _) = lint_content("# PDF\n", Path::new("."), DEFAULT_MAX_LINE_LENGTH);
+        assert_eq!(findings[0].rule, "frontmatter");
+        assert_eq!(findings[0].severity, RuleSeverity::Error);
+    }
+
+    #[test]
+    fn test_fix_headings_and_whitespace() {
+        let dir = skill("##Usage \n\n#### Details\n\n```sh\n#comment\n```\nText  \nend");
+        let lint = lint_skill(
+            dir.path(),
+            &LintOptions {
+                fix: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let fixed = std::fs::read_to_string(dir.path().join("SKILL.md")).unwrap();
+        assert!(fixed.ends_with("## Usage\n\n### Details\n\n```sh\n#comment\n```\nText  \nend\n"));
+        assert_eq!(lint.fixed, 4);
+        // Only the missing title is left; it has no safe fix
+        assert_eq!(rules(&lint.findings), vec!["heading-structure"]);
+        assert!(!lint.findings[0].fixable);
+    }
+
+    #[test]
+    fn test_broken_link_fixed_by_case() {
+        let dir = skill("# PDF\n\nRun `scripts/Fill.py` or see [ref](references/missing.md).\n");
+        std::fs::create_dir(dir.path().join("scripts")).unwrap();
+        std::fs::write(dir.path().join("scripts/fill.py"), "").unwrap();
+
+        let lint = lint_skill(dir.path(), &LintOptions::default()).unwrap();
+        assert_eq!(rules(&lint.findings), vec!["broken-link", "broken-link"]);
+        assert!(lint.findings.iter().any(|f| f.fixable));
+
+        let options = LintOptions {
+            fix: true,
+            ..Default::default()
+        };
+        let lint = lint_skill(dir.path(), &options).unwrap();
+        assert_eq!(lint.fixed, 1);
+        assert_eq!(lint.findings.len(), 1);
+        assert!(lint.findings[0].message.contains("references/missing.md"));
+        let fixed = std::fs::read_to_string(dir.path().join("SKILL.md")).unwrap();
+        assert!(fixed.contains("`scripts/fill.py`"));
+    }
+
+    #[test]
+    fn test_link_outside_skill_is_an_error() {
+        let parent = TempDir::new().unwrap();
+        std::fs::write(parent.path().join("secret.md"), "x").unwrap();
+        let dir = parent.path().join("pdf");
+        std::fs::create_dir(&dir).unwrap();
+        std::fs::write(
+            dir.join("SKILL.md"),
+            format!(
+                "{}# PDF\n\n[a](../secret.md) [b](references/../../secret.md) [c](/etc/passwd)\n",
+                FRONTMATTER
+            ),
+        )
+        .unwrap();
+        let lint = lint_skill(&dir, &LintOptions::default()).unwrap();
+        assert_eq!(lint.errors(), 3);
+        assert!(lint
+            .findings
+            .iter()
+            .all(|f| f.rule == "link-outside-skill" && !f.fixable));
+    }
+
+    #[test]
+    fn test_line_length_skips_code_tables_and_urls() {
+        let long = "word ".repeat(30);
+        let long = long.trim_end();
+        let body = format!(
+            "# PDF\n\n{long}\n\n```\n{long}\n```\n\n| {long} |\n\nSee https://example.com/{long}\n"
+        );
+        let (findings, _) = lint_content(
+            &format!("{}{}", FRONTMATTER, body),
+            Path::new("."),
+            DEFAULT_MAX_LINE_LENGTH,
+        );
+        assert_eq!(rules(&findings), vec!["line-length"]);
+        assert_eq!(findings[0].line, Some(9));
+    }
+}
@@ -9,12 +9,16 @@ pub mod extension_check;
 pub mod field_validation;
 pub mod file_structure;
 pub mod frontmatter;
+pub mod lint;
+pub(crate) mod requirements;
 pub mod result;
+pub mod rules;
 pub mod skill_validator;
 pub mod standard_validator;
 pub mod zip_validator;
 
 pub use result::{ErrorSeverity, ValidationError, ValidationResult, ValidationWarning};
+pub use rules::{RuleSeverity, ValidationRules};
 pub use skill_validator::SkillValidator;
 pub use standard_validator::StandardValidator;
 pub use zip_validator::ZipValidator;
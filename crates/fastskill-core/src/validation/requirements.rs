@@ -0,0 +1,52 @@
+//! `requires` validation: constraints must parse, and the environment running
+//! the validator should meet them.
+
+use crate::core::metadata::parse_yaml_frontmatter;
+use crate::core::requirements::{check, declared_requirements, Environment, Runtime};
+use crate::validation::result::{ErrorSeverity, ValidationResult};
+use std::path::Path;
+
+/// Check the requirements declared by the skill in `skill_dir` whose SKILL.md
+/// reads `content`. A constraint that does not parse or an unmet FastSkill
+/// version is an error; a missing or mismatched Python or Node.js is only a
+/// warning, as the skill may be meant for another machine.
+pub(crate) async fn validate_content(
+    skill_dir: &Path,
+    content: &str,
+    mut result: ValidationResult,
+) -> ValidationResult {
+    // Unparseable frontmatter is reported by the frontmatter checks
+    let Ok(frontmatter) = parse_yaml_frontmatter(content) else {
+        return result;
+    };
+    let requires = match declared_requirements(skill_dir, &frontmatter) {
+        Ok(requires) => requires,
+        Err(e) => return result.with_error("requires", &e.to_string(), ErrorSeverity::Error),
+    };
+    if requires.is_empty() {
+        return result;
+    }
+
+    let invalid = requires.invalid();
+    for (_, message) in &invalid {
+        result = result.with_error("requires", message, ErrorSeverity::Error);
+    }
+    let Ok(env) = tokio::task::spawn_blocking(Environment::current).await else {
+        return result;
+    };
+    for violation in check(&requires, env) {
+        if invalid
+            .iter()
+            .any(|(runtime, _)| *runtime == violation.runtime)
+        {
+            continue;
+        }
+        let message = format!("Skill {}", violation.message);
+        result = if violation.runtime == Runtime::FastSkill {
+            result.with_error("requires", &message, ErrorSeverity::Error)
+        } else {
+            result.with_warning("requires", &message)
+        };
+    }
+    result
+}
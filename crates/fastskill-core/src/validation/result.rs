@@ -74,7 +74,7 @@ impl ValidationResult {
 }
 
 /// Validation error details
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct ValidationError {
     /// Field or component that failed validation
     pub field: String,
@@ -87,7 +87,7 @@ pub struct ValidationError {
 }
 
 /// Error severity levels
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
 pub enum ErrorSeverity {
     /// Warning - not critical but should be addressed
     Warning,
@@ -98,7 +98,7 @@ pub enum ErrorSeverity {
 }
 
 /// Validation warning details
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct ValidationWarning {
     /// Field or component with warning
     pub field: String,
@@ -0,0 +1,475 @@
+//! Dangerous-pattern rules for content safety validation
+//!
+//! The built-in rules can be tuned per skills directory in
+//! `<skills dir>/.fastskill/validation-rules.toml`:
+//!
+//! ```toml
+//! # Keep the built-in rules (default); false starts from an empty set
+//! defaults = true
+//! # Rules to turn off, built-in or custom
+//! disabled = ["shell-chown"]
+//!
+//! [[rules]]
+//! id = "curl-pipe-shell"
+//! pattern = 'curl\s[^|]*\|\s*(ba)?sh'
+//! severity = "error"      # "warning" (default) or "error"
+//! languages = ["shell"]   # default: every language
+//! message = "pipes a download into a shell"
+//!
+//! # Skills a rule does not apply to; works for built-in rules too
+//! [allow]
+//! python-import-os = ["file-organizer"]
+//! ```
+//!
+//! A custom rule with the id of a built-in one replaces it. Patterns are
+//! regular expressions matched one line at a time. Script languages are told
+//! from the file extension or shebang; full-line comments are skipped and a
+//! rule with `languages` only applies to scripts in those languages. SKILL.md,
+//! which embeds code in any language, and scripts whose language is unknown
+//! are checked against every rule.
+
+use crate::core::service::ServiceError;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// What a match does to the validation result
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleSeverity {
+    /// Advisory; the skill still validates
+    #[default]
+    Warning,
+    /// Fails validation
+    Error,
+}
+
+/// Script language, for rules that only make sense in some languages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptLanguage {
+    Python,
+    Shell,
+    JavaScript,
+    Ruby,
+    Go,
+    Rust,
+}
+
+impl ScriptLanguage {
+    const ALL: [ScriptLanguage; 6] = [
+        ScriptLanguage::Python,
+        ScriptLanguage::Shell,
+        ScriptLanguage::JavaScript,
+        ScriptLanguage::Ruby,
+        ScriptLanguage::Go,
+        ScriptLanguage::Rust,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ScriptLanguage::Python => "python",
+            ScriptLanguage::Shell => "shell",
+            ScriptLanguage::JavaScript => "javascript",
+            ScriptLanguage::Ruby => "ruby",
+            ScriptLanguage::Go => "go",
+            ScriptLanguage::Rust => "rust",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|l| l.as_str() == s.trim())
+    }
+
+    /// Language of the script at `path`, from its extension or, failing
+    /// that, the shebang on the first line of `content`.
+    pub fn detect(path: &Path, content: &str) -> Option<Self> {
+        let by_extension = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .and_then(|ext| match ext.as_str() {
+                "py" => Some(ScriptLanguage::Python),
+                "sh" | "bash" | "zsh" => Some(ScriptLanguage::Shell),
+                "js" | "mjs" | "cjs" | "ts" => Some(ScriptLanguage::JavaScript),
+                "rb" => Some(ScriptLanguage::Ruby),
+                "go" => Some(ScriptLanguage::Go),
+                "rs" => Some(ScriptLanguage::Rust),
+                _ => None,
+            });
+        if by_extension.is_some() {
+            return by_extension;
+        }
+        let shebang = content.lines().next()?.strip_prefix("#!")?;
+        let mut words = shebang.split_whitespace();
+        let mut interpreter = words.next()?.rsplit('/').next()?;
+        if interpreter == "env" {
+            interpreter = words.find(|w| !w.starts_with('-'))?;
+        }
+        match interpreter.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.') {
+            "python" => Some(ScriptLanguage::Python),
+            "node" | "deno" | "bun" => Some(ScriptLanguage::JavaScript),
+            "ruby" => Some(ScriptLanguage::Ruby),
+            "sh" | "bash" | "zsh" | "dash" | "ksh" => Some(ScriptLanguage::Shell),
+            _ => None,
+        }
+    }
+
+    /// Whether `line` is a comment and nothing else
+    fn is_comment(self, line: &str) -> bool {
+        let line = line.trim_start();
+        match self {
+            ScriptLanguage::Python | ScriptLanguage::Shell | ScriptLanguage::Ruby => {
+                line.starts_with('#')
+            }
+            ScriptLanguage::JavaScript | ScriptLanguage::Go | ScriptLanguage::Rust => {
+                line.starts_with("//")
+            }
+        }
+    }
+}
+
+/// One compiled rule
+#[derive(Debug, Clone)]
+pub struct ValidationRule {
+    pub id: String,
+    pub pattern: Regex,
+    pub severity: RuleSeverity,
+    /// Languages the rule applies to in scripts; empty for all
+    pub languages: Vec<ScriptLanguage>,
+    /// Shown in the finding; the pattern when not configured
+    pub message: String,
+    /// Skill ids the rule does not apply to
+    pub allow: Vec<String>,
+}
+
+impl ValidationRule {
+    fn applies_to(&self, skill_id: Option<&str>, language: Option<ScriptLanguage>) -> bool {
+        if skill_id.is_some_and(|id| self.allow.iter().any(|a| a == id)) {
+            return false;
+        }
+        match language {
+            Some(language) if !self.languages.is_empty() => self.languages.contains(&language),
+            _ => true,
+        }
+    }
+}
+
+/// A rule that matched, with the 1-based line of its first match
+#[derive(Debug, Clone, Copy)]
+pub struct RuleMatch<'a> {
+    pub rule: &'a ValidationRule,
+    pub line: usize,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RulesFile {
+    #[serde(default = "default_true")]
+    defaults: bool,
+    #[serde(default)]
+    disabled: Vec<String>,
+    #[serde(default)]
+    rules: Vec<RuleEntry>,
+    #[serde(default)]
+    allow: HashMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleEntry {
+    id: String,
+    pattern: String,
+    #[serde(default)]
+    severity: RuleSeverity,
+    #[serde(default)]
+    languages: Vec<String>,
+    message: Option<String>,
+    #[serde(default)]
+    allow: Vec<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// The rule set a [`crate::validation::SkillValidator`] checks content with
+#[derive(Debug, Clone)]
+pub struct ValidationRules {
+    rules: Vec<ValidationRule>,
+}
+
+impl Default for ValidationRules {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl ValidationRules {
+    /// Default location: `<skills dir>/.fastskill/validation-rules.toml`.
+    pub fn default_path(skills_dir: &Path) -> PathBuf {
+        skills_dir.join(".fastskill").join("validation-rules.toml")
+    }
+
+    /// The rules used when no rules file exists
+    pub fn builtin() -> Self {
+        // (id, pattern, languages, message)
+        const BUILTIN: &[(&str, &str, &[ScriptLanguage], &str)] = &[
+            (
+                "python-import-os",
+                r"^\s*(import\s+os\b|from\s+os\s+import\b)",
+                &[ScriptLanguage::Python],
+                "import os",
+            ),
+            (
+                "python-import-subprocess",
+                r"^\s*(import\s+subprocess\b|from\s+subprocess\s+import\b)",
+                &[ScriptLanguage::Python],
+                "import subprocess",
+            ),
+            (
+                "python-import-sys",
+                r"^\s*(import\s+sys\b|from\s+sys\s+import\b)",
+                &[ScriptLanguage::Python],
+                "import sys",
+            ),
+            ("dynamic-exec", r"\bexec\s*\(", &[], "exec("),
+            ("dynamic-eval", r"\beval\s*\(", &[], "eval("),
+            ("system-call", r"\bsystem\s*\(", &[], "system("),
+            ("popen-call", r"\b[pP]open\s*\(", &[], "popen("),
+            ("shell-rm-rf", r"\brm\s+-(rf|fr|Rf|fR)\b", &[], "rm -rf"),
+            ("shell-sudo", r"\bsudo\b", &[], "sudo"),
+            (
+                "shell-chmod-777",
+                r"\bchmod\s+(-R\s+)?777\b",
+                &[],
+                "chmod 777",
+            ),
+            ("shell-chown", r"\bchown\b", &[], "chown"),
+            (
+                "shell-su",
+                r"(^|[;&|]\s*|\bsudo\s+)su(\s+-|\s+\w|\s*$)",
+                &[],
+                "su ",
+            ),
+            ("shell-passwd", r"\bpasswd\b", &[], "passwd"),
+        ];
+        let rules = BUILTIN
+            .iter()
+            .filter_map(|(id, pattern, languages, message)| {
+                Some(ValidationRule {
+                    id: (*id).to_string(),
+                    pattern: Regex::new(pattern).ok()?,
+                    severity: RuleSeverity::Warning,
+                    languages: languages.to_vec(),
+                    message: (*message).to_string(),
+                    allow: Vec::new(),
+                })
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Read the rules file at `path`; a missing file means the built-in rules.
+    pub fn load(path: &Path) -> Result<Self, ServiceError> {
+        if !path.exists() {
+            return Ok(Self::builtin());
+        }
+        let content = std::fs::read_to_string(path)?;
+        Self::from_toml_str(&content).map_err(|e| {
+            ServiceError::Config(format!(
+                "Validation rules file {} is invalid: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+
+    /// Parse a rules file
+    pub fn from_toml_str(content: &str) -> Result<Self, String> {
+        let file: RulesFile = toml::from_str(content).map_err(|e| e.to_string())?;
+        let mut rules = if file.defaults {
+            Self::builtin().rules
+        } else {
+            Vec::new()
+        };
+
+        for entry in file.rules {
+            let pattern = Regex::new(&entry.pattern)
+                .map_err(|e| format!("rule '{}' has an invalid pattern: {}", entry.id, e))?;
+            let languages = entry
+                .languages
+                .iter()
+                .map(|l| {
+                    ScriptLanguage::parse(l).ok_or_else(|| {
+                        format!(
+                            "rule '{}' has unknown language '{}' (expected one of: {})",
+                            entry.id,
+                            l,
+                            ScriptLanguage::ALL.map(ScriptLanguage::as_str).join(", ")
+                        )
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let rule = ValidationRule {
+                message: entry.message.unwrap_or_else(|| entry.pattern.clone()),
+                id: entry.id,
+                pattern,
+                severity: entry.severity,
+                languages,
+                allow: entry.allow,
+            };
+            match rules.iter_mut().find(|r| r.id == rule.id) {
+                Some(existing) => *existing = rule,
+                None => rules.push(rule),
+            }
+        }
+
+        rules.retain(|r| !file.disabled.contains(&r.id));
+        for (id, skills) in file.allow {
+            let rule = rules
+                .iter_mut()
+                .find(|r| r.id == id)
+                .ok_or_else(|| format!("[allow] names unknown rule '{}'", id))?;
+            rule.allow.extend(skills);
+        }
+        Ok(Self { rules })
+    }
+
+    pub fn rules(&self) -> &[ValidationRule] {
+        &self.rules
+    }
+
+    /// Rules that match `content`, in rule order. `language` is the script's
+    /// language, or `None` for SKILL.md and scripts of unknown language.
+    pub fn scan(
+        &self,
+        content: &str,
+        skill_id: Option<&str>,
+        language: Option<ScriptLanguage>,
+    ) -> Vec<RuleMatch<'_>> {
+        let lines: Vec<&str> = content
+            .lines()
+            .map(|line| match language {
+                Some(language) if language.is_comment(line) => "",
+                _ => line,
+            })
+            .collect();
+        self.rules
+            .iter()
+            .filter(|rule| rule.applies_to(skill_id, language))
+            .filter_map(|rule| {
+                let index = lines.iter().position(|line| rule.pattern.is_match(line))?;
+                Some(RuleMatch {
+                    rule,
+                    line: index + 1,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn ids(matches: &[RuleMatch<'_>]) -> Vec<String> {
+        matches.iter().map(|m| m.rule.id.clone()).collect()
+    }
+
+    #[test]
+    fn test_builtin_rules_are_language_aware() {
+        let rules = ValidationRules::builtin();
+        assert_eq!(rules.rules().len(), 13);
+
+        let python =
+            "#!/usr/bin/env python3\n# sudo is mentioned here\nimport os\nprint(os.getcwd())\n";
+        let found = rules.scan(python, None, Some(ScriptLanguage::Python));
+        assert_eq!(ids(&found), ["python-import-os"]);
+        assert_eq!(found[0].line, 3);
+
+        // Python imports are not dangerous in a shell script's text
+        let shell = "echo 'import os'\nsudo rm -rf /tmp/x\n";
+        let found = rules.scan(shell, None, Some(ScriptLanguage::Shell));
+        assert_eq!(ids(&found), ["shell-rm-rf", "shell-sudo"]);
+
+        // SKILL.md is checked against every rule
+        let found = rules.scan("import subprocess\nissue tracker", None, None);
+        assert_eq!(ids(&found), ["python-import-subprocess"]);
+
+        assert_eq!(
+            ScriptLanguage::detect(Path::new("run"), "#!/bin/bash\necho"),
+            Some(ScriptLanguage::Shell)
+        );
+        assert_eq!(
+            ScriptLanguage::detect(Path::new("run"), "#!/usr/bin/env python3.11"),
+            Some(ScriptLanguage::Python)
+        );
+        assert_eq!(
+            ScriptLanguage::detect(Path::new("x.py"), ""),
+            Some(ScriptLanguage::Python)
+        );
+        assert_eq!(
+            ScriptLanguage::detect(Path::new("run"), "#!/usr/bin/fish"),
+            None
+        );
+        assert_eq!(ScriptLanguage::detect(Path::new("notes"), "text"), None);
+    }
+
+    #[test]
+    fn test_rules_file() {
+        let rules = ValidationRules::from_toml_str(
+            r#"
+disabled = ["shell-passwd"]
+
+[[rules]]
+id = "curl-pipe-shell"
+pattern = 'curl\s[^|]*\|\s*(ba)?sh'
+severity = "error"
+languages = ["shell"]
+message = "pipes a download into a shell"
+
+[[rules]]
+id = "shell-sudo"
+pattern = '\bsudo\s+-i\b'
+
+[allow]
+python-import-os = ["file-organizer"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(rules.rules().len(), 13);
+
+        let script = "curl -fsSL https://x.sh | bash\nsudo ls\npasswd\n";
+        let found = rules.scan(script, None, Some(ScriptLanguage::Shell));
+        assert_eq!(ids(&found), ["curl-pipe-shell"]);
+        assert_eq!(found[0].rule.severity, RuleSeverity::Error);
+        assert_eq!(found[0].rule.message, "pipes a download into a shell");
+        assert!(rules
+            .scan(script, None, Some(ScriptLanguage::Python))
+            .is_empty());
+
+        let python = "import os\n";
+        assert!(rules
+            .scan(python, Some("file-organizer"), Some(ScriptLanguage::Python))
+            .is_empty());
+        assert_eq!(rules.scan(python, Some("other"), None).len(), 1);
+
+        let only_custom = ValidationRules::from_toml_str(
+            "defaults = false\n[[rules]]\nid = \"x\"\npattern = \"y\"",
+        )
+        .unwrap();
+        assert_eq!(ids(&only_custom.scan("y", None, None)), ["x"]);
+        assert_eq!(only_custom.rules()[0].message, "y");
+
+        let bad_pattern = "[[rules]]\nid = \"x\"\npattern = \"(\"";
+        assert!(ValidationRules::from_toml_str(bad_pattern)
+            .unwrap_err()
+            .contains("invalid pattern"));
+        let bad_language = "[[rules]]\nid = \"x\"\npattern = \"y\"\nlanguages = [\"cobol\"]";
+        assert!(ValidationRules::from_toml_str(bad_language)
+            .unwrap_err()
+            .contains("cobol"));
+        assert!(ValidationRules::from_toml_str("[allow]\nnope = [\"a\"]").is_err());
+    }
+}
@@ -8,7 +8,9 @@ use crate::validation::extension_check::{self, ExtensionCheckConfig, ExtensionPr
 use crate::validation::field_validation;
 use crate::validation::file_structure;
 use crate::validation::frontmatter;
+use crate::validation::requirements;
 use crate::validation::result::{ErrorSeverity, ValidationResult};
+use crate::validation::rules::ValidationRules;
 use std::path::Path;
 use tokio::fs;
 
@@ -23,8 +25,8 @@ pub struct SkillValidator {
     /// Required fields that must be present
     required_fields: Vec<String>,
 
-    /// Dangerous patterns to check for in content
-    dangerous_patterns: Vec<String>,
+    /// Dangerous-pattern rules to check content against
+    rules: ValidationRules,
 }
 
 impl Default for SkillValidator {
@@ -44,7 +46,7 @@ impl SkillValidator {
                 "description".to_string(),
                 "version".to_string(),
             ],
-            dangerous_patterns: content_safety::default_dangerous_patterns(),
+            rules: ValidationRules::builtin(),
         }
     }
 
@@ -58,10 +60,16 @@ impl SkillValidator {
             max_file_size_mb,
             max_description_length,
             required_fields,
-            dangerous_patterns: content_safety::default_dangerous_patterns(),
+            rules: ValidationRules::builtin(),
         }
     }
 
+    /// Check content against `rules` instead of the built-in rules
+    pub fn with_rules(mut self, rules: ValidationRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
     /// Validate a skill definition comprehensively
     pub async fn validate_skill(
         &self,
@@ -97,11 +105,13 @@ impl SkillValidator {
         skill: &SkillDefinition,
         result: ValidationResult,
     ) -> Result<ValidationResult, ServiceError> {
+        let skill_id = skill.id.to_string();
         let result = if skill.skill_file.exists() {
             content_safety::validate_skill_file_content(
                 &skill.skill_file,
                 result,
-                &self.dangerous_patterns,
+                &self.rules,
+                Some(&skill_id),
             )
             .await?
         } else {
@@ -114,7 +124,8 @@ impl SkillValidator {
                 result = content_safety::validate_script_file_content(
                     script_file,
                     result,
-                    &self.dangerous_patterns,
+                    &self.rules,
+                    Some(&skill_id),
                 )
                 .await?;
             }
@@ -143,6 +154,9 @@ impl SkillValidator {
             }
         };
         result = frontmatter::validate_content(&content, result);
+        if let Some(skill_dir) = skill.skill_file.parent() {
+            result = requirements::validate_content(skill_dir, &content, result).await;
+        }
         Ok(result)
     }
 
@@ -157,12 +171,33 @@ impl SkillValidator {
         if !skill_path.is_dir() {
             return Ok(ValidationResult::invalid("Skill path is not a directory"));
         }
-        let result = dir_structure::ensure_skill_md_exists(skill_path, ValidationResult::valid());
+        let skill_id = skill_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string());
+        let mut result =
+            dir_structure::ensure_skill_md_exists(skill_path, ValidationResult::valid());
+        let skill_file = skill_path.join("SKILL.md");
+        if skill_file.is_file() {
+            result = content_safety::validate_skill_file_content(
+                &skill_file,
+                result,
+                &self.rules,
+                skill_id.as_deref(),
+            )
+            .await?;
+            if let Ok(content) = fs::read_to_string(&skill_file).await {
+                result = requirements::validate_content(skill_path, &content, result).await;
+            }
+        }
         let (has_scripts, has_references, has_assets, mut result) =
             dir_structure::scan_skill_directory_entries(skill_path, result).await?;
         if has_scripts {
             result = self
-                .validate_scripts_directory(&skill_path.join("scripts"), result)
+                .validate_scripts_directory(
+                    &skill_path.join("scripts"),
+                    result,
+                    skill_id.as_deref(),
+                )
                 .await?;
         }
         if has_references {
@@ -196,18 +231,34 @@ impl SkillValidator {
         Ok(result)
     }
 
-    /// Validate scripts directory
+    /// Validate scripts directory: extensions and content safety
     async fn validate_scripts_directory(
         &self,
         scripts_path: &Path,
         result: ValidationResult,
+        skill_id: Option<&str>,
     ) -> Result<ValidationResult, ServiceError> {
-        self.validate_extension_directory(
-            scripts_path,
-            result,
-            extension_check::extension_config(ExtensionPreset::Scripts),
-        )
-        .await
+        let mut result = self
+            .validate_extension_directory(
+                scripts_path,
+                result,
+                extension_check::extension_config(ExtensionPreset::Scripts),
+            )
+            .await?;
+        let mut entries = fs::read_dir(scripts_path).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.is_file() {
+                result = content_safety::validate_script_file_content(
+                    &path,
+                    result,
+                    &self.rules,
+                    skill_id,
+                )
+                .await?;
+            }
+        }
+        Ok(result)
     }
 
     /// Validate references directory
@@ -1,31 +1,62 @@
 //! ZIP package validation implementation
 
 use crate::core::service::ServiceError;
-use crate::storage::zip::ZipHandler;
+use crate::storage::zip::{ZipHandler, ZipLimits, ZipViolation};
+use crate::validation::result::{ErrorSeverity, ValidationResult};
 use std::path::Path;
 
-pub struct ZipValidator;
-
-impl Default for ZipValidator {
-    fn default() -> Self {
-        Self::new()
-    }
+#[derive(Default)]
+pub struct ZipValidator {
+    handler: ZipHandler,
 }
 
 impl ZipValidator {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Validator enforcing `limits` instead of the defaults
+    pub fn with_limits(limits: ZipLimits) -> Self {
+        Self {
+            handler: ZipHandler::with_limits(limits),
+        }
     }
 
     /// Validate ZIP package.
     ///
-    /// Runs the entry-count / declared-size / compression-ratio pre-flight checks
-    /// (SEC-3) so an oversized or bomb-shaped archive is rejected before extraction.
+    /// Runs the entry-count / declared-size / compression-ratio / path pre-flight
+    /// checks (SEC-3) so an oversized, bomb-shaped or escaping archive is rejected
+    /// before extraction.
     ///
     /// Delegates to [`ZipHandler::validate_package`], which owns the single
     /// open + construct + preflight implementation.
     pub async fn validate_zip_package(&self, zip_path: &Path) -> Result<(), ServiceError> {
-        ZipHandler::new()?.validate_package(zip_path).await
+        self.handler.validate_package(zip_path).await
+    }
+
+    /// Every violation in the package, one `ValidationResult` error each with
+    /// the violation kind as its field (`zip.path_traversal`, ...). Escaping
+    /// paths and symlinks are critical; exceeded limits are errors.
+    pub async fn validate_zip_report(
+        &self,
+        zip_path: &Path,
+    ) -> Result<ValidationResult, ServiceError> {
+        let violations = self.handler.package_violations(zip_path)?;
+        Ok(violations
+            .iter()
+            .fold(ValidationResult::valid(), |result, violation| {
+                let severity = match violation {
+                    ZipViolation::PathTraversal { .. } | ZipViolation::Symlink { .. } => {
+                        ErrorSeverity::Critical
+                    }
+                    _ => ErrorSeverity::Error,
+                };
+                result.with_error(
+                    &format!("zip.{}", violation.kind()),
+                    &violation.to_string(),
+                    severity,
+                )
+            }))
     }
 }
 
@@ -118,6 +149,27 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_validate_zip_report_lists_violations() {
+        let (_t, zip_path) = write_zip(&[("SKILL.md", b"ok"), ("../evil.txt", b"x")]);
+        let validator = ZipValidator::with_limits(ZipLimits {
+            max_total_uncompressed: 2,
+            ..Default::default()
+        });
+        let report = validator.validate_zip_report(&zip_path).await.unwrap();
+        assert!(!report.is_valid);
+        let fields: Vec<&str> = report.errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["zip.path_traversal", "zip.total_too_large"]);
+        assert_eq!(report.errors[0].severity, ErrorSeverity::Critical);
+
+        let (_t, clean) = write_zip(&[("SKILL.md", b"ok")]);
+        let report = ZipValidator::new()
+            .validate_zip_report(&clean)
+            .await
+            .unwrap();
+        assert!(report.is_valid);
+    }
+
     #[tokio::test]
     async fn test_zip_validator_default() {
         #[allow(clippy::default_constructed_unit_structs)]
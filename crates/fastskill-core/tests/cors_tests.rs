@@ -37,6 +37,10 @@ fn test_build_cors_layer_empty_origins() {
         http_server: Some(HttpServerConfig {
             allowed_origins: vec![],
             allowed_headers: vec!["Content-Type".to_string(), "Authorization".to_string()],
+            client_profiles: Vec::new(),
+            rate_limit: Default::default(),
+            injection_rules: Default::default(),
+            jobs: Default::default(),
         }),
         ..Default::default()
     };
@@ -57,6 +61,10 @@ fn test_build_cors_layer_with_origins() {
                 "http://localhost:3000".to_string(),
             ],
             allowed_headers: vec!["Content-Type".to_string(), "Authorization".to_string()],
+            client_profiles: Vec::new(),
+            rate_limit: Default::default(),
+            injection_rules: Default::default(),
+            jobs: Default::default(),
         }),
         ..Default::default()
     };
@@ -113,6 +121,44 @@ allowed_headers = ["Content-Type", "Authorization", "X-API-Key"]
         .contains(&"X-API-Key".to_string()));
 }
 
+#[test]
+fn test_load_server_client_profiles_from_toml() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_file = temp_dir.path().join("skill-project.toml");
+
+    let content = r#"
+[dependencies]
+
+[tool.fastskill.server]
+allowed_origins = ["https://example.com"]
+
+[[tool.fastskill.server.clients]]
+name = "ci-agent"
+dynamic_min_relevance = 0.6
+max_dynamic_skills = 3
+allowed_scopes = ["acme/*"]
+
+[[tool.fastskill.server.clients]]
+name = "trusted"
+api_key_env = "TRUSTED_AGENT_KEY"
+    "#;
+
+    fs::write(&project_file, content).unwrap();
+
+    let project = SkillProjectToml::load_from_file(&project_file).unwrap();
+    let server_config = project.tool.unwrap().fastskill.unwrap().server.unwrap();
+
+    assert_eq!(server_config.clients.len(), 2);
+    let ci = &server_config.clients[0];
+    assert_eq!(ci.name, "ci-agent");
+    assert_eq!(ci.dynamic_min_relevance, Some(0.6));
+    assert_eq!(ci.max_dynamic_skills, Some(3));
+    assert_eq!(ci.allowed_scopes, vec!["acme/*".to_string()]);
+    let trusted = &server_config.clients[1];
+    assert_eq!(trusted.api_key_env.as_deref(), Some("TRUSTED_AGENT_KEY"));
+    assert!(trusted.allowed_scopes.is_empty());
+}
+
 #[test]
 fn test_load_server_config_with_invalid_origins() {
     let temp_dir = TempDir::new().unwrap();
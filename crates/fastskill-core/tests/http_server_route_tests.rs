@@ -3,14 +3,17 @@
 //! Two groups:
 //!  1. Spawned-server (reqwest) tests for pieces only reachable through the real
 //!     `serve()` wiring: embedded static assets, the root dashboard fallback, and
-//!     the `/index` registry mount.
+//!     the `/index` registry mount, the peer address the per-IP rate
+//!     limit keys on, and a follower syncing from a replication primary.
 //!  2. Direct unit tests for the public `build_cors_layer` (all origin/header
 //!     branches incl. the SEC-10 wildcard guard) and for address normalization /
 //!     parsing via the `FastSkillServer` constructors.
 
 #![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
 
-use fastskill_core::core::service::HttpServerConfig;
+use fastskill_core::core::integrity::IntegrityGuard;
+use fastskill_core::core::replication::{Follower, ReplicationKey};
+use fastskill_core::core::service::{HttpServerConfig, RateLimit, RateLimitConfig};
 use fastskill_core::http::server::{build_cors_layer, FastSkillServer};
 use fastskill_core::{FastSkillService, ServiceConfig};
 use std::fs;
@@ -148,6 +151,109 @@ async fn static_assets_and_dashboard_and_index_mount() {
     handle.abort();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn per_ip_rate_limit_sees_the_peer_address() {
+    let storage = TempDir::new().unwrap();
+    let store = storage.path().join("store");
+    fs::create_dir_all(&store).unwrap();
+    let Some(port) = free_port() else {
+        return;
+    };
+    let config = ServiceConfig {
+        skill_storage_path: store,
+        http_server: Some(HttpServerConfig {
+            rate_limit: RateLimitConfig {
+                per_token: None,
+                per_ip: Some(RateLimit {
+                    requests_per_minute: 1,
+                    burst: Some(1),
+                }),
+            },
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let mut svc = FastSkillService::new(config).await.unwrap();
+    svc.initialize().await.unwrap();
+    let server = FastSkillServer::new(Arc::new(svc), "127.0.0.1", port);
+    let handle = tokio::spawn(async move {
+        let _ = server.serve().await;
+    });
+    assert!(wait_for_port(port, 10), "server failed to start");
+
+    // serve() hands the peer address to the limiter: a burst of one
+    let client = reqwest::Client::new();
+    let url = format!("http://127.0.0.1:{port}/api/v1/status");
+    let first = client.get(&url).send().await.unwrap();
+    assert_eq!(first.status(), reqwest::StatusCode::OK);
+    let second = client.get(&url).send().await.unwrap();
+    assert_eq!(second.status(), reqwest::StatusCode::TOO_MANY_REQUESTS);
+    assert!(second.headers().contains_key(reqwest::header::RETRY_AFTER));
+
+    handle.abort();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn guarded_follower_accepts_synced_updates() {
+    const SECRET: &str = "0123456789abcdef0123456789abcdef";
+    let storage = TempDir::new().unwrap();
+    let primary_store = storage.path().join("primary");
+    let follower_store = storage.path().join("follower");
+    let write_demo = |description: &str| {
+        let dir = primary_store.join("demo-skill");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            format!("---\nname: demo-skill\ndescription: {description}\n---\n# Demo\n"),
+        )
+        .unwrap();
+    };
+    write_demo("First release");
+    fs::create_dir_all(&follower_store).unwrap();
+
+    let Some(port) = free_port() else {
+        return;
+    };
+    let primary = make_service(primary_store.clone(), None).await;
+    let server = FastSkillServer::new(primary, "127.0.0.1", port)
+        .replication_key(ReplicationKey::new(SECRET).unwrap());
+    let handle = tokio::spawn(async move {
+        let _ = server.serve().await;
+    });
+    assert!(wait_for_port(port, 10), "server failed to start");
+
+    let follower_service = make_service(follower_store.clone(), None).await;
+    let guard = Arc::new(IntegrityGuard::snapshot(&follower_store).unwrap());
+    let mut follower = Follower::new(
+        &format!("http://127.0.0.1:{port}"),
+        ReplicationKey::new(SECRET).unwrap(),
+    )
+    .unwrap()
+    .with_integrity_guard(Arc::clone(&guard));
+    let skill_file = follower_store.join("demo-skill").join("SKILL.md");
+
+    assert_eq!(
+        follower.sync_once(&follower_service).await.unwrap().fetched,
+        1
+    );
+    // Served once, so the guard records the first release
+    guard
+        .verify("demo-skill", &fs::read(&skill_file).unwrap())
+        .unwrap();
+
+    write_demo("Second release");
+    assert_eq!(
+        follower.sync_once(&follower_service).await.unwrap().fetched,
+        1
+    );
+    let synced = fs::read(&skill_file).unwrap();
+    assert!(String::from_utf8_lossy(&synced).contains("Second release"));
+    guard.verify("demo-skill", &synced).unwrap();
+    assert!(!guard.is_quarantined("demo-skill"));
+
+    handle.abort();
+}
+
 // ---------------------------------------------------------------------------
 // build_cors_layer branch coverage
 // ---------------------------------------------------------------------------
@@ -159,6 +265,10 @@ fn cors_wildcard_origin_falls_back_to_deny_all() {
         http_server: Some(HttpServerConfig {
             allowed_origins: vec!["*".to_string()],
             allowed_headers: vec!["Content-Type".to_string()],
+            client_profiles: Vec::new(),
+            rate_limit: Default::default(),
+            injection_rules: Default::default(),
+            jobs: Default::default(),
         }),
         ..Default::default()
     };
@@ -172,6 +282,10 @@ fn cors_invalid_origin_value_falls_back() {
             // Newline is not a valid header value -> parse_origins errors.
             allowed_origins: vec!["https://ok.com".to_string(), "bad\norigin".to_string()],
             allowed_headers: vec!["Content-Type".to_string()],
+            client_profiles: Vec::new(),
+            rate_limit: Default::default(),
+            injection_rules: Default::default(),
+            jobs: Default::default(),
         }),
         ..Default::default()
     };
@@ -185,6 +299,10 @@ fn cors_invalid_header_name_uses_fallback_headers() {
             allowed_origins: vec!["https://ok.com".to_string()],
             // Space/newline is not a valid header name -> parse_headers errors.
             allowed_headers: vec!["bad header\n".to_string()],
+            client_profiles: Vec::new(),
+            rate_limit: Default::default(),
+            injection_rules: Default::default(),
+            jobs: Default::default(),
         }),
         ..Default::default()
     };
@@ -197,6 +315,10 @@ fn cors_valid_origins_and_headers_credentialed() {
         http_server: Some(HttpServerConfig {
             allowed_origins: vec!["https://a.com".to_string(), "https://b.com".to_string()],
             allowed_headers: vec!["X-Custom".to_string(), "Authorization".to_string()],
+            client_profiles: Vec::new(),
+            rate_limit: Default::default(),
+            injection_rules: Default::default(),
+            jobs: Default::default(),
         }),
         ..Default::default()
     };
@@ -29,9 +29,14 @@ fn test_init_creates_skill_project_toml_with_both_sections() {
             author: None,
             download_url: None,
             name: None,
+            exclude: None,
         }),
         dependencies: Some(DependenciesSection { dependencies: deps }),
         tool: None,
+        optional_dependencies: None,
+        peer_dependencies: None,
+        requires: None,
+        env: None,
     };
 
     project.save_to_file(&project_path).unwrap();
@@ -60,9 +65,14 @@ fn test_init_without_skill_md() {
             author: None,
             download_url: None,
             name: None,
+            exclude: None,
         }),
         dependencies: None,
         tool: None,
+        optional_dependencies: None,
+        peer_dependencies: None,
+        requires: None,
+        env: None,
     };
 
     project.save_to_file(&project_path).unwrap();
@@ -108,9 +118,14 @@ capabilities: [testing]
             author: Some("Test Author".to_string()),
             download_url: None,
             name: None,
+            exclude: None,
         }),
         dependencies: None,
         tool: None,
+        optional_dependencies: None,
+        peer_dependencies: None,
+        requires: None,
+        env: None,
     };
 
     let project_path = temp_dir.path().join("skill-project.toml");
@@ -137,9 +152,14 @@ fn test_package_command_uses_metadata_section() {
             author: Some("Package Author".to_string()),
             download_url: None,
             name: None,
+            exclude: None,
         }),
         dependencies: None,
         tool: None,
+        optional_dependencies: None,
+        peer_dependencies: None,
+        requires: None,
+        env: None,
     };
 
     project.save_to_file(&project_path).unwrap();
@@ -167,9 +187,14 @@ fn test_package_command_reads_metadata_from_skill_project_toml() {
             author: Some("Package Author".to_string()),
             download_url: None,
             name: None,
+            exclude: None,
         }),
         dependencies: None,
         tool: None,
+        optional_dependencies: None,
+        peer_dependencies: None,
+        requires: None,
+        env: None,
     };
 
     project.save_to_file(&project_path).unwrap();
@@ -291,6 +291,7 @@ test-skill = "1.0.0"
             author: None,
             download_url: None,
             name: Some("test-project".to_string()),
+            exclude: None,
         }),
         dependencies: Some(DependenciesSection {
             dependencies: {
@@ -307,6 +308,10 @@ test-skill = "1.0.0"
             },
         }),
         tool: None,
+        optional_dependencies: None,
+        peer_dependencies: None,
+        requires: None,
+        env: None,
     };
 
     // Test 6: Verify serialization works
@@ -0,0 +1,157 @@
+//! Property tests for parsers that handle user or network content
+//!
+//! Malformed input must produce a structured error (or be skipped), never a
+//! panic. The same parsers have cargo-fuzz targets in `fuzz/`; these
+//! strategies keep a quick version of that check in the normal test run.
+
+#![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+use fastskill_core::core::metadata::parse_yaml_frontmatter;
+use fastskill_core::core::registry::client::parse_index_entries;
+use fastskill_core::core::registry_index::{parse_index_lines, VersionEntry};
+use fastskill_core::storage::zip::ZipHandler;
+use proptest::prelude::*;
+use std::collections::HashMap;
+use std::io::{Cursor, Write};
+use tempfile::TempDir;
+use walkdir::WalkDir;
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+/// Frontmatter with a `name` and `description` that YAML reads back as plain
+/// strings, followed by an arbitrary body.
+fn skill_md() -> impl Strategy<Value = (String, String, String)> {
+    (
+        "[a-z][a-z0-9-]{0,30}",
+        "[A-Za-z0-9]([A-Za-z0-9 ,.]{0,78}[A-Za-z0-9.])?",
+        any::<String>(),
+    )
+        .prop_map(|(name, description, body)| {
+            (
+                format!("skill-{}", name),
+                format!("Does {}", description),
+                body,
+            )
+        })
+}
+
+/// Archive entry names mixing ordinary, traversal, absolute and empty segments.
+fn entry_name() -> impl Strategy<Value = String> {
+    prop::collection::vec(
+        prop_oneof![
+            Just("..".to_string()),
+            Just(".".to_string()),
+            Just(String::new()),
+            "[a-z]{1,8}",
+            "[a-z]{1,8}\\.md",
+        ],
+        1..6,
+    )
+    .prop_flat_map(|segments| {
+        let path = segments.join("/");
+        prop_oneof![Just(path.clone()), Just(format!("/{}", path))]
+    })
+}
+
+fn build_zip(names: &[String]) -> Vec<u8> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = FileOptions::default().compression_method(zip::CompressionMethod::Stored);
+    for name in names {
+        if name.ends_with('/') {
+            let _ = zip.add_directory(name.as_str(), options);
+        } else if zip.start_file(name.as_str(), options).is_ok() {
+            zip.write_all(b"payload").unwrap();
+        }
+    }
+    zip.finish().unwrap().into_inner()
+}
+
+fn version_entry() -> impl Strategy<Value = VersionEntry> {
+    (
+        "[a-z]{1,10}/[a-z]{1,10}",
+        "[0-9]{1,2}\\.[0-9]{1,2}\\.[0-9]{1,2}",
+        any::<bool>(),
+    )
+        .prop_map(|(name, vers, yanked)| VersionEntry {
+            name,
+            vers,
+            deps: Vec::new(),
+            cksum: "sha256:00".to_string(),
+            features: HashMap::new(),
+            yanked,
+            links: None,
+            download_url: "https://example.com/skill.zip".to_string(),
+            published_at: "2024-01-01T00:00:00Z".to_string(),
+            metadata: None,
+            scoped_name: None,
+        })
+}
+
+proptest! {
+    #[test]
+    fn frontmatter_never_panics(content in any::<String>()) {
+        let _ = parse_yaml_frontmatter(&content);
+    }
+
+    #[test]
+    fn frontmatter_reads_back_generated_fields((name, description, body) in skill_md()) {
+        let content = format!("---\nname: {}\ndescription: {}\n---\n{}", name, description, body);
+        let parsed = parse_yaml_frontmatter(&content).unwrap();
+        prop_assert_eq!(parsed.name, name);
+        prop_assert_eq!(parsed.description, description);
+    }
+
+    #[test]
+    fn registry_index_never_panics(content in any::<String>()) {
+        let _ = parse_index_lines(&content, "acme/skill");
+        let _ = parse_index_entries(&content);
+    }
+
+    #[test]
+    fn registry_index_skips_only_corrupt_lines(
+        entries in prop::collection::vec(version_entry(), 0..8),
+        garbage in prop::collection::vec("[^\n]{0,40}", 0..8),
+    ) {
+        let mut lines: Vec<String> = entries
+            .iter()
+            .map(|e| serde_json::to_string(e).unwrap())
+            .collect();
+        // Garbage lines that happen to be valid JSON objects can't be entries:
+        // every required field is missing.
+        lines.extend(garbage);
+        let parsed = parse_index_lines(&lines.join("\n"), "acme/skill");
+        prop_assert_eq!(parsed.len(), entries.len());
+        prop_assert_eq!(parse_index_entries(&lines.join("\n")).len(), entries.len());
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn zip_extract_never_panics(data in prop::collection::vec(any::<u8>(), 0..512)) {
+        let dest = TempDir::new().unwrap();
+        let _ = ZipHandler::new().unwrap().extract_reader_to_dir(Cursor::new(data), dest.path());
+    }
+
+    #[test]
+    fn zip_extract_stays_under_dest(names in prop::collection::vec(entry_name(), 1..6)) {
+        let base = TempDir::new().unwrap();
+        let dest = base.path().join("dest");
+        std::fs::create_dir_all(&dest).unwrap();
+
+        let _ = ZipHandler::new()
+            .unwrap()
+            .extract_reader_to_dir(Cursor::new(build_zip(&names)), &dest);
+
+        for entry in WalkDir::new(base.path()).min_depth(1) {
+            let entry = entry.unwrap();
+            prop_assert!(
+                entry.path().starts_with(&dest),
+                "{} written outside the extraction root for entries {:?}",
+                entry.path().display(),
+                names
+            );
+        }
+    }
+}
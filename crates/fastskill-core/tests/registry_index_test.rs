@@ -352,3 +352,61 @@ async fn test_concurrent_reads_no_locking() {
         );
     }
 }
+
+#[tokio::test]
+async fn test_yank_and_unyank_version() {
+    use fastskill_core::core::registry_index::{
+        scan_registry_index, set_version_yanked, update_skill_version, ListSkillsOptions,
+        VersionMetadata, GENERATION_FILE,
+    };
+    use fastskill_core::ServiceError;
+
+    let temp_dir = TempDir::new().unwrap();
+    let registry_path = temp_dir.path();
+    let skill_id = "acme/yankable";
+
+    for version in ["1.0.0", "1.1.0"] {
+        let metadata = VersionMetadata {
+            name: skill_id.to_string(),
+            vers: version.to_string(),
+            deps: vec![],
+            cksum: format!("sha256:{}", version),
+            features: HashMap::new(),
+            yanked: false,
+            links: None,
+            download_url: format!("https://example.com/yankable-{}.zip", version),
+            published_at: "2024-01-01T00:00:00Z".to_string(),
+            metadata: None,
+        };
+        update_skill_version(skill_id, version, &metadata, registry_path).unwrap();
+    }
+    let generation = std::fs::read_to_string(registry_path.join(GENERATION_FILE)).unwrap();
+
+    let entry = set_version_yanked(registry_path, skill_id, "1.1.0", true).unwrap();
+    assert!(entry.yanked);
+    assert_eq!(entry.cksum, "sha256:1.1.0");
+
+    // Rewritten in place: still one line per version
+    let entries = read_skill_versions(registry_path, skill_id).unwrap();
+    assert_eq!(entries.len(), 2);
+    assert!(!entries[0].yanked);
+    assert!(entries[1].yanked);
+    assert_ne!(
+        std::fs::read_to_string(registry_path.join(GENERATION_FILE)).unwrap(),
+        generation
+    );
+
+    let summaries = scan_registry_index(registry_path, &ListSkillsOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(summaries[0].latest_version, "1.0.0");
+
+    set_version_yanked(registry_path, skill_id, "1.1.0", false).unwrap();
+    let entries = read_skill_versions(registry_path, skill_id).unwrap();
+    assert!(entries.iter().all(|e| !e.yanked));
+
+    let missing = set_version_yanked(registry_path, skill_id, "9.9.9", true).unwrap_err();
+    assert!(matches!(missing, ServiceError::SkillNotFound(_)));
+    let unknown = set_version_yanked(registry_path, "acme/unknown", "1.0.0", true).unwrap_err();
+    assert!(matches!(unknown, ServiceError::SkillNotFound(_)));
+}
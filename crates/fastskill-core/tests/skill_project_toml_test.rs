@@ -18,9 +18,14 @@ fn test_skill_project_toml_serialization_with_metadata_only() {
             author: Some("Test Author".to_string()),
             download_url: None,
             name: None,
+            exclude: None,
         }),
         dependencies: None,
         tool: None,
+        optional_dependencies: None,
+        peer_dependencies: None,
+        requires: None,
+        env: None,
     };
 
     let toml_string = toml::to_string_pretty(&project).unwrap();
@@ -49,6 +54,7 @@ fn test_skill_project_toml_serialization_with_dependencies_only() {
                 ),
             },
             groups: None,
+            extras: None,
         },
     );
 
@@ -56,6 +62,10 @@ fn test_skill_project_toml_serialization_with_dependencies_only() {
         metadata: None,
         dependencies: Some(DependenciesSection { dependencies: deps }),
         tool: None,
+        optional_dependencies: None,
+        peer_dependencies: None,
+        requires: None,
+        env: None,
     };
 
     let toml_string = toml::to_string_pretty(&project).unwrap();
@@ -85,9 +95,14 @@ fn test_skill_project_toml_serialization_with_both_sections() {
             author: None,
             download_url: None,
             name: None,
+            exclude: None,
         }),
         dependencies: Some(DependenciesSection { dependencies: deps }),
         tool: None,
+        optional_dependencies: None,
+        peer_dependencies: None,
+        requires: None,
+        env: None,
     };
 
     let toml_string = toml::to_string_pretty(&project).unwrap();
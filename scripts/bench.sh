@@ -0,0 +1,118 @@
+#!/bin/bash
+
+# Benchmark Runner Script
+# Runs the fastskill-core criterion benches, saves them as a named criterion
+# baseline and writes a markdown summary (with machine details) to
+# benchmarks/results/<baseline>.md so results can be shared and compared.
+#
+# Usage: ./scripts/bench.sh [OPTIONS] [-- CRITERION_ARGS...]
+#
+# Options:
+#   -b, --baseline NAME   Baseline name (default: <hostname>-<UTC date>).
+#   -c, --compare NAME    Compare against a previously saved baseline instead of saving one.
+#   -f, --filter REGEX    Only run benchmarks whose id matches REGEX.
+#   -h, --help            Show this help message.
+#
+# Criterion keeps full reports (including HTML) under target/criterion/.
+
+set -e  # Exit on any error
+
+TOP="$(cd "$(dirname "$0")/.." && pwd)"
+BASELINE="$(hostname -s 2>/dev/null || echo local)-$(date -u +"%Y%m%d")"
+COMPARE=""
+FILTER=""
+EXTRA_ARGS=()
+
+error_exit() {
+    echo "Error: $1" >&2
+    echo "Usage: $0 [-b NAME] [-c NAME] [-f REGEX] [-- CRITERION_ARGS...]" >&2
+    exit 1
+}
+
+while [[ $# -gt 0 ]]; do
+    case $1 in
+        -b|--baseline)
+            [[ -n "$2" ]] || error_exit "--baseline needs a name"
+            BASELINE="$2"
+            shift 2
+            ;;
+        -c|--compare)
+            [[ -n "$2" ]] || error_exit "--compare needs a name"
+            COMPARE="$2"
+            shift 2
+            ;;
+        -f|--filter)
+            [[ -n "$2" ]] || error_exit "--filter needs a pattern"
+            FILTER="$2"
+            shift 2
+            ;;
+        -h|--help)
+            sed -n '3,15p' "$0" | sed 's/^# \{0,1\}//'
+            exit 0
+            ;;
+        --)
+            shift
+            EXTRA_ARGS=("$@")
+            break
+            ;;
+        *)
+            error_exit "Unknown option: $1"
+            ;;
+    esac
+done
+
+command -v jq >/dev/null 2>&1 || error_exit "jq is required to summarise results"
+
+cd "$TOP"
+
+BENCH_ARGS=()
+[[ -n "$FILTER" ]] && BENCH_ARGS+=("$FILTER")
+if [[ -n "$COMPARE" ]]; then
+    BENCH_ARGS+=(--baseline "$COMPARE")
+else
+    BENCH_ARGS+=(--save-baseline "$BASELINE")
+fi
+
+cargo bench -p fastskill-core --bench core_paths -- "${BENCH_ARGS[@]}" "${EXTRA_ARGS[@]}"
+
+# Comparisons are printed by criterion; only saved baselines get a summary.
+[[ -n "$COMPARE" ]] && exit 0
+
+RESULTS_DIR="$TOP/benchmarks/results"
+SUMMARY="$RESULTS_DIR/$BASELINE.md"
+mkdir -p "$RESULTS_DIR"
+
+cpu_model() {
+    if [[ -r /proc/cpuinfo ]]; then
+        grep -m1 'model name' /proc/cpuinfo | cut -d: -f2- | sed 's/^ *//'
+    else
+        sysctl -n machdep.cpu.brand_string 2>/dev/null || echo unknown
+    fi
+}
+
+{
+    echo "# Benchmark results: $BASELINE"
+    echo ""
+    echo "- Date: $(date -u +"%Y-%m-%dT%H:%M:%SZ")"
+    echo "- Commit: $(git rev-parse --short HEAD 2>/dev/null || echo unknown)"
+    echo "- fastskill: $(grep -m1 '^version' Cargo.toml | cut -d'"' -f2)"
+    echo "- rustc: $(rustc --version)"
+    echo "- OS: $(uname -srm)"
+    echo "- CPU: $(cpu_model)"
+    echo ""
+    echo "| Benchmark | Mean | Std. dev. |"
+    echo "|---|---|---|"
+    find target/criterion -path "*/$BASELINE/estimates.json" | sort | while read -r estimates; do
+        dir="$(dirname "$(dirname "$estimates")")"
+        id="$(jq -r '.full_id // .title' "$dir/$BASELINE/benchmark.json" 2>/dev/null || echo "${dir#target/criterion/}")"
+        jq -r --arg id "$id" '
+            def fmt: if . >= 1e9 then "\(. / 1e9 * 100 | round / 100) s"
+                     elif . >= 1e6 then "\(. / 1e6 * 100 | round / 100) ms"
+                     elif . >= 1e3 then "\(. / 1e3 * 100 | round / 100) µs"
+                     else "\(. * 100 | round / 100) ns" end;
+            "| \($id) | \(.mean.point_estimate | fmt) | \(.std_dev.point_estimate | fmt) |"
+        ' "$estimates"
+    done
+} > "$SUMMARY"
+
+echo "Summary written to ${SUMMARY#"$TOP"/}"
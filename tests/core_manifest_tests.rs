@@ -52,6 +52,7 @@ fn test_toml_serialization() {
             author: None,
             download_url: None,
             name: None,
+            exclude: None,
         }),
         dependencies: None,
         tool: None,
@@ -73,6 +74,7 @@ fn test_context_detection_skill_level() {
             author: None,
             download_url: None,
             name: None,
+            exclude: None,
         }),
         dependencies: None,
         tool: None,
@@ -122,6 +124,7 @@ fn test_validation_skill_level() {
             author: None,
             download_url: None,
             name: None,
+            exclude: None,
         }),
         dependencies: None,
         tool: None,
@@ -141,6 +144,7 @@ fn test_validation_skill_level_missing_id() {
             author: None,
             download_url: None,
             name: None,
+            exclude: None,
         }),
         dependencies: None,
         tool: None,
@@ -352,6 +356,7 @@ fn test_ambiguous_context_detection() {
             author: None,
             download_url: None,
             name: None,
+            exclude: None,
         }),
         dependencies: Some(fastskill::core::manifest::DependenciesSection {
             dependencies: std::collections::HashMap::new(),
@@ -379,6 +384,7 @@ fn test_content_based_context_resolution() {
             author: None,
             download_url: None,
             name: None,
+            exclude: None,
         }),
         dependencies: None,
         tool: None,
@@ -416,6 +422,7 @@ fn test_content_based_context_resolution() {
             author: None,
             download_url: None,
             name: None,
+            exclude: None,
         }),
         dependencies: Some(DependenciesSection { dependencies: deps }),
         tool: None,